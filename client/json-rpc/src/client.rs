@@ -1,12 +1,36 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{errors::JsonRpcError, views::AccountView, JsonRpcResponse};
-use anyhow::{ensure, format_err, Error, Result};
-use libra_types::{account_address::AccountAddress, transaction::SignedTransaction};
-use reqwest::{Client, ClientBuilder, Url};
+use crate::{
+    errors::JsonRpcError,
+    views::{
+        AccountStateWithProofView, AccountView, BlockMetadata, CurrencyInfoView, EventView,
+        EventWithProofView, StateProofView, TransactionListWithProofView, TransactionView,
+    },
+    JsonRpcResponse, ResponseAsView,
+};
+use anyhow::{bail, ensure, format_err, Error, Result};
+use libra_retrier::{jittered_exp_retry_strategy, retry_async};
+use libra_types::{
+    account_address::AccountAddress,
+    account_state_blob::AccountStateBlob,
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{SignedTransaction, TransactionListWithProof, Version},
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use reqwest::{Client, ClientBuilder, RequestBuilder, StatusCode, Url};
 use serde_json::{json, Value};
-use std::{collections::HashSet, convert::TryFrom, fmt, time::Duration};
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const MAX_IDLE_CONNECTIONS_PER_HOST: usize = 16;
+const IDLE_CONNECTION_TIMEOUT_SECS: u64 = 90;
 
 #[derive(Clone, Default)]
 pub struct JsonRpcBatch {
@@ -40,6 +64,15 @@ impl JsonRpcBatch {
         Ok(())
     }
 
+    pub fn add_submit_and_wait_request(&mut self, transaction: SignedTransaction) -> Result<()> {
+        let txn_payload = hex::encode(lcs::to_bytes(&transaction)?);
+        self.add_request(
+            "submit_and_wait".to_string(),
+            vec![Value::String(txn_payload)],
+        );
+        Ok(())
+    }
+
     pub fn add_get_account_state_request(&mut self, address: AccountAddress) {
         self.add_request(
             "get_account_state".to_string(),
@@ -67,6 +100,18 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_transactions_with_proofs_request(
+        &mut self,
+        start_version: u64,
+        limit: u64,
+        ledger_version: Option<u64>,
+    ) {
+        self.add_request(
+            "get_transactions_with_proofs".to_string(),
+            vec![json!(start_version), json!(limit), json!(ledger_version)],
+        );
+    }
+
     pub fn add_get_account_transaction_request(
         &mut self,
         account: AccountAddress,
@@ -90,6 +135,45 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_events_with_proofs_request(
+        &mut self,
+        event_key: String,
+        start: u64,
+        ascending: bool,
+        limit: u64,
+        known_version: Option<u64>,
+    ) {
+        let order = if ascending { "ascending" } else { "descending" };
+        self.add_request(
+            "get_events_with_proofs".to_string(),
+            vec![
+                json!(event_key),
+                json!(start),
+                json!(order),
+                json!(limit),
+                json!(known_version),
+            ],
+        );
+    }
+
+    pub fn add_get_events_by_type_request(
+        &mut self,
+        account: AccountAddress,
+        type_tag: String,
+        start: u64,
+        limit: u64,
+    ) {
+        self.add_request(
+            "get_events_by_type".to_string(),
+            vec![
+                json!(account.to_string()),
+                json!(type_tag),
+                json!(start),
+                json!(limit),
+            ],
+        );
+    }
+
     pub fn add_get_state_proof_request(&mut self, known_version: u64) {
         self.add_request("get_state_proof".to_string(), vec![json!(known_version)]);
     }
@@ -110,36 +194,107 @@ impl JsonRpcBatch {
         );
     }
 
+    pub fn add_get_source_map_request(&mut self, address: AccountAddress, module_name: &str) {
+        self.add_request(
+            "get_source_map".to_string(),
+            vec![json!(address.to_string()), json!(module_name)],
+        );
+    }
+
     pub fn add_get_network_status_request(&mut self) {
         self.add_request("get_network_status".to_string(), vec![]);
     }
+
+    pub fn add_get_gas_price_estimate_request(&mut self) {
+        self.add_request("get_gas_price_estimate".to_string(), vec![]);
+    }
+
+    pub fn add_get_expiration_window_request(&mut self) {
+        self.add_request("get_expiration_window".to_string(), vec![]);
+    }
+
+    pub fn add_get_latest_waypoint_request(&mut self) {
+        self.add_request("get_latest_waypoint".to_string(), vec![]);
+    }
+}
+
+/// How `JsonRpcAsyncClient` retries requests which failed to reach the server or which the
+/// server was too busy or unable to answer. Delays grow exponentially with random jitter.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    pub max_retries: usize,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    pub fn no_retry() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    fn delays(&self) -> impl Iterator<Item = Duration> {
+        jittered_exp_retry_strategy(self.base_delay_ms, self.max_delay_ms, self.max_retries)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 100,
+            max_delay_ms: 2000,
+        }
+    }
 }
 
+/// Async client of the JSON RPC API. Clones share the connection pool, and the trusted state when
+/// one was set with `with_trust_anchor`.
 #[derive(Clone)]
 pub struct JsonRpcAsyncClient {
     address: String,
     client: Client,
+    retry_policy: RetryPolicy,
+    trusted_state: Option<Arc<Mutex<TrustedState>>>,
 }
 
 impl JsonRpcAsyncClient {
     /// Pass in full url for endpoint, supports HTTPS
     pub fn new(url: Url) -> Self {
-        Self {
-            address: url.to_string(),
-            client: ClientBuilder::new()
+        Self::new_with_client(
+            ClientBuilder::new()
+                .pool_max_idle_per_host(MAX_IDLE_CONNECTIONS_PER_HOST)
+                .pool_idle_timeout(Duration::from_secs(IDLE_CONNECTION_TIMEOUT_SECS))
                 .timeout(Duration::from_secs(30))
                 .build()
                 .expect("Unable to build Client."),
-        }
+            url,
+        )
     }
 
     pub fn new_with_client(client: Client, url: Url) -> Self {
         Self {
             address: url.to_string(),
             client,
+            retry_policy: RetryPolicy::default(),
+            trusted_state: None,
         }
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Enables the `get_verified_*` methods, which verify responses against a trusted state
+    /// starting at `waypoint` and ratcheted forward by the state proofs of the server
+    pub fn with_trust_anchor(mut self, waypoint: Waypoint) -> Self {
+        self.trusted_state = Some(Arc::new(Mutex::new(TrustedState::from(waypoint))));
+        self
+    }
+
     pub async fn get_accounts_state(
         &self,
         accounts: &[AccountAddress],
@@ -170,30 +325,240 @@ impl JsonRpcAsyncClient {
         Ok(results)
     }
 
+    pub async fn get_account_state(&self, account: AccountAddress) -> Result<Option<AccountView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_account_state_request(account);
+        AccountView::optional_from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_metadata(&self, version: Option<u64>) -> Result<BlockMetadata> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_metadata_request(version);
+        BlockMetadata::from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_currencies(&self) -> Result<Vec<CurrencyInfoView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_currencies_info();
+        CurrencyInfoView::vec_from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_transactions(
+        &self,
+        start_version: u64,
+        limit: u64,
+        include_events: bool,
+    ) -> Result<Vec<TransactionView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_transactions_request(start_version, limit, include_events);
+        TransactionView::vec_from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_account_transaction(
+        &self,
+        account: AccountAddress,
+        sequence_number: u64,
+        include_events: bool,
+    ) -> Result<Option<TransactionView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_account_transaction_request(account, sequence_number, include_events);
+        TransactionView::optional_from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_events(
+        &self,
+        event_key: String,
+        start: u64,
+        limit: u64,
+    ) -> Result<Vec<EventView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_events_request(event_key, start, limit);
+        EventView::vec_from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_state_proof(&self, known_version: u64) -> Result<StateProofView> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_state_proof_request(known_version);
+        StateProofView::from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_account_state_with_proof(
+        &self,
+        account: AccountAddress,
+        version: Option<u64>,
+        ledger_version: Option<u64>,
+    ) -> Result<AccountStateWithProofView> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_account_state_with_proof_request(account, version, ledger_version);
+        AccountStateWithProofView::from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_transactions_with_proofs(
+        &self,
+        start_version: u64,
+        limit: u64,
+        ledger_version: Option<u64>,
+    ) -> Result<TransactionListWithProofView> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_transactions_with_proofs_request(start_version, limit, ledger_version);
+        TransactionListWithProofView::from_response(self.execute_one(batch).await?)
+    }
+
+    pub async fn get_events_with_proofs(
+        &self,
+        event_key: String,
+        start: u64,
+        ascending: bool,
+        limit: u64,
+        known_version: Option<u64>,
+    ) -> Result<Vec<EventWithProofView>> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_events_with_proofs_request(event_key, start, ascending, limit, known_version);
+        EventWithProofView::vec_from_response(self.execute_one(batch).await?)
+    }
+
+    /// Retrieves the state blob of `account` at the latest version, verified against the trusted
+    /// state, along with that version
+    pub async fn get_verified_account_state(
+        &self,
+        account: AccountAddress,
+    ) -> Result<(Option<AccountStateBlob>, Version)> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_account_state_with_proof_request(account, None, None);
+        let (response, li) = self.execute_verified(batch).await?;
+
+        let account_state_with_proof =
+            AccountStateWithProofView::from_response(response)?.decode()?;
+        let version = li.ledger_info().version();
+        account_state_with_proof.verify(li.ledger_info(), version, account)?;
+        Ok((account_state_with_proof.blob, version))
+    }
+
+    /// Retrieves up to `limit` transactions from `start_version`, verified against the trusted
+    /// state
+    pub async fn get_verified_transactions(
+        &self,
+        start_version: u64,
+        limit: u64,
+    ) -> Result<TransactionListWithProof> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_transactions_with_proofs_request(start_version, limit, None);
+        let (response, li) = self.execute_verified(batch).await?;
+
+        let txn_list = TransactionListWithProofView::from_response(response)?.decode()?;
+        // The server returns all transactions from start_version up to the ledger version, if
+        // there are fewer than the limit
+        let ledger_version = li.ledger_info().version();
+        let (expected_first_version, expected_len) = if start_version <= ledger_version {
+            (
+                Some(start_version),
+                std::cmp::min(limit, ledger_version - start_version + 1),
+            )
+        } else {
+            (None, 0)
+        };
+        ensure!(
+            txn_list.transactions.len() as u64 == expected_len,
+            "Got {} transactions, expected {}",
+            txn_list.transactions.len(),
+            expected_len
+        );
+        txn_list.verify(li.ledger_info(), expected_first_version)?;
+        Ok(txn_list)
+    }
+
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<()> {
         let mut batch = JsonRpcBatch::new();
         batch.add_submit_request(txn)?;
-        let mut exec_result = self.execute(batch).await?;
-        assert!(exec_result.len() == 1);
-        exec_result.remove(0).map(|_| ())
+        self.execute_one(batch).await.map(|_| ())
+    }
+
+    /// Submits a transaction and waits until it is committed, returning the committed
+    /// transaction. The request fails if the transaction expires, or if it is not committed
+    /// within the timeout of the server.
+    pub async fn submit_transaction_and_wait(
+        &self,
+        txn: SignedTransaction,
+    ) -> Result<TransactionView> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_submit_and_wait_request(txn)?;
+        TransactionView::from_response(self.execute_one(batch).await?)
     }
 
     pub async fn execute(&self, batch: JsonRpcBatch) -> Result<Vec<Result<JsonRpcResponse>>> {
         let requests = batch.json_request();
-        let resp = self
-            .client
-            .post(&self.address)
-            .json(&requests)
-            .send()
-            .await?;
+        let responses = retry_async(self.retry_policy.delays(), || {
+            let request = self.client.post(&self.address).json(&requests);
+            Box::pin(async move { Self::send(request).await })
+        })
+        .await??;
+        process_batch_response(batch, responses)
+    }
 
+    // Sends a request, the outer error is set when the request is worth retrying
+    async fn send(request: RequestBuilder) -> Result<Result<Vec<Value>>> {
+        let resp = request.send().await?;
+        let status = resp.status();
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            bail!("Http error code {}", status);
+        }
+        if status != StatusCode::OK {
+            return Ok(Err(format_err!("Http error code {}", status)));
+        }
+        Ok(resp.json().await.map_err(Into::into))
+    }
+
+    // Executes a batch of a single request and returns its response
+    async fn execute_one(&self, batch: JsonRpcBatch) -> Result<JsonRpcResponse> {
+        let mut responses = self.execute(batch).await?;
         ensure!(
-            resp.status() == 200,
-            format!("Http error code {}", resp.status())
+            responses.len() == 1,
+            "Received {} JSON RPC responses for a single request",
+            responses.len()
         );
+        responses.remove(0)
+    }
 
-        let responses: Vec<Value> = resp.json().await?;
-        process_batch_response(batch, responses)
+    // Executes a batch of a single request along with a state proof request, and ratchets the
+    // trusted state to the ledger info of the proof, which the response can be verified against
+    async fn execute_verified(
+        &self,
+        mut batch: JsonRpcBatch,
+    ) -> Result<(JsonRpcResponse, LedgerInfoWithSignatures)> {
+        let shared_state = self.trusted_state.as_ref().ok_or_else(|| {
+            format_err!("Verified requests need a trust anchor, see `with_trust_anchor`")
+        })?;
+        // Concurrent requests may ratchet the shared state while this one is in flight, so the
+        // state proof is verified against the state it was requested from
+        let trusted_state = shared_state.lock().expect("lock poisoned").clone();
+        batch.add_get_state_proof_request(trusted_state.latest_version());
+
+        let mut responses = self.execute(batch).await?;
+        ensure!(
+            responses.len() == 2,
+            "Received {} JSON RPC responses for 2 requests",
+            responses.len()
+        );
+        let state_proof = StateProofView::from_response(responses.remove(1)?)?;
+        let response = responses.remove(0)?;
+
+        let (li, epoch_change_proof, _) = state_proof.decode()?;
+        ensure!(
+            li.ledger_info().version() >= trusted_state.latest_version(),
+            "Got stale ledger_info with version {}, known version: {}",
+            li.ledger_info().version(),
+            trusted_state.latest_version(),
+        );
+        let new_state = match trusted_state.verify_and_ratchet(&li, &epoch_change_proof)? {
+            TrustedStateChange::Epoch { new_state, .. }
+            | TrustedStateChange::Version { new_state } => new_state,
+            TrustedStateChange::NoChange => return Ok((response, li)),
+        };
+        let mut shared_state = shared_state.lock().expect("lock poisoned");
+        if new_state.latest_version() > shared_state.latest_version() {
+            *shared_state = new_state;
+        }
+        Ok((response, li))
     }
 }
 
@@ -7,7 +7,7 @@ mod response;
 
 pub use blocking::JsonRpcClient;
 pub use client::{
-    get_response_from_batch, process_batch_response, JsonRpcAsyncClient, JsonRpcBatch,
+    get_response_from_batch, process_batch_response, JsonRpcAsyncClient, JsonRpcBatch, RetryPolicy,
 };
 pub use libra_json_rpc_types::{errors, views};
 pub use response::{JsonRpcResponse, ResponseAsView};
@@ -3,7 +3,8 @@
 
 use crate::views::{
     AccountStateWithProofView, AccountView, BlockMetadata, CurrencyInfoView, EventView,
-    StateProofView, TransactionView,
+    EventWithProofView, ExpirationWindowView, GasPriceEstimateView, SignedWaypointView,
+    SourceMapView, StateProofView, TransactionListWithProofView, TransactionView,
 };
 use anyhow::{ensure, format_err, Error, Result};
 
@@ -14,15 +15,22 @@ use std::convert::TryFrom;
 #[derive(Clone, PartialEq, Debug)]
 pub enum JsonRpcResponse {
     SubmissionResponse,
+    SubmitAndWaitResponse(TransactionView),
     AccountResponse(Option<AccountView>),
     StateProofResponse(StateProofView),
     AccountTransactionResponse(Option<TransactionView>),
     TransactionsResponse(Vec<TransactionView>),
+    TransactionsWithProofsResponse(TransactionListWithProofView),
     EventsResponse(Vec<EventView>),
+    EventsWithProofsResponse(Vec<EventWithProofView>),
     BlockMetadataResponse(BlockMetadata),
     CurrenciesResponse(Vec<CurrencyInfoView>),
     AccountStateWithProofResponse(AccountStateWithProofView),
+    SourceMapResponse(Option<SourceMapView>),
     NetworkStatusResponse(Number),
+    GasPriceEstimateResponse(GasPriceEstimateView),
+    ExpirationWindowResponse(ExpirationWindowView),
+    LatestWaypointResponse(Option<SignedWaypointView>),
     UnknownResponse(Value),
 }
 
@@ -39,6 +47,10 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 );
                 Ok(JsonRpcResponse::SubmissionResponse)
             }
+            "submit_and_wait" => {
+                let txn: TransactionView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::SubmitAndWaitResponse(txn))
+            }
             "get_account_state" => {
                 let account = match value {
                     Value::Null => None,
@@ -49,10 +61,14 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 };
                 Ok(JsonRpcResponse::AccountResponse(account))
             }
-            "get_events" => {
+            "get_events" | "get_events_by_type" => {
                 let events: Vec<EventView> = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::EventsResponse(events))
             }
+            "get_events_with_proofs" => {
+                let events: Vec<EventWithProofView> = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::EventsWithProofsResponse(events))
+            }
             "get_metadata" => {
                 let metadata: BlockMetadata = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::BlockMetadataResponse(metadata))
@@ -85,12 +101,44 @@ impl TryFrom<(String, Value)> for JsonRpcResponse {
                 let txns: Vec<TransactionView> = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::TransactionsResponse(txns))
             }
+            "get_transactions_with_proofs" => {
+                let txns: TransactionListWithProofView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::TransactionsWithProofsResponse(txns))
+            }
+            "get_source_map" => {
+                let source_map = match value {
+                    Value::Null => None,
+                    _ => {
+                        let source_map: SourceMapView = serde_json::from_value(value)?;
+                        Some(source_map)
+                    }
+                };
+                Ok(JsonRpcResponse::SourceMapResponse(source_map))
+            }
             "get_network_status" => {
                 let connected_peers_count: Number = serde_json::from_value(value)?;
                 Ok(JsonRpcResponse::NetworkStatusResponse(
                     connected_peers_count,
                 ))
             }
+            "get_gas_price_estimate" => {
+                let estimate: GasPriceEstimateView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::GasPriceEstimateResponse(estimate))
+            }
+            "get_expiration_window" => {
+                let window: ExpirationWindowView = serde_json::from_value(value)?;
+                Ok(JsonRpcResponse::ExpirationWindowResponse(window))
+            }
+            "get_latest_waypoint" => {
+                let waypoint = match value {
+                    Value::Null => None,
+                    _ => {
+                        let waypoint: SignedWaypointView = serde_json::from_value(value)?;
+                        Some(waypoint)
+                    }
+                };
+                Ok(JsonRpcResponse::LatestWaypointResponse(waypoint))
+            }
             _ => Ok(JsonRpcResponse::UnknownResponse(value)),
         }
     }
@@ -136,6 +184,16 @@ impl ResponseAsView for EventView {
     }
 }
 
+impl ResponseAsView for EventWithProofView {
+    fn vec_from_response(response: JsonRpcResponse) -> Result<Vec<Self>> {
+        if let JsonRpcResponse::EventsWithProofsResponse(events) = response {
+            Ok(events)
+        } else {
+            Self::unexpected_response_error::<Vec<Self>>(response)
+        }
+    }
+}
+
 impl ResponseAsView for BlockMetadata {
     fn from_response(response: JsonRpcResponse) -> Result<Self> {
         if let JsonRpcResponse::BlockMetadataResponse(metadata) = response {
@@ -146,6 +204,36 @@ impl ResponseAsView for BlockMetadata {
     }
 }
 
+impl ResponseAsView for GasPriceEstimateView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::GasPriceEstimateResponse(estimate) = response {
+            Ok(estimate)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
+impl ResponseAsView for ExpirationWindowView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::ExpirationWindowResponse(window) = response {
+            Ok(window)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
+impl ResponseAsView for SignedWaypointView {
+    fn optional_from_response(response: JsonRpcResponse) -> Result<Option<Self>> {
+        if let JsonRpcResponse::LatestWaypointResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Option<Self>>(response)
+        }
+    }
+}
+
 impl ResponseAsView for CurrencyInfoView {
     fn vec_from_response(response: JsonRpcResponse) -> Result<Vec<Self>> {
         if let JsonRpcResponse::CurrenciesResponse(info) = response {
@@ -157,6 +245,14 @@ impl ResponseAsView for CurrencyInfoView {
 }
 
 impl ResponseAsView for TransactionView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::SubmitAndWaitResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+
     fn optional_from_response(response: JsonRpcResponse) -> Result<Option<Self>> {
         if let JsonRpcResponse::AccountTransactionResponse(view) = response {
             Ok(view)
@@ -193,3 +289,23 @@ impl ResponseAsView for AccountStateWithProofView {
         }
     }
 }
+
+impl ResponseAsView for TransactionListWithProofView {
+    fn from_response(response: JsonRpcResponse) -> Result<Self> {
+        if let JsonRpcResponse::TransactionsWithProofsResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Self>(response)
+        }
+    }
+}
+
+impl ResponseAsView for SourceMapView {
+    fn optional_from_response(response: JsonRpcResponse) -> Result<Option<Self>> {
+        if let JsonRpcResponse::SourceMapResponse(view) = response {
+            Ok(view)
+        } else {
+            Self::unexpected_response_error::<Option<Self>>(response)
+        }
+    }
+}
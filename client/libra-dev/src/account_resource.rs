@@ -112,7 +112,7 @@ mod tests {
             EventHandle::new(EventKey::new_from_address(&addr, 0), 888),
             false,
         );
-        let br = BalanceResource::new(100);
+        let br: BalanceResource = BalanceResource::new(100);
 
         // Fill in data
         map.insert(
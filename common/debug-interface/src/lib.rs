@@ -1,14 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::json_log::JsonLogEntry;
-use anyhow::Result;
+use crate::{json_log::JsonLogEntry, trace_store::Trace};
+use anyhow::{format_err, Result};
 use reqwest::blocking;
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 pub mod json_log;
 pub mod libra_trace;
 pub mod node_debug_service;
+pub mod trace_store;
 
 pub mod prelude {
     pub use crate::{
@@ -60,6 +61,40 @@ impl NodeDebugClient {
 
         Ok(response.json()?)
     }
+
+    pub fn get_traces(&mut self) -> Result<Vec<Trace>> {
+        let response = self.client.get(&format!("{}/traces", self.addr)).send()?;
+
+        Ok(response.json()?)
+    }
+
+    /// Returns the current time of the node, as a Duration since the UNIX_EPOCH
+    pub fn get_time(&mut self) -> Result<Duration> {
+        let response = self.client.get(&format!("{}/time", self.addr)).send()?;
+
+        Ok(Duration::from_micros(response.json()?))
+    }
+
+    /// Moves the clock of the node forward by `duration`, returning its new time. Fails unless
+    /// the node was started with an adjustable clock.
+    pub fn advance_time(&mut self, duration: Duration) -> Result<Duration> {
+        let response = self
+            .client
+            .post(&format!(
+                "{}/time/advance/{}",
+                self.addr,
+                duration.as_millis()
+            ))
+            .send()?;
+        if !response.status().is_success() {
+            return Err(format_err!(
+                "Failed to advance the time: {}",
+                response.text()?
+            ));
+        }
+
+        Ok(Duration::from_micros(response.json()?))
+    }
 }
 
 /// Implement default utility client for AsyncNodeDebugInterface
@@ -112,4 +147,14 @@ impl AsyncNodeDebugClient {
 
         Ok(response.json().await?)
     }
+
+    pub async fn get_traces(&mut self) -> Result<Vec<Trace>> {
+        let response = self
+            .client
+            .get(&format!("{}/traces", self.addr))
+            .send()
+            .await?;
+
+        Ok(response.json().await?)
+    }
 }
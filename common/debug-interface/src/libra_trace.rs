@@ -3,8 +3,12 @@
 
 use crate::json_log::JsonLogEntry;
 use anyhow::{bail, ensure, Result};
+use libra_logger::span::Span;
 use std::time::Instant;
 
+// Used by `trace_edge!` in other crates
+pub use libra_logger::span::TraceId;
+
 pub const TRACE_EVENT: &str = "trace_event";
 pub const TRACE_EDGE: &str = "trace_edge";
 pub const LIBRA_TRACE: &str = "libra_trace";
@@ -34,7 +38,9 @@ struct CategorySampling {
 macro_rules! trace_event {
     ($stage:expr, $node:tt) => {
         if $crate::is_selected($crate::node_sampling_data!($node)) {
-            trace_event!($stage; {$crate::format_node!($node), module_path!(), Option::<u64>::None});
+            let node = $crate::format_node!($node);
+            $crate::libra_trace::trace_span($stage, &node, module_path!()).end();
+            trace_event!($stage; {node, module_path!(), Option::<u64>::None});
         }
     };
     ($stage:expr; {$node:expr, $path:expr, $duration:expr}) => {
@@ -69,6 +75,7 @@ macro_rules! node_sampling_data {
 macro_rules! send_logs {
     ($name:expr, $json:expr) => {
         let log_entry = $crate::json_log::JsonLogEntry::new($name, $json);
+        $crate::trace_store::record(&log_entry);
         $crate::json_log::send_json_log(log_entry.clone());
         libra_logger::send_struct_log!(libra_logger::StructuredLogEntry::new_named(
             $crate::libra_trace::LIBRA_TRACE
@@ -80,29 +87,48 @@ macro_rules! send_logs {
 #[macro_export]
 macro_rules! trace_code_block {
     ($stage:expr, $node:tt) => {
+        let trace_selected = $crate::is_selected($crate::node_sampling_data!($node));
         let trace_guard = $crate::libra_trace::TraceBlockGuard::new_entered(
             concat!($stage, "::done"),
             $crate::format_node!($node),
             module_path!(),
-        );
-        trace_event!($stage, $node);
+        )
+        .with_span($stage, trace_selected);
+        if trace_selected {
+            trace_event!($stage; {trace_guard.node(), module_path!(), Option::<u64>::None});
+        }
     };
     ($stage:expr, $node:tt, $guard_vec:tt) => {
+        let trace_selected = $crate::is_selected($crate::node_sampling_data!($node));
         let trace_guard = $crate::libra_trace::TraceBlockGuard::new_entered(
             concat!($stage, "::done"),
             $crate::format_node!($node),
             module_path!(),
-        );
-        trace_event!($stage, $node);
+        )
+        .with_span($stage, trace_selected);
+        if trace_selected {
+            trace_event!($stage; {trace_guard.node(), module_path!(), Option::<u64>::None});
+        }
         $guard_vec.push(trace_guard);
     };
 }
 
+/// Starts a span in the trace of a node, e.g., `txn::<sender>::<sequence number>` or
+/// `block::<id>`. The trace ID is derived from the node, so that the spans of every component
+/// handling the same transaction or block end up in the same trace.
+pub fn trace_span(stage: &'static str, node: &str, module_path: &'static str) -> Span {
+    Span::new(stage, TraceId::from_key(node))
+        .module(module_path)
+        .attribute("node", node)
+}
+
 pub struct TraceBlockGuard {
     stage: &'static str,
     node: String,
     module_path: &'static str,
     started: Instant,
+    selected: bool,
+    span: Option<Span>,
 }
 
 impl TraceBlockGuard {
@@ -117,14 +143,36 @@ impl TraceBlockGuard {
             node,
             module_path,
             started,
+            selected: true,
+            span: None,
+        }
+    }
+
+    /// Covers the block with a span, ended along with the guard. Nothing is traced for blocks of
+    /// nodes that are not selected.
+    pub fn with_span(mut self, stage: &'static str, selected: bool) -> Self {
+        self.selected = selected;
+        if selected {
+            self.span = Some(trace_span(stage, &self.node, self.module_path));
         }
+        self
+    }
+
+    pub fn node(&self) -> &str {
+        &self.node
     }
 }
 
 impl Drop for TraceBlockGuard {
     fn drop(&mut self) {
+        if !self.selected {
+            return;
+        }
         let duration = format!("{:.0?}", Instant::now().duration_since(self.started));
         trace_event!(self.stage; {self.node, self.module_path, duration});
+        if let Some(span) = self.span.take() {
+            span.end();
+        }
     }
 }
 
@@ -132,9 +180,13 @@ impl Drop for TraceBlockGuard {
 macro_rules! end_trace {
     ($stage:expr, $node:tt) => {
         if $crate::is_selected($crate::node_sampling_data!($node)) {
+            let node = $crate::format_node!($node);
+            $crate::libra_trace::trace_span($stage, &node, module_path!())
+                .attribute("end", true)
+                .end();
             let json = serde_json::json!({
                     "path": module_path!(),
-                    "node": $crate::format_node!($node),
+                    "node": node,
                     "stage": $stage,
                     "end": true,
                 });
@@ -147,10 +199,17 @@ macro_rules! end_trace {
 macro_rules! trace_edge {
     ($stage:expr, $node_from:tt, $node_to:tt) => {
         if $crate::is_selected($crate::node_sampling_data!($node_from)) {
+            let node_from = $crate::format_node!($node_from);
+            let node_to = $crate::format_node!($node_to);
+            // The span belongs to the trace of the destination, e.g., the block a transaction is
+            // pulled into, and links to the trace of the source
+            $crate::libra_trace::trace_span($stage, &node_to, module_path!())
+                .link($crate::libra_trace::TraceId::from_key(&node_from))
+                .end();
             let json = serde_json::json!({
                     "path": module_path!(),
-                    "node": $crate::format_node!($node_from),
-                    "node_to": $crate::format_node!($node_to),
+                    "node": node_from,
+                    "node_to": node_to,
                     "stage": $stage,
                 });
             $crate::send_logs!($crate::libra_trace::TRACE_EDGE, json);
@@ -3,10 +3,11 @@
 
 //! Debug interface to access information in a specific node.
 
-use crate::json_log;
-use std::net::SocketAddr;
+use crate::{json_log, trace_store};
+use libra_time_service::TimeService;
+use std::{net::SocketAddr, time::Duration};
 use tokio::runtime::{Builder, Runtime};
-use warp::Filter;
+use warp::{http::StatusCode, Filter};
 
 #[derive(Debug)]
 pub struct NodeDebugService {
@@ -14,7 +15,7 @@ pub struct NodeDebugService {
 }
 
 impl NodeDebugService {
-    pub fn new(address: SocketAddr) -> Self {
+    pub fn new(address: SocketAddr, time_service: TimeService) -> Self {
         let runtime = Builder::new()
             .thread_name("nodedebug-")
             .threaded_scheduler()
@@ -29,7 +30,39 @@ impl NodeDebugService {
         // GET /evnets
         let events = warp::path("events").map(|| warp::reply::json(&json_log::pop_last_entries()));
 
-        let routes = warp::get().and(metrics.or(events));
+        // GET /traces
+        let traces = warp::path("traces")
+            .and(warp::path::end())
+            .map(|| warp::reply::json(&trace_store::traces()));
+
+        // GET /traces/captured
+        let captured_traces = warp::path!("traces" / "captured")
+            .map(|| warp::reply::json(&trace_store::captured_traces()));
+
+        // GET /time
+        let clock = time_service.clone();
+        let time = warp::path("time")
+            .and(warp::path::end())
+            .map(move || warp::reply::json(&(clock.now().as_micros() as u64)));
+
+        // POST /time/advance/{millis}, only served by nodes whose clock is adjustable
+        let advance_time = warp::path!("time" / "advance" / u64).map(move |millis| {
+            if time_service.advance(Duration::from_millis(millis)) {
+                warp::reply::with_status(
+                    warp::reply::json(&(time_service.now().as_micros() as u64)),
+                    StatusCode::OK,
+                )
+            } else {
+                warp::reply::with_status(
+                    warp::reply::json(&"the clock of this node is not adjustable"),
+                    StatusCode::FORBIDDEN,
+                )
+            }
+        });
+
+        let routes = warp::get()
+            .and(metrics.or(events).or(traces).or(captured_traces).or(time))
+            .or(warp::post().and(advance_time));
 
         let server = runtime.enter(move || warp::serve(routes).bind(address));
         runtime.handle().spawn(server);
@@ -0,0 +1,244 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recent traces kept in memory so that they can be fetched from the debug interface of a node in
+//! production, where the event stream is not drained.
+//!
+//! Events of sampled nodes are grouped into traces, of which only the most recent are kept.
+//! Independently of sampling, components capture the traces of transactions that fail or exceed
+//! the latency SLO. Captured traces are kept in a separate buffer, so that they are not evicted by
+//! sampled traffic.
+
+use crate::json_log::JsonLogEntry;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::value as json;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+pub const TRACE_CAPTURED: &str = "trace_captured";
+
+/// Bound on the number of events kept per trace, which also bounds the memory of long-lived nodes
+const MAX_EVENTS_PER_TRACE: usize = 256;
+const DEFAULT_MAX_TRACES: usize = 1_000;
+const DEFAULT_LATENCY_SLO_MS: u64 = 10_000;
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Trace {
+    pub node: String,
+    /// Why the trace was captured, if it was not only sampled
+    pub captured: Option<String>,
+    pub events: Vec<JsonLogEntry>,
+}
+
+impl Trace {
+    fn new(node: String) -> Self {
+        Self {
+            node,
+            captured: None,
+            events: vec![],
+        }
+    }
+
+    fn push(&mut self, entry: JsonLogEntry) {
+        if self.events.len() < MAX_EVENTS_PER_TRACE {
+            self.events.push(entry);
+        }
+    }
+}
+
+struct TraceStore {
+    sampled: HashMap<String, Trace>,
+    // Nodes of the sampled traces, from the least to the most recently started
+    sampled_order: VecDeque<String>,
+    captured: VecDeque<Trace>,
+    max_traces: usize,
+    max_captured_traces: usize,
+}
+
+impl TraceStore {
+    fn new(max_traces: usize, max_captured_traces: usize) -> Self {
+        Self {
+            sampled: HashMap::new(),
+            sampled_order: VecDeque::new(),
+            captured: VecDeque::new(),
+            max_traces,
+            max_captured_traces,
+        }
+    }
+
+    fn record(&mut self, node: &str, entry: JsonLogEntry) {
+        if self.max_traces == 0 {
+            return;
+        }
+        if let Some(trace) = self.sampled.get_mut(node) {
+            trace.push(entry);
+            return;
+        }
+        while self.sampled_order.len() >= self.max_traces {
+            if let Some(oldest) = self.sampled_order.pop_front() {
+                self.sampled.remove(&oldest);
+            }
+        }
+        let mut trace = Trace::new(node.to_string());
+        trace.push(entry);
+        self.sampled.insert(node.to_string(), trace);
+        self.sampled_order.push_back(node.to_string());
+    }
+
+    /// Moves the trace of the node, if sampled, to the captured traces along with the reason
+    fn capture(&mut self, node: &str, reason: &str, entry: JsonLogEntry) {
+        if self.max_captured_traces == 0 {
+            return;
+        }
+        let mut trace = match self.sampled.remove(node) {
+            Some(trace) => {
+                self.sampled_order.retain(|n| n != node);
+                trace
+            }
+            None => Trace::new(node.to_string()),
+        };
+        trace.captured = Some(reason.to_string());
+        trace.push(entry);
+        while self.captured.len() >= self.max_captured_traces {
+            self.captured.pop_front();
+        }
+        self.captured.push_back(trace);
+    }
+
+    fn traces(&self) -> Vec<Trace> {
+        self.captured
+            .iter()
+            .cloned()
+            .chain(
+                self.sampled_order
+                    .iter()
+                    .filter_map(|node| self.sampled.get(node).cloned()),
+            )
+            .collect()
+    }
+}
+
+static TRACE_STORE: Lazy<Mutex<TraceStore>> =
+    Lazy::new(|| Mutex::new(TraceStore::new(DEFAULT_MAX_TRACES, DEFAULT_MAX_TRACES)));
+static LATENCY_SLO_MS: AtomicU64 = AtomicU64::new(DEFAULT_LATENCY_SLO_MS);
+
+fn trace_store() -> std::sync::MutexGuard<'static, TraceStore> {
+    TRACE_STORE
+        .lock()
+        .expect("[trace store] failed to acquire lock")
+}
+
+/// Sets the bounds on the number of traces kept and the latency above which traces are captured
+pub fn configure(max_traces: usize, max_captured_traces: usize, latency_slo: Duration) {
+    {
+        let mut store = trace_store();
+        store.max_traces = max_traces;
+        store.max_captured_traces = max_captured_traces;
+        while store.sampled_order.len() > max_traces {
+            if let Some(oldest) = store.sampled_order.pop_front() {
+                store.sampled.remove(&oldest);
+            }
+        }
+        while store.captured.len() > max_captured_traces {
+            store.captured.pop_front();
+        }
+    }
+    LATENCY_SLO_MS.store(latency_slo.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Adds a trace event or edge to the traces of the nodes it refers to
+pub fn record(entry: &JsonLogEntry) {
+    let mut store = trace_store();
+    for key in &["node", "node_to"] {
+        if let Some(node) = entry.json.get(key).and_then(json::Value::as_str) {
+            store.record(node, entry.clone());
+        }
+    }
+}
+
+/// Captures the trace of a node, whether it was sampled or not, e.g., because the transaction
+/// failed. The details are added to the trace as a `trace_captured` event.
+pub fn capture(node: &str, reason: &str, details: json::Value) {
+    let entry = JsonLogEntry::new(
+        TRACE_CAPTURED,
+        serde_json::json!({
+            "node": node,
+            "reason": reason,
+            "details": details,
+        }),
+    );
+    trace_store().capture(node, reason, entry);
+}
+
+/// Captures the trace of a node if its latency exceeds the SLO. The details are only computed for
+/// captured traces.
+pub fn capture_if_slow(node: &str, latency: Duration, details: impl FnOnce() -> json::Value) {
+    if latency.as_millis() as u64 > LATENCY_SLO_MS.load(Ordering::Relaxed) {
+        capture(node, "latency_slo_exceeded", details());
+    }
+}
+
+/// Returns the captured traces followed by the recent sampled traces, from the oldest
+pub fn traces() -> Vec<Trace> {
+    trace_store().traces()
+}
+
+/// Returns the captured traces, from the oldest
+pub fn captured_traces() -> Vec<Trace> {
+    trace_store().captured.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(node: &str) -> JsonLogEntry {
+        JsonLogEntry::new("trace_event", serde_json::json!({ "node": node }))
+    }
+
+    #[test]
+    fn test_trace_store_is_bounded() {
+        let mut store = TraceStore::new(2, 1);
+        store.record("txn::a::0", event("txn::a::0"));
+        store.record("txn::a::1", event("txn::a::1"));
+        store.record("txn::a::0", event("txn::a::0"));
+        store.record("txn::a::2", event("txn::a::2"));
+        let nodes: Vec<_> = store.traces().into_iter().map(|t| t.node).collect();
+        assert_eq!(nodes, vec!["txn::a::1", "txn::a::2"]);
+
+        for _ in 0..MAX_EVENTS_PER_TRACE {
+            store.record("txn::a::2", event("txn::a::2"));
+        }
+        assert_eq!(
+            store.sampled["txn::a::2"].events.len(),
+            MAX_EVENTS_PER_TRACE
+        );
+    }
+
+    #[test]
+    fn test_capture_keeps_sampled_events() {
+        let mut store = TraceStore::new(2, 1);
+        store.record("txn::a::0", event("txn::a::0"));
+        store.capture("txn::a::0", "rejected", event("txn::a::0"));
+        store.capture("txn::a::1", "rejected", event("txn::a::1"));
+        store.capture("txn::a::2", "rejected", event("txn::a::2"));
+
+        assert!(store.sampled.is_empty());
+        let traces = store.traces();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].node, "txn::a::2");
+        assert_eq!(traces[0].captured.as_deref(), Some("rejected"));
+
+        let mut store = TraceStore::new(2, 1);
+        store.record("txn::a::0", event("txn::a::0"));
+        store.capture("txn::a::0", "rejected", event("txn::a::0"));
+        assert_eq!(store.captured[0].events.len(), 2);
+    }
+}
@@ -114,6 +114,12 @@
 //! 1) `NopStructLog` ignores structured logs
 //! 2) `PrintStructLog` immediately prints structured logs to stdout
 //! 3) `FileStructLog` prints structured logs into provided file. Using this logger creates separate thread for writing files and structured logging itself is asynchronous in this case.
+//!
+//! ## Spans
+//!
+//! The `span` module emits spans, with the fields of OpenTelemetry spans, as structured log
+//! entries named `span`. Structured log entries created while a span is entered on the current
+//! thread carry its `trace_id` and `span_id`, so that logs can be correlated with traces.
 
 pub use log;
 
@@ -121,6 +127,7 @@ pub mod prelude {
     pub use crate::{crit, debug, error, info, send_struct_log, trace, warn};
 }
 
+pub mod span;
 mod struct_log;
 
 pub use struct_log::{
@@ -130,7 +137,7 @@ pub use struct_log::{
 
 mod text_log;
 pub use log::Level;
-pub use text_log::{Logger, CHANNEL_SIZE, DEFAULT_TARGET};
+pub use text_log::{set_level, Logger, CHANNEL_SIZE, DEFAULT_TARGET};
 
 /// Define crit macro that specify libra as the target
 // TODO Remove historical crit from code base since it isn't supported in Rust Log.
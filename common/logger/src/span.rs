@@ -0,0 +1,273 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spans emitted as structured log entries named `span`, with the fields of an OpenTelemetry span,
+//! so that a log shipper can export them to any OpenTelemetry collector.
+//!
+//! Trace IDs are derived from a key identifying the object being traced, e.g., the sender and
+//! sequence number of a transaction or the id of a block, rather than propagated alongside it.
+//! Every component, on every node, that handles a transaction thus emits its spans into the same
+//! trace without any change to the messages exchanged between components.
+
+use crate::struct_log::{struct_logger_set, StructuredLogEntry};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    fmt, process,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const SPAN: &str = "span";
+
+/// 128-bit trace ID, formatted as 32 hex digits as in W3C trace context
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TraceId(u128);
+
+impl TraceId {
+    /// Derives the trace ID from a key with a stable hash (FNV-1a), so that all nodes derive the
+    /// same ID from the same key.
+    pub fn from_key(key: &str) -> Self {
+        const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+        const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+        let hash = key.bytes().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+        });
+        // An all-zero trace ID is invalid
+        Self(hash.max(1))
+    }
+}
+
+/// 64-bit span ID, formatted as 16 hex digits as in W3C trace context
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SpanId(u64);
+
+static SPAN_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl SpanId {
+    /// Generates a span ID that is unique with high probability across processes.
+    pub fn generate() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+            ^ (u64::from(process::id()) << 32);
+        // splitmix64, to spread consecutive counters over the whole range
+        let mut z = seed.wrapping_add(
+            SPAN_COUNTER
+                .fetch_add(1, Ordering::Relaxed)
+                .wrapping_mul(0x9e37_79b9_7f4a_7c15),
+        );
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        // An all-zero span ID is invalid
+        Self((z ^ (z >> 31)).max(1))
+    }
+}
+
+impl fmt::Display for TraceId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl fmt::Display for SpanId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl Serialize for TraceId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for SpanId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Identifies a span within a trace
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct SpanContext {
+    pub trace_id: TraceId,
+    pub span_id: SpanId,
+}
+
+thread_local! {
+    static CURRENT_SPAN: Cell<Option<SpanContext>> = Cell::new(None);
+}
+
+/// Returns the context of the span entered on this thread, if any
+pub fn current_span() -> Option<SpanContext> {
+    CURRENT_SPAN.with(Cell::get)
+}
+
+/// A unit of work within a trace, emitted when ended or dropped. A span created while another span
+/// of the same trace is entered on the thread becomes its child.
+pub struct Span {
+    name: &'static str,
+    module: Option<&'static str>,
+    context: SpanContext,
+    parent_span_id: Option<SpanId>,
+    start: SystemTime,
+    attributes: HashMap<&'static str, Value>,
+    links: Vec<TraceId>,
+    ended: bool,
+}
+
+impl Span {
+    pub fn new(name: &'static str, trace_id: TraceId) -> Self {
+        let parent_span_id = current_span()
+            .filter(|parent| parent.trace_id == trace_id)
+            .map(|parent| parent.span_id);
+        Self {
+            name,
+            module: None,
+            context: SpanContext {
+                trace_id,
+                span_id: SpanId::generate(),
+            },
+            parent_span_id,
+            start: SystemTime::now(),
+            attributes: HashMap::new(),
+            links: vec![],
+            ended: false,
+        }
+    }
+
+    pub fn module(mut self, module: &'static str) -> Self {
+        self.module = Some(module);
+        self
+    }
+
+    pub fn attribute<V: Serialize>(mut self, key: &'static str, value: V) -> Self {
+        self.attributes.insert(
+            key,
+            serde_json::to_value(value).expect("Failed to serialize span attribute"),
+        );
+        self
+    }
+
+    /// Links the span to another trace, e.g., a block to the transactions it contains
+    pub fn link(mut self, trace_id: TraceId) -> Self {
+        self.links.push(trace_id);
+        self
+    }
+
+    pub fn context(&self) -> SpanContext {
+        self.context
+    }
+
+    /// Makes this the current span of the thread until the guard is dropped, so that structured
+    /// logs and spans created in the meantime are correlated with it. The guard must not be held
+    /// across an `.await`, as other tasks may run on the thread in the meantime.
+    pub fn enter(&self) -> SpanGuard {
+        let previous = CURRENT_SPAN.with(|current| current.replace(Some(self.context)));
+        SpanGuard { previous }
+    }
+
+    /// Ends the span and emits it, if structured logging is enabled
+    pub fn end(mut self) {
+        self.emit();
+    }
+
+    fn emit(&mut self) {
+        if self.ended {
+            return;
+        }
+        self.ended = true;
+        if !struct_logger_set() {
+            return;
+        }
+
+        let links: Vec<_> = self
+            .links
+            .iter()
+            .map(|trace_id| serde_json::json!({ "trace_id": trace_id }))
+            .collect();
+        let mut entry = StructuredLogEntry::new_named(SPAN)
+            .data("trace_id", self.context.trace_id)
+            .data("span_id", self.context.span_id)
+            .data("name", self.name)
+            .data("kind", "internal")
+            .data("start_time_unix_nano", unix_nanos(self.start))
+            .data("end_time_unix_nano", unix_nanos(SystemTime::now()));
+        if let Some(parent_span_id) = self.parent_span_id {
+            entry = entry.data("parent_span_id", parent_span_id);
+        }
+        if !self.attributes.is_empty() {
+            entry = entry.data("attributes", &self.attributes);
+        }
+        if !links.is_empty() {
+            entry = entry.data("links", links);
+        }
+        entry.span_context(Some(self.context));
+        if let Some(module) = self.module {
+            entry.module(module);
+        }
+        entry.send();
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.emit();
+    }
+}
+
+/// Restores the previously entered span when dropped
+pub struct SpanGuard {
+    previous: Option<SpanContext>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        CURRENT_SPAN.with(|current| current.set(self.previous));
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_id_is_stable() {
+        assert_eq!(
+            TraceId::from_key("txn::0000000000000000000000000a550c18::0"),
+            TraceId::from_key("txn::0000000000000000000000000a550c18::0")
+        );
+        assert_ne!(
+            TraceId::from_key("txn::0000000000000000000000000a550c18::0"),
+            TraceId::from_key("txn::0000000000000000000000000a550c18::1")
+        );
+        assert_eq!(TraceId::from_key("").to_string().len(), 32);
+        assert_ne!(SpanId::generate(), SpanId::generate());
+    }
+
+    #[test]
+    fn test_span_nesting() {
+        let trace_id = TraceId::from_key("block::1");
+        let parent = Span::new("parent", trace_id);
+        {
+            let _guard = parent.enter();
+            assert_eq!(current_span(), Some(parent.context()));
+
+            let child = Span::new("child", trace_id);
+            assert_eq!(child.parent_span_id, Some(parent.context().span_id));
+            let other = Span::new("other", TraceId::from_key("block::2"));
+            assert_eq!(other.parent_span_id, None);
+        }
+        assert_eq!(current_span(), None);
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::span::{current_span, SpanContext, SpanId, TraceId};
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use serde::Serialize;
@@ -57,6 +58,10 @@ pub struct StructuredLogEntry {
     timestamp: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     severity: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<TraceId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span_id: Option<SpanId>,
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     data: HashMap<&'static str, Value>,
 }
@@ -66,6 +71,7 @@ impl StructuredLogEntry {
     pub fn new_unnamed() -> Self {
         let mut ret = Self::default();
         ret.timestamp = Some(Utc::now().format("%F %T").to_string());
+        ret.span_context(current_span());
         ret
     }
 
@@ -73,6 +79,7 @@ impl StructuredLogEntry {
         let mut ret = Self::default();
         ret.name = Some(name);
         ret.timestamp = Some(Utc::now().format("%F %T").to_string());
+        ret.span_context(current_span());
         ret
     }
 
@@ -103,6 +110,13 @@ impl StructuredLogEntry {
         self.data(field.0, value)
     }
 
+    /// Correlates the entry with a span, by default the span entered on the current thread
+    pub fn span_context(&mut self, context: Option<SpanContext>) -> &mut Self {
+        self.trace_id = context.map(|context| context.trace_id);
+        self.span_id = context.map(|context| context.span_id);
+        self
+    }
+
     #[doc(hidden)] // set from macro
     pub fn log(&mut self, log: String) -> &mut Self {
         self.log = Some(log);
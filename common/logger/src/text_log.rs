@@ -8,7 +8,10 @@ use log::{self, Level, Log, Metadata, Record};
 use std::{
     env, fmt,
     fmt::Write,
-    sync::mpsc::{self, Receiver, RecvError, SyncSender, TrySendError},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvError, SyncSender, TrySendError},
+    },
     thread,
 };
 
@@ -16,6 +19,30 @@ pub const CHANNEL_SIZE: usize = 256;
 pub const DEFAULT_TARGET: &str = "libra";
 const RUST_LOG: &str = "RUST_LOG";
 
+/// The level set via `set_level`, or 0 if the filter established by `Logger::init` is in use.
+static LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Changes the logging level of the active logger at runtime. This replaces the filter the logger
+/// was initialized with, including any set by RUST_LOG.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as usize, Ordering::Release);
+    log::set_max_level(level.to_level_filter());
+}
+
+fn enabled(filter: &filter::Filter, metadata: &Metadata) -> bool {
+    match LEVEL.load(Ordering::Acquire) {
+        0 => filter.enabled(metadata),
+        level => metadata.level() as usize <= level,
+    }
+}
+
+fn matches(filter: &filter::Filter, record: &Record) -> bool {
+    match LEVEL.load(Ordering::Acquire) {
+        0 => filter.matches(record),
+        level => record.level() as usize <= level,
+    }
+}
+
 /// Logging framework for Libra that encapsulates a minimal dependency logger with support for
 /// environmental variable (RUST_LOG) and asynchronous logging.
 /// Note: only a single logger can be instantiated at a time. Repeated instantiates of the loggers
@@ -94,6 +121,7 @@ impl Logger {
 
         let filter = filter_builder.build();
         // Even if there is an existing logger, update the logging level
+        LEVEL.store(0, Ordering::Release);
         log::set_max_level(filter.filter());
 
         if self.is_async {
@@ -133,7 +161,7 @@ struct SyncLogger<W> {
 impl<W: Writer> Log for SyncLogger<W> {
     /// Determines if a log message with the specified metadata would be logged.
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.filter.enabled(metadata)
+        enabled(&self.filter, metadata)
     }
 
     /// Logs the provided record but first evaluates the filters and then writes it.
@@ -143,7 +171,7 @@ impl<W: Writer> Log for SyncLogger<W> {
             return;
         }
 
-        if !self.filter.matches(record) {
+        if !matches(&self.filter, record) {
             return;
         }
 
@@ -201,7 +229,7 @@ struct AsyncLogClient {
 impl Log for AsyncLogClient {
     /// Determines if a log message with the specified metadata would be logged.
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.filter.enabled(metadata)
+        enabled(&self.filter, metadata)
     }
 
     /// Logs the provided record but first evaluates the filters and then sending it to the
@@ -212,7 +240,7 @@ impl Log for AsyncLogClient {
             return;
         }
 
-        if !self.filter.matches(record) {
+        if !matches(&self.filter, record) {
             return;
         }
 
@@ -0,0 +1,156 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Health of the components of a node. Each component registers a HealthReporter, and the metrics
+//! server aggregates their reports at `/health`, for readiness probes, and at `/health/liveness`,
+//! for liveness probes.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::RwLock};
+
+/// The status of a component, ordered from best to worst
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// The component is working and ready to serve
+    Healthy,
+    /// The component is working, but cannot serve yet, e.g., while catching up with its peers
+    NotReady,
+    /// The component has stopped working and the node should be restarted
+    Unhealthy,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ComponentHealth {
+    pub status: HealthStatus,
+    pub message: String,
+}
+
+impl ComponentHealth {
+    pub fn healthy(message: impl Into<String>) -> Self {
+        Self::new(HealthStatus::Healthy, message)
+    }
+
+    pub fn not_ready(message: impl Into<String>) -> Self {
+        Self::new(HealthStatus::NotReady, message)
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self::new(HealthStatus::Unhealthy, message)
+    }
+
+    fn new(status: HealthStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Reports the current health of a component. Reporters are invoked on every request to the
+/// health endpoints, so they should be cheap and must not block for long.
+pub trait HealthReporter: Send + Sync {
+    fn health(&self) -> ComponentHealth;
+}
+
+impl<F> HealthReporter for F
+where
+    F: Fn() -> ComponentHealth + Send + Sync,
+{
+    fn health(&self) -> ComponentHealth {
+        self()
+    }
+}
+
+static REPORTERS: Lazy<RwLock<BTreeMap<String, Box<dyn HealthReporter>>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+/// Registers the reporter for a component, replacing any reporter previously registered for it.
+pub fn register_health_reporter(component: &str, reporter: Box<dyn HealthReporter>) {
+    REPORTERS
+        .write()
+        .expect("Unable to lock health reporters")
+        .insert(component.to_string(), reporter);
+}
+
+pub fn unregister_health_reporter(component: &str) {
+    REPORTERS
+        .write()
+        .expect("Unable to lock health reporters")
+        .remove(component);
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HealthReport {
+    /// The worst status of any component
+    pub status: HealthStatus,
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl HealthReport {
+    /// Whether all components are ready to serve
+    pub fn is_ready(&self) -> bool {
+        self.status == HealthStatus::Healthy
+    }
+
+    /// Whether all components are still working, even if not ready
+    pub fn is_live(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+}
+
+/// Collects the health of all registered components.
+pub fn health_report() -> HealthReport {
+    let components: BTreeMap<_, _> = REPORTERS
+        .read()
+        .expect("Unable to lock health reporters")
+        .iter()
+        .map(|(component, reporter)| (component.clone(), reporter.health()))
+        .collect();
+    let status = components
+        .values()
+        .map(|health| health.status)
+        .max()
+        .unwrap_or(HealthStatus::Healthy);
+    HealthReport { status, components }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_report() {
+        register_health_reporter(
+            "test_healthy",
+            Box::new(|| ComponentHealth::healthy("version 1")),
+        );
+        let report = health_report();
+        assert!(report.is_ready());
+        assert_eq!(
+            report.components["test_healthy"],
+            ComponentHealth::healthy("version 1")
+        );
+
+        register_health_reporter(
+            "test_syncing",
+            Box::new(|| ComponentHealth::not_ready("10 versions behind")),
+        );
+        let report = health_report();
+        assert!(!report.is_ready());
+        assert!(report.is_live());
+
+        register_health_reporter(
+            "test_syncing",
+            Box::new(|| ComponentHealth::unhealthy("stopped")),
+        );
+        let report = health_report();
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert!(!report.is_live());
+
+        unregister_health_reporter("test_healthy");
+        unregister_health_reporter("test_syncing");
+        assert!(!health_report().components.contains_key("test_syncing"));
+    }
+}
@@ -5,8 +5,10 @@
 #![recursion_limit = "128"]
 
 pub mod counters;
+pub mod health;
 mod json_encoder;
 mod json_metrics;
+pub mod metric_push;
 pub mod metric_server;
 mod public_metrics;
 
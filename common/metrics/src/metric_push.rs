@@ -0,0 +1,226 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pushes the metrics of the process to a collector at a fixed interval, for operators whose
+//! scraping infrastructure can't reach the hosts of the nodes. The metrics are either pushed to a
+//! Prometheus push gateway, in the text format, or to an OpenTelemetry collector, as OTLP over
+//! HTTP with JSON payloads.
+
+use anyhow::{ensure, Result};
+use libra_logger::prelude::*;
+use prometheus::{
+    proto::{LabelPair, Metric, MetricFamily, MetricType},
+    Encoder, TextEncoder,
+};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Where the metrics are pushed to.
+#[derive(Clone, Debug)]
+pub enum PushTarget {
+    /// A Prometheus push gateway at `url`, grouping the metrics under `job` and the labels.
+    PushGateway { url: String, job: String },
+    /// An OpenTelemetry collector receiving OTLP over HTTP at `url`.
+    Otlp { url: String },
+}
+
+/// Pushes all the registered metrics to `target` every `interval`, with `labels` added to them.
+/// Failed pushes are logged and retried at the next interval.
+pub fn push_all_metrics_periodically(
+    target: PushTarget,
+    interval: Duration,
+    labels: BTreeMap<String, String>,
+) {
+    info!("Pushing metrics to {:?} every {:?}", target, interval);
+    thread::Builder::new()
+        .name("metrics-push".into())
+        .spawn(move || {
+            let client = reqwest::blocking::Client::builder()
+                .timeout(interval)
+                .build()
+                .expect("Failed to create the metrics push client");
+            loop {
+                if let Err(e) = push(&client, &target, &labels) {
+                    warn!("Failed to push metrics to {:?}: {:?}", target, e);
+                }
+                thread::sleep(interval);
+            }
+        })
+        .expect("Failed to spawn the metrics push thread");
+}
+
+fn push(
+    client: &reqwest::blocking::Client,
+    target: &PushTarget,
+    labels: &BTreeMap<String, String>,
+) -> Result<()> {
+    let metric_families = prometheus::gather();
+    let response = match target {
+        PushTarget::PushGateway { url, job } => {
+            let encoder = TextEncoder::new();
+            let mut buffer = Vec::new();
+            encoder.encode(&metric_families, &mut buffer)?;
+            // PUT replaces all the metrics of the group, so metrics which are no longer
+            // exported don't linger in the gateway
+            client
+                .put(&push_gateway_url(url, job, labels))
+                .header(reqwest::header::CONTENT_TYPE, encoder.format_type())
+                .body(buffer)
+                .send()?
+        }
+        PushTarget::Otlp { url } => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+            client
+                .post(url)
+                .json(&encode_otlp(&metric_families, labels, now))
+                .send()?
+        }
+    };
+    ensure!(
+        response.status().is_success(),
+        "Unexpected response status {}",
+        response.status()
+    );
+    Ok(())
+}
+
+/// The url of the group of the metrics in a push gateway. The label values are base64 encoded,
+/// since they may contain characters which are not allowed in a path segment.
+pub(crate) fn push_gateway_url(url: &str, job: &str, labels: &BTreeMap<String, String>) -> String {
+    let mut group_url = format!(
+        "{}/metrics/job@base64/{}",
+        url.trim_end_matches('/'),
+        encode_label_value(job)
+    );
+    for (name, value) in labels {
+        group_url.push_str(&format!("/{}@base64/{}", name, encode_label_value(value)));
+    }
+    group_url
+}
+
+fn encode_label_value(value: &str) -> String {
+    // The push gateway represents an empty value with a single padding character
+    if value.is_empty() {
+        "=".to_string()
+    } else {
+        base64::encode_config(value, base64::URL_SAFE)
+    }
+}
+
+/// Encodes the metric families as an OTLP `ExportMetricsServiceRequest`, with `labels` as the
+/// attributes of the resource. Counters become monotonic cumulative sums. Summaries and untyped
+/// metrics are not registered by Libra and are skipped.
+pub(crate) fn encode_otlp(
+    metric_families: &[MetricFamily],
+    labels: &BTreeMap<String, String>,
+    now: Duration,
+) -> Value {
+    let metrics = metric_families
+        .iter()
+        .filter_map(|family| {
+            let data_points = family.get_metric().iter();
+            let data = match family.get_field_type() {
+                MetricType::COUNTER => json!({
+                    "sum": {
+                        "dataPoints": data_points
+                            .map(|m| number_data_point(m, m.get_counter().get_value(), now))
+                            .collect::<Vec<_>>(),
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                        "isMonotonic": true,
+                    }
+                }),
+                MetricType::GAUGE => json!({
+                    "gauge": {
+                        "dataPoints": data_points
+                            .map(|m| number_data_point(m, m.get_gauge().get_value(), now))
+                            .collect::<Vec<_>>(),
+                    }
+                }),
+                MetricType::HISTOGRAM => json!({
+                    "histogram": {
+                        "dataPoints": data_points
+                            .map(|m| histogram_data_point(m, now))
+                            .collect::<Vec<_>>(),
+                        "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                    }
+                }),
+                MetricType::SUMMARY | MetricType::UNTYPED => return None,
+            };
+            let mut metric = json!({
+                "name": family.get_name(),
+                "description": family.get_help(),
+            });
+            if let (Value::Object(metric), Value::Object(data)) = (&mut metric, data) {
+                metric.extend(data);
+            }
+            Some(metric)
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": labels
+                    .iter()
+                    .map(|(name, value)| attribute(name, value))
+                    .collect::<Vec<_>>(),
+            },
+            "scopeMetrics": [{
+                "scope": { "name": "libra" },
+                "metrics": metrics,
+            }],
+        }]
+    })
+}
+
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+
+fn attribute(name: &str, value: &str) -> Value {
+    json!({ "key": name, "value": { "stringValue": value } })
+}
+
+fn attributes(label_pairs: &[LabelPair]) -> Vec<Value> {
+    label_pairs
+        .iter()
+        .map(|pair| attribute(pair.get_name(), pair.get_value()))
+        .collect()
+}
+
+// 64 bit integers are encoded as strings in OTLP JSON
+fn number_data_point(metric: &Metric, value: f64, now: Duration) -> Value {
+    json!({
+        "attributes": attributes(metric.get_label()),
+        "timeUnixNano": now.as_nanos().to_string(),
+        "asDouble": value,
+    })
+}
+
+fn histogram_data_point(metric: &Metric, now: Duration) -> Value {
+    let histogram = metric.get_histogram();
+    // Prometheus buckets are cumulative, OTLP ones are not and end with an overflow bucket
+    let mut bucket_counts = Vec::new();
+    let mut explicit_bounds = Vec::new();
+    let mut previous = 0;
+    for bucket in histogram.get_bucket() {
+        if bucket.get_upper_bound().is_infinite() {
+            continue;
+        }
+        bucket_counts.push(bucket.get_cumulative_count().saturating_sub(previous));
+        explicit_bounds.push(bucket.get_upper_bound());
+        previous = bucket.get_cumulative_count();
+    }
+    bucket_counts.push(histogram.get_sample_count().saturating_sub(previous));
+
+    json!({
+        "attributes": attributes(metric.get_label()),
+        "timeUnixNano": now.as_nanos().to_string(),
+        "count": histogram.get_sample_count().to_string(),
+        "sum": histogram.get_sample_sum(),
+        "bucketCounts": bucket_counts.iter().map(u64::to_string).collect::<Vec<_>>(),
+        "explicitBounds": explicit_bounds,
+    })
+}
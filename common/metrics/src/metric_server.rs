@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    json_encoder::JsonEncoder, json_metrics::get_json_metrics, public_metrics::PUBLIC_METRICS,
+    health::{health_report, HealthReport},
+    json_encoder::JsonEncoder,
+    json_metrics::get_json_metrics,
+    public_metrics::PUBLIC_METRICS,
 };
 use futures::future;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
 };
+use once_cell::sync::OnceCell;
 use prometheus::{proto::MetricFamily, Encoder, TextEncoder};
 use std::{
     collections::HashMap,
@@ -17,6 +21,17 @@ use std::{
 };
 use tokio::runtime;
 
+/// Reloads the configuration of the process, returning a description of the outcome
+pub type ReloadHandler = Box<dyn Fn() -> Result<String, String> + Send + Sync>;
+
+static RELOAD_HANDLER: OnceCell<ReloadHandler> = OnceCell::new();
+
+/// Sets the handler invoked by `POST /reload` on the non-public metrics server. The handler can
+/// only be set once.
+pub fn set_reload_handler(handler: ReloadHandler) -> Result<(), ReloadHandler> {
+    RELOAD_HANDLER.set(handler)
+}
+
 fn encode_metrics(encoder: impl Encoder, whitelist: &'static [&'static str]) -> Vec<u8> {
     let mut metric_families = prometheus::gather();
     if !whitelist.is_empty() {
@@ -58,6 +73,18 @@ fn whitelist_json_metrics(
     whitelist_metrics
 }
 
+fn health_response(report: HealthReport, ok: bool) -> Response<Body> {
+    let mut resp = Response::new(Body::from(serde_json::to_string(&report).unwrap()));
+    resp.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    if !ok {
+        *resp.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+    }
+    resp
+}
+
 async fn serve_metrics(req: Request<Body>) -> Result<Response<Body>, hyper::Error> {
     let mut resp = Response::new(Body::empty());
     match (req.method(), req.uri().path()) {
@@ -79,6 +106,28 @@ async fn serve_metrics(req: Request<Body>) -> Result<Response<Body>, hyper::Erro
             let buffer = encode_metrics(encoder, &[]);
             *resp.body_mut() = Body::from(buffer);
         }
+        // Readiness: fails while any component is not ready, e.g., while state sync catches up
+        (&Method::GET, "/health") => {
+            let report = health_report();
+            let ready = report.is_ready();
+            resp = health_response(report, ready);
+        }
+        // Liveness: only fails once a component has stopped working
+        (&Method::GET, "/health/liveness") => {
+            let report = health_report();
+            let live = report.is_live();
+            resp = health_response(report, live);
+        }
+        (&Method::POST, "/reload") => match RELOAD_HANDLER.get() {
+            Some(handler) => match handler() {
+                Ok(message) => *resp.body_mut() = Body::from(message),
+                Err(message) => {
+                    *resp.status_mut() = StatusCode::BAD_REQUEST;
+                    *resp.body_mut() = Body::from(message);
+                }
+            },
+            None => *resp.status_mut() = StatusCode::NOT_FOUND,
+        },
         _ => {
             *resp.status_mut() = StatusCode::NOT_FOUND;
         }
@@ -0,0 +1,66 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metric_push::{encode_otlp, push_gateway_url};
+use prometheus::{Histogram, HistogramOpts, IntCounterVec, Opts, Registry};
+use std::{collections::BTreeMap, time::Duration};
+
+fn labels() -> BTreeMap<String, String> {
+    let mut labels = BTreeMap::new();
+    labels.insert("role".to_string(), "validator".to_string());
+    labels.insert("peer_id".to_string(), "".to_string());
+    labels
+}
+
+#[test]
+fn push_gateway_url_test() {
+    assert_eq!(
+        push_gateway_url("http://pushgateway:9091/", "libra/node", &labels()),
+        "http://pushgateway:9091/metrics/job@base64/bGlicmEvbm9kZQ==/peer_id@base64/=/role@base64/dmFsaWRhdG9y"
+    );
+}
+
+#[test]
+fn encode_otlp_test() {
+    let registry = Registry::new();
+    let counter = IntCounterVec::new(Opts::new("requests", "The requests"), &["op"]).unwrap();
+    let histogram =
+        Histogram::with_opts(HistogramOpts::new("latency", "The latency").buckets(vec![1.0, 2.0]))
+            .unwrap();
+    registry.register(Box::new(counter.clone())).unwrap();
+    registry.register(Box::new(histogram.clone())).unwrap();
+    counter.with_label_values(&["get"]).inc_by(3);
+    histogram.observe(0.5);
+    histogram.observe(1.5);
+    histogram.observe(3.0);
+
+    let request = encode_otlp(&registry.gather(), &labels(), Duration::from_secs(1));
+    let resource_metrics = &request["resourceMetrics"][0];
+    assert_eq!(
+        resource_metrics["resource"]["attributes"][1]["value"]["stringValue"],
+        "validator"
+    );
+    let metrics = resource_metrics["scopeMetrics"][0]["metrics"]
+        .as_array()
+        .unwrap();
+
+    // families are gathered in the order of their names
+    let histogram_point = &metrics[0]["histogram"]["dataPoints"][0];
+    assert_eq!(metrics[0]["name"], "latency");
+    assert_eq!(histogram_point["count"], "3");
+    assert_eq!(
+        histogram_point["bucketCounts"],
+        serde_json::json!(["1", "1", "1"])
+    );
+    assert_eq!(
+        histogram_point["explicitBounds"],
+        serde_json::json!([1.0, 2.0])
+    );
+    assert_eq!(histogram_point["timeUnixNano"], "1000000000");
+
+    let sum = &metrics[1]["sum"];
+    assert_eq!(metrics[1]["name"], "requests");
+    assert_eq!(sum["isMonotonic"], true);
+    assert_eq!(sum["dataPoints"][0]["asDouble"], 3.0);
+    assert_eq!(sum["dataPoints"][0]["attributes"][0]["key"], "op");
+}
@@ -4,6 +4,7 @@
 #![forbid(unsafe_code)]
 
 use libra_logger::debug;
+use rand::Rng;
 use std::{future::Future, pin::Pin, thread, time::Duration};
 
 /// Given an operation retries it successfully sleeping everytime it fails
@@ -76,6 +77,44 @@ impl Iterator for FixedDelay {
     }
 }
 
+/// Delays doubling from `base_delay_ms` up to `max_delay_ms`, each randomized between half and
+/// all of its value, so that clients which failed together do not all retry together
+pub fn jittered_exp_retry_strategy(
+    base_delay_ms: u64,
+    max_delay_ms: u64,
+    tries: usize,
+) -> impl Iterator<Item = Duration> {
+    JitteredExponentialDelay::new(base_delay_ms, max_delay_ms).take(tries)
+}
+
+/// An iterator which uses an exponentially growing delay with random jitter
+pub struct JitteredExponentialDelay {
+    current_ms: u64,
+    max_ms: u64,
+}
+
+impl JitteredExponentialDelay {
+    /// Create a new `JitteredExponentialDelay` using the given delays in milliseconds.
+    fn new(base_ms: u64, max_ms: u64) -> Self {
+        JitteredExponentialDelay {
+            current_ms: base_ms.min(max_ms),
+            max_ms,
+        }
+    }
+}
+
+impl Iterator for JitteredExponentialDelay {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let delay_ms = self.current_ms;
+        self.current_ms = delay_ms.saturating_mul(2).min(self.max_ms);
+        let half = delay_ms / 2;
+        let jitter = rand::thread_rng().gen_range(0, delay_ms - half + 1);
+        Some(Duration::from_millis(half + jitter))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +141,14 @@ mod tests {
         });
         assert_eq!(result, Err("not 5"));
     }
+
+    #[test]
+    fn test_jittered_exp_retry_strategy() {
+        let delays: Vec<_> = jittered_exp_retry_strategy(100, 500, 5).collect();
+        assert_eq!(delays.len(), 5);
+        for (delay, expected_ms) in delays.iter().zip(&[100, 200, 400, 500, 500]) {
+            assert!(*delay >= Duration::from_millis(expected_ms / 2));
+            assert!(*delay <= Duration::from_millis(*expected_ms));
+        }
+    }
 }
@@ -12,7 +12,7 @@ use channel::{
 };
 use libra_types::{
     event::EventKey,
-    on_chain_config::{ConfigID, OnChainConfigPayload},
+    on_chain_config::{ConfigID, OnChainConfig, OnChainConfigPayload},
 };
 use std::{collections::HashSet, num::NonZeroUsize};
 
@@ -50,7 +50,7 @@ impl<T: Clone, U> SubscriptionService<T, U> {
 /// when `subscription_service` crate is a dependency of `types`, the build-dev fails
 pub type ReconfigSubscription = SubscriptionService<SubscriptionBundle, OnChainConfigPayload>;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct SubscriptionBundle {
     pub configs: HashSet<ConfigID>,
     pub events: HashSet<EventKey>,
@@ -63,6 +63,19 @@ impl SubscriptionBundle {
 
         Self { configs, events }
     }
+
+    /// Subscribes to the changes of the on-chain config `T`, which can then be read from the
+    /// payloads published to the subscription
+    pub fn with_config<T: OnChainConfig>(mut self) -> Self {
+        self.configs.insert(T::CONFIG_ID);
+        self
+    }
+
+    /// Subscribes to the emission of the events with `key`
+    pub fn with_event(mut self, key: EventKey) -> Self {
+        self.events.insert(key);
+        self
+    }
 }
 
 impl ReconfigSubscription {
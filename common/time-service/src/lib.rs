@@ -0,0 +1,134 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! The wall clock of a node, shared by the components whose behavior depends on the current time:
+//! the timestamps consensus gives to blocks, and the expiration of the transactions in mempool.
+//! Tests can replace it to make a node observe time passing without waiting for it.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// A source of the current time. Clones share the same clock, so that moving it forward is
+/// observed by all the components it was handed to.
+#[derive(Clone, Debug)]
+pub enum TimeService {
+    /// The system clock
+    Real,
+    /// The system clock moved forward by an offset in microseconds, which only ever grows. Time
+    /// keeps flowing, so that timeouts still fire, but tests can jump ahead.
+    Adjustable(Arc<AtomicU64>),
+    /// A clock standing still at a time in microseconds since the UNIX_EPOCH, which only moves
+    /// when advanced or slept on
+    Mock(Arc<AtomicU64>),
+}
+
+impl Default for TimeService {
+    fn default() -> Self {
+        TimeService::Real
+    }
+}
+
+impl TimeService {
+    pub fn real() -> Self {
+        TimeService::Real
+    }
+
+    pub fn adjustable() -> Self {
+        TimeService::Adjustable(Arc::new(AtomicU64::new(0)))
+    }
+
+    pub fn mock(now: Duration) -> Self {
+        TimeService::Mock(Arc::new(AtomicU64::new(now.as_micros() as u64)))
+    }
+
+    /// Returns the current time as a Duration since the UNIX_EPOCH
+    pub fn now(&self) -> Duration {
+        match self {
+            TimeService::Real => system_now(),
+            TimeService::Adjustable(offset) => {
+                system_now() + Duration::from_micros(offset.load(Ordering::SeqCst))
+            }
+            TimeService::Mock(now) => Duration::from_micros(now.load(Ordering::SeqCst)),
+        }
+    }
+
+    /// Returns the current time as a SystemTime
+    pub fn now_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + self.now()
+    }
+
+    /// Returns true if the clock can be moved forward
+    pub fn is_adjustable(&self) -> bool {
+        !matches!(self, TimeService::Real)
+    }
+
+    /// Moves the clock forward by `duration`. Returns false if it is the system clock, which can't
+    /// be moved.
+    pub fn advance(&self, duration: Duration) -> bool {
+        match self {
+            TimeService::Real => false,
+            TimeService::Adjustable(micros) | TimeService::Mock(micros) => {
+                micros.fetch_add(duration.as_micros() as u64, Ordering::SeqCst);
+                true
+            }
+        }
+    }
+
+    /// Sleeps the calling thread for `duration`, or only advances a mock clock
+    pub fn sleep(&self, duration: Duration) {
+        match self {
+            TimeService::Mock(now) => {
+                now.fetch_add(duration.as_micros() as u64, Ordering::SeqCst);
+            }
+            _ => thread::sleep(duration),
+        }
+    }
+}
+
+fn system_now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Timestamp generated is before the UNIX_EPOCH!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_time() {
+        let service = TimeService::real();
+        let before = system_now();
+        assert!(service.now() >= before);
+        assert!(!service.advance(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_adjustable_time() {
+        let service = TimeService::adjustable();
+        let clone = service.clone();
+        assert!(service.advance(Duration::from_secs(3600)));
+        assert!(clone.now() >= system_now() + Duration::from_secs(3599));
+    }
+
+    #[test]
+    fn test_mock_time() {
+        let service = TimeService::mock(Duration::from_secs(10));
+        assert_eq!(service.now(), Duration::from_secs(10));
+        service.sleep(Duration::from_millis(500));
+        assert_eq!(service.now(), Duration::from_millis(10_500));
+        assert!(service.clone().advance(Duration::from_secs(1)));
+        assert_eq!(
+            service.now_system_time(),
+            UNIX_EPOCH + Duration::from_millis(11_500)
+        );
+    }
+}
@@ -24,6 +24,8 @@ pub struct FullNodeConfig {
     pub genesis: Option<Transaction>,
     pub listen_address: NetworkAddress,
     pub mutual_authentication: bool,
+    /// The network between the full nodes and their upstream node
+    pub network_id: NetworkId,
     template: NodeConfig,
     validator_config: ValidatorConfig,
 }
@@ -46,6 +48,7 @@ impl Default for FullNodeConfig {
             genesis: None,
             listen_address: NetworkAddress::from_str(DEFAULT_LISTEN_ADDRESS).unwrap(),
             mutual_authentication: true,
+            network_id: NetworkId::Public,
             template,
             validator_config: ValidatorConfig::new(),
         }
@@ -198,7 +201,7 @@ impl FullNodeConfig {
                 .full_node_networks
                 .last_mut()
                 .ok_or(Error::MissingFullNodeNetwork)?;
-            network.network_id = NetworkId::Public;
+            network.network_id = self.network_id.clone();
             network.network_peers = network_peers.clone();
             network.seed_peers = seed_peers.clone();
             if idx < actual_nodes - 1 {
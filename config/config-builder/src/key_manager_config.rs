@@ -45,6 +45,7 @@ impl KeyManagerConfig {
         key_manager_config.secure_backend = SecureBackend::Vault(VaultConfig {
             ca_certificate: None,
             namespace: self.vault_namespace.clone(),
+            enterprise_namespace: None,
             server: self.vault_host.clone(),
             token: Token::FromConfig(self.vault_token.clone()),
         });
@@ -159,6 +159,8 @@ impl ValidatorConfig {
             let db_rw = DbReaderWriter::new(LibraDB::open(
                 &path, false, /* readonly */
                 None,  /* pruner */
+                None,  /* archiver */
+                None,  /* waypoint_interval */
             )?);
             let waypoint = db_bootstrapper::bootstrap_db_if_empty::<LibraVM>(&db_rw, &genesis)?
                 .ok_or_else(|| format_err!("Failed to bootstrap empty DB."))?;
@@ -197,6 +199,7 @@ impl ValidatorConfig {
                 "on-disk" => SecureBackend::OnDiskStorage(OnDiskStorageConfig::default()),
                 "vault" => SecureBackend::Vault(VaultConfig {
                     namespace: self.safety_rules_namespace.clone(),
+                    enterprise_namespace: None,
                     server: self
                         .safety_rules_host
                         .as_ref()
@@ -0,0 +1,88 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use libra_secure_storage::AuditLog;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+/// Verifies the hash chain of a secure storage audit log and optionally exports the verified
+/// entries as a JSON array for further analysis.
+#[derive(Debug, StructOpt)]
+pub struct VerifyAuditLog {
+    /// Path to the audit log
+    #[structopt(long)]
+    path: PathBuf,
+    /// If specified, the verified entries are written to this file
+    #[structopt(long)]
+    export_path: Option<PathBuf>,
+}
+
+impl VerifyAuditLog {
+    pub fn execute(self) -> Result<String, Error> {
+        let entries =
+            AuditLog::verify(&self.path).map_err(|e| Error::AuditLogError(e.to_string()))?;
+
+        if let Some(export_path) = &self.export_path {
+            let data = serde_json::to_vec_pretty(&entries)
+                .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+            fs::write(export_path, data).map_err(|e| {
+                Error::UnableToWriteFile(export_path.to_str().unwrap().to_string(), e.to_string())
+            })?;
+        }
+
+        Ok(match entries.last() {
+            Some(entry) => format!(
+                "Verified {} entries, latest hash: {}",
+                entries.len(),
+                entry.hash.to_hex()
+            ),
+            None => "Audit log is empty".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_secure_storage::{AuditEntry, AuditOperation};
+    use libra_temppath::TempPath;
+
+    #[test]
+    fn test_verify_and_export() {
+        let log_path = TempPath::new();
+        let log = AuditLog::open(log_path.path().to_path_buf()).unwrap();
+        log.append("safety_rules", AuditOperation::CreateKey, "consensus", true)
+            .unwrap();
+        let latest = log
+            .append(
+                "safety_rules",
+                AuditOperation::SignMessage,
+                "consensus",
+                true,
+            )
+            .unwrap();
+
+        let export_path = TempPath::new();
+        let verify = VerifyAuditLog {
+            path: log_path.path().to_path_buf(),
+            export_path: Some(export_path.path().to_path_buf()),
+        };
+        assert_eq!(
+            verify.execute().unwrap(),
+            format!("Verified 2 entries, latest hash: {}", latest.hash.to_hex())
+        );
+
+        let exported: Vec<AuditEntry> =
+            serde_json::from_slice(&fs::read(export_path.path()).unwrap()).unwrap();
+        assert_eq!(exported.last().unwrap(), &latest);
+
+        let contents = fs::read_to_string(log_path.path()).unwrap();
+        fs::write(log_path.path(), contents.replace("consensus", "operator")).unwrap();
+        let verify = VerifyAuditLog {
+            path: log_path.path().to_path_buf(),
+            export_path: None,
+        };
+        assert!(verify.execute().is_err());
+    }
+}
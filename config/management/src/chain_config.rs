@@ -0,0 +1,187 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    waypoint::{genesis_waypoint, read_genesis},
+};
+use libra_config::{
+    chain_id::ChainId,
+    config::{NodeConfig, PersistableConfig, SeedPeersConfig, WaypointConfig},
+};
+use libra_global_constants::WAYPOINT;
+use libra_secure_storage::{KVStorage, Storage};
+use libra_types::waypoint::Waypoint;
+use std::{
+    convert::TryFrom,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use structopt::StructOpt;
+
+/// The node config in a bundle, next to which the genesis blob is written
+const NODE_CONFIG_FILE: &str = "node.yaml";
+const WAYPOINT_FILE: &str = "waypoint.txt";
+
+/// Generates the config bundle of a node for a chain from a template. The bundle holds a node
+/// config carrying the chain id, the waypoint of the genesis transaction, and the seed peers of
+/// the chain, next to the genesis blob and a file with the waypoint.
+#[derive(Debug, StructOpt)]
+pub struct GenerateChainConfig {
+    /// The chain: testnet, premainnet, or the name of a custom chain
+    #[structopt(long)]
+    chain_id: ChainId,
+    /// The node config the bundle is generated from
+    #[structopt(long)]
+    template: PathBuf,
+    /// The genesis blob of the chain
+    #[structopt(long)]
+    genesis_path: PathBuf,
+    /// A YAML map from the peer id of each seed peer of the full node networks to its addresses.
+    /// Validators discover each other on-chain.
+    #[structopt(long)]
+    seed_peers_path: Option<PathBuf>,
+    /// The directory the bundle is written to
+    #[structopt(long)]
+    output_dir: PathBuf,
+}
+
+impl GenerateChainConfig {
+    pub fn execute(self) -> Result<Waypoint, Error> {
+        let mut config = load_node_config(&self.template)?;
+        let genesis = read_genesis(&self.genesis_path)?;
+        let waypoint = genesis_waypoint(&genesis)?;
+
+        config.base.chain_id = self.chain_id.clone();
+        config.base.waypoint = WaypointConfig::FromConfig(waypoint);
+        config.execution.genesis = Some(genesis);
+        // The genesis blob is written next to the node config
+        config.execution.genesis_file_location = PathBuf::new();
+        if let Some(seed_peers_path) = &self.seed_peers_path {
+            let seed_peers = SeedPeersConfig::load_config(seed_peers_path)
+                .map_err(|e| Error::UnableToParseFile(display(seed_peers_path), e.to_string()))?;
+            for network in &mut config.full_node_networks {
+                network.seed_peers = seed_peers.clone();
+            }
+        }
+        verify_chain_config(&config, Some(&self.chain_id))?;
+
+        let output_dir = display(&self.output_dir);
+        fs::create_dir_all(&self.output_dir)
+            .map_err(|e| Error::UnableToWriteFile(output_dir.clone(), e.to_string()))?;
+        config
+            .save(self.output_dir.join(NODE_CONFIG_FILE))
+            .map_err(|e| Error::UnableToWriteFile(output_dir.clone(), e.to_string()))?;
+        fs::write(self.output_dir.join(WAYPOINT_FILE), waypoint.to_string())
+            .map_err(|e| Error::UnableToWriteFile(output_dir, e.to_string()))?;
+        Ok(waypoint)
+    }
+}
+
+/// Verifies that the sections of a node config agree on the chain the node belongs to.
+#[derive(Debug, StructOpt)]
+pub struct VerifyChainConfig {
+    /// The node config to verify
+    #[structopt(long)]
+    config: PathBuf,
+    /// The chain the node is expected to belong to
+    #[structopt(long)]
+    chain_id: Option<ChainId>,
+}
+
+impl VerifyChainConfig {
+    pub fn execute(self) -> Result<String, Error> {
+        let config = load_node_config(&self.config)?;
+        let waypoint = verify_chain_config(&config, self.chain_id.as_ref())?;
+        Ok(format!(
+            "The config agrees on chain {}, with genesis waypoint {}",
+            config.base.chain_id, waypoint
+        ))
+    }
+}
+
+/// Checks that the config is valid, that it belongs to the expected chain, if any, and that its
+/// waypoint is the one of its genesis transaction. Returns the waypoint.
+fn verify_chain_config(config: &NodeConfig, chain_id: Option<&ChainId>) -> Result<Waypoint, Error> {
+    if let Err(e) = config.validate() {
+        return Err(Error::ChainConfigMismatch(e.to_string()));
+    }
+    if let Some(chain_id) = chain_id {
+        if config.base.chain_id != *chain_id {
+            return Err(Error::ChainConfigMismatch(format!(
+                "base.chain_id is {}, expected {}",
+                config.base.chain_id, chain_id
+            )));
+        }
+    }
+
+    // Both are present in a valid config
+    let genesis = config.execution.genesis.as_ref().ok_or_else(|| {
+        Error::ChainConfigMismatch("execution.genesis_file_location is missing".into())
+    })?;
+    let waypoint = configured_waypoint(&config.base.waypoint)?
+        .ok_or_else(|| Error::ChainConfigMismatch("base.waypoint is missing".into()))?;
+    let expected_waypoint = genesis_waypoint(genesis)?;
+    if waypoint != expected_waypoint {
+        return Err(Error::ChainConfigMismatch(format!(
+            "base.waypoint is {}, but the genesis transaction produces {}",
+            waypoint, expected_waypoint
+        )));
+    }
+
+    let networks = config
+        .full_node_networks
+        .iter()
+        .chain(config.validator_network.iter());
+    for network in networks {
+        network.verify_seed_peer_addrs().map_err(|e| {
+            Error::ChainConfigMismatch(format!("{} network: {}", network.network_id, e))
+        })?;
+    }
+    Ok(waypoint)
+}
+
+fn configured_waypoint(waypoint_config: &WaypointConfig) -> Result<Option<Waypoint>, Error> {
+    match waypoint_config {
+        WaypointConfig::FromConfig(waypoint) => Ok(Some(*waypoint)),
+        WaypointConfig::FromStorage(backend) => {
+            let storage = Storage::try_from(backend)
+                .map_err(|e| Error::LocalStorageUnavailable(e.to_string()))?;
+            let waypoint = storage
+                .get(WAYPOINT)
+                .and_then(|v| v.value.string())
+                .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))?;
+            Waypoint::from_str(&waypoint)
+                .map(Some)
+                .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))
+        }
+        WaypointConfig::None => Ok(None),
+    }
+}
+
+fn load_node_config(path: &Path) -> Result<NodeConfig, Error> {
+    NodeConfig::load(path).map_err(|e| Error::UnableToReadFile(display(path), e.to_string()))
+}
+
+fn display(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_chain_config() {
+        let (mut config, _) = config_builder::test_config();
+        config.base.chain_id = ChainId::testnet();
+        let waypoint = verify_chain_config(&config, Some(&ChainId::testnet())).unwrap();
+        assert_eq!(config.base.waypoint.waypoint_from_config(), Some(waypoint));
+
+        assert!(verify_chain_config(&config, Some(&ChainId::premainnet())).is_err());
+
+        config.base.waypoint = WaypointConfig::FromConfig(Waypoint::default());
+        assert!(verify_chain_config(&config, None).is_err());
+    }
+}
@@ -6,14 +6,24 @@ use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq)]
 pub enum Error {
+    #[error("Audit log failed verification: {0}")]
+    AuditLogError(String),
     #[error("Invalid key value found in backend: {0}")]
     BackendInvalidKeyValue(String),
     #[error("Backend is missing the backend key")]
     BackendMissingBackendKey,
     #[error("Backend parsing error: {0}")]
     BackendParsingError(String),
+    #[error("Config does not agree on its chain: {0}")]
+    ChainConfigMismatch(String),
     #[error("Invalid arguments: {0}")]
     CommandArgumentError(String),
+    #[error("Validator set failed verification: {0}")]
+    InvalidValidatorSet(String),
+    #[error("Failed to read, {0}, from JSON-RPC: {1}")]
+    JsonRpcReadError(&'static str, String),
+    #[error("Failed to submit, {0}, via JSON-RPC: {1}")]
+    JsonRpcWriteError(&'static str, String),
     #[error("Local storage unavailable, please check your configuration: {0}")]
     LocalStorageUnavailable(String),
     #[error("Failed to read, {0}, from local storage: {1}")]
@@ -22,16 +32,22 @@ pub enum Error {
     LocalStorageSigningError(&'static str, &'static str, String),
     #[error("Failed to write, {0}, to local storage: {1}")]
     LocalStorageWriteError(&'static str, String),
+    #[error("Failed to verify the state read against the waypoint: {0}")]
+    ProofVerificationError(String),
     #[error("Failed to read, {0}, from remote storage: {1}")]
     RemoteStorageReadError(&'static str, String),
     #[error("Failed to write, {0}, to remote storage: {1}")]
     RemoteStorageWriteError(&'static str, String),
     #[error("Remote storage unavailable, please check your configuration: {0}")]
     RemoteStorageUnavailable(String),
+    #[error("Transaction {0} was not confirmed: {1}")]
+    TransactionNotConfirmed(String, String),
     #[error("Unable to read file, {0}, error {1}")]
     UnableToReadFile(String, String),
     #[error("Unable to parse file, {0}, error {1}")]
     UnableToParseFile(String, String),
+    #[error("Unable to write file, {0}, error {1}")]
+    UnableToWriteFile(String, String),
     #[error("Unexpected command, expected {0}, found {1}")]
     UnexpectedCommand(CommandName, CommandName),
     #[error("Unexpected error: {0}")]
@@ -2,10 +2,29 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Error;
+use libra_crypto::hash::{CryptoHash, HashValue};
 use libra_secure_json_rpc::JsonRpcClient;
-use std::{fs, path::PathBuf};
+use libra_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    ledger_info::LedgerInfo,
+    transaction::{SignedTransaction, Transaction},
+    trusted_state::{TrustedState, TrustedStateChange},
+    waypoint::Waypoint,
+};
+use std::{
+    convert::TryFrom,
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
 use structopt::StructOpt;
 
+/// How long to wait for a submitted transaction to be committed
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, StructOpt)]
 pub struct SubmitTransaction {
     #[structopt(long)]
@@ -45,3 +64,108 @@ impl ReadAccountState {
         })
     }
 }
+
+/// A JSON-RPC client that only trusts what it reads if it is proven relative to a waypoint, so
+/// that operators need not trust the full node serving their requests.
+pub struct VerifyingClient {
+    client: JsonRpcClient,
+    waypoint: Waypoint,
+    trusted_state: TrustedState,
+}
+
+impl VerifyingClient {
+    pub fn new(host: String, waypoint: Waypoint) -> Self {
+        Self {
+            client: JsonRpcClient::new(host),
+            waypoint,
+            trusted_state: TrustedState::from(waypoint),
+        }
+    }
+
+    /// Returns the waypoint emitted last by the server, if any, once proven from the waypoint
+    pub fn latest_waypoint(&self) -> Result<Option<Waypoint>, Error> {
+        let signed_waypoint = match self
+            .client
+            .get_latest_waypoint()
+            .map_err(|e| Error::JsonRpcReadError("latest waypoint", e.to_string()))?
+        {
+            Some(signed_waypoint) => signed_waypoint,
+            None => return Ok(None),
+        };
+        let (_, epoch_change_proof) = self
+            .client
+            .get_state_proof(self.waypoint.version())
+            .map_err(|e| Error::JsonRpcReadError("state proof", e.to_string()))?;
+        signed_waypoint
+            .verify(&self.waypoint, &epoch_change_proof)
+            .map_err(|e| Error::ProofVerificationError(e.to_string()))?;
+        Ok(Some(signed_waypoint.waypoint()))
+    }
+
+    /// Ratchets the trusted state to the latest ledger info of the server and returns it
+    pub fn latest_ledger_info(&mut self) -> Result<LedgerInfo, Error> {
+        let (ledger_info, epoch_change_proof) = self
+            .client
+            .get_state_proof(self.trusted_state.latest_version())
+            .map_err(|e| Error::JsonRpcReadError("state proof", e.to_string()))?;
+        match self
+            .trusted_state
+            .verify_and_ratchet(&ledger_info, &epoch_change_proof)
+            .map_err(|e| Error::ProofVerificationError(e.to_string()))?
+        {
+            TrustedStateChange::Epoch { new_state, .. }
+            | TrustedStateChange::Version { new_state } => self.trusted_state = new_state,
+            TrustedStateChange::NoChange => (),
+        }
+        Ok(ledger_info.ledger_info().clone())
+    }
+
+    /// Returns the latest state of the account, verified against the waypoint
+    pub fn account_state(&mut self, account: AccountAddress) -> Result<AccountState, Error> {
+        let ledger_info = self.latest_ledger_info()?;
+        let version = ledger_info.version();
+        let account_state_with_proof = self
+            .client
+            .get_account_state_with_proof(account, Some(version), Some(version))
+            .map_err(|e| Error::JsonRpcReadError("account state", e.to_string()))?;
+        account_state_with_proof
+            .verify(&ledger_info, version, account)
+            .map_err(|e| Error::ProofVerificationError(e.to_string()))?;
+
+        let blob = account_state_with_proof
+            .blob
+            .ok_or_else(|| Error::JsonRpcReadError("account state", "not found".into()))?;
+        AccountState::try_from(&blob)
+            .map_err(|e| Error::JsonRpcReadError("account state", e.to_string()))
+    }
+
+    pub fn sequence_number(&mut self, account: AccountAddress) -> Result<u64, Error> {
+        self.account_state(account)?
+            .get_account_resource()
+            .map_err(|e| Error::JsonRpcReadError("account resource", e.to_string()))?
+            .map(|account_resource| account_resource.sequence_number())
+            .ok_or_else(|| Error::JsonRpcReadError("account resource", "not found".into()))
+    }
+
+    /// Submits the transaction and waits until its sender's sequence number shows it committed.
+    /// Returns the hash of the transaction.
+    pub fn submit_and_confirm(&mut self, txn: SignedTransaction) -> Result<HashValue, Error> {
+        let (sender, sequence_number) = (txn.sender(), txn.sequence_number());
+        let hash = Transaction::UserTransaction(txn.clone()).hash();
+        self.client
+            .submit_transaction(txn)
+            .map_err(|e| Error::JsonRpcWriteError("transaction", e.to_string()))?;
+
+        let start = Instant::now();
+        while self.sequence_number(sender)? <= sequence_number {
+            if start.elapsed() > CONFIRMATION_TIMEOUT {
+                return Err(Error::TransactionNotConfirmed(
+                    hash.to_hex(),
+                    format!("not committed after {}s", CONFIRMATION_TIMEOUT.as_secs()),
+                ));
+            }
+            thread::sleep(CONFIRMATION_POLL_INTERVAL);
+        }
+        Ok(hash)
+    }
+}
@@ -3,6 +3,8 @@
 
 #![forbid(unsafe_code)]
 
+mod audit_log;
+mod chain_config;
 mod error;
 mod genesis;
 mod json_rpc;
@@ -10,6 +12,7 @@ mod key;
 mod layout;
 mod secure_backend;
 mod validator_config;
+mod validator_set;
 mod verify;
 mod waypoint;
 
@@ -20,7 +23,7 @@ mod smoke_test;
 mod storage_helper;
 
 use crate::{error::Error, layout::SetLayout, secure_backend::SecureBackend};
-use libra_crypto::ed25519::Ed25519PublicKey;
+use libra_crypto::{ed25519::Ed25519PublicKey, HashValue};
 use libra_types::{transaction::Transaction, waypoint::Waypoint};
 use structopt::StructOpt;
 
@@ -29,6 +32,7 @@ pub mod constants {
     pub const COMMON_NS: &str = "common";
     pub const LAYOUT: &str = "layout";
     pub const VALIDATOR_CONFIG: &str = "validator_config";
+    pub const WAYPOINT_PROVENANCE: &str = "waypoint_provenance";
 
     pub const GAS_UNIT_PRICE: u64 = 0;
     pub const MAX_GAS_AMOUNT: u64 = 1_000_000;
@@ -43,6 +47,10 @@ pub enum Command {
     AssociationKey(crate::key::AssociationKey),
     #[structopt(about = "Create a waypoint and optionally place it in a store")]
     CreateWaypoint(crate::waypoint::CreateWaypoint),
+    #[structopt(about = "Fetches the latest waypoint of a node, verified against a trusted one")]
+    FetchWaypoint(crate::waypoint::FetchWaypoint),
+    #[structopt(about = "Generates the config of a node for a chain from a template")]
+    GenerateChainConfig(crate::chain_config::GenerateChainConfig),
     #[structopt(about = "Retrieves data from a store to produce genesis")]
     Genesis(crate::genesis::Genesis),
     #[structopt(about = "Insert a waypoint")]
@@ -53,29 +61,55 @@ pub enum Command {
     OwnerKey(crate::key::OwnerKey),
     #[structopt(about = "Read account state from JSON-RPC endpoint")]
     ReadAccountState(crate::json_rpc::ReadAccountState),
+    #[structopt(about = "Adds a validator to the validator set")]
+    RegisterValidator(crate::validator_set::RegisterValidator),
+    #[structopt(about = "Removes a validator from the validator set")]
+    RemoveValidator(crate::validator_set::RemoveValidator),
+    #[structopt(about = "Rotates the operator key and the operator account's authentication key")]
+    RotateOperatorKey(crate::validator_set::RotateOperatorKey),
     #[structopt(about = "Submit a transaction to the blockchain")]
     SubmitTransaction(crate::json_rpc::SubmitTransaction),
     #[structopt(about = "Submits a Layout doc to a shared storage")]
     SetLayout(SetLayout),
+    #[structopt(about = "Updates the ValidatorConfig, including network addresses, on-chain")]
+    SetValidatorConfig(crate::validator_set::SetValidatorConfig),
     #[structopt(about = "Constructs and signs a ValidatorConfig")]
     ValidatorConfig(crate::validator_config::ValidatorConfig),
     #[structopt(about = "Verifies and prints the current configuration state")]
     Verify(crate::verify::Verify),
+    #[structopt(about = "Verifies and optionally exports a secure storage audit log")]
+    VerifyAuditLog(crate::audit_log::VerifyAuditLog),
+    #[structopt(about = "Verifies that the sections of a node config agree on its chain")]
+    VerifyChainConfig(crate::chain_config::VerifyChainConfig),
+    #[structopt(about = "Verifies the current or a proposed validator set as consensus would")]
+    VerifyValidatorSet(crate::validator_set::VerifyValidatorSet),
+    #[structopt(about = "Verifies a waypoint against a running node and records its provenance")]
+    VerifyWaypoint(crate::waypoint::VerifyWaypoint),
 }
 
 #[derive(Debug, PartialEq)]
 pub enum CommandName {
     AssociationKey,
     CreateWaypoint,
+    FetchWaypoint,
+    GenerateChainConfig,
     Genesis,
     InsertWaypoint,
     OperatorKey,
     OwnerKey,
     ReadAccountState,
+    RegisterValidator,
+    RemoveValidator,
+    RotateOperatorKey,
     SetLayout,
+    SetValidatorConfig,
     SubmitTransaction,
     ValidatorConfig,
     Verify,
+    VerifyAuditLog,
+    VerifyChainConfig,
+    VerifyValidatorSet,
+    VerifyWaypoint,
 }
 
 impl From<&Command> for CommandName {
@@ -83,15 +117,25 @@ impl From<&Command> for CommandName {
         match command {
             Command::AssociationKey(_) => CommandName::AssociationKey,
             Command::CreateWaypoint(_) => CommandName::CreateWaypoint,
+            Command::FetchWaypoint(_) => CommandName::FetchWaypoint,
+            Command::GenerateChainConfig(_) => CommandName::GenerateChainConfig,
             Command::Genesis(_) => CommandName::Genesis,
             Command::InsertWaypoint(_) => CommandName::InsertWaypoint,
             Command::OperatorKey(_) => CommandName::OperatorKey,
             Command::OwnerKey(_) => CommandName::OwnerKey,
             Command::ReadAccountState(_) => CommandName::ReadAccountState,
+            Command::RegisterValidator(_) => CommandName::RegisterValidator,
+            Command::RemoveValidator(_) => CommandName::RemoveValidator,
+            Command::RotateOperatorKey(_) => CommandName::RotateOperatorKey,
             Command::SetLayout(_) => CommandName::SetLayout,
+            Command::SetValidatorConfig(_) => CommandName::SetValidatorConfig,
             Command::SubmitTransaction(_) => CommandName::SubmitTransaction,
             Command::ValidatorConfig(_) => CommandName::ValidatorConfig,
             Command::Verify(_) => CommandName::Verify,
+            Command::VerifyAuditLog(_) => CommandName::VerifyAuditLog,
+            Command::VerifyChainConfig(_) => CommandName::VerifyChainConfig,
+            Command::VerifyValidatorSet(_) => CommandName::VerifyValidatorSet,
+            Command::VerifyWaypoint(_) => CommandName::VerifyWaypoint,
         }
     }
 }
@@ -101,15 +145,25 @@ impl std::fmt::Display for CommandName {
         let name = match self {
             CommandName::AssociationKey => "association-key",
             CommandName::CreateWaypoint => "create-waypoint",
+            CommandName::FetchWaypoint => "fetch-waypoint",
+            CommandName::GenerateChainConfig => "generate-chain-config",
             CommandName::Genesis => "genesis",
             CommandName::InsertWaypoint => "insert-waypoint",
             CommandName::OperatorKey => "operator-key",
             CommandName::OwnerKey => "owner-key",
             CommandName::ReadAccountState => "read-account-state",
+            CommandName::RegisterValidator => "register-validator",
+            CommandName::RemoveValidator => "remove-validator",
+            CommandName::RotateOperatorKey => "rotate-operator-key",
             CommandName::SetLayout => "set-layout",
+            CommandName::SetValidatorConfig => "set-validator-config",
             CommandName::SubmitTransaction => "submit-transaction",
             CommandName::ValidatorConfig => "validator-config",
             CommandName::Verify => "verify",
+            CommandName::VerifyAuditLog => "verify-audit-log",
+            CommandName::VerifyChainConfig => "verify-chain-config",
+            CommandName::VerifyValidatorSet => "verify-validator-set",
+            CommandName::VerifyWaypoint => "verify-waypoint",
         };
         write!(f, "{}", name)
     }
@@ -120,12 +174,18 @@ impl Command {
         match &self {
             Command::AssociationKey(_) => self.association_key().unwrap().to_string(),
             Command::CreateWaypoint(_) => self.create_waypoint().unwrap().to_string(),
+            Command::FetchWaypoint(_) => self.fetch_waypoint().unwrap().to_string(),
+            Command::GenerateChainConfig(_) => self.generate_chain_config().unwrap().to_string(),
             Command::Genesis(_) => format!("{:?}", self.genesis().unwrap()),
             Command::InsertWaypoint(_) => self.insert_waypoint().unwrap().to_string(),
             Command::OperatorKey(_) => self.operator_key().unwrap().to_string(),
             Command::OwnerKey(_) => self.owner_key().unwrap().to_string(),
             Command::ReadAccountState(_) => format!("{:?}", self.read_account_state().unwrap()),
+            Command::RegisterValidator(_) => self.register_validator().unwrap().to_string(),
+            Command::RemoveValidator(_) => self.remove_validator().unwrap().to_string(),
+            Command::RotateOperatorKey(_) => self.rotate_operator_key().unwrap().to_string(),
             Command::SetLayout(_) => self.set_layout().unwrap().to_string(),
+            Command::SetValidatorConfig(_) => self.set_validator_config().unwrap().to_string(),
             Command::SubmitTransaction(_) => self
                 .submit_transaction()
                 .map(|_| "success!")
@@ -133,6 +193,12 @@ impl Command {
                 .to_string(),
             Command::ValidatorConfig(_) => format!("{:?}", self.validator_config().unwrap()),
             Command::Verify(_) => self.verify().unwrap(),
+            Command::VerifyAuditLog(_) => self.verify_audit_log().unwrap(),
+            Command::VerifyChainConfig(_) => self.verify_chain_config().unwrap(),
+            Command::VerifyValidatorSet(_) => self.verify_validator_set().unwrap(),
+            Command::VerifyWaypoint(_) => {
+                serde_json::to_string_pretty(&self.verify_waypoint().unwrap()).unwrap()
+            }
         }
     }
 
@@ -150,6 +216,20 @@ impl Command {
         }
     }
 
+    pub fn fetch_waypoint(self) -> Result<Waypoint, Error> {
+        match self {
+            Command::FetchWaypoint(fetch_waypoint) => fetch_waypoint.execute(),
+            _ => Err(self.unexpected_command(CommandName::FetchWaypoint)),
+        }
+    }
+
+    pub fn generate_chain_config(self) -> Result<Waypoint, Error> {
+        match self {
+            Command::GenerateChainConfig(generate_chain_config) => generate_chain_config.execute(),
+            _ => Err(self.unexpected_command(CommandName::GenerateChainConfig)),
+        }
+    }
+
     pub fn genesis(self) -> Result<Transaction, Error> {
         match self {
             Command::Genesis(genesis) => genesis.execute(),
@@ -185,6 +265,27 @@ impl Command {
         }
     }
 
+    pub fn register_validator(self) -> Result<HashValue, Error> {
+        match self {
+            Command::RegisterValidator(register_validator) => register_validator.execute(),
+            _ => Err(self.unexpected_command(CommandName::RegisterValidator)),
+        }
+    }
+
+    pub fn remove_validator(self) -> Result<HashValue, Error> {
+        match self {
+            Command::RemoveValidator(remove_validator) => remove_validator.execute(),
+            _ => Err(self.unexpected_command(CommandName::RemoveValidator)),
+        }
+    }
+
+    pub fn rotate_operator_key(self) -> Result<Ed25519PublicKey, Error> {
+        match self {
+            Command::RotateOperatorKey(rotate_operator_key) => rotate_operator_key.execute(),
+            _ => Err(self.unexpected_command(CommandName::RotateOperatorKey)),
+        }
+    }
+
     pub fn set_layout(self) -> Result<crate::layout::Layout, Error> {
         match self {
             Command::SetLayout(set_layout) => set_layout.execute(),
@@ -192,6 +293,13 @@ impl Command {
         }
     }
 
+    pub fn set_validator_config(self) -> Result<HashValue, Error> {
+        match self {
+            Command::SetValidatorConfig(set_validator_config) => set_validator_config.execute(),
+            _ => Err(self.unexpected_command(CommandName::SetValidatorConfig)),
+        }
+    }
+
     pub fn submit_transaction(self) -> Result<(), Error> {
         match self {
             Command::SubmitTransaction(submit_transaction) => submit_transaction.execute(),
@@ -213,6 +321,34 @@ impl Command {
         }
     }
 
+    pub fn verify_audit_log(self) -> Result<String, Error> {
+        match self {
+            Command::VerifyAuditLog(verify_audit_log) => verify_audit_log.execute(),
+            _ => Err(self.unexpected_command(CommandName::VerifyAuditLog)),
+        }
+    }
+
+    pub fn verify_chain_config(self) -> Result<String, Error> {
+        match self {
+            Command::VerifyChainConfig(verify_chain_config) => verify_chain_config.execute(),
+            _ => Err(self.unexpected_command(CommandName::VerifyChainConfig)),
+        }
+    }
+
+    pub fn verify_validator_set(self) -> Result<String, Error> {
+        match self {
+            Command::VerifyValidatorSet(verify_validator_set) => verify_validator_set.execute(),
+            _ => Err(self.unexpected_command(CommandName::VerifyValidatorSet)),
+        }
+    }
+
+    pub fn verify_waypoint(self) -> Result<crate::waypoint::WaypointProvenance, Error> {
+        match self {
+            Command::VerifyWaypoint(verify_waypoint) => verify_waypoint.execute(),
+            _ => Err(self.unexpected_command(CommandName::VerifyWaypoint)),
+        }
+    }
+
     fn unexpected_command(self, expected: CommandName) -> Error {
         Error::UnexpectedCommand(expected, CommandName::from(&self))
     }
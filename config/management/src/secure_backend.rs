@@ -136,6 +136,7 @@ impl TryInto<config::SecureBackend> for SecureBackend {
                     .ok_or_else(|| Error::BackendParsingError("missing token".into()))?;
                 config::SecureBackend::Vault(VaultConfig {
                     namespace: self.parameters.remove("namespace"),
+                    enterprise_namespace: self.parameters.remove("enterprise_namespace"),
                     server,
                     ca_certificate: certificate,
                     token: Token::FromDisk(PathBuf::from(token)),
@@ -158,7 +159,7 @@ impl TryInto<Storage> for SecureBackend {
 
     fn try_into(self) -> Result<Storage, Error> {
         let config: config::SecureBackend = self.try_into()?;
-        Ok((&config).into())
+        Storage::try_from(&config).map_err(|e| Error::UnexpectedError(e.to_string()))
     }
 }
 
@@ -232,6 +233,12 @@ mod tests {
         );
         storage(&vault).unwrap();
 
+        let vault = format!(
+            "backend=vault;server=http://127.0.0.1:8080;token={};enterprise_namespace=libra",
+            path_str
+        );
+        storage(&vault).unwrap();
+
         let vault = "backend=vault";
         assert!(storage(vault).is_err());
     }
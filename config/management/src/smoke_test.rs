@@ -148,6 +148,7 @@ fn smoke_test() {
         dir: LibraSwarmDir::Temporary(temppath),
         nodes: std::collections::HashMap::new(),
         config: SwarmConfig::build(&management_builder, &swarm_path).unwrap(),
+        proxies: vec![],
     };
 
     // Step 8) Launch validators
@@ -167,6 +168,7 @@ fn smoke_test() {
         dir: LibraSwarmDir::Temporary(temppath),
         nodes: std::collections::HashMap::new(),
         config: SwarmConfig::build(&management_builder, &swarm_path).unwrap(),
+        proxies: vec![],
     };
 
     swarm.launch_attempt(RoleType::FullNode, false).unwrap();
@@ -17,7 +17,8 @@ use libra_secure_storage::{CryptoStorage, KVStorage, Storage, Value};
 use libra_secure_time::{RealTimeService, TimeService};
 use libra_types::{
     account_address::{self, AccountAddress},
-    transaction::{RawTransaction, SignedTransaction, Transaction},
+    transaction::{RawTransaction, Script, SignedTransaction, Transaction},
+    validator_config::ValidatorConfig as OnChainValidatorConfig,
 };
 use std::{convert::TryFrom, time::Duration};
 use structopt::StructOpt;
@@ -38,65 +39,28 @@ pub struct ValidatorConfig {
 impl ValidatorConfig {
     pub fn execute(self) -> Result<Transaction, Error> {
         let mut local_storage = self.backends.local.create_storage(LocalStorage)?;
-
-        // Step 1) Retrieve keys from local storage
-        let consensus_key = ed25519_from_storage(CONSENSUS_KEY, &local_storage)?;
-        let fullnode_network_key = x25519_from_storage(FULLNODE_NETWORK_KEY, &local_storage)?;
-        let validator_network_key = x25519_from_storage(VALIDATOR_NETWORK_KEY, &local_storage)?;
         let operator_key = ed25519_from_storage(OPERATOR_KEY, &local_storage)?;
 
-        // append ln-noise-ik and ln-handshake protocols to base network addresses
-
-        let validator_address = self
-            .validator_address
-            .clone()
-            .append_prod_protos(validator_network_key, HANDSHAKE_VERSION);
-        let raw_validator_address = RawNetworkAddress::try_from(&validator_address)
-            .map_err(|e| Error::UnexpectedError(format!("(raw_validator_address) {}", e)))?;
-
-        let fullnode_address = self
-            .fullnode_address
-            .clone()
-            .append_prod_protos(fullnode_network_key, HANDSHAKE_VERSION);
-        let raw_fullnode_address = RawNetworkAddress::try_from(&fullnode_address)
-            .map_err(|e| Error::UnexpectedError(format!("(raw_fullnode_address) {}", e)))?;
-
-        // Step 2) Generate transaction
-
         // TODO(davidiw): This is currently not supported
         // let sender = self.owner_address;
         let sender = account_address::from_public_key(&operator_key);
 
-        // TODO(philiphayes): remove network identity pubkey field from struct when
-        // transition complete
-        let script = transaction_builder::encode_set_validator_config_script(
-            sender,
-            consensus_key.to_bytes().to_vec(),
-            validator_network_key.to_bytes(),
-            raw_validator_address.into(),
-            fullnode_network_key.to_bytes(),
-            raw_fullnode_address.into(),
-        );
-
-        // TODO(davidiw): In genesis this is irrelevant -- afterward we need to obtain the
-        // current sequence number by querying the blockchain.
+        // In genesis the sequence number is irrelevant -- afterward set-validator-config obtains
+        // the current sequence number by querying the blockchain.
         let sequence_number = 0;
-        let expiration_time = RealTimeService::new().now() + constants::TXN_EXPIRATION_SECS;
-        let raw_transaction = RawTransaction::new_script(
+        let config = build_validator_config(
+            &local_storage,
+            self.validator_address,
+            self.fullnode_address,
+        )?;
+        let signed_txn = build_validator_config_transaction(
+            &mut local_storage,
+            sender,
             sender,
             sequence_number,
-            script,
-            constants::MAX_GAS_AMOUNT,
-            constants::GAS_UNIT_PRICE,
-            constants::GAS_CURRENCY_CODE.to_owned(),
-            Duration::from_secs(expiration_time),
-        );
-        let signature = local_storage
-            .sign_message(OPERATOR_KEY, &raw_transaction.hash())
-            .map_err(|e| {
-                Error::LocalStorageSigningError("validator-config", OPERATOR_KEY, e.to_string())
-            })?;
-        let signed_txn = SignedTransaction::new(raw_transaction, operator_key, signature);
+            &config,
+            "validator-config",
+        )?;
         let txn = Transaction::UserTransaction(signed_txn);
 
         // Step 3) Submit to remote storage
@@ -115,7 +79,98 @@ impl ValidatorConfig {
     }
 }
 
-fn ed25519_from_storage(
+/// Builds the config of the validator from the keys in local storage and the given addresses
+pub(crate) fn build_validator_config(
+    local_storage: &Storage,
+    validator_address: NetworkAddress,
+    fullnode_address: NetworkAddress,
+) -> Result<OnChainValidatorConfig, Error> {
+    // Step 1) Retrieve keys from local storage
+    let consensus_key = ed25519_from_storage(CONSENSUS_KEY, local_storage)?;
+    let fullnode_network_key = x25519_from_storage(FULLNODE_NETWORK_KEY, local_storage)?;
+    let validator_network_key = x25519_from_storage(VALIDATOR_NETWORK_KEY, local_storage)?;
+
+    // append ln-noise-ik and ln-handshake protocols to base network addresses
+
+    let validator_address =
+        validator_address.append_prod_protos(validator_network_key, HANDSHAKE_VERSION);
+    let raw_validator_address = RawNetworkAddress::try_from(&validator_address)
+        .map_err(|e| Error::UnexpectedError(format!("(raw_validator_address) {}", e)))?;
+
+    let fullnode_address =
+        fullnode_address.append_prod_protos(fullnode_network_key, HANDSHAKE_VERSION);
+    let raw_fullnode_address = RawNetworkAddress::try_from(&fullnode_address)
+        .map_err(|e| Error::UnexpectedError(format!("(raw_fullnode_address) {}", e)))?;
+
+    Ok(OnChainValidatorConfig::new(
+        consensus_key,
+        validator_network_key,
+        raw_validator_address,
+        fullnode_network_key,
+        raw_fullnode_address,
+    ))
+}
+
+/// Builds the transaction that sets the config of the validator, signed by the operator key
+pub(crate) fn build_validator_config_transaction(
+    local_storage: &mut Storage,
+    validator_account: AccountAddress,
+    sender: AccountAddress,
+    sequence_number: u64,
+    config: &OnChainValidatorConfig,
+    command: &'static str,
+) -> Result<SignedTransaction, Error> {
+    // Step 2) Generate transaction
+
+    // TODO(philiphayes): remove network identity pubkey field from struct when
+    // transition complete
+    let script = transaction_builder::encode_set_validator_config_script(
+        validator_account,
+        config.consensus_public_key.to_bytes().to_vec(),
+        config.validator_network_identity_public_key.to_bytes(),
+        config.validator_network_address.clone().into(),
+        config.full_node_network_identity_public_key.to_bytes(),
+        config.full_node_network_address.clone().into(),
+    );
+    let raw_transaction = build_raw_transaction(sender, sequence_number, script);
+    sign_transaction(local_storage, OPERATOR_KEY, raw_transaction, command)
+}
+
+pub(crate) fn build_raw_transaction(
+    sender: AccountAddress,
+    sequence_number: u64,
+    script: Script,
+) -> RawTransaction {
+    let expiration_time = RealTimeService::new().now() + constants::TXN_EXPIRATION_SECS;
+    RawTransaction::new_script(
+        sender,
+        sequence_number,
+        script,
+        constants::MAX_GAS_AMOUNT,
+        constants::GAS_UNIT_PRICE,
+        constants::GAS_CURRENCY_CODE.to_owned(),
+        Duration::from_secs(expiration_time),
+    )
+}
+
+pub(crate) fn sign_transaction(
+    local_storage: &mut Storage,
+    key_name: &'static str,
+    raw_transaction: RawTransaction,
+    command: &'static str,
+) -> Result<SignedTransaction, Error> {
+    let public_key = ed25519_from_storage(key_name, local_storage)?;
+    let signature = local_storage
+        .sign_message(key_name, &raw_transaction.hash())
+        .map_err(|e| Error::LocalStorageSigningError(command, key_name, e.to_string()))?;
+    Ok(SignedTransaction::new(
+        raw_transaction,
+        public_key,
+        signature,
+    ))
+}
+
+pub(crate) fn ed25519_from_storage(
     key_name: &'static str,
     storage: &Storage,
 ) -> Result<Ed25519PublicKey, Error> {
@@ -0,0 +1,328 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands that manage the validator set of a running blockchain. Each command builds its
+//! transaction, signs it with a key in secure storage, submits it to a full node and waits for its
+//! effect to be visible on-chain. All state read from the full node is verified against a waypoint.
+
+use crate::{
+    error::Error,
+    json_rpc::VerifyingClient,
+    secure_backend::StorageLocation::LocalStorage,
+    validator_config::{
+        build_raw_transaction, build_validator_config, build_validator_config_transaction,
+        ed25519_from_storage, sign_transaction,
+    },
+    SingleBackend,
+};
+use libra_crypto::{ed25519::Ed25519PublicKey, hash::CryptoHash, HashValue};
+use libra_global_constants::{ASSOCIATION_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY};
+use libra_network_address::NetworkAddress;
+use libra_secure_storage::{CryptoStorage, KVStorage, Storage, Value};
+use libra_types::{
+    account_address::{self, AccountAddress},
+    account_config,
+    on_chain_config::ValidatorSet,
+    transaction::{authenticator::AuthenticationKey, Script, SignedTransaction},
+    validator_config::ValidatorConfig,
+    validator_info::ValidatorInfo,
+    waypoint::Waypoint,
+};
+use std::str::FromStr;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub struct ChainConfig {
+    /// JSON-RPC endpoint of a full node
+    #[structopt(long)]
+    host: String,
+    /// Waypoint against which all state read from the full node is verified
+    #[structopt(long)]
+    waypoint: Waypoint,
+    /// The secure backend holding the key signing the transaction
+    #[structopt(flatten)]
+    backend: SingleBackend,
+}
+
+impl ChainConfig {
+    fn connect(self) -> Result<(Storage, VerifyingClient), Error> {
+        let storage = self.backend.backend.create_storage(LocalStorage)?;
+        Ok((storage, VerifyingClient::new(self.host, self.waypoint)))
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct RegisterValidator {
+    /// Account of the validator, which must already have a validator config
+    #[structopt(long)]
+    validator_account: AccountAddress,
+    #[structopt(flatten)]
+    chain: ChainConfig,
+}
+
+impl RegisterValidator {
+    pub fn execute(self) -> Result<HashValue, Error> {
+        let validator_account = self.validator_account;
+        let script = transaction_builder::encode_add_validator_script(validator_account);
+        submit_validator_set_change(self.chain, script, "register-validator", validator_account)
+            .and_then(|(hash, in_validator_set)| {
+                if in_validator_set {
+                    Ok(hash)
+                } else {
+                    Err(Error::TransactionNotConfirmed(
+                        hash.to_hex(),
+                        "validator is not in the validator set".into(),
+                    ))
+                }
+            })
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct RemoveValidator {
+    #[structopt(long)]
+    validator_account: AccountAddress,
+    #[structopt(flatten)]
+    chain: ChainConfig,
+}
+
+impl RemoveValidator {
+    pub fn execute(self) -> Result<HashValue, Error> {
+        let validator_account = self.validator_account;
+        let script = transaction_builder::encode_remove_validator_script(validator_account);
+        submit_validator_set_change(self.chain, script, "remove-validator", validator_account)
+            .and_then(|(hash, in_validator_set)| {
+                if in_validator_set {
+                    Err(Error::TransactionNotConfirmed(
+                        hash.to_hex(),
+                        "validator is still in the validator set".into(),
+                    ))
+                } else {
+                    Ok(hash)
+                }
+            })
+    }
+}
+
+/// Submits a change of the validator set signed by the association and returns whether the
+/// validator is in the validator set once the change is committed.
+fn submit_validator_set_change(
+    chain: ChainConfig,
+    script: Script,
+    command: &'static str,
+    validator_account: AccountAddress,
+) -> Result<(HashValue, bool), Error> {
+    let (mut storage, mut client) = chain.connect()?;
+    let sender = account_config::association_address();
+    let sequence_number = client.sequence_number(sender)?;
+    let txn = sign_transaction(
+        &mut storage,
+        ASSOCIATION_KEY,
+        build_raw_transaction(sender, sequence_number, script),
+        command,
+    )?;
+    let hash = client.submit_and_confirm(txn)?;
+
+    let validator_set = client
+        .account_state(account_config::validator_set_address())?
+        .get_validator_set()
+        .map_err(|e| Error::JsonRpcReadError("validator set", e.to_string()))?
+        .ok_or_else(|| Error::JsonRpcReadError("validator set", "not found".into()))?;
+    let in_validator_set = validator_set
+        .payload()
+        .iter()
+        .any(|info| info.account_address() == &validator_account);
+    Ok((hash, in_validator_set))
+}
+
+#[derive(Debug, StructOpt)]
+pub struct RotateOperatorKey {
+    #[structopt(flatten)]
+    chain: ChainConfig,
+}
+
+impl RotateOperatorKey {
+    /// Rotates the operator key in secure storage and the authentication key of the operator
+    /// account to match it. The rotation in storage is rolled back if the transaction cannot be
+    /// submitted.
+    pub fn execute(self) -> Result<Ed25519PublicKey, Error> {
+        let (mut storage, mut client) = self.chain.connect()?;
+        let current_key = ed25519_from_storage(OPERATOR_KEY, &storage)?;
+        // Once rotated, the operator account can no longer be derived from the operator key
+        let operator_account = operator_account(&mut storage, &current_key)?;
+        let sequence_number = client.sequence_number(operator_account)?;
+
+        let new_key = storage
+            .rotate_key(OPERATOR_KEY)
+            .map_err(|e| Error::LocalStorageWriteError(OPERATOR_KEY, e.to_string()))?;
+        let auth_key = AuthenticationKey::ed25519(&new_key);
+        let script =
+            transaction_builder::encode_rotate_authentication_key_script(auth_key.to_vec());
+        let raw_transaction = build_raw_transaction(operator_account, sequence_number, script);
+        let signature = storage
+            .sign_message_using_version(OPERATOR_KEY, current_key.clone(), &raw_transaction.hash())
+            .map_err(|e| {
+                Error::LocalStorageSigningError("rotate-operator-key", OPERATOR_KEY, e.to_string())
+            })?;
+        let txn = SignedTransaction::new(raw_transaction, current_key, signature);
+
+        let hash = match client.submit_and_confirm(txn) {
+            Err(e @ Error::JsonRpcWriteError(..)) => {
+                storage
+                    .rollback_key(OPERATOR_KEY)
+                    .map_err(|e| Error::LocalStorageWriteError(OPERATOR_KEY, e.to_string()))?;
+                return Err(e);
+            }
+            result => result?,
+        };
+
+        let account_resource = client
+            .account_state(operator_account)?
+            .get_account_resource()
+            .map_err(|e| Error::JsonRpcReadError("account resource", e.to_string()))?
+            .ok_or_else(|| Error::JsonRpcReadError("account resource", "not found".into()))?;
+        if account_resource.authentication_key() != auth_key.to_vec().as_slice() {
+            return Err(Error::TransactionNotConfirmed(
+                hash.to_hex(),
+                "authentication key was not rotated".into(),
+            ));
+        }
+        Ok(new_key)
+    }
+}
+
+/// Returns the operator account recorded in storage, recording the one derived from the operator
+/// key if none is.
+fn operator_account(
+    storage: &mut Storage,
+    operator_key: &Ed25519PublicKey,
+) -> Result<AccountAddress, Error> {
+    if let Ok(response) = storage.get(OPERATOR_ACCOUNT) {
+        let account = response
+            .value
+            .string()
+            .map_err(|e| Error::LocalStorageReadError(OPERATOR_ACCOUNT, e.to_string()))?;
+        return AccountAddress::from_str(&account)
+            .map_err(|e| Error::LocalStorageReadError(OPERATOR_ACCOUNT, e.to_string()));
+    }
+
+    let account = account_address::from_public_key(operator_key);
+    storage
+        .set(OPERATOR_ACCOUNT, Value::String(account.to_string()))
+        .map_err(|e| Error::LocalStorageWriteError(OPERATOR_ACCOUNT, e.to_string()))?;
+    Ok(account)
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SetValidatorConfig {
+    #[structopt(long)]
+    validator_account: AccountAddress,
+    #[structopt(long)]
+    validator_address: NetworkAddress,
+    #[structopt(long)]
+    fullnode_address: NetworkAddress,
+    #[structopt(flatten)]
+    chain: ChainConfig,
+}
+
+impl SetValidatorConfig {
+    /// Updates the network addresses of the validator, along with the keys in storage
+    pub fn execute(self) -> Result<HashValue, Error> {
+        let (mut storage, mut client) = self.chain.connect()?;
+        let operator_key = ed25519_from_storage(OPERATOR_KEY, &storage)?;
+        let operator_account = operator_account(&mut storage, &operator_key)?;
+        let sequence_number = client.sequence_number(operator_account)?;
+
+        let config =
+            build_validator_config(&storage, self.validator_address, self.fullnode_address)?;
+        let txn = build_validator_config_transaction(
+            &mut storage,
+            self.validator_account,
+            operator_account,
+            sequence_number,
+            &config,
+            "set-validator-config",
+        )?;
+        let hash = client.submit_and_confirm(txn)?;
+
+        let on_chain_config = client
+            .account_state(self.validator_account)?
+            .get_validator_config_resource()
+            .map_err(|e| Error::JsonRpcReadError("validator config", e.to_string()))?
+            .and_then(|resource| resource.validator_config);
+        if !on_chain_config.map_or(false, |on_chain| same_config(&on_chain, &config)) {
+            return Err(Error::TransactionNotConfirmed(
+                hash.to_hex(),
+                "validator config was not updated".into(),
+            ));
+        }
+        Ok(hash)
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyValidatorSet {
+    /// JSON-RPC endpoint of a full node
+    #[structopt(long)]
+    host: String,
+    /// Waypoint against which all state read from the full node is verified
+    #[structopt(long)]
+    waypoint: Waypoint,
+    /// Verifies the set with this validator added, as register-validator would
+    #[structopt(long)]
+    add_validator: Option<AccountAddress>,
+    /// Verifies the set with this validator removed, as remove-validator would
+    #[structopt(long)]
+    remove_validator: Option<AccountAddress>,
+    /// Also verifies that this validator is in the set
+    #[structopt(long)]
+    validator_account: Option<AccountAddress>,
+}
+
+impl VerifyValidatorSet {
+    /// Runs the checks consensus performs before entering an epoch on the current validator set,
+    /// or on the set proposed by adding or removing a validator.
+    pub fn execute(self) -> Result<String, Error> {
+        let mut client = VerifyingClient::new(self.host, self.waypoint);
+        let validator_set = client
+            .account_state(account_config::validator_set_address())?
+            .get_validator_set()
+            .map_err(|e| Error::JsonRpcReadError("validator set", e.to_string()))?
+            .ok_or_else(|| Error::JsonRpcReadError("validator set", "not found".into()))?;
+
+        let mut payload = validator_set.payload().to_vec();
+        if let Some(account) = self.remove_validator {
+            payload.retain(|info| info.account_address() != &account);
+        }
+        if let Some(account) = self.add_validator {
+            let config = client
+                .account_state(account)?
+                .get_validator_config_resource()
+                .map_err(|e| Error::JsonRpcReadError("validator config", e.to_string()))?
+                .and_then(|resource| resource.validator_config)
+                .ok_or_else(|| Error::JsonRpcReadError("validator config", "not found".into()))?;
+            // Validators are added with a voting power of 1 by LibraSystem::add_validator
+            payload.push(ValidatorInfo::new(account, 1, config));
+        }
+        let proposed_set = ValidatorSet::new(payload);
+
+        proposed_set
+            .verify()
+            .map_err(|e| Error::InvalidValidatorSet(e.to_string()))?;
+        if let Some(account) = self.validator_account {
+            proposed_set
+                .verify_includes(account)
+                .map_err(|e| Error::InvalidValidatorSet(e.to_string()))?;
+        }
+        Ok(format!(
+            "Validator set of {} validators is valid",
+            proposed_set.payload().len()
+        ))
+    }
+}
+
+fn same_config(on_chain: &ValidatorConfig, expected: &ValidatorConfig) -> bool {
+    on_chain.consensus_public_key == expected.consensus_public_key
+        && on_chain.validator_network_address == expected.validator_network_address
+        && on_chain.full_node_network_address == expected.full_node_network_address
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    constants,
     error::Error,
+    json_rpc::VerifyingClient,
     secure_backend::{
         StorageLocation,
         StorageLocation::{LocalStorage, RemoteStorage},
@@ -10,13 +12,19 @@ use crate::{
     SecureBackends, SingleBackend,
 };
 use executor::db_bootstrapper;
-use libra_global_constants::WAYPOINT;
-use libra_secure_storage::{KVStorage, Storage, Value};
+use libra_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    HashValue, Signature,
+};
+use libra_global_constants::{OPERATOR_KEY, WAYPOINT};
+use libra_secure_storage::{CryptoStorage, KVStorage, Storage, Value};
+use libra_secure_time::{RealTimeService, TimeService};
 use libra_temppath::TempPath;
-use libra_types::waypoint::Waypoint;
+use libra_types::{transaction::Transaction, waypoint::Waypoint};
 use libra_vm::LibraVM;
 use libradb::LibraDB;
-use std::{convert::TryInto, str::FromStr};
+use serde::{Deserialize, Serialize};
+use std::{convert::TryInto, fs, path::PathBuf, str::FromStr};
 use storage_interface::DbReaderWriter;
 use structopt::StructOpt;
 
@@ -26,26 +34,26 @@ use structopt::StructOpt;
 pub struct CreateWaypoint {
     #[structopt(flatten)]
     secure_backends: SecureBackends,
+    /// Computes the waypoint from this genesis blob rather than building genesis from the layout
+    /// in the local backend
+    #[structopt(long)]
+    genesis_path: Option<PathBuf>,
 }
 
 impl CreateWaypoint {
     pub fn execute(self) -> Result<Waypoint, Error> {
-        let backend = self.secure_backends.local;
-        let genesis_helper = crate::genesis::Genesis {
-            backend: SingleBackend { backend },
-            path: None,
+        let genesis = if let Some(genesis_path) = &self.genesis_path {
+            read_genesis(genesis_path)?
+        } else {
+            let backend = self.secure_backends.local;
+            let genesis_helper = crate::genesis::Genesis {
+                backend: SingleBackend { backend },
+                path: None,
+            };
+            genesis_helper.execute()?
         };
 
-        let genesis = genesis_helper.execute()?;
-
-        let path = TempPath::new();
-        let libradb =
-            LibraDB::open(&path, false, None).map_err(|e| Error::UnexpectedError(e.to_string()))?;
-        let db_rw = DbReaderWriter::new(libradb);
-
-        let waypoint = db_bootstrapper::bootstrap_db_if_empty::<LibraVM>(&db_rw, &genesis)
-            .map_err(|e| Error::UnexpectedError(e.to_string()))?
-            .ok_or_else(|| Error::UnexpectedError("Unable to generate a waypoint".to_string()))?;
+        let waypoint = genesis_waypoint(&genesis)?;
 
         if let Some(remote) = self.secure_backends.remote {
             let mut remote_storage: Storage = remote.try_into()?;
@@ -59,6 +67,25 @@ impl CreateWaypoint {
     }
 }
 
+/// Computes the waypoint of a genesis transaction by executing it in a temporary DB.
+pub(crate) fn genesis_waypoint(genesis: &Transaction) -> Result<Waypoint, Error> {
+    let path = TempPath::new();
+    let libradb = LibraDB::open(&path, false, None, None, None)
+        .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+    let db_rw = DbReaderWriter::new(libradb);
+
+    db_bootstrapper::bootstrap_db_if_empty::<LibraVM>(&db_rw, genesis)
+        .map_err(|e| Error::UnexpectedError(e.to_string()))?
+        .ok_or_else(|| Error::UnexpectedError("Unable to generate a waypoint".to_string()))
+}
+
+pub(crate) fn read_genesis(path: &PathBuf) -> Result<Transaction, Error> {
+    let path_string = path.to_str().unwrap().to_string();
+    let data =
+        fs::read(path).map_err(|e| Error::UnableToReadFile(path_string.clone(), e.to_string()))?;
+    lcs::from_bytes(&data).map_err(|e| Error::UnableToParseFile(path_string, e.to_string()))
+}
+
 #[derive(Debug, StructOpt)]
 pub struct InsertWaypoint {
     #[structopt(flatten)]
@@ -119,3 +146,222 @@ impl InsertWaypoint {
         Ok(())
     }
 }
+
+/// Verifies a waypoint against the ledger of a running node, i.e., that the node's ledger can be
+/// proven from the waypoint, and optionally inserts it into the local storage. The verification is
+/// recorded in a provenance record signed by the operator key and kept in the local storage.
+#[derive(Debug, StructOpt)]
+pub struct VerifyWaypoint {
+    /// JSON-RPC endpoint of a running node
+    #[structopt(long)]
+    host: String,
+    /// The waypoint to verify, read from the local backend if not provided
+    #[structopt(long)]
+    waypoint: Option<Waypoint>,
+    /// Inserts the waypoint into the local backend once verified
+    #[structopt(long)]
+    insert: bool,
+    /// If specified, the provenance record is also written to this file
+    #[structopt(long)]
+    provenance_path: Option<PathBuf>,
+    /// The local backend, holding the operator key
+    #[structopt(flatten)]
+    backend: SingleBackend,
+}
+
+impl VerifyWaypoint {
+    pub fn execute(self) -> Result<WaypointProvenance, Error> {
+        let mut local_storage = self.backend.backend.create_storage(LocalStorage)?;
+        let waypoint = match self.waypoint {
+            Some(waypoint) => waypoint,
+            None => {
+                let waypoint = local_storage
+                    .get(WAYPOINT)
+                    .and_then(|v| v.value.string())
+                    .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))?;
+                Waypoint::from_str(&waypoint)
+                    .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))?
+            }
+        };
+
+        let ledger_info = VerifyingClient::new(self.host.clone(), waypoint).latest_ledger_info()?;
+        let provenance = WaypointProvenance::sign(
+            &mut local_storage,
+            waypoint,
+            self.host,
+            ledger_info.epoch(),
+            ledger_info.version(),
+            RealTimeService::new().now(),
+        )?;
+
+        if self.insert {
+            InsertWaypoint::insert_waypoint_to_backend(
+                &waypoint,
+                &mut local_storage,
+                LocalStorage,
+            )?;
+        }
+        let record = serde_json::to_string_pretty(&provenance)
+            .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+        local_storage
+            .set(
+                constants::WAYPOINT_PROVENANCE,
+                Value::String(record.clone()),
+            )
+            .map_err(|e| {
+                Error::LocalStorageWriteError(constants::WAYPOINT_PROVENANCE, e.to_string())
+            })?;
+        if let Some(path) = &self.provenance_path {
+            fs::write(path, record).map_err(|e| {
+                Error::UnableToWriteFile(path.to_str().unwrap().to_string(), e.to_string())
+            })?;
+        }
+        Ok(provenance)
+    }
+}
+
+/// Fetches the latest waypoint emitted by a running node, proves it from a trusted waypoint and
+/// optionally inserts it into the local storage, so that a new node bootstraps from a recent
+/// waypoint instead of genesis.
+#[derive(Debug, StructOpt)]
+pub struct FetchWaypoint {
+    /// JSON-RPC endpoint of a running node
+    #[structopt(long)]
+    host: String,
+    /// The trusted waypoint, read from the local backend if not provided
+    #[structopt(long)]
+    waypoint: Option<Waypoint>,
+    /// Inserts the fetched waypoint into the local backend once verified
+    #[structopt(long)]
+    insert: bool,
+    #[structopt(flatten)]
+    backend: SingleBackend,
+}
+
+impl FetchWaypoint {
+    pub fn execute(self) -> Result<Waypoint, Error> {
+        let mut local_storage = self.backend.backend.create_storage(LocalStorage)?;
+        let trusted = match self.waypoint {
+            Some(waypoint) => waypoint,
+            None => {
+                let waypoint = local_storage
+                    .get(WAYPOINT)
+                    .and_then(|v| v.value.string())
+                    .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))?;
+                Waypoint::from_str(&waypoint)
+                    .map_err(|e| Error::LocalStorageReadError(WAYPOINT, e.to_string()))?
+            }
+        };
+
+        let waypoint = VerifyingClient::new(self.host, trusted)
+            .latest_waypoint()?
+            .ok_or_else(|| Error::JsonRpcReadError("latest waypoint", "not found".into()))?;
+        if self.insert {
+            InsertWaypoint::insert_waypoint_to_backend(
+                &waypoint,
+                &mut local_storage,
+                LocalStorage,
+            )?;
+        }
+        Ok(waypoint)
+    }
+}
+
+/// Records that a waypoint was verified against the ledger of a node, signed by the operator that
+/// verified it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WaypointProvenance {
+    pub waypoint: Waypoint,
+    /// JSON-RPC endpoint of the node the waypoint was verified against
+    pub host: String,
+    /// Epoch and version of the latest ledger info proven from the waypoint
+    pub epoch: u64,
+    pub version: u64,
+    /// When the waypoint was verified, in seconds since the Unix epoch
+    pub verified_at_secs: u64,
+    pub signer: Ed25519PublicKey,
+    pub signature: Ed25519Signature,
+}
+
+impl WaypointProvenance {
+    fn sign(
+        storage: &mut Storage,
+        waypoint: Waypoint,
+        host: String,
+        epoch: u64,
+        version: u64,
+        verified_at_secs: u64,
+    ) -> Result<Self, Error> {
+        let hash = Self::signed_hash(&waypoint, &host, epoch, version, verified_at_secs)?;
+        let signer = storage
+            .get_public_key(OPERATOR_KEY)
+            .map_err(|e| Error::LocalStorageReadError(OPERATOR_KEY, e.to_string()))?
+            .public_key;
+        let signature = storage.sign_message(OPERATOR_KEY, &hash).map_err(|e| {
+            Error::LocalStorageSigningError("verify-waypoint", OPERATOR_KEY, e.to_string())
+        })?;
+        Ok(Self {
+            waypoint,
+            host,
+            epoch,
+            version,
+            verified_at_secs,
+            signer,
+            signature,
+        })
+    }
+
+    /// Checks that the record was signed by its signer
+    pub fn verify(&self) -> Result<(), Error> {
+        let hash = Self::signed_hash(
+            &self.waypoint,
+            &self.host,
+            self.epoch,
+            self.version,
+            self.verified_at_secs,
+        )?;
+        self.signature
+            .verify(&hash, &self.signer)
+            .map_err(|e| Error::UnexpectedError(format!("Invalid provenance record: {}", e)))
+    }
+
+    fn signed_hash(
+        waypoint: &Waypoint,
+        host: &str,
+        epoch: u64,
+        version: u64,
+        verified_at_secs: u64,
+    ) -> Result<HashValue, Error> {
+        let bytes = lcs::to_bytes(&(waypoint, host, epoch, version, verified_at_secs))
+            .map_err(|e| Error::UnexpectedError(e.to_string()))?;
+        Ok(HashValue::sha3_256_of(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_secure_storage::InMemoryStorage;
+
+    #[test]
+    fn test_waypoint_provenance() {
+        let mut storage = Storage::from(InMemoryStorage::new());
+        storage.create_key(OPERATOR_KEY).unwrap();
+        let waypoint = Waypoint::default();
+
+        let provenance = WaypointProvenance::sign(
+            &mut storage,
+            waypoint,
+            "http://localhost:8080".into(),
+            1,
+            10,
+            1_000,
+        )
+        .unwrap();
+        provenance.verify().unwrap();
+
+        let mut tampered = provenance;
+        tampered.version = 11;
+        assert!(tampered.verify().is_err());
+    }
+}
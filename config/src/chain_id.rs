@@ -1,6 +1,8 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct ChainId(pub String);
@@ -15,4 +17,55 @@ impl ChainId {
     pub fn new(chain_id: &str) -> Self {
         ChainId(chain_id.to_string())
     }
+
+    /// The public test network
+    pub fn testnet() -> Self {
+        ChainId::new("testnet")
+    }
+
+    /// The network run by the association members ahead of mainnet
+    pub fn premainnet() -> Self {
+        ChainId::new("premainnet")
+    }
+
+    /// Whether this is one of the well-known chains, as opposed to a custom chain
+    pub fn is_named(&self) -> bool {
+        *self == Self::testnet() || *self == Self::premainnet()
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid chain id {0:?}: must be a non-empty name without whitespace")]
+pub struct ParseChainIdError(String);
+
+impl FromStr for ChainId {
+    type Err = ParseChainIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || s.contains(char::is_whitespace) {
+            Err(ParseChainIdError(s.to_string()))
+        } else {
+            Ok(ChainId::new(s))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_id() {
+        assert_eq!(ChainId::from_str("testnet").unwrap(), ChainId::testnet());
+        assert!(ChainId::from_str("testnet").unwrap().is_named());
+        assert!(!ChainId::from_str("my-chain").unwrap().is_named());
+        assert!(ChainId::from_str("").is_err());
+        assert!(ChainId::from_str("my chain").is_err());
+    }
 }
@@ -11,7 +11,17 @@ pub struct ConsensusConfig {
     pub contiguous_rounds: u32,
     pub max_block_size: u64,
     pub max_pruned_blocks_in_mem: usize,
+    // The number of recently committed transactions excluded from proposals
+    pub max_recently_committed_txns: usize,
+    // The number of rounds below the last committed one whose blocks and quorum certificates are
+    // kept in the consensus db, older ones are pruned on commit
+    pub consensusdb_pruning_window: u64,
     pub round_initial_timeout_ms: u64,
+    // The number of threads signing and verifying consensus messages, off the event loop
+    pub crypto_pool_threads: usize,
+    // The maximum number of messages from other validators waiting for their signatures to be
+    // verified, further messages are dropped
+    pub crypto_pool_queue_size: usize,
     pub proposer_type: ConsensusProposerType,
     pub safety_rules: SafetyRulesConfig,
 }
@@ -22,7 +32,11 @@ impl Default for ConsensusConfig {
             contiguous_rounds: 2,
             max_block_size: 1000,
             max_pruned_blocks_in_mem: 10000,
+            max_recently_committed_txns: 10000,
+            consensusdb_pruning_window: 100,
             round_initial_timeout_ms: 1000,
+            crypto_pool_threads: 4,
+            crypto_pool_queue_size: 1024,
             proposer_type: ConsensusProposerType::LeaderReputation(LeaderReputationConfig {
                 active_weights: 99,
                 inactive_weights: 1,
@@ -3,12 +3,16 @@
 
 use crate::utils;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DebugInterfaceConfig {
     pub admission_control_node_debug_port: u16,
+    /// Port of the admin API, which is only served on localhost. The API is disabled if unset.
+    pub admin_port: Option<u16>,
+    /// File holding the token that admin API requests present as a bearer token
+    pub admin_token_file: Option<PathBuf>,
     pub address: String,
     pub libra_trace: LibraTraceConfig,
     pub metrics_server_port: u16,
@@ -19,6 +23,8 @@ impl Default for DebugInterfaceConfig {
     fn default() -> DebugInterfaceConfig {
         DebugInterfaceConfig {
             admission_control_node_debug_port: 6191,
+            admin_port: None,
+            admin_token_file: None,
             address: "0.0.0.0".to_string(),
             metrics_server_port: 9101,
             public_metrics_server_port: 9102,
@@ -30,6 +36,9 @@ impl Default for DebugInterfaceConfig {
 impl DebugInterfaceConfig {
     pub fn randomize_ports(&mut self) {
         self.admission_control_node_debug_port = utils::get_available_port();
+        if self.admin_port.is_some() {
+            self.admin_port = Some(utils::get_available_port());
+        }
         self.metrics_server_port = utils::get_available_port();
         self.public_metrics_server_port = utils::get_available_port();
     }
@@ -39,6 +48,12 @@ impl DebugInterfaceConfig {
 #[serde(default, deny_unknown_fields)]
 pub struct LibraTraceConfig {
     pub sampling: HashMap<String, String>,
+    /// Bound on the number of recent sampled traces served by the debug interface
+    pub max_traces: usize,
+    /// Bound on the number of traces captured for failed or slow transactions
+    pub max_captured_traces: usize,
+    /// Transactions committed later than this after being seen have their trace captured
+    pub latency_slo_ms: u64,
 }
 
 impl Default for LibraTraceConfig {
@@ -46,6 +61,11 @@ impl Default for LibraTraceConfig {
         let mut map = HashMap::new();
         map.insert(String::from("txn"), String::from("1/100"));
         map.insert(String::from("block"), String::from("1/1"));
-        LibraTraceConfig { sampling: map }
+        LibraTraceConfig {
+            sampling: map,
+            max_traces: 1_000,
+            max_captured_traces: 1_000,
+            latency_slo_ms: 10_000,
+        }
     }
 }
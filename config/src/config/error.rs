@@ -1,10 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::config::{format_validation_errors, ValidationError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
+    #[error("Invalid config:{}", format_validation_errors(.0))]
+    Invalid(Vec<ValidationError>),
     #[error("Invariant violation: {0}")]
     InvariantViolation(String),
     #[error("Error accessing {0}: {1}")]
@@ -15,6 +18,8 @@ pub enum Error {
     Yaml(String, #[source] serde_yaml::Error),
     #[error("Config is missing expected value: {0}")]
     Missing(&'static str),
+    #[error("Error opening secure storage at {0}: {1}")]
+    SecureStorage(String, #[source] libra_secure_storage::Error),
 }
 
 pub fn invariant(cond: bool, msg: String) -> Result<(), Error> {
@@ -8,6 +8,7 @@ use std::path::{Path, PathBuf};
 const DEFAULT_JSON_RPC_ENDPOINT: &str = "https://127.0.0.1:8080";
 
 // Timing related defaults
+const DEFAULT_MAX_RESUBMISSIONS: u64 = 3;
 const DEFAULT_ROTATION_PERIOD_SECS: u64 = 604_800; // 1 week
 const DEFAULT_SLEEP_PERIOD_SECS: u64 = 600; // 10 minutes
 const DEFAULT_TXN_EXPIRATION_SECS: u64 = 3600; // 1 hour
@@ -15,8 +16,14 @@ const DEFAULT_TXN_EXPIRATION_SECS: u64 = 3600; // 1 hour
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct KeyManagerConfig {
+    /// A wallet recovery file, as written by the client, holding the mnemonic that new consensus
+    /// keys are derived from. If unset, secure storage generates new consensus keys.
+    pub consensus_key_wallet_file: Option<PathBuf>,
     pub logger: LoggerConfig,
     pub json_rpc_endpoint: String,
+    /// The number of times an expired rotation transaction is resubmitted before the rotation is
+    /// rolled back in secure storage
+    pub max_resubmissions: u64,
     pub rotation_period_secs: u64,
     pub secure_backend: SecureBackend,
     pub sleep_period_secs: u64,
@@ -26,8 +33,10 @@ pub struct KeyManagerConfig {
 impl Default for KeyManagerConfig {
     fn default() -> KeyManagerConfig {
         KeyManagerConfig {
+            consensus_key_wallet_file: None,
             json_rpc_endpoint: DEFAULT_JSON_RPC_ENDPOINT.into(),
             logger: LoggerConfig::default(),
+            max_resubmissions: DEFAULT_MAX_RESUBMISSIONS,
             rotation_period_secs: DEFAULT_ROTATION_PERIOD_SECS,
             secure_backend: SecureBackend::InMemoryStorage,
             sleep_period_secs: DEFAULT_SLEEP_PERIOD_SECS,
@@ -49,8 +58,6 @@ impl KeyManagerConfig {
     }
 
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
-        if let SecureBackend::OnDiskStorage(backend) = &mut self.secure_backend {
-            backend.set_data_dir(data_dir);
-        }
+        self.secure_backend.set_data_dir(data_dir);
     }
 }
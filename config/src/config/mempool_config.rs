@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use libra_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -10,13 +11,16 @@ pub struct MempoolConfig {
     pub capacity: usize,
     pub capacity_per_user: usize,
     pub max_broadcasts_per_peer: usize,
+    pub shared_mempool_ack_timeout_ms: u64,
     pub shared_mempool_backoff_interval_ms: u64,
     pub shared_mempool_batch_size: usize,
+    pub shared_mempool_max_ack_timeout_ms: u64,
     pub shared_mempool_max_concurrent_inbound_syncs: usize,
     pub shared_mempool_min_broadcast_recipient_count: Option<usize>,
     pub shared_mempool_tick_interval_ms: u64,
     pub system_transaction_timeout_secs: u64,
     pub system_transaction_gc_interval_ms: u64,
+    pub admission_quotas: AdmissionQuotaConfig,
 }
 
 impl Default for MempoolConfig {
@@ -24,8 +28,10 @@ impl Default for MempoolConfig {
         MempoolConfig {
             broadcast_transactions: true,
             shared_mempool_tick_interval_ms: 50,
+            shared_mempool_ack_timeout_ms: 2_000,
             shared_mempool_backoff_interval_ms: 30_000,
             shared_mempool_batch_size: 100,
+            shared_mempool_max_ack_timeout_ms: 60_000,
             shared_mempool_max_concurrent_inbound_syncs: 100,
             shared_mempool_min_broadcast_recipient_count: None,
             max_broadcasts_per_peer: 25,
@@ -33,6 +39,34 @@ impl Default for MempoolConfig {
             capacity_per_user: 100,
             system_transaction_timeout_secs: 86400,
             system_transaction_gc_interval_ms: 180_000,
+            admission_quotas: AdmissionQuotaConfig::default(),
+        }
+    }
+}
+
+/// Quotas on the transactions admitted into mempool, checked when they are validated. They only
+/// apply to the transactions a node admits, not to the ones it executes.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdmissionQuotaConfig {
+    /// The maximum number of transactions of a sender in mempool, unlimited if `None`
+    pub max_in_flight_per_sender: Option<usize>,
+    /// The maximum number of module publishing transactions admitted per window, unlimited if
+    /// `None`
+    pub max_module_publishes_per_window: Option<usize>,
+    /// The length of the window of `max_module_publishes_per_window`, in seconds of block time
+    pub module_publish_window_secs: u64,
+    /// The only senders writeset transactions are admitted from, any sender if `None`
+    pub writeset_senders: Option<Vec<AccountAddress>>,
+}
+
+impl Default for AdmissionQuotaConfig {
+    fn default() -> AdmissionQuotaConfig {
+        AdmissionQuotaConfig {
+            max_in_flight_per_sender: None,
+            max_module_publishes_per_window: None,
+            module_publish_window_secs: 60,
+            writeset_senders: None,
         }
     }
 }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -10,6 +10,8 @@ pub struct MetricsConfig {
     pub collection_interval_ms: u64,
     pub dir: PathBuf,
     pub enabled: bool,
+    /// Pushes the metrics to a collector, for hosts which the scraping infrastructure can't reach
+    pub push: Option<MetricsPushConfig>,
     #[serde(skip)]
     data_dir: PathBuf,
 }
@@ -21,6 +23,7 @@ impl Default for MetricsConfig {
             data_dir: PathBuf::from("/opt/libra/data"),
             enabled: false,
             dir: PathBuf::from("metrics"),
+            push: None,
         }
     }
 }
@@ -38,3 +41,32 @@ impl MetricsConfig {
         self.data_dir = data_dir;
     }
 }
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsPushConfig {
+    pub endpoint: MetricsPushEndpoint,
+    #[serde(default = "MetricsPushConfig::default_interval_ms")]
+    pub interval_ms: u64,
+    /// Labels added to all the pushed metrics, on top of the chain id, role and peer id of the
+    /// node
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl MetricsPushConfig {
+    fn default_interval_ms() -> u64 {
+        15_000
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum MetricsPushEndpoint {
+    /// A Prometheus push gateway, e.g. `http://pushgateway:9091`, grouping the metrics under
+    /// `job` and the labels
+    PushGateway { url: String, job: String },
+    /// An OpenTelemetry collector receiving OTLP over HTTP, e.g.
+    /// `http://collector:4318/v1/metrics`
+    Otlp { url: String },
+}
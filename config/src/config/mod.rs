@@ -5,6 +5,7 @@ use rand::{rngs::StdRng, SeedableRng};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
     collections::HashSet,
+    convert::TryInto,
     fmt,
     fs::File,
     io::{Read, Write},
@@ -43,6 +44,8 @@ mod safety_rules_config;
 pub use safety_rules_config::*;
 mod upstream_config;
 pub use upstream_config::*;
+mod validation;
+pub use validation::*;
 mod test_config;
 use crate::{chain_id::ChainId, network_id::NetworkId};
 use libra_secure_storage::{KVStorage, Storage};
@@ -94,6 +97,8 @@ pub struct BaseConfig {
     pub chain_id: ChainId,
     pub role: RoleType,
     pub waypoint: WaypointConfig,
+    /// How long the node takes at most to shut down in order on SIGTERM, before exiting anyway
+    pub shutdown_deadline_ms: u64,
 }
 
 impl Default for BaseConfig {
@@ -103,6 +108,7 @@ impl Default for BaseConfig {
             chain_id: ChainId::default(),
             role: RoleType::Validator,
             waypoint: WaypointConfig::None,
+            shutdown_deadline_ms: 30_000,
         }
     }
 }
@@ -128,7 +134,7 @@ impl WaypointConfig {
         let waypoint = match &self {
             WaypointConfig::FromConfig(waypoint) => Some(*waypoint),
             WaypointConfig::FromStorage(backend) => {
-                let storage: Storage = backend.into();
+                let storage: Storage = backend.try_into().expect("Unable to initialize storage");
                 let waypoint = storage
                     .get(libra_global_constants::WAYPOINT)
                     .expect("Unable to read waypoint")
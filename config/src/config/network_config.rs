@@ -43,6 +43,13 @@ pub struct NetworkConfig {
     // TODO: Add support for multiple listen/advertised addresses in config.
     // The address that this node is listening on for new connections.
     pub listen_address: NetworkAddress,
+    // The maximum number of inbound connections. Once it is reached, a new connection either
+    // evicts an idle inbound connection from an untrusted peer or is refused. Connections from
+    // trusted peers are always accepted.
+    pub max_inbound_connections: usize,
+    // The maximum number of outbound connections the connectivity manager dials, on full node
+    // networks. Validators always dial every other validator.
+    pub max_outbound_connections: usize,
     // Select this to enforce that both peers should authenticate each other, otherwise
     // authentication only occurs for outgoing connections.
     pub mutual_authentication: bool,
@@ -67,6 +74,8 @@ impl NetworkConfig {
             discovery_method: DiscoveryMethod::None,
             identity: Identity::None,
             listen_address: "/ip4/0.0.0.0/tcp/6180".parse().unwrap(),
+            max_inbound_connections: 100,
+            max_outbound_connections: 3,
             mutual_authentication: false,
             network_id,
             network_peers: HashMap::default(),
@@ -86,6 +95,8 @@ impl NetworkConfig {
             discovery_method: self.discovery_method.clone(),
             identity: Identity::None,
             listen_address: self.listen_address.clone(),
+            max_inbound_connections: self.max_inbound_connections,
+            max_outbound_connections: self.max_outbound_connections,
             mutual_authentication: self.mutual_authentication,
             network_id: self.network_id.clone(),
             network_peers: self.network_peers.clone(),
@@ -97,7 +108,9 @@ impl NetworkConfig {
         let key = match &mut self.identity {
             Identity::FromConfig(config) => config.keypair.take_private(),
             Identity::FromStorage(config) => {
-                let storage: Storage = (&config.backend).into();
+                let storage: Storage = (&config.backend)
+                    .try_into()
+                    .expect("Unable to initialize storage");
                 let key = storage
                     .export_private_key(&config.key_name)
                     .expect("Unable to read key");
@@ -131,7 +144,9 @@ impl NetworkConfig {
         match &self.identity {
             Identity::FromConfig(config) => Some(config.peer_id),
             Identity::FromStorage(config) => {
-                let storage: Storage = (&config.backend).into();
+                let storage: Storage = (&config.backend)
+                    .try_into()
+                    .expect("Unable to initialize storage");
                 let peer_id = storage
                     .get(&config.peer_id_name)
                     .expect("Unable to read peer id")
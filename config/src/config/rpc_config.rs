@@ -3,15 +3,63 @@
 
 use crate::utils;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RpcConfig {
     pub address: SocketAddr,
+    // Maximum number of requests in a single batch
+    pub batch_size_limit: u16,
+    // Maximum number of items returned by a single paginated request, e.g., get_transactions
+    pub page_size_limit: u16,
+    // Maximum size in bytes of the body of a request
+    pub content_length_limit: usize,
+    // Maximum time a submit_and_wait request waits for its transaction to be committed
+    pub submit_and_wait_timeout_ms: u64,
+    // Maximum number of open connections, further connections are closed as soon as they are
+    // accepted. Unlimited if not set
+    pub max_connections: Option<usize>,
+    // If set, requests are served over TLS instead of plain HTTP
+    pub tls: Option<RpcTlsConfig>,
+    // If set, browsers may send requests from the allowed origins
+    pub cors: Option<RpcCorsConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcTlsConfig {
+    // PEM file of the certificate chain of the server
+    pub cert_path: PathBuf,
+    // PEM file of the private key of the server, in PKCS8 or RSA format
+    pub key_path: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcCorsConfig {
+    // Origins allowed to send requests, e.g. "https://example.com", or "*" to allow any origin
+    pub allowed_origins: Vec<String>,
+    // How long browsers may cache the result of a preflight request
+    pub max_age_secs: u64,
+}
+
+impl Default for RpcCorsConfig {
+    fn default() -> RpcCorsConfig {
+        RpcCorsConfig {
+            allowed_origins: vec![],
+            max_age_secs: DEFAULT_CORS_MAX_AGE_SECS,
+        }
+    }
 }
 
 pub const DEFAULT_JSON_RPC_PORT: u16 = 8080;
+pub const DEFAULT_BATCH_SIZE_LIMIT: u16 = 20;
+pub const DEFAULT_PAGE_SIZE_LIMIT: u16 = 1000;
+pub const DEFAULT_CONTENT_LENGTH_LIMIT: usize = 1024 * 1024;
+// Below the default timeout of the clients
+pub const DEFAULT_SUBMIT_AND_WAIT_TIMEOUT_MS: u64 = 20_000;
+pub const DEFAULT_CORS_MAX_AGE_SECS: u64 = 600;
 
 impl Default for RpcConfig {
     fn default() -> RpcConfig {
@@ -19,6 +67,13 @@ impl Default for RpcConfig {
             address: format!("0.0.0.0:{}", DEFAULT_JSON_RPC_PORT)
                 .parse()
                 .unwrap(),
+            batch_size_limit: DEFAULT_BATCH_SIZE_LIMIT,
+            page_size_limit: DEFAULT_PAGE_SIZE_LIMIT,
+            content_length_limit: DEFAULT_CONTENT_LENGTH_LIMIT,
+            submit_and_wait_timeout_ms: DEFAULT_SUBMIT_AND_WAIT_TIMEOUT_MS,
+            max_connections: None,
+            tls: None,
+            cors: None,
         }
     }
 }
@@ -2,21 +2,67 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
+use libra_kms_client::{aws, gcp, Kms};
 use libra_secure_storage::{
-    GitHubStorage, InMemoryStorage, NamespacedStorage, OnDiskStorage, Storage, VaultStorage,
+    AuditLog, AuditedStorage, GitHubStorage, InMemoryStorage, KVStorage, KmsStorage,
+    NamespacedStorage, OnDiskStorage, Storage, VaultStorage,
 };
 use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Read, path::PathBuf};
+use std::{convert::TryFrom, fs::File, io::Read, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum SecureBackend {
+    Audited(AuditedConfig),
     GitHub(GitHubConfig),
     InMemoryStorage,
+    Kms(KmsConfig),
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
 }
 
+impl SecureBackend {
+    /// Sets the directory relative to which on disk storage paths are resolved.
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        match self {
+            SecureBackend::Audited(config) => config.set_data_dir(data_dir),
+            SecureBackend::Kms(config) => config.storage.set_data_dir(data_dir),
+            SecureBackend::OnDiskStorage(config) => config.set_data_dir(data_dir),
+            _ => (),
+        }
+    }
+}
+
+/// Records every operation performed on another backend, along with the identity of the caller,
+/// within an append-only, hash-chained audit log on the local filesystem.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditedConfig {
+    /// The backend whose operations are recorded
+    pub backend: Box<SecureBackend>,
+    /// The identity recorded for each operation, e.g., safety_rules
+    pub identity: String,
+    /// Path to the audit log, relative to data_dir unless absolute
+    pub log_path: PathBuf,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl AuditedConfig {
+    pub fn log_path(&self) -> PathBuf {
+        if self.log_path.is_relative() {
+            self.data_dir.join(&self.log_path)
+        } else {
+            self.log_path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.backend.set_data_dir(data_dir.clone());
+        self.data_dir = data_dir;
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GitHubConfig {
@@ -41,6 +87,10 @@ pub struct VaultConfig {
     /// a secret, S, without a namespace would be available in secret/data/S, with a namespace, N, it
     /// would be in secret/data/N/S.
     pub namespace: Option<String>,
+    /// An optional Vault Enterprise namespace in which all operations are performed. Unlike
+    /// `namespace`, this is enforced by Vault itself, which isolates engines, policies, and
+    /// tokens between namespaces.
+    pub enterprise_namespace: Option<String>,
     /// Vault's URL, note: only HTTP is currently supported.
     pub server: String,
     /// The authorization token for accessing secrets
@@ -57,6 +107,86 @@ impl VaultConfig {
     }
 }
 
+/// Stores data on disk, encrypted by a key held within a cloud key management service, so that
+/// private keys are never persisted in plaintext.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KmsConfig {
+    /// The key management service and key used to encrypt and decrypt all data
+    pub service: KmsService,
+    /// Where the encrypted data is stored
+    pub storage: OnDiskStorageConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum KmsService {
+    Aws(AwsKmsConfig),
+    Gcp(GcpKmsConfig),
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsKmsConfig {
+    /// The AWS region hosting the key, e.g., us-west-2
+    pub region: String,
+    /// The id, ARN, or alias (e.g., alias/libra) of a symmetric key
+    pub key_id: String,
+    /// Credentials of an IAM user. If absent, the IAM role of the EC2 instance is used.
+    pub credentials: Option<AwsCredentialsConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCredentialsConfig {
+    pub access_key_id: String,
+    pub secret_access_key: Token,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GcpKmsConfig {
+    /// The resource name of a symmetric key, i.e.,
+    /// projects/PROJECT/locations/LOCATION/keyRings/KEY_RING/cryptoKeys/KEY
+    pub key_name: String,
+    /// An OAuth 2.0 access token. If absent, the service account of the Compute Engine instance
+    /// is used.
+    pub token: Option<Token>,
+}
+
+impl KmsService {
+    fn client(&self) -> Box<dyn Kms> {
+        match self {
+            KmsService::Aws(config) => {
+                let provider = match &config.credentials {
+                    Some(credentials) => aws::CredentialsProvider::Static(aws::Credentials::new(
+                        credentials.access_key_id.clone(),
+                        credentials
+                            .secret_access_key
+                            .read_token()
+                            .expect("Unable to read secret access key"),
+                    )),
+                    None => aws::CredentialsProvider::InstanceMetadata,
+                };
+                Box::new(aws::Client::new(
+                    config.region.clone(),
+                    config.key_id.clone(),
+                    provider,
+                ))
+            }
+            KmsService::Gcp(config) => {
+                let provider = match &config.token {
+                    Some(token) => gcp::TokenProvider::Static(
+                        token.read_token().expect("Unable to read token"),
+                    ),
+                    None => gcp::TokenProvider::InstanceMetadata,
+                };
+                Box::new(gcp::Client::new(config.key_name.clone(), provider))
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct OnDiskStorageConfig {
@@ -133,14 +263,26 @@ fn read_file(path: &PathBuf) -> Result<String, Error> {
     Ok(contents)
 }
 
-impl From<&SecureBackend> for Storage {
-    fn from(backend: &SecureBackend) -> Self {
-        match backend {
+impl TryFrom<&SecureBackend> for Storage {
+    type Error = Error;
+
+    fn try_from(backend: &SecureBackend) -> Result<Self, Error> {
+        let storage = match backend {
+            SecureBackend::Audited(config) => {
+                let log_path = config.log_path();
+                let log = AuditLog::open(log_path.clone())
+                    .map_err(|e| Error::SecureStorage(log_path.to_str().unwrap().to_string(), e))?;
+                Storage::from(AuditedStorage::new(
+                    Storage::try_from(config.backend.as_ref())?,
+                    log,
+                    config.identity.clone(),
+                ))
+            }
             SecureBackend::GitHub(config) => {
                 let storage = GitHubStorage::new(
                     config.repository_owner.clone(),
                     config.repository.clone(),
-                    config.token.read_token().expect("Unable to read token"),
+                    config.token.read_token()?,
                 );
                 if let Some(namespace) = &config.namespace {
                     Storage::from(NamespacedStorage::new(Box::new(storage), namespace.clone()))
@@ -149,6 +291,16 @@ impl From<&SecureBackend> for Storage {
                 }
             }
             SecureBackend::InMemoryStorage => Storage::from(InMemoryStorage::new()),
+            SecureBackend::Kms(config) => {
+                let storage = OnDiskStorage::new(config.storage.path());
+                let storage: Box<dyn KVStorage> = if let Some(namespace) = &config.storage.namespace
+                {
+                    Box::new(NamespacedStorage::new(Box::new(storage), namespace.clone()))
+                } else {
+                    Box::new(storage)
+                };
+                Storage::from(KmsStorage::new(config.service.client(), storage))
+            }
             SecureBackend::OnDiskStorage(config) => {
                 let storage = OnDiskStorage::new(config.path());
                 if let Some(namespace) = &config.namespace {
@@ -159,14 +311,17 @@ impl From<&SecureBackend> for Storage {
             }
             SecureBackend::Vault(config) => Storage::from(VaultStorage::new(
                 config.server.clone(),
-                config.token.read_token().expect("Unable to read token"),
+                config.token.read_token()?,
                 config.namespace.clone(),
+                config.enterprise_namespace.clone(),
                 config
                     .ca_certificate
                     .as_ref()
-                    .map(|_| config.ca_certificate().unwrap()),
+                    .map(|_| config.ca_certificate())
+                    .transpose()?,
             )),
-        }
+        };
+        Ok(storage)
     }
 }
 #[cfg(test)]
@@ -184,6 +339,7 @@ mod tests {
         let from_config = Config {
             vault: VaultConfig {
                 namespace: None,
+                enterprise_namespace: None,
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromConfig("test".to_string()),
@@ -208,6 +364,7 @@ vault:
         let from_disk = Config {
             vault: VaultConfig {
                 namespace: None,
+                enterprise_namespace: None,
                 server: "127.0.0.1:8200".to_string(),
                 ca_certificate: None,
                 token: Token::FromDisk(PathBuf::from("/token")),
@@ -227,6 +384,136 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_kms_parsing() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Config {
+            backend: SecureBackend,
+        }
+
+        let aws = Config {
+            backend: SecureBackend::Kms(KmsConfig {
+                service: KmsService::Aws(AwsKmsConfig {
+                    region: "us-west-2".to_string(),
+                    key_id: "alias/libra".to_string(),
+                    credentials: None,
+                }),
+                storage: OnDiskStorageConfig {
+                    path: PathBuf::from("secure_storage.json"),
+                    namespace: None,
+                    data_dir: PathBuf::new(),
+                },
+            }),
+        };
+        let text_aws = r#"
+backend:
+    type: "kms"
+    service:
+        type: "aws"
+        region: "us-west-2"
+        key_id: "alias/libra"
+    storage:
+        path: "secure_storage.json"
+        "#;
+        let de_aws: Config = serde_yaml::from_str(text_aws).unwrap();
+        assert_eq!(de_aws, aws);
+        serde_yaml::to_string(&aws).unwrap();
+
+        let gcp = Config {
+            backend: SecureBackend::Kms(KmsConfig {
+                service: KmsService::Gcp(GcpKmsConfig {
+                    key_name:
+                        "projects/libra/locations/global/keyRings/validator/cryptoKeys/storage"
+                            .to_string(),
+                    token: Some(Token::FromDisk(PathBuf::from("/token"))),
+                }),
+                storage: OnDiskStorageConfig {
+                    path: PathBuf::from("secure_storage.json"),
+                    namespace: Some("safety_rules".to_string()),
+                    data_dir: PathBuf::new(),
+                },
+            }),
+        };
+        let text_gcp = r#"
+backend:
+    type: "kms"
+    service:
+        type: "gcp"
+        key_name: "projects/libra/locations/global/keyRings/validator/cryptoKeys/storage"
+        token:
+            from_disk: "/token"
+    storage:
+        path: "secure_storage.json"
+        namespace: "safety_rules"
+        "#;
+        let de_gcp: Config = serde_yaml::from_str(text_gcp).unwrap();
+        assert_eq!(de_gcp, gcp);
+        serde_yaml::to_string(&gcp).unwrap();
+    }
+
+    #[test]
+    fn test_audited_parsing() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Config {
+            backend: SecureBackend,
+        }
+
+        let mut audited = Config {
+            backend: SecureBackend::Audited(AuditedConfig {
+                backend: Box::new(SecureBackend::OnDiskStorage(OnDiskStorageConfig {
+                    path: PathBuf::from("secure_storage.json"),
+                    namespace: None,
+                    data_dir: PathBuf::new(),
+                })),
+                identity: "safety_rules".to_string(),
+                log_path: PathBuf::from("audit.log"),
+                data_dir: PathBuf::new(),
+            }),
+        };
+        let text_audited = r#"
+backend:
+    type: "audited"
+    backend:
+        type: "on_disk_storage"
+        path: "secure_storage.json"
+    identity: "safety_rules"
+    log_path: "audit.log"
+        "#;
+        let de_audited: Config = serde_yaml::from_str(text_audited).unwrap();
+        assert_eq!(de_audited, audited);
+        serde_yaml::to_string(&audited).unwrap();
+
+        audited
+            .backend
+            .set_data_dir(PathBuf::from("/opt/libra/data"));
+        if let SecureBackend::Audited(config) = &audited.backend {
+            assert_eq!(
+                config.log_path(),
+                PathBuf::from("/opt/libra/data/audit.log")
+            );
+            if let SecureBackend::OnDiskStorage(storage) = config.backend.as_ref() {
+                assert_eq!(
+                    storage.path(),
+                    PathBuf::from("/opt/libra/data/secure_storage.json")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_audited_storage_unwritable_log() {
+        let mut backend = SecureBackend::Audited(AuditedConfig {
+            backend: Box::new(SecureBackend::InMemoryStorage),
+            identity: "safety_rules".to_string(),
+            log_path: PathBuf::from("audit.log"),
+            data_dir: PathBuf::new(),
+        });
+        // The data directory is never created, so the log cannot be opened
+        let data_dir = libra_temppath::TempPath::new();
+        backend.set_data_dir(data_dir.path().to_path_buf());
+        assert!(Storage::try_from(&backend).is_err());
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = libra_temppath::TempPath::new();
@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncConfig {
     // Size of chunk to request for state synchronization
     pub chunk_limit: u64,
+    // maximum number of received chunks executed and committed to storage at once; a chunk
+    // carrying a LedgerInfo to commit always completes the batch
+    pub chunk_batch_size: u64,
     // default timeout used for long polling to remote peer
     pub long_poll_timeout_ms: u64,
     // valid maximum chunk limit for sanity check
@@ -18,17 +22,28 @@ pub struct StateSyncConfig {
     pub sync_request_timeout_ms: u64,
     // interval used for checking state synchronization progress
     pub tick_interval_ms: u64,
+    // unix socket on which committed transactions are streamed to local consumers, e.g.
+    // indexers; None disables the commit stream
+    pub commit_stream_path: Option<PathBuf>,
+    // maximum number of transactions sent to a commit stream consumer at once
+    pub commit_stream_batch_size: u64,
+    // time a commit stream consumer has to read a batch before it is disconnected
+    pub commit_stream_write_timeout_ms: u64,
 }
 
 impl Default for StateSyncConfig {
     fn default() -> Self {
         Self {
             chunk_limit: 250,
+            chunk_batch_size: 10,
             long_poll_timeout_ms: 30000,
             max_chunk_limit: 1000,
             max_timeout_ms: 120_000,
             sync_request_timeout_ms: 60_000,
             tick_interval_ms: 100,
+            commit_stream_path: None,
+            commit_stream_batch_size: 100,
+            commit_stream_write_timeout_ms: 10_000,
         }
     }
 }
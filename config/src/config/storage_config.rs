@@ -1,9 +1,11 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::utils;
+use crate::{config::SecureBackend, utils};
+use libra_crypto::x25519;
+use libra_secure_storage::{CryptoStorage, Storage};
 use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, path::PathBuf};
+use std::{convert::TryInto, net::SocketAddr, path::PathBuf};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -15,6 +17,16 @@ pub struct StorageConfig {
     /// None disables pruning. The windows is in number of versions, consider system tps
     /// (transaction per second) when calculating proper window.
     pub prune_window: Option<u64>,
+    /// None disables archiving. Transactions and events older than this many versions are moved
+    /// out of the DB into compressed archive files, where they stay readable at a higher latency.
+    pub archive_window: Option<u64>,
+    /// None disables waypoint emission. A waypoint, signed by the validators through its ledger
+    /// info, is persisted at the end of every this many epochs so that new nodes can bootstrap
+    /// from a recent one instead of genesis.
+    pub waypoint_interval: Option<u64>,
+    /// None serves clients over plain TCP, which is only safe on the same host. Set it when
+    /// clients connect from other hosts, e.g. full nodes sharing one storage service.
+    pub secure_channel: Option<StorageSecureChannelConfig>,
     #[serde(skip)]
     data_dir: PathBuf,
 }
@@ -27,6 +39,9 @@ impl Default for StorageConfig {
             dir: PathBuf::from("libradb/db"),
             grpc_max_receive_len: Some(100_000_000),
             prune_window: None,
+            archive_window: None,
+            waypoint_interval: Some(1),
+            secure_channel: None,
             data_dir: PathBuf::from("/opt/libra/data/common"),
         }
     }
@@ -42,6 +57,9 @@ impl StorageConfig {
     }
 
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        if let Some(secure_channel) = &mut self.secure_channel {
+            secure_channel.backend.set_data_dir(data_dir.clone());
+        }
         self.data_dir = data_dir;
     }
 
@@ -50,3 +68,26 @@ impl StorageConfig {
         self.backup_service_port = utils::get_available_port();
     }
 }
+
+/// The Noise channel between the storage service and its clients. Only the clients whose public
+/// keys are listed can connect.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageSecureChannelConfig {
+    pub backend: SecureBackend,
+    pub identity_key_name: String,
+    pub trusted_clients: Vec<x25519::PublicKey>,
+}
+
+impl StorageSecureChannelConfig {
+    pub fn identity_key(&self) -> x25519::PrivateKey {
+        let storage: Storage = (&self.backend)
+            .try_into()
+            .expect("Unable to initialize storage");
+        let key = storage
+            .export_private_key(&self.identity_key_name)
+            .expect("Unable to read key");
+        x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
+            .expect("Unable to convert key")
+    }
+}
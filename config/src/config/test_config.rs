@@ -33,6 +33,10 @@ pub struct TestConfig {
     temp_dir: Option<TempPath>,
 
     pub publishing_option: Option<VMPublishingOption>,
+    // Lets the clock of the node be moved forward through the debug interface, so that tests can
+    // exercise expirations and timeouts without waiting for them
+    #[serde(default)]
+    pub adjustable_time: bool,
 }
 
 #[cfg(any(test, feature = "fuzzing"))]
@@ -46,6 +50,7 @@ impl Clone for TestConfig {
             initialize_storage: self.initialize_storage,
             temp_dir: None,
             publishing_option: self.publishing_option.clone(),
+            adjustable_time: self.adjustable_time,
         }
     }
 }
@@ -57,6 +62,7 @@ impl PartialEq for TestConfig {
             && self.consensus_keypair == other.consensus_keypair
             && self.execution_keypair == other.execution_keypair
             && self.initialize_storage == other.initialize_storage
+            && self.adjustable_time == other.adjustable_time
     }
 }
 
@@ -70,6 +76,7 @@ impl TestConfig {
             initialize_storage: false,
             temp_dir: None,
             publishing_option: Some(VMPublishingOption::Open),
+            adjustable_time: false,
         }
     }
 
@@ -84,6 +91,7 @@ impl TestConfig {
             initialize_storage: false,
             temp_dir: Some(temp_dir),
             publishing_option: None,
+            adjustable_time: false,
         }
     }
 
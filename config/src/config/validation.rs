@@ -0,0 +1,364 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    chain_id::ChainId,
+    config::{Error, NodeConfig, RoleType, WaypointConfig},
+    network_id::NetworkId,
+};
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// Backups read the database at a fixed version over an extended period of time, so pruning
+/// versions more recent than this would cause backups in progress to fail.
+pub const MIN_PRUNE_WINDOW: u64 = 100_000;
+
+/// A problem with the value of a specific field in a NodeConfig
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    /// The path of the field, e.g., full_node_networks[0].network_id
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+pub(crate) fn format_validation_errors(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|error| format!("\n  {}", error))
+        .collect()
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    fn check(&mut self, cond: bool, field: impl Into<String>, message: impl Into<String>) {
+        if !cond {
+            self.errors.push(ValidationError {
+                field: field.into(),
+                message: message.into(),
+            });
+        }
+    }
+}
+
+impl NodeConfig {
+    /// Cross-checks the sections of a loaded config and reports all problems found at once, rather
+    /// than leaving them to fail one at a time during startup.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut validator = Validator::default();
+        self.validate_networks(&mut validator);
+        self.validate_genesis(&mut validator);
+        self.validate_ports(&mut validator);
+        self.validate_admin(&mut validator);
+        self.validate_limits(&mut validator);
+
+        if validator.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Invalid(validator.errors))
+        }
+    }
+
+    fn validate_networks(&self, validator: &mut Validator) {
+        match self.base.role {
+            RoleType::Validator => validator.check(
+                self.validator_network.is_some(),
+                "validator_network",
+                "validators require a validator network",
+            ),
+            RoleType::FullNode => {
+                validator.check(
+                    self.validator_network.is_none(),
+                    "validator_network",
+                    "full nodes cannot have a validator network",
+                );
+                validator.check(
+                    !self.full_node_networks.is_empty(),
+                    "full_node_networks",
+                    "full nodes require at least one network",
+                );
+            }
+        }
+
+        if let Some(network) = &self.validator_network {
+            validator.check(
+                network.network_id == NetworkId::Validator,
+                "validator_network.network_id",
+                format!("expected Validator, found {}", network.network_id),
+            );
+            validator.check(
+                network.network_peers.is_empty(),
+                "validator_network.network_peers",
+                "validators discover their peers on-chain and cannot define network peers",
+            );
+        }
+
+        let mut network_ids = HashMap::new();
+        for (index, network) in self.full_node_networks.iter().enumerate() {
+            let field = format!("full_node_networks[{}].network_id", index);
+            validator.check(
+                network.network_id != NetworkId::Validator,
+                field.clone(),
+                "only the validator network can use the Validator network id",
+            );
+            if let Some(other) = network_ids.insert(network.network_id.clone(), index) {
+                validator.check(
+                    false,
+                    field,
+                    format!(
+                        "{} is already used by full_node_networks[{}]",
+                        network.network_id, other
+                    ),
+                );
+            }
+        }
+
+        for (index, network_id) in self.upstream.networks.iter().enumerate() {
+            validator.check(
+                network_ids.contains_key(network_id),
+                format!("upstream.networks[{}]", index),
+                format!("{} is not one of the full_node_networks", network_id),
+            );
+        }
+    }
+
+    fn validate_genesis(&self, validator: &mut Validator) {
+        validator.check(
+            ChainId::from_str(&self.base.chain_id.0).is_ok(),
+            "base.chain_id",
+            "must be a non-empty name without whitespace",
+        );
+        validator.check(
+            self.execution.genesis.is_some(),
+            "execution.genesis_file_location",
+            "a genesis transaction is required to bootstrap the database",
+        );
+        validator.check(
+            self.base.waypoint != WaypointConfig::None,
+            "base.waypoint",
+            "a waypoint is required to verify the genesis transaction and state sync",
+        );
+    }
+
+    fn validate_ports(&self, validator: &mut Validator) {
+        let mut ports = vec![
+            (
+                "debug_interface.admission_control_node_debug_port",
+                self.debug_interface.admission_control_node_debug_port,
+            ),
+            (
+                "debug_interface.metrics_server_port",
+                self.debug_interface.metrics_server_port,
+            ),
+            (
+                "debug_interface.public_metrics_server_port",
+                self.debug_interface.public_metrics_server_port,
+            ),
+            ("rpc.address", self.rpc.address.port()),
+            ("storage.address", self.storage.address.port()),
+            (
+                "storage.backup_service_port",
+                self.storage.backup_service_port,
+            ),
+        ];
+        if let Some(admin_port) = self.debug_interface.admin_port {
+            ports.push(("debug_interface.admin_port", admin_port));
+        }
+
+        let mut fields_by_port = HashMap::new();
+        for (field, port) in ports.iter() {
+            if let Some(other) = fields_by_port.insert(port, field) {
+                validator.check(
+                    false,
+                    *field,
+                    format!("port {} is already used by {}", port, other),
+                );
+            }
+        }
+    }
+
+    fn validate_admin(&self, validator: &mut Validator) {
+        validator.check(
+            self.debug_interface.admin_port.is_none()
+                || self.debug_interface.admin_token_file.is_some(),
+            "debug_interface.admin_token_file",
+            "the admin API requires a token file",
+        );
+    }
+
+    fn validate_limits(&self, validator: &mut Validator) {
+        if let Some(prune_window) = self.storage.prune_window {
+            validator.check(
+                prune_window >= MIN_PRUNE_WINDOW,
+                "storage.prune_window",
+                format!(
+                    "must be at least {} versions, so that backups served on \
+                     storage.backup_service_port are not pruned while in progress",
+                    MIN_PRUNE_WINDOW
+                ),
+            );
+        }
+
+        validator.check(
+            self.mempool.capacity > 0,
+            "mempool.capacity",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.mempool.capacity_per_user <= self.mempool.capacity,
+            "mempool.capacity_per_user",
+            format!(
+                "must not exceed mempool.capacity ({})",
+                self.mempool.capacity
+            ),
+        );
+        validator.check(
+            self.consensus.crypto_pool_threads > 0,
+            "consensus.crypto_pool_threads",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.consensus.crypto_pool_queue_size > 0,
+            "consensus.crypto_pool_queue_size",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.rpc.batch_size_limit > 0,
+            "rpc.batch_size_limit",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.rpc.page_size_limit > 0,
+            "rpc.page_size_limit",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.rpc.content_length_limit > 0,
+            "rpc.content_length_limit",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.rpc.submit_and_wait_timeout_ms > 0,
+            "rpc.submit_and_wait_timeout_ms",
+            "must be greater than 0",
+        );
+        validator.check(
+            self.rpc.max_connections != Some(0),
+            "rpc.max_connections",
+            "must be greater than 0",
+        );
+        if let Some(cors) = &self.rpc.cors {
+            validator.check(
+                !cors.allowed_origins.is_empty(),
+                "rpc.cors.allowed_origins",
+                "must not be empty",
+            );
+        }
+        validator.check(
+            self.state_sync.chunk_limit > 0
+                && self.state_sync.chunk_limit <= self.state_sync.max_chunk_limit,
+            "state_sync.chunk_limit",
+            format!(
+                "must be between 1 and state_sync.max_chunk_limit ({})",
+                self.state_sync.max_chunk_limit
+            ),
+        );
+        validator.check(
+            self.state_sync.chunk_batch_size > 0,
+            "state_sync.chunk_batch_size",
+            "must be greater than 0",
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::NetworkConfig;
+    use libra_types::{
+        transaction::{ChangeSet, Transaction},
+        waypoint::Waypoint,
+        write_set::WriteSetMut,
+    };
+
+    fn valid_config(mut config: NodeConfig) -> NodeConfig {
+        config.execution.genesis = Some(Transaction::WaypointWriteSet(ChangeSet::new(
+            WriteSetMut::new(vec![]).freeze().unwrap(),
+            vec![],
+        )));
+        config.base.waypoint = WaypointConfig::FromConfig(Waypoint::default());
+        config
+    }
+
+    fn errors(config: &NodeConfig) -> Vec<String> {
+        match config.validate() {
+            Ok(()) => vec![],
+            Err(Error::Invalid(errors)) => errors.into_iter().map(|e| e.field).collect(),
+            Err(e) => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_valid_configs() {
+        let config = valid_config(NodeConfig::default_for_validator());
+        assert_eq!(errors(&config), Vec::<String>::new());
+        let config = valid_config(NodeConfig::default_for_public_full_node());
+        assert_eq!(errors(&config), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_all_errors_reported() {
+        let mut config = NodeConfig::default_for_validator();
+        config.full_node_networks = vec![
+            NetworkConfig::network_with_id(NetworkId::Public),
+            NetworkConfig::network_with_id(NetworkId::Public),
+        ];
+        config.upstream.networks = vec![NetworkId::Public, NetworkId::Validator];
+        config.base.waypoint = WaypointConfig::None;
+        config.storage.backup_service_port = config.rpc.address.port();
+        config.debug_interface.admin_port = Some(9103);
+        config.storage.prune_window = Some(1);
+        config.mempool.capacity_per_user = config.mempool.capacity + 1;
+
+        assert_eq!(
+            errors(&config),
+            vec![
+                "full_node_networks[1].network_id",
+                "upstream.networks[1]",
+                "execution.genesis_file_location",
+                "base.waypoint",
+                "storage.backup_service_port",
+                "debug_interface.admin_token_file",
+                "storage.prune_window",
+                "mempool.capacity_per_user",
+            ]
+        );
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message
+            .contains("storage.backup_service_port: port 8080 is already used by rpc.address"));
+    }
+
+    #[test]
+    fn test_role_mismatch() {
+        let mut config = valid_config(NodeConfig::default_for_public_full_node());
+        config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Public));
+        config.full_node_networks.clear();
+        assert_eq!(
+            errors(&config),
+            vec![
+                "validator_network",
+                "full_node_networks",
+                "validator_network.network_id",
+            ]
+        );
+    }
+}
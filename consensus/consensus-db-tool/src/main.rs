@@ -0,0 +1,131 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use anyhow::{Context, Result};
+use consensus::consensusdb::ConsensusDB;
+use consensus_types::{timeout_certificate::TimeoutCertificate, vote::Vote};
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "Inspect the consensus db of a stopped node")]
+struct Opt {
+    /// The storage dir of the node, which contains the `consensusdb` directory
+    #[structopt(long, parse(from_os_str))]
+    db: PathBuf,
+
+    #[structopt(subcommand)]
+    cmd: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// List the blocks, ordered by epoch and round
+    #[structopt(name = "list-blocks")]
+    ListBlocks,
+    /// List the quorum certificates, ordered by the epoch and round of the certified block
+    #[structopt(name = "list-qcs")]
+    ListQCs,
+    /// Print the last vote
+    #[structopt(name = "print-last-vote")]
+    PrintLastVote,
+    /// Print the highest timeout certificate
+    #[structopt(name = "print-highest-tc")]
+    PrintHighestTC,
+}
+
+/// Print the number of blocks and QCs and the range of rounds they cover.
+fn print_summary(db: &ConsensusDB) -> Result<()> {
+    let blocks = db.get_blocks()?;
+    println!("Blocks: {}", blocks.len());
+    if let (Some(first), Some(last)) = (blocks.first(), blocks.last()) {
+        println!(
+            "From epoch {} round {} to epoch {} round {}",
+            first.epoch(),
+            first.round(),
+            last.epoch(),
+            last.round()
+        );
+    }
+    println!(
+        "Quorum certificates: {}",
+        db.get_quorum_certificates()?.len()
+    );
+    println!(
+        "Last vote: {}",
+        if db.get_last_vote()?.is_some() {
+            "present"
+        } else {
+            "none"
+        }
+    );
+    println!(
+        "Highest timeout certificate: {}",
+        if db.get_highest_timeout_certificate()?.is_some() {
+            "present"
+        } else {
+            "none"
+        }
+    );
+    Ok(())
+}
+
+fn list_blocks(db: &ConsensusDB) -> Result<()> {
+    for block in db.get_blocks()? {
+        println!("{} {}", block, block.id());
+    }
+    Ok(())
+}
+
+fn list_qcs(db: &ConsensusDB) -> Result<()> {
+    for qc in db.get_quorum_certificates()? {
+        println!("{}", qc);
+    }
+    Ok(())
+}
+
+fn print_last_vote(db: &ConsensusDB) -> Result<()> {
+    match db.get_last_vote()? {
+        Some(bytes) => {
+            let vote: Vote = lcs::from_bytes(&bytes).context("Unable to deserialize last vote")?;
+            println!("{}", vote);
+        }
+        None => println!("No last vote"),
+    }
+    Ok(())
+}
+
+fn print_highest_tc(db: &ConsensusDB) -> Result<()> {
+    match db.get_highest_timeout_certificate()? {
+        Some(bytes) => {
+            let tc: TimeoutCertificate = lcs::from_bytes(&bytes)
+                .context("Unable to deserialize highest timeout certificate")?;
+            println!("{}", tc);
+        }
+        None => println!("No highest timeout certificate"),
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let opt = Opt::from_args();
+
+    let db = ConsensusDB::open_readonly(&opt.db)
+        .with_context(|| format!("Unable to open the consensus db under {:?}", opt.db))?;
+
+    match opt.cmd {
+        Some(Command::ListBlocks) => list_blocks(&db),
+        Some(Command::ListQCs) => list_qcs(&db),
+        Some(Command::PrintLastVote) => print_last_vote(&db),
+        Some(Command::PrintHighestTC) => print_highest_tc(&db),
+        None => {
+            print_summary(&db)?;
+            println!();
+            Opt::clap().print_help()?;
+            println!();
+            Ok(())
+        }
+    }
+}
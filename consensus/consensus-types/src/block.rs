@@ -87,6 +87,10 @@ impl Block {
         self.block_data.payload()
     }
 
+    pub fn failed_authors(&self) -> &[Author] {
+        self.block_data.failed_authors()
+    }
+
     pub fn quorum_cert(&self) -> &QuorumCert {
         self.block_data.quorum_cert()
     }
@@ -170,6 +174,7 @@ impl Block {
         let block_data = BlockData::new_proposal(
             payload,
             validator_signer.author(),
+            vec![],
             round,
             timestamp_usecs,
             quorum_cert,
@@ -225,6 +230,10 @@ impl Block {
             parent.epoch() == self.epoch(),
             "block's parent should be in the same epoch"
         );
+        ensure!(
+            (self.failed_authors().len() as u64) < self.round() - parent.round(),
+            "Block cannot have more failed authors than rounds since its parent"
+        );
         if parent.has_reconfiguration() {
             ensure!(
                 self.payload().map_or(true, |p| p.is_empty()),
@@ -284,6 +293,7 @@ impl From<&Block> for BlockMetadata {
     fn from(block: &Block) -> Self {
         Self::new(
             block.id(),
+            block.parent_id(),
             block.round(),
             block.timestamp_usecs(),
             // an ordered vector of voters' account address
@@ -296,6 +306,7 @@ impl From<&Block> for BlockMetadata {
                 .collect(),
             // For nil block, we use 0x0 which is convention for nil address in move.
             block.author().unwrap_or(AccountAddress::ZERO),
+            block.failed_authors().to_vec(),
         )
     }
 }
@@ -23,6 +23,10 @@ pub enum BlockType {
         payload: Payload,
         /// Author of the block that can be validated by the author's public key and the signature
         author: Author,
+        /// Valid proposers of the rounds between the parent and this block, which failed to
+        /// produce a certified block, in round order (at most the most recent ones, see
+        /// ProposerElection)
+        failed_authors: Vec<Author>,
     },
     /// NIL blocks don't have authors or signatures: they're generated upon timeouts to fill in the
     /// gaps in the rounds.
@@ -77,6 +81,16 @@ impl BlockData {
         }
     }
 
+    /// The proposers which failed to produce a certified block since the parent, empty for NIL
+    /// and genesis blocks
+    pub fn failed_authors(&self) -> &[Author] {
+        if let BlockType::Proposal { failed_authors, .. } = &self.block_type {
+            failed_authors
+        } else {
+            &[]
+        }
+    }
+
     pub fn block_type(&self) -> &BlockType {
         &self.block_type
     }
@@ -176,6 +190,7 @@ impl BlockData {
     pub fn new_proposal(
         payload: Payload,
         author: Author,
+        failed_authors: Vec<Author>,
         round: Round,
         timestamp_usecs: u64,
         quorum_cert: QuorumCert,
@@ -185,7 +200,11 @@ impl BlockData {
             round,
             timestamp_usecs,
             quorum_cert,
-            block_type: BlockType::Proposal { payload, author },
+            block_type: BlockType::Proposal {
+                payload,
+                author,
+                failed_authors,
+            },
         }
     }
 }
@@ -89,6 +89,7 @@ prop_compose! {
                 block_data: BlockData::new_proposal(
                     block.payload().unwrap().clone(),
                     block.author().unwrap(),
+                    block.failed_authors().to_vec(),
                     block.round(),
                     get_current_timestamp().as_micros() as u64,
                     block.quorum_cert().clone(),
@@ -43,7 +43,7 @@ impl TimeoutCertificate {
     pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
         let timeout_hash = self.timeout.hash();
         validator
-            .verify_aggregated_signature(timeout_hash, &self.signatures)
+            .batch_verify_aggregated_signature(timeout_hash, &self.signatures)
             .context("Failed to verify TimeoutCertificate")?;
         Ok(())
     }
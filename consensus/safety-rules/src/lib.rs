@@ -10,6 +10,7 @@ mod local_client;
 mod persistent_safety_storage;
 mod process;
 mod remote_service;
+mod safety_data_snapshot;
 mod safety_rules;
 mod safety_rules_manager;
 mod serializer;
@@ -18,9 +19,14 @@ mod t_safety_rules;
 mod thread;
 
 pub use crate::{
-    consensus_state::ConsensusState, counters::COUNTERS, error::Error,
-    persistent_safety_storage::PersistentSafetyStorage, process::Process,
-    safety_rules::SafetyRules, safety_rules_manager::SafetyRulesManager,
+    consensus_state::ConsensusState,
+    counters::COUNTERS,
+    error::Error,
+    persistent_safety_storage::PersistentSafetyStorage,
+    process::Process,
+    safety_data_snapshot::{SafetyDataSnapshot, SignedSafetyDataSnapshot},
+    safety_rules::SafetyRules,
+    safety_rules_manager::SafetyRulesManager,
     t_safety_rules::TSafetyRules,
 };
 
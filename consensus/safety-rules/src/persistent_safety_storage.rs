@@ -1,14 +1,21 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use crate::safety_data_snapshot::{SafetyDataSnapshot, SignedSafetyDataSnapshot};
+use anyhow::{ensure, Result};
 use consensus_types::common::{Author, Round};
-use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use libra_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    hash::CryptoHash,
+    Signature,
+};
 use libra_global_constants::{
     CONSENSUS_KEY, EPOCH, EXECUTION_KEY, LAST_VOTED_ROUND, OPERATOR_ACCOUNT, PREFERRED_ROUND,
     WAYPOINT,
 };
-use libra_secure_storage::{CryptoStorage, InMemoryStorage, KVStorage, Storage, Value};
+use libra_secure_storage::{
+    CryptoStorage, Error as StorageError, InMemoryStorage, KVStorage, Storage, Value,
+};
 use libra_types::waypoint::Waypoint;
 use std::str::FromStr;
 
@@ -151,12 +158,113 @@ impl PersistentSafetyStorage {
         Ok(())
     }
 
+    /// Exports the current safety data signed by the consensus key, so that it can be imported
+    /// into another backend via `import_snapshot`. The exporting SafetyRules must not vote after
+    /// the export, as those votes would not be reflected in the snapshot.
+    pub fn export_snapshot(&mut self) -> Result<SignedSafetyDataSnapshot> {
+        let snapshot = SafetyDataSnapshot {
+            author: self.author()?,
+            epoch: self.epoch()?,
+            last_voted_round: self.last_voted_round()?,
+            preferred_round: self.preferred_round()?,
+            waypoint: self.waypoint()?,
+        };
+        let signature = self
+            .internal_store
+            .sign_message(CONSENSUS_KEY, &snapshot.hash())?;
+        Ok(SignedSafetyDataSnapshot {
+            snapshot,
+            signature,
+        })
+    }
+
+    /// Imports a snapshot produced by `export_snapshot`. The consensus key must already be present
+    /// in this storage and must have signed the snapshot. To prevent double voting, the snapshot
+    /// is rejected if it would move this storage backwards, i.e., to an earlier epoch, an earlier
+    /// round within the same epoch, or an earlier waypoint. Values not yet set in this storage are
+    /// not checked.
+    pub fn import_snapshot(&mut self, signed_snapshot: &SignedSafetyDataSnapshot) -> Result<()> {
+        let snapshot = &signed_snapshot.snapshot;
+        let consensus_key = self
+            .internal_store
+            .get_public_key(CONSENSUS_KEY)?
+            .public_key;
+        signed_snapshot
+            .signature
+            .verify(&snapshot.hash(), &consensus_key)
+            .map_err(|e| anyhow::anyhow!("Snapshot not signed by the consensus key: {}", e))?;
+
+        if let Some(author) = optional(self.author())? {
+            ensure!(
+                author == snapshot.author,
+                "Snapshot author {} does not match {}",
+                snapshot.author,
+                author
+            );
+        }
+        if let Some(waypoint) = optional(self.waypoint())? {
+            ensure!(
+                snapshot.waypoint.version() >= waypoint.version(),
+                "Snapshot waypoint {} precedes {}",
+                snapshot.waypoint,
+                waypoint
+            );
+        }
+        if let Some(epoch) = optional(self.epoch())? {
+            ensure!(
+                snapshot.epoch >= epoch,
+                "Snapshot epoch {} precedes {}",
+                snapshot.epoch,
+                epoch
+            );
+            if snapshot.epoch == epoch {
+                if let Some(last_voted_round) = optional(self.last_voted_round())? {
+                    ensure!(
+                        snapshot.last_voted_round >= last_voted_round,
+                        "Snapshot last voted round {} precedes {}",
+                        snapshot.last_voted_round,
+                        last_voted_round
+                    );
+                }
+                if let Some(preferred_round) = optional(self.preferred_round())? {
+                    ensure!(
+                        snapshot.preferred_round >= preferred_round,
+                        "Snapshot preferred round {} precedes {}",
+                        snapshot.preferred_round,
+                        preferred_round
+                    );
+                }
+            }
+        }
+
+        // This is ordered in the same manner as starting a new epoch, so that an interrupted
+        // import can be retried.
+        self.internal_store
+            .set(OPERATOR_ACCOUNT, Value::String(snapshot.author.to_string()))?;
+        self.set_waypoint(&snapshot.waypoint)?;
+        self.set_last_voted_round(snapshot.last_voted_round)?;
+        self.set_preferred_round(snapshot.preferred_round)?;
+        self.set_epoch(snapshot.epoch)?;
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn internal_store(&mut self) -> &mut Storage {
         &mut self.internal_store
     }
 }
 
+/// Converts a missing value into None, so that absent values can be distinguished from failures.
+fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) => match e.downcast_ref::<StorageError>() {
+            Some(StorageError::KeyNotSet(_)) => Ok(None),
+            _ => Err(e),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +288,58 @@ mod tests {
         assert_eq!(storage.last_voted_round().unwrap(), 8);
         assert_eq!(storage.preferred_round().unwrap(), 1);
     }
+
+    #[test]
+    fn test_snapshot() {
+        let author = Author::random();
+        let consensus_key = ValidatorSigner::from_int(0).private_key().clone();
+        let new_storage = |consensus_key: Ed25519PrivateKey| {
+            PersistentSafetyStorage::initialize(
+                Storage::from(InMemoryStorage::new()),
+                author,
+                consensus_key,
+                Ed25519PrivateKey::generate_for_testing(),
+                Waypoint::default(),
+            )
+        };
+
+        let mut source = new_storage(consensus_key.clone());
+        source.set_epoch(2).unwrap();
+        source.set_last_voted_round(8).unwrap();
+        source.set_preferred_round(6).unwrap();
+        let snapshot = source.export_snapshot().unwrap();
+
+        let mut target = new_storage(consensus_key.clone());
+        target.import_snapshot(&snapshot).unwrap();
+        assert_eq!(target.author().unwrap(), author);
+        assert_eq!(target.epoch().unwrap(), 2);
+        assert_eq!(target.last_voted_round().unwrap(), 8);
+        assert_eq!(target.preferred_round().unwrap(), 6);
+        assert_eq!(target.waypoint().unwrap(), Waypoint::default());
+
+        // Importing the same snapshot again is harmless, but going backwards is not
+        target.import_snapshot(&snapshot).unwrap();
+        target.set_last_voted_round(9).unwrap();
+        target.import_snapshot(&snapshot).unwrap_err();
+        target.set_epoch(3).unwrap();
+        target.set_last_voted_round(0).unwrap();
+        target.import_snapshot(&snapshot).unwrap_err();
+        assert_eq!(target.epoch().unwrap(), 3);
+
+        // A snapshot from a later epoch supersedes rounds of an earlier one
+        let mut target = new_storage(consensus_key);
+        target.set_last_voted_round(20).unwrap();
+        target.import_snapshot(&snapshot).unwrap();
+        assert_eq!(target.last_voted_round().unwrap(), 8);
+
+        // Snapshots must be signed by the consensus key held by the target
+        let mut target = new_storage(Ed25519PrivateKey::generate_for_testing());
+        target.import_snapshot(&snapshot).unwrap_err();
+        assert_eq!(target.epoch().unwrap(), 1);
+
+        let mut tampered = snapshot;
+        tampered.snapshot.last_voted_round = 100;
+        let mut target = new_storage(ValidatorSigner::from_int(0).private_key().clone());
+        target.import_snapshot(&tampered).unwrap_err();
+    }
 }
@@ -0,0 +1,28 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus_types::common::{Author, Round};
+use libra_crypto::ed25519::Ed25519Signature;
+use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
+use libra_types::waypoint::Waypoint;
+use serde::{Deserialize, Serialize};
+
+/// The safety data of a validator at a point in time. Together with the consensus key, this is
+/// everything SafetyRules requires to safely resume voting from a different storage backend.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, LCSCryptoHash)]
+pub struct SafetyDataSnapshot {
+    pub author: Author,
+    pub epoch: u64,
+    pub last_voted_round: Round,
+    pub preferred_round: Round,
+    pub waypoint: Waypoint,
+}
+
+/// A SafetyDataSnapshot signed by the consensus key of the validator that exported it, so that it
+/// can only be imported into a storage holding the same consensus key and cannot be altered in
+/// transit.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignedSafetyDataSnapshot {
+    pub snapshot: SafetyDataSnapshot,
+    pub signature: Ed25519Signature,
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_storage::{block_tree::BlockTree, BlockReader},
+    block_storage::{
+        block_tree::BlockTree, recently_committed_txns::RecentlyCommittedTxns, BlockReader,
+    },
     counters,
     persistent_liveness_storage::{
         PersistentLivenessStorage, RecoveryData, RootInfo, RootMetadata,
@@ -22,10 +24,13 @@ use libra_crypto::HashValue;
 use libra_logger::prelude::*;
 #[cfg(any(test, feature = "fuzzing"))]
 use libra_types::epoch_state::EpochState;
-use libra_types::{ledger_info::LedgerInfoWithSignatures, transaction::TransactionStatus};
+use libra_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{SignedTransaction, TransactionStatus},
+};
 use std::{
     collections::vec_deque::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
     time::Duration,
 };
 use termion::color::*;
@@ -96,6 +101,8 @@ pub struct BlockStore {
     storage: Arc<dyn PersistentLivenessStorage>,
     /// Used to ensure that any block stored will have a timestamp < the local time
     time_service: Arc<dyn TimeService>,
+    /// The transactions of the latest committed blocks, which are no longer worth proposing
+    recently_committed_txns: Mutex<RecentlyCommittedTxns>,
 }
 
 impl BlockStore {
@@ -104,6 +111,7 @@ impl BlockStore {
         initial_data: RecoveryData,
         state_computer: Arc<dyn StateComputer>,
         max_pruned_blocks_in_mem: usize,
+        recently_committed_txns_capacity: usize,
         time_service: Arc<dyn TimeService>,
     ) -> Self {
         let highest_tc = initial_data.highest_timeout_certificate();
@@ -117,6 +125,7 @@ impl BlockStore {
             state_computer,
             storage,
             max_pruned_blocks_in_mem,
+            recently_committed_txns_capacity,
             time_service,
         )
     }
@@ -130,6 +139,7 @@ impl BlockStore {
         state_computer: Arc<dyn StateComputer>,
         storage: Arc<dyn PersistentLivenessStorage>,
         max_pruned_blocks_in_mem: usize,
+        recently_committed_txns_capacity: usize,
         time_service: Arc<dyn TimeService>,
     ) -> Self {
         let RootInfo(root_block, root_qc, root_li) = root;
@@ -178,6 +188,9 @@ impl BlockStore {
             state_computer,
             storage,
             time_service,
+            recently_committed_txns: Mutex::new(RecentlyCommittedTxns::new(
+                recently_committed_txns_capacity,
+            )),
         };
         for block in blocks {
             block_store
@@ -224,6 +237,12 @@ impl BlockStore {
             .await
             .expect("Failed to persist commit");
         update_counters_for_committed_blocks(&blocks_to_commit);
+        {
+            let mut recently_committed_txns = self.recently_committed_txns.lock().unwrap();
+            for block in &blocks_to_commit {
+                recently_committed_txns.insert_block(block);
+            }
+        }
         debug!("{}Committed{} {}", Fg(Blue), Fg(Reset), *block_to_commit);
         event!("committed",
             "block_id": block_to_commit.id().short_str(),
@@ -231,6 +250,13 @@ impl BlockStore {
             "parent_id": block_to_commit.parent_id().short_str(),
         );
         self.prune_tree(block_to_commit.id());
+        if let Err(e) = self
+            .storage
+            .prune_committed(block_to_commit.epoch(), block_to_commit.round())
+        {
+            // it's fine to fail here, the blocks will be pruned on a later commit.
+            error!("fail to prune committed blocks: {:?}", e);
+        }
         Ok(())
     }
 
@@ -242,6 +268,8 @@ impl BlockStore {
         quorum_certs: Vec<QuorumCert>,
     ) {
         let max_pruned_blocks_in_mem = self.inner.read().unwrap().max_pruned_blocks_in_mem();
+        let recently_committed_txns_capacity =
+            self.recently_committed_txns.lock().unwrap().capacity();
         // Rollover the previous highest TC from the old tree to the new one.
         let prev_htc = self.highest_timeout_cert().map(|tc| tc.as_ref().clone());
         let BlockStore { inner, .. } = Self::build(
@@ -253,6 +281,7 @@ impl BlockStore {
             Arc::clone(&self.state_computer),
             Arc::clone(&self.storage),
             max_pruned_blocks_in_mem,
+            recently_committed_txns_capacity,
             Arc::clone(&self.time_service),
         );
         let to_remove = self.inner.read().unwrap().get_all_block_id();
@@ -371,22 +400,14 @@ impl BlockStore {
     /// prune_tree(B3) should be left with
     /// B3--> B4, root = B3
     ///
-    /// Returns the block ids of the blocks removed.
+    /// Returns the block ids of the blocks removed. They are only removed from memory, the
+    /// storage prunes its blocks by round on commit.
     fn prune_tree(&self, next_root_id: HashValue) -> VecDeque<HashValue> {
         let id_to_remove = self
             .inner
             .read()
             .unwrap()
             .find_blocks_to_prune(next_root_id);
-        if let Err(e) = self
-            .storage
-            .prune_tree(id_to_remove.clone().into_iter().collect())
-        {
-            // it's fine to fail here, as long as the commit succeeds, the next restart will clean
-            // up dangling blocks, and we need to prune the tree to keep the root consistent with
-            // executor.
-            error!("fail to delete block: {:?}", e);
-        }
         self.inner
             .write()
             .unwrap()
@@ -442,6 +463,10 @@ impl BlockReader for BlockStore {
             self.highest_timeout_cert().map(|tc| tc.as_ref().clone()),
         )
     }
+
+    fn is_recently_committed(&self, txn: &SignedTransaction) -> bool {
+        self.recently_committed_txns.lock().unwrap().contains(txn)
+    }
 }
 
 #[cfg(any(test, feature = "fuzzing"))]
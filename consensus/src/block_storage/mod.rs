@@ -5,12 +5,13 @@ use consensus_types::{
     executed_block::ExecutedBlock, quorum_cert::QuorumCert, timeout_certificate::TimeoutCertificate,
 };
 use libra_crypto::HashValue;
-use libra_types::validator_verifier::VerifyError;
+use libra_types::{transaction::SignedTransaction, validator_verifier::VerifyError};
 use std::sync::Arc;
 
 mod block_store;
 mod block_tree;
 mod pending_votes;
+mod recently_committed_txns;
 
 pub use block_store::{sync_manager::BlockRetriever, BlockStore};
 use consensus_types::sync_info::SyncInfo;
@@ -72,4 +73,8 @@ pub trait BlockReader: Send + Sync {
 
     /// Return the combination of highest quorum cert, timeout cert and commit cert.
     fn sync_info(&self) -> SyncInfo;
+
+    /// Return true if a transaction with the sender and sequence number of `txn` was kept by a
+    /// recently committed block, so that including `txn` in a block would only waste space.
+    fn is_recently_committed(&self, txn: &SignedTransaction) -> bool;
 }
@@ -0,0 +1,70 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use consensus_types::executed_block::ExecutedBlock;
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use std::collections::{HashSet, VecDeque};
+
+#[cfg(test)]
+#[path = "recently_committed_txns_test.rs"]
+mod recently_committed_txns_test;
+
+/// The (sender, sequence number) of the transactions kept by the most recently committed blocks,
+/// bounded by a capacity. Mempool is notified of commits asynchronously, so right after a commit
+/// it may still hand out (or peers may re-broadcast) transactions which would only be discarded
+/// on execution, having the sequence number of a committed transaction.
+pub struct RecentlyCommittedTxns {
+    txns: HashSet<(AccountAddress, u64)>,
+    // The same transactions in the order they were committed, to evict the oldest ones first
+    order: VecDeque<(AccountAddress, u64)>,
+    capacity: usize,
+}
+
+impl RecentlyCommittedTxns {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            txns: HashSet::new(),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Adds the transactions kept by a committed block, evicting the oldest ones beyond the
+    /// capacity. Discarded transactions did not use their sequence number, so they are not added.
+    pub fn insert_block(&mut self, block: &ExecutedBlock) {
+        let txns = match block.payload() {
+            Some(txns) => txns,
+            None => return,
+        };
+        // skip the block metadata txn result
+        for (txn, status) in txns
+            .iter()
+            .zip(block.compute_result().compute_status().iter().skip(1))
+        {
+            if let TransactionStatus::Keep(_) = status {
+                let key = (txn.sender(), txn.sequence_number());
+                if self.txns.insert(key) {
+                    self.order.push_back(key);
+                }
+            }
+        }
+        while self.order.len() > self.capacity {
+            if let Some(key) = self.order.pop_front() {
+                self.txns.remove(&key);
+            }
+        }
+    }
+
+    /// Returns true if a transaction with the sender and sequence number of `txn` was committed
+    /// recently.
+    pub fn contains(&self, txn: &SignedTransaction) -> bool {
+        self.txns.contains(&(txn.sender(), txn.sequence_number()))
+    }
+}
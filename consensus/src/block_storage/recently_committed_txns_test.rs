@@ -0,0 +1,75 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_storage::recently_committed_txns::RecentlyCommittedTxns;
+use consensus_types::{
+    block::{
+        block_test_utils::{certificate_for_genesis, random_payload},
+        Block,
+    },
+    common::Payload,
+    executed_block::ExecutedBlock,
+};
+use executor_types::StateComputeResult;
+use libra_crypto::HashValue;
+use libra_types::{
+    transaction::TransactionStatus,
+    validator_signer::ValidatorSigner,
+    vm_error::{StatusCode, VMStatus},
+};
+
+// A committed block whose transactions have the given statuses
+fn committed_block(payload: Payload, statuses: Vec<TransactionStatus>) -> ExecutedBlock {
+    let signer = ValidatorSigner::random(None);
+    let block = Block::new_proposal(payload, 1, 1, certificate_for_genesis(), &signer);
+    // the block metadata txn comes first
+    let mut compute_status = vec![TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))];
+    compute_status.extend(statuses);
+    let compute_result = StateComputeResult::new(
+        HashValue::zero(),
+        vec![],
+        0,
+        vec![],
+        0,
+        None,
+        compute_status,
+        vec![],
+    );
+    ExecutedBlock::new(block, compute_result)
+}
+
+fn kept() -> TransactionStatus {
+    TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+}
+
+#[test]
+fn test_only_kept_txns_are_added() {
+    let payload = random_payload(3);
+    let mut recently_committed = RecentlyCommittedTxns::new(10);
+    recently_committed.insert_block(&committed_block(
+        payload.clone(),
+        vec![
+            kept(),
+            TransactionStatus::Discard(VMStatus::new(StatusCode::SEQUENCE_NUMBER_TOO_NEW)),
+            TransactionStatus::Retry,
+        ],
+    ));
+    assert!(recently_committed.contains(&payload[0]));
+    assert!(!recently_committed.contains(&payload[1]));
+    assert!(!recently_committed.contains(&payload[2]));
+}
+
+#[test]
+fn test_oldest_txns_are_evicted() {
+    let first = random_payload(3);
+    let second = random_payload(2);
+    let mut recently_committed = RecentlyCommittedTxns::new(4);
+    recently_committed.insert_block(&committed_block(first.clone(), vec![kept(); 3]));
+    recently_committed.insert_block(&committed_block(second.clone(), vec![kept(); 2]));
+
+    assert!(!recently_committed.contains(&first[0]));
+    assert!(first[1..]
+        .iter()
+        .all(|txn| recently_committed.contains(txn)));
+    assert!(second.iter().all(|txn| recently_committed.contains(txn)));
+}
@@ -4,6 +4,7 @@
 use crate::{
     counters,
     epoch_manager::EpochManager,
+    health::RoundProgress,
     network::NetworkTask,
     network_interface::{ConsensusNetworkEvents, ConsensusNetworkSender},
     persistent_liveness_storage::StorageWriteProxy,
@@ -11,19 +12,60 @@ use crate::{
     txn_manager::MempoolProxy,
     util::time_service::ClockTimeService,
 };
+use anyhow::Result;
 use channel::libra_channel;
+use consensus_types::common::Round;
 use execution_correctness::ExecutionCorrectnessManager;
-use futures::channel::mpsc;
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use libra_config::config::NodeConfig;
 use libra_logger::prelude::*;
 use libra_mempool::ConsensusRequest;
+use libra_metrics::health::register_health_reporter;
+use libra_time_service::TimeService;
 use libra_types::on_chain_config::OnChainConfigPayload;
+use safety_rules::ConsensusState;
+use serde::Serialize;
 use state_synchronizer::StateSyncClient;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use storage_interface::DbReader;
 use tokio::runtime::{self, Runtime};
 
-/// Helper function to start consensus based on configuration and return the runtime
+/// Rounds back off exponentially on timeouts, so a single round may take several initial timeouts
+const MAX_ROUND_TIMEOUTS: u64 = 20;
+
+/// A snapshot of the state of consensus, for operators
+#[derive(Debug, Serialize)]
+pub struct ConsensusStateDump {
+    pub epoch: u64,
+    /// Whether consensus is catching up from its peers, as it could not recover its state locally
+    pub recovering: bool,
+    pub current_round: Option<Round>,
+    pub highest_quorum_cert_round: Option<Round>,
+    pub highest_commit_cert_round: Option<Round>,
+    /// The state of the safety rules, unless they cannot be reached
+    pub safety_rules: Option<ConsensusState>,
+}
+
+/// Handle to a running consensus for inspecting its state
+#[derive(Clone)]
+pub struct ConsensusHandle {
+    state_requests: mpsc::Sender<oneshot::Sender<ConsensusStateDump>>,
+}
+
+impl ConsensusHandle {
+    /// Returns a snapshot of the state of consensus, once it processed the events received before
+    pub async fn dump_state(&self) -> Result<ConsensusStateDump> {
+        let (callback, response) = oneshot::channel();
+        self.state_requests.clone().send(callback).await?;
+        Ok(response.await?)
+    }
+}
+
+/// Helper function to start consensus based on configuration and return the runtime, with a
+/// handle to inspect it
 pub fn start_consensus(
     node_config: &mut NodeConfig,
     network_sender: ConsensusNetworkSender,
@@ -32,7 +74,8 @@ pub fn start_consensus(
     consensus_to_mempool_sender: mpsc::Sender<ConsensusRequest>,
     libra_db: Arc<dyn DbReader>,
     reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
-) -> Runtime {
+    clock: TimeService,
+) -> (Runtime, ConsensusHandle) {
     let runtime = runtime::Builder::new()
         .thread_name("consensus-")
         .threaded_scheduler()
@@ -46,10 +89,11 @@ pub fn start_consensus(
         execution_correctness_manager.client(),
         state_sync_client,
     ));
-    let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
+    let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone(), clock));
 
     let (timeout_sender, timeout_receiver) = channel::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
     let (self_sender, self_receiver) = channel::new(1_024, &counters::PENDING_SELF_MESSAGES);
+    let (state_requests, state_request_receiver) = mpsc::channel(1);
 
     let epoch_mgr = EpochManager::new(
         node_config,
@@ -64,9 +108,19 @@ pub fn start_consensus(
 
     let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
 
+    let round_progress = RoundProgress::new(Duration::from_millis(
+        MAX_ROUND_TIMEOUTS * node_config.consensus.round_initial_timeout_ms,
+    ));
+    register_health_reporter("consensus", Box::new(move || round_progress.health()));
+
     runtime.spawn(network_task.start());
-    runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, reconfig_events));
+    runtime.spawn(epoch_mgr.start(
+        timeout_receiver,
+        network_receiver,
+        reconfig_events,
+        state_request_receiver,
+    ));
 
     debug!("Consensus started.");
-    runtime
+    (runtime, ConsensusHandle { state_requests })
 }
@@ -4,6 +4,7 @@
 use super::*;
 use consensus_types::block::block_test_utils::certificate_for_genesis;
 use libra_temppath::TempPath;
+use libra_types::validator_signer::ValidatorSigner;
 
 #[test]
 fn test_put_get() {
@@ -48,3 +49,52 @@ fn test_delete_block_and_qc() {
     assert_eq!(db.get_blocks().unwrap().len(), 0);
     assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
 }
+
+#[test]
+fn test_prune_below_round() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    let signer = ValidatorSigner::random(None);
+    let blocks: Vec<_> = (1..=5)
+        .map(|round| Block::new_proposal(vec![], round, round, certificate_for_genesis(), &signer))
+        .collect();
+    let epoch = blocks[0].epoch();
+    db.save_blocks_and_quorum_certificates(blocks.clone(), vec![])
+        .unwrap();
+
+    db.prune_below_round(epoch, 3).unwrap();
+    let rounds: Vec<_> = db.get_blocks().unwrap().iter().map(Block::round).collect();
+    assert_eq!(rounds, vec![3, 4, 5]);
+
+    // The pruned blocks are no longer known by id
+    db.delete_blocks_and_quorum_certificates(vec![blocks[0].id(), blocks[3].id()])
+        .unwrap();
+    let rounds: Vec<_> = db.get_blocks().unwrap().iter().map(Block::round).collect();
+    assert_eq!(rounds, vec![3, 5]);
+
+    // All the blocks of the previous epochs are pruned
+    db.prune_below_round(epoch + 1, 0).unwrap();
+    assert_eq!(db.get_blocks().unwrap().len(), 0);
+}
+
+#[test]
+fn test_blocks_survive_reopen() {
+    let tmp_dir = TempPath::new();
+    let block = Block::make_genesis_block();
+    {
+        let db = ConsensusDB::new(&tmp_dir);
+        db.save_blocks_and_quorum_certificates(
+            vec![block.clone()],
+            vec![certificate_for_genesis()],
+        )
+        .unwrap();
+    }
+    let db = ConsensusDB::new(&tmp_dir);
+    assert_eq!(db.get_blocks().unwrap(), vec![block.clone()]);
+    // The index of keys is rebuilt on open
+    db.delete_blocks_and_quorum_certificates(vec![block.id()])
+        .unwrap();
+    assert_eq!(db.get_blocks().unwrap().len(), 0);
+    assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
+}
@@ -7,34 +7,58 @@ mod schema;
 
 use crate::consensusdb::schema::{
     block::{BlockSchema, SchemaBlock},
+    legacy::{LegacyBlockSchema, LegacyQCSchema},
     quorum_certificate::QCSchema,
     single_entry::{SingleEntryKey, SingleEntrySchema},
+    RoundKey,
 };
 use anyhow::{ensure, Result};
 use consensus_types::{block::Block, quorum_cert::QuorumCert};
 use libra_crypto::HashValue;
 use libra_logger::prelude::*;
-use schema::{BLOCK_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
-use schemadb::{ReadOptions, SchemaBatch, DB, DEFAULT_CF_NAME};
-use std::{collections::HashMap, iter::Iterator, path::Path, time::Instant};
+use libra_types::block_info::Round;
+use schema::{
+    BLOCK_CF_NAME, LEGACY_BLOCK_CF_NAME, LEGACY_QC_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME,
+};
+use schemadb::{ColumnFamilyName, ReadOptions, SchemaBatch, DB, DEFAULT_CF_NAME};
+use std::{collections::HashMap, iter::Iterator, path::Path, sync::Mutex, time::Instant};
 
+/// The consensus db, under the `consensusdb` directory of the data dir.
 pub struct ConsensusDB {
     db: DB,
+    // The keys of the stored blocks and QCs by block id, to delete them by id
+    keys: Mutex<HashMap<HashValue, RoundKey>>,
+}
+
+fn column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* UNUSED CF = */ DEFAULT_CF_NAME,
+        BLOCK_CF_NAME,
+        QC_CF_NAME,
+        SINGLE_ENTRY_CF_NAME,
+        LEGACY_BLOCK_CF_NAME,
+        LEGACY_QC_CF_NAME,
+    ]
 }
 
 impl ConsensusDB {
+    /// Opens the db under `db_root_path`, creating it if needed. The blocks and QCs stored by
+    /// previous versions, keyed by block id only, are migrated to the schemas keyed by round.
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![
-            /* UNUSED CF = */ DEFAULT_CF_NAME,
-            BLOCK_CF_NAME,
-            QC_CF_NAME,
-            SINGLE_ENTRY_CF_NAME,
-        ];
-
         let path = db_root_path.as_ref().join("consensusdb");
         let instant = Instant::now();
-        let db = DB::open(path.clone(), "consensus", column_families)
+        let db = DB::open(path.clone(), "consensus", column_families())
             .expect("ConsensusDB open failed; unable to continue");
+        let consensus_db = Self {
+            db,
+            keys: Mutex::new(HashMap::new()),
+        };
+        consensus_db
+            .migrate_legacy_data()
+            .expect("ConsensusDB migration failed; unable to continue");
+        consensus_db
+            .load_keys()
+            .expect("ConsensusDB read failed; unable to continue");
 
         info!(
             "Opened ConsensusDB at {:?} in {} ms",
@@ -42,9 +66,27 @@ impl ConsensusDB {
             instant.elapsed().as_millis()
         );
 
-        Self { db }
+        consensus_db
     }
 
+    /// Opens the db under `db_root_path` in readonly mode, e.g. to inspect it with the node
+    /// stopped. The db must have been opened by a node of this version before, since the legacy
+    /// data is only migrated by `new`.
+    pub fn open_readonly<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        let db = DB::open_readonly(
+            db_root_path.as_ref().join("consensusdb"),
+            "consensus",
+            column_families(),
+        )?;
+        let consensus_db = Self {
+            db,
+            keys: Mutex::new(HashMap::new()),
+        };
+        consensus_db.load_keys()?;
+        Ok(consensus_db)
+    }
+
+    /// Returns the last vote, the highest timeout certificate, and all the blocks and QCs.
     pub fn get_data(
         &self,
     ) -> Result<(
@@ -55,16 +97,8 @@ impl ConsensusDB {
     )> {
         let last_vote = self.get_last_vote()?;
         let highest_timeout_certificate = self.get_highest_timeout_certificate()?;
-        let consensus_blocks = self
-            .get_blocks()?
-            .into_iter()
-            .map(|(_block_hash, block_content)| block_content)
-            .collect::<Vec<_>>();
-        let consensus_qcs = self
-            .get_quorum_certificates()?
-            .into_iter()
-            .map(|(_block_hash, qc)| qc)
-            .collect::<Vec<_>>();
+        let consensus_blocks = self.get_blocks()?;
+        let consensus_qcs = self.get_quorum_certificates()?;
         Ok((
             last_vote,
             highest_timeout_certificate,
@@ -73,6 +107,7 @@ impl ConsensusDB {
         ))
     }
 
+    /// Saves the serialized highest timeout certificate, replacing the previous one.
     pub fn save_highest_timeout_certificate(
         &self,
         highest_timeout_certificate: Vec<u8>,
@@ -85,12 +120,14 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    /// Saves the serialized last vote, replacing the previous one.
     pub fn save_vote(&self, last_vote: Vec<u8>) -> Result<()> {
         let mut batch = SchemaBatch::new();
         batch.put::<SingleEntrySchema>(&SingleEntryKey::LastVoteMsg, &last_vote)?;
         self.commit(batch)
     }
 
+    /// Saves blocks and QCs atomically.
     pub fn save_blocks_and_quorum_certificates(
         &self,
         block_data: Vec<Block>,
@@ -100,30 +137,72 @@ impl ConsensusDB {
             !block_data.is_empty() || !qc_data.is_empty(),
             "Consensus block and qc data is empty!"
         );
+        let mut keys = self.keys.lock().unwrap();
         let mut batch = SchemaBatch::new();
-        block_data
-            .iter()
-            .map(|block| {
-                batch.put::<BlockSchema>(&block.id(), &SchemaBlock::from_block(block.clone()))
-            })
-            .collect::<Result<()>>()?;
-        qc_data
-            .iter()
-            .map(|qc| batch.put::<QCSchema>(&qc.certified_block().id(), qc))
-            .collect::<Result<()>>()?;
-        self.commit(batch)
+        let mut new_keys = vec![];
+        for block in &block_data {
+            let key = RoundKey::of_block(block);
+            batch.put::<BlockSchema>(&key, &SchemaBlock::from_block(block.clone()))?;
+            new_keys.push(key);
+        }
+        for qc in &qc_data {
+            let key = RoundKey::of_qc(qc);
+            batch.put::<QCSchema>(&key, qc)?;
+            new_keys.push(key);
+        }
+        self.commit(batch)?;
+        keys.extend(new_keys.into_iter().map(|key| (key.id, key)));
+        Ok(())
     }
 
+    /// Deletes the blocks with the given ids and the QCs certifying them. Ids which are not
+    /// stored are ignored.
     pub fn delete_blocks_and_quorum_certificates(&self, block_ids: Vec<HashValue>) -> Result<()> {
         ensure!(!block_ids.is_empty(), "Consensus block ids is empty!");
-        let mut batch = SchemaBatch::new();
-        block_ids
+        let mut keys = self.keys.lock().unwrap();
+        let deleted = block_ids
             .iter()
-            .map(|hash| {
-                batch.delete::<BlockSchema>(hash)?;
-                batch.delete::<QCSchema>(hash)
-            })
-            .collect::<Result<_>>()?;
+            .filter_map(|id| keys.get(id).cloned())
+            .collect::<Vec<_>>();
+        self.delete_keys(&deleted)?;
+        for key in deleted {
+            keys.remove(&key.id);
+        }
+        Ok(())
+    }
+
+    /// Deletes the blocks and QCs of the rounds below `round` in `epoch`, and of all the previous
+    /// epochs.
+    pub fn prune_below_round(&self, epoch: u64, round: Round) -> Result<()> {
+        let mut keys = self.keys.lock().unwrap();
+        let bound = RoundKey::new(epoch, round, HashValue::zero());
+        let pruned = keys
+            .values()
+            .filter(|key| **key < bound)
+            .cloned()
+            .collect::<Vec<_>>();
+        if pruned.is_empty() {
+            return Ok(());
+        }
+        self.delete_keys(&pruned)?;
+        for key in &pruned {
+            keys.remove(&key.id);
+        }
+        debug!(
+            "Pruned {} blocks and quorum certificates below epoch {} round {}",
+            pruned.len(),
+            epoch,
+            round
+        );
+        Ok(())
+    }
+
+    fn delete_keys(&self, keys: &[RoundKey]) -> Result<()> {
+        let mut batch = SchemaBatch::new();
+        for key in keys {
+            batch.delete::<BlockSchema>(key)?;
+            batch.delete::<QCSchema>(key)?;
+        }
         self.commit(batch)
     }
 
@@ -133,8 +212,60 @@ impl ConsensusDB {
         self.db.write_schemas(batch)
     }
 
+    /// Moves the blocks and QCs keyed by block id only, stored by previous versions, to the
+    /// schemas keyed by round, in a single batch.
+    fn migrate_legacy_data(&self) -> Result<()> {
+        let mut batch = SchemaBatch::new();
+        let mut num_migrated = 0;
+
+        let mut iter = self.db.iter::<LegacyBlockSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (id, block) = item?;
+            batch.put::<BlockSchema>(&RoundKey::of_block(block.borrow_into_block()), &block)?;
+            batch.delete::<LegacyBlockSchema>(&id)?;
+            num_migrated += 1;
+        }
+
+        let mut iter = self.db.iter::<LegacyQCSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (id, qc) = item?;
+            batch.put::<QCSchema>(&RoundKey::of_qc(&qc), &qc)?;
+            batch.delete::<LegacyQCSchema>(&id)?;
+            num_migrated += 1;
+        }
+
+        if num_migrated > 0 {
+            self.commit(batch)?;
+            info!(
+                "Migrated {} blocks and quorum certificates to the schemas keyed by round",
+                num_migrated
+            );
+        }
+        Ok(())
+    }
+
+    /// Builds the index of the keys of the stored blocks and QCs by block id.
+    fn load_keys(&self) -> Result<()> {
+        let mut keys = self.keys.lock().unwrap();
+        let mut iter = self.db.iter::<BlockSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (key, _) = item?;
+            keys.insert(key.id, key);
+        }
+        let mut iter = self.db.iter::<QCSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (key, _) = item?;
+            keys.insert(key.id, key);
+        }
+        Ok(())
+    }
+
     /// Get latest timeout certificates (we only store the latest highest timeout certificates).
-    fn get_highest_timeout_certificate(&self) -> Result<Option<Vec<u8>>> {
+    pub fn get_highest_timeout_certificate(&self) -> Result<Option<Vec<u8>>> {
         self.db
             .get::<SingleEntrySchema>(&SingleEntryKey::HighestTimeoutCertificate)
     }
@@ -147,29 +278,31 @@ impl ConsensusDB {
     }
 
     /// Get serialized latest vote (if available)
-    fn get_last_vote(&self) -> Result<Option<Vec<u8>>> {
+    pub fn get_last_vote(&self) -> Result<Option<Vec<u8>>> {
         self.db
             .get::<SingleEntrySchema>(&SingleEntryKey::LastVoteMsg)
     }
 
+    /// Delete the last vote
     pub fn delete_last_vote_msg(&self) -> Result<()> {
         let mut batch = SchemaBatch::new();
         batch.delete::<SingleEntrySchema>(&SingleEntryKey::LastVoteMsg)?;
         self.commit(batch)
     }
 
-    /// Get all consensus blocks.
-    fn get_blocks(&self) -> Result<HashMap<HashValue, Block>> {
+    /// Get all consensus blocks, ordered by epoch and round.
+    pub fn get_blocks(&self) -> Result<Vec<Block>> {
         let mut iter = self.db.iter::<BlockSchema>(ReadOptions::default())?;
         iter.seek_to_first();
-        iter.map(|value| value.and_then(|(k, v)| Ok((k, v.borrow_into_block().clone()))))
-            .collect::<Result<HashMap<HashValue, Block>>>()
+        iter.map(|value| value.map(|(_, v)| v.borrow_into_block().clone()))
+            .collect::<Result<Vec<Block>>>()
     }
 
-    /// Get all consensus QCs.
-    fn get_quorum_certificates(&self) -> Result<HashMap<HashValue, QuorumCert>> {
+    /// Get all consensus QCs, ordered by the epoch and round of the certified block.
+    pub fn get_quorum_certificates(&self) -> Result<Vec<QuorumCert>> {
         let mut iter = self.db.iter::<QCSchema>(ReadOptions::default())?;
         iter.seek_to_first();
-        iter.collect::<Result<HashMap<HashValue, QuorumCert>>>()
+        iter.map(|value| value.map(|(_, qc)| qc))
+            .collect::<Result<Vec<QuorumCert>>>()
     }
 }
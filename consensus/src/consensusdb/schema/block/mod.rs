@@ -3,16 +3,15 @@
 
 //! This module defines physical storage schema for consensus block.
 //!
-//! Serialized block bytes identified by block_hash.
+//! Serialized block bytes identified by the epoch, round and hash of the block.
 //! ```text
 //! |<---key---->|<---value--->|
-//! | block_hash |    block    |
+//! | round_key  |    block    |
 //! ```
 
-use super::BLOCK_CF_NAME;
+use super::{RoundKey, BLOCK_CF_NAME};
 use anyhow::Result;
 use consensus_types::block::Block;
-use libra_crypto::HashValue;
 use schemadb::schema::{KeyCodec, Schema, ValueCodec};
 use std::{cmp, fmt};
 
@@ -20,17 +19,17 @@ pub struct BlockSchema;
 
 impl Schema for BlockSchema {
     const COLUMN_FAMILY_NAME: schemadb::ColumnFamilyName = BLOCK_CF_NAME;
-    type Key = HashValue;
+    type Key = RoundKey;
     type Value = SchemaBlock;
 }
 
-impl KeyCodec<BlockSchema> for HashValue {
+impl KeyCodec<BlockSchema> for RoundKey {
     fn encode_key(&self) -> Result<Vec<u8>> {
-        Ok(self.to_vec())
+        self.encode()
     }
 
     fn decode_key(data: &[u8]) -> Result<Self> {
-        Ok(HashValue::from_slice(data)?)
+        RoundKey::decode(data)
     }
 }
 
@@ -0,0 +1,68 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the physical storage schemas of blocks and quorum certificates before they
+//! were keyed by round. They are only read to migrate the data to the current schemas.
+//!
+//! Serialized block and quorum certificate bytes identified by block_hash.
+//! ```text
+//! |<---key---->|<----value---->|
+//! | block_hash | block / QC    |
+//! ```
+
+use super::{block::SchemaBlock, LEGACY_BLOCK_CF_NAME, LEGACY_QC_CF_NAME};
+use anyhow::Result;
+use consensus_types::quorum_cert::QuorumCert;
+use libra_crypto::HashValue;
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+
+define_schema!(
+    LegacyBlockSchema,
+    HashValue,
+    SchemaBlock,
+    LEGACY_BLOCK_CF_NAME
+);
+define_schema!(LegacyQCSchema, HashValue, QuorumCert, LEGACY_QC_CF_NAME);
+
+impl KeyCodec<LegacyBlockSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<LegacyBlockSchema> for SchemaBlock {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(lcs::to_bytes(self.borrow_into_block())?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(SchemaBlock::from_block(lcs::from_bytes(data)?))
+    }
+}
+
+impl KeyCodec<LegacyQCSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<LegacyQCSchema> for QuorumCert {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(lcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(lcs::from_bytes(data)?)
+    }
+}
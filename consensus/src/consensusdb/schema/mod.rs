@@ -2,15 +2,74 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod block;
+pub(crate) mod legacy;
 pub(crate) mod quorum_certificate;
 pub(crate) mod single_entry;
 
 use anyhow::{ensure, Result};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use consensus_types::{block::Block, quorum_cert::QuorumCert};
+use libra_crypto::HashValue;
+use libra_types::block_info::Round;
 use schemadb::ColumnFamilyName;
+use std::mem::size_of;
 
-pub(super) const BLOCK_CF_NAME: ColumnFamilyName = "block";
-pub(super) const QC_CF_NAME: ColumnFamilyName = "quorum_certificate";
+pub(super) const BLOCK_CF_NAME: ColumnFamilyName = "block_by_round";
+pub(super) const QC_CF_NAME: ColumnFamilyName = "quorum_certificate_by_round";
 pub(super) const SINGLE_ENTRY_CF_NAME: ColumnFamilyName = "single_entry";
+// The column families of blocks and QCs keyed by block id only, migrated when opening the db
+pub(super) const LEGACY_BLOCK_CF_NAME: ColumnFamilyName = "block";
+pub(super) const LEGACY_QC_CF_NAME: ColumnFamilyName = "quorum_certificate";
+
+/// The key of a block, and of the QC certifying it: the epoch and round come first, encoded in
+/// big endian so that the keys are sorted by round, then the block id since several blocks may
+/// share a round.
+/// ```text
+/// |<--epoch-->|<--round-->|<--block_hash-->|
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) struct RoundKey {
+    pub epoch: u64,
+    pub round: Round,
+    pub id: HashValue,
+}
+
+impl RoundKey {
+    pub fn new(epoch: u64, round: Round, id: HashValue) -> Self {
+        Self { epoch, round, id }
+    }
+
+    pub fn of_block(block: &Block) -> Self {
+        Self::new(block.epoch(), block.round(), block.id())
+    }
+
+    pub fn of_qc(qc: &QuorumCert) -> Self {
+        let certified_block = qc.certified_block();
+        Self::new(
+            certified_block.epoch(),
+            certified_block.round(),
+            certified_block.id(),
+        )
+    }
+
+    fn encode(&self) -> Result<Vec<u8>> {
+        let mut encoded = Vec::with_capacity(ROUND_KEY_LEN);
+        encoded.write_u64::<BigEndian>(self.epoch)?;
+        encoded.write_u64::<BigEndian>(self.round)?;
+        encoded.extend_from_slice(&self.id.to_vec());
+        Ok(encoded)
+    }
+
+    fn decode(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, ROUND_KEY_LEN)?;
+        let epoch = data.read_u64::<BigEndian>()?;
+        let round = data.read_u64::<BigEndian>()?;
+        let id = HashValue::from_slice(data)?;
+        Ok(Self::new(epoch, round, id))
+    }
+}
+
+const ROUND_KEY_LEN: usize = size_of::<u64>() + size_of::<Round>() + HashValue::LENGTH;
 
 fn ensure_slice_len_eq(data: &[u8], len: usize) -> Result<()> {
     ensure!(
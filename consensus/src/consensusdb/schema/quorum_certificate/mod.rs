@@ -3,30 +3,30 @@
 
 //! This module defines physical storage schema for consensus quorum certificate (of a block).
 //!
-//! Serialized quorum certificate bytes identified by block_hash.
+//! Serialized quorum certificate bytes identified by the epoch, round and hash of the certified
+//! block.
 //! ```text
 //! |<---key---->|<----value--->|
-//! | block_hash |  QuorumCert  |
+//! | round_key  |  QuorumCert  |
 //! ```
 
-use super::QC_CF_NAME;
+use super::{RoundKey, QC_CF_NAME};
 use anyhow::Result;
 use consensus_types::quorum_cert::QuorumCert;
-use libra_crypto::HashValue;
 use schemadb::{
     define_schema,
     schema::{KeyCodec, ValueCodec},
 };
 
-define_schema!(QCSchema, HashValue, QuorumCert, QC_CF_NAME);
+define_schema!(QCSchema, RoundKey, QuorumCert, QC_CF_NAME);
 
-impl KeyCodec<QCSchema> for HashValue {
+impl KeyCodec<QCSchema> for RoundKey {
     fn encode_key(&self) -> Result<Vec<u8>> {
-        Ok(self.to_vec())
+        self.encode()
     }
 
     fn decode_key(data: &[u8]) -> Result<Self> {
-        Ok(HashValue::from_slice(data)?)
+        RoundKey::decode(data)
     }
 }
 
@@ -8,5 +8,5 @@ use schemadb::schema::assert_encode_decode;
 #[test]
 fn test_encode_decode() {
     let qc = certificate_for_genesis();
-    assert_encode_decode::<QCSchema>(&qc.certified_block().id(), &qc);
+    assert_encode_decode::<QCSchema>(&RoundKey::of_qc(&qc), &qc);
 }
@@ -286,3 +286,21 @@ pub static BLOCK_RETRIEVAL_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Count of the signing and verification jobs queued or running on the crypto pool
+pub static CRYPTO_POOL_QUEUE_DEPTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "libra_consensus_crypto_pool_queue_depth",
+        "Count of the signing and verification jobs queued or running on the crypto pool"
+    )
+    .unwrap()
+});
+
+/// Count of the messages dropped because the crypto pool was full
+pub static CRYPTO_POOL_DROPPED_MSGS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "libra_consensus_crypto_pool_dropped_msgs_count",
+        "Count of the messages dropped because the crypto pool was full"
+    )
+    .unwrap()
+});
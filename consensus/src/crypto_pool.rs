@@ -0,0 +1,102 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use futures::{channel::oneshot, Future, FutureExt};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Crypto pool is full, {0} jobs are pending")]
+pub struct CryptoPoolFull(usize);
+
+/// CryptoPool signs and verifies consensus messages on dedicated threads, so that the event loop
+/// keeps processing timeouts and other events during a burst of messages. The jobs complete
+/// through futures, which the event loop awaits along with its other events.
+#[derive(Clone)]
+pub struct CryptoPool {
+    pool: Arc<rayon::ThreadPool>,
+    queue_size: usize,
+    pending: Arc<AtomicUsize>,
+}
+
+impl CryptoPool {
+    pub fn new(num_threads: usize, queue_size: usize) -> Self {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("consensus-crypto-{}", index))
+            .build()
+            .expect("Building the consensus crypto thread pool should work.");
+        Self {
+            pool: Arc::new(pool),
+            queue_size,
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Runs `job` on the pool regardless of the jobs pending, for the messages of this validator,
+    /// which are never dropped.
+    pub fn spawn<T, F>(&self, job: F) -> impl Future<Output = T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let pending = self.pending.fetch_add(1, Ordering::Relaxed) + 1;
+        counters::CRYPTO_POOL_QUEUE_DEPTH.set(pending as i64);
+
+        let (sender, receiver) = oneshot::channel();
+        let pending = Arc::clone(&self.pending);
+        self.pool.spawn(move || {
+            let result = job();
+            let pending = pending.fetch_sub(1, Ordering::Relaxed) - 1;
+            counters::CRYPTO_POOL_QUEUE_DEPTH.set(pending as i64);
+            // The caller may have given up on the result
+            let _ = sender.send(result);
+        });
+        receiver.map(|result| result.expect("Crypto pool jobs always complete"))
+    }
+
+    /// Runs `job` on the pool unless the queue is full, for the messages of other validators.
+    pub fn try_spawn<T, F>(&self, job: F) -> Result<impl Future<Output = T>, CryptoPoolFull>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let pending = self.pending.load(Ordering::Relaxed);
+        if pending >= self.queue_size {
+            counters::CRYPTO_POOL_DROPPED_MSGS.inc();
+            return Err(CryptoPoolFull(pending));
+        }
+        Ok(self.spawn(job))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_crypto_pool() {
+        let pool = CryptoPool::new(1, 2);
+        assert_eq!(block_on(pool.spawn(|| 1 + 1)), 2);
+
+        // Block the only thread of the pool until two jobs are pending
+        let (sender, receiver) = mpsc::channel::<()>();
+        let blocked = pool.try_spawn(move || receiver.recv().unwrap()).unwrap();
+        let queued = pool.try_spawn(|| 3).unwrap();
+        assert!(pool.try_spawn(|| 4).is_err());
+
+        // Signing jobs are never dropped
+        let signed = pool.spawn(|| 5);
+        sender.send(()).unwrap();
+        block_on(blocked);
+        assert_eq!(block_on(queued), 3);
+        assert_eq!(block_on(signed), 5);
+        assert_eq!(block_on(pool.try_spawn(|| 6).unwrap()), 6);
+    }
+}
@@ -3,7 +3,9 @@
 
 use crate::{
     block_storage::BlockStore,
+    consensus_provider::ConsensusStateDump,
     counters,
+    crypto_pool::CryptoPool,
     liveness::{
         leader_reputation::{ActiveInactiveHeuristic, LeaderReputation, LibraDBBackend},
         proposal_generator::ProposalGenerator,
@@ -25,7 +27,13 @@ use consensus_types::{
     common::{Author, Round},
     epoch_retrieval::EpochRetrievalRequest,
 };
-use futures::{select, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    future::BoxFuture,
+    select,
+    stream::FuturesOrdered,
+    FutureExt, StreamExt,
+};
 use libra_config::config::{ConsensusConfig, ConsensusProposerType, NodeConfig};
 use libra_logger::prelude::*;
 use libra_metrics::monitor;
@@ -34,6 +42,7 @@ use libra_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     on_chain_config::{OnChainConfigPayload, ValidatorSet},
+    validator_verifier::ValidatorVerifier,
 };
 use network::protocols::network::Event;
 use safety_rules::{SafetyRulesManager, TSafetyRules};
@@ -77,8 +86,15 @@ pub struct EpochManager {
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
     processor: Option<RoundProcessor>,
+    crypto_pool: CryptoPool,
+    // The validators of the current epoch, shared with the crypto pool verifying their messages
+    epoch_verifier: Option<Arc<ValidatorVerifier>>,
+    // The messages of the current epoch being verified, in the order they were received
+    pending_verifications: FuturesOrdered<BoxFuture<'static, PendingVerification>>,
 }
 
+type PendingVerification = (AccountAddress, anyhow::Result<VerifiedEvent>);
+
 impl EpochManager {
     pub fn new(
         node_config: &mut NodeConfig,
@@ -93,6 +109,8 @@ impl EpochManager {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
         let safety_rules_manager = SafetyRulesManager::new(node_config);
+        let crypto_pool =
+            CryptoPool::new(config.crypto_pool_threads, config.crypto_pool_queue_size);
         Self {
             author,
             config,
@@ -105,6 +123,9 @@ impl EpochManager {
             storage,
             safety_rules_manager,
             processor: None,
+            crypto_pool,
+            epoch_verifier: None,
+            pending_verifications: FuturesOrdered::new(),
         }
     }
 
@@ -264,6 +285,7 @@ impl EpochManager {
             recovery_data,
             Arc::clone(&self.state_computer),
             self.config.max_pruned_blocks_in_mem,
+            self.config.max_recently_committed_txns,
             Arc::clone(&self.time_service),
         ));
 
@@ -314,6 +336,7 @@ impl EpochManager {
             proposer_election,
             proposal_generator,
             safety_rules,
+            self.crypto_pool.clone(),
             network_sender,
             self.txn_manager.clone(),
             self.storage.clone(),
@@ -348,14 +371,26 @@ impl EpochManager {
         info!("SyncProcessor started");
     }
 
-    pub async fn start_processor(&mut self, payload: OnChainConfigPayload) {
+    pub async fn start_processor(&mut self, payload: OnChainConfigPayload) -> anyhow::Result<()> {
         let validator_set: ValidatorSet = payload
             .get()
-            .expect("failed to get ValidatorSet from payload");
+            .context("[EpochManager] Failed to get ValidatorSet from payload")?;
+        // Refuse to enter an epoch that consensus cannot run, and keep running the current one
+        validator_set.verify().context(format!(
+            "[EpochManager] Invalid validator set for epoch {}",
+            payload.epoch()
+        ))?;
+        // A validator removed from the set still follows the epoch without voting
+        if let Err(e) = validator_set.verify_includes(self.author) {
+            warn!("[EpochManager] Epoch {}: {}", payload.epoch(), e);
+        }
         let epoch_state = EpochState {
             epoch: payload.epoch(),
             verifier: (&validator_set).into(),
         };
+        // The messages of the previous epoch still being verified are dropped
+        self.epoch_verifier = Some(Arc::new(epoch_state.verifier.clone()));
+        self.pending_verifications = FuturesOrdered::new();
 
         match self.storage.start() {
             LivenessStorageData::RecoveryData(initial_data) => {
@@ -366,6 +401,7 @@ impl EpochManager {
                     .await
             }
         }
+        Ok(())
     }
 
     pub async fn process_message(
@@ -374,14 +410,32 @@ impl EpochManager {
         consensus_msg: ConsensusMsg,
     ) -> anyhow::Result<()> {
         if let Some(event) = self.process_epoch(peer_id, consensus_msg).await? {
-            let verified_event = event
-                .verify(&self.epoch_state().verifier)
-                .context("[EpochManager] Verify event")?;
-            self.process_event(peer_id, verified_event).await?;
+            // The signatures are verified on the crypto pool, so that a burst of messages doesn't
+            // delay the other events, e.g., local timeouts
+            let verifier = Arc::clone(
+                self.epoch_verifier
+                    .as_ref()
+                    .expect("[EpochManager] not started yet"),
+            );
+            let verification = self
+                .crypto_pool
+                .try_spawn(move || event.verify(&verifier))
+                .context(format!("[EpochManager] Drop event from {}", peer_id))?;
+            self.pending_verifications
+                .push(verification.map(move |event| (peer_id, event)).boxed());
         }
         Ok(())
     }
 
+    async fn process_verified_event(
+        &mut self,
+        peer_id: AccountAddress,
+        event: anyhow::Result<VerifiedEvent>,
+    ) -> anyhow::Result<()> {
+        let verified_event = event.context("[EpochManager] Verify event")?;
+        self.process_event(peer_id, verified_event).await
+    }
+
     async fn process_epoch(
         &mut self,
         peer_id: AccountAddress,
@@ -450,6 +504,20 @@ impl EpochManager {
         }
     }
 
+    fn dump_state(&mut self) -> ConsensusStateDump {
+        match self.processor_mut() {
+            RoundProcessor::Normal(p) => p.dump_state(),
+            RoundProcessor::Recovery(p) => ConsensusStateDump {
+                epoch: p.epoch_state().epoch,
+                recovering: true,
+                current_round: None,
+                highest_quorum_cert_round: None,
+                highest_commit_cert_round: None,
+                safety_rules: None,
+            },
+        }
+    }
+
     fn processor_mut(&mut self) -> &mut RoundProcessor {
         self.processor
             .as_mut()
@@ -478,28 +546,42 @@ impl EpochManager {
         mut round_timeout_sender_rx: channel::Receiver<Round>,
         mut network_receivers: NetworkReceivers,
         mut reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
+        mut state_requests: mpsc::Receiver<oneshot::Sender<ConsensusStateDump>>,
     ) {
-        // initial start of the processor
-        if let Some(payload) = reconfig_events.next().await {
-            self.start_processor(payload).await;
+        // initial start of the processor, which waits for a valid epoch
+        while let Some(payload) = reconfig_events.next().await {
+            match self.start_processor(payload).await {
+                Ok(()) => break,
+                Err(e) => {
+                    counters::ERROR_COUNT.inc();
+                    error!("{:?}", e);
+                }
+            }
         }
         loop {
             if let Err(e) = monitor!(
                 "main_loop",
                 select! {
                     payload = reconfig_events.select_next_some() => {
-                        monitor!("reconfig", self.start_processor(payload).await);
-                        Ok(())
+                        monitor!("reconfig", self.start_processor(payload).await)
                     }
                     msg = network_receivers.consensus_messages.select_next_some() => {
                         monitor!("process_message", self.process_message(msg.0, msg.1).await)
                     }
+                    (peer_id, event) = self.pending_verifications.select_next_some() => {
+                        monitor!("process_verified_event", self.process_verified_event(peer_id, event).await)
+                    }
                     block_retrieval = network_receivers.block_retrieval.select_next_some() => {
                         monitor!("process_block_retrieval", self.process_block_retrieval(block_retrieval).await)
                     }
                     round = round_timeout_sender_rx.select_next_some() => {
                         monitor!("process_local_timeout", self.process_local_timeout(round).await)
                     }
+                    callback = state_requests.select_next_some() => {
+                        // The requester may have given up waiting
+                        let _ = callback.send(self.dump_state());
+                        Ok(())
+                    }
                 }
             ) {
                 counters::ERROR_COUNT.inc();
@@ -0,0 +1,87 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use libra_metrics::health::ComponentHealth;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// A validator is considered not to participate if its last vote is this many rounds behind
+const MAX_VOTE_LAG: i64 = 10;
+
+/// Tracks whether rounds progress, based on the rounds published in the consensus counters.
+/// Stalls are reported as not ready rather than unhealthy, as restarting a node does not help
+/// when the whole network is stalled.
+pub struct RoundProgress {
+    // The last round observed and when it was first observed
+    last_round: Mutex<(i64, Instant)>,
+    max_round_duration: Duration,
+}
+
+impl RoundProgress {
+    pub fn new(max_round_duration: Duration) -> Self {
+        Self {
+            last_round: Mutex::new((0, Instant::now())),
+            max_round_duration,
+        }
+    }
+
+    pub fn health(&self) -> ComponentHealth {
+        self.check(
+            counters::CURRENT_ROUND.get(),
+            counters::LAST_VOTE_ROUND.get(),
+        )
+    }
+
+    fn check(&self, current_round: i64, last_vote_round: i64) -> ComponentHealth {
+        let since_progress = {
+            let mut last_round = self
+                .last_round
+                .lock()
+                .expect("Unable to lock round progress");
+            if current_round != last_round.0 {
+                *last_round = (current_round, Instant::now());
+            }
+            last_round.1.elapsed()
+        };
+
+        let message = format!(
+            "current round {}, last vote round {}",
+            current_round, last_vote_round
+        );
+        if current_round == 0 {
+            ComponentHealth::not_ready("waiting for the first round")
+        } else if since_progress > self.max_round_duration {
+            ComponentHealth::not_ready(format!(
+                "{}, no progress for {}s",
+                message,
+                since_progress.as_secs()
+            ))
+        } else if current_round - last_vote_round > MAX_VOTE_LAG {
+            ComponentHealth::not_ready(format!("{}, not voting", message))
+        } else {
+            ComponentHealth::healthy(message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_metrics::health::HealthStatus;
+
+    #[test]
+    fn test_round_progress() {
+        let progress = RoundProgress::new(Duration::from_secs(60));
+        assert_eq!(progress.check(0, 0).status, HealthStatus::NotReady);
+        assert_eq!(progress.check(5, 4).status, HealthStatus::Healthy);
+        assert_eq!(progress.check(20, 4).status, HealthStatus::NotReady);
+
+        let progress = RoundProgress::new(Duration::from_secs(0));
+        progress.check(5, 4);
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(progress.check(5, 4).status, HealthStatus::NotReady);
+    }
+}
@@ -13,9 +13,10 @@
 #![recursion_limit = "512"]
 
 mod block_storage;
-mod consensusdb;
 mod counters;
+mod crypto_pool;
 mod epoch_manager;
+mod health;
 mod liveness;
 mod metrics_safety_rules;
 mod network;
@@ -34,6 +35,9 @@ mod util;
 
 /// LibraBFT implementation
 pub mod consensus_provider;
+/// The persistent storage of consensus: the blocks and quorum certificates not pruned yet, keyed
+/// by epoch and round, along with the last vote and the highest timeout certificate.
+pub mod consensusdb;
 /// LibraNet interface.
 pub mod network_interface;
 
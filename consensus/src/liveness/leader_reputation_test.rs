@@ -36,7 +36,14 @@ impl MetadataBackend for MockHistory {
 }
 
 fn create_block(proposer: Author, voters: Vec<&ValidatorSigner>) -> NewBlockEvent {
-    NewBlockEvent::new(0, proposer, voters.iter().map(|v| v.author()).collect(), 0)
+    NewBlockEvent::new(
+        0,
+        proposer,
+        voters.iter().map(|v| v.author()).collect(),
+        0,
+        vec![],
+        vec![],
+    )
 }
 
 #[test]
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_storage::BlockReader, state_replication::TxnManager, util::time_service::TimeService,
+    block_storage::BlockReader, liveness::proposer_election::ProposerElection,
+    state_replication::TxnManager, util::time_service::TimeService,
 };
 use anyhow::{bail, ensure, format_err, Context};
 use consensus_types::{
@@ -73,11 +74,16 @@ impl ProposalGenerator {
 
     /// Reconfiguration rule - we propose empty blocks with parents' timestamp
     /// after reconfiguration until it's committed
-    pub fn generate_reconfig_empty_suffix(&self, round: Round) -> anyhow::Result<BlockData> {
+    pub fn generate_reconfig_empty_suffix(
+        &self,
+        round: Round,
+        proposer_election: &(dyn ProposerElection + Send + Sync),
+    ) -> anyhow::Result<BlockData> {
         let hqc = self.ensure_highest_quorum_cert(round)?;
         Ok(BlockData::new_proposal(
             vec![],
             self.author,
+            proposer_election.get_failed_authors(hqc.certified_block().round(), round),
             round,
             hqc.certified_block().timestamp_usecs(),
             hqc.as_ref().clone(),
@@ -94,7 +100,13 @@ impl ProposalGenerator {
     /// 2. The round is provided by the caller.
     /// 3. In case a given round is not greater than the calculated parent, return an OldRound
     /// error.
-    pub async fn generate_proposal(&mut self, round: Round) -> anyhow::Result<BlockData> {
+    /// The proposers of the rounds skipped since the parent are recorded in the proposal, as
+    /// given by `proposer_election`.
+    pub async fn generate_proposal(
+        &mut self,
+        round: Round,
+        proposer_election: &(dyn ProposerElection + Send + Sync),
+    ) -> anyhow::Result<BlockData> {
         {
             let mut last_round_generated = self.last_round_generated.lock().unwrap();
             if *last_round_generated < round {
@@ -107,7 +119,7 @@ impl ProposalGenerator {
         let hqc = self.ensure_highest_quorum_cert(round)?;
 
         if hqc.certified_block().has_reconfiguration() {
-            return self.generate_reconfig_empty_suffix(round);
+            return self.generate_reconfig_empty_suffix(round, proposer_election);
         }
 
         // One needs to hold the blocks with the references to the payloads while get_block is
@@ -130,15 +142,18 @@ impl ProposalGenerator {
         // the local time exceeds it.
         let block_timestamp = self.time_service.get_current_timestamp();
 
-        let txns = self
+        let mut txns = self
             .txn_manager
             .pull_txns(self.max_block_size, exclude_payload)
             .await
             .context("Fail to retrieve txn")?;
+        // Mempool may not have been notified of the latest commits yet
+        txns.retain(|txn| !self.block_store.is_recently_committed(txn));
 
         Ok(BlockData::new_proposal(
             txns,
             self.author,
+            proposer_election.get_failed_authors(hqc.certified_block().round(), round),
             round,
             block_timestamp.as_micros() as u64,
             hqc.as_ref().clone(),
@@ -3,7 +3,11 @@
 
 use crate::{
     block_storage::BlockReader,
-    liveness::proposal_generator::ProposalGenerator,
+    liveness::{
+        proposal_generator::ProposalGenerator,
+        proposer_election::{ProposerElection, MAX_FAILED_AUTHORS},
+        rotating_proposer_election::RotatingProposer,
+    },
     test_utils::{build_empty_tree, MockTransactionManager, TreeInserter},
     util::mock_time_service::SimulatedTimeService,
 };
@@ -14,6 +18,7 @@ use std::sync::Arc;
 #[tokio::test]
 async fn test_proposal_generation_empty_tree() {
     let signer = ValidatorSigner::random(None);
+    let proposer_election = RotatingProposer::new(vec![signer.author()], 1);
     let block_store = build_empty_tree();
     let mut proposal_generator = ProposalGenerator::new(
         signer.author(),
@@ -25,20 +30,27 @@ async fn test_proposal_generation_empty_tree() {
     let genesis = block_store.root();
 
     // Generate proposals for an empty tree.
-    let proposal_data = proposal_generator.generate_proposal(1).await.unwrap();
+    let proposal_data = proposal_generator
+        .generate_proposal(1, &proposer_election)
+        .await
+        .unwrap();
     let proposal = Block::new_proposal_from_block_data(proposal_data, &signer);
     assert_eq!(proposal.parent_id(), genesis.id());
     assert_eq!(proposal.round(), 1);
     assert_eq!(proposal.quorum_cert().certified_block().id(), genesis.id());
 
     // Duplicate proposals on the same round are not allowed
-    let proposal_err = proposal_generator.generate_proposal(1).await.err();
+    let proposal_err = proposal_generator
+        .generate_proposal(1, &proposer_election)
+        .await
+        .err();
     assert!(proposal_err.is_some());
 }
 
 #[tokio::test]
 async fn test_proposal_generation_parent() {
     let mut inserter = TreeInserter::default();
+    let proposer_election = RotatingProposer::new(vec![inserter.signer().author()], 1);
     let block_store = inserter.block_store();
     let mut proposal_generator = ProposalGenerator::new(
         inserter.signer().author(),
@@ -55,7 +67,7 @@ async fn test_proposal_generation_parent() {
     // generate proposals for an empty tree.
     assert_eq!(
         proposal_generator
-            .generate_proposal(10)
+            .generate_proposal(10, &proposer_election)
             .await
             .unwrap()
             .parent_id(),
@@ -64,14 +76,20 @@ async fn test_proposal_generation_parent() {
 
     // Once a1 is certified, it should be the one to choose from
     inserter.insert_qc_for_block(a1.as_ref(), None);
-    let a1_child_res = proposal_generator.generate_proposal(11).await.unwrap();
+    let a1_child_res = proposal_generator
+        .generate_proposal(11, &proposer_election)
+        .await
+        .unwrap();
     assert_eq!(a1_child_res.parent_id(), a1.id());
     assert_eq!(a1_child_res.round(), 11);
     assert_eq!(a1_child_res.quorum_cert().certified_block().id(), a1.id());
 
     // Once b1 is certified, it should be the one to choose from
     inserter.insert_qc_for_block(b1.as_ref(), None);
-    let b1_child_res = proposal_generator.generate_proposal(12).await.unwrap();
+    let b1_child_res = proposal_generator
+        .generate_proposal(12, &proposer_election)
+        .await
+        .unwrap();
     assert_eq!(b1_child_res.parent_id(), b1.id());
     assert_eq!(b1_child_res.round(), 12);
     assert_eq!(b1_child_res.quorum_cert().certified_block().id(), b1.id());
@@ -80,6 +98,7 @@ async fn test_proposal_generation_parent() {
 #[tokio::test]
 async fn test_old_proposal_generation() {
     let mut inserter = TreeInserter::default();
+    let proposer_election = RotatingProposer::new(vec![inserter.signer().author()], 1);
     let block_store = inserter.block_store();
     let mut proposal_generator = ProposalGenerator::new(
         inserter.signer().author(),
@@ -92,6 +111,56 @@ async fn test_old_proposal_generation() {
     let a1 = inserter.insert_block_with_qc(certificate_for_genesis(), &genesis, 1);
     inserter.insert_qc_for_block(a1.as_ref(), None);
 
-    let proposal_err = proposal_generator.generate_proposal(1).await.err();
+    let proposal_err = proposal_generator
+        .generate_proposal(1, &proposer_election)
+        .await
+        .err();
     assert!(proposal_err.is_some());
 }
+
+#[tokio::test]
+async fn test_proposal_generation_failed_authors() {
+    let mut inserter = TreeInserter::default();
+    let other = ValidatorSigner::random([1; 32]);
+    let proposer_election =
+        RotatingProposer::new(vec![inserter.signer().author(), other.author()], 1);
+    let block_store = inserter.block_store();
+    let mut proposal_generator = ProposalGenerator::new(
+        inserter.signer().author(),
+        block_store.clone(),
+        Arc::new(MockTransactionManager::new(None)),
+        Arc::new(SimulatedTimeService::new()),
+        1,
+    );
+    let genesis = block_store.root();
+
+    // No round was skipped since genesis
+    let proposal = proposal_generator
+        .generate_proposal(1, &proposer_election)
+        .await
+        .unwrap();
+    assert!(proposal.failed_authors().is_empty());
+
+    // The proposers of rounds 2 to 4 failed to get a block certified
+    let a1 = inserter.insert_block_with_qc(certificate_for_genesis(), &genesis, 1);
+    inserter.insert_qc_for_block(a1.as_ref(), None);
+    let proposal = proposal_generator
+        .generate_proposal(5, &proposer_election)
+        .await
+        .unwrap();
+    assert_eq!(
+        proposal.failed_authors(),
+        &[
+            proposer_election.get_valid_proposer(2),
+            proposer_election.get_valid_proposer(3),
+            proposer_election.get_valid_proposer(4),
+        ]
+    );
+
+    // Only the most recent failed rounds are recorded
+    let proposal = proposal_generator
+        .generate_proposal(MAX_FAILED_AUTHORS + 10, &proposer_election)
+        .await
+        .unwrap();
+    assert_eq!(proposal.failed_authors().len() as u64, MAX_FAILED_AUTHORS);
+}
@@ -6,6 +6,10 @@ use consensus_types::{
     common::{Author, Round},
 };
 
+/// The maximum number of failed proposers recorded in a block, the ones of the most recent rounds
+/// are kept.
+pub const MAX_FAILED_AUTHORS: u64 = 10;
+
 /// ProposerElection incorporates the logic of choosing a leader among multiple candidates.
 /// We are open to a possibility for having multiple proposers per round, the ultimate choice
 /// of a proposal is exposed by the election protocol via the stream of proposals.
@@ -27,6 +31,16 @@ pub trait ProposerElection {
             self.is_valid_proposer(author, block.round())
         })
     }
+
+    /// Return the valid proposers of the rounds between `parent_round` and `round` (both
+    /// excluded), which failed to produce a certified block. Only the last MAX_FAILED_AUTHORS
+    /// rounds are considered.
+    fn get_failed_authors(&self, parent_round: Round, round: Round) -> Vec<Author> {
+        let start = std::cmp::max(parent_round + 1, round.saturating_sub(MAX_FAILED_AUTHORS));
+        (start..round)
+            .map(|round| self.get_valid_proposer(round))
+            .collect()
+    }
 }
 
 // next continuously mutates a state and returns a u64-index
@@ -21,6 +21,7 @@ use network::{
     protocols::{
         network::{NetworkEvents, NetworkSender, NewNetworkSender},
         rpc::error::RpcError,
+        wire::handshake::v1::ApplicationVersions,
     },
     ProtocolId,
 };
@@ -87,6 +88,15 @@ pub fn network_endpoint_config() -> (
     )
 }
 
+/// The versions of the consensus message formats this node supports, advertised to peers during
+/// the network handshake.
+pub fn supported_versions() -> ApplicationVersions {
+    let mut versions = ApplicationVersions::default();
+    versions.add(ProtocolId::ConsensusRpc, vec![0]);
+    versions.add(ProtocolId::ConsensusDirectSend, vec![0]);
+    versions
+}
+
 impl NewNetworkSender for ConsensusNetworkSender {
     /// Returns a Sender that only sends for the `CONSENSUS_DIRECT_SEND_PROTOCOL` and
     /// `CONSENSUS_RPC_PROTOCOL` ProtocolId.
@@ -30,6 +30,10 @@ pub trait PersistentLivenessStorage: Send + Sync {
     /// Delete the corresponding blocks and quorum certs atomically.
     fn prune_tree(&self, block_ids: Vec<HashValue>) -> Result<()>;
 
+    /// Delete the blocks and quorum certs older than the committed block of `epoch` and `round`,
+    /// possibly keeping the ones of a window of rounds below it.
+    fn prune_committed(&self, epoch: u64, round: Round) -> Result<()>;
+
     /// Persist consensus' state
     fn save_vote(&self, vote: &Vote) -> Result<()>;
 
@@ -274,12 +278,18 @@ impl RecoveryData {
 pub struct StorageWriteProxy {
     db: Arc<ConsensusDB>,
     libra_db: Arc<dyn DbReader>,
+    // The number of rounds below the committed one whose blocks and quorum certs are kept
+    pruning_window: u64,
 }
 
 impl StorageWriteProxy {
     pub fn new(config: &NodeConfig, libra_db: Arc<dyn DbReader>) -> Self {
         let db = Arc::new(ConsensusDB::new(config.storage.dir()));
-        StorageWriteProxy { db, libra_db }
+        StorageWriteProxy {
+            db,
+            libra_db,
+            pruning_window: config.consensus.consensusdb_pruning_window,
+        }
     }
 }
 
@@ -301,6 +311,11 @@ impl PersistentLivenessStorage for StorageWriteProxy {
         Ok(())
     }
 
+    fn prune_committed(&self, epoch: u64, round: Round) -> Result<()> {
+        self.db
+            .prune_below_round(epoch, round.saturating_sub(self.pruning_window))
+    }
+
     fn save_vote(&self, vote: &Vote) -> Result<()> {
         self.db.save_vote(lcs::to_bytes(vote)?)
     }
@@ -3,7 +3,9 @@
 
 use crate::{
     block_storage::{BlockReader, BlockRetriever, BlockStore, VoteReceptionResult},
+    consensus_provider::ConsensusStateDump,
     counters,
+    crypto_pool::CryptoPool,
     liveness::{
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
@@ -30,12 +32,17 @@ use consensus_types::{
 };
 use debug_interface::prelude::*;
 use libra_logger::prelude::*;
+use libra_mempool::txn_latency::{self, TxnStage};
 use libra_security_logger::{security_log, SecurityEvent};
 use libra_types::{epoch_state::EpochState, validator_verifier::ValidatorVerifier};
 #[cfg(test)]
 use safety_rules::ConsensusState;
 use safety_rules::TSafetyRules;
-use std::{sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
 use termion::color::*;
 
 pub enum UnverifiedEvent {
@@ -176,7 +183,9 @@ pub struct RoundManager {
     round_state: RoundState,
     proposer_election: Box<dyn ProposerElection + Send + Sync>,
     proposal_generator: ProposalGenerator,
-    safety_rules: MetricsSafetyRules,
+    // Shared with the crypto pool, which signs the messages of this validator
+    safety_rules: Arc<Mutex<MetricsSafetyRules>>,
+    crypto_pool: CryptoPool,
     network: NetworkSender,
     txn_manager: Arc<dyn TxnManager>,
     storage: Arc<dyn PersistentLivenessStorage>,
@@ -190,6 +199,7 @@ impl RoundManager {
         proposer_election: Box<dyn ProposerElection + Send + Sync>,
         proposal_generator: ProposalGenerator,
         safety_rules: MetricsSafetyRules,
+        crypto_pool: CryptoPool,
         network: NetworkSender,
         txn_manager: Arc<dyn TxnManager>,
         storage: Arc<dyn PersistentLivenessStorage>,
@@ -200,7 +210,8 @@ impl RoundManager {
             round_state,
             proposer_election,
             proposal_generator,
-            safety_rules,
+            safety_rules: Arc::new(Mutex::new(safety_rules)),
+            crypto_pool,
             txn_manager,
             network,
             storage,
@@ -211,6 +222,26 @@ impl RoundManager {
         BlockRetriever::new(self.network.clone(), author)
     }
 
+    fn safety_rules(&self) -> MutexGuard<MetricsSafetyRules> {
+        self.safety_rules
+            .lock()
+            .expect("SafetyRules lock is poisoned")
+    }
+
+    /// Signs a message of this validator with SafetyRules on the crypto pool, the event loop
+    /// waits for the signature without holding its thread.
+    fn sign<T, F>(&self, sign: F) -> impl Future<Output = T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut MetricsSafetyRules) -> T + Send + 'static,
+    {
+        let safety_rules = Arc::clone(&self.safety_rules);
+        self.crypto_pool.spawn(move || {
+            let mut safety_rules = safety_rules.lock().expect("SafetyRules lock is poisoned");
+            sign(&mut safety_rules)
+        })
+    }
+
     /// Leader:
     ///
     /// This event is triggered by a new quorum certificate at the previous round or a
@@ -257,9 +288,11 @@ impl RoundManager {
         // Proposal generator will ensure that at most one proposal is generated per round
         let proposal = self
             .proposal_generator
-            .generate_proposal(new_round_event.round)
+            .generate_proposal(new_round_event.round, self.proposer_election.as_ref())
+            .await?;
+        let signed_proposal = self
+            .sign(move |safety_rules| safety_rules.sign_proposal(proposal))
             .await?;
-        let signed_proposal = self.safety_rules.sign_proposal(proposal)?;
         self.txn_manager.trace_transactions(&signed_proposal);
         trace_edge!("parent_proposal", {"block", signed_proposal.parent_id()}, {"block", signed_proposal.id()});
         trace_event!("round_manager::generate_proposal", {"block", signed_proposal.id()});
@@ -410,8 +443,8 @@ impl RoundManager {
         if !timeout_vote.is_timeout() {
             let timeout = timeout_vote.timeout();
             let signature = self
-                .safety_rules
-                .sign_timeout(&timeout)
+                .sign(move |safety_rules| safety_rules.sign_timeout(&timeout))
+                .await
                 .context("[RoundManager] SafetyRules signs timeout")?;
             timeout_vote.add_timeout_signature(signature);
         }
@@ -432,7 +465,8 @@ impl RoundManager {
     }
 
     /// This function processes a proposal for the current round:
-    /// 1. Filter if it's proposed by valid proposer.
+    /// 1. Filter if it's proposed by valid proposer, records the proposers which failed since its
+    /// parent, and doesn't include recently committed transactions.
     /// 2. Execute and add it to a block store.
     /// 3. Try to vote for it following the safety rules.
     /// 4. In case a validator chooses to vote, send the vote to the representatives at the next
@@ -447,6 +481,28 @@ impl RoundManager {
             proposal,
         );
 
+        let expected_failed_authors = self.proposer_election.get_failed_authors(
+            proposal.quorum_cert().certified_block().round(),
+            proposal.round(),
+        );
+        ensure!(
+            proposal.failed_authors() == expected_failed_authors.as_slice(),
+            "[RoundManager] Proposal {} has failed authors {:?}, expected {:?}",
+            proposal,
+            proposal.failed_authors(),
+            expected_failed_authors,
+        );
+
+        if let Some(payload) = proposal.payload() {
+            ensure!(
+                !payload
+                    .iter()
+                    .any(|txn| self.block_store.is_recently_committed(txn)),
+                "[RoundManager] Proposal {} includes recently committed transactions",
+                proposal,
+            );
+        }
+
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
         ensure!(
@@ -488,10 +544,12 @@ impl RoundManager {
     /// * return a VoteMsg with the LedgerInfo to be committed in case the vote gathers QC.
     async fn execute_and_vote(&mut self, proposed_block: Block) -> anyhow::Result<Vote> {
         trace_code_block!("round_manager::execute_and_vote", {"block", proposed_block.id()});
+        record_txn_stage(&proposed_block, TxnStage::Included);
         let executed_block = self
             .block_store
             .execute_and_insert_block(proposed_block)
             .context("[RoundManager] Failed to execute_and_insert the block")?;
+        record_txn_stage(executed_block.block(), TxnStage::Executed);
         // notify mempool about failed txn
         let compute_result = executed_block.compute_result();
         if let Err(e) = self
@@ -514,8 +572,10 @@ impl RoundManager {
 
         let maybe_signed_vote_proposal = executed_block.maybe_signed_vote_proposal();
         let vote = self
-            .safety_rules
-            .construct_and_sign_vote(&maybe_signed_vote_proposal)
+            .sign(move |safety_rules| {
+                safety_rules.construct_and_sign_vote(&maybe_signed_vote_proposal)
+            })
+            .await
             .context(format!(
                 "[RoundManager] SafetyRules {}Rejected{} {}",
                 Fg(Red),
@@ -523,7 +583,7 @@ impl RoundManager {
                 executed_block.block()
             ))?;
 
-        let consensus_state = self.safety_rules.consensus_state()?;
+        let consensus_state = self.safety_rules().consensus_state()?;
         counters::LAST_VOTE_ROUND.set(consensus_state.last_voted_round() as i64);
         counters::PREFERRED_BLOCK_ROUND.set(consensus_state.preferred_round() as i64);
         self.storage
@@ -681,7 +741,26 @@ impl RoundManager {
     /// Inspect the current consensus state.
     #[cfg(test)]
     pub fn consensus_state(&mut self) -> ConsensusState {
-        self.safety_rules.consensus_state().unwrap()
+        self.safety_rules().consensus_state().unwrap()
+    }
+
+    /// Takes a snapshot of the state of consensus for operators.
+    pub fn dump_state(&mut self) -> ConsensusStateDump {
+        ConsensusStateDump {
+            epoch: self.epoch_state.epoch,
+            recovering: false,
+            current_round: Some(self.round_state.current_round()),
+            highest_quorum_cert_round: Some(
+                self.block_store
+                    .highest_quorum_cert()
+                    .certified_block()
+                    .round(),
+            ),
+            highest_commit_cert_round: Some(
+                self.block_store.highest_commit_cert().commit_info().round(),
+            ),
+            safety_rules: self.safety_rules().consensus_state().ok(),
+        }
     }
 
     pub fn epoch_state(&self) -> &EpochState {
@@ -692,3 +771,12 @@ impl RoundManager {
         &self.round_state
     }
 }
+
+/// Records the stage reached by the transactions in the block for latency metrics
+fn record_txn_stage(block: &Block, stage: TxnStage) {
+    if let Some(txns) = block.payload() {
+        for txn in txns {
+            txn_latency::record_txn_stage(txn.sender(), txn.sequence_number(), stage);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     block_storage::BlockStore,
+    crypto_pool::CryptoPool,
     liveness::{
         proposal_generator::ProposalGenerator,
         rotating_proposer_election::RotatingProposer,
@@ -69,6 +70,7 @@ fn build_empty_store(
         initial_data,
         Arc::new(EmptyStateComputer),
         10, // max pruned blocks in mem
+        10, // max recently committed txns
         Arc::new(SimulatedTimeService::new()),
     ))
 }
@@ -161,6 +163,7 @@ fn create_node_for_fuzzing() -> RoundManager {
         proposer_election,
         proposal_generator,
         MetricsSafetyRules::new(Box::new(safety_rules)),
+        CryptoPool::new(1, 1024),
         network,
         Arc::new(MockTransactionManager::new(None)),
         storage,
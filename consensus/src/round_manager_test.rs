@@ -3,6 +3,7 @@
 
 use crate::{
     block_storage::{BlockReader, BlockStore},
+    crypto_pool::CryptoPool,
     liveness::{
         proposal_generator::ProposalGenerator,
         proposer_election::ProposerElection,
@@ -27,6 +28,7 @@ use consensus_types::{
         block_test_utils::{certificate_for_genesis, gen_test_certificate},
         Block,
     },
+    block_data::BlockData,
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalStatus},
     common::{Author, Payload},
     proposal_msg::ProposalMsg,
@@ -171,13 +173,17 @@ impl NodeSetup {
             commit_cb_sender,
             Arc::clone(&storage),
         ));
-        let time_service = Arc::new(ClockTimeService::new(executor));
+        let time_service = Arc::new(ClockTimeService::new(
+            executor,
+            libra_time_service::TimeService::real(),
+        ));
 
         let block_store = Arc::new(BlockStore::new(
             storage.clone(),
             initial_data,
             state_computer,
             10, // max pruned blocks in mem
+            10, // max recently committed txns
             time_service.clone(),
         ));
 
@@ -202,6 +208,7 @@ impl NodeSetup {
             proposer_election,
             proposal_generator,
             safety_rules,
+            CryptoPool::new(1, 1024),
             network,
             Arc::new(MockTransactionManager::new(None)),
             storage.clone(),
@@ -496,7 +503,18 @@ fn new_round_on_timeout_certificate() {
         .unwrap();
     let genesis_qc = certificate_for_genesis();
     let correct_block = Block::new_proposal(vec![], 1, 1, genesis_qc.clone(), &node.signer);
-    let block_skip_round = Block::new_proposal(vec![], 2, 2, genesis_qc.clone(), &node.signer);
+    // The proposer of round 1 is recorded as failed
+    let block_skip_round = Block::new_proposal_from_block_data(
+        BlockData::new_proposal(
+            vec![],
+            node.signer.author(),
+            vec![node.signer.author()],
+            2,
+            2,
+            genesis_qc.clone(),
+        ),
+        &node.signer,
+    );
     let timeout = Timeout::new(1, 1);
     let timeout_signature = timeout.sign(&node.signer);
 
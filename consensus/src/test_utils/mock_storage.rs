@@ -13,6 +13,7 @@ use consensus_types::{
 };
 use libra_crypto::HashValue;
 use libra_types::{
+    block_info::Round,
     epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     on_chain_config::ValidatorSet,
@@ -212,6 +213,22 @@ impl PersistentLivenessStorage for MockStorage {
         Ok(())
     }
 
+    fn prune_committed(&self, epoch: u64, round: Round) -> Result<()> {
+        self.shared_storage
+            .block
+            .lock()
+            .unwrap()
+            .retain(|_, block| (block.epoch(), block.round()) >= (epoch, round));
+        self.shared_storage.qc.lock().unwrap().retain(|_, qc| {
+            let certified_block = qc.certified_block();
+            (certified_block.epoch(), certified_block.round()) >= (epoch, round)
+        });
+        if let Err(e) = self.verify_consistency() {
+            panic!("invalid db after prune committed: {}", e);
+        }
+        Ok(())
+    }
+
     fn save_vote(&self, last_vote: &Vote) -> Result<()> {
         self.shared_storage
             .last_vote
@@ -287,6 +304,10 @@ impl PersistentLivenessStorage for EmptyStorage {
         Ok(())
     }
 
+    fn prune_committed(&self, _: u64, _: Round) -> Result<()> {
+        Ok(())
+    }
+
     fn save_vote(&self, _: &Vote) -> Result<()> {
         Ok(())
     }
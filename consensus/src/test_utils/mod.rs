@@ -78,6 +78,7 @@ pub fn build_empty_tree() -> Arc<BlockStore> {
         initial_data,
         Arc::new(EmptyStateComputer),
         10, // max pruned blocks in mem
+        10, // max recently committed txns
         Arc::new(SimulatedTimeService::new()),
     ))
 }
@@ -115,7 +115,14 @@ impl SMRNode {
         let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
 
         runtime.spawn(network_task.start());
-        runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver, reconfig_events));
+        // The state of consensus is not inspected
+        let (_, state_request_receiver) = mpsc::channel(1);
+        runtime.spawn(epoch_mgr.start(
+            timeout_receiver,
+            network_receiver,
+            reconfig_events,
+            state_request_receiver,
+        ));
         Self {
             config,
             smr_id,
@@ -4,15 +4,16 @@
 use channel::libra_channel::Receiver;
 use libra_types::{
     account_config::NewEpochEvent,
-    on_chain_config::{OnChainConfigPayload, ON_CHAIN_CONFIG_REGISTRY},
+    on_chain_config::{OnChainConfigPayload, ValidatorSet},
 };
-use subscription_service::ReconfigSubscription;
+use subscription_service::{ReconfigSubscription, SubscriptionBundle};
 
 /// Creates consensus's subscription to reconfiguration notification from state sync
 pub fn gen_consensus_reconfig_subscription(
 ) -> (ReconfigSubscription, Receiver<(), OnChainConfigPayload>) {
-    ReconfigSubscription::subscribe_all(
-        ON_CHAIN_CONFIG_REGISTRY.to_vec(),
-        vec![NewEpochEvent::event_key()],
+    ReconfigSubscription::subscribe(
+        SubscriptionBundle::default()
+            .with_config::<ValidatorSet>()
+            .with_event(NewEpochEvent::event_key()),
     )
 }
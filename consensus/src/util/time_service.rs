@@ -5,7 +5,6 @@ use futures::{Future, FutureExt, SinkExt};
 use libra_logger::prelude::*;
 use std::{
     pin::Pin,
-    thread,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -99,13 +98,16 @@ where
 /// TimeService implementation that uses actual clock to schedule tasks
 pub struct ClockTimeService {
     executor: Handle,
+    // The clock of the node, which tests may have moved forward
+    clock: libra_time_service::TimeService,
 }
 
 impl ClockTimeService {
-    /// Creates new TimeService that runs tasks based on actual clock
+    /// Creates new TimeService that runs tasks based on actual clock, and reads the current time
+    /// from `clock`, which tests may move forward.
     /// It needs executor to schedule internal tasks that facilitates it's work
-    pub fn new(executor: Handle) -> ClockTimeService {
-        ClockTimeService { executor }
+    pub fn new(executor: Handle, clock: libra_time_service::TimeService) -> ClockTimeService {
+        ClockTimeService { executor, clock }
     }
 }
 
@@ -119,11 +121,11 @@ impl TimeService for ClockTimeService {
     }
 
     fn get_current_timestamp(&self) -> Duration {
-        duration_since_epoch()
+        self.clock.now()
     }
 
     fn sleep(&self, t: Duration) {
-        thread::sleep(t)
+        self.clock.sleep(t)
     }
 }
 
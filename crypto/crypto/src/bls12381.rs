@@ -0,0 +1,522 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for the BLS signature scheme over the BLS12-381 pairing-friendly
+//! curve, following the proof-of-possession variant of the
+//! [IETF draft](https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02) in its
+//! "minimal-pubkey-size" flavour: public keys are 48-byte points in G1 and signatures are
+//! 96-byte points in G2.
+//!
+//! Signatures from different signers on the same message can be aggregated into a single
+//! signature (see [`AggregateSignature`]), which is verified against the signers' public keys.
+//! To prevent rogue-key attacks, every public key taking part in an aggregate verification must
+//! first have been vetted with its [`ProofOfPossession`].
+//!
+//! # Examples
+//!
+//! ```
+//! use libra_crypto::hash::{CryptoHasher, TestOnlyHasher};
+//! use libra_crypto::{
+//!     bls12381::*,
+//!     traits::{AggregateSignature, Signature, SigningKey, Uniform},
+//! };
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let mut hasher = TestOnlyHasher::default();
+//! hasher.update("Test message".as_bytes());
+//! let hashed_message = hasher.finish();
+//!
+//! let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+//! let private_keys: Vec<_> = (0..3).map(|_| BLS12381PrivateKey::generate(&mut rng)).collect();
+//! let public_keys: Vec<BLS12381PublicKey> = private_keys.iter().map(|k| k.into()).collect();
+//! for (private_key, public_key) in private_keys.iter().zip(public_keys.iter()) {
+//!     let pop = ProofOfPossession::create(private_key);
+//!     assert!(pop.verify(public_key).is_ok());
+//! }
+//!
+//! let signatures: Vec<_> = private_keys
+//!     .iter()
+//!     .map(|k| k.sign_message(&hashed_message))
+//!     .collect();
+//! let aggregate = BLS12381Signature::aggregate(signatures).unwrap();
+//! let keys: Vec<_> = public_keys.iter().collect();
+//! assert!(aggregate.verify_aggregate(&hashed_message, &keys).is_ok());
+//! ```
+//! **Note**: The above example generates a private key using a private function intended only for
+//! testing purposes. Production code should find an alternate means for secure key generation.
+use crate::{
+    hash::{CryptoHash, CryptoHasher},
+    traits::*,
+    HashValue,
+};
+use anyhow::{anyhow, Result};
+use blst::BLST_ERROR;
+use core::convert::TryFrom;
+use libra_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use serde::Serialize;
+use std::fmt;
+
+/// The length of the BLS12381PrivateKey
+pub const BLS12381_PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the BLS12381PublicKey (a compressed G1 point)
+pub const BLS12381_PUBLIC_KEY_LENGTH: usize = 48;
+/// The length of the BLS12381Signature (a compressed G2 point)
+pub const BLS12381_SIGNATURE_LENGTH: usize = 96;
+
+/// Domain separation tag used when hashing messages to G2 for signing.
+const DST_BLS_SIG: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// Domain separation tag used when hashing public keys to G2 for proofs of possession.
+const DST_BLS_POP: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// A BLS12-381 private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct BLS12381PrivateKey(blst::min_pk::SecretKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(BLS12381PrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for BLS12381PrivateKey {
+    fn clone(&self) -> Self {
+        let serialized: &[u8] = &(self.to_bytes());
+        BLS12381PrivateKey::try_from(serialized).unwrap()
+    }
+}
+
+/// A BLS12-381 public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct BLS12381PublicKey(blst::min_pk::PublicKey);
+
+/// A BLS12-381 signature, either from a single signer or aggregated from several signers
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct BLS12381Signature(blst::min_pk::Signature);
+
+/// A proof that the owner of a BLS12-381 public key knows the corresponding private key.
+///
+/// It is a signature on the public key itself under a dedicated domain separation tag. Public
+/// keys must have their proof of possession verified before being used in aggregate verification.
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct ProofOfPossession(blst::min_pk::Signature);
+
+impl BLS12381PrivateKey {
+    /// The length of the BLS12381PrivateKey
+    pub const LENGTH: usize = BLS12381_PRIVATE_KEY_LENGTH;
+
+    /// Serialize a BLS12381PrivateKey.
+    pub fn to_bytes(&self) -> [u8; BLS12381_PRIVATE_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Private function aimed at minimizing code duplication between sign
+    /// methods of the SigningKey implementation. This should remain private.
+    fn sign_arbitrary_message(&self, message: &[u8]) -> BLS12381Signature {
+        BLS12381Signature(self.0.sign(message, DST_BLS_SIG, &[]))
+    }
+}
+
+impl BLS12381PublicKey {
+    /// Serialize a BLS12381PublicKey.
+    pub fn to_bytes(&self) -> [u8; BLS12381_PUBLIC_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Aggregate public keys into a single public key, which verifies the aggregation of
+    /// signatures by the corresponding private keys on a common message.
+    ///
+    /// The keys are expected to have had their proof of possession verified.
+    pub fn aggregate(public_keys: &[&Self]) -> Result<Self> {
+        let keys: Vec<_> = public_keys.iter().map(|key| &key.0).collect();
+        let aggregate = blst::min_pk::AggregatePublicKey::aggregate(&keys, false)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(BLS12381PublicKey(aggregate.to_public_key()))
+    }
+}
+
+impl BLS12381Signature {
+    /// The length of the BLS12381Signature
+    pub const LENGTH: usize = BLS12381_SIGNATURE_LENGTH;
+
+    /// Serialize a BLS12381Signature.
+    pub fn to_bytes(&self) -> [u8; BLS12381_SIGNATURE_LENGTH] {
+        self.0.to_bytes()
+    }
+}
+
+impl ProofOfPossession {
+    /// Creates the proof of possession for the public key of `private_key`.
+    pub fn create(private_key: &BLS12381PrivateKey) -> Self {
+        let public_key: BLS12381PublicKey = private_key.into();
+        ProofOfPossession(private_key.0.sign(&public_key.to_bytes(), DST_BLS_POP, &[]))
+    }
+
+    /// Checks that `self` proves possession of the private key for `public_key`.
+    pub fn verify(&self, public_key: &BLS12381PublicKey) -> Result<()> {
+        check_blst_result(self.0.verify(
+            true,
+            &public_key.to_bytes(),
+            DST_BLS_POP,
+            &[],
+            &public_key.0,
+            true,
+        ))
+    }
+
+    /// Serialize a ProofOfPossession.
+    pub fn to_bytes(&self) -> [u8; BLS12381_SIGNATURE_LENGTH] {
+        self.0.to_bytes()
+    }
+}
+
+fn check_blst_result(result: BLST_ERROR) -> Result<()> {
+    match result {
+        BLST_ERROR::BLST_SUCCESS => Ok(()),
+        e => Err(anyhow!("BLS12-381 signature verification failed: {:?}", e)),
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for BLS12381PrivateKey {
+    type PublicKeyMaterial = BLS12381PublicKey;
+}
+
+impl SigningKey for BLS12381PrivateKey {
+    type VerifyingKeyMaterial = BLS12381PublicKey;
+    type SignatureMaterial = BLS12381Signature;
+
+    fn sign<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> Result<BLS12381Signature, CryptoMaterialError> {
+        let mut bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
+        lcs::serialize_into(&mut bytes, &message)
+            .map_err(|_| CryptoMaterialError::SerializationError)?;
+        Ok(BLS12381PrivateKey::sign_arbitrary_message(
+            &self,
+            bytes.as_ref(),
+        ))
+    }
+
+    fn sign_message(&self, message: &HashValue) -> BLS12381Signature {
+        BLS12381PrivateKey::sign_arbitrary_message(&self, message.as_ref())
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_arbitrary_message(&self, message: &[u8]) -> BLS12381Signature {
+        BLS12381PrivateKey::sign_arbitrary_message(self, message)
+    }
+}
+
+impl Uniform for BLS12381PrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng,
+    {
+        let mut ikm = [0u8; 32];
+        rng.fill_bytes(&mut ikm);
+        // Key generation only fails for input key material shorter than 32 bytes.
+        BLS12381PrivateKey(blst::min_pk::SecretKey::key_gen(&ikm, &[]).unwrap())
+    }
+}
+
+impl PartialEq<Self> for BLS12381PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for BLS12381PrivateKey {}
+
+impl TryFrom<&[u8]> for BLS12381PrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381PrivateKey. This method will also check that the scalar is
+    /// non-zero and smaller than the group order.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381PrivateKey, CryptoMaterialError> {
+        if bytes.len() != BLS12381_PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst::min_pk::SecretKey::from_bytes(bytes)
+            .map(BLS12381PrivateKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for BLS12381PrivateKey {
+    fn length(&self) -> usize {
+        Self::LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381PrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Genesis for BLS12381PrivateKey {
+    fn genesis() -> Self {
+        let mut buf = [0u8; BLS12381_PRIVATE_KEY_LENGTH];
+        buf[BLS12381_PRIVATE_KEY_LENGTH - 1] = 1;
+        Self::try_from(buf.as_ref()).unwrap()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl From<&BLS12381PrivateKey> for BLS12381PublicKey {
+    fn from(private_key: &BLS12381PrivateKey) -> Self {
+        BLS12381PublicKey(private_key.0.sk_to_pk())
+    }
+}
+
+impl PublicKey for BLS12381PublicKey {
+    type PrivateKeyMaterial = BLS12381PrivateKey;
+}
+
+impl std::hash::Hash for BLS12381PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_pubkey = self.to_bytes();
+        state.write(&encoded_pubkey);
+    }
+}
+
+impl PartialEq for BLS12381PublicKey {
+    fn eq(&self, other: &BLS12381PublicKey) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for BLS12381PublicKey {}
+
+impl VerifyingKey for BLS12381PublicKey {
+    type SigningKeyMaterial = BLS12381PrivateKey;
+    type SignatureMaterial = BLS12381Signature;
+}
+
+impl fmt::Display for BLS12381PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for BLS12381PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BLS12381PublicKey({})", self)
+    }
+}
+
+impl TryFrom<&[u8]> for BLS12381PublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381PublicKey. This method will also check that the point is on the
+    /// curve, lies in the prime-order subgroup and is not the identity.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381PublicKey, CryptoMaterialError> {
+        if bytes.len() != BLS12381_PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst::min_pk::PublicKey::key_validate(bytes)
+            .map(BLS12381PublicKey)
+            .map_err(|e| match e {
+                BLST_ERROR::BLST_POINT_NOT_ON_CURVE => CryptoMaterialError::PointNotOnCurveError,
+                BLST_ERROR::BLST_POINT_NOT_IN_GROUP | BLST_ERROR::BLST_PK_IS_INFINITY => {
+                    CryptoMaterialError::SmallSubgroupError
+                }
+                _ => CryptoMaterialError::DeserializationError,
+            })
+    }
+}
+
+impl Length for BLS12381PublicKey {
+    fn length(&self) -> usize {
+        BLS12381_PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for BLS12381Signature {
+    type VerifyingKeyMaterial = BLS12381PublicKey;
+    type SigningKeyMaterial = BLS12381PrivateKey;
+
+    /// Checks that `self` is valid for `message` using `public_key`.
+    fn verify(&self, message: &HashValue, public_key: &BLS12381PublicKey) -> Result<()> {
+        self.verify_arbitrary_msg(message.as_ref(), public_key)
+    }
+
+    fn verify_struct_msg<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &BLS12381PublicKey,
+    ) -> Result<()> {
+        let mut bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
+        lcs::serialize_into(&mut bytes, &message)
+            .map_err(|_| CryptoMaterialError::SerializationError)?;
+        Self::verify_arbitrary_msg(self, &bytes, public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`.
+    fn verify_arbitrary_msg(&self, message: &[u8], public_key: &BLS12381PublicKey) -> Result<()> {
+        // Public keys are validated upon deserialization, the signature is group-checked here.
+        check_blst_result(
+            self.0
+                .verify(true, message, DST_BLS_SIG, &[], &public_key.0, false),
+        )
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl AggregateSignature for BLS12381Signature {
+    fn aggregate(signatures: Vec<Self>) -> Result<Self> {
+        let signatures: Vec<_> = signatures.iter().map(|signature| &signature.0).collect();
+        let aggregate = blst::min_pk::AggregateSignature::aggregate(&signatures, true)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(BLS12381Signature(aggregate.to_signature()))
+    }
+
+    fn verify_aggregate_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_keys: &[&BLS12381PublicKey],
+    ) -> Result<()> {
+        let keys: Vec<_> = public_keys.iter().map(|key| &key.0).collect();
+        check_blst_result(
+            self.0
+                .fast_aggregate_verify(true, message, DST_BLS_SIG, &keys),
+        )
+    }
+}
+
+impl Length for BLS12381Signature {
+    fn length(&self) -> usize {
+        BLS12381_SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for BLS12381Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for BLS12381Signature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for BLS12381Signature {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a BLS12381Signature. The subgroup check is deferred to verification.
+    fn try_from(bytes: &[u8]) -> std::result::Result<BLS12381Signature, CryptoMaterialError> {
+        if bytes.len() != BLS12381_SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        blst::min_pk::Signature::from_bytes(bytes)
+            .map(BLS12381Signature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl PartialEq for BLS12381Signature {
+    fn eq(&self, other: &BLS12381Signature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for BLS12381Signature {}
+
+impl fmt::Display for BLS12381Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl fmt::Debug for BLS12381Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BLS12381Signature({})", self)
+    }
+}
+
+//////////////////////////////
+// ProofOfPossession Traits //
+//////////////////////////////
+
+impl TryFrom<&[u8]> for ProofOfPossession {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<ProofOfPossession, CryptoMaterialError> {
+        BLS12381Signature::try_from(bytes).map(|signature| ProofOfPossession(signature.0))
+    }
+}
+
+impl Length for ProofOfPossession {
+    fn length(&self) -> usize {
+        BLS12381_SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for ProofOfPossession {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl PartialEq for ProofOfPossession {
+    fn eq(&self, other: &ProofOfPossession) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for ProofOfPossession {}
+
+impl fmt::Debug for ProofOfPossession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ProofOfPossession({})",
+            hex::encode(&self.to_bytes()[..])
+        )
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+
+/// Produces a uniformly random BLS12-381 keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy() -> impl Strategy<Value = KeyPair<BLS12381PrivateKey, BLS12381PublicKey>> {
+    test_utils::uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for BLS12381PublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        crate::test_utils::uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+            .prop_map(|v| v.public_key)
+            .boxed()
+    }
+}
@@ -6,14 +6,17 @@
 
 //! A library supplying various cryptographic primitives
 
+pub mod bls12381;
 pub mod ed25519;
 pub mod error;
 pub mod hash;
 pub mod hkdf;
 pub mod multi_ed25519;
 pub mod noise;
+pub mod secp256k1;
 pub mod test_utils;
 pub mod traits;
+pub mod vrf;
 pub mod x25519;
 
 #[cfg(test)]
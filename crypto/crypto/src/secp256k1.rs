@@ -0,0 +1,50 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides the recovery of public keys from secp256k1 ECDSA signatures, as used by
+//! Ethereum and Bitcoin. Libra does not sign with secp256k1: recovery only serves verifying
+//! signatures made on other chains, e.g., in Move modules through the
+//! `Signature::secp256k1_recover` native function.
+//!
+//! Signatures are 64 bytes, the big-endian `r` and `s` values, together with a recovery id in
+//! `0..4`. Ethereum encodes the recovery id as `v`, i.e., `27` or `28`, from which `27` must be
+//! subtracted. Recovered public keys are the 64 byte concatenation of the big-endian `x` and `y`
+//! coordinates, without the `0x04` prefix of the uncompressed SEC1 encoding, whose Keccak-256
+//! hash yields an Ethereum address.
+
+use anyhow::{anyhow, Result};
+use std::convert::TryFrom;
+
+/// The length of a message hash signed with secp256k1
+pub const SECP256K1_MESSAGE_LENGTH: usize = 32;
+
+/// The length of a secp256k1 signature, without its recovery id
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+
+/// The length of a recovered secp256k1 public key
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 64;
+
+/// Recovers the public key that made `signature` of `message_hash` with `recovery_id`. Fails if
+/// the lengths of the inputs are invalid, or if no public key can be recovered; a public key is
+/// recovered from any other well-formed signature, which must then be compared to the expected
+/// one.
+pub fn recover(
+    message_hash: &[u8],
+    signature: &[u8],
+    recovery_id: u8,
+) -> Result<[u8; SECP256K1_PUBLIC_KEY_LENGTH]> {
+    let message_hash = <&[u8; SECP256K1_MESSAGE_LENGTH]>::try_from(message_hash)
+        .map_err(|_| anyhow!("secp256k1 message hashes must be 32 bytes"))?;
+    let message = secp256k1::Message::parse(message_hash);
+    let signature = secp256k1::Signature::parse_slice(signature)
+        .map_err(|e| anyhow!("Invalid secp256k1 signature: {:?}", e))?;
+    let recovery_id = secp256k1::RecoveryId::parse(recovery_id)
+        .map_err(|e| anyhow!("Invalid secp256k1 recovery id: {:?}", e))?;
+
+    let public_key = secp256k1::recover(&message, &signature, &recovery_id)
+        .map_err(|e| anyhow!("Unable to recover a secp256k1 public key: {:?}", e))?;
+    let mut recovered = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+    // Skip the prefix of the uncompressed encoding
+    recovered.copy_from_slice(&public_key.serialize()[1..]);
+    Ok(recovered)
+}
@@ -6,7 +6,10 @@
 //! For examples on how to use these traits, see the implementations of the [`ed25519`] or
 //! [`bls12381`] modules.
 
-use crate::{hash::CryptoHash, HashValue};
+use crate::{
+    hash::{CryptoHash, CryptoHasher},
+    HashValue,
+};
 use anyhow::Result;
 use core::convert::{From, TryFrom};
 use rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
@@ -276,6 +279,46 @@ pub trait Signature:
     }
 }
 
+/// A type family for signature schemes in which signatures on a common message can be combined
+/// into a single signature, verifiable against the public keys of all the signers.
+///
+/// Implementations may require the public keys to have been vetted beforehand (e.g. through a
+/// proof of possession) to rule out rogue-key attacks; see the documentation of each scheme.
+pub trait AggregateSignature: Signature {
+    /// Aggregate signatures produced on the same message into a single signature.
+    fn aggregate(signatures: Vec<Self>) -> Result<Self>;
+
+    /// Verification of an aggregate signature on an arbitrary &[u8] `message`, against the
+    /// public keys of the signers.
+    fn verify_aggregate_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_keys: &[&Self::VerifyingKeyMaterial],
+    ) -> Result<()>;
+
+    /// Verification of an aggregate signature on a message represented by its `HashValue`.
+    fn verify_aggregate(
+        &self,
+        message: &HashValue,
+        public_keys: &[&Self::VerifyingKeyMaterial],
+    ) -> Result<()> {
+        self.verify_aggregate_arbitrary_msg(message.as_ref(), public_keys)
+    }
+
+    /// Verification of an aggregate signature on a struct we unambiguously know how to
+    /// serialize and that we have a domain separation prefix for.
+    fn verify_aggregate_struct_msg<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_keys: &[&Self::VerifyingKeyMaterial],
+    ) -> Result<()> {
+        let mut bytes = <T::Hasher as CryptoHasher>::seed().to_vec();
+        lcs::serialize_into(&mut bytes, &message)
+            .map_err(|_| CryptoMaterialError::SerializationError)?;
+        self.verify_aggregate_arbitrary_msg(&bytes, public_keys)
+    }
+}
+
 /// A type family for schemes which know how to generate key material from
 /// a cryptographically-secure [`CryptoRng`][::rand::CryptoRng].
 pub trait Uniform {
@@ -308,7 +351,7 @@ pub trait Genesis: PrivateKey {
 pub(crate) mod private {
     pub trait Sealed {}
 
-    // Implement for the ed25519, multi-ed25519 signatures
+    // Implement for the ed25519, multi-ed25519 and bls12381 signatures
     impl Sealed for crate::ed25519::Ed25519PrivateKey {}
     impl Sealed for crate::ed25519::Ed25519PublicKey {}
     impl Sealed for crate::ed25519::Ed25519Signature {}
@@ -316,4 +359,8 @@ pub(crate) mod private {
     impl Sealed for crate::multi_ed25519::MultiEd25519PrivateKey {}
     impl Sealed for crate::multi_ed25519::MultiEd25519PublicKey {}
     impl Sealed for crate::multi_ed25519::MultiEd25519Signature {}
+
+    impl Sealed for crate::bls12381::BLS12381PrivateKey {}
+    impl Sealed for crate::bls12381::BLS12381PublicKey {}
+    impl Sealed for crate::bls12381::BLS12381Signature {}
 }
@@ -0,0 +1,161 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate as libra_crypto;
+use crate::{
+    bls12381::{
+        BLS12381PrivateKey, BLS12381PublicKey, BLS12381Signature, ProofOfPossession,
+        BLS12381_PUBLIC_KEY_LENGTH, BLS12381_SIGNATURE_LENGTH,
+    },
+    hash::HashValue,
+    test_utils::{uniform_keypair_strategy, KeyPair},
+    traits::*,
+};
+use core::convert::TryFrom;
+use libra_crypto_derive::{CryptoHasher, LCSCryptoHash};
+use proptest::{collection::vec, prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[derive(CryptoHasher, LCSCryptoHash, Serialize, Deserialize)]
+struct CryptoHashable(pub usize);
+
+fn keypairs_strategy() -> impl Strategy<Value = Vec<KeyPair<BLS12381PrivateKey, BLS12381PublicKey>>>
+{
+    vec(
+        uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        1..10,
+    )
+}
+
+proptest! {
+    #[test]
+    fn test_keys_encode(keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()) {
+        let encoded = keypair.private_key.to_encoded_string().unwrap();
+        let decoded = BLS12381PrivateKey::from_encoded_string(&encoded);
+        prop_assert_eq!(Some(keypair.private_key), decoded.ok());
+
+        let encoded = keypair.public_key.to_encoded_string().unwrap();
+        let decoded = BLS12381PublicKey::from_encoded_string(&encoded);
+        prop_assert_eq!(Some(keypair.public_key), decoded.ok());
+    }
+
+    #[test]
+    fn test_keys_serde(keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()) {
+        let serialized = lcs::to_bytes(&keypair.public_key).unwrap();
+        let deserialized: BLS12381PublicKey = lcs::from_bytes(&serialized).unwrap();
+        prop_assert_eq!(keypair.public_key, deserialized);
+
+        let serialized = serde_json::to_string(&keypair.private_key).unwrap();
+        let deserialized: BLS12381PrivateKey = serde_json::from_str(&serialized).unwrap();
+        prop_assert_eq!(keypair.private_key, deserialized);
+    }
+
+    #[test]
+    fn test_sign_and_verify(
+        hash in any::<HashValue>(),
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+    ) {
+        let signature = keypair.private_key.sign_message(&hash);
+        prop_assert_eq!(signature.to_bytes().len(), BLS12381_SIGNATURE_LENGTH);
+        prop_assert!(signature.verify(&hash, &keypair.public_key).is_ok());
+
+        let serialized = lcs::to_bytes(&signature).unwrap();
+        let deserialized: BLS12381Signature = lcs::from_bytes(&serialized).unwrap();
+        prop_assert!(deserialized.verify(&hash, &keypair.public_key).is_ok());
+    }
+
+    #[test]
+    fn test_sign_and_verify_struct(
+        x in any::<usize>(),
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+    ) {
+        let signature = keypair.private_key.sign(&CryptoHashable(x)).unwrap();
+        prop_assert!(signature.verify_struct_msg(&CryptoHashable(x), &keypair.public_key).is_ok());
+        prop_assert!(signature
+            .verify_struct_msg(&CryptoHashable(x.wrapping_add(1)), &keypair.public_key)
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_wrong_key(
+        hash in any::<HashValue>(),
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        other in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+    ) {
+        prop_assume!(keypair.public_key != other.public_key);
+        let signature = keypair.private_key.sign_message(&hash);
+        prop_assert!(signature.verify(&hash, &other.public_key).is_err());
+    }
+
+    #[test]
+    fn test_proof_of_possession(
+        keypair in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>(),
+        other in uniform_keypair_strategy::<BLS12381PrivateKey, BLS12381PublicKey>()
+    ) {
+        prop_assume!(keypair.public_key != other.public_key);
+        let pop = ProofOfPossession::create(&keypair.private_key);
+        prop_assert!(pop.verify(&keypair.public_key).is_ok());
+        prop_assert!(pop.verify(&other.public_key).is_err());
+
+        let deserialized = ProofOfPossession::try_from(&pop.to_bytes()[..]).unwrap();
+        prop_assert_eq!(pop, deserialized);
+    }
+
+    #[test]
+    fn test_aggregate_verify(hash in any::<HashValue>(), keypairs in keypairs_strategy()) {
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| keypair.private_key.sign_message(&hash))
+            .collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let aggregate = BLS12381Signature::aggregate(signatures).unwrap();
+        prop_assert!(aggregate.verify_aggregate(&hash, &public_keys).is_ok());
+
+        let aggregate_key = BLS12381PublicKey::aggregate(&public_keys).unwrap();
+        prop_assert!(aggregate.verify(&hash, &aggregate_key).is_ok());
+
+        // Dropping one signer invalidates the aggregate.
+        if public_keys.len() > 1 {
+            prop_assert!(aggregate
+                .verify_aggregate(&hash, &public_keys[1..])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_aggregate_verify_wrong_message(
+        hash in any::<HashValue>(),
+        other_hash in any::<HashValue>(),
+        keypairs in keypairs_strategy()
+    ) {
+        prop_assume!(hash != other_hash);
+        let signatures: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| keypair.private_key.sign_message(&hash))
+            .collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let aggregate = BLS12381Signature::aggregate(signatures).unwrap();
+        prop_assert!(aggregate.verify_aggregate(&other_hash, &public_keys).is_err());
+    }
+}
+
+#[test]
+fn test_public_key_deserialization() {
+    // Wrong length
+    assert_eq!(
+        BLS12381PublicKey::try_from(&[0u8; BLS12381_PUBLIC_KEY_LENGTH - 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+    // Not a valid compressed point
+    assert!(BLS12381PublicKey::try_from(&[0u8; BLS12381_PUBLIC_KEY_LENGTH][..]).is_err());
+    // The compressed identity is rejected
+    let mut identity = [0u8; BLS12381_PUBLIC_KEY_LENGTH];
+    identity[0] = 0xc0;
+    assert!(BLS12381PublicKey::try_from(&identity[..]).is_err());
+}
+
+#[test]
+fn test_aggregate_empty() {
+    assert!(BLS12381Signature::aggregate(vec![]).is_err());
+    assert!(BLS12381PublicKey::aggregate(&[]).is_err());
+}
@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod bls12381_test;
 mod cross_test;
 mod cryptohasher;
 mod ed25519_test;
@@ -8,3 +9,5 @@ mod hash_test;
 mod hkdf_test;
 mod multi_ed25519_test;
 mod noise_test;
+mod secp256k1_test;
+mod vrf_test;
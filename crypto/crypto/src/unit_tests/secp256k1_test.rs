@@ -0,0 +1,30 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{hash::HashValue, secp256k1::recover};
+
+#[test]
+fn test_recover() {
+    let secret_key = secp256k1::SecretKey::parse(&[7u8; 32]).unwrap();
+    let public_key = secp256k1::PublicKey::from_secret_key(&secret_key).serialize();
+    let message_hash = HashValue::sha3_256_of(b"Test message");
+    let message = secp256k1::Message::parse(message_hash.as_ref());
+    let (signature, recovery_id) = secp256k1::sign(&message, &secret_key);
+    let signature = signature.serialize();
+
+    let recovered = recover(message_hash.as_ref(), &signature, recovery_id.serialize()).unwrap();
+    assert_eq!(&recovered[..], &public_key[1..]);
+
+    // The other recovery id yields another key
+    let other = recover(
+        message_hash.as_ref(),
+        &signature,
+        recovery_id.serialize() ^ 1,
+    )
+    .unwrap();
+    assert_ne!(&other[..], &public_key[1..]);
+
+    assert!(recover(message_hash.as_ref(), &signature, 4).is_err());
+    assert!(recover(&message_hash.as_ref()[1..], &signature, 0).is_err());
+    assert!(recover(message_hash.as_ref(), &signature[1..], 0).is_err());
+}
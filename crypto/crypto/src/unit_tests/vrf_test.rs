@@ -0,0 +1,129 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    traits::*,
+    vrf::ecvrf::{keypair_strategy, Output, Proof, VRFPrivateKey, VRFPublicKey, PROOF_LENGTH},
+};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+
+/// Test vectors for ECVRF-EDWARDS25519-SHA512-TAI from draft-irtf-cfrg-vrf-05, Appendix A.3:
+/// (private key, public key, alpha, proof, output).
+const TEST_VECTORS: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "9275df67a68c8745c0ff97b48201ee6db447f7c93b23ae24cdc2400f52fdb08a1a6ac7ec71bf9c9c76e96ee4675ebff60625af28718501047bfd87b810c2d2139b73c23bd69de66360953a642c2a330a",
+        "a64c292ec45f6b252828aff9a02a0fe88d2fcc7f5fc61bb328f03f4c6c0657a9d26efb23b87647ff54f71cd51a6fa4c4e31661d8f72b41ff00ac4d2eec2ea7b3",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "84a63e74eca8fdd64e9972dcda1c6f33d03ce3cd4d333fd6cc789db12b5a7b9d03f1cb6b2bf7cd81a2a20bacf6e1c04e59f2fa16d9119c73a45a97194b504fb9a5c8cf37f6da85e03368d6882e511008",
+        "cddaa399bb9c56d3be15792e43a6742fb72b1d248a7f24fd5cc585b232c26c934711393b4d97284b2bcca588775b72dc0b0f4b5a195bc41f8d2b80b6981c784e",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "aca8ade9b7f03e2b149637629f95654c94fc9053c225ec21e5838f193af2b727b84ad849b0039ad38b41513fe5a66cdd2367737a84b488d62486bd2fb110b4801a46bfca770af98e059158ac563b690f",
+        "d938b2012f2551b0e13a49568612effcbdca2aed5d1d3a13f47e180e01218916e049837bd246f66d5058e56d3413dbbbad964f5e9f160a81c9a1355dcd99b453",
+    ),
+];
+
+#[test]
+fn test_vectors() {
+    for (private_key, public_key, alpha, proof, output) in TEST_VECTORS {
+        let private_key = VRFPrivateKey::from_encoded_string(private_key).unwrap();
+        let public_key = VRFPublicKey::from_encoded_string(public_key).unwrap();
+        assert_eq!(VRFPublicKey::from(&private_key), public_key);
+
+        let alpha = hex::decode(alpha).unwrap();
+        let expected_proof = Proof::from_encoded_string(proof).unwrap();
+        let proof = private_key.prove(&alpha);
+        assert_eq!(proof, expected_proof);
+        assert!(public_key.verify(&proof, &alpha).is_ok());
+
+        let expected_output = hex::decode(output).unwrap();
+        assert_eq!(&Output::from(&proof).to_bytes()[..], &expected_output[..]);
+    }
+}
+
+#[test]
+fn test_public_key_validation() {
+    // The identity is of small order.
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    assert_eq!(
+        VRFPublicKey::try_from(&identity[..]),
+        Err(CryptoMaterialError::SmallSubgroupError)
+    );
+    // Not a point on the curve.
+    let mut not_on_curve = [0u8; 32];
+    not_on_curve[0] = 2;
+    assert_eq!(
+        VRFPublicKey::try_from(&not_on_curve[..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+    assert_eq!(
+        VRFPublicKey::try_from(&[0u8; 31][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+}
+
+#[test]
+fn test_proof_rejects_non_canonical_scalar() {
+    let (_, _, _, proof, _) = TEST_VECTORS[0];
+    let mut bytes = hex::decode(proof).unwrap();
+    assert_eq!(bytes.len(), PROOF_LENGTH);
+    // Setting the top bits of s makes it larger than the group order.
+    bytes[PROOF_LENGTH - 1] |= 0xf0;
+    assert_eq!(
+        Proof::try_from(&bytes[..]),
+        Err(CryptoMaterialError::CanonicalRepresentationError)
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_prove_and_verify(
+        message in any::<Vec<u8>>(),
+        other_message in any::<Vec<u8>>(),
+        keypair in keypair_strategy(),
+        other_keypair in keypair_strategy(),
+    ) {
+        let proof = keypair.private_key.prove(&message);
+        prop_assert!(keypair.public_key.verify(&proof, &message).is_ok());
+
+        // The proof does not verify for another message or another key.
+        prop_assume!(message != other_message);
+        prop_assert!(keypair.public_key.verify(&proof, &other_message).is_err());
+        prop_assume!(keypair.public_key != other_keypair.public_key);
+        prop_assert!(other_keypair.public_key.verify(&proof, &message).is_err());
+
+        // The output is a deterministic function of the key and message.
+        let other_proof = other_keypair.private_key.prove(&message);
+        prop_assert_eq!(Output::from(&proof), Output::from(&keypair.private_key.prove(&message)));
+        prop_assert_ne!(Output::from(&proof), Output::from(&other_proof));
+    }
+
+    #[test]
+    fn test_serde(message in any::<Vec<u8>>(), keypair in keypair_strategy()) {
+        let proof = keypair.private_key.prove(&message);
+
+        let serialized = lcs::to_bytes(&proof).unwrap();
+        let deserialized: Proof = lcs::from_bytes(&serialized).unwrap();
+        prop_assert_eq!(proof, deserialized);
+
+        let serialized = lcs::to_bytes(&keypair.public_key).unwrap();
+        let deserialized: VRFPublicKey = lcs::from_bytes(&serialized).unwrap();
+        prop_assert_eq!(keypair.public_key, deserialized);
+
+        let serialized = serde_json::to_string(&keypair.private_key).unwrap();
+        let deserialized: VRFPrivateKey = serde_json::from_str(&serialized).unwrap();
+        prop_assert_eq!(keypair.private_key, deserialized);
+    }
+}
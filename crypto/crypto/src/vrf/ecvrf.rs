@@ -0,0 +1,409 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements ECVRF-EDWARDS25519-SHA512-TAI, the verifiable random function over
+//! edwards25519 described in
+//! [draft-irtf-cfrg-vrf-05](https://tools.ietf.org/html/draft-irtf-cfrg-vrf-05).
+//!
+//! Private keys are 32-byte seeds that are expanded the same way as Ed25519 private keys. Proofs
+//! are 80 bytes long and hash to 64-byte outputs.
+//!
+//! # Examples
+//!
+//! ```
+//! use libra_crypto::{traits::Uniform, vrf::ecvrf::*};
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! let message = b"Test message";
+//! let mut rng: StdRng = SeedableRng::from_seed([0_u8; 32]);
+//! let private_key = VRFPrivateKey::generate(&mut rng);
+//! let public_key: VRFPublicKey = (&private_key).into();
+//!
+//! let proof = private_key.prove(message);
+//! assert!(public_key.verify(&proof, message).is_ok());
+//! let output: Output = (&proof).into();
+//! assert_eq!(output.to_bytes().len(), OUTPUT_LENGTH);
+//! ```
+
+use crate::traits::*;
+use anyhow::{bail, Result};
+use core::convert::TryFrom;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_POINT,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar as ed25519_Scalar,
+};
+use libra_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+const SUITE: u8 = 0x03;
+const ONE: u8 = 0x01;
+const TWO: u8 = 0x02;
+const THREE: u8 = 0x03;
+
+/// The number of bytes of [`Output`]
+pub const OUTPUT_LENGTH: usize = 64;
+/// The number of bytes of [`Proof`]
+pub const PROOF_LENGTH: usize = 80;
+/// The number of bytes of [`VRFPrivateKey`]
+pub const PRIVATE_KEY_LENGTH: usize = 32;
+/// The number of bytes of [`VRFPublicKey`]
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+/// The number of bytes of the challenge `c` in a [`Proof`]
+const CHALLENGE_LENGTH: usize = 16;
+
+/// An ECVRF private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct VRFPrivateKey([u8; PRIVATE_KEY_LENGTH]);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(VRFPrivateKey: Clone);
+
+/// An ECVRF public key
+#[derive(Clone, Copy, DeserializeKey, Eq, Hash, PartialEq, SerializeKey)]
+pub struct VRFPublicKey([u8; PUBLIC_KEY_LENGTH]);
+
+/// A longer private key which is slightly optimized for proof generation.
+///
+/// This is similar in structure to ed25519_dalek::ExpandedSecretKey. It can be produced from
+/// a VRFPrivateKey.
+struct VRFExpandedPrivateKey {
+    key: ed25519_Scalar,
+    nonce: [u8; 32],
+}
+
+/// A VRF proof that can be used to validate an input with a public key
+#[derive(Clone, Copy, DeserializeKey, Eq, PartialEq, SerializeKey)]
+pub struct Proof {
+    gamma: EdwardsPoint,
+    c: ed25519_Scalar,
+    s: ed25519_Scalar,
+}
+
+/// The ECVRF output produced from the proof
+#[derive(Clone, Copy)]
+pub struct Output([u8; OUTPUT_LENGTH]);
+
+impl VRFPrivateKey {
+    /// Produces a proof for an input (using the private key)
+    pub fn prove(&self, alpha: &[u8]) -> Proof {
+        VRFExpandedPrivateKey::from(self).prove(&VRFPublicKey::from(self), alpha)
+    }
+}
+
+impl VRFExpandedPrivateKey {
+    fn prove(&self, pk: &VRFPublicKey, alpha: &[u8]) -> Proof {
+        let h_point = pk.hash_to_curve(alpha);
+        let h_point_bytes = h_point.compress().to_bytes();
+        let gamma = h_point * self.key;
+
+        let mut k_bytes = [0u8; 64];
+        k_bytes.copy_from_slice(
+            &Sha512::new()
+                .chain(&self.nonce)
+                .chain(&h_point_bytes)
+                .finalize()[..],
+        );
+        let k = ed25519_Scalar::from_bytes_mod_order_wide(&k_bytes);
+
+        let c = hash_points(&[h_point, gamma, ED25519_BASEPOINT_POINT * k, h_point * k]);
+        let s = k + c * self.key;
+
+        Proof { gamma, c, s }
+    }
+}
+
+impl<'a> From<&'a VRFPrivateKey> for VRFExpandedPrivateKey {
+    fn from(private_key: &'a VRFPrivateKey) -> Self {
+        let h = Sha512::digest(&private_key.0);
+        let mut key_bytes = [0u8; 32];
+        let mut nonce = [0u8; 32];
+        key_bytes.copy_from_slice(&h[..32]);
+        nonce.copy_from_slice(&h[32..]);
+
+        // Clamp the scalar as in Ed25519.
+        key_bytes[0] &= 248;
+        key_bytes[31] &= 127;
+        key_bytes[31] |= 64;
+
+        VRFExpandedPrivateKey {
+            key: ed25519_Scalar::from_bits(key_bytes),
+            nonce,
+        }
+    }
+}
+
+impl VRFPublicKey {
+    /// Given a [`Proof`] and an input, returns whether or not the proof is valid for the input
+    /// and public key
+    pub fn verify(&self, proof: &Proof, alpha: &[u8]) -> Result<()> {
+        let y_point = self.point();
+        let h_point = self.hash_to_curve(alpha);
+
+        let u_point = ED25519_BASEPOINT_POINT * proof.s - y_point * proof.c;
+        let v_point = h_point * proof.s - proof.gamma * proof.c;
+        let c_prime = hash_points(&[h_point, proof.gamma, u_point, v_point]);
+
+        if c_prime != proof.c {
+            bail!("The proof failed to verify for this public key")
+        }
+        Ok(())
+    }
+
+    /// Hashes the public key and an input to a curve point using the try-and-increment method
+    /// (ECVRF_hash_to_curve_try_and_increment).
+    fn hash_to_curve(&self, alpha: &[u8]) -> EdwardsPoint {
+        let mut result = [0u8; 32];
+        let mut counter: u8 = 0;
+        let mut wrapped_point: Option<EdwardsPoint> = None;
+
+        while wrapped_point.is_none() {
+            result.copy_from_slice(
+                &Sha512::new()
+                    .chain(&[SUITE, ONE])
+                    .chain(&self.0)
+                    .chain(alpha)
+                    .chain(&[counter])
+                    .finalize()[..32],
+            );
+            wrapped_point = CompressedEdwardsY::from_slice(&result).decompress();
+            // This has less than a 2^-255 chance of wrapping around, and even then the next
+            // counter values simply restart the search.
+            counter = counter.wrapping_add(1);
+        }
+
+        wrapped_point.unwrap().mul_by_cofactor()
+    }
+
+    fn point(&self) -> EdwardsPoint {
+        CompressedEdwardsY(self.0)
+            .decompress()
+            .expect("A VRFPublicKey always holds a valid point")
+    }
+}
+
+impl<'a> From<&'a VRFPrivateKey> for VRFPublicKey {
+    fn from(private_key: &'a VRFPrivateKey) -> Self {
+        let expanded = VRFExpandedPrivateKey::from(private_key);
+        VRFPublicKey(
+            (ED25519_BASEPOINT_POINT * expanded.key)
+                .compress()
+                .to_bytes(),
+        )
+    }
+}
+
+impl Proof {
+    /// Converts a Proof into bytes
+    pub fn to_bytes(&self) -> [u8; PROOF_LENGTH] {
+        let mut bytes = [0u8; PROOF_LENGTH];
+        bytes[..32].copy_from_slice(&self.gamma.compress().to_bytes()[..]);
+        bytes[32..32 + CHALLENGE_LENGTH].copy_from_slice(&self.c.to_bytes()[..CHALLENGE_LENGTH]);
+        bytes[32 + CHALLENGE_LENGTH..].copy_from_slice(&self.s.to_bytes()[..]);
+        bytes
+    }
+}
+
+impl Output {
+    /// Converts an Output into bytes
+    pub fn to_bytes(&self) -> [u8; OUTPUT_LENGTH] {
+        self.0
+    }
+}
+
+impl PartialEq for Output {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl Eq for Output {}
+
+impl<'a> From<&'a Proof> for Output {
+    fn from(proof: &'a Proof) -> Output {
+        let mut output = [0u8; OUTPUT_LENGTH];
+        output.copy_from_slice(
+            &Sha512::new()
+                .chain(&[SUITE, THREE])
+                .chain(&proof.gamma.mul_by_cofactor().compress().to_bytes()[..])
+                .finalize()[..],
+        );
+        Output(output)
+    }
+}
+
+/// Hashes a list of points to the challenge scalar (ECVRF_hash_points).
+fn hash_points(points: &[EdwardsPoint]) -> ed25519_Scalar {
+    let mut hasher = Sha512::new().chain(&[SUITE, TWO]);
+    for point in points {
+        hasher = hasher.chain(&point.compress().to_bytes()[..]);
+    }
+    let mut c_bytes = [0u8; 32];
+    c_bytes[..CHALLENGE_LENGTH].copy_from_slice(&hasher.finalize()[..CHALLENGE_LENGTH]);
+    // A 128-bit integer is always a canonical scalar.
+    ed25519_Scalar::from_bits(c_bytes)
+}
+
+//
+// Traits implementations
+// ======================
+//
+
+impl TryFrom<&[u8]> for VRFPrivateKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<VRFPrivateKey, CryptoMaterialError> {
+        if bytes.len() != PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let mut key = [0u8; PRIVATE_KEY_LENGTH];
+        key.copy_from_slice(bytes);
+        Ok(VRFPrivateKey(key))
+    }
+}
+
+impl TryFrom<&[u8]> for VRFPublicKey {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<VRFPublicKey, CryptoMaterialError> {
+        if bytes.len() != PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let point = CompressedEdwardsY::from_slice(bytes)
+            .decompress()
+            .ok_or(CryptoMaterialError::DeserializationError)?;
+        // Small order keys let anyone produce valid proofs, so they are rejected here.
+        if point.is_small_order() {
+            return Err(CryptoMaterialError::SmallSubgroupError);
+        }
+        let mut key = [0u8; PUBLIC_KEY_LENGTH];
+        key.copy_from_slice(bytes);
+        Ok(VRFPublicKey(key))
+    }
+}
+
+impl TryFrom<&[u8]> for Proof {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Proof, CryptoMaterialError> {
+        if bytes.len() != PROOF_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let gamma = CompressedEdwardsY::from_slice(&bytes[..32])
+            .decompress()
+            .ok_or(CryptoMaterialError::DeserializationError)?;
+
+        let mut c_bytes = [0u8; 32];
+        c_bytes[..CHALLENGE_LENGTH].copy_from_slice(&bytes[32..32 + CHALLENGE_LENGTH]);
+        let c = ed25519_Scalar::from_bits(c_bytes);
+
+        let mut s_bytes = [0u8; 32];
+        s_bytes.copy_from_slice(&bytes[32 + CHALLENGE_LENGTH..]);
+        let s = ed25519_Scalar::from_canonical_bytes(s_bytes)
+            .ok_or(CryptoMaterialError::CanonicalRepresentationError)?;
+
+        Ok(Proof { gamma, c, s })
+    }
+}
+
+impl PrivateKey for VRFPrivateKey {
+    type PublicKeyMaterial = VRFPublicKey;
+}
+
+impl PublicKey for VRFPublicKey {
+    type PrivateKeyMaterial = VRFPrivateKey;
+}
+
+impl Uniform for VRFPrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: RngCore + CryptoRng,
+    {
+        let mut key = [0u8; PRIVATE_KEY_LENGTH];
+        rng.fill_bytes(&mut key);
+        VRFPrivateKey(key)
+    }
+}
+
+impl ValidCryptoMaterial for VRFPrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl ValidCryptoMaterial for VRFPublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl ValidCryptoMaterial for Proof {
+    fn to_bytes(&self) -> Vec<u8> {
+        Proof::to_bytes(self).to_vec()
+    }
+}
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for VRFPrivateKey {
+    fn clone(&self) -> Self {
+        VRFPrivateKey(self.0)
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl PartialEq for VRFPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl Eq for VRFPrivateKey {}
+
+impl std::fmt::Display for VRFPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0[..]))
+    }
+}
+
+impl std::fmt::Debug for VRFPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VRFPublicKey({})", self)
+    }
+}
+
+impl std::fmt::Debug for Proof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Proof({})", hex::encode(&self.to_bytes()[..]))
+    }
+}
+
+impl std::fmt::Debug for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Output({})", hex::encode(&self.0[..]))
+    }
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+
+/// Produces a uniformly random ECVRF keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy() -> impl Strategy<Value = KeyPair<VRFPrivateKey, VRFPublicKey>> {
+    test_utils::uniform_keypair_strategy::<VRFPrivateKey, VRFPublicKey>()
+}
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for VRFPublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        keypair_strategy()
+            .prop_map(|keypair| keypair.public_key)
+            .boxed()
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifiable Random Functions (VRFs).
+//!
+//! A VRF is the public-key analog of a keyed hash: only the holder of a private key can compute
+//! the output for a given input, while anyone holding the public key can check, using the
+//! accompanying proof, that the output was computed correctly. Outputs are unpredictable to
+//! parties not holding the private key, which makes VRFs suitable as a source of randomness for
+//! leader election.
+
+pub mod ecvrf;
@@ -19,4 +19,4 @@ fi
     ${params[@]}
 
 cd /opt/libra/bin && \
-exec gunicorn --bind 0.0.0.0:8000 --access-logfile - --error-logfile - --log-level $LOG_LEVEL server
+exec gunicorn --bind 0.0.0.0:8000 --workers 1 --threads 8 --access-logfile - --error-logfile - --log-level $LOG_LEVEL server
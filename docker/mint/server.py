@@ -1,13 +1,28 @@
 """
 Simple faucet server
 Proxies mint requests to local client that owns association keys
+
+Requests are placed on a queue and minted by a single worker, which owns the client. Pending
+requests for the same account and currency are batched into a single mint transaction. Clients
+may supply an idempotency key, via the Idempotency-Key header or the idempotency_key parameter,
+so that retrying a request never mints twice. Requests are rate limited per IP and per account.
+
+POST /          enqueues a mint and waits for it to be submitted, returning the sequence number
+                of the association account as before
+POST /requests  enqueues a mint and immediately returns its id for polling
+GET  /requests/<id>  returns the status of a request: queued, submitted, or failed
 """
+import collections
 import decimal
 import os
 import platform
+import queue
 import random
 import re
 import sys
+import threading
+import time
+import uuid
 
 import flask
 import pexpect
@@ -15,6 +30,24 @@ import pexpect
 
 MAX_MINT = 10 ** 19  # 10 trillion libras
 
+# Maximum number of mint requests accepted per IP and per account within RATE_LIMIT_WINDOW seconds
+IP_RATE_LIMIT = int(os.environ.get('FAUCET_IP_RATE_LIMIT', 100))
+ACCOUNT_RATE_LIMIT = int(os.environ.get('FAUCET_ACCOUNT_RATE_LIMIT', 10))
+RATE_LIMIT_WINDOW = int(os.environ.get('FAUCET_RATE_LIMIT_WINDOW', 3600))
+# Number of proxies in front of the faucet whose X-Forwarded-For entries are trusted. Clients can
+# forge any entries before those, so with no trusted proxies the header is ignored.
+TRUSTED_PROXIES = int(os.environ.get('FAUCET_TRUSTED_PROXIES', 0))
+# Maximum number of queued requests processed together
+BATCH_SIZE = int(os.environ.get('FAUCET_BATCH_SIZE', 50))
+# How long POST / waits for its request to be submitted
+SYNC_TIMEOUT = int(os.environ.get('FAUCET_SYNC_TIMEOUT', 30))
+# How long completed requests remain available for polling and idempotent retries
+REQUEST_TTL = int(os.environ.get('FAUCET_REQUEST_TTL', 24 * 3600))
+
+QUEUED = 'queued'
+SUBMITTED = 'submitted'
+FAILED = 'failed'
+
 
 def create_client():
     if application.client is None or not application.client.isalive():
@@ -36,41 +69,239 @@ def create_client():
         application.client.expect("Please, input commands")
 
 
+class MintRequest:
+    def __init__(self, auth_key, amount, currency_code, idempotency_key):
+        self.id = uuid.uuid4().hex
+        self.auth_key = auth_key
+        self.amount = amount
+        self.currency_code = currency_code
+        self.idempotency_key = idempotency_key
+        self.created = time.time()
+        self.status = QUEUED
+        self.sequence_number = None
+        self.error = None
+        self.done = threading.Event()
+
+    def same_mint(self, auth_key, amount, currency_code):
+        return (self.auth_key, self.amount, self.currency_code) == \
+            (auth_key, amount, currency_code)
+
+    def complete(self, status, sequence_number=None, error=None):
+        self.status = status
+        self.sequence_number = sequence_number
+        self.error = error
+        self.done.set()
+
+    def to_json(self):
+        return {
+            'id': self.id,
+            'auth_key': self.auth_key,
+            'amount': str(self.amount),
+            'currency_code': self.currency_code,
+            'status': self.status,
+            'sequence_number': self.sequence_number,
+            'error': self.error,
+        }
+
+
+class RateLimiter:
+    """Sliding window limit on the number of events per key"""
+
+    def __init__(self, limit, window):
+        self.limit = limit
+        self.window = window
+        self.events = collections.defaultdict(collections.deque)
+
+    def allow(self, key, now):
+        events = self.events[key]
+        while events and events[0] <= now - self.window:
+            events.popleft()
+        if len(events) >= self.limit:
+            return False
+        events.append(now)
+        return True
+
+    def prune(self, now):
+        for key in [k for k, v in self.events.items() if not v or v[-1] <= now - self.window]:
+            del self.events[key]
+
+
+class Faucet:
+    def __init__(self):
+        self.lock = threading.Lock()
+        self.queue = queue.Queue()
+        self.requests = {}
+        self.idempotency_keys = {}
+        self.ip_limiter = RateLimiter(IP_RATE_LIMIT, RATE_LIMIT_WINDOW)
+        self.account_limiter = RateLimiter(ACCOUNT_RATE_LIMIT, RATE_LIMIT_WINDOW)
+        self.worker = threading.Thread(target=self.run, daemon=True)
+        self.worker.start()
+
+    def submit(self, ip, auth_key, amount, currency_code, idempotency_key):
+        """Returns the request and an error message with an HTTP status code, if rejected"""
+        now = time.time()
+        with self.lock:
+            self.expire(now)
+
+            if idempotency_key is not None and idempotency_key in self.idempotency_keys:
+                request = self.idempotency_keys[idempotency_key]
+                if not request.same_mint(auth_key, amount, currency_code):
+                    return None, ('Idempotency key reused with different parameters', 422)
+                return request, None
+
+            if not self.ip_limiter.allow(ip, now):
+                return None, ('Too many requests from {}'.format(ip), 429)
+            if not self.account_limiter.allow(auth_key, now):
+                return None, ('Too many requests for {}'.format(auth_key), 429)
+
+            request = MintRequest(auth_key, amount, currency_code, idempotency_key)
+            self.requests[request.id] = request
+            if idempotency_key is not None:
+                self.idempotency_keys[idempotency_key] = request
+            self.queue.put(request)
+            return request, None
+
+    def get(self, request_id):
+        with self.lock:
+            return self.requests.get(request_id)
+
+    def expire(self, now):
+        expired = [r for r in self.requests.values()
+                   if r.done.is_set() and r.created <= now - REQUEST_TTL]
+        for request in expired:
+            del self.requests[request.id]
+            if request.idempotency_key is not None:
+                del self.idempotency_keys[request.idempotency_key]
+        self.ip_limiter.prune(now)
+        self.account_limiter.prune(now)
+
+    def run(self):
+        while True:
+            batch = [self.queue.get()]
+            while len(batch) < BATCH_SIZE:
+                try:
+                    batch.append(self.queue.get_nowait())
+                except queue.Empty:
+                    break
+
+            mints = collections.OrderedDict()
+            for request in batch:
+                key = (request.auth_key, request.currency_code)
+                mints.setdefault(key, []).append(request)
+
+            for (auth_key, currency_code), requests in mints.items():
+                # Keep each transaction within the maximum amount
+                group = []
+                for request in requests:
+                    if group and sum(r.amount for r in group) + request.amount > MAX_MINT:
+                        self.mint(auth_key, currency_code, group)
+                        group = []
+                    group.append(request)
+                self.mint(auth_key, currency_code, group)
+
+    def mint(self, auth_key, currency_code, requests):
+        amount = sum(r.amount for r in requests)
+        submitted = False
+        try:
+            create_client()
+            application.client.sendline(
+                "a m {} {} {} use_base_units".format(auth_key, amount, currency_code))
+            application.client.expect("Mint request submitted", timeout=2)
+            submitted = True
+
+            application.client.sendline("a la")
+            application.client.expect(r"sequence_number: ([0-9]+)", timeout=1)
+            sequence_number = int(application.client.match.groups()[0])
+            for request in requests:
+                request.complete(SUBMITTED, sequence_number=sequence_number)
+        except Exception as e:
+            if application.client is not None:
+                application.client.terminate(True)
+            # Once submitted, the mint must not be retried, even though its sequence number is
+            # unknown.
+            status = SUBMITTED if submitted else FAILED
+            for request in requests:
+                request.complete(status, error=str(e))
+
+
+def parse_request():
+    """Returns the validated mint parameters or an error message with an HTTP status code"""
+    auth_key = flask.request.args.get('auth_key', '')
+
+    # Return immediately if auth_key is invalid
+    if re.match('^[a-f0-9]{64}$', auth_key) is None:
+        return None, ('Malformed auth_key', 400)
+
+    try:
+        amount = decimal.Decimal(flask.request.args.get('amount', ''))
+    except decimal.InvalidOperation:
+        return None, ('Bad amount', 400)
+
+    if amount <= 0 or amount != amount.to_integral_value():
+        return None, ('Bad amount', 400)
+
+    if amount > MAX_MINT:
+        return None, ('Exceeded max amount of {}'.format(MAX_MINT), 400)
+
+    currency_code = flask.request.args.get('currency_code', '')
+    if re.match('^[A-Za-z0-9]+$', currency_code) is None:
+        return None, ('Malformed currency_code', 400)
+
+    return (auth_key, int(amount), currency_code), None
+
+
+def enqueue():
+    params, error = parse_request()
+    if error is not None:
+        return None, error
+
+    idempotency_key = flask.request.headers.get(
+        'Idempotency-Key', flask.request.args.get('idempotency_key'))
+    return application.faucet.submit(client_ip(), *params, idempotency_key)
+
+
+def client_ip():
+    """Returns the address of the client as seen by the outermost trusted proxy"""
+    forwarded_for = flask.request.headers.get('X-Forwarded-For', '').split(',')
+    forwarded_for = [ip.strip() for ip in forwarded_for if ip.strip()]
+    if TRUSTED_PROXIES > 0 and len(forwarded_for) >= TRUSTED_PROXIES:
+        return forwarded_for[-TRUSTED_PROXIES]
+    return flask.request.remote_addr or ''
+
+
 application = flask.Flask(__name__)
 application.client = None
 print(sys.version, platform.python_version())
 create_client()
+application.faucet = Faucet()
 
 
 @application.route("/", methods=('POST',))
 def send_transaction():
-    auth_key = flask.request.args['auth_key']
+    request, error = enqueue()
+    if error is not None:
+        return error
 
-    # Return immediately if auth_key is invalid
-    if re.match('^[a-f0-9]{64}$', auth_key) is None:
-        return 'Malformed auth_key', 400
+    if not request.done.wait(SYNC_TIMEOUT):
+        return flask.jsonify(request.to_json()), 202
+    if request.status == FAILED:
+        return 'Mint failed: {}'.format(request.error), 500
+    if request.sequence_number is None:
+        return 'Mint submitted, but its sequence number is unknown: {}'.format(request.error), 500
+    return str(request.sequence_number)
 
-    try:
-        amount = decimal.Decimal(flask.request.args['amount'])
-    except decimal.InvalidOperation:
-        return 'Bad amount', 400
 
-    if amount > MAX_MINT:
-        return 'Exceeded max amount of {}'.format(MAX_MINT), 400
+@application.route("/requests", methods=('POST',))
+def queue_transaction():
+    request, error = enqueue()
+    if error is not None:
+        return error
+    return flask.jsonify(request.to_json()), 202
 
-    currency_code = flask.request.args['currency_code']
 
-    try:
-        create_client()
-        application.client.sendline(
-            "a m {} {} {} use_base_units".format(auth_key, amount, currency_code))
-        application.client.expect("Mint request submitted", timeout=2)
-
-        application.client.sendline("a la")
-        application.client.expect(r"sequence_number: ([0-9]+)", timeout=1)
-        application.client.terminate(True)
-    except pexpect.exceptions.ExceptionPexpect:
-        application.client.terminate(True)
-        raise
-
-    return application.client.match.groups()[0]
+@application.route("/requests/<request_id>", methods=('GET',))
+def request_status(request_id):
+    request = application.faucet.get(request_id)
+    if request is None:
+        return 'Unknown request', 404
+    return flask.jsonify(request.to_json())
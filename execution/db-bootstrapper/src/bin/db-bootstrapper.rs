@@ -39,6 +39,8 @@ fn main() -> Result<()> {
             &opt.db_dir,
             false, /* readonly */
             None,  /* pruner */
+            None,  /* archiver */
+            None,  /* waypoint_interval */
         )
         .with_context(|| format_err!("Failed to open DB."))?,
     );
@@ -9,13 +9,14 @@ use crate::{
     serializer::{SerializerClient, SerializerService},
     spawned_process::SpawnedProcess,
     thread::ThreadService,
+    verifying_client::VerifyingClient,
 };
 use executor::Executor;
 use libra_config::{
     config::{ExecutionCorrectnessService, NodeConfig},
     keys::KeyPair,
 };
-use libra_crypto::ed25519::Ed25519PrivateKey;
+use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use libra_global_constants::EXECUTION_KEY;
 use libra_secure_storage::{CryptoStorage, Storage};
 use libra_vm::LibraVM;
@@ -58,6 +59,30 @@ pub fn extract_execution_prikey(config: &mut NodeConfig) -> Option<Ed25519Privat
     }
 }
 
+/// Returns the public key the execution results are expected to be signed with, if signing is
+/// enabled. This is read from the test config or the secure storage, as the private key may only
+/// be available to a separate execution process.
+pub fn extract_execution_pubkey(config: &NodeConfig) -> Option<Ed25519PublicKey> {
+    if !config.execution.sign_vote_proposal {
+        return None;
+    }
+    if let Some(keypair) = config
+        .test
+        .as_ref()
+        .and_then(|test_config| test_config.execution_keypair.as_ref())
+    {
+        return Some(keypair.public_key());
+    }
+    let backend = &config.execution.backend;
+    let storage: Storage = backend.try_into().expect("Unable to initialize storage");
+    Some(
+        storage
+            .get_public_key(EXECUTION_KEY)
+            .expect("Missing execution public key in secure storage")
+            .public_key,
+    )
+}
+
 enum ExecutionCorrectnessWrapper {
     Local(Arc<Mutex<LocalService>>),
     Process(ProcessService),
@@ -68,13 +93,18 @@ enum ExecutionCorrectnessWrapper {
 
 pub struct ExecutionCorrectnessManager {
     internal_execution_correctness: ExecutionCorrectnessWrapper,
+    // If set, the execution results returned by clients are verified with this key
+    execution_pubkey: Option<Ed25519PublicKey>,
 }
 
 impl ExecutionCorrectnessManager {
     pub fn new(config: &mut NodeConfig) -> Self {
         match &config.execution.service {
             ExecutionCorrectnessService::Process(remote_service) => {
-                return Self::new_process(remote_service.server_address)
+                return Self::new_process(
+                    remote_service.server_address,
+                    extract_execution_pubkey(config),
+                )
             }
             ExecutionCorrectnessService::SpawnedProcess(_) => {
                 return Self::new_spawned_process(config)
@@ -108,17 +138,23 @@ impl ExecutionCorrectnessManager {
         let block_executor = Box::new(Executor::<LibraVM>::new(
             StorageClient::new(&storage_address).into(),
         ));
+        let execution_pubkey = execution_prikey.as_ref().map(Ed25519PublicKey::from);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Local(Arc::new(
                 Mutex::new(LocalService::new(block_executor, execution_prikey)),
             )),
+            execution_pubkey,
         }
     }
 
-    pub fn new_process(server_addr: SocketAddr) -> Self {
+    pub fn new_process(
+        server_addr: SocketAddr,
+        execution_pubkey: Option<Ed25519PublicKey>,
+    ) -> Self {
         let process_service = ProcessService::new(server_addr);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Process(process_service),
+            execution_pubkey,
         }
     }
 
@@ -129,11 +165,13 @@ impl ExecutionCorrectnessManager {
         let block_executor = Box::new(Executor::<LibraVM>::new(
             StorageClient::new(&storage_address).into(),
         ));
+        let execution_pubkey = execution_prikey.as_ref().map(Ed25519PublicKey::from);
         let serializer_service = SerializerService::new(block_executor, execution_prikey);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Serializer(Arc::new(
                 Mutex::new(serializer_service),
             )),
+            execution_pubkey,
         }
     }
 
@@ -141,6 +179,7 @@ impl ExecutionCorrectnessManager {
         let process = SpawnedProcess::new(config);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::SpawnedProcess(process),
+            execution_pubkey: extract_execution_pubkey(config),
         }
     }
 
@@ -148,13 +187,27 @@ impl ExecutionCorrectnessManager {
         storage_address: SocketAddr,
         execution_prikey: Option<Ed25519PrivateKey>,
     ) -> Self {
+        let execution_pubkey = execution_prikey.as_ref().map(Ed25519PublicKey::from);
         let thread = ThreadService::new(storage_address, execution_prikey);
         Self {
             internal_execution_correctness: ExecutionCorrectnessWrapper::Thread(thread),
+            execution_pubkey,
         }
     }
 
+    /// Returns a client of the service. If the execution results are signed, the client verifies
+    /// their signatures.
     pub fn client(&self) -> Box<dyn ExecutionCorrectness + Send + Sync> {
+        let client = self.unverified_client();
+        match &self.execution_pubkey {
+            Some(execution_pubkey) => {
+                Box::new(VerifyingClient::new(client, execution_pubkey.clone()))
+            }
+            None => client,
+        }
+    }
+
+    fn unverified_client(&self) -> Box<dyn ExecutionCorrectness + Send + Sync> {
         match &self.internal_execution_correctness {
             ExecutionCorrectnessWrapper::Local(local_service) => {
                 Box::new(LocalClient::new(local_service.clone()))
@@ -15,10 +15,12 @@ mod remote_service;
 mod serializer;
 mod spawned_process;
 mod thread;
+mod verifying_client;
 
 pub use crate::{
     execution_correctness::ExecutionCorrectness,
     execution_correctness_manager::ExecutionCorrectnessManager, process::Process,
+    verifying_client::VerifyingClient,
 };
 
 #[cfg(any(test, feature = "testing"))]
@@ -6,6 +6,7 @@ mod serializer;
 mod spawned_process;
 mod suite;
 mod thread;
+mod verifying_client;
 
 use executor::db_bootstrapper::bootstrap_db_if_empty;
 use libra_config::{config::NodeConfig, utils};
@@ -0,0 +1,65 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::start_storage_service;
+use crate::{
+    execution_correctness::ExecutionCorrectness, ExecutionCorrectnessManager, VerifyingClient,
+};
+use consensus_types::block::Block;
+use executor_types::Error;
+use libra_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    Uniform,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+// A client of a local service signing with `prikey`, verifying with `pubkey`
+fn verifying_client(
+    prikey: Option<Ed25519PrivateKey>,
+    pubkey: Ed25519PublicKey,
+) -> VerifyingClient {
+    let (config, _handle) = start_storage_service();
+    let execution_correctness_manager =
+        ExecutionCorrectnessManager::new_local(config.storage.address, prikey);
+    VerifyingClient::new(execution_correctness_manager.client(), pubkey)
+}
+
+fn execute_genesis(client: &mut VerifyingClient) -> Result<(), Error> {
+    let parent_block_id = client.committed_block_id().unwrap();
+    client
+        .execute_block(Block::make_genesis_block(), parent_block_id)
+        .map(|_| ())
+}
+
+#[test]
+fn test_valid_signature() {
+    let prikey = Ed25519PrivateKey::generate_for_testing();
+    let pubkey = Ed25519PublicKey::from(&prikey);
+    let mut client = verifying_client(Some(prikey), pubkey);
+    execute_genesis(&mut client).unwrap();
+}
+
+#[test]
+fn test_signature_with_another_key() {
+    let mut client = verifying_client(
+        Some(Ed25519PrivateKey::generate_for_testing()),
+        // generate_for_testing always returns the same key
+        Ed25519PublicKey::from(&Ed25519PrivateKey::generate(&mut StdRng::from_seed(
+            [1u8; 32],
+        ))),
+    );
+    match execute_genesis(&mut client) {
+        Err(Error::InvalidSignature(_)) => (),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
+
+#[test]
+fn test_missing_signature() {
+    let pubkey = Ed25519PublicKey::from(&Ed25519PrivateKey::generate_for_testing());
+    let mut client = verifying_client(None, pubkey);
+    match execute_genesis(&mut client) {
+        Err(Error::InvalidSignature(_)) => (),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
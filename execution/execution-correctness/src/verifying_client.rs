@@ -0,0 +1,76 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::execution_correctness::ExecutionCorrectness;
+use consensus_types::{block::Block, vote_proposal::VoteProposal};
+use executor_types::{Error, StateComputeResult};
+use libra_crypto::{
+    ed25519::Ed25519PublicKey,
+    hash::{CryptoHash, HashValue},
+    traits::Signature,
+};
+use libra_types::{
+    contract_event::ContractEvent, ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
+};
+
+/// Wraps the client of an ExecutionCorrectness service to verify the signature of each
+/// execution result with the execution public key, so that consensus rejects results which were
+/// not computed by the service it trusts before voting on them.
+pub struct VerifyingClient {
+    internal: Box<dyn ExecutionCorrectness + Send + Sync>,
+    public_key: Ed25519PublicKey,
+}
+
+impl VerifyingClient {
+    pub fn new(
+        internal: Box<dyn ExecutionCorrectness + Send + Sync>,
+        public_key: Ed25519PublicKey,
+    ) -> Self {
+        Self {
+            internal,
+            public_key,
+        }
+    }
+}
+
+impl ExecutionCorrectness for VerifyingClient {
+    fn committed_block_id(&mut self) -> Result<HashValue, Error> {
+        self.internal.committed_block_id()
+    }
+
+    fn reset(&mut self) -> Result<(), Error> {
+        self.internal.reset()
+    }
+
+    fn execute_block(
+        &mut self,
+        block: Block,
+        parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, Error> {
+        let result = self
+            .internal
+            .execute_block(block.clone(), parent_block_id)?;
+        let signature = result
+            .signature()
+            .as_ref()
+            .ok_or_else(|| Error::InvalidSignature("missing execution signature".into()))?;
+        let vote_proposal = VoteProposal::new(
+            result.extension_proof(),
+            block,
+            result.epoch_state().clone(),
+        );
+        signature
+            .verify(&vote_proposal.hash(), &self.public_key)
+            .map_err(|e| Error::InvalidSignature(format!("{}", e)))?;
+        Ok(result)
+    }
+
+    fn commit_blocks(
+        &mut self,
+        block_ids: Vec<HashValue>,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> Result<(Vec<Transaction>, Vec<ContractEvent>), Error> {
+        self.internal
+            .commit_blocks(block_ids, ledger_info_with_sigs)
+    }
+}
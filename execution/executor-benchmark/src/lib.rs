@@ -269,6 +269,8 @@ fn create_storage_service_and_executor(
             &config.storage.dir(),
             false, /* readonly */
             None,  /* pruner */
+            None,  /* archiver */
+            None,  /* waypoint_interval */
         )
         .expect("DB should open."),
     );
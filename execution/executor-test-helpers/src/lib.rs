@@ -65,10 +65,12 @@ pub fn extract_signer(config: &mut NodeConfig) -> ValidatorSigner {
 pub fn gen_block_metadata(index: u8, proposer: AccountAddress) -> BlockMetadata {
     BlockMetadata::new(
         gen_block_id(index),
+        gen_block_id(index.wrapping_sub(1)),
         index as u64,
         index as u64,
         vec![],
         proposer,
+        vec![],
     )
 }
 
@@ -16,6 +16,9 @@ pub enum Error {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Invalid execution signature: {0}")]
+    InvalidSignature(String),
 }
 
 impl From<anyhow::Error> for Error {
@@ -39,6 +39,18 @@ pub trait ChunkExecutor: Send {
         // carrying any epoch change LI.
         epoch_change_li: Option<LedgerInfoWithSignatures>,
     ) -> Result<Vec<ContractEvent>>;
+
+    /// Same as `execute_and_commit_chunk`, but executes several contiguous chunks one after
+    /// another and commits all of them to storage at once. Only the last chunk may carry a
+    /// LedgerInfo to commit. Returns the reconfiguration events of all the chunks.
+    fn execute_and_commit_chunks(
+        &mut self,
+        chunks: Vec<(
+            TransactionListWithProof,
+            LedgerInfoWithSignatures,
+            Option<LedgerInfoWithSignatures>,
+        )>,
+    ) -> Result<Vec<ContractEvent>>;
 }
 
 pub trait BlockExecutor: Send {
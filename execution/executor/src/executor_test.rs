@@ -352,6 +352,55 @@ fn test_executor_execute_and_commit_chunk() {
     assert_eq!(li, ledger_info);
 }
 
+#[test]
+fn test_executor_execute_and_commit_chunks() {
+    let first_batch_size = 30;
+    let second_batch_size = 40;
+    let third_batch_size = 20;
+    let overlapping_size = 5;
+
+    let (chunks, ledger_info) = {
+        let first_batch_start = 1;
+        let second_batch_start = first_batch_start + first_batch_size;
+        let third_batch_start = second_batch_start + second_batch_size - overlapping_size;
+        create_transaction_chunks(vec![
+            first_batch_start..first_batch_start + first_batch_size,
+            second_batch_start..second_batch_start + second_batch_size,
+            third_batch_start..third_batch_start + third_batch_size,
+        ])
+    };
+
+    let (config, _) = build_test_config();
+    let db = create_storage(&config);
+    let mut executor = Executor::<MockVM>::new(db.clone());
+
+    // Execute the first two chunks in one batch. Nothing carries a ledger info to commit, so we
+    // should still get the genesis ledger info from DB.
+    executor
+        .execute_and_commit_chunks(vec![
+            (chunks[0].clone(), ledger_info.clone(), None),
+            (chunks[1].clone(), ledger_info.clone(), None),
+        ])
+        .unwrap();
+    let li = db.reader.get_latest_ledger_info().unwrap();
+    assert_eq!(li.ledger_info().version(), 0);
+    assert_eq!(
+        db.reader.get_latest_state_root().unwrap().0,
+        first_batch_size + second_batch_size
+    );
+
+    // Execute the second chunk again together with the overlapping third one. After that we
+    // should get the new ledger info.
+    executor
+        .execute_and_commit_chunks(vec![
+            (chunks[1].clone(), ledger_info.clone(), None),
+            (chunks[2].clone(), ledger_info.clone(), None),
+        ])
+        .unwrap();
+    let li = db.reader.get_latest_ledger_info().unwrap();
+    assert_eq!(li, ledger_info);
+}
+
 #[test]
 fn test_executor_execute_and_commit_chunk_restart() {
     let first_batch_size = 30;
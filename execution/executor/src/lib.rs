@@ -353,7 +353,9 @@ where
                             bail!("Write set should be a subset of read set.")
                         }
                         Transaction::UserTransaction(txn) => match txn.payload() {
-                            TransactionPayload::Module(_) | TransactionPayload::Script(_) => {
+                            TransactionPayload::Module(_)
+                            | TransactionPayload::Script(_)
+                            | TransactionPayload::Batch(_) => {
                                 bail!("Write set should be a subset of read set.")
                             }
                             TransactionPayload::WriteSet(_) => (),
@@ -373,15 +375,13 @@ where
             let account_blob = AccountStateBlob::try_from(account_state)?;
             updated_blobs.insert(addr, account_blob);
         }
+        let updates = updated_blobs
+            .iter()
+            .map(|(addr, value)| (addr.hash(), value.clone()))
+            .collect::<Vec<_>>();
         let state_tree = Arc::new(
             previous_state_tree
-                .update(
-                    updated_blobs
-                        .iter()
-                        .map(|(addr, value)| (addr.hash(), value.clone()))
-                        .collect(),
-                    proof_reader,
-                )
+                .batch_update(&updates, proof_reader)
                 .expect("Failed to update state tree."),
         );
 
@@ -395,6 +395,16 @@ where
         };
     }
 
+    /// The senders of the user transactions, whose accounts are read by every transaction
+    /// prologue, so that they are loaded from storage in one batch before execution.
+    fn transaction_senders(transactions: &[Transaction]) -> Vec<AccountAddress> {
+        transactions
+            .iter()
+            .filter_map(|txn| txn.as_signed_user_txn().ok())
+            .map(|txn| txn.sender())
+            .collect()
+    }
+
     fn extract_reconfig_events(events: Vec<ContractEvent>) -> Vec<ContractEvent> {
         let new_epoch_event_key = on_chain_config::new_epoch_event_key();
         events
@@ -432,25 +442,27 @@ where
             executed_trees.state_tree(),
         )
     }
-}
 
-impl<V: VMExecutor> ChunkExecutor for Executor<V> {
-    fn execute_and_commit_chunk(
-        &mut self,
+    /// Executes a single chunk on top of `synced_trees`, whose state is only persisted up to
+    /// `persisted_trees`. Returns the executed output, the transactions to commit, the
+    /// reconfiguration events and the ledger info to commit, if any. Nothing is written to storage.
+    fn execute_chunk(
+        &self,
         txn_list_with_proof: TransactionListWithProof,
-        // Target LI that has been verified independently: the proofs are relative to this version.
         verified_target_li: LedgerInfoWithSignatures,
-        // An optional end of epoch LedgerInfo. We do not allow chunks that end epoch without
-        // carrying any epoch change LI.
         epoch_change_li: Option<LedgerInfoWithSignatures>,
-    ) -> Result<Vec<ContractEvent>> {
-        // Update the cache in executor to be consistent with latest synced state.
-        self.reset_cache()?;
-
+        persisted_trees: &ExecutedTrees,
+        synced_trees: &ExecutedTrees,
+    ) -> Result<(
+        ProcessedVMOutput,
+        Vec<TransactionToCommit>,
+        Vec<ContractEvent>,
+        Option<LedgerInfoWithSignatures>,
+    )> {
         info!(
             "Local synced version: {}. First transaction version in request: {:?}. \
              Number of transactions in request: {}.",
-            self.cache.synced_trees().txn_accumulator().num_leaves() - 1,
+            synced_trees.txn_accumulator().num_leaves() - 1,
             txn_list_with_proof.first_transaction_version,
             txn_list_with_proof.transactions.len(),
         );
@@ -458,7 +470,7 @@ impl<V: VMExecutor> ChunkExecutor for Executor<V> {
         let (num_txns_to_skip, first_version) = Self::verify_chunk(
             &txn_list_with_proof,
             &verified_target_li,
-            self.cache.synced_trees().txn_accumulator().num_leaves(),
+            synced_trees.txn_accumulator().num_leaves(),
         )?;
 
         info!("Skipping the first {} transactions.", num_txns_to_skip);
@@ -499,19 +511,21 @@ impl<V: VMExecutor> ChunkExecutor for Executor<V> {
 
             // The two accumulator root hashes should be identical.
             ensure!(
-                self.cache.synced_trees().state_id() == accu_from_proof.root_hash(),
+                synced_trees.state_id() == accu_from_proof.root_hash(),
                 "Fork happens because the current synced_trees doesn't match the txn list provided."
             )
         }
 
-        // Construct a StateView and pass the transactions to VM.
+        // Construct a StateView and pass the transactions to VM. State written by earlier chunks
+        // of the same batch only lives in the speculative tree of `synced_trees`.
         let state_view = VerifiedStateView::new(
             StateViewId::ChunkExecution { first_version },
             Arc::clone(&self.db.reader),
-            self.cache.synced_trees().version(),
-            self.cache.synced_trees().state_root(),
-            self.cache.synced_trees().state_tree(),
+            persisted_trees.version(),
+            persisted_trees.state_root(),
+            synced_trees.state_tree(),
         );
+        state_view.prefetch_accounts(&Self::transaction_senders(&transactions))?;
         let vm_outputs = {
             let _timer = OP_COUNTERS.timer("vm_execute_chunk_time_s");
             V::execute_block(transactions.to_vec(), &state_view)?
@@ -532,7 +546,7 @@ impl<V: VMExecutor> ChunkExecutor for Executor<V> {
             account_to_proof,
             &transactions,
             vm_outputs,
-            self.cache.synced_trees(),
+            synced_trees,
         )?;
 
         // Since we have verified the proofs, we just need to verify that each TransactionInfo
@@ -569,21 +583,83 @@ impl<V: VMExecutor> ChunkExecutor for Executor<V> {
 
         let ledger_info_to_commit =
             Self::find_chunk_li(verified_target_li, epoch_change_li, &output)?;
+        Ok((
+            output,
+            txns_to_commit,
+            reconfig_events,
+            ledger_info_to_commit,
+        ))
+    }
+}
+
+impl<V: VMExecutor> ChunkExecutor for Executor<V> {
+    fn execute_and_commit_chunk(
+        &mut self,
+        txn_list_with_proof: TransactionListWithProof,
+        // Target LI that has been verified independently: the proofs are relative to this version.
+        verified_target_li: LedgerInfoWithSignatures,
+        // An optional end of epoch LedgerInfo. We do not allow chunks that end epoch without
+        // carrying any epoch change LI.
+        epoch_change_li: Option<LedgerInfoWithSignatures>,
+    ) -> Result<Vec<ContractEvent>> {
+        self.execute_and_commit_chunks(vec![(
+            txn_list_with_proof,
+            verified_target_li,
+            epoch_change_li,
+        )])
+    }
+
+    fn execute_and_commit_chunks(
+        &mut self,
+        chunks: Vec<(
+            TransactionListWithProof,
+            LedgerInfoWithSignatures,
+            Option<LedgerInfoWithSignatures>,
+        )>,
+    ) -> Result<Vec<ContractEvent>> {
+        // Update the cache in executor to be consistent with latest synced state.
+        self.reset_cache()?;
+
+        let persisted_trees = self.cache.synced_trees().clone();
+        let first_version = persisted_trees.txn_accumulator().num_leaves();
+        let mut synced_trees = persisted_trees.clone();
+        let mut txns_to_commit = vec![];
+        let mut reconfig_events = vec![];
+        let mut ledger_info_to_commit = None;
+        for (txn_list_with_proof, verified_target_li, epoch_change_li) in chunks {
+            ensure!(
+                ledger_info_to_commit.is_none(),
+                "Only the last chunk of a batch can carry a LedgerInfo to commit."
+            );
+            let (output, mut chunk_txns_to_commit, mut chunk_reconfig_events, chunk_li) = self
+                .execute_chunk(
+                    txn_list_with_proof,
+                    verified_target_li,
+                    epoch_change_li,
+                    &persisted_trees,
+                    &synced_trees,
+                )?;
+            synced_trees = output.executed_trees().clone();
+            txns_to_commit.append(&mut chunk_txns_to_commit);
+            reconfig_events.append(&mut chunk_reconfig_events);
+            ledger_info_to_commit = chunk_li;
+        }
+
         if ledger_info_to_commit.is_none() && txns_to_commit.is_empty() {
             return Ok(reconfig_events);
         }
+        // The whole batch goes to storage in a single write.
         self.db.writer.save_transactions(
             &txns_to_commit,
             first_version,
             ledger_info_to_commit.as_ref(),
         )?;
 
-        let output_trees = output.executed_trees().clone();
         if let Some(ledger_info_with_sigs) = &ledger_info_to_commit {
             self.cache
-                .update_block_tree_root(output_trees, ledger_info_with_sigs.ledger_info());
+                .update_block_tree_root(synced_trees, ledger_info_with_sigs.ledger_info());
         } else {
-            self.cache.update_synced_trees(output_trees);
+            self.cache.update_synced_trees(synced_trees);
         }
         self.cache.reset();
         info!(
@@ -661,6 +737,7 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
                 StateViewId::BlockExecution { block_id },
                 &parent_block_executed_trees,
             );
+            state_view.prefetch_accounts(&Self::transaction_senders(&transactions))?;
 
             let vm_outputs = {
                 trace_code_block!("executor::execute_block", {"block", block_id});
@@ -789,16 +866,24 @@ impl<V: VMExecutor> BlockExecutor for Executor<V> {
         let txns_to_commit = &txns_to_keep[num_txns_to_skip as usize..];
 
         let num_txns_to_commit = txns_to_commit.len() as u64;
+        // The transactions and the ledger info are persisted in two phases, so that a crash in
+        // between leaves a record of the commit, which the DB completes when it is opened again.
         {
+            trace_code_block!("storage::pre_commit_transactions", {"block", block_id_to_commit});
             let _timer = OP_COUNTERS.timer("storage_save_transactions_time_s");
             OP_COUNTERS.observe("storage_save_transactions.count", num_txns_to_commit as f64);
             assert_eq!(first_version_to_commit, version + 1 - num_txns_to_commit);
-            self.db.writer.save_transactions(
+            self.db.writer.pre_commit_transactions(
                 txns_to_commit,
                 first_version_to_commit,
-                Some(&ledger_info_with_sigs),
+                &ledger_info_with_sigs,
             )?;
         }
+        {
+            trace_code_block!("storage::commit_ledger_info", {"block", block_id_to_commit});
+            let _timer = OP_COUNTERS.timer("storage_commit_ledger_info_time_s");
+            self.db.writer.commit_ledger_info(&ledger_info_with_sigs)?;
+        }
 
         // Prune the tree.
         for block in blocks {
@@ -1,11 +1,22 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{balance_ap, encode_mint_transaction, encode_transfer_transaction, seqnum_ap, MockVM};
+use super::{
+    balance_ap, encode_mint_program, encode_mint_transaction, encode_transfer_transaction,
+    seqnum_ap, MockVM, UNKNOWN_SCRIPT_STATUS,
+};
 use anyhow::Result;
+use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use libra_state_view::StateView;
-use libra_types::{access_path::AccessPath, account_address::AccountAddress, write_set::WriteOp};
+use libra_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::LBR_NAME,
+    transaction::{RawTransaction, Transaction, TransactionPayload},
+    write_set::WriteOp,
+};
 use libra_vm::VMExecutor;
+use std::time::Duration;
 
 fn gen_address(index: u8) -> AccountAddress {
     AccountAddress::new([index; AccountAddress::LENGTH])
@@ -126,3 +137,43 @@ fn test_mock_vm_payment() {
         ]
     );
 }
+
+#[test]
+fn test_mock_vm_discards_batch() {
+    let privkey = Ed25519PrivateKey::generate_for_testing();
+    let batch = RawTransaction::new(
+        gen_address(0),
+        0,
+        TransactionPayload::Batch(vec![encode_mint_program(100)]),
+        0,
+        0,
+        LBR_NAME.to_owned(),
+        Duration::from_secs(0),
+    )
+    .sign(&privkey, privkey.public_key())
+    .expect("Failed to sign raw transaction.")
+    .into_inner();
+    let txns = vec![
+        Transaction::UserTransaction(batch),
+        encode_mint_transaction(gen_address(1), 100),
+    ];
+
+    let outputs =
+        MockVM::execute_block(txns, &MockStateView).expect("MockVM should not fail to start");
+
+    assert_eq!(outputs[0].status(), &*UNKNOWN_SCRIPT_STATUS);
+    assert!(outputs[0].write_set().is_empty());
+    assert_eq!(
+        outputs[1].write_set().iter().cloned().collect::<Vec<_>>(),
+        vec![
+            (
+                balance_ap(gen_address(1)),
+                WriteOp::Value(100u64.to_le_bytes().to_vec())
+            ),
+            (
+                seqnum_ap(gen_address(1)),
+                WriteOp::Value(1u64.to_le_bytes().to_vec())
+            ),
+        ]
+    );
+}
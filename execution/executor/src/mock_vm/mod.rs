@@ -39,6 +39,7 @@ enum MockVMTransaction {
         amount: u64,
     },
     Reconfiguration,
+    Batch,
 }
 
 pub static KEEP_STATUS: Lazy<TransactionStatus> =
@@ -49,6 +50,10 @@ pub static DISCARD_STATUS: Lazy<TransactionStatus> = Lazy::new(|| {
     TransactionStatus::Discard(VMStatus::new(StatusCode::ABORTED).with_sub_status(10))
 });
 
+// The MockVM only knows its own mint and payment scripts, and discards batches of scripts.
+pub static UNKNOWN_SCRIPT_STATUS: Lazy<TransactionStatus> =
+    Lazy::new(|| TransactionStatus::Discard(VMStatus::new(StatusCode::UNKNOWN_SCRIPT)));
+
 pub struct MockVM;
 
 impl VMExecutor for MockVM {
@@ -160,6 +165,14 @@ impl VMExecutor for MockVM {
                         KEEP_STATUS.clone(),
                     ));
                 }
+                MockVMTransaction::Batch => {
+                    outputs.push(TransactionOutput::new(
+                        WriteSet::default(),
+                        vec![],
+                        0,
+                        UNKNOWN_SCRIPT_STATUS.clone(),
+                    ));
+                }
             }
         }
 
@@ -373,5 +386,6 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
         TransactionPayload::Module(_) => {
             unimplemented!("MockVM does not support Module transaction payload.")
         }
+        TransactionPayload::Batch(_) => MockVMTransaction::Batch,
     }
 }
@@ -23,7 +23,7 @@ use libra_types::{
     account_address::AccountAddress,
     account_config::{
         from_currency_code_string, lbr_type_tag, treasury_compliance_account_address,
-        BalanceResource, LBR_NAME,
+        BalanceResource, LBR, LBR_NAME,
     },
     account_state::AccountState,
     account_state_blob::AccountStateBlob,
@@ -267,8 +267,8 @@ fn test_pre_genesis() {
                 WriteOp::Value(lcs::to_bytes(&ValidatorSet::new(vec![])).unwrap()),
             ),
             (
-                AccessPath::new(account1, BalanceResource::resource_path()),
-                WriteOp::Value(lcs::to_bytes(&BalanceResource::new(1000)).unwrap()),
+                AccessPath::new(account1, BalanceResource::<LBR>::resource_path()),
+                WriteOp::Value(lcs::to_bytes(&BalanceResource::<LBR>::new(1000)).unwrap()),
             ),
         ])
         .freeze()
@@ -338,8 +338,8 @@ fn test_new_genesis() {
                 WriteOp::Value(lcs::to_bytes(&configuration.bump_epoch_for_test()).unwrap()),
             ),
             (
-                AccessPath::new(account1, BalanceResource::resource_path()),
-                WriteOp::Value(lcs::to_bytes(&BalanceResource::new(1_000_000)).unwrap()),
+                AccessPath::new(account1, BalanceResource::<LBR>::resource_path()),
+                WriteOp::Value(lcs::to_bytes(&BalanceResource::<LBR>::new(1_000_000)).unwrap()),
             ),
         ])
         .freeze()
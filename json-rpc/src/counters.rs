@@ -1,7 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_metrics::{register_int_gauge_vec, IntGaugeVec};
+use libra_metrics::{
+    register_int_counter, register_int_gauge, register_int_gauge_vec, IntCounter, IntGauge,
+    IntGaugeVec,
+};
 use once_cell::sync::Lazy;
 
 /// Cumulative number of valid requests that the JSON RPC client service receives
@@ -28,3 +31,21 @@ pub static INVALID_REQUESTS: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of connections currently open to the JSON RPC server
+pub static OPEN_CONNECTIONS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "libra_client_service_open_connections",
+        "Number of connections currently open to the JSON RPC server"
+    )
+    .unwrap()
+});
+
+/// Cumulative number of connections closed because rpc.max_connections were already open
+pub static REJECTED_CONNECTIONS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "libra_client_service_rejected_connections_count",
+        "Cumulative number of connections closed because of the connection limit"
+    )
+    .unwrap()
+});
@@ -11,17 +11,21 @@
 //! Module organization:
 //! ├── methods.rs        # contains all available JSON RPC method handlers
 //! ├── runtime.rs        # implementation of JSON RPC protocol over HTTP
+//! ├── server.rs         # TLS, CORS and connection limits of the HTTP server
 //! ├── tests.rs          # tests
 
 #[macro_use]
 mod util;
 
 mod counters;
+mod limits;
 mod methods;
 mod runtime;
+mod server;
 
 pub use libra_json_rpc_types::{errors, views};
 
+pub use limits::RpcLimits;
 pub use runtime::{bootstrap, bootstrap_from_config};
 
 #[cfg(any(feature = "fuzzing", test))]
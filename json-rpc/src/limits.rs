@@ -0,0 +1,55 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_config::config::RpcConfig;
+use std::{
+    sync::atomic::{AtomicU16, AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Limits on JSON-RPC requests and responses. These are shared with the running
+/// server, so that they can be updated without restarting it.
+#[derive(Debug)]
+pub struct RpcLimits {
+    batch_size_limit: AtomicU16,
+    page_size_limit: AtomicU16,
+    submit_and_wait_timeout_ms: AtomicU64,
+}
+
+impl RpcLimits {
+    pub fn new(config: &RpcConfig) -> Self {
+        Self {
+            batch_size_limit: AtomicU16::new(config.batch_size_limit),
+            page_size_limit: AtomicU16::new(config.page_size_limit),
+            submit_and_wait_timeout_ms: AtomicU64::new(config.submit_and_wait_timeout_ms),
+        }
+    }
+
+    /// Applies the limits in the config to all subsequent requests.
+    pub fn update(&self, config: &RpcConfig) {
+        self.batch_size_limit
+            .store(config.batch_size_limit, Ordering::Relaxed);
+        self.page_size_limit
+            .store(config.page_size_limit, Ordering::Relaxed);
+        self.submit_and_wait_timeout_ms
+            .store(config.submit_and_wait_timeout_ms, Ordering::Relaxed);
+    }
+
+    pub fn batch_size_limit(&self) -> u16 {
+        self.batch_size_limit.load(Ordering::Relaxed)
+    }
+
+    pub fn page_size_limit(&self) -> u16 {
+        self.page_size_limit.load(Ordering::Relaxed)
+    }
+
+    pub fn submit_and_wait_timeout(&self) -> Duration {
+        Duration::from_millis(self.submit_and_wait_timeout_ms.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for RpcLimits {
+    fn default() -> Self {
+        Self::new(&RpcConfig::default())
+    }
+}
@@ -4,52 +4,97 @@
 //! Module contains RPC method handlers for Full Node JSON-RPC interface
 use crate::{
     errors::JsonRpcError,
+    limits::RpcLimits,
     views::{
-        AccountStateWithProofView, AccountView, BlockMetadata, CurrencyInfoView, EventView,
-        StateProofView, TransactionView,
+        AccountStateWithProofView, AccountView, BlockMetadata, BytesView, CurrencyInfoView,
+        EventView, EventWithProofView, ExpirationWindowView, GasPriceEstimateView,
+        SignedWaypointView, SourceMapView, StateProofView, TransactionListWithProofView,
+        TransactionView,
     },
 };
-use anyhow::{ensure, format_err, Error, Result};
+use anyhow::{bail, ensure, format_err, Error, Result};
 use core::future::Future;
 use debug_interface::prelude::*;
 use futures::{channel::oneshot, SinkExt};
 use libra_config::config::RoleType;
 use libra_crypto::hash::CryptoHash;
-use libra_mempool::MempoolClientSender;
+use libra_mempool::{
+    txn_latency::{self, TxnStage},
+    MempoolClientSender,
+};
 use libra_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
     account_config::{from_currency_code_string, CurrencyInfoResource},
     account_state::AccountState,
+    block_congestion::BlockCongestionResource,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     mempool_status::MempoolStatusCode,
     move_resource::MoveStorage,
-    on_chain_config::{OnChainConfig, RegisteredCurrencies},
-    transaction::SignedTransaction,
+    on_chain_config::{
+        config_address, ConfigStorage, LibraTransactionTimeout, OnChainConfig,
+        RegisteredCurrencies, VMConfig,
+    },
+    transaction::{SignedTransaction, Transaction, TransactionWithProof},
+};
+use move_core_types::{
+    gas_schedule::GasAlgebra, identifier::Identifier, language_storage::ModuleId,
+    parser::parse_type_tag,
 };
 use network::counters;
 use serde_json::Value;
-use std::{collections::HashMap, convert::TryFrom, ops::Deref, pin::Pin, str::FromStr, sync::Arc};
-use storage_interface::DbReader;
+use std::{
+    collections::HashMap, convert::TryFrom, ops::Deref, pin::Pin, str::FromStr, sync::Arc,
+    time::Duration,
+};
+use storage_interface::{DbReader, LedgerInfoSubscription, Order};
+use tokio::time::{delay_for, timeout};
+
+/// How often `submit_and_wait` polls storage for the transaction, when storage doesn't publish its
+/// commits
+const SUBMIT_AND_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone)]
 pub(crate) struct JsonRpcService {
     db: Arc<dyn DbReader>,
+    /// The latest ledger info, published by the DB on commit when it runs in the same process
+    ledger_infos: Option<LedgerInfoSubscription>,
     mempool_sender: MempoolClientSender,
     role: RoleType,
+    limits: Arc<RpcLimits>,
 }
 
 impl JsonRpcService {
-    pub fn new(db: Arc<dyn DbReader>, mempool_sender: MempoolClientSender, role: RoleType) -> Self {
+    pub fn new(
+        db: Arc<dyn DbReader>,
+        mempool_sender: MempoolClientSender,
+        role: RoleType,
+        limits: Arc<RpcLimits>,
+    ) -> Self {
+        let ledger_infos = db.subscribe_to_ledger_infos();
         Self {
             db,
+            ledger_infos,
             mempool_sender,
             role,
+            limits,
         }
     }
 
+    pub fn batch_size_limit(&self) -> u16 {
+        self.limits.batch_size_limit()
+    }
+
     pub fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures> {
-        self.db.get_latest_ledger_info()
+        match self
+            .ledger_infos
+            .as_ref()
+            .and_then(|li| li.borrow().clone())
+        {
+            Some(ledger_info) => Ok(ledger_info),
+            None => self.db.get_latest_ledger_info(),
+        }
     }
 }
 
@@ -79,7 +124,96 @@ impl JsonRpcRequest {
 async fn submit(mut service: JsonRpcService, request: JsonRpcRequest) -> Result<()> {
     let txn_payload: String = serde_json::from_value(request.get_param(0))?;
     let transaction: SignedTransaction = lcs::from_bytes(&hex::decode(txn_payload)?)?;
+    submit_transaction(&mut service, transaction).await
+}
+
+/// Submits transaction to full node, and waits until it is committed or expires, for at most the
+/// timeout set by the server. Returns the committed transaction.
+async fn submit_and_wait(
+    mut service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<TransactionView> {
+    let txn_payload: String = serde_json::from_value(request.get_param(0))?;
+    let transaction: SignedTransaction = lcs::from_bytes(&hex::decode(txn_payload)?)?;
+    let sender = transaction.sender();
+    let sequence_number = transaction.sequence_number();
+    let expiration_usecs = transaction.expiration_time().as_micros() as u64;
+    let hash = Transaction::UserTransaction(transaction.clone()).hash();
+
+    submit_transaction(&mut service, transaction).await?;
+    let wait_timeout = service.limits.submit_and_wait_timeout();
+    let tx = timeout(
+        wait_timeout,
+        wait_for_transaction(service, sender, sequence_number, expiration_usecs),
+    )
+    .await
+    .map_err(|_| {
+        format_err!(
+            "transaction {} was not committed within {} ms",
+            hash,
+            wait_timeout.as_millis()
+        )
+    })??;
+    ensure!(
+        tx.transaction.hash() == hash,
+        "sequence number {} of account {} was used by transaction {}",
+        sequence_number,
+        sender,
+        tx.transaction.hash()
+    );
+    Ok(transaction_view(tx))
+}
+
+/// Waits until the transaction of `sender` with `sequence_number` is committed, checking storage
+/// on each commit. Fails once the ledger is past the expiration time of the transaction.
+async fn wait_for_transaction(
+    service: JsonRpcService,
+    sender: AccountAddress,
+    sequence_number: u64,
+    expiration_usecs: u64,
+) -> Result<TransactionWithProof> {
+    let mut ledger_infos = service.ledger_infos.clone();
+    loop {
+        let ledger_info = service.get_latest_ledger_info()?;
+        let ledger_info = ledger_info.ledger_info();
+        if let Some(tx) =
+            service
+                .db
+                .get_txn_by_account(sender, sequence_number, ledger_info.version(), true)?
+        {
+            return Ok(tx);
+        }
+        if ledger_info.timestamp_usecs() >= expiration_usecs {
+            bail!(
+                "transaction {} of account {} expired",
+                sequence_number,
+                sender
+            );
+        }
+        wait_for_commit(&mut ledger_infos).await;
+    }
+}
+
+/// Waits until a new ledger info is committed. The storage only publishes its commits when it
+/// runs in the same process, otherwise it is polled.
+async fn wait_for_commit(ledger_infos: &mut Option<LedgerInfoSubscription>) {
+    if let Some(subscription) = ledger_infos {
+        if subscription.recv().await.is_some() {
+            return;
+        }
+        // The storage is shutting down.
+        *ledger_infos = None;
+    }
+    delay_for(SUBMIT_AND_WAIT_POLL_INTERVAL).await
+}
+
+/// Submits a transaction to mempool, failing if it is not accepted
+async fn submit_transaction(
+    service: &mut JsonRpcService,
+    transaction: SignedTransaction,
+) -> Result<()> {
     trace_code_block!("json-rpc::submit", {"txn", transaction.sender(), transaction.sequence_number()});
+    txn_latency::record_txn(&transaction, TxnStage::Submitted);
 
     let (req_sender, callback) = oneshot::channel();
     service
@@ -150,9 +284,11 @@ async fn get_transactions(
     let limit: u64 = serde_json::from_value(request.get_param(1))?;
     let include_events: bool = serde_json::from_value(request.get_param(2))?;
 
+    let page_size_limit = service.limits.page_size_limit();
     ensure!(
-        limit > 0 && limit <= 1000,
-        "limit must be smaller than 1000"
+        limit > 0 && limit <= u64::from(page_size_limit),
+        "limit must be no greater than {}",
+        page_size_limit
     );
 
     let txs =
@@ -199,6 +335,38 @@ async fn get_transactions(
     Ok(result)
 }
 
+/// Returns transactions along with their events and the proof of their transaction infos relative
+/// to the ledger_version specified by the client, or to the latest version if it is not specified.
+/// Unlike get_transactions, this allows clients to verify what they read, e.g., to replay
+/// transactions.
+async fn get_transactions_with_proofs(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<TransactionListWithProofView> {
+    let start_version: u64 = serde_json::from_value(request.get_param(0))?;
+    let limit: u64 = serde_json::from_value(request.get_param(1))?;
+    let ledger_version =
+        serde_json::from_value::<u64>(request.get_param(2)).unwrap_or_else(|_| request.version());
+
+    let page_size_limit = service.limits.page_size_limit();
+    ensure!(
+        limit > 0 && limit <= u64::from(page_size_limit),
+        "limit must be no greater than {}",
+        page_size_limit
+    );
+    ensure!(
+        ledger_version <= request.version(),
+        "ledger_version {} is greater than the latest version {}",
+        ledger_version,
+        request.version()
+    );
+
+    let txs = service
+        .db
+        .get_transactions(start_version, limit, ledger_version, true)?;
+    TransactionListWithProofView::try_from(txs)
+}
+
 /// Returns account transaction by account and sequence_number
 async fn get_account_transaction(
     service: JsonRpcService,
@@ -221,28 +389,32 @@ async fn get_account_transaction(
                 "Storage layer didn't return events when requested!"
             );
         }
-        let tx_version = tx.version;
-
-        let events = tx
-            .events
-            .unwrap_or_default()
-            .into_iter()
-            .map(|x| ((tx_version, x).into()))
-            .collect();
-
-        Ok(Some(TransactionView {
-            version: tx_version,
-            hash: tx.transaction.hash().to_string(),
-            transaction: tx.transaction.into(),
-            events,
-            vm_status: tx.proof.transaction_info().major_status(),
-            gas_used: tx.proof.transaction_info().gas_used(),
-        }))
+        Ok(Some(transaction_view(tx)))
     } else {
         Ok(None)
     }
 }
 
+fn transaction_view(tx: TransactionWithProof) -> TransactionView {
+    let tx_version = tx.version;
+
+    let events = tx
+        .events
+        .unwrap_or_default()
+        .into_iter()
+        .map(|x| ((tx_version, x).into()))
+        .collect();
+
+    TransactionView {
+        version: tx_version,
+        hash: tx.transaction.hash().to_string(),
+        transaction: tx.transaction.into(),
+        events,
+        vm_status: tx.proof.transaction_info().major_status(),
+        gas_used: tx.proof.transaction_info().gas_used(),
+    }
+}
+
 /// Returns events by given access path
 async fn get_events(service: JsonRpcService, request: JsonRpcRequest) -> Result<Vec<EventView>> {
     let raw_event_key: String = serde_json::from_value(request.get_param(0))?;
@@ -261,6 +433,71 @@ async fn get_events(service: JsonRpcService, request: JsonRpcRequest) -> Result<
     Ok(events)
 }
 
+/// Returns events by given access path, from `start` in the given order, each with its proof
+/// towards the ledger at `known_version`, or at the version of the request if not specified.
+/// Clients paginating through a stream pass the same `known_version` to every request, so that
+/// the pages are consistent with each other.
+async fn get_events_with_proofs(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Vec<EventWithProofView>> {
+    let raw_event_key: String = serde_json::from_value(request.get_param(0))?;
+    let start: u64 = serde_json::from_value(request.get_param(1))?;
+    let order: String = serde_json::from_value(request.get_param(2))?;
+    let limit: u64 = serde_json::from_value(request.get_param(3))?;
+    let known_version =
+        serde_json::from_value::<u64>(request.get_param(4)).unwrap_or_else(|_| request.version());
+
+    let order = match order.as_str() {
+        "ascending" => Order::Ascending,
+        "descending" => Order::Descending,
+        _ => return Err(format_err!("invalid order {}", order)),
+    };
+    let page_size_limit = service.limits.page_size_limit();
+    ensure!(
+        limit > 0 && limit <= u64::from(page_size_limit),
+        "limit must be no greater than {}",
+        page_size_limit
+    );
+    ensure!(
+        known_version <= request.version(),
+        "known_version {} is greater than the latest version {}",
+        known_version,
+        request.version()
+    );
+
+    let event_key = EventKey::try_from(&hex::decode(raw_event_key)?[..])?;
+    service
+        .db
+        .get_events_with_proof_by_event_key(&event_key, start, order, limit, Some(known_version))?
+        .into_iter()
+        .map(EventWithProofView::try_from)
+        .collect()
+}
+
+/// Returns the events of a typed event stream, given the account and the type of the events
+async fn get_events_by_type(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Vec<EventView>> {
+    let address: String = serde_json::from_value(request.get_param(0))?;
+    let type_tag: String = serde_json::from_value(request.get_param(1))?;
+    let start: u64 = serde_json::from_value(request.get_param(2))?;
+    let limit: u64 = serde_json::from_value(request.get_param(3))?;
+
+    let account = AccountAddress::try_from(address)?;
+    let event_key = EventKey::new_for_type(&account, &parse_type_tag(&type_tag)?);
+    let events_with_proof = service.db.get_events(&event_key, start, true, limit)?;
+
+    let req_version = request.version();
+    let events = events_with_proof
+        .into_iter()
+        .filter(|(version, _event)| version <= &req_version)
+        .map(|event| event.into())
+        .collect();
+    Ok(events)
+}
+
 /// Returns meta information about supported currencies
 async fn currencies_info(
     service: JsonRpcService,
@@ -327,6 +564,100 @@ async fn get_account_state_with_proof(
     )?)
 }
 
+/// Returns the compressed source map published alongside a module, if any
+async fn get_source_map(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<Option<SourceMapView>> {
+    let address: String = serde_json::from_value(request.get_param(0))?;
+    let module_name: String = serde_json::from_value(request.get_param(1))?;
+    let module_id = ModuleId::new(
+        AccountAddress::from_str(&address)?,
+        Identifier::new(module_name)?,
+    );
+    let blob = service
+        .db
+        .get_account_state_with_proof_by_version(*module_id.address(), request.version())?
+        .0;
+    if let Some(blob) = blob {
+        let account_state = AccountState::try_from(&blob)?;
+        let path = AccessPath::source_map_access_path(&module_id);
+        if let Some(source_map) = account_state.get(&path.path) {
+            return Ok(Some(SourceMapView {
+                source_map: BytesView::from(source_map),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the range of gas unit prices the VM currently accepts: the minimum is raised above the
+/// one of the gas schedule when the recent blocks were congested
+async fn get_gas_price_estimate(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<GasPriceEstimateView> {
+    let raw_data = service.db.deref().batch_fetch_resources_by_version(
+        vec![
+            VMConfig::CONFIG_ID.access_path(),
+            BlockCongestionResource::access_path(),
+        ],
+        request.version(),
+    )?;
+    ensure!(raw_data.len() == 2, "invalid storage result");
+    let gas_constants = VMConfig::deserialize_into_config(&raw_data[0])?
+        .gas_schedule
+        .gas_constants;
+    let congestion = lcs::from_bytes::<BlockCongestionResource>(&raw_data[1])?;
+    Ok(GasPriceEstimateView {
+        min_gas_unit_price: congestion.min_gas_unit_price(&gas_constants),
+        max_gas_unit_price: gas_constants.max_price_per_gas_unit.get(),
+        recent_block_fullness: congestion.recent_fullness(),
+    })
+}
+
+/// Returns the expiration window: transactions must expire after the timestamp of the ledger, and
+/// at most `duration_microseconds` after it
+async fn get_expiration_window(
+    service: JsonRpcService,
+    request: JsonRpcRequest,
+) -> Result<ExpirationWindowView> {
+    let blob = service
+        .db
+        .get_account_state_with_proof_by_version(config_address(), request.version())?
+        .0
+        .ok_or_else(|| format_err!("missing config account state"))?;
+    let account_state = AccountState::try_from(&blob)?;
+    let timeout = LibraTransactionTimeout::fetch_config(AccountStateConfigStorage(&account_state))
+        .ok_or_else(|| format_err!("missing expiration window config"))?;
+    Ok(ExpirationWindowView {
+        timestamp: request.ledger_info.ledger_info().timestamp_usecs(),
+        duration_microseconds: timeout.duration_microseconds,
+    })
+}
+
+/// Reads the on-chain configs from the account state of the config address
+struct AccountStateConfigStorage<'a>(&'a AccountState);
+
+impl ConfigStorage for AccountStateConfigStorage<'_> {
+    fn fetch_config(&self, access_path: AccessPath) -> Option<Vec<u8>> {
+        self.0.get(&access_path.path).cloned()
+    }
+}
+
+/// Returns the waypoint emitted last by storage, with the ledger info it was generated from, so
+/// that new nodes can bootstrap from a recent waypoint instead of genesis
+async fn get_latest_waypoint(
+    service: JsonRpcService,
+    _request: JsonRpcRequest,
+) -> Result<Option<SignedWaypointView>> {
+    service
+        .db
+        .get_latest_signed_waypoint()?
+        .map(SignedWaypointView::try_from)
+        .transpose()
+}
+
 /// Returns the number of peers this node is connected to
 async fn get_network_status(service: JsonRpcService, _request: JsonRpcRequest) -> Result<u64> {
     let blah = counters::LIBRA_NETWORK_PEERS
@@ -340,9 +671,16 @@ async fn get_network_status(service: JsonRpcService, _request: JsonRpcRequest) -
 pub(crate) fn build_registry() -> RpcRegistry {
     let mut registry = RpcRegistry::new();
     register_rpc_method!(registry, "submit", submit, 1);
+    register_rpc_method!(registry, "submit_and_wait", submit_and_wait, 1);
     register_rpc_method!(registry, "get_metadata", get_metadata, 1);
     register_rpc_method!(registry, "get_account_state", get_account_state, 1);
     register_rpc_method!(registry, "get_transactions", get_transactions, 3);
+    register_rpc_method!(
+        registry,
+        "get_transactions_with_proofs",
+        get_transactions_with_proofs,
+        3
+    );
     register_rpc_method!(
         registry,
         "get_account_transaction",
@@ -350,6 +688,13 @@ pub(crate) fn build_registry() -> RpcRegistry {
         3
     );
     register_rpc_method!(registry, "get_events", get_events, 3);
+    register_rpc_method!(registry, "get_events_by_type", get_events_by_type, 4);
+    register_rpc_method!(
+        registry,
+        "get_events_with_proofs",
+        get_events_with_proofs,
+        5
+    );
     register_rpc_method!(registry, "get_currencies", currencies_info, 0);
 
     register_rpc_method!(registry, "get_state_proof", get_state_proof, 1);
@@ -359,7 +704,16 @@ pub(crate) fn build_registry() -> RpcRegistry {
         get_account_state_with_proof,
         3
     );
+    register_rpc_method!(registry, "get_source_map", get_source_map, 2);
     register_rpc_method!(registry, "get_network_status", get_network_status, 0);
+    register_rpc_method!(
+        registry,
+        "get_gas_price_estimate",
+        get_gas_price_estimate,
+        0
+    );
+    register_rpc_method!(registry, "get_expiration_window", get_expiration_window, 0);
+    register_rpc_method!(registry, "get_latest_waypoint", get_latest_waypoint, 0);
 
     registry
 }
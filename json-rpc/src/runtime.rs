@@ -4,14 +4,20 @@
 use crate::{
     counters,
     errors::JsonRpcError,
+    limits::RpcLimits,
     methods::{build_registry, JsonRpcRequest, JsonRpcService, RpcRegistry},
+    server,
 };
 use futures::future::join_all;
-use libra_config::config::{NodeConfig, RoleType};
+use libra_config::config::{NodeConfig, RoleType, RpcConfig};
 use libra_mempool::MempoolClientSender;
+use libra_metrics::health::{register_health_reporter, ComponentHealth};
 use libra_types::ledger_info::LedgerInfoWithSignatures;
 use serde_json::{map::Map, Value};
-use std::{net::SocketAddr, sync::Arc};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use storage_interface::DbReader;
 use tokio::runtime::{Builder, Runtime};
 use warp::{
@@ -22,10 +28,11 @@ use warp::{
 /// Creates HTTP server (warp-based) that serves JSON RPC requests
 /// Returns handle to corresponding Tokio runtime
 pub fn bootstrap(
-    address: SocketAddr,
+    config: &RpcConfig,
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     role: RoleType,
+    limits: Arc<RpcLimits>,
 ) -> Runtime {
     let runtime = Builder::new()
         .thread_name("rpc-")
@@ -35,16 +42,29 @@ pub fn bootstrap(
         .expect("[rpc] failed to create runtime");
 
     let registry = Arc::new(build_registry());
-    let service = JsonRpcService::new(libra_db, mp_sender, role);
+    let service = JsonRpcService::new(libra_db, mp_sender, role, limits);
 
     let handler = warp::any()
         .and(warp::path::end())
         .and(warp::post())
         .and(warp::header::exact("content-type", "application/json"))
+        .and(warp::body::content_length_limit(
+            config.content_length_limit as u64,
+        ))
         .and(warp::body::json())
         .and(warp::any().map(move || service.clone()))
         .and(warp::any().map(move || Arc::clone(&registry)))
         .and_then(rpc_endpoint);
+    let handler = match &config.cors {
+        Some(cors) => handler
+            .with(server::cors(cors))
+            .map(|reply| Box::new(reply) as Box<dyn warp::Reply>)
+            .boxed(),
+        None => handler.boxed(),
+    };
+    let tls_acceptor = config.tls.as_ref().map(|tls| {
+        server::tls_acceptor(tls).expect("[rpc] failed to load TLS certificate and key")
+    });
 
     // Ensure that we actually bind to the socket first before spawning the
     // server tasks. This helps in tests to prevent races where a client attempts
@@ -53,18 +73,49 @@ pub fn bootstrap(
     //
     // Note: we need to enter the runtime context first to actually bind, since
     //       tokio TcpListener can only be bound inside a tokio context.
-    let server = runtime.enter(move || warp::serve(handler).bind(address));
-    runtime.handle().spawn(server);
+    let address = config.address;
+    let listener = runtime
+        .enter(|| server::bind(address))
+        .expect("[rpc] failed to bind");
+    let server = server::serve(handler, listener, config.max_connections, tls_acceptor);
+
+    let stopped = Arc::new(AtomicBool::new(false));
+    let guard = StoppedGuard(stopped.clone());
+    register_health_reporter(
+        "json_rpc",
+        Box::new(move || {
+            if stopped.load(Ordering::Relaxed) {
+                ComponentHealth::unhealthy(format!("server on {} has stopped", address))
+            } else {
+                ComponentHealth::healthy(format!("serving on {}", address))
+            }
+        }),
+    );
+    runtime.handle().spawn(async move {
+        let _guard = guard;
+        server.await
+    });
     runtime
 }
 
+/// Marks the server as stopped once its task ends, whether it completes or is cancelled
+struct StoppedGuard(Arc<AtomicBool>);
+
+impl Drop for StoppedGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Creates JSON RPC endpoint by given node config
+/// The limits are shared with the caller, so that they can be updated while the server is running
 pub fn bootstrap_from_config(
     config: &NodeConfig,
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    limits: Arc<RpcLimits>,
 ) -> Runtime {
-    bootstrap(config.rpc.address, libra_db, mp_sender, config.base.role)
+    bootstrap(&config.rpc, libra_db, mp_sender, config.base.role, limits)
 }
 
 /// JSON RPC entry point
@@ -81,6 +132,25 @@ async fn rpc_endpoint(
         .map_err(|_| reject::custom(DatabaseError))?;
     if let Value::Array(requests) = data {
         // batch API call
+        let batch_size_limit = service.batch_size_limit();
+        if requests.len() > usize::from(batch_size_limit) {
+            counters::INVALID_REQUESTS
+                .with_label_values(&["batch_size_limit"])
+                .inc();
+            let mut response = Map::new();
+            response.insert("jsonrpc".to_string(), Value::String("2.0".to_string()));
+            response.insert("id".to_string(), Value::Null);
+            response.insert(
+                "error".to_string(),
+                JsonRpcError::invalid_request_with_msg(format!(
+                    "batch size {} exceeds the limit of {}",
+                    requests.len(),
+                    batch_size_limit
+                ))
+                .serialize(),
+            );
+            return Ok(Box::new(warp::reply::json(&Value::Object(response))));
+        }
         let futures = requests.into_iter().map(|req| {
             rpc_request_handler(
                 req,
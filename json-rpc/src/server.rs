@@ -0,0 +1,232 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serves the JSON RPC filter on a TCP listener, over TLS if configured, with a limit on the
+//! number of open connections. This is the hardening otherwise left to a reverse proxy.
+
+use crate::counters;
+use anyhow::{format_err, Context, Result};
+use futures::{future, stream::StreamExt, Future};
+use libra_config::config::{RpcCorsConfig, RpcTlsConfig};
+use libra_logger::prelude::*;
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    net::SocketAddr,
+    path::Path,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context as TaskContext, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    time::{delay_for, timeout},
+};
+use tokio_rustls::{
+    rustls::{
+        internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
+        NoClientAuth, PrivateKey, ServerConfig,
+    },
+    TlsAcceptor,
+};
+use warp::{filters::BoxedFilter, Reply};
+
+pub type RpcFilter = BoxedFilter<(Box<dyn Reply>,)>;
+
+// How long to wait before accepting connections again after a failure, e.g., when the process
+// is out of file descriptors
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+// How long a client may take to complete the handshake, e.g., of TLS, before its connection is
+// closed. Connections count against the limit during their handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Binds the server socket. Must be called within a tokio runtime context.
+pub fn bind(address: SocketAddr) -> io::Result<TcpListener> {
+    let listener = std::net::TcpListener::bind(address)?;
+    listener.set_nonblocking(true)?;
+    TcpListener::from_std(listener)
+}
+
+/// Builds the CORS policy of the server
+pub fn cors(config: &RpcCorsConfig) -> warp::cors::Builder {
+    let cors = warp::cors()
+        .allow_methods(vec!["POST"])
+        .allow_header("content-type")
+        .max_age(config.max_age_secs);
+    if config.allowed_origins.iter().any(|origin| origin == "*") {
+        cors.allow_any_origin()
+    } else {
+        cors.allow_origins(config.allowed_origins.iter().map(String::as_str))
+    }
+}
+
+/// Loads the certificate chain and private key of the server
+pub fn tls_acceptor(config: &RpcTlsConfig) -> Result<TlsAcceptor> {
+    let certs = certs(&mut open(&config.cert_path)?)
+        .map_err(|_| format_err!("Invalid certificates in {:?}", config.cert_path))?;
+    let key = private_key(&config.key_path)?;
+    let mut server_config = ServerConfig::new(NoClientAuth::new());
+    server_config.set_single_cert(certs, key)?;
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+fn private_key(path: &Path) -> Result<PrivateKey> {
+    let invalid_key = || format_err!("Invalid private key in {:?}", path);
+    let mut keys = pkcs8_private_keys(&mut open(path)?).map_err(|_| invalid_key())?;
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut open(path)?).map_err(|_| invalid_key())?;
+    }
+    keys.into_iter()
+        .next()
+        .ok_or_else(|| format_err!("No private key in {:?}", path))
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    Ok(BufReader::new(file))
+}
+
+/// Serves `filter` on the connections accepted by `listener`, until the future is dropped
+pub async fn serve(
+    filter: RpcFilter,
+    listener: TcpListener,
+    max_connections: Option<usize>,
+    tls_acceptor: Option<TlsAcceptor>,
+) {
+    match tls_acceptor {
+        Some(acceptor) => {
+            serve_connections(filter, listener, max_connections, move |stream| {
+                acceptor.accept(stream)
+            })
+            .await
+        }
+        None => {
+            serve_connections(
+                filter,
+                listener,
+                max_connections,
+                future::ok::<_, io::Error>,
+            )
+            .await
+        }
+    }
+}
+
+async fn serve_connections<S, H, F>(
+    filter: RpcFilter,
+    mut listener: TcpListener,
+    max_connections: Option<usize>,
+    handshake: H,
+) where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    H: Fn(TcpStream) -> F + Clone + Send + 'static,
+    F: Future<Output = io::Result<S>> + Send + 'static,
+{
+    // Connections are handed to the server once their handshake completes, so that a slow client
+    // does not hold up the others
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let open_connections = Arc::new(AtomicUsize::new(0));
+    let accept = async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!("[rpc] failed to accept connection: {}", e);
+                    delay_for(ACCEPT_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+            let guard = match ConnectionGuard::try_new(&open_connections, max_connections) {
+                Some(guard) => guard,
+                None => {
+                    counters::REJECTED_CONNECTIONS.inc();
+                    continue;
+                }
+            };
+            let handshake = handshake.clone();
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                match timeout(HANDSHAKE_TIMEOUT, handshake(stream)).await {
+                    Ok(Ok(stream)) => {
+                        // The server only stops when this future is dropped, with the receiver
+                        let _ = sender.send(Connection {
+                            stream,
+                            _guard: guard,
+                        });
+                    }
+                    Ok(Err(e)) => debug!("[rpc] connection handshake failed: {}", e),
+                    // The stream is dropped along with the handshake
+                    Err(_) => debug!("[rpc] connection handshake timed out"),
+                }
+            });
+        }
+    };
+    let server = warp::serve(filter).run_incoming(receiver.map(Ok::<_, io::Error>));
+    future::join(accept, server).await;
+}
+
+/// Counts a connection as open for as long as it is alive
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    // Returns None if there are already `max_connections` open connections
+    fn try_new(
+        open_connections: &Arc<AtomicUsize>,
+        max_connections: Option<usize>,
+    ) -> Option<Self> {
+        let open = open_connections.fetch_add(1, Ordering::Relaxed);
+        counters::OPEN_CONNECTIONS.inc();
+        let guard = Self(Arc::clone(open_connections));
+        match max_connections {
+            Some(max_connections) if open >= max_connections => None,
+            _ => Some(guard),
+        }
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+        counters::OPEN_CONNECTIONS.dec();
+    }
+}
+
+struct Connection<S> {
+    stream: S,
+    _guard: ConnectionGuard,
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for Connection<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for Connection<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
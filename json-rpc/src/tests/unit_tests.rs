@@ -4,29 +4,34 @@
 use crate::{
     errors::{JsonRpcError, ServerCode},
     tests::utils::{test_bootstrap, MockLibraDB},
+    RpcLimits,
 };
 use futures::{channel::mpsc::channel, StreamExt};
-use libra_config::utils;
+use libra_config::{
+    config::{RoleType, RpcConfig, RpcCorsConfig},
+    utils,
+};
 use libra_crypto::{ed25519::Ed25519PrivateKey, hash::CryptoHash, HashValue, PrivateKey, Uniform};
 use libra_json_rpc_client::{
     views::{
-        AccountStateWithProofView, BlockMetadata, BytesView, EventView, StateProofView,
-        TransactionDataView, TransactionView,
+        AccountStateWithProofView, BlockMetadata, BytesView, EventView, EventWithProofView,
+        SignedWaypointView, SourceMapView, StateProofView, TransactionDataView,
+        TransactionListWithProofView, TransactionView,
     },
     JsonRpcAsyncClient, JsonRpcBatch, JsonRpcResponse, ResponseAsView,
 };
 use libra_proptest_helpers::ValueGenerator;
 use libra_types::{
     account_address::AccountAddress,
-    account_config::AccountResource,
+    account_config::{AccountResource, LBR_NAME},
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     proof::{SparseMerkleProof, TransactionAccumulatorProof, TransactionInfoWithProof},
-    test_helpers::transaction_test_helpers::get_test_signed_txn,
-    transaction::{Transaction, TransactionInfo, TransactionPayload},
+    test_helpers::transaction_test_helpers::{get_test_signed_transaction, get_test_signed_txn},
+    transaction::{Transaction, TransactionInfo, TransactionListWithProof, TransactionPayload},
     vm_error::{StatusCode, VMStatus},
 };
 use libradb::test_helper::arb_blocks_to_commit;
@@ -167,6 +172,96 @@ fn test_json_rpc_protocol() {
     assert_eq!(fetch_error(resp), -32000);
 }
 
+#[test]
+fn test_json_rpc_limits() {
+    let address = format!("0.0.0.0:{}", utils::get_available_port());
+    let mock_db = mock_db();
+    let mp_sender = channel(1024).0;
+    let mut config = RpcConfig::default();
+    config.address = address.parse().unwrap();
+    config.batch_size_limit = 2;
+    config.page_size_limit = 1;
+    let limits = Arc::new(RpcLimits::new(&config));
+    let _runtime = crate::bootstrap(
+        &config,
+        Arc::new(mock_db),
+        mp_sender,
+        RoleType::Validator,
+        Arc::clone(&limits),
+    );
+    let client = reqwest::blocking::Client::new();
+    let url = format!("http://{}", address);
+
+    let get_transactions = serde_json::json!({"jsonrpc": "2.0", "method": "get_transactions", "params": [0, 2, false], "id": 1});
+    let batch = serde_json::Value::Array(vec![get_transactions.clone(); 3]);
+
+    let resp = client.post(&url).json(&batch).send().unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(fetch_error(resp), -32600);
+    let resp = client.post(&url).json(&get_transactions).send().unwrap();
+    assert_eq!(fetch_error(resp), -32000);
+
+    // Updated limits apply to subsequent requests
+    config.batch_size_limit = 3;
+    config.page_size_limit = 2;
+    limits.update(&config);
+    let resp = client.post(&url).json(&batch).send().unwrap();
+    let responses: Vec<JsonMap> = resp.json().unwrap();
+    assert_eq!(responses.len(), 3);
+    assert!(responses
+        .iter()
+        .all(|response| response.get("result").is_some()));
+}
+
+#[test]
+fn test_json_rpc_http_limits_and_cors() {
+    let address = format!("0.0.0.0:{}", utils::get_available_port());
+    let mut config = RpcConfig::default();
+    config.address = address.parse().unwrap();
+    config.content_length_limit = 200;
+    config.cors = Some(RpcCorsConfig {
+        allowed_origins: vec!["https://example.com".to_string()],
+        ..RpcCorsConfig::default()
+    });
+    let _runtime = crate::bootstrap(
+        &config,
+        Arc::new(mock_db()),
+        channel(1024).0,
+        RoleType::Validator,
+        Arc::new(RpcLimits::new(&config)),
+    );
+    let client = reqwest::blocking::Client::new();
+    let url = format!("http://{}", address);
+
+    let request =
+        serde_json::json!({"jsonrpc": "2.0", "method": "get_metadata", "params": [], "id": 1});
+    let resp = client
+        .post(&url)
+        .header("origin", "https://example.com")
+        .json(&request)
+        .send()
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(
+        resp.headers()
+            .get("access-control-allow-origin")
+            .and_then(|origin| origin.to_str().ok()),
+        Some("https://example.com")
+    );
+
+    let resp = client
+        .post(&url)
+        .header("origin", "https://example.org")
+        .json(&request)
+        .send()
+        .unwrap();
+    assert_eq!(resp.status(), 403);
+
+    let large_request = serde_json::json!({"jsonrpc": "2.0", "method": "get_metadata", "params": ["0".repeat(200)], "id": 1});
+    let resp = client.post(&url).json(&large_request).send().unwrap();
+    assert_eq!(resp.status(), 413);
+}
+
 #[test]
 fn test_transaction_submission() {
     let (mp_sender, mut mp_events) = channel(1);
@@ -227,6 +322,61 @@ fn test_transaction_submission() {
     }
 }
 
+#[test]
+fn test_submit_and_wait() {
+    let (mp_sender, mut mp_events) = channel(1);
+    let mock_db = mock_db();
+    let committed_txn = mock_db
+        .all_txns
+        .iter()
+        .find_map(|(txn, _)| txn.as_signed_user_txn().ok().cloned())
+        .expect("mock db must have a user transaction");
+    let port = utils::get_available_port();
+    let address = format!("0.0.0.0:{}", port);
+    let mut runtime = test_bootstrap(address.parse().unwrap(), Arc::new(mock_db), mp_sender);
+    let client = JsonRpcAsyncClient::new(
+        reqwest::Url::from_str(format!("http://{}:{}", "127.0.0.1", port).as_str())
+            .expect("invalid url"),
+    );
+
+    // future that mocks shared mempool accepting every transaction
+    runtime.spawn(async move {
+        while let Some((_, cb)) = mp_events.next().await {
+            cb.send(Ok((MempoolStatus::new(MempoolStatusCode::Accepted), None)))
+                .unwrap();
+        }
+    });
+
+    // the committed transaction is returned
+    let view = runtime
+        .block_on(client.submit_transaction_and_wait(committed_txn.clone()))
+        .unwrap();
+    assert_eq!(
+        view.hash,
+        Transaction::UserTransaction(committed_txn)
+            .hash()
+            .to_string()
+    );
+
+    // a transaction that can't be committed anymore fails
+    let privkey = Ed25519PrivateKey::generate_for_testing();
+    let expired_txn = get_test_signed_transaction(
+        AccountAddress::new([9; AccountAddress::LENGTH]),
+        0,
+        &privkey,
+        privkey.public_key(),
+        None,
+        0,
+        0,
+        LBR_NAME.to_owned(),
+        None,
+    );
+    let error = runtime
+        .block_on(client.submit_transaction_and_wait(expired_txn))
+        .unwrap_err();
+    assert!(error.to_string().contains("expired"));
+}
+
 // TODO: Once account configs are published in the mock DB this test can be turned back on
 //#[test]
 //fn test_get_account_state() {
@@ -356,6 +506,71 @@ fn test_get_events() {
     );
 }
 
+#[test]
+fn test_get_events_with_proofs() {
+    let mut mock_db = mock_db();
+    let account = get_first_account_from_mock_db(&mock_db);
+    let version = mock_db.get_latest_version().unwrap();
+    let event_key = EventKey::new_from_address(&account, 7);
+    for seq_num in 0..5 {
+        mock_db.events.push((
+            version,
+            ContractEvent::new(event_key, seq_num, TypeTag::U64, vec![]),
+        ));
+    }
+    let (_, client, mut runtime) = create_client_and_runtime(mock_db, 1);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_events_with_proofs_request(
+        hex::encode(event_key.as_bytes()),
+        u64::max_value(),
+        false, /* ascending */
+        3,
+        Some(version),
+    );
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+
+    let events = EventWithProofView::vec_from_response(result)
+        .unwrap()
+        .into_iter()
+        .map(|view| {
+            lcs::from_bytes::<EventWithProof>(&view.event_with_proof.into_bytes().unwrap()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events
+            .iter()
+            .map(|e| e.event.sequence_number())
+            .collect::<Vec<_>>(),
+        vec![4, 3, 2]
+    );
+    assert!(events.iter().all(|e| e.transaction_version == version));
+}
+
+#[test]
+fn test_get_events_by_type() {
+    let mut mock_db = mock_db();
+    let account = get_first_account_from_mock_db(&mock_db);
+    let version = mock_db.get_latest_version().unwrap();
+    let event = ContractEvent::new(
+        EventKey::new_for_type(&account, &TypeTag::U64),
+        0,
+        TypeTag::U64,
+        lcs::to_bytes(&7u64).unwrap(),
+    );
+    mock_db.events.push((version, event));
+    let (_, client, mut runtime) = create_client_and_runtime(mock_db, 1);
+
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_events_by_type_request(account, "u64".to_string(), 0, 10);
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+
+    let events = EventView::vec_from_response(result).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].sequence_number, 0);
+    assert_eq!(events[0].transaction_version, version);
+}
+
 #[test]
 fn test_get_transactions() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -432,6 +647,27 @@ fn test_get_transactions() {
     }
 }
 
+#[test]
+fn test_get_transactions_with_proofs() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    let version = mock_db.get_latest_version().unwrap();
+    let limit = mock_db.all_txns.len().min(100);
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_transactions_with_proofs_request(0, limit as u64, Some(version));
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    let view = TransactionListWithProofView::from_response(result).unwrap();
+    let txns: TransactionListWithProof =
+        lcs::from_bytes(&view.transaction_list_with_proof.into_bytes().unwrap()).unwrap();
+
+    assert_eq!(txns.first_transaction_version, Some(0));
+    assert_eq!(txns.transactions.len(), limit);
+    for (txn, (expected_txn, _)) in txns.transactions.iter().zip(mock_db.all_txns.iter()) {
+        assert_eq!(txn, expected_txn);
+    }
+    assert!(txns.events.is_some());
+}
+
 #[test]
 fn test_get_account_transaction() {
     let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -589,6 +825,38 @@ fn test_get_state_proof() {
     assert_eq!(li.ledger_info().version(), version);
 }
 
+#[test]
+fn test_get_source_map() {
+    let (mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    // No source map is published for a module that does not exist
+    let account = get_first_account_from_mock_db(&mock_db);
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_source_map_request(account, "M");
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    assert_eq!(SourceMapView::optional_from_response(result).unwrap(), None);
+
+    // Module names must be valid identifiers
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_source_map_request(account, "not a module");
+    let responses = runtime.block_on(client.execute(batch)).unwrap();
+    assert!(responses[0].is_err());
+}
+
+#[test]
+fn test_get_latest_waypoint() {
+    let (_mock_db, client, mut runtime) = create_database_client_and_runtime(1);
+
+    // The mock DB doesn't emit waypoints
+    let mut batch = JsonRpcBatch::default();
+    batch.add_get_latest_waypoint_request();
+    let result = execute_batch_and_get_first_response(&client, &mut runtime, batch);
+    assert_eq!(
+        SignedWaypointView::optional_from_response(result).unwrap(),
+        None
+    );
+}
+
 #[test]
 fn test_get_network_status() {
     let (_mock_db, client, mut runtime) = create_database_client_and_runtime(1);
@@ -611,8 +879,13 @@ fn test_get_network_status() {
 fn create_database_client_and_runtime(
     channel_buffer: usize,
 ) -> (MockLibraDB, JsonRpcAsyncClient, Runtime) {
-    let mock_db = mock_db();
+    create_client_and_runtime(mock_db(), channel_buffer)
+}
 
+fn create_client_and_runtime(
+    mock_db: MockLibraDB,
+    channel_buffer: usize,
+) -> (MockLibraDB, JsonRpcAsyncClient, Runtime) {
     let host = "0.0.0.0";
     let port = utils::get_available_port();
     let address = format!("{}:{}", host, port);
@@ -1,21 +1,23 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::RpcLimits;
 use anyhow::{Error, Result};
-use libra_config::config::RoleType;
+use libra_config::config::{RoleType, RpcConfig};
 use libra_crypto::HashValue;
 use libra_mempool::MempoolClientSender;
 use libra_types::{
     account_address::AccountAddress,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
     block_info::BlockInfo,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     event::EventKey,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::{
-        AccumulatorConsistencyProof, AccumulatorRangeProof, SparseMerkleProof,
-        TransactionAccumulatorProof, TransactionInfoWithProof, TransactionListProof,
+        AccumulatorConsistencyProof, AccumulatorRangeProof, EventAccumulatorProof, EventProof,
+        SparseMerkleProof, TransactionAccumulatorProof, TransactionInfoWithProof,
+        TransactionListProof,
     },
     transaction::{
         Transaction, TransactionInfo, TransactionListWithProof, TransactionWithProof, Version,
@@ -23,7 +25,7 @@ use libra_types::{
     vm_error::StatusCode,
 };
 use std::{collections::BTreeMap, net::SocketAddr, sync::Arc};
-use storage_interface::{DbReader, StartupInfo, TreeState};
+use storage_interface::{DbReader, Order, StartupInfo, TreeState};
 use tokio::runtime::Runtime;
 
 /// Creates JSON RPC server for a Validator node
@@ -33,7 +35,16 @@ pub fn test_bootstrap(
     libra_db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
 ) -> Runtime {
-    crate::bootstrap(address, libra_db, mp_sender, RoleType::Validator)
+    crate::bootstrap(
+        &RpcConfig {
+            address,
+            ..RpcConfig::default()
+        },
+        libra_db,
+        mp_sender,
+        RoleType::Validator,
+        Arc::new(RpcLimits::default()),
+    )
 }
 
 /// Lightweight mock of LibraDB
@@ -192,6 +203,53 @@ impl DbReader for MockLibraDB {
         Ok(events)
     }
 
+    fn get_events_with_proof_by_event_key(
+        &self,
+        key: &EventKey,
+        start_seq_num: u64,
+        order: Order,
+        limit: u64,
+        known_version: Option<Version>,
+    ) -> Result<Vec<EventWithProof>> {
+        let ledger_version = known_version.unwrap_or(self.version);
+        let mut events = self
+            .events
+            .iter()
+            .filter(|(version, e)| {
+                e.key() == key
+                    && *version <= ledger_version
+                    && match order {
+                        Order::Ascending => start_seq_num <= e.sequence_number(),
+                        Order::Descending => e.sequence_number() <= start_seq_num,
+                    }
+            })
+            .collect::<Vec<_>>();
+        events.sort_by_key(|(_, e)| e.sequence_number());
+        if order == Order::Descending {
+            events.reverse();
+        }
+        Ok(events
+            .into_iter()
+            .take(limit as usize)
+            .map(|(version, e)| {
+                let proof = EventProof::new(
+                    TransactionInfoWithProof::new(
+                        TransactionAccumulatorProof::new(vec![]),
+                        TransactionInfo::new(
+                            Default::default(),
+                            Default::default(),
+                            Default::default(),
+                            0,
+                            StatusCode::EXECUTED,
+                        ),
+                    ),
+                    EventAccumulatorProof::new(vec![]),
+                );
+                EventWithProof::new(*version, 0, e.clone(), proof)
+            })
+            .collect())
+    }
+
     fn get_state_proof(
         &self,
         known_version: u64,
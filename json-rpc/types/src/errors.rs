@@ -59,6 +59,14 @@ impl JsonRpcError {
         }
     }
 
+    pub fn invalid_request_with_msg(message: String) -> Self {
+        Self {
+            code: -32600,
+            message: format!("Invalid Request: {}", message),
+            data: None,
+        }
+    }
+
     pub fn invalid_params() -> Self {
         Self {
             code: -32602,
@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Error, Result};
+use anyhow::{ensure, format_err, Error, Result};
 use libra_crypto::HashValue;
 use libra_types::{
     account_config::{
@@ -10,12 +10,13 @@ use libra_types::{
         ReceivedPaymentEvent, SentPaymentEvent, ToLBRExchangeRateUpdateEvent, UpgradeEvent,
     },
     account_state_blob::AccountStateWithProof,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    proof::{AccountStateProof, AccumulatorConsistencyProof},
-    transaction::{Transaction, TransactionArgument, TransactionPayload},
+    proof::{AccountStateProof, AccumulatorConsistencyProof, TransactionInfoWithProof},
+    transaction::{Transaction, TransactionArgument, TransactionListWithProof, TransactionPayload},
     vm_error::StatusCode,
+    waypoint::{SignedWaypoint, Waypoint},
 };
 use move_core_types::{
     identifier::Identifier,
@@ -23,7 +24,7 @@ use move_core_types::{
     move_resource::MoveResource,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, convert::TryFrom};
+use std::{collections::BTreeMap, convert::TryFrom, str::FromStr};
 use transaction_builder::get_transaction_name;
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -334,6 +335,7 @@ pub enum TransactionDataView {
     #[serde(rename = "user")]
     UserTransaction {
         sender: String,
+        fee_payer: String,
         signature_scheme: String,
         signature: String,
         public_key: String,
@@ -394,6 +396,7 @@ impl From<Transaction> for TransactionDataView {
 
                 Ok(TransactionDataView::UserTransaction {
                     sender: t.sender().to_string(),
+                    fee_payer: t.fee_payer().to_string(),
                     signature_scheme: t.authenticator().scheme().to_string(),
                     signature: hex::encode(t.authenticator().signature_bytes()),
                     public_key: hex::encode(t.authenticator().public_key_bytes()),
@@ -454,6 +457,7 @@ impl From<TransactionPayload> for ScriptView {
             TransactionPayload::Module(_) => {
                 ("module publishing".to_string(), empty_vec, empty_ty_vec)
             }
+            TransactionPayload::Batch(_) => ("batch".to_string(), empty_vec, empty_ty_vec),
         };
 
         let res = match code.as_str() {
@@ -519,6 +523,24 @@ pub struct StateProofView {
     pub ledger_consistency_proof: BytesView,
 }
 
+impl StateProofView {
+    /// Decodes the latest ledger info and the proofs, which still need to be verified against a
+    /// trusted state.
+    pub fn decode(
+        &self,
+    ) -> Result<(
+        LedgerInfoWithSignatures,
+        EpochChangeProof,
+        AccumulatorConsistencyProof,
+    )> {
+        Ok((
+            lcs::from_bytes(&self.ledger_info_with_signatures.clone().into_bytes()?)?,
+            lcs::from_bytes(&self.epoch_change_proof.clone().into_bytes()?)?,
+            lcs::from_bytes(&self.ledger_consistency_proof.clone().into_bytes()?)?,
+        ))
+    }
+}
+
 impl
     TryFrom<(
         LedgerInfoWithSignatures,
@@ -545,6 +567,119 @@ impl
     }
 }
 
+/// A list of transactions with their events and the proof of their transaction infos, LCS encoded
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TransactionListWithProofView {
+    pub transaction_list_with_proof: BytesView,
+}
+
+impl TransactionListWithProofView {
+    /// Decodes the transactions, which still need to be verified against a trusted ledger info.
+    pub fn decode(&self) -> Result<TransactionListWithProof> {
+        Ok(lcs::from_bytes(
+            &self.transaction_list_with_proof.clone().into_bytes()?,
+        )?)
+    }
+}
+
+impl TryFrom<TransactionListWithProof> for TransactionListWithProofView {
+    type Error = Error;
+
+    fn try_from(
+        transaction_list_with_proof: TransactionListWithProof,
+    ) -> Result<TransactionListWithProofView, Error> {
+        Ok(TransactionListWithProofView {
+            transaction_list_with_proof: BytesView::from(&lcs::to_bytes(
+                &transaction_list_with_proof,
+            )?),
+        })
+    }
+}
+
+/// An event with its proof, LCS encoded
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EventWithProofView {
+    pub event_with_proof: BytesView,
+}
+
+impl EventWithProofView {
+    /// Decodes the event, which still needs to be verified against a trusted ledger info.
+    pub fn decode(&self) -> Result<EventWithProof> {
+        Ok(lcs::from_bytes(
+            &self.event_with_proof.clone().into_bytes()?,
+        )?)
+    }
+}
+
+impl TryFrom<EventWithProof> for EventWithProofView {
+    type Error = Error;
+
+    fn try_from(event_with_proof: EventWithProof) -> Result<EventWithProofView, Error> {
+        Ok(EventWithProofView {
+            event_with_proof: BytesView::from(&lcs::to_bytes(&event_with_proof)?),
+        })
+    }
+}
+
+/// The compressed source map published alongside a module
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SourceMapView {
+    pub source_map: BytesView,
+}
+
+/// The waypoint emitted last by storage, with the epoch-ending ledger info it was generated from,
+/// LCS encoded
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SignedWaypointView {
+    pub epoch: u64,
+    pub waypoint: String,
+    pub ledger_info_with_signatures: BytesView,
+}
+
+impl SignedWaypointView {
+    /// Decodes the signed waypoint, checking that its waypoint is the one of its ledger info.
+    /// It still needs to be verified against a trusted waypoint.
+    pub fn signed_waypoint(&self) -> Result<SignedWaypoint> {
+        let ledger_info_with_sigs: LedgerInfoWithSignatures =
+            lcs::from_bytes(&self.ledger_info_with_signatures.clone().into_bytes()?)?;
+        let signed_waypoint = SignedWaypoint::new(ledger_info_with_sigs)?;
+        let waypoint = Waypoint::from_str(&self.waypoint)?;
+        ensure!(
+            signed_waypoint.waypoint() == waypoint && signed_waypoint.epoch() == self.epoch,
+            "The waypoint {} doesn't match its ledger info",
+            waypoint,
+        );
+        Ok(signed_waypoint)
+    }
+}
+
+impl TryFrom<SignedWaypoint> for SignedWaypointView {
+    type Error = Error;
+
+    fn try_from(signed_waypoint: SignedWaypoint) -> Result<SignedWaypointView, Error> {
+        Ok(SignedWaypointView {
+            epoch: signed_waypoint.epoch(),
+            waypoint: signed_waypoint.waypoint().to_string(),
+            ledger_info_with_signatures: BytesView::from(&lcs::to_bytes(
+                signed_waypoint.ledger_info_with_sigs(),
+            )?),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ExpirationWindowView {
+    pub timestamp: u64,
+    pub duration_microseconds: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GasPriceEstimateView {
+    pub min_gas_unit_price: u64,
+    pub max_gas_unit_price: u64,
+    pub recent_block_fullness: u64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AccountStateWithProofView {
     pub version: u64,
@@ -552,6 +687,38 @@ pub struct AccountStateWithProofView {
     pub proof: AccountStateProofView,
 }
 
+impl AccountStateWithProofView {
+    /// Decodes the account state, which still needs to be verified against a trusted ledger
+    /// info.
+    pub fn decode(&self) -> Result<AccountStateWithProof> {
+        let blob = match &self.blob {
+            Some(bytes) => Some(lcs::from_bytes(&bytes.clone().into_bytes()?)?),
+            None => None,
+        };
+        let transaction_info_with_proof = TransactionInfoWithProof::new(
+            lcs::from_bytes(
+                &self
+                    .proof
+                    .ledger_info_to_transaction_info_proof
+                    .clone()
+                    .into_bytes()?,
+            )?,
+            lcs::from_bytes(&self.proof.transaction_info.clone().into_bytes()?)?,
+        );
+        let proof = AccountStateProof::new(
+            transaction_info_with_proof,
+            lcs::from_bytes(
+                &self
+                    .proof
+                    .transaction_info_to_account_proof
+                    .clone()
+                    .into_bytes()?,
+            )?,
+        );
+        Ok(AccountStateWithProof::new(self.version, blob, proof))
+    }
+}
+
 impl TryFrom<AccountStateWithProof> for AccountStateWithProofView {
     type Error = Error;
 
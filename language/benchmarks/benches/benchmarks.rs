@@ -31,6 +31,10 @@ fn call(c: &mut Criterion) {
     bench(c, "call");
 }
 
-criterion_group!(vm_benches, arith, call);
+fn fields(c: &mut Criterion) {
+    bench(c, "fields");
+}
+
+criterion_group!(vm_benches, arith, call, fields);
 
 criterion_main!(vm_benches);
@@ -70,4 +70,26 @@ module Bench {
     fun call_2_2(): u64 {
         100 + 300
     }
+
+    //
+    // `fields` benchmark
+    //
+    struct Pair { first: u64, second: u64 }
+
+    struct Wrapper<T> { value: T }
+
+    public fun fields() {
+        let i = 0;
+        // 3000 is the number of loops to make the benchmark run for a couple of minutes, which is an eternity.
+        // Adjust according to your needs, it's just a reference
+        while (i < 3000) {
+            let pair = Pair { first: i, second: 0 };
+            pair.second = pair.first + 1;
+            let wrapper = Wrapper { value: pair };
+            check(wrapper.value.second == i + 1, 300);
+            let Wrapper { value: Pair { first, second } } = wrapper;
+            check(first + 1 == second, 301);
+            i = i + 1;
+        };
+    }
 }
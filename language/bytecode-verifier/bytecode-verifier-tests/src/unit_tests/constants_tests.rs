@@ -32,6 +32,10 @@ fn valid_primitives() {
             type_: SignatureToken::U128,
             data: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
         },
+        Constant {
+            type_: SignatureToken::U256,
+            data: vec![0; 32],
+        },
         Constant {
             type_: SignatureToken::Address,
             data: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
@@ -46,6 +50,7 @@ fn invalid_primitives() {
     malformed(SignatureToken::U8, vec![0, 0]);
     malformed(SignatureToken::U64, vec![0]);
     malformed(SignatureToken::U128, vec![0]);
+    malformed(SignatureToken::U256, vec![0; 16]);
     let data = vec![
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0,
@@ -111,6 +116,10 @@ fn valid_vectors() {
             type_: tvec(SignatureToken::U128),
             data: large_vec(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
         },
+        Constant {
+            type_: tvec(SignatureToken::U256),
+            data: large_vec(vec![0; 32]),
+        },
         Constant {
             type_: tvec(SignatureToken::Address),
             data: large_vec(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
@@ -3,7 +3,7 @@
 
 use bytecode_verifier::{SignatureChecker, VerifiedModule};
 use invalid_mutations::signature::{FieldRefMutation, SignatureRefMutation};
-use libra_types::account_address::AccountAddress;
+use libra_types::{account_address::AccountAddress, vm_error::StatusCode};
 use move_core_types::identifier::Identifier;
 use proptest::{collection::vec, prelude::*, sample::Index as PropIndex};
 use vm::file_format::{Bytecode::*, CompiledModule, SignatureToken::*, *};
@@ -18,6 +18,19 @@ fn test_reference_of_reference() {
     assert!(errors.is_err());
 }
 
+#[test]
+fn test_vector_op_with_two_type_tokens() {
+    let mut m = basic_test_module();
+    let sig_idx = SignatureIndex(m.signatures.len() as TableIndex);
+    m.signatures.push(Signature(vec![U64, Bool]));
+    m.function_defs[0].code.as_mut().unwrap().code = vec![VecPack(sig_idx, 0), Pop, Ret];
+    let err = SignatureChecker::verify(&m.freeze().unwrap()).unwrap_err();
+    assert_eq!(
+        err.major_status,
+        StatusCode::NUMBER_OF_TYPE_ARGUMENTS_MISMATCH
+    );
+}
+
 proptest! {
     #[test]
     fn valid_signatures(module in CompiledModule::valid_strategy(20)) {
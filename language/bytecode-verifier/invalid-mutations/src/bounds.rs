@@ -353,6 +353,6 @@ fn struct_handle(token: &SignatureToken) -> Option<StructHandleIndex> {
         Struct(sh_idx) => Some(*sh_idx),
         StructInstantiation(sh_idx, _) => Some(*sh_idx),
         Reference(token) | MutableReference(token) => struct_handle(token),
-        Bool | U8 | U64 | U128 | Address | Signer | Vector(_) | TypeParameter(_) => None,
+        Bool | U8 | U64 | U128 | U256 | Address | Signer | Vector(_) | TypeParameter(_) => None,
     }
 }
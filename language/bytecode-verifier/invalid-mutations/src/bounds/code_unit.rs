@@ -10,7 +10,7 @@ use vm::{
     file_format::{
         Bytecode, CodeOffset, CompiledModuleMut, ConstantPoolIndex, FieldHandleIndex,
         FieldInstantiationIndex, FunctionHandleIndex, FunctionInstantiationIndex, LocalIndex,
-        StructDefInstantiationIndex, StructDefinitionIndex, TableIndex,
+        SignatureIndex, StructDefInstantiationIndex, StructDefinitionIndex, TableIndex,
     },
     internals::ModuleIndex,
     IndexKind,
@@ -83,6 +83,23 @@ macro_rules! struct_bytecode {
     }};
 }
 
+macro_rules! vec_num_bytecode {
+    ($dst_len: expr, $bytecode_idx: expr, $offset: expr, $num: expr, $bytecode_ident: tt) => {{
+        let dst_len = $dst_len;
+        let new_idx = (dst_len + $offset) as TableIndex;
+        (
+            $bytecode_ident(SignatureIndex::new(new_idx), $num),
+            bytecode_offset_err(
+                SignatureIndex::KIND,
+                new_idx as usize,
+                dst_len,
+                $bytecode_idx,
+                StatusCode::INDEX_OUT_OF_BOUNDS,
+            ),
+        )
+    }};
+}
+
 macro_rules! code_bytecode {
     ($code_len: expr, $bytecode_idx: expr, $offset: expr, $bytecode_ident: tt) => {{
         let code_len = $code_len;
@@ -172,6 +189,7 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
         let struct_inst_len = self.module.struct_def_instantiations.len();
         let function_inst_len = self.module.function_instantiations.len();
         let field_inst_len = self.module.field_instantiations.len();
+        let signatures_len = self.module.signatures.len();
 
         mutations
             .iter()
@@ -343,6 +361,50 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
                         StructDefInstantiationIndex,
                         MoveToGeneric
                     ),
+                    VecLen(_) => {
+                        new_bytecode!(signatures_len, bytecode_idx, offset, SignatureIndex, VecLen)
+                    }
+                    VecImmBorrow(_) => new_bytecode!(
+                        signatures_len,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        VecImmBorrow
+                    ),
+                    VecMutBorrow(_) => new_bytecode!(
+                        signatures_len,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        VecMutBorrow
+                    ),
+                    VecPushBack(_) => new_bytecode!(
+                        signatures_len,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        VecPushBack
+                    ),
+                    VecPopBack(_) => new_bytecode!(
+                        signatures_len,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        VecPopBack
+                    ),
+                    VecSwap(_) => new_bytecode!(
+                        signatures_len,
+                        bytecode_idx,
+                        offset,
+                        SignatureIndex,
+                        VecSwap
+                    ),
+                    VecPack(_, num) => {
+                        vec_num_bytecode!(signatures_len, bytecode_idx, offset, num, VecPack)
+                    }
+                    VecUnpack(_, num) => {
+                        vec_num_bytecode!(signatures_len, bytecode_idx, offset, num, VecUnpack)
+                    }
                     BrTrue(_) => code_bytecode!(code_len, bytecode_idx, offset, BrTrue),
                     BrFalse(_) => code_bytecode!(code_len, bytecode_idx, offset, BrFalse),
                     Branch(_) => code_bytecode!(code_len, bytecode_idx, offset, Branch),
@@ -358,10 +420,10 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
 
                     // List out the other options explicitly so there's a compile error if a new
                     // bytecode gets added.
-                    FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64
-                    | CastU128 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
-                    | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt
-                    | Gt | Le | Ge | Abort | GetTxnSenderAddress | Nop => {
+                    FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | LdU256(_) | CastU8
+                    | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | ReadRef | WriteRef
+                    | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And
+                    | Not | Eq | Neq | Lt | Gt | Le | Ge | Abort | GetTxnSenderAddress | Nop => {
                         panic!("Bytecode has no internal index: {:?}", code[bytecode_idx])
                     }
                 };
@@ -401,6 +463,14 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
         | MoveToSenderGeneric(_)
         | MoveTo(_)
         | MoveToGeneric(_)
+        | VecPack(..)
+        | VecLen(_)
+        | VecImmBorrow(_)
+        | VecMutBorrow(_)
+        | VecPushBack(_)
+        | VecPopBack(_)
+        | VecUnpack(..)
+        | VecSwap(_)
         | BrTrue(_)
         | BrFalse(_)
         | Branch(_)
@@ -412,9 +482,9 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
 
         // List out the other options explicitly so there's a compile error if a new
         // bytecode gets added.
-        FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64 | CastU128
-        | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd
-        | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | Abort
-        | GetTxnSenderAddress | Nop => false,
+        FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | LdU256(_) | CastU8 | CastU64
+        | CastU128 | CastU256 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
+        | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le
+        | Ge | Abort | GetTxnSenderAddress | Nop => false,
     }
 }
@@ -33,7 +33,7 @@ impl<'a> ConstantsChecker<'a> {
     fn verify_constant_type(&self, idx: usize, type_: &SignatureToken) -> VMResult<()> {
         use SignatureToken as S;
         match type_ {
-            S::Bool | S::U8 | S::U64 | S::U128 | S::Address => Ok(()),
+            S::Bool | S::U8 | S::U64 | S::U128 | S::U256 | S::Address => Ok(()),
             S::Vector(inner) => self.verify_constant_type(idx, inner),
             S::Signer
             | S::Struct(_)
@@ -138,7 +138,7 @@ impl<'a> InstantiationLoopChecker<'a> {
 
         fn rec(type_params: &mut HashSet<TypeParameterIndex>, ty: &SignatureToken) {
             match ty {
-                Bool | Address | U8 | U64 | U128 | Signer | Struct(_) => (),
+                Bool | Address | U8 | U64 | U128 | U256 | Signer | Struct(_) => (),
                 TypeParameter(idx) => {
                     type_params.insert(*idx);
                 }
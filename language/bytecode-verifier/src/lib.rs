@@ -53,7 +53,9 @@ pub(crate) fn kind(module: &CompiledModule, ty: &SignatureToken, constraints: &[
 
     match ty {
         // The primitive types & references have kind unrestricted.
-        Bool | U8 | U64 | U128 | Address | Reference(_) | MutableReference(_) => Kind::Copyable,
+        Bool | U8 | U64 | U128 | U256 | Address | Reference(_) | MutableReference(_) => {
+            Kind::Copyable
+        }
         Signer => Kind::Resource,
         TypeParameter(idx) => constraints[*idx as usize],
         Vector(ty) => kind(module, ty, constraints),
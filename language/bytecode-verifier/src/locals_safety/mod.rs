@@ -121,6 +121,7 @@ fn execute_inner(state: &mut AbstractState, bytecode: &Bytecode, offset: usize)
         | Bytecode::LdU8(_)
         | Bytecode::LdU64(_)
         | Bytecode::LdU128(_)
+        | Bytecode::LdU256(_)
         | Bytecode::LdConst(_)
         | Bytecode::LdTrue
         | Bytecode::LdFalse
@@ -135,6 +136,7 @@ fn execute_inner(state: &mut AbstractState, bytecode: &Bytecode, offset: usize)
         | Bytecode::CastU8
         | Bytecode::CastU64
         | Bytecode::CastU128
+        | Bytecode::CastU256
         | Bytecode::Add
         | Bytecode::Sub
         | Bytecode::Mul
@@ -166,6 +168,14 @@ fn execute_inner(state: &mut AbstractState, bytecode: &Bytecode, offset: usize)
         | Bytecode::MoveToSenderGeneric(_)
         | Bytecode::MoveTo(_)
         | Bytecode::MoveToGeneric(_)
+        | Bytecode::VecPack(..)
+        | Bytecode::VecLen(_)
+        | Bytecode::VecImmBorrow(_)
+        | Bytecode::VecMutBorrow(_)
+        | Bytecode::VecPushBack(_)
+        | Bytecode::VecPopBack(_)
+        | Bytecode::VecUnpack(..)
+        | Bytecode::VecSwap(_)
         | Bytecode::GetTxnSenderAddress => (),
     };
     Ok(())
@@ -525,6 +525,52 @@ impl AbstractState {
         Ok(return_values)
     }
 
+    /// Checks an operation on the vector behind `vector`, which updates it if `mut_` is set
+    pub fn vector_op(&mut self, offset: usize, vector: AbstractValue, mut_: bool) -> VMResult<()> {
+        let id = vector.ref_id().unwrap();
+        let accessible = if mut_ {
+            self.is_writable(id)
+        } else {
+            self.is_readable(id, None)
+        };
+        if !accessible {
+            return Err(err_at_offset(
+                StatusCode::VEC_UPDATE_EXISTS_MUTABLE_BORROW_ERROR,
+                offset,
+            ));
+        }
+
+        self.release(id);
+        Ok(())
+    }
+
+    /// Borrows an element of the vector behind `vector`: the element is borrowed from the vector
+    /// as a whole, as the verifier does not know which element is borrowed
+    pub fn vector_element_borrow(
+        &mut self,
+        offset: usize,
+        vector: AbstractValue,
+        mut_: bool,
+    ) -> VMResult<AbstractValue> {
+        let vec_id = vector.ref_id().unwrap();
+        let accessible = if mut_ {
+            self.is_writable(vec_id)
+        } else {
+            self.is_readable(vec_id, None)
+        };
+        if !accessible {
+            return Err(err_at_offset(
+                StatusCode::VEC_BORROW_ELEMENT_EXISTS_MUTABLE_BORROW_ERROR,
+                offset,
+            ));
+        }
+
+        let elem_id = self.new_ref(mut_);
+        self.add_borrow(vec_id, elem_id);
+        self.release(vec_id);
+        Ok(AbstractValue::Reference(elem_id))
+    }
+
     pub fn ret(&mut self, offset: usize, values: Vec<AbstractValue>) -> VMResult<()> {
         // release all local variables
         let mut released = BTreeSet::new();
@@ -248,6 +248,7 @@ fn execute_inner(
         | Bytecode::CastU8
         | Bytecode::CastU64
         | Bytecode::CastU128
+        | Bytecode::CastU256
         | Bytecode::Not
         | Bytecode::Exists(_)
         | Bytecode::ExistsGeneric(_) => (),
@@ -272,6 +273,7 @@ fn execute_inner(
         Bytecode::LdU8(_) => verifier.stack.push(state.value_for(&SignatureToken::U8)),
         Bytecode::LdU64(_) => verifier.stack.push(state.value_for(&SignatureToken::U64)),
         Bytecode::LdU128(_) => verifier.stack.push(state.value_for(&SignatureToken::U128)),
+        Bytecode::LdU256(_) => verifier.stack.push(state.value_for(&SignatureToken::U256)),
         Bytecode::GetTxnSenderAddress => verifier
             .stack
             .push(state.value_for(&SignatureToken::Address)),
@@ -302,6 +304,47 @@ fn execute_inner(
             verifier.stack.push(AbstractValue::NonReference)
         }
 
+        Bytecode::VecPack(_, num) => {
+            for _ in 0..*num {
+                checked_verify!(verifier.stack.pop().unwrap().is_value())
+            }
+            verifier.stack.push(AbstractValue::NonReference)
+        }
+        Bytecode::VecUnpack(_, num) => {
+            checked_verify!(verifier.stack.pop().unwrap().is_value());
+            for _ in 0..*num {
+                verifier.stack.push(AbstractValue::NonReference)
+            }
+        }
+        Bytecode::VecLen(_) => {
+            let vec_ref = verifier.stack.pop().unwrap();
+            state.vector_op(offset, vec_ref, false)?;
+            verifier.stack.push(AbstractValue::NonReference)
+        }
+        Bytecode::VecImmBorrow(_) | Bytecode::VecMutBorrow(_) => {
+            let mut_ = matches!(bytecode, Bytecode::VecMutBorrow(_));
+            checked_verify!(verifier.stack.pop().unwrap().is_value());
+            let vec_ref = verifier.stack.pop().unwrap();
+            let elem_ref = state.vector_element_borrow(offset, vec_ref, mut_)?;
+            verifier.stack.push(elem_ref)
+        }
+        Bytecode::VecPushBack(_) => {
+            checked_verify!(verifier.stack.pop().unwrap().is_value());
+            let vec_ref = verifier.stack.pop().unwrap();
+            state.vector_op(offset, vec_ref, true)?
+        }
+        Bytecode::VecPopBack(_) => {
+            let vec_ref = verifier.stack.pop().unwrap();
+            state.vector_op(offset, vec_ref, true)?;
+            verifier.stack.push(AbstractValue::NonReference)
+        }
+        Bytecode::VecSwap(_) => {
+            checked_verify!(verifier.stack.pop().unwrap().is_value());
+            checked_verify!(verifier.stack.pop().unwrap().is_value());
+            let vec_ref = verifier.stack.pop().unwrap();
+            state.vector_op(offset, vec_ref, true)?
+        }
+
         Bytecode::Pack(idx) => {
             let struct_def = verifier.module().struct_def_at(*idx);
             pack(verifier, struct_def)
@@ -64,6 +64,7 @@ impl Resolver {
             | SignatureToken::U8
             | SignatureToken::U64
             | SignatureToken::U128
+            | SignatureToken::U256
             | SignatureToken::Address
             | SignatureToken::Signer
             | SignatureToken::TypeParameter(_) => Ok(sig_token.clone()),
@@ -73,6 +73,7 @@ impl<'a> ResourceTransitiveChecker<'a> {
             | SignatureToken::U8
             | SignatureToken::U64
             | SignatureToken::U128
+            | SignatureToken::U256
             | SignatureToken::Address
             | SignatureToken::TypeParameter(_) => false,
         }
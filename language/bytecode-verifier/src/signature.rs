@@ -140,6 +140,25 @@ impl<'a> SignatureChecker<'a> {
                             type_parameters,
                         )
                     }
+                    VecPack(idx, _)
+                    | VecLen(idx)
+                    | VecImmBorrow(idx)
+                    | VecMutBorrow(idx)
+                    | VecPushBack(idx)
+                    | VecPopBack(idx)
+                    | VecUnpack(idx, _)
+                    | VecSwap(idx) => {
+                        let type_arguments = &self.module.signature_at(*idx).0;
+                        if type_arguments.len() != 1 {
+                            Err(VMStatus::new(StatusCode::NUMBER_OF_TYPE_ARGUMENTS_MISMATCH)
+                                .with_message(format!(
+                                    "expected 1 type token for vector operations, got {}",
+                                    type_arguments.len()
+                                )))
+                        } else {
+                            self.check_signature_tokens(type_arguments)
+                        }
+                    }
                     _ => Ok(()),
                 };
                 result.map_err(|err| {
@@ -180,7 +199,9 @@ impl<'a> SignatureChecker<'a> {
     fn check_signature_token(&self, ty: &SignatureToken) -> VMResult<()> {
         use SignatureToken::*;
         match ty {
-            U8 | U64 | U128 | Bool | Address | Signer | Struct(_) | TypeParameter(_) => Ok(()),
+            U8 | U64 | U128 | U256 | Bool | Address | Signer | Struct(_) | TypeParameter(_) => {
+                Ok(())
+            }
             Reference(_) | MutableReference(_) => {
                 // TODO: Prop tests expect us to NOT check the inner types.
                 // Revisit this once we rework prop tests.
@@ -63,7 +63,17 @@ impl<'a> StackUsageVerifier<'a> {
                 ));
             }
             stack_size_increment -= num_pops;
-            stack_size_increment += num_pushes;
+            // Vector instructions take their number of elements as a u64: a block whose stack
+            // grows past that cannot be balanced
+            stack_size_increment = match stack_size_increment.checked_add(num_pushes) {
+                Some(size) => size,
+                None => {
+                    return Err(err_at_offset(
+                        StatusCode::POSITIVE_STACK_SIZE_AT_BLOCK_END,
+                        block_start as usize,
+                    ))
+                }
+            };
         }
 
         if stack_size_increment == 0 {
@@ -79,7 +89,7 @@ impl<'a> StackUsageVerifier<'a> {
     /// The effect of an instruction is a tuple where the first element
     /// is the number of pops it does, and the second element is the number
     /// of pushes it does
-    fn instruction_effect(&self, instruction: &Bytecode) -> (u32, u32) {
+    fn instruction_effect(&self, instruction: &Bytecode) -> (u64, u64) {
         match instruction {
             // Instructions that pop, but don't push
             Bytecode::Pop
@@ -94,6 +104,7 @@ impl<'a> StackUsageVerifier<'a> {
             Bytecode::LdU8(_)
             | Bytecode::LdU64(_)
             | Bytecode::LdU128(_)
+            | Bytecode::LdU256(_)
             | Bytecode::LdTrue
             | Bytecode::LdFalse
             | Bytecode::LdConst(_)
@@ -121,7 +132,10 @@ impl<'a> StackUsageVerifier<'a> {
             | Bytecode::MoveFromGeneric(_)
             | Bytecode::CastU8
             | Bytecode::CastU64
-            | Bytecode::CastU128 => (1, 1),
+            | Bytecode::CastU128
+            | Bytecode::CastU256
+            | Bytecode::VecLen(_)
+            | Bytecode::VecPopBack(_) => (1, 1),
 
             // Binary operations (pop twice and push once)
             Bytecode::Add
@@ -141,10 +155,18 @@ impl<'a> StackUsageVerifier<'a> {
             | Bytecode::Lt
             | Bytecode::Gt
             | Bytecode::Le
-            | Bytecode::Ge => (2, 1),
+            | Bytecode::Ge
+            | Bytecode::VecImmBorrow(_)
+            | Bytecode::VecMutBorrow(_) => (2, 1),
 
-            // MoveTo and WriteRef pop twice but do not push
-            Bytecode::MoveTo(_) | Bytecode::MoveToGeneric(_) | Bytecode::WriteRef => (2, 0),
+            // MoveTo, WriteRef and VecPushBack pop twice but do not push
+            Bytecode::MoveTo(_)
+            | Bytecode::MoveToGeneric(_)
+            | Bytecode::WriteRef
+            | Bytecode::VecPushBack(_) => (2, 0),
+
+            // VecSwap pops three times but does not push
+            Bytecode::VecSwap(_) => (3, 0),
 
             // Branch and Nop neither pops nor pushes
             Bytecode::Branch(_) | Bytecode::Nop => (0, 0),
@@ -152,21 +174,21 @@ impl<'a> StackUsageVerifier<'a> {
             // Return performs `return_count` pops
             Bytecode::Ret => {
                 let return_count = self.module.signature_at(self.function_handle.return_).len();
-                (return_count as u32, 0)
+                (return_count as u64, 0)
             }
 
             // Call performs `arg_count` pops and `return_count` pushes
             Bytecode::Call(idx) => {
                 let function_handle = self.module.function_handle_at(*idx);
-                let arg_count = self.module.signature_at(function_handle.parameters).len() as u32;
-                let return_count = self.module.signature_at(function_handle.return_).len() as u32;
+                let arg_count = self.module.signature_at(function_handle.parameters).len() as u64;
+                let return_count = self.module.signature_at(function_handle.return_).len() as u64;
                 (arg_count, return_count)
             }
             Bytecode::CallGeneric(idx) => {
                 let func_inst = self.module.function_instantiation_at(*idx);
                 let function_handle = self.module.function_handle_at(func_inst.handle);
-                let arg_count = self.module.signature_at(function_handle.parameters).len() as u32;
-                let return_count = self.module.signature_at(function_handle.return_).len() as u32;
+                let arg_count = self.module.signature_at(function_handle.parameters).len() as u64;
+                let return_count = self.module.signature_at(function_handle.return_).len() as u64;
                 (arg_count, return_count)
             }
 
@@ -178,7 +200,7 @@ impl<'a> StackUsageVerifier<'a> {
                     StructFieldInformation::Native => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
-                (field_count as u32, 1)
+                (field_count as u64, 1)
             }
             Bytecode::PackGeneric(idx) => {
                 let struct_inst = self.module.struct_instantiation_at(*idx);
@@ -188,7 +210,7 @@ impl<'a> StackUsageVerifier<'a> {
                     StructFieldInformation::Native => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
-                (field_count as u32, 1)
+                (field_count as u64, 1)
             }
 
             // Unpack performs one pop and `num_fields` pushes
@@ -199,7 +221,7 @@ impl<'a> StackUsageVerifier<'a> {
                     StructFieldInformation::Native => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
-                (1, field_count as u32)
+                (1, field_count as u64)
             }
             Bytecode::UnpackGeneric(idx) => {
                 let struct_inst = self.module.struct_instantiation_at(*idx);
@@ -209,8 +231,12 @@ impl<'a> StackUsageVerifier<'a> {
                     StructFieldInformation::Native => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
-                (1, field_count as u32)
+                (1, field_count as u64)
             }
+
+            // VecPack performs `num` pops and one push, VecUnpack the reverse
+            Bytecode::VecPack(_, num) => (*num, 1),
+            Bytecode::VecUnpack(_, num) => (1, *num),
         }
     }
 }
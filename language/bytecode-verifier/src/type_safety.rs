@@ -15,8 +15,8 @@ use vm::{
     errors::{err_at_offset, VMResult},
     file_format::{
         Bytecode, CompiledModule, FieldHandleIndex, FunctionDefinition, FunctionHandle, Kind,
-        LocalIndex, Signature, SignatureToken, SignatureToken as ST, StructDefinition,
-        StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        LocalIndex, Signature, SignatureIndex, SignatureToken, SignatureToken as ST,
+        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
     },
 };
 
@@ -421,6 +421,43 @@ fn move_to(
     }
 }
 
+/// The element type of a vector instruction, checked to hold one type by the signature checker
+fn vec_elem_type(verifier: &TypeSafetyChecker, idx: SignatureIndex) -> SignatureToken {
+    verifier.module.signature_at(idx).0[0].clone()
+}
+
+/// Pops a reference to a vector of `elem_type`, which must be mutable if `mut_` is set
+fn pop_vec_ref(
+    verifier: &mut TypeSafetyChecker,
+    offset: usize,
+    elem_type: &SignatureToken,
+    mut_: bool,
+) -> VMResult<()> {
+    let operand = verifier.stack.pop().unwrap();
+    let inner = match operand {
+        ST::MutableReference(inner) => inner,
+        ST::Reference(inner) if !mut_ => inner,
+        ST::Reference(_) => {
+            return Err(err_at_offset(
+                StatusCode::VEC_NO_MUTABLE_REFERENCE_ERROR,
+                offset,
+            ))
+        }
+        _ => return Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset)),
+    };
+    match *inner {
+        ST::Vector(ty) if &*ty == elem_type => Ok(()),
+        _ => Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset)),
+    }
+}
+
+fn pop_u64(verifier: &mut TypeSafetyChecker, offset: usize) -> VMResult<()> {
+    match verifier.stack.pop().unwrap() {
+        ST::U64 => Ok(()),
+        _ => Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset)),
+    }
+}
+
 fn verify_instr(
     verifier: &mut TypeSafetyChecker,
     bytecode: &Bytecode,
@@ -524,6 +561,10 @@ fn verify_instr(
             verifier.stack.push(ST::U128);
         }
 
+        Bytecode::LdU256(_) => {
+            verifier.stack.push(ST::U256);
+        }
+
         Bytecode::LdConst(idx) => {
             let signature = verifier.module.constant_at(*idx).type_.clone();
             verifier.stack.push(signature);
@@ -672,6 +713,16 @@ fn verify_instr(
             }
             verifier.stack.push(ST::U128);
         }
+        Bytecode::CastU256 => {
+            let operand = verifier.stack.pop().unwrap();
+            if !operand.is_integer() {
+                return Err(err_at_offset(
+                    StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR,
+                    offset,
+                ));
+            }
+            verifier.stack.push(ST::U256);
+        }
 
         Bytecode::Add
         | Bytecode::Sub
@@ -822,6 +873,72 @@ fn verify_instr(
             move_to(verifier, offset, struct_def, type_args)?
         }
 
+        Bytecode::VecPack(idx, num) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            for _ in 0..*num {
+                let operand = verifier.stack.pop().unwrap();
+                if operand != elem_type {
+                    return Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset));
+                }
+            }
+            verifier.stack.push(ST::Vector(Box::new(elem_type)));
+        }
+
+        Bytecode::VecLen(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            pop_vec_ref(verifier, offset, &elem_type, false)?;
+            verifier.stack.push(ST::U64);
+        }
+
+        Bytecode::VecImmBorrow(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            pop_u64(verifier, offset)?;
+            pop_vec_ref(verifier, offset, &elem_type, false)?;
+            verifier.stack.push(ST::Reference(Box::new(elem_type)));
+        }
+
+        Bytecode::VecMutBorrow(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            pop_u64(verifier, offset)?;
+            pop_vec_ref(verifier, offset, &elem_type, true)?;
+            verifier
+                .stack
+                .push(ST::MutableReference(Box::new(elem_type)));
+        }
+
+        Bytecode::VecPushBack(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            let operand = verifier.stack.pop().unwrap();
+            if operand != elem_type {
+                return Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset));
+            }
+            pop_vec_ref(verifier, offset, &elem_type, true)?;
+        }
+
+        Bytecode::VecPopBack(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            pop_vec_ref(verifier, offset, &elem_type, true)?;
+            verifier.stack.push(elem_type);
+        }
+
+        Bytecode::VecUnpack(idx, num) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            let operand = verifier.stack.pop().unwrap();
+            if operand != ST::Vector(Box::new(elem_type.clone())) {
+                return Err(err_at_offset(StatusCode::VEC_TYPE_MISMATCH_ERROR, offset));
+            }
+            for _ in 0..*num {
+                verifier.stack.push(elem_type.clone());
+            }
+        }
+
+        Bytecode::VecSwap(idx) => {
+            let elem_type = vec_elem_type(verifier, *idx);
+            pop_u64(verifier, offset)?;
+            pop_u64(verifier, offset)?;
+            pop_vec_ref(verifier, offset, &elem_type, true)?;
+        }
+
         Bytecode::GetTxnSenderAddress => {
             verifier.stack.push(ST::Address);
         }
@@ -849,6 +966,7 @@ fn instantiate(token: &SignatureToken, subst: &Signature) -> SignatureToken {
         U8 => U8,
         U64 => U64,
         U128 => U128,
+        U256 => U256,
         Address => Address,
         Signer => Signer,
         Vector(ty) => Vector(Box::new(instantiate(ty, subst))),
@@ -52,6 +52,16 @@ impl<'a> UnusedEntryChecker<'a> {
                         let struct_inst = self.module.struct_instantiation_at(*idx);
                         self.signatures[struct_inst.type_parameters.0 as usize] = true;
                     }
+                    VecPack(idx, _)
+                    | VecLen(idx)
+                    | VecImmBorrow(idx)
+                    | VecMutBorrow(idx)
+                    | VecPushBack(idx)
+                    | VecPopBack(idx)
+                    | VecUnpack(idx, _)
+                    | VecSwap(idx) => {
+                        self.signatures[idx.0 as usize] = true;
+                    }
                     _ => (),
                 }
             }
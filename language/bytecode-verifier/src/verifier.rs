@@ -166,6 +166,7 @@ pub fn verify_main_signature(script: &CompiledScript) -> VMResult<()> {
                 S::Bool
                 | S::U64
                 | S::U128
+                | S::U256
                 | S::Address
                 | S::Signer
                 | S::Struct(_)
@@ -180,7 +181,9 @@ pub fn verify_main_signature(script: &CompiledScript) -> VMResult<()> {
             // based on the sender of the transaction
             S::Reference(inner) => idx == 0 && matches!(&**inner, S::Signer),
 
-            S::Signer
+            // There are no transaction arguments of type u256
+            S::U256
+            | S::Signer
             | S::Struct(_)
             | S::StructInstantiation(_, _)
             | S::MutableReference(_)
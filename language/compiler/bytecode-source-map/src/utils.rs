@@ -12,9 +12,15 @@ use codespan_reporting::{
         Config,
     },
 };
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
 use move_ir_types::location::Loc;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{collections::HashMap, fs::File, io::Read, path::Path};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
 
 pub type Error = (Loc, String);
 pub type Errors = Vec<Error>;
@@ -32,6 +38,27 @@ where
         .map_err(|_| format_err!("Error deserializing into source map"))
 }
 
+/// Compresses a serialized source map, as read from a source map file, to publish it on-chain
+/// alongside its module.
+pub fn compress_source_map(source_map_bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(source_map_bytes)?;
+    Ok(encoder.finish()?)
+}
+
+/// Reads a source map published on-chain alongside its module.
+pub fn decompress_source_map<Location>(compressed: &[u8]) -> Result<SourceMap<Location>>
+where
+    Location: Clone + Eq + Default + DeserializeOwned,
+{
+    let mut bytes = Vec::new();
+    DeflateDecoder::new(compressed)
+        .read_to_end(&mut bytes)
+        .map_err(|_| format_err!("Error while decompressing source map"))?;
+    lcs::from_bytes::<SourceMap<Location>>(&bytes)
+        .map_err(|_| format_err!("Error deserializing into source map"))
+}
+
 pub fn render_errors(source_mapper: &SourceMapping<Loc>, errors: Errors) -> Result<()> {
     if let Some((source_file_name, source_string)) = &source_mapper.source_code {
         let mut codemap = Files::new();
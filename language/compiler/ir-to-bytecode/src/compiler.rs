@@ -26,8 +26,8 @@ use vm::{
     file_format::{
         Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledModuleMut, CompiledScript,
         CompiledScriptMut, Constant, FieldDefinition, FunctionDefinition, FunctionSignature, Kind,
-        Signature, SignatureToken, StructDefinition, StructDefinitionIndex, StructFieldInformation,
-        StructHandleIndex, TableIndex, TypeParameterIndex, TypeSignature,
+        Signature, SignatureIndex, SignatureToken, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, StructHandleIndex, TableIndex, TypeParameterIndex, TypeSignature,
     },
 };
 
@@ -160,6 +160,7 @@ enum InferredType {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     Vector(Box<InferredType>),
@@ -181,6 +182,7 @@ impl InferredType {
             S::U8 => I::U8,
             S::U64 => I::U64,
             S::U128 => I::U128,
+            S::U256 => I::U256,
             S::Address => I::Address,
             S::Signer => I::Signer,
             S::Vector(s_inner) => I::Vector(Box::new(Self::from_signature_token_with_subst(
@@ -234,6 +236,7 @@ impl InferredType {
             InferredType::U8 => bail!("no struct type for U8"),
             InferredType::U64 => bail!("no struct type for U64"),
             InferredType::U128 => bail!("no struct type for U128"),
+            InferredType::U256 => bail!("no struct type for U256"),
             InferredType::Address => bail!("no struct type for Address"),
             InferredType::Signer => bail!("no struct type for Signer"),
             InferredType::Vector(_) => bail!("no struct type for vector"),
@@ -253,6 +256,7 @@ impl InferredType {
             I::U8 => S::U8,
             I::U64 => S::U64,
             I::U128 => S::U128,
+            I::U256 => S::U256,
             I::Address => S::Address,
             I::Signer => S::Signer,
             I::Vector(inner) => S::Vector(Box::new(Self::to_signature_token(inner)?)),
@@ -662,6 +666,7 @@ fn compile_type(
         Type::U8 => SignatureToken::U8,
         Type::U64 => SignatureToken::U64,
         Type::U128 => SignatureToken::U128,
+        Type::U256 => SignatureToken::U256,
         Type::Bool => SignatureToken::Bool,
         Type::Vector(inner_type) => SignatureToken::Vector(Box::new(compile_type(
             context,
@@ -1158,6 +1163,7 @@ fn infer_int_bin_op_result_ty(
         (I::U8, I::U8) => I::U8,
         (I::U64, I::U64) => I::U64,
         (I::U128, I::U128) => I::U128,
+        (I::U256, I::U256) => I::U256,
         _ => I::Anything,
     }
 }
@@ -1224,6 +1230,11 @@ fn compile_expression(
                 function_frame.push()?;
                 vec_deque![InferredType::U128]
             }
+            CopyableVal_::U256(i) => {
+                push_instr!(exp.loc, Bytecode::LdU256(i));
+                function_frame.push()?;
+                vec_deque![InferredType::U256]
+            }
             CopyableVal_::ByteArray(buf) => {
                 let vec_value = MoveValue::vector_u8(buf);
                 let ty = Type::Vector(Box::new(Type::U8));
@@ -1611,6 +1622,12 @@ fn compile_call(
                     function_frame.push()?;
                     vec_deque![InferredType::U128]
                 }
+                Builtin::ToU256 => {
+                    push_instr!(call.loc, Bytecode::CastU256);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                    vec_deque![InferredType::U256]
+                }
             }
         }
         FunctionCall_::ModuleFunctionCall {
@@ -1658,6 +1675,7 @@ fn compile_constant(_context: &mut Context, ty: Type, value: MoveValue) -> Resul
             Type::U8 => MoveTypeLayout::U8,
             Type::U64 => MoveTypeLayout::U64,
             Type::U128 => MoveTypeLayout::U128,
+            Type::U256 => MoveTypeLayout::U256,
             Type::Bool => MoveTypeLayout::Bool,
             Type::Vector(inner_type) => MoveTypeLayout::Vector(Box::new(type_layout(*inner_type)?)),
             Type::Reference(_, _) => bail!("References are not supported in constant type layouts"),
@@ -1749,9 +1767,11 @@ fn compile_bytecode(
         IRBytecode_::LdU8(u) => Bytecode::LdU8(u),
         IRBytecode_::LdU64(u) => Bytecode::LdU64(u),
         IRBytecode_::LdU128(u) => Bytecode::LdU128(u),
+        IRBytecode_::LdU256(u) => Bytecode::LdU256(u),
         IRBytecode_::CastU8 => Bytecode::CastU8,
         IRBytecode_::CastU64 => Bytecode::CastU64,
         IRBytecode_::CastU128 => Bytecode::CastU128,
+        IRBytecode_::CastU256 => Bytecode::CastU256,
         IRBytecode_::LdByteArray(b) => {
             let vec_value = MoveValue::vector_u8(b);
             let ty = Type::Vector(Box::new(Type::U8));
@@ -1978,11 +1998,47 @@ fn compile_bytecode(
         }
         IRBytecode_::Shl => Bytecode::Shl,
         IRBytecode_::Shr => Bytecode::Shr,
+        IRBytecode_::VecPack(ty, n) => {
+            let sig = compile_vector_signature(context, function_frame, &ty)?;
+            Bytecode::VecPack(sig, n)
+        }
+        IRBytecode_::VecLen(ty) => {
+            Bytecode::VecLen(compile_vector_signature(context, function_frame, &ty)?)
+        }
+        IRBytecode_::VecImmBorrow(ty) => {
+            Bytecode::VecImmBorrow(compile_vector_signature(context, function_frame, &ty)?)
+        }
+        IRBytecode_::VecMutBorrow(ty) => {
+            Bytecode::VecMutBorrow(compile_vector_signature(context, function_frame, &ty)?)
+        }
+        IRBytecode_::VecPushBack(ty) => {
+            Bytecode::VecPushBack(compile_vector_signature(context, function_frame, &ty)?)
+        }
+        IRBytecode_::VecPopBack(ty) => {
+            Bytecode::VecPopBack(compile_vector_signature(context, function_frame, &ty)?)
+        }
+        IRBytecode_::VecUnpack(ty, n) => {
+            let sig = compile_vector_signature(context, function_frame, &ty)?;
+            Bytecode::VecUnpack(sig, n)
+        }
+        IRBytecode_::VecSwap(ty) => {
+            Bytecode::VecSwap(compile_vector_signature(context, function_frame, &ty)?)
+        }
     };
     push_instr!(loc, ff_instr);
     Ok(())
 }
 
+/// Compiles the element type of a vector instruction into the signature it refers to
+fn compile_vector_signature(
+    context: &mut Context,
+    function_frame: &FunctionFrame,
+    ty: &Type,
+) -> Result<SignatureIndex> {
+    let token = compile_type(context, function_frame.type_parameters(), ty)?;
+    context.signature_index(Signature(vec![token]))
+}
+
 fn remap_branch_offsets(code: &mut Vec<Bytecode>, fake_to_actual: &HashMap<u16, u16>) {
     for instr in code {
         match instr {
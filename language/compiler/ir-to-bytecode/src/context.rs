@@ -671,6 +671,7 @@ impl<'a> Context<'a> {
             | x @ SignatureToken::U8
             | x @ SignatureToken::U64
             | x @ SignatureToken::U128
+            | x @ SignatureToken::U256
             | x @ SignatureToken::Address
             | x @ SignatureToken::Signer
             | x @ SignatureToken::TypeParameter(_) => x,
@@ -12,6 +12,7 @@ pub enum Tok {
     U8Value,
     U64Value,
     U128Value,
+    U256Value,
     NameValue,
     NameBeginTyValue,
     DotNameValue,
@@ -72,6 +73,7 @@ pub enum Tok {
     ToU8,
     ToU64,
     ToU128,
+    ToU256,
     If,
     Import,
     /// For spec language
@@ -103,6 +105,7 @@ pub enum Tok {
     U8,
     U64,
     U128,
+    U256,
     Vector,
     Copyable,
     While,
@@ -389,6 +392,8 @@ fn get_decimal_number(text: &str) -> (Tok, usize) {
         (Tok::U64Value, len + 3)
     } else if rest.starts_with("u128") {
         (Tok::U128Value, len + 4)
+    } else if rest.starts_with("u256") {
+        (Tok::U256Value, len + 4)
     } else {
         (Tok::U64Value, len)
     }
@@ -436,6 +441,7 @@ fn get_name_token(name: &str) -> Tok {
         "to_u8" => Tok::ToU8,
         "to_u64" => Tok::ToU64,
         "to_u128" => Tok::ToU128,
+        "to_u256" => Tok::ToU256,
         "if" => Tok::If,
         "import" => Tok::Import,
         "let" => Tok::Let,
@@ -459,6 +465,7 @@ fn get_name_token(name: &str) -> Tok {
         "u8" => Tok::U8,
         "u64" => Tok::U64,
         "u128" => Tok::U128,
+        "u256" => Tok::U256,
         "copyable" => Tok::Copyable,
         "while" => Tok::While,
         _ => Tok::NameValue,
@@ -24,6 +24,7 @@
 //!   | u8        // unsigned 8 bit integer
 //!   | u64       // unsigned 64 bit integer
 //!   | u128      // unsigned 128 bit integer
+//!   | u256      // unsigned 256 bit integer
 //!   | address   // 32 byte account address
 //!   | bytearray // immutable, arbitrarily sized array of bytes
 //!
@@ -7,7 +7,10 @@ use std::{fmt, str::FromStr};
 
 use crate::lexer::*;
 use libra_types::account_address::AccountAddress;
-use move_core_types::identifier::{IdentStr, Identifier};
+use move_core_types::{
+    identifier::{IdentStr, Identifier},
+    u256::U256,
+};
 use move_ir_types::{ast::*, location::*, spec_language_ast::*};
 
 // FIXME: The following simplified version of ParseError copied from
@@ -258,6 +261,15 @@ fn parse_copyable_val<'input>(
             tokens.advance()?;
             CopyableVal_::U128(i)
         }
+        Tok::U256Value => {
+            let mut s = tokens.content();
+            if s.ends_with("u256") {
+                s = &s[..s.len() - 4]
+            }
+            let i = U256::from_str(s).unwrap();
+            tokens.advance()?;
+            CopyableVal_::U256(i)
+        }
         Tok::ByteArrayValue => {
             let s = tokens.content();
             let buf = hex::decode(&s[2..s.len() - 1]).unwrap_or_else(|_| {
@@ -396,7 +408,8 @@ fn parse_qualified_function_name<'input>(
         | Tok::Freeze
         | Tok::ToU8
         | Tok::ToU64
-        | Tok::ToU128 => {
+        | Tok::ToU128
+        | Tok::ToU256 => {
             let f = parse_builtin(tokens)?;
             FunctionCall_::Builtin(f)
         }
@@ -536,7 +549,8 @@ fn parse_call_or_term_<'input>(
         | Tok::DotNameValue
         | Tok::ToU8
         | Tok::ToU64
-        | Tok::ToU128 => {
+        | Tok::ToU128
+        | Tok::ToU256 => {
             let f = parse_qualified_function_name(tokens)?;
             let exp = parse_call_or_term(tokens)?;
             Ok(Exp_::FunctionCall(f, Box::new(exp)))
@@ -622,6 +636,7 @@ fn parse_term_<'input>(tokens: &mut Lexer<'input>) -> Result<Exp_, ParseError<Lo
         | Tok::U8Value
         | Tok::U64Value
         | Tok::U128Value
+        | Tok::U256Value
         | Tok::ByteArrayValue => Ok(Exp_::Value(parse_copyable_val(tokens)?)),
         Tok::NameValue | Tok::NameBeginTyValue => {
             let (name, type_actuals) = parse_name_and_type_actuals(tokens)?;
@@ -768,6 +783,10 @@ fn parse_builtin<'input>(
             tokens.advance()?;
             Ok(Builtin::ToU128)
         }
+        Tok::ToU256 => {
+            tokens.advance()?;
+            Ok(Builtin::ToU256)
+        }
         _ => Err(ParseError::InvalidToken {
             location: current_token_loc(tokens),
         }),
@@ -936,7 +955,8 @@ fn parse_cmd_<'input>(tokens: &mut Lexer<'input>) -> Result<Cmd_, ParseError<Loc
         | Tok::DotNameValue
         | Tok::ToU8
         | Tok::ToU64
-        | Tok::ToU128 => Ok(Cmd_::Exp(Box::new(parse_call(tokens)?))),
+        | Tok::ToU128
+        | Tok::ToU256 => Ok(Cmd_::Exp(Box::new(parse_call(tokens)?))),
         Tok::LParen => {
             tokens.advance()?;
             let start = tokens.start_loc();
@@ -1190,6 +1210,10 @@ fn parse_type<'input>(tokens: &mut Lexer<'input>) -> Result<Type, ParseError<Loc
             tokens.advance()?;
             Type::U128
         }
+        Tok::U256 => {
+            tokens.advance()?;
+            Type::U256
+        }
         Tok::Bool => {
             tokens.advance()?;
             Type::Bool
@@ -1474,6 +1498,7 @@ fn parse_unary_spec_exp<'input>(
         | Tok::U8Value
         | Tok::U64Value
         | Tok::U128Value
+        | Tok::U256Value
         | Tok::ByteArrayValue => SpecExp::Constant(parse_copyable_val(tokens)?.value),
         Tok::GlobalExists => {
             consume_token(tokens, Tok::GlobalExists)?;
@@ -12,9 +12,9 @@ use libra_types::{
     account_config::{
         self, from_currency_code_string, type_tag_for_currency_code, AccountResource,
         BalanceResource, KeyRotationCapabilityResource, ReceivedPaymentEvent, RoleId,
-        SentPaymentEvent, WithdrawCapabilityResource, COIN1_NAME, COIN2_NAME, LBR_NAME,
+        SentPaymentEvent, WithdrawCapabilityResource, COIN1_NAME, COIN2_NAME, LBR, LBR_NAME,
     },
-    event::EventHandle,
+    event::{EventHandle, EventKey},
     transaction::{
         authenticator::AuthenticationKey, RawTransaction, Script, SignedTransaction,
         TransactionArgument, TransactionPayload,
@@ -202,6 +202,36 @@ impl Account {
         .into_inner()
     }
 
+    /// Returns a sponsored [`SignedTransaction`] with a payload, this account as the sender and
+    /// `fee_payer` as the account paying for the gas.
+    pub fn create_sponsored_user_txn(
+        &self,
+        fee_payer: &Account,
+        payload: TransactionPayload,
+        sequence_number: u64,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        gas_currency_code: String,
+    ) -> SignedTransaction {
+        Self::create_raw_user_txn(
+            *self.address(),
+            payload,
+            sequence_number,
+            max_gas_amount,
+            gas_unit_price,
+            gas_currency_code,
+        )
+        .sign_with_fee_payer(
+            &self.privkey,
+            self.pubkey.clone(),
+            *fee_payer.address(),
+            &fee_payer.privkey,
+            fee_payer.pubkey.clone(),
+        )
+        .unwrap()
+        .into_inner()
+    }
+
     pub fn create_raw_user_txn(
         address: AccountAddress,
         payload: TransactionPayload,
@@ -232,6 +262,15 @@ impl Account {
                 gas_currency_code,
                 Duration::from_secs(DEFAULT_EXPIRATION_TIME),
             ),
+            payload @ TransactionPayload::Batch(_) => RawTransaction::new(
+                address,
+                sequence_number,
+                payload,
+                max_gas_amount,
+                gas_unit_price,
+                gas_currency_code,
+                Duration::from_secs(DEFAULT_EXPIRATION_TIME),
+            ),
         }
     }
 
@@ -417,7 +456,7 @@ impl Balance {
         FatStructType {
             address: account_config::CORE_CODE_ADDRESS,
             module: AccountResource::module_identifier(),
-            name: BalanceResource::struct_identifier(),
+            name: BalanceResource::<LBR>::struct_identifier(),
             is_resource: true,
             ty_args: vec![],
             layout: vec![FatType::U64],
@@ -640,8 +679,10 @@ pub struct AccountData {
     account_role: AccountRole,
 }
 
-fn new_event_handle(count: u64) -> EventHandle {
-    EventHandle::random_handle(count)
+/// Returns the event handle that the account creation script would give the account, so that the
+/// state of an account only depends on the account and its counters.
+fn new_event_handle(address: &AccountAddress, salt: u64, count: u64) -> EventHandle {
+    EventHandle::new(EventKey::new_from_address(address, salt), count)
 }
 
 impl AccountData {
@@ -730,6 +771,9 @@ impl AccountData {
     ) -> Self {
         let mut balances = BTreeMap::new();
         balances.insert(balance_currency_code, Balance::new(balance));
+        // Accounts created on-chain get their received events handle first
+        let received_events = new_event_handle(account.address(), 0, received_events_count);
+        let sent_events = new_event_handle(account.address(), 1, sent_events_count);
         Self {
             account_role: AccountRole::new(*account.address(), account_specifier),
             event_generator: EventHandleGenerator::new_with_event_count(*account.address(), 2),
@@ -739,8 +783,8 @@ impl AccountData {
             balances,
             sequence_number,
             is_frozen,
-            sent_events: new_event_handle(sent_events_count),
-            received_events: new_event_handle(received_events_count),
+            sent_events,
+            received_events,
         }
     }
 
@@ -8,10 +8,11 @@ use crate::{
     account_universe::{default_num_accounts, default_num_transactions, AccountCurrent},
     executor::FakeExecutor,
 };
-use libra_proptest_helpers::{pick_slice_idxs, Index};
+use libra_proptest_helpers::{pick_slice_idxs, Index, ValueGenerator};
 use proptest::{
     collection::{vec, SizeRange},
     prelude::*,
+    test_runner::{RngAlgorithm, TestRng},
 };
 use proptest_derive::Arbitrary;
 
@@ -71,6 +72,21 @@ impl AccountUniverseGen {
         })
     }
 
+    /// Generates a universe of accounts from a seed, so that the same seed always generates the
+    /// same accounts, keys and balances.
+    ///
+    /// This allows tests to rebuild an identical universe, e.g., to execute the same transactions
+    /// in different orders against it.
+    pub fn from_seed(
+        seed: [u8; 32],
+        num_accounts: impl Into<SizeRange>,
+        balance_strategy: impl Strategy<Value = u64>,
+    ) -> Self {
+        let mut generator =
+            ValueGenerator::new_with_rng(TestRng::from_seed(RngAlgorithm::ChaCha, &seed));
+        generator.generate(Self::strategy(num_accounts, balance_strategy))
+    }
+
     /// Returns a [`Strategy`] that generates a universe of accounts that's guaranteed to succeed,
     /// assuming that any transfers out of accounts will be 100_000 or below.
     pub fn success_strategy(min_accounts: usize) -> impl Strategy<Value = Self> {
@@ -5,6 +5,7 @@
 
 use compiler::Compiler;
 
+use bytecode_source_map::utils::compress_source_map;
 use bytecode_verifier::VerifiedModule;
 use libra_types::{
     account_address::AccountAddress,
@@ -29,6 +30,31 @@ pub fn compile_module_with_address(
     ))
 }
 
+/// Compile the provided Move code into a blob which can be used as the code to be published
+/// (a Module), along with its compressed source map.
+pub fn compile_module_with_source_map(
+    address: &AccountAddress,
+    file_name: &str,
+    code: &str,
+) -> TransactionPayload {
+    let compiler = Compiler {
+        address: *address,
+        ..Compiler::default()
+    };
+    let (compiled_module, source_map) = compiler
+        .into_compiled_module_and_source_map(file_name, code)
+        .expect("Module compilation failed");
+    let mut module_blob = vec![];
+    compiled_module
+        .serialize(&mut module_blob)
+        .expect("Module must serialize");
+    let source_map_bytes = lcs::to_bytes(&source_map).expect("Source map must serialize");
+    TransactionPayload::Module(Module::with_source_map(
+        module_blob,
+        compress_source_map(&source_map_bytes).expect("Source map must compress"),
+    ))
+}
+
 /// Compile the provided Move code into a blob which can be used as the code to be executed
 /// (a Script).
 pub fn compile_script_with_address(
@@ -6,6 +6,7 @@
 use crate::{
     account::{Account, AccountData},
     data_store::{FakeDataStore, GENESIS_CHANGE_SET, GENESIS_CHANGE_SET_FRESH},
+    gas_baseline::{self, GasBaseline},
 };
 use bytecode_verifier::VerifiedModule;
 use compiled_stdlib::{stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions};
@@ -14,8 +15,11 @@ use libra_crypto::HashValue;
 use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath,
-    account_config::{AccountResource, BalanceResource, CORE_CODE_ADDRESS},
+    account_config::{
+        self, AccountResource, AccountVersionResource, BalanceResource, CORE_CODE_ADDRESS,
+    },
     block_metadata::{new_block_event_key, BlockMetadata, NewBlockEvent},
+    libra_timestamp::LibraTimestampResource,
     on_chain_config::{OnChainConfig, VMPublishingOption, ValidatorSet},
     transaction::{
         SignedTransaction, Transaction, TransactionOutput, TransactionStatus, VMValidatorResult,
@@ -26,15 +30,18 @@ use libra_types::{
 use libra_vm::{data_cache::RemoteStorage, LibraVM, VMExecutor, VMValidator};
 use move_core_types::{
     account_address::AccountAddress,
+    errmap::ErrorMapping,
     gas_schedule::{GasAlgebra, GasUnits},
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
+    move_resource::MoveResource,
 };
 use move_vm_runtime::{data_cache::TransactionDataCache, move_vm::MoveVM};
 use move_vm_types::{
     gas_schedule::{zero_cost_schedule, CostStrategy},
     values::Value,
 };
+use std::thread;
 use vm::CompiledModule;
 use vm_genesis::GENESIS_KEYPAIR;
 
@@ -45,6 +52,14 @@ use vm_genesis::GENESIS_KEYPAIR;
 pub struct FakeExecutor {
     data_store: FakeDataStore,
     block_time: u64,
+    gas_baseline: Option<GasBaseline>,
+}
+
+/// The state of a [`FakeExecutor`] at some point, to which it can be rolled back.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutorSnapshot {
+    data_store: FakeDataStore,
+    block_time: u64,
 }
 
 impl FakeExecutor {
@@ -53,6 +68,7 @@ impl FakeExecutor {
         let mut executor = FakeExecutor {
             data_store: FakeDataStore::default(),
             block_time: 0,
+            gas_baseline: None,
         };
         executor.apply_write_set(write_set);
         executor
@@ -96,6 +112,7 @@ impl FakeExecutor {
         FakeExecutor {
             data_store: FakeDataStore::default(),
             block_time: 0,
+            gas_baseline: None,
         }
     }
 
@@ -113,6 +130,7 @@ impl FakeExecutor {
                 &GENESIS_KEYPAIR.1,
                 &vm_genesis::validator_registrations(&swarm.nodes),
                 &genesis_modules,
+                &ErrorMapping::default(),
                 publishing_options,
             )
             .0
@@ -173,6 +191,45 @@ impl FakeExecutor {
             })
     }
 
+    /// Reads the version of the layout of the resources of an account from this executor's data
+    /// store. Accounts created before the versioning have none.
+    pub fn read_account_version_resource(
+        &self,
+        account: &Account,
+    ) -> Option<AccountVersionResource> {
+        let ap = AccessPath::new(*account.address(), AccountVersionResource::resource_path());
+        StateView::get(&self.data_store, &ap)
+            .unwrap_or_else(|_| panic!("account {:?} must exist in data store", account.address()))
+            .map(|data_blob| {
+                lcs::from_bytes(data_blob.as_slice())
+                    .expect("Failure decoding account version resource")
+            })
+    }
+
+    /// Reads the current block time from this executor's data store, as set by the last block
+    /// prologue.
+    pub fn read_timestamp_resource(&self) -> Option<LibraTimestampResource> {
+        let ap = AccessPath::new(
+            account_config::association_address(),
+            LibraTimestampResource::resource_path(),
+        );
+        StateView::get(&self.data_store, &ap)
+            .expect("data store must be readable")
+            .map(|data_blob| {
+                lcs::from_bytes(data_blob.as_slice()).expect("Failure decoding timestamp resource")
+            })
+    }
+
+    /// Records the gas used by the transactions executed from now on, to be compared with the
+    /// baseline of the test `name` when the executor is dropped.
+    ///
+    /// For more, see the [`gas_baseline` module documentation][gas_baseline].
+    pub fn set_gas_baseline(&mut self, name: &str) {
+        let baseline = GasBaseline::new(&gas_baseline::default_baseline_dir(), name)
+            .expect("Unable to set up the gas baseline");
+        self.gas_baseline = Some(baseline);
+    }
+
     /// Executes the given block of transactions.
     ///
     /// Typical tests will call this method and check that the output matches what was expected.
@@ -181,13 +238,19 @@ impl FakeExecutor {
         &self,
         txn_block: Vec<SignedTransaction>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
-        LibraVM::execute_block(
+        let outputs = LibraVM::execute_block(
             txn_block
                 .into_iter()
                 .map(Transaction::UserTransaction)
                 .collect(),
             &self.data_store,
-        )
+        )?;
+        if let Some(baseline) = &self.gas_baseline {
+            for output in &outputs {
+                baseline.record(output);
+            }
+        }
+        Ok(outputs)
     }
 
     /// Executes the transaction as a singleton block and applies the resulting write set to the
@@ -211,6 +274,37 @@ impl FakeExecutor {
         }
     }
 
+    /// Executes the given block of transactions and applies the resulting write sets to the data
+    /// store, as the executor would when committing the block.
+    pub fn execute_and_apply_block(
+        &mut self,
+        txn_block: Vec<SignedTransaction>,
+    ) -> Result<Vec<TransactionOutput>, VMStatus> {
+        let outputs = self.execute_block(txn_block)?;
+        for output in &outputs {
+            self.apply_write_set(output.write_set());
+        }
+        Ok(outputs)
+    }
+
+    /// Returns the current state of the executor, which can be restored with
+    /// [`FakeExecutor::rollback`].
+    ///
+    /// Together, these allow running several blocks, e.g., the same transactions in different
+    /// orders, against the same initial state.
+    pub fn snapshot(&self) -> ExecutorSnapshot {
+        ExecutorSnapshot {
+            data_store: self.data_store.clone(),
+            block_time: self.block_time,
+        }
+    }
+
+    /// Restores the state of the executor from a snapshot, discarding all changes made since.
+    pub fn rollback(&mut self, snapshot: ExecutorSnapshot) {
+        self.data_store = snapshot.data_store;
+        self.block_time = snapshot.block_time;
+    }
+
     pub fn execute_transaction_block(
         &self,
         txn_block: Vec<Transaction>,
@@ -249,11 +343,13 @@ impl FakeExecutor {
             .expect("Unable to retrieve the validator set from storage");
         self.block_time += 1;
         let new_block = BlockMetadata::new(
+            HashValue::zero(),
             HashValue::zero(),
             0,
             self.block_time,
             vec![],
             *validator_set.payload()[0].account_address(),
+            vec![],
         );
         let output = self
             .execute_transaction_block(vec![Transaction::BlockMetadata(new_block)])
@@ -308,3 +404,16 @@ impl FakeExecutor {
         self.data_store.add_write_set(&write_set);
     }
 }
+
+impl Drop for FakeExecutor {
+    fn drop(&mut self) {
+        // Do not mask the failure of a test that already panicked
+        if let Some(baseline) = &self.gas_baseline {
+            if !thread::panicking() {
+                if let Err(e) = baseline.check() {
+                    panic!("{}", e);
+                }
+            }
+        }
+    }
+}
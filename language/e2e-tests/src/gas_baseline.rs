@@ -0,0 +1,159 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Baselines of the gas used by the transactions of tests, checked in under `goldens/`.
+//!
+//! Tests opt in with [`FakeExecutor::set_gas_baseline`]. The gas used by every transaction the
+//! executor runs afterwards is recorded, and compared with the baseline of the test once the
+//! executor is dropped. The test fails if the gas used by a transaction differs from the baseline
+//! by more than a threshold, in percent, set with the `GAS_THRESHOLD` environment variable (0 by
+//! default).
+//!
+//! Missing baselines are recorded. Run the tests with `UPDATE_BASELINE=1` (or `UB=1`) to bless the
+//! gas currently used as the new baselines.
+//!
+//! [`FakeExecutor::set_gas_baseline`]: crate::executor::FakeExecutor::set_gas_baseline
+
+use anyhow::{bail, ensure, format_err, Result};
+use libra_types::transaction::{TransactionOutput, TransactionStatus};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+pub const UPDATE_BASELINE: &str = "UPDATE_BASELINE";
+pub const UB: &str = "UB";
+pub const GAS_THRESHOLD: &str = "GAS_THRESHOLD";
+
+const BASELINE_EXT: &str = "gas";
+
+/// Returns the directory of the baselines checked in with this crate
+pub fn default_baseline_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("goldens")
+}
+
+fn read_bool_var(name: &str) -> bool {
+    env::var(name).map_or(false, |val| val == "1" || val == "true")
+}
+
+/// The gas used by the transactions of a test, to be compared with its baseline
+#[derive(Debug)]
+pub struct GasBaseline {
+    path: PathBuf,
+    threshold_percent: u64,
+    // One line per transaction, holding the gas used followed by the status
+    recorded: Mutex<Vec<(u64, String)>>,
+}
+
+impl GasBaseline {
+    /// Creates an empty record for the test `name`, with the threshold set in the environment
+    pub fn new(dir: &Path, name: &str) -> Result<Self> {
+        let threshold_percent = match env::var(GAS_THRESHOLD) {
+            Ok(val) => val
+                .parse()
+                .map_err(|e| format_err!("Invalid {} {:?}: {}", GAS_THRESHOLD, val, e))?,
+            Err(_) => 0,
+        };
+        Ok(Self {
+            path: dir.join(name).with_extension(BASELINE_EXT),
+            threshold_percent,
+            recorded: Mutex::new(vec![]),
+        })
+    }
+
+    /// Overrides the threshold, in percent, set in the environment
+    pub fn with_threshold_percent(mut self, threshold_percent: u64) -> Self {
+        self.threshold_percent = threshold_percent;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, output: &TransactionOutput) {
+        let status = match output.status() {
+            TransactionStatus::Keep(status) => format!("keep {:?}", status.major_status),
+            TransactionStatus::Discard(status) => format!("discard {:?}", status.major_status),
+            TransactionStatus::Retry => "retry".to_string(),
+        };
+        self.recorded
+            .lock()
+            .expect("[gas baseline] failed to acquire lock")
+            .push((output.gas_used(), status));
+    }
+
+    /// Compares the gas recorded with the baseline, or writes the baseline if it is missing or
+    /// `UPDATE_BASELINE` is set.
+    pub fn check(&self) -> Result<()> {
+        self.check_or_update(read_bool_var(UPDATE_BASELINE) || read_bool_var(UB))
+    }
+
+    pub(crate) fn check_or_update(&self, update_baseline: bool) -> Result<()> {
+        let recorded = self
+            .recorded
+            .lock()
+            .expect("[gas baseline] failed to acquire lock");
+        if update_baseline || !self.path.exists() {
+            if let Some(dir) = self.path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let contents: String = recorded
+                .iter()
+                .map(|(gas_used, status)| format!("{} {}\n", gas_used, status))
+                .collect();
+            fs::write(&self.path, contents)?;
+            return Ok(());
+        }
+
+        let baseline = fs::read_to_string(&self.path)?
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .next()
+                    .and_then(|gas_used| gas_used.parse::<u64>().ok())
+                    .ok_or_else(|| format_err!("Invalid line in {:?}: {:?}", self.path, line))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            baseline.len() == recorded.len(),
+            "{} transactions were executed, but the baseline {:?} has {}. Run with {}=1 to update it.",
+            recorded.len(),
+            self.path,
+            baseline.len(),
+            UPDATE_BASELINE
+        );
+
+        let drifts: Vec<_> = baseline
+            .iter()
+            .zip(recorded.iter())
+            .enumerate()
+            .filter(|(_, (expected, (actual, _)))| {
+                exceeds_threshold(**expected, *actual, self.threshold_percent)
+            })
+            .map(|(idx, (expected, (actual, status)))| {
+                format!(
+                    "transaction {} ({}) used {} gas, the baseline is {}",
+                    idx, status, actual, expected
+                )
+            })
+            .collect();
+        if !drifts.is_empty() {
+            bail!(
+                "Gas used drifted more than {}% from the baseline {:?}:\n{}\nRun with {}=1 to \
+                 update it.",
+                self.threshold_percent,
+                self.path,
+                drifts.join("\n"),
+                UPDATE_BASELINE
+            );
+        }
+        Ok(())
+    }
+}
+
+fn exceeds_threshold(expected: u64, actual: u64, threshold_percent: u64) -> bool {
+    let drift = u128::from(expected.max(actual) - expected.min(actual));
+    drift * 100 > u128::from(expected) * u128::from(threshold_percent)
+}
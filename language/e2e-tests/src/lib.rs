@@ -19,6 +19,7 @@ pub mod compile;
 pub mod data_store;
 pub mod execution_strategies;
 pub mod executor;
+pub mod gas_baseline;
 pub mod gas_costs;
 pub mod keygen;
 mod proptest_types;
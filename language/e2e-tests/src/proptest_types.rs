@@ -1,18 +1,30 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account::{lbr_currency_code, Account, AccountData, AccountRoleSpecifier};
+use crate::{
+    account::{lbr_currency_code, Account, AccountData, AccountRoleSpecifier},
+    keygen::KeyGen,
+};
 use proptest::prelude::*;
 
 impl Arbitrary for Account {
     type Parameters = ();
     fn arbitrary_with(_params: ()) -> Self::Strategy {
-        // Provide Account::new as the canonical strategy. This means that no shrinking will happen,
-        // but that's fine as accounts have nothing to shrink inside them anyway.
-        Account::new as Self::Strategy
+        // Derive the keys from a seed drawn from the test runner, so that a runner with a fixed seed
+        // generates the same accounts. Shrinking the seed would only produce other random
+        // accounts, so it is disabled.
+        any::<[u8; 32]>()
+            .prop_map(account_from_seed)
+            .no_shrink()
+            .boxed()
     }
 
-    type Strategy = fn() -> Account;
+    type Strategy = BoxedStrategy<Account>;
+}
+
+fn account_from_seed(seed: [u8; 32]) -> Account {
+    let (privkey, pubkey) = KeyGen::from_seed(seed).generate_keypair();
+    Account::with_keypair(privkey, pubkey)
 }
 
 impl AccountData {
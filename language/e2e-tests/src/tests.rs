@@ -9,11 +9,14 @@
 //! separate binary. The linker ends up repeating a lot of work for each binary to not much
 //! benefit.
 
+mod account_migration;
 mod account_universe;
+mod batch;
 mod create_account;
 mod data_store;
 mod execution_strategies;
 mod failed_transaction_tests;
+mod gas_baseline;
 mod genesis;
 mod mint;
 mod module_publishing;
@@ -21,6 +24,7 @@ mod on_chain_configs;
 mod peer_to_peer;
 mod rotate_key;
 mod scripts;
+mod sponsored_transaction;
 mod transaction_builder;
 mod transaction_fees;
 mod validator_set_management;
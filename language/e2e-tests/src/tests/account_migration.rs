@@ -0,0 +1,71 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{Account, AccountData},
+    common_transactions::create_account_txn,
+    executor::FakeExecutor,
+};
+use libra_types::{
+    account_config::MigrationEvent,
+    event::EventKey,
+    transaction::{TransactionOutput, TransactionStatus},
+    vm_error::{StatusCode, VMStatus},
+};
+
+fn migrations(output: &TransactionOutput, key: &EventKey) -> Vec<(u64, u64)> {
+    output
+        .events()
+        .iter()
+        .filter(|event| event.key() == key)
+        .map(|event| {
+            let event = MigrationEvent::try_from_bytes(event.event_data()).unwrap();
+            (event.from_version(), event.to_version())
+        })
+        .collect()
+}
+
+#[test]
+fn sender_migrated_by_first_transaction() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    // Accounts added directly to the data store predate the versioning
+    let sender = AccountData::new(1_000_000, 10);
+    executor.add_account_data(&sender);
+    assert!(executor
+        .read_account_version_resource(sender.account())
+        .is_none());
+
+    let new_account = Account::new();
+    let txn = create_account_txn(sender.account(), &new_account, 10, 1_000);
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    );
+    executor.apply_write_set(output.write_set());
+
+    let version = executor
+        .read_account_version_resource(sender.account())
+        .expect("sender must be migrated");
+    assert_eq!(version.version(), 1);
+    assert_eq!(
+        migrations(&output, version.migration_events().key()),
+        vec![(0, 1)]
+    );
+
+    // New accounts have the latest layout
+    let new_version = executor
+        .read_account_version_resource(&new_account)
+        .expect("new account must be versioned");
+    assert_eq!(new_version.version(), 1);
+    assert_eq!(new_version.migration_events().count(), 0);
+
+    // The sender is migrated only once
+    let txn = create_account_txn(sender.account(), &Account::new(), 11, 1_000);
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    );
+    assert!(migrations(&output, version.migration_events().key()).is_empty());
+}
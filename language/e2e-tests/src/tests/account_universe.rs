@@ -9,8 +9,8 @@ mod rotate_key;
 use crate::{
     account_universe::{
         all_transactions_strategy, default_num_accounts, default_num_transactions,
-        log_balance_strategy, run_and_assert_universe, AccountCurrent, AccountPairGen,
-        AccountPickStyle, AccountUniverseGen,
+        log_balance_strategy, run_and_assert_universe, AUTransactionGen, AccountCurrent,
+        AccountPairGen, AccountPickStyle, AccountUniverseGen,
     },
     executor::FakeExecutor,
 };
@@ -81,4 +81,54 @@ proptest! {
 
         run_and_assert_universe(universe, transactions)?;
     }
+    /// Ensure that universes generated from the same seed set up the same state.
+    #[test]
+    fn seeded_universe(seed in any::<[u8; 32]>()) {
+        let snapshot = |seed| {
+            let mut executor = FakeExecutor::from_genesis_file();
+            AccountUniverseGen::from_seed(
+                seed,
+                2..default_num_accounts(),
+                log_balance_strategy(10_000_000),
+            )
+            .setup(&mut executor);
+            executor.snapshot()
+        };
+        prop_assert!(snapshot(seed) == snapshot(seed), "universes from the same seed differ");
+    }
+
+    /// Ensure that executing transactions as one block has the same effect as executing them one
+    /// at a time, starting over from the same state.
+    #[test]
+    fn block_and_sequential_execution(
+        seed in any::<[u8; 32]>(),
+        transactions in vec(all_transactions_strategy(1, 1_000_000), 0..default_num_transactions()),
+    ) {
+        let mut executor = FakeExecutor::from_genesis_file();
+        let mut universe = AccountUniverseGen::from_seed(
+            seed,
+            2..default_num_accounts(),
+            log_balance_strategy(10_000_000),
+        )
+        .setup(&mut executor);
+        let transactions: Vec<_> = transactions
+            .iter()
+            .map(|transaction_gen| transaction_gen.apply(&mut universe).0)
+            .collect();
+
+        let initial = executor.snapshot();
+        let block_outputs = executor.execute_and_apply_block(transactions.clone()).unwrap();
+        let after_block = executor.snapshot();
+
+        executor.rollback(initial);
+        let mut sequential_outputs = vec![];
+        for transaction in transactions {
+            sequential_outputs.extend(executor.execute_and_apply_block(vec![transaction]).unwrap());
+        }
+        prop_assert_eq!(block_outputs, sequential_outputs);
+        prop_assert!(
+            executor.snapshot() == after_block,
+            "block and sequential execution lead to different states"
+        );
+    }
 }
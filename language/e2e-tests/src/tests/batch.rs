@@ -0,0 +1,154 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{self, Account, AccountData},
+    executor::FakeExecutor,
+    gas_costs,
+};
+use compiled_stdlib::transaction_scripts::StdlibScript;
+use libra_types::{
+    account_config::{lbr_type_tag, LBR_NAME},
+    transaction::{
+        Script, SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+use move_core_types::gas_schedule::GasConstants;
+
+fn peer_to_peer_script(receiver: &Account, transfer_amount: u64) -> Script {
+    Script::new(
+        StdlibScript::PeerToPeerWithMetadata
+            .compiled_bytes()
+            .into_vec(),
+        vec![lbr_type_tag()],
+        vec![
+            TransactionArgument::Address(*receiver.address()),
+            TransactionArgument::U64(transfer_amount),
+            TransactionArgument::U8Vector(vec![]),
+            TransactionArgument::U8Vector(vec![]),
+        ],
+    )
+}
+
+fn batch_txn(sender: &Account, scripts: Vec<Script>, seq_num: u64) -> SignedTransaction {
+    sender.create_user_txn(
+        TransactionPayload::Batch(scripts),
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        0,
+        LBR_NAME.to_owned(),
+    )
+}
+
+fn balance(executor: &FakeExecutor, account: &Account) -> u64 {
+    executor
+        .read_balance_resource(account, account::lbr_currency_code())
+        .expect("balance must exist")
+        .coin()
+}
+
+#[test]
+fn batch_executes_all_scripts() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver_1 = AccountData::new(100_000, 10);
+    let receiver_2 = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver_1);
+    executor.add_account_data(&receiver_2);
+
+    let txn = batch_txn(
+        sender.account(),
+        vec![
+            peer_to_peer_script(receiver_1.account(), 1_000),
+            peer_to_peer_script(receiver_2.account(), 2_000),
+        ],
+        10,
+    );
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    );
+    executor.apply_write_set(output.write_set());
+
+    assert_eq!(balance(&executor, sender.account()), 1_000_000 - 3_000);
+    assert_eq!(balance(&executor, receiver_1.account()), 100_000 + 1_000);
+    assert_eq!(balance(&executor, receiver_2.account()), 100_000 + 2_000);
+    let updated_sender = executor
+        .read_account_resource(sender.account())
+        .expect("sender must exist");
+    assert_eq!(updated_sender.sequence_number(), 11);
+    assert_eq!(updated_sender.sent_events().count(), 2);
+}
+
+#[test]
+fn batch_is_atomic() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver_1 = AccountData::new(100_000, 10);
+    let receiver_2 = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver_1);
+    executor.add_account_data(&receiver_2);
+
+    // The second transfer exceeds the balance of the sender, so the first one is reverted too.
+    let txn = batch_txn(
+        sender.account(),
+        vec![
+            peer_to_peer_script(receiver_1.account(), 1_000),
+            peer_to_peer_script(receiver_2.account(), 10_000_000),
+        ],
+        10,
+    );
+    let output = executor.execute_transaction(txn);
+    match output.status() {
+        TransactionStatus::Keep(status) => assert_ne!(status.major_status, StatusCode::EXECUTED),
+        status => panic!("the transaction should be kept, got {:?}", status),
+    }
+    executor.apply_write_set(output.write_set());
+
+    assert_eq!(balance(&executor, sender.account()), 1_000_000);
+    assert_eq!(balance(&executor, receiver_1.account()), 100_000);
+    assert_eq!(balance(&executor, receiver_2.account()), 100_000);
+    let updated_sender = executor
+        .read_account_resource(sender.account())
+        .expect("sender must exist");
+    assert_eq!(updated_sender.sequence_number(), 11);
+    assert_eq!(updated_sender.sent_events().count(), 0);
+}
+
+#[test]
+fn batch_size_is_limited() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let max_scripts = GasConstants::default().max_scripts_per_batch as usize;
+    let full_batch = batch_txn(
+        sender.account(),
+        vec![peer_to_peer_script(receiver.account(), 1); max_scripts],
+        10,
+    );
+    assert_eq!(executor.verify_transaction(full_batch).status(), None);
+
+    let empty_batch = batch_txn(sender.account(), vec![], 10);
+    let too_large_batch = batch_txn(
+        sender.account(),
+        vec![peer_to_peer_script(receiver.account(), 1); max_scripts + 1],
+        10,
+    );
+    for txn in vec![empty_batch, too_large_batch] {
+        assert_eq!(
+            executor.verify_transaction(txn.clone()).status(),
+            Some(VMStatus::new(StatusCode::INVALID_BATCH_SIZE))
+        );
+        assert_eq!(
+            executor.execute_transaction(txn).status(),
+            &TransactionStatus::Discard(VMStatus::new(StatusCode::INVALID_BATCH_SIZE))
+        );
+    }
+}
@@ -16,6 +16,7 @@ use move_vm_types::values::Value;
 #[test]
 fn create_account() {
     let mut executor = FakeExecutor::from_genesis_file();
+    executor.set_gas_baseline("create_account/create_account");
     // create and publish a sender with 1_000_000 coins
     let sender = AccountData::new(1_000_000, 10);
     executor.add_account_data(&sender);
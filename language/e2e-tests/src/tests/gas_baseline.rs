@@ -0,0 +1,62 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::gas_baseline::GasBaseline;
+use libra_temppath::TempPath;
+use libra_types::{
+    transaction::{TransactionOutput, TransactionStatus},
+    vm_error::{StatusCode, VMStatus},
+    write_set::WriteSet,
+};
+
+fn baseline_with_gas(dir: &TempPath, threshold_percent: u64, gas: &[u64]) -> GasBaseline {
+    let baseline = GasBaseline::new(dir.path(), "test")
+        .unwrap()
+        .with_threshold_percent(threshold_percent);
+    for gas_used in gas {
+        baseline.record(&TransactionOutput::new(
+            WriteSet::default(),
+            vec![],
+            *gas_used,
+            TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+        ));
+    }
+    baseline
+}
+
+#[test]
+fn gas_baseline_detects_drift() {
+    let dir = TempPath::new();
+    dir.create_as_dir().unwrap();
+
+    // The missing baseline is recorded
+    let baseline = baseline_with_gas(&dir, 0, &[1_000, 2_000]);
+    baseline.check_or_update(false).unwrap();
+    assert!(baseline.path().exists());
+
+    baseline_with_gas(&dir, 0, &[1_000, 2_000])
+        .check_or_update(false)
+        .unwrap();
+    assert!(baseline_with_gas(&dir, 0, &[1_000, 2_001])
+        .check_or_update(false)
+        .is_err());
+    assert!(baseline_with_gas(&dir, 0, &[1_000])
+        .check_or_update(false)
+        .is_err());
+
+    // Drift within the threshold is tolerated, in both directions
+    baseline_with_gas(&dir, 10, &[1_100, 1_800])
+        .check_or_update(false)
+        .unwrap();
+    assert!(baseline_with_gas(&dir, 10, &[1_101, 2_000])
+        .check_or_update(false)
+        .is_err());
+
+    // Blessing replaces the baseline
+    baseline_with_gas(&dir, 0, &[1_500])
+        .check_or_update(true)
+        .unwrap();
+    baseline_with_gas(&dir, 0, &[1_500])
+        .check_or_update(false)
+        .unwrap();
+}
@@ -4,16 +4,20 @@
 use crate::{
     account::{Account, AccountData},
     assert_prologue_parity, assert_status_eq,
-    compile::compile_module_with_address,
+    compile::{compile_module_with_address, compile_module_with_source_map},
     executor::FakeExecutor,
     transaction_status_eq,
 };
+use bytecode_source_map::utils::{decompress_source_map, OwnedLoc};
 use libra_types::{
+    access_path::AccessPath,
     account_config::{self, LBR_NAME},
     on_chain_config::VMPublishingOption,
     transaction::TransactionStatus,
     vm_error::{StatusCode, StatusType, VMStatus},
 };
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use vm::file_format::FunctionDefinitionIndex;
 
 // A module with an address different from the sender's address should be rejected
 #[test]
@@ -117,6 +121,51 @@ fn duplicate_module() {
     ));
 }
 
+// A source map published along with a module is stored next to its code
+#[test]
+fn module_with_source_map() {
+    let mut executor = FakeExecutor::from_genesis_with_options(VMPublishingOption::Open);
+
+    let sequence_number = 2;
+    let account = AccountData::new(1_000_000, sequence_number);
+    executor.add_account_data(&account);
+
+    let program = String::from(
+        "
+        module M {
+            public f(): u64 {
+                return 1;
+            }
+        }
+        ",
+    );
+    let compiled_module = compile_module_with_source_map(account.address(), "file_name", &program);
+    let txn = account.account().create_signed_txn_impl(
+        *account.address(),
+        compiled_module,
+        sequence_number,
+        100_000,
+        1,
+        LBR_NAME.to_owned(),
+    );
+
+    let output = executor.execute_transaction(txn);
+    assert!(transaction_status_eq(
+        &output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED)),
+    ));
+    executor.apply_write_set(output.write_set());
+
+    let module_id = ModuleId::new(*account.address(), Identifier::new("M").unwrap());
+    let compressed = executor
+        .read_from_access_path(&AccessPath::source_map_access_path(&module_id))
+        .expect("source map must be published");
+    let source_map = decompress_source_map::<OwnedLoc>(&compressed).unwrap();
+    assert!(source_map
+        .get_function_source_map(FunctionDefinitionIndex(0))
+        .is_ok());
+}
+
 #[test]
 pub fn test_publishing_no_modules_non_whitelist_script() {
     // create a FakeExecutor with a genesis from file
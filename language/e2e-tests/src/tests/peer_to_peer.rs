@@ -25,6 +25,7 @@ fn single_peer_to_peer_with_event() {
     ::libra_logger::Logger::new().environment_only(true).init();
     // create a FakeExecutor with a genesis from file
     let mut executor = FakeExecutor::from_genesis_file();
+    executor.set_gas_baseline("peer_to_peer/single_peer_to_peer_with_event");
     // create and publish a sender with 1_000_000 coins and a receiver with 100_000 coins
     let sender = AccountData::new(1_000_000, 10);
     let receiver = AccountData::new(100_000, 10);
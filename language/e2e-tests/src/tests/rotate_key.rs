@@ -22,6 +22,7 @@ use libra_types::{
 fn rotate_ed25519_key() {
     let balance = 1_000_000;
     let mut executor = FakeExecutor::from_genesis_file();
+    executor.set_gas_baseline("rotate_key/rotate_ed25519_key");
     // create and publish sender
     let mut sender = AccountData::new(balance, 10);
     executor.add_account_data(&sender);
@@ -0,0 +1,134 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account::{self, Account, AccountData},
+    executor::FakeExecutor,
+    gas_costs,
+};
+use compiled_stdlib::transaction_scripts::StdlibScript;
+use libra_types::{
+    account_config::{lbr_type_tag, LBR_NAME},
+    transaction::{
+        Script, SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+    },
+    vm_error::{StatusCode, VMStatus},
+};
+
+fn peer_to_peer_txn(
+    sender: &Account,
+    fee_payer: &Account,
+    receiver: &Account,
+    transfer_amount: u64,
+    seq_num: u64,
+) -> SignedTransaction {
+    let script = Script::new(
+        StdlibScript::PeerToPeerWithMetadata
+            .compiled_bytes()
+            .into_vec(),
+        vec![lbr_type_tag()],
+        vec![
+            TransactionArgument::Address(*receiver.address()),
+            TransactionArgument::U64(transfer_amount),
+            TransactionArgument::U8Vector(vec![]),
+            TransactionArgument::U8Vector(vec![]),
+        ],
+    );
+    sender.create_sponsored_user_txn(
+        fee_payer,
+        TransactionPayload::Script(script),
+        seq_num,
+        gas_costs::TXN_RESERVED,
+        1,
+        LBR_NAME.to_owned(),
+    )
+}
+
+fn balance(executor: &FakeExecutor, account: &Account) -> u64 {
+    executor
+        .read_balance_resource(account, account::lbr_currency_code())
+        .expect("balance must exist")
+        .coin()
+}
+
+#[test]
+fn fee_payer_pays_for_gas() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(100_000, 10);
+    let fee_payer = AccountData::new(1_000_000, 3);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&fee_payer);
+    executor.add_account_data(&receiver);
+
+    let txn = peer_to_peer_txn(
+        sender.account(),
+        fee_payer.account(),
+        receiver.account(),
+        1_000,
+        10,
+    );
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(VMStatus::new(StatusCode::EXECUTED))
+    );
+    assert!(output.gas_used() > 0);
+    executor.apply_write_set(output.write_set());
+
+    // The script runs as the sender, the gas is charged to the fee payer
+    assert_eq!(balance(&executor, sender.account()), 100_000 - 1_000);
+    assert_eq!(balance(&executor, receiver.account()), 100_000 + 1_000);
+    assert_eq!(
+        balance(&executor, fee_payer.account()),
+        1_000_000 - output.gas_used()
+    );
+    let updated_sender = executor
+        .read_account_resource(sender.account())
+        .expect("sender must exist");
+    assert_eq!(updated_sender.sequence_number(), 11);
+    let updated_fee_payer = executor
+        .read_account_resource(fee_payer.account())
+        .expect("fee payer must exist");
+    assert_eq!(updated_fee_payer.sequence_number(), 3);
+}
+
+#[test]
+fn invalid_fee_payer() {
+    let mut executor = FakeExecutor::from_genesis_file();
+    let sender = AccountData::new(1_000_000, 10);
+    let poor_fee_payer = AccountData::new(10, 0);
+    let receiver = AccountData::new(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&poor_fee_payer);
+    executor.add_account_data(&receiver);
+
+    let missing_fee_payer = Account::new();
+    let mut wrong_key_fee_payer = poor_fee_payer.account().clone();
+    let new_key = Account::new();
+    wrong_key_fee_payer.rotate_key(new_key.privkey, new_key.pubkey);
+
+    let cases = vec![
+        (sender.account(), StatusCode::INVALID_FEE_PAYER),
+        (
+            &missing_fee_payer,
+            StatusCode::FEE_PAYER_ACCOUNT_DOES_NOT_EXIST,
+        ),
+        (&wrong_key_fee_payer, StatusCode::INVALID_FEE_PAYER_AUTH_KEY),
+        (
+            poor_fee_payer.account(),
+            StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE,
+        ),
+    ];
+    for (fee_payer, status) in cases {
+        let txn = peer_to_peer_txn(sender.account(), fee_payer, receiver.account(), 1_000, 10);
+        assert_eq!(
+            executor.verify_transaction(txn.clone()).status(),
+            Some(VMStatus::new(status))
+        );
+        assert_eq!(
+            executor.execute_transaction(txn).status(),
+            &TransactionStatus::Discard(VMStatus::new(status))
+        );
+    }
+}
@@ -11,8 +11,13 @@ use bytecode_verifier::{
     VerifiedModule, VerifiedScript,
 };
 use compiled_stdlib::{stdlib_modules, StdLibOptions};
-use language_e2e_tests::executor::FakeExecutor;
-use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use language_e2e_tests::{
+    account::Account, common_transactions::reconfigure_txn, executor::FakeExecutor,
+};
+use libra_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    HashValue,
+};
 use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath,
@@ -20,7 +25,7 @@ use libra_types::{
     account_config,
     account_config::LBR_NAME,
     block_metadata::BlockMetadata,
-    on_chain_config::VMPublishingOption,
+    on_chain_config::{OnChainConfig, VMPublishingOption, ValidatorSet},
     transaction::{
         Module as TransactionModule, RawTransaction, Script as TransactionScript,
         SignedTransaction, Transaction as LibraTransaction, TransactionOutput, TransactionStatus,
@@ -53,6 +58,7 @@ pub struct Transaction<'a> {
 /// Commands that drives the operation of LibraVM. Such as:
 /// 1. Execute user transaction
 /// 2. Publish a new block metadata
+/// 3. Advance the block time or the epoch
 ///
 /// In the future we will add more commands to mimic the full public API of LibraVM,
 /// including reloading the on-chain configuration that will affect the code path for LibraVM,
@@ -61,6 +67,12 @@ pub struct Transaction<'a> {
 pub enum Command<'a> {
     Transaction(Transaction<'a>),
     BlockMetadata(BlockMetadata),
+    /// Runs a block prologue at the given time, in microseconds, proposed by a validator
+    BlockTime(u64),
+    /// Has the association reconfigure the network, which emits a `NewEpochEvent`
+    TriggerReconfiguration,
+    /// Advances the block time and triggers a reconfiguration
+    NewEpoch,
 }
 
 /// Indicates one step in the pipeline the given move module/program goes through.
@@ -578,6 +590,73 @@ pub fn eval_block_metadata(
     }
 }
 
+/// Runs a block prologue at `timestamp`, proposed by the first validator in the validator set.
+fn eval_block_time(
+    executor: &mut FakeExecutor,
+    timestamp: u64,
+    log: &mut EvaluationLog,
+) -> Result<Status> {
+    let validator_set = ValidatorSet::fetch_config(executor.get_state_view())
+        .ok_or_else(|| ErrorKind::Other("validator set not found".to_string()))?;
+    let proposer = match validator_set.payload().first() {
+        Some(validator) => *validator.account_address(),
+        None => {
+            let err: Error =
+                ErrorKind::Other("no validator to propose the block".to_string()).into();
+            log.append(EvaluationOutput::Error(Box::new(err)));
+            return Ok(Status::Failure);
+        }
+    };
+    eval_block_metadata(
+        executor,
+        BlockMetadata::new(
+            HashValue::zero(),
+            HashValue::zero(),
+            0,
+            timestamp,
+            vec![],
+            proposer,
+            vec![],
+        ),
+        log,
+    )
+}
+
+/// Runs the reconfiguration script as the association.
+fn eval_reconfiguration(executor: &mut FakeExecutor, log: &mut EvaluationLog) -> Result<Status> {
+    let association = Account::new_association();
+    let sequence_number = executor
+        .read_account_resource(&association)
+        .ok_or_else(|| ErrorKind::Other("association account not found".to_string()))?
+        .sequence_number();
+    match run_transaction(executor, reconfigure_txn(&association, sequence_number)) {
+        Ok(output) => {
+            log.append(EvaluationOutput::Output(OutputType::TransactionOutput(
+                Box::new(output),
+            )));
+            Ok(Status::Success)
+        }
+        Err(err) => {
+            log.append(EvaluationOutput::Error(Box::new(err)));
+            Ok(Status::Failure)
+        }
+    }
+}
+
+/// Starts a new block one microsecond after the current time, so that the network can be
+/// reconfigured, and reconfigures it.
+fn eval_new_epoch(executor: &mut FakeExecutor, log: &mut EvaluationLog) -> Result<Status> {
+    let timestamp = executor
+        .read_timestamp_resource()
+        .ok_or_else(|| ErrorKind::Other("block time not found".to_string()))?
+        .libra_timestamp
+        .microseconds;
+    match eval_block_time(executor, timestamp + 1, log)? {
+        Status::Success => eval_reconfiguration(executor, log),
+        Status::Failure => Ok(Status::Failure),
+    }
+}
+
 /// Feeds all given transactions through the pipeline and produces an EvaluationLog.
 pub fn eval<TComp: Compiler>(
     config: &GlobalConfig,
@@ -622,6 +701,24 @@ pub fn eval<TComp: Compiler>(
                 let status = eval_block_metadata(&mut exec, block_metadata.clone(), &mut log)?;
                 log.append(EvaluationOutput::Status(status));
             }
+            Command::BlockTime(timestamp) => {
+                log.append(EvaluationOutput::Transaction(idx));
+                log.append(EvaluationOutput::Stage(Stage::Runtime));
+                let status = eval_block_time(&mut exec, *timestamp, &mut log)?;
+                log.append(EvaluationOutput::Status(status));
+            }
+            Command::TriggerReconfiguration => {
+                log.append(EvaluationOutput::Transaction(idx));
+                log.append(EvaluationOutput::Stage(Stage::Runtime));
+                let status = eval_reconfiguration(&mut exec, &mut log)?;
+                log.append(EvaluationOutput::Status(status));
+            }
+            Command::NewEpoch => {
+                log.append(EvaluationOutput::Transaction(idx));
+                log.append(EvaluationOutput::Stage(Stage::Runtime));
+                let status = eval_new_epoch(&mut exec, &mut log)?;
+                log.append(EvaluationOutput::Status(status));
+            }
         }
     }
 
@@ -3,7 +3,7 @@
 
 use crate::{
     checker::Directive,
-    common::LineSp,
+    common::{strip, LineSp},
     config::{
         block_metadata::{build_block_metadata, is_new_block, Entry as BlockEntry},
         global::{Config as GlobalConfig, Entry as GlobalConfigEntry},
@@ -50,12 +50,18 @@ pub struct RawTransactionInput {
 pub enum RawCommand {
     Transaction(RawTransactionInput),
     BlockMetadata(Vec<BlockEntry>),
+    BlockTime(u64),
+    TriggerReconfiguration,
+    NewEpoch,
 }
 
 fn is_empty_command(cmd: &RawCommand) -> bool {
     match cmd {
         RawCommand::Transaction(txn) => txn.text.is_empty() && txn.config_entries.is_empty(),
         RawCommand::BlockMetadata(entries) => entries.is_empty(),
+        RawCommand::BlockTime(_) | RawCommand::TriggerReconfiguration | RawCommand::NewEpoch => {
+            false
+        }
     }
 }
 
@@ -85,6 +91,9 @@ fn check_raw_command(cmd: &RawCommand) -> Result<()> {
                 Ok(())
             }
         }
+        RawCommand::BlockTime(_) | RawCommand::TriggerReconfiguration | RawCommand::NewEpoch => {
+            Ok(())
+        }
     }
 }
 
@@ -101,6 +110,30 @@ fn new_command(input: &str) -> Option<RawCommand> {
     None
 }
 
+/// Parses the commands that take a single line and advance the chain between transactions:
+/// - `//! block-time: <microseconds>` runs a block prologue at that time, proposed by a validator.
+///   Within a block prologue, this sets the time of that block instead.
+/// - `//! trigger-reconfiguration` has the association reconfigure the network in a transaction.
+/// - `//! new-epoch` advances the block time and triggers a reconfiguration.
+fn new_single_line_command(input: &str, current: &RawCommand) -> Result<Option<RawCommand>> {
+    let input = input.split_whitespace().collect::<String>();
+    let input = match strip(&input, "//!") {
+        Some(input) => input,
+        None => return Ok(None),
+    };
+    if let Some(time) = strip(input, "block-time:") {
+        if let RawCommand::BlockMetadata(_) = current {
+            return Ok(None);
+        }
+        return Ok(Some(RawCommand::BlockTime(time.parse::<u64>()?)));
+    }
+    match input {
+        "trigger-reconfiguration" => Ok(Some(RawCommand::TriggerReconfiguration)),
+        "new-epoch" => Ok(Some(RawCommand::NewEpoch)),
+        _ => Ok(None),
+    }
+}
+
 pub fn extract_global_config(
     lines: impl IntoIterator<Item = impl AsRef<str>>,
 ) -> Result<GlobalConfig> {
@@ -125,7 +158,9 @@ pub fn split_input(
 ) -> Result<(Vec<LineSp<Directive>>, Vec<RawCommand>)> {
     let mut directives = vec![];
     let mut commands = vec![];
-    let mut first_transaction = true;
+    // Whether the current command was started implicitly, either at the beginning of the input or
+    // after a single-line command, in which case it is dropped if it stays empty
+    let mut implicit_command = true;
 
     let mut command = RawCommand::Transaction(RawTransactionInput {
         config_entries: vec![],
@@ -134,15 +169,28 @@ pub fn split_input(
 
     for (line_idx, line) in lines.into_iter().enumerate() {
         let line = line.as_ref();
+        if let Some(single_line_command) = new_single_line_command(line, &command)? {
+            if !(implicit_command && is_empty_command(&command)) {
+                check_raw_command(&command)?;
+                commands.push(command);
+            }
+            commands.push(single_line_command);
+            command = RawCommand::Transaction(RawTransactionInput {
+                config_entries: vec![],
+                text: vec![],
+            });
+            implicit_command = true;
+            continue;
+        }
         if let Some(new_command) = new_command(line) {
-            if first_transaction && is_empty_command(&command) {
+            if implicit_command && is_empty_command(&command) {
                 command = new_command;
                 continue;
             }
             check_raw_command(&command)?;
             commands.push(command);
             command = new_command;
-            first_transaction = false;
+            implicit_command = false;
             continue;
         }
 
@@ -174,11 +222,19 @@ pub fn split_input(
                     continue;
                 }
             }
+            RawCommand::BlockTime(_)
+            | RawCommand::TriggerReconfiguration
+            | RawCommand::NewEpoch => {
+                unreachable!("single-line commands are followed by a transaction")
+            }
         }
     }
 
-    check_raw_command(&command)?;
-    commands.push(command);
+    // An input without any command is rejected as an empty transaction
+    if !(implicit_command && is_empty_command(&command) && !commands.is_empty()) {
+        check_raw_command(&command)?;
+        commands.push(command);
+    }
 
     Ok((directives, commands))
 }
@@ -197,6 +253,9 @@ pub fn build_transactions<'a>(
             RawCommand::BlockMetadata(entries) => Ok(Command::BlockMetadata(build_block_metadata(
                 config, &entries,
             )?)),
+            RawCommand::BlockTime(time) => Ok(Command::BlockTime(*time)),
+            RawCommand::TriggerReconfiguration => Ok(Command::TriggerReconfiguration),
+            RawCommand::NewEpoch => Ok(Command::NewEpoch),
         })
         .collect()
 }
@@ -3,7 +3,7 @@
 
 use crate::{
     errors::*,
-    preprocessor::{build_transactions, extract_global_config, split_input},
+    preprocessor::{build_transactions, extract_global_config, split_input, RawCommand},
 };
 
 fn parse_input(input: &str) -> Result<()> {
@@ -68,3 +68,47 @@ fn parse_input_empty_transaction_with_config() {
         main() {}
     ").unwrap_err();
 }
+
+#[rustfmt::skip]
+#[test]
+fn parse_input_single_line_commands() {
+    let config = extract_global_config("".lines()).unwrap();
+    let (_, commands) = split_input(r"
+        //! block-time: 3
+        main() {}
+
+        //! new-epoch
+
+        //! new-transaction
+        main() {}
+        //! trigger-reconfiguration
+    ".lines(), &config).unwrap();
+    assert!(matches!(
+        commands.as_slice(),
+        [
+            RawCommand::BlockTime(3),
+            RawCommand::Transaction(_),
+            RawCommand::NewEpoch,
+            RawCommand::Transaction(_),
+            RawCommand::TriggerReconfiguration,
+        ]
+    ));
+}
+
+#[rustfmt::skip]
+#[test]
+fn parse_input_block_time_in_block_prologue() {
+    let config = extract_global_config("".lines()).unwrap();
+    let (_, commands) = split_input(r"
+        //! block-prologue
+        //! proposer-address: 0x1
+        //! block-time: 3
+    ".lines(), &config).unwrap();
+    assert!(matches!(commands.as_slice(), [RawCommand::BlockMetadata(entries)] if entries.len() == 2));
+}
+
+#[rustfmt::skip]
+#[test]
+fn parse_input_invalid_block_time() {
+    parse_input("//! block-time: soon").unwrap_err();
+}
@@ -85,6 +85,14 @@ pub static TXN_EPILOGUE_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static TXN_MIGRATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "libra_vm_txn_migration_seconds",
+        "Histogram of account migration time per transaction"
+    )
+    .unwrap()
+});
+
 pub static TXN_EXECUTION_GAS_USAGE: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         "libra_vm_txn_execution_gas_usage",
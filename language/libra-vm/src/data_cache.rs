@@ -6,6 +6,7 @@ use libra_logger::prelude::*;
 use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath,
+    chunked_resource,
     vm_error::{StatusCode, VMStatus},
     write_set::{WriteOp, WriteSet},
 };
@@ -41,8 +42,21 @@ impl<'a> StateViewCache<'a> {
         }
     }
 
-    // Get some data either through the cache or the `StateView` on a cache miss.
+    // Get some data either through the cache or the `StateView` on a cache miss. Resources stored
+    // as chunks are reassembled.
     pub(crate) fn get(&self, access_path: &AccessPath) -> VMResult<Option<Vec<u8>>> {
+        chunked_resource::read(access_path, |ap| self.get_stored(ap)).map_err(|e| {
+            crit!(
+                "[VM] Error reading chunked resource {:?}: {}",
+                access_path,
+                e
+            );
+            VMStatus::new(StatusCode::STORAGE_ERROR)
+        })
+    }
+
+    // Get an entry as it is stored, i.e., without reassembling chunked resources.
+    fn get_stored(&self, access_path: &AccessPath) -> VMResult<Option<Vec<u8>>> {
         match self.data_map.get(access_path) {
             Some(opt_data) => Ok(opt_data.clone()),
             None => match self.data_view.get(&access_path) {
@@ -56,6 +70,20 @@ impl<'a> StateViewCache<'a> {
         }
     }
 
+    // Rewrites the resource writes of a `WriteSet` computed by the Move VM into their storage
+    // layout, splitting the resources larger than `chunk_size` into chunks.
+    pub(crate) fn chunk_write_set(
+        &self,
+        write_set: WriteSet,
+        chunk_size: u64,
+    ) -> VMResult<WriteSet> {
+        chunked_resource::chunk_write_set(write_set, chunk_size as usize, |ap| self.get_stored(ap))
+            .map_err(|e| {
+                crit!("[VM] Error chunking write set: {}", e);
+                VMStatus::new(StatusCode::STORAGE_ERROR)
+            })
+    }
+
     // Publishes a `WriteSet` computed at the end of a transaction.
     // The effect is to build a layer in front of the `StateView` which keeps
     // track of the data as if the changes were applied immediately.
@@ -91,8 +119,11 @@ impl<'a> RemoteStorage<'a> {
 
 impl<'a> RemoteCache for RemoteStorage<'a> {
     fn get(&self, access_path: &AccessPath) -> VMResult<Option<Vec<u8>>> {
-        self.0
-            .get(access_path)
-            .map_err(|_| VMStatus::new(StatusCode::STORAGE_ERROR))
+        chunked_resource::read(access_path, |ap| {
+            self.0
+                .get(ap)
+                .map_err(|_| VMStatus::new(StatusCode::STORAGE_ERROR))
+        })
+        .map_err(|_| VMStatus::new(StatusCode::STORAGE_ERROR))
     }
 }
@@ -16,8 +16,9 @@ use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
     account_config::{self, RoleId},
+    block_congestion::BlockCongestionResource,
     block_metadata::BlockMetadata,
-    on_chain_config::{LibraVersion, OnChainConfig, VMConfig},
+    on_chain_config::{LibraVersion, ModulePublishingPolicy, OnChainConfig, VMConfig},
     transaction::{
         ChangeSet, Module, Script, SignatureCheckedTransaction, SignedTransaction, Transaction,
         TransactionArgument, TransactionOutput, TransactionPayload, TransactionStatus,
@@ -27,34 +28,44 @@ use libra_types::{
     write_set::{WriteSet, WriteSetMut},
 };
 use move_core_types::{
-    gas_schedule::{AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasUnits},
+    gas_schedule::{AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasConstants, GasUnits},
     identifier::IdentStr,
     language_storage::{ResourceKey, StructTag, TypeTag},
     move_resource::MoveResource,
 };
 
 use move_vm_runtime::{
-    data_cache::{RemoteCache, TransactionDataCache},
+    data_cache::{RemoteCache, TransactionDataCache, TransactionLimits},
     move_vm::MoveVM,
 };
 use move_vm_types::{
+    data_store::DataStore,
     gas_schedule::{calculate_intrinsic_gas, zero_cost_schedule, CostStrategy},
     values::Value,
 };
 use rayon::prelude::*;
 use std::{collections::HashSet, convert::TryFrom, sync::Arc};
-use vm::errors::{convert_prologue_runtime_error, VMResult};
+use vm::{
+    errors::{convert_prologue_runtime_error, VMResult},
+    file_format::CompiledModule,
+};
 
 /// Any transation sent from an account with a role id below this cutoff will be priorited over
 /// other transactions.
 const PRIORITIZED_TRANSACTION_ROLE_CUTOFF: u64 = 5;
 
+/// Number of transactions whose signatures are batch verified together. Batches are verified in
+/// parallel, so this trades the speedup of batching off against parallelism on small blocks.
+const SIGNATURE_VERIFICATION_BATCH_SIZE: usize = 32;
+
 #[derive(Clone)]
 /// A wrapper to make VMRuntime standalone and thread safe.
 pub struct LibraVM {
     move_vm: Arc<MoveVM>,
     on_chain_config: Option<VMConfig>,
     version: Option<LibraVersion>,
+    // Without a policy, only the holders of the module publishing privilege can publish modules
+    module_publishing_policy: Option<ModulePublishingPolicy>,
 }
 
 impl LibraVM {
@@ -65,15 +76,21 @@ impl LibraVM {
             move_vm: Arc::new(inner),
             on_chain_config: None,
             version: None,
+            module_publishing_policy: None,
         }
     }
 
-    pub fn init_with_config(version: LibraVersion, on_chain_config: VMConfig) -> Self {
+    pub fn init_with_config(
+        version: LibraVersion,
+        on_chain_config: VMConfig,
+        module_publishing_policy: Option<ModulePublishingPolicy>,
+    ) -> Self {
         let inner = MoveVM::new();
         Self {
             move_vm: Arc::new(inner),
             on_chain_config: Some(on_chain_config),
             version: Some(version),
+            module_publishing_policy,
         }
     }
 
@@ -95,6 +112,7 @@ impl LibraVM {
     fn load_configs_impl(&mut self, data_cache: &dyn RemoteCache) {
         self.on_chain_config = VMConfig::fetch_config(data_cache);
         self.version = LibraVersion::fetch_config(data_cache);
+        self.module_publishing_policy = ModulePublishingPolicy::fetch_config(data_cache);
     }
 
     pub fn get_gas_schedule(&self) -> VMResult<&CostTable> {
@@ -114,7 +132,28 @@ impl LibraVM {
         })
     }
 
-    fn check_gas(&self, txn: &SignedTransaction) -> VMResult<()> {
+    /// Returns the address under which `sender` publishes modules, or None if it cannot publish
+    /// modules. The accounts allowed by the module publishing policy publish under their own
+    /// address, and the holders of the module publishing privilege under the core code address.
+    fn module_publishing_address(
+        &self,
+        sender: AccountAddress,
+        remote_cache: &dyn RemoteCache,
+    ) -> Option<AccountAddress> {
+        let is_allowed_publisher = self
+            .module_publishing_policy
+            .as_ref()
+            .map_or(false, |policy| policy.is_allowed_publisher(&sender));
+        if is_allowed_publisher {
+            Some(sender)
+        } else if can_publish_modules(sender, remote_cache) {
+            Some(account_config::CORE_CODE_ADDRESS)
+        } else {
+            None
+        }
+    }
+
+    fn check_gas(&self, txn: &SignedTransaction, remote_cache: &dyn RemoteCache) -> VMResult<()> {
         let gas_constants = &self.get_gas_schedule()?.gas_constants;
         let raw_bytes_len = AbstractMemorySize::new(txn.raw_txn_bytes_len() as GasCarrier);
         // The transaction is too large.
@@ -179,20 +218,18 @@ impl LibraVM {
             );
         }
 
-        // The submitted gas price is less than the minimum gas unit price set by the VM.
-        // NB: MIN_PRICE_PER_GAS_UNIT may equal zero, but need not in the future. Hence why
-        // we turn off the clippy warning.
-        #[allow(clippy::absurd_extreme_comparisons)]
-        let below_min_bound = txn.gas_unit_price() < gas_constants.min_price_per_gas_unit.get();
-        if below_min_bound {
+        // The submitted gas price is less than the minimum gas unit price set by the VM, which is
+        // raised above the one of the gas schedule when the recent blocks were congested.
+        let min_gas_unit_price = min_gas_unit_price(gas_constants, remote_cache)?;
+        if txn.gas_unit_price() < min_gas_unit_price {
             let error_str = format!(
                 "gas unit min price: {}, submitted price: {}",
-                gas_constants.min_price_per_gas_unit.get(),
+                min_gas_unit_price,
                 txn.gas_unit_price()
             );
             warn!(
                 "[VM] Gas unit error; min {}, submitted {}",
-                gas_constants.min_price_per_gas_unit.get(),
+                min_gas_unit_price,
                 txn.gas_unit_price()
             );
             return Err(
@@ -249,6 +286,48 @@ impl LibraVM {
         ))
     }
 
+    fn verify_batch(
+        &self,
+        remote_cache: &dyn RemoteCache,
+        scripts: &[Script],
+        txn_data: TransactionMetadata,
+        account_currency_symbol: &IdentStr,
+    ) -> VMResult<VerifiedTransactionPayload> {
+        let max_scripts = self.get_gas_schedule()?.gas_constants.max_scripts_per_batch;
+        if scripts.is_empty() || scripts.len() as u64 > max_scripts {
+            warn!("[VM] Invalid batch size: {}", scripts.len());
+            return Err(VMStatus::new(StatusCode::INVALID_BATCH_SIZE));
+        }
+        let publishing_option = &self.on_chain_config()?.publishing_option;
+        if let Some(script) = scripts
+            .iter()
+            .find(|script| !publishing_option.is_allowed_script(&script.code()))
+        {
+            warn!("[VM] Custom scripts not allowed: {:?}", &script.code());
+            return Err(VMStatus::new(StatusCode::UNKNOWN_SCRIPT));
+        }
+        let mut cost_strategy = CostStrategy::system(self.get_gas_schedule()?, GasUnits::new(0));
+        let mut data_store = TransactionDataCache::new(remote_cache);
+        self.run_prologue(
+            &mut data_store,
+            &mut cost_strategy,
+            &txn_data,
+            account_currency_symbol,
+        )?;
+        Ok(VerifiedTransactionPayload::Batch(
+            scripts
+                .iter()
+                .map(|script| {
+                    (
+                        script.code().to_vec(),
+                        script.ty_args().to_vec(),
+                        convert_txn_args(script.args()),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
     fn verify_module(
         &self,
         remote_cache: &dyn RemoteCache,
@@ -258,10 +337,14 @@ impl LibraVM {
     ) -> VMResult<VerifiedTransactionPayload> {
         let mut cost_strategy = CostStrategy::system(self.get_gas_schedule()?, GasUnits::new(0));
         let mut data_store = TransactionDataCache::new(remote_cache);
-        if !&self.on_chain_config()?.publishing_option.is_open()
-            && !can_publish_modules(txn_data.sender(), remote_cache)
+        if self
+            .module_publishing_address(txn_data.sender(), remote_cache)
+            .is_none()
         {
-            warn!("[VM] Custom modules not allowed");
+            warn!(
+                "[VM] Module publishing not allowed for {}",
+                txn_data.sender()
+            );
             return Err(VMStatus::new(StatusCode::INVALID_MODULE_PUBLISHER));
         };
         self.run_prologue(
@@ -270,7 +353,10 @@ impl LibraVM {
             &txn_data,
             account_currency_symbol,
         )?;
-        Ok(VerifiedTransactionPayload::Module(module.code().to_vec()))
+        Ok(VerifiedTransactionPayload::Module(
+            module.code().to_vec(),
+            module.source_map().map(<[u8]>::to_vec),
+        ))
     }
 
     fn verify_writeset(
@@ -279,6 +365,11 @@ impl LibraVM {
         _change_set: &ChangeSet,
         txn_data: &TransactionMetadata,
     ) -> VMResult<()> {
+        // Writeset transactions are not charged for gas, so there is nothing to sponsor
+        if txn_data.fee_payer().is_some() {
+            warn!("[VM] Writeset transactions cannot be sponsored");
+            return Err(VMStatus::new(StatusCode::INVALID_FEE_PAYER));
+        }
         let mut data_store = TransactionDataCache::new(remote_cache);
         self.run_writeset_prologue(&mut data_store, &txn_data)?;
         Ok(())
@@ -290,7 +381,7 @@ impl LibraVM {
         remote_cache: &dyn RemoteCache,
         account_currency_symbol: &IdentStr,
     ) -> VMResult<VerifiedTransactionPayload> {
-        self.check_gas(transaction)?;
+        self.check_gas(transaction, remote_cache)?;
         let txn_data = TransactionMetadata::new(transaction);
         match transaction.payload() {
             TransactionPayload::Script(script) => {
@@ -299,6 +390,9 @@ impl LibraVM {
             TransactionPayload::Module(module) => {
                 self.verify_module(remote_cache, module, txn_data, account_currency_symbol)
             }
+            TransactionPayload::Batch(scripts) => {
+                self.verify_batch(remote_cache, scripts, txn_data, account_currency_symbol)
+            }
             TransactionPayload::WriteSet(_) => Err(VMStatus::new(StatusCode::UNREACHABLE)),
         }
     }
@@ -312,15 +406,20 @@ impl LibraVM {
         let txn_data = TransactionMetadata::new(transaction);
         match transaction.payload() {
             TransactionPayload::Script(script) => {
-                self.check_gas(transaction)?;
+                self.check_gas(transaction, remote_cache)?;
                 self.verify_script(remote_cache, script, txn_data, account_currency_symbol)?;
                 Ok(())
             }
             TransactionPayload::Module(module) => {
-                self.check_gas(transaction)?;
+                self.check_gas(transaction, remote_cache)?;
                 self.verify_module(remote_cache, module, txn_data, account_currency_symbol)?;
                 Ok(())
             }
+            TransactionPayload::Batch(scripts) => {
+                self.check_gas(transaction, remote_cache)?;
+                self.verify_batch(remote_cache, scripts, txn_data, account_currency_symbol)?;
+                Ok(())
+            }
             TransactionPayload::WriteSet(cs) => self.verify_writeset(remote_cache, cs, &txn_data),
         }
     }
@@ -337,29 +436,51 @@ impl LibraVM {
             Err(e) => return discard_error_output(e),
         };
         let mut cost_strategy = CostStrategy::transaction(gas_schedule, txn_data.max_gas_amount());
-        let mut data_store = TransactionDataCache::new(remote_cache);
+        let mut data_store = TransactionDataCache::with_limits(
+            remote_cache,
+            TransactionLimits::new(&gas_schedule.gas_constants),
+        );
+        if let Err(err) = self.run_account_migration(&mut data_store, gas_schedule, txn_data) {
+            return self.failed_transaction_cleanup(
+                err,
+                gas_schedule,
+                cost_strategy.remaining_gas(),
+                txn_data,
+                remote_cache,
+                account_currency_symbol,
+            );
+        }
         // TODO: The logic for handling falied transaction fee is pretty ugly right now. Fix it later.
         let mut failed_gas_left = GasUnits::new(0);
         match payload {
-            VerifiedTransactionPayload::Module(m) => cost_strategy
+            VerifiedTransactionPayload::Module(m, source_map) => cost_strategy
                 .charge_intrinsic_gas(txn_data.transaction_size())
                 .and_then(|_| {
-                    // Module publishing is currently restricted to the Association, so we choose to
-                    // publish all modules under the address 0x0...1 for convenience.
-                    // This will change to the sender's address once module publishing becomes open.
-                    // REVIEW: should we check that the address of the Module is in fact
-                    // `CORE_CODE_ADDRESS`?
-                    let module_address = if self.on_chain_config()?.publishing_option.is_open() {
-                        txn_data.sender()
-                    } else {
-                        account_config::CORE_CODE_ADDRESS
+                    // The policy is checked again, as it may have changed since the transaction was
+                    // validated.
+                    // REVIEW: should we check that the address of the Module is the address it is
+                    // published under?
+                    let module_address = self
+                        .module_publishing_address(txn_data.sender(), remote_cache)
+                        .ok_or_else(|| VMStatus::new(StatusCode::INVALID_MODULE_PUBLISHER))?;
+                    // The source map is charged for as part of the transaction size, and is
+                    // stored as is under the path mirroring the code of the module.
+                    let source_map = match source_map {
+                        Some(source_map) => {
+                            Some((CompiledModule::deserialize(&m)?.self_id(), source_map))
+                        }
+                        None => None,
                     };
                     self.move_vm.publish_module(
                         m,
                         module_address,
                         &mut data_store,
                         &mut cost_strategy,
-                    )
+                    )?;
+                    if let Some((module_id, source_map)) = source_map {
+                        data_store.publish_source_map(module_id, source_map)?;
+                    }
+                    Ok(())
                 }),
             VerifiedTransactionPayload::Script(s, ty_args, args) => {
                 let ret = cost_strategy
@@ -381,6 +502,32 @@ impl LibraVM {
                 TXN_EXECUTION_GAS_USAGE.observe(gas_usage as f64);
                 ret
             }
+            // The scripts share the data store, so that each one sees the effects of the previous
+            // ones, and the gas budget of the transaction. If any of them fails, the data store is
+            // dropped and the failure is handled as for a single script.
+            VerifiedTransactionPayload::Batch(scripts) => {
+                let ret = cost_strategy
+                    .charge_intrinsic_gas(txn_data.transaction_size())
+                    .and_then(|_| {
+                        for (s, ty_args, args) in scripts {
+                            self.move_vm.execute_script(
+                                s,
+                                ty_args,
+                                args,
+                                txn_data.sender(),
+                                &mut data_store,
+                                &mut cost_strategy,
+                            )?;
+                        }
+                        Ok(())
+                    });
+                let gas_usage = txn_data
+                    .max_gas_amount()
+                    .sub(cost_strategy.remaining_gas())
+                    .get();
+                TXN_EXECUTION_GAS_USAGE.observe(gas_usage as f64);
+                ret
+            }
         }
         .map_err(|err| {
             failed_gas_left = cost_strategy.remaining_gas();
@@ -398,6 +545,7 @@ impl LibraVM {
             .and_then(|_| {
                 get_transaction_output(
                     &mut data_store,
+                    remote_cache,
                     &cost_strategy,
                     txn_data,
                     VMStatus::new(StatusCode::EXECUTED),
@@ -429,6 +577,13 @@ impl LibraVM {
     ) -> TransactionOutput {
         let mut cost_strategy = CostStrategy::system(gas_schedule, gas_left);
         let mut data_store = TransactionDataCache::new(remote_cache);
+        if self
+            .run_account_migration(&mut data_store, gas_schedule, txn_data)
+            .is_err()
+        {
+            // The sender is migrated by its next transaction
+            data_store = TransactionDataCache::new(remote_cache);
+        }
         match TransactionStatus::from(error_code) {
             TransactionStatus::Keep(status) => self
                 .run_failure_epilogue(
@@ -438,7 +593,13 @@ impl LibraVM {
                     account_currency_symbol,
                 )
                 .and_then(|_| {
-                    get_transaction_output(&mut data_store, &cost_strategy, txn_data, status)
+                    get_transaction_output(
+                        &mut data_store,
+                        remote_cache,
+                        &cost_strategy,
+                        txn_data,
+                        status,
+                    )
                 })
                 .unwrap_or_else(discard_error_output),
             TransactionStatus::Discard(status) => discard_error_output(status),
@@ -529,13 +690,17 @@ impl LibraVM {
         cost_strategy.charge_intrinsic_gas(txn_data.transaction_size())?;
         let mut data_store = TransactionDataCache::new(remote_cache);
 
-        if let Ok((round, timestamp, previous_vote, proposer)) = block_metadata.into_inner() {
+        if let Ok((round, timestamp, previous_vote, proposer, parent_id, failed_proposers)) =
+            block_metadata.into_inner()
+        {
             let args = vec![
                 Value::transaction_argument_signer_reference(txn_data.sender),
                 Value::u64(round),
                 Value::u64(timestamp),
                 Value::vector_address(previous_vote),
                 Value::address(proposer),
+                Value::vector_u8(parent_id.to_vec()),
+                Value::vector_address(failed_proposers),
             ];
             self.move_vm.execute_function(
                 &LIBRA_BLOCK_MODULE,
@@ -552,6 +717,7 @@ impl LibraVM {
 
         get_transaction_output(
             &mut data_store,
+            remote_cache,
             &cost_strategy,
             &txn_data,
             VMStatus::new(StatusCode::EXECUTED),
@@ -676,7 +842,8 @@ impl LibraVM {
     }
 
     /// Run the prologue of a transaction by calling into `PROLOGUE_NAME` function stored
-    /// in the `ACCOUNT_MODULE` on chain.
+    /// in the `ACCOUNT_MODULE` on chain, or `SPONSORED_PROLOGUE_NAME` if the transaction is
+    /// sponsored.
     fn run_prologue(
         &self,
         data_store: &mut TransactionDataCache,
@@ -691,12 +858,9 @@ impl LibraVM {
         let txn_gas_price = txn_data.gas_unit_price().get();
         let txn_max_gas_units = txn_data.max_gas_amount().get();
         let txn_expiration_time = txn_data.expiration_time();
-        let _timer = TXN_PROLOGUE_SECONDS.start_timer();
-        self.move_vm
-            .execute_function(
-                &account_config::ACCOUNT_MODULE,
+        let (prologue_name, args) = match txn_data.fee_payer() {
+            None => (
                 &PROLOGUE_NAME,
-                vec![gas_currency_ty],
                 vec![
                     Value::transaction_argument_signer_reference(txn_data.sender),
                     Value::u64(txn_sequence_number),
@@ -705,6 +869,28 @@ impl LibraVM {
                     Value::u64(txn_max_gas_units),
                     Value::u64(txn_expiration_time),
                 ],
+            ),
+            Some(fee_payer) => (
+                &SPONSORED_PROLOGUE_NAME,
+                vec![
+                    Value::transaction_argument_signer_reference(txn_data.sender),
+                    Value::u64(txn_sequence_number),
+                    Value::vector_u8(txn_public_key),
+                    Value::address(fee_payer),
+                    Value::vector_u8(txn_data.fee_payer_authentication_key_preimage().to_vec()),
+                    Value::u64(txn_gas_price),
+                    Value::u64(txn_max_gas_units),
+                    Value::u64(txn_expiration_time),
+                ],
+            ),
+        };
+        let _timer = TXN_PROLOGUE_SECONDS.start_timer();
+        self.move_vm
+            .execute_function(
+                &account_config::ACCOUNT_MODULE,
+                prologue_name,
+                vec![gas_currency_ty],
+                args,
                 txn_data.sender,
                 data_store,
                 cost_strategy,
@@ -712,8 +898,37 @@ impl LibraVM {
             .map_err(|err| convert_prologue_runtime_error(&err, &txn_data.sender))
     }
 
-    /// Run the epilogue of a transaction by calling into `EPILOGUE_NAME` function stored
-    /// in the `ACCOUNT_MODULE` on chain.
+    /// Migrate the resources of the sender of a transaction to their latest layout by calling
+    /// into `MIGRATE_NAME` function stored in the `ACCOUNT_MIGRATION_MODULE` on chain, before
+    /// running the transaction. Migrations are not charged to the sender, and there are none to
+    /// run on chains whose framework predates the versioning of account resources.
+    fn run_account_migration(
+        &self,
+        data_store: &mut TransactionDataCache,
+        gas_schedule: &CostTable,
+        txn_data: &TransactionMetadata,
+    ) -> VMResult<()> {
+        if !data_store.exists_module(&ACCOUNT_MIGRATION_MODULE) {
+            return Ok(());
+        }
+        let mut cost_strategy = CostStrategy::system(gas_schedule, txn_data.max_gas_amount());
+        let _timer = TXN_MIGRATION_SECONDS.start_timer();
+        self.move_vm.execute_function(
+            &ACCOUNT_MIGRATION_MODULE,
+            &MIGRATE_NAME,
+            vec![],
+            vec![Value::transaction_argument_signer_reference(
+                txn_data.sender,
+            )],
+            txn_data.sender,
+            data_store,
+            &mut cost_strategy,
+        )
+    }
+
+    /// Run the epilogue of a transaction by calling into `SUCCESS_EPILOGUE_NAME` function stored
+    /// in the `ACCOUNT_MODULE` on chain, or `SPONSORED_SUCCESS_EPILOGUE_NAME` if the transaction is
+    /// sponsored.
     fn run_success_epilogue(
         &self,
         data_store: &mut TransactionDataCache,
@@ -727,18 +942,33 @@ impl LibraVM {
         let txn_gas_price = txn_data.gas_unit_price().get();
         let txn_max_gas_units = txn_data.max_gas_amount().get();
         let gas_remaining = cost_strategy.remaining_gas().get();
+        let (epilogue_name, mut args) = match txn_data.fee_payer() {
+            None => (
+                &SUCCESS_EPILOGUE_NAME,
+                vec![Value::transaction_argument_signer_reference(
+                    txn_data.sender,
+                )],
+            ),
+            Some(fee_payer) => (
+                &SPONSORED_SUCCESS_EPILOGUE_NAME,
+                vec![
+                    Value::transaction_argument_signer_reference(txn_data.sender),
+                    Value::address(fee_payer),
+                ],
+            ),
+        };
+        args.extend(vec![
+            Value::u64(txn_sequence_number),
+            Value::u64(txn_gas_price),
+            Value::u64(txn_max_gas_units),
+            Value::u64(gas_remaining),
+        ]);
         let _timer = TXN_EPILOGUE_SECONDS.start_timer();
         self.move_vm.execute_function(
             &account_config::ACCOUNT_MODULE,
-            &SUCCESS_EPILOGUE_NAME,
+            epilogue_name,
             vec![gas_currency_ty],
-            vec![
-                Value::transaction_argument_signer_reference(txn_data.sender),
-                Value::u64(txn_sequence_number),
-                Value::u64(txn_gas_price),
-                Value::u64(txn_max_gas_units),
-                Value::u64(gas_remaining),
-            ],
+            args,
             txn_data.sender,
             data_store,
             cost_strategy,
@@ -746,7 +976,8 @@ impl LibraVM {
     }
 
     /// Run the failure epilogue of a transaction by calling into `FAILURE_EPILOGUE_NAME` function
-    /// stored in the `ACCOUNT_MODULE` on chain.
+    /// stored in the `ACCOUNT_MODULE` on chain, or `SPONSORED_FAILURE_EPILOGUE_NAME` if the
+    /// transaction is sponsored.
     fn run_failure_epilogue(
         &self,
         data_store: &mut TransactionDataCache,
@@ -760,18 +991,33 @@ impl LibraVM {
         let txn_gas_price = txn_data.gas_unit_price().get();
         let txn_max_gas_units = txn_data.max_gas_amount().get();
         let gas_remaining = cost_strategy.remaining_gas().get();
+        let (epilogue_name, mut args) = match txn_data.fee_payer() {
+            None => (
+                &FAILURE_EPILOGUE_NAME,
+                vec![Value::transaction_argument_signer_reference(
+                    txn_data.sender,
+                )],
+            ),
+            Some(fee_payer) => (
+                &SPONSORED_FAILURE_EPILOGUE_NAME,
+                vec![
+                    Value::transaction_argument_signer_reference(txn_data.sender),
+                    Value::address(fee_payer),
+                ],
+            ),
+        };
+        args.extend(vec![
+            Value::u64(txn_sequence_number),
+            Value::u64(txn_gas_price),
+            Value::u64(txn_max_gas_units),
+            Value::u64(gas_remaining),
+        ]);
         let _timer = TXN_EPILOGUE_SECONDS.start_timer();
         self.move_vm.execute_function(
             &account_config::ACCOUNT_MODULE,
-            &FAILURE_EPILOGUE_NAME,
+            epilogue_name,
             vec![gas_currency_ty],
-            vec![
-                Value::transaction_argument_signer_reference(txn_data.sender),
-                Value::u64(txn_sequence_number),
-                Value::u64(txn_gas_price),
-                Value::u64(txn_max_gas_units),
-                Value::u64(gas_remaining),
-            ],
+            args,
             txn_data.sender,
             data_store,
             cost_strategy,
@@ -895,10 +1141,9 @@ impl LibraVM {
             trace_code_block!("libra_vm::verify_signatures", {"block", block_id});
             signature_verified_block = txn_block
                 .into_par_iter()
-                .map(|txn| {
-                    txn.check_signature()
-                        .map_err(|_| VMStatus::new(StatusCode::INVALID_SIGNATURE))
-                })
+                .chunks(SIGNATURE_VERIFICATION_BATCH_SIZE)
+                .flat_map(SignedTransaction::check_signatures)
+                .map(|result| result.map_err(|_| VMStatus::new(StatusCode::INVALID_SIGNATURE)))
                 .collect();
         }
         let mut result = vec![];
@@ -1094,6 +1339,20 @@ fn can_publish_modules(sender: AccountAddress, remote_cache: &dyn RemoteCache) -
     }
 }
 
+/// The minimum gas unit price, adjusted for the congestion of the recent blocks once it is
+/// recorded on-chain.
+fn min_gas_unit_price(
+    gas_constants: &GasConstants,
+    remote_cache: &dyn RemoteCache,
+) -> VMResult<u64> {
+    match remote_cache.get(&BlockCongestionResource::access_path())? {
+        Some(blob) => lcs::from_bytes::<BlockCongestionResource>(&blob)
+            .map(|congestion| congestion.min_gas_unit_price(gas_constants))
+            .map_err(|_| VMStatus::new(StatusCode::VALUE_DESERIALIZATION_ERROR)),
+        None => Ok(gas_constants.min_price_per_gas_unit.get()),
+    }
+}
+
 fn normalize_gas_price(
     gas_price: u64,
     currency_code: &IdentStr,
@@ -1160,7 +1419,8 @@ pub fn chunk_block_transactions(txns: Vec<Transaction>) -> Vec<TransactionBlock>
 
 enum VerifiedTransactionPayload {
     Script(Vec<u8>, Vec<TypeTag>, Vec<Value>),
-    Module(Vec<u8>),
+    Module(Vec<u8>, Option<Vec<u8>>),
+    Batch(Vec<(Vec<u8>, Vec<TypeTag>, Vec<Value>)>),
 }
 
 /// Convert the transaction arguments into move values.
@@ -1185,6 +1445,7 @@ fn create_access_path(address: AccountAddress, tag: StructTag) -> AccessPath {
 
 fn get_transaction_output(
     data_store: &mut TransactionDataCache,
+    remote_cache: &StateViewCache<'_>,
     cost_strategy: &CostStrategy,
     txn_data: &TransactionMetadata,
     status: VMStatus,
@@ -1193,7 +1454,13 @@ fn get_transaction_output(
         .max_gas_amount()
         .sub(cost_strategy.remaining_gas())
         .get();
-    let write_set = data_store.make_write_set()?;
+    let write_set = remote_cache.chunk_write_set(
+        data_store.make_write_set()?,
+        cost_strategy
+            .cost_table()
+            .gas_constants
+            .resource_chunk_size_in_bytes,
+    )?;
     TXN_TOTAL_GAS_USAGE.observe(gas_used as f64);
     Ok(TransactionOutput::new(
         write_set,
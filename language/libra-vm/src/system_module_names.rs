@@ -59,6 +59,13 @@ pub static LIBRA_CONFIG_MODULE: Lazy<ModuleId> = Lazy::new(|| {
         Identifier::new("LibraConfig").unwrap(),
     )
 });
+/// The ModuleId for the account migration module
+pub static ACCOUNT_MIGRATION_MODULE: Lazy<ModuleId> = Lazy::new(|| {
+    ModuleId::new(
+        account_config::CORE_CODE_ADDRESS,
+        Identifier::new("AccountMigration").unwrap(),
+    )
+});
 /// The ModuleId for the gas schedule module
 pub static GAS_SCHEDULE_MODULE: Lazy<ModuleId> = Lazy::new(|| {
     ModuleId::new(
@@ -77,8 +84,15 @@ pub static SUCCESS_EPILOGUE_NAME: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("success_epilogue").unwrap());
 pub static FAILURE_EPILOGUE_NAME: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("failure_epilogue").unwrap());
+pub static SPONSORED_PROLOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("sponsored_prologue").unwrap());
+pub static SPONSORED_SUCCESS_EPILOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("sponsored_success_epilogue").unwrap());
+pub static SPONSORED_FAILURE_EPILOGUE_NAME: Lazy<Identifier> =
+    Lazy::new(|| Identifier::new("sponsored_failure_epilogue").unwrap());
 pub static BUMP_SEQUENCE_NUMBER_NAME: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("bump_sequence_number").unwrap());
+pub static MIGRATE_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new("migrate").unwrap());
 pub static BLOCK_PROLOGUE: Lazy<Identifier> =
     Lazy::new(|| Identifier::new("block_prologue").unwrap());
 pub static DISTRIBUTE_TXN_FEES: Lazy<Identifier> =
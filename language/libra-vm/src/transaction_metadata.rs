@@ -14,6 +14,8 @@ use std::{convert::TryFrom, time::Duration};
 pub struct TransactionMetadata {
     pub sender: AccountAddress,
     pub authentication_key_preimage: Vec<u8>,
+    pub fee_payer: Option<AccountAddress>,
+    pub fee_payer_authentication_key_preimage: Vec<u8>,
     pub sequence_number: u64,
     pub max_gas_amount: GasUnits<GasCarrier>,
     pub gas_unit_price: GasPrice<GasCarrier>,
@@ -23,12 +25,15 @@ pub struct TransactionMetadata {
 
 impl TransactionMetadata {
     pub fn new(txn: &SignedTransaction) -> Self {
+        let authenticator = txn.authenticator();
         Self {
             sender: txn.sender(),
-            authentication_key_preimage: txn
-                .authenticator()
-                .authentication_key_preimage()
-                .into_vec(),
+            authentication_key_preimage: authenticator.authentication_key_preimage().into_vec(),
+            fee_payer: authenticator.fee_payer_address(),
+            fee_payer_authentication_key_preimage: authenticator
+                .fee_payer_authenticator()
+                .map(|fee_payer| fee_payer.authentication_key_preimage().into_vec())
+                .unwrap_or_default(),
             sequence_number: txn.sequence_number(),
             max_gas_amount: GasUnits::new(txn.max_gas_amount()),
             gas_unit_price: GasPrice::new(txn.gas_unit_price()),
@@ -53,6 +58,15 @@ impl TransactionMetadata {
         &self.authentication_key_preimage
     }
 
+    /// The account paying for the gas of a sponsored transaction, `None` if the sender pays
+    pub fn fee_payer(&self) -> Option<AccountAddress> {
+        self.fee_payer
+    }
+
+    pub fn fee_payer_authentication_key_preimage(&self) -> &[u8] {
+        &self.fee_payer_authentication_key_preimage
+    }
+
     pub fn sequence_number(&self) -> u64 {
         self.sequence_number
     }
@@ -74,6 +88,8 @@ impl Default for TransactionMetadata {
         TransactionMetadata {
             sender: AccountAddress::ZERO,
             authentication_key_preimage: AuthenticationKeyPreimage::ed25519(&public_key).into_vec(),
+            fee_payer: None,
+            fee_payer_authentication_key_preimage: vec![],
             sequence_number: 0,
             max_gas_amount: GasUnits::new(100_000_000),
             gas_unit_price: GasPrice::new(0),
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{ensure, Error, Result};
+use bech32::{FromBase32, ToBase32};
 use libra_crypto::{
     hash::{CryptoHash, CryptoHasher},
     x25519, HashValue,
@@ -15,6 +16,9 @@ use std::{convert::TryFrom, fmt, str::FromStr};
 
 const SHORT_STRING_LENGTH: usize = 4;
 
+/// The human-readable part of the bech32 encoding of addresses.
+pub const BECH32_HRP: &str = "lbr";
+
 /// A struct that represents an account address.
 #[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy, CryptoHasher)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
@@ -72,6 +76,42 @@ impl AccountAddress {
         AccountAddress::try_from(padded_result)
     }
 
+    /// Encodes the address in bech32, with the `BECH32_HRP` prefix and a checksum which catches
+    /// copy errors, e.g. `lbr1e2zry70rgfc5fn4dtex4nxdr6qryuc9z`.
+    pub fn to_bech32(&self) -> String {
+        bech32::encode(BECH32_HRP, self.0.to_base32()).expect("BECH32_HRP must be valid")
+    }
+
+    /// Parses an address encoded in bech32, checking its prefix and checksum.
+    pub fn from_bech32(s: &str) -> Result<Self> {
+        let (hrp, data) = bech32::decode(s)?;
+        ensure!(
+            hrp == BECH32_HRP,
+            "Address {} should start with {}1",
+            s,
+            BECH32_HRP
+        );
+        AccountAddress::try_from(Vec::<u8>::from_base32(&data)?)
+    }
+
+    /// Whether the string looks like a bech32 address rather than a hex one: the bech32 prefix
+    /// cannot be mistaken for hex digits.
+    pub fn is_bech32(s: &str) -> bool {
+        s.get(..BECH32_HRP.len())
+            .map_or(false, |hrp| hrp.eq_ignore_ascii_case(BECH32_HRP))
+            && s[BECH32_HRP.len()..].starts_with('1')
+    }
+
+    /// Parses an address either encoded in bech32 or as hex without the `0x` prefix.
+    fn from_bech32_or_hex(s: &str) -> Result<Self> {
+        if Self::is_bech32(s) {
+            Self::from_bech32(s)
+        } else {
+            let bytes_out = ::hex::decode(s)?;
+            AccountAddress::try_from(bytes_out.as_slice())
+        }
+    }
+
     // Note: This is inconsistent with current types because AccountAddress is derived
     // from consensus key which is of type Ed25519PublicKey. Since AccountAddress does
     // not mean anything in a setting without remote authentication, we use the network
@@ -191,8 +231,7 @@ impl TryFrom<String> for AccountAddress {
 
     fn try_from(s: String) -> Result<AccountAddress> {
         assert!(!s.is_empty());
-        let bytes_out = ::hex::decode(s)?;
-        AccountAddress::try_from(bytes_out.as_slice())
+        AccountAddress::from_bech32_or_hex(&s)
     }
 }
 
@@ -201,8 +240,7 @@ impl FromStr for AccountAddress {
 
     fn from_str(s: &str) -> Result<Self> {
         assert!(!s.is_empty());
-        let bytes_out = ::hex::decode(s)?;
-        AccountAddress::try_from(bytes_out.as_slice())
+        AccountAddress::from_bech32_or_hex(s)
     }
 }
 
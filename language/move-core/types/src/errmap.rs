@@ -0,0 +1,75 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The symbolic names of the abort codes of modules.
+//!
+//! By convention, a module declares each of its abort codes as a `u64` constant whose name starts
+//! with `E`, documented by a doc comment:
+//!
+//! ```move
+//! /// The account is not a VASP
+//! const ENOT_A_VASP: u64 = 2223;
+//! ```
+//!
+//! The compiler collects these constants into an `ErrorMapping`, and the error map of each module
+//! is published alongside its code, so that aborts can be mapped back to their names.
+
+use crate::language_storage::ModuleId;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The name and documentation of an abort code.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ErrorDescription {
+    /// The name of the constant declaring the code, e.g. `ENOT_A_VASP`
+    pub code_name: String,
+    /// The doc comment of the constant
+    pub code_description: String,
+}
+
+/// The abort codes declared by a module.
+pub type ModuleErrorMap = BTreeMap<u64, ErrorDescription>;
+
+/// The abort codes declared by a set of modules.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ErrorMapping {
+    pub module_error_maps: BTreeMap<ModuleId, ModuleErrorMap>,
+}
+
+impl ErrorMapping {
+    /// Records that `module_id` aborts with `code` for the reason in `description`. Fails if the
+    /// module declares the code twice, since aborts with it would be ambiguous.
+    pub fn add_module_error(
+        &mut self,
+        module_id: ModuleId,
+        code: u64,
+        description: ErrorDescription,
+    ) -> Result<()> {
+        let module_error_map = self.module_error_maps.entry(module_id.clone()).or_default();
+        if let Some(previous) = module_error_map.get(&code) {
+            bail!(
+                "Abort code {} of {:?} declared twice, by {} and {}",
+                code,
+                module_id,
+                previous.code_name,
+                description.code_name
+            );
+        }
+        module_error_map.insert(code, description);
+        Ok(())
+    }
+
+    /// Returns the description of `code` if `module_id` declares it.
+    pub fn get_explanation(&self, module_id: &ModuleId, code: u64) -> Option<&ErrorDescription> {
+        self.module_error_maps.get(module_id)?.get(&code)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(lcs::from_bytes(bytes)?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(lcs::to_bytes(self)?)
+    }
+}
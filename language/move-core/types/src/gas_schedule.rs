@@ -186,6 +186,25 @@ pub struct GasConstants {
     pub max_price_per_gas_unit: GasPrice<GasCarrier>,
 
     pub max_transaction_size_in_bytes: u64,
+
+    /// Resources whose serialized size exceeds this are stored across multiple chunks of at most
+    /// this size.
+    pub resource_chunk_size_in_bytes: u64,
+
+    /// The maximum total size of the values written by a transaction.
+    pub max_write_set_size_in_bytes: u64,
+
+    /// The maximum number of write ops of a transaction.
+    pub max_write_ops: u64,
+
+    /// The maximum number of events emitted by a transaction.
+    pub max_events: u64,
+
+    /// The maximum size of the data of an event.
+    pub max_event_size_in_bytes: u64,
+
+    /// The maximum number of scripts in a batch transaction.
+    pub max_scripts_per_batch: u64,
 }
 
 impl Default for GasConstants {
@@ -200,6 +219,12 @@ impl Default for GasConstants {
             min_price_per_gas_unit: GasPrice(0),
             max_price_per_gas_unit: GasPrice(10_000),
             max_transaction_size_in_bytes: 4096,
+            resource_chunk_size_in_bytes: 65536,
+            max_write_set_size_in_bytes: 1_048_576,
+            max_write_ops: 4096,
+            max_events: 1024,
+            max_event_size_in_bytes: 65536,
+            max_scripts_per_batch: 8,
         }
     }
 }
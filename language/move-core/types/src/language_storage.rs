@@ -29,6 +29,8 @@ pub enum TypeTag {
     Signer,
     Vector(Box<TypeTag>),
     Struct(StructTag),
+    // Appended so that the serialized tags of the other types do not change
+    U256,
 }
 
 #[derive(
@@ -159,6 +161,7 @@ impl Display for TypeTag {
             TypeTag::U8 => write!(f, "U8"),
             TypeTag::U64 => write!(f, "U64"),
             TypeTag::U128 => write!(f, "U128"),
+            TypeTag::U256 => write!(f, "U256"),
             TypeTag::Address => write!(f, "Address"),
             TypeTag::Signer => write!(f, "Signer"),
             TypeTag::Bool => write!(f, "Bool"),
@@ -4,6 +4,7 @@
 //! Core types for Move.
 
 pub mod account_address;
+pub mod errmap;
 pub mod gas_schedule;
 pub mod identifier;
 pub mod language_storage;
@@ -12,6 +13,7 @@ pub mod parser;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
 pub mod transaction_argument;
+pub mod u256;
 #[cfg(test)]
 mod unit_tests;
 pub mod value;
@@ -15,6 +15,7 @@ enum Token {
     U8Type,
     U64Type,
     U128Type,
+    U256Type,
     BoolType,
     AddressType,
     VectorType,
@@ -48,6 +49,7 @@ fn name_token(s: String) -> Token {
         "u8" => Token::U8Type,
         "u64" => Token::U64Type,
         "u128" => Token::U128Type,
+        "u256" => Token::U256Type,
         "bool" => Token::BoolType,
         "address" => Token::AddressType,
         "vector" => Token::VectorType,
@@ -239,6 +241,7 @@ impl<I: Iterator<Item = Token>> Parser<I> {
             Token::U8Type => TypeTag::U8,
             Token::U64Type => TypeTag::U64,
             Token::U128Type => TypeTag::U128,
+            Token::U256Type => TypeTag::U256,
             Token::BoolType => TypeTag::Bool,
             Token::AddressType => TypeTag::Address,
             Token::VectorType => {
@@ -320,6 +323,10 @@ pub fn parse_type_tags(s: &str) -> Result<Vec<TypeTag>> {
     })
 }
 
+pub fn parse_type_tag(s: &str) -> Result<TypeTag> {
+    parse(s, |parser| parser.parse_type_tag())
+}
+
 pub fn parse_transaction_arguments(s: &str) -> Result<Vec<TransactionArgument>> {
     parse(s, |parser| {
         parser.parse_comma_list(
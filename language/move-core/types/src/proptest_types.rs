@@ -6,6 +6,7 @@ use crate::{
     identifier::Identifier,
     language_storage::{StructTag, TypeTag},
     transaction_argument::TransactionArgument,
+    u256::U256,
 };
 use proptest::{collection::vec, prelude::*};
 impl Arbitrary for TypeTag {
@@ -19,6 +20,7 @@ impl Arbitrary for TypeTag {
             Just(U8),
             Just(U64),
             Just(U128),
+            Just(U256),
             Just(Address),
             Just(Vector(Box::new(Bool))),
         ];
@@ -61,3 +63,14 @@ impl Arbitrary for TransactionArgument {
 
     type Strategy = BoxedStrategy<Self>;
 }
+
+impl Arbitrary for U256 {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: ()) -> Self::Strategy {
+        any::<[u8; U256::BYTES]>()
+            .prop_map(U256::from_le_bytes)
+            .boxed()
+    }
+}
@@ -0,0 +1,365 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A 256-bit unsigned integer, the Rust representation of the Move `u256` type.
+//!
+//! Only the operations Move needs are provided: arithmetic is checked, as Move aborts on
+//! overflow, while bitwise operations and shifts never fail. Values serialize as 32 little-endian
+//! bytes, like the other Move integers.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{BitAnd, BitOr, BitXor, Shl, Shr},
+    str::FromStr,
+};
+use thiserror::Error;
+
+const LIMBS: usize = 4;
+const LIMB_BITS: u32 = 64;
+/// The largest power of 10 that fits in a limb, used to print values
+const DECIMAL_CHUNK: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 19;
+
+/// Limbs of 64 bits, the least significant first
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct U256([u64; LIMBS]);
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ParseU256Error {
+    #[error("cannot parse u256 from empty string")]
+    Empty,
+    #[error("invalid digit found in u256 string")]
+    InvalidDigit,
+    #[error("number too large to fit in u256")]
+    Overflow,
+}
+
+impl U256 {
+    pub const BYTES: usize = 32;
+
+    pub const fn zero() -> Self {
+        U256([0; LIMBS])
+    }
+
+    pub const fn max_value() -> Self {
+        U256([u64::max_value(); LIMBS])
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|limb| *limb == 0)
+    }
+
+    pub fn from_le_bytes(bytes: [u8; Self::BYTES]) -> Self {
+        let mut limbs = [0; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks(8)) {
+            let mut limb_bytes = [0; 8];
+            limb_bytes.copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(limb_bytes);
+        }
+        U256(limbs)
+    }
+
+    pub fn to_le_bytes(self) -> [u8; Self::BYTES] {
+        let mut bytes = [0; Self::BYTES];
+        for (chunk, limb) in bytes.chunks_mut(8).zip(self.0.iter()) {
+            chunk.copy_from_slice(&limb.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the value if it fits in a `u8`
+    pub fn to_u8(self) -> Option<u8> {
+        self.to_u64().and_then(|value| {
+            if value <= u64::from(u8::max_value()) {
+                Some(value as u8)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the value if it fits in a `u64`
+    pub fn to_u64(self) -> Option<u64> {
+        if self.0[1..].iter().all(|limb| *limb == 0) {
+            Some(self.0[0])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value if it fits in a `u128`
+    pub fn to_u128(self) -> Option<u128> {
+        if self.0[2..].iter().all(|limb| *limb == 0) {
+            Some(u128::from(self.0[0]) | (u128::from(self.0[1]) << LIMB_BITS))
+        } else {
+            None
+        }
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let mut result = [0; LIMBS];
+        let mut carry = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (sum, carry_1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, carry_2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = carry_1 || carry_2;
+        }
+        if carry {
+            None
+        } else {
+            Some(U256(result))
+        }
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (result, borrow) = self.overflowing_sub(other);
+        if borrow {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let mut result = [0u64; 2 * LIMBS];
+        for i in 0..LIMBS {
+            let mut carry = 0u128;
+            for j in 0..LIMBS {
+                // At most (2^64 - 1)^2 + 2 * (2^64 - 1) = 2^128 - 1, so this never overflows
+                let product = u128::from(self.0[i]) * u128::from(other.0[j])
+                    + u128::from(result[i + j])
+                    + carry;
+                result[i + j] = product as u64;
+                carry = product >> LIMB_BITS;
+            }
+            result[i + LIMBS] = carry as u64;
+        }
+        if result[LIMBS..].iter().any(|limb| *limb != 0) {
+            return None;
+        }
+        let mut limbs = [0; LIMBS];
+        limbs.copy_from_slice(&result[..LIMBS]);
+        Some(U256(limbs))
+    }
+
+    /// Returns None if `other` is zero
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.div_rem(other).map(|(quotient, _)| quotient)
+    }
+
+    /// Returns None if `other` is zero
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        self.div_rem(other).map(|(_, remainder)| remainder)
+    }
+
+    fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let mut result = [0; LIMBS];
+        let mut borrow = false;
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (difference, borrow_1) = self.0[i].overflowing_sub(other.0[i]);
+            let (difference, borrow_2) = difference.overflowing_sub(borrow as u64);
+            *limb = difference;
+            borrow = borrow_1 || borrow_2;
+        }
+        (U256(result), borrow)
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        (self.0[(index / LIMB_BITS) as usize] >> (index % LIMB_BITS)) & 1 == 1
+    }
+
+    fn set_bit(&mut self, index: u32) {
+        self.0[(index / LIMB_BITS) as usize] |= 1 << (index % LIMB_BITS);
+    }
+
+    fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let divisor = u128::from(divisor);
+        let mut quotient = [0; LIMBS];
+        let mut remainder = 0u128;
+        for i in (0..LIMBS).rev() {
+            let dividend = (remainder << LIMB_BITS) | u128::from(self.0[i]);
+            quotient[i] = (dividend / divisor) as u64;
+            remainder = dividend % divisor;
+        }
+        (U256(quotient), remainder as u64)
+    }
+
+    fn div_rem(self, divisor: Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        if let Some(divisor) = divisor.to_u64() {
+            let (quotient, remainder) = self.div_rem_u64(divisor);
+            return Some((quotient, U256::from(remainder)));
+        }
+        // Long division, one bit at a time
+        let mut quotient = U256::zero();
+        let mut remainder = U256::zero();
+        for index in (0..LIMBS as u32 * LIMB_BITS).rev() {
+            // The remainder is below the divisor, so it exceeds 256 bits only if its top bit is
+            // shifted out, and then it is certainly above the divisor
+            let overflow = remainder.bit(LIMBS as u32 * LIMB_BITS - 1);
+            remainder = remainder << 1;
+            if self.bit(index) {
+                remainder.set_bit(0);
+            }
+            if overflow || remainder >= divisor {
+                remainder = remainder.overflowing_sub(divisor).0;
+                quotient.set_bit(index);
+            }
+        }
+        Some((quotient, remainder))
+    }
+}
+
+impl From<u8> for U256 {
+    fn from(value: u8) -> Self {
+        U256::from(u64::from(value))
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        U256([value, 0, 0, 0])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        U256([value as u64, (value >> LIMB_BITS) as u64, 0, 0])
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl BitAnd for U256 {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        let mut result = self;
+        for (limb, other) in result.0.iter_mut().zip(other.0.iter()) {
+            *limb &= other;
+        }
+        result
+    }
+}
+
+impl BitOr for U256 {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        let mut result = self;
+        for (limb, other) in result.0.iter_mut().zip(other.0.iter()) {
+            *limb |= other;
+        }
+        result
+    }
+}
+
+impl BitXor for U256 {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        let mut result = self;
+        for (limb, other) in result.0.iter_mut().zip(other.0.iter()) {
+            *limb ^= other;
+        }
+        result
+    }
+}
+
+/// Shifts by a `u8` never overflow: the bits shifted out are dropped
+impl Shl<u8> for U256 {
+    type Output = Self;
+
+    fn shl(self, n_bits: u8) -> Self {
+        let limb_shift = (u32::from(n_bits) / LIMB_BITS) as usize;
+        let bit_shift = u32::from(n_bits) % LIMB_BITS;
+        let mut result = [0; LIMBS];
+        for i in limb_shift..LIMBS {
+            result[i] = self.0[i - limb_shift] << bit_shift;
+            if bit_shift > 0 && i > limb_shift {
+                result[i] |= self.0[i - limb_shift - 1] >> (LIMB_BITS - bit_shift);
+            }
+        }
+        U256(result)
+    }
+}
+
+impl Shr<u8> for U256 {
+    type Output = Self;
+
+    fn shr(self, n_bits: u8) -> Self {
+        let limb_shift = (u32::from(n_bits) / LIMB_BITS) as usize;
+        let bit_shift = u32::from(n_bits) % LIMB_BITS;
+        let mut result = [0; LIMBS];
+        for i in 0..LIMBS - limb_shift {
+            result[i] = self.0[i + limb_shift] >> bit_shift;
+            if bit_shift > 0 && i + limb_shift + 1 < LIMBS {
+                result[i] |= self.0[i + limb_shift + 1] << (LIMB_BITS - bit_shift);
+            }
+        }
+        U256(result)
+    }
+}
+
+impl FromStr for U256 {
+    type Err = ParseU256Error;
+
+    /// Parses a decimal number
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseU256Error::Empty);
+        }
+        let ten = U256::from(10u8);
+        s.chars().try_fold(U256::zero(), |value, c| {
+            let digit = c.to_digit(10).ok_or(ParseU256Error::InvalidDigit)?;
+            value
+                .checked_mul(ten)
+                .and_then(|value| value.checked_add(U256::from(u64::from(digit))))
+                .ok_or(ParseU256Error::Overflow)
+        })
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chunks = vec![];
+        let mut value = *self;
+        loop {
+            let (quotient, chunk) = value.div_rem_u64(DECIMAL_CHUNK);
+            chunks.push(chunk);
+            if quotient.is_zero() {
+                break;
+            }
+            value = quotient;
+        }
+        let mut chunks = chunks.iter().rev();
+        if let Some(most_significant) = chunks.next() {
+            write!(f, "{}", most_significant)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
@@ -1,11 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_address::AccountAddress;
+use crate::account_address::{AccountAddress, BECH32_HRP};
+use bech32::ToBase32;
 use hex::FromHex;
 use libra_crypto::{hash::CryptoHash, HashValue};
 use proptest::prelude::*;
-use std::convert::{AsRef, TryFrom};
+use std::{
+    convert::{AsRef, TryFrom},
+    str::FromStr,
+};
 
 #[test]
 fn test_address_bytes() {
@@ -92,6 +96,12 @@ proptest! {
         prop_assert_eq!(addr, addr2);
     }
 
+    #[test]
+    fn test_address_bech32_roundtrip(addr in any::<AccountAddress>()) {
+        let addr2 = AccountAddress::from_str(&addr.to_bech32()).unwrap();
+        prop_assert_eq!(addr, addr2);
+    }
+
     #[test]
     fn test_address_protobuf_roundtrip(addr in any::<AccountAddress>()) {
         let bytes = addr.to_vec();
@@ -100,3 +110,32 @@ proptest! {
         prop_assert_eq!(addr, addr2);
     }
 }
+
+#[test]
+fn test_bech32() {
+    let address = AccountAddress::from_str("ca843279e3427144cead5e4d5999a3d0").unwrap();
+    let encoded = "lbr1e2zry70rgfc5fn4dtex4nxdr6qryuc9z";
+    assert_eq!(address.to_bech32(), encoded);
+    assert_eq!(AccountAddress::from_bech32(encoded).unwrap(), address);
+    assert_eq!(AccountAddress::from_str(encoded).unwrap(), address);
+    assert_eq!(
+        AccountAddress::from_str(&encoded.to_uppercase()).unwrap(),
+        address
+    );
+    assert_eq!(
+        AccountAddress::ZERO.to_bech32(),
+        "lbr1qqqqqqqqqqqqqqqqqqqqqqqqqqgr4cwz"
+    );
+}
+
+#[test]
+fn test_bech32_invalid() {
+    // A single mistyped character breaks the checksum
+    assert!(AccountAddress::from_str("lbr1e2zry70rgfc5fn4dtex4nxdr6qryuc8z").is_err());
+    // Wrong human-readable part
+    let encoded = bech32::encode("tlb", [0u8; AccountAddress::LENGTH].to_base32()).unwrap();
+    assert!(AccountAddress::from_bech32(&encoded).is_err());
+    // Wrong length
+    let encoded = bech32::encode(BECH32_HRP, [0u8; 20].to_base32()).unwrap();
+    assert!(AccountAddress::from_bech32(&encoded).is_err());
+}
@@ -0,0 +1,46 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_address::AccountAddress,
+    errmap::{ErrorDescription, ErrorMapping},
+    identifier::Identifier,
+    language_storage::ModuleId,
+};
+
+fn description(code_name: &str) -> ErrorDescription {
+    ErrorDescription {
+        code_name: code_name.to_string(),
+        code_description: format!("The description of {}", code_name),
+    }
+}
+
+#[test]
+fn test_error_mapping() {
+    let module_id = ModuleId::new(AccountAddress::ZERO, Identifier::new("M").unwrap());
+    let other_module_id = ModuleId::new(AccountAddress::ZERO, Identifier::new("N").unwrap());
+    let mut error_mapping = ErrorMapping::default();
+    error_mapping
+        .add_module_error(module_id.clone(), 1, description("EONE"))
+        .unwrap();
+    error_mapping
+        .add_module_error(other_module_id.clone(), 1, description("EOTHER"))
+        .unwrap();
+    // codes are namespaced by module, but must be unique within one
+    assert!(error_mapping
+        .add_module_error(module_id.clone(), 1, description("EDUPLICATE"))
+        .is_err());
+
+    assert_eq!(
+        error_mapping.get_explanation(&module_id, 1),
+        Some(&description("EONE"))
+    );
+    assert_eq!(
+        error_mapping.get_explanation(&other_module_id, 1),
+        Some(&description("EOTHER"))
+    );
+    assert_eq!(error_mapping.get_explanation(&module_id, 2), None);
+
+    let bytes = error_mapping.to_bytes().unwrap();
+    assert_eq!(ErrorMapping::from_bytes(&bytes).unwrap(), error_mapping);
+}
@@ -2,5 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod address_test;
+mod errmap_test;
 mod identifier_test;
 mod language_storage_test;
+mod u256_test;
@@ -0,0 +1,119 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::u256::{ParseU256Error, U256};
+use lcs::test_helpers::assert_canonical_encode_decode;
+use proptest::prelude::*;
+use std::str::FromStr;
+
+const MAX: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+#[test]
+fn parse_and_display() {
+    assert_eq!(U256::from_str("0"), Ok(U256::zero()));
+    assert_eq!(U256::from_str(MAX), Ok(U256::max_value()));
+    assert_eq!(U256::max_value().to_string(), MAX);
+    assert_eq!(
+        U256::from(u128::max_value()).to_string(),
+        u128::max_value().to_string()
+    );
+    assert_eq!(U256::from_str(""), Err(ParseU256Error::Empty));
+    assert_eq!(U256::from_str("12a"), Err(ParseU256Error::InvalidDigit));
+    assert_eq!(
+        U256::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        ),
+        Err(ParseU256Error::Overflow)
+    );
+}
+
+#[test]
+fn overflow() {
+    let one = U256::from(1u8);
+    assert_eq!(U256::max_value().checked_add(one), None);
+    assert_eq!(U256::zero().checked_sub(one), None);
+    assert_eq!(U256::max_value().checked_mul(U256::from(2u8)), None);
+    assert_eq!((one << 255).checked_mul(U256::from(2u8)), None);
+    assert_eq!(one.checked_div(U256::zero()), None);
+    assert_eq!(one.checked_rem(U256::zero()), None);
+    assert_eq!(U256::max_value().checked_div(U256::max_value()), Some(one));
+}
+
+#[test]
+fn shifts() {
+    let one = U256::from(1u8);
+    assert_eq!((one << 255) >> 255, one);
+    assert_eq!((one << 255) << 1, U256::zero());
+    assert_eq!(U256::max_value() >> 192, U256::from(u64::max_value()));
+    assert_eq!(
+        (U256::from(u128::max_value()) << 64) >> 64,
+        U256::from(u128::max_value())
+    );
+}
+
+#[test]
+fn casts() {
+    assert_eq!(U256::from(255u8).to_u8(), Some(255));
+    assert_eq!(U256::from(256u64).to_u8(), None);
+    assert_eq!(
+        U256::from(u64::max_value()).to_u64(),
+        Some(u64::max_value())
+    );
+    assert_eq!(U256::from(u128::max_value()).to_u64(), None);
+    assert_eq!(
+        U256::from(u128::max_value()).to_u128(),
+        Some(u128::max_value())
+    );
+    assert_eq!((U256::from(1u8) << 128).to_u128(), None);
+}
+
+fn u256_from_u128s(high: u128, low: u128) -> U256 {
+    (U256::from(high) << 128) | U256::from(low)
+}
+
+proptest! {
+    #[test]
+    fn matches_u128(a in any::<u128>(), b in any::<u128>()) {
+        let (x, y) = (U256::from(a), U256::from(b));
+        prop_assert_eq!(x.checked_add(y).and_then(U256::to_u128), a.checked_add(b));
+        prop_assert_eq!(x.checked_sub(y).map(|v| v.to_u128().unwrap()), a.checked_sub(b));
+        prop_assert_eq!(x.checked_mul(y).and_then(U256::to_u128), a.checked_mul(b));
+        prop_assert_eq!(x.checked_div(y).map(|v| v.to_u128().unwrap()), a.checked_div(b));
+        prop_assert_eq!(x.checked_rem(y).map(|v| v.to_u128().unwrap()), a.checked_rem(b));
+        prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+        prop_assert_eq!(x.to_string(), a.to_string());
+    }
+
+    #[test]
+    fn div_rem_roundtrip(
+        a_high in any::<u128>(),
+        a_low in any::<u128>(),
+        b_high in any::<u128>(),
+        b_low in any::<u128>(),
+    ) {
+        let a = u256_from_u128s(a_high, a_low);
+        let b = u256_from_u128s(b_high, b_low);
+        prop_assume!(!b.is_zero());
+        let quotient = a.checked_div(b).unwrap();
+        let remainder = a.checked_rem(b).unwrap();
+        prop_assert!(remainder < b);
+        prop_assert_eq!(
+            quotient.checked_mul(b).and_then(|v| v.checked_add(remainder)),
+            Some(a)
+        );
+    }
+
+    #[test]
+    fn display_roundtrip(high in any::<u128>(), low in any::<u128>()) {
+        let value = u256_from_u128s(high, low);
+        prop_assert_eq!(U256::from_str(&value.to_string()), Ok(value));
+        prop_assert_eq!(U256::from_le_bytes(value.to_le_bytes()), value);
+    }
+
+    #[test]
+    fn lcs_roundtrip(high in any::<u128>(), low in any::<u128>()) {
+        let value = u256_from_u128s(high, low);
+        prop_assert_eq!(lcs::to_bytes(&value).unwrap(), value.to_le_bytes().to_vec());
+        assert_canonical_encode_decode(value);
+    }
+}
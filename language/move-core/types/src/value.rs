@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_address::AccountAddress;
+use crate::{account_address::AccountAddress, u256::U256};
 use anyhow::Result as AResult;
 use serde::{
     de::Error as DeError,
@@ -18,6 +18,7 @@ pub enum MoveValue {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
     Vector(Vec<MoveValue>),
@@ -34,6 +35,7 @@ pub enum MoveTypeLayout {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Vector(Box<MoveTypeLayout>),
     Struct(MoveStructLayout),
@@ -93,6 +95,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
             MoveTypeLayout::U8 => u8::deserialize(deserializer).map(MoveValue::U8),
             MoveTypeLayout::U64 => u64::deserialize(deserializer).map(MoveValue::U64),
             MoveTypeLayout::U128 => u128::deserialize(deserializer).map(MoveValue::U128),
+            MoveTypeLayout::U256 => U256::deserialize(deserializer).map(MoveValue::U256),
             MoveTypeLayout::Address => {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Address)
             }
@@ -173,6 +176,7 @@ impl serde::Serialize for MoveValue {
             MoveValue::U8(i) => serializer.serialize_u8(*i),
             MoveValue::U64(i) => serializer.serialize_u64(*i),
             MoveValue::U128(i) => serializer.serialize_u128(*i),
+            MoveValue::U256(i) => i.serialize(serializer),
             MoveValue::Address(a) => a.serialize(serializer),
             MoveValue::Signer(a) => a.serialize(serializer),
             MoveValue::Vector(v) => {
@@ -289,6 +289,26 @@ pub enum StatusCode {
     INVALID_MODULE_PUBLISHER = 21,
     // The sending account has no role
     NO_ACCOUNT_ROLE = 22,
+    // A batch transaction is empty or has more scripts than allowed
+    INVALID_BATCH_SIZE = 23,
+    // The transaction expires beyond the expiration window set on-chain
+    TRANSACTION_EXPIRATION_TOO_FAR = 24,
+    // The fee payer of a sponsored transaction doesn't exist
+    FEE_PAYER_ACCOUNT_DOES_NOT_EXIST = 25,
+    // The fee payer of a sponsored transaction is frozen
+    FEE_PAYER_ACCOUNT_FROZEN = 26,
+    // The fee payer's public key doesn't match the authentication key of the fee payer account
+    INVALID_FEE_PAYER_AUTH_KEY = 27,
+    // The transaction cannot be sponsored, or not by this fee payer
+    INVALID_FEE_PAYER = 28,
+    // The transaction is signed with a signature scheme which is not enabled on-chain
+    UNSUPPORTED_AUTHENTICATION_SCHEME = 29,
+    // The sender already has as many transactions in mempool as the node admits per sender
+    SENDER_QUOTA_EXCEEDED = 30,
+    // The node admitted as many module publishing transactions as it allows in the current window
+    MODULE_PUBLISH_QUOTA_EXCEEDED = 31,
+    // The sender of a writeset transaction is not one the node admits writeset transactions from
+    WRITESET_SENDER_NOT_ALLOWED = 32,
 
     // When a code module/script is published it is verified. These are the
     // possible errors that can arise from the verification process.
@@ -391,6 +411,14 @@ pub enum StatusCode {
     MOVETO_NO_RESOURCE_ERROR = 1091,
     GENERIC_MEMBER_OPCODE_MISMATCH = 1092,
     FUNCTION_RESOLUTION_FAILURE = 1093,
+    VEC_TYPE_MISMATCH_ERROR = 1094,
+    VEC_NO_MUTABLE_REFERENCE_ERROR = 1095,
+    VEC_UPDATE_EXISTS_MUTABLE_BORROW_ERROR = 1096,
+    VEC_BORROW_ELEMENT_EXISTS_MUTABLE_BORROW_ERROR = 1097,
+    CYCLIC_MODULE_DEPENDENCY = 1098,
+    // The parameters or return values of a native function declared in a module don't match the
+    // native function of the VM
+    NATIVE_FUNCTION_ARITY_MISMATCH = 1099,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -435,6 +463,13 @@ pub enum StatusCode {
     BAD_U64 = 3019,
     BAD_U128 = 3020,
     BAD_ULEB_U8 = 3021,
+    BAD_U256 = 3022,
+    // The binary exceeds the size limit of the deserializer
+    BINARY_TOO_LARGE = 3023,
+    // A table of the binary has more entries than the deserializer allows
+    TOO_MANY_TABLE_ENTRIES = 3024,
+    // The binary is not in canonical form: serializing it again does not give the same bytes
+    NON_CANONICAL_BINARY = 3025,
 
     // Errors that can arise at runtime
     // Runtime Errors: 4000-4999
@@ -469,6 +504,11 @@ pub enum StatusCode {
     CALL_STACK_OVERFLOW = 4021,
     NATIVE_FUNCTION_ERROR = 4022,
     GAS_SCHEDULE_ERROR = 4023,
+    // The limits of the VM config on the effects of a transaction were exceeded
+    WRITE_SET_SIZE_LIMIT_EXCEEDED = 4025,
+    WRITE_OPS_LIMIT_EXCEEDED = 4026,
+    EVENT_COUNT_LIMIT_EXCEEDED = 4027,
+    EVENT_SIZE_LIMIT_EXCEEDED = 4028,
 
     // A reserved status to represent an unknown vm status.
     // this is std::u64::MAX, but we can't pattern match on that, so put the hardcoded value in
@@ -538,6 +578,14 @@ pub mod sub_status {
     pub const NFE_VECTOR_ERROR_BASE: u64 = 0;
     // Failure in LCS deserialization
     pub const NFE_LCS_SERIALIZATION_FAILURE: u64 = 0x1C5;
+    // Bytes that are not the LCS encoding of a value of the expected type
+    pub const NFE_LCS_DESERIALIZATION_FAILURE: u64 = 0x1C6;
+    // A type whose values cannot be created from bytes
+    pub const NFE_LCS_UNSUPPORTED_TYPE: u64 = 0x1C7;
+    // Adding an entry for a key a table already has an entry for
+    pub const NFE_TABLE_ENTRY_ALREADY_EXISTS: u64 = 0x1C8;
+    // Reading or removing the entry of a key a table has no entry for
+    pub const NFE_TABLE_ENTRY_NOT_FOUND: u64 = 0x1C9;
 
     pub const GSE_UNABLE_TO_LOAD_MODULE: u64 = 0;
     pub const GSE_UNABLE_TO_LOAD_RESOURCE: u64 = 1;
@@ -7,7 +7,9 @@ use crate::{
 };
 use anyhow::Result;
 use libra_types::account_address::AccountAddress;
-use move_core_types::{identifier::Identifier, language_storage::ModuleId, value::MoveValue};
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, u256::U256, value::MoveValue,
+};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -185,6 +187,8 @@ pub enum Type {
     U64,
     /// `u128`
     U128,
+    /// `u256`
+    U256,
     /// `bool`
     Bool,
     /// `vector`
@@ -395,6 +399,8 @@ pub enum Builtin {
     ToU64,
     /// Cast an integer into u128.
     ToU128,
+    /// Cast an integer into u256.
+    ToU256,
 }
 
 /// Enum for different function calls
@@ -513,6 +519,8 @@ pub enum CopyableVal_ {
     U64(u64),
     /// An unsigned 128-bit integer
     U128(u128),
+    /// An unsigned 256-bit integer
+    U256(U256),
     /// true or false
     Bool(bool),
     /// `b"<bytes>"`
@@ -645,9 +653,11 @@ pub enum Bytecode_ {
     LdU8(u8),
     LdU64(u64),
     LdU128(u128),
+    LdU256(U256),
     CastU8,
     CastU64,
     CastU128,
+    CastU256,
     LdByteArray(Vec<u8>),
     LdAddr(AccountAddress),
     LdTrue,
@@ -693,6 +703,14 @@ pub enum Bytecode_ {
     MoveTo(StructName, Vec<Type>),
     Shl,
     Shr,
+    VecPack(Type, u64),
+    VecLen(Type),
+    VecImmBorrow(Type),
+    VecMutBorrow(Type),
+    VecPushBack(Type),
+    VecPopBack(Type),
+    VecUnpack(Type, u64),
+    VecSwap(Type),
 }
 pub type Bytecode = Spanned<Bytecode_>;
 
@@ -1572,6 +1590,7 @@ impl fmt::Display for Type {
             Type::U8 => write!(f, "u8"),
             Type::U64 => write!(f, "u64"),
             Type::U128 => write!(f, "u128"),
+            Type::U256 => write!(f, "u256"),
             Type::Bool => write!(f, "bool"),
             Type::Address => write!(f, "address"),
             Type::Signer => write!(f, "signer"),
@@ -1615,6 +1634,7 @@ impl fmt::Display for Builtin {
             Builtin::ToU8 => write!(f, "to_u8"),
             Builtin::ToU64 => write!(f, "to_u64"),
             Builtin::ToU128 => write!(f, "to_u128"),
+            Builtin::ToU256 => write!(f, "to_u256"),
         }
     }
 }
@@ -1744,6 +1764,7 @@ impl fmt::Display for CopyableVal_ {
             CopyableVal_::U8(v) => write!(f, "{}u8", v),
             CopyableVal_::U64(v) => write!(f, "{}", v),
             CopyableVal_::U128(v) => write!(f, "{}u128", v),
+            CopyableVal_::U256(v) => write!(f, "{}u256", v),
             CopyableVal_::Bool(v) => write!(f, "{}", v),
             CopyableVal_::ByteArray(v) => write!(f, "0b{}", hex::encode(v)),
             CopyableVal_::Address(v) => write!(f, "0x{}", hex::encode(v)),
@@ -1855,9 +1876,11 @@ impl fmt::Display for Bytecode_ {
             Bytecode_::LdU8(u) => write!(f, "LdU8 {}", u),
             Bytecode_::LdU64(u) => write!(f, "LdU64 {}", u),
             Bytecode_::LdU128(u) => write!(f, "LdU128 {}", u),
+            Bytecode_::LdU256(u) => write!(f, "LdU256 {}", u),
             Bytecode_::CastU8 => write!(f, "CastU8"),
             Bytecode_::CastU64 => write!(f, "CastU64"),
             Bytecode_::CastU128 => write!(f, "CastU128"),
+            Bytecode_::CastU256 => write!(f, "CastU256"),
             Bytecode_::LdByteArray(b) => write!(f, "LdByteArray 0b{}", hex::encode(b)),
             Bytecode_::LdAddr(a) => write!(f, "LdAddr {}", a),
             Bytecode_::LdTrue => write!(f, "LdTrue"),
@@ -1921,6 +1944,14 @@ impl fmt::Display for Bytecode_ {
             Bytecode_::MoveTo(n, tys) => write!(f, "MoveTo {}{}", n, format_type_actuals(tys)),
             Bytecode_::Shl => write!(f, "Shl"),
             Bytecode_::Shr => write!(f, "Shr"),
+            Bytecode_::VecPack(ty, n) => write!(f, "VecPack<{}> {}", ty, n),
+            Bytecode_::VecLen(ty) => write!(f, "VecLen<{}>", ty),
+            Bytecode_::VecImmBorrow(ty) => write!(f, "VecImmBorrow<{}>", ty),
+            Bytecode_::VecMutBorrow(ty) => write!(f, "VecMutBorrow<{}>", ty),
+            Bytecode_::VecPushBack(ty) => write!(f, "VecPushBack<{}>", ty),
+            Bytecode_::VecPopBack(ty) => write!(f, "VecPopBack<{}>", ty),
+            Bytecode_::VecUnpack(ty, n) => write!(f, "VecUnpack<{}> {}", ty, n),
+            Bytecode_::VecSwap(ty) => write!(f, "VecSwap<{}>", ty),
         }
     }
 }
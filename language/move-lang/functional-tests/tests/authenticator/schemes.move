@@ -0,0 +1,85 @@
+//! account: bob
+
+script {
+use 0x1::Authenticator;
+
+fun main() {
+    // Only the Ed25519 and MultiEd25519 schemes are enabled at genesis
+    assert(Authenticator::is_scheme_enabled(Authenticator::ED25519_SCHEME_ID()), 3000);
+    assert(Authenticator::is_scheme_enabled(Authenticator::MULTI_ED25519_SCHEME_ID()), 3001);
+    assert(!Authenticator::is_scheme_enabled(2), 3002);
+
+    // The scheme of a preimage is its last byte
+    assert(Authenticator::scheme_id(&x"7013b6ed01") == 1, 3003);
+    assert(
+        Authenticator::authentication_key(x"7013b6ed", 0) == Authenticator::ed25519_authentication_key(x"7013b6ed"),
+        3004
+    );
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+use 0x1::Authenticator;
+
+fun main() {
+    Authenticator::scheme_id(&x"");
+}
+}
+// check: ABORTED
+// check: 7005
+
+// Rotating to a key of a scheme which is not enabled would lock the account out
+//! new-transaction
+//! sender: bob
+script {
+use 0x1::LibraAccount;
+
+fun main(account: &signer) {
+    let rot_cap = LibraAccount::extract_key_rotation_capability(account);
+    LibraAccount::rotate_authentication_key_for_scheme(
+        &rot_cap,
+        x"7013b6ed7dde3cfb1251db1b04ae9cd7853470284085693590a75def645a926d",
+        2
+    );
+    LibraAccount::restore_key_rotation_capability(rot_cap);
+}
+}
+// check: ABORTED
+// check: 16
+
+//! new-transaction
+//! sender: association
+script {
+use 0x1::Authenticator;
+use 0x1::Roles::{Self, LibraRootRole};
+
+fun main(association: &signer) {
+    let r = Roles::extract_privilege_to_capability<LibraRootRole>(association);
+    Authenticator::enable_scheme(&r, 2);
+    // Enabling a scheme twice is a no-op
+    Authenticator::enable_scheme(&r, 2);
+    Roles::restore_capability_to_privilege(association, r);
+    assert(Authenticator::is_scheme_enabled(2), 3005);
+}
+}
+// check: EXECUTED
+
+// The account keeps its address, and can only sign with the new scheme from now on
+//! new-transaction
+//! sender: bob
+script {
+use 0x1::LibraAccount;
+
+fun main(account: &signer) {
+    let rot_cap = LibraAccount::extract_key_rotation_capability(account);
+    LibraAccount::rotate_authentication_key_for_scheme(
+        &rot_cap,
+        x"7013b6ed7dde3cfb1251db1b04ae9cd7853470284085693590a75def645a926d",
+        2
+    );
+    LibraAccount::restore_key_rotation_capability(rot_cap);
+}
+}
+// check: EXECUTED
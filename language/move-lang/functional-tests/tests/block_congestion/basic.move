@@ -0,0 +1,38 @@
+//! account: vivian, 1000000, 0, validator
+
+//! new-transaction
+script{
+use 0x1::BlockCongestion;
+
+fun main() {
+    assert(BlockCongestion::recent_fullness() == 0, 77);
+}
+}
+// check: EXECUTED
+
+//! block-prologue
+//! proposer: vivian
+//! block-time: 1000000
+
+//! new-transaction
+script{
+use 0x1::BlockCongestion;
+
+fun main() {
+    // A single transaction in a block holding 1000 barely moves the average
+    assert(BlockCongestion::recent_fullness() == 0, 78);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: vivian
+script{
+use 0x1::BlockCongestion;
+
+fun main(account: &signer) {
+    BlockCongestion::end_block(account);
+}
+}
+// check: ABORTED
+// check: 33
@@ -0,0 +1,12 @@
+script {
+use 0x1::FixedPoint64;
+
+fun main() {
+    // A denominator of zero should cause an arithmetic error.
+    let f1 = FixedPoint64::create_from_rational(2, 0, FixedPoint64::ROUND_NEAREST());
+    // The above should fail at runtime so that the following assertion
+    // is never even tested.
+    assert(FixedPoint64::get_raw_value(f1) == 999, 1);
+}
+}
+// check: ARITHMETIC_ERROR
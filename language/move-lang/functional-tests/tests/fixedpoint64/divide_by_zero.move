@@ -0,0 +1,14 @@
+script {
+use 0x1::FixedPoint64;
+
+fun main() {
+    // Dividing by zero should cause an arithmetic error.
+    let f1 = FixedPoint64::create_from_raw_value(0);
+    let f2 = FixedPoint64::create_from_u64(1);
+    let fail = FixedPoint64::divide(f2, copy f1, FixedPoint64::ROUND_UP());
+    // The above should fail at runtime so that the following assertion
+    // is never even tested.
+    assert(FixedPoint64::get_raw_value(fail) == 999, 1);
+}
+}
+// check: ARITHMETIC_ERROR
@@ -0,0 +1,14 @@
+script {
+use 0x1::FixedPoint64;
+
+fun main() {
+    let f1 = FixedPoint64::create_from_u64(1);
+    // 3 is not a rounding mode.
+    let fail = FixedPoint64::multiply_u64(1, copy f1, 3);
+    // The above should fail at runtime so that the following assertion
+    // is never even tested.
+    assert(fail == 999, 1);
+}
+}
+// check: ABORTED
+// check: 17
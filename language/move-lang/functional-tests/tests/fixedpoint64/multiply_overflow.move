@@ -0,0 +1,13 @@
+script {
+use 0x1::FixedPoint64;
+
+fun main() {
+    let f1 = FixedPoint64::create_from_rational(3, 2, FixedPoint64::ROUND_DOWN()); // 1.5
+    // Multiply the maximum u64 value by 1.5. This should overflow.
+    let overflow = FixedPoint64::multiply_u64(18446744073709551615, copy f1, FixedPoint64::ROUND_DOWN());
+    // The above should fail at runtime so that the following assertion
+    // is never even tested.
+    assert(overflow == 999, 1);
+}
+}
+// check: ARITHMETIC_ERROR
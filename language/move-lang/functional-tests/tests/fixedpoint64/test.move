@@ -0,0 +1,52 @@
+script {
+use 0x1::FixedPoint32;
+use 0x1::FixedPoint64;
+
+fun main() {
+    let down = FixedPoint64::ROUND_DOWN();
+    let up = FixedPoint64::ROUND_UP();
+    let nearest = FixedPoint64::ROUND_NEAREST();
+
+    let f1 = FixedPoint64::create_from_rational(3, 4, down); // 0.75
+    let nine = FixedPoint64::multiply_u64(12, copy f1, down); // 12 * 0.75
+    assert(nine == 9, nine);
+    let twelve = FixedPoint64::divide_u64(9, copy f1, down); // 9 / 0.75
+    assert(twelve == 12, twelve);
+
+    // 1/3 is not representable in binary, so 9 * 1/3 is slightly below 3
+    // when 1/3 is rounded down, and slightly above it when rounded up.
+    let third_down = FixedPoint64::create_from_rational(1, 3, down);
+    let third_up = FixedPoint64::create_from_rational(1, 3, up);
+    assert(FixedPoint64::get_raw_value(copy third_up) == FixedPoint64::get_raw_value(copy third_down) + 1, 1);
+    assert(FixedPoint64::multiply_u64(9, copy third_down, down) == 2, 2);
+    assert(FixedPoint64::multiply_u64(9, copy third_down, up) == 3, 3);
+    assert(FixedPoint64::multiply_u64(9, copy third_down, nearest) == 3, 4);
+    assert(FixedPoint64::multiply_u64(9, copy third_up, down) == 3, 5);
+
+    // 10 / 4 == 2.5, which is a tie rounded up.
+    let four = FixedPoint64::create_from_u64(4);
+    assert(FixedPoint64::divide_u64(10, copy four, down) == 2, 6);
+    assert(FixedPoint64::divide_u64(10, copy four, up) == 3, 7);
+    assert(FixedPoint64::divide_u64(10, copy four, nearest) == 3, 8);
+    assert(FixedPoint64::divide_u64(9, copy four, nearest) == 2, 9);
+
+    // The product of the largest u64 value and 1.0 needs more than 128 bits
+    // before it is scaled back.
+    let one = FixedPoint64::create_from_rational(18446744073709551615, 18446744073709551615, down);
+    assert(FixedPoint64::get_raw_value(copy one) == 18446744073709551616, 10); // 0x1.0000000000000000
+    assert(FixedPoint64::multiply_u64(18446744073709551615, copy one, down) == 18446744073709551615, 11);
+    let max = FixedPoint64::create_from_u64(18446744073709551615);
+    let product = FixedPoint64::multiply(copy max, copy one, down);
+    assert(FixedPoint64::to_u64(copy product, down) == 18446744073709551615, 12);
+    let quotient = FixedPoint64::divide(copy product, copy four, nearest);
+    assert(FixedPoint64::to_u64(copy quotient, down) == 4611686018427387903, 13);
+    assert(FixedPoint64::to_u64(copy quotient, up) == 4611686018427387904, 14);
+
+    // FixedPoint32 values are represented exactly.
+    let ratio = FixedPoint64::create_from_fixed_point32(FixedPoint32::create_from_rational(1, 2));
+    assert(FixedPoint64::multiply_u64(7, copy ratio, down) == 3, 15);
+    assert(FixedPoint64::multiply_u64(7, copy ratio, nearest) == 4, 16);
+    let back = FixedPoint64::to_fixed_point32(copy ratio, down);
+    assert(FixedPoint32::get_raw_value(back) == 2147483648, 17);
+}
+}
@@ -1,47 +1,41 @@
 //! new-transaction
 script {
     use 0x1::LibraTransactionTimeout;
+    use 0x1::LibraConfig::CreateOnChainConfig;
+    use 0x1::Roles;
     fun main(account: &signer) {
-        LibraTransactionTimeout::initialize(account);
+        let cap = Roles::extract_privilege_to_capability<CreateOnChainConfig>(account);
+        LibraTransactionTimeout::initialize(account, &cap);
+        Roles::restore_capability_to_privilege(account, cap);
     }
 }
 // check: ABORTED
-// check: 1
 
 //! new-transaction
 script {
     use 0x1::LibraTransactionTimeout;
-    use 0x1::Roles::{Self, LibraRootRole};
-    fun main(account: &signer) {
-        let cap = Roles::extract_privilege_to_capability<LibraRootRole>(account);
-        LibraTransactionTimeout::set_timeout(&cap, 0);
-        Roles::restore_capability_to_privilege(account, cap);
+    fun main() {
+        assert(LibraTransactionTimeout::duration_microseconds() == 86400000000, 77);
     }
 }
-// check: ABORTED
-// check: 3
+// check: EXECUTED
 
 //! new-transaction
 script {
     use 0x1::LibraTransactionTimeout;
-    use 0x1::Roles::{Self, LibraRootRole};
     fun main(account: &signer) {
-        let cap = Roles::extract_privilege_to_capability<LibraRootRole>(account);
-        LibraTransactionTimeout::set_timeout(&cap, 0);
-        Roles::restore_capability_to_privilege(account, cap);
+        LibraTransactionTimeout::set_timeout(account, 0);
     }
 }
 // check: ABORTED
-// check: 3
+// check: 24
 
 //! new-transaction
 //! sender: association
 script {
     use 0x1::LibraTransactionTimeout;
-    use 0x1::Roles::{Self, LibraRootRole};
     fun main(account: &signer) {
-        let cap = Roles::extract_privilege_to_capability<LibraRootRole>(account);
-        LibraTransactionTimeout::set_timeout(&cap, 86400000000);
-        Roles::restore_capability_to_privilege(account, cap);
+        LibraTransactionTimeout::set_timeout(account, 86400000000);
     }
 }
+// check: EXECUTED
@@ -0,0 +1,20 @@
+//! account: vivian, 1000000, 0, validator
+
+//! block-prologue
+//! proposer: vivian
+//! block-time: 1000000
+
+// Expires at the end of the window, one day after the block time
+//! new-transaction
+//! expiration-time: 86401
+script {
+fun main() {}
+}
+// check: EXECUTED
+
+//! new-transaction
+//! expiration-time: 86402
+script {
+fun main() {}
+}
+// check: TRANSACTION_EXPIRATION_TOO_FAR
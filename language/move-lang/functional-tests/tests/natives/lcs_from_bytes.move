@@ -0,0 +1,82 @@
+// Tests for LCS deserialization in Move
+
+script {
+use 0x1::LCS;
+
+fun main() {
+    // address
+    let addr = 0x89b9f9d1fadc027cf9532d6f99041522;
+    assert(LCS::from_bytes<address>(x"89b9f9d1fadc027cf9532d6f99041522") == addr, 8001);
+
+    // bool
+    assert(LCS::from_bytes<bool>(x"01"), 8002);
+
+    // u8
+    assert(LCS::from_bytes<u8>(x"01") == 1u8, 8003);
+
+    // u64
+    assert(LCS::from_bytes<u64>(x"0100000000000000") == 1, 8004);
+
+    // u128
+    assert(LCS::from_bytes<u128>(x"01000000000000000000000000000000") == 1u128, 8005);
+
+    // vector<u8>
+    assert(LCS::from_bytes<vector<u8>>(x"010f") == x"0f", 8006);
+
+    // vector<vector<u8>>
+    let v = LCS::from_bytes<vector<vector<u8>>>(x"02010f00");
+    assert(LCS::to_bytes(&v) == x"02010f00", 8007);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+use 0x1::LCS;
+
+fun main() {
+    // Left over bytes
+    LCS::from_bytes<u8>(x"0102");
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+script {
+use 0x1::LCS;
+
+fun main() {
+    // Not a boolean
+    LCS::from_bytes<bool>(x"02");
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+script {
+use 0x1::LCS;
+
+fun main() {
+    // Missing bytes
+    LCS::from_bytes<u64>(x"01000000");
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+module M {
+    struct S { x: u64 }
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+use 0x1::LCS;
+use {{default}}::M;
+
+fun main() {
+    // Structs may only be created by their module
+    let _ = LCS::from_bytes<M::S>(x"0100000000000000");
+}
+}
+// check: NATIVE_FUNCTION_ERROR
@@ -0,0 +1,39 @@
+// Tests for secp256k1 public key recovery
+
+script {
+use 0x1::Signature;
+
+fun main() {
+    // The SHA3-256 hash of "Test message", signed with the secret key of 32 bytes 0x07
+    let message_hash = x"acbc96654fd7850a11ceb965a4a9e3544d572047fa71eef5c864f7195d5a85c2";
+    let signature = x"bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d1b352d191fd395ec6d4a3838fed91ba8460e4cd44fd216382b8ea29e6c91c350";
+    let public_key = x"989c0b76cb563971fdc9bef31ec06c3560f3249d6ee9e5d83c57625596e05f6f631f4d05b3ae518776ee08755a7703e64b2ebc32547504de0b55a142d4ecdf80";
+
+    let (key, ok) = Signature::secp256k1_recover(copy message_hash, 0, copy signature);
+    assert(ok, 9000);
+    assert(key == public_key, 9001);
+
+    // The other recovery id yields another key
+    let (key, ok) = Signature::secp256k1_recover(copy message_hash, 1, copy signature);
+    assert(ok, 9002);
+    assert(key != public_key, 9003);
+
+    // Invalid recovery ids yield no key
+    let (key, ok) = Signature::secp256k1_recover(message_hash, 4, signature);
+    assert(!ok, 9004);
+    assert(key == x"", 9005);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+use 0x1::Signature;
+
+fun main() {
+    // A signature of the wrong length
+    let message_hash = x"acbc96654fd7850a11ceb965a4a9e3544d572047fa71eef5c864f7195d5a85c2";
+    let (_, _) = Signature::secp256k1_recover(message_hash, 0, x"bb50e2d89a4ed70663d0");
+}
+}
+// check: NATIVE_FUNCTION_ERROR
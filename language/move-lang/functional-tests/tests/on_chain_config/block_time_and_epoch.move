@@ -0,0 +1,38 @@
+//! account: vivian, 1000000, 0, validator
+
+//! block-time: 3
+
+//! new-transaction
+script {
+use 0x1::LibraTimestamp;
+
+fun main() {
+    assert(LibraTimestamp::now_microseconds() == 3, 77);
+}
+}
+// check: EXECUTED
+
+//! new-epoch
+// check: NewBlockEvent
+// check: NewEpochEvent
+// check: EXECUTED
+
+//! new-transaction
+script {
+use 0x1::LibraTimestamp;
+
+fun main() {
+    assert(LibraTimestamp::now_microseconds() == 4, 78);
+}
+}
+// check: EXECUTED
+
+// There can be at most one reconfiguration at a given time
+//! trigger-reconfiguration
+// check: ABORTED
+// check: 23
+
+//! block-time: 10
+//! trigger-reconfiguration
+// check: NewEpochEvent
+// check: EXECUTED
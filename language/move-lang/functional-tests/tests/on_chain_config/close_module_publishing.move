@@ -1,7 +1,7 @@
 //! account: alice, 1000000, 0
 //! account: vivian, 1000000, 0, validator
 
-// Changing the publishing option from Open to CustomScript
+// Changing the module publishing policy from open to closed
 // Step 1: Make sure we can publish module at the beginning
 module FooConfig {
     struct T {
@@ -21,13 +21,12 @@ module FooConfig {
 
 //! new-transaction
 //! sender: association
-//! args: b"01",
-// Step 2: Change option to CustomScript
+// Step 2: Close module publishing
 script {
-use 0x1::LibraVMConfig;
+use 0x1::LibraModulePublishingPolicy;
 
-fun main(config: &signer, args: vector<u8>) {
-    LibraVMConfig::set_publishing_option(config, args)
+fun main(config: &signer) {
+    LibraModulePublishingPolicy::set_closed(config)
 }
 }
 
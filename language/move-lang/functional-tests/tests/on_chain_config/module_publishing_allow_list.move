@@ -0,0 +1,56 @@
+//! account: alice, 1000000, 0
+//! account: bob, 1000000, 0
+//! account: vivian, 1000000, 0, validator
+
+// Restricting module publishing to an allow-list of accounts
+
+//! block-prologue
+//! proposer: vivian
+//! block-time: 2
+
+//! new-transaction
+//! sender: association
+script {
+use 0x1::LibraModulePublishingPolicy;
+use 0x1::Vector;
+
+fun main(config: &signer) {
+    LibraModulePublishingPolicy::set_allow_list(config, Vector::singleton({{alice}}));
+    assert(LibraModulePublishingPolicy::is_allowed_publisher({{alice}}), 77);
+    assert(!LibraModulePublishingPolicy::is_allowed_publisher({{bob}}), 78);
+}
+}
+
+// check: EXECUTED
+// check: NewEpochEvent
+
+//! new-transaction
+//! sender: alice
+module AliceModule {
+    public fun f() {}
+}
+
+// check: EXECUTED
+
+//! new-transaction
+//! sender: bob
+module BobModule {
+    public fun f() {}
+}
+
+// check: INVALID_MODULE_PUBLISHER
+
+//! new-transaction
+//! sender: association
+// Unknown policies are rejected
+script {
+use 0x1::LibraModulePublishingPolicy;
+use 0x1::Vector;
+
+fun main(config: &signer) {
+    LibraModulePublishingPolicy::set(config, 3, Vector::empty());
+}
+}
+
+// check: ABORTED
+// check: 2
@@ -0,0 +1,50 @@
+// Tests for the non-aborting behavior of SortedMap functions
+script {
+use 0x1::SortedMap;
+
+fun main() {
+    let map = SortedMap::empty<u64, u64>();
+    assert(SortedMap::is_empty(&map), 8001);
+
+    // insertion out of order keeps the keys sorted
+    SortedMap::insert(&mut map, 300, 3);
+    SortedMap::insert(&mut map, 1, 0);
+    SortedMap::insert(&mut map, 256, 2);
+    SortedMap::insert(&mut map, 20, 1);
+    assert(SortedMap::length(&map) == 4, 8002);
+    assert(*SortedMap::key_at(&map, 0) == 1, 8003);
+    assert(*SortedMap::key_at(&map, 1) == 20, 8004);
+    assert(*SortedMap::key_at(&map, 2) == 256, 8005);
+    assert(*SortedMap::key_at(&map, 3) == 300, 8006);
+
+    // lookups
+    assert(SortedMap::contains_key(&map, &256), 8007);
+    assert(!SortedMap::contains_key(&map, &255), 8008);
+    assert(*SortedMap::borrow(&map, &20) == 1, 8009);
+    *SortedMap::borrow_mut(&mut map, &20) = 10;
+    assert(*SortedMap::value_at(&map, 1) == 10, 8010);
+
+    // range iteration over the keys in [10, 300)
+    let i = SortedMap::lower_bound(&map, &10);
+    let end = SortedMap::lower_bound(&map, &300);
+    assert(i == 1 && end == 3, 8011);
+    let sum = 0;
+    while (i < end) {
+        sum = sum + *SortedMap::value_at(&map, i);
+        i = i + 1;
+    };
+    assert(sum == 12, 8012);
+    assert(SortedMap::lower_bound(&map, &301) == 4, 8013);
+
+    // removal
+    let (key, value) = SortedMap::remove(&mut map, &1);
+    assert(key == 1 && value == 0, 8014);
+    let (key, value) = SortedMap::remove_at(&mut map, 2);
+    assert(key == 300 && value == 3, 8015);
+    let (_, _) = SortedMap::remove(&mut map, &20);
+    let (_, _) = SortedMap::remove(&mut map, &256);
+    SortedMap::destroy_empty(map);
+}
+}
+
+// check: EXECUTED
@@ -0,0 +1,60 @@
+// Test that SortedMap functions abort when they should
+
+script {
+use 0x1::SortedMap;
+
+fun main() {
+    let map = SortedMap::empty<u64, bool>();
+    SortedMap::insert(&mut map, 1, true);
+    SortedMap::insert(&mut map, 1, false);
+    SortedMap::destroy_empty(map);
+}
+}
+
+// check: ABORTED
+// check: 1
+
+
+//! new-transaction
+script {
+use 0x1::SortedMap;
+
+fun main() {
+    let map = SortedMap::empty<u64, bool>();
+    SortedMap::insert(&mut map, 1, true);
+    let _ = SortedMap::borrow(&map, &2);
+    SortedMap::destroy_empty(map);
+}
+}
+
+// check: ABORTED
+// check: 0
+
+
+//! new-transaction
+script {
+use 0x1::SortedMap;
+
+fun main() {
+    let map = SortedMap::empty<u64, bool>();
+    let (_, _) = SortedMap::remove(&mut map, &2);
+    SortedMap::destroy_empty(map);
+}
+}
+
+// check: ABORTED
+// check: 0
+
+
+//! new-transaction
+script {
+use 0x1::SortedMap;
+
+fun main() {
+    let map = SortedMap::empty<u64, bool>();
+    SortedMap::insert(&mut map, 1, true);
+    SortedMap::destroy_empty(map);
+}
+}
+
+// check: NATIVE_FUNCTION_ERROR
@@ -0,0 +1,99 @@
+//! account: alice
+
+// A module keeping a table of balances, which outlives the transactions writing to it
+
+//! sender: alice
+module Balances {
+    use 0x1::Table::{Self, Table};
+
+    resource struct T { balances: Table<address, u64> }
+
+    public fun publish(account: &signer) {
+        move_to(account, T { balances: Table::new(account) })
+    }
+
+    public fun set(addr: address, amount: u64) acquires T {
+        Table::upsert(&mut borrow_global_mut<T>({{alice}}).balances, addr, amount)
+    }
+
+    public fun get(addr: address): u64 acquires T {
+        Table::read(&borrow_global<T>({{alice}}).balances, addr)
+    }
+
+    public fun remove(addr: address): u64 acquires T {
+        Table::remove(&mut borrow_global_mut<T>({{alice}}).balances, addr)
+    }
+
+    public fun contains(addr: address): bool acquires T {
+        Table::contains(&borrow_global<T>({{alice}}).balances, addr)
+    }
+
+    public fun length(): u64 acquires T {
+        Table::length(&borrow_global<T>({{alice}}).balances)
+    }
+}
+// check: EXECUTED
+
+//! new-transaction
+//! sender: alice
+script {
+use {{alice}}::Balances;
+
+fun main(account: &signer) {
+    Balances::publish(account);
+    Balances::set(0x1, 10);
+    Balances::set(0x2, 20);
+    assert(Balances::get(0x1) == 10, 9000);
+    assert(Balances::length() == 2, 9001);
+}
+}
+// check: EXECUTED
+
+// The entries are read back from storage
+//! new-transaction
+script {
+use {{alice}}::Balances;
+
+fun main() {
+    assert(Balances::get(0x1) == 10, 9002);
+    assert(Balances::get(0x2) == 20, 9003);
+    assert(!Balances::contains(0x3), 9004);
+
+    Balances::set(0x1, 11);
+    assert(Balances::get(0x1) == 11, 9005);
+    assert(Balances::remove(0x2) == 20, 9006);
+    assert(!Balances::contains(0x2), 9007);
+    assert(Balances::length() == 1, 9008);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+use {{alice}}::Balances;
+
+fun main() {
+    assert(Balances::get(0x1) == 11, 9009);
+    assert(!Balances::contains(0x2), 9010);
+}
+}
+// check: EXECUTED
+
+// Tables hold resources too
+//! new-transaction
+//! sender: alice
+script {
+use 0x1::Table;
+use 0x1::Coin1::Coin1;
+use 0x1::Libra::{Self, Libra};
+
+fun main(account: &signer) {
+    let table = Table::new<u64, Libra<Coin1>>(account);
+    Table::add(&mut table, 0, Libra::zero<Coin1>());
+    assert(Table::contains(&table, 0), 9011);
+    Libra::destroy_zero(Table::remove(&mut table, 0));
+    assert(Table::is_empty(&table), 9012);
+    Table::destroy_empty(table);
+}
+}
+// check: EXECUTED
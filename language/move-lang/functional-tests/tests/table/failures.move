@@ -0,0 +1,51 @@
+// Test that Table functions fail when they should
+
+script {
+use 0x1::Table;
+
+fun main(account: &signer) {
+    let table = Table::new<u64, bool>(account);
+    Table::add(&mut table, 1, true);
+    Table::add(&mut table, 1, false);
+    Table::destroy_empty(table);
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+script {
+use 0x1::Table;
+
+fun main(account: &signer) {
+    let table = Table::new<u64, bool>(account);
+    Table::add(&mut table, 1, true);
+    let _ = Table::read(&table, 2);
+    Table::destroy_empty(table);
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+script {
+use 0x1::Table;
+
+fun main(account: &signer) {
+    let table = Table::new<u64, bool>(account);
+    let _ = Table::remove(&mut table, 2);
+    Table::destroy_empty(table);
+}
+}
+// check: NATIVE_FUNCTION_ERROR
+
+//! new-transaction
+script {
+use 0x1::Table;
+
+fun main(account: &signer) {
+    let table = Table::new<u64, bool>(account);
+    Table::add(&mut table, 1, true);
+    Table::destroy_empty(table);
+}
+}
+// check: ABORTED
+// check: 0
@@ -12,10 +12,13 @@
 script{
 use 0x1::LibraTimestamp;
 use 0x1::LibraBlock;
+use 0x1::Vector;
 
 fun main() {
     assert(LibraBlock::get_current_block_height() == 1, 73);
     assert(LibraTimestamp::now_microseconds() == 1000000, 76);
+    assert(Vector::length(&LibraBlock::get_parent_block_hash()) == 32, 78);
+    assert(Vector::is_empty(&LibraBlock::get_failed_proposers()), 79);
 }
 }
 
@@ -34,7 +37,15 @@ use 0x1::LibraBlock;
 use 0x1::Vector;
 
 fun main(account: &signer) {
-    LibraBlock::block_prologue(account, 1, 10, Vector::empty<address>(), {{vivian}});
+    LibraBlock::block_prologue(
+        account,
+        1,
+        10,
+        Vector::empty<address>(),
+        {{vivian}},
+        Vector::empty<u8>(),
+        Vector::empty<address>()
+    );
 }
 }
 // check: ABORTED
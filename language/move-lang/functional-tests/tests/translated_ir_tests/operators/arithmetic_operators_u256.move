@@ -0,0 +1,92 @@
+script {
+fun main() {
+    assert(0u256 + 0u256 == 0u256, 1000);
+    assert(0u256 + 1u256 == 1u256, 1001);
+    assert(1u256 + 1u256 == 2u256, 1002);
+
+    assert(340282366920938463463374607431768211455u256 + 1u256 == 340282366920938463463374607431768211456u256, 1100);
+
+    assert(0u256 + 115792089237316195423570985008687907853269984665640564039457584007913129639935u256 == 115792089237316195423570985008687907853269984665640564039457584007913129639935u256, 1200);
+    assert(1u256 + 115792089237316195423570985008687907853269984665640564039457584007913129639934u256 == 115792089237316195423570985008687907853269984665640564039457584007913129639935u256, 1201);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+fun main() {
+    1u256 + 115792089237316195423570985008687907853269984665640564039457584007913129639935u256;
+}
+}
+// check: ARITHMETIC_ERROR
+
+//! new-transaction
+script {
+fun main() {
+    assert(52u256 - 13u256 == 39u256, 2000);
+    assert(340282366920938463463374607431768211456u256 - 1u256 == 340282366920938463463374607431768211455u256, 2001);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+fun main() {
+    0u256 - 1u256;
+}
+}
+// check: ARITHMETIC_ERROR
+
+//! new-transaction
+script {
+fun main() {
+    assert(6u256 * 7u256 == 42u256, 3000);
+    assert(340282366920938463463374607431768211456u256 * 340282366920938463463374607431768211455u256 == 115792089237316195423570985008687907852929702298719625575994209400481361428480u256, 3001);
+    assert(57896044618658097711785492504343953926634992332820282019728792003956564819967u256 * 2u256 == 115792089237316195423570985008687907853269984665640564039457584007913129639934u256, 3002);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+fun main() {
+    57896044618658097711785492504343953926634992332820282019728792003956564819968u256 * 2u256;
+}
+}
+// check: ARITHMETIC_ERROR
+
+//! new-transaction
+script {
+fun main() {
+    assert(42u256 / 6u256 == 7u256, 4000);
+    assert(115792089237316195423570985008687907853269984665640564039457584007913129639935u256 / 340282366920938463463374607431768211456u256 == 340282366920938463463374607431768211455u256, 4001);
+    assert(115792089237316195423570985008687907853269984665640564039457584007913129639935u256 % 340282366920938463463374607431768211456u256 == 340282366920938463463374607431768211455u256, 4002);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+fun main() {
+    1u256 / 0u256;
+}
+}
+// check: ARITHMETIC_ERROR
+
+//! new-transaction
+script {
+fun main() {
+    assert((340282366920938463463374607431768211455u128 as u256) + 1u256 == 340282366920938463463374607431768211456u256, 5000);
+    assert((340282366920938463463374607431768211455u256 as u128) == 340282366920938463463374607431768211455u128, 5001);
+    assert((1u256 << 255) >> 255 == 1u256, 5002);
+}
+}
+// check: EXECUTED
+
+//! new-transaction
+script {
+fun main() {
+    (340282366920938463463374607431768211456u256 as u128);
+}
+}
+// check: ARITHMETIC_ERROR
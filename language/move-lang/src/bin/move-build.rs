@@ -33,6 +33,10 @@ pub struct Options {
     )]
     pub sender: Option<Address>,
 
+    /// The features enabled for the `#[cfg(feature = b"...")]` attributes
+    #[structopt(name = "FEATURE", short = cli::FEATURE_SHORT, long = cli::FEATURE)]
+    pub features: Vec<String>,
+
     /// The Move bytecode output directory
     #[structopt(
         name = "PATH_TO_OUTPUT_DIRECTORY",
@@ -49,6 +53,10 @@ pub struct Options {
         long = cli::SOURCE_MAP,
     )]
     pub emit_source_map: bool,
+
+    /// Save the error map of the modules, built from their error constants, to this file
+    #[structopt(name = "PATH_TO_ERROR_MAP_FILE", long = cli::ERROR_MAP)]
+    pub error_map: Option<String>,
 }
 
 pub fn main() -> anyhow::Result<()> {
@@ -56,9 +64,17 @@ pub fn main() -> anyhow::Result<()> {
         source_files,
         dependencies,
         sender,
+        features,
         out_dir,
         emit_source_map,
+        error_map,
     } = Options::from_args();
-    let (files, compiled_units) = move_lang::move_compile(&source_files, &dependencies, sender)?;
+    if let Some(error_map_path) = error_map {
+        let (_, error_mapping) =
+            move_lang::move_build_error_map(&source_files, &dependencies, sender, &features)?;
+        move_lang::output_error_map(&error_mapping, &error_map_path)?;
+    }
+    let (files, compiled_units) =
+        move_lang::move_compile(&source_files, &dependencies, sender, &features)?;
     move_lang::output_compiled_units(emit_source_map, files, compiled_units, &out_dir)
 }
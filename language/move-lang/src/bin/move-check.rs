@@ -35,6 +35,10 @@ pub struct Options {
         parse(try_from_str = cli::parse_address)
     )]
     pub sender: Option<Address>,
+
+    /// The features enabled for the `#[cfg(feature = b"...")]` attributes
+    #[structopt(name = "FEATURE", short = cli::FEATURE_SHORT, long = cli::FEATURE)]
+    pub features: Vec<String>,
 }
 
 pub fn main() -> anyhow::Result<()> {
@@ -42,6 +46,7 @@ pub fn main() -> anyhow::Result<()> {
         source_files,
         dependencies,
         sender,
+        features,
     } = Options::from_args();
-    move_lang::move_check(&source_files, &dependencies, sender)
+    move_lang::move_check(&source_files, &dependencies, sender, &features)
 }
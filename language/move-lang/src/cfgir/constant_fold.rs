@@ -9,6 +9,7 @@ use crate::{
     parser::ast::{BinOp, BinOp_, UnaryOp, UnaryOp_},
     shared::*,
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::convert::TryFrom;
 
@@ -179,30 +180,37 @@ fn fold_binary_op(
         (B::Add, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_add(u2)?),
         (B::Add, FV::U64(u1), FV::U64(u2)) => FV::U64(u1.checked_add(u2)?),
         (B::Add, FV::U128(u1), FV::U128(u2)) => FV::U128(u1.checked_add(u2)?),
+        (B::Add, FV::U256(u1), FV::U256(u2)) => FV::U256(u1.checked_add(u2)?),
 
         (B::Sub, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_sub(u2)?),
         (B::Sub, FV::U64(u1), FV::U64(u2)) => FV::U64(u1.checked_sub(u2)?),
         (B::Sub, FV::U128(u1), FV::U128(u2)) => FV::U128(u1.checked_sub(u2)?),
+        (B::Sub, FV::U256(u1), FV::U256(u2)) => FV::U256(u1.checked_sub(u2)?),
 
         (B::Mul, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_mul(u2)?),
         (B::Mul, FV::U64(u1), FV::U64(u2)) => FV::U64(u1.checked_mul(u2)?),
         (B::Mul, FV::U128(u1), FV::U128(u2)) => FV::U128(u1.checked_mul(u2)?),
+        (B::Mul, FV::U256(u1), FV::U256(u2)) => FV::U256(u1.checked_mul(u2)?),
 
         (B::Mod, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_rem(u2)?),
         (B::Mod, FV::U64(u1), FV::U64(u2)) => FV::U64(u1.checked_rem(u2)?),
         (B::Mod, FV::U128(u1), FV::U128(u2)) => FV::U128(u1.checked_rem(u2)?),
+        (B::Mod, FV::U256(u1), FV::U256(u2)) => FV::U256(u1.checked_rem(u2)?),
 
         (B::Div, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_div(u2)?),
         (B::Div, FV::U64(u1), FV::U64(u2)) => FV::U64(u1.checked_div(u2)?),
         (B::Div, FV::U128(u1), FV::U128(u2)) => FV::U128(u1.checked_div(u2)?),
+        (B::Div, FV::U256(u1), FV::U256(u2)) => FV::U256(u1.checked_div(u2)?),
 
         (B::Shl, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_shl(u2 as u32)?),
         (B::Shl, FV::U64(u1), FV::U8(u2)) => FV::U64(u1.checked_shl(u2 as u32)?),
         (B::Shl, FV::U128(u1), FV::U8(u2)) => FV::U128(u1.checked_shl(u2 as u32)?),
+        (B::Shl, FV::U256(u1), FV::U8(u2)) => FV::U256(u1 << u2),
 
         (B::Shr, FV::U8(u1), FV::U8(u2)) => FV::U8(u1.checked_shr(u2 as u32)?),
         (B::Shr, FV::U64(u1), FV::U8(u2)) => FV::U64(u1.checked_shr(u2 as u32)?),
         (B::Shr, FV::U128(u1), FV::U8(u2)) => FV::U128(u1.checked_shr(u2 as u32)?),
+        (B::Shr, FV::U256(u1), FV::U8(u2)) => FV::U256(u1 >> u2),
 
         //************************************
         // Pure arith
@@ -210,14 +218,17 @@ fn fold_binary_op(
         (B::BitOr, FV::U8(u1), FV::U8(u2)) => FV::U8(u1 | u2),
         (B::BitOr, FV::U64(u1), FV::U64(u2)) => FV::U64(u1 | u2),
         (B::BitOr, FV::U128(u1), FV::U128(u2)) => FV::U128(u1 | u2),
+        (B::BitOr, FV::U256(u1), FV::U256(u2)) => FV::U256(u1 | u2),
 
         (B::BitAnd, FV::U8(u1), FV::U8(u2)) => FV::U8(u1 & u2),
         (B::BitAnd, FV::U64(u1), FV::U64(u2)) => FV::U64(u1 & u2),
         (B::BitAnd, FV::U128(u1), FV::U128(u2)) => FV::U128(u1 & u2),
+        (B::BitAnd, FV::U256(u1), FV::U256(u2)) => FV::U256(u1 & u2),
 
         (B::Xor, FV::U8(u1), FV::U8(u2)) => FV::U8(u1 ^ u2),
         (B::Xor, FV::U64(u1), FV::U64(u2)) => FV::U64(u1 ^ u2),
         (B::Xor, FV::U128(u1), FV::U128(u2)) => FV::U128(u1 ^ u2),
+        (B::Xor, FV::U256(u1), FV::U256(u2)) => FV::U256(u1 ^ u2),
 
         //************************************
         // Logical
@@ -231,18 +242,22 @@ fn fold_binary_op(
         (B::Lt, FV::U8(u1), FV::U8(u2)) => FV::Bool(u1 < u2),
         (B::Lt, FV::U64(u1), FV::U64(u2)) => FV::Bool(u1 < u2),
         (B::Lt, FV::U128(u1), FV::U128(u2)) => FV::Bool(u1 < u2),
+        (B::Lt, FV::U256(u1), FV::U256(u2)) => FV::Bool(u1 < u2),
 
         (B::Gt, FV::U8(u1), FV::U8(u2)) => FV::Bool(u1 > u2),
         (B::Gt, FV::U64(u1), FV::U64(u2)) => FV::Bool(u1 > u2),
         (B::Gt, FV::U128(u1), FV::U128(u2)) => FV::Bool(u1 > u2),
+        (B::Gt, FV::U256(u1), FV::U256(u2)) => FV::Bool(u1 > u2),
 
         (B::Le, FV::U8(u1), FV::U8(u2)) => FV::Bool(u1 <= u2),
         (B::Le, FV::U64(u1), FV::U64(u2)) => FV::Bool(u1 <= u2),
         (B::Le, FV::U128(u1), FV::U128(u2)) => FV::Bool(u1 <= u2),
+        (B::Le, FV::U256(u1), FV::U256(u2)) => FV::Bool(u1 <= u2),
 
         (B::Ge, FV::U8(u1), FV::U8(u2)) => FV::Bool(u1 >= u2),
         (B::Ge, FV::U64(u1), FV::U64(u2)) => FV::Bool(u1 >= u2),
         (B::Ge, FV::U128(u1), FV::U128(u2)) => FV::Bool(u1 >= u2),
+        (B::Ge, FV::U256(u1), FV::U256(u2)) => FV::Bool(u1 >= u2),
 
         (B::Eq, v1, v2) => FV::Bool(v1 == v2),
         (B::Neq, v1, v2) => FV::Bool(v1 != v2),
@@ -262,14 +277,22 @@ fn fold_cast(loc: Loc, sp!(_, bt_): &BuiltinTypeName, v: FoldableValue) -> Optio
         (BT::U8, FV::U8(u)) => FV::U8(u),
         (BT::U8, FV::U64(u)) => FV::U8(u8::try_from(u).ok()?),
         (BT::U8, FV::U128(u)) => FV::U8(u8::try_from(u).ok()?),
+        (BT::U8, FV::U256(u)) => FV::U8(u.to_u8()?),
 
         (BT::U64, FV::U8(u)) => FV::U64(u as u64),
         (BT::U64, FV::U64(u)) => FV::U64(u),
         (BT::U64, FV::U128(u)) => FV::U64(u64::try_from(u).ok()?),
+        (BT::U64, FV::U256(u)) => FV::U64(u.to_u64()?),
 
         (BT::U128, FV::U8(u)) => FV::U128(u as u128),
         (BT::U128, FV::U64(u)) => FV::U128(u as u128),
         (BT::U128, FV::U128(u)) => FV::U128(u),
+        (BT::U128, FV::U256(u)) => FV::U128(u.to_u128()?),
+
+        (BT::U256, FV::U8(u)) => FV::U256(U256::from(u)),
+        (BT::U256, FV::U64(u)) => FV::U256(U256::from(u)),
+        (BT::U256, FV::U128(u)) => FV::U256(U256::from(u)),
+        (BT::U256, FV::U256(u)) => FV::U256(u),
 
         (_, v) => panic!("ICE unexpected cast while folding: {:?} as {:?}", v, bt_),
     };
@@ -285,6 +308,7 @@ fn evalue_(loc: Loc, fv: FoldableValue) -> UnannotatedExp_ {
         FV::U8(u) => V::U8(u),
         FV::U64(u) => V::U64(u),
         FV::U128(u) => V::U128(u),
+        FV::U256(u) => V::U256(u),
         FV::Bool(b) => V::Bool(b),
         FV::Address(a) => V::Address(a),
         FV::Bytearray(b) => V::Bytearray(b),
@@ -302,6 +326,7 @@ enum FoldableValue {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(Address),
     Bytearray(Vec<u8>),
@@ -314,6 +339,7 @@ fn foldable_value(sp!(_, v_): &Value) -> Option<FoldableValue> {
         V::U8(u) => FV::U8(*u),
         V::U64(u) => FV::U64(*u),
         V::U128(u) => FV::U128(*u),
+        V::U256(u) => FV::U256(*u),
         V::Bool(b) => FV::Bool(*b),
         V::Address(a) => FV::Address(*a),
         V::Bytearray(b) => FV::Bytearray(b.clone()),
@@ -334,6 +360,7 @@ impl PartialEq for FoldableValue {
             (FoldableValue::U8(x), FoldableValue::U8(y)) => x == y,
             (FoldableValue::U64(x), FoldableValue::U64(y)) => x == y,
             (FoldableValue::U128(x), FoldableValue::U128(y)) => x == y,
+            (FoldableValue::U256(x), FoldableValue::U256(y)) => x == y,
             (FoldableValue::Bool(x), FoldableValue::Bool(y)) => x == y,
             (FoldableValue::Address(x), FoldableValue::Address(y)) => x == y,
             (FoldableValue::Bytearray(x), FoldableValue::Bytearray(y)) => x == y,
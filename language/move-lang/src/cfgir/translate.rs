@@ -277,6 +277,7 @@ fn move_value_from_value(sp!(_, v_): Value) -> MoveValue {
         V::U8(u) => MV::U8(u),
         V::U64(u) => MV::U64(u),
         V::U128(u) => MV::U128(u),
+        V::U256(u) => MV::U256(u),
         V::Bool(b) => MV::Bool(b),
         V::Bytearray(v) => MV::Vector(v.into_iter().map(MV::U8).collect()),
     }
@@ -16,6 +16,11 @@ pub const DEFAULT_OUTPUT_DIR: &str = "move_build_output";
 pub const SOURCE_MAP: &str = "source-map";
 pub const SOURCE_MAP_SHORT: &str = "m";
 
+pub const ERROR_MAP: &str = "error-map";
+
+pub const FEATURE: &str = "feature";
+pub const FEATURE_SHORT: &str = "f";
+
 pub fn parse_address(s: &str) -> Result<Address, String> {
     Address::parse_str(s).map_err(|msg| format!("Invalid argument to '{}': {}", SENDER, msg))
 }
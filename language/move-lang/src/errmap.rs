@@ -0,0 +1,73 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Builds the `ErrorMapping` of a program from the error constants its modules declare: the
+//! `u64` constants whose name starts with `E`, described by their doc comment.
+
+use crate::{
+    errors::*,
+    expansion::ast::{Exp_, ModuleDefinition, Program, Value_},
+    parser::ast::{Identifier as _, ModuleIdent, ModuleIdent_},
+    CommentMap,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    errmap::{ErrorDescription, ErrorMapping},
+    identifier::Identifier,
+    language_storage::ModuleId,
+};
+
+const ERROR_PREFIX: &str = "E";
+
+/// Builds the error mapping of the source modules of `prog`. Dependencies are skipped, their
+/// error maps are built with their own sources.
+pub fn build(prog: &Program, comment_map: &CommentMap) -> Result<ErrorMapping, Errors> {
+    let mut mapping = ErrorMapping::default();
+    let mut errors = vec![];
+    for (ident, mdef) in &prog.modules {
+        if mdef.is_source_module {
+            module(&mut mapping, &mut errors, comment_map, ident, mdef)
+        }
+    }
+    if errors.is_empty() {
+        Ok(mapping)
+    } else {
+        Err(errors)
+    }
+}
+
+fn module(
+    mapping: &mut ErrorMapping,
+    errors: &mut Errors,
+    comment_map: &CommentMap,
+    ident: ModuleIdent,
+    mdef: &ModuleDefinition,
+) {
+    let ModuleIdent_ { address, name } = ident.0.value;
+    let module_id = ModuleId::new(
+        AccountAddress::new(address.to_u8()),
+        Identifier::new(name.0.value).unwrap(),
+    );
+    for (cname, constant) in &mdef.constants {
+        if !cname.value().starts_with(ERROR_PREFIX) {
+            continue;
+        }
+        let code = match &constant.value.value {
+            Exp_::Value(sp!(_, Value_::U64(code))) => *code,
+            Exp_::InferredNum(code) if *code <= u64::MAX as u128 => *code as u64,
+            _ => continue,
+        };
+        let code_description = comment_map
+            .get(constant.loc.file())
+            .and_then(|comments| comments.get(&constant.loc.span().start()))
+            .map(|comment| comment.trim().to_string())
+            .unwrap_or_default();
+        let description = ErrorDescription {
+            code_name: cname.value().to_string(),
+            code_description,
+        };
+        if let Err(e) = mapping.add_module_error(module_id.clone(), code, description) {
+            errors.push(vec![(constant.loc, e.to_string())])
+        }
+    }
+}
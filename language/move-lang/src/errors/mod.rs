@@ -3,7 +3,7 @@
 
 use codespan::{FileId, Files, Span};
 use codespan_reporting::{
-    diagnostic::{Diagnostic, Label},
+    diagnostic::{Diagnostic, Label, Severity},
     term::{
         emit,
         termcolor::{Buffer, ColorChoice, StandardStream, WriteColor},
@@ -22,6 +22,9 @@ pub type Error = Vec<(Loc, String)>;
 pub type ErrorSlice = [(Loc, String)];
 pub type HashableError = Vec<(&'static str, usize, usize, String)>;
 
+// Warnings are reported like errors but do not stop the compilation
+pub type Warnings = Vec<Error>;
+
 pub type FilesSourceText = HashMap<&'static str, String>;
 
 type FileMapping = HashMap<&'static str, FileId>;
@@ -48,6 +51,14 @@ pub fn report_errors(files: FilesSourceText, errors: Errors) -> ! {
     std::process::exit(1)
 }
 
+pub fn report_warnings(files: &FilesSourceText, warnings: Warnings) {
+    if warnings.is_empty() {
+        return;
+    }
+    let mut writer = StandardStream::stderr(ColorChoice::Auto);
+    output_diagnostics(&mut writer, files.clone(), warnings, Severity::Warning);
+}
+
 pub fn report_errors_to_buffer(files: FilesSourceText, errors: Errors) -> Vec<u8> {
     let mut writer = Buffer::no_color();
     output_errors(&mut writer, files, errors);
@@ -61,6 +72,15 @@ pub fn report_errors_to_color_buffer(files: FilesSourceText, errors: Errors) ->
 }
 
 fn output_errors<W: WriteColor>(writer: &mut W, sources: FilesSourceText, errors: Errors) {
+    output_diagnostics(writer, sources, errors, Severity::Error)
+}
+
+fn output_diagnostics<W: WriteColor>(
+    writer: &mut W,
+    sources: FilesSourceText,
+    errors: Errors,
+    severity: Severity,
+) {
     assert!(!errors.is_empty());
     let mut files = Files::new();
     let mut file_mapping = HashMap::new();
@@ -68,7 +88,7 @@ fn output_errors<W: WriteColor>(writer: &mut W, sources: FilesSourceText, errors
         let id = files.add(fname, source);
         file_mapping.insert(fname, id);
     }
-    render_errors(writer, &files, &file_mapping, errors);
+    render_errors(writer, &files, &file_mapping, errors, severity);
 }
 
 fn hashable_error(error: &ErrorSlice) -> HashableError {
@@ -90,6 +110,7 @@ fn render_errors<W: WriteColor>(
     files: &Files<String>,
     file_mapping: &FileMapping,
     mut errors: Errors,
+    severity: Severity,
 ) {
     errors.sort_by(|e1, e2| {
         let loc1: &Loc = &e1[0].0;
@@ -103,7 +124,7 @@ fn render_errors<W: WriteColor>(
             continue;
         }
         seen.insert(hashable_error);
-        let err = render_error(files, file_mapping, error);
+        let err = render_error(files, file_mapping, error, severity);
         emit(writer, &Config::default(), &files, &err).unwrap()
     }
 }
@@ -117,14 +138,19 @@ fn convert_loc(files: &Files<String>, file_mapping: &FileMapping, loc: Loc) -> (
     (id, Span::new(begin_index, end_index))
 }
 
-fn render_error(files: &Files<String>, file_mapping: &FileMapping, mut error: Error) -> Diagnostic {
+fn render_error(
+    files: &Files<String>,
+    file_mapping: &FileMapping,
+    mut error: Error,
+    severity: Severity,
+) -> Diagnostic {
     let mk_lbl = |err: (Loc, String)| -> Label {
         let (id, span) = convert_loc(files, file_mapping, err.0);
         Label::new(id, span, err.1)
     };
     let err = error.remove(0);
     // TODO message with each error msg
-    let mut diag = Diagnostic::new_error("", mk_lbl(err));
+    let mut diag = Diagnostic::new(severity, "", mk_lbl(err));
     diag = diag.with_secondary_labels(error.into_iter().map(mk_lbl));
     diag
 }
@@ -9,6 +9,7 @@ use crate::{
     },
     shared::{ast_debug::*, unique_map::UniqueMap, *},
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::{
     collections::{BTreeMap, BTreeSet, VecDeque},
@@ -94,6 +95,9 @@ pub type FunctionBody = Spanned<FunctionBody_>;
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub struct SpecId(usize);
 
+// The location of a `#[deprecated]` attribute, with its optional note
+pub type Deprecation = Spanned<Option<String>>;
+
 #[derive(PartialEq, Debug)]
 pub struct Function {
     pub loc: Loc,
@@ -102,6 +106,7 @@ pub struct Function {
     pub acquires: Vec<ModuleAccess>,
     pub body: FunctionBody,
     pub specs: BTreeMap<SpecId, SpecBlock>,
+    pub deprecation: Option<Deprecation>,
 }
 
 //**************************************************************************************************
@@ -219,6 +224,8 @@ pub enum Value_ {
     U64(u64),
     // <num>u128
     U128(u128),
+    // <num>u256
+    U256(U256),
     // true
     // false
     Bool(bool),
@@ -696,6 +703,7 @@ impl AstDebug for Value_ {
             V::U8(u) => format!("{}u8", u),
             V::U64(u) => format!("{}u64", u),
             V::U128(u) => format!("{}u128", u),
+            V::U256(u) => format!("{}u256", u),
             V::Bool(b) => format!("{}", b),
             V::Bytearray(v) => format!("{:?}", v),
         })
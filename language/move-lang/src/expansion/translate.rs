@@ -220,7 +220,12 @@ fn set_sender_address(context: &mut Context, loc: Loc, sender: Option<Address>)
 }
 
 fn module_(context: &mut Context, mdef: P::ModuleDefinition) -> (ModuleIdent, E::ModuleDefinition) {
-    let P::ModuleDefinition { loc, name, members } = mdef;
+    let P::ModuleDefinition {
+        loc,
+        attributes: _,
+        name,
+        members,
+    } = mdef;
     let _ = check_restricted_self_name(context, "module", &name.0);
 
     let name_loc = name.loc();
@@ -601,6 +606,7 @@ fn struct_def_(
 ) -> (StructName, E::StructDefinition) {
     let P::StructDefinition {
         loc,
+        attributes: _,
         name,
         resource_opt,
         type_parameters: pty_params,
@@ -666,6 +672,7 @@ fn constant_(context: &mut Context, pconstant: P::Constant) -> (ConstantName, E:
     assert!(context.exp_specs.is_empty());
     let P::Constant {
         loc,
+        attributes: _,
         name,
         signature: psignature,
         value: pvalue,
@@ -699,12 +706,14 @@ fn function(
 fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E::Function) {
     let P::Function {
         loc,
+        attributes,
         name,
         visibility,
         signature: psignature,
         body: pbody,
         acquires,
     } = pfunction;
+    let deprecation = crate::parser::attributes::deprecation(&attributes);
     assert!(context.exp_specs.is_empty());
     let old_aliases = context.new_alias_scope(AliasMap::new());
     let signature = function_signature(context, psignature);
@@ -721,6 +730,7 @@ fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E:
         acquires,
         body,
         specs,
+        deprecation,
     };
     context.set_to_outer_scope(old_aliases);
 
@@ -1246,6 +1256,7 @@ fn value(context: &mut Context, sp!(loc, pvalue_): P::Value) -> Option<E::Value>
         PV::U8(u) => EV::U8(u),
         PV::U64(u) => EV::U64(u),
         PV::U128(u) => EV::U128(u),
+        PV::U256(u) => EV::U256(u),
         PV::Bool(b) => EV::Bool(b),
         PV::HexString(s) => match hex_string::decode(loc, &s) {
             Ok(v) => EV::Bytearray(v),
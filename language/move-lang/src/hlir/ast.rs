@@ -370,7 +370,7 @@ impl BaseType_ {
         use BuiltinTypeName_::*;
 
         let kind = match b_ {
-            U8 | U64 | U128 | Bool | Address => sp(loc, Kind_::Copyable),
+            U8 | U64 | U128 | U256 | Bool | Address => sp(loc, Kind_::Copyable),
             Signer => sp(loc, Kind_::Resource),
             Vector => {
                 assert!(
@@ -414,6 +414,10 @@ impl BaseType_ {
     pub fn u128(loc: Loc) -> BaseType {
         Self::builtin(loc, BuiltinTypeName_::U128, vec![])
     }
+
+    pub fn u256(loc: Loc) -> BaseType {
+        Self::builtin(loc, BuiltinTypeName_::U256, vec![])
+    }
 }
 
 impl SingleType_ {
@@ -441,6 +445,10 @@ impl SingleType_ {
         Self::base(BaseType_::u128(loc))
     }
 
+    pub fn u256(loc: Loc) -> SingleType {
+        Self::base(BaseType_::u256(loc))
+    }
+
     pub fn kind(&self, loc: Loc) -> Kind {
         match self {
             SingleType_::Ref(_, _) => sp(loc, Kind_::Copyable),
@@ -478,6 +486,10 @@ impl Type_ {
         Self::single(SingleType_::u128(loc))
     }
 
+    pub fn u256(loc: Loc) -> Type {
+        Self::single(SingleType_::u256(loc))
+    }
+
     pub fn type_at_index(&self, idx: usize) -> &SingleType {
         match self {
             Type_::Unit => panic!("ICE type mismatch on index lookup"),
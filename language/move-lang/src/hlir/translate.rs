@@ -1103,7 +1103,8 @@ fn exp_impl(context: &mut Context, result: &mut Block, e: T::Exp) -> H::Exp {
             let bt = match rhs_ty.value.builtin_name() {
                 Some(bt @ sp!(_, BT::U8))
                 | Some(bt @ sp!(_, BT::U64))
-                | Some(bt @ sp!(_, BT::U128)) => bt.clone(),
+                | Some(bt @ sp!(_, BT::U128))
+                | Some(bt @ sp!(_, BT::U256)) => bt.clone(),
                 _ => panic!("ICE typing failed for cast"),
             };
             HE::Cast(e, bt)
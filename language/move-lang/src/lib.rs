@@ -9,6 +9,7 @@ extern crate move_ir_types;
 pub mod cfgir;
 pub mod command_line;
 pub mod compiled_unit;
+pub mod errmap;
 pub mod errors;
 pub mod expansion;
 pub mod hlir;
@@ -24,6 +25,7 @@ use anyhow::anyhow;
 use codespan::{ByteIndex, Span};
 use compiled_unit::CompiledUnit;
 use errors::*;
+use move_core_types::errmap::ErrorMapping;
 use move_ir_types::location::*;
 use parser::syntax::parse_file_string;
 use shared::Address;
@@ -46,28 +48,34 @@ pub const SOURCE_MAP_EXTENSION: &str = "mvsm";
 
 /// Given a set of targets and a set of dependencies
 /// - Checks the targets with the dependencies (targets can be dependencies of other targets)
+/// - Reports the warnings, e.g. calls to deprecated functions, to stderr
 /// Does not run compile to Move bytecode
 /// Very large programs might fail on compilation even though they have been checked due to size
 ///   limitations of the Move bytecode
+/// Only the items whose `#[cfg(...)]` attributes hold for the enabled `features` are checked
 pub fn move_check(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+    features: &[String],
 ) -> anyhow::Result<()> {
-    let (files, errors) = move_check_no_report(targets, deps, sender_opt)?;
-    if !errors.is_empty() {
-        errors::report_errors(files, errors)
+    let (files, pprog_and_comments_res) = parse_program(targets, deps, features)?;
+    let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
+    match check_program(pprog_res, sender_opt) {
+        Err(errors) => errors::report_errors(files, errors),
+        Ok((_, warnings)) => errors::report_warnings(&files, warnings),
     }
     Ok(())
 }
 
-/// Move check but it returns the errors instead of reporting them to stderr
+/// Move check but it returns the errors instead of reporting them to stderr. Warnings are dropped
 pub fn move_check_no_report(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+    features: &[String],
 ) -> anyhow::Result<(FilesSourceText, Errors)> {
-    let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
+    let (files, pprog_and_comments_res) = parse_program(targets, deps, features)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
     match check_program(pprog_res, sender_opt) {
         Err(errors) => Ok((files, errors)),
@@ -77,33 +85,41 @@ pub fn move_check_no_report(
 
 /// Given a set of targets and a set of dependencies
 /// - Checks the targets with the dependencies (targets can be dependencies of other targets)
+/// - Reports the warnings, e.g. calls to deprecated functions, to stderr
 /// - Compiles the targets to Move bytecode
 /// Does not run the Move bytecode verifier on the compiled targets, as the Move front end should
 ///   be more restrictive
+/// Only the items whose `#[cfg(...)]` attributes hold for the enabled `features` are compiled
 pub fn move_compile(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+    features: &[String],
 ) -> anyhow::Result<(FilesSourceText, Vec<CompiledUnit>)> {
-    let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
+    let (files, pprog_and_comments_res) = parse_program(targets, deps, features)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
     match compile_program(pprog_res, sender_opt) {
         Err(errors) => errors::report_errors(files, errors),
-        Ok(compiled_units) => Ok((files, compiled_units)),
+        Ok((compiled_units, warnings)) => {
+            errors::report_warnings(&files, warnings);
+            Ok((files, compiled_units))
+        }
     }
 }
 
-/// Move compile but it returns the errors instead of reporting them to stderr
+/// Move compile but it returns the errors instead of reporting them to stderr. Warnings are
+/// dropped
 pub fn move_compile_no_report(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+    features: &[String],
 ) -> anyhow::Result<(FilesSourceText, Result<Vec<CompiledUnit>, Errors>)> {
-    let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
+    let (files, pprog_and_comments_res) = parse_program(targets, deps, features)?;
     let pprog_res = pprog_and_comments_res.map(|(pprog, _)| pprog);
     Ok(match compile_program(pprog_res, sender_opt) {
         Err(errors) => (files, Err(errors)),
-        Ok(units) => (files, Ok(units)),
+        Ok((units, _warnings)) => (files, Ok(units)),
     })
 }
 
@@ -114,11 +130,12 @@ pub fn move_compile_to_expansion_no_report(
     targets: &[String],
     deps: &[String],
     sender_opt: Option<Address>,
+    features: &[String],
 ) -> anyhow::Result<(
     FilesSourceText,
     Result<(expansion::ast::Program, CommentMap), Errors>,
 )> {
-    let (files, pprog_and_comments_res) = parse_program(targets, deps)?;
+    let (files, pprog_and_comments_res) = parse_program(targets, deps, features)?;
     let res = pprog_and_comments_res.and_then(|(pprog, comment_map)| {
         let (eprog, errors) = expansion::translate::program(pprog, sender_opt);
         check_errors(errors)?;
@@ -127,6 +144,32 @@ pub fn move_compile_to_expansion_no_report(
     Ok((files, res))
 }
 
+/// Given a set of targets and a set of dependencies
+/// - Checks the targets with the dependencies up to the expansion phase
+/// - Builds the error mapping of the modules in the targets, from their error constants
+pub fn move_build_error_map(
+    targets: &[String],
+    deps: &[String],
+    sender_opt: Option<Address>,
+    features: &[String],
+) -> anyhow::Result<(FilesSourceText, ErrorMapping)> {
+    let (files, eprog_and_comments_res) =
+        move_compile_to_expansion_no_report(targets, deps, sender_opt, features)?;
+    match eprog_and_comments_res
+        .and_then(|(eprog, comment_map)| errmap::build(&eprog, &comment_map))
+    {
+        Err(errors) => errors::report_errors(files, errors),
+        Ok(error_mapping) => Ok((files, error_mapping)),
+    }
+}
+
+/// Writes the error mapping to `path`, in its LCS serialized form
+pub fn output_error_map(error_mapping: &ErrorMapping, path: &str) -> anyhow::Result<()> {
+    let bytes = error_mapping.to_bytes()?;
+    File::create(path)?.write_all(&bytes)?;
+    Ok(())
+}
+
 //**************************************************************************************************
 // Utils
 //**************************************************************************************************
@@ -204,23 +247,24 @@ pub fn output_compiled_units(
 fn check_program(
     prog: Result<parser::ast::Program, Errors>,
     sender_opt: Option<Address>,
-) -> Result<cfgir::ast::Program, Errors> {
+) -> Result<(cfgir::ast::Program, Warnings), Errors> {
     let (eprog, errors) = expansion::translate::program(prog?, sender_opt);
-    let (nprog, errors) = naming::translate::program(eprog, errors);
+    let (nprog, errors, warnings) = naming::translate::program(eprog, errors);
     let (tprog, errors) = typing::translate::program(nprog, errors);
     check_errors(errors)?;
     let (hprog, errors) = hlir::translate::program(tprog);
     let (cprog, errors) = cfgir::translate::program(errors, hprog);
     check_errors(errors)?;
-    Ok(cprog)
+    Ok((cprog, warnings))
 }
 
 fn compile_program(
     prog: Result<parser::ast::Program, Errors>,
     sender_opt: Option<Address>,
-) -> Result<Vec<CompiledUnit>, Errors> {
-    let cprog = check_program(prog, sender_opt)?;
-    to_bytecode::translate::program(cprog)
+) -> Result<(Vec<CompiledUnit>, Warnings), Errors> {
+    let (cprog, warnings) = check_program(prog, sender_opt)?;
+    let units = to_bytecode::translate::program(cprog)?;
+    Ok((units, warnings))
 }
 
 //**************************************************************************************************
@@ -230,6 +274,7 @@ fn compile_program(
 fn parse_program(
     targets: &[String],
     deps: &[String],
+    features: &[String],
 ) -> anyhow::Result<(
     FilesSourceText,
     Result<(parser::ast::Program, CommentMap), Errors>,
@@ -262,13 +307,13 @@ fn parse_program(
     }
 
     let res = if errors.is_empty() {
-        Ok((
-            parser::ast::Program {
-                source_definitions,
-                lib_definitions,
-            },
-            source_comments,
-        ))
+        let pprog = parser::ast::Program {
+            source_definitions,
+            lib_definitions,
+        };
+        let features = features.iter().cloned().collect();
+        let (pprog, errors) = parser::attributes::program(pprog, &features);
+        check_errors(errors).map(|()| (pprog, source_comments))
     } else {
         Err(errors)
     };
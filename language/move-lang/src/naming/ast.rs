@@ -122,6 +122,8 @@ pub enum BuiltinTypeName_ {
     U64,
     // u128
     U128,
+    // u256
+    U256,
     // Vector
     Vector,
     // bool
@@ -271,6 +273,7 @@ impl BuiltinTypeName_ {
     pub const U_8: &'static str = "u8";
     pub const U_64: &'static str = "u64";
     pub const U_128: &'static str = "u128";
+    pub const U_256: &'static str = "u256";
     pub const BOOL: &'static str = "bool";
     pub const VECTOR: &'static str = "vector";
 
@@ -281,6 +284,7 @@ impl BuiltinTypeName_ {
         s.insert(Self::U_8);
         s.insert(Self::U_64);
         s.insert(Self::U_128);
+        s.insert(Self::U_256);
         s.insert(Self::BOOL);
         s.insert(Self::VECTOR);
         s
@@ -292,6 +296,7 @@ impl BuiltinTypeName_ {
         s.insert(BT::U8);
         s.insert(BT::U64);
         s.insert(BT::U128);
+        s.insert(BT::U256);
         s
     }
 
@@ -316,6 +321,7 @@ impl BuiltinTypeName_ {
             BT::U_8 => Some(BT::U8),
             BT::U_64 => Some(BT::U64),
             BT::U_128 => Some(BT::U128),
+            BT::U_256 => Some(BT::U256),
             BT::BOOL => Some(BT::Bool),
             BT::VECTOR => Some(BT::Vector),
             _ => None,
@@ -326,7 +332,7 @@ impl BuiltinTypeName_ {
         use BuiltinTypeName_::*;
         // Match here to make sure this function is fixed when collections are added
         match self {
-            Address | Signer | U8 | U64 | U128 | Bool => vec![],
+            Address | Signer | U8 | U64 | U128 | U256 | Bool => vec![],
             Vector => vec![Spanned::new(loc, Kind_::Unknown)],
         }
     }
@@ -402,7 +408,7 @@ impl Type_ {
         use BuiltinTypeName_::*;
 
         let kind = match b.value {
-            U8 | U64 | U128 | Address | Bool => Some(sp(b.loc, Kind_::Copyable)),
+            U8 | U64 | U128 | U256 | Address | Bool => Some(sp(b.loc, Kind_::Copyable)),
             Signer => Some(sp(b.loc, Kind_::Resource)),
             Vector => None,
         };
@@ -438,6 +444,10 @@ impl Type_ {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::U128), vec![])
     }
 
+    pub fn u256(loc: Loc) -> Type {
+        Self::builtin(loc, sp(loc, BuiltinTypeName_::U256), vec![])
+    }
+
     pub fn vector(loc: Loc, elem: Type) -> Type {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::Vector), vec![elem])
     }
@@ -470,6 +480,7 @@ impl Value_ {
             U8(_) => Type_::u8(loc),
             U64(_) => Type_::u64(loc),
             U128(_) => Type_::u128(loc),
+            U256(_) => Type_::u256(loc),
             Bool(_) => Type_::bool(loc),
             Bytearray(_) => Type_::vector(loc, Type_::u8(loc)),
         }
@@ -492,6 +503,7 @@ impl fmt::Display for BuiltinTypeName_ {
                 BT::U8 => BT::U_8,
                 BT::U64 => BT::U_64,
                 BT::U128 => BT::U_128,
+                BT::U256 => BT::U_256,
                 BT::Bool => BT::BOOL,
                 BT::Vector => BT::VECTOR,
             }
@@ -36,10 +36,12 @@ impl ResolvedType {
 
 struct Context {
     errors: Errors,
+    warnings: Warnings,
     current_module: Option<ModuleIdent>,
     scoped_types: BTreeMap<ModuleIdent, BTreeMap<String, (Loc, ModuleIdent, Option<Kind>)>>,
     unscoped_types: BTreeMap<String, ResolvedType>,
     scoped_functions: BTreeMap<ModuleIdent, BTreeMap<String, Loc>>,
+    deprecated_functions: BTreeMap<ModuleIdent, BTreeMap<String, E::Deprecation>>,
     unscoped_constants: BTreeMap<String, Loc>,
     scoped_constants: BTreeMap<ModuleIdent, BTreeMap<String, Loc>>,
 }
@@ -74,6 +76,21 @@ impl Context {
                 (mident, mems)
             })
             .collect();
+        let deprecated_functions = prog
+            .modules
+            .iter()
+            .map(|(mident, mdef)| {
+                let mems = mdef
+                    .functions
+                    .iter()
+                    .filter_map(|(n, f)| {
+                        let deprecation = f.deprecation.clone()?;
+                        Some((n.value().to_string(), deprecation))
+                    })
+                    .collect();
+                (mident, mems)
+            })
+            .collect();
         let scoped_constants = prog
             .modules
             .iter()
@@ -92,9 +109,11 @@ impl Context {
             .collect();
         Self {
             errors,
+            warnings: vec![],
             current_module: None,
             scoped_types,
             scoped_functions,
+            deprecated_functions,
             scoped_constants,
             unscoped_types,
             unscoped_constants: BTreeMap::new(),
@@ -110,6 +129,11 @@ impl Context {
         self.errors
     }
 
+    fn warning(&mut self, e: Vec<(Loc, impl Into<String>)>) {
+        self.warnings
+            .push(e.into_iter().map(|(loc, msg)| (loc, msg.into())).collect())
+    }
+
     fn has_errors(&self) -> bool {
         !self.errors.is_empty()
     }
@@ -166,8 +190,34 @@ impl Context {
                 )]);
                 None
             }
-            Some(_) => Some(FunctionName(n.clone())),
+            Some(_) => {
+                self.check_deprecation(loc, m, n);
+                Some(FunctionName(n.clone()))
+            }
+        }
+    }
+
+    // Calls to a deprecated function are reported, unless they come from its own module
+    fn check_deprecation(&mut self, loc: Loc, m: &ModuleIdent, n: &Name) {
+        if self.current_module.as_ref() == Some(m) {
+            return;
         }
+        let deprecation = match self
+            .deprecated_functions
+            .get(m)
+            .and_then(|functions| functions.get(&n.value))
+        {
+            None => return,
+            Some(deprecation) => deprecation.clone(),
+        };
+        let msg = match &deprecation.value {
+            None => format!("Use of deprecated function '{}::{}'", m, n),
+            Some(note) => format!("Use of deprecated function '{}::{}': {}", m, n, note),
+        };
+        self.warning(vec![
+            (loc, msg),
+            (deprecation.loc, "Deprecated here".to_string()),
+        ]);
     }
 
     fn resolve_module_constant(
@@ -289,12 +339,17 @@ impl Context {
 // Entry
 //**************************************************************************************************
 
-pub fn program(prog: E::Program, errors: Errors) -> (N::Program, Errors) {
+pub fn program(prog: E::Program, errors: Errors) -> (N::Program, Errors, Warnings) {
     let mut context = Context::new(&prog, errors);
     let mut modules = modules(&mut context, prog.modules);
     let scripts = scripts(&mut context, prog.scripts);
     super::uses::verify(&mut context.errors, &mut modules);
-    (N::Program { modules, scripts }, context.get_errors())
+    let warnings = std::mem::take(&mut context.warnings);
+    (
+        N::Program { modules, scripts },
+        context.get_errors(),
+        warnings,
+    )
 }
 
 fn modules(
@@ -352,6 +407,7 @@ fn script(context: &mut Context, escript: E::Script) -> N::Script {
         function: efunction,
         specs: _specs,
     } = escript;
+    context.current_module = None;
     let outer_unscoped = context.save_unscoped();
     for (n, _) in &econstants {
         let sp!(loc, s) = n.0;
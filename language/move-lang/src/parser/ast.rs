@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::shared::{ast_debug::*, Address, Identifier, Name, TName};
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::fmt;
 
@@ -77,6 +78,25 @@ pub enum Use {
     Members(ModuleIdent, Vec<(Name, Option<Name>)>),
 }
 
+//**************************************************************************************************
+// Attributes
+//**************************************************************************************************
+
+// #[a, b = <value>, c(d, e = <value>)]
+#[derive(Debug, PartialEq)]
+pub enum Attribute_ {
+    // a
+    Name(Name),
+    // b = <value>
+    Assigned(Name, Value),
+    // c(d, e = <value>)
+    Parameterized(Name, Vec<Attribute>),
+}
+pub type Attribute = Spanned<Attribute_>;
+
+// All the attributes of one #[...]
+pub type Attributes = Spanned<Vec<Attribute>>;
+
 //**************************************************************************************************
 // Modules
 //**************************************************************************************************
@@ -94,6 +114,7 @@ pub struct ModuleIdent(pub Spanned<ModuleIdent_>);
 #[derive(Debug)]
 pub struct ModuleDefinition {
     pub loc: Loc,
+    pub attributes: Vec<Attributes>,
     pub name: ModuleName,
     pub members: Vec<ModuleMember>,
 }
@@ -119,6 +140,7 @@ pub type ResourceLoc = Option<Loc>;
 #[derive(Debug, PartialEq)]
 pub struct StructDefinition {
     pub loc: Loc,
+    pub attributes: Vec<Attributes>,
     pub resource_opt: ResourceLoc,
     pub name: StructName,
     pub type_parameters: Vec<(Name, Kind)>,
@@ -164,6 +186,7 @@ pub type FunctionBody = Spanned<FunctionBody_>;
 // (public?) native foo<T1(: copyable?), ..., TN(: copyable?)>(x1: t1, ..., xn: tn): t1 * ... * tn;
 pub struct Function {
     pub loc: Loc,
+    pub attributes: Vec<Attributes>,
     pub visibility: FunctionVisibility,
     pub signature: FunctionSignature,
     pub acquires: Vec<ModuleAccess>,
@@ -180,6 +203,7 @@ new_name!(ConstantName);
 #[derive(PartialEq, Debug)]
 pub struct Constant {
     pub loc: Loc,
+    pub attributes: Vec<Attributes>,
     pub signature: Type,
     pub name: ConstantName,
     pub value: Exp,
@@ -373,6 +397,8 @@ pub enum Value_ {
     U64(u64),
     // <num>u128
     U128(u128),
+    // <num>u256
+    U256(U256),
     // true
     // false
     Bool(bool),
@@ -777,9 +803,11 @@ impl AstDebug for ModuleDefinition {
     fn ast_debug(&self, w: &mut AstWriter) {
         let ModuleDefinition {
             loc: _loc,
+            attributes,
             name,
             members,
         } = self;
+        attributes.ast_debug(w);
         w.write(&format!("module {}", name));
         w.block(|w| {
             for mem in members {
@@ -789,6 +817,33 @@ impl AstDebug for ModuleDefinition {
     }
 }
 
+impl AstDebug for Vec<Attributes> {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        for attributes in self {
+            w.write("#[");
+            w.comma(&attributes.value, |w, attribute| attribute.ast_debug(w));
+            w.writeln("]");
+        }
+    }
+}
+
+impl AstDebug for Attribute_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        match self {
+            Attribute_::Name(n) => w.write(&n.value),
+            Attribute_::Assigned(n, value) => {
+                w.write(&format!("{} = ", n));
+                value.ast_debug(w);
+            }
+            Attribute_::Parameterized(n, inner) => {
+                w.write(&format!("{}(", n));
+                w.comma(inner, |w, attribute| attribute.ast_debug(w));
+                w.write(")");
+            }
+        }
+    }
+}
+
 impl AstDebug for ModuleMember {
     fn ast_debug(&self, w: &mut AstWriter) {
         match self {
@@ -830,11 +885,13 @@ impl AstDebug for StructDefinition {
     fn ast_debug(&self, w: &mut AstWriter) {
         let StructDefinition {
             loc: _loc,
+            attributes,
             resource_opt,
             name,
             type_parameters,
             fields,
         } = self;
+        attributes.ast_debug(w);
         if let StructFields::Native(_) = fields {
             w.write("native ");
         }
@@ -1018,12 +1075,14 @@ impl AstDebug for Function {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Function {
             loc: _loc,
+            attributes,
             visibility,
             signature,
             acquires,
             name,
             body,
         } = self;
+        attributes.ast_debug(w);
         visibility.ast_debug(w);
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
@@ -1074,10 +1133,12 @@ impl AstDebug for Constant {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Constant {
             loc: _loc,
+            attributes,
             name,
             signature,
             value,
         } = self;
+        attributes.ast_debug(w);
         w.write(&format!("const {}:", name));
         signature.ast_debug(w);
         w.write(" = ");
@@ -1383,6 +1444,7 @@ impl AstDebug for Value_ {
             V::U8(u) => format!("{}u8", u),
             V::U64(u) => format!("{}u64", u),
             V::U128(u) => format!("{}u128", u),
+            V::U256(u) => format!("{}u256", u),
             V::Bool(b) => format!("{}", b),
             V::HexString(s) => format!("x\"{}\"", s),
             V::ByteString(s) => format!("b\"{}\"", s),
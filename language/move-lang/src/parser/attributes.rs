@@ -0,0 +1,265 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Processing of the attributes given to modules and module members:
+//! - `#[cfg(<predicate>)]` removes the item unless the predicate holds for the features enabled
+//!   in the build. Predicates are `feature = b"<name>"`, `not(<predicate>)`,
+//!   `all(<predicate>, ...)` and `any(<predicate>, ...)`.
+//! - `#[deprecated]` and `#[deprecated(note = b"<note>")]` mark a function whose calls from
+//!   other modules are reported with a warning.
+//! Any other attribute is an error.
+
+use crate::{errors::*, parser::ast::*, shared::Identifier};
+use move_ir_types::location::*;
+use std::collections::BTreeSet;
+
+const CFG: &str = "cfg";
+const DEPRECATED: &str = "deprecated";
+const FEATURE: &str = "feature";
+const NOTE: &str = "note";
+const NOT: &str = "not";
+const ALL: &str = "all";
+const ANY: &str = "any";
+
+//**************************************************************************************************
+// Context
+//**************************************************************************************************
+
+struct Context<'a> {
+    features: &'a BTreeSet<String>,
+    errors: Errors,
+}
+
+impl<'a> Context<'a> {
+    fn error(&mut self, loc: Loc, msg: impl Into<String>) {
+        self.errors.push(vec![(loc, msg.into())])
+    }
+}
+
+//**************************************************************************************************
+// Entry
+//**************************************************************************************************
+
+/// Removes the modules and module members disabled by their `cfg` attributes, given the enabled
+/// `features`, and checks all the attributes
+pub fn program(prog: Program, features: &BTreeSet<String>) -> (Program, Errors) {
+    let mut context = Context {
+        features,
+        errors: vec![],
+    };
+    let Program {
+        source_definitions,
+        lib_definitions,
+    } = prog;
+    let source_definitions = definitions(&mut context, source_definitions);
+    let lib_definitions = definitions(&mut context, lib_definitions);
+    let prog = Program {
+        source_definitions,
+        lib_definitions,
+    };
+    (prog, context.errors)
+}
+
+/// Returns the note of the `deprecated` attribute among `attributes`, if there is one: `None`
+/// if the function is not deprecated, `Some(None)` if it is deprecated without a note
+pub fn deprecation(attributes: &[Attributes]) -> Option<Spanned<Option<String>>> {
+    attributes
+        .iter()
+        .flat_map(|attributes| &attributes.value)
+        .find_map(|attribute| match &attribute.value {
+            Attribute_::Name(n) if n.value == DEPRECATED => Some(sp(attribute.loc, None)),
+            Attribute_::Parameterized(n, inner) if n.value == DEPRECATED => {
+                let note = inner.iter().find_map(|a| match &a.value {
+                    Attribute_::Assigned(n, sp!(_, Value_::ByteString(note)))
+                        if n.value == NOTE =>
+                    {
+                        Some(note.clone())
+                    }
+                    _ => None,
+                });
+                Some(sp(attribute.loc, note))
+            }
+            _ => None,
+        })
+}
+
+fn definitions(context: &mut Context, defs: Vec<Definition>) -> Vec<Definition> {
+    defs.into_iter()
+        .filter_map(|def| match def {
+            Definition::Module(mdef) => module(context, mdef).map(Definition::Module),
+            Definition::Address(loc, addr, mdefs) => {
+                let mdefs = mdefs
+                    .into_iter()
+                    .filter_map(|mdef| module(context, mdef))
+                    .collect();
+                Some(Definition::Address(loc, addr, mdefs))
+            }
+            Definition::Script(script) => Some(Definition::Script(script)),
+        })
+        .collect()
+}
+
+//**************************************************************************************************
+// Modules
+//**************************************************************************************************
+
+fn module(context: &mut Context, mut mdef: ModuleDefinition) -> Option<ModuleDefinition> {
+    if !is_enabled(context, &mdef.attributes, /* is_function */ false) {
+        return None;
+    }
+
+    let mut removed_functions = BTreeSet::new();
+    let mut removed_structs = BTreeSet::new();
+    let members = std::mem::take(&mut mdef.members);
+    mdef.members = members
+        .into_iter()
+        .filter(|member| match member {
+            ModuleMember::Function(f) => {
+                let enabled = is_enabled(context, &f.attributes, /* is_function */ true);
+                if !enabled {
+                    removed_functions.insert(f.name.value().to_owned());
+                }
+                enabled
+            }
+            ModuleMember::Struct(s) => {
+                let enabled = is_enabled(context, &s.attributes, /* is_function */ false);
+                if !enabled {
+                    removed_structs.insert(s.name.value().to_owned());
+                }
+                enabled
+            }
+            ModuleMember::Constant(c) => {
+                is_enabled(context, &c.attributes, /* is_function */ false)
+            }
+            ModuleMember::Spec(_) | ModuleMember::Use(_) => true,
+        })
+        .collect();
+
+    // The specifications of removed functions and structs are removed with them, unless another
+    // definition with the same name is enabled
+    for member in &mdef.members {
+        match member {
+            ModuleMember::Function(f) => {
+                removed_functions.remove(f.name.value());
+            }
+            ModuleMember::Struct(s) => {
+                removed_structs.remove(s.name.value());
+            }
+            _ => (),
+        }
+    }
+    mdef.members.retain(|member| match member {
+        ModuleMember::Spec(sp!(_, spec)) => match &spec.target.value {
+            SpecBlockTarget_::Function(name) => !removed_functions.contains(name.value()),
+            SpecBlockTarget_::Structure(name) => !removed_structs.contains(name.value()),
+            _ => true,
+        },
+        _ => true,
+    });
+    Some(mdef)
+}
+
+//**************************************************************************************************
+// Attributes
+//**************************************************************************************************
+
+// Checks the attributes of an item, returning false if one of its `cfg` predicates does not hold
+fn is_enabled(context: &mut Context, attributes: &[Attributes], is_function: bool) -> bool {
+    let mut enabled = true;
+    for attribute in attributes.iter().flat_map(|attributes| &attributes.value) {
+        match &attribute.value {
+            Attribute_::Parameterized(n, inner) if n.value == CFG => match inner.as_slice() {
+                [predicate] => {
+                    if let Some(holds) = cfg_predicate(context, predicate) {
+                        enabled &= holds
+                    }
+                }
+                _ => context.error(
+                    attribute.loc,
+                    "Invalid 'cfg' attribute. Expected exactly one predicate",
+                ),
+            },
+            Attribute_::Name(n) | Attribute_::Assigned(n, _) | Attribute_::Parameterized(n, _)
+                if n.value == DEPRECATED =>
+            {
+                check_deprecated(context, attribute, is_function)
+            }
+            Attribute_::Name(n) | Attribute_::Assigned(n, _) | Attribute_::Parameterized(n, _) => {
+                context.error(n.loc, format!("Unknown attribute '{}'", n))
+            }
+        }
+    }
+    enabled
+}
+
+// Evaluates a `cfg` predicate, returning None if it is invalid
+fn cfg_predicate(context: &mut Context, predicate: &Attribute) -> Option<bool> {
+    match &predicate.value {
+        Attribute_::Assigned(n, value) if n.value == FEATURE => match &value.value {
+            Value_::ByteString(feature) => Some(context.features.contains(feature)),
+            _ => {
+                context.error(
+                    value.loc,
+                    "Invalid feature. Expected a byte string, e.g. b\"testnet\"",
+                );
+                None
+            }
+        },
+        Attribute_::Parameterized(n, inner) if n.value == NOT => match inner.as_slice() {
+            [inner_predicate] => cfg_predicate(context, inner_predicate).map(|holds| !holds),
+            _ => {
+                context.error(
+                    predicate.loc,
+                    "Invalid 'not' predicate. Expected exactly one predicate",
+                );
+                None
+            }
+        },
+        Attribute_::Parameterized(n, inner) if n.value == ALL || n.value == ANY => {
+            // Every predicate is evaluated, to report all the invalid ones
+            let results = inner
+                .iter()
+                .map(|p| cfg_predicate(context, p))
+                .collect::<Vec<_>>();
+            let results = results.into_iter().collect::<Option<Vec<_>>>()?;
+            Some(if n.value == ALL {
+                results.into_iter().all(|holds| holds)
+            } else {
+                results.into_iter().any(|holds| holds)
+            })
+        }
+        _ => {
+            context.error(
+                predicate.loc,
+                "Invalid 'cfg' predicate. Expected 'feature = b\"<name>\"', 'not(..)', 'all(..)' \
+                 or 'any(..)'",
+            );
+            None
+        }
+    }
+}
+
+fn check_deprecated(context: &mut Context, attribute: &Attribute, is_function: bool) {
+    if !is_function {
+        context.error(
+            attribute.loc,
+            "Invalid 'deprecated' attribute. Only functions can be deprecated",
+        );
+        return;
+    }
+    let valid = match &attribute.value {
+        Attribute_::Name(_) => true,
+        Attribute_::Parameterized(_, inner) => match inner.as_slice() {
+            [sp!(_, Attribute_::Assigned(n, sp!(_, Value_::ByteString(_))))] => n.value == NOTE,
+            _ => false,
+        },
+        Attribute_::Assigned(_, _) => false,
+    };
+    if !valid {
+        context.error(
+            attribute.loc,
+            "Invalid 'deprecated' attribute. Expected 'deprecated' or \
+             'deprecated(note = b\"<note>\")'",
+        );
+    }
+}
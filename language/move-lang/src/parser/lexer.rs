@@ -14,10 +14,12 @@ pub enum Tok {
     U8Value,
     U64Value,
     U128Value,
+    U256Value,
     ByteStringValue,
     IdentifierValue,
     Exclaim,
     ExclaimEqual,
+    NumSign,
     Percent,
     Amp,
     AmpAmp,
@@ -90,10 +92,12 @@ impl fmt::Display for Tok {
             U8Value => "[U8]",
             U64Value => "[U64]",
             U128Value => "[U128]",
+            U256Value => "[U256]",
             ByteStringValue => "[ByteString]",
             IdentifierValue => "[Identifier]",
             Exclaim => "!",
             ExclaimEqual => "!=",
+            NumSign => "#",
             Percent => "%",
             Amp => "&",
             AmpAmp => "&&",
@@ -253,7 +257,11 @@ impl<'input> Lexer<'input> {
         for span in matched {
             self.doc_comments.remove(&span);
         }
-        self.matched_doc_comments.insert(ByteIndex(end), merged);
+        // A module member matches its comments before its attributes are parsed, and spec blocks
+        // match them again at the same position: don't overwrite the comments found first.
+        if !merged.is_empty() || !self.matched_doc_comments.contains_key(&ByteIndex(end)) {
+            self.matched_doc_comments.insert(ByteIndex(end), merged);
+        }
     }
 
     // At the end of parsing, checks whether there are any unmatched documentation comments,
@@ -393,6 +401,7 @@ fn find_token(file: &'static str, text: &str, start_offset: usize) -> Result<(To
             }
         }
         '%' => (Tok::Percent, 1),
+        '#' => (Tok::NumSign, 1),
         '(' => (Tok::LParen, 1),
         ')' => (Tok::RParen, 1),
         '[' => (Tok::LBracket, 1),
@@ -450,6 +459,8 @@ fn get_decimal_number(text: &str) -> (Tok, usize) {
         (Tok::U64Value, len + 3)
     } else if rest.starts_with("u128") {
         (Tok::U128Value, len + 4)
+    } else if rest.starts_with("u256") {
+        (Tok::U256Value, len + 4)
     } else {
         (Tok::NumValue, len)
     }
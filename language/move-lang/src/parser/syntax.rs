@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use codespan::{ByteIndex, Span};
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::str::FromStr;
 
@@ -443,6 +444,7 @@ fn parse_byte_string<'input>(tokens: &mut Lexer<'input>) -> Result<Value_, Error
 //          | <U8Value>
 //          | <U64Value>
 //          | <U128Value>
+//          | <U256Value>
 //          | <ByteString>
 fn parse_value<'input>(tokens: &mut Lexer<'input>) -> Result<Value, Error> {
     let start_loc = tokens.start_loc();
@@ -486,6 +488,24 @@ fn parse_value<'input>(tokens: &mut Lexer<'input>) -> Result<Value, Error> {
             tokens.advance()?;
             Value_::U128(i)
         }
+        Tok::U256Value => {
+            let mut s = tokens.content();
+            if s.ends_with("u256") {
+                s = &s[..s.len() - 4]
+            }
+            let i = match U256::from_str(s) {
+                Ok(i) => i,
+                Err(_) => {
+                    let end_loc = start_loc + tokens.content().len();
+                    let loc = make_loc(tokens.file_name(), start_loc, end_loc);
+                    let msg = "Invalid number literal. The given literal is too large to fit into \
+                               the largest number type 'u256'";
+                    return Err(vec![(loc, msg.to_owned())]);
+                }
+            };
+            tokens.advance()?;
+            Value_::U256(i)
+        }
         Tok::ByteStringValue => parse_byte_string(tokens)?,
         _ => unreachable!("parse_value called with invalid token"),
     };
@@ -625,6 +645,7 @@ fn parse_term<'input>(tokens: &mut Lexer<'input>) -> Result<Exp, Error> {
         | Tok::U8Value
         | Tok::U64Value
         | Tok::U128Value
+        | Tok::U256Value
         | Tok::ByteStringValue => Exp_::Value(parse_value(tokens)?),
 
         Tok::NumValue => Exp_::InferredNum(parse_num(tokens)?),
@@ -1274,11 +1295,105 @@ fn parse_optional_type_parameters<'input>(
     }
 }
 
+//**************************************************************************************************
+// Attributes
+//**************************************************************************************************
+
+// Parse an attribute:
+//      Attribute =
+//          <Identifier>
+//          | <Identifier> "=" <AttributeValue>
+//          | <Identifier> "(" Comma<Attribute> ")"
+//      AttributeValue = <Value> | <Num>
+fn parse_attribute<'input>(tokens: &mut Lexer<'input>) -> Result<Attribute, Error> {
+    let start_loc = tokens.start_loc();
+    let name = parse_identifier(tokens)?;
+    let attribute_ = match tokens.peek() {
+        Tok::Equal => {
+            tokens.advance()?;
+            let value_start_loc = tokens.start_loc();
+            let value = match tokens.peek() {
+                Tok::True
+                | Tok::False
+                | Tok::U8Value
+                | Tok::U64Value
+                | Tok::U128Value
+                | Tok::U256Value
+                | Tok::ByteStringValue
+                | Tok::AddressValue => parse_value(tokens)?,
+                Tok::NumValue => {
+                    let i = parse_num(tokens)?;
+                    spanned(
+                        tokens.file_name(),
+                        value_start_loc,
+                        tokens.previous_end_loc(),
+                        Value_::U128(i),
+                    )
+                }
+                _ => return Err(unexpected_token_error(tokens, "an attribute value")),
+            };
+            Attribute_::Assigned(name, value)
+        }
+        Tok::LParen => {
+            let inner = parse_comma_list(
+                tokens,
+                Tok::LParen,
+                Tok::RParen,
+                parse_attribute,
+                "an attribute",
+            )?;
+            Attribute_::Parameterized(name, inner)
+        }
+        _ => Attribute_::Name(name),
+    };
+    Ok(spanned(
+        tokens.file_name(),
+        start_loc,
+        tokens.previous_end_loc(),
+        attribute_,
+    ))
+}
+
+// Parse the attributes in front of a module or a module member:
+//      Attributes = ("#" "[" Comma<Attribute> "]")*
+fn parse_attributes<'input>(tokens: &mut Lexer<'input>) -> Result<Vec<Attributes>, Error> {
+    let mut attributes = vec![];
+    while tokens.peek() == Tok::NumSign {
+        let start_loc = tokens.start_loc();
+        tokens.advance()?;
+        let inner = parse_comma_list(
+            tokens,
+            Tok::LBracket,
+            Tok::RBracket,
+            parse_attribute,
+            "an attribute",
+        )?;
+        attributes.push(spanned(
+            tokens.file_name(),
+            start_loc,
+            tokens.previous_end_loc(),
+            inner,
+        ));
+    }
+    Ok(attributes)
+}
+
+// Attributes can only be given to modules, functions, structs and constants
+fn check_no_attributes(attributes: &[Attributes], item_description: &str) -> Result<(), Error> {
+    match attributes.first() {
+        None => Ok(()),
+        Some(first) => Err(vec![(
+            first.loc,
+            format!("Attributes are not allowed on {}", item_description),
+        )]),
+    }
+}
+
 //**************************************************************************************************
 // Functions
 //**************************************************************************************************
 
-// Parse a function declaration:
+// Parse a function declaration, after its attributes:
 //      FunctionDecl =
 //          <NativeFunctionDecl>
 //          | <MoveFunctionDecl>
@@ -1298,13 +1413,13 @@ fn parse_optional_type_parameters<'input>(
 //          <Identifier> <OptionalTypeParameters>
 //
 // If the "allow_native" parameter is false, this will only accept Move
-// functions.
+// functions. The declaration starts at `start_loc`, in front of its attributes.
 fn parse_function_decl<'input>(
     tokens: &mut Lexer<'input>,
+    start_loc: usize,
+    attributes: Vec<Attributes>,
     allow_native: bool,
 ) -> Result<Function, Error> {
-    tokens.match_doc_comments();
-    let start_loc = tokens.start_loc();
     // Record the source location of the "native" keyword (if there is one).
     let native_opt = if allow_native {
         consume_optional_token_with_loc(tokens, Tok::Native)?
@@ -1392,6 +1507,7 @@ fn parse_function_decl<'input>(
     let loc = make_loc(tokens.file_name(), start_loc, tokens.previous_end_loc());
     Ok(Function {
         loc,
+        attributes,
         visibility,
         signature,
         acquires,
@@ -1413,16 +1529,17 @@ fn parse_parameter<'input>(tokens: &mut Lexer<'input>) -> Result<(Var, Type), Er
 // Structs
 //**************************************************************************************************
 
-// Parse a struct definition:
+// Parse a struct definition, after its attributes:
 //      StructDefinition =
 //          <DocComments> "resource"? "struct" <StructDefName> "{" Comma<FieldAnnot> "}"
 //          | <DocComments> "native" "resource"? "struct" <StructDefName> ";"
 //      StructDefName =
 //          <Identifier> <OptionalTypeParameters>
-fn parse_struct_definition<'input>(tokens: &mut Lexer<'input>) -> Result<StructDefinition, Error> {
-    tokens.match_doc_comments();
-    let start_loc = tokens.start_loc();
-
+fn parse_struct_definition<'input>(
+    tokens: &mut Lexer<'input>,
+    start_loc: usize,
+    attributes: Vec<Attributes>,
+) -> Result<StructDefinition, Error> {
     // Record the source location of the "native" keyword (if there is one).
     let native_opt = consume_optional_token_with_loc(tokens, Tok::Native)?;
 
@@ -1455,6 +1572,7 @@ fn parse_struct_definition<'input>(tokens: &mut Lexer<'input>) -> Result<StructD
     let loc = make_loc(tokens.file_name(), start_loc, tokens.previous_end_loc());
     Ok(StructDefinition {
         loc,
+        attributes,
         resource_opt,
         name,
         type_parameters,
@@ -1476,12 +1594,13 @@ fn parse_field_annot<'input>(tokens: &mut Lexer<'input>) -> Result<(Field, Type)
 // Constants
 //**************************************************************************************************
 
-// Parse a constant:
+// Parse a constant, after its attributes:
 //      ConstantDecl = "const" <Identifier> ":" <Type> "=" <Exp> ";"
-fn parse_constant<'input>(tokens: &mut Lexer<'input>) -> Result<Constant, Error> {
-    tokens.match_doc_comments();
-    let start_loc = tokens.start_loc();
-
+fn parse_constant<'input>(
+    tokens: &mut Lexer<'input>,
+    start_loc: usize,
+    attributes: Vec<Attributes>,
+) -> Result<Constant, Error> {
     consume_token(tokens, Tok::Const)?;
     let name = ConstantName(parse_identifier(tokens)?);
     consume_token(tokens, Tok::Colon)?;
@@ -1492,6 +1611,7 @@ fn parse_constant<'input>(tokens: &mut Lexer<'input>) -> Result<Constant, Error>
     let loc = make_loc(tokens.file_name(), start_loc, tokens.previous_end_loc());
     Ok(Constant {
         loc,
+        attributes,
         name,
         signature,
         value,
@@ -1505,7 +1625,7 @@ fn parse_constant<'input>(tokens: &mut Lexer<'input>) -> Result<Constant, Error>
 // Parse an address block:
 //      AddressBlock =
 //          "address" <Address> "{"
-//              <Module>*
+//              (<DocComments> <Attributes> <Module>)*
 //          "}"
 //
 // Note that "address" is not a token.
@@ -1537,7 +1657,10 @@ fn parse_address_block<'input>(
     consume_token(tokens, Tok::LBrace)?;
     let mut modules = vec![];
     while tokens.peek() != Tok::RBrace {
-        modules.push(parse_module(tokens)?);
+        tokens.match_doc_comments();
+        let start_loc = tokens.start_loc();
+        let attributes = parse_attributes(tokens)?;
+        modules.push(parse_module(tokens, start_loc, attributes)?);
     }
     consume_token(tokens, Tok::RBrace)?;
 
@@ -1606,37 +1729,66 @@ fn is_struct_definition<'input>(tokens: &mut Lexer<'input>) -> Result<bool, Erro
     Ok(t == Tok::Struct || t == Tok::Resource)
 }
 
-// Parse a module:
+// Parse a module, after its attributes:
 //      Module =
-//          <DocComments> "module" <ModuleName> "{"
+//          "module" <ModuleName> "{"
 //              <UseDecl>*
-//              ( <ConstantDecl> | <StructDefinition> | <FunctionDecl> | <Spec> )*
+//              ( <DocComments> <Attributes>
+//                ( <ConstantDecl> | <StructDefinition> | <FunctionDecl> | <Spec> ) )*
 //          "}"
-fn parse_module<'input>(tokens: &mut Lexer<'input>) -> Result<ModuleDefinition, Error> {
-    tokens.match_doc_comments();
-    let start_loc = tokens.start_loc();
-
+// Only constants, structs and functions can have attributes.
+fn parse_module<'input>(
+    tokens: &mut Lexer<'input>,
+    start_loc: usize,
+    attributes: Vec<Attributes>,
+) -> Result<ModuleDefinition, Error> {
     consume_token(tokens, Tok::Module)?;
     let name = parse_module_name(tokens)?;
     consume_token(tokens, Tok::LBrace)?;
 
     let mut members = vec![];
     while tokens.peek() != Tok::RBrace {
+        // Uses cannot be documented
+        if tokens.peek() != Tok::Use {
+            tokens.match_doc_comments();
+        }
+        let member_start_loc = tokens.start_loc();
+        let member_attributes = parse_attributes(tokens)?;
         members.push(match tokens.peek() {
-            Tok::Spec => ModuleMember::Spec(parse_spec_block(tokens)?),
-            Tok::Use => ModuleMember::Use(parse_use_decl(tokens)?),
-            Tok::Const => ModuleMember::Constant(parse_constant(tokens)?),
-            // TODO rework parsing modifiers
-            _ if is_struct_definition(tokens)? => {
-                ModuleMember::Struct(parse_struct_definition(tokens)?)
+            Tok::Spec => {
+                check_no_attributes(&member_attributes, "specification blocks")?;
+                ModuleMember::Spec(parse_spec_block(tokens)?)
+            }
+            Tok::Use => {
+                check_no_attributes(&member_attributes, "use declarations")?;
+                ModuleMember::Use(parse_use_decl(tokens)?)
+            }
+            Tok::Const => {
+                ModuleMember::Constant(parse_constant(tokens, member_start_loc, member_attributes)?)
             }
-            _ => ModuleMember::Function(parse_function_decl(tokens, /* allow_native */ true)?),
+            // TODO rework parsing modifiers
+            _ if is_struct_definition(tokens)? => ModuleMember::Struct(parse_struct_definition(
+                tokens,
+                member_start_loc,
+                member_attributes,
+            )?),
+            _ => ModuleMember::Function(parse_function_decl(
+                tokens,
+                member_start_loc,
+                member_attributes,
+                /* allow_native */ true,
+            )?),
         })
     }
     consume_token(tokens, Tok::RBrace)?;
 
     let loc = make_loc(tokens.file_name(), start_loc, tokens.previous_end_loc());
-    Ok(ModuleDefinition { loc, name, members })
+    Ok(ModuleDefinition {
+        loc,
+        attributes,
+        name,
+        members,
+    })
 }
 
 //**************************************************************************************************
@@ -1661,10 +1813,19 @@ fn parse_script<'input>(tokens: &mut Lexer<'input>) -> Result<Script, Error> {
         uses.push(parse_use_decl(tokens)?);
     }
     let mut constants = vec![];
+    tokens.match_doc_comments();
     while tokens.peek() == Tok::Const {
-        constants.push(parse_constant(tokens)?);
+        let start_loc = tokens.start_loc();
+        constants.push(parse_constant(tokens, start_loc, vec![])?);
+        tokens.match_doc_comments();
     }
-    let function = parse_function_decl(tokens, /* allow_native */ false)?;
+    let function_start_loc = tokens.start_loc();
+    let function = parse_function_decl(
+        tokens,
+        function_start_loc,
+        vec![],
+        /* allow_native */ false,
+    )?;
     let mut specs = vec![];
     while tokens.peek() == Tok::Spec {
         specs.push(parse_spec_block(tokens)?)
@@ -2117,6 +2278,7 @@ fn parse_spec_pragma_property<'input>(tokens: &mut Lexer<'input>) -> Result<Prag
             | Tok::U8Value
             | Tok::U64Value
             | Tok::U128Value
+            | Tok::U256Value
             | Tok::ByteStringValue
             | Tok::AddressValue => Some(parse_value(tokens)?),
             Tok::NumValue => {
@@ -2147,14 +2309,24 @@ fn parse_spec_pragma_property<'input>(tokens: &mut Lexer<'input>) -> Result<Prag
 
 // Parse a file:
 //      File =
-//          (<AddressBlock> | <Module> | <Script>)*
+//          (<AddressBlock> | <DocComments> <Attributes> <Module> | <Script>)*
 fn parse_file<'input>(tokens: &mut Lexer<'input>) -> Result<Vec<Definition>, Error> {
     let mut defs = vec![];
     while tokens.peek() != Tok::EOF {
+        // Only modules can be documented
+        if matches!(tokens.peek(), Tok::Module | Tok::NumSign) {
+            tokens.match_doc_comments();
+        }
+        let start_loc = tokens.start_loc();
+        let attributes = parse_attributes(tokens)?;
         defs.push(match tokens.peek() {
-            Tok::Module => Definition::Module(parse_module(tokens)?),
-            Tok::Script => Definition::Script(parse_script(tokens)?),
+            Tok::Module => Definition::Module(parse_module(tokens, start_loc, attributes)?),
+            Tok::Script => {
+                check_no_attributes(&attributes, "scripts")?;
+                Definition::Script(parse_script(tokens)?)
+            }
             _ => {
+                check_no_attributes(&attributes, "address blocks")?;
                 let (loc, addr, modules) = parse_address_block(tokens)?;
                 Definition::Address(loc, addr, modules)
             }
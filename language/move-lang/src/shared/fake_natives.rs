@@ -17,6 +17,24 @@ pub mod transaction {
     pub const SENDER: &str = "sender";
 }
 
+//**************************************************************************************************
+// Vector
+//**************************************************************************************************
+
+/// Native functions of the Vector module that are compiled to the vector instructions
+pub mod vector {
+    pub const MOD: &str = "Vector";
+
+    pub const EMPTY: &str = "empty";
+    pub const LENGTH: &str = "length";
+    pub const BORROW: &str = "borrow";
+    pub const BORROW_MUT: &str = "borrow_mut";
+    pub const PUSH_BACK: &str = "push_back";
+    pub const POP_BACK: &str = "pop_back";
+    pub const DESTROY_EMPTY: &str = "destroy_empty";
+    pub const SWAP: &str = "swap";
+}
+
 pub fn is_fake_native(mident: &ModuleIdent) -> bool {
     mident.0.value.address == Address::LIBRA_CORE && mident.0.value.name.value() == transaction::MOD
 }
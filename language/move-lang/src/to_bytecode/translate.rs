@@ -635,6 +635,7 @@ fn base_type(context: &mut Context, sp!(_, bt_): H::BaseType) -> IR::Type {
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U8))), _) => IRT::U8,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U64))), _) => IRT::U64,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U128))), _) => IRT::U128,
+        B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U256))), _) => IRT::U256,
 
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Bool))), _) => IRT::Bool,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Vector))), mut args) => {
@@ -791,6 +792,7 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
                     V::U8(u) => B::LdU8(u),
                     V::U64(u) => B::LdU64(u),
                     V::U128(u) => B::LdU128(u),
+                    V::U256(u) => B::LdU256(u),
                     V::Bool(b) => {
                         if b {
                             B::LdTrue
@@ -900,6 +902,7 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
                 BT::U8 => B::CastU8,
                 BT::U64 => B::CastU64,
                 BT::U128 => B::CastU128,
+                BT::U256 => B::CastU256,
                 _ => panic!("ICE type checking failed. unexpected cast"),
             };
             code.push(sp(loc, instr));
@@ -914,12 +917,29 @@ fn call(
     f: FunctionName,
     tys: Vec<H::BaseType>,
 ) {
-    use crate::shared::fake_natives::transaction as TXN;
+    use crate::shared::fake_natives::{transaction as TXN, vector as V};
     use Address as A;
     use IR::Bytecode_ as B;
 
+    macro_rules! vector_instr {
+        ($instr:expr) => {{
+            let mut tys = base_types(context, tys);
+            assert!(tys.len() == 1, "vector operations take one type argument");
+            let ty = tys.pop().unwrap();
+            code.push(sp(f.loc(), $instr(ty)))
+        }};
+    }
+
     match (&m.0.value.address, m.0.value.name.value(), f.value()) {
         (&A::LIBRA_CORE, TXN::MOD, TXN::SENDER) => code.push(sp(f.loc(), B::GetTxnSenderAddress)),
+        (&A::LIBRA_CORE, V::MOD, V::EMPTY) => vector_instr!(|ty| B::VecPack(ty, 0)),
+        (&A::LIBRA_CORE, V::MOD, V::LENGTH) => vector_instr!(B::VecLen),
+        (&A::LIBRA_CORE, V::MOD, V::BORROW) => vector_instr!(B::VecImmBorrow),
+        (&A::LIBRA_CORE, V::MOD, V::BORROW_MUT) => vector_instr!(B::VecMutBorrow),
+        (&A::LIBRA_CORE, V::MOD, V::PUSH_BACK) => vector_instr!(B::VecPushBack),
+        (&A::LIBRA_CORE, V::MOD, V::POP_BACK) => vector_instr!(B::VecPopBack),
+        (&A::LIBRA_CORE, V::MOD, V::DESTROY_EMPTY) => vector_instr!(|ty| B::VecUnpack(ty, 0)),
+        (&A::LIBRA_CORE, V::MOD, V::SWAP) => vector_instr!(B::VecSwap),
         _ => module_call(context, code, m, f, tys),
     }
 }
@@ -8,6 +8,7 @@ use crate::{
     parser::ast::{Kind, Kind_},
     typing::ast as T,
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::convert::TryInto;
 
@@ -171,7 +172,8 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             let max = match bt {
                 BT::U8 => u8_max,
                 BT::U64 => u64_max,
-                BT::U128 => u128_max,
+                // Inferred numbers are at most u128, so they always fit in a u256
+                BT::U128 | BT::U256 => u128_max,
                 _ => unreachable!(),
             };
             let new_exp = if v > max {
@@ -201,6 +203,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
                     BT::U8 => Value_::U8(v.try_into().unwrap()),
                     BT::U64 => Value_::U64(v.try_into().unwrap()),
                     BT::U128 => Value_::U128(v),
+                    BT::U256 => Value_::U256(U256::from(v)),
                     _ => unreachable!(),
                 };
                 E::Value(sp(e.exp.loc, value_))
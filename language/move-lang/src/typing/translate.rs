@@ -311,6 +311,7 @@ mod check_valid_constant {
             Type_::u8(loc),
             Type_::u64(loc),
             Type_::u128(loc),
+            Type_::u256(loc),
             Type_::bool(loc),
             Type_::address(loc),
         ];
@@ -0,0 +1,20 @@
+address 0x2 {
+module M {
+    #[deprecated(note = b"Use 'g' instead")]
+    public fun f(): u64 { 0 }
+
+    public fun g(): u64 { f() }
+
+    #[deprecated]
+    public fun h() {}
+}
+
+module N {
+    use 0x2::M;
+
+    fun call(): u64 {
+        M::h();
+        M::f() + M::g()
+    }
+}
+}
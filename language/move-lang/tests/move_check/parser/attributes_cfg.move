@@ -0,0 +1,26 @@
+address 0x2 {
+module M {
+    #[cfg(feature = b"testnet")]
+    struct S { f: 0x2::Missing::T }
+    spec struct S {
+        invariant f == f;
+    }
+
+    #[cfg(all(feature = b"testnet", not(feature = b"mainnet")))]
+    const C: u64 = 0x2::Missing::c();
+
+    #[cfg(any(feature = b"testnet", feature = b"devnet"))]
+    fun f(): u64 { 0x2::Missing::f() }
+    spec fun f {
+        aborts_if false;
+    }
+
+    #[cfg(not(feature = b"testnet"))]
+    fun f(): u64 { 0 }
+}
+}
+
+#[cfg(feature = b"testnet")]
+module N {
+    fun g() { 0x2::Missing::g() }
+}
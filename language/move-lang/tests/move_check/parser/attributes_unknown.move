@@ -0,0 +1,4 @@
+module M {
+    #[inline]
+    fun f() {}
+}
@@ -18,7 +18,7 @@ fn sanity_check_testsuite(path: &Path) -> datatest_stable::Result<()> {
 
     let out_path = path.with_extension(OUT_EXT);
 
-    let (files, units_or_errors) = move_compile_no_report(&targets, &[], sender)?;
+    let (files, units_or_errors) = move_compile_no_report(&targets, &[], sender, &[])?;
     let errors = match units_or_errors {
         Err(errors) => errors,
         Ok(units) => move_lang::compiled_unit::verify_units(units).1,
@@ -6,11 +6,17 @@ use log::{debug, info, warn};
 
 use anyhow::bail;
 use heck::SnakeCase;
-use libra_types::transaction::{ArgumentABI, ScriptABI, TypeArgumentABI};
-use move_core_types::language_storage::TypeTag;
+use libra_types::transaction::{
+    ArgumentABI, FunctionABI, ModuleABI, ParameterABI, ScriptABI, TypeABI, TypeArgumentABI,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
 use serde::{Deserialize, Serialize};
 use spec_lang::{
-    env::{GlobalEnv, ModuleEnv},
+    env::{FunctionEnv, GlobalEnv, ModuleEnv},
     ty,
 };
 use std::{collections::BTreeMap, io::Read, path::PathBuf};
@@ -42,8 +48,13 @@ pub struct Abigen<'env> {
     env: &'env GlobalEnv,
     /// Map from file name to generated script ABI (if any).
     output: BTreeMap<String, ScriptABI>,
+    /// Map from file name, without extension, to generated module ABI.
+    module_output: BTreeMap<String, ModuleABI>,
 }
 
+/// The sub-directory of the output directory where module ABIs are stored.
+pub const MODULE_ABI_DIR: &str = "modules";
+
 impl<'env> Abigen<'env> {
     /// Creates a new ABI generator.
     pub fn new(env: &'env GlobalEnv, options: &'env AbigenOptions) -> Self {
@@ -51,25 +62,57 @@ impl<'env> Abigen<'env> {
             options,
             env,
             output: Default::default(),
+            module_output: Default::default(),
         }
     }
 
     /// Returns the result of ABI generation, a vector of pairs of filenames
-    /// and JSON content.
+    /// and content. Script ABIs are serialized with LCS. Module ABIs are serialized both with
+    /// LCS, in a `.abi` file, and in JSON, in a `.json` file.
     pub fn into_result(mut self) -> Vec<(String, Vec<u8>)> {
-        std::mem::take(&mut self.output)
+        let mut result: Vec<_> = std::mem::take(&mut self.output)
             .into_iter()
             .map(|(path, abi)| {
                 let content = lcs::to_bytes(&abi).expect("ABI serialization should not fail");
                 (path, content)
             })
-            .collect()
+            .collect();
+        for (path, abi) in std::mem::take(&mut self.module_output) {
+            let content = lcs::to_bytes(&abi).expect("ABI serialization should not fail");
+            result.push((format!("{}.abi", path), content));
+            let content =
+                serde_json::to_vec_pretty(&abi).expect("ABI serialization should not fail");
+            result.push((format!("{}.json", path), content));
+        }
+        result
     }
 
-    /// Generates ABIs for all script modules in the environment (excluding the dependency set).
+    /// Generates ABIs for all modules in the environment (excluding the dependency set).
+    /// Script ABIs are stored in the output directory, and module ABIs in its `modules`
+    /// sub-directory.
     pub fn gen(&mut self) {
         for module in self.env.get_modules() {
-            if module.is_script_module() && !module.is_dependency() {
+            if module.is_dependency() {
+                continue;
+            }
+            if !module.is_script_module() {
+                let name = module.symbol_pool().string(module.get_name().name());
+                let mut path = PathBuf::from(&self.options.output_directory);
+                path.push(MODULE_ABI_DIR);
+                path.push(name.as_str());
+                match self.compute_module_abi(&module) {
+                    Ok(abi) => {
+                        self.module_output
+                            .insert(path.to_string_lossy().to_string(), abi);
+                    }
+                    Err(error) => panic!(
+                        "Error while processing module {} in file {:?}: {}",
+                        name,
+                        module.get_source_path(),
+                        error
+                    ),
+                }
+            } else {
                 let mut path = PathBuf::from(&self.options.output_directory);
                 path.push(
                     PathBuf::from(module.get_source_path())
@@ -92,6 +135,100 @@ impl<'env> Abigen<'env> {
         }
     }
 
+    /// Compute the ABI of a module, from its public functions.
+    fn compute_module_abi(&self, module_env: &ModuleEnv<'_>) -> anyhow::Result<ModuleABI> {
+        let module_id = self.get_module_id(module_env)?;
+        let doc = module_env.get_doc().to_string();
+        let functions = module_env
+            .get_functions()
+            .filter(|func| func.is_public())
+            .map(|func| self.compute_function_abi(&func))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(ModuleABI::new(module_id, doc, functions))
+    }
+
+    fn compute_function_abi(&self, func: &FunctionEnv<'_>) -> anyhow::Result<FunctionABI> {
+        let symbol_pool = func.module_env.symbol_pool();
+        let name = symbol_pool.string(func.get_name()).to_string();
+        let doc = func.get_doc().to_string();
+        let ty_args = func
+            .get_named_type_parameters()
+            .iter()
+            .map(|ty_param| {
+                TypeArgumentABI::new(symbol_pool.string(ty_param.0).to_string().to_snake_case())
+            })
+            .collect();
+        let args = func
+            .get_parameters()
+            .iter()
+            .map(|param| {
+                Ok(ParameterABI::new(
+                    symbol_pool.string(param.0).to_string(),
+                    self.get_type_abi(&param.1)?,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let returns = func
+            .get_return_types()
+            .iter()
+            .map(|ty| self.get_type_abi(ty))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(FunctionABI::new(name, doc, ty_args, args, returns))
+    }
+
+    fn get_module_id(&self, module_env: &ModuleEnv<'_>) -> anyhow::Result<ModuleId> {
+        let name = module_env.get_name();
+        let address = AccountAddress::from_hex_literal(&format!("0x{:x}", name.addr()))?;
+        let name = Identifier::new(module_env.symbol_pool().string(name.name()).as_str())?;
+        Ok(ModuleId::new(address, name))
+    }
+
+    fn get_type_abi(&self, ty0: &ty::Type) -> anyhow::Result<TypeABI> {
+        use ty::Type::*;
+        let type_abi = match ty0 {
+            Primitive(prim) => {
+                use ty::PrimitiveType::*;
+                match prim {
+                    Bool => TypeABI::Bool,
+                    U8 => TypeABI::U8,
+                    U64 => TypeABI::U64,
+                    U128 => TypeABI::U128,
+                    U256 => TypeABI::U256,
+                    Address => TypeABI::Address,
+                    Signer => TypeABI::Signer,
+                    Num | Range | TypeValue => {
+                        bail!("Type {:?} is not allowed in public functions.", ty0)
+                    }
+                }
+            }
+            Vector(ty) => TypeABI::Vector(Box::new(self.get_type_abi(ty)?)),
+            Struct(module_id, struct_id, ty_args) => {
+                let module_env = self.env.get_module(*module_id);
+                let struct_env = module_env.get_struct(*struct_id);
+                TypeABI::Struct {
+                    module_id: self.get_module_id(&module_env)?,
+                    name: module_env
+                        .symbol_pool()
+                        .string(struct_env.get_name())
+                        .to_string(),
+                    ty_args: ty_args
+                        .iter()
+                        .map(|ty| self.get_type_abi(ty))
+                        .collect::<anyhow::Result<_>>()?,
+                }
+            }
+            TypeParameter(idx) => TypeABI::TypeParameter(*idx),
+            Reference(mutable, ty) => TypeABI::Reference {
+                mutable: *mutable,
+                to: Box::new(self.get_type_abi(ty)?),
+            },
+            Tuple(_) | Fun(_, _) | TypeDomain(_) | TypeLocal(_) | Error | Var(_) => {
+                bail!("Type {:?} is not allowed in public functions.", ty0)
+            }
+        };
+        Ok(type_abi)
+    }
+
     /// Compute the ABI of a script module.
     fn compute_abi(&self, module_env: &ModuleEnv<'env>) -> anyhow::Result<ScriptABI> {
         let symbol_pool = module_env.symbol_pool();
@@ -153,6 +290,7 @@ impl<'env> Abigen<'env> {
                     U8 => TypeTag::U8,
                     U64 => TypeTag::U64,
                     U128 => TypeTag::U128,
+                    U256 => TypeTag::U256,
                     Address => TypeTag::Address,
                     Signer => TypeTag::Signer,
                     Num | Range | TypeValue => bail!("Type {:?} is not allowed in scripts.", ty0),
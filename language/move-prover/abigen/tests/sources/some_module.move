@@ -0,0 +1,16 @@
+address 0x1 {
+
+/// A module with a public function.
+module SomeModule {
+    resource struct Coin<Token> { value: u64 }
+
+    /// Returns the value of a coin.
+    public fun value<Token>(coin: &Coin<Token>): u64 {
+        coin.value
+    }
+
+    fun zero<Token>(): Coin<Token> {
+        Coin { value: 0 }
+    }
+}
+}
@@ -3,6 +3,7 @@
 
 use std::path::Path;
 
+use abigen::MODULE_ABI_DIR;
 use codespan_reporting::term::termcolor::Buffer;
 use libra_temppath::TempPath;
 use move_prover::{cli::Options, run_move_prover};
@@ -11,7 +12,10 @@ use test_utils::baseline_test::verify_or_update_baseline;
 
 #[allow(unused_imports)]
 use log::debug;
-use std::{fs::File, io::Read};
+use std::{
+    fs::{read_dir, File},
+    io::Read,
+};
 
 const FLAGS: &[&str] = &["--verbose=warn", "--abigen"];
 
@@ -43,6 +47,20 @@ fn test_abigen(path: &Path, mut options: Options, suffix: &str) -> anyhow::Resul
             if let Ok(mut file) = File::open(temp_path.as_path()) {
                 file.read_to_string(&mut contents).unwrap();
             }
+            // Module ABIs are compared in their JSON format.
+            temp_path.set_file_name(MODULE_ABI_DIR);
+            if let Ok(entries) = read_dir(temp_path.as_path()) {
+                let mut json_paths = entries
+                    .map(|entry| entry.unwrap().path())
+                    .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+                    .collect::<Vec<_>>();
+                json_paths.sort();
+                for json_path in json_paths {
+                    let mut file = File::open(json_path.as_path()).unwrap();
+                    file.read_to_string(&mut contents).unwrap();
+                    contents.push('\n');
+                }
+            }
             contents
         }
         Err(err) => format!("Move prover abigen returns: {}\n", err),
@@ -15,8 +15,8 @@ use spec_lang::{
     code_writer::CodeWriter,
     emit, emitln,
     env::{
-        FunctionEnv, GlobalEnv, Loc, ModuleEnv, ModuleId, Parameter, StructEnv, TypeConstraint,
-        TypeParameter,
+        FunId, FunctionEnv, GlobalEnv, Loc, ModuleEnv, ModuleId, Parameter, StructEnv,
+        TypeConstraint, TypeParameter,
     },
     symbol::Symbol,
     ty::TypeDisplayContext,
@@ -102,6 +102,18 @@ pub struct DocgenOptions {
     pub output_directory: String,
     /// In which directories to look for references.
     pub doc_path: Vec<String>,
+    /// Whether to include, for each module, links to the modules it uses and to the modules
+    /// using it.
+    pub include_dep_links: bool,
+    /// Whether to include, for each function, links to the functions it calls and to the
+    /// functions calling it.
+    pub include_call_graph: bool,
+    /// Templates of root documents. For each template, a root document with the same file name
+    /// is generated in the output directory, where a line `> {{move-include NAME}}` is replaced
+    /// by the documentation of the module or script named NAME, or by a root document generated
+    /// from an earlier template named NAME, and a line `> {{move-toc}}` is replaced by links to
+    /// the documentation of all modules and scripts.
+    pub root_doc_templates: Vec<String>,
 }
 
 impl Default for DocgenOptions {
@@ -116,6 +128,9 @@ impl Default for DocgenOptions {
             collapsed_sections: true,
             output_directory: "doc".to_string(),
             doc_path: vec!["doc".to_string()],
+            include_dep_links: false,
+            include_call_graph: false,
+            root_doc_templates: vec![],
         }
     }
 }
@@ -131,6 +146,12 @@ pub struct Docgen<'env> {
     output: BTreeMap<ModuleId, String>,
     /// Map from module id to the file (relative to output dir) where module is documented.
     ref_map: BTreeMap<ModuleId, String>,
+    /// Map from module id to the set of modules using it.
+    module_users: BTreeMap<ModuleId, BTreeSet<ModuleId>>,
+    /// Map from function to the set of functions calling it.
+    function_callers: BTreeMap<(ModuleId, FunId), BTreeSet<(ModuleId, FunId)>>,
+    /// Root documents generated from templates, pairs of filenames and content.
+    root_docs: Vec<(String, String)>,
     /// Current code writer.
     writer: CodeWriter,
     /// Current module.
@@ -162,6 +183,9 @@ impl<'env> Docgen<'env> {
             declared_schemas: Default::default(),
             output: Default::default(),
             ref_map: Default::default(),
+            module_users: Default::default(),
+            function_callers: Default::default(),
+            root_docs: Default::default(),
             writer: CodeWriter::new(env.unknown_loc()),
             current_module: None,
             loc_to_spec_item_map: Default::default(),
@@ -173,6 +197,7 @@ impl<'env> Docgen<'env> {
     /// Returns the result of documentation generation, a vector of pairs of filenames
     /// and content.
     pub fn into_result(mut self) -> Vec<(String, String)> {
+        let root_docs = std::mem::take(&mut self.root_docs);
         std::mem::take(&mut self.output)
             .into_iter()
             .map(|(id, content)| {
@@ -181,19 +206,23 @@ impl<'env> Docgen<'env> {
                 path.push(fname);
                 (path.to_string_lossy().to_string(), content)
             })
+            .chain(root_docs)
             .collect_vec()
     }
 
     /// Generate documentation for all modules in the environment which are not in the dependency
-    /// set.
+    /// set, and the root documents of the templates in the options.
     pub fn gen(&mut self) {
         self.compute_declared_schemas();
         self.compute_file_map();
+        self.compute_users_and_callers();
         for m in self.env.get_modules() {
             if !m.is_dependency() {
                 self.gen_module(&m);
             }
         }
+        self.current_module = None;
+        self.gen_root_docs();
     }
 
     /// Compute the schemas declared in all modules. This information is currently not directly
@@ -210,6 +239,28 @@ impl<'env> Docgen<'env> {
         }
     }
 
+    /// Computes the users of all modules and the callers of all functions in the environment, so
+    /// they are available to generate reference links.
+    fn compute_users_and_callers(&mut self) {
+        for module_env in self.env.get_modules() {
+            for used in module_env.get_used_modules() {
+                self.module_users
+                    .entry(used)
+                    .or_insert_with(BTreeSet::new)
+                    .insert(module_env.get_id());
+            }
+            for func_env in module_env.get_functions() {
+                let caller = (module_env.get_id(), func_env.get_id());
+                for callee in func_env.get_called_functions() {
+                    self.function_callers
+                        .entry(callee)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(caller);
+                }
+            }
+        }
+    }
+
     /// Computes file locations for all modules in the environment, so they are available
     /// to generate reference links.
     fn compute_file_map(&mut self) {
@@ -320,6 +371,10 @@ impl<'env> Docgen<'env> {
 
         self.doc_text(module_env, module_env.get_doc());
 
+        if self.options.include_dep_links {
+            self.gen_dep_links(module_env);
+        }
+
         let spec_block_map = self.organize_spec_blocks(module_env);
 
         if self.options.specs_inlined {
@@ -494,9 +549,144 @@ impl<'env> Docgen<'env> {
             );
             self.end_collapsed();
         }
+        if self.options.include_call_graph {
+            self.gen_call_graph(func_env);
+        }
         self.decrement_section_nest();
     }
 
+    /// Generates links to the modules used by a module, and to the modules using it.
+    fn gen_dep_links(&self, module_env: &ModuleEnv<'_>) {
+        let uses = module_env.get_used_modules();
+        let no_users = BTreeSet::new();
+        let users = self
+            .module_users
+            .get(&module_env.get_id())
+            .unwrap_or(&no_users);
+        if uses.is_empty() && users.is_empty() {
+            return;
+        }
+        self.begin_collapsed("Dependencies");
+        self.link_list_text("Uses", uses.iter().map(|id| self.module_link(*id)));
+        self.link_list_text("Used by", users.iter().map(|id| self.module_link(*id)));
+        self.end_collapsed();
+    }
+
+    /// Generates links to the functions called by a function, and to the functions calling it.
+    fn gen_call_graph(&self, func_env: &FunctionEnv<'_>) {
+        let callees = func_env.get_called_functions();
+        let no_callers = BTreeSet::new();
+        let callers = self
+            .function_callers
+            .get(&(func_env.module_env.get_id(), func_env.get_id()))
+            .unwrap_or(&no_callers);
+        if callees.is_empty() && callers.is_empty() {
+            return;
+        }
+        self.begin_collapsed("Call graph");
+        self.link_list_text(
+            "Calls",
+            callees
+                .iter()
+                .map(|(mid, fid)| self.function_link(*mid, *fid)),
+        );
+        self.link_list_text(
+            "Called by",
+            callers
+                .iter()
+                .map(|(mid, fid)| self.function_link(*mid, *fid)),
+        );
+        self.end_collapsed();
+    }
+
+    /// Generates the root documents from the templates in the options.
+    fn gen_root_docs(&mut self) {
+        // Map from the name of a module, script or root document to its content.
+        let mut contents = BTreeMap::new();
+        for (id, content) in &self.output {
+            let module_env = self.env.get_module(*id);
+            contents.insert(self.module_short_name(&module_env), content.clone());
+        }
+        for template in &self.options.root_doc_templates {
+            let path = PathBuf::from(template);
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => self.expand_template(template, &content, &contents),
+                Err(error) => {
+                    self.env.error(
+                        &self.env.unknown_loc(),
+                        &format!(
+                            "cannot read root document template `{}`: {}",
+                            template, error
+                        ),
+                    );
+                    continue;
+                }
+            };
+            let mut output_path = PathBuf::from(&self.options.output_directory);
+            output_path.push(path.file_name().expect("file name"));
+            let name = path
+                .file_stem()
+                .expect("file name")
+                .to_string_lossy()
+                .to_string();
+            contents.insert(name, content.clone());
+            self.root_docs
+                .push((output_path.to_string_lossy().to_string(), content));
+        }
+    }
+
+    /// Expands the placeholders of a root document template, given the content of the modules,
+    /// scripts and root documents which can be included.
+    fn expand_template(
+        &self,
+        template: &str,
+        text: &str,
+        contents: &BTreeMap<String, String>,
+    ) -> String {
+        static REX: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"^>\s*\{\{\s*move-(?P<cmd>include|toc)(\s+(?P<arg>[\w.]+))?\s*\}\}\s*$")
+                .unwrap()
+        });
+        let mut r = String::new();
+        for line in text.lines() {
+            match REX.captures(line) {
+                Some(cap) if &cap["cmd"] == "toc" => {
+                    for (id, fname) in &self.ref_map {
+                        if self.output.contains_key(id) {
+                            let module_env = self.env.get_module(*id);
+                            r += &format!(
+                                "-  [`{}`]({}#{})\n",
+                                self.module_display_name(&module_env),
+                                fname,
+                                self.label_for_module(&module_env)
+                            );
+                        }
+                    }
+                }
+                Some(cap) => {
+                    let name = cap.name("arg").map(|m| m.as_str()).unwrap_or("");
+                    if let Some(content) = contents.get(name) {
+                        r += content;
+                    } else {
+                        self.env.error(
+                            &self.env.unknown_loc(),
+                            &format!(
+                                "unknown module, script or template `{}` included in root \
+                                 document template `{}`",
+                                name, template
+                            ),
+                        );
+                    }
+                }
+                None => {
+                    r += line;
+                    r += "\n";
+                }
+            }
+        }
+        r
+    }
+
     /// Generates documentation for a function signature.
     fn function_header_display(&self, func_env: &FunctionEnv<'_>) -> String {
         let name = self.name_string(func_env.get_name());
@@ -1040,6 +1230,69 @@ impl<'env> Docgen<'env> {
         format!("{}#{}", file, label)
     }
 
+    /// Return the name of a module, or the file name of a script.
+    fn module_display_name(&self, module_env: &ModuleEnv<'_>) -> String {
+        if module_env.is_script_module() {
+            PathBuf::from(module_env.get_source_path())
+                .file_name()
+                .expect("file name")
+                .to_string_lossy()
+                .to_string()
+        } else {
+            module_env
+                .get_name()
+                .display_full(self.env.symbol_pool())
+                .to_string()
+        }
+    }
+
+    /// Return the name by which a module, or a script, is included in root documents: the simple
+    /// name of a module, or the file name of a script without extension.
+    fn module_short_name(&self, module_env: &ModuleEnv<'_>) -> String {
+        if module_env.is_script_module() {
+            PathBuf::from(module_env.get_source_path())
+                .file_stem()
+                .expect("file name")
+                .to_string_lossy()
+                .to_string()
+        } else {
+            self.name_string(module_env.get_name().name()).to_string()
+        }
+    }
+
+    /// Return a link to the documentation of a module, or its name if it is not documented.
+    fn module_link(&self, id: ModuleId) -> String {
+        let module_env = self.env.get_module(id);
+        let name = self.module_display_name(&module_env);
+        if self.ref_map.contains_key(&id) {
+            format!("[`{}`]({})", name, self.ref_for_module(&module_env))
+        } else {
+            format!("`{}`", name)
+        }
+    }
+
+    /// Return a link to the documentation of a function, or its name if it is not documented.
+    fn function_link(&self, module_id: ModuleId, fun_id: FunId) -> String {
+        let module_env = self.env.get_module(module_id);
+        let func_env = module_env.get_function(fun_id);
+        let name = format!(
+            "{}::{}",
+            self.module_short_name(&module_env),
+            self.name_string(func_env.get_name())
+        );
+        if self.ref_map.contains_key(&module_id)
+            && (self.options.include_private_fun || func_env.is_public())
+        {
+            format!(
+                "[`{}`]({})",
+                name,
+                self.ref_for_module_item(&module_env, func_env.get_name())
+            )
+        } else {
+            format!("`{}`", name)
+        }
+    }
+
     /// Return the label for an item in a module.
     fn label_for_module_item(&self, module_env: &ModuleEnv<'_>, item: Symbol) -> String {
         self.label_for_module_item_str(module_env, self.name_string(item).as_str())
@@ -1080,6 +1333,14 @@ impl<'env> Docgen<'env> {
         }
     }
 
+    /// Emit an item with a comma-separated list of links, if the list is not empty.
+    fn link_list_text(&self, title: &str, links: impl Iterator<Item = String>) {
+        let links = links.join(", ");
+        if !links.is_empty() {
+            self.item_text(None, &format!("{}: {}", title, links));
+        }
+    }
+
     /// Begin a definition list.
     fn begin_definitions(&self) {
         emitln!(self.writer);
@@ -330,6 +330,7 @@ pub enum Operation {
     MaxU8,
     MaxU64,
     MaxU128,
+    MaxU256,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1027,6 +1027,24 @@ impl<'env> ModuleEnv<'env> {
         module_env.into_function(FunId::new(self.env.symbol_pool.make(view.name().as_str())))
     }
 
+    /// Returns the modules this module depends on, i.e. the modules other than itself for which it
+    /// declares a handle.
+    pub fn get_used_modules(&self) -> BTreeSet<ModuleId> {
+        let module = &self.data.module;
+        let self_handle = module.self_handle();
+        module
+            .module_handles()
+            .iter()
+            .filter(|handle| *handle != self_handle)
+            .filter_map(|handle| {
+                let module_name = self
+                    .env
+                    .to_module_name(&module.module_id_for_handle(handle));
+                self.env.find_module(&module_name).map(|m| m.get_id())
+            })
+            .collect()
+    }
+
     /// Gets a StructEnv in this module by name.
     pub fn find_struct(&self, name: Symbol) -> Option<StructEnv<'_>> {
         let id = StructId(name);
@@ -1104,6 +1122,7 @@ impl<'env> ModuleEnv<'env> {
             SignatureToken::U8 => Type::Primitive(PrimitiveType::U8),
             SignatureToken::U64 => Type::Primitive(PrimitiveType::U64),
             SignatureToken::U128 => Type::Primitive(PrimitiveType::U128),
+            SignatureToken::U256 => Type::Primitive(PrimitiveType::U256),
             SignatureToken::Address => Type::Primitive(PrimitiveType::Address),
             SignatureToken::Signer => Type::Primitive(PrimitiveType::Signer),
             SignatureToken::Reference(t) => {
@@ -1661,6 +1680,23 @@ impl<'env> FunctionEnv<'env> {
         self.get_loc()
     }
 
+    /// Returns the functions called by this function, which might be from this or other modules.
+    pub fn get_called_functions(&self) -> BTreeSet<(ModuleId, FunId)> {
+        let module = &self.module_env.data.module;
+        self.get_bytecode()
+            .iter()
+            .filter_map(|bytecode| match bytecode {
+                Bytecode::Call(idx) => Some(*idx),
+                Bytecode::CallGeneric(idx) => Some(module.function_instantiation_at(*idx).handle),
+                _ => None,
+            })
+            .map(|idx| {
+                let func_env = self.module_env.get_called_function(idx);
+                (func_env.module_env.get_id(), func_env.get_id())
+            })
+            .collect()
+    }
+
     /// Returns the bytecode associated with this function.
     pub fn get_bytecode(&self) -> &[Bytecode] {
         let function_definition = self
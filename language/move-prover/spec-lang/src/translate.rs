@@ -476,6 +476,18 @@ impl<'env> Translator<'env> {
                 },
             );
 
+            add_builtin(
+                self,
+                self.builtin_fun_symbol("max_u256"),
+                SpecFunEntry {
+                    loc: loc.clone(),
+                    oper: Operation::MaxU256,
+                    type_params: vec![],
+                    arg_types: vec![],
+                    result_type: num_t.clone(),
+                },
+            );
+
             // Vectors
             add_builtin(
                 self,
@@ -3049,6 +3061,7 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                         U8 => Type::new_prim(PrimitiveType::U8),
                         U64 => Type::new_prim(PrimitiveType::U64),
                         U128 => Type::new_prim(PrimitiveType::U128),
+                        U256 => Type::new_prim(PrimitiveType::U256),
                         Vector => Type::Vector(Box::new(self.translate_hlir_base_type(&args[0]))),
                         Bool => Type::new_prim(PrimitiveType::Bool),
                     },
@@ -3115,6 +3128,9 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                         "u128" => {
                             return check_zero_args(self, Type::new_prim(PrimitiveType::U128));
                         }
+                        "u256" => {
+                            return check_zero_args(self, Type::new_prim(PrimitiveType::U256));
+                        }
                         "num" => return check_zero_args(self, Type::new_prim(PrimitiveType::Num)),
                         "range" => {
                             return check_zero_args(self, Type::new_prim(PrimitiveType::Range));
@@ -3348,6 +3364,10 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                 Value::Number(BigUint::from_u128(*x).unwrap()),
                 Type::new_prim(PrimitiveType::U128),
             )),
+            EA::Value_::U256(x) => Some((
+                Value::Number(BigUint::from_bytes_le(&x.to_le_bytes())),
+                Type::new_prim(PrimitiveType::U256),
+            )),
             EA::Value_::Bool(x) => Some((Value::Bool(*x), Type::new_prim(PrimitiveType::Bool))),
             EA::Value_::Bytearray(x) => {
                 let ty = Type::Vector(Box::new(Type::new_prim(PrimitiveType::U8)));
@@ -42,6 +42,7 @@ pub enum PrimitiveType {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     // Types only appearing in specifications
@@ -103,6 +104,7 @@ impl Type {
             if let PrimitiveType::U8
             | PrimitiveType::U64
             | PrimitiveType::U128
+            | PrimitiveType::U256
             | PrimitiveType::Num = p
             {
                 return true;
@@ -553,6 +555,7 @@ impl fmt::Display for PrimitiveType {
             U8 => f.write_str("u8"),
             U64 => f.write_str("u64"),
             U128 => f.write_str("u128"),
+            U256 => f.write_str("u256"),
             Address => f.write_str("address"),
             Signer => f.write_str("signer"),
             Range => f.write_str("range"),
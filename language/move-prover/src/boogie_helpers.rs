@@ -93,9 +93,11 @@ pub fn boogie_type_value(env: &GlobalEnv, ty: &Type) -> String {
     match ty {
         Type::Primitive(p) => match p {
             PrimitiveType::Bool => "$BooleanType()".to_string(),
-            PrimitiveType::U8 | PrimitiveType::U64 | PrimitiveType::U128 | PrimitiveType::Num => {
-                "$IntegerType()".to_string()
-            }
+            PrimitiveType::U8
+            | PrimitiveType::U64
+            | PrimitiveType::U128
+            | PrimitiveType::U256
+            | PrimitiveType::Num => "$IntegerType()".to_string(),
             PrimitiveType::Address => "$AddressType()".to_string(),
             // TODO fix this for a real boogie type
             PrimitiveType::Signer => "$AddressType()".to_string(),
@@ -182,6 +184,7 @@ fn boogie_well_formed_expr_impl(
             PrimitiveType::U8 => conds.push(format!("$IsValidU8({})", name)),
             PrimitiveType::U64 => conds.push(format!("$IsValidU64({})", name)),
             PrimitiveType::U128 => conds.push(format!("$IsValidU128({})", name)),
+            PrimitiveType::U256 => conds.push(format!("$IsValidU256({})", name)),
             PrimitiveType::Num => conds.push(format!("$IsValidNum({})", name)),
             PrimitiveType::Bool => conds.push(format!("is#$Boolean({})", name)),
             PrimitiveType::Address => conds.push(format!("is#$Address({})", name)),
@@ -1144,6 +1144,10 @@ impl ModelValue {
                 "{}u128",
                 self.extract_primitive("$Integer")?.to_string()
             ))),
+            Type::Primitive(PrimitiveType::U256) => Some(PrettyDoc::text(format!(
+                "{}u256",
+                self.extract_primitive("$Integer")?.to_string()
+            ))),
             Type::Primitive(PrimitiveType::Num) => Some(PrettyDoc::text(format!(
                 "{}u128",
                 self.extract_primitive("$Integer")?.to_string()
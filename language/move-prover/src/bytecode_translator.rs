@@ -993,6 +993,7 @@ impl<'env> ModuleTranslator<'env> {
                     Constant::U8(num) => format!("$Integer({})", num),
                     Constant::U64(num) => format!("$Integer({})", num),
                     Constant::U128(num) => format!("$Integer({})", num),
+                    Constant::U256(num) => format!("$Integer({})", num),
                     Constant::Address(val) => format!("$Address({})", val),
                     Constant::TxnSenderAddress => "$TxnSender($txn)".to_string(),
                     Constant::ByteArray(val) => boogie_byte_blob(val),
@@ -1391,6 +1392,13 @@ impl<'env> ModuleTranslator<'env> {
                         emitln!(self.writer, &propagate_abort());
                         emitln!(self.writer, &update_and_track_local(dest, "$tmp"));
                     }
+                    CastU256 => {
+                        let src = srcs[0];
+                        let dest = dests[0];
+                        emitln!(self.writer, "call $tmp := $CastU256({});", str_local(src));
+                        emitln!(self.writer, &propagate_abort());
+                        emitln!(self.writer, &update_and_track_local(dest, "$tmp"));
+                    }
                     Not => {
                         let src = srcs[0];
                         let dest = dests[0];
@@ -1405,6 +1413,7 @@ impl<'env> ModuleTranslator<'env> {
                             Type::Primitive(PrimitiveType::U8) => "U8",
                             Type::Primitive(PrimitiveType::U64) => "U64",
                             Type::Primitive(PrimitiveType::U128) => "U128",
+                            Type::Primitive(PrimitiveType::U256) => "U256",
                             _ => unreachable!(),
                         };
                         emitln!(
@@ -1438,6 +1447,7 @@ impl<'env> ModuleTranslator<'env> {
                             Type::Primitive(PrimitiveType::U8) => "U8",
                             Type::Primitive(PrimitiveType::U64) => "U64",
                             Type::Primitive(PrimitiveType::U128) => "U128",
+                            Type::Primitive(PrimitiveType::U256) => "U256",
                             _ => unreachable!(),
                         };
                         emitln!(
@@ -277,7 +277,8 @@ impl Options {
                 Arg::with_name("abigen")
                     .long("abigen")
                     .help("run the ABI generator instead of the prover. \
-                    Generated ABIs will be written into the directory `./abi` unless configured otherwise via toml"),
+                    Generated ABIs will be written into the directory `./abi` unless configured otherwise via toml, \
+                    with the ABIs of modules in its `modules` sub-directory"),
             )
             .arg(
                 Arg::with_name("verify")
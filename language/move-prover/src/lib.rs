@@ -74,7 +74,7 @@ pub fn run_move_prover<W: WriteColor>(
 
     // Until this point, prover and docgen have same code. Here we part ways.
     if options.run_docgen {
-        return run_docgen(&env, &options, now);
+        return run_docgen(&env, &options, error_writer, now);
     }
     // Same for ABI generator.
     if options.run_abigen {
@@ -137,11 +137,20 @@ pub fn run_move_prover_errors_to_stderr(options: Options) -> anyhow::Result<()>
     run_move_prover(&mut error_writer, options)
 }
 
-fn run_docgen(env: &GlobalEnv, options: &Options, now: Instant) -> anyhow::Result<()> {
+fn run_docgen<W: WriteColor>(
+    env: &GlobalEnv,
+    options: &Options,
+    error_writer: &mut W,
+    now: Instant,
+) -> anyhow::Result<()> {
     let mut generator = Docgen::new(env, &options.docgen);
     let checking_elapsed = now.elapsed();
     info!("generating documentation");
     generator.gen();
+    if env.has_errors() {
+        env.report_errors(error_writer);
+        return Err(anyhow!("exiting with documentation generation errors"));
+    }
     for (file, content) in generator.into_result() {
         let path = PathBuf::from(&file);
         fs::create_dir_all(path.parent().unwrap())?;
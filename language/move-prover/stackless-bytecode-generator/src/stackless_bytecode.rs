@@ -3,6 +3,7 @@
 
 use crate::function_target::FunctionTarget;
 use itertools::Itertools;
+use move_core_types::u256::U256;
 use num::BigUint;
 use spec_lang::{
     env::{FunId, ModuleId, StructId},
@@ -77,6 +78,7 @@ pub enum Constant {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
     Address(BigUint),
     ByteArray(Vec<u8>),
     TxnSenderAddress,
@@ -118,6 +120,7 @@ pub enum Operation {
     CastU8,
     CastU64,
     CastU128,
+    CastU256,
     Not,
 
     // Binary
@@ -574,6 +577,7 @@ impl<'env> fmt::Display for OperationDisplay<'env> {
             CastU8 => write!(f, "(u8)")?,
             CastU64 => write!(f, "(u64)")?,
             CastU128 => write!(f, "(u128)")?,
+            CastU256 => write!(f, "(u256)")?,
             Not => write!(f, "!")?,
 
             // Binary
@@ -637,6 +641,7 @@ impl fmt::Display for Constant {
             U8(x) => write!(f, "{}", x)?,
             U64(x) => write!(f, "{}", x)?,
             U128(x) => write!(f, "{}", x)?,
+            U256(x) => write!(f, "{}", x)?,
             Address(x) => write!(f, "0x{}", x.to_str_radix(16))?,
             ByteArray(x) => write!(f, "{:?}", x)?,
             TxnSenderAddress => write!(f, "txn_sender")?,
@@ -129,6 +129,69 @@ impl<'a> StacklessBytecodeGenerator<'a> {
             .get_type_actuals(Some(type_params_index))
     }
 
+    /// Translates a vector bytecode into a call to the function of the `Vector` module which
+    /// implements it, with the element type as the only type parameter.
+    fn translate_vector_op(
+        &mut self,
+        attr_id: AttrId,
+        fun_name: &str,
+        elem_index: SignatureIndex,
+        num_args: usize,
+        return_types: impl FnOnce(Type) -> Vec<Type>,
+    ) {
+        let elem_type = self.get_type_params(elem_index).remove(0);
+        let env = self.func_env.module_env.env;
+        let vector_module = env
+            .find_module_by_name(env.symbol_pool().make("Vector"))
+            .expect("Vector module must be loaded to translate vector bytecodes");
+        let vector_fun = vector_module
+            .find_function(env.symbol_pool().make(fun_name))
+            .unwrap_or_else(|| panic!("Vector module has no function `{}`", fun_name));
+
+        let mut arg_temp_indices = vec![];
+        for _ in 0..num_args {
+            arg_temp_indices.push(self.temp_stack.pop().unwrap());
+        }
+        arg_temp_indices.reverse();
+        let mut return_temp_indices = vec![];
+        for return_type in return_types(elem_type.clone()) {
+            let return_temp_index = self.temp_count;
+            return_temp_indices.push(return_temp_index);
+            self.temp_stack.push(return_temp_index);
+            self.local_types.push(return_type);
+            self.temp_count += 1;
+        }
+        self.code.push(Bytecode::Call(
+            attr_id,
+            return_temp_indices,
+            Operation::Function(vector_module.get_id(), vector_fun.get_id(), vec![elem_type]),
+            arg_temp_indices,
+        ));
+    }
+
+    /// Reports an instruction which cannot be translated yet. Its operands are replaced by fresh
+    /// temporaries of the result types, so that the translation can go on.
+    fn unsupported(
+        &mut self,
+        code_offset: CodeOffset,
+        what: &str,
+        num_args: usize,
+        return_types: Vec<Type>,
+    ) {
+        self.func_env.module_env.env.error(
+            &self.func_env.get_bytecode_loc(code_offset),
+            &format!("{} is not yet supported", what),
+        );
+        for _ in 0..num_args {
+            self.temp_stack.pop();
+        }
+        for return_type in return_types {
+            self.temp_stack.push(self.temp_count);
+            self.local_types.push(return_type);
+            self.temp_count += 1;
+        }
+    }
+
     #[allow(clippy::cognitive_complexity)]
     pub fn generate_bytecode(
         &mut self,
@@ -322,6 +385,15 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::LdU256(number) => {
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U256));
+                self.code
+                    .push(Bytecode::Load(attr_id, temp_index, Constant::U256(*number)));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::CastU8 => {
                 let operand_index = self.temp_stack.pop().unwrap();
                 let temp_index = self.temp_count;
@@ -352,6 +424,16 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::CastU256 => {
+                let operand_index = self.temp_stack.pop().unwrap();
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U256));
+                self.code
+                    .push(mk_unary(Operation::CastU256, temp_index, operand_index));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::LdConst(idx) => {
                 let temp_index = self.temp_count;
                 self.temp_stack.push(temp_index);
@@ -1067,6 +1149,57 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::VecPack(si, num) => {
+                if *num != 0 {
+                    let elem_type = self.get_type_params(*si).remove(0);
+                    self.unsupported(
+                        code_offset,
+                        "packing of vector with elements",
+                        *num as usize,
+                        vec![Type::Vector(Box::new(elem_type))],
+                    );
+                    return;
+                }
+                self.translate_vector_op(attr_id, "empty", *si, 0, |elem| {
+                    vec![Type::Vector(Box::new(elem))]
+                })
+            }
+            MoveBytecode::VecLen(si) => self.translate_vector_op(attr_id, "length", *si, 1, |_| {
+                vec![Type::Primitive(PrimitiveType::U64)]
+            }),
+            MoveBytecode::VecImmBorrow(si) => {
+                self.translate_vector_op(attr_id, "borrow", *si, 2, |elem| {
+                    vec![Type::Reference(false, Box::new(elem))]
+                })
+            }
+            MoveBytecode::VecMutBorrow(si) => {
+                self.translate_vector_op(attr_id, "borrow_mut", *si, 2, |elem| {
+                    vec![Type::Reference(true, Box::new(elem))]
+                })
+            }
+            MoveBytecode::VecPushBack(si) => {
+                self.translate_vector_op(attr_id, "push_back", *si, 2, |_| vec![])
+            }
+            MoveBytecode::VecPopBack(si) => {
+                self.translate_vector_op(attr_id, "pop_back", *si, 1, |elem| vec![elem])
+            }
+            MoveBytecode::VecUnpack(si, num) => {
+                if *num != 0 {
+                    let elem_type = self.get_type_params(*si).remove(0);
+                    self.unsupported(
+                        code_offset,
+                        "unpacking of vector with elements",
+                        1,
+                        vec![elem_type; *num as usize],
+                    );
+                    return;
+                }
+                self.translate_vector_op(attr_id, "destroy_empty", *si, 1, |_| vec![])
+            }
+            MoveBytecode::VecSwap(si) => {
+                self.translate_vector_op(attr_id, "swap", *si, 3, |_| vec![])
+            }
+
             MoveBytecode::Nop => self.code.push(Bytecode::Nop(attr_id)),
         }
     }
@@ -1087,6 +1220,7 @@ impl<'a> StacklessBytecodeGenerator<'a> {
             (Type::Primitive(PrimitiveType::U8), MoveValue::U8(b)) => Constant::U8(*b),
             (Type::Primitive(PrimitiveType::U64), MoveValue::U64(b)) => Constant::U64(*b),
             (Type::Primitive(PrimitiveType::U128), MoveValue::U128(b)) => Constant::U128(*b),
+            (Type::Primitive(PrimitiveType::U256), MoveValue::U256(b)) => Constant::U256(*b),
             (Type::Primitive(PrimitiveType::Address), MoveValue::Address(a)) => {
                 Constant::Address(ModuleEnv::addr_to_big_uint(a))
             }
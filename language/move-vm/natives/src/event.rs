@@ -2,14 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libra_types::{
+    account_address::AccountAddress,
     contract_event::ContractEvent,
     event::EventKey,
     vm_error::{StatusCode, VMStatus},
 };
 use move_core_types::gas_schedule::ZERO_GAS_UNITS;
 use move_vm_types::{
+    gas_schedule::NativeCostIndex,
     loaded_data::runtime_types::Type,
-    natives::function::{NativeContext, NativeResult},
+    natives::function::{native_gas, NativeContext, NativeResult},
     values::Value,
 };
 use std::{collections::VecDeque, convert::TryFrom};
@@ -40,3 +42,27 @@ pub fn native_emit_event(
 
     Ok(NativeResult::ok(ZERO_GAS_UNITS, vec![]))
 }
+
+/// The key of the stream of the events of the type argument of an account, derived from the
+/// address and the type
+pub fn native_event_stream_key(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.len() == 1);
+
+    let mut ty_args = context.convert_to_fat_types(ty_args)?;
+    let type_tag = ty_args.pop().unwrap().type_tag()?;
+    let address = pop_arg!(arguments, AccountAddress);
+
+    let key = EventKey::new_for_type(&address, &type_tag);
+    // Deriving the key hashes the type
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::SHA3_256,
+        EventKey::LENGTH,
+    );
+    Ok(NativeResult::ok(cost, vec![Value::vector_u8(key.to_vec())]))
+}
@@ -0,0 +1,101 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_types::vm_error::{StatusCode, VMStatus};
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeContext, NativeResult},
+    values::Value,
+};
+use std::collections::VecDeque;
+use vm::errors::VMResult;
+
+/// The rounding modes of `FixedPoint64`, see `FixedPoint64::ROUND_*`.
+const ROUND_DOWN: u8 = 0;
+const ROUND_UP: u8 = 1;
+const ROUND_NEAREST: u8 = 2;
+
+/// Computes `a * b / c` with the given rounding mode, keeping the full 256 bit product. Returns
+/// `None` if `c` is zero, the rounding mode is unknown, or the result doesn't fit in a u128.
+pub fn mul_div(a: u128, b: u128, c: u128, rounding: u8) -> Option<u128> {
+    if c == 0 || rounding > ROUND_NEAREST {
+        return None;
+    }
+    let (high, low) = full_mul(a, b);
+    // The quotient has more than 128 bits
+    if high >= c {
+        return None;
+    }
+
+    // Long division of the 256 bit product, one bit of the low half at a time. The remainder
+    // stays below `c`, so it overflows at most by the bit shifted out.
+    let mut quotient = 0u128;
+    let mut remainder = high;
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= c {
+            remainder = remainder.wrapping_sub(c);
+            quotient |= 1;
+        }
+    }
+
+    let round_up = match rounding {
+        ROUND_DOWN => false,
+        ROUND_UP => remainder != 0,
+        // Ties are rounded up, i.e., when the remainder is at least half of `c`
+        _ => remainder != 0 && remainder >= c - remainder,
+    };
+    if round_up {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Returns the high and low halves of the 256 bit product of `a` and `b`.
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::max_value() as u128;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 64) + (low_high & MASK) + (high_low & MASK);
+    let low = (low_low & MASK) | (middle << 64);
+    let high = high_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+    (high, low)
+}
+
+pub fn native_mul_div(
+    context: &impl NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 4);
+
+    let rounding = pop_arg!(arguments, u8);
+    let c = pop_arg!(arguments, u128);
+    let b = pop_arg!(arguments, u128);
+    let a = pop_arg!(arguments, u128);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::FIXED_POINT_MUL_DIV,
+        1,
+    );
+    match mul_div(a, b, c, rounding) {
+        Some(result) => Ok(NativeResult::ok(cost, vec![Value::u128(result)])),
+        // Same failure as the arithmetic instructions
+        None => Ok(NativeResult::err(
+            cost,
+            VMStatus::new(StatusCode::ARITHMETIC_ERROR),
+        )),
+    }
+}
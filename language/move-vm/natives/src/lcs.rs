@@ -1,10 +1,15 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_types::vm_error::{sub_status::NFE_LCS_SERIALIZATION_FAILURE, StatusCode, VMStatus};
+use libra_types::vm_error::{
+    sub_status::{
+        NFE_LCS_DESERIALIZATION_FAILURE, NFE_LCS_SERIALIZATION_FAILURE, NFE_LCS_UNSUPPORTED_TYPE,
+    },
+    StatusCode, VMStatus,
+};
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
-    loaded_data::runtime_types::Type,
+    loaded_data::{runtime_types::Type, types::FatType},
     natives::function::{native_gas, NativeContext, NativeResult},
     values::{values_impl::Reference, Value},
 };
@@ -44,3 +49,61 @@ pub fn native_to_bytes(
         vec![Value::vector_u8(serialized_value)],
     ))
 }
+
+/// Rust implementation of Move's `native public fun from_bytes<T: copyable>(vector<u8>): T`
+pub fn native_from_bytes(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+
+    // The bytes are attacker controlled, so they are paid for before they are decoded. Every
+    // value of a supported type takes at least one byte, so decoding is linear in their length.
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::LCS_FROM_BYTES,
+        bytes.len(),
+    );
+
+    let mut ty_args = context.convert_to_fat_types(ty_args)?;
+    let ty = ty_args.pop().unwrap();
+    if !is_deserializable(&ty) {
+        return Ok(NativeResult::err(
+            cost,
+            VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                .with_sub_status(NFE_LCS_UNSUPPORTED_TYPE),
+        ));
+    }
+    // Fails on malformed or non-canonical encodings, and on trailing bytes
+    match Value::simple_deserialize(&bytes, &ty) {
+        Ok(value) => Ok(NativeResult::ok(cost, vec![value])),
+        Err(_) => Ok(NativeResult::err(
+            cost,
+            VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                .with_sub_status(NFE_LCS_DESERIALIZATION_FAILURE),
+        )),
+    }
+}
+
+/// Whether values of `ty` may be created from bytes. Resources and signers are ruled out by the
+/// `copyable` constraint of `from_bytes`, but structs are too: only their module may create them.
+fn is_deserializable(ty: &FatType) -> bool {
+    match ty {
+        FatType::Bool
+        | FatType::U8
+        | FatType::U64
+        | FatType::U128
+        | FatType::U256
+        | FatType::Address => true,
+        FatType::Vector(ty) => is_deserializable(ty),
+        FatType::Signer
+        | FatType::Struct(_)
+        | FatType::Reference(_)
+        | FatType::MutableReference(_)
+        | FatType::TyParam(_) => false,
+    }
+}
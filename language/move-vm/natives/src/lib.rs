@@ -7,8 +7,10 @@ extern crate move_vm_types;
 pub mod account;
 pub mod debug;
 pub mod event;
+pub mod fixed_point;
 pub mod hash;
 pub mod lcs;
 pub mod signature;
 pub mod signer;
+pub mod table;
 pub mod vector;
@@ -3,7 +3,9 @@
 
 use bit_vec::BitVec;
 use libra_crypto::{
+    bls12381::{BLS12381PublicKey, BLS12381Signature},
     ed25519::{self, Ed25519PublicKey, Ed25519Signature},
+    secp256k1,
     traits::*,
     HashValue,
 };
@@ -106,6 +108,81 @@ pub fn native_ed25519_signature_verification(
     let return_values = vec![Value::bool(bool_value)];
     Ok(NativeResult::ok(cost, return_values))
 }
+/// Recover the secp256k1 public key of a signature, returning it with `true`, or an empty key with
+/// `false` if none can be recovered.
+pub fn native_secp256k1_recover(
+    context: &impl NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let signature = pop_arg!(arguments, Vec<u8>);
+    let recovery_id = pop_arg!(arguments, u8);
+    let message_hash = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::SECP256K1_RECOVER, 1);
+    if message_hash.len() != secp256k1::SECP256K1_MESSAGE_LENGTH
+        || signature.len() != secp256k1::SECP256K1_SIGNATURE_LENGTH
+    {
+        return Ok(NativeResult::err(
+            cost,
+            VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR).with_sub_status(DEFAULT_ERROR_CODE),
+        ));
+    }
+
+    let return_values = match secp256k1::recover(&message_hash, &signature, recovery_id) {
+        Ok(public_key) => vec![Value::vector_u8(public_key.to_vec()), Value::bool(true)],
+        Err(_) => vec![Value::vector_u8(vec![]), Value::bool(false)],
+    };
+    Ok(NativeResult::ok(cost, return_values))
+}
+
+pub fn native_bls12381_signature_verification(
+    context: &impl NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::BLS12381_VERIFY,
+        msg.len(),
+    );
+
+    let sig = match BLS12381Signature::try_from(signature.as_slice()) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return Ok(NativeResult::err(
+                cost,
+                VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                    .with_sub_status(SIGNATURE_DESERIALIZATION_FAILURE),
+            ));
+        }
+    };
+    let pk = match BLS12381PublicKey::try_from(pubkey.as_slice()) {
+        Ok(pk) => pk,
+        Err(_) => {
+            return Ok(NativeResult::err(
+                cost,
+                VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                    .with_sub_status(PUBLIC_KEY_DESERIALIZATION_FAILURE),
+            ));
+        }
+    };
+
+    let bool_value = sig.verify_arbitrary_msg(msg.as_slice(), &pk).is_ok();
+    let return_values = vec![Value::bool(bool_value)];
+    Ok(NativeResult::ok(cost, return_values))
+}
+
 // TODO: activate the batch feature in the import of libra-crypto in the
 // present crate, once https://github.com/libra/libra/issues/3567 is fixed
 /// Batch verify a collection of signatures using a bitmap for matching signatures to keys.
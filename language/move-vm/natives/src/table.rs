@@ -0,0 +1,174 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The natives of the `Table` module. The entries of a table are stored in the data store, each
+//! under a path derived from the handle of the table and the LCS encoding of its key, and are
+//! paid for per entry accessed, in proportion to the size of the key and of the value.
+
+use libra_types::{
+    access_path::AccessPath,
+    vm_error::{
+        sub_status::{
+            NFE_LCS_SERIALIZATION_FAILURE, NFE_TABLE_ENTRY_ALREADY_EXISTS,
+            NFE_TABLE_ENTRY_NOT_FOUND,
+        },
+        StatusCode, VMStatus,
+    },
+};
+use move_core_types::gas_schedule::{GasCarrier, GasUnits};
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::{runtime_types::Type, types::FatType},
+    natives::function::{native_gas, NativeContext, NativeResult},
+    values::{values_impl::Reference, Value},
+};
+use std::collections::VecDeque;
+use vm::errors::VMResult;
+
+/// Rust implementation of Move's `native fun add_entry<K, V>(vector<u8>, &K, V)`
+pub fn native_add_entry(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 2);
+    debug_assert!(args.len() == 3);
+
+    let (key_type, value_type) = entry_types(context, ty_args)?;
+    let value = args.pop_back().unwrap();
+    let (ap, key_len) = match entry_access_path(&mut args, &key_type)? {
+        Some(entry) => entry,
+        None => return Ok(serialization_failure(context)),
+    };
+    let blob = match value.simple_serialize(&value_type) {
+        Some(blob) => blob,
+        None => return Ok(serialization_failure(context)),
+    };
+
+    let cost = entry_cost(context, key_len, blob.len());
+    if context.read_table_entry(&ap)?.is_some() {
+        return Ok(NativeResult::err(
+            cost,
+            VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                .with_sub_status(NFE_TABLE_ENTRY_ALREADY_EXISTS),
+        ));
+    }
+    context.write_table_entry(ap, Some(blob))?;
+    Ok(NativeResult::ok(cost, vec![]))
+}
+
+/// Rust implementation of Move's `native fun read_entry<K, V: copyable>(vector<u8>, &K): V`
+pub fn native_read_entry(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    take_entry(context, ty_args, args, false)
+}
+
+/// Rust implementation of Move's `native fun remove_entry<K, V>(vector<u8>, &K): V`
+pub fn native_remove_entry(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    take_entry(context, ty_args, args, true)
+}
+
+/// Rust implementation of Move's `native fun contains_entry<K>(vector<u8>, &K): bool`
+pub fn native_contains_entry(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> VMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let key_type = context.convert_to_fat_types(ty_args)?.pop().unwrap();
+    let (ap, key_len) = match entry_access_path(&mut args, &key_type)? {
+        Some(entry) => entry,
+        None => return Ok(serialization_failure(context)),
+    };
+    let exists = context.read_table_entry(&ap)?.is_some();
+    let cost = entry_cost(context, key_len, 0);
+    Ok(NativeResult::ok(cost, vec![Value::bool(exists)]))
+}
+
+// Reads the value of an entry, and removes the entry if `remove` is true.
+fn take_entry(
+    context: &mut impl NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+    remove: bool,
+) -> VMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 2);
+    debug_assert!(args.len() == 2);
+
+    let (key_type, value_type) = entry_types(context, ty_args)?;
+    let (ap, key_len) = match entry_access_path(&mut args, &key_type)? {
+        Some(entry) => entry,
+        None => return Ok(serialization_failure(context)),
+    };
+    let blob = match context.read_table_entry(&ap)? {
+        Some(blob) => blob,
+        None => {
+            return Ok(NativeResult::err(
+                entry_cost(context, key_len, 0),
+                VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+                    .with_sub_status(NFE_TABLE_ENTRY_NOT_FOUND),
+            ))
+        }
+    };
+
+    let cost = entry_cost(context, key_len, blob.len());
+    // The entries of a table are only written by `add_entry`, with the value type of the table
+    let value = Value::simple_deserialize(&blob, &value_type)?;
+    if remove {
+        context.write_table_entry(ap, None)?;
+    }
+    Ok(NativeResult::ok(cost, vec![value]))
+}
+
+fn entry_types(context: &impl NativeContext, ty_args: Vec<Type>) -> VMResult<(FatType, FatType)> {
+    let mut ty_args = context.convert_to_fat_types(ty_args)?;
+    let value_type = ty_args.pop().unwrap();
+    let key_type = ty_args.pop().unwrap();
+    Ok((key_type, value_type))
+}
+
+// Pops the handle and the key reference off the arguments, and returns the path of the entry
+// along with the size of the serialized key, or `None` if the key cannot be serialized.
+fn entry_access_path(
+    args: &mut VecDeque<Value>,
+    key_type: &FatType,
+) -> VMResult<Option<(AccessPath, usize)>> {
+    let key_ref = pop_arg!(args, Reference);
+    let handle = pop_arg!(args, Vec<u8>);
+    Ok(key_ref.read_ref()?.simple_serialize(key_type).map(|key| {
+        (
+            AccessPath::table_entry_access_path(&handle, &key),
+            key.len(),
+        )
+    }))
+}
+
+// Each entry accessed is charged at least one unit, plus the size of its key and value.
+fn entry_cost(
+    context: &impl NativeContext,
+    key_len: usize,
+    value_len: usize,
+) -> GasUnits<GasCarrier> {
+    native_gas(
+        context.cost_table(),
+        NativeCostIndex::TABLE_ENTRY_ACCESS,
+        1 + key_len + value_len,
+    )
+}
+
+fn serialization_failure(context: &impl NativeContext) -> NativeResult {
+    NativeResult::err(
+        entry_cost(context, 0, 0),
+        VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
+            .with_sub_status(NFE_LCS_SERIALIZATION_FAILURE),
+    )
+}
@@ -1,7 +1,8 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_core_types::gas_schedule::GasAlgebra;
+use libra_types::vm_error::StatusCode;
+use move_core_types::gas_schedule::{GasAlgebra, GasCarrier, GasUnits};
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
     loaded_data::runtime_types::Type,
@@ -11,6 +12,20 @@ use move_vm_types::{
 use std::collections::VecDeque;
 use vm::errors::VMResult;
 
+/// Turns the failure of a vector operation into the failure of the native function
+fn native_result(
+    cost: GasUnits<GasCarrier>,
+    result: VMResult<Vec<Value>>,
+) -> VMResult<NativeResult> {
+    match result {
+        Ok(values) => Ok(NativeResult::ok(cost, values)),
+        Err(err) if err.major_status == StatusCode::NATIVE_FUNCTION_ERROR => {
+            Ok(NativeResult::err(cost, err))
+        }
+        Err(err) => Err(err),
+    }
+}
+
 pub fn native_empty(
     context: &impl NativeContext,
     ty_args: Vec<Type>,
@@ -20,7 +35,7 @@ pub fn native_empty(
     debug_assert!(args.is_empty());
 
     let cost = native_gas(context.cost_table(), NativeCostIndex::EMPTY, 1);
-    Vector::empty(cost, &ty_args[0], context)
+    native_result(cost, Vector::empty(&ty_args[0], context).map(|v| vec![v]))
 }
 
 pub fn native_length(
@@ -73,7 +88,10 @@ pub fn native_borrow(
 
     let cost = native_gas(context.cost_table(), NativeCostIndex::BORROW, 1);
 
-    r.borrow_elem(idx, cost, &ty_args[0], context)
+    native_result(
+        cost,
+        r.borrow_elem(idx, &ty_args[0], context).map(|v| vec![v]),
+    )
 }
 
 pub fn native_pop(
@@ -88,7 +106,7 @@ pub fn native_pop(
 
     let cost = native_gas(context.cost_table(), NativeCostIndex::POP_BACK, 1);
 
-    r.pop(cost, &ty_args[0], context)
+    native_result(cost, r.pop(&ty_args[0], context).map(|v| vec![v]))
 }
 
 pub fn native_destroy_empty(
@@ -103,7 +121,7 @@ pub fn native_destroy_empty(
 
     let cost = native_gas(context.cost_table(), NativeCostIndex::DESTROY_EMPTY, 1);
 
-    v.destroy_empty(cost, &ty_args[0], context)
+    native_result(cost, v.destroy_empty(&ty_args[0], context).map(|_| vec![]))
 }
 
 pub fn native_swap(
@@ -120,5 +138,8 @@ pub fn native_swap(
 
     let cost = native_gas(context.cost_table(), NativeCostIndex::SWAP, 1);
 
-    r.swap(idx1, idx2, cost, &ty_args[0], context)
+    native_result(
+        cost,
+        r.swap(idx1, idx2, &ty_args[0], context).map(|_| vec![]),
+    )
 }
@@ -0,0 +1,42 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_metrics::{
+    register_histogram, register_histogram_vec, register_int_counter_vec, Histogram, HistogramVec,
+    IntCounterVec,
+};
+use once_cell::sync::Lazy;
+
+/// Time spent loading a module that was not in the cache, with a "phase" label: "deserialize",
+/// "verify" (the bytecode verifier, on the module alone), "link" (loading and checking the
+/// dependencies) and "cache" (building the runtime representation).
+pub static MODULE_LOAD_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_move_vm_module_load_seconds",
+        "Histogram of the time spent loading a module, by phase",
+        &["phase"]
+    )
+    .unwrap()
+});
+
+/// Count the lookups of modules in the loader cache, with a "result" label to distinguish hits
+/// and misses.
+pub static MODULE_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_move_vm_module_cache_lookups",
+        "Number of lookups of modules in the loader cache",
+        &["result"]
+    )
+    .unwrap()
+});
+
+/// Depth in the dependency graph at which modules are loaded: 0 for a module loaded directly,
+/// 1 for its dependencies, and so on.
+pub static MODULE_DEPENDENCY_DEPTH: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "libra_move_vm_module_dependency_depth",
+        "Histogram of the depth in the dependency graph at which modules are loaded",
+        vec![0.0, 1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 16.0, 24.0, 32.0]
+    )
+    .unwrap()
+});
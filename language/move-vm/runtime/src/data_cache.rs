@@ -8,7 +8,7 @@ use libra_types::{
     vm_error::{StatusCode, VMStatus},
     write_set::{WriteOp, WriteSet, WriteSetMut},
 };
-use move_core_types::language_storage::ModuleId;
+use move_core_types::{gas_schedule::GasConstants, language_storage::ModuleId};
 use move_vm_types::{
     data_store::DataStore,
     loaded_data::types::FatStructType,
@@ -31,6 +31,29 @@ impl ConfigStorage for &dyn RemoteCache {
     }
 }
 
+/// Limits on the effects of a transaction, from the gas constants of the VM config. They are
+/// checked as the effects are made: events as they are emitted, and modules and table entries as
+/// they are written. Resources are mutated in place, so they are only accounted for when the
+/// write set is made.
+#[derive(Clone, Copy, Debug)]
+pub struct TransactionLimits {
+    pub max_write_set_size_in_bytes: u64,
+    pub max_write_ops: u64,
+    pub max_events: u64,
+    pub max_event_size_in_bytes: u64,
+}
+
+impl TransactionLimits {
+    pub fn new(gas_constants: &GasConstants) -> Self {
+        Self {
+            max_write_set_size_in_bytes: gas_constants.max_write_set_size_in_bytes,
+            max_write_ops: gas_constants.max_write_ops,
+            max_events: gas_constants.max_events,
+            max_event_size_in_bytes: gas_constants.max_event_size_in_bytes,
+        }
+    }
+}
+
 /// Transaction data cache. Keep updates within a transaction so they can all be published at
 /// once when the transaction succeeeds.
 ///
@@ -47,8 +70,14 @@ impl ConfigStorage for &dyn RemoteCache {
 pub struct TransactionDataCache<'txn> {
     data_map: BTreeMap<AccessPath, Option<(FatStructType, GlobalValue)>>,
     module_map: BTreeMap<ModuleId, Vec<u8>>,
+    source_map_map: BTreeMap<ModuleId, Vec<u8>>,
+    error_map_map: BTreeMap<ModuleId, Vec<u8>>,
+    table_entry_map: BTreeMap<AccessPath, Option<Vec<u8>>>,
     event_data: Vec<ContractEvent>,
     data_cache: &'txn dyn RemoteCache,
+    limits: Option<TransactionLimits>,
+    // The total size of the modules, source and error maps and table entries written so far
+    written_bytes: u64,
 }
 
 impl<'txn> TransactionDataCache<'txn> {
@@ -59,12 +88,49 @@ impl<'txn> TransactionDataCache<'txn> {
             data_cache,
             data_map: BTreeMap::new(),
             module_map: BTreeMap::new(),
+            source_map_map: BTreeMap::new(),
+            error_map_map: BTreeMap::new(),
+            table_entry_map: BTreeMap::new(),
             event_data: vec![],
+            limits: None,
+            written_bytes: 0,
         }
     }
 
-    /// Make a write set from the updated (dirty, deleted) global resources along with
-    /// published modules.
+    /// Create a `TransactionDataCache` failing the transaction as soon as its effects exceed
+    /// `limits`.
+    pub fn with_limits(data_cache: &'txn dyn RemoteCache, limits: TransactionLimits) -> Self {
+        TransactionDataCache {
+            limits: Some(limits),
+            ..Self::new(data_cache)
+        }
+    }
+
+    /// Publish the compressed source map of a module published in the transaction. The source
+    /// map is opaque to the VM, it is only stored alongside the code of the module.
+    pub fn publish_source_map(&mut self, module_id: ModuleId, source_map: Vec<u8>) -> VMResult<()> {
+        let new_size = source_map.len();
+        let old_size = self
+            .source_map_map
+            .insert(module_id, source_map)
+            .map_or(0, |old| old.len());
+        self.record_write(old_size, new_size)
+    }
+
+    /// Publish the LCS-serialized `ModuleErrorMap` of a module published in the transaction. It
+    /// is stored alongside the code of the module, and used to name the codes the module aborts
+    /// with.
+    pub fn publish_error_map(&mut self, module_id: ModuleId, error_map: Vec<u8>) -> VMResult<()> {
+        let new_size = error_map.len();
+        let old_size = self
+            .error_map_map
+            .insert(module_id, error_map)
+            .map_or(0, |old| old.len());
+        self.record_write(old_size, new_size)
+    }
+
+    /// Make a write set from the updated (dirty, deleted) global resources and table entries
+    /// along with published modules and their source and error maps.
     ///
     /// Gives all proper guarantees on lifetime of global data as well.
     pub fn make_write_set(&mut self) -> VMResult<WriteSet> {
@@ -105,6 +171,40 @@ impl<'txn> TransactionDataCache<'txn> {
             sorted_ws.insert((&module_id).into(), WriteOp::Value(module));
         }
 
+        let source_map_map = replace(&mut self.source_map_map, BTreeMap::new());
+        for (module_id, source_map) in source_map_map {
+            sorted_ws.insert(
+                AccessPath::source_map_access_path(&module_id),
+                WriteOp::Value(source_map),
+            );
+        }
+
+        let error_map_map = replace(&mut self.error_map_map, BTreeMap::new());
+        for (module_id, error_map) in error_map_map {
+            sorted_ws.insert(
+                AccessPath::error_map_access_path(&module_id),
+                WriteOp::Value(error_map),
+            );
+        }
+
+        let table_entry_map = replace(&mut self.table_entry_map, BTreeMap::new());
+        for (key, value) in table_entry_map {
+            let write_op = match value {
+                Some(blob) => WriteOp::Value(blob),
+                None => WriteOp::Deletion,
+            };
+            sorted_ws.insert(key, write_op);
+        }
+
+        let write_set_size = sorted_ws
+            .values()
+            .map(|op| match op {
+                WriteOp::Value(blob) => blob.len() as u64,
+                WriteOp::Deletion => 0,
+            })
+            .sum();
+        self.check_write_set(sorted_ws.len(), write_set_size)?;
+
         let mut write_set = WriteSetMut::new(Vec::new());
         for (key, value) in sorted_ws {
             write_set.push((key, value));
@@ -119,6 +219,41 @@ impl<'txn> TransactionDataCache<'txn> {
         &self.event_data
     }
 
+    // Accounts for a module, source or error map, or table entry of `new_size` bytes replacing one of
+    // `old_size` bytes, and checks the effects written so far against the limits.
+    fn record_write(&mut self, old_size: usize, new_size: usize) -> VMResult<()> {
+        self.written_bytes = self.written_bytes - old_size as u64 + new_size as u64;
+        let num_write_ops = self.module_map.len()
+            + self.source_map_map.len()
+            + self.error_map_map.len()
+            + self.table_entry_map.len();
+        self.check_write_set(num_write_ops, self.written_bytes)
+    }
+
+    fn check_write_set(&self, num_write_ops: usize, size: u64) -> VMResult<()> {
+        let limits = match &self.limits {
+            Some(limits) => limits,
+            None => return Ok(()),
+        };
+        if num_write_ops as u64 > limits.max_write_ops {
+            return Err(
+                VMStatus::new(StatusCode::WRITE_OPS_LIMIT_EXCEEDED).with_message(format!(
+                    "{} write ops, the limit is {}",
+                    num_write_ops, limits.max_write_ops
+                )),
+            );
+        }
+        if size > limits.max_write_set_size_in_bytes {
+            return Err(
+                VMStatus::new(StatusCode::WRITE_SET_SIZE_LIMIT_EXCEEDED).with_message(format!(
+                    "{} bytes written, the limit is {}",
+                    size, limits.max_write_set_size_in_bytes
+                )),
+            );
+        }
+        Ok(())
+    }
+
     // Retrieve data from the local cache or loads it from the remote cache into the local cache.
     // All operations on the global data are based on this API and they all load the data
     // into the cache.
@@ -198,8 +333,9 @@ impl<'a> DataStore for TransactionDataCache<'a> {
     }
 
     fn publish_module(&mut self, m: ModuleId, bytes: Vec<u8>) -> VMResult<()> {
-        self.module_map.insert(m, bytes);
-        Ok(())
+        let new_size = bytes.len();
+        let old_size = self.module_map.insert(m, bytes).map_or(0, |old| old.len());
+        self.record_write(old_size, new_size)
     }
 
     fn exists_module(&self, m: &ModuleId) -> bool {
@@ -209,7 +345,47 @@ impl<'a> DataStore for TransactionDataCache<'a> {
         }
     }
 
-    fn emit_event(&mut self, event: ContractEvent) {
-        self.event_data.push(event)
+    fn load_error_map(&self, m: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        match self.error_map_map.get(m) {
+            Some(bytes) => Ok(Some(bytes.clone())),
+            None => self.data_cache.get(&AccessPath::error_map_access_path(m)),
+        }
+    }
+
+    fn read_table_entry(&mut self, ap: &AccessPath) -> VMResult<Option<Vec<u8>>> {
+        match self.table_entry_map.get(ap) {
+            Some(value) => Ok(value.clone()),
+            None => self.data_cache.get(ap),
+        }
+    }
+
+    fn write_table_entry(&mut self, ap: AccessPath, value: Option<Vec<u8>>) -> VMResult<()> {
+        let new_size = value.as_ref().map_or(0, Vec::len);
+        let old_size = self
+            .table_entry_map
+            .insert(ap, value)
+            .and_then(|old| old)
+            .map_or(0, |old| old.len());
+        self.record_write(old_size, new_size)
+    }
+
+    fn emit_event(&mut self, event: ContractEvent) -> VMResult<()> {
+        if let Some(limits) = &self.limits {
+            if self.event_data.len() as u64 >= limits.max_events {
+                return Err(VMStatus::new(StatusCode::EVENT_COUNT_LIMIT_EXCEEDED)
+                    .with_message(format!("more than {} events emitted", limits.max_events)));
+            }
+            let size = event.event_data().len() as u64;
+            if size > limits.max_event_size_in_bytes {
+                return Err(
+                    VMStatus::new(StatusCode::EVENT_SIZE_LIMIT_EXCEEDED).with_message(format!(
+                        "event of {} bytes emitted, the limit is {}",
+                        size, limits.max_event_size_in_bytes
+                    )),
+                );
+            }
+        }
+        self.event_data.push(event);
+        Ok(())
     }
 }
@@ -0,0 +1,103 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::u256::U256;
+use move_vm_types::loaded_data::runtime_types::Type;
+use vm::{
+    file_format::{
+        CodeOffset, ConstantPoolIndex, FunctionHandleIndex, FunctionInstantiationIndex,
+        StructDefInstantiationIndex, StructDefinitionIndex,
+    },
+    file_format_common::Opcodes,
+};
+
+// An instruction as executed by the interpreter.
+// The loader decodes the code of every function once, when its module or script is loaded, so
+// that the interpreter does not look up the binary on every instruction: field offsets, field
+// counts and the element types of vector instructions are resolved, and instructions that only
+// differ in their gas cost are merged into one carrying the opcode to charge.
+// Instructions that need the type arguments of the running function, or the global cache,
+// keep the index they had in the binary.
+#[derive(Debug)]
+pub(crate) enum Instruction {
+    Pop,
+    Ret,
+    BrTrue(CodeOffset),
+    BrFalse(CodeOffset),
+    Branch(CodeOffset),
+    LdU8(u8),
+    LdU64(u64),
+    LdU128(u128),
+    LdU256(U256),
+    LdConst(ConstantPoolIndex),
+    LdTrue,
+    LdFalse,
+    CopyLoc(usize),
+    MoveLoc(usize),
+    StLoc(usize),
+    Call(FunctionHandleIndex),
+    CallGeneric(FunctionInstantiationIndex),
+    // `MutBorrowLoc` and `ImmBorrowLoc`
+    BorrowLoc(Opcodes, usize),
+    // `MutBorrowField`, `ImmBorrowField` and their generic versions, with the field offset
+    BorrowField(Opcodes, usize),
+    Pack {
+        field_count: u16,
+        is_resource: bool,
+    },
+    // Whether the struct is a resource depends on the type arguments
+    PackGeneric {
+        idx: StructDefInstantiationIndex,
+        field_count: u16,
+    },
+    // `Unpack` and `UnpackGeneric`, with the field count
+    Unpack(Opcodes, u16),
+    ReadRef,
+    WriteRef,
+    FreezeRef,
+    CastU8,
+    CastU64,
+    CastU128,
+    CastU256,
+    Add,
+    Sub,
+    Mul,
+    Mod,
+    Div,
+    BitOr,
+    BitAnd,
+    Xor,
+    Shl,
+    Shr,
+    Or,
+    And,
+    Not,
+    Eq,
+    Neq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Abort,
+    // `MutBorrowGlobal` and `ImmBorrowGlobal`
+    BorrowGlobal(StructDefinitionIndex),
+    BorrowGlobalGeneric(StructDefInstantiationIndex),
+    Exists(StructDefinitionIndex),
+    ExistsGeneric(StructDefInstantiationIndex),
+    MoveFrom(StructDefinitionIndex),
+    MoveFromGeneric(StructDefInstantiationIndex),
+    MoveTo(StructDefinitionIndex),
+    MoveToGeneric(StructDefInstantiationIndex),
+    // The vector instructions carry their element type, to substitute with the type arguments
+    VecPack(Type, u64),
+    VecLen(Type),
+    // `VecMutBorrow` and `VecImmBorrow`
+    VecBorrow(Opcodes, Type),
+    VecPushBack(Type),
+    VecPopBack(Type),
+    VecUnpack(Type, u64),
+    VecSwap(Type),
+    Nop,
+    // An instruction that is still in the file format but cannot be executed anymore, by name
+    Deprecated(&'static str),
+}
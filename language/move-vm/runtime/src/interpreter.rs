@@ -3,6 +3,7 @@
 
 use crate::{
     data_operations::{borrow_global, move_resource_from, move_resource_to, resource_exists},
+    instruction::Instruction,
     loader::{Function, Loader, Resolver},
     native_functions::FunctionContext,
     trace,
@@ -13,19 +14,26 @@ use libra_types::{
     account_address::AccountAddress,
     vm_error::{StatusCode, StatusType, VMStatus},
 };
-use move_core_types::gas_schedule::{AbstractMemorySize, GasAlgebra, GasCarrier};
+use move_core_types::{
+    errmap::ModuleErrorMap,
+    gas_schedule::{AbstractMemorySize, GasAlgebra, GasCarrier},
+    language_storage::ModuleId,
+};
 use move_vm_types::{
     data_store::DataStore,
     gas_schedule::CostStrategy,
     loaded_data::{runtime_types::Type, types::FatStructType},
-    values::{self, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value},
+    values::{
+        self, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value, Vector,
+        VectorRef,
+    },
 };
-use std::{cmp::min, collections::VecDeque, fmt::Write, sync::Arc};
+use std::{cmp::min, collections::VecDeque, convert::TryFrom, fmt::Write, sync::Arc};
 use vm::{
     errors::*,
     file_format::{
-        Bytecode, FunctionHandleIndex, FunctionInstantiationIndex, Signature,
-        StructDefInstantiationIndex, StructDefinitionIndex,
+        FunctionHandleIndex, FunctionInstantiationIndex, Signature, StructDefInstantiationIndex,
+        StructDefinitionIndex,
     },
     file_format_common::Opcodes,
 };
@@ -249,6 +257,7 @@ impl Interpreter {
                 IntegerValue::U8(x) => Value::u8(x),
                 IntegerValue::U64(x) => Value::u64(x),
                 IntegerValue::U128(x) => Value::u128(x),
+                IntegerValue::U256(x) => Value::u256(x),
             })
         })
     }
@@ -662,46 +671,50 @@ impl Frame {
                 self.pc += 1;
 
                 match instruction {
-                    Bytecode::Pop => {
+                    Instruction::Pop => {
                         cost_strategy.charge_instr(Opcodes::POP)?;
                         interpreter.operand_stack.pop()?;
                     }
-                    Bytecode::Ret => {
+                    Instruction::Ret => {
                         cost_strategy.charge_instr(Opcodes::RET)?;
                         return Ok(ExitCode::Return);
                     }
-                    Bytecode::BrTrue(offset) => {
+                    Instruction::BrTrue(offset) => {
                         cost_strategy.charge_instr(Opcodes::BR_TRUE)?;
                         if interpreter.operand_stack.pop_as::<bool>()? {
                             self.pc = *offset;
                             break;
                         }
                     }
-                    Bytecode::BrFalse(offset) => {
+                    Instruction::BrFalse(offset) => {
                         cost_strategy.charge_instr(Opcodes::BR_FALSE)?;
                         if !interpreter.operand_stack.pop_as::<bool>()? {
                             self.pc = *offset;
                             break;
                         }
                     }
-                    Bytecode::Branch(offset) => {
+                    Instruction::Branch(offset) => {
                         cost_strategy.charge_instr(Opcodes::BRANCH)?;
                         self.pc = *offset;
                         break;
                     }
-                    Bytecode::LdU8(int_const) => {
+                    Instruction::LdU8(int_const) => {
                         cost_strategy.charge_instr(Opcodes::LD_U8)?;
                         interpreter.operand_stack.push(Value::u8(*int_const))?;
                     }
-                    Bytecode::LdU64(int_const) => {
+                    Instruction::LdU64(int_const) => {
                         cost_strategy.charge_instr(Opcodes::LD_U64)?;
                         interpreter.operand_stack.push(Value::u64(*int_const))?;
                     }
-                    Bytecode::LdU128(int_const) => {
+                    Instruction::LdU128(int_const) => {
                         cost_strategy.charge_instr(Opcodes::LD_U128)?;
                         interpreter.operand_stack.push(Value::u128(*int_const))?;
                     }
-                    Bytecode::LdConst(idx) => {
+                    Instruction::LdU256(int_const) => {
+                        cost_strategy.charge_instr(Opcodes::LD_U256)?;
+                        interpreter.operand_stack.push(Value::u256(*int_const))?;
+                    }
+                    Instruction::LdConst(idx) => {
                         let constant = resolver.constant_at(*idx);
                         cost_strategy.charge_instr_with_size(
                             Opcodes::LD_CONST,
@@ -717,94 +730,73 @@ impl Frame {
                             })?,
                         )?
                     }
-                    Bytecode::LdTrue => {
+                    Instruction::LdTrue => {
                         cost_strategy.charge_instr(Opcodes::LD_TRUE)?;
                         interpreter.operand_stack.push(Value::bool(true))?;
                     }
-                    Bytecode::LdFalse => {
+                    Instruction::LdFalse => {
                         cost_strategy.charge_instr(Opcodes::LD_FALSE)?;
                         interpreter.operand_stack.push(Value::bool(false))?;
                     }
-                    Bytecode::CopyLoc(idx) => {
-                        let local = self.locals.copy_loc(*idx as usize)?;
+                    Instruction::CopyLoc(idx) => {
+                        let local = self.locals.copy_loc(*idx)?;
                         cost_strategy.charge_instr_with_size(Opcodes::COPY_LOC, local.size())?;
                         interpreter.operand_stack.push(local)?;
                     }
-                    Bytecode::MoveLoc(idx) => {
-                        let local = self.locals.move_loc(*idx as usize)?;
+                    Instruction::MoveLoc(idx) => {
+                        let local = self.locals.move_loc(*idx)?;
                         cost_strategy.charge_instr_with_size(Opcodes::MOVE_LOC, local.size())?;
 
                         interpreter.operand_stack.push(local)?;
                     }
-                    Bytecode::StLoc(idx) => {
+                    Instruction::StLoc(idx) => {
                         let value_to_store = interpreter.operand_stack.pop()?;
                         cost_strategy
                             .charge_instr_with_size(Opcodes::ST_LOC, value_to_store.size())?;
-                        self.locals.store_loc(*idx as usize, value_to_store)?;
+                        self.locals.store_loc(*idx, value_to_store)?;
                     }
-                    Bytecode::Call(idx) => {
+                    Instruction::Call(idx) => {
                         return Ok(ExitCode::Call(*idx));
                     }
-                    Bytecode::CallGeneric(idx) => {
+                    Instruction::CallGeneric(idx) => {
                         return Ok(ExitCode::CallGeneric(*idx));
                     }
-                    Bytecode::MutBorrowLoc(idx) | Bytecode::ImmBorrowLoc(idx) => {
-                        let opcode = match instruction {
-                            Bytecode::MutBorrowLoc(_) => Opcodes::MUT_BORROW_LOC,
-                            _ => Opcodes::IMM_BORROW_LOC,
-                        };
-                        cost_strategy.charge_instr(opcode)?;
+                    Instruction::BorrowLoc(opcode, idx) => {
+                        cost_strategy.charge_instr(*opcode)?;
                         interpreter
                             .operand_stack
-                            .push(self.locals.borrow_loc(*idx as usize)?)?;
-                    }
-                    Bytecode::ImmBorrowField(fh_idx) | Bytecode::MutBorrowField(fh_idx) => {
-                        let opcode = match instruction {
-                            Bytecode::MutBorrowField(_) => Opcodes::MUT_BORROW_FIELD,
-                            _ => Opcodes::IMM_BORROW_FIELD,
-                        };
-                        cost_strategy.charge_instr(opcode)?;
-
-                        let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
-                        let offset = resolver.field_offset(*fh_idx);
-                        let field_ref = reference.borrow_field(offset)?;
-                        interpreter.operand_stack.push(field_ref)?;
+                            .push(self.locals.borrow_loc(*idx)?)?;
                     }
-                    Bytecode::ImmBorrowFieldGeneric(fi_idx)
-                    | Bytecode::MutBorrowFieldGeneric(fi_idx) => {
-                        let opcode = match instruction {
-                            Bytecode::MutBorrowField(_) => Opcodes::MUT_BORROW_FIELD_GENERIC,
-                            _ => Opcodes::IMM_BORROW_FIELD_GENERIC,
-                        };
-                        cost_strategy.charge_instr(opcode)?;
+                    Instruction::BorrowField(opcode, offset) => {
+                        cost_strategy.charge_instr(*opcode)?;
 
                         let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
-                        let offset = resolver.field_instantiation_offset(*fi_idx);
-                        let field_ref = reference.borrow_field(offset)?;
+                        let field_ref = reference.borrow_field(*offset)?;
                         interpreter.operand_stack.push(field_ref)?;
                     }
-                    Bytecode::Pack(sd_idx) => {
-                        let field_count = resolver.field_count(*sd_idx);
-                        let args = interpreter.operand_stack.popn(field_count)?;
+                    Instruction::Pack {
+                        field_count,
+                        is_resource,
+                    } => {
+                        let args = interpreter.operand_stack.popn(*field_count)?;
                         let size = args.iter().fold(
-                            AbstractMemorySize::new(GasCarrier::from(field_count)),
+                            AbstractMemorySize::new(GasCarrier::from(*field_count)),
                             |acc, v| acc.add(v.size()),
                         );
                         cost_strategy.charge_instr_with_size(Opcodes::PACK, size)?;
-                        let is_resource = resolver.struct_at(*sd_idx).is_resource;
                         interpreter
                             .operand_stack
-                            .push(Value::struct_(Struct::pack(args, is_resource)))?;
+                            .push(Value::struct_(Struct::pack(args, *is_resource)))?;
                     }
-                    Bytecode::PackGeneric(si_idx) => {
-                        let field_count = resolver.field_instantiation_count(*si_idx);
+                    Instruction::PackGeneric { idx, field_count } => {
+                        let field_count = *field_count;
                         let args = interpreter.operand_stack.popn(field_count)?;
                         let size = args.iter().fold(
                             AbstractMemorySize::new(GasCarrier::from(field_count)),
                             |acc, v| acc.add(v.size()),
                         );
                         cost_strategy.charge_instr_with_size(Opcodes::PACK_GENERIC, size)?;
-                        let struct_def = resolver.struct_instantiation_at(*si_idx);
+                        let struct_def = resolver.struct_instantiation_at(*idx);
                         let struct_ty = resolver.struct_type_at(struct_def.get_def_idx());
                         let is_nominal_resource = struct_ty.is_resource;
 
@@ -824,104 +816,94 @@ impl Frame {
                             .operand_stack
                             .push(Value::struct_(Struct::pack(args, is_resource)))?;
                     }
-                    Bytecode::Unpack(sd_idx) => {
-                        let field_count = resolver.field_count(*sd_idx);
+                    Instruction::Unpack(opcode, field_count) => {
                         let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
                         cost_strategy.charge_instr_with_size(
-                            Opcodes::UNPACK,
-                            AbstractMemorySize::new(GasCarrier::from(field_count)),
+                            *opcode,
+                            AbstractMemorySize::new(GasCarrier::from(*field_count)),
                         )?;
                         // TODO: Whether or not we want this gas metering in the loop is
                         // questionable.  However, if we don't have it in the loop we could wind up
                         // doing a fair bit of work before charging for it.
                         for value in struct_.unpack()? {
-                            cost_strategy.charge_instr_with_size(Opcodes::UNPACK, value.size())?;
+                            cost_strategy.charge_instr_with_size(*opcode, value.size())?;
                             interpreter.operand_stack.push(value)?;
                         }
                     }
-                    Bytecode::UnpackGeneric(si_idx) => {
-                        let field_count = resolver.field_instantiation_count(*si_idx);
-                        let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
-                        cost_strategy.charge_instr_with_size(
-                            Opcodes::UNPACK_GENERIC,
-                            AbstractMemorySize::new(GasCarrier::from(field_count)),
-                        )?;
-                        // TODO: Whether or not we want this gas metering in the loop is
-                        // questionable.  However, if we don't have it in the loop we could wind up
-                        // doing a fair bit of work before charging for it.
-                        for value in struct_.unpack()? {
-                            cost_strategy
-                                .charge_instr_with_size(Opcodes::UNPACK_GENERIC, value.size())?;
-                            interpreter.operand_stack.push(value)?;
-                        }
-                    }
-                    Bytecode::ReadRef => {
+                    Instruction::ReadRef => {
                         let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                         let value = reference.read_ref()?;
                         cost_strategy.charge_instr_with_size(Opcodes::READ_REF, value.size())?;
                         interpreter.operand_stack.push(value)?;
                     }
-                    Bytecode::WriteRef => {
+                    Instruction::WriteRef => {
                         let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                         let value = interpreter.operand_stack.pop()?;
                         cost_strategy.charge_instr_with_size(Opcodes::WRITE_REF, value.size())?;
                         reference.write_ref(value)?;
                     }
-                    Bytecode::CastU8 => {
+                    Instruction::CastU8 => {
                         cost_strategy.charge_instr(Opcodes::CAST_U8)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u8(integer_value.cast_u8()?))?;
                     }
-                    Bytecode::CastU64 => {
+                    Instruction::CastU64 => {
                         cost_strategy.charge_instr(Opcodes::CAST_U64)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u64(integer_value.cast_u64()?))?;
                     }
-                    Bytecode::CastU128 => {
+                    Instruction::CastU128 => {
                         cost_strategy.charge_instr(Opcodes::CAST_U128)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u128(integer_value.cast_u128()?))?;
                     }
+                    Instruction::CastU256 => {
+                        cost_strategy.charge_instr(Opcodes::CAST_U256)?;
+                        let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::u256(integer_value.cast_u256()?))?;
+                    }
                     // Arithmetic Operations
-                    Bytecode::Add => {
+                    Instruction::Add => {
                         cost_strategy.charge_instr(Opcodes::ADD)?;
                         interpreter.binop_int(IntegerValue::add_checked)?
                     }
-                    Bytecode::Sub => {
+                    Instruction::Sub => {
                         cost_strategy.charge_instr(Opcodes::SUB)?;
                         interpreter.binop_int(IntegerValue::sub_checked)?
                     }
-                    Bytecode::Mul => {
+                    Instruction::Mul => {
                         cost_strategy.charge_instr(Opcodes::MUL)?;
                         interpreter.binop_int(IntegerValue::mul_checked)?
                     }
-                    Bytecode::Mod => {
+                    Instruction::Mod => {
                         cost_strategy.charge_instr(Opcodes::MOD)?;
                         interpreter.binop_int(IntegerValue::rem_checked)?
                     }
-                    Bytecode::Div => {
+                    Instruction::Div => {
                         cost_strategy.charge_instr(Opcodes::DIV)?;
                         interpreter.binop_int(IntegerValue::div_checked)?
                     }
-                    Bytecode::BitOr => {
+                    Instruction::BitOr => {
                         cost_strategy.charge_instr(Opcodes::BIT_OR)?;
                         interpreter.binop_int(IntegerValue::bit_or)?
                     }
-                    Bytecode::BitAnd => {
+                    Instruction::BitAnd => {
                         cost_strategy.charge_instr(Opcodes::BIT_AND)?;
                         interpreter.binop_int(IntegerValue::bit_and)?
                     }
-                    Bytecode::Xor => {
+                    Instruction::Xor => {
                         cost_strategy.charge_instr(Opcodes::XOR)?;
                         interpreter.binop_int(IntegerValue::bit_xor)?
                     }
-                    Bytecode::Shl => {
+                    Instruction::Shl => {
                         cost_strategy.charge_instr(Opcodes::SHL)?;
                         let rhs = interpreter.operand_stack.pop_as::<u8>()?;
                         let lhs = interpreter.operand_stack.pop_as::<IntegerValue>()?;
@@ -929,7 +911,7 @@ impl Frame {
                             .operand_stack
                             .push(lhs.shl_checked(rhs)?.into_value())?;
                     }
-                    Bytecode::Shr => {
+                    Instruction::Shr => {
                         cost_strategy.charge_instr(Opcodes::SHR)?;
                         let rhs = interpreter.operand_stack.pop_as::<u8>()?;
                         let lhs = interpreter.operand_stack.pop_as::<IntegerValue>()?;
@@ -937,42 +919,50 @@ impl Frame {
                             .operand_stack
                             .push(lhs.shr_checked(rhs)?.into_value())?;
                     }
-                    Bytecode::Or => {
+                    Instruction::Or => {
                         cost_strategy.charge_instr(Opcodes::OR)?;
                         interpreter.binop_bool(|l, r| Ok(l || r))?
                     }
-                    Bytecode::And => {
+                    Instruction::And => {
                         cost_strategy.charge_instr(Opcodes::AND)?;
                         interpreter.binop_bool(|l, r| Ok(l && r))?
                     }
-                    Bytecode::Lt => {
+                    Instruction::Lt => {
                         cost_strategy.charge_instr(Opcodes::LT)?;
                         interpreter.binop_bool(IntegerValue::lt)?
                     }
-                    Bytecode::Gt => {
+                    Instruction::Gt => {
                         cost_strategy.charge_instr(Opcodes::GT)?;
                         interpreter.binop_bool(IntegerValue::gt)?
                     }
-                    Bytecode::Le => {
+                    Instruction::Le => {
                         cost_strategy.charge_instr(Opcodes::LE)?;
                         interpreter.binop_bool(IntegerValue::le)?
                     }
-                    Bytecode::Ge => {
+                    Instruction::Ge => {
                         cost_strategy.charge_instr(Opcodes::GE)?;
                         interpreter.binop_bool(IntegerValue::ge)?
                     }
-                    Bytecode::Abort => {
+                    Instruction::Abort => {
                         cost_strategy.charge_instr(Opcodes::ABORT)?;
                         let error_code = interpreter.operand_stack.pop_as::<u64>()?;
+                        let explanation = self
+                            .function
+                            .module_id()
+                            .and_then(|module_id| {
+                                abort_explanation(data_store, module_id, error_code)
+                            })
+                            .unwrap_or_default();
                         return Err(VMStatus::new(StatusCode::ABORTED)
                             .with_sub_status(error_code)
                             .with_message(format!(
-                                "{} at offset {}",
+                                "{} at offset {}{}",
                                 self.function.pretty_string(),
                                 self.pc,
+                                explanation,
                             )));
                     }
-                    Bytecode::Eq => {
+                    Instruction::Eq => {
                         let lhs = interpreter.operand_stack.pop()?;
                         let rhs = interpreter.operand_stack.pop()?;
                         cost_strategy
@@ -981,7 +971,7 @@ impl Frame {
                             .operand_stack
                             .push(Value::bool(lhs.equals(&rhs)?))?;
                     }
-                    Bytecode::Neq => {
+                    Instruction::Neq => {
                         let lhs = interpreter.operand_stack.pop()?;
                         let rhs = interpreter.operand_stack.pop()?;
                         cost_strategy
@@ -990,14 +980,14 @@ impl Frame {
                             .operand_stack
                             .push(Value::bool(!lhs.equals(&rhs)?))?;
                     }
-                    Bytecode::GetTxnSenderAddress => {
+                    Instruction::Deprecated(name) => {
                         return Err(VMStatus::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                            .with_message(
-                                "GetTxnSenderAddress is deprecated and will be removed soon"
-                                    .to_string(),
-                            ));
+                            .with_message(format!(
+                                "{} is deprecated and will be removed soon",
+                                name
+                            )));
                     }
-                    Bytecode::MutBorrowGlobal(sd_idx) | Bytecode::ImmBorrowGlobal(sd_idx) => {
+                    Instruction::BorrowGlobal(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op(
                             resolver,
@@ -1008,8 +998,7 @@ impl Frame {
                         )?;
                         cost_strategy.charge_instr_with_size(Opcodes::MUT_BORROW_GLOBAL, size)?;
                     }
-                    Bytecode::MutBorrowGlobalGeneric(si_idx)
-                    | Bytecode::ImmBorrowGlobalGeneric(si_idx) => {
+                    Instruction::BorrowGlobalGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op_generic(
                             resolver,
@@ -1022,7 +1011,7 @@ impl Frame {
                         cost_strategy
                             .charge_instr_with_size(Opcodes::MUT_BORROW_GLOBAL_GENERIC, size)?;
                     }
-                    Bytecode::Exists(sd_idx) => {
+                    Instruction::Exists(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op(
                             resolver,
@@ -1033,7 +1022,7 @@ impl Frame {
                         )?;
                         cost_strategy.charge_instr_with_size(Opcodes::EXISTS, size)?;
                     }
-                    Bytecode::ExistsGeneric(si_idx) => {
+                    Instruction::ExistsGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op_generic(
                             resolver,
@@ -1045,7 +1034,7 @@ impl Frame {
                         )?;
                         cost_strategy.charge_instr_with_size(Opcodes::EXISTS_GENERIC, size)?;
                     }
-                    Bytecode::MoveFrom(sd_idx) => {
+                    Instruction::MoveFrom(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op(
                             resolver,
@@ -1058,7 +1047,7 @@ impl Frame {
                         // the size of the data that we are about to read in.
                         cost_strategy.charge_instr_with_size(Opcodes::MOVE_FROM, size)?;
                     }
-                    Bytecode::MoveFromGeneric(si_idx) => {
+                    Instruction::MoveFromGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let size = interpreter.global_data_op_generic(
                             resolver,
@@ -1072,19 +1061,7 @@ impl Frame {
                         // the size of the data that we are about to read in.
                         cost_strategy.charge_instr_with_size(Opcodes::MOVE_FROM_GENERIC, size)?;
                     }
-                    Bytecode::MoveToSender(_) => {
-                        return Err(VMStatus::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                            .with_message(
-                                "MoveToSender is deprecated and will be removed soon".to_string(),
-                            ));
-                    }
-                    Bytecode::MoveToSenderGeneric(_) => {
-                        return Err(VMStatus::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                            .with_message(
-                                "MoveToSender is deprecated and will be removed soon".to_string(),
-                            ));
-                    }
-                    Bytecode::MoveTo(sd_idx) => {
+                    Instruction::MoveTo(sd_idx) => {
                         let resource = interpreter.operand_stack.pop_as::<Struct>()?;
                         let signer_reference = interpreter.operand_stack.pop_as::<StructRef>()?;
                         let addr = signer_reference
@@ -1101,7 +1078,7 @@ impl Frame {
                         )?;
                         cost_strategy.charge_instr_with_size(Opcodes::MOVE_TO, size)?;
                     }
-                    Bytecode::MoveToGeneric(si_idx) => {
+                    Instruction::MoveToGeneric(si_idx) => {
                         let resource = interpreter.operand_stack.pop_as::<Struct>()?;
                         let signer_reference = interpreter.operand_stack.pop_as::<StructRef>()?;
                         let addr = signer_reference
@@ -1119,16 +1096,89 @@ impl Frame {
                         )?;
                         cost_strategy.charge_instr_with_size(Opcodes::MOVE_TO_GENERIC, size)?;
                     }
-                    Bytecode::FreezeRef => {
+                    Instruction::FreezeRef => {
                         // FreezeRef should just be a null op as we don't distinguish between mut
                         // and immut ref at runtime.
                     }
-                    Bytecode::Not => {
+                    Instruction::Not => {
                         cost_strategy.charge_instr(Opcodes::NOT)?;
                         let value = !interpreter.operand_stack.pop_as::<bool>()?;
                         interpreter.operand_stack.push(Value::bool(value))?;
                     }
-                    Bytecode::Nop => {
+                    Instruction::VecPack(elem_ty, num) => {
+                        let num = u16::try_from(*num)
+                            .map_err(|_| VMStatus::new(StatusCode::EMPTY_VALUE_STACK))?;
+                        let elements = interpreter.operand_stack.popn(num)?;
+                        // An empty vector costs as much as any other vector instruction
+                        let size = elements
+                            .iter()
+                            .fold(AbstractMemorySize::new(1), |acc, v| acc.add(v.size()));
+                        cost_strategy.charge_instr_with_size(Opcodes::VEC_PACK, size)?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        let vector = Vector::pack(&ty, elements, &context)?;
+                        interpreter.operand_stack.push(vector)?;
+                    }
+                    Instruction::VecLen(elem_ty) => {
+                        cost_strategy.charge_instr(Opcodes::VEC_LEN)?;
+                        let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        let len = vec_ref.len(&ty, &context)?;
+                        interpreter.operand_stack.push(len)?;
+                    }
+                    Instruction::VecBorrow(opcode, elem_ty) => {
+                        cost_strategy.charge_instr(*opcode)?;
+                        let idx = interpreter.operand_stack.pop_as::<u64>()? as usize;
+                        let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        let elem_ref = vec_ref.borrow_elem(idx, &ty, &context)?;
+                        interpreter.operand_stack.push(elem_ref)?;
+                    }
+                    Instruction::VecPushBack(elem_ty) => {
+                        let elem = interpreter.operand_stack.pop()?;
+                        let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
+                        cost_strategy.charge_instr(Opcodes::VEC_PUSH_BACK)?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        vec_ref.push_back(elem, &ty, &context)?;
+                    }
+                    Instruction::VecPopBack(elem_ty) => {
+                        cost_strategy.charge_instr(Opcodes::VEC_POP_BACK)?;
+                        let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        let elem = vec_ref.pop(&ty, &context)?;
+                        interpreter.operand_stack.push(elem)?;
+                    }
+                    Instruction::VecUnpack(elem_ty, num) => {
+                        cost_strategy.charge_instr(Opcodes::VEC_UNPACK)?;
+                        let vector = interpreter.operand_stack.pop_as::<Vector>()?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        let elements = vector.unpack(&ty, *num, &context)?;
+                        for elem in elements {
+                            interpreter.operand_stack.push(elem)?;
+                        }
+                    }
+                    Instruction::VecSwap(elem_ty) => {
+                        cost_strategy.charge_instr(Opcodes::VEC_SWAP)?;
+                        let idx2 = interpreter.operand_stack.pop_as::<u64>()? as usize;
+                        let idx1 = interpreter.operand_stack.pop_as::<u64>()? as usize;
+                        let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
+                        let ty = elem_ty.subst(self.ty_args())?;
+                        let context =
+                            FunctionContext::new(interpreter, data_store, cost_strategy, resolver);
+                        vec_ref.swap(idx1, idx2, &ty, &context)?;
+                    }
+                    Instruction::Nop => {
                         cost_strategy.charge_instr(Opcodes::NOP)?;
                     }
                 }
@@ -1180,3 +1230,20 @@ fn verify_args(signature: &Signature, _ty_args: &[Type], args: &[Value]) -> VMRe
     }
     Ok(())
 }
+
+// Names the abort `code` of the module `module_id` after the error constant declaring it, if the
+// module was published with an error map that has it. A missing or malformed error map only
+// costs the explanation, never the abort itself.
+fn abort_explanation(
+    data_store: &dyn DataStore,
+    module_id: &ModuleId,
+    code: u64,
+) -> Option<String> {
+    let bytes = data_store.load_error_map(module_id).ok()??;
+    let error_map: ModuleErrorMap = lcs::from_bytes(&bytes).ok()?;
+    let description = error_map.get(&code)?;
+    Some(format!(
+        ": {} ({})",
+        description.code_name, description.code_description
+    ))
+}
@@ -12,8 +12,10 @@
 #[macro_use]
 extern crate mirai_annotations;
 
+mod counters;
 pub mod data_cache;
 mod data_operations;
+mod instruction;
 mod interpreter;
 mod loader;
 pub mod move_vm;
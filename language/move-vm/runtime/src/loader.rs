@@ -1,7 +1,11 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::native_functions::NativeFunction;
+use crate::{
+    counters::{MODULE_CACHE_LOOKUPS, MODULE_DEPENDENCY_DEPTH, MODULE_LOAD_SECONDS},
+    instruction::Instruction,
+    native_functions::NativeFunction,
+};
 use bytecode_verifier::{
     constants::ConstantsChecker,
     instantiation_loops::InstantiationLoopChecker,
@@ -30,17 +34,20 @@ use std::{
     collections::{BTreeMap, HashMap},
     fmt::Debug,
     hash::Hash,
+    iter,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 use vm::{
     access::{ModuleAccess, ScriptAccess},
     errors::{verification_error, vm_error, Location, VMResult},
     file_format::{
         Bytecode, CompiledScript, Constant, ConstantPoolIndex, FieldHandleIndex,
-        FieldInstantiationIndex, FunctionDefinition, FunctionHandleIndex,
-        FunctionInstantiationIndex, Kind, Signature, SignatureToken, StructDefInstantiationIndex,
-        StructDefinition, StructDefinitionIndex, StructFieldInformation,
+        FunctionDefinition, FunctionHandleIndex, FunctionInstantiationIndex, Kind, Signature,
+        SignatureIndex, SignatureToken, StructDefInstantiationIndex, StructDefinition,
+        StructDefinitionIndex, StructFieldInformation,
     },
+    file_format_common::Opcodes,
     CompiledModule, IndexKind,
 };
 
@@ -219,6 +226,7 @@ impl ModuleCache {
             SignatureToken::U8 => Type::U8,
             SignatureToken::U64 => Type::U64,
             SignatureToken::U128 => Type::U128,
+            SignatureToken::U256 => Type::U256,
             SignatureToken::Address => Type::Address,
             SignatureToken::Signer => Type::Signer,
             SignatureToken::TypeParameter(idx) => Type::TyParam(*idx as usize),
@@ -305,7 +313,147 @@ impl ModuleCache {
         module: &CompiledModule,
         func_def: &FunctionDefinition,
     ) -> VMResult<Function> {
-        Ok(Function::new(func_def, module))
+        Function::new(func_def, module, self)
+    }
+
+    // Decodes the code of a function or a script into the instructions the interpreter executes.
+    // The verifier guarantees that the indexes in the code are in bounds and that the signatures
+    // of the vector instructions hold a single type.
+    fn decode_code(
+        &self,
+        binary: &dyn ModuleAccess,
+        code: &[Bytecode],
+    ) -> VMResult<Vec<Instruction>> {
+        code.iter()
+            .map(|instr| self.decode_instruction(binary, instr))
+            .collect()
+    }
+
+    fn decode_instruction(
+        &self,
+        binary: &dyn ModuleAccess,
+        instr: &Bytecode,
+    ) -> VMResult<Instruction> {
+        let field_offset = |idx: FieldHandleIndex| binary.field_handle_at(idx).field as usize;
+        let field_count =
+            |idx: StructDefinitionIndex| binary.struct_def_at(idx).declared_field_count();
+        let instantiation_field_count =
+            |idx: StructDefInstantiationIndex| field_count(binary.struct_instantiation_at(idx).def);
+        let element_type =
+            |idx: SignatureIndex| self.make_type(binary, &binary.signature_at(idx).0[0]);
+
+        let instruction = match instr {
+            Bytecode::Pop => Instruction::Pop,
+            Bytecode::Ret => Instruction::Ret,
+            Bytecode::BrTrue(offset) => Instruction::BrTrue(*offset),
+            Bytecode::BrFalse(offset) => Instruction::BrFalse(*offset),
+            Bytecode::Branch(offset) => Instruction::Branch(*offset),
+            Bytecode::LdU8(int_const) => Instruction::LdU8(*int_const),
+            Bytecode::LdU64(int_const) => Instruction::LdU64(*int_const),
+            Bytecode::LdU128(int_const) => Instruction::LdU128(*int_const),
+            Bytecode::LdU256(int_const) => Instruction::LdU256(*int_const),
+            Bytecode::LdConst(idx) => Instruction::LdConst(*idx),
+            Bytecode::LdTrue => Instruction::LdTrue,
+            Bytecode::LdFalse => Instruction::LdFalse,
+            Bytecode::CopyLoc(idx) => Instruction::CopyLoc(*idx as usize),
+            Bytecode::MoveLoc(idx) => Instruction::MoveLoc(*idx as usize),
+            Bytecode::StLoc(idx) => Instruction::StLoc(*idx as usize),
+            Bytecode::Call(idx) => Instruction::Call(*idx),
+            Bytecode::CallGeneric(idx) => Instruction::CallGeneric(*idx),
+            Bytecode::MutBorrowLoc(idx) => {
+                Instruction::BorrowLoc(Opcodes::MUT_BORROW_LOC, *idx as usize)
+            }
+            Bytecode::ImmBorrowLoc(idx) => {
+                Instruction::BorrowLoc(Opcodes::IMM_BORROW_LOC, *idx as usize)
+            }
+            Bytecode::MutBorrowField(fh_idx) => {
+                Instruction::BorrowField(Opcodes::MUT_BORROW_FIELD, field_offset(*fh_idx))
+            }
+            Bytecode::ImmBorrowField(fh_idx) => {
+                Instruction::BorrowField(Opcodes::IMM_BORROW_FIELD, field_offset(*fh_idx))
+            }
+            Bytecode::MutBorrowFieldGeneric(fi_idx) => Instruction::BorrowField(
+                Opcodes::MUT_BORROW_FIELD_GENERIC,
+                field_offset(binary.field_instantiation_at(*fi_idx).handle),
+            ),
+            Bytecode::ImmBorrowFieldGeneric(fi_idx) => Instruction::BorrowField(
+                Opcodes::IMM_BORROW_FIELD_GENERIC,
+                field_offset(binary.field_instantiation_at(*fi_idx).handle),
+            ),
+            Bytecode::Pack(sd_idx) => {
+                let struct_handle =
+                    binary.struct_handle_at(binary.struct_def_at(*sd_idx).struct_handle);
+                Instruction::Pack {
+                    field_count: field_count(*sd_idx)?,
+                    is_resource: struct_handle.is_nominal_resource,
+                }
+            }
+            Bytecode::PackGeneric(si_idx) => Instruction::PackGeneric {
+                idx: *si_idx,
+                field_count: instantiation_field_count(*si_idx)?,
+            },
+            Bytecode::Unpack(sd_idx) => Instruction::Unpack(Opcodes::UNPACK, field_count(*sd_idx)?),
+            Bytecode::UnpackGeneric(si_idx) => {
+                Instruction::Unpack(Opcodes::UNPACK_GENERIC, instantiation_field_count(*si_idx)?)
+            }
+            Bytecode::ReadRef => Instruction::ReadRef,
+            Bytecode::WriteRef => Instruction::WriteRef,
+            Bytecode::FreezeRef => Instruction::FreezeRef,
+            Bytecode::CastU8 => Instruction::CastU8,
+            Bytecode::CastU64 => Instruction::CastU64,
+            Bytecode::CastU128 => Instruction::CastU128,
+            Bytecode::CastU256 => Instruction::CastU256,
+            Bytecode::Add => Instruction::Add,
+            Bytecode::Sub => Instruction::Sub,
+            Bytecode::Mul => Instruction::Mul,
+            Bytecode::Mod => Instruction::Mod,
+            Bytecode::Div => Instruction::Div,
+            Bytecode::BitOr => Instruction::BitOr,
+            Bytecode::BitAnd => Instruction::BitAnd,
+            Bytecode::Xor => Instruction::Xor,
+            Bytecode::Shl => Instruction::Shl,
+            Bytecode::Shr => Instruction::Shr,
+            Bytecode::Or => Instruction::Or,
+            Bytecode::And => Instruction::And,
+            Bytecode::Not => Instruction::Not,
+            Bytecode::Eq => Instruction::Eq,
+            Bytecode::Neq => Instruction::Neq,
+            Bytecode::Lt => Instruction::Lt,
+            Bytecode::Gt => Instruction::Gt,
+            Bytecode::Le => Instruction::Le,
+            Bytecode::Ge => Instruction::Ge,
+            Bytecode::Abort => Instruction::Abort,
+            Bytecode::MutBorrowGlobal(sd_idx) | Bytecode::ImmBorrowGlobal(sd_idx) => {
+                Instruction::BorrowGlobal(*sd_idx)
+            }
+            Bytecode::MutBorrowGlobalGeneric(si_idx) | Bytecode::ImmBorrowGlobalGeneric(si_idx) => {
+                Instruction::BorrowGlobalGeneric(*si_idx)
+            }
+            Bytecode::Exists(sd_idx) => Instruction::Exists(*sd_idx),
+            Bytecode::ExistsGeneric(si_idx) => Instruction::ExistsGeneric(*si_idx),
+            Bytecode::MoveFrom(sd_idx) => Instruction::MoveFrom(*sd_idx),
+            Bytecode::MoveFromGeneric(si_idx) => Instruction::MoveFromGeneric(*si_idx),
+            Bytecode::MoveTo(sd_idx) => Instruction::MoveTo(*sd_idx),
+            Bytecode::MoveToGeneric(si_idx) => Instruction::MoveToGeneric(*si_idx),
+            Bytecode::VecPack(si, num) => Instruction::VecPack(element_type(*si)?, *num),
+            Bytecode::VecLen(si) => Instruction::VecLen(element_type(*si)?),
+            Bytecode::VecMutBorrow(si) => {
+                Instruction::VecBorrow(Opcodes::VEC_MUT_BORROW, element_type(*si)?)
+            }
+            Bytecode::VecImmBorrow(si) => {
+                Instruction::VecBorrow(Opcodes::VEC_IMM_BORROW, element_type(*si)?)
+            }
+            Bytecode::VecPushBack(si) => Instruction::VecPushBack(element_type(*si)?),
+            Bytecode::VecPopBack(si) => Instruction::VecPopBack(element_type(*si)?),
+            Bytecode::VecUnpack(si, num) => Instruction::VecUnpack(element_type(*si)?, *num),
+            Bytecode::VecSwap(si) => Instruction::VecSwap(element_type(*si)?),
+            Bytecode::Nop => Instruction::Nop,
+            Bytecode::GetTxnSenderAddress => Instruction::Deprecated("GetTxnSenderAddress"),
+            Bytecode::MoveToSender(_) | Bytecode::MoveToSenderGeneric(_) => {
+                Instruction::Deprecated("MoveToSender")
+            }
+        };
+        Ok(instruction)
     }
 }
 
@@ -410,6 +558,7 @@ impl Loader {
             TypeTag::U8 => Type::U8,
             TypeTag::U64 => Type::U64,
             TypeTag::U128 => Type::U128,
+            TypeTag::U256 => Type::U256,
             TypeTag::Address => Type::Address,
             TypeTag::Signer => Type::Signer,
             TypeTag::Vector(tt) => Type::Vector(Box::new(self.load_type(tt, data_store)?)),
@@ -436,12 +585,48 @@ impl Loader {
     }
 
     fn load_module(&self, id: &ModuleId, data_store: &mut dyn DataStore) -> VMResult<Arc<Module>> {
+        self.load_module_with_path(id, data_store, &mut vec![])
+    }
+
+    // Loads a module and, depth first, the dependencies that are not in the cache.
+    // `path` holds the modules being loaded, from the one loaded directly down to the one
+    // depending on `id`, so that a dependency on any of them is reported as a cycle.
+    fn load_module_with_path(
+        &self,
+        id: &ModuleId,
+        data_store: &mut dyn DataStore,
+        path: &mut Vec<ModuleId>,
+    ) -> VMResult<Arc<Module>> {
         if let Some(module) = self.module_cache.lock().unwrap().get(id) {
+            MODULE_CACHE_LOOKUPS.with_label_values(&["hit"]).inc();
             return Ok(module);
         }
-        let module = self.deserialize_and_verify_module(id, data_store)?;
+        MODULE_CACHE_LOOKUPS.with_label_values(&["miss"]).inc();
+        if let Some(start) = path.iter().position(|module_id| module_id == id) {
+            let cycle: Vec<_> = path[start..]
+                .iter()
+                .chain(iter::once(id))
+                .map(module_name)
+                .collect();
+            return Err(VMStatus::new(StatusCode::CYCLIC_MODULE_DEPENDENCY)
+                .with_message(format!("Cyclic module dependency: {}", cycle.join(" -> "))));
+        }
+        MODULE_DEPENDENCY_DEPTH.observe(path.len() as f64);
+
+        path.push(id.clone());
+        let module = self.deserialize_and_verify_module(id, data_store, path);
+        path.pop();
+        let module = module?;
         Self::check_natives(&module)?;
-        self.module_cache.lock().unwrap().insert(id.clone(), module)
+
+        let start = Instant::now();
+        let module = self
+            .module_cache
+            .lock()
+            .unwrap()
+            .insert(id.clone(), module)?;
+        observe_load_phase("cache", start.elapsed());
+        Ok(module)
     }
 
     fn verify_ty_args(&self, constraints: &[Kind], ty_args: &[Type]) -> VMResult<()> {
@@ -470,7 +655,7 @@ impl Loader {
         {
             let fh = module.function_handle_at(native_function.function);
             let mh = module.module_handle_at(fh.module);
-            NativeFunction::resolve(
+            let native = NativeFunction::resolve(
                 module.address_identifier_at(mh.address),
                 module.identifier_at(mh.name).as_str(),
                 module.identifier_at(fh.name).as_str(),
@@ -482,6 +667,18 @@ impl Loader {
                     StatusCode::MISSING_DEPENDENCY,
                 )
             })?;
+            // The natives pop their arguments without checking how many there are
+            let arity = (
+                module.signature_at(fh.parameters).len(),
+                module.signature_at(fh.return_).len(),
+            );
+            if arity != native.arity() {
+                return Err(verification_error(
+                    IndexKind::FunctionHandle,
+                    idx,
+                    StatusCode::NATIVE_FUNCTION_ARITY_MISMATCH,
+                ));
+            }
         }
         // TODO: fix check and error code if we leave something around for native structs.
         // For now this generates the only error test cases care about...
@@ -606,7 +803,9 @@ impl Loader {
         &self,
         id: &ModuleId,
         data_store: &mut dyn DataStore,
+        path: &mut Vec<ModuleId>,
     ) -> VMResult<CompiledModule> {
+        let start = Instant::now();
         let module = match data_store.load_module(id) {
             Ok(blob) => match CompiledModule::deserialize(&blob) {
                 Ok(module) => module,
@@ -620,24 +819,37 @@ impl Loader {
                 return Err(err);
             }
         };
-        match self.verify_module(&module) {
-            Ok(_) => {
-                self.check_dependencies(&module, data_store)?;
-                Ok(module)
-            }
-            Err(err) => Err(err),
-        }
+        let deserialized = Instant::now();
+        self.verify_module(&module)?;
+        let verified = Instant::now();
+        // Includes the time spent loading the dependencies not in the cache
+        self.check_dependencies(&module, data_store, path)?;
+        let linked = Instant::now();
+
+        observe_load_phase("deserialize", deserialized - start);
+        observe_load_phase("verify", verified - deserialized);
+        observe_load_phase("link", linked - verified);
+        debug!(
+            "[VM] Loaded module {} at depth {}: deserialize {:?}, verify {:?}, link {:?}",
+            module_name(id),
+            path.len(),
+            deserialized - start,
+            verified - deserialized,
+            linked - verified
+        );
+        Ok(module)
     }
 
     fn check_dependencies(
         &self,
         module: &CompiledModule,
         data_store: &mut dyn DataStore,
+        path: &mut Vec<ModuleId>,
     ) -> VMResult<()> {
         let deps = load_module_dependencies(module);
         let mut dependencies = vec![];
         for dep in &deps {
-            dependencies.push(self.load_module(dep, data_store)?);
+            dependencies.push(self.load_module_with_path(dep, data_store, path)?);
         }
         let mut dependency_map = BTreeMap::new();
         for dependency in &dependencies {
@@ -721,34 +933,6 @@ impl<'a> Resolver<'a> {
         }
     }
 
-    pub(crate) fn field_offset(&self, idx: FieldHandleIndex) -> usize {
-        match &self.binary {
-            BinaryType::Module(module) => module.field_offset(idx),
-            BinaryType::Script(_) => unreachable!("Scripts cannot have field instructions"),
-        }
-    }
-
-    pub(crate) fn field_instantiation_offset(&self, idx: FieldInstantiationIndex) -> usize {
-        match &self.binary {
-            BinaryType::Module(module) => module.field_instantiation_offset(idx),
-            BinaryType::Script(_) => unreachable!("Scripts cannot have field instructions"),
-        }
-    }
-
-    pub(crate) fn field_count(&self, idx: StructDefinitionIndex) -> u16 {
-        match &self.binary {
-            BinaryType::Module(module) => module.field_count(idx.0),
-            BinaryType::Script(_) => unreachable!("Scripts cannot have type instructions"),
-        }
-    }
-
-    pub(crate) fn field_instantiation_count(&self, idx: StructDefInstantiationIndex) -> u16 {
-        match &self.binary {
-            BinaryType::Module(module) => module.field_instantiation_count(idx.0),
-            BinaryType::Script(_) => unreachable!("Scripts cannot have type instructions"),
-        }
-    }
-
     pub(crate) fn get_libra_type_info(
         &self,
         module_id: &ModuleId,
@@ -817,11 +1001,6 @@ pub struct Module {
     function_refs: Vec<usize>,
     // materialized instantiations, whether partial or not
     function_instantiations: Vec<FunctionInstantiation>,
-
-    // fields as a pair of index, first to the type, second to the field position in that type
-    field_handles: Vec<FieldHandle>,
-    // materialized instantiations, whether partial or not
-    field_instantiations: Vec<FieldInstantiation>,
 }
 
 impl Module {
@@ -839,21 +1018,18 @@ impl Module {
         let mut structs = vec![];
         for struct_def in module.struct_defs() {
             let idx = struct_refs[struct_def.struct_handle.0 as usize];
-            let field_count = cache.structs[idx].fields.len() as u16;
-            structs.push(StructDef { idx, field_count });
+            structs.push(StructDef { idx });
         }
 
         let mut struct_instantiations = vec![];
         for struct_inst in module.struct_instantiations() {
             let def = struct_inst.def.0 as usize;
             let struct_def = &structs[def];
-            let field_count = struct_def.field_count;
             let mut instantiation = vec![];
             for ty in &module.signature_at(struct_inst.type_parameters).0 {
                 instantiation.push(cache.make_type(&module, ty)?);
             }
             struct_instantiations.push(StructInstantiation {
-                field_count,
                 def: struct_def.idx,
                 instantiation,
             });
@@ -881,22 +1057,6 @@ impl Module {
             });
         }
 
-        let mut field_handles = vec![];
-        for f_handle in module.field_handles() {
-            let def_idx = f_handle.owner;
-            let owner = structs[def_idx.0 as usize].idx;
-            let offset = f_handle.field as usize;
-            field_handles.push(FieldHandle { owner, offset });
-        }
-
-        let mut field_instantiations: Vec<FieldInstantiation> = vec![];
-        for f_inst in module.field_instantiations() {
-            let fh_idx = f_inst.handle;
-            let owner = field_handles[fh_idx.0 as usize].owner;
-            let offset = field_handles[fh_idx.0 as usize].offset;
-            field_instantiations.push(FieldInstantiation { owner, offset });
-        }
-
         Ok(Self {
             id,
             module,
@@ -905,8 +1065,6 @@ impl Module {
             function_refs,
             struct_instantiations,
             function_instantiations,
-            field_handles,
-            field_instantiations,
         })
     }
 
@@ -930,25 +1088,9 @@ impl Module {
         &self.function_instantiations[idx as usize]
     }
 
-    fn field_count(&self, idx: u16) -> u16 {
-        self.structs[idx as usize].field_count
-    }
-
-    fn field_instantiation_count(&self, idx: u16) -> u16 {
-        self.struct_instantiations[idx as usize].field_count
-    }
-
     fn module(&self) -> &CompiledModule {
         &self.module
     }
-
-    fn field_offset(&self, idx: FieldHandleIndex) -> usize {
-        self.field_handles[idx.0 as usize].offset
-    }
-
-    fn field_instantiation_offset(&self, idx: FieldInstantiationIndex) -> usize {
-        self.field_instantiations[idx.0 as usize].offset
-    }
 }
 
 // A Script is very similar to a binary Script but data is "transformed" to a representation
@@ -1014,7 +1156,7 @@ impl Script {
         let scope = Scope::Script(*script_hash);
 
         let compiled_script = script.as_inner();
-        let code: Vec<Bytecode> = compiled_script.code.code.clone();
+        let code = cache.decode_code(&module, &compiled_script.code.code)?;
         let parameters = script.signature_at(compiled_script.parameters).clone();
         let return_ = Signature(vec![]);
         let locals = Signature(
@@ -1072,7 +1214,7 @@ enum Scope {
 // A runtime function
 #[derive(Debug)]
 pub struct Function {
-    code: Vec<Bytecode>,
+    code: Vec<Instruction>,
     parameters: Signature,
     return_: Signature,
     locals: Signature,
@@ -1083,7 +1225,11 @@ pub struct Function {
 }
 
 impl Function {
-    fn new(def: &FunctionDefinition, module: &CompiledModule) -> Self {
+    fn new(
+        def: &FunctionDefinition,
+        module: &CompiledModule,
+        cache: &ModuleCache,
+    ) -> VMResult<Self> {
         let handle = module.function_handle_at(def.function);
         let name = module.identifier_at(handle.name).to_owned();
         let module_id = module.self_id();
@@ -1101,7 +1247,7 @@ impl Function {
         // Native functions do not have a code unit
         let (code, locals) = match &def.code {
             Some(code) => (
-                code.code.clone(),
+                cache.decode_code(module, &code.code)?,
                 Signature(
                     parameters
                         .0
@@ -1115,7 +1261,7 @@ impl Function {
         };
         let return_ = module.signature_at(handle.return_).clone();
         let type_parameters = handle.type_parameters.clone();
-        Self {
+        Ok(Self {
             code,
             parameters,
             return_,
@@ -1124,7 +1270,7 @@ impl Function {
             native,
             scope,
             name,
-        }
+        })
     }
 
     pub(crate) fn module_id(&self) -> Option<&ModuleId> {
@@ -1163,7 +1309,7 @@ impl Function {
         self.name.as_str()
     }
 
-    pub(crate) fn code(&self) -> &[Bytecode] {
+    pub(crate) fn code(&self) -> &[Instruction] {
         &self.code
     }
 
@@ -1224,18 +1370,15 @@ impl FunctionInstantiation {
     }
 }
 
-// A struct definition carries an index to the type in the ModuleCache and the field count
-// which is the most common information used at runtime
+// A struct definition carries an index to the type in the ModuleCache
 #[derive(Debug)]
 struct StructDef {
-    field_count: u16,
     idx: usize,
 }
 
 // A struct insantiation.
 #[derive(Debug)]
 pub(crate) struct StructInstantiation {
-    field_count: u16,
     def: usize,
     instantiation: Vec<Type>,
 }
@@ -1250,20 +1393,6 @@ impl StructInstantiation {
     }
 }
 
-// A field handle. The offset is the only used information when operating on a field
-#[derive(Debug)]
-struct FieldHandle {
-    offset: usize,
-    owner: usize,
-}
-
-// A field instantiation. The offset is the only used information when operating on a field
-#[derive(Debug)]
-pub struct FieldInstantiation {
-    offset: usize,
-    owner: usize,
-}
-
 //
 // Utility functions
 //
@@ -1294,6 +1423,16 @@ fn load_module_dependencies(binary: &dyn ModuleAccess) -> Vec<ModuleId> {
     deps
 }
 
+fn module_name(id: &ModuleId) -> String {
+    format!("0x{}::{}", id.address(), id.name().as_str())
+}
+
+fn observe_load_phase(phase: &str, duration: Duration) {
+    MODULE_LOAD_SECONDS
+        .with_label_values(&[phase])
+        .observe(duration.as_secs_f64());
+}
+
 fn struct_match(struct_: &StructType, module: &ModuleId, name: &IdentStr) -> bool {
     struct_.name.as_ident_str() == name && &struct_.module == module
 }
@@ -1411,6 +1550,7 @@ impl Loader {
             U8 => FatType::U8,
             U64 => FatType::U64,
             U128 => FatType::U128,
+            U256 => FatType::U256,
             Address => FatType::Address,
             Signer => FatType::Signer,
             Vector(ty) => FatType::Vector(Box::new(self.type_to_fat_type(ty)?)),
@@ -1482,3 +1622,41 @@ impl Loader {
         Ok(libra_cache.set_libra_type_info(name.to_owned(), type_params.to_vec(), libra_type))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vm::file_format::{
+        basic_test_module, FieldHandle, FieldInstantiation, FieldInstantiationIndex,
+    };
+
+    fn charged_opcode(instr: Bytecode) -> u8 {
+        let mut module = basic_test_module();
+        module.field_handles.push(FieldHandle {
+            owner: StructDefinitionIndex(0),
+            field: 0,
+        });
+        module.field_instantiations.push(FieldInstantiation {
+            handle: FieldHandleIndex(0),
+            type_parameters: SignatureIndex(0),
+        });
+        let module = module.freeze().unwrap();
+        let instruction = ModuleCache::new().decode_instruction(&module, &instr);
+        match instruction.unwrap() {
+            Instruction::BorrowField(opcode, 0) => opcode as u8,
+            instruction => panic!("unexpected instruction {:?}", instruction),
+        }
+    }
+
+    #[test]
+    fn generic_field_borrows_charge_their_own_opcode() {
+        assert_eq!(
+            charged_opcode(Bytecode::MutBorrowFieldGeneric(FieldInstantiationIndex(0))),
+            Opcodes::MUT_BORROW_FIELD_GENERIC as u8,
+        );
+        assert_eq!(
+            charged_opcode(Bytecode::ImmBorrowFieldGeneric(FieldInstantiationIndex(0))),
+            Opcodes::IMM_BORROW_FIELD_GENERIC as u8,
+        );
+    }
+}
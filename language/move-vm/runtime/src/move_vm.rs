@@ -8,7 +8,7 @@ use move_core_types::{
     language_storage::{ModuleId, TypeTag},
 };
 use move_vm_types::{data_store::DataStore, gas_schedule::CostStrategy, values::Value};
-use vm::errors::VMResult;
+use vm::{deserializer::DeserializerConfig, errors::VMResult};
 
 pub struct MoveVM {
     runtime: VMRuntime,
@@ -21,6 +21,14 @@ impl MoveVM {
         }
     }
 
+    /// Creates a VM deserializing the modules it publishes with `publishing_config`, e.g. to
+    /// only accept modules in canonical form.
+    pub fn new_with_publishing_config(publishing_config: DeserializerConfig) -> Self {
+        Self {
+            runtime: VMRuntime::new_with_publishing_config(publishing_config),
+        }
+    }
+
     pub fn execute_function(
         &self,
         module: &ModuleId,
@@ -7,7 +7,9 @@ use libra_types::{
     contract_event::ContractEvent,
 };
 use move_core_types::{gas_schedule::CostTable, identifier::IdentStr, language_storage::ModuleId};
-use move_vm_natives::{account, debug, event, hash, lcs, signature, signer, vector};
+use move_vm_natives::{
+    account, debug, event, fixed_point, hash, lcs, signature, signer, table, vector,
+};
 use move_vm_types::{
     data_store::DataStore,
     gas_schedule::CostStrategy,
@@ -29,6 +31,7 @@ pub(crate) enum NativeFunction {
     HashSha2_256,
     HashSha3_256,
     LCSToBytes,
+    LCSFromBytes,
     PubED25519Validate,
     SigED25519Verify,
     SigED25519ThresholdVerify,
@@ -41,11 +44,19 @@ pub(crate) enum NativeFunction {
     VectorDestroyEmpty,
     VectorSwap,
     AccountWriteEvent,
+    EventStreamKey,
     DebugPrint,
     DebugPrintStackTrace,
     SignerBorrowAddress,
     CreateSigner,
     DestroySigner,
+    FixedPointMulDiv,
+    Secp256k1Recover,
+    SigBLS12381Verify,
+    TableAddEntry,
+    TableReadEntry,
+    TableRemoveEntry,
+    TableContainsEntry,
 }
 
 impl NativeFunction {
@@ -61,11 +72,14 @@ impl NativeFunction {
             (&CORE_CODE_ADDRESS, "Hash", "sha2_256") => HashSha2_256,
             (&CORE_CODE_ADDRESS, "Hash", "sha3_256") => HashSha3_256,
             (&CORE_CODE_ADDRESS, "LCS", "to_bytes") => LCSToBytes,
+            (&CORE_CODE_ADDRESS, "LCS", "from_bytes") => LCSFromBytes,
             (&CORE_CODE_ADDRESS, "Signature", "ed25519_validate_pubkey") => PubED25519Validate,
             (&CORE_CODE_ADDRESS, "Signature", "ed25519_verify") => SigED25519Verify,
             (&CORE_CODE_ADDRESS, "Signature", "ed25519_threshold_verify") => {
                 SigED25519ThresholdVerify
             }
+            (&CORE_CODE_ADDRESS, "Signature", "secp256k1_recover") => Secp256k1Recover,
+            (&CORE_CODE_ADDRESS, "Signature", "bls12381_verify") => SigBLS12381Verify,
             (&CORE_CODE_ADDRESS, "Vector", "length") => VectorLength,
             (&CORE_CODE_ADDRESS, "Vector", "empty") => VectorEmpty,
             (&CORE_CODE_ADDRESS, "Vector", "borrow") => VectorBorrow,
@@ -75,15 +89,49 @@ impl NativeFunction {
             (&CORE_CODE_ADDRESS, "Vector", "destroy_empty") => VectorDestroyEmpty,
             (&CORE_CODE_ADDRESS, "Vector", "swap") => VectorSwap,
             (&CORE_CODE_ADDRESS, "Event", "write_to_event_store") => AccountWriteEvent,
+            (&CORE_CODE_ADDRESS, "Event", "stream_key") => EventStreamKey,
             (&CORE_CODE_ADDRESS, "LibraAccount", "create_signer") => CreateSigner,
             (&CORE_CODE_ADDRESS, "LibraAccount", "destroy_signer") => DestroySigner,
             (&CORE_CODE_ADDRESS, "Debug", "print") => DebugPrint,
             (&CORE_CODE_ADDRESS, "Debug", "print_stack_trace") => DebugPrintStackTrace,
             (&CORE_CODE_ADDRESS, "Signer", "borrow_address") => SignerBorrowAddress,
+            (&CORE_CODE_ADDRESS, "FixedPoint64", "mul_div") => FixedPointMulDiv,
+            (&CORE_CODE_ADDRESS, "Table", "add_entry") => TableAddEntry,
+            (&CORE_CODE_ADDRESS, "Table", "read_entry") => TableReadEntry,
+            (&CORE_CODE_ADDRESS, "Table", "remove_entry") => TableRemoveEntry,
+            (&CORE_CODE_ADDRESS, "Table", "contains_entry") => TableContainsEntry,
             _ => return None,
         })
     }
 
+    /// The number of parameters and return values of the native function, which its declaration
+    /// in a module must match.
+    pub(crate) fn arity(self) -> (usize, usize) {
+        use NativeFunction::*;
+
+        match self {
+            HashSha2_256 | HashSha3_256 | LCSToBytes | LCSFromBytes | PubED25519Validate => (1, 1),
+            SigED25519Verify | SigBLS12381Verify => (3, 1),
+            SigED25519ThresholdVerify => (4, 1),
+            VectorLength | VectorPopBack => (1, 1),
+            VectorEmpty => (0, 1),
+            VectorBorrow | VectorBorrowMut => (2, 1),
+            VectorPushBack => (2, 0),
+            VectorDestroyEmpty => (1, 0),
+            VectorSwap => (3, 0),
+            AccountWriteEvent => (3, 0),
+            EventStreamKey => (1, 1),
+            DebugPrint => (1, 0),
+            DebugPrintStackTrace => (0, 0),
+            SignerBorrowAddress | CreateSigner => (1, 1),
+            DestroySigner => (1, 0),
+            FixedPointMulDiv => (4, 1),
+            Secp256k1Recover => (3, 2),
+            TableAddEntry => (3, 0),
+            TableReadEntry | TableRemoveEntry | TableContainsEntry => (2, 1),
+        }
+    }
+
     /// Given the vector of aguments, it executes the native function.
     pub(crate) fn dispatch(
         self,
@@ -99,6 +147,8 @@ impl NativeFunction {
             Self::SigED25519ThresholdVerify => {
                 signature::native_ed25519_threshold_signature_verification(ctx, t, v)
             }
+            Self::Secp256k1Recover => signature::native_secp256k1_recover(ctx, t, v),
+            Self::SigBLS12381Verify => signature::native_bls12381_signature_verification(ctx, t, v),
             Self::VectorLength => vector::native_length(ctx, t, v),
             Self::VectorEmpty => vector::native_empty(ctx, t, v),
             Self::VectorBorrow => vector::native_borrow(ctx, t, v),
@@ -109,12 +159,19 @@ impl NativeFunction {
             Self::VectorSwap => vector::native_swap(ctx, t, v),
             // natives that need the full API of `NativeContext`
             Self::AccountWriteEvent => event::native_emit_event(ctx, t, v),
+            Self::EventStreamKey => event::native_event_stream_key(ctx, t, v),
             Self::LCSToBytes => lcs::native_to_bytes(ctx, t, v),
+            Self::LCSFromBytes => lcs::native_from_bytes(ctx, t, v),
             Self::DebugPrint => debug::native_print(ctx, t, v),
             Self::DebugPrintStackTrace => debug::native_print_stack_trace(ctx, t, v),
             Self::SignerBorrowAddress => signer::native_borrow_address(ctx, t, v),
             Self::CreateSigner => account::native_create_signer(ctx, t, v),
             Self::DestroySigner => account::native_destroy_signer(ctx, t, v),
+            Self::FixedPointMulDiv => fixed_point::native_mul_div(ctx, t, v),
+            Self::TableAddEntry => table::native_add_entry(ctx, t, v),
+            Self::TableReadEntry => table::native_read_entry(ctx, t, v),
+            Self::TableRemoveEntry => table::native_remove_entry(ctx, t, v),
+            Self::TableContainsEntry => table::native_contains_entry(ctx, t, v),
         }
     }
 }
@@ -173,7 +230,15 @@ impl<'a> NativeContext for FunctionContext<'a> {
     }
 
     fn save_event(&mut self, event: ContractEvent) -> VMResult<()> {
-        Ok(self.data_store.emit_event(event))
+        self.data_store.emit_event(event)
+    }
+
+    fn read_table_entry(&mut self, ap: &AccessPath) -> VMResult<Option<Vec<u8>>> {
+        self.data_store.read_table_entry(ap)
+    }
+
+    fn write_table_entry(&mut self, ap: AccessPath, value: Option<Vec<u8>>) -> VMResult<()> {
+        self.data_store.write_table_entry(ap, value)
     }
 
     fn convert_to_fat_types(&self, types: Vec<Type>) -> VMResult<Vec<FatType>> {
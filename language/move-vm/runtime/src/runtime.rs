@@ -12,6 +12,7 @@ use move_core_types::{
 use move_vm_types::{data_store::DataStore, gas_schedule::CostStrategy, values::Value};
 use vm::{
     access::ModuleAccess,
+    deserializer::DeserializerConfig,
     errors::{verification_error, vm_error, Location, VMResult},
     file_format::SignatureToken,
     CompiledModule, IndexKind,
@@ -20,12 +21,19 @@ use vm::{
 /// An instantiation of the MoveVM.
 pub(crate) struct VMRuntime {
     loader: Loader,
+    /// Limits and checks of the deserialization of published modules
+    publishing_config: DeserializerConfig,
 }
 
 impl VMRuntime {
     pub(crate) fn new() -> Self {
+        Self::new_with_publishing_config(DeserializerConfig::default())
+    }
+
+    pub(crate) fn new_with_publishing_config(publishing_config: DeserializerConfig) -> Self {
         VMRuntime {
             loader: Loader::new(),
+            publishing_config,
         }
     }
 
@@ -38,13 +46,14 @@ impl VMRuntime {
     ) -> VMResult<()> {
         // deserialize the module. Perform bounds check. After this indexes can be
         // used with the `[]` operator
-        let compiled_module = match CompiledModule::deserialize(&module) {
-            Ok(module) => module,
-            Err(err) => {
-                warn!("[VM] module deserialization failed {:?}", err);
-                return Err(err);
-            }
-        };
+        let compiled_module =
+            match CompiledModule::deserialize_with_config(&module, &self.publishing_config) {
+                Ok(module) => module,
+                Err(err) => {
+                    warn!("[VM] module deserialization failed {:?}", err);
+                    return Err(err);
+                }
+            };
 
         // Make sure the module's self address matches the transaction sender. The self address is
         // where the module will actually be published. If we did not check this, the sender could
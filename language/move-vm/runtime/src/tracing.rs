@@ -1,6 +1,8 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(debug_assertions)]
+use crate::instruction::Instruction;
 #[cfg(debug_assertions)]
 use once_cell::sync::Lazy;
 #[cfg(debug_assertions)]
@@ -10,8 +12,6 @@ use std::{
     io::Write,
     sync::Mutex,
 };
-#[cfg(debug_assertions)]
-use vm::file_format::Bytecode;
 
 #[cfg(debug_assertions)]
 const MOVE_VM_TRACING_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
@@ -38,7 +38,7 @@ static LOGGING_FILE: Lazy<Mutex<File>> = Lazy::new(|| {
 
 // Only include in debug builds
 #[cfg(debug_assertions)]
-pub fn trace(function_desc: &str, pc: u16, instr: &Bytecode) {
+pub(crate) fn trace(function_desc: &str, pc: u16, instr: &Instruction) {
     if *TRACING_ENABLED {
         let f = &mut *LOGGING_FILE.lock().unwrap();
         writeln!(f, "{},{},{:?}", function_desc, pc, instr).unwrap();
@@ -47,10 +47,23 @@ pub trait DataStore {
     /// Check if this module exists.
     fn exists_module(&self, key: &ModuleId) -> bool;
 
+    /// Get the LCS-serialized `ModuleErrorMap` published alongside a module, if any.
+    fn load_error_map(&self, module: &ModuleId) -> VMResult<Option<Vec<u8>>>;
+
+    // ---
+    // TableStore operations
+    // ---
+
+    /// Get the serialized value of a table entry.
+    fn read_table_entry(&mut self, ap: &AccessPath) -> VMResult<Option<Vec<u8>>>;
+
+    /// Set the serialized value of a table entry, or delete it if `value` is `None`.
+    fn write_table_entry(&mut self, ap: AccessPath, value: Option<Vec<u8>>) -> VMResult<()>;
+
     // ---
     // EventStore operations
     // ---
 
     /// Emit an event to the EventStore
-    fn emit_event(&mut self, event: ContractEvent);
+    fn emit_event(&mut self, event: ContractEvent) -> VMResult<()>;
 }
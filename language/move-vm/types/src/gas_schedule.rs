@@ -11,16 +11,19 @@ use libra_types::{
     vm_error::{StatusCode, VMStatus},
 };
 use mirai_annotations::*;
-use move_core_types::gas_schedule::{
-    words_in, AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasConstants, GasCost,
-    GasUnits,
+use move_core_types::{
+    gas_schedule::{
+        words_in, AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasConstants, GasCost,
+        GasUnits,
+    },
+    u256::U256,
 };
 use vm::{
     errors::VMResult,
     file_format::{
         Bytecode, ConstantPoolIndex, FieldHandleIndex, FieldInstantiationIndex,
-        FunctionHandleIndex, FunctionInstantiationIndex, StructDefInstantiationIndex,
-        StructDefinitionIndex, NUMBER_OF_NATIVE_FUNCTIONS,
+        FunctionHandleIndex, FunctionInstantiationIndex, SignatureIndex,
+        StructDefInstantiationIndex, StructDefinitionIndex, NUMBER_OF_NATIVE_FUNCTIONS,
     },
     file_format_common::{instruction_key, Opcodes},
 };
@@ -205,9 +208,11 @@ pub fn zero_cost_schedule() -> CostTable {
         (LdU8(0), GasCost::new(0, 0)),
         (LdU64(0), GasCost::new(0, 0)),
         (LdU128(0), GasCost::new(0, 0)),
+        (LdU256(U256::zero()), GasCost::new(0, 0)),
         (CastU8, GasCost::new(0, 0)),
         (CastU64, GasCost::new(0, 0)),
         (CastU128, GasCost::new(0, 0)),
+        (CastU256, GasCost::new(0, 0)),
         (Abort, GasCost::new(0, 0)),
         (MutBorrowLoc(0), GasCost::new(0, 0)),
         (ImmBorrowLoc(0), GasCost::new(0, 0)),
@@ -267,6 +272,14 @@ pub fn zero_cost_schedule() -> CostTable {
             GasCost::new(0, 0),
         ),
         (Nop, GasCost::new(0, 0)),
+        (VecPack(SignatureIndex::new(0), 0), GasCost::new(0, 0)),
+        (VecLen(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (VecImmBorrow(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (VecMutBorrow(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (VecPushBack(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (VecPopBack(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (VecUnpack(SignatureIndex::new(0), 0), GasCost::new(0, 0)),
+        (VecSwap(SignatureIndex::new(0)), GasCost::new(0, 0)),
     ];
     let native_table = (0..NUMBER_OF_NATIVE_FUNCTIONS)
         .map(|_| GasCost::new(0, 0))
@@ -312,4 +325,9 @@ pub enum NativeCostIndex {
     SIGNER_BORROW = 15,
     CREATE_SIGNER = 16,
     DESTROY_SIGNER = 17,
+    FIXED_POINT_MUL_DIV = 18,
+    SECP256K1_RECOVER = 19,
+    BLS12381_VERIFY = 20,
+    LCS_FROM_BYTES = 21,
+    TABLE_ENTRY_ACCESS = 22,
 }
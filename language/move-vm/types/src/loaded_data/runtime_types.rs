@@ -24,6 +24,7 @@ pub enum Type {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     Vector(Box<Type>),
@@ -52,6 +53,7 @@ impl Type {
             Type::U8 => Type::U8,
             Type::U64 => Type::U64,
             Type::U128 => Type::U128,
+            Type::U256 => Type::U256,
             Type::Address => Type::Address,
             Type::Signer => Type::Signer,
             Type::Vector(ty) => Type::Vector(Box::new(ty.subst(ty_args)?)),
@@ -43,6 +43,7 @@ pub enum FatType {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     Vector(Box<FatType>),
@@ -127,6 +128,7 @@ impl FatType {
             U8 => U8,
             U64 => U64,
             U128 => U128,
+            U256 => U256,
             Address => Address,
             Signer => Signer,
             Vector(ty) => Vector(Box::new(ty.subst(ty_args)?)),
@@ -147,6 +149,7 @@ impl FatType {
             U8 => TypeTag::U8,
             U64 => TypeTag::U64,
             U128 => TypeTag::U128,
+            U256 => TypeTag::U256,
             Address => TypeTag::Address,
             Signer => TypeTag::Signer,
             Vector(ty) => TypeTag::Vector(Box::new(ty.type_tag()?)),
@@ -165,7 +168,9 @@ impl FatType {
         use FatType::*;
 
         match self {
-            Bool | U8 | U64 | U128 | Address | Reference(_) | MutableReference(_) => Ok(false),
+            Bool | U8 | U64 | U128 | U256 | Address | Reference(_) | MutableReference(_) => {
+                Ok(false)
+            }
             Signer => Ok(true),
             Vector(ty) => ty.is_resource(),
             Struct(struct_ty) => Ok(struct_ty.is_resource),
@@ -187,6 +192,7 @@ impl FatType {
             U8 => debug_write!(buf, "u8"),
             U64 => debug_write!(buf, "u64"),
             U128 => debug_write!(buf, "u128"),
+            U256 => debug_write!(buf, "u256"),
             Address => debug_write!(buf, "address"),
             Signer => debug_write!(buf, "signer"),
             Vector(elem_ty) => {
@@ -224,6 +230,7 @@ pub mod prop {
                 Just(U8),
                 Just(U64),
                 Just(U128),
+                Just(U256),
                 Just(Address),
                 Just(Signer)
             ]
@@ -300,6 +307,7 @@ impl TryInto<MoveTypeLayout> for &FatType {
             FatType::U8 => MoveTypeLayout::U8,
             FatType::U64 => MoveTypeLayout::U64,
             FatType::U128 => MoveTypeLayout::U128,
+            FatType::U256 => MoveTypeLayout::U256,
             FatType::Bool => MoveTypeLayout::Bool,
             FatType::Vector(v) => MoveTypeLayout::Vector(Box::new(v.as_ref().try_into()?)),
             FatType::Struct(s) => MoveTypeLayout::Struct(MoveStructLayout::new(
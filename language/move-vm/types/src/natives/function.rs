@@ -22,7 +22,8 @@ use crate::{
     values::{Struct, Value},
 };
 use libra_types::{
-    account_address::AccountAddress, contract_event::ContractEvent, vm_error::VMStatus,
+    access_path::AccessPath, account_address::AccountAddress, contract_event::ContractEvent,
+    vm_error::VMStatus,
 };
 use move_core_types::{
     gas_schedule::{AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasUnits},
@@ -54,6 +55,10 @@ pub trait NativeContext {
     ) -> VMResult<()>;
     /// Saves contract event.
     fn save_event(&mut self, event: ContractEvent) -> VMResult<()>;
+    /// Gets the serialized value of a table entry.
+    fn read_table_entry(&mut self, ap: &AccessPath) -> VMResult<Option<Vec<u8>>>;
+    /// Sets the serialized value of a table entry, or deletes it if `value` is `None`.
+    fn write_table_entry(&mut self, ap: AccessPath, value: Option<Vec<u8>>) -> VMResult<()>;
     /// Converts types to fet types.
     fn convert_to_fat_types(&self, types: Vec<Type>) -> VMResult<Vec<FatType>>;
     /// Whether a type is a resource or not.
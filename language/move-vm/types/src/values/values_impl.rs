@@ -1,17 +1,17 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{
-    loaded_data::types::{FatStructType, FatType},
-    natives::function::NativeResult,
-};
+use crate::loaded_data::types::{FatStructType, FatType};
 use libra_types::{
     account_address::AccountAddress,
     vm_error::{sub_status::NFE_VECTOR_ERROR_BASE, StatusCode, VMStatus},
 };
-use move_core_types::gas_schedule::{
-    words_in, AbstractMemorySize, GasAlgebra, GasCarrier, GasUnits, CONST_SIZE, REFERENCE_SIZE,
-    STRUCT_SIZE,
+use move_core_types::{
+    gas_schedule::{
+        words_in, AbstractMemorySize, GasAlgebra, GasCarrier, CONST_SIZE, REFERENCE_SIZE,
+        STRUCT_SIZE,
+    },
+    u256::U256,
 };
 use std::{
     cell::{Ref, RefCell, RefMut},
@@ -44,6 +44,7 @@ enum ValueImpl {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
 
@@ -69,6 +70,7 @@ enum Container {
     U8(Vec<u8>),
     U64(Vec<u64>),
     U128(Vec<u128>),
+    U256(Vec<U256>),
     Bool(Vec<bool>),
     Address(Vec<AccountAddress>),
 }
@@ -168,6 +170,7 @@ pub enum IntegerValue {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
 }
 
 /// A Move struct.
@@ -208,6 +211,7 @@ impl Container {
             U8(v) => v.len(),
             U64(v) => v.len(),
             U128(v) => v.len(),
+            U256(v) => v.len(),
             Bool(v) => v.len(),
             Address(v) => v.len(),
         }
@@ -218,7 +222,7 @@ impl Container {
 
         match self {
             Resource(_) => true,
-            General(_) | U8(_) | U64(_) | U128(_) | Address(_) | Bool(_) => false,
+            General(_) | U8(_) | U64(_) | U128(_) | U256(_) | Address(_) | Bool(_) => false,
         }
     }
 
@@ -236,8 +240,8 @@ impl ValueImpl {
         use ValueImpl::*;
 
         match self {
-            Invalid | U8(_) | U64(_) | U128(_) | Address(_) | Bool(_) | ContainerRef(_)
-            | IndexedRef(_) => false,
+            Invalid | U8(_) | U64(_) | U128(_) | U256(_) | Address(_) | Bool(_)
+            | ContainerRef(_) | IndexedRef(_) => false,
 
             Container(c) => c.borrow().is_resource(),
         }
@@ -254,6 +258,7 @@ impl Value {
             (SignatureToken::U8, ValueImpl::U8(_)) => true,
             (SignatureToken::U64, ValueImpl::U64(_)) => true,
             (SignatureToken::U128, ValueImpl::U128(_)) => true,
+            (SignatureToken::U256, ValueImpl::U256(_)) => true,
             (SignatureToken::Bool, ValueImpl::Bool(_)) => true,
             (SignatureToken::Address, ValueImpl::Address(_)) => true,
             (SignatureToken::Vector(ty), ValueImpl::Container(r)) => match (&**ty, &*r.borrow()) {
@@ -261,6 +266,7 @@ impl Value {
                 | (SignatureToken::U8, Container::U8(_))
                 | (SignatureToken::U64, Container::U64(_))
                 | (SignatureToken::U128, Container::U128(_))
+                | (SignatureToken::U256, Container::U256(_))
                 | (SignatureToken::Address, Container::Address(_)) => true,
                 _ => false,
             },
@@ -283,6 +289,7 @@ impl Value {
             | ValueImpl::U8(_)
             | ValueImpl::U64(_)
             | ValueImpl::U128(_)
+            | ValueImpl::U256(_)
             | ValueImpl::Address(_) => true,
             ValueImpl::Container(r) => {
                 match &*r.borrow() {
@@ -290,6 +297,7 @@ impl Value {
                     | Container::U8(_)
                     | Container::U64(_)
                     | Container::U128(_)
+                    | Container::U256(_)
                     | Container::Address(_) => true,
                     // TODO: we need to enable vector of vectors and move Value will change
                     // to have a knowledge of vectors vs structs
@@ -374,6 +382,7 @@ macro_rules! impl_vm_value_ref {
 impl_vm_value_ref!(u8, U8);
 impl_vm_value_ref!(u64, U64);
 impl_vm_value_ref!(u128, U128);
+impl_vm_value_ref!(U256, U256);
 impl_vm_value_ref!(bool, Bool);
 impl_vm_value_ref!(AccountAddress, Address);
 
@@ -405,6 +414,7 @@ impl ValueImpl {
             U8(x) => U8(*x),
             U64(x) => U64(*x),
             U128(x) => U128(*x),
+            U256(x) => U256(*x),
             Bool(x) => Bool(*x),
             Address(x) => Address(*x),
 
@@ -431,6 +441,7 @@ impl Container {
             U8(v) => U8(v.clone()),
             U64(v) => U64(v.clone()),
             U128(v) => U128(v.clone()),
+            U256(v) => U256(v.clone()),
             Bool(v) => Bool(v.clone()),
             Address(v) => Address(v.clone()),
         })
@@ -489,6 +500,7 @@ impl ValueImpl {
             (U8(l), U8(r)) => l == r,
             (U64(l), U64(r)) => l == r,
             (U128(l), U128(r)) => l == r,
+            (U256(l), U256(r)) => l == r,
             (Bool(l), Bool(r)) => l == r,
             (Address(l), Address(r)) => l == r,
 
@@ -527,6 +539,7 @@ impl Container {
                 (U8(l), U8(r)) => l == r,
                 (U64(l), U64(r)) => l == r,
                 (U128(l), U128(r)) => l == r,
+                (U256(l), U256(r)) => l == r,
                 (Bool(l), Bool(r)) => l == r,
                 (Address(l), Address(r)) => l == r,
                 _ => {
@@ -562,6 +575,7 @@ impl IndexedRef {
             (U8(v1), U8(v2)) => v1[self.idx] == v2[other.idx],
             (U64(v1), U64(v2)) => v1[self.idx] == v2[other.idx],
             (U128(v1), U128(v2)) => v1[self.idx] == v2[other.idx],
+            (U256(v1), U256(v2)) => v1[self.idx] == v2[other.idx],
             (Bool(v1), Bool(v2)) => v1[self.idx] == v2[other.idx],
             (Address(v1), Address(v2)) => v1[self.idx] == v2[other.idx],
 
@@ -586,6 +600,12 @@ impl IndexedRef {
             (U128(v1), General(v2)) | (U128(v1), Resource(v2)) => {
                 v1[self.idx] == *v2[other.idx].as_value_ref::<u128>()?
             }
+            (General(v1), U256(v2)) | (Resource(v1), U256(v2)) => {
+                *v1[self.idx].as_value_ref::<U256>()? == v2[other.idx]
+            }
+            (U256(v1), General(v2)) | (U256(v1), Resource(v2)) => {
+                v1[self.idx] == *v2[other.idx].as_value_ref::<U256>()?
+            }
 
             (General(v1), Bool(v2)) | (Resource(v1), Bool(v2)) => {
                 *v1[self.idx].as_value_ref::<bool>()? == v2[other.idx]
@@ -640,6 +660,7 @@ impl IndexedRef {
             U8(v) => ValueImpl::U8(v[self.idx]),
             U64(v) => ValueImpl::U64(v[self.idx]),
             U128(v) => ValueImpl::U128(v[self.idx]),
+            U256(v) => ValueImpl::U256(v[self.idx]),
             Bool(v) => ValueImpl::Bool(v[self.idx]),
             Address(v) => ValueImpl::Address(v[self.idx]),
         };
@@ -735,6 +756,7 @@ impl IndexedRef {
             (Container::U8(v), ValueImpl::U8(x)) => v[self.idx] = *x,
             (Container::U64(v), ValueImpl::U64(x)) => v[self.idx] = *x,
             (Container::U128(v), ValueImpl::U128(x)) => v[self.idx] = *x,
+            (Container::U256(v), ValueImpl::U256(x)) => v[self.idx] = *x,
             (Container::Bool(v), ValueImpl::Bool(x)) => v[self.idx] = *x,
             _ => {
                 return Err(
@@ -809,6 +831,7 @@ impl ContainerRef {
             Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Address(_)
             | Container::Bool(_) => ValueImpl::IndexedRef(IndexedRef {
                 idx,
@@ -852,6 +875,7 @@ impl Locals {
                 ValueImpl::U8(_)
                 | ValueImpl::U64(_)
                 | ValueImpl::U128(_)
+                | ValueImpl::U256(_)
                 | ValueImpl::Bool(_)
                 | ValueImpl::Address(_) => Ok(Value(ValueImpl::IndexedRef(IndexedRef {
                     container_ref: ContainerRef::Local(Rc::clone(&self.0)),
@@ -867,6 +891,7 @@ impl Locals {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Address(_)
             | Container::Bool(_) => {
                 Err(VMStatus::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
@@ -914,6 +939,7 @@ impl Locals {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Address(_)
             | Container::Bool(_) => unreachable!(),
         }
@@ -928,6 +954,7 @@ impl Locals {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Address(_)
             | Container::Bool(_) => unreachable!(),
         };
@@ -957,6 +984,7 @@ impl Locals {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Address(_)
             | Container::Bool(_) => unreachable!(),
         };
@@ -1022,6 +1050,10 @@ impl Value {
         Self(ValueImpl::U128(x))
     }
 
+    pub fn u256(x: U256) -> Self {
+        Self(ValueImpl::U256(x))
+    }
+
     pub fn bool(x: bool) -> Self {
         Self(ValueImpl::Bool(x))
     }
@@ -1065,6 +1097,12 @@ impl Value {
         )))
     }
 
+    pub fn vector_u256(it: impl IntoIterator<Item = U256>) -> Self {
+        Self(ValueImpl::new_container(Container::U256(
+            it.into_iter().collect(),
+        )))
+    }
+
     pub fn vector_bool(it: impl IntoIterator<Item = bool>) -> Self {
         Self(ValueImpl::new_container(Container::Bool(
             it.into_iter().collect(),
@@ -1123,6 +1161,7 @@ macro_rules! impl_vm_value_cast {
 impl_vm_value_cast!(u8, U8);
 impl_vm_value_cast!(u64, U64);
 impl_vm_value_cast!(u128, U128);
+impl_vm_value_cast!(U256, U256);
 impl_vm_value_cast!(bool, Bool);
 impl_vm_value_cast!(AccountAddress, Address);
 impl_vm_value_cast!(ContainerRef, ContainerRef);
@@ -1134,6 +1173,7 @@ impl VMValueCast<IntegerValue> for Value {
             ValueImpl::U8(x) => Ok(IntegerValue::U8(x)),
             ValueImpl::U64(x) => Ok(IntegerValue::U64(x)),
             ValueImpl::U128(x) => Ok(IntegerValue::U128(x)),
+            ValueImpl::U256(x) => Ok(IntegerValue::U256(x)),
             v => Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR)
                 .with_message(format!("cannot cast {:?} to integer", v,))),
         }
@@ -1171,6 +1211,7 @@ impl VMValueCast<Vec<Value>> for Value {
                 Container::U8(vs) => vs.into_iter().map(Value::u8).collect(),
                 Container::U64(vs) => vs.into_iter().map(Value::u64).collect(),
                 Container::U128(vs) => vs.into_iter().map(Value::u128).collect(),
+                Container::U256(vs) => vs.into_iter().map(Value::u256).collect(),
                 Container::Bool(vs) => vs.into_iter().map(Value::bool).collect(),
                 Container::Address(vs) => vs.into_iter().map(Value::address).collect(),
             }),
@@ -1279,6 +1320,16 @@ impl VMValueCast<u128> for IntegerValue {
     }
 }
 
+impl VMValueCast<U256> for IntegerValue {
+    fn cast(self) -> VMResult<U256> {
+        match self {
+            Self::U256(x) => Ok(x),
+            v => Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to u256", v,))),
+        }
+    }
+}
+
 impl IntegerValue {
     pub fn value_as<T>(self) -> VMResult<T>
     where
@@ -1302,6 +1353,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => u8::checked_add(l, r).map(IntegerValue::U8),
             (U64(l), U64(r)) => u64::checked_add(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_add(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => U256::checked_add(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot add {:?} and {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1316,6 +1368,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => u8::checked_sub(l, r).map(IntegerValue::U8),
             (U64(l), U64(r)) => u64::checked_sub(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_sub(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => U256::checked_sub(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot sub {:?} from {:?}", r, l);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1330,6 +1383,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => u8::checked_mul(l, r).map(IntegerValue::U8),
             (U64(l), U64(r)) => u64::checked_mul(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_mul(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => U256::checked_mul(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot mul {:?} and {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1344,6 +1398,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => u8::checked_div(l, r).map(IntegerValue::U8),
             (U64(l), U64(r)) => u64::checked_div(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_div(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => U256::checked_div(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot div {:?} by {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1358,6 +1413,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => u8::checked_rem(l, r).map(IntegerValue::U8),
             (U64(l), U64(r)) => u64::checked_rem(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_rem(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => U256::checked_rem(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot rem {:?} by {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1372,6 +1428,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => IntegerValue::U8(l | r),
             (U64(l), U64(r)) => IntegerValue::U64(l | r),
             (U128(l), U128(r)) => IntegerValue::U128(l | r),
+            (U256(l), U256(r)) => IntegerValue::U256(l | r),
             (l, r) => {
                 let msg = format!("Cannot bit_or {:?} and {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1385,6 +1442,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => IntegerValue::U8(l & r),
             (U64(l), U64(r)) => IntegerValue::U64(l & r),
             (U128(l), U128(r)) => IntegerValue::U128(l & r),
+            (U256(l), U256(r)) => IntegerValue::U256(l & r),
             (l, r) => {
                 let msg = format!("Cannot bit_and {:?} and {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1398,6 +1456,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => IntegerValue::U8(l ^ r),
             (U64(l), U64(r)) => IntegerValue::U64(l ^ r),
             (U128(l), U128(r)) => IntegerValue::U128(l ^ r),
+            (U256(l), U256(r)) => IntegerValue::U256(l ^ r),
             (l, r) => {
                 let msg = format!("Cannot bit_xor {:?} and {:?}", l, r);
                 return Err(VMStatus::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1427,6 +1486,8 @@ impl IntegerValue {
                 }
                 IntegerValue::U128(x << n_bits)
             }
+            // Shifts by a u8 are always below 256 bits
+            U256(x) => IntegerValue::U256(x << n_bits),
         })
     }
 
@@ -1452,6 +1513,7 @@ impl IntegerValue {
                 }
                 IntegerValue::U128(x >> n_bits)
             }
+            U256(x) => IntegerValue::U256(x >> n_bits),
         })
     }
 
@@ -1462,6 +1524,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => l < r,
             (U64(l), U64(r)) => l < r,
             (U128(l), U128(r)) => l < r,
+            (U256(l), U256(r)) => l < r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1479,6 +1542,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => l <= r,
             (U64(l), U64(r)) => l <= r,
             (U128(l), U128(r)) => l <= r,
+            (U256(l), U256(r)) => l <= r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1496,6 +1560,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => l > r,
             (U64(l), U64(r)) => l > r,
             (U128(l), U128(r)) => l > r,
+            (U256(l), U256(r)) => l > r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1513,6 +1578,7 @@ impl IntegerValue {
             (U8(l), U8(r)) => l >= r,
             (U64(l), U64(r)) => l >= r,
             (U128(l), U128(r)) => l >= r,
+            (U256(l), U256(r)) => l >= r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1530,6 +1596,7 @@ impl IntegerValue {
             U8(x) => Value::u8(x),
             U64(x) => Value::u64(x),
             U128(x) => Value::u128(x),
+            U256(x) => Value::u256(x),
         }
     }
 }
@@ -1556,6 +1623,10 @@ impl IntegerValue {
                     Ok(x as u8)
                 }
             }
+            U256(x) => x.to_u8().ok_or_else(|| {
+                VMStatus::new(StatusCode::ARITHMETIC_ERROR)
+                    .with_message(format!("Cannot cast u256({}) to u8", x))
+            }),
         }
     }
 
@@ -1573,16 +1644,35 @@ impl IntegerValue {
                     Ok(x as u64)
                 }
             }
+            U256(x) => x.to_u64().ok_or_else(|| {
+                VMStatus::new(StatusCode::ARITHMETIC_ERROR)
+                    .with_message(format!("Cannot cast u256({}) to u64", x))
+            }),
         }
     }
 
     pub fn cast_u128(self) -> VMResult<u128> {
         use IntegerValue::*;
 
+        match self {
+            U8(x) => Ok(x as u128),
+            U64(x) => Ok(x as u128),
+            U128(x) => Ok(x),
+            U256(x) => x.to_u128().ok_or_else(|| {
+                VMStatus::new(StatusCode::ARITHMETIC_ERROR)
+                    .with_message(format!("Cannot cast u256({}) to u128", x))
+            }),
+        }
+    }
+
+    pub fn cast_u256(self) -> VMResult<U256> {
+        use IntegerValue::*;
+
         Ok(match self {
-            U8(x) => x as u128,
-            U64(x) => x as u128,
-            U128(x) => x,
+            U8(x) => U256::from(x),
+            U64(x) => U256::from(x),
+            U128(x) => U256::from(x),
+            U256(x) => x,
         })
     }
 }
@@ -1591,19 +1681,16 @@ impl IntegerValue {
 *
 * Vector
 *
-*   Native function imeplementations of the Vector module.
-*
-*   TODO: split the code into two parts:
-*         1) Internal vector APIs that define & implements the core operations
-             (and operations only).
-*         2) Native function adapters that the dispatcher can call into. These will
-*            check if arguments are valid and deal with gas metering.
+*   Core operations on vectors, shared by the vector bytecodes and the native functions
+*   of the Vector module. Both fail with a `NATIVE_FUNCTION_ERROR`, so that compiling
+*   calls to the natives into bytecodes does not change how transactions abort.
 *
 **************************************************************************************/
 
 pub const INDEX_OUT_OF_BOUNDS: u64 = NFE_VECTOR_ERROR_BASE + 1;
 pub const POP_EMPTY_VEC: u64 = NFE_VECTOR_ERROR_BASE + 2;
 pub const DESTROY_NON_EMPTY_VEC: u64 = NFE_VECTOR_ERROR_BASE + 3;
+pub const UNPACK_LENGTH_MISMATCH: u64 = NFE_VECTOR_ERROR_BASE + 4;
 
 fn check_elem_layout(context: &impl NativeContext, ty: &Type, v: &Container) -> VMResult<()> {
     let is_resource = context.is_resource(ty)?;
@@ -1612,6 +1699,7 @@ fn check_elem_layout(context: &impl NativeContext, ty: &Type, v: &Container) ->
         (Type::U8, Container::U8(_))
         | (Type::U64, Container::U64(_))
         | (Type::U128, Container::U128(_))
+        | (Type::U256, Container::U256(_))
         | (Type::Bool, Container::Bool(_))
         | (Type::Address, Container::Address(_))
         | (Type::Signer, Container::Resource(_))
@@ -1639,6 +1727,7 @@ fn check_elem_layout(context: &impl NativeContext, ty: &Type, v: &Container) ->
         (Type::U8, _)
         | (Type::U64, _)
         | (Type::U128, _)
+        | (Type::U256, _)
         | (Type::Bool, _)
         | (Type::Address, _)
         | (Type::Signer, _)
@@ -1654,6 +1743,10 @@ fn check_elem_layout(context: &impl NativeContext, ty: &Type, v: &Container) ->
     }
 }
 
+fn vector_error(sub_status: u64) -> VMStatus {
+    VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR).with_sub_status(sub_status)
+}
+
 impl VectorRef {
     pub fn len(&self, type_param: &Type, context: &impl NativeContext) -> VMResult<Value> {
         let v = self.0.borrow();
@@ -1663,6 +1756,7 @@ impl VectorRef {
             Container::U8(v) => v.len(),
             Container::U64(v) => v.len(),
             Container::U128(v) => v.len(),
+            Container::U256(v) => v.len(),
             Container::Bool(v) => v.len(),
             Container::Address(v) => v.len(),
             Container::Resource(v) | Container::General(v) => v.len(),
@@ -1683,6 +1777,7 @@ impl VectorRef {
             Container::U8(v) => v.push(e.value_as()?),
             Container::U64(v) => v.push(e.value_as()?),
             Container::U128(v) => v.push(e.value_as()?),
+            Container::U256(v) => v.push(e.value_as()?),
             Container::Bool(v) => v.push(e.value_as()?),
             Container::Address(v) => v.push(e.value_as()?),
             Container::Resource(v) | Container::General(v) => v.push(e.0),
@@ -1690,96 +1785,53 @@ impl VectorRef {
         Ok(())
     }
 
+    /// Fails with a `NATIVE_FUNCTION_ERROR` if `idx` is out of bounds
     pub fn borrow_elem(
         &self,
         idx: usize,
-        cost: GasUnits<GasCarrier>,
         type_param: &Type,
         context: &impl NativeContext,
-    ) -> VMResult<NativeResult> {
+    ) -> VMResult<Value> {
         let v = self.0.borrow();
         check_elem_layout(context, type_param, &*v)?;
         if idx >= v.len() {
-            return Ok(NativeResult::err(
-                cost,
-                VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
-                    .with_sub_status(INDEX_OUT_OF_BOUNDS),
-            ));
+            return Err(vector_error(INDEX_OUT_OF_BOUNDS));
         }
-        Ok(NativeResult::ok(
-            cost,
-            vec![Value(self.0.borrow_elem(idx)?)],
-        ))
+        Ok(Value(self.0.borrow_elem(idx)?))
     }
 
-    pub fn pop(
-        &self,
-        cost: GasUnits<GasCarrier>,
-        type_param: &Type,
-        context: &impl NativeContext,
-    ) -> VMResult<NativeResult> {
+    /// Fails with a `NATIVE_FUNCTION_ERROR` if the vector is empty
+    pub fn pop(&self, type_param: &Type, context: &impl NativeContext) -> VMResult<Value> {
         let mut v = self.0.borrow_mut();
         check_elem_layout(context, type_param, &*v)?;
 
-        macro_rules! err_pop_empty_vec {
-            () => {
-                return Ok(NativeResult::err(
-                    cost,
-                    VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR).with_sub_status(POP_EMPTY_VEC),
-                ));
-            };
-        }
-
         let res = match &mut *v {
-            Container::U8(v) => match v.pop() {
-                Some(x) => Value::u8(x),
-                None => err_pop_empty_vec!(),
-            },
-            Container::U64(v) => match v.pop() {
-                Some(x) => Value::u64(x),
-                None => err_pop_empty_vec!(),
-            },
-            Container::U128(v) => match v.pop() {
-                Some(x) => Value::u128(x),
-                None => err_pop_empty_vec!(),
-            },
-            Container::Bool(v) => match v.pop() {
-                Some(x) => Value::bool(x),
-                None => err_pop_empty_vec!(),
-            },
-            Container::Address(v) => match v.pop() {
-                Some(x) => Value::address(x),
-                None => err_pop_empty_vec!(),
-            },
-
-            Container::Resource(v) | Container::General(v) => match v.pop() {
-                Some(x) => Value(x),
-                None => err_pop_empty_vec!(),
-            },
+            Container::U8(v) => v.pop().map(Value::u8),
+            Container::U64(v) => v.pop().map(Value::u64),
+            Container::U128(v) => v.pop().map(Value::u128),
+            Container::U256(v) => v.pop().map(Value::u256),
+            Container::Bool(v) => v.pop().map(Value::bool),
+            Container::Address(v) => v.pop().map(Value::address),
+            Container::Resource(v) | Container::General(v) => v.pop().map(Value),
         };
-
-        Ok(NativeResult::ok(cost, vec![res]))
+        res.ok_or_else(|| vector_error(POP_EMPTY_VEC))
     }
 
+    /// Fails with a `NATIVE_FUNCTION_ERROR` if either index is out of bounds
     pub fn swap(
         &self,
         idx1: usize,
         idx2: usize,
-        cost: GasUnits<GasCarrier>,
         type_param: &Type,
         context: &impl NativeContext,
-    ) -> VMResult<NativeResult> {
+    ) -> VMResult<()> {
         let mut v = self.0.borrow_mut();
         check_elem_layout(context, type_param, &*v)?;
 
         macro_rules! swap {
             ($v: ident) => {{
                 if idx1 >= $v.len() || idx2 >= $v.len() {
-                    return Ok(NativeResult::err(
-                        cost,
-                        VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
-                            .with_sub_status(INDEX_OUT_OF_BOUNDS),
-                    ));
+                    return Err(vector_error(INDEX_OUT_OF_BOUNDS));
                 }
                 $v.swap(idx1, idx2);
             }};
@@ -1789,35 +1841,53 @@ impl VectorRef {
             Container::U8(v) => swap!(v),
             Container::U64(v) => swap!(v),
             Container::U128(v) => swap!(v),
+            Container::U256(v) => swap!(v),
             Container::Bool(v) => swap!(v),
             Container::Address(v) => swap!(v),
             Container::Resource(v) | Container::General(v) => swap!(v),
         }
-
-        Ok(NativeResult::ok(cost, vec![]))
+        Ok(())
     }
 }
 
 impl Vector {
-    pub fn empty(
-        cost: GasUnits<GasCarrier>,
+    pub fn empty(type_param: &Type, context: &impl NativeContext) -> VMResult<Value> {
+        Self::pack(type_param, vec![], context)
+    }
+
+    /// Packs `elements`, all of type `type_param`, into a vector
+    pub fn pack(
         type_param: &Type,
+        elements: Vec<Value>,
         context: &impl NativeContext,
-    ) -> VMResult<NativeResult> {
-        let container = match type_param {
-            Type::U8 => Value::vector_u8(iter::empty::<u8>()),
-            Type::U64 => Value::vector_u64(iter::empty::<u64>()),
-            Type::U128 => Value::vector_u128(iter::empty::<u128>()),
-            Type::Bool => Value::vector_bool(iter::empty::<bool>()),
-            Type::Address => Value::vector_address(iter::empty::<AccountAddress>()),
+    ) -> VMResult<Value> {
+        macro_rules! collect {
+            ($ty: ty) => {
+                elements
+                    .into_iter()
+                    .map(|e| e.value_as::<$ty>())
+                    .collect::<VMResult<Vec<_>>>()?
+            };
+        }
 
-            Type::Signer => Value(ValueImpl::new_container(Container::Resource(vec![]))),
+        let container = match type_param {
+            Type::U8 => Value::vector_u8(collect!(u8)),
+            Type::U64 => Value::vector_u64(collect!(u64)),
+            Type::U128 => Value::vector_u128(collect!(u128)),
+            Type::U256 => Value::vector_u256(collect!(U256)),
+            Type::Bool => Value::vector_bool(collect!(bool)),
+            Type::Address => Value::vector_address(collect!(AccountAddress)),
+
+            Type::Signer => Value(ValueImpl::new_container(Container::Resource(
+                elements.into_iter().map(|e| e.0).collect(),
+            ))),
 
             Type::Vector(_) | Type::Struct(_) | Type::StructInstantiation(_, _) => {
+                let elements = elements.into_iter().map(|e| e.0).collect();
                 if context.is_resource(type_param)? {
-                    Value(ValueImpl::new_container(Container::Resource(vec![])))
+                    Value(ValueImpl::new_container(Container::Resource(elements)))
                 } else {
-                    Value(ValueImpl::new_container(Container::General(vec![])))
+                    Value(ValueImpl::new_container(Container::General(elements)))
                 }
             }
 
@@ -1827,37 +1897,42 @@ impl Vector {
             }
         };
 
-        Ok(NativeResult::ok(cost, vec![container]))
+        Ok(container)
+    }
+
+    pub fn destroy_empty(self, type_param: &Type, context: &impl NativeContext) -> VMResult<()> {
+        self.unpack(type_param, 0, context).map(|_| ())
     }
 
-    pub fn destroy_empty(
+    /// Unpacks the elements of a vector that must hold exactly `expected_num` of them, or fails
+    /// with a `NATIVE_FUNCTION_ERROR`
+    pub fn unpack(
         self,
-        cost: GasUnits<GasCarrier>,
         type_param: &Type,
+        expected_num: u64,
         context: &impl NativeContext,
-    ) -> VMResult<NativeResult> {
-        let v = self.0.borrow();
+    ) -> VMResult<Vec<Value>> {
+        let mut v = self.0.borrow_mut();
         check_elem_layout(context, type_param, &*v)?;
 
-        let is_empty = match &*v {
-            Container::U8(v) => v.is_empty(),
-            Container::U64(v) => v.is_empty(),
-            Container::U128(v) => v.is_empty(),
-            Container::Bool(v) => v.is_empty(),
-            Container::Address(v) => v.is_empty(),
-
-            Container::Resource(v) | Container::General(v) => v.is_empty(),
+        if v.len() as u64 != expected_num {
+            return Err(vector_error(if expected_num == 0 {
+                DESTROY_NON_EMPTY_VEC
+            } else {
+                UNPACK_LENGTH_MISMATCH
+            }));
+        }
+
+        let elements = match &mut *v {
+            Container::U8(v) => v.drain(..).map(Value::u8).collect(),
+            Container::U64(v) => v.drain(..).map(Value::u64).collect(),
+            Container::U128(v) => v.drain(..).map(Value::u128).collect(),
+            Container::U256(v) => v.drain(..).map(Value::u256).collect(),
+            Container::Bool(v) => v.drain(..).map(Value::bool).collect(),
+            Container::Address(v) => v.drain(..).map(Value::address).collect(),
+            Container::Resource(v) | Container::General(v) => v.drain(..).map(Value).collect(),
         };
-
-        if is_empty {
-            Ok(NativeResult::ok(cost, vec![]))
-        } else {
-            Ok(NativeResult::err(
-                cost,
-                VMStatus::new(StatusCode::NATIVE_FUNCTION_ERROR)
-                    .with_sub_status(DESTROY_NON_EMPTY_VEC),
-            ))
-        }
+        Ok(elements)
     }
 }
 
@@ -1878,6 +1953,7 @@ impl Container {
             Self::U8(v) => AbstractMemorySize::new((v.len() * size_of::<u8>()) as u64),
             Self::U64(v) => AbstractMemorySize::new((v.len() * size_of::<u64>()) as u64),
             Self::U128(v) => AbstractMemorySize::new((v.len() * size_of::<u128>()) as u64),
+            Self::U256(v) => AbstractMemorySize::new((v.len() * size_of::<U256>()) as u64),
             Self::Bool(v) => AbstractMemorySize::new((v.len() * size_of::<bool>()) as u64),
             Self::Address(v) => {
                 AbstractMemorySize::new((v.len() * size_of::<AccountAddress>()) as u64)
@@ -1903,7 +1979,7 @@ impl ValueImpl {
         use ValueImpl::*;
 
         match self {
-            Invalid | U8(_) | U64(_) | U128(_) | Bool(_) => CONST_SIZE,
+            Invalid | U8(_) | U64(_) | U128(_) | U256(_) | Bool(_) => CONST_SIZE,
             Address(_) => AbstractMemorySize::new(AccountAddress::LENGTH as u64),
             ContainerRef(r) => r.size(),
             IndexedRef(r) => r.size(),
@@ -1970,6 +2046,7 @@ impl Struct {
             Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Bool(_)
             | Container::Address(_) => {
                 Err(VMStatus::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
@@ -2051,6 +2128,7 @@ impl Display for ValueImpl {
             Self::U8(x) => write!(f, "U8({})", x),
             Self::U64(x) => write!(f, "U64({})", x),
             Self::U128(x) => write!(f, "U128({})", x),
+            Self::U256(x) => write!(f, "U256({})", x),
             Self::Bool(x) => write!(f, "{}", x),
             Self::Address(addr) => write!(f, "Address({})", addr.short_str()),
 
@@ -2107,6 +2185,7 @@ impl Display for Container {
             Self::U8(v) => display_list_of_items(v, f),
             Self::U64(v) => display_list_of_items(v, f),
             Self::U128(v) => display_list_of_items(v, f),
+            Self::U256(v) => display_list_of_items(v, f),
             Self::Bool(v) => display_list_of_items(v, f),
             Self::Address(v) => display_list_of_items(v, f),
         }
@@ -2137,6 +2216,7 @@ impl Display for Locals {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Bool(_)
             | Container::Address(_) => unreachable!(),
         }
@@ -2153,6 +2233,7 @@ pub mod debug {
             (FatType::U8, ValueImpl::U8(x)) => debug_write!(buf, "{}u8", x),
             (FatType::U64, ValueImpl::U64(x)) => debug_write!(buf, "{}u64", x),
             (FatType::U128, ValueImpl::U128(x)) => debug_write!(buf, "{}u128", x),
+            (FatType::U256, ValueImpl::U256(x)) => debug_write!(buf, "{}u256", x),
             (FatType::Bool, ValueImpl::Bool(x)) => debug_write!(buf, "{}", x),
             (FatType::Address, ValueImpl::Address(x)) => debug_write!(buf, "{}", x),
 
@@ -2209,6 +2290,7 @@ pub mod debug {
             (FatType::U8, Container::U8(v)) => print_vector!(v, "u8"),
             (FatType::U64, Container::U64(v)) => print_vector!(v, "u64"),
             (FatType::U128, Container::U128(v)) => print_vector!(v, "u128"),
+            (FatType::U256, Container::U256(v)) => print_vector!(v, "u256"),
             (FatType::Bool, Container::Bool(v)) => print_vector!(v, ""),
             (FatType::Address, Container::Address(v)) => print_vector!(v, ""),
 
@@ -2305,16 +2387,19 @@ pub mod debug {
             (FatType::U8, Container::U8(v)) => print_vector_elem!(v, idx, "u8"),
             (FatType::U64, Container::U64(v)) => print_vector_elem!(v, idx, "u64"),
             (FatType::U128, Container::U128(v)) => print_vector_elem!(v, idx, "u128"),
+            (FatType::U256, Container::U256(v)) => print_vector_elem!(v, idx, "u256"),
             (FatType::Bool, Container::Bool(v)) => print_vector_elem!(v, idx, ""),
 
             (FatType::U8, Container::Resource(v))
             | (FatType::U64, Container::Resource(v))
             | (FatType::U128, Container::Resource(v))
+            | (FatType::U256, Container::Resource(v))
             | (FatType::Bool, Container::Resource(v))
             | (FatType::Address, Container::Resource(v))
             | (FatType::U8, Container::General(v))
             | (FatType::U64, Container::General(v))
             | (FatType::U128, Container::General(v))
+            | (FatType::U256, Container::General(v))
             | (FatType::Bool, Container::General(v))
             | (FatType::Address, Container::General(v)) => match v.get(idx) {
                 Some(val) => print_value_impl(buf, val_ty, val),
@@ -2359,6 +2444,7 @@ pub mod debug {
             | Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Bool(_)
             | Container::Address(_) => unreachable!(),
         }
@@ -2430,6 +2516,7 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, FatType, ValueImpl> {
             (FatType::U8, ValueImpl::U8(x)) => serializer.serialize_u8(*x),
             (FatType::U64, ValueImpl::U64(x)) => serializer.serialize_u64(*x),
             (FatType::U128, ValueImpl::U128(x)) => serializer.serialize_u128(*x),
+            (FatType::U256, ValueImpl::U256(x)) => x.serialize(serializer),
             (FatType::Bool, ValueImpl::Bool(x)) => serializer.serialize_bool(*x),
             (FatType::Address, ValueImpl::Address(x)) => x.serialize(serializer),
 
@@ -2448,6 +2535,7 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, FatType, ValueImpl> {
                     (FatType::U8, Container::U8(v)) => v.serialize(serializer),
                     (FatType::U64, Container::U64(v)) => v.serialize(serializer),
                     (FatType::U128, Container::U128(v)) => v.serialize(serializer),
+                    (FatType::U256, Container::U256(v)) => v.serialize(serializer),
                     (FatType::Bool, Container::Bool(v)) => v.serialize(serializer),
                     (FatType::Address, Container::Address(v)) => v.serialize(serializer),
 
@@ -2495,6 +2583,7 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, FatStructType, Containe
             Container::U8(_)
             | Container::U64(_)
             | Container::U128(_)
+            | Container::U256(_)
             | Container::Bool(_)
             | Container::Address(_) => {
                 return Err(invariant_violation::<S>(format!(
@@ -2530,6 +2619,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &FatType {
             FatType::U8 => u8::deserialize(deserializer).map(Value::u8),
             FatType::U64 => u64::deserialize(deserializer).map(Value::u64),
             FatType::U128 => u128::deserialize(deserializer).map(Value::u128),
+            FatType::U256 => U256::deserialize(deserializer).map(Value::u256),
             FatType::Address => AccountAddress::deserialize(deserializer).map(Value::address),
             FatType::Signer => AccountAddress::deserialize(deserializer).map(Value::signer),
 
@@ -2540,6 +2630,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &FatType {
                     FatType::U8 => Container::U8(Vec::deserialize(deserializer)?),
                     FatType::U64 => Container::U64(Vec::deserialize(deserializer)?),
                     FatType::U128 => Container::U128(Vec::deserialize(deserializer)?),
+                    FatType::U256 => Container::U256(Vec::deserialize(deserializer)?),
                     FatType::Bool => Container::Bool(Vec::deserialize(deserializer)?),
                     FatType::Address => Container::Address(Vec::deserialize(deserializer)?),
                     layout => {
@@ -2642,6 +2733,7 @@ impl Value {
             S::U8 => T::U8,
             S::U64 => T::U64,
             S::U128 => T::U128,
+            S::U256 => T::U256,
             S::Address => T::Address,
             S::Signer => T::Signer,
             S::Vector(inner) => T::Vector(Box::new(Self::constant_sig_token_to_type(inner)?)),
@@ -2676,6 +2768,7 @@ pub mod prop {
             FatType::U8 => any::<u8>().prop_map(Value::u8).boxed(),
             FatType::U64 => any::<u64>().prop_map(Value::u64).boxed(),
             FatType::U128 => any::<u128>().prop_map(Value::u128).boxed(),
+            FatType::U256 => any::<U256>().prop_map(Value::u256).boxed(),
             FatType::Bool => any::<bool>().prop_map(Value::bool).boxed(),
             FatType::Address => any::<AccountAddress>().prop_map(Value::address).boxed(),
             FatType::Signer => any::<AccountAddress>().prop_map(Value::signer).boxed(),
@@ -2690,6 +2783,9 @@ pub mod prop {
                 FatType::U128 => vec(any::<u128>(), 0..10)
                     .prop_map(|vals| Value(ValueImpl::new_container(Container::U128(vals))))
                     .boxed(),
+                FatType::U256 => vec(any::<U256>(), 0..10)
+                    .prop_map(|vals| Value(ValueImpl::new_container(Container::U256(vals))))
+                    .boxed(),
                 FatType::Bool => vec(any::<bool>(), 0..10)
                     .prop_map(|vals| Value(ValueImpl::new_container(Container::Bool(vals))))
                     .boxed(),
@@ -2749,6 +2845,7 @@ pub mod prop {
                 (FatType::U8, ValueImpl::U8(x)) => MoveValue::U8(*x),
                 (FatType::U64, ValueImpl::U64(x)) => MoveValue::U64(*x),
                 (FatType::U128, ValueImpl::U128(x)) => MoveValue::U128(*x),
+                (FatType::U256, ValueImpl::U256(x)) => MoveValue::U256(*x),
                 (FatType::Bool, ValueImpl::Bool(x)) => MoveValue::Bool(*x),
                 (FatType::Address, ValueImpl::Address(x)) => MoveValue::Address(*x),
 
@@ -2771,6 +2868,7 @@ pub mod prop {
                         Container::U8(v) => v.iter().map(|u| MoveValue::U8(*u)).collect(),
                         Container::U64(v) => v.iter().map(|u| MoveValue::U64(*u)).collect(),
                         Container::U128(v) => v.iter().map(|u| MoveValue::U128(*u)).collect(),
+                        Container::U256(v) => v.iter().map(|u| MoveValue::U256(*u)).collect(),
                         Container::Bool(b) => b.iter().map(|u| MoveValue::Bool(*u)).collect(),
                         Container::Address(v) => v.iter().map(|u| MoveValue::Address(*u)).collect(),
                         Container::Resource(v) | Container::General(v) => v
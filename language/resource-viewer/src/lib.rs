@@ -2,17 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{cached_access_path_table::resource_vec_to_type_tag, resolver::Resolver};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use libra_state_view::StateView;
 use libra_types::{
     access_path::AccessPath, account_address::AccountAddress, account_state::AccountState,
-    contract_event::ContractEvent,
+    chunked_resource, contract_event::ContractEvent,
 };
 use move_core_types::{
     identifier::Identifier,
-    language_storage::StructTag,
+    language_storage::{StructTag, TypeTag},
+    u256::U256,
     value::{MoveStruct, MoveValue},
 };
+use serde::ser::{Serialize, SerializeMap, SerializeStruct, Serializer};
+use serde_json::Value as JsonValue;
 use std::{
     collections::btree_map::BTreeMap,
     convert::TryInto,
@@ -37,14 +40,16 @@ pub struct AnnotatedMoveStruct {
 }
 
 /// AnnotatedMoveValue is a fully expanded version of on chain move data. This should only be used
-/// for debugging/client purpose right now and just for a better visualization of on chain data. In
-/// the long run, we would like to transform this struct to a Json value so that we can have a cross
-/// platform interpretation of the on chain data.
+/// for debugging/client purpose right now and just for a better visualization of on chain data.
+/// It serializes to a JSON tree for a cross platform interpretation of the on chain data: structs
+/// are objects with their type and their fields by name, `u128` and `u256` integers are decimal
+/// strings, and addresses and byte vectors are hex strings.
 #[derive(Debug)]
 pub enum AnnotatedMoveValue {
     U8(u8),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
     Vector(Vec<AnnotatedMoveValue>),
@@ -77,14 +82,32 @@ impl<'a> MoveValueAnnotator<'a> {
     }
 
     pub fn view_contract_event(&self, event: &ContractEvent) -> Result<AnnotatedMoveValue> {
-        let ty = self.cache.resolve_type(event.type_tag())?;
+        self.view_value(event.type_tag(), event.event_data())
+    }
+
+    /// Decode an LCS blob holding a value of type `type_tag`.
+    pub fn view_value(&self, type_tag: &TypeTag, blob: &[u8]) -> Result<AnnotatedMoveValue> {
+        let ty = self.cache.resolve_type(type_tag)?;
         let move_ty = (&ty).try_into()?;
-        let move_value = MoveValue::simple_deserialize(event.event_data(), &move_ty)?;
+        let move_value = MoveValue::simple_deserialize(blob, &move_ty)?;
         self.annotate_value(&move_value, &ty)
     }
 
+    /// Build the LCS encoding of a value of type `type_tag` from its JSON representation.
+    ///
+    /// Structs are JSON objects keyed by field name, addresses and `vector<u8>` are hex strings
+    /// and integers may be given either as numbers or as decimal strings (needed for `u128`).
+    pub fn encode_json_value(&self, type_tag: &TypeTag, json: &JsonValue) -> Result<Vec<u8>> {
+        let ty = self.cache.resolve_type(type_tag)?;
+        let value = self.json_to_value(json, &ty)?;
+        value
+            .simple_serialize()
+            .ok_or_else(|| anyhow!("Failed to serialize value {:?}", value))
+    }
+
     pub fn view_account_state(&self, state: &AccountState) -> Result<AnnotatedAccountStateBlob> {
         let mut output = BTreeMap::new();
+        let state = chunked_resource::reassemble_account_state(state)?;
         for (k, v) in state.iter() {
             let ty = resource_vec_to_type_tag(k.as_slice())?;
             let struct_def = (&ty).try_into()?;
@@ -121,6 +144,7 @@ impl<'a> MoveValueAnnotator<'a> {
             (MoveValue::U8(i), FatType::U8) => AnnotatedMoveValue::U8(*i),
             (MoveValue::U64(i), FatType::U64) => AnnotatedMoveValue::U64(*i),
             (MoveValue::U128(i), FatType::U128) => AnnotatedMoveValue::U128(*i),
+            (MoveValue::U256(i), FatType::U256) => AnnotatedMoveValue::U256(*i),
             (MoveValue::Address(a), FatType::Address) => AnnotatedMoveValue::Address(*a),
             (MoveValue::Vector(a), FatType::Vector(ty)) => match ty.as_ref() {
                 FatType::U8 => AnnotatedMoveValue::Bytes(
@@ -149,6 +173,140 @@ impl<'a> MoveValueAnnotator<'a> {
             }
         })
     }
+
+    fn json_to_value(&self, json: &JsonValue, ty: &FatType) -> Result<MoveValue> {
+        Ok(match ty {
+            FatType::Bool => MoveValue::Bool(
+                json.as_bool()
+                    .ok_or_else(|| anyhow!("Expected a bool, found {}", json))?,
+            ),
+            FatType::U8 => MoveValue::U8(json_to_u128(json)?.try_into()?),
+            FatType::U64 => MoveValue::U64(json_to_u128(json)?.try_into()?),
+            FatType::U128 => MoveValue::U128(json_to_u128(json)?),
+            FatType::U256 => MoveValue::U256(json_to_u256(json)?),
+            FatType::Address => MoveValue::Address(AccountAddress::from_hex_literal(
+                &normalize_hex(json_to_str(json)?),
+            )?),
+            FatType::Vector(inner) => match (inner.as_ref(), json) {
+                (FatType::U8, JsonValue::String(s)) => {
+                    MoveValue::vector_u8(hex::decode(s.trim_start_matches("0x"))?)
+                }
+                (_, JsonValue::Array(values)) => MoveValue::Vector(
+                    values
+                        .iter()
+                        .map(|v| self.json_to_value(v, inner))
+                        .collect::<Result<_>>()?,
+                ),
+                _ => bail!("Expected an array, found {}", json),
+            },
+            FatType::Struct(struct_ty) => {
+                let fields = json
+                    .as_object()
+                    .ok_or_else(|| anyhow!("Expected an object, found {}", json))?;
+                let field_names = self.cache.get_field_names(struct_ty)?;
+                ensure!(
+                    fields.len() == field_names.len(),
+                    "Expected {} fields for {}, found {}",
+                    field_names.len(),
+                    struct_ty.struct_tag()?,
+                    fields.len()
+                );
+                let mut values = vec![];
+                for (name, ty) in field_names.iter().zip(struct_ty.layout.iter()) {
+                    let field = fields
+                        .get(name.as_str())
+                        .ok_or_else(|| anyhow!("Missing field {}", name))?;
+                    values.push(self.json_to_value(field, ty)?);
+                }
+                MoveValue::Struct(MoveStruct::new(values))
+            }
+            FatType::Signer
+            | FatType::Reference(_)
+            | FatType::MutableReference(_)
+            | FatType::TyParam(_) => bail!("Cannot construct a value of type {:?}", ty),
+        })
+    }
+}
+
+fn json_to_str(json: &JsonValue) -> Result<&str> {
+    json.as_str()
+        .ok_or_else(|| anyhow!("Expected a string, found {}", json))
+}
+
+fn json_to_u128(json: &JsonValue) -> Result<u128> {
+    match json {
+        JsonValue::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| anyhow!("Expected an unsigned integer, found {}", n)),
+        JsonValue::String(s) => Ok(s.parse()?),
+        _ => bail!("Expected an unsigned integer, found {}", json),
+    }
+}
+
+fn json_to_u256(json: &JsonValue) -> Result<U256> {
+    match json {
+        JsonValue::Number(n) => n
+            .as_u64()
+            .map(U256::from)
+            .ok_or_else(|| anyhow!("Expected an unsigned integer, found {}", n)),
+        JsonValue::String(s) => Ok(s.parse()?),
+        _ => bail!("Expected an unsigned integer, found {}", json),
+    }
+}
+
+fn normalize_hex(s: &str) -> String {
+    if s.starts_with("0x") {
+        s.to_string()
+    } else {
+        format!("0x{}", s)
+    }
+}
+
+impl Serialize for AnnotatedMoveValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            AnnotatedMoveValue::U8(v) => serializer.serialize_u8(*v),
+            AnnotatedMoveValue::U64(v) => serializer.serialize_u64(*v),
+            // JSON numbers can't hold these exactly
+            AnnotatedMoveValue::U128(v) => serializer.collect_str(v),
+            AnnotatedMoveValue::U256(v) => serializer.collect_str(v),
+            AnnotatedMoveValue::Bool(b) => serializer.serialize_bool(*b),
+            AnnotatedMoveValue::Address(a) => serializer.collect_str(a),
+            AnnotatedMoveValue::Vector(v) => serializer.collect_seq(v),
+            AnnotatedMoveValue::Bytes(v) => serializer.serialize_str(&hex::encode(v)),
+            AnnotatedMoveValue::Struct(s) => s.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for AnnotatedMoveStruct {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("AnnotatedMoveStruct", 3)?;
+        s.serialize_field("type", &self.type_.to_string())?;
+        s.serialize_field("is_resource", &self.is_resource)?;
+        s.serialize_field("fields", &AnnotatedFields(&self.value))?;
+        s.end()
+    }
+}
+
+/// The fields of a struct, as a map in declaration order
+struct AnnotatedFields<'a>(&'a [(Identifier, AnnotatedMoveValue)]);
+
+impl<'a> Serialize for AnnotatedFields<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in self.0 {
+            map.serialize_entry(name.as_str(), value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for AnnotatedAccountStateBlob {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.values())
+    }
 }
 
 fn write_indent(f: &mut Formatter, indent: u64) -> std::fmt::Result {
@@ -168,6 +326,7 @@ fn pretty_print_value(
         AnnotatedMoveValue::U8(v) => write!(f, "{}u8", v),
         AnnotatedMoveValue::U64(v) => write!(f, "{}", v),
         AnnotatedMoveValue::U128(v) => write!(f, "{}u128", v),
+        AnnotatedMoveValue::U256(v) => write!(f, "{}u256", v),
         AnnotatedMoveValue::Address(a) => write!(f, "{}", a.short_str()),
         AnnotatedMoveValue::Vector(v) => {
             writeln!(f, "[")?;
@@ -244,3 +403,58 @@ impl StateView for NullStateView {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::parser::parse_type_tag;
+
+    #[test]
+    fn test_json_lcs_round_trip() {
+        let view = NullStateView::default();
+        let annotator = MoveValueAnnotator::new(&view);
+        let type_tag = parse_type_tag("0x1::Libra::Libra<0x1::LBR::LBR>").unwrap();
+
+        let blob = annotator
+            .encode_json_value(&type_tag, &serde_json::json!({ "value": "10" }))
+            .unwrap();
+        assert_eq!(blob, lcs::to_bytes(&10u64).unwrap());
+        let value = annotator.view_value(&type_tag, &blob).unwrap();
+        assert!(value.to_string().contains("value: 10"));
+
+        assert!(annotator
+            .encode_json_value(&type_tag, &serde_json::json!({ "value": -1 }))
+            .is_err());
+        assert!(annotator
+            .encode_json_value(&type_tag, &serde_json::json!({ "amount": 10 }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_generic_struct_json_tree() {
+        let view = NullStateView::default();
+        let annotator = MoveValueAnnotator::new(&view);
+        let type_tag =
+            parse_type_tag("0x1::Option::Option<0x1::Libra::Libra<0x1::LBR::LBR>>").unwrap();
+        let coin_type_tag = parse_type_tag("0x1::Libra::Libra<0x1::LBR::LBR>").unwrap();
+
+        let blob = annotator
+            .encode_json_value(&type_tag, &serde_json::json!({ "vec": [{ "value": 10 }] }))
+            .unwrap();
+        let value = annotator.view_value(&type_tag, &blob).unwrap();
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            serde_json::json!({
+                "type": type_tag.to_string(),
+                "is_resource": false,
+                "fields": {
+                    "vec": [{
+                        "type": coin_type_tag.to_string(),
+                        "is_resource": true,
+                        "fields": { "value": 10 },
+                    }],
+                },
+            })
+        );
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::module_cache::ModuleCache;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use compiled_stdlib::{stdlib_modules, StdLibOptions};
 use libra_state_view::StateView;
 use libra_types::{access_path::AccessPath, account_address::AccountAddress};
@@ -65,6 +65,7 @@ impl<'a> Resolver<'a> {
             TypeTag::U8 => FatType::U8,
             TypeTag::U64 => FatType::U64,
             TypeTag::U128 => FatType::U128,
+            TypeTag::U256 => FatType::U256,
             TypeTag::Vector(ty) => FatType::Vector(Box::new(self.resolve_type(ty)?)),
         })
     }
@@ -72,7 +73,24 @@ impl<'a> Resolver<'a> {
     pub fn resolve_struct(&self, struct_tag: &StructTag) -> Result<FatStructType> {
         let module = self.get_module(&struct_tag.address, &struct_tag.module)?;
         let struct_def = find_struct_def_in_module(module.clone(), struct_tag.name.as_ident_str())?;
-        self.resolve_struct_definition(module, struct_def)
+        let struct_ty = self.resolve_struct_definition(module, struct_def)?;
+        // Instantiate the type parameters of a generic struct, which may be structs of other
+        // modules themselves
+        let args = struct_tag
+            .type_params
+            .iter()
+            .map(|ty| self.resolve_type(ty))
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            args.len() == struct_ty.ty_args.len(),
+            "Struct {} expects {} type arguments, found {}",
+            struct_tag,
+            struct_ty.ty_args.len(),
+            args.len()
+        );
+        struct_ty
+            .subst(&args)
+            .map_err(|status| anyhow!("Substitution failure: {:?}", status))
     }
 
     pub fn get_field_names(&self, ty: &FatStructType) -> Result<Vec<Identifier>> {
@@ -99,6 +117,7 @@ impl<'a> Resolver<'a> {
             SignatureToken::U8 => FatType::U8,
             SignatureToken::U64 => FatType::U64,
             SignatureToken::U128 => FatType::U128,
+            SignatureToken::U256 => FatType::U256,
             SignatureToken::Address => FatType::Address,
             SignatureToken::Signer => FatType::Signer,
             SignatureToken::Vector(ty) => {
@@ -6,8 +6,9 @@
 pub mod transaction_scripts;
 
 use bytecode_verifier::{batch_verify_modules, VerifiedModule};
+use move_core_types::errmap::ErrorMapping;
 use once_cell::sync::Lazy;
-use stdlib::build_stdlib;
+use stdlib::{build_stdlib, build_stdlib_error_map};
 use vm::file_format::CompiledModule;
 
 pub const NO_USE_COMPILED: &str = "MOVE_NO_USE_COMPILED";
@@ -39,9 +40,17 @@ static COMPILED_MOVELANG_STDLIB: Lazy<Vec<VerifiedModule>> = Lazy::new(|| {
     batch_verify_modules(modules)
 });
 
+static FRESH_STDLIB_ERROR_MAP: Lazy<ErrorMapping> = Lazy::new(build_stdlib_error_map);
+
+/// The error map of the compiled stdlib, kept next to it for the same reasons.
+pub const COMPILED_ERROR_MAP_BYTES: &[u8] = std::include_bytes!("../error_descriptions.errmap");
+
+static COMPILED_STDLIB_ERROR_MAP: Lazy<ErrorMapping> =
+    Lazy::new(|| ErrorMapping::from_bytes(COMPILED_ERROR_MAP_BYTES).unwrap());
+
 /// An enum specifying whether the compiled stdlib/scripts should be used or freshly built versions
 /// should be used.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum StdLibOptions {
     Compiled,
     Fresh,
@@ -57,6 +66,14 @@ pub fn stdlib_modules(option: StdLibOptions) -> &'static [VerifiedModule] {
     }
 }
 
+/// Returns the error map of the standard library, compiled or freshly built depending on `option`.
+pub fn stdlib_error_map(option: StdLibOptions) -> &'static ErrorMapping {
+    match option {
+        StdLibOptions::Compiled => &*COMPILED_STDLIB_ERROR_MAP,
+        StdLibOptions::Fresh => &*FRESH_STDLIB_ERROR_MAP,
+    }
+}
+
 /// Returns a reference to the standard library built by move-lang compiler, compiled with the
 /// [default address](account_config::core_code_address).
 ///
@@ -78,3 +95,41 @@ pub fn env_stdlib_modules() -> &'static [VerifiedModule] {
 pub fn use_compiled() -> bool {
     std::env::var(NO_USE_COMPILED).is_err()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use libra_types::move_resource::stdlib_structs;
+    use vm::access::ModuleAccess;
+
+    #[test]
+    fn test_stdlib_structs() {
+        // Make sure that the Move structs that Rust types stand for are declared in the Move source
+        // of the stdlib, with the same number of type parameters.
+        let modules = stdlib_modules(StdLibOptions::Fresh);
+        for stdlib_struct in stdlib_structs() {
+            let module = modules
+                .iter()
+                .find(|module| module.name().as_str() == stdlib_struct.module)
+                .unwrap_or_else(|| panic!("Module {} is not in the stdlib", stdlib_struct.module));
+            let handle = module
+                .struct_defs()
+                .iter()
+                .map(|def| module.struct_handle_at(def.struct_handle))
+                .find(|handle| module.identifier_at(handle.name).as_str() == stdlib_struct.name)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Struct {}::{} is not in the stdlib",
+                        stdlib_struct.module, stdlib_struct.name
+                    )
+                });
+            assert_eq!(
+                handle.type_parameters.len(),
+                stdlib_struct.type_params,
+                "Struct {}::{} has a different number of type parameters in the stdlib",
+                stdlib_struct.module,
+                stdlib_struct.name,
+            );
+        }
+    }
+}
@@ -48,7 +48,10 @@ pub enum StdlibScript {
     RotateBaseUrl,
     RotateCompliancePublicKey,
     RotateSharedEd2551PublicKey,
+    RotateValidatorNetworkConfig,
+    SetModulePublishingPolicy,
     SetValidatorConfig,
+    SetValidatorNextNetworkConfig,
     TieredMint,
     UpdateTravelRuleLimit,
     UnfreezeAccount,
@@ -93,7 +96,10 @@ impl StdlibScript {
             RotateBaseUrl,
             RotateCompliancePublicKey,
             RotateSharedEd2551PublicKey,
+            RotateValidatorNetworkConfig,
+            SetModulePublishingPolicy,
             SetValidatorConfig,
+            SetValidatorNextNetworkConfig,
             TieredMint,
             UpdateTravelRuleLimit,
             UnfreezeAccount,
@@ -218,7 +224,10 @@ impl fmt::Display for StdlibScript {
                 RotateBaseUrl => "rotate_base_url",
                 RotateCompliancePublicKey => "rotate_compliance_public_key",
                 RotateSharedEd2551PublicKey => "rotate_shared_ed25519_public_key",
+                RotateValidatorNetworkConfig => "rotate_validator_network_config",
+                SetModulePublishingPolicy => "set_module_publishing_policy",
                 SetValidatorConfig => "set_validator_config",
+                SetValidatorNextNetworkConfig => "set_validator_next_network_config",
                 TieredMint => "tiered_mint",
                 UpdateTravelRuleLimit => "update_travel_rule_limit",
                 UnfreezeAccount => "unfreeze_account",
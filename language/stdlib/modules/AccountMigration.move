@@ -0,0 +1,88 @@
+address 0x1 {
+
+/**
+The AccountMigration module versions the layout of the resources published under accounts, so
+that a release of the framework can change it.
+Every account carries the version of the layout of its resources. A release that changes a layout
+bumps `latest_version` and adds the step migrating the accounts from the previous version to
+`migrate_step`. The VM migrates the sender of every transaction before running it, so accounts are
+migrated lazily, the first time they send a transaction after the release, and a `MigrationEvent`
+records every step.
+*/
+module AccountMigration {
+    use 0x1::Event::{Self, EventHandle};
+    use 0x1::Signer;
+
+    resource struct AccountVersion {
+        // The version of the layout of the resources of the account
+        version: u64,
+        migration_events: EventHandle<MigrationEvent>,
+    }
+
+    // Emitted when an account is migrated from a version to the next one
+    struct MigrationEvent {
+        from_version: u64,
+        to_version: u64,
+    }
+
+    // The version of the layout of account resources in this release. Accounts created before the
+    // versioning, without an `AccountVersion`, are at version 0.
+    public fun latest_version(): u64 {
+        1
+    }
+
+    public fun version_of(addr: address): u64 acquires AccountVersion {
+        if (exists<AccountVersion>(addr)) {
+            borrow_global<AccountVersion>(addr).version
+        } else {
+            0
+        }
+    }
+
+    // Publish the version of a new account, whose resources have the latest layout.
+    public fun publish(account: &signer) {
+        move_to(
+            account,
+            AccountVersion {
+                version: latest_version(),
+                migration_events: Event::new_event_handle<MigrationEvent>(account),
+            }
+        );
+    }
+
+    // Bring the resources of `account` to the latest layout, one version at a time. The VM calls
+    // this for the sender of every transaction, before running it.
+    fun migrate(account: &signer) acquires AccountVersion {
+        let addr = Signer::address_of(account);
+        if (!exists<AccountVersion>(addr)) {
+            move_to(
+                account,
+                AccountVersion {
+                    version: 0,
+                    migration_events: Event::new_event_handle<MigrationEvent>(account),
+                }
+            );
+        };
+        let version = borrow_global<AccountVersion>(addr).version;
+        let latest = latest_version();
+        while (version < latest) {
+            migrate_step(account, version);
+            let account_version = borrow_global_mut<AccountVersion>(addr);
+            account_version.version = version + 1;
+            Event::emit_event<MigrationEvent>(
+                &mut account_version.migration_events,
+                MigrationEvent { from_version: version, to_version: version + 1 },
+            );
+            version = version + 1;
+        }
+    }
+
+    // Migrate the resources of `account` from `from_version` to the next version.
+    fun migrate_step(_account: &signer, from_version: u64) {
+        // 0 -> 1: the accounts created before the versioning only lack their `AccountVersion`,
+        // published by `migrate`.
+        assert(from_version < latest_version(), 1);
+    }
+}
+
+}
@@ -1,13 +1,25 @@
 // Move representation of the authenticator types used in Libra:
 // - Ed25519 (single-sig)
 // - MultiEd25519 (K-of-N multisig)
+//
+// An authentication key is the sha3 hash of a public key followed by the identifier of its
+// signature scheme. Accounts opt into a new scheme by rotating to a key of that scheme, which
+// keeps their address. The schemes transactions may be signed with are the ones enabled on-chain.
 
 address 0x1 {
 module Authenticator {
+    use 0x1::CoreAddresses;
     use 0x1::Hash;
     use 0x1::LCS;
+    use 0x1::Roles::{Capability, LibraRootRole};
+    use 0x1::Signer;
     use 0x1::Vector;
 
+    // The signature schemes transactions may be signed with, by their identifiers
+    resource struct EnabledSchemes {
+        scheme_ids: vector<u8>,
+    }
+
     // A multi-ed25519 public key
     struct MultiEd25519PublicKey {
         // vector of ed25519 public keys
@@ -16,6 +28,61 @@ module Authenticator {
         threshold: u8,
     }
 
+    // Identifiers of the signature schemes, which are the last byte of the preimage of an
+    // authentication key
+    public fun ED25519_SCHEME_ID(): u8 {
+        0
+    }
+
+    public fun MULTI_ED25519_SCHEME_ID(): u8 {
+        1
+    }
+
+    // Enable the Ed25519 and MultiEd25519 schemes.
+    // This can only be invoked by the Association address, and only a single time.
+    // Currently, it is invoked in the genesis transaction
+    public fun initialize(account: &signer) {
+        // Operational constraint, only callable by the Association address
+        assert(Signer::address_of(account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 7004);
+        let scheme_ids = Vector::empty();
+        Vector::push_back(&mut scheme_ids, ED25519_SCHEME_ID());
+        Vector::push_back(&mut scheme_ids, MULTI_ED25519_SCHEME_ID());
+        move_to(account, EnabledSchemes { scheme_ids });
+    }
+
+    // Allow transactions to be signed with the scheme `scheme_id`. Schemes cannot be disabled,
+    // since that would lock the accounts which use them out.
+    public fun enable_scheme(_: &Capability<LibraRootRole>, scheme_id: u8)
+    acquires EnabledSchemes {
+        let scheme_ids = &mut borrow_global_mut<EnabledSchemes>(CoreAddresses::LIBRA_ROOT_ADDRESS()).scheme_ids;
+        if (!Vector::contains(scheme_ids, &scheme_id)) {
+            Vector::push_back(scheme_ids, scheme_id)
+        }
+    }
+
+    // Return true if transactions may be signed with the scheme `scheme_id`. Before genesis, only
+    // the Ed25519 and MultiEd25519 schemes are.
+    public fun is_scheme_enabled(scheme_id: u8): bool acquires EnabledSchemes {
+        if (!exists<EnabledSchemes>(CoreAddresses::LIBRA_ROOT_ADDRESS())) {
+            return scheme_id == ED25519_SCHEME_ID() || scheme_id == MULTI_ED25519_SCHEME_ID()
+        };
+        Vector::contains(&borrow_global<EnabledSchemes>(CoreAddresses::LIBRA_ROOT_ADDRESS()).scheme_ids, &scheme_id)
+    }
+
+    // Return the identifier of the scheme of the authentication key preimage `preimage`
+    // Aborts if `preimage` is empty
+    public fun scheme_id(preimage: &vector<u8>): u8 {
+        let len = Vector::length(preimage);
+        assert(len > 0, 7005);
+        *Vector::borrow(preimage, len - 1)
+    }
+
+    // Compute an authentication key for the public key `public_key` of the scheme `scheme_id`
+    public fun authentication_key(public_key: vector<u8>, scheme_id: u8): vector<u8> {
+        Vector::push_back(&mut public_key, scheme_id);
+        Hash::sha3_256(public_key)
+    }
+
     // Create a a multisig policy from a vector of ed25519 public keys and a threshold.
     // Note: this does *not* check uniqueness of keys. Repeated keys are convenient to
     // encode weighted multisig policies. For example Alice AND 1 of Bob or Carol is
@@ -38,9 +105,7 @@ module Authenticator {
 
     // Compute an authentication key for the ed25519 public key `public_key`
     public fun ed25519_authentication_key(public_key: vector<u8>): vector<u8> {
-        // TODO: add constant ED25519_SCHEME_ID = 0u8
-        Vector::push_back(&mut public_key, 0u8);
-        Hash::sha3_256(public_key)
+        authentication_key(public_key, ED25519_SCHEME_ID())
     }
 
     // Compute a multied25519 account authentication key for the policy `k`
@@ -58,9 +123,7 @@ module Authenticator {
             i = i + 1;
         };
         Vector::append(&mut authentication_key_preimage, LCS::to_bytes(&k.threshold));
-        // TODO: add constant MULTI_ED25519_SCHEME_ID = 1u8
-        Vector::push_back(&mut authentication_key_preimage, 1u8);
-        Hash::sha3_256(authentication_key_preimage)
+        authentication_key(authentication_key_preimage, MULTI_ED25519_SCHEME_ID())
     }
 
     // Return the public keys involved in the multisig policy `k`
@@ -0,0 +1,83 @@
+address 0x1 {
+
+module BlockCongestion {
+    use 0x1::CoreAddresses;
+    use 0x1::Roles::{Capability, LibraRootRole};
+    use 0x1::Signer;
+
+    // Tracks how full the recent blocks were, which the VM uses to raise the minimum gas price
+    // when the network is congested.
+    resource struct BlockCongestion {
+      // Number of user transactions a block can hold
+      block_capacity: u64,
+      // Number of user transactions executed in the current block so far
+      current_block_transactions: u64,
+      // Moving average of the fullness of the recent blocks, in percent of the capacity
+      recent_fullness: u64,
+    }
+
+    // Weight of the previous average when a block ends, out of FULLNESS_WEIGHT_DENOMINATOR: each
+    // block moves the average an eighth of the way to its own fullness.
+    fun FULLNESS_WEIGHT(): u64 { 7 }
+    fun FULLNESS_WEIGHT_DENOMINATOR(): u64 { 8 }
+
+    // This can only be invoked by the Association address, and only a single time.
+    // Currently, it is invoked in the genesis transaction
+    public fun initialize(account: &signer, block_capacity: u64) {
+      // Operational constraint, only callable by the Association address
+      assert(Signer::address_of(account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 1);
+      assert(block_capacity > 0, 2);
+
+      move_to<BlockCongestion>(
+          account,
+          BlockCongestion {
+              block_capacity,
+              current_block_transactions: 0,
+              recent_fullness: 0,
+          }
+      );
+    }
+
+    public fun set_block_capacity(
+        _: &Capability<LibraRootRole>,
+        block_capacity: u64
+    ) acquires BlockCongestion {
+      assert(block_capacity > 0, 2);
+      borrow_global_mut<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS()).block_capacity = block_capacity;
+    }
+
+    // Count a user transaction in the current block. Called by the transaction epilogue; a
+    // transaction calling it directly only raises the gas price it pays itself.
+    public fun record_transaction() acquires BlockCongestion {
+      if (!exists<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS())) return;
+      let congestion = borrow_global_mut<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS());
+      congestion.current_block_transactions = congestion.current_block_transactions + 1;
+    }
+
+    // Fold the fullness of the block that just ended into the moving average.
+    // The runtime runs this from the block prologue, before executing the transactions of a block.
+    public fun end_block(vm: &signer) acquires BlockCongestion {
+      // Can only be invoked by LibraVM privilege.
+      assert(Signer::address_of(vm) == CoreAddresses::VM_RESERVED_ADDRESS(), 33);
+      if (!exists<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS())) return;
+
+      let congestion = borrow_global_mut<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS());
+      let fullness = if (congestion.current_block_transactions >= congestion.block_capacity) {
+        100
+      } else {
+        congestion.current_block_transactions * 100 / congestion.block_capacity
+      };
+      congestion.recent_fullness =
+        (congestion.recent_fullness * FULLNESS_WEIGHT()
+          + fullness * (FULLNESS_WEIGHT_DENOMINATOR() - FULLNESS_WEIGHT()))
+        / FULLNESS_WEIGHT_DENOMINATOR();
+      congestion.current_block_transactions = 0;
+    }
+
+    // Get the moving average of the fullness of the recent blocks, in percent
+    public fun recent_fullness(): u64 acquires BlockCongestion {
+      borrow_global<BlockCongestion>(CoreAddresses::LIBRA_ROOT_ADDRESS()).recent_fullness
+    }
+}
+
+}
@@ -76,6 +76,43 @@ module Event {
         EventHandle<T> { counter: _, guid: _ } = handle;
     }
 
+    // The number of events in the stream of `T` events of an account, for the streams used
+    // without an `EventHandle`. The key of such a stream is derived from the address of the
+    // account and the type `T`, so only the module defining `T` can emit to it.
+    resource struct EventStreamCounter<T: copyable> {
+        counter: u64,
+    }
+
+    // Publish the stream of `T` events of `account`.
+    public fun publish_stream<T: copyable>(account: &signer) {
+        move_to(account, EventStreamCounter<T> { counter: 0 })
+    }
+
+    public fun has_stream<T: copyable>(addr: address): bool {
+        exists<EventStreamCounter<T>>(addr)
+    }
+
+    // Emit an event with payload `msg` to the stream of `T` events of the account at `addr`.
+    public fun emit<T: copyable>(addr: address, msg: T) acquires EventStreamCounter {
+        assert(exists<EventStreamCounter<T>>(addr), 0);
+        let stream = borrow_global_mut<EventStreamCounter<T>>(addr);
+        write_to_event_store<T>(stream_key<T>(addr), stream.counter, msg);
+        stream.counter = stream.counter + 1;
+    }
+
+    // Emit an event with payload `msg` to the stream of `T` events of `account`, publishing the
+    // stream on first use.
+    public fun emit_from<T: copyable>(account: &signer, msg: T) acquires EventStreamCounter {
+        let addr = Signer::address_of(account);
+        if (!exists<EventStreamCounter<T>>(addr)) {
+            publish_stream<T>(account);
+        };
+        emit<T>(addr, msg)
+    }
+
+    // Native procedure that derives the key of the stream of `T` events of the account at `addr`.
+    native fun stream_key<T: copyable>(addr: address): vector<u8>;
+
     // ****************** SPECIFICATIONS *******************
 
     /// # Module specifications
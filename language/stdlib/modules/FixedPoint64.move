@@ -0,0 +1,239 @@
+address 0x1 {
+
+module FixedPoint64 {
+    use 0x1::FixedPoint32::{Self, FixedPoint32};
+
+    // Define a fixed-point numeric type with 64 integer and 64 fractional bits.
+    // Unlike FixedPoint32, every u64 amount and every FixedPoint32 value can be
+    // represented exactly, and the operations take an explicit rounding mode
+    // rather than always truncating.
+    struct FixedPoint64 { value: u128 }
+
+    // Rounding modes of the operations. ROUND_DOWN truncates any fractional
+    // part of the result, ROUND_UP rounds it up to the next representable
+    // value, and ROUND_NEAREST rounds to the closest representable value, with
+    // ties rounded up. Any other mode aborts with error 17.
+    public fun ROUND_DOWN(): u8 {
+        0
+    }
+
+    public fun ROUND_UP(): u8 {
+        1
+    }
+
+    public fun ROUND_NEAREST(): u8 {
+        2
+    }
+
+    // Compute `a * b / c` with the given rounding, keeping the full 256 bit
+    // product, which Move integers cannot hold. This will abort with an
+    // arithmetic error if `c` is zero or if the result overflows a u128.
+    native fun mul_div(a: u128, b: u128, c: u128, rounding: u8): u128;
+
+    // The raw value of 1.0.
+    fun scale(): u128 {
+        1 << 64
+    }
+
+    fun assert_valid_rounding(rounding: u8) {
+        assert(rounding <= ROUND_NEAREST(), 17);
+    }
+
+    // Divide `a` by `b` with the given rounding, for quotients that do not
+    // need a wider intermediate value.
+    fun divide_with_rounding(a: u128, b: u128, rounding: u8): u128 {
+        assert_valid_rounding(rounding);
+        // If the divisor is zero, this will fail with a divide-by-zero error.
+        let quotient = a / b;
+        let remainder = a % b;
+        if (remainder == 0 || rounding == ROUND_DOWN()) {
+            quotient
+        } else if (rounding == ROUND_UP() || remainder >= b - remainder) {
+            // This will fail with an arithmetic error if the quotient is the
+            // largest u128 value.
+            quotient + 1
+        } else {
+            quotient
+        }
+    }
+
+    // Multiply a u64 integer by a fixed-point number, rounding the product to
+    // an integer. This will abort if the product overflows a u64.
+    public fun multiply_u64(num: u64, multiplier: FixedPoint64, rounding: u8): u64 {
+        assert_valid_rounding(rounding);
+        // The unscaled product has 64 fractional bits (from the multiplier),
+        // which are removed by the division.
+        let product = mul_div((num as u128), multiplier.value, scale(), rounding);
+        // The cast will fail with an arithmetic error if the product is too
+        // large.
+        (product as u64)
+    }
+
+    // Divide a u64 integer by a fixed-point number, rounding the quotient to
+    // an integer. This will abort if the divisor is zero or if the quotient
+    // overflows a u64.
+    public fun divide_u64(num: u64, divisor: FixedPoint64, rounding: u8): u64 {
+        // Add 64 fractional zero bits to the dividend, so that the quotient
+        // has none. This always fits in a u128.
+        let scaled_value = (num as u128) << 64;
+        let quotient = divide_with_rounding(scaled_value, divisor.value, rounding);
+        (quotient as u64)
+    }
+
+    // Multiply two fixed-point numbers, rounding the product. This will abort
+    // if the product is 2^64 or larger.
+    public fun multiply(a: FixedPoint64, b: FixedPoint64, rounding: u8): FixedPoint64 {
+        assert_valid_rounding(rounding);
+        FixedPoint64 { value: mul_div(a.value, b.value, scale(), rounding) }
+    }
+
+    // Divide two fixed-point numbers, rounding the quotient. This will abort
+    // if the divisor is zero or if the quotient is 2^64 or larger.
+    public fun divide(a: FixedPoint64, b: FixedPoint64, rounding: u8): FixedPoint64 {
+        assert_valid_rounding(rounding);
+        FixedPoint64 { value: mul_div(a.value, scale(), b.value, rounding) }
+    }
+
+    // Create a fixed-point value from a rational number specified by its
+    // numerator and denominator, rounding it to 64 fractional bits. This will
+    // abort if the denominator is zero. Unlike for FixedPoint32, every ratio
+    // of u64 integers is in the range of FixedPoint64, so there is no
+    // underflow or overflow.
+    public fun create_from_rational(numerator: u64, denominator: u64, rounding: u8): FixedPoint64 {
+        let scaled_numerator = (numerator as u128) << 64;
+        FixedPoint64 { value: divide_with_rounding(scaled_numerator, (denominator as u128), rounding) }
+    }
+
+    public fun create_from_raw_value(value: u128): FixedPoint64 {
+        FixedPoint64 { value }
+    }
+
+    // Convert a u64 integer to a fixed-point number. This is exact.
+    public fun create_from_u64(num: u64): FixedPoint64 {
+        FixedPoint64 { value: (num as u128) << 64 }
+    }
+
+    // Convert a FixedPoint32 number, e.g., an exchange rate, to a FixedPoint64
+    // one. This is exact.
+    public fun create_from_fixed_point32(num: FixedPoint32): FixedPoint64 {
+        FixedPoint64 { value: (FixedPoint32::get_raw_value(num) as u128) << 32 }
+    }
+
+    // Convert a fixed-point number to a u64 integer with the given rounding.
+    // This will abort if the rounded value overflows a u64.
+    public fun to_u64(num: FixedPoint64, rounding: u8): u64 {
+        (divide_with_rounding(num.value, scale(), rounding) as u64)
+    }
+
+    // Convert a fixed-point number to a FixedPoint32 one, rounding it to 32
+    // fractional bits. This will abort if the rounded value doesn't fit in a
+    // FixedPoint32, i.e., if it is 2^32 or larger.
+    public fun to_fixed_point32(num: FixedPoint64, rounding: u8): FixedPoint32 {
+        let value = divide_with_rounding(num.value, 1 << 32, rounding);
+        FixedPoint32::create_from_raw_value((value as u64))
+    }
+
+    // Accessor for the raw u128 value. Other less common operations, such as
+    // adding or subtracting FixedPoint64 values, can be done using the raw
+    // values directly.
+    public fun get_raw_value(num: FixedPoint64): u128 {
+        num.value
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    /*
+    The operations are specified by exact rounding: the exact result over the (unbounded) integers is
+    computed first, then rounded with the given mode. The rounding bounds follow from the definition of
+    `spec_round`: the rounded value is within one unit of the least significant bit of the exact value,
+    below it for ROUND_DOWN, above it for ROUND_UP, and within half a unit for ROUND_NEAREST.
+    The native `mul_div` is modeled the same way in the prover prelude.
+    */
+
+    spec module {
+        define spec_scale(): num {
+            18446744073709551616
+        }
+
+        /// The exact value of `a / b`, rounded with `rounding`.
+        define spec_round(a: num, b: num, rounding: u8): num {
+            if (rounding == 0 || a % b == 0) {
+                a / b
+            } else if (rounding == 1 || 2 * (a % b) >= b) {
+                a / b + 1
+            } else {
+                a / b
+            }
+        }
+
+        /// The rounding bounds of `result`, the rounded value of `a / b`.
+        define spec_rounding_bounds(a: num, b: num, rounding: u8, result: num): bool {
+            (rounding == 0 ==> result * b <= a && a < (result + 1) * b)
+            && (rounding == 1 ==> a <= result * b && (result - 1) * b < a)
+            && (rounding == 2 ==> 2 * a < (2 * result + 1) * b && (2 * result - 1) * b <= 2 * a)
+        }
+    }
+
+    spec fun divide_with_rounding {
+        aborts_if rounding > 2;
+        aborts_if b == 0;
+        aborts_if spec_round(a, b, rounding) > max_u128();
+        ensures result == spec_round(a, b, rounding);
+        ensures spec_rounding_bounds(a, b, rounding, result);
+    }
+
+    spec fun multiply_u64 {
+        aborts_if rounding > 2;
+        aborts_if spec_round(num * multiplier.value, spec_scale(), rounding) > max_u64();
+        ensures spec_rounding_bounds(num * multiplier.value, spec_scale(), rounding, result);
+    }
+
+    spec fun divide_u64 {
+        aborts_if rounding > 2;
+        aborts_if divisor.value == 0;
+        aborts_if spec_round(num * spec_scale(), divisor.value, rounding) > max_u64();
+        ensures spec_rounding_bounds(num * spec_scale(), divisor.value, rounding, result);
+    }
+
+    spec fun multiply {
+        aborts_if rounding > 2;
+        aborts_if spec_round(a.value * b.value, spec_scale(), rounding) > max_u128();
+        ensures spec_rounding_bounds(a.value * b.value, spec_scale(), rounding, result.value);
+    }
+
+    spec fun divide {
+        aborts_if rounding > 2;
+        aborts_if b.value == 0;
+        aborts_if spec_round(a.value * spec_scale(), b.value, rounding) > max_u128();
+        ensures spec_rounding_bounds(a.value * spec_scale(), b.value, rounding, result.value);
+    }
+
+    spec fun create_from_rational {
+        aborts_if rounding > 2;
+        aborts_if denominator == 0;
+        ensures result.value == spec_round(numerator * spec_scale(), denominator, rounding);
+    }
+
+    spec fun create_from_raw_value {
+        aborts_if false;
+        ensures result == FixedPoint64 { value };
+    }
+
+    spec fun create_from_u64 {
+        aborts_if false;
+        ensures result.value == num * spec_scale();
+    }
+
+    spec fun to_u64 {
+        aborts_if rounding > 2;
+        aborts_if spec_round(num.value, spec_scale(), rounding) > max_u64();
+        ensures spec_rounding_bounds(num.value, spec_scale(), rounding, result);
+    }
+
+    spec fun get_raw_value {
+        aborts_if false;
+        ensures result == num.value;
+    }
+}
+
+}
@@ -5,6 +5,8 @@
 address 0x1 {
 module Genesis {
     use 0x1::AccountLimits;
+    use 0x1::Authenticator;
+    use 0x1::BlockCongestion;
     use 0x1::Coin1;
     use 0x1::Coin2;
     use 0x1::DualAttestationLimit;
@@ -25,6 +27,7 @@ module Genesis {
     use 0x1::Roles::{Self, LibraRootRole, TreasuryComplianceRole};
     use 0x1::SlidingNonce::{Self, CreateSlidingNonce};
     use 0x1::LibraVMConfig;
+    use 0x1::LibraModulePublishingPolicy;
 
 
     fun initialize(
@@ -33,6 +36,8 @@ module Genesis {
         tc_addr: address,
         genesis_auth_key: vector<u8>,
         publishing_option: vector<u8>,
+        module_publishing_policy: u8,
+        allowed_module_publishers: vector<address>,
         instruction_schedule: vector<u8>,
         native_schedule: vector<u8>,
     ) {
@@ -109,14 +114,23 @@ module Genesis {
         AccountLimits::publish_unrestricted_limits(tc_account);
         AccountLimits::certify_limits_definition(&tc_capability, tc_addr);
 
-        LibraTransactionTimeout::initialize(association);
+        LibraTransactionTimeout::initialize(association, &create_config_capability);
         LibraSystem::initialize_validator_set(association, &create_config_capability);
         LibraVersion::initialize(association, &create_config_capability);
+        LibraModulePublishingPolicy::initialize(
+            association,
+            &create_config_capability,
+            module_publishing_policy,
+            allowed_module_publishers,
+        );
 
         DualAttestationLimit::initialize(association, tc_account, &create_config_capability);
         LibraBlock::initialize_block_metadata(association);
+        // The default maximum number of transactions in a block proposed by consensus
+        BlockCongestion::initialize(association, 1000);
         LibraWriteSetManager::initialize(association);
         LibraTimestamp::initialize(association);
+        Authenticator::initialize(association);
 
         let assoc_rotate_key_cap = LibraAccount::extract_key_rotation_capability(association);
         LibraAccount::rotate_authentication_key(&assoc_rotate_key_cap, copy genesis_auth_key);
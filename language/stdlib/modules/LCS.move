@@ -8,6 +8,13 @@ module LCS {
     // Return the binary representation of `v` in LCS (Libra Canonical Serialization) format
     native public fun to_bytes<MoveValue>(v: &MoveValue): vector<u8>;
 
+    // Return the value whose binary representation in LCS format is `bytes`, e.g., a field of an
+    // off-chain payload whose signature has been checked. Only booleans, integers, addresses and
+    // vectors of them are supported: other structs may only be created by their module.
+    // Aborts if `bytes` is not the canonical encoding of a value of type `MoveValue`, including
+    // when bytes are left over, or if the type is not supported.
+    native public fun from_bytes<MoveValue: copyable>(bytes: vector<u8>): MoveValue;
+
     // ------------------------------------------------------------------------
     // Specification
     // ------------------------------------------------------------------------
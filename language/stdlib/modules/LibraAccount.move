@@ -2,8 +2,11 @@ address 0x1 {
 
 // The module for the account resource that governs every Libra account
 module LibraAccount {
+    use 0x1::BlockCongestion;
     use 0x1::CoreAddresses;
     use 0x1::AccountLimits;
+    use 0x1::AccountMigration;
+    use 0x1::Authenticator;
     use 0x1::Coin1::Coin1;
     use 0x1::Coin2::Coin2;
     use 0x1::Event::{Self, EventHandle};
@@ -398,6 +401,19 @@ module LibraAccount {
         sender_account_resource.authentication_key = new_authentication_key;
     }
 
+    // Rotate the authentication key for the account under cap.account_address to the key of
+    // `public_key` in the signature scheme `scheme_id`, which lets the account opt into a new
+    // scheme while keeping its address
+    // Aborts if the scheme is not enabled, as the account could not sign transactions anymore
+    public fun rotate_authentication_key_for_scheme(
+        cap: &KeyRotationCapability,
+        public_key: vector<u8>,
+        scheme_id: u8,
+    ) acquires LibraAccount {
+        assert(Authenticator::is_scheme_enabled(scheme_id), 16);
+        rotate_authentication_key(cap, Authenticator::authentication_key(public_key, scheme_id))
+    }
+
     // Return a unique capability granting permission to rotate the sender's authentication key
     public fun extract_key_rotation_capability(account: &signer): KeyRotationCapability
     acquires LibraAccount {
@@ -501,8 +517,10 @@ module LibraAccount {
                 is_frozen: false,
             }
         );
+        // (2) the resources of a new account have the latest layout
+        AccountMigration::publish(&new_account);
 
-        // (2) TODO: publish account limits?
+        // (3) TODO: publish account limits?
         destroy_signer(new_account);
     }
 
@@ -762,6 +780,7 @@ module LibraAccount {
     // The prologue is invoked at the beginning of every transaction
     // It verifies:
     // - The account's auth key matches the transaction's public key
+    // - The transaction is signed with an enabled signature scheme
     // - That the account has enough balance to pay for all of the gas
     // - That the sequence number matches the transaction's sequence key
     fun prologue<Token>(
@@ -785,9 +804,10 @@ module LibraAccount {
 
         // Check that the hash of the transaction's public key matches the account's auth key
         assert(
-            Hash::sha3_256(txn_public_key) == *&sender_account.authentication_key,
+            Hash::sha3_256(copy txn_public_key) == *&sender_account.authentication_key,
             2
         );
+        assert(Authenticator::is_scheme_enabled(Authenticator::scheme_id(&txn_public_key)), 16);
 
         // Check that the account has enough balance for all of the gas
         let max_transaction_fee = txn_gas_price * txn_max_gas_units;
@@ -801,11 +821,70 @@ module LibraAccount {
         assert(txn_sequence_number >= sender_account.sequence_number, 3);
         assert(txn_sequence_number == sender_account.sequence_number, 4);
         assert(LibraTransactionTimeout::is_valid_transaction_timestamp(txn_expiration_time), 7);
+        assert(LibraTransactionTimeout::is_within_expiration_window(txn_expiration_time), 9);
+    }
+
+    // The sponsored_prologue is invoked instead of the prologue for transactions whose gas is
+    // paid by another account than the sender, the fee payer.
+    // It verifies the same as the prologue, except that:
+    // - The fee payer's auth key must match the transaction's fee payer public key
+    // - The fee payer, and not the sender, must have enough balance to pay for all of the gas
+    fun sponsored_prologue<Token>(
+        sender: &signer,
+        txn_sequence_number: u64,
+        txn_public_key: vector<u8>,
+        fee_payer: address,
+        fee_payer_public_key: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+    ) acquires LibraAccount, Balance {
+        let transaction_sender = Signer::address_of(sender);
+
+        // Verify that the transaction sender's account exists
+        assert(exists_at(transaction_sender), 5);
+        assert(!account_is_frozen(transaction_sender), 0);
+
+        // Check that the hash of the transaction's public key matches the sender's auth key
+        let sender_account = borrow_global<LibraAccount>(transaction_sender);
+        assert(
+            Hash::sha3_256(copy txn_public_key) == *&sender_account.authentication_key,
+            2
+        );
+        assert(Authenticator::is_scheme_enabled(Authenticator::scheme_id(&txn_public_key)), 16);
+
+        // Verify that the fee payer is another account, and that it exists
+        assert(fee_payer != transaction_sender, 15);
+        assert(exists_at(fee_payer), 12);
+        assert(!account_is_frozen(fee_payer), 13);
+
+        // Check that the hash of the fee payer's public key matches the fee payer's auth key
+        assert(
+            Hash::sha3_256(copy fee_payer_public_key) == *&borrow_global<LibraAccount>(fee_payer).authentication_key,
+            14
+        );
+        assert(Authenticator::is_scheme_enabled(Authenticator::scheme_id(&fee_payer_public_key)), 16);
+
+        // Check that the fee payer has enough balance for all of the gas
+        let max_transaction_fee = txn_gas_price * txn_max_gas_units;
+        // Don't grab the balance if the transaction fee is zero
+        if (max_transaction_fee > 0) {
+            let balance_amount = balance<Token>(fee_payer);
+            assert(balance_amount >= max_transaction_fee, 6);
+        };
+
+        // Check that the transaction sequence number matches the sequence number of the sender
+        assert(txn_sequence_number >= sender_account.sequence_number, 3);
+        assert(txn_sequence_number == sender_account.sequence_number, 4);
+        assert(LibraTransactionTimeout::is_valid_transaction_timestamp(txn_expiration_time), 7);
+        assert(LibraTransactionTimeout::is_within_expiration_window(txn_expiration_time), 9);
     }
 
-    //  Collects gas and bumps the sequence number for executing a transaction
+    //  Collects gas from the fee payer and bumps the sequence number of the sender for executing a
+    //  transaction. The fee payer is the sender unless the transaction is sponsored.
     fun epilogue<Token>(
         sender: address,
+        fee_payer: address,
         transaction_fee_amount: u64,
         txn_sequence_number: u64,
     ) acquires LibraAccount, Balance, AccountOperationsCapability {
@@ -814,11 +893,12 @@ module LibraAccount {
 
         // Bump the sequence number
         sender_account.sequence_number = txn_sequence_number + 1;
+        BlockCongestion::record_transaction();
 
         if (transaction_fee_amount > 0) {
-            let sender_balance = borrow_global_mut<Balance<Token>>(sender);
+            let fee_payer_balance = borrow_global_mut<Balance<Token>>(fee_payer);
             TransactionFee::pay_fee(
-                withdraw_from_balance(sender, sender_balance, transaction_fee_amount)
+                withdraw_from_balance(fee_payer, fee_payer_balance, transaction_fee_amount)
             )
         }
     }
@@ -839,7 +919,7 @@ module LibraAccount {
         // Load the transaction sender's balance resource only if it exists. If it doesn't we default the value to 0
         let sender_balance = if (exists<Balance<Token>>(sender)) balance<Token>(sender) else 0;
         assert(sender_balance >= transaction_fee_amount, 6);
-        epilogue<Token>(sender, transaction_fee_amount, txn_sequence_number);
+        epilogue<Token>(sender, sender, transaction_fee_amount, txn_sequence_number);
     }
 
     // The failure_epilogue is invoked at the end of transactions when the transaction is aborted during execution or
@@ -855,7 +935,45 @@ module LibraAccount {
         // Charge for gas
         let transaction_fee_amount = txn_gas_price * (txn_max_gas_units - gas_units_remaining);
 
-        epilogue<Token>(sender, transaction_fee_amount, txn_sequence_number);
+        epilogue<Token>(sender, sender, transaction_fee_amount, txn_sequence_number);
+    }
+
+    // The sponsored_success_epilogue is invoked at the end of successfully executed sponsored
+    // transactions, and charges the gas to the fee payer.
+    fun sponsored_success_epilogue<Token>(
+        account: &signer,
+        fee_payer: address,
+        txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) acquires LibraAccount, Balance, AccountOperationsCapability {
+        let sender = Signer::address_of(account);
+
+        // Charge for gas
+        let transaction_fee_amount = txn_gas_price * (txn_max_gas_units - gas_units_remaining);
+
+        // Load the fee payer's balance resource only if it exists. If it doesn't we default the value to 0
+        let fee_payer_balance = if (exists<Balance<Token>>(fee_payer)) balance<Token>(fee_payer) else 0;
+        assert(fee_payer_balance >= transaction_fee_amount, 6);
+        epilogue<Token>(sender, fee_payer, transaction_fee_amount, txn_sequence_number);
+    }
+
+    // The sponsored_failure_epilogue is invoked at the end of sponsored transactions when the
+    // transaction is aborted during execution or during `sponsored_success_epilogue`.
+    fun sponsored_failure_epilogue<Token>(
+        account: &signer,
+        fee_payer: address,
+        txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) acquires LibraAccount, Balance, AccountOperationsCapability {
+        let sender = Signer::address_of(account);
+        // Charge for gas
+        let transaction_fee_amount = txn_gas_price * (txn_max_gas_units - gas_units_remaining);
+
+        epilogue<Token>(sender, fee_payer, transaction_fee_amount, txn_sequence_number);
     }
 
     // Bump the sequence number of an account. This function should be used only for bumping the sequence number when
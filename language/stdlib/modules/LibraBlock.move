@@ -1,11 +1,13 @@
 address 0x1 {
 
 module LibraBlock {
+    use 0x1::BlockCongestion;
     use 0x1::CoreAddresses;
     use 0x1::Event;
     use 0x1::LibraSystem;
     use 0x1::LibraTimestamp;
     use 0x1::Signer;
+    use 0x1::Vector;
 
     resource struct BlockMetadata {
       // Height of the current block
@@ -13,6 +15,11 @@ module LibraBlock {
       height: u64,
       // Handle where events with the time of new blocks are emitted
       new_block_events: Event::EventHandle<Self::NewBlockEvent>,
+      // Hash of the parent of the current block, e.g. to seed randomness
+      parent_block_hash: vector<u8>,
+      // Proposers of the rounds between the parent and the current block which failed to
+      // produce a certified block, in round order
+      failed_proposers: vector<address>,
     }
 
     struct NewBlockEvent {
@@ -22,6 +29,9 @@ module LibraBlock {
 
       // On-chain time during  he block at the given height
       time_microseconds: u64,
+
+      parent_block_hash: vector<u8>,
+      failed_proposers: vector<address>,
     }
 
     // This can only be invoked by the Association address, and only a single time.
@@ -35,6 +45,8 @@ module LibraBlock {
           BlockMetadata {
               height: 0,
               new_block_events: Event::new_event_handle<Self::NewBlockEvent>(account),
+              parent_block_hash: Vector::empty(),
+              failed_proposers: Vector::empty(),
           }
       );
     }
@@ -49,12 +61,22 @@ module LibraBlock {
         round: u64,
         timestamp: u64,
         previous_block_votes: vector<address>,
-        proposer: address
+        proposer: address,
+        parent_block_hash: vector<u8>,
+        failed_proposers: vector<address>
     ) acquires BlockMetadata {
         // Can only be invoked by LibraVM privilege.
         assert(Signer::address_of(vm) == CoreAddresses::VM_RESERVED_ADDRESS(), 33);
 
-        process_block_prologue(vm,  round, timestamp, previous_block_votes, proposer);
+        process_block_prologue(
+            vm,
+            round,
+            timestamp,
+            previous_block_votes,
+            proposer,
+            parent_block_hash,
+            failed_proposers
+        );
 
         // TODO(valerini): call regular reconfiguration here LibraSystem2::update_all_validator_info()
     }
@@ -65,14 +87,19 @@ module LibraBlock {
         round: u64,
         timestamp: u64,
         previous_block_votes: vector<address>,
-        proposer: address
+        proposer: address,
+        parent_block_hash: vector<u8>,
+        failed_proposers: vector<address>
     ) acquires BlockMetadata {
         let block_metadata_ref = borrow_global_mut<BlockMetadata>(CoreAddresses::LIBRA_ROOT_ADDRESS());
 
         // TODO: Figure out a story for errors in the system transactions.
         if(proposer != CoreAddresses::VM_RESERVED_ADDRESS()) assert(LibraSystem::is_validator(proposer), 5002);
         LibraTimestamp::update_global_time(vm, proposer, timestamp);
+        BlockCongestion::end_block(vm);
         block_metadata_ref.height = block_metadata_ref.height + 1;
+        block_metadata_ref.parent_block_hash = copy parent_block_hash;
+        block_metadata_ref.failed_proposers = copy failed_proposers;
         Event::emit_event<NewBlockEvent>(
           &mut block_metadata_ref.new_block_events,
           NewBlockEvent {
@@ -80,6 +107,8 @@ module LibraBlock {
             proposer: proposer,
             previous_block_votes: previous_block_votes,
             time_microseconds: timestamp,
+            parent_block_hash: parent_block_hash,
+            failed_proposers: failed_proposers,
           }
         );
     }
@@ -88,6 +117,17 @@ module LibraBlock {
     public fun get_current_block_height(): u64 acquires BlockMetadata {
       borrow_global<BlockMetadata>(CoreAddresses::LIBRA_ROOT_ADDRESS()).height
     }
+
+    // Get the hash of the parent of the current block
+    public fun get_parent_block_hash(): vector<u8> acquires BlockMetadata {
+      *&borrow_global<BlockMetadata>(CoreAddresses::LIBRA_ROOT_ADDRESS()).parent_block_hash
+    }
+
+    // Get the proposers of the rounds since the parent block which failed to produce a
+    // certified block
+    public fun get_failed_proposers(): vector<address> acquires BlockMetadata {
+      *&borrow_global<BlockMetadata>(CoreAddresses::LIBRA_ROOT_ADDRESS()).failed_proposers
+    }
 }
 
 }
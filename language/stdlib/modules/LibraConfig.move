@@ -3,6 +3,7 @@ module LibraConfig {
     use 0x1::CoreAddresses;
     use 0x1::Event;
     use 0x1::LibraTimestamp;
+    use 0x1::Option::{Self, Option};
     use 0x1::Signer;
     use 0x1::Offer;
     use 0x1::Roles::{Self, Capability, LibraRootRole};
@@ -12,6 +13,14 @@ module LibraConfig {
     // A generic singleton resource that holds a value of a specific type.
     resource struct LibraConfig<Config: copyable> { payload: Config }
 
+    // A new value of a config item scheduled to take effect at the start of `activation_epoch`.
+    // Until then, the VM and the validators keep using the current value, so that all the
+    // validators switch at the same reconfiguration.
+    resource struct PendingConfig<Config: copyable> {
+        activation_epoch: u64,
+        payload: Option<Config>,
+    }
+
     struct NewEpochEvent {
         epoch: u64,
     }
@@ -48,21 +57,32 @@ module LibraConfig {
         );
     }
 
-    // Get a copy of `Config` value stored under `addr`.
-    public fun get<Config: copyable>(): Config acquires LibraConfig {
+    // Get a copy of the `Config` value in effect in the current epoch: a pending value once its
+    // activation epoch has started, the stored value otherwise.
+    public fun get<Config: copyable>(): Config acquires LibraConfig, PendingConfig, Configuration {
         let addr = CoreAddresses::LIBRA_ROOT_ADDRESS();
         assert(exists<LibraConfig<Config>>(addr), 24);
+        if (exists<PendingConfig<Config>>(addr)) {
+            let pending = borrow_global<PendingConfig<Config>>(addr);
+            if (Option::is_some(&pending.payload) && pending.activation_epoch <= current_epoch()) {
+                return *Option::borrow(&pending.payload)
+            }
+        };
         *&borrow_global<LibraConfig<Config>>(addr).payload
     }
 
     // Set a config item to a new value with the default capability stored under config address and trigger a
     // reconfiguration.
-    public fun set<Config: copyable>(account: &signer, payload: Config) acquires LibraConfig, Configuration {
+    public fun set<Config: copyable>(
+        account: &signer,
+        payload: Config
+    ) acquires LibraConfig, PendingConfig, Configuration {
         let addr = CoreAddresses::LIBRA_ROOT_ADDRESS();
         assert(exists<LibraConfig<Config>>(addr), 24);
         let signer_address = Signer::address_of(account);
         assert(exists<ModifyConfigCapability<Config>>(signer_address), 24);
 
+        apply_pending<Config>();
         let config = borrow_global_mut<LibraConfig<Config>>(addr);
         config.payload = payload;
 
@@ -73,14 +93,54 @@ module LibraConfig {
     public fun set_with_capability<Config: copyable>(
         _cap: &ModifyConfigCapability<Config>,
         payload: Config
-    ) acquires LibraConfig, Configuration {
+    ) acquires LibraConfig, PendingConfig, Configuration {
         let addr = CoreAddresses::LIBRA_ROOT_ADDRESS();
         assert(exists<LibraConfig<Config>>(addr), 24);
+        apply_pending<Config>();
         let config = borrow_global_mut<LibraConfig<Config>>(addr);
         config.payload = payload;
         reconfigure_();
     }
 
+    // Schedule a config item to take a new value at the start of `activation_epoch`, which must be a
+    // future epoch. This replaces any update scheduled before and does not trigger a reconfiguration:
+    // the new value takes effect with the reconfiguration that starts `activation_epoch`.
+    public fun set_with_activation_epoch<Config: copyable>(
+        account: &signer,
+        payload: Config,
+        activation_epoch: u64,
+    ) acquires LibraConfig, PendingConfig, Configuration {
+        let addr = CoreAddresses::LIBRA_ROOT_ADDRESS();
+        assert(exists<LibraConfig<Config>>(addr), 24);
+        assert(exists<PendingConfig<Config>>(addr), 24);
+        let signer_address = Signer::address_of(account);
+        assert(exists<ModifyConfigCapability<Config>>(signer_address), 24);
+        assert(activation_epoch > current_epoch(), 25);
+
+        apply_pending<Config>();
+        let pending = borrow_global_mut<PendingConfig<Config>>(addr);
+        pending.activation_epoch = activation_epoch;
+        pending.payload = Option::some(payload);
+    }
+
+    // Store the pending value of a config item, if its activation epoch has started, so that it is
+    // not overwritten by the next update.
+    fun apply_pending<Config: copyable>() acquires LibraConfig, PendingConfig, Configuration {
+        let addr = CoreAddresses::LIBRA_ROOT_ADDRESS();
+        if (!exists<PendingConfig<Config>>(addr)) {
+            return ()
+        };
+        let pending = borrow_global_mut<PendingConfig<Config>>(addr);
+        if (Option::is_some(&pending.payload) && pending.activation_epoch <= current_epoch()) {
+            let config = borrow_global_mut<LibraConfig<Config>>(addr);
+            config.payload = Option::extract(&mut pending.payload);
+        }
+    }
+
+    fun current_epoch(): u64 acquires Configuration {
+        borrow_global<Configuration>(CoreAddresses::LIBRA_ROOT_ADDRESS()).epoch
+    }
+
     // Publish a new config item. The caller will use the returned ModifyConfigCapability to specify the access control
     // policy for who can modify the config.
     public fun publish_new_config_with_capability<Config: copyable>(
@@ -90,8 +150,9 @@ module LibraConfig {
     ): ModifyConfigCapability<Config> {
         assert(Signer::address_of(config_account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 1);
         move_to(config_account, LibraConfig { payload });
+        publish_pending_config<Config>(config_account);
         // We don't trigger reconfiguration here, instead we'll wait for all validators update the binary
-        // to register this config into ON_CHAIN_CONFIG_REGISTRY then send another transaction to change
+        // to subscribe to this config then send another transaction to change
         // the value which triggers the reconfiguration.
         return ModifyConfigCapability<Config> {}
     }
@@ -104,6 +165,7 @@ module LibraConfig {
         payload: Config,
     ) {
         move_to(config_account, LibraConfig { payload });
+        publish_pending_config<Config>(config_account);
         move_to(tc_account, ModifyConfigCapability<Config> {});
     }
 
@@ -116,8 +178,9 @@ module LibraConfig {
         assert(Signer::address_of(config_account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 1);
         move_to(config_account, ModifyConfigCapability<Config> {});
         move_to(config_account, LibraConfig{ payload });
+        publish_pending_config<Config>(config_account);
         // We don't trigger reconfiguration here, instead we'll wait for all validators update the binary
-        // to register this config into ON_CHAIN_CONFIG_REGISTRY then send another transaction to change
+        // to subscribe to this config then send another transaction to change
         // the value which triggers the reconfiguration.
     }
 
@@ -131,11 +194,20 @@ module LibraConfig {
         assert(Signer::address_of(config_account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 1);
         Offer::create(config_account, ModifyConfigCapability<Config>{}, delegate);
         move_to(config_account, LibraConfig { payload });
+        publish_pending_config<Config>(config_account);
         // We don't trigger reconfiguration here, instead we'll wait for all validators update the
-        // binary to register this config into ON_CHAIN_CONFIG_REGISTRY then send another
+        // binary to subscribe to this config then send another
         // transaction to change the value which triggers the reconfiguration.
     }
 
+    // Publish an empty slot for the scheduled updates of a new config item.
+    fun publish_pending_config<Config: copyable>(config_account: &signer) {
+        move_to(
+            config_account,
+            PendingConfig<Config> { activation_epoch: 0, payload: Option::none() }
+        );
+    }
+
     // Claim a delegated modify config capability granted by publish_new_config_with_delegate.
     public fun claim_delegated_modify_config<Config>(account: &signer, offer_address: address) {
         move_to(account, Offer::redeem<ModifyConfigCapability<Config>>(account, offer_address))
@@ -0,0 +1,81 @@
+address 0x1 {
+
+module LibraModulePublishingPolicy {
+    use 0x1::CoreAddresses;
+    use 0x1::LibraConfig::{Self, CreateOnChainConfig};
+    use 0x1::Signer;
+    use 0x1::Roles::Capability;
+    use 0x1::Vector;
+
+    // Any account can publish modules
+    const OPEN: u8 = 0;
+    // Only the accounts in `allowed_publishers` can publish modules
+    const ALLOW_LIST: u8 = 1;
+    // No account can publish modules
+    const CLOSED: u8 = 2;
+
+    const ENOT_LIBRA_ROOT: u64 = 1;
+    const EINVALID_POLICY: u64 = 2;
+
+    // The policy checked by the VM when a transaction publishes a module. It applies to the
+    // modules published under the address of the sender: the holders of the module publishing
+    // privilege can always publish modules under the core code address.
+    // * policy: One of OPEN, ALLOW_LIST and CLOSED.
+    // * allowed_publishers: The accounts allowed to publish modules under the ALLOW_LIST policy.
+    struct LibraModulePublishingPolicy {
+        policy: u8,
+        allowed_publishers: vector<address>,
+    }
+
+    public fun initialize(
+        account: &signer,
+        create_config_capability: &Capability<CreateOnChainConfig>,
+        policy: u8,
+        allowed_publishers: vector<address>,
+    ) {
+        assert(Signer::address_of(account) == CoreAddresses::LIBRA_ROOT_ADDRESS(), ENOT_LIBRA_ROOT);
+        assert(policy <= CLOSED, EINVALID_POLICY);
+
+        LibraConfig::publish_new_config<LibraModulePublishingPolicy>(
+            account,
+            create_config_capability,
+            LibraModulePublishingPolicy { policy, allowed_publishers },
+        );
+    }
+
+    // Replace the policy, which triggers a reconfiguration for the VM to reload it
+    public fun set(account: &signer, policy: u8, allowed_publishers: vector<address>) {
+        assert(policy <= CLOSED, EINVALID_POLICY);
+
+        LibraConfig::set<LibraModulePublishingPolicy>(
+            account,
+            LibraModulePublishingPolicy { policy, allowed_publishers },
+        );
+    }
+
+    public fun set_open(account: &signer) {
+        set(account, OPEN, Vector::empty())
+    }
+
+    public fun set_allow_list(account: &signer, allowed_publishers: vector<address>) {
+        set(account, ALLOW_LIST, allowed_publishers)
+    }
+
+    public fun set_closed(account: &signer) {
+        set(account, CLOSED, Vector::empty())
+    }
+
+    // Whether `publisher` can publish modules under its own address
+    public fun is_allowed_publisher(publisher: address): bool {
+        let config = LibraConfig::get<LibraModulePublishingPolicy>();
+        if (config.policy == OPEN) {
+            true
+        } else if (config.policy == ALLOW_LIST) {
+            Vector::contains(&config.allowed_publishers, &publisher)
+        } else {
+            false
+        }
+    }
+}
+
+}
@@ -2,44 +2,73 @@ address 0x1 {
 
 module LibraTransactionTimeout {
   use 0x1::CoreAddresses;
+  use 0x1::LibraConfig::{Self, CreateOnChainConfig};
   use 0x1::Signer;
   use 0x1::LibraTimestamp;
-  use 0x1::Roles::{Capability, LibraRootRole};
+  use 0x1::Roles::Capability;
 
-  resource struct TTL {
-    // Only transactions with timestamp in between block time and block time + duration would be accepted.
+  // The expiration window: only transactions with timestamp in between block time and block
+  // time + duration are accepted.
+  struct LibraTransactionTimeout {
     duration_microseconds: u64,
   }
 
-  public fun initialize(association: &signer) {
+  public fun initialize(
+    association: &signer,
+    create_config_capability: &Capability<CreateOnChainConfig>,
+  ) {
     // Operational constraint, only callable by the Association address
     assert(Signer::address_of(association) == CoreAddresses::LIBRA_ROOT_ADDRESS(), 1);
     // Currently set to 1day.
-    move_to(association, TTL {duration_microseconds: 86400000000});
+    LibraConfig::publish_new_config<LibraTransactionTimeout>(
+      association,
+      create_config_capability,
+      LibraTransactionTimeout { duration_microseconds: 86400000000 },
+    );
   }
 
-  public fun set_timeout(_: &Capability<LibraRootRole>, new_duration: u64) acquires TTL {
-    let timeout = borrow_global_mut<TTL>(CoreAddresses::LIBRA_ROOT_ADDRESS());
-    timeout.duration_microseconds = new_duration;
+  public fun set_timeout(account: &signer, new_duration: u64) {
+    LibraConfig::set<LibraTransactionTimeout>(
+      account,
+      LibraTransactionTimeout { duration_microseconds: new_duration },
+    );
   }
 
-  public fun is_valid_transaction_timestamp(timestamp: u64): bool acquires TTL {
+  public fun duration_microseconds(): u64 {
+    LibraConfig::get<LibraTransactionTimeout>().duration_microseconds
+  }
+
+  // Whether the transaction has not expired yet
+  public fun is_valid_transaction_timestamp(timestamp: u64): bool {
     // Reject timestamp greater than u64::MAX / 1_000_000;
     if(timestamp > 9223372036854) {
       return false
     };
 
     let current_block_time = LibraTimestamp::now_microseconds();
-    let timeout = borrow_global<TTL>(CoreAddresses::LIBRA_ROOT_ADDRESS()).duration_microseconds;
-    let _max_txn_time = current_block_time + timeout;
-
     let txn_time_microseconds = timestamp * 1000000;
-    // TODO: Add LibraTimestamp::is_before_exclusive(&txn_time_microseconds, &max_txn_time)
-    //       This is causing flaky test right now. The reason is that we will use this logic for AC, where its wall
-    //       clock time might be out of sync with the real block time stored in StateStore.
-    //       See details in issue #2346.
     current_block_time < txn_time_microseconds
   }
+
+  // Whether the transaction expires within the expiration window, so that nodes don't hold
+  // transactions expiring in a far future.
+  // Until the first block, the chain has no notion of the current time and any expiration is
+  // accepted.
+  public fun is_within_expiration_window(timestamp: u64): bool {
+    if(timestamp > 9223372036854) {
+      return false
+    };
+    if (LibraTimestamp::is_not_initialized()) {
+      return true
+    };
+
+    let current_block_time = LibraTimestamp::now_microseconds();
+    let txn_time_microseconds = timestamp * 1000000;
+    if (txn_time_microseconds <= current_block_time) {
+      return true
+    };
+    txn_time_microseconds - current_block_time <= duration_microseconds()
+  }
 }
 
 }
@@ -59,6 +59,25 @@ module LibraVMConfig {
         max_price_per_gas_unit: u64,
 
         max_transaction_size_in_bytes: u64,
+
+        /// Resources whose serialized size exceeds this are stored across multiple chunks of at most
+        /// this size.
+        resource_chunk_size_in_bytes: u64,
+
+        /// The maximum total size of the values written by a transaction.
+        max_write_set_size_in_bytes: u64,
+
+        /// The maximum number of write ops of a transaction.
+        max_write_ops: u64,
+
+        /// The maximum number of events emitted by a transaction.
+        max_events: u64,
+
+        /// The maximum size of the data of an event.
+        max_event_size_in_bytes: u64,
+
+        /// The maximum number of scripts in a batch transaction.
+        max_scripts_per_batch: u64,
     }
 
     // Initialize the table under the association account
@@ -80,6 +99,12 @@ module LibraVMConfig {
             min_price_per_gas_unit: 0,
             max_price_per_gas_unit: 10000,
             max_transaction_size_in_bytes: 4096,
+            resource_chunk_size_in_bytes: 65536,
+            max_write_set_size_in_bytes: 1048576,
+            max_write_ops: 4096,
+            max_events: 1024,
+            max_event_size_in_bytes: 65536,
+            max_scripts_per_batch: 8,
         };
 
 
@@ -104,6 +129,30 @@ module LibraVMConfig {
         current_config.publishing_option = publishing_option;
         LibraConfig::set<LibraVMConfig>(account, current_config);
     }
+
+    // Set the limits on the effects of a transaction, enforced by the VM as it executes
+    public fun set_transaction_limits(
+        account: &signer,
+        max_write_set_size_in_bytes: u64,
+        max_write_ops: u64,
+        max_events: u64,
+        max_event_size_in_bytes: u64,
+    ) {
+        let current_config = LibraConfig::get<LibraVMConfig>();
+        let gas_constants = &mut current_config.gas_schedule.gas_constants;
+        gas_constants.max_write_set_size_in_bytes = max_write_set_size_in_bytes;
+        gas_constants.max_write_ops = max_write_ops;
+        gas_constants.max_events = max_events;
+        gas_constants.max_event_size_in_bytes = max_event_size_in_bytes;
+        LibraConfig::set<LibraVMConfig>(account, current_config);
+    }
+
+    // Set the maximum number of scripts in a batch transaction
+    public fun set_max_scripts_per_batch(account: &signer, max_scripts_per_batch: u64) {
+        let current_config = LibraConfig::get<LibraVMConfig>();
+        current_config.gas_schedule.gas_constants.max_scripts_per_batch = max_scripts_per_batch;
+        LibraConfig::set<LibraVMConfig>(account, current_config);
+    }
 }
 
 }
@@ -36,6 +36,23 @@ module LibraVersion {
             LibraVersion { major }
         );
     }
+
+    // Schedule the version to change to `major` at the start of `activation_epoch`, so that all the
+    // validators upgrade at the same reconfiguration.
+    public fun set_with_activation_epoch(account: &signer, major: u64, activation_epoch: u64) {
+        let old_config = LibraConfig::get<LibraVersion>();
+
+        assert(
+            old_config.major < major,
+            25
+        );
+
+        LibraConfig::set_with_activation_epoch<LibraVersion>(
+            account,
+            LibraVersion { major },
+            activation_epoch,
+        );
+    }
 }
 
 }
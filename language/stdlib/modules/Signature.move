@@ -4,6 +4,17 @@ module Signature {
     native public fun ed25519_validate_pubkey(public_key: vector<u8>): bool;
     native public fun ed25519_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
     native public fun ed25519_threshold_verify(bitmap: vector<u8>, signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): u64;
+
+    // Recover the secp256k1 public key that made the 64 byte `signature` of the 32 byte
+    // `message_hash` with `recovery_id`, e.g., Ethereum's `v` minus 27. Returns the 64 byte key,
+    // without the prefix of its uncompressed encoding, and `true`, or an empty vector and `false`
+    // if no key can be recovered. The key must be compared to the expected one: a well-formed but
+    // wrong signature recovers another key.
+    native public fun secp256k1_recover(message_hash: vector<u8>, recovery_id: u8, signature: vector<u8>): (vector<u8>, bool);
+    // Verify a BLS12-381 signature, with the 48 byte `public_key` and the 96 byte `signature`.
+    // The public key must have been vetted with its proof of possession before taking part in
+    // any aggregation.
+    native public fun bls12381_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
 }
 
 }
@@ -0,0 +1,207 @@
+address 0x1 {
+
+/**
+This module defines an ordered map over vectors. Keys are ordered by `Compare::cmp_lcs_bytes` over
+their LCS encoding, which is the numeric order for the integer types. Lookups are binary searches,
+while insertions and removals shift the entries after the affected key.
+*/
+module SortedMap {
+    use 0x1::Compare;
+    use 0x1::LCS;
+    use 0x1::Vector;
+
+    // The map, as parallel vectors of keys and values. The keys are sorted
+    // and unique, and `values[i]` is the value of `keys[i]`.
+    struct SortedMap<K, V> { keys: vector<K>, values: vector<V> }
+
+    // Return an empty map
+    public fun empty<K, V>(): SortedMap<K, V> {
+        SortedMap { keys: Vector::empty(), values: Vector::empty() }
+    }
+
+    // Return the number of entries in `map`
+    public fun length<K, V>(map: &SortedMap<K, V>): u64 {
+        Vector::length(&map.keys)
+    }
+
+    // Return true if `map` has no entries
+    public fun is_empty<K, V>(map: &SortedMap<K, V>): bool {
+        Vector::is_empty(&map.keys)
+    }
+
+    // Return true if `map` has an entry for `key`
+    public fun contains_key<K, V>(map: &SortedMap<K, V>, key: &K): bool {
+        let (_, found) = find(map, key);
+        found
+    }
+
+    // Return an immutable reference to the value of `key`
+    // Aborts if `map` has no entry for `key`
+    public fun borrow<K, V>(map: &SortedMap<K, V>, key: &K): &V {
+        let (index, found) = find(map, key);
+        assert(found, 0);
+        Vector::borrow(&map.values, index)
+    }
+
+    // Return a mutable reference to the value of `key`
+    // Aborts if `map` has no entry for `key`
+    public fun borrow_mut<K, V>(map: &mut SortedMap<K, V>, key: &K): &mut V {
+        let (index, found) = find(map, key);
+        assert(found, 0);
+        Vector::borrow_mut(&mut map.values, index)
+    }
+
+    // Add an entry for `key` with `value`
+    // Aborts if `map` already has an entry for `key`
+    public fun insert<K, V>(map: &mut SortedMap<K, V>, key: K, value: V) {
+        let (index, found) = find(map, &key);
+        assert(!found, 1);
+        let keys = &mut map.keys;
+        let values = &mut map.values;
+        Vector::push_back(keys, key);
+        Vector::push_back(values, value);
+        // Move the new entry down to its position
+        let i = Vector::length(keys) - 1;
+        while (i > index) {
+            Vector::swap(keys, i - 1, i);
+            Vector::swap(values, i - 1, i);
+            i = i - 1;
+        }
+    }
+
+    // Remove the entry for `key` and return its key and value
+    // Aborts if `map` has no entry for `key`
+    public fun remove<K, V>(map: &mut SortedMap<K, V>, key: &K): (K, V) {
+        let (index, found) = find(map, key);
+        assert(found, 0);
+        remove_at(map, index)
+    }
+
+    // Return the index of the first entry whose key is not less than `key`, or the length of
+    // `map` if there is none. A range of keys is iterated over by walking the indexes from the
+    // lower bound of its first key to the lower bound of the key following its last one.
+    public fun lower_bound<K, V>(map: &SortedMap<K, V>, key: &K): u64 {
+        let (index, _) = find(map, key);
+        index
+    }
+
+    // Return an immutable reference to the key of the entry at `index`
+    // Aborts if `index` is out of bounds
+    public fun key_at<K, V>(map: &SortedMap<K, V>, index: u64): &K {
+        Vector::borrow(&map.keys, index)
+    }
+
+    // Return an immutable reference to the value of the entry at `index`
+    // Aborts if `index` is out of bounds
+    public fun value_at<K, V>(map: &SortedMap<K, V>, index: u64): &V {
+        Vector::borrow(&map.values, index)
+    }
+
+    // Return a mutable reference to the value of the entry at `index`
+    // Aborts if `index` is out of bounds
+    public fun value_at_mut<K, V>(map: &mut SortedMap<K, V>, index: u64): &mut V {
+        Vector::borrow_mut(&mut map.values, index)
+    }
+
+    // Remove the entry at `index` and return its key and value
+    // Aborts if `index` is out of bounds
+    public fun remove_at<K, V>(map: &mut SortedMap<K, V>, index: u64): (K, V) {
+        (Vector::remove(&mut map.keys, index), Vector::remove(&mut map.values, index))
+    }
+
+    // Destroy `map`
+    // Aborts if `map` has entries
+    public fun destroy_empty<K, V>(map: SortedMap<K, V>) {
+        let SortedMap { keys, values } = map;
+        Vector::destroy_empty(keys);
+        Vector::destroy_empty(values)
+    }
+
+    // Binary search for `key`. Return the index of the first entry whose key is not less than
+    // `key`, and whether that key is `key`.
+    fun find<K, V>(map: &SortedMap<K, V>, key: &K): (u64, bool) {
+        let key_bytes = LCS::to_bytes(key);
+        let low = 0;
+        let high = Vector::length(&map.keys);
+        while (low < high) {
+            let mid = low + (high - low) / 2;
+            let cmp = Compare::cmp_lcs_bytes(&LCS::to_bytes(Vector::borrow(&map.keys, mid)), &key_bytes);
+            if (cmp == 0) return (mid, true);
+            if (cmp == 1) low = mid + 1
+            else high = mid
+        };
+        (low, false)
+    }
+
+    // ****************** SPECIFICATIONS *******************
+
+    spec struct SortedMap {
+        invariant len(keys) == len(values);
+        /// The keys are strictly increasing, hence unique.
+        invariant forall i in 0..len(keys), j in 0..len(keys) where i < j: spec_less(keys[i], keys[j]);
+    }
+
+    spec module {
+        /// The order of keys, i.e., `Compare::cmp_lcs_bytes` over their LCS encoding returning
+        /// `LESS_THAN`. It is modeled as an arbitrary strict total order in the prover prelude,
+        /// which is all the specifications rely on.
+        native define spec_less<K>(k1: K, k2: K): bool;
+
+        /// Return true iff `map` has an entry for `key`.
+        define spec_contains_key<K, V>(map: SortedMap<K, V>, key: K): bool {
+            exists i in 0..len(map.keys): map.keys[i] == key
+        }
+    }
+
+    spec fun empty {
+        aborts_if false;
+        ensures len(result.keys) == 0;
+    }
+
+    spec fun length {
+        aborts_if false;
+        ensures result == len(map.keys);
+    }
+
+    spec fun is_empty {
+        aborts_if false;
+        ensures result == (len(map.keys) == 0);
+    }
+
+    spec fun contains_key {
+        aborts_if false;
+        ensures result == spec_contains_key(map, key);
+    }
+
+    spec fun borrow {
+        aborts_if !spec_contains_key(map, key);
+        ensures exists i in 0..len(map.keys): map.keys[i] == key && result == map.values[i];
+    }
+
+    spec fun insert {
+        aborts_if spec_contains_key(map, key);
+        ensures spec_contains_key(map, key);
+        ensures len(map.keys) == len(old(map).keys) + 1;
+        ensures forall k: K where spec_contains_key(old(map), k): spec_contains_key(map, k);
+    }
+
+    spec fun remove {
+        aborts_if !spec_contains_key(map, key);
+        ensures !spec_contains_key(map, key);
+        ensures len(map.keys) == len(old(map).keys) - 1;
+        ensures result_1 == key;
+    }
+
+    spec fun lower_bound {
+        aborts_if false;
+        ensures result <= len(map.keys);
+        ensures forall i in 0..result: spec_less(map.keys[i], key);
+        ensures forall i in result..len(map.keys): !spec_less(map.keys[i], key);
+    }
+
+    spec fun destroy_empty {
+        aborts_if len(map.keys) > 0;
+    }
+}
+
+}
@@ -0,0 +1,96 @@
+address 0x1 {
+
+/**
+This module defines a `Table` of entries with keys and values of any types. Unlike a map held in
+a resource, the entries of a table are not stored in the account of its owner, but each under its
+own key in storage. Reading or writing an entry only loads and writes that entry, and is charged
+for it alone, however large the table is.
+*/
+module Table {
+    use 0x1::LCS;
+    use 0x1::Signer;
+    use 0x1::Vector;
+
+    // The counter used to generate the handles of the tables created by an account. There is no
+    // destructor, so that a handle is never generated twice.
+    resource struct TableHandleGenerator { counter: u64 }
+
+    // A table. The handle identifies its entries in storage, it is globally unique because it is
+    // derived from the address of the account which created the table and the counter of its
+    // generator. Entries are keyed by the LCS encoding of their key.
+    resource struct Table<K: copyable, V> {
+        handle: vector<u8>,
+        length: u64,
+    }
+
+    // Create an empty table, whose handle is generated under `account`
+    public fun new<K: copyable, V>(account: &signer): Table<K, V> acquires TableHandleGenerator {
+        let addr = Signer::address_of(account);
+        if (!exists<TableHandleGenerator>(addr)) {
+            move_to(account, TableHandleGenerator { counter: 0 })
+        };
+        let generator = borrow_global_mut<TableHandleGenerator>(addr);
+        let handle = LCS::to_bytes(&generator.counter);
+        Vector::append(&mut handle, LCS::to_bytes(&addr));
+        generator.counter = generator.counter + 1;
+        Table { handle, length: 0 }
+    }
+
+    // Return the number of entries in `table`
+    public fun length<K: copyable, V>(table: &Table<K, V>): u64 {
+        table.length
+    }
+
+    // Return true if `table` has no entries
+    public fun is_empty<K: copyable, V>(table: &Table<K, V>): bool {
+        table.length == 0
+    }
+
+    // Return true if `table` has an entry for `key`
+    public fun contains<K: copyable, V>(table: &Table<K, V>, key: K): bool {
+        contains_entry(*&table.handle, &key)
+    }
+
+    // Add an entry for `key` with `value`
+    // Fails with a native function error if `table` already has an entry for `key`
+    public fun add<K: copyable, V>(table: &mut Table<K, V>, key: K, value: V) {
+        add_entry(*&table.handle, &key, value);
+        table.length = table.length + 1;
+    }
+
+    // Return a copy of the value of `key`
+    // Fails with a native function error if `table` has no entry for `key`
+    public fun read<K: copyable, V: copyable>(table: &Table<K, V>, key: K): V {
+        read_entry(*&table.handle, &key)
+    }
+
+    // Remove the entry for `key` and return its value
+    // Fails with a native function error if `table` has no entry for `key`
+    public fun remove<K: copyable, V>(table: &mut Table<K, V>, key: K): V {
+        let value = remove_entry(*&table.handle, &key);
+        table.length = table.length - 1;
+        value
+    }
+
+    // Set the value of `key`, adding an entry for it if there is none
+    public fun upsert<K: copyable, V: copyable>(table: &mut Table<K, V>, key: K, value: V) {
+        if (contains(table, copy key)) {
+            let _ = remove(table, copy key);
+        };
+        add(table, key, value)
+    }
+
+    // Destroy `table`
+    // Aborts if `table` has entries, which would no longer be reachable
+    public fun destroy_empty<K: copyable, V>(table: Table<K, V>) {
+        let Table { handle: _, length } = table;
+        assert(length == 0, 0);
+    }
+
+    native fun add_entry<K, V>(handle: vector<u8>, key: &K, value: V);
+    native fun read_entry<K, V: copyable>(handle: vector<u8>, key: &K): V;
+    native fun remove_entry<K, V>(handle: vector<u8>, key: &K): V;
+    native fun contains_entry<K>(handle: vector<u8>, key: &K): bool;
+}
+
+}
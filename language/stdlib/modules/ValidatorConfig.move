@@ -8,12 +8,15 @@
 // 1106 -> VALIDATOR_RESOURCE_DOES_NOT_EXIST
 // 1107 -> INVALID_NET
 // 1108 -> INVALID_CONSENSUS_KEY
+// 1109 -> INVALID_NEXT_NETWORK_CONFIG
+// 1110 -> NEXT_NETWORK_CONFIG_IS_NOT_SET
 address 0x1 {
 
 module ValidatorConfig {
     use 0x1::Option::{Self, Option};
     use 0x1::Signature;
     use 0x1::Signer;
+    use 0x1::Vector;
     use 0x1::Roles::{Self, Capability, LibraRootRole};
 
     resource struct UpdateValidatorConfig {}
@@ -29,6 +32,13 @@ module ValidatorConfig {
         validator_network_address: vector<u8>,
         full_node_network_identity_pubkey: vector<u8>,
         full_node_network_address: vector<u8>,
+        // The identity pubkey and address each network switches to once a network key rotation
+        // completes, or empty if no rotation is in progress. Until then peers accept either
+        // identity and may dial either address.
+        validator_network_next_identity_pubkey: vector<u8>,
+        validator_network_next_address: vector<u8>,
+        full_node_network_next_identity_pubkey: vector<u8>,
+        full_node_network_next_address: vector<u8>,
     }
 
     resource struct ValidatorConfig {
@@ -92,13 +102,97 @@ module ValidatorConfig {
         assert(Signature::ed25519_validate_pubkey(copy consensus_pubkey), 1108);
         // TODO(valerini): verify the proof of posession for consensus_pubkey
         let t_ref = borrow_global_mut<ValidatorConfig>(validator_account);
-        t_ref.config = Option::some(Config {
-            consensus_pubkey,
-            validator_network_identity_pubkey,
-            validator_network_address,
-            full_node_network_identity_pubkey,
-            full_node_network_address,
-        });
+        // An announced next network config survives the rotation of the rest of the config
+        if (Option::is_some(&t_ref.config)) {
+            let config_ref = Option::borrow_mut(&mut t_ref.config);
+            config_ref.consensus_pubkey = consensus_pubkey;
+            config_ref.validator_network_identity_pubkey = validator_network_identity_pubkey;
+            config_ref.validator_network_address = validator_network_address;
+            config_ref.full_node_network_identity_pubkey = full_node_network_identity_pubkey;
+            config_ref.full_node_network_address = full_node_network_address;
+        } else {
+            t_ref.config = Option::some(Config {
+                consensus_pubkey,
+                validator_network_identity_pubkey,
+                validator_network_address,
+                full_node_network_identity_pubkey,
+                full_node_network_address,
+                validator_network_next_identity_pubkey: Vector::empty(),
+                validator_network_next_address: Vector::empty(),
+                full_node_network_next_identity_pubkey: Vector::empty(),
+                full_node_network_next_address: Vector::empty(),
+            });
+        }
+    }
+
+    // Announce the identity pubkey and address each network switches to on the next call to
+    // rotate_network_config. A network whose next pubkey and address are both empty keeps its
+    // current identity.
+    // Aborts if the config is not set, or if only one of a network's next pubkey and address is set
+    public fun set_next_network_config(
+        signer: &signer,
+        validator_account: address,
+        validator_network_next_identity_pubkey: vector<u8>,
+        validator_network_next_address: vector<u8>,
+        full_node_network_next_identity_pubkey: vector<u8>,
+        full_node_network_next_address: vector<u8>,
+    ) acquires ValidatorConfig {
+        assert(
+            Signer::address_of(signer) == get_operator(validator_account),
+            1101
+        );
+        assert(
+            Vector::is_empty(&validator_network_next_identity_pubkey) ==
+                Vector::is_empty(&validator_network_next_address),
+            1109
+        );
+        assert(
+            Vector::is_empty(&full_node_network_next_identity_pubkey) ==
+                Vector::is_empty(&full_node_network_next_address),
+            1109
+        );
+        let t_ref = borrow_global_mut<ValidatorConfig>(validator_account);
+        assert(Option::is_some(&t_ref.config), 1104);
+        let config_ref = Option::borrow_mut(&mut t_ref.config);
+        config_ref.validator_network_next_identity_pubkey = validator_network_next_identity_pubkey;
+        config_ref.validator_network_next_address = validator_network_next_address;
+        config_ref.full_node_network_next_identity_pubkey = full_node_network_next_identity_pubkey;
+        config_ref.full_node_network_next_address = full_node_network_next_address;
+    }
+
+    // Switch each network with an announced next identity pubkey and address over to them, and
+    // clear the announcement.
+    // Aborts if the config is not set or no next network config has been announced
+    public fun rotate_network_config(
+        signer: &signer,
+        validator_account: address,
+    ) acquires ValidatorConfig {
+        assert(
+            Signer::address_of(signer) == get_operator(validator_account),
+            1101
+        );
+        let t_ref = borrow_global_mut<ValidatorConfig>(validator_account);
+        assert(Option::is_some(&t_ref.config), 1104);
+        let config_ref = Option::borrow_mut(&mut t_ref.config);
+        assert(
+            !Vector::is_empty(&config_ref.validator_network_next_identity_pubkey) ||
+                !Vector::is_empty(&config_ref.full_node_network_next_identity_pubkey),
+            1110
+        );
+        if (!Vector::is_empty(&config_ref.validator_network_next_identity_pubkey)) {
+            config_ref.validator_network_identity_pubkey =
+                *&config_ref.validator_network_next_identity_pubkey;
+            config_ref.validator_network_address = *&config_ref.validator_network_next_address;
+            config_ref.validator_network_next_identity_pubkey = Vector::empty();
+            config_ref.validator_network_next_address = Vector::empty();
+        };
+        if (!Vector::is_empty(&config_ref.full_node_network_next_identity_pubkey)) {
+            config_ref.full_node_network_identity_pubkey =
+                *&config_ref.full_node_network_next_identity_pubkey;
+            config_ref.full_node_network_address = *&config_ref.full_node_network_next_address;
+            config_ref.full_node_network_next_identity_pubkey = Vector::empty();
+            config_ref.full_node_network_next_address = Vector::empty();
+        };
     }
 
     ///////////////////////////////////////////////////////////////////////////
@@ -147,5 +241,17 @@ module ValidatorConfig {
     public fun get_validator_network_address(config_ref: &Config): &vector<u8> {
         &config_ref.validator_network_address
     }
+
+    // Get validator's next network identity pubkey from Config, empty if none is announced
+    // Never aborts
+    public fun get_validator_network_next_identity_pubkey(config_ref: &Config): &vector<u8> {
+        &config_ref.validator_network_next_identity_pubkey
+    }
+
+    // Get validator's next network address from Config, empty if none is announced
+    // Never aborts
+    public fun get_validator_network_next_address(config_ref: &Config): &vector<u8> {
+        &config_ref.validator_network_next_address
+    }
 }
 }
@@ -5,7 +5,8 @@
 
 use bytecode_verifier::{batch_verify_modules, VerifiedModule};
 use log::LevelFilter;
-use move_lang::{compiled_unit::CompiledUnit, move_compile, shared::Address};
+use move_core_types::errmap::ErrorMapping;
+use move_lang::{compiled_unit::CompiledUnit, move_build_error_map, move_compile, shared::Address};
 use sha2::{Digest, Sha256};
 use std::{
     fs::File,
@@ -23,6 +24,10 @@ pub const COMPILED_OUTPUT_PATH: &str = "compiled";
 pub const COMPILED_STDLIB_NAME: &str = "stdlib";
 /// The extension for compiled files
 pub const COMPILED_EXTENSION: &str = "mv";
+/// The file name for the error map of the compiled stdlib
+pub const COMPILED_ERROR_MAP_NAME: &str = "error_descriptions";
+/// The extension for error map files
+pub const ERROR_MAP_EXTENSION: &str = "errmap";
 /// The file name of the debug module
 pub const DEBUG_MODULE_FILE_NAME: &str = "debug.move";
 
@@ -61,7 +66,7 @@ pub fn stdlib_files() -> Vec<String> {
 
 pub fn build_stdlib() -> Vec<VerifiedModule> {
     let (_, compiled_units) =
-        move_compile(&stdlib_files(), &[], Some(Address::LIBRA_CORE)).unwrap();
+        move_compile(&stdlib_files(), &[], Some(Address::LIBRA_CORE), &[]).unwrap();
     batch_verify_modules(
         compiled_units
             .into_iter()
@@ -73,11 +78,19 @@ pub fn build_stdlib() -> Vec<VerifiedModule> {
     )
 }
 
+/// Builds the error mapping of the stdlib modules, from the error constants they declare.
+pub fn build_stdlib_error_map() -> ErrorMapping {
+    let (_, error_mapping) =
+        move_build_error_map(&stdlib_files(), &[], Some(Address::LIBRA_CORE), &[]).unwrap();
+    error_mapping
+}
+
 pub fn compile_script(source_file_str: String) -> Vec<u8> {
     let (_, mut compiled_program) = move_compile(
         &[source_file_str],
         &stdlib_files(),
         Some(Address::LIBRA_CORE),
+        &[],
     )
     .unwrap();
     let mut script_bytes = vec![];
@@ -11,11 +11,12 @@ use std::{
     time::Instant,
 };
 use stdlib::{
-    build_stdlib, build_stdlib_doc, build_transaction_script_abi, build_transaction_script_doc,
-    compile_script, filter_move_files, generate_rust_transaction_builders, save_binary,
-    COMPILED_EXTENSION, COMPILED_OUTPUT_PATH, COMPILED_STDLIB_NAME,
-    COMPILED_TRANSACTION_SCRIPTS_ABI_DIR, COMPILED_TRANSACTION_SCRIPTS_DIR, STD_LIB_DOC_DIR,
-    TRANSACTION_SCRIPTS, TRANSACTION_SCRIPTS_DOC_DIR,
+    build_stdlib, build_stdlib_doc, build_stdlib_error_map, build_transaction_script_abi,
+    build_transaction_script_doc, compile_script, filter_move_files,
+    generate_rust_transaction_builders, save_binary, COMPILED_ERROR_MAP_NAME, COMPILED_EXTENSION,
+    COMPILED_OUTPUT_PATH, COMPILED_STDLIB_NAME, COMPILED_TRANSACTION_SCRIPTS_ABI_DIR,
+    COMPILED_TRANSACTION_SCRIPTS_DIR, ERROR_MAP_EXTENSION, STD_LIB_DOC_DIR, TRANSACTION_SCRIPTS,
+    TRANSACTION_SCRIPTS_DOC_DIR,
 };
 
 // Generates the compiled stdlib and transaction scripts. Until this is run changes to the source
@@ -76,6 +77,15 @@ fn main() {
                 println!("Compiled module binary has changed");
             };
         });
+        time_it("Creating stdlib error map", || {
+            let mut error_map_path = PathBuf::from(COMPILED_OUTPUT_PATH);
+            error_map_path.push(COMPILED_ERROR_MAP_NAME);
+            error_map_path.set_extension(ERROR_MAP_EXTENSION);
+            let bytes = build_stdlib_error_map().to_bytes().unwrap();
+            if save_binary(&error_map_path, &bytes) {
+                println!("Compiled error map has changed");
+            };
+        });
     }
 
     let txn_source_files =
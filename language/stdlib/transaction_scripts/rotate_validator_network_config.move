@@ -0,0 +1,8 @@
+script {
+    use 0x1::ValidatorConfig;
+
+    /// Switch the validator's networks to their announced identity keys and addresses.
+    fun rotate_validator_network_config(account: &signer, validator_account: address) {
+        ValidatorConfig::rotate_network_config(account, validator_account);
+    }
+}
@@ -0,0 +1,10 @@
+script {
+use 0x1::LibraModulePublishingPolicy;
+
+/// Set the module publishing policy: `policy` is 0 to let any account publish modules, 1 to only
+/// let the accounts of `allowed_publishers` publish modules, and 2 to let no account publish
+/// modules.
+fun set_module_publishing_policy(account: &signer, policy: u8, allowed_publishers: vector<address>) {
+    LibraModulePublishingPolicy::set(account, policy, allowed_publishers)
+}
+}
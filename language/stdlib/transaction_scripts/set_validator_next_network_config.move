@@ -0,0 +1,22 @@
+script {
+    use 0x1::ValidatorConfig;
+
+    /// Announce the network identity keys and addresses the validator rotates to next.
+    fun set_validator_next_network_config(
+        account: &signer,
+        validator_account: address,
+        validator_network_next_identity_pubkey: vector<u8>,
+        validator_network_next_address: vector<u8>,
+        fullnodes_network_next_identity_pubkey: vector<u8>,
+        fullnodes_network_next_address: vector<u8>,
+    ) {
+        ValidatorConfig::set_next_network_config(
+            account,
+            validator_account,
+            validator_network_next_identity_pubkey,
+            validator_network_next_address,
+            fullnodes_network_next_identity_pubkey,
+            fullnodes_network_next_address
+        );
+     }
+}
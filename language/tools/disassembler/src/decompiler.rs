@@ -0,0 +1,883 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A best-effort decompiler of function bodies into pseudo-Move, on top of the disassembler.
+//!
+//! Expressions are rebuilt from the operand stack. `if`/`else`, `while` and `loop` are recovered
+//! from the branches as laid out by the Move compilers, and the branches which cannot be
+//! structured are printed as a `goto` to a label. Locals are named after the source map when
+//! there is one.
+
+use crate::disassembler::Disassembler;
+use anyhow::{format_err, Result};
+use bytecode_source_map::source_map::FunctionSourceMap;
+use move_core_types::value::MoveValue;
+use std::collections::BTreeSet;
+use vm::{
+    access::ModuleAccess,
+    file_format::{
+        Bytecode, CodeOffset, CompiledModule, ConstantPoolIndex, FieldHandleIndex,
+        FunctionDefinitionIndex, FunctionHandleIndex, Signature, SignatureIndex,
+        StructDefInstantiationIndex, StructDefinitionIndex, StructFieldInformation,
+    },
+};
+
+/// A line of decompiled code, or the label of a code offset, which is only printed if it is the
+/// target of a `goto`.
+enum Line {
+    Code(usize, String),
+    Label(CodeOffset),
+}
+
+pub(crate) struct Decompiler<'a, Location: Clone + Eq> {
+    disassembler: &'a Disassembler<Location>,
+    code: &'a [Bytecode],
+    parameters: &'a Signature,
+    // The types of the locals, including the parameters.
+    locals: &'a Signature,
+    return_count: usize,
+    function_source_map: &'a FunctionSourceMap<Location>,
+    // The headers and exits of the loops enclosing the code being decompiled.
+    loops: Vec<(CodeOffset, CodeOffset)>,
+    // The targets of the `goto`s printed so far.
+    goto_targets: BTreeSet<CodeOffset>,
+    // The number of temporaries introduced so far, for the values which are not stored in locals.
+    temp_count: usize,
+    lines: Vec<Line>,
+}
+
+impl<'a, Location: Clone + Eq> Decompiler<'a, Location> {
+    pub(crate) fn new(
+        disassembler: &'a Disassembler<Location>,
+        function_definition_index: FunctionDefinitionIndex,
+    ) -> Result<Self> {
+        let bytecode = &disassembler.source_mapper.bytecode;
+        let function_definition = bytecode.function_def_at(function_definition_index);
+        let code = function_definition
+            .code
+            .as_ref()
+            .ok_or_else(|| format_err!("Unable to decompile a native function"))?;
+        let function_handle = bytecode.function_handle_at(function_definition.function);
+        let function_source_map = disassembler
+            .source_mapper
+            .source_map
+            .get_function_source_map(function_definition_index)?;
+        Ok(Self {
+            disassembler,
+            code: &code.code,
+            parameters: bytecode.signature_at(function_handle.parameters),
+            locals: bytecode.signature_at(code.locals),
+            return_count: bytecode.signature_at(function_handle.return_).len(),
+            function_source_map,
+            loops: vec![],
+            goto_targets: BTreeSet::new(),
+            temp_count: 0,
+            lines: vec![],
+        })
+    }
+
+    /// Returns the lines of the decompiled function body, without the enclosing braces.
+    pub(crate) fn decompile(mut self) -> Result<Vec<String>> {
+        for local_idx in self.parameters.len()..self.locals.len() {
+            let name = self.local_name(local_idx)?;
+            let ty = self.disassembler.disassemble_sig_tok(
+                self.locals.0[local_idx].clone(),
+                &self.function_source_map.type_parameters,
+            )?;
+            self.emit(1, format!("let {}: {};", name, ty));
+        }
+        self.range(0, self.code.len() as CodeOffset, 1, None)?;
+
+        let mut printed_labels = BTreeSet::new();
+        let goto_targets = self.goto_targets;
+        Ok(self
+            .lines
+            .into_iter()
+            .filter_map(|line| match line {
+                Line::Code(depth, code) => Some(format!("{}{}", "    ".repeat(depth), code)),
+                Line::Label(offset)
+                    if goto_targets.contains(&offset) && printed_labels.insert(offset) =>
+                {
+                    Some(format!("L{}:", offset))
+                }
+                Line::Label(_) => None,
+            })
+            .collect())
+    }
+
+    //***************************************************************************
+    // Control Flow
+    //***************************************************************************
+
+    // Decompiles the code in [start, end). `loop_header` is the offset of the loop being
+    // decompiled, if the range is the body of a `loop`.
+    fn range(
+        &mut self,
+        start: CodeOffset,
+        end: CodeOffset,
+        depth: usize,
+        loop_header: Option<CodeOffset>,
+    ) -> Result<()> {
+        let code = self.code;
+        let mut stack = vec![];
+        let mut pc = start;
+        while pc < end {
+            self.lines.push(Line::Label(pc));
+            if loop_header != Some(pc) {
+                if let Some(back_edge) = self.back_edge(pc, end) {
+                    self.flush(&mut stack, depth);
+                    pc = self.loop_(pc, back_edge, depth)?;
+                    continue;
+                }
+            }
+            pc = match &code[pc as usize] {
+                Bytecode::BrTrue(_) | Bytecode::BrFalse(_) => {
+                    self.conditional(pc, end, &mut stack, depth)?
+                }
+                Bytecode::Branch(target) => {
+                    let target = *target;
+                    self.flush(&mut stack, depth);
+                    self.jump(target, pc + 1, depth)
+                }
+                _ => {
+                    self.instruction(pc, &mut stack, depth)?;
+                    pc + 1
+                }
+            };
+        }
+        self.flush(&mut stack, depth);
+        Ok(())
+    }
+
+    // Returns the offset of the last unconditional branch back to `header` in [header, end), if
+    // any, in which case `header` starts a loop.
+    fn back_edge(&self, header: CodeOffset, end: CodeOffset) -> Option<CodeOffset> {
+        (header..end).rev().find(
+            |pc| matches!(self.code[*pc as usize], Bytecode::Branch(target) if target == header),
+        )
+    }
+
+    // Decompiles the loop starting at `header`, returning the offset of its exit.
+    fn loop_(
+        &mut self,
+        header: CodeOffset,
+        back_edge: CodeOffset,
+        depth: usize,
+    ) -> Result<CodeOffset> {
+        let exit = back_edge + 1;
+        self.loops.push((header, exit));
+        match self.while_condition(header, back_edge)? {
+            Some((cond, body)) => {
+                self.emit(depth, format!("while ({}) {{", unparen(&cond)));
+                self.range(body, back_edge, depth + 1, None)?;
+            }
+            None => {
+                self.emit(depth, "loop {");
+                self.range(header, back_edge, depth + 1, Some(header))?;
+            }
+        }
+        self.emit(depth, "}");
+        self.loops.pop();
+        Ok(exit)
+    }
+
+    // Returns the condition of the loop starting at `header` and the offset of its body, if the
+    // loop starts with an expression exiting the loop when it does not hold.
+    fn while_condition(
+        &mut self,
+        header: CodeOffset,
+        back_edge: CodeOffset,
+    ) -> Result<Option<(String, CodeOffset)>> {
+        let (lines_len, temp_count) = (self.lines.len(), self.temp_count);
+        let exit = back_edge + 1;
+        let mut stack = vec![];
+        let mut result = None;
+        let code = self.code;
+        for pc in header..back_edge {
+            match &code[pc as usize] {
+                Bytecode::BrTrue(_) | Bytecode::BrFalse(_) => {
+                    if stack.len() == 1 {
+                        let cond = stack.pop().unwrap();
+                        let (cond, on_true, on_false, next) = self.branch_targets(pc, cond);
+                        if on_true == next && on_false == exit {
+                            result = Some((cond, next));
+                        } else if on_false == next && on_true == exit {
+                            result = Some((negate(&cond), next));
+                        }
+                    }
+                    break;
+                }
+                Bytecode::Branch(_) | Bytecode::Ret | Bytecode::Abort => break,
+                _ => {
+                    self.instruction(pc, &mut stack, 0)?;
+                    // The condition must be an expression, without statements
+                    if self.lines.len() != lines_len {
+                        break;
+                    }
+                }
+            }
+        }
+        if result.is_none() {
+            self.lines.truncate(lines_len);
+            self.temp_count = temp_count;
+        }
+        Ok(result)
+    }
+
+    // Returns the condition of the conditional branch at `pc`, where it goes when the condition
+    // holds and when it does not, and the offset following the branch. The Move compiler follows
+    // a conditional branch with an unconditional branch for the other case, which is skipped.
+    fn branch_targets(
+        &self,
+        pc: CodeOffset,
+        cond: String,
+    ) -> (String, CodeOffset, CodeOffset, CodeOffset) {
+        let (on_true, on_false) = match &self.code[pc as usize] {
+            Bytecode::BrTrue(target) => (*target, pc + 1),
+            Bytecode::BrFalse(target) => (pc + 1, *target),
+            _ => unreachable!("Not a conditional branch"),
+        };
+        match self.code.get(pc as usize + 1) {
+            Some(Bytecode::Branch(target)) if on_false == pc + 1 => {
+                (cond, on_true, *target, pc + 2)
+            }
+            Some(Bytecode::Branch(target)) if on_true == pc + 1 => {
+                (cond, *target, on_false, pc + 2)
+            }
+            _ => (cond, on_true, on_false, pc + 1),
+        }
+    }
+
+    // Decompiles the conditional branch at `pc`, returning the offset where decompilation
+    // continues.
+    fn conditional(
+        &mut self,
+        pc: CodeOffset,
+        end: CodeOffset,
+        stack: &mut Vec<String>,
+        depth: usize,
+    ) -> Result<CodeOffset> {
+        let cond = pop(stack);
+        self.flush(stack, depth);
+        let (cond, on_true, on_false, next) = self.branch_targets(pc, cond);
+        if let Some(jump) = self.loop_jump(on_true) {
+            self.emit(depth, format!("if ({}) {};", unparen(&cond), jump));
+            return Ok(self.jump(on_false, next, depth));
+        }
+        if let Some(jump) = self.loop_jump(on_false) {
+            self.emit(depth, format!("if ({}) {};", unparen(&negate(&cond)), jump));
+            return Ok(self.jump(on_true, next, depth));
+        }
+
+        let (cond, then_start, else_start) = if on_true == next {
+            (cond, on_true, on_false)
+        } else if on_false == next {
+            (negate(&cond), on_false, on_true)
+        } else {
+            let (goto_true, goto_false) = (self.goto(on_true), self.goto(on_false));
+            self.emit(
+                depth,
+                format!("if ({}) {} else {};", unparen(&cond), goto_true, goto_false),
+            );
+            return Ok(next);
+        };
+        if else_start <= then_start || else_start > end {
+            let goto = self.goto(else_start);
+            self.emit(depth, format!("if ({}) {};", unparen(&negate(&cond)), goto));
+            return Ok(then_start);
+        }
+
+        self.emit(depth, format!("if ({}) {{", unparen(&cond)));
+        // The `then` branch of an `if` with an `else` ends by jumping over the `else` branch.
+        let join = match &self.code[else_start as usize - 1] {
+            Bytecode::Branch(join) if *join > else_start && *join <= end => Some(*join),
+            _ => None,
+        };
+        match join {
+            Some(join) => {
+                self.range(then_start, else_start - 1, depth + 1, None)?;
+                self.emit(depth, "} else {");
+                self.range(else_start, join, depth + 1, None)?;
+                self.emit(depth, "}");
+                Ok(join)
+            }
+            None => {
+                self.range(then_start, else_start, depth + 1, None)?;
+                self.emit(depth, "}");
+                Ok(else_start)
+            }
+        }
+    }
+
+    // Decompiles a jump to `target`, from code which would otherwise continue at `next`.
+    fn jump(&mut self, target: CodeOffset, next: CodeOffset, depth: usize) -> CodeOffset {
+        if target != next {
+            let jump = match self.loop_jump(target) {
+                Some(jump) => jump.to_string(),
+                None => self.goto(target),
+            };
+            self.emit(depth, format!("{};", jump));
+        }
+        next
+    }
+
+    // Returns `continue` or `break` if `target` is the header or the exit of the innermost loop.
+    fn loop_jump(&self, target: CodeOffset) -> Option<&'static str> {
+        match self.loops.last() {
+            Some((header, _)) if *header == target => Some("continue"),
+            Some((_, exit)) if *exit == target => Some("break"),
+            _ => None,
+        }
+    }
+
+    fn goto(&mut self, target: CodeOffset) -> String {
+        self.goto_targets.insert(target);
+        format!("goto L{}", target)
+    }
+
+    //***************************************************************************
+    // Expressions and Statements
+    //***************************************************************************
+
+    // Decompiles an instruction which is not a branch, pushing the expressions it computes on
+    // the stack and emitting the statements it performs.
+    fn instruction(&mut self, pc: CodeOffset, stack: &mut Vec<String>, depth: usize) -> Result<()> {
+        let code = self.code;
+        match &code[pc as usize] {
+            Bytecode::Pop => {
+                let e = pop(stack);
+                self.emit(depth, format!("_ = {};", unparen(&e)));
+            }
+            Bytecode::Ret => {
+                let values = pop_n(stack, self.return_count);
+                self.flush(stack, depth);
+                match values.len() {
+                    0 => self.emit(depth, "return;"),
+                    1 => self.emit(depth, format!("return {};", unparen(&values[0]))),
+                    _ => self.emit(depth, format!("return ({});", values.join(", "))),
+                }
+            }
+            Bytecode::Abort => {
+                let e = pop(stack);
+                self.flush(stack, depth);
+                self.emit(depth, format!("abort {};", unparen(&e)));
+            }
+            Bytecode::BrTrue(_) | Bytecode::BrFalse(_) | Bytecode::Branch(_) => {
+                unreachable!("Branches are decompiled with the control flow")
+            }
+            Bytecode::Nop => (),
+
+            Bytecode::LdU8(n) => stack.push(format!("{}u8", n)),
+            Bytecode::LdU64(n) => stack.push(format!("{}", n)),
+            Bytecode::LdU128(n) => stack.push(format!("{}u128", n)),
+            Bytecode::LdU256(n) => stack.push(format!("{}u256", n)),
+            Bytecode::LdConst(idx) => stack.push(self.constant(*idx)),
+            Bytecode::LdTrue => stack.push("true".to_string()),
+            Bytecode::LdFalse => stack.push("false".to_string()),
+            Bytecode::CastU8 => cast(stack, "u8"),
+            Bytecode::CastU64 => cast(stack, "u64"),
+            Bytecode::CastU128 => cast(stack, "u128"),
+            Bytecode::CastU256 => cast(stack, "u256"),
+
+            Bytecode::CopyLoc(idx) | Bytecode::MoveLoc(idx) => {
+                stack.push(self.local_name(*idx as usize)?)
+            }
+            Bytecode::StLoc(idx) => {
+                let e = pop(stack);
+                let name = self.local_name(*idx as usize)?;
+                self.emit(depth, format!("{} = {};", name, unparen(&e)));
+            }
+            Bytecode::ImmBorrowLoc(idx) => {
+                stack.push(format!("&{}", self.local_name(*idx as usize)?))
+            }
+            Bytecode::MutBorrowLoc(idx) => {
+                stack.push(format!("&mut {}", self.local_name(*idx as usize)?))
+            }
+            Bytecode::ImmBorrowField(idx) => self.borrow_field(stack, *idx, "&")?,
+            Bytecode::MutBorrowField(idx) => self.borrow_field(stack, *idx, "&mut ")?,
+            Bytecode::ImmBorrowFieldGeneric(idx) => {
+                let handle = self.bytecode().field_instantiation_at(*idx).handle;
+                self.borrow_field(stack, handle, "&")?
+            }
+            Bytecode::MutBorrowFieldGeneric(idx) => {
+                let handle = self.bytecode().field_instantiation_at(*idx).handle;
+                self.borrow_field(stack, handle, "&mut ")?
+            }
+            Bytecode::ReadRef => {
+                let r = pop(stack);
+                stack.push(match borrowed(&r) {
+                    Some(place) => place.to_string(),
+                    None => format!("*{}", r),
+                })
+            }
+            Bytecode::WriteRef => {
+                let r = pop(stack);
+                let e = pop(stack);
+                let place = match borrowed(&r) {
+                    Some(place) => place.to_string(),
+                    None => format!("*{}", r),
+                };
+                self.emit(depth, format!("{} = {};", place, unparen(&e)));
+            }
+            Bytecode::FreezeRef => {
+                let r = pop(stack);
+                stack.push(format!("freeze({})", unparen(&r)))
+            }
+
+            Bytecode::Call(idx) => self.call(stack, *idx, "".to_string(), depth)?,
+            Bytecode::CallGeneric(idx) => {
+                let instantiation = self.bytecode().function_instantiation_at(*idx);
+                let type_args = self.type_args(instantiation.type_parameters)?;
+                self.call(stack, instantiation.handle, type_args, depth)?
+            }
+
+            Bytecode::Pack(idx) => self.pack(stack, *idx, Signature(vec![]))?,
+            Bytecode::PackGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.pack(stack, def, signature)?
+            }
+            Bytecode::Unpack(idx) => self.unpack(stack, *idx, Signature(vec![]), depth)?,
+            Bytecode::UnpackGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.unpack(stack, def, signature, depth)?
+            }
+            Bytecode::Exists(idx) => self.global(stack, "exists", *idx, Signature(vec![]))?,
+            Bytecode::ExistsGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.global(stack, "exists", def, signature)?
+            }
+            Bytecode::ImmBorrowGlobal(idx) => {
+                self.global(stack, "borrow_global", *idx, Signature(vec![]))?
+            }
+            Bytecode::ImmBorrowGlobalGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.global(stack, "borrow_global", def, signature)?
+            }
+            Bytecode::MutBorrowGlobal(idx) => {
+                self.global(stack, "borrow_global_mut", *idx, Signature(vec![]))?
+            }
+            Bytecode::MutBorrowGlobalGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.global(stack, "borrow_global_mut", def, signature)?
+            }
+            Bytecode::MoveFrom(idx) => self.global(stack, "move_from", *idx, Signature(vec![]))?,
+            Bytecode::MoveFromGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.global(stack, "move_from", def, signature)?
+            }
+            Bytecode::MoveToSender(idx) => {
+                self.global(stack, "move_to_sender", *idx, Signature(vec![]))?;
+                self.statement(stack, depth)
+            }
+            Bytecode::MoveToSenderGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.global(stack, "move_to_sender", def, signature)?;
+                self.statement(stack, depth)
+            }
+            Bytecode::MoveTo(idx) => {
+                self.move_to(stack, *idx, Signature(vec![]))?;
+                self.statement(stack, depth)
+            }
+            Bytecode::MoveToGeneric(idx) => {
+                let (def, signature) = self.struct_instantiation(*idx);
+                self.move_to(stack, def, signature)?;
+                self.statement(stack, depth)
+            }
+            Bytecode::GetTxnSenderAddress => stack.push("get_txn_sender()".to_string()),
+
+            Bytecode::Add => binary(stack, "+"),
+            Bytecode::Sub => binary(stack, "-"),
+            Bytecode::Mul => binary(stack, "*"),
+            Bytecode::Mod => binary(stack, "%"),
+            Bytecode::Div => binary(stack, "/"),
+            Bytecode::BitOr => binary(stack, "|"),
+            Bytecode::BitAnd => binary(stack, "&"),
+            Bytecode::Xor => binary(stack, "^"),
+            Bytecode::Shl => binary(stack, "<<"),
+            Bytecode::Shr => binary(stack, ">>"),
+            Bytecode::Or => binary(stack, "||"),
+            Bytecode::And => binary(stack, "&&"),
+            Bytecode::Eq => binary(stack, "=="),
+            Bytecode::Neq => binary(stack, "!="),
+            Bytecode::Lt => binary(stack, "<"),
+            Bytecode::Gt => binary(stack, ">"),
+            Bytecode::Le => binary(stack, "<="),
+            Bytecode::Ge => binary(stack, ">="),
+            Bytecode::Not => {
+                let e = pop(stack);
+                stack.push(negate(&e))
+            }
+
+            Bytecode::VecPack(_, n) => {
+                let elements = pop_n(stack, *n as usize);
+                stack.push(format!("vector[{}]", elements.join(", ")))
+            }
+            Bytecode::VecLen(_) => vector_op(stack, "length", 1),
+            Bytecode::VecImmBorrow(_) => vector_op(stack, "borrow", 2),
+            Bytecode::VecMutBorrow(_) => vector_op(stack, "borrow_mut", 2),
+            Bytecode::VecPopBack(_) => vector_op(stack, "pop_back", 1),
+            Bytecode::VecPushBack(_) => {
+                vector_op(stack, "push_back", 2);
+                self.statement(stack, depth)
+            }
+            Bytecode::VecSwap(_) => {
+                vector_op(stack, "swap", 3);
+                self.statement(stack, depth)
+            }
+            Bytecode::VecUnpack(_, n) => {
+                vector_op(stack, "unpack", 1);
+                let e = pop(stack);
+                self.bind(stack, e, *n as usize, depth)
+            }
+        }
+        Ok(())
+    }
+
+    fn borrow_field(
+        &self,
+        stack: &mut Vec<String>,
+        field_idx: FieldHandleIndex,
+        borrow: &str,
+    ) -> Result<()> {
+        let r = pop(stack);
+        let field = self.field_name(field_idx)?;
+        let place = borrowed(&r).unwrap_or(&r);
+        stack.push(format!("{}{}.{}", borrow, place, field));
+        Ok(())
+    }
+
+    fn call(
+        &mut self,
+        stack: &mut Vec<String>,
+        function_handle_idx: FunctionHandleIndex,
+        type_args: String,
+        depth: usize,
+    ) -> Result<()> {
+        let bytecode = self.bytecode();
+        let function_handle = bytecode.function_handle_at(function_handle_idx);
+        let name = bytecode.identifier_at(function_handle.name);
+        let name = if function_handle.module == bytecode.self_handle_idx() {
+            name.to_string()
+        } else {
+            let module_handle = bytecode.module_handle_at(function_handle.module);
+            format!("{}::{}", bytecode.identifier_at(module_handle.name), name)
+        };
+        let arg_count = bytecode.signature_at(function_handle.parameters).len();
+        let return_count = bytecode.signature_at(function_handle.return_).len();
+        let args = pop_n(stack, arg_count);
+        let call = format!("{}{}({})", name, type_args, args.join(", "));
+        self.bind(stack, call, return_count, depth);
+        Ok(())
+    }
+
+    fn pack(
+        &self,
+        stack: &mut Vec<String>,
+        struct_idx: StructDefinitionIndex,
+        signature: Signature,
+    ) -> Result<()> {
+        let (name, type_args) = self.disassembler.struct_type_info(struct_idx, &signature)?;
+        let field_names = self.field_names(struct_idx);
+        let values = pop_n(stack, field_names.len());
+        let fields = field_names
+            .iter()
+            .zip(values.iter())
+            .map(|(field, value)| format!("{}: {}", field, unparen(value)))
+            .collect::<Vec<_>>();
+        stack.push(format!("{}{} {{ {} }}", name, type_args, fields.join(", ")));
+        Ok(())
+    }
+
+    fn unpack(
+        &mut self,
+        stack: &mut Vec<String>,
+        struct_idx: StructDefinitionIndex,
+        signature: Signature,
+        depth: usize,
+    ) -> Result<()> {
+        let (name, type_args) = self.disassembler.struct_type_info(struct_idx, &signature)?;
+        let e = pop(stack);
+        let fields = self
+            .field_names(struct_idx)
+            .into_iter()
+            .map(|field| {
+                let temp = self.temp();
+                stack.push(temp.clone());
+                format!("{}: {}", field, temp)
+            })
+            .collect::<Vec<_>>();
+        self.emit(
+            depth,
+            format!(
+                "let {}{} {{ {} }} = {};",
+                name,
+                type_args,
+                fields.join(", "),
+                unparen(&e)
+            ),
+        );
+        Ok(())
+    }
+
+    fn global(
+        &self,
+        stack: &mut Vec<String>,
+        operation: &str,
+        struct_idx: StructDefinitionIndex,
+        signature: Signature,
+    ) -> Result<()> {
+        let (name, type_args) = self.disassembler.struct_type_info(struct_idx, &signature)?;
+        let e = pop(stack);
+        stack.push(format!(
+            "{}<{}{}>({})",
+            operation,
+            name,
+            type_args,
+            unparen(&e)
+        ));
+        Ok(())
+    }
+
+    fn move_to(
+        &self,
+        stack: &mut Vec<String>,
+        struct_idx: StructDefinitionIndex,
+        signature: Signature,
+    ) -> Result<()> {
+        let (name, type_args) = self.disassembler.struct_type_info(struct_idx, &signature)?;
+        let value = pop(stack);
+        let signer = pop(stack);
+        stack.push(format!(
+            "move_to<{}{}>({}, {})",
+            name,
+            type_args,
+            unparen(&signer),
+            unparen(&value)
+        ));
+        Ok(())
+    }
+
+    // Makes the `count` values computed by `e` available on the stack, binding them to
+    // temporaries if there is more than one.
+    fn bind(&mut self, stack: &mut Vec<String>, e: String, count: usize, depth: usize) {
+        match count {
+            0 => self.emit(depth, format!("{};", unparen(&e))),
+            1 => stack.push(e),
+            _ => {
+                let temps = (0..count).map(|_| self.temp()).collect::<Vec<_>>();
+                self.emit(
+                    depth,
+                    format!("let ({}) = {};", temps.join(", "), unparen(&e)),
+                );
+                stack.extend(temps);
+            }
+        }
+    }
+
+    // Emits the expression on top of the stack as a statement.
+    fn statement(&mut self, stack: &mut Vec<String>, depth: usize) {
+        let e = pop(stack);
+        self.emit(depth, format!("{};", unparen(&e)));
+    }
+
+    // Emits the expressions left on the stack as statements, before control flow.
+    fn flush(&mut self, stack: &mut Vec<String>, depth: usize) {
+        for e in stack.drain(..) {
+            self.lines
+                .push(Line::Code(depth, format!("{};", unparen(&e))));
+        }
+    }
+
+    fn emit(&mut self, depth: usize, code: impl Into<String>) {
+        self.lines.push(Line::Code(depth, code.into()));
+    }
+
+    fn temp(&mut self) -> String {
+        self.temp_count += 1;
+        format!("$t{}", self.temp_count - 1)
+    }
+
+    //***************************************************************************
+    // Names
+    //***************************************************************************
+
+    fn bytecode(&self) -> &'a CompiledModule {
+        &self.disassembler.source_mapper.bytecode
+    }
+
+    fn local_name(&self, local_idx: usize) -> Result<String> {
+        self.disassembler
+            .name_for_parameter_or_local(local_idx, self.function_source_map)
+    }
+
+    fn field_names(&self, struct_idx: StructDefinitionIndex) -> Vec<String> {
+        let bytecode = self.bytecode();
+        match &bytecode.struct_def_at(struct_idx).field_information {
+            StructFieldInformation::Native => vec![],
+            StructFieldInformation::Declared(fields) => fields
+                .iter()
+                .map(|field| bytecode.identifier_at(field.name).to_string())
+                .collect(),
+        }
+    }
+
+    fn field_name(&self, field_idx: FieldHandleIndex) -> Result<String> {
+        let field_handle = self.bytecode().field_handle_at(field_idx);
+        self.field_names(field_handle.owner)
+            .get(field_handle.field as usize)
+            .cloned()
+            .ok_or_else(|| format_err!("Bad field index"))
+    }
+
+    fn struct_instantiation(
+        &self,
+        idx: StructDefInstantiationIndex,
+    ) -> (StructDefinitionIndex, Signature) {
+        let bytecode = self.bytecode();
+        let struct_instantiation = bytecode.struct_instantiation_at(idx);
+        (
+            struct_instantiation.def,
+            bytecode
+                .signature_at(struct_instantiation.type_parameters)
+                .clone(),
+        )
+    }
+
+    fn type_args(&self, signature_idx: SignatureIndex) -> Result<String> {
+        let type_args = self
+            .bytecode()
+            .signature_at(signature_idx)
+            .0
+            .iter()
+            .map(|sig_tok| {
+                self.disassembler
+                    .disassemble_sig_tok(sig_tok.clone(), &self.function_source_map.type_parameters)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Disassembler::<Location>::format_type_params(&type_args))
+    }
+
+    fn constant(&self, idx: ConstantPoolIndex) -> String {
+        match self.bytecode().constant_at(idx).deserialize_constant() {
+            Some(value) => format_value(&value),
+            None => format!("CONSTANT_{}", idx),
+        }
+    }
+}
+
+//***************************************************************************
+// Formatting Helpers
+//***************************************************************************
+
+fn pop(stack: &mut Vec<String>) -> String {
+    // The stack can only be unbalanced for code which could not be structured.
+    stack.pop().unwrap_or_else(|| "?".to_string())
+}
+
+// Pops the last `n` values of the stack, in the order they were pushed.
+fn pop_n(stack: &mut Vec<String>, n: usize) -> Vec<String> {
+    let mut values = (0..n).map(|_| pop(stack)).collect::<Vec<_>>();
+    values.reverse();
+    values
+}
+
+fn binary(stack: &mut Vec<String>, op: &str) {
+    let rhs = pop(stack);
+    let lhs = pop(stack);
+    stack.push(format!("({} {} {})", lhs, op, rhs));
+}
+
+fn cast(stack: &mut Vec<String>, ty: &str) {
+    let e = pop(stack);
+    stack.push(format!("({} as {})", e, ty));
+}
+
+fn vector_op(stack: &mut Vec<String>, function: &str, arg_count: usize) {
+    let args = pop_n(stack, arg_count)
+        .iter()
+        .map(|arg| unparen(arg).to_string())
+        .collect::<Vec<_>>();
+    stack.push(format!("Vector::{}({})", function, args.join(", ")));
+}
+
+fn negate(e: &str) -> String {
+    if e.starts_with('!') {
+        e[1..].to_string()
+    } else if is_parenthesized(e) || !e.contains(' ') {
+        format!("!{}", e)
+    } else {
+        format!("!({})", e)
+    }
+}
+
+// Returns the place borrowed by a borrow expression.
+fn borrowed(r: &str) -> Option<&str> {
+    if r.starts_with("&mut ") {
+        Some(&r[5..])
+    } else if r.starts_with('&') {
+        Some(&r[1..])
+    } else {
+        None
+    }
+}
+
+fn unparen(e: &str) -> &str {
+    if is_parenthesized(e) {
+        &e[1..e.len() - 1]
+    } else {
+        e
+    }
+}
+
+// Returns true if the expression is enclosed in a pair of matching parentheses.
+fn is_parenthesized(e: &str) -> bool {
+    if !e.starts_with('(') || !e.ends_with(')') {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, c) in e.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == e.len() - 1;
+                }
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+fn format_value(value: &MoveValue) -> String {
+    match value {
+        MoveValue::U8(n) => format!("{}u8", n),
+        MoveValue::U64(n) => format!("{}", n),
+        MoveValue::U128(n) => format!("{}u128", n),
+        MoveValue::U256(n) => format!("{}u256", n),
+        MoveValue::Bool(b) => format!("{}", b),
+        MoveValue::Address(address) | MoveValue::Signer(address) => format!("0x{}", address),
+        MoveValue::Vector(values) => {
+            let bytes = values
+                .iter()
+                .map(|value| match value {
+                    MoveValue::U8(byte) => Some(format!("{:02x}", byte)),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            match bytes {
+                Some(bytes) if !bytes.is_empty() => format!("x\"{}\"", bytes.join("")),
+                _ => format!(
+                    "vector[{}]",
+                    values
+                        .iter()
+                        .map(format_value)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+        MoveValue::Struct(_) => format!("{:?}", value),
+    }
+}
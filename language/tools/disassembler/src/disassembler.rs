@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::decompiler::Decompiler;
 use anyhow::{bail, format_err, Result};
 use bytecode_source_map::{
     mapping::SourceMapping,
@@ -33,6 +34,9 @@ pub struct DisassemblerOptions {
 
     /// Print the locals inside each function body.
     pub print_locals: bool,
+
+    /// Print the function bodies as pseudo-Move source code instead of bytecode.
+    pub decompile: bool,
 }
 
 impl DisassemblerOptions {
@@ -42,12 +46,13 @@ impl DisassemblerOptions {
             print_code: false,
             print_basic_blocks: false,
             print_locals: false,
+            decompile: false,
         }
     }
 }
 
 pub struct Disassembler<Location: Clone + Eq> {
-    source_mapper: SourceMapping<Location>,
+    pub(crate) source_mapper: SourceMapping<Location>,
     // The various options that we can set for disassembly.
     options: DisassemblerOptions,
     // Optional coverage map for use in displaying code coverage
@@ -201,7 +206,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         Ok(ty)
     }
 
-    fn struct_type_info(
+    pub(crate) fn struct_type_info(
         &self,
         struct_idx: StructDefinitionIndex,
         signature: &Signature,
@@ -231,7 +236,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         Ok((name, Self::format_type_params(&type_arguments)))
     }
 
-    fn name_for_parameter_or_local(
+    pub(crate) fn name_for_parameter_or_local(
         &self,
         local_idx: usize,
         function_source_map: &FunctionSourceMap<Location>,
@@ -277,7 +282,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         self.disassemble_sig_tok(sig_tok.clone(), &function_source_map.type_parameters)
     }
 
-    fn format_type_params(ty_params: &[String]) -> String {
+    pub(crate) fn format_type_params(ty_params: &[String]) -> String {
         if ty_params.is_empty() {
             "".to_string()
         } else {
@@ -313,7 +318,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
 
     // These need to be in the context of a function or a struct definition since type parameters
     // can refer to function/struct type parameters.
-    fn disassemble_sig_tok(
+    pub(crate) fn disassemble_sig_tok(
         &self,
         sig_tok: SignatureToken,
         type_param_context: &[SourceName<Location>],
@@ -323,6 +328,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
             SignatureToken::U8 => "u8".to_string(),
             SignatureToken::U64 => "u64".to_string(),
             SignatureToken::U128 => "u128".to_string(),
+            SignatureToken::U256 => "u256".to_string(),
             SignatureToken::Address => "address".to_string(),
             SignatureToken::Signer => "signer".to_string(),
             SignatureToken::Struct(struct_handle_idx) => self
@@ -850,6 +856,7 @@ impl<Location: Clone + Eq> Disassembler<Location> {
             return Ok("".to_string());
         }
 
+        let fun_keyword = if self.options.decompile { "fun " } else { "" };
         let visibility_modifier = if function_definition.is_native() {
             "native "
         } else if function_definition.is_public() {
@@ -898,6 +905,10 @@ impl<Location: Clone + Eq> Disassembler<Location> {
             .collect::<Result<Vec<_>>>()?;
 
         let body = match &function_definition.code {
+            Some(_) if self.options.decompile => {
+                let lines = Decompiler::new(self, function_definition_index)?.decompile()?;
+                format!(" {{\n{}\n}}", lines.join("\n"))
+            }
             Some(code) => {
                 let locals =
                     self.disassemble_locals(function_source_map, code.locals, parameters.len())?;
@@ -909,8 +920,9 @@ impl<Location: Clone + Eq> Disassembler<Location> {
         Ok(self.format_function_coverage(
             name,
             format!(
-                "{visibility_modifier}{name}{ty_params}({params}){ret_type}{body}",
+                "{visibility_modifier}{fun_keyword}{name}{ty_params}({params}){ret_type}{body}",
                 visibility_modifier = visibility_modifier,
+                fun_keyword = fun_keyword,
                 name = name,
                 ty_params = ty_params,
                 params = &parameters.join(", "),
@@ -37,6 +37,10 @@ struct Args {
     #[structopt(long = "skip-basic-blocks")]
     pub skip_basic_blocks: bool,
 
+    /// Print the function bodies as pseudo-Move source code, reconstructed from the bytecode.
+    #[structopt(long = "decompile")]
+    pub decompile: bool,
+
     /// Treat input file as a script (default is to treat file as a module)
     #[structopt(short = "s", long = "script")]
     pub is_script: bool,
@@ -86,6 +90,7 @@ fn main() {
     disassembler_options.only_public = args.skip_private;
     disassembler_options.print_basic_blocks = !args.skip_basic_blocks;
     disassembler_options.print_locals = !args.skip_locals;
+    disassembler_options.decompile = args.decompile;
 
     // TODO: make source mapping work with the move source language
     let no_loc = Spanned::unsafe_no_loc(()).loc;
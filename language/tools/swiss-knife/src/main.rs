@@ -0,0 +1,53 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_core_types::{language_storage::TypeTag, parser::parse_type_tag};
+use resource_viewer::{MoveValueAnnotator, NullStateView};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "Swiss Knife")]
+/// Tool to inspect and construct LCS encoded on-chain data.
+///
+/// Types are written the same way as in transaction type arguments, e.g.
+/// `0x1::LibraAccount::Balance<0x1::LBR::LBR>`. Only types declared in the Move standard library
+/// can currently be resolved.
+enum Command {
+    /// Pretty-print a hex encoded LCS blob as a value of the given type.
+    Decode {
+        #[structopt(parse(try_from_str = parse_type_tag))]
+        type_tag: TypeTag,
+        /// LCS bytes in hex, with or without a leading `0x`.
+        hex_bytes: String,
+    },
+    /// Encode a JSON value of the given type into hex LCS bytes.
+    ///
+    /// Structs are JSON objects keyed by field name, addresses and `vector<u8>` are hex strings
+    /// and `u128` values can be passed as decimal strings.
+    Encode {
+        #[structopt(parse(try_from_str = parse_type_tag))]
+        type_tag: TypeTag,
+        json: String,
+    },
+}
+
+fn main() -> Result<()> {
+    let view = NullStateView::default();
+    let annotator = MoveValueAnnotator::new(&view);
+    match Command::from_args() {
+        Command::Decode {
+            type_tag,
+            hex_bytes,
+        } => {
+            let blob = hex::decode(hex_bytes.trim_start_matches("0x"))?;
+            println!("{}", annotator.view_value(&type_tag, &blob)?);
+        }
+        Command::Encode { type_tag, json } => {
+            let value = serde_json::from_str(&json)?;
+            let blob = annotator.encode_json_value(&type_tag, &value)?;
+            println!("{}", hex::encode(blob));
+        }
+    }
+    Ok(())
+}
@@ -11,6 +11,7 @@ use crate::{
     substitute, summaries,
 };
 use libra_logger::{debug, error, warn};
+use move_core_types::u256::U256;
 use rand::{rngs::StdRng, Rng};
 use vm::{
     access::ModuleAccess,
@@ -37,6 +38,9 @@ type U64ToBytecode = fn(u64) -> Bytecode;
 /// This type represents bytecode instructions that take a `u128`
 type U128ToBytecode = fn(u128) -> Bytecode;
 
+/// This type represents bytecode instructions that take a `U256`
+type U256ToBytecode = fn(U256) -> Bytecode;
+
 /// This type represents bytecode instructions that take a `AddressPoolIndex`
 type ConstantPoolIndexToBytecode = fn(ConstantPoolIndex) -> Bytecode;
 
@@ -79,6 +83,9 @@ enum BytecodeType {
     /// Instructions that take a `u128`
     U128(U128ToBytecode),
 
+    /// Instructions that take a `U256`
+    U256(U256ToBytecode),
+
     /// Instructions that take an `ConstantPoolIndex`
     ConstantPoolIndex(ConstantPoolIndexToBytecode),
 
@@ -166,9 +173,11 @@ impl<'a> BytecodeGenerator<'a> {
             (StackEffect::Add, BytecodeType::U8(Bytecode::LdU8)),
             (StackEffect::Add, BytecodeType::U64(Bytecode::LdU64)),
             (StackEffect::Add, BytecodeType::U128(Bytecode::LdU128)),
+            (StackEffect::Add, BytecodeType::U256(Bytecode::LdU256)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU8)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU64)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU128)),
+            (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU256)),
             (
                 StackEffect::Add,
                 BytecodeType::ConstantPoolIndex(Bytecode::LdConst),
@@ -388,6 +397,10 @@ impl<'a> BytecodeGenerator<'a> {
                     // Generate a random u128 constant to load
                     Some(instruction(self.rng.gen_range(0, u128::max_value())))
                 }
+                BytecodeType::U256(instruction) => {
+                    // Generate a random u256 constant to load
+                    Some(instruction(U256::from_le_bytes(self.rng.gen())))
+                }
                 BytecodeType::ConstantPoolIndex(instruction) => {
                     // Select a random address from the module's address pool
                     Self::index_or_none(&module.constant_pool, &mut self.rng)
@@ -874,6 +887,7 @@ impl<'a> BytecodeGenerator<'a> {
             SignatureToken::U64 => vec![Bytecode::LdU64(0)],
             SignatureToken::U8 => vec![Bytecode::LdU8(0)],
             SignatureToken::U128 => vec![Bytecode::LdU128(0)],
+            SignatureToken::U256 => vec![Bytecode::LdU256(U256::zero())],
             SignatureToken::Bool => vec![Bytecode::LdFalse],
             SignatureToken::Struct(handle_idx) => {
                 let struct_def_idx = module
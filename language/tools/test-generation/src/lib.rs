@@ -363,6 +363,7 @@ pub(crate) fn substitute(token: &SignatureToken, tys: &[SignatureToken]) -> Sign
         U8 => U8,
         U64 => U64,
         U128 => U128,
+        U256 => U256,
         Address => Address,
         Signer => Signer,
         Vector(ty) => Vector(Box::new(substitute(ty, tys))),
@@ -387,7 +388,9 @@ pub fn kind(module: &impl ModuleAccess, ty: &SignatureToken, constraints: &[Kind
 
     match ty {
         // The primitive types & references have kind unrestricted.
-        Bool | U8 | U64 | U128 | Address | Reference(_) | MutableReference(_) => Kind::Copyable,
+        Bool | U8 | U64 | U128 | U256 | Address | Reference(_) | MutableReference(_) => {
+            Kind::Copyable
+        }
         Signer => Kind::Resource,
         TypeParameter(idx) => constraints[*idx as usize],
         Vector(ty) => kind(module, ty, constraints),
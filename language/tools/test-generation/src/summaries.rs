@@ -93,6 +93,12 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 SignatureToken::U128
             ))]),
         },
+        Bytecode::LdU256(_) => Summary {
+            preconditions: vec![],
+            effects: Effects::NoTyParams(vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U256
+            ))]),
+        },
         Bytecode::CastU8 => Summary {
             preconditions: vec![state_stack_is_castable!(SignatureToken::U8)],
             effects: Effects::NoTyParams(vec![
@@ -114,6 +120,13 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 state_stack_push!(AbstractValue::new_primitive(SignatureToken::U128)),
             ]),
         },
+        Bytecode::CastU256 => Summary {
+            preconditions: vec![state_stack_is_castable!(SignatureToken::U256)],
+            effects: Effects::NoTyParams(vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U256)),
+            ]),
+        },
         // TODO actual constant generation
         Bytecode::LdConst(_) => Summary {
             preconditions: vec![],
@@ -586,6 +599,18 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
             ],
             effects: Effects::NoTyParams(vec![state_stack_pop!()]),
         },
+        // TODO: Add vectors to the abstract state to generate vector instructions
+        Bytecode::VecPack(_, _)
+        | Bytecode::VecLen(_)
+        | Bytecode::VecImmBorrow(_)
+        | Bytecode::VecMutBorrow(_)
+        | Bytecode::VecPushBack(_)
+        | Bytecode::VecPopBack(_)
+        | Bytecode::VecUnpack(_, _)
+        | Bytecode::VecSwap(_) => Summary {
+            preconditions: vec![state_never!()],
+            effects: Effects::NoTyParams(vec![]),
+        },
         Bytecode::Nop => Summary {
             preconditions: vec![],
             effects: Effects::NoTyParams(vec![]),
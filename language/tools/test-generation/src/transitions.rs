@@ -142,7 +142,12 @@ pub fn stack_top_is_castable_to(state: &AbstractState, typ: SignatureToken) -> b
                     Some(AbstractValue::new_primitive(SignatureToken::U64)),
                 )
             }
-            SignatureToken::U128 => true,
+            SignatureToken::U128 => !stack_has(
+                state,
+                0,
+                Some(AbstractValue::new_primitive(SignatureToken::U256)),
+            ),
+            SignatureToken::U256 => true,
             _ => false,
         }
 }
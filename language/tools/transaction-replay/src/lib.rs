@@ -0,0 +1,369 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Replays a committed transaction against the state it was executed on, as read from a full node,
+//! and reports how the output of the local Move VM diverges from what the full node committed.
+//!
+//! Nothing read from the full node is trusted: the transaction, its events and transaction info
+//! and the state of every account the VM reads are verified against a ledger info that is itself
+//! verified against a waypoint. A divergence thus shows that either the full node or the local VM
+//! does not execute transactions as the validators that signed the ledger info did.
+
+use anyhow::{bail, ensure, format_err, Result};
+use executor_types::ProofReader;
+use libra_crypto::{hash::CryptoHash, HashValue};
+use libra_secure_json_rpc::JsonRpcClient;
+use libra_state_view::StateView;
+use libra_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_state::AccountState,
+    account_state_blob::AccountStateBlob,
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfo,
+    proof::SparseMerkleProof,
+    transaction::{Transaction, TransactionOutput, TransactionStatus, Version},
+    trusted_state::{TrustedState, TrustedStateChange},
+    vm_error::StatusCode,
+    waypoint::Waypoint,
+    write_set::WriteOp,
+};
+use libra_vm::{LibraVM, VMExecutor};
+use scratchpad::SparseMerkleTree;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+    convert::TryFrom,
+    fmt,
+};
+
+/// A way in which the replayed output differs from the committed one
+#[derive(Debug)]
+pub enum Divergence {
+    Status {
+        committed: StatusCode,
+        replayed: TransactionStatus,
+    },
+    Gas {
+        committed: u64,
+        replayed: u64,
+    },
+    Events {
+        committed: Vec<ContractEvent>,
+        replayed: Vec<ContractEvent>,
+    },
+    /// The root hash of the state after the transaction, which covers the whole write set
+    StateRoot {
+        committed: HashValue,
+        replayed: HashValue,
+    },
+    /// A value written by the replayed transaction, of which the committed value differs
+    Resource {
+        access_path: AccessPath,
+        committed: Option<Vec<u8>>,
+        replayed: Option<Vec<u8>>,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Divergence::Status {
+                committed,
+                replayed,
+            } => write!(
+                f,
+                "status: committed {:?}, replayed {:?}",
+                committed, replayed
+            ),
+            Divergence::Gas {
+                committed,
+                replayed,
+            } => write!(
+                f,
+                "gas used: committed {}, replayed {}",
+                committed, replayed
+            ),
+            Divergence::Events {
+                committed,
+                replayed,
+            } => write!(
+                f,
+                "events: committed {:#?}, replayed {:#?}",
+                committed, replayed
+            ),
+            Divergence::StateRoot {
+                committed,
+                replayed,
+            } => write!(
+                f,
+                "state root hash: committed {}, replayed {}",
+                committed, replayed
+            ),
+            Divergence::Resource {
+                access_path,
+                committed,
+                replayed,
+            } => write!(
+                f,
+                "{}: committed {}, replayed {}",
+                access_path,
+                display_value(committed),
+                display_value(replayed)
+            ),
+        }
+    }
+}
+
+fn display_value(value: &Option<Vec<u8>>) -> String {
+    match value {
+        Some(value) => hex::encode(value),
+        None => "none".into(),
+    }
+}
+
+pub struct ReplayReport {
+    pub version: Version,
+    pub transaction: Transaction,
+    pub output: TransactionOutput,
+    pub divergences: Vec<Divergence>,
+}
+
+/// Replays transactions read from a full node, verifying all it reads against a waypoint
+pub struct ReplayClient {
+    client: JsonRpcClient,
+    trusted_state: TrustedState,
+}
+
+impl ReplayClient {
+    pub fn new(host: String, waypoint: Waypoint) -> Self {
+        Self {
+            client: JsonRpcClient::new(host),
+            trusted_state: TrustedState::from(waypoint),
+        }
+    }
+
+    /// Ratchets the trusted state to the latest ledger info of the full node and returns it
+    fn latest_ledger_info(&mut self) -> Result<LedgerInfo> {
+        let (ledger_info, epoch_change_proof) = self
+            .client
+            .get_state_proof(self.trusted_state.latest_version())?;
+        match self
+            .trusted_state
+            .verify_and_ratchet(&ledger_info, &epoch_change_proof)?
+        {
+            TrustedStateChange::Epoch { new_state, .. }
+            | TrustedStateChange::Version { new_state } => self.trusted_state = new_state,
+            TrustedStateChange::NoChange => (),
+        }
+        Ok(ledger_info.ledger_info().clone())
+    }
+
+    /// Re-executes the transaction committed at `version` on the state after `version - 1` and
+    /// compares the output with the committed one.
+    pub fn replay(&mut self, version: Version) -> Result<ReplayReport> {
+        ensure!(version > 0, "The genesis transaction cannot be replayed");
+        let ledger_info = self.latest_ledger_info()?;
+        ensure!(
+            version <= ledger_info.version(),
+            "Version {} is not committed yet, the latest version is {}",
+            version,
+            ledger_info.version()
+        );
+
+        // The transaction info of the previous transaction holds the state root to replay on
+        let txns = self.client.get_transactions_with_proofs(
+            version - 1,
+            2,
+            Some(ledger_info.version()),
+        )?;
+        txns.verify(&ledger_info, Some(version - 1))?;
+        let (pre_state_root, txn_info) = match txns.proof.transaction_infos() {
+            [previous, current] => (previous.state_root_hash(), current.clone()),
+            _ => bail!(
+                "Expected the transaction infos of versions {} and {}",
+                version - 1,
+                version
+            ),
+        };
+        let transaction = txns
+            .transactions
+            .get(1)
+            .cloned()
+            .ok_or_else(|| format_err!("Transaction {} not found", version))?;
+        let committed_events = txns
+            .events
+            .and_then(|mut events| events.pop())
+            .ok_or_else(|| format_err!("Events of transaction {} not found", version))?;
+
+        let state_view = RemoteStateView {
+            client: &self.client,
+            ledger_info: &ledger_info,
+            version: version - 1,
+            accounts: RefCell::new(HashMap::new()),
+        };
+        let output = LibraVM::execute_block(vec![transaction.clone()], &state_view)
+            .map_err(|status| format_err!("Failed to execute the transaction: {:?}", status))?
+            .pop()
+            .ok_or_else(|| format_err!("No output for transaction {}", version))?;
+
+        let mut divergences = vec![];
+        match output.status() {
+            TransactionStatus::Keep(status) if status.major_status == txn_info.major_status() => {}
+            replayed => divergences.push(Divergence::Status {
+                committed: txn_info.major_status(),
+                replayed: replayed.clone(),
+            }),
+        }
+        if output.gas_used() != txn_info.gas_used() {
+            divergences.push(Divergence::Gas {
+                committed: txn_info.gas_used(),
+                replayed: output.gas_used(),
+            });
+        }
+        if output.events() != committed_events.as_slice() {
+            divergences.push(Divergence::Events {
+                committed: committed_events,
+                replayed: output.events().to_vec(),
+            });
+        }
+
+        let (replayed_state_root, replayed_accounts) = state_view.apply(pre_state_root, &output)?;
+        if replayed_state_root != txn_info.state_root_hash() {
+            divergences.push(Divergence::StateRoot {
+                committed: txn_info.state_root_hash(),
+                replayed: replayed_state_root,
+            });
+            // Find the values that differ among those the replayed transaction wrote
+            let written: BTreeSet<_> = output
+                .write_set()
+                .iter()
+                .map(|(access_path, _)| access_path.clone())
+                .collect();
+            for (address, replayed_state) in replayed_accounts {
+                let (committed_state, _) =
+                    fetch_account(&self.client, &ledger_info, version, address)?;
+                for access_path in written.iter().filter(|path| path.address == address) {
+                    let committed = committed_state.get(&access_path.path).cloned();
+                    let replayed = replayed_state.get(&access_path.path).cloned();
+                    if committed != replayed {
+                        divergences.push(Divergence::Resource {
+                            access_path: access_path.clone(),
+                            committed,
+                            replayed,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(ReplayReport {
+            version,
+            transaction,
+            output,
+            divergences,
+        })
+    }
+}
+
+/// Fetches the state of the account at `version`, verified against the ledger info, along with the
+/// proof of the state in the state tree at `version`.
+fn fetch_account(
+    client: &JsonRpcClient,
+    ledger_info: &LedgerInfo,
+    version: Version,
+    address: AccountAddress,
+) -> Result<(AccountState, SparseMerkleProof)> {
+    let account_state_with_proof =
+        client.get_account_state_with_proof(address, Some(version), Some(ledger_info.version()))?;
+    account_state_with_proof.verify(ledger_info, version, address)?;
+    let state = match &account_state_with_proof.blob {
+        Some(blob) => AccountState::try_from(blob)?,
+        None => AccountState::default(),
+    };
+    Ok((
+        state,
+        account_state_with_proof
+            .proof
+            .transaction_info_to_account_proof()
+            .clone(),
+    ))
+}
+
+/// The state after a version, read from a full node as the VM needs it
+struct RemoteStateView<'a> {
+    client: &'a JsonRpcClient,
+    ledger_info: &'a LedgerInfo,
+    version: Version,
+    accounts: RefCell<HashMap<AccountAddress, (AccountState, SparseMerkleProof)>>,
+}
+
+impl<'a> RemoteStateView<'a> {
+    fn load(&self, address: AccountAddress) -> Result<()> {
+        if !self.accounts.borrow().contains_key(&address) {
+            let account = fetch_account(self.client, self.ledger_info, self.version, address)?;
+            self.accounts.borrow_mut().insert(address, account);
+        }
+        Ok(())
+    }
+
+    /// Applies the write set of the output to the accounts it writes and returns the resulting
+    /// state root hash, along with the new state of these accounts.
+    fn apply(
+        self,
+        pre_state_root: HashValue,
+        output: &TransactionOutput,
+    ) -> Result<(HashValue, HashMap<AccountAddress, AccountState>)> {
+        // Write set transactions may write accounts that the VM did not read
+        for (access_path, _) in output.write_set().iter() {
+            self.load(access_path.address)?;
+        }
+        let mut accounts = self.accounts.into_inner();
+        let mut written = HashSet::new();
+        for (access_path, write_op) in output.write_set().iter() {
+            let (state, _) = accounts
+                .get_mut(&access_path.address)
+                .expect("Written accounts are loaded");
+            match write_op {
+                WriteOp::Value(value) => state.insert(access_path.path.clone(), value.clone()),
+                WriteOp::Deletion => state.remove(&access_path.path),
+            };
+            written.insert(access_path.address);
+        }
+
+        let mut proofs = HashMap::new();
+        let mut blobs = vec![];
+        let mut states = HashMap::new();
+        for (address, (state, proof)) in accounts {
+            proofs.insert(address.hash(), proof);
+            if written.contains(&address) {
+                blobs.push((address.hash(), AccountStateBlob::try_from(&state)?));
+                states.insert(address, state);
+            }
+        }
+        let state_tree = SparseMerkleTree::new(pre_state_root)
+            .update(blobs, &ProofReader::new(proofs))
+            .map_err(|e| format_err!("Failed to update the state tree: {:?}", e))?;
+        Ok((state_tree.root_hash(), states))
+    }
+}
+
+impl<'a> StateView for RemoteStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        self.load(access_path.address)?;
+        Ok(self.accounts.borrow()[&access_path.address]
+            .0
+            .get(&access_path.path)
+            .cloned())
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use anyhow::{bail, Result};
+use libra_types::{transaction::Version, waypoint::Waypoint};
+use structopt::StructOpt;
+use transaction_replay::ReplayClient;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "Transaction Replay")]
+/// Tool to audit a full node by replaying the transactions it serves.
+///
+/// Each transaction is re-executed in the local Move VM on the state before it, as read from the
+/// full node and verified against the waypoint. Any divergence of the status, gas used, events or
+/// write set from what was committed is reported.
+struct Args {
+    /// JSON-RPC endpoint of the full node
+    #[structopt(long)]
+    host: String,
+    /// Waypoint against which all data read from the full node is verified
+    #[structopt(long)]
+    waypoint: Waypoint,
+    /// Version of the first transaction to replay
+    #[structopt(long)]
+    version: Version,
+    /// Number of consecutive transactions to replay
+    #[structopt(long, default_value = "1")]
+    count: u64,
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    let mut client = ReplayClient::new(args.host, args.waypoint);
+
+    let mut diverged = 0;
+    for version in args.version..args.version + args.count {
+        let report = client.replay(version)?;
+        if report.divergences.is_empty() {
+            println!("Version {}: replayed without divergence", version);
+        } else {
+            diverged += 1;
+            println!(
+                "Version {}: {} divergences",
+                version,
+                report.divergences.len()
+            );
+            for divergence in &report.divergences {
+                println!("  {}", divergence);
+            }
+        }
+    }
+    if diverged > 0 {
+        bail!("{} of {} transactions diverged", diverged, args.count);
+    }
+    Ok(())
+}
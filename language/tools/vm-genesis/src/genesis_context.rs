@@ -200,6 +200,10 @@ impl<'txn> GenesisDataCache<'txn> {
         self.data_store.event_data()
     }
 
+    pub fn publish_error_map(&mut self, module_id: ModuleId, error_map: Vec<u8>) -> VMResult<()> {
+        self.data_store.publish_error_map(module_id, error_map)
+    }
+
     pub fn make_write_set(&mut self) -> VMResult<WriteSet> {
         self.data_store.make_write_set()
     }
@@ -250,7 +254,19 @@ impl<'txn> DataStore for GenesisDataCache<'txn> {
         self.data_store.publish_module(module_id, module)
     }
 
-    fn emit_event(&mut self, event: ContractEvent) {
+    fn load_error_map(&self, module: &ModuleId) -> VMResult<Option<Vec<u8>>> {
+        self.data_store.load_error_map(module)
+    }
+
+    fn read_table_entry(&mut self, ap: &AccessPath) -> VMResult<Option<Vec<u8>>> {
+        self.data_store.read_table_entry(ap)
+    }
+
+    fn write_table_entry(&mut self, ap: AccessPath, value: Option<Vec<u8>>) -> VMResult<()> {
+        self.data_store.write_table_entry(ap, value)
+    }
+
+    fn emit_event(&mut self, event: ContractEvent) -> VMResult<()> {
         self.data_store.emit_event(event)
     }
 }
@@ -3,13 +3,13 @@
 
 //! This file contains the starting gas schedule published at genesis.
 
-use move_core_types::gas_schedule::GasCost;
+use move_core_types::{gas_schedule::GasCost, u256::U256};
 use once_cell::sync::Lazy;
 use vm::{
     file_format::{
         Bytecode, ConstantPoolIndex, FieldHandleIndex, FieldInstantiationIndex,
-        FunctionHandleIndex, FunctionInstantiationIndex, StructDefInstantiationIndex,
-        StructDefinitionIndex, NUMBER_OF_NATIVE_FUNCTIONS,
+        FunctionHandleIndex, FunctionInstantiationIndex, SignatureIndex,
+        StructDefInstantiationIndex, StructDefinitionIndex, NUMBER_OF_NATIVE_FUNCTIONS,
     },
     file_format_common::instruction_key,
 };
@@ -77,9 +77,11 @@ pub(crate) static INITIAL_GAS_SCHEDULE: Lazy<(Vec<u8>, Vec<u8>)> = Lazy::new(||
         (LdU8(0), GasCost::new(29, 1)),
         (LdU64(0), GasCost::new(29, 1)),
         (LdU128(0), GasCost::new(29, 1)),
+        (LdU256(U256::zero()), GasCost::new(29, 1)),
         (CastU8, GasCost::new(29, 1)),
         (CastU64, GasCost::new(29, 1)),
         (CastU128, GasCost::new(29, 1)),
+        (CastU256, GasCost::new(29, 1)),
         (Abort, GasCost::new(39, 1)),
         (MutBorrowLoc(0), GasCost::new(45, 1)),
         (ImmBorrowLoc(0), GasCost::new(45, 1)),
@@ -139,6 +141,16 @@ pub(crate) static INITIAL_GAS_SCHEDULE: Lazy<(Vec<u8>, Vec<u8>)> = Lazy::new(||
             GasCost::new(73, 1),
         ),
         (Nop, GasCost::new(10, 1)),
+        // The vector instructions replace calls to the natives of the Vector module, which are
+        // free, and cost as much as those calls: a generic call with one type argument.
+        (VecPack(SignatureIndex::new(0), 0), GasCost::new(395, 1)),
+        (VecLen(SignatureIndex::new(0)), GasCost::new(395, 1)),
+        (VecImmBorrow(SignatureIndex::new(0)), GasCost::new(395, 1)),
+        (VecMutBorrow(SignatureIndex::new(0)), GasCost::new(395, 1)),
+        (VecPushBack(SignatureIndex::new(0)), GasCost::new(395, 1)),
+        (VecPopBack(SignatureIndex::new(0)), GasCost::new(395, 1)),
+        (VecUnpack(SignatureIndex::new(0), 0), GasCost::new(395, 1)),
+        (VecSwap(SignatureIndex::new(0)), GasCost::new(395, 1)),
     ];
     // Note that the LibraVM is expecting the table sorted by instruction order.
     instrs.sort_by_key(|cost| instruction_key(&cost.0));
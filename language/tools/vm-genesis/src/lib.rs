@@ -7,11 +7,13 @@ mod genesis_context;
 mod genesis_gas_schedule;
 
 use crate::{
-    genesis_context::{GenesisContext, GenesisStateView},
+    genesis_context::{GenesisContext, GenesisDataCache, GenesisStateView},
     genesis_gas_schedule::INITIAL_GAS_SCHEDULE,
 };
 use bytecode_verifier::VerifiedModule;
-use compiled_stdlib::{stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions};
+use compiled_stdlib::{
+    stdlib_error_map, stdlib_modules, transaction_scripts::StdlibScript, StdLibOptions,
+};
 use libra_config::config::{NodeConfig, HANDSHAKE_VERSION};
 use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
@@ -21,11 +23,14 @@ use libra_network_address::RawNetworkAddress;
 use libra_types::{
     account_config,
     contract_event::ContractEvent,
-    on_chain_config::{new_epoch_event_key, VMPublishingOption},
+    on_chain_config::{new_epoch_event_key, ModulePublishingPolicy, VMPublishingOption},
     transaction::{authenticator::AuthenticationKey, ChangeSet, Script, Transaction},
 };
 use libra_vm::data_cache::StateViewCache;
-use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::{
+    errmap::ErrorMapping,
+    language_storage::{StructTag, TypeTag},
+};
 use move_vm_types::{data_store::DataStore, loaded_data::types::FatStructType, values::Value};
 use once_cell::sync::Lazy;
 use rand::prelude::*;
@@ -55,6 +60,7 @@ pub fn encode_genesis_transaction_with_validator(
         public_key,
         validators,
         stdlib_modules(StdLibOptions::Compiled), // Must use compiled stdlib
+        stdlib_error_map(StdLibOptions::Compiled),
         vm_publishing_option
             .unwrap_or_else(|| VMPublishingOption::Locked(StdlibScript::whitelist())),
     )
@@ -64,6 +70,7 @@ pub fn encode_genesis_change_set(
     public_key: &Ed25519PublicKey,
     validators: &[ValidatorRegistration],
     stdlib_modules: &[VerifiedModule],
+    stdlib_error_map: &ErrorMapping,
     vm_publishing_option: VMPublishingOption,
 ) -> (ChangeSet, BTreeMap<Vec<u8>, FatStructType>) {
     // create a data view for move_vm
@@ -95,6 +102,7 @@ pub fn encode_genesis_change_set(
 
     let mut interpreter_context = genesis_context.into_data_store();
     publish_stdlib(&mut interpreter_context, stdlib_modules);
+    publish_stdlib_error_maps(&mut interpreter_context, stdlib_modules, stdlib_error_map);
 
     verify_genesis_write_set(interpreter_context.events());
     (
@@ -112,6 +120,7 @@ pub fn encode_genesis_transaction(
     public_key: Ed25519PublicKey,
     validators: &[ValidatorRegistration],
     stdlib_modules: &[VerifiedModule],
+    stdlib_error_map: &ErrorMapping,
     vm_publishing_option: VMPublishingOption,
 ) -> Transaction {
     Transaction::WaypointWriteSet(
@@ -119,6 +128,7 @@ pub fn encode_genesis_transaction(
             &public_key,
             validators,
             stdlib_modules,
+            stdlib_error_map,
             vm_publishing_option,
         )
         .0,
@@ -139,6 +149,12 @@ fn create_and_initialize_main_accounts(
 
     let option_bytes =
         lcs::to_bytes(&publishing_option).expect("Cannot serialize publishing option");
+    // Only the open publishing option lets accounts publish modules from genesis on
+    let module_publishing_policy = if publishing_option.is_open() {
+        ModulePublishingPolicy::Open
+    } else {
+        ModulePublishingPolicy::Closed
+    };
 
     context.set_sender(root_association_address);
     context.exec(
@@ -151,6 +167,8 @@ fn create_and_initialize_main_accounts(
             Value::address(tc_account_address),
             Value::vector_u8(genesis_auth_key.to_vec()),
             Value::vector_u8(option_bytes),
+            Value::u8(module_publishing_policy.code()),
+            Value::vector_address(module_publishing_policy.allowed_publishers().to_vec()),
             Value::vector_u8(INITIAL_GAS_SCHEDULE.0.clone()),
             Value::vector_u8(INITIAL_GAS_SCHEDULE.1.clone()),
         ],
@@ -227,6 +245,23 @@ fn publish_stdlib(interpreter_context: &mut dyn DataStore, stdlib: &[VerifiedMod
     }
 }
 
+/// Publish the error maps of the standard library modules, so that their aborts can be named.
+fn publish_stdlib_error_maps(
+    interpreter_context: &mut GenesisDataCache,
+    stdlib: &[VerifiedModule],
+    error_map: &ErrorMapping,
+) {
+    for module in remove_genesis(stdlib) {
+        let module_id = module.self_id();
+        if let Some(module_error_map) = error_map.module_error_maps.get(&module_id) {
+            let bytes = lcs::to_bytes(module_error_map).expect("Error maps must serialize");
+            interpreter_context
+                .publish_error_map(module_id.clone(), bytes)
+                .unwrap_or_else(|_| panic!("Failure publishing error map of {:?}", module_id));
+        }
+    }
+}
+
 /// Trigger a reconfiguration. This emits an event that will be passed along to the storage layer.
 fn reconfigure(context: &mut GenesisContext) {
     context.set_sender(account_config::association_address());
@@ -266,12 +301,14 @@ fn verify_genesis_write_set(events: &[ContractEvent]) {
 /// Generate an artificial genesis `ChangeSet` for testing
 pub fn generate_genesis_change_set_for_testing(stdlib_options: StdLibOptions) -> ChangeSet {
     let stdlib_modules = stdlib_modules(stdlib_options);
+    let stdlib_error_map = stdlib_error_map(stdlib_options);
     let swarm = libra_config::generator::validator_swarm_for_testing(10);
 
     encode_genesis_change_set(
         &GENESIS_KEYPAIR.1,
         &validator_registrations(&swarm.nodes),
         stdlib_modules,
+        stdlib_error_map,
         VMPublishingOption::Open,
     )
     .0
@@ -286,6 +323,7 @@ pub fn generate_genesis_type_mapping() -> BTreeMap<Vec<u8>, FatStructType> {
         &GENESIS_KEYPAIR.1,
         &validator_registrations(&swarm.nodes),
         stdlib_modules,
+        stdlib_error_map(StdLibOptions::Compiled),
         VMPublishingOption::Open,
     )
     .1
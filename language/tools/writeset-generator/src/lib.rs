@@ -0,0 +1,193 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Builds, previews and signs the write set transactions used for administrative interventions on
+//! a blockchain, e.g., updating an on-chain config that cannot be updated with a script.
+//!
+//! Write set transactions are sent by the association and bypass the VM, except for their prologue
+//! and epilogue. The epilogue always triggers a reconfiguration, so that every write set, even an
+//! empty one, bumps the epoch and emits a `NewEpochEvent`.
+
+use anyhow::{format_err, Result};
+use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use libra_state_view::StateView;
+use libra_types::{
+    access_path::AccessPath,
+    account_config::{self, AccountResource},
+    on_chain_config::OnChainConfig,
+    transaction::{
+        ChangeSet, RawTransaction, SignedTransaction, Transaction, TransactionOutput,
+        TransactionStatus,
+    },
+    vm_error::StatusCode,
+    write_set::{WriteOp, WriteSet, WriteSetMut},
+};
+use libra_vm::{LibraVM, VMExecutor};
+use move_core_types::move_resource::MoveResource;
+use resource_viewer::MoveValueAnnotator;
+use serde::Serialize;
+use std::fmt;
+
+/// Returns a change set that writes a new value of an on-chain config. The epilogue of the write
+/// set transaction reconfigures the network, so that the new value is loaded by all validators.
+///
+/// The config is LCS-encoded as is, so this does not apply to configs with a custom encoding, such
+/// as `VMConfig`.
+pub fn encode_config_update<T: OnChainConfig + Serialize>(config: &T) -> Result<ChangeSet> {
+    let write_set = WriteSetMut::new(vec![(
+        T::CONFIG_ID.access_path(),
+        WriteOp::Value(lcs::to_bytes(config)?),
+    )])
+    .freeze()?;
+    Ok(ChangeSet::new(write_set, vec![]))
+}
+
+/// Returns a change set that only forces a reconfiguration, e.g., to have validators reload the
+/// on-chain configs after they got stuck in an epoch.
+pub fn encode_reconfiguration() -> ChangeSet {
+    ChangeSet::new(WriteSet::default(), vec![])
+}
+
+/// Returns the sequence number of the next transaction of the association in `state_view`
+pub fn association_sequence_number(state_view: &dyn StateView) -> Result<u64> {
+    let access_path = AccessPath::new(
+        account_config::association_address(),
+        AccountResource::resource_path(),
+    );
+    let blob = state_view
+        .get(&access_path)?
+        .ok_or_else(|| format_err!("Association account not found"))?;
+    Ok(lcs::from_bytes::<AccountResource>(&blob)?.sequence_number())
+}
+
+/// Returns the write set transaction of the association applying the change set
+pub fn build_transaction(sequence_number: u64, change_set: ChangeSet) -> RawTransaction {
+    RawTransaction::new_change_set(
+        account_config::association_address(),
+        sequence_number,
+        change_set,
+    )
+}
+
+/// Signs the transaction with the association key
+pub fn sign_transaction(
+    raw_transaction: RawTransaction,
+    private_key: &Ed25519PrivateKey,
+) -> Result<SignedTransaction> {
+    let public_key = Ed25519PublicKey::from(private_key);
+    Ok(raw_transaction.sign(private_key, public_key)?.into_inner())
+}
+
+/// Executes the transaction against `state_view` without committing its output, as the executor
+/// would when it is included in a block.
+pub fn simulate(state_view: &dyn StateView, txn: SignedTransaction) -> Result<TransactionOutput> {
+    let mut outputs =
+        LibraVM::execute_block(vec![Transaction::UserTransaction(txn)], state_view)
+            .map_err(|status| format_err!("Failed to execute the transaction: {:?}", status))?;
+    outputs
+        .pop()
+        .ok_or_else(|| format_err!("No output for the transaction"))
+}
+
+/// Returns whether the simulated transaction would be committed successfully
+pub fn is_executed(output: &TransactionOutput) -> bool {
+    match output.status() {
+        TransactionStatus::Keep(status) => status.major_status == StatusCode::EXECUTED,
+        _ => false,
+    }
+}
+
+/// The value of a resource or module before and after a write set, in a human-readable form
+pub struct Change {
+    pub access_path: AccessPath,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "@@ {} @@", self.access_path)?;
+        if let Some(before) = &self.before {
+            for line in before.lines() {
+                writeln!(f, "-{}", line)?;
+            }
+        }
+        if let Some(after) = &self.after {
+            for line in after.lines() {
+                writeln!(f, "+{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the changes made by the write set to the state in `state_view`. Resources are decoded
+/// with the modules in `state_view`, and shown as raw bytes if they cannot be decoded.
+pub fn preview(state_view: &dyn StateView, write_set: &WriteSet) -> Result<Vec<Change>> {
+    let annotator = MoveValueAnnotator::new(state_view);
+    let mut changes = vec![];
+    for (access_path, op) in write_set.iter() {
+        let before = state_view
+            .get(access_path)?
+            .map(|blob| view_blob(&annotator, access_path, &blob));
+        let after = match op {
+            WriteOp::Value(blob) => Some(view_blob(&annotator, access_path, blob)),
+            WriteOp::Deletion => None,
+        };
+        if before != after {
+            changes.push(Change {
+                access_path: access_path.clone(),
+                before,
+                after,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+fn view_blob(annotator: &MoveValueAnnotator, access_path: &AccessPath, blob: &[u8]) -> String {
+    if access_path.path.first() == Some(&AccessPath::RESOURCE_TAG) {
+        if let Ok(resource) = annotator.view_access_path(access_path.clone(), blob) {
+            return resource.to_string();
+        }
+    }
+    format!("{} bytes: {}", blob.len(), hex::encode(blob))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use language_e2e_tests::{account::Account, executor::FakeExecutor};
+    use libra_types::on_chain_config::{ConfigurationResource, LibraVersion};
+
+    #[test]
+    fn test_simulate_config_update() {
+        let executor = FakeExecutor::from_genesis_file();
+        let state_view = executor.get_state_view();
+        let association = Account::new_association();
+
+        let change_set = encode_config_update(&LibraVersion { major: 42 }).unwrap();
+        let sequence_number = association_sequence_number(state_view).unwrap();
+        let txn = sign_transaction(
+            build_transaction(sequence_number, change_set),
+            &association.privkey,
+        )
+        .unwrap();
+        let output = simulate(state_view, txn).unwrap();
+        assert!(is_executed(&output));
+
+        let changes = preview(state_view, output.write_set()).unwrap();
+        let changed = |access_path: AccessPath| {
+            changes
+                .iter()
+                .any(|change| change.access_path == access_path)
+        };
+        assert!(changed(LibraVersion::CONFIG_ID.access_path()));
+        assert!(changed(AccessPath::new(
+            libra_types::on_chain_config::config_address(),
+            ConfigurationResource::resource_path(),
+        )));
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use anyhow::{bail, Result};
+use libra_state_view::StateViewId;
+use libra_types::{
+    on_chain_config::{DualAttestationLimit, LibraVersion},
+    transaction::ChangeSet,
+};
+use libradb::LibraDB;
+use scratchpad::SparseMerkleTree;
+use std::{fs, path::PathBuf, sync::Arc};
+use storage_interface::{state_view::VerifiedStateView, DbReader};
+use structopt::StructOpt;
+use writeset_generator::{
+    association_sequence_number, build_transaction, encode_config_update, encode_reconfiguration,
+    is_executed, preview, sign_transaction, simulate,
+};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "Writeset Generator")]
+/// Tool to generate the write set transactions of administrative interventions.
+///
+/// The changes made by the write set are previewed against the latest state of a copy of the
+/// database of a node. With `--dry-run`, the signed transaction is executed against that state
+/// and the preview includes the changes made by the transaction epilogue, e.g., the new epoch.
+struct Args {
+    /// Path to the database of a node, opened read-only
+    #[structopt(long, parse(from_os_str))]
+    db: PathBuf,
+    /// Sequence number of the transaction, read from the database if not set
+    #[structopt(long)]
+    sequence_number: Option<u64>,
+    /// Path to the association private key, as generated by generate-key. The transaction is
+    /// written unsigned if not set.
+    #[structopt(long, parse(from_os_str))]
+    key: Option<PathBuf>,
+    /// Path to which the LCS-encoded transaction is written
+    #[structopt(long, parse(from_os_str))]
+    output: Option<PathBuf>,
+    /// Execute the signed transaction against the database instead of only previewing the write set
+    #[structopt(long)]
+    dry_run: bool,
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Set the major version of Libra
+    #[structopt(name = "update-libra-version")]
+    UpdateLibraVersion {
+        #[structopt(long)]
+        major: u64,
+    },
+    /// Set the limit above which transfers require dual attestation
+    #[structopt(name = "update-dual-attestation-limit")]
+    UpdateDualAttestationLimit {
+        #[structopt(long)]
+        micro_lbr_limit: u64,
+    },
+    /// Only bump the epoch, so that validators reload the on-chain configs
+    #[structopt(name = "reconfigure")]
+    Reconfigure,
+}
+
+impl Command {
+    fn change_set(&self) -> Result<ChangeSet> {
+        match self {
+            Command::UpdateLibraVersion { major } => {
+                encode_config_update(&LibraVersion { major: *major })
+            }
+            Command::UpdateDualAttestationLimit { micro_lbr_limit } => {
+                encode_config_update(&DualAttestationLimit {
+                    micro_lbr_limit: *micro_lbr_limit,
+                })
+            }
+            Command::Reconfigure => Ok(encode_reconfiguration()),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::from_args();
+    if args.dry_run && args.key.is_none() {
+        bail!("--dry-run requires --key, as only signed transactions can be executed");
+    }
+
+    let db: Arc<dyn DbReader> = Arc::new(LibraDB::open(
+        &args.db, true, /* readonly */
+        None, /* pruner */
+        None, /* archiver */
+        None, /* waypoint_interval */
+    )?);
+    let (version, state_root) = db.get_latest_state_root()?;
+    let smt = SparseMerkleTree::new(state_root);
+    let state_view = VerifiedStateView::new(
+        StateViewId::Miscellaneous,
+        Arc::clone(&db),
+        Some(version),
+        state_root,
+        &smt,
+    );
+
+    let sequence_number = match args.sequence_number {
+        Some(sequence_number) => sequence_number,
+        None => association_sequence_number(&state_view)?,
+    };
+    let change_set = args.cmd.change_set()?;
+    let mut write_set = change_set.write_set().clone();
+    let raw_transaction = build_transaction(sequence_number, change_set);
+    println!(
+        "Write set transaction {} of the association, against version {}",
+        sequence_number, version
+    );
+
+    let encoded = match &args.key {
+        Some(path) => {
+            let txn = sign_transaction(raw_transaction, &generate_key::load_key(path))?;
+            if args.dry_run {
+                let output = simulate(&state_view, txn.clone())?;
+                println!("Dry run status: {:?}", output.status());
+                if !is_executed(&output) {
+                    bail!("The transaction would not be executed");
+                }
+                write_set = output.write_set().clone();
+            }
+            lcs::to_bytes(&txn)?
+        }
+        None => lcs::to_bytes(&raw_transaction)?,
+    };
+
+    let changes = preview(&state_view, &write_set)?;
+    println!("{} resources changed", changes.len());
+    for change in changes {
+        print!("{}", change);
+    }
+
+    if let Some(output) = &args.output {
+        fs::write(output, encoded)?;
+        println!("Transaction written to {:?}", output);
+    }
+    Ok(())
+}
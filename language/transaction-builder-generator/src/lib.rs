@@ -1,7 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_types::transaction::ScriptABI;
+use libra_types::transaction::{ModuleABI, ScriptABI};
 use std::io::Read;
 
 /// Support for code-generation in C++17.
@@ -33,6 +33,25 @@ pub fn read_abis<P: AsRef<std::path::Path>>(dir_path: P) -> anyhow::Result<Vec<S
     Ok(abis)
 }
 
+/// Read all module ABI files, i.e. the files with the `abi` extension, in a directory.
+pub fn read_module_abis<P: AsRef<std::path::Path>>(dir_path: P) -> anyhow::Result<Vec<ModuleABI>> {
+    let mut abis = Vec::<ModuleABI>::new();
+    for entry in std::fs::read_dir(dir_path)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() || path.extension().map_or(true, |ext| ext != "abi") {
+            continue;
+        }
+        let mut buffer = Vec::new();
+        let mut f = std::fs::File::open(path)?;
+        f.read_to_end(&mut buffer)?;
+        abis.push(lcs::from_bytes(&buffer)?);
+    }
+    // Sorting modules by address, then by alphabetical order.
+    abis.sort_by(|a, b| a.module_id().cmp(b.module_id()));
+    Ok(abis)
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
@@ -142,7 +142,7 @@ fn quote_type(type_tag: &TypeTag) -> String {
             _ => type_not_allowed(type_tag),
         },
 
-        Struct(_) | Signer => type_not_allowed(type_tag),
+        Struct(_) | Signer | U256 => type_not_allowed(type_tag),
     }
 }
 
@@ -159,7 +159,7 @@ fn make_transaction_argument(type_tag: &TypeTag, name: &str) -> String {
             _ => type_not_allowed(type_tag),
         },
 
-        Struct(_) | Signer => type_not_allowed(type_tag),
+        Struct(_) | Signer | U256 => type_not_allowed(type_tag),
     }
 }
 
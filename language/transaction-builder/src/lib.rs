@@ -10,7 +10,8 @@ use libra_types::{
     block_metadata::BlockMetadata,
     on_chain_config::{LibraVersion, VMPublishingOption},
     transaction::{
-        authenticator::AuthenticationKey, ChangeSet, Script, Transaction, TransactionArgument,
+        authenticator::{AccountPublicKey, AuthenticationKey},
+        ChangeSet, Script, Transaction, TransactionArgument,
     },
     write_set::{WriteOp, WriteSetMut},
 };
@@ -70,6 +71,13 @@ fn validate_auth_key_prefix(auth_key_prefix: &[u8]) {
     );
 }
 
+/// Encode a program rotating the sender's authentication key to the key of `public_key`. The
+/// scheme of the key must be enabled on-chain for the account to be able to sign transactions
+/// afterwards.
+pub fn encode_rotate_authentication_key_for_scheme_script(public_key: &AccountPublicKey) -> Script {
+    encode_rotate_authentication_key_script(public_key.authentication_key().to_vec())
+}
+
 // TODO: this should go away once we are no longer using it in tests/testnet
 /// Encode a program creating `amount` coins for sender
 pub fn encode_mint_script(
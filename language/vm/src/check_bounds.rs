@@ -327,6 +327,22 @@ impl<'a> BoundsChecker<'a> {
                         }
                     }
                 }
+                // Instructions that refer to a signature holding the vector element type.
+                VecPack(idx, _)
+                | VecLen(idx)
+                | VecImmBorrow(idx)
+                | VecMutBorrow(idx)
+                | VecPushBack(idx)
+                | VecPopBack(idx)
+                | VecUnpack(idx, _)
+                | VecSwap(idx) => {
+                    check_code_unit_bounds_impl(&self.module.signatures, bytecode_offset, *idx)?;
+                    if let Some(sig) = self.module.signatures.get(idx.into_index()) {
+                        for ty in &sig.0 {
+                            self.check_type_parameter(ty, type_param_count)?
+                        }
+                    }
+                }
                 // Instructions that refer to this code block.
                 BrTrue(offset) | BrFalse(offset) | Branch(offset) => {
                     let offset = *offset as usize;
@@ -357,10 +373,10 @@ impl<'a> BoundsChecker<'a> {
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64
-                | CastU128 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
-                | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt
-                | Le | Ge | Abort | GetTxnSenderAddress | Nop => (),
+                FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | LdU256(_) | CastU8
+                | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | ReadRef | WriteRef | Add
+                | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not
+                | Eq | Neq | Lt | Gt | Le | Ge | Abort | GetTxnSenderAddress | Nop => (),
             }
         }
         Ok(())
@@ -371,8 +387,8 @@ impl<'a> BoundsChecker<'a> {
 
         for ty in ty.preorder_traversal() {
             match ty {
-                Bool | U8 | U64 | U128 | Address | Signer | TypeParameter(_) | Reference(_)
-                | MutableReference(_) | Vector(_) => (),
+                Bool | U8 | U64 | U128 | U256 | Address | Signer | TypeParameter(_)
+                | Reference(_) | MutableReference(_) | Vector(_) => (),
                 Struct(idx) => {
                     check_bounds_impl(&self.module.struct_handles, *idx)?;
                     if let Some(sh) = self.module.struct_handles.get(idx.into_index()) {
@@ -427,6 +443,7 @@ impl<'a> BoundsChecker<'a> {
                 | U8
                 | U64
                 | U128
+                | U256
                 | Address
                 | Signer
                 | Struct(_)
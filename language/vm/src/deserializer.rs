@@ -5,6 +5,7 @@ use crate::{errors::*, file_format::*, file_format_common::*};
 use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
+    u256::U256,
     vm_error::{StatusCode, VMStatus},
 };
 use std::{
@@ -13,13 +14,53 @@ use std::{
     io::{Cursor, Read},
 };
 
+/// Limits enforced while deserializing a binary. They are checked as the binary is parsed, so a
+/// binary exceeding them is rejected before the memory for its content is allocated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeserializerConfig {
+    /// The maximum size of the whole binary, in bytes
+    pub max_binary_size: u64,
+    /// The maximum number of entries in each table of the binary
+    pub max_table_entries: u64,
+    /// Whether the binary must be in canonical form, i.e. be exactly the bytes the serializer
+    /// produces for what is deserialized. Otherwise different binaries can encode the same code,
+    /// and their hashes do not identify it.
+    pub require_canonical: bool,
+}
+
+impl Default for DeserializerConfig {
+    fn default() -> Self {
+        Self {
+            max_binary_size: BINARY_SIZE_MAX,
+            max_table_entries: TABLE_ENTRIES_MAX,
+            require_canonical: false,
+        }
+    }
+}
+
 impl CompiledScript {
     /// Deserializes a &[u8] slice into a `CompiledScript` instance.
     pub fn deserialize(binary: &[u8]) -> BinaryLoaderResult<Self> {
-        let deserialized = CompiledScriptMut::deserialize_no_check_bounds(binary)?;
-        deserialized
+        Self::deserialize_with_config(binary, &DeserializerConfig::default())
+    }
+
+    /// Deserializes a &[u8] slice into a `CompiledScript` instance, within the limits of `config`.
+    pub fn deserialize_with_config(
+        binary: &[u8],
+        config: &DeserializerConfig,
+    ) -> BinaryLoaderResult<Self> {
+        let deserialized = deserialize_compiled_script(binary, config)?;
+        let script = deserialized
             .freeze()
-            .map_err(|_| VMStatus::new(StatusCode::MALFORMED))
+            .map_err(|_| VMStatus::new(StatusCode::MALFORMED))?;
+        if config.require_canonical {
+            let mut serialized = vec![];
+            script
+                .serialize(&mut serialized)
+                .map_err(|_| VMStatus::new(StatusCode::NON_CANONICAL_BINARY))?;
+            check_canonical(binary, &serialized)?;
+        }
+        Ok(script)
     }
 }
 
@@ -27,29 +68,66 @@ impl CompiledScriptMut {
     // exposed as a public function to enable testing the deserializer
     #[doc(hidden)]
     pub fn deserialize_no_check_bounds(binary: &[u8]) -> BinaryLoaderResult<Self> {
-        deserialize_compiled_script(binary)
+        deserialize_compiled_script(binary, &DeserializerConfig::default())
     }
 }
 
 impl CompiledModule {
     /// Deserialize a &[u8] slice into a `CompiledModule` instance.
     pub fn deserialize(binary: &[u8]) -> BinaryLoaderResult<Self> {
-        let deserialized = CompiledModuleMut::deserialize_no_check_bounds(binary)?;
-        deserialized
+        Self::deserialize_with_config(binary, &DeserializerConfig::default())
+    }
+
+    /// Deserialize a &[u8] slice into a `CompiledModule` instance, within the limits of `config`.
+    pub fn deserialize_with_config(
+        binary: &[u8],
+        config: &DeserializerConfig,
+    ) -> BinaryLoaderResult<Self> {
+        let deserialized = deserialize_compiled_module(binary, config)?;
+        let module = deserialized
             .freeze()
-            .map_err(|_| VMStatus::new(StatusCode::MALFORMED))
+            .map_err(|_| VMStatus::new(StatusCode::MALFORMED))?;
+        if config.require_canonical {
+            let mut serialized = vec![];
+            module
+                .serialize(&mut serialized)
+                .map_err(|_| VMStatus::new(StatusCode::NON_CANONICAL_BINARY))?;
+            check_canonical(binary, &serialized)?;
+        }
+        Ok(module)
     }
 }
 
 impl CompiledModuleMut {
     // exposed as a public function to enable testing the deserializer
     pub fn deserialize_no_check_bounds(binary: &[u8]) -> BinaryLoaderResult<Self> {
-        deserialize_compiled_module(binary)
+        deserialize_compiled_module(binary, &DeserializerConfig::default())
     }
 }
 
+/// Checks that a binary is the canonical serialization of what was deserialized from it, and
+/// reports the first offset at which they differ otherwise.
+fn check_canonical(binary: &[u8], canonical: &[u8]) -> BinaryLoaderResult<()> {
+    if binary == canonical {
+        return Ok(());
+    }
+    let offset = binary
+        .iter()
+        .zip(canonical)
+        .position(|(byte, canonical_byte)| byte != canonical_byte)
+        .unwrap_or_else(|| binary.len().min(canonical.len()));
+    Err(at_offset(
+        VMStatus::new(StatusCode::NON_CANONICAL_BINARY)
+            .with_message("Binary differs from its canonical serialization".to_string()),
+        offset as u64,
+    ))
+}
+
 /// Table info: table type, offset where the table content starts from, count of bytes for
 /// the table content.
+///
+/// The offset is read relative to the start of the table contents, and rebased on the start of
+/// the binary once the header is checked.
 #[derive(Clone, Debug)]
 struct Table {
     kind: TableType,
@@ -67,6 +145,25 @@ impl Table {
     }
 }
 
+/// Adds the offset in the binary at which deserialization failed to an error.
+fn at_offset(status: VMStatus, offset: u64) -> VMStatus {
+    status.append_message_with_separator(' ', format!("at offset {}", offset))
+}
+
+/// Reads `len` bytes, checking first that they are in the binary: lengths are read from the
+/// binary, so a buffer is never allocated before its content is known to be there.
+fn read_bytes(cursor: &mut Cursor<&[u8]>, len: usize) -> BinaryLoaderResult<Vec<u8>> {
+    let start = cursor.position() as usize;
+    let remaining = cursor.get_ref().len().saturating_sub(start);
+    if len > remaining {
+        return Err(VMStatus::new(StatusCode::MALFORMED)
+            .with_message("Unexpected end of table".to_string()));
+    }
+    let bytes = cursor.get_ref()[start..start + len].to_vec();
+    cursor.set_position((start + len) as u64);
+    Ok(bytes)
+}
+
 fn read_u64_internal(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<u64> {
     let mut u64_bytes = [0; 8];
     cursor
@@ -83,6 +180,14 @@ fn read_u128_internal(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<u128> {
     Ok(u128::from_le_bytes(u128_bytes))
 }
 
+fn read_u256_internal(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<U256> {
+    let mut u256_bytes = [0; U256::BYTES];
+    cursor
+        .read_exact(&mut u256_bytes)
+        .map_err(|_| VMStatus::new(StatusCode::BAD_U256))?;
+    Ok(U256::from_le_bytes(u256_bytes))
+}
+
 //
 // Helpers to read all uleb128 encoded integers.
 //
@@ -254,45 +359,85 @@ fn load_local_index(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<u8> {
 }
 
 /// Module internal function that manages deserialization of transactions.
-fn deserialize_compiled_script(binary: &[u8]) -> BinaryLoaderResult<CompiledScriptMut> {
+fn deserialize_compiled_script(
+    binary: &[u8],
+    config: &DeserializerConfig,
+) -> BinaryLoaderResult<CompiledScriptMut> {
     let mut cursor = Cursor::new(binary);
-    check_binary(&mut cursor)?;
-    let table_count = load_table_count(&mut cursor)?;
-    let mut tables: Vec<Table> = Vec::new();
-    read_tables(&mut cursor, table_count, &mut tables)?;
-    let content_len = check_tables(&mut tables)?;
-
-    let table_contents = read_table_contents(&mut cursor, content_len as usize)?;
+    let tables = read_header(&mut cursor, config)?;
 
     let mut script = CompiledScriptMut::default();
-    script.type_parameters = load_kinds(&mut cursor)?;
-    script.parameters = load_signature_index(&mut cursor)?;
-    script.code = load_code_unit(&mut cursor)?;
+    script.type_parameters =
+        load_kinds(&mut cursor).map_err(|e| at_offset(e, cursor.position()))?;
+    script.parameters =
+        load_signature_index(&mut cursor).map_err(|e| at_offset(e, cursor.position()))?;
+    script.code = load_code_unit(&mut cursor).map_err(|e| at_offset(e, cursor.position()))?;
 
-    build_compiled_script(&mut script, &table_contents, &tables)?;
+    build_compiled_script(&mut script, binary, &tables, config)?;
 
     Ok(script)
 }
 
 /// Module internal function that manages deserialization of modules.
-fn deserialize_compiled_module(binary: &[u8]) -> BinaryLoaderResult<CompiledModuleMut> {
+fn deserialize_compiled_module(
+    binary: &[u8],
+    config: &DeserializerConfig,
+) -> BinaryLoaderResult<CompiledModuleMut> {
     let mut cursor = Cursor::new(binary);
-    check_binary(&mut cursor)?;
-    let table_count = load_table_count(&mut cursor)?;
-    let mut tables: Vec<Table> = Vec::new();
-    read_tables(&mut cursor, table_count, &mut tables)?;
-    let content_len = check_tables(&mut tables)?;
-
-    let table_contents = read_table_contents(&mut cursor, content_len as usize)?;
+    let tables = read_header(&mut cursor, config)?;
 
     let mut module = CompiledModuleMut::default();
-    module.self_module_handle_idx = load_module_handle_index(&mut cursor)?;
+    module.self_module_handle_idx =
+        load_module_handle_index(&mut cursor).map_err(|e| at_offset(e, cursor.position()))?;
 
-    build_compiled_module(&mut module, &table_contents, &tables)?;
+    build_compiled_module(&mut module, binary, &tables, config)?;
 
     Ok(module)
 }
 
+/// Reads and checks the header of a binary, up to the table headers.
+///
+/// Returns the tables, with their offsets rebased on the start of the binary, and leaves the
+/// cursor at the end of the table contents. The contents are not copied: tables are loaded from
+/// the binary in place.
+fn read_header(
+    cursor: &mut Cursor<&[u8]>,
+    config: &DeserializerConfig,
+) -> BinaryLoaderResult<Vec<Table>> {
+    let binary_size = cursor.get_ref().len() as u64;
+    if binary_size > config.max_binary_size {
+        return Err(
+            VMStatus::new(StatusCode::BINARY_TOO_LARGE).with_message(format!(
+                "binary size ({}) exceeds the limit ({})",
+                binary_size, config.max_binary_size
+            )),
+        );
+    }
+    check_binary(cursor).map_err(|e| at_offset(e, cursor.position()))?;
+    let table_count = load_table_count(cursor).map_err(|e| at_offset(e, cursor.position()))?;
+    let mut tables: Vec<Table> = Vec::new();
+    read_tables(cursor, table_count, &mut tables).map_err(|e| at_offset(e, cursor.position()))?;
+    let content_len = check_tables(&mut tables)?;
+
+    let content_start = cursor.position();
+    let content_end = content_start + u64::from(content_len);
+    if content_end > binary_size {
+        return Err(at_offset(
+            VMStatus::new(StatusCode::MALFORMED)
+                .with_message("Error reading table contents".to_string()),
+            binary_size,
+        ));
+    }
+    for table in &mut tables {
+        table.offset = table
+            .offset
+            .checked_add(content_start as u32)
+            .ok_or_else(|| VMStatus::new(StatusCode::BAD_HEADER_TABLE))?;
+    }
+    cursor.set_position(content_end);
+    Ok(tables)
+}
+
 /// Verifies the correctness of the "static" part of the binary's header.
 ///
 /// Returns the offset where the count of tables in the binary.
@@ -359,21 +504,6 @@ fn read_table(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Table> {
     Ok(Table::new(TableType::from_u8(kind)?, table_offset, count))
 }
 
-fn read_table_contents(cursor: &mut Cursor<&[u8]>, n: usize) -> BinaryLoaderResult<Vec<u8>> {
-    let mut bytes = vec![];
-    // TODO: can we rewrite this in a better way?
-    for _ in 0..n {
-        match read_u8(cursor) {
-            Ok(b) => bytes.push(b),
-            Err(_) => {
-                return Err(VMStatus::new(StatusCode::MALFORMED)
-                    .with_message("Error reading table contents".to_string()))
-            }
-        }
-    }
-    Ok(bytes)
-}
-
 /// Verify correctness of tables.
 ///
 /// Tables cannot have duplicates, must cover the entire blob and must be disjoint.
@@ -390,9 +520,9 @@ fn check_tables(tables: &mut Vec<Table>) -> BinaryLoaderResult<u32> {
         if table.count == 0 {
             return Err(VMStatus::new(StatusCode::BAD_HEADER_TABLE));
         }
-        if let Some(checked_offset) = current_offset.checked_add(table.count) {
-            current_offset = checked_offset;
-        }
+        current_offset = current_offset
+            .checked_add(table.count)
+            .ok_or_else(|| VMStatus::new(StatusCode::BAD_HEADER_TABLE))?;
         if !table_types.insert(table.kind) {
             return Err(VMStatus::new(StatusCode::DUPLICATE_TABLE));
         }
@@ -491,8 +621,9 @@ fn build_compiled_script(
     script: &mut CompiledScriptMut,
     binary: &[u8],
     tables: &[Table],
+    config: &DeserializerConfig,
 ) -> BinaryLoaderResult<()> {
-    build_common_tables(binary, tables, script)?;
+    build_common_tables(binary, tables, config, script)?;
     build_script_tables(binary, tables, script)?;
     Ok(())
 }
@@ -502,9 +633,10 @@ fn build_compiled_module(
     module: &mut CompiledModuleMut,
     binary: &[u8],
     tables: &[Table],
+    config: &DeserializerConfig,
 ) -> BinaryLoaderResult<()> {
-    build_common_tables(binary, tables, module)?;
-    build_module_tables(binary, tables, module)?;
+    build_common_tables(binary, tables, config, module)?;
+    build_module_tables(binary, tables, config, module)?;
     Ok(())
 }
 
@@ -512,33 +644,39 @@ fn build_compiled_module(
 fn build_common_tables(
     binary: &[u8],
     tables: &[Table],
+    config: &DeserializerConfig,
     common: &mut impl CommonTables,
 ) -> BinaryLoaderResult<()> {
     for table in tables {
         match table.kind {
             TableType::MODULE_HANDLES => {
-                load_module_handles(binary, table, common.get_module_handles())?;
+                load_module_handles(binary, table, config, common.get_module_handles())?;
             }
             TableType::STRUCT_HANDLES => {
-                load_struct_handles(binary, table, common.get_struct_handles())?;
+                load_struct_handles(binary, table, config, common.get_struct_handles())?;
             }
             TableType::FUNCTION_HANDLES => {
-                load_function_handles(binary, table, common.get_function_handles())?;
+                load_function_handles(binary, table, config, common.get_function_handles())?;
             }
             TableType::FUNCTION_INST => {
-                load_function_instantiations(binary, table, common.get_function_instantiations())?;
+                load_function_instantiations(
+                    binary,
+                    table,
+                    config,
+                    common.get_function_instantiations(),
+                )?;
             }
             TableType::SIGNATURES => {
-                load_signatures(binary, table, common.get_signatures())?;
+                load_signatures(binary, table, config, common.get_signatures())?;
             }
             TableType::CONSTANT_POOL => {
-                load_constant_pool(binary, table, common.get_constant_pool())?;
+                load_constant_pool(binary, table, config, common.get_constant_pool())?;
             }
             TableType::IDENTIFIERS => {
-                load_identifiers(binary, table, common.get_identifiers())?;
+                load_identifiers(binary, table, config, common.get_identifiers())?;
             }
             TableType::ADDRESS_IDENTIFIERS => {
-                load_address_identifiers(binary, table, common.get_address_identifiers())?;
+                load_address_identifiers(binary, table, config, common.get_address_identifiers())?;
             }
             TableType::FUNCTION_DEFS
             | TableType::STRUCT_DEFS
@@ -554,24 +692,30 @@ fn build_common_tables(
 fn build_module_tables(
     binary: &[u8],
     tables: &[Table],
+    config: &DeserializerConfig,
     module: &mut CompiledModuleMut,
 ) -> BinaryLoaderResult<()> {
     for table in tables {
         match table.kind {
             TableType::STRUCT_DEFS => {
-                load_struct_defs(binary, table, &mut module.struct_defs)?;
+                load_struct_defs(binary, table, config, &mut module.struct_defs)?;
             }
             TableType::STRUCT_DEF_INST => {
-                load_struct_instantiations(binary, table, &mut module.struct_def_instantiations)?;
+                load_struct_instantiations(
+                    binary,
+                    table,
+                    config,
+                    &mut module.struct_def_instantiations,
+                )?;
             }
             TableType::FUNCTION_DEFS => {
-                load_function_defs(binary, table, &mut module.function_defs)?;
+                load_function_defs(binary, table, config, &mut module.function_defs)?;
             }
             TableType::FIELD_HANDLE => {
-                load_field_handles(binary, table, &mut module.field_handles)?;
+                load_field_handles(binary, table, config, &mut module.field_handles)?;
             }
             TableType::FIELD_INST => {
-                load_field_instantiations(binary, table, &mut module.field_instantiations)?;
+                load_field_instantiations(binary, table, config, &mut module.field_instantiations)?;
             }
             TableType::MODULE_HANDLES
             | TableType::STRUCT_HANDLES
@@ -619,194 +763,186 @@ fn build_script_tables(
     Ok(())
 }
 
-/// Builds the `ModuleHandle` table.
-fn load_module_handles(
+/// Loads the entries of a table, one by one until the end of its content. The number of entries
+/// is checked against the limit as they are loaded, and errors carry the offset in the binary at
+/// which loading failed.
+fn load_table<T>(
     binary: &[u8],
     table: &Table,
-    module_handles: &mut Vec<ModuleHandle>,
+    config: &DeserializerConfig,
+    entries: &mut Vec<T>,
+    mut load_entry: impl FnMut(&mut Cursor<&[u8]>) -> BinaryLoaderResult<T>,
 ) -> BinaryLoaderResult<()> {
     let start = table.offset as usize;
     let end = start + table.count as usize;
     let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < table.count as u64 {
-        let address = load_address_identifier_index(&mut cursor)?;
-        let name = load_identifier_index(&mut cursor)?;
-        module_handles.push(ModuleHandle { address, name });
+    while cursor.position() < u64::from(table.count) {
+        if entries.len() as u64 >= config.max_table_entries {
+            return Err(at_offset(
+                VMStatus::new(StatusCode::TOO_MANY_TABLE_ENTRIES).with_message(format!(
+                    "{:?} table exceeds {} entries",
+                    table.kind, config.max_table_entries
+                )),
+                u64::from(table.offset) + cursor.position(),
+            ));
+        }
+        let entry = load_entry(&mut cursor)
+            .map_err(|e| at_offset(e, u64::from(table.offset) + cursor.position()))?;
+        entries.push(entry);
     }
     Ok(())
 }
 
+/// Builds the `ModuleHandle` table.
+fn load_module_handles(
+    binary: &[u8],
+    table: &Table,
+    config: &DeserializerConfig,
+    module_handles: &mut Vec<ModuleHandle>,
+) -> BinaryLoaderResult<()> {
+    load_table(binary, table, config, module_handles, |cursor| {
+        let address = load_address_identifier_index(cursor)?;
+        let name = load_identifier_index(cursor)?;
+        Ok(ModuleHandle { address, name })
+    })
+}
+
 /// Builds the `StructHandle` table.
 fn load_struct_handles(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     struct_handles: &mut Vec<StructHandle>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < table.count as u64 {
-        let module = load_module_handle_index(&mut cursor)?;
-        let name = load_identifier_index(&mut cursor)?;
-        let is_nominal_resource = load_nominal_resource_flag(&mut cursor)?;
-        let type_parameters = load_kinds(&mut cursor)?;
-        struct_handles.push(StructHandle {
+    load_table(binary, table, config, struct_handles, |cursor| {
+        let module = load_module_handle_index(cursor)?;
+        let name = load_identifier_index(cursor)?;
+        let is_nominal_resource = load_nominal_resource_flag(cursor)?;
+        let type_parameters = load_kinds(cursor)?;
+        Ok(StructHandle {
             module,
             name,
             is_nominal_resource,
             type_parameters,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 /// Builds the `FunctionHandle` table.
 fn load_function_handles(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     function_handles: &mut Vec<FunctionHandle>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < table.count as u64 {
-        let module = load_module_handle_index(&mut cursor)?;
-        let name = load_identifier_index(&mut cursor)?;
-        let parameters = load_signature_index(&mut cursor)?;
-        let return_ = load_signature_index(&mut cursor)?;
-        let type_parameters = load_kinds(&mut cursor)?;
-
-        function_handles.push(FunctionHandle {
+    load_table(binary, table, config, function_handles, |cursor| {
+        let module = load_module_handle_index(cursor)?;
+        let name = load_identifier_index(cursor)?;
+        let parameters = load_signature_index(cursor)?;
+        let return_ = load_signature_index(cursor)?;
+        let type_parameters = load_kinds(cursor)?;
+        Ok(FunctionHandle {
             module,
             name,
             parameters,
             return_,
             type_parameters,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 /// Builds the `StructInstantiation` table.
 fn load_struct_instantiations(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     struct_insts: &mut Vec<StructDefInstantiation>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-
-    while cursor.position() < table.count as u64 {
-        let def = load_struct_def_index(&mut cursor)?;
-        let type_parameters = load_signature_index(&mut cursor)?;
-        struct_insts.push(StructDefInstantiation {
+    load_table(binary, table, config, struct_insts, |cursor| {
+        let def = load_struct_def_index(cursor)?;
+        let type_parameters = load_signature_index(cursor)?;
+        Ok(StructDefInstantiation {
             def,
             type_parameters,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 /// Builds the `FunctionInstantiation` table.
 fn load_function_instantiations(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     func_insts: &mut Vec<FunctionInstantiation>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < table.count as u64 {
-        let handle = load_function_handle_index(&mut cursor)?;
-        let type_parameters = load_signature_index(&mut cursor)?;
-        func_insts.push(FunctionInstantiation {
+    load_table(binary, table, config, func_insts, |cursor| {
+        let handle = load_function_handle_index(cursor)?;
+        let type_parameters = load_signature_index(cursor)?;
+        Ok(FunctionInstantiation {
             handle,
             type_parameters,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 /// Builds the `IdentifierPool`.
 fn load_identifiers(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     identifiers: &mut IdentifierPool,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < u64::from(table.count) {
-        let size = load_identifier_size(&mut cursor)?;
-        let mut buffer: Vec<u8> = vec![0u8; size];
-        if let Ok(count) = cursor.read(&mut buffer) {
-            if count != size {
-                return Err(VMStatus::new(StatusCode::MALFORMED)
-                    .with_message("Bad Identifier pool size".to_string()));
-            }
-            let s = Identifier::from_utf8(buffer).map_err(|_| {
-                VMStatus::new(StatusCode::MALFORMED).with_message("Invalid Identifier".to_string())
-            })?;
-            identifiers.push(s);
-        }
-    }
-    Ok(())
+    load_table(binary, table, config, identifiers, |cursor| {
+        let size = load_identifier_size(cursor)?;
+        let buffer = read_bytes(cursor, size).map_err(|_| {
+            VMStatus::new(StatusCode::MALFORMED)
+                .with_message("Bad Identifier pool size".to_string())
+        })?;
+        Identifier::from_utf8(buffer).map_err(|_| {
+            VMStatus::new(StatusCode::MALFORMED).with_message("Invalid Identifier".to_string())
+        })
+    })
 }
 
 /// Builds the `AddressIdentifierPool`.
 fn load_address_identifiers(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     addresses: &mut AddressIdentifierPool,
 ) -> BinaryLoaderResult<()> {
-    let mut start = table.offset as usize;
     if table.count as usize % AccountAddress::LENGTH != 0 {
-        return Err(VMStatus::new(StatusCode::MALFORMED)
-            .with_message("Bad Address Identifier pool size".to_string()));
+        return Err(at_offset(
+            VMStatus::new(StatusCode::MALFORMED)
+                .with_message("Bad Address Identifier pool size".to_string()),
+            u64::from(table.offset),
+        ));
     }
-    for _i in 0..table.count as usize / AccountAddress::LENGTH {
-        let end_addr = start + AccountAddress::LENGTH;
-        let address = (&binary[start..end_addr]).try_into();
-        if address.is_err() {
-            return Err(VMStatus::new(StatusCode::MALFORMED)
-                .with_message("Invalid Address format".to_string()));
-        }
-        start = end_addr;
-
-        addresses.push(address.unwrap());
-    }
-    Ok(())
+    load_table(binary, table, config, addresses, |cursor| {
+        let bytes = read_bytes(cursor, AccountAddress::LENGTH)?;
+        bytes.as_slice().try_into().map_err(|_| {
+            VMStatus::new(StatusCode::MALFORMED).with_message("Invalid Address format".to_string())
+        })
+    })
 }
 
 /// Builds the `ConstantPool`.
 fn load_constant_pool(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     constants: &mut ConstantPool,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < u64::from(table.count) {
-        constants.push(load_constant(&mut cursor)?)
-    }
-    Ok(())
+    load_table(binary, table, config, constants, load_constant)
 }
 
 /// Build a single `Constant`
 fn load_constant(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Constant> {
     let type_ = load_signature_token(cursor)?;
     let size = load_constant_size(cursor)?;
-    let mut data: Vec<u8> = vec![0u8; size];
-    let count = cursor.read(&mut data).map_err(|_| {
-        VMStatus::new(StatusCode::MALFORMED).with_message("Unexpected end of table".to_string())
+    let data = read_bytes(cursor, size).map_err(|_| {
+        VMStatus::new(StatusCode::MALFORMED).with_message("Bad Constant data size".to_string())
     })?;
-    if count != size {
-        return Err(
-            VMStatus::new(StatusCode::MALFORMED).with_message("Bad Constant data size".to_string())
-        );
-    }
     Ok(Constant { type_, data })
 }
 
@@ -814,15 +950,12 @@ fn load_constant(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Constant> {
 fn load_signatures(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     signatures: &mut SignaturePool,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < u64::from(table.count) {
-        signatures.push(Signature(load_signature_tokens(&mut cursor)?));
-    }
-    Ok(())
+    load_table(binary, table, config, signatures, |cursor| {
+        Ok(Signature(load_signature_tokens(cursor)?))
+    })
 }
 
 fn load_signature_tokens(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Vec<SignatureToken>> {
@@ -922,6 +1055,7 @@ fn load_signature_token(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Signat
                 S::U8 => T::Saturated(SignatureToken::U8),
                 S::U64 => T::Saturated(SignatureToken::U64),
                 S::U128 => T::Saturated(SignatureToken::U128),
+                S::U256 => T::Saturated(SignatureToken::U256),
                 S::ADDRESS => T::Saturated(SignatureToken::Address),
                 S::SIGNER => T::Saturated(SignatureToken::Signer),
                 S::VECTOR => T::Vector,
@@ -1004,14 +1138,12 @@ fn load_kinds(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Vec<Kind>> {
 fn load_struct_defs(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     struct_defs: &mut Vec<StructDefinition>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < u64::from(table.count) {
-        let struct_handle = load_struct_handle_index(&mut cursor)?;
-        let field_information_flag = match read_u8(&mut cursor) {
+    load_table(binary, table, config, struct_defs, |cursor| {
+        let struct_handle = load_struct_handle_index(cursor)?;
+        let field_information_flag = match read_u8(cursor) {
             Ok(byte) => SerializedNativeStructFlag::from_u8(byte)?,
             Err(_) => {
                 return Err(VMStatus::new(StatusCode::MALFORMED)
@@ -1021,16 +1153,15 @@ fn load_struct_defs(
         let field_information = match field_information_flag {
             SerializedNativeStructFlag::NATIVE => StructFieldInformation::Native,
             SerializedNativeStructFlag::DECLARED => {
-                let fields = load_field_defs(&mut cursor)?;
+                let fields = load_field_defs(cursor)?;
                 StructFieldInformation::Declared(fields)
             }
         };
-        struct_defs.push(StructDefinition {
+        Ok(StructDefinition {
             struct_handle,
             field_information,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 fn load_field_defs(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<Vec<FieldDefinition>> {
@@ -1055,60 +1186,44 @@ fn load_field_def(cursor: &mut Cursor<&[u8]>) -> BinaryLoaderResult<FieldDefinit
 fn load_function_defs(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     func_defs: &mut Vec<FunctionDefinition>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    while cursor.position() < u64::from(table.count) {
-        let func_def = load_function_def(&mut cursor)?;
-        func_defs.push(func_def);
-    }
-    Ok(())
+    load_table(binary, table, config, func_defs, load_function_def)
 }
 
+/// Builds the `FieldHandle` table.
 fn load_field_handles(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     field_handles: &mut Vec<FieldHandle>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    loop {
-        if cursor.position() == u64::from(table.count) {
-            break;
-        }
-        let struct_idx = load_struct_def_index(&mut cursor)?;
-        let offset = load_field_offset(&mut cursor)?;
-        field_handles.push(FieldHandle {
+    load_table(binary, table, config, field_handles, |cursor| {
+        let struct_idx = load_struct_def_index(cursor)?;
+        let offset = load_field_offset(cursor)?;
+        Ok(FieldHandle {
             owner: struct_idx,
             field: offset,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
+/// Builds the `FieldInstantiation` table.
 fn load_field_instantiations(
     binary: &[u8],
     table: &Table,
+    config: &DeserializerConfig,
     field_insts: &mut Vec<FieldInstantiation>,
 ) -> BinaryLoaderResult<()> {
-    let start = table.offset as usize;
-    let end = start + table.count as usize;
-    let mut cursor = Cursor::new(&binary[start..end]);
-    loop {
-        if cursor.position() == u64::from(table.count) {
-            break;
-        }
-        let handle = load_field_handle_index(&mut cursor)?;
-        let type_parameters = load_signature_index(&mut cursor)?;
-        field_insts.push(FieldInstantiation {
+    load_table(binary, table, config, field_insts, |cursor| {
+        let handle = load_field_handle_index(cursor)?;
+        let type_parameters = load_signature_index(cursor)?;
+        Ok(FieldInstantiation {
             handle,
             type_parameters,
-        });
-    }
-    Ok(())
+        })
+    })
 }
 
 /// Deserializes a `FunctionDefinition`.
@@ -1189,6 +1304,11 @@ fn load_code(cursor: &mut Cursor<&[u8]>, code: &mut Vec<Bytecode>) -> BinaryLoad
             Opcodes::CAST_U8 => Bytecode::CastU8,
             Opcodes::CAST_U64 => Bytecode::CastU64,
             Opcodes::CAST_U128 => Bytecode::CastU128,
+            Opcodes::LD_U256 => {
+                let value = read_u256_internal(cursor)?;
+                Bytecode::LdU256(value)
+            }
+            Opcodes::CAST_U256 => Bytecode::CastU256,
             Opcodes::LD_CONST => Bytecode::LdConst(load_constant_pool_index(cursor)?),
             Opcodes::LD_TRUE => Bytecode::LdTrue,
             Opcodes::LD_FALSE => Bytecode::LdFalse,
@@ -1258,6 +1378,20 @@ fn load_code(cursor: &mut Cursor<&[u8]>, code: &mut Vec<Bytecode>) -> BinaryLoad
             }
             Opcodes::FREEZE_REF => Bytecode::FreezeRef,
             Opcodes::NOP => Bytecode::Nop,
+            Opcodes::VEC_PACK => {
+                let sig_idx = load_signature_index(cursor)?;
+                Bytecode::VecPack(sig_idx, read_u64_internal(cursor)?)
+            }
+            Opcodes::VEC_LEN => Bytecode::VecLen(load_signature_index(cursor)?),
+            Opcodes::VEC_IMM_BORROW => Bytecode::VecImmBorrow(load_signature_index(cursor)?),
+            Opcodes::VEC_MUT_BORROW => Bytecode::VecMutBorrow(load_signature_index(cursor)?),
+            Opcodes::VEC_PUSH_BACK => Bytecode::VecPushBack(load_signature_index(cursor)?),
+            Opcodes::VEC_POP_BACK => Bytecode::VecPopBack(load_signature_index(cursor)?),
+            Opcodes::VEC_UNPACK => {
+                let sig_idx = load_signature_index(cursor)?;
+                Bytecode::VecUnpack(sig_idx, read_u64_internal(cursor)?)
+            }
+            Opcodes::VEC_SWAP => Bytecode::VecSwap(load_signature_index(cursor)?),
         };
         code.push(bytecode);
     }
@@ -1300,6 +1434,7 @@ impl SerializedType {
             0xA => Ok(SerializedType::VECTOR),
             0xB => Ok(SerializedType::STRUCT_INST),
             0xC => Ok(SerializedType::SIGNER),
+            0xD => Ok(SerializedType::U256),
             _ => Err(VMStatus::new(StatusCode::UNKNOWN_SERIALIZED_TYPE)),
         }
     }
@@ -1405,6 +1540,16 @@ impl Opcodes {
             0x40 => Ok(Opcodes::NOP),
             0x41 => Ok(Opcodes::MOVE_TO),
             0x42 => Ok(Opcodes::MOVE_TO_GENERIC),
+            0x43 => Ok(Opcodes::LD_U256),
+            0x44 => Ok(Opcodes::CAST_U256),
+            0x45 => Ok(Opcodes::VEC_PACK),
+            0x46 => Ok(Opcodes::VEC_LEN),
+            0x47 => Ok(Opcodes::VEC_IMM_BORROW),
+            0x48 => Ok(Opcodes::VEC_MUT_BORROW),
+            0x49 => Ok(Opcodes::VEC_PUSH_BACK),
+            0x4A => Ok(Opcodes::VEC_POP_BACK),
+            0x4B => Ok(Opcodes::VEC_UNPACK),
+            0x4C => Ok(Opcodes::VEC_SWAP),
             _ => Err(VMStatus::new(StatusCode::UNKNOWN_OPCODE)),
         }
     }
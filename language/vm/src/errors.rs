@@ -24,6 +24,12 @@ pub const EACCOUNT_DOES_NOT_EXIST: u64 = 5; // transaction sender's account does
 pub const ECANT_PAY_GAS_DEPOSIT: u64 = 6; // insufficient balance to pay for gas deposit
 pub const ETRANSACTION_EXPIRED: u64 = 7; // transaction expiration time exceeds block time.
 pub const ENO_ACCOUNT_ROLE: u64 = 8; // Account does not have a role
+pub const ETRANSACTION_EXPIRATION_TOO_FAR: u64 = 9; // expiration time beyond the expiration window
+pub const EFEE_PAYER_DOES_NOT_EXIST: u64 = 12; // transaction fee payer's account does not exist
+pub const EFEE_PAYER_FROZEN: u64 = 13; // fee paying account is frozen
+pub const EBAD_FEE_PAYER_AUTHENTICATION_KEY: u64 = 14; // fee payer auth key in transaction is invalid
+pub const EFEE_PAYER_IS_SENDER: u64 = 15; // sponsored transaction paid for by its sender
+pub const EUNSUPPORTED_AUTHENTICATION_SCHEME: u64 = 16; // signature scheme is not enabled
 
 /// Generic error codes. These codes don't have any special meaning for the VM, but they are useful
 /// conventions for debugging
@@ -76,6 +82,20 @@ pub fn convert_prologue_runtime_error(err: &VMStatus, txn_sender: &AccountAddres
             }
             Some(ETRANSACTION_EXPIRED) => VMStatus::new(StatusCode::TRANSACTION_EXPIRED),
             Some(ENO_ACCOUNT_ROLE) => VMStatus::new(StatusCode::NO_ACCOUNT_ROLE),
+            Some(ETRANSACTION_EXPIRATION_TOO_FAR) => {
+                VMStatus::new(StatusCode::TRANSACTION_EXPIRATION_TOO_FAR)
+            }
+            Some(EFEE_PAYER_DOES_NOT_EXIST) => {
+                VMStatus::new(StatusCode::FEE_PAYER_ACCOUNT_DOES_NOT_EXIST)
+            }
+            Some(EFEE_PAYER_FROZEN) => VMStatus::new(StatusCode::FEE_PAYER_ACCOUNT_FROZEN),
+            Some(EBAD_FEE_PAYER_AUTHENTICATION_KEY) => {
+                VMStatus::new(StatusCode::INVALID_FEE_PAYER_AUTH_KEY)
+            }
+            Some(EFEE_PAYER_IS_SENDER) => VMStatus::new(StatusCode::INVALID_FEE_PAYER),
+            Some(EUNSUPPORTED_AUTHENTICATION_SCHEME) => {
+                VMStatus::new(StatusCode::UNSUPPORTED_AUTHENTICATION_SCHEME)
+            }
             // This should never happen...
             _ => err.clone(),
         }
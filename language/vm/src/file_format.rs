@@ -35,6 +35,7 @@ use move_core_types::{
     account_address::AccountAddress,
     identifier::{IdentStr, Identifier},
     language_storage::ModuleId,
+    u256::U256,
     vm_error::{StatusCode, VMStatus},
 };
 use num_variants::NumVariants;
@@ -542,6 +543,8 @@ pub enum SignatureToken {
     U64,
     /// Unsigned integers, 128 bits length.
     U128,
+    /// Unsigned integers, 256 bits length.
+    U256,
     /// Address, a 16 bytes immutable type.
     Address,
     /// Signer, a 16 bytes immutable type representing the capability to publish at an address
@@ -584,7 +587,8 @@ impl<'a> Iterator for SignatureTokenPreorderTraversalIter<'a> {
                         self.stack.extend(inner_toks.iter().rev())
                     }
 
-                    Signer | Bool | Address | U8 | U64 | U128 | Struct(_) | TypeParameter(_) => (),
+                    Signer | Bool | Address | U8 | U64 | U128 | U256 | Struct(_)
+                    | TypeParameter(_) => (),
                 }
                 Some(tok)
             }
@@ -607,6 +611,7 @@ impl Arbitrary for SignatureToken {
             Just(U8),
             Just(U64),
             Just(U128),
+            Just(U256),
             Just(Address),
             any::<StructHandleIndex>().prop_map(Struct),
             any::<TypeParameterIndex>().prop_map(TypeParameter),
@@ -634,6 +639,7 @@ impl std::fmt::Debug for SignatureToken {
             SignatureToken::U8 => write!(f, "U8"),
             SignatureToken::U64 => write!(f, "U64"),
             SignatureToken::U128 => write!(f, "U128"),
+            SignatureToken::U256 => write!(f, "U256"),
             SignatureToken::Address => write!(f, "Address"),
             SignatureToken::Signer => write!(f, "Signer"),
             SignatureToken::Vector(boxed) => write!(f, "Vector({:?})", boxed),
@@ -663,6 +669,7 @@ impl SignatureToken {
             | U8
             | U64
             | U128
+            | U256
             | Address
             | Signer
             | Struct(_)
@@ -678,7 +685,7 @@ impl SignatureToken {
     pub fn is_integer(&self) -> bool {
         use SignatureToken::*;
         match self {
-            U8 | U64 | U128 => true,
+            U8 | U64 | U128 | U256 => true,
             Bool
             | Address
             | Signer
@@ -829,6 +836,18 @@ pub enum Bytecode {
     ///
     /// ```..., integer_value -> ..., u128_value```
     CastU128,
+    /// Push a U256 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u256_value```
+    LdU256(U256),
+    /// Convert the value at the top of the stack into u256.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u256_value```
+    CastU256,
     /// Push a `Constant` onto the stack. The value is loaded and deserialized (according to it's
     /// type) from the the `ConstantPool` via `ConstantPoolIndex`
     ///
@@ -1167,13 +1186,66 @@ pub enum Bytecode {
     ///
     /// ```..., u64_value(1), u64_value(2) -> ..., u64_value```
     Shr,
+    /// Create a vector by packing a statically known number of elements from the top of the
+    /// stack. The `SignatureIndex` points to a signature with the single element type.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., e1, e2, ..., eN -> ..., vec[e1, e2, ..., eN]```
+    VecPack(SignatureIndex, u64),
+    /// Return the length of the vector.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference -> ..., u64_value```
+    VecLen(SignatureIndex),
+    /// Acquire an immutable reference to the element at a given index of the vector. Abort the
+    /// execution if the index is out of bounds.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference, u64_value -> .., element_reference```
+    VecImmBorrow(SignatureIndex),
+    /// Acquire a mutable reference to the element at a given index of the vector. Abort the
+    /// execution if the index is out of bounds.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference, u64_value -> .., element_reference```
+    VecMutBorrow(SignatureIndex),
+    /// Add an element to the end of the vector.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference, element -> ...```
+    VecPushBack(SignatureIndex),
+    /// Pop an element from the end of vector. Abort if the vector is empty.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference -> ..., element```
+    VecPopBack(SignatureIndex),
+    /// Destroy the vector and unpack a statically known number of elements onto the stack. Abort
+    /// if the vector does not have a length N.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vec[e1, e2, ..., eN] -> ..., e1, e2, ..., eN```
+    VecUnpack(SignatureIndex, u64),
+    /// Swaps the elements at two indices in the vector. Abort the execution if any of the indices
+    /// is out of bounds.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., vector_reference, u64_value(1), u64_value(2) -> ...```
+    VecSwap(SignatureIndex),
     /// No operation.
     ///
     /// Stack transition: none
     Nop,
 }
 
-pub const NUMBER_OF_NATIVE_FUNCTIONS: usize = 17;
+pub const NUMBER_OF_NATIVE_FUNCTIONS: usize = 23;
 
 impl ::std::fmt::Debug for Bytecode {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
@@ -1189,6 +1261,8 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::CastU8 => write!(f, "CastU8"),
             Bytecode::CastU64 => write!(f, "CastU64"),
             Bytecode::CastU128 => write!(f, "CastU128"),
+            Bytecode::LdU256(a) => write!(f, "LdU256({})", a),
+            Bytecode::CastU256 => write!(f, "CastU256"),
             Bytecode::LdConst(a) => write!(f, "LdConst({})", a),
             Bytecode::LdTrue => write!(f, "LdTrue"),
             Bytecode::LdFalse => write!(f, "LdFalse"),
@@ -1243,6 +1317,14 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::MoveToSenderGeneric(a) => write!(f, "MoveToSenderGeneric({:?})", a),
             Bytecode::MoveTo(a) => write!(f, "MoveTo({:?})", a),
             Bytecode::MoveToGeneric(a) => write!(f, "MoveToGeneric({:?})", a),
+            Bytecode::VecPack(a, n) => write!(f, "VecPack({}, {})", a, n),
+            Bytecode::VecLen(a) => write!(f, "VecLen({})", a),
+            Bytecode::VecImmBorrow(a) => write!(f, "VecImmBorrow({})", a),
+            Bytecode::VecMutBorrow(a) => write!(f, "VecMutBorrow({})", a),
+            Bytecode::VecPushBack(a) => write!(f, "VecPushBack({})", a),
+            Bytecode::VecPopBack(a) => write!(f, "VecPopBack({})", a),
+            Bytecode::VecUnpack(a, n) => write!(f, "VecUnpack({}, {})", a, n),
+            Bytecode::VecSwap(a) => write!(f, "VecSwap({})", a),
             Bytecode::Nop => write!(f, "Nop"),
         }
     }
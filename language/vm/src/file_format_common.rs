@@ -12,6 +12,7 @@
 //! It's used to compress mostly indexes into the main binary tables.
 use crate::file_format::Bytecode;
 use anyhow::{bail, Result};
+use move_core_types::u256::U256;
 use std::{
     io::{Cursor, Read},
     mem::size_of,
@@ -39,6 +40,12 @@ pub const TABLE_OFFSET_MAX: u64 = 0xffff_ffff;
 pub const TABLE_SIZE_MAX: u64 = 0xffff_ffff;
 pub const TABLE_CONTENT_SIZE_MAX: u64 = 0xffff_ffff;
 
+/// Default limit on the size of a binary enforced by the deserializer
+pub const BINARY_SIZE_MAX: u64 = 0xffff_ffff;
+/// Default limit on the number of entries of each table enforced by the deserializer: as tables
+/// are indexed by u16, more entries could not be referenced
+pub const TABLE_ENTRIES_MAX: u64 = TABLE_INDEX_MAX + 1;
+
 pub const TABLE_INDEX_MAX: u64 = 65535;
 pub const SIGNATURE_INDEX_MAX: u64 = TABLE_INDEX_MAX;
 pub const ADDRESS_INDEX_MAX: u64 = TABLE_INDEX_MAX;
@@ -114,6 +121,7 @@ pub enum SerializedType {
     VECTOR                  = 0xA,
     STRUCT_INST             = 0xB,
     SIGNER                  = 0xC,
+    U256                    = 0xD,
 }
 
 #[rustfmt::skip]
@@ -216,6 +224,16 @@ pub enum Opcodes {
     NOP                         = 0x40,
     MOVE_TO                     = 0x41,
     MOVE_TO_GENERIC             = 0x42,
+    LD_U256                     = 0x43,
+    CAST_U256                   = 0x44,
+    VEC_PACK                    = 0x45,
+    VEC_LEN                     = 0x46,
+    VEC_IMM_BORROW              = 0x47,
+    VEC_MUT_BORROW              = 0x48,
+    VEC_PUSH_BACK               = 0x49,
+    VEC_POP_BACK                = 0x4A,
+    VEC_UNPACK                  = 0x4B,
+    VEC_SWAP                    = 0x4C,
 }
 
 /// Upper limit on the binary size
@@ -324,6 +342,11 @@ pub fn write_u128(binary: &mut BinaryData, value: u128) -> Result<()> {
     binary.extend(&value.to_le_bytes())
 }
 
+/// Write a `U256` in Little Endian format.
+pub fn write_u256(binary: &mut BinaryData, value: U256) -> Result<()> {
+    binary.extend(&value.to_le_bytes())
+}
+
 pub fn read_u8(cursor: &mut Cursor<&[u8]>) -> Result<u8> {
     let mut buf = [0; 1];
     cursor.read_exact(&mut buf)?;
@@ -371,6 +394,8 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         CastU8 => Opcodes::CAST_U8,
         CastU64 => Opcodes::CAST_U64,
         CastU128 => Opcodes::CAST_U128,
+        LdU256(_) => Opcodes::LD_U256,
+        CastU256 => Opcodes::CAST_U256,
         LdConst(_) => Opcodes::LD_CONST,
         LdTrue => Opcodes::LD_TRUE,
         LdFalse => Opcodes::LD_FALSE,
@@ -426,6 +451,14 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         Nop => Opcodes::NOP,
         MoveTo(_) => Opcodes::MOVE_TO,
         MoveToGeneric(_) => Opcodes::MOVE_TO_GENERIC,
+        VecPack(..) => Opcodes::VEC_PACK,
+        VecLen(_) => Opcodes::VEC_LEN,
+        VecImmBorrow(_) => Opcodes::VEC_IMM_BORROW,
+        VecMutBorrow(_) => Opcodes::VEC_MUT_BORROW,
+        VecPushBack(_) => Opcodes::VEC_PUSH_BACK,
+        VecPopBack(_) => Opcodes::VEC_POP_BACK,
+        VecUnpack(..) => Opcodes::VEC_UNPACK,
+        VecSwap(_) => Opcodes::VEC_SWAP,
     };
     opcode as u8
 }
@@ -373,7 +373,7 @@ impl StDefnMaterializeState {
             }
             Vector(targ) => self.contains_nominal_resource(targ),
             Reference(token) | MutableReference(token) => self.contains_nominal_resource(token),
-            Bool | U8 | U64 | U128 | Address | TypeParameter(_) => false,
+            Bool | U8 | U64 | U128 | U256 | Address | TypeParameter(_) => false,
         }
     }
 }
@@ -55,6 +55,7 @@ pub enum SignatureTokenGen {
     U8,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     TypeParameter(PropIndex),
@@ -95,7 +96,8 @@ impl SignatureTokenGen {
     pub fn owned_non_struct_strategy() -> impl Strategy<Value = Self> {
         use SignatureTokenGen::*;
 
-        static OWNED_NON_STRUCTS: &[SignatureTokenGen] = &[Bool, U8, U64, U128, Address, Signer];
+        static OWNED_NON_STRUCTS: &[SignatureTokenGen] =
+            &[Bool, U8, U64, U128, U256, Address, Signer];
 
         select(OWNED_NON_STRUCTS)
     }
@@ -127,6 +129,7 @@ impl SignatureTokenGen {
             U8 => SignatureToken::U8,
             U64 => SignatureToken::U64,
             U128 => SignatureToken::U128,
+            U256 => SignatureToken::U256,
             Address => SignatureToken::Address,
             Signer => SignatureToken::Signer,
             Struct(idx) => {
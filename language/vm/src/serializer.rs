@@ -602,6 +602,7 @@ fn serialize_signature_token(binary: &mut BinaryData, token: &SignatureToken) ->
             SignatureToken::U8 => binary.push(SerializedType::U8 as u8)?,
             SignatureToken::U64 => binary.push(SerializedType::U64 as u8)?,
             SignatureToken::U128 => binary.push(SerializedType::U128 as u8)?,
+            SignatureToken::U256 => binary.push(SerializedType::U256 as u8)?,
             SignatureToken::Address => binary.push(SerializedType::ADDRESS as u8)?,
             SignatureToken::Signer => binary.push(SerializedType::SIGNER as u8)?,
             SignatureToken::Vector(_) => {
@@ -706,6 +707,11 @@ fn serialize_instruction_inner(binary: &mut BinaryData, opcode: &Bytecode) -> Re
         Bytecode::CastU8 => binary.push(Opcodes::CAST_U8 as u8),
         Bytecode::CastU64 => binary.push(Opcodes::CAST_U64 as u8),
         Bytecode::CastU128 => binary.push(Opcodes::CAST_U128 as u8),
+        Bytecode::LdU256(value) => {
+            binary.push(Opcodes::LD_U256 as u8)?;
+            write_u256(binary, *value)
+        }
+        Bytecode::CastU256 => binary.push(Opcodes::CAST_U256 as u8),
         Bytecode::LdConst(const_idx) => {
             binary.push(Opcodes::LD_CONST as u8)?;
             serialize_constant_pool_index(binary, const_idx)
@@ -844,6 +850,40 @@ fn serialize_instruction_inner(binary: &mut BinaryData, opcode: &Bytecode) -> Re
             serialize_struct_def_inst_index(binary, class_idx)
         }
         Bytecode::Nop => binary.push(Opcodes::NOP as u8),
+        Bytecode::VecPack(sig_idx, num) => {
+            binary.push(Opcodes::VEC_PACK as u8)?;
+            serialize_signature_index(binary, sig_idx)?;
+            write_u64(binary, *num)
+        }
+        Bytecode::VecLen(sig_idx) => {
+            binary.push(Opcodes::VEC_LEN as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
+        Bytecode::VecImmBorrow(sig_idx) => {
+            binary.push(Opcodes::VEC_IMM_BORROW as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
+        Bytecode::VecMutBorrow(sig_idx) => {
+            binary.push(Opcodes::VEC_MUT_BORROW as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
+        Bytecode::VecPushBack(sig_idx) => {
+            binary.push(Opcodes::VEC_PUSH_BACK as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
+        Bytecode::VecPopBack(sig_idx) => {
+            binary.push(Opcodes::VEC_POP_BACK as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
+        Bytecode::VecUnpack(sig_idx, num) => {
+            binary.push(Opcodes::VEC_UNPACK as u8)?;
+            serialize_signature_index(binary, sig_idx)?;
+            write_u64(binary, *num)
+        }
+        Bytecode::VecSwap(sig_idx) => {
+            binary.push(Opcodes::VEC_SWAP as u8)?;
+            serialize_signature_index(binary, sig_idx)
+        }
     };
     res?;
     Ok(())
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    file_format::{CompiledModule, CompiledScript},
+    deserializer::DeserializerConfig,
+    file_format::{basic_test_module, CompiledModule, CompiledScript},
     file_format_common::*,
 };
 use move_core_types::vm_error::StatusCode;
@@ -74,3 +75,72 @@ static EMPTY_SCRIPT: &[u8] =
 fn deserialize_file() {
     CompiledScript::deserialize(EMPTY_SCRIPT).expect("script should deserialize properly");
 }
+
+#[test]
+fn binary_too_large() {
+    let mut binary = vec![];
+    basic_test_module().serialize(&mut binary).unwrap();
+    let config = DeserializerConfig {
+        max_binary_size: binary.len() as u64 - 1,
+        ..DeserializerConfig::default()
+    };
+    let res = CompiledModule::deserialize_with_config(&binary, &config);
+    assert_eq!(
+        res.expect_err("Expected binary too large").major_status,
+        StatusCode::BINARY_TOO_LARGE
+    );
+}
+
+#[test]
+fn too_many_table_entries() {
+    let mut binary = vec![];
+    let module = basic_test_module();
+    module.serialize(&mut binary).unwrap();
+    let config = DeserializerConfig {
+        max_table_entries: module.identifiers.len() as u64 - 1,
+        ..DeserializerConfig::default()
+    };
+    let status = CompiledModule::deserialize_with_config(&binary, &config)
+        .expect_err("Expected too many table entries");
+    assert_eq!(status.major_status, StatusCode::TOO_MANY_TABLE_ENTRIES);
+    assert!(status.message.unwrap().contains("at offset"));
+
+    let config = DeserializerConfig {
+        max_table_entries: module.identifiers.len() as u64,
+        ..DeserializerConfig::default()
+    };
+    CompiledModule::deserialize_with_config(&binary, &config)
+        .expect("module should deserialize within the limits");
+}
+
+#[test]
+fn truncated_table_contents() {
+    let mut binary = vec![];
+    basic_test_module().serialize(&mut binary).unwrap();
+    binary.truncate(binary.len() - 4);
+    let status = CompiledModule::deserialize(&binary).expect_err("Expected malformed binary");
+    assert_eq!(status.major_status, StatusCode::MALFORMED);
+}
+
+#[test]
+fn non_canonical_binary() {
+    let mut binary = vec![];
+    basic_test_module().serialize(&mut binary).unwrap();
+    let config = DeserializerConfig {
+        require_canonical: true,
+        ..DeserializerConfig::default()
+    };
+    CompiledModule::deserialize_with_config(&binary, &config)
+        .expect("serialized module should be canonical");
+
+    // Trailing bytes are ignored by the deserializer, but are not part of the canonical form
+    binary.push(0);
+    CompiledModule::deserialize(&binary).expect("trailing bytes should be ignored");
+    let status = CompiledModule::deserialize_with_config(&binary, &config)
+        .expect_err("Expected non canonical binary");
+    assert_eq!(status.major_status, StatusCode::NON_CANONICAL_BINARY);
+    assert!(status
+        .message
+        .unwrap()
+        .contains(&format!("at offset {}", binary.len() - 1)));
+}
@@ -0,0 +1,257 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admin API for operating a running node without a restart. It is only served on localhost, and
+//! every request must present the token in the configured token file as a bearer token, i.e.,
+//! with an `Authorization: Bearer <token>` header.
+//!
+//! - `POST /mempool/flush`: removes all transactions from mempool.
+//! - `POST /peers/<peer id>/disconnect`: disconnects a peer on all networks. The connectivity
+//!   manager dials seed peers and validators again later on.
+//! - `POST /log_level/<level>`: sets the log level, until the next restart or config reload.
+//! - `POST /state_sync/rebootstrap`: makes state sync start over from the local storage.
+//! - `GET /consensus/state`: dumps the state of consensus as JSON.
+
+use consensus::consensus_provider::ConsensusHandle;
+use futures::future;
+use hyper::{
+    header::AUTHORIZATION,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use libra_config::network_id::NetworkId;
+use libra_logger::{prelude::*, Level};
+use libra_mempool::MempoolHandle;
+use libra_types::PeerId;
+use network::peer_manager::{ConnectionRequestSender, PeerManagerError};
+use state_synchronizer::StateSyncClient;
+use std::{
+    fs, io,
+    net::{Ipv4Addr, SocketAddr},
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+use tokio::runtime::{Builder, Runtime};
+
+/// The components of the node operated through the admin API
+#[derive(Clone)]
+pub struct AdminHandles {
+    pub mempool: MempoolHandle,
+    pub networks: Vec<(NetworkId, ConnectionRequestSender)>,
+    pub state_sync: Arc<StateSyncClient>,
+    /// Only validators run consensus
+    pub consensus: Option<ConsensusHandle>,
+}
+
+struct AdminContext {
+    token: String,
+    handles: AdminHandles,
+}
+
+pub struct AdminService {
+    _runtime: Runtime,
+}
+
+impl AdminService {
+    /// Starts serving the admin API on `port` of localhost, with the token read from `token_file`.
+    pub fn new(port: u16, token_file: &Path, handles: AdminHandles) -> io::Result<Self> {
+        let token = fs::read_to_string(token_file)?.trim().to_string();
+        if token.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Admin token file {:?} is empty", token_file),
+            ));
+        }
+        let context = Arc::new(AdminContext { token, handles });
+
+        let runtime = Builder::new()
+            .thread_name("admin-")
+            .basic_scheduler()
+            .enable_all()
+            .build()?;
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let server = runtime
+            .enter(|| Server::try_bind(&address))
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?
+            .serve(make_service_fn(move |_| {
+                let context = Arc::clone(&context);
+                future::ok::<_, hyper::Error>(service_fn(move |req| {
+                    serve_admin(Arc::clone(&context), req)
+                }))
+            }));
+        runtime.spawn(async move {
+            if let Err(e) = server.await {
+                error!("[admin] server failed: {}", e);
+            }
+        });
+        info!("[admin] Admin API listening on {}", address);
+
+        Ok(Self { _runtime: runtime })
+    }
+}
+
+async fn serve_admin(
+    context: Arc<AdminContext>,
+    req: Request<Body>,
+) -> Result<Response<Body>, hyper::Error> {
+    let authorization = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if !is_authorized(authorization, &context.token) {
+        return Ok(response(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid admin token".to_string(),
+        ));
+    }
+
+    let handles = &context.handles;
+    let segments: Vec<&str> = req.uri().path().trim_matches('/').split('/').collect();
+    let resp = match (req.method(), segments.as_slice()) {
+        (&Method::POST, ["mempool", "flush"]) => {
+            let removed = handles.mempool.flush();
+            info!("[admin] Flushed {} transactions from mempool", removed);
+            response(
+                StatusCode::OK,
+                format!("Removed {} transactions from mempool", removed),
+            )
+        }
+        (&Method::POST, ["peers", peer_id, "disconnect"]) => match PeerId::from_str(peer_id) {
+            Ok(peer_id) => disconnect_peer(&handles.networks, peer_id).await,
+            Err(e) => response(StatusCode::BAD_REQUEST, format!("Invalid peer id: {}", e)),
+        },
+        (&Method::POST, ["log_level", level]) => match Level::from_str(level) {
+            Ok(level) => {
+                libra_logger::set_level(level);
+                info!("[admin] Set log level to {}", level);
+                response(StatusCode::OK, format!("Log level set to {}", level))
+            }
+            Err(_) => response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid log level: {}", level),
+            ),
+        },
+        (&Method::POST, ["state_sync", "rebootstrap"]) => {
+            match handles.state_sync.rebootstrap().await {
+                Ok(()) => {
+                    info!("[admin] Rebootstrapped state sync");
+                    response(StatusCode::OK, "State sync rebootstrapped".to_string())
+                }
+                Err(e) => response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to rebootstrap state sync: {}", e),
+                ),
+            }
+        }
+        (&Method::GET, ["consensus", "state"]) => match &handles.consensus {
+            Some(consensus) => match consensus.dump_state().await {
+                Ok(state) => {
+                    let mut resp = response(
+                        StatusCode::OK,
+                        serde_json::to_string_pretty(&state)
+                            .expect("Consensus state should serialize"),
+                    );
+                    resp.headers_mut().insert(
+                        hyper::header::CONTENT_TYPE,
+                        hyper::header::HeaderValue::from_static("application/json"),
+                    );
+                    resp
+                }
+                Err(e) => response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to dump the consensus state: {}", e),
+                ),
+            },
+            None => response(
+                StatusCode::NOT_FOUND,
+                "This node does not run consensus".to_string(),
+            ),
+        },
+        _ => response(StatusCode::NOT_FOUND, "Unknown admin operation".to_string()),
+    };
+    Ok(resp)
+}
+
+async fn disconnect_peer(
+    networks: &[(NetworkId, ConnectionRequestSender)],
+    peer_id: PeerId,
+) -> Response<Body> {
+    let mut disconnected = vec![];
+    for (network_id, sender) in networks {
+        match sender.clone().disconnect_peer(peer_id).await {
+            Ok(()) => disconnected.push(network_id.to_string()),
+            Err(PeerManagerError::NotConnected(_)) => (),
+            Err(e) => {
+                return response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!(
+                        "Failed to disconnect peer {} on network {}: {}",
+                        peer_id, network_id, e
+                    ),
+                )
+            }
+        }
+    }
+
+    if disconnected.is_empty() {
+        response(
+            StatusCode::NOT_FOUND,
+            format!("Peer {} is not connected", peer_id),
+        )
+    } else {
+        info!(
+            "[admin] Disconnected peer {} on {}",
+            peer_id,
+            disconnected.join(", ")
+        );
+        response(
+            StatusCode::OK,
+            format!(
+                "Disconnected peer {} on {}",
+                peer_id,
+                disconnected.join(", ")
+            ),
+        )
+    }
+}
+
+fn response(status: StatusCode, message: String) -> Response<Body> {
+    let mut resp = Response::new(Body::from(message));
+    *resp.status_mut() = status;
+    resp
+}
+
+/// Checks the bearer token in the `Authorization` header, in constant time for tokens of the
+/// expected length.
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    const SCHEME: &str = "Bearer ";
+    let presented = match authorization {
+        Some(value) if value.starts_with(SCHEME) && value.len() == SCHEME.len() + token.len() => {
+            &value[SCHEME.len()..]
+        }
+        _ => return false,
+    };
+    presented
+        .bytes()
+        .zip(token.bytes())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authorized() {
+        let token = "c2VjcmV0";
+        assert!(is_authorized(Some("Bearer c2VjcmV0"), token));
+        assert!(!is_authorized(None, token));
+        assert!(!is_authorized(Some("c2VjcmV0"), token));
+        assert!(!is_authorized(Some("Bearer c2VjcmV1"), token));
+        assert!(!is_authorized(Some("Bearer c2VjcmV0c2VjcmV0"), token));
+        assert!(!is_authorized(Some("Basic c2VjcmV0"), token));
+        assert!(!is_authorized(Some("xBearer c2VjcmV0"), token));
+    }
+}
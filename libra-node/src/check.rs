@@ -0,0 +1,375 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-test run by `libra-node --check`, which validates the environment of a node without
+//! starting it: its storage, the genesis and the waypoint it is bootstrapped from, the keys in
+//! secure storage and the ports it listens on. None of the checks modifies the environment, so
+//! they can run next to a running node, whose ports are then reported as taken.
+
+use anyhow::{bail, ensure, format_err, Result};
+use executor::db_bootstrapper::calculate_genesis;
+use libra_config::{
+    config::{NodeConfig, SecureBackend, WaypointConfig},
+    utils::get_genesis_txn,
+};
+use libra_crypto::hash::CryptoHash;
+use libra_global_constants::{
+    CONSENSUS_KEY, EPOCH, EXECUTION_KEY, LAST_VOTED_ROUND, OPERATOR_ACCOUNT, PREFERRED_ROUND,
+    WAYPOINT,
+};
+use libra_network_address::parse_ip_tcp;
+use libra_secure_storage::{CryptoStorage, Error as StorageError, KVStorage, Storage};
+use libra_temppath::TempPath;
+use libra_types::waypoint::Waypoint;
+use libra_vm::LibraVM;
+use libradb::LibraDB;
+use serde_json::{json, Value};
+use std::{
+    convert::TryFrom,
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, ToSocketAddrs},
+    str::FromStr,
+};
+use storage_interface::DbReaderWriter;
+
+/// The outcome of a single check
+pub struct CheckOutcome {
+    pub name: &'static str,
+    /// What was verified if the check passed, why it failed otherwise
+    pub result: Result<String, String>,
+}
+
+/// The outcomes of all the checks of a node
+#[derive(Default)]
+pub struct CheckReport {
+    pub outcomes: Vec<CheckOutcome>,
+}
+
+impl CheckReport {
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.result.is_ok())
+    }
+
+    /// The report as a JSON object, for operators and deployment scripts
+    pub fn to_json(&self) -> Value {
+        let checks: Vec<_> = self
+            .outcomes
+            .iter()
+            .map(|outcome| match &outcome.result {
+                Ok(details) => json!({"name": outcome.name, "passed": true, "details": details}),
+                Err(error) => json!({"name": outcome.name, "passed": false, "error": error}),
+            })
+            .collect();
+        json!({"passed": self.passed(), "checks": checks})
+    }
+
+    fn add(&mut self, name: &'static str, result: Result<String>) {
+        self.outcomes.push(CheckOutcome {
+            name,
+            result: result.map_err(|e| format!("{:#}", e)),
+        })
+    }
+}
+
+/// Runs all the checks of the node started from `config`
+pub fn check_node(config: &NodeConfig) -> CheckReport {
+    let mut report = CheckReport::default();
+    match open_db(config) {
+        Ok(db) => {
+            let db = db.as_ref();
+            report.add("storage", check_storage(db));
+            let mut genesis_waypoint = None;
+            let genesis = check_genesis(config, db).map(|(details, waypoint)| {
+                genesis_waypoint = waypoint;
+                details
+            });
+            report.add("genesis", genesis);
+            report.add("waypoint", check_waypoint(config, db, genesis_waypoint));
+        }
+        Err(e) => {
+            report.add("storage", Err(e));
+            for name in &["genesis", "waypoint"] {
+                report.add(
+                    *name,
+                    Err(format_err!("Skipped, the DB could not be opened")),
+                );
+            }
+        }
+    }
+    report.add("secure_storage", check_secure_storage(config));
+    report.add("ports", check_ports(config));
+    report
+}
+
+// Opens the DB of the node read-only. Returns None if the node has no DB yet, in which case it
+// creates one on startup.
+fn open_db(config: &NodeConfig) -> Result<Option<DbReaderWriter>> {
+    let dir = config.storage.dir();
+    if !dir.join("libradb").exists() {
+        return Ok(None);
+    }
+    let db = LibraDB::open(
+        &dir,
+        true, /* readonly */
+        config.storage.prune_window,
+        config.storage.archive_window,
+        config.storage.waypoint_interval,
+    )?;
+    Ok(Some(DbReaderWriter::new(db)))
+}
+
+// Returns the latest version of the DB, if it has been bootstrapped
+fn latest_version(db: Option<&DbReaderWriter>) -> Result<Option<u64>> {
+    Ok(match db {
+        Some(db) => db
+            .reader
+            .get_startup_info()?
+            .map(|info| info.latest_ledger_info.ledger_info().version()),
+        None => None,
+    })
+}
+
+fn check_storage(db: Option<&DbReaderWriter>) -> Result<String> {
+    let db = match db {
+        Some(db) => db,
+        None => return Ok("No DB yet, it is created from the genesis on startup".to_string()),
+    };
+    Ok(match db.reader.get_startup_info()? {
+        Some(info) => {
+            let ledger_info = info.latest_ledger_info.ledger_info();
+            format!(
+                "DB at version {} of epoch {}",
+                ledger_info.version(),
+                ledger_info.epoch()
+            )
+        }
+        None => "Empty DB, it is bootstrapped from the genesis on startup".to_string(),
+    })
+}
+
+// Checks the genesis transaction against the first transaction of the DB or, if the DB has not
+// been bootstrapped yet, executes it on the side. In the latter case the waypoint of the genesis
+// is returned.
+fn check_genesis(
+    config: &NodeConfig,
+    db: Option<&DbReaderWriter>,
+) -> Result<(String, Option<Waypoint>)> {
+    let genesis = get_genesis_txn(config)
+        .ok_or_else(|| format_err!("No genesis transaction in the config"))?;
+    let hash = genesis.hash();
+
+    if let Some(version) = latest_version(db)? {
+        let db = db.expect("Bootstrapped DB must be open");
+        let first_txn = db
+            .reader
+            .get_transactions(0, 1, version, false /* fetch_events */)?
+            .transactions
+            .pop()
+            .ok_or_else(|| format_err!("No transaction at version 0 of the DB"))?;
+        ensure!(
+            first_txn.hash() == hash,
+            "Genesis transaction {} doesn't match the transaction {} at version 0 of the DB",
+            hash,
+            first_txn.hash()
+        );
+        return Ok((format!("Genesis transaction {} matches the DB", hash), None));
+    }
+
+    // Executing the genesis doesn't commit it, an empty DB is left as it is. A node without a DB
+    // yet gets a scratch one, which is removed afterwards.
+    let scratch_dir = TempPath::new();
+    let db = match db {
+        Some(db) => db.clone(),
+        None => {
+            scratch_dir.create_as_dir()?;
+            DbReaderWriter::new(LibraDB::open(
+                scratch_dir.path(),
+                false, /* readonly */
+                None,  /* pruner */
+                None,  /* archiver */
+                None,  /* waypoint_interval */
+            )?)
+        }
+    };
+    let tree_state = db.reader.get_latest_tree_state()?;
+    let waypoint = calculate_genesis::<LibraVM>(&db, tree_state, genesis)?.waypoint();
+    Ok((
+        format!("Genesis transaction {} executes to {}", hash, waypoint),
+        Some(waypoint),
+    ))
+}
+
+// Reads the waypoint the way the node does, without panicking if it is missing
+fn read_waypoint(config: &WaypointConfig) -> Result<Waypoint> {
+    match config {
+        WaypointConfig::FromConfig(waypoint) => Ok(*waypoint),
+        WaypointConfig::FromStorage(backend) => {
+            let storage = Storage::try_from(backend)?;
+            let waypoint = storage.get(WAYPOINT)?.value.string()?;
+            Waypoint::from_str(&waypoint)
+        }
+        WaypointConfig::None => bail!("No waypoint in the config"),
+    }
+}
+
+fn check_waypoint(
+    config: &NodeConfig,
+    db: Option<&DbReaderWriter>,
+    genesis_waypoint: Option<Waypoint>,
+) -> Result<String> {
+    let waypoint = read_waypoint(&config.base.waypoint)?;
+    match latest_version(db)? {
+        Some(version) if version >= waypoint.version() => {
+            let db = db.expect("Bootstrapped DB must be open");
+            let ledger_info = db.reader.get_epoch_ending_ledger_info(waypoint.version())?;
+            waypoint.verify(ledger_info.ledger_info())?;
+            Ok(format!("{} matches the DB", waypoint))
+        }
+        Some(version) => Ok(format!(
+            "{} is ahead of the DB at version {}, state sync catches up on startup",
+            waypoint, version
+        )),
+        None => match genesis_waypoint {
+            Some(genesis_waypoint) if waypoint.version() == 0 => {
+                ensure!(
+                    waypoint == genesis_waypoint,
+                    "{} doesn't match the genesis, which executes to {}",
+                    waypoint,
+                    genesis_waypoint
+                );
+                Ok(format!("{} matches the genesis", waypoint))
+            }
+            _ => Ok(format!(
+                "{} is ahead of the genesis, state sync catches up on startup",
+                waypoint
+            )),
+        },
+    }
+}
+
+// Checks that safety rules can read the keys and the safety data it needs from secure storage
+fn check_secure_storage(config: &NodeConfig) -> Result<String> {
+    if !config.base.role.is_validator() {
+        return Ok("Not used by full nodes".to_string());
+    }
+    let backend = &config.consensus.safety_rules.backend;
+    if let SecureBackend::InMemoryStorage = backend {
+        return Ok("In-memory storage, initialized on startup".to_string());
+    }
+    let storage = Storage::try_from(backend)?;
+    storage.available()?;
+    if config.test.is_some() {
+        return Ok("Available, the keys are imported from the test config on startup".to_string());
+    }
+
+    let mut errors = vec![];
+    for key in &[CONSENSUS_KEY, EXECUTION_KEY] {
+        if let Err(e) = storage.get_public_key(key) {
+            errors.push(storage_error(key, e));
+        }
+    }
+    for key in &[
+        OPERATOR_ACCOUNT,
+        EPOCH,
+        LAST_VOTED_ROUND,
+        PREFERRED_ROUND,
+        WAYPOINT,
+    ] {
+        if let Err(e) = storage.get(key) {
+            errors.push(storage_error(key, e));
+        }
+    }
+    ensure!(errors.is_empty(), "{}", errors.join(", "));
+    Ok("Available, all the keys of safety rules are readable".to_string())
+}
+
+fn storage_error(key: &str, error: StorageError) -> String {
+    match error {
+        StorageError::PermissionDenied => format!("{}: denied by the storage policy", key),
+        e => format!("{}: {}", key, e),
+    }
+}
+
+// Binds all the addresses the node listens on. The listeners are held until the end of the check,
+// so that an address used by several services fails too.
+fn check_ports(config: &NodeConfig) -> Result<String> {
+    let mut addresses = vec![];
+    for network in config
+        .full_node_networks
+        .iter()
+        .chain(&config.validator_network)
+    {
+        // Other transports, e.g. in-memory ones in tests, have no port to check
+        if let Some(((ip, port), _)) = parse_ip_tcp(network.listen_address.as_slice()) {
+            addresses.push((
+                format!("{} network", network.network_id),
+                SocketAddr::new(ip, port),
+            ));
+        }
+    }
+    addresses.push(("JSON-RPC".to_string(), config.rpc.address));
+    addresses.push(("storage service".to_string(), config.storage.address));
+    addresses.push((
+        "backup service".to_string(),
+        SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            config.storage.backup_service_port,
+        ),
+    ));
+
+    let debug_interface = &config.debug_interface;
+    let mut debug_ports = vec![
+        (
+            "debug interface",
+            debug_interface.admission_control_node_debug_port,
+        ),
+        ("metrics server", debug_interface.metrics_server_port),
+        (
+            "public metrics server",
+            debug_interface.public_metrics_server_port,
+        ),
+    ];
+    if let Some(port) = debug_interface.admin_port {
+        debug_ports.push(("admin API", port));
+    }
+    for (name, port) in debug_ports {
+        let address = (debug_interface.address.as_str(), port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| format_err!("Unable to resolve {}", debug_interface.address))?;
+        addresses.push((name.to_string(), address));
+    }
+
+    let mut listeners = vec![];
+    let mut errors = vec![];
+    for (name, address) in &addresses {
+        match TcpListener::bind(address) {
+            Ok(listener) => listeners.push(listener),
+            Err(e) => errors.push(format!("{} on {}: {}", name, address, e)),
+        }
+    }
+    ensure!(errors.is_empty(), "{}", errors.join(", "));
+    Ok(format!("All {} addresses are available", addresses.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_ports() {
+        let mut config = NodeConfig::default();
+        config.randomize_ports();
+        check_ports(&config).unwrap();
+
+        // A port taken by another process
+        let listener = TcpListener::bind(config.rpc.address).unwrap();
+        let error = check_ports(&config).unwrap_err();
+        assert!(error.to_string().starts_with("JSON-RPC on"));
+        drop(listener);
+
+        // Two services on the same address
+        config.storage.address = config.rpc.address;
+        let error = check_ports(&config).unwrap_err();
+        assert!(error.to_string().starts_with("storage service on"));
+    }
+}
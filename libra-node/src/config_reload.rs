@@ -0,0 +1,277 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Applies changes in the config file of a running node. Only the log level, mempool capacities,
+//! and JSON-RPC limits can be changed without a restart. A reload that changes any other setting
+//! is rejected as a whole, so that the node never runs with a partially applied config.
+
+use libra_config::config::{Error as ConfigError, NodeConfig, PersistableConfig};
+use libra_json_rpc::RpcLimits;
+use libra_logger::prelude::*;
+use libra_mempool::MempoolHandle;
+use serde_yaml::Value;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// Settings that are applied to the running node
+const RELOADABLE: &[&str] = &[
+    "logger.level",
+    "mempool.capacity",
+    "mempool.capacity_per_user",
+    "rpc.batch_size_limit",
+    "rpc.page_size_limit",
+    "rpc.submit_and_wait_timeout_ms",
+];
+
+/// Sections holding keys or network identities, which can never be changed by a reload
+const IMMUTABLE: &[&str] = &[
+    "consensus.safety_rules",
+    "execution.backend",
+    "full_node_networks[].identity",
+    "test",
+    "validator_network.identity",
+];
+
+#[derive(Debug, Error)]
+pub enum ReloadError {
+    #[error("Unable to load config: {0}")]
+    Load(#[from] ConfigError),
+    #[error("Unable to serialize config: {0}")]
+    Serialization(#[from] serde_yaml::Error),
+    #[error("{0}")]
+    Rejected(ReloadReport),
+}
+
+/// The settings changed by a reload
+#[derive(Debug, Default, PartialEq)]
+pub struct ReloadReport {
+    /// Settings that can be applied without a restart
+    pub applied: Vec<String>,
+    /// Settings holding keys or network identities
+    pub immutable: Vec<String>,
+    /// All other settings, which only take effect on restart
+    pub requires_restart: Vec<String>,
+}
+
+impl ReloadReport {
+    fn new(changes: Vec<String>) -> Self {
+        let mut report = Self::default();
+        for change in changes {
+            if RELOADABLE.contains(&change.as_str()) {
+                report.applied.push(change);
+            } else if is_immutable(&change) {
+                report.immutable.push(change);
+            } else {
+                report.requires_restart.push(change);
+            }
+        }
+        report
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        !self.immutable.is_empty() || !self.requires_restart.is_empty()
+    }
+}
+
+impl fmt::Display for ReloadReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_rejected() {
+            write!(f, "Rejected config reload, no changes were applied.")?;
+            if !self.immutable.is_empty() {
+                write!(
+                    f,
+                    " Keys and network identities cannot be changed: {}.",
+                    self.immutable.join(", ")
+                )?;
+            }
+            if !self.requires_restart.is_empty() {
+                write!(
+                    f,
+                    " Changes require a restart: {}.",
+                    self.requires_restart.join(", ")
+                )?;
+            }
+            Ok(())
+        } else if self.applied.is_empty() {
+            write!(f, "Config reloaded, nothing changed.")
+        } else {
+            write!(f, "Config reloaded, applied: {}.", self.applied.join(", "))
+        }
+    }
+}
+
+/// Re-reads the config file of a running node and applies changes to reloadable settings.
+pub struct ConfigReloader {
+    path: PathBuf,
+    current: Mutex<Value>,
+    mempool: MempoolHandle,
+    rpc_limits: Arc<RpcLimits>,
+}
+
+impl ConfigReloader {
+    pub(crate) fn new(
+        path: PathBuf,
+        mempool: MempoolHandle,
+        rpc_limits: Arc<RpcLimits>,
+    ) -> Result<Self, ReloadError> {
+        let (_config, current) = read(&path)?;
+        Ok(Self {
+            path,
+            current: Mutex::new(current),
+            mempool,
+            rpc_limits,
+        })
+    }
+
+    pub fn reload(&self) -> Result<ReloadReport, ReloadError> {
+        let (config, new) = read(&self.path)?;
+        let mut current = self.current.lock().expect("Unable to lock config");
+
+        let mut changes = vec![];
+        diff("", &current, &new, &mut changes);
+        let report = ReloadReport::new(changes);
+        if report.is_rejected() {
+            return Err(ReloadError::Rejected(report));
+        }
+
+        for change in &report.applied {
+            match change.as_str() {
+                "logger.level" => libra_logger::set_level(config.logger.level),
+                "mempool.capacity" | "mempool.capacity_per_user" => {
+                    self.mempool.update_capacity(&config.mempool)
+                }
+                "rpc.batch_size_limit"
+                | "rpc.page_size_limit"
+                | "rpc.submit_and_wait_timeout_ms" => self.rpc_limits.update(&config.rpc),
+                change => unreachable!("{} is not reloadable", change),
+            }
+        }
+        *current = new;
+        Ok(report)
+    }
+
+    /// Reloads the config and logs the outcome, which is also returned as a message.
+    pub fn reload_and_log(&self) -> Result<String, String> {
+        match self.reload() {
+            Ok(report) => {
+                info!("{}", report);
+                Ok(report.to_string())
+            }
+            Err(e) => {
+                error!("{}", e);
+                Err(e.to_string())
+            }
+        }
+    }
+}
+
+/// Reads the config without any of the processing performed by `NodeConfig::load`, e.g.,
+/// generating missing network identities, so that reading an unchanged file yields the same value.
+fn read(path: &Path) -> Result<(NodeConfig, Value), ReloadError> {
+    let config = NodeConfig::load_config(path)?;
+    let value = serde_yaml::to_value(&config)?;
+    Ok((config, value))
+}
+
+/// Collects the paths of all settings that differ between the two values.
+fn diff(path: &str, current: &Value, new: &Value, changes: &mut Vec<String>) {
+    match (current, new) {
+        (Value::Mapping(current), Value::Mapping(new)) => {
+            let mut keys: Vec<_> = current.iter().map(|(key, _)| key).collect();
+            keys.extend(
+                new.iter()
+                    .map(|(key, _)| key)
+                    .filter(|key| !current.contains_key(key)),
+            );
+            for key in keys {
+                let key_path = match key {
+                    Value::String(key) if path.is_empty() => key.clone(),
+                    Value::String(key) => format!("{}.{}", path, key),
+                    key => format!("{}.{:?}", path, key),
+                };
+                diff(
+                    &key_path,
+                    current.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (Value::Sequence(current), Value::Sequence(new)) if current.len() == new.len() => {
+            for (index, (current, new)) in current.iter().zip(new).enumerate() {
+                diff(&format!("{}[{}]", path, index), current, new, changes);
+            }
+        }
+        (current, new) => {
+            if current != new {
+                changes.push(path.to_string());
+            }
+        }
+    }
+}
+
+fn is_immutable(path: &str) -> bool {
+    // Sequence indices are irrelevant, e.g., full_node_networks[1].identity
+    let mut section = String::new();
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => in_index = true,
+            ']' => in_index = false,
+            _ if in_index => continue,
+            _ => (),
+        }
+        section.push(c);
+    }
+
+    IMMUTABLE.iter().any(|immutable| {
+        section == *immutable
+            || section.starts_with(&format!("{}.", immutable))
+            || section.starts_with(&format!("{}[", immutable))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(current: &str, new: &str) -> ReloadReport {
+        let current: Value = serde_yaml::from_str(current).unwrap();
+        let new: Value = serde_yaml::from_str(new).unwrap();
+        let mut changes = vec![];
+        diff("", &current, &new, &mut changes);
+        ReloadReport::new(changes)
+    }
+
+    #[test]
+    fn test_reloadable_changes() {
+        let report = changes(
+            "{logger: {level: INFO}, rpc: {batch_size_limit: 20}}",
+            "{logger: {level: DEBUG}, rpc: {batch_size_limit: 20, page_size_limit: 10}}",
+        );
+        assert!(!report.is_rejected());
+        assert_eq!(report.applied, vec!["logger.level", "rpc.page_size_limit"]);
+
+        let report = changes("{logger: {level: INFO}}", "{logger: {level: INFO}}");
+        assert_eq!(report, ReloadReport::default());
+    }
+
+    #[test]
+    fn test_rejected_changes() {
+        let report = changes(
+            "{mempool: {capacity: 1}, full_node_networks: [{identity: {peer_id: a}}, {identity: {peer_id: b}}], storage: {dir: a}}",
+            "{mempool: {capacity: 2}, full_node_networks: [{identity: {peer_id: a}}, {identity: {peer_id: c}}], storage: {dir: b}}",
+        );
+        assert!(report.is_rejected());
+        assert_eq!(report.applied, vec!["mempool.capacity"]);
+        assert_eq!(
+            report.immutable,
+            vec!["full_node_networks[1].identity.peer_id"]
+        );
+        assert_eq!(report.requires_restart, vec!["storage.dir"]);
+    }
+}
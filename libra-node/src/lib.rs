@@ -3,4 +3,7 @@
 
 #![forbid(unsafe_code)]
 
+pub mod admin;
+pub mod check;
+pub mod config_reload;
 pub mod main_node;
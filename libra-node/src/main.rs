@@ -3,16 +3,16 @@
 
 #![forbid(unsafe_code)]
 
-use libra_config::config::NodeConfig;
+use libra_config::config::{MetricsPushConfig, MetricsPushEndpoint, NodeConfig};
+use libra_logger::prelude::*;
+use libra_metrics::{metric_push, metric_server};
 use libra_types::PeerId;
-use std::{
-    path::PathBuf,
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc,
-    },
-};
+use std::{path::PathBuf, process, sync::Arc, time::Duration};
 use structopt::StructOpt;
+use tokio::{
+    runtime::Builder,
+    signal::unix::{signal, SignalKind},
+};
 
 #[derive(Debug, StructOpt)]
 #[structopt(about = "Libra Node")]
@@ -23,6 +23,10 @@ struct Args {
     #[structopt(short = "d", long)]
     /// Disable logging
     no_logging: bool,
+    #[structopt(long)]
+    /// Check the storage, keys, ports and genesis of the node, print a JSON report and exit
+    /// without starting the node
+    check: bool,
 }
 
 #[global_allocator]
@@ -31,7 +35,19 @@ static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 fn main() {
     let args = Args::from_args();
 
-    let mut config = NodeConfig::load(args.config).expect("Failed to load node config");
+    let mut config = NodeConfig::load(&args.config).expect("Failed to load node config");
+    if let Err(e) = config.validate() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+    if args.check {
+        let report = libra_node::check::check_node(&config);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report.to_json()).expect("JSON values serialize")
+        );
+        process::exit(if report.passed() { 0 } else { 1 });
+    }
     println!("Using node config {:?}", &config);
     crash_handler::setup_panic_handler();
 
@@ -56,13 +72,49 @@ fn main() {
         }
     }
 
-    let _node_handle = libra_node::main_node::setup_environment(&mut config);
+    if let Some(push_config) = &config.metrics.push {
+        setup_metrics_push(push_config, &config);
+    }
 
-    let term = Arc::new(AtomicBool::new(false));
+    let node_handle = libra_node::main_node::setup_environment(&mut config);
 
-    while !term.load(Ordering::Acquire) {
-        std::thread::park();
+    // The config can be reloaded either via SIGHUP or `POST /reload` on the metrics server
+    let reloader = Arc::new(
+        node_handle
+            .config_reloader(args.config)
+            .expect("Failed to read node config"),
+    );
+    let handler_reloader = Arc::clone(&reloader);
+    if metric_server::set_reload_handler(Box::new(move || handler_reloader.reload_and_log()))
+        .is_err()
+    {
+        warn!("Config reload handler already set");
     }
+
+    let mut runtime = Builder::new()
+        .thread_name("config-reload")
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .expect("Failed to create config reload runtime");
+    runtime.block_on(async move {
+        let mut hangup = signal(SignalKind::hangup()).expect("Failed to listen for SIGHUP");
+        let mut terminate = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+        loop {
+            tokio::select! {
+                _ = hangup.recv() => {
+                    // The outcome is logged by the reloader
+                    let _ = reloader.reload_and_log();
+                }
+                _ = terminate.recv() => break,
+            }
+        }
+    });
+
+    let deadline = Duration::from_millis(config.base.shutdown_deadline_ms);
+    info!("Received SIGTERM, shutting down within {:?}", deadline);
+    node_handle.shutdown(deadline);
+    info!("Shutdown complete");
 }
 
 fn setup_metrics(peer_id: PeerId, config: &NodeConfig) {
@@ -72,3 +124,32 @@ fn setup_metrics(peer_id: PeerId, config: &NodeConfig) {
         config.metrics.collection_interval_ms,
     );
 }
+
+fn setup_metrics_push(push_config: &MetricsPushConfig, config: &NodeConfig) {
+    let target = match &push_config.endpoint {
+        MetricsPushEndpoint::PushGateway { url, job } => metric_push::PushTarget::PushGateway {
+            url: url.clone(),
+            job: job.clone(),
+        },
+        MetricsPushEndpoint::Otlp { url } => metric_push::PushTarget::Otlp { url: url.clone() },
+    };
+
+    // Identify the node among all the nodes pushing to the same collector
+    let mut labels = push_config.labels.clone();
+    labels.insert("chain_id".to_string(), config.base.chain_id.to_string());
+    labels.insert("role".to_string(), config.base.role.to_string());
+    let peer_id = config
+        .validator_network
+        .as_ref()
+        .or_else(|| config.full_node_networks.first())
+        .map(|network| network.peer_id());
+    if let Some(peer_id) = peer_id {
+        labels.insert("peer_id".to_string(), peer_id.to_string());
+    }
+
+    metric_push::push_all_metrics_periodically(
+        target,
+        Duration::from_millis(push_config.interval_ms),
+        labels,
+    );
+}
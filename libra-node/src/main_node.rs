@@ -1,6 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    admin::{AdminHandles, AdminService},
+    config_reload::{ConfigReloader, ReloadError},
+};
 use backup_service::start_backup_service;
 use consensus::{consensus_provider::start_consensus, gen_consensus_reconfig_subscription};
 use debug_interface::node_debug_service::NodeDebugService;
@@ -11,10 +15,11 @@ use libra_config::{
     config::{NetworkConfig, NodeConfig, RoleType},
     utils::get_genesis_txn,
 };
-use libra_json_rpc::bootstrap_from_config as bootstrap_rpc;
+use libra_json_rpc::{bootstrap_from_config as bootstrap_rpc, RpcLimits};
 use libra_logger::prelude::*;
-use libra_mempool::gen_mempool_reconfig_subscription;
+use libra_mempool::{gen_mempool_reconfig_subscription, MempoolHandle};
 use libra_metrics::metric_server;
+use libra_time_service::TimeService;
 use libra_vm::LibraVM;
 use libradb::LibraDB;
 use network_builder::builder::NetworkBuilder;
@@ -22,31 +27,88 @@ use network_simple_onchain_discovery::{
     gen_simple_discovery_reconfig_subscription, ConfigurationChangeListener,
 };
 use state_synchronizer::StateSynchronizer;
-use std::{boxed::Box, net::ToSocketAddrs, sync::Arc, thread, time::Instant};
+use std::{
+    boxed::Box,
+    net::ToSocketAddrs,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 use storage_interface::DbReaderWriter;
 use storage_service::start_storage_service_with_db;
 use tokio::runtime::Runtime;
 
-use debug_interface::libra_trace;
+use debug_interface::{libra_trace, trace_store};
 
 const AC_SMP_CHANNEL_BUFFER_SIZE: usize = 1_024;
 const INTRA_NODE_CHANNEL_BUFFER_SIZE: usize = 1;
 
 pub struct LibraHandle {
-    _rpc: Runtime,
-    _mempool: Runtime,
-    _state_synchronizer: StateSynchronizer,
-    _network_runtimes: Vec<Runtime>,
-    _consensus_runtime: Option<Runtime>,
-    _debug: NodeDebugService,
-    _backup: Runtime,
+    rpc: Runtime,
+    mempool: Runtime,
+    state_synchronizer: StateSynchronizer,
+    network_runtimes: Vec<Runtime>,
+    consensus_runtime: Option<Runtime>,
+    debug: NodeDebugService,
+    admin: Option<AdminService>,
+    backup: Runtime,
+    libra_db: Arc<LibraDB>,
+    mempool_handle: MempoolHandle,
+    rpc_limits: Arc<RpcLimits>,
+}
+
+impl LibraHandle {
+    /// Creates a ConfigReloader that applies changes in the config file at `path`, from which
+    /// this node was started, to the running node.
+    pub fn config_reloader(&self, path: PathBuf) -> Result<ConfigReloader, ReloadError> {
+        ConfigReloader::new(
+            path,
+            self.mempool_handle.clone(),
+            Arc::clone(&self.rpc_limits),
+        )
+    }
+
+    /// Stops the node within `deadline`, in an order that lets the components finish their work
+    /// in flight: the RPC stops taking submissions first, then consensus and state sync complete
+    /// their commits, so that mempool processes the commit notifications before it stops, then the
+    /// networks and the remaining services stop, and the DB is closed last.
+    ///
+    /// Each step waits at most for what remains of the deadline; the tasks still running when it
+    /// is over are dropped.
+    pub fn shutdown(self, deadline: Duration) {
+        let deadline = Instant::now() + deadline;
+        let remaining = || deadline.saturating_duration_since(Instant::now());
+
+        info!("Stopping JSON-RPC");
+        self.rpc.shutdown_timeout(remaining());
+        if let Some(consensus_runtime) = self.consensus_runtime {
+            info!("Stopping consensus");
+            consensus_runtime.shutdown_timeout(remaining());
+        }
+        info!("Stopping state sync");
+        self.state_synchronizer.shutdown(remaining());
+        info!("Stopping mempool");
+        self.mempool.shutdown_timeout(remaining());
+        info!("Stopping networks");
+        for runtime in self.network_runtimes {
+            runtime.shutdown_timeout(remaining());
+        }
+        info!("Stopping backup service");
+        self.backup.shutdown_timeout(remaining());
+        drop(self.debug);
+        drop(self.admin);
+
+        info!("Closing the DB");
+        drop(self.libra_db);
+    }
 }
 
 fn setup_chunk_executor(db: DbReaderWriter) -> Box<dyn ChunkExecutor> {
     Box::new(Executor::<LibraVM>::new(db))
 }
 
-fn setup_debug_interface(config: &NodeConfig) -> NodeDebugService {
+fn setup_debug_interface(config: &NodeConfig, time_service: TimeService) -> NodeDebugService {
     let addr = format!(
         "{}:{}",
         config.debug_interface.address, config.debug_interface.admission_control_node_debug_port,
@@ -56,10 +118,16 @@ fn setup_debug_interface(config: &NodeConfig) -> NodeDebugService {
     .next()
     .unwrap();
 
-    libra_trace::set_libra_trace(&config.debug_interface.libra_trace.sampling)
+    let trace_config = &config.debug_interface.libra_trace;
+    libra_trace::set_libra_trace(&trace_config.sampling)
         .expect("Failed to set libra trace sampling rate.");
+    trace_store::configure(
+        trace_config.max_traces,
+        trace_config.max_captured_traces,
+        Duration::from_millis(trace_config.latency_slo_ms),
+    );
 
-    NodeDebugService::new(addr)
+    NodeDebugService::new(addr, time_service)
 }
 
 pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
@@ -70,12 +138,26 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
         .build_global()
         .expect("Building rayon global thread pool should work.");
 
+    // The clock shared by the components, which tests may move forward
+    let time_service = if node_config
+        .test
+        .as_ref()
+        .map_or(false, |test| test.adjustable_time)
+    {
+        warn!("The clock of this node can be moved forward through the debug interface");
+        TimeService::adjustable()
+    } else {
+        TimeService::real()
+    };
+
     let mut instant = Instant::now();
     let (libra_db, db_rw) = DbReaderWriter::wrap(
         LibraDB::open(
             &node_config.storage.dir(),
             false, /* readonly */
             node_config.storage.prune_window,
+            node_config.storage.archive_window,
+            node_config.storage.waypoint_interval,
         )
         .expect("DB should open."),
     );
@@ -101,6 +183,7 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
         instant.elapsed().as_millis()
     );
     let mut network_runtimes = vec![];
+    let mut connection_request_senders = vec![];
     let mut state_sync_network_handles = vec![];
     let mut mempool_network_handles = vec![];
     let mut consensus_network_handles = None;
@@ -142,6 +225,10 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
         // Create the endpoints to connect the Network to StateSynchronizer.
         let (state_sync_sender, state_sync_events) = network_builder
             .add_protocol_handler(state_synchronizer::network::network_endpoint_config());
+        network_builder
+            .advertise_application_versions(state_synchronizer::network::supported_versions());
+        let state_sync_sender =
+            state_sync_sender.with_peer_versions(network_builder.peer_application_versions());
         state_sync_network_handles.push((network_id.clone(), state_sync_sender, state_sync_events));
 
         // Create the endpoints t connect the Network to MemPool.
@@ -150,6 +237,8 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
                 // TODO:  Make this configuration option more clear.
                 node_config.mempool.max_broadcasts_per_peer,
             ));
+        network_builder
+            .advertise_application_versions(libra_mempool::network::supported_versions());
         mempool_network_handles.push((network_id, mempool_sender, mempool_events));
 
         match role {
@@ -178,6 +267,9 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
                     Some(network_builder.add_protocol_handler(
                         consensus::network_interface::network_endpoint_config(),
                     ));
+                network_builder.advertise_application_versions(
+                    consensus::network_interface::supported_versions(),
+                );
             }
             // Currently no FullNode network specific steps.
             RoleType::FullNode => (),
@@ -186,6 +278,10 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
         // Start the network and cache the runtime so it does not go out of scope.
         // TODO:  move all 'start' commands to a second phase at the end of setup_environment.  Target is to have one pass to wire the pieces together and a second pass to start processing in an appropriate order.
         let peer_id = network_builder.peer_id();
+        connection_request_senders.push((
+            network_builder.network_context().network_id().clone(),
+            network_builder.connection_request_sender(),
+        ));
         let _listen_addr = network_builder.build();
         network_runtimes.push(runtime);
         debug!("Network started for peer_id: {}", peer_id);
@@ -208,13 +304,20 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
     );
     let (mp_client_sender, mp_client_events) = channel(AC_SMP_CHANNEL_BUFFER_SIZE);
 
-    let rpc_runtime = bootstrap_rpc(&node_config, libra_db.clone(), mp_client_sender);
+    let rpc_limits = Arc::new(RpcLimits::new(&node_config.rpc));
+    let rpc_runtime = bootstrap_rpc(
+        &node_config,
+        libra_db.clone(),
+        mp_client_sender,
+        Arc::clone(&rpc_limits),
+    );
 
     let mut consensus_runtime = None;
+    let mut consensus_handle = None;
     let (consensus_to_mempool_sender, consensus_requests) = channel(INTRA_NODE_CHANNEL_BUFFER_SIZE);
 
     instant = Instant::now();
-    let mempool = libra_mempool::bootstrap(
+    let (mempool, mempool_handle) = libra_mempool::bootstrap(
         node_config,
         Arc::clone(&db_rw.reader),
         mempool_network_handles,
@@ -222,6 +325,7 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
         consensus_requests,
         state_sync_requests,
         mempool_reconfig_events,
+        time_service.clone(),
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
@@ -240,19 +344,36 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
 
         // Initialize and start consensus.
         instant = Instant::now();
-        consensus_runtime = Some(start_consensus(
+        let (runtime, handle) = start_consensus(
             node_config,
             consensus_network_sender,
             consensus_network_events,
             state_synchronizer.create_client(),
             consensus_to_mempool_sender,
-            libra_db,
+            Arc::clone(&libra_db),
             consensus_reconfig_events,
-        ));
+            time_service.clone(),
+        );
+        consensus_runtime = Some(runtime);
+        consensus_handle = Some(handle);
         debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     }
 
-    let debug_if = setup_debug_interface(&node_config);
+    let debug_if = setup_debug_interface(&node_config, time_service);
+    let admin = node_config.debug_interface.admin_port.map(|port| {
+        let token_file = node_config
+            .debug_interface
+            .admin_token_file
+            .as_ref()
+            .expect("The admin API requires a token file");
+        let handles = AdminHandles {
+            mempool: mempool_handle.clone(),
+            networks: connection_request_senders,
+            state_sync: state_synchronizer.create_client(),
+            consensus: consensus_handle,
+        };
+        AdminService::new(port, token_file, handles).expect("Failed to start the admin API")
+    });
 
     let metrics_port = node_config.debug_interface.metrics_server_port;
     let metric_host = node_config.debug_interface.address.clone();
@@ -264,12 +385,16 @@ pub fn setup_environment(node_config: &mut NodeConfig) -> LibraHandle {
     });
 
     LibraHandle {
-        _network_runtimes: network_runtimes,
-        _rpc: rpc_runtime,
-        _mempool: mempool,
-        _state_synchronizer: state_synchronizer,
-        _consensus_runtime: consensus_runtime,
-        _debug: debug_if,
-        _backup: backup_service,
+        network_runtimes,
+        rpc: rpc_runtime,
+        mempool,
+        state_synchronizer,
+        consensus_runtime,
+        debug: debug_if,
+        admin,
+        backup: backup_service,
+        libra_db,
+        mempool_handle,
+        rpc_limits,
     }
 }
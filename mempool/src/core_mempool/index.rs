@@ -278,6 +278,44 @@ impl ParkingLotIndex {
     }
 }
 
+/// FeePayerIndex groups transactions by the account paying for their gas: the fee payer for
+/// sponsored transactions, the sender otherwise.
+/// It's used to apply the per-user capacity to fee payers, so that an account can't fill Mempool
+/// by sponsoring transactions of many senders
+pub struct FeePayerIndex {
+    data: BTreeMap<AccountAddress, BTreeSet<TxnPointer>>,
+}
+
+impl FeePayerIndex {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
+        self.data
+            .entry(txn.get_fee_payer())
+            .or_insert_with(BTreeSet::new)
+            .insert(TxnPointer::from(txn));
+    }
+
+    pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
+        let fee_payer = txn.get_fee_payer();
+        if let Some(txns) = self.data.get_mut(&fee_payer) {
+            txns.remove(&TxnPointer::from(txn));
+            if txns.is_empty() {
+                self.data.remove(&fee_payer);
+            }
+        }
+    }
+
+    /// returns the number of transactions whose gas is paid by `fee_payer`
+    pub(crate) fn count(&self, fee_payer: &AccountAddress) -> usize {
+        self.data.get(fee_payer).map_or(0, BTreeSet::len)
+    }
+}
+
 /// Logical pointer to `MempoolTransaction`
 /// Includes Account's address and transaction sequence number
 pub type TxnPointer = (AccountAddress, u64);
@@ -10,21 +10,25 @@ use crate::{
         transaction_store::TransactionStore,
         ttl_cache::TtlCache,
     },
+    txn_latency::{self, TxnStage},
     OP_COUNTERS,
 };
 use debug_interface::prelude::*;
-use libra_config::config::NodeConfig;
+use libra_config::config::{MempoolConfig, NodeConfig};
 use libra_logger::prelude::*;
+use libra_time_service::TimeService;
 use libra_types::{
     account_address::AccountAddress,
     mempool_status::{MempoolStatus, MempoolStatusCode},
-    transaction::SignedTransaction,
+    transaction::{SignedTransaction, TransactionPayload},
+    vm_error::VMStatus,
 };
 use std::{
     cmp::max,
     collections::HashSet,
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime},
 };
+use vm_validator::admission_quotas::AdmissionQuotas;
 
 pub struct Mempool {
     // stores metadata of all transactions in mempool (of all states)
@@ -37,10 +41,18 @@ pub struct Mempool {
     // by consensus
     pub(crate) metrics_cache: TtlCache<(AccountAddress, u64), SystemTime>,
     pub system_transaction_timeout: Duration,
+    admission_quotas: AdmissionQuotas,
+    // The clock the system TTL and the client-specified expiration time of transactions are
+    // checked against
+    pub(crate) time_service: TimeService,
 }
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
+        Self::new_with_time_service(config, TimeService::real())
+    }
+
+    pub fn new_with_time_service(config: &NodeConfig, time_service: TimeService) -> Self {
         Mempool {
             transactions: TransactionStore::new(&config.mempool),
             sequence_number_cache: TtlCache::new(config.mempool.capacity, Duration::from_secs(100)),
@@ -48,9 +60,36 @@ impl Mempool {
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            admission_quotas: AdmissionQuotas::new(&config.mempool.admission_quotas),
+            time_service,
         }
     }
 
+    /// Applies the capacity limits in the config to subsequently added transactions
+    pub(crate) fn update_capacity(&mut self, config: &MempoolConfig) {
+        self.transactions.update_capacity(config);
+        self.admission_quotas
+            .update_config(&config.admission_quotas);
+    }
+
+    /// Checks `txn` against the admission quotas, before it is added
+    pub(crate) fn check_admission_quotas(&self, txn: &SignedTransaction) -> Result<(), VMStatus> {
+        let in_flight = self
+            .transactions
+            .count_other_transactions(&txn.sender(), txn.sequence_number());
+        self.admission_quotas.check(txn, in_flight)
+    }
+
+    /// Returns the number of transactions in Mempool and its capacity
+    pub(crate) fn size_and_capacity(&self) -> (usize, usize) {
+        self.transactions.size_and_capacity()
+    }
+
+    /// Removes all transactions, returning how many were removed
+    pub(crate) fn clear(&mut self) -> usize {
+        self.transactions.clear()
+    }
+
     /// This function will be called once the transaction has been stored
     pub(crate) fn remove_transaction(
         &mut self,
@@ -67,6 +106,11 @@ impl Mempool {
         );
         self.log_latency(*sender, sequence_number, "e2e.latency");
         self.metrics_cache.remove(&(*sender, sequence_number));
+        if is_rejected {
+            txn_latency::discard_txn(*sender, sequence_number);
+        } else {
+            txn_latency::record_txn_stage(*sender, sequence_number, TxnStage::Committed);
+        }
         OP_COUNTERS.inc(&format!("remove_transaction.{}", is_rejected));
 
         let current_seq_number = self
@@ -133,15 +177,15 @@ impl Mempool {
             ));
         }
 
-        let expiration_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("init timestamp failure")
-            + self.system_transaction_timeout;
+        let expiration_time = self.time_service.now() + self.system_transaction_timeout;
         if timeline_state != TimelineState::NonQualified {
             self.metrics_cache
                 .insert((txn.sender(), txn.sequence_number()), SystemTime::now());
         }
 
+        let (sender, txn_type) = (txn.sender(), txn_latency::txn_type(&txn));
+        let txn_sequence_number = txn.sequence_number();
+        let is_module_publish = matches!(txn.payload(), TransactionPayload::Module(_));
         let txn_info = MempoolTransaction::new(
             txn,
             expiration_time,
@@ -152,6 +196,17 @@ impl Mempool {
         );
 
         let status = self.transactions.insert(txn_info, sequence_number);
+        if status.code == MempoolStatusCode::Accepted {
+            txn_latency::record_typed_txn(
+                sender,
+                txn_sequence_number,
+                txn_type,
+                TxnStage::Admitted,
+            );
+            if is_module_publish {
+                self.admission_quotas.record_module_publish();
+            }
+        }
         OP_COUNTERS.inc(&format!("insert.{:?}", status));
         status
     }
@@ -234,7 +289,7 @@ impl Mempool {
     /// clears expired entries in metrics cache and sequence number cache
     pub(crate) fn gc(&mut self) {
         let now = SystemTime::now();
-        self.transactions.gc_by_system_ttl();
+        self.transactions.gc_by_system_ttl(self.time_service.now());
         self.metrics_cache.gc(now);
         self.sequence_number_cache.gc(now);
     }
@@ -244,6 +299,12 @@ impl Mempool {
         self.transactions.gc_by_expiration_time(block_time);
     }
 
+    /// Moves the windows of the admission quotas to the timestamp of the latest committed block
+    pub(crate) fn update_block_timestamp(&mut self, block_timestamp_usecs: u64) {
+        self.admission_quotas
+            .update_block_timestamp(block_timestamp_usecs);
+    }
+
     /// Read `count` transactions from timeline since `timeline_id`
     /// Returns block of transactions and new last_timeline_id
     pub(crate) fn read_timeline(
@@ -39,6 +39,9 @@ impl MempoolTransaction {
     pub(crate) fn get_sender(&self) -> AccountAddress {
         self.txn.sender()
     }
+    pub(crate) fn get_fee_payer(&self) -> AccountAddress {
+        self.txn.fee_payer()
+    }
     pub(crate) fn get_gas_price(&self) -> u64 {
         self.txn.gas_unit_price()
     }
@@ -4,8 +4,8 @@
 use crate::{
     core_mempool::{
         index::{
-            AccountTransactions, ParkingLotIndex, PriorityIndex, PriorityQueueIter, TTLIndex,
-            TimelineIndex,
+            AccountTransactions, FeePayerIndex, ParkingLotIndex, PriorityIndex, PriorityQueueIter,
+            TTLIndex, TimelineIndex,
         },
         transaction::{MempoolTransaction, TimelineState},
     },
@@ -19,11 +19,7 @@ use libra_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
     transaction::SignedTransaction,
 };
-use std::{
-    collections::HashMap,
-    ops::Bound,
-    time::{Duration, SystemTime, UNIX_EPOCH},
-};
+use std::{collections::HashMap, ops::Bound, time::Duration};
 
 /// TransactionStore is in-memory storage for all transactions in mempool
 pub struct TransactionStore {
@@ -41,6 +37,8 @@ pub struct TransactionStore {
     timeline_index: TimelineIndex,
     // keeps track of "non-ready" txns (transactions that can't be included in next block)
     parking_lot_index: ParkingLotIndex,
+    // groups txns by the account paying for their gas, to enforce the capacity per fee payer
+    fee_payer_index: FeePayerIndex,
 
     // configuration
     capacity: usize,
@@ -61,6 +59,7 @@ impl TransactionStore {
             priority_index: PriorityIndex::new(),
             timeline_index: TimelineIndex::new(),
             parking_lot_index: ParkingLotIndex::new(),
+            fee_payer_index: FeePayerIndex::new(),
 
             // configuration
             capacity: config.capacity,
@@ -68,6 +67,18 @@ impl TransactionStore {
         }
     }
 
+    /// updates the capacity limits, which apply to subsequent insertions
+    /// transactions already in Mempool are kept even if they exceed the new limits
+    pub(crate) fn update_capacity(&mut self, config: &MempoolConfig) {
+        self.capacity = config.capacity;
+        self.capacity_per_user = config.capacity_per_user;
+    }
+
+    /// returns the number of transactions in Mempool and its capacity
+    pub(crate) fn size_and_capacity(&self) -> (usize, usize) {
+        (self.system_ttl_index.size(), self.capacity)
+    }
+
     /// fetch transaction by account address + sequence_number
     pub(crate) fn get(
         &self,
@@ -84,6 +95,18 @@ impl TransactionStore {
         None
     }
 
+    /// returns the number of transactions of `address` in Mempool, other than the one with
+    /// `sequence_number`, which a new transaction would replace
+    pub(crate) fn count_other_transactions(
+        &self,
+        address: &AccountAddress,
+        sequence_number: u64,
+    ) -> usize {
+        self.transactions.get(address).map_or(0, |txns| {
+            txns.len() - txns.contains_key(&sequence_number) as usize
+        })
+    }
+
     /// insert transaction into TransactionStore
     /// performs validation checks and updates indexes
     pub(crate) fn insert(
@@ -126,10 +149,22 @@ impl TransactionStore {
                     ),
                 );
             }
+            // the capacity per user also applies to the transactions an account pays for
+            let fee_payer = txn.get_fee_payer();
+            let fee_payer_txns = self.fee_payer_index.count(&fee_payer);
+            if fee_payer_txns >= self.capacity_per_user {
+                return MempoolStatus::new(MempoolStatusCode::TooManyTransactions).with_message(
+                    format!(
+                        "txns paid by {}: {} capacity per user: {}",
+                        fee_payer, fee_payer_txns, self.capacity_per_user,
+                    ),
+                );
+            }
 
             // insert into storage and other indexes
             self.system_ttl_index.insert(&txn);
             self.expiration_time_index.insert(&txn);
+            self.fee_payer_index.insert(&txn);
             txns.insert(sequence_number, txn);
             self.track_indices();
         }
@@ -288,6 +323,20 @@ impl TransactionStore {
         }
     }
 
+    /// removes all transactions, returning how many were removed
+    /// the timeline ids keep increasing, so that peers keep reading the timeline from where they were
+    pub(crate) fn clear(&mut self) -> usize {
+        let transactions: Vec<_> = self
+            .transactions
+            .drain()
+            .flat_map(|(_, txns)| txns.into_iter().map(|(_, txn)| txn))
+            .collect();
+        for txn in &transactions {
+            self.index_remove(txn);
+        }
+        transactions.len()
+    }
+
     /// removes transaction from all indexes
     fn index_remove(&mut self, txn: &MempoolTransaction) {
         self.system_ttl_index.remove(&txn);
@@ -295,6 +344,7 @@ impl TransactionStore {
         self.priority_index.remove(&txn);
         self.timeline_index.remove(&txn);
         self.parking_lot_index.remove(&txn);
+        self.fee_payer_index.remove(&txn);
         self.track_indices();
     }
 
@@ -349,12 +399,8 @@ impl TransactionStore {
             .collect()
     }
 
-    /// GC old transactions
-    pub(crate) fn gc_by_system_ttl(&mut self) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("init timestamp failure");
-
+    /// GC old transactions, given the current time
+    pub(crate) fn gc_by_system_ttl(&mut self, now: Duration) {
         self.gc(now, true);
     }
 
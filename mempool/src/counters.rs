@@ -1,7 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use libra_metrics::{register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec};
+use libra_metrics::{
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge_vec,
+    HistogramVec, IntCounter, IntCounterVec, IntGaugeVec,
+};
 use once_cell::sync::Lazy;
 
 /// Counter of pending network events to Mempool
@@ -51,6 +54,33 @@ pub static SHARED_MEMPOOL_TRANSACTIONS_PROCESSED: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Buckets for transaction latencies, which range from milliseconds to minutes
+const TXN_LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Time between consecutive stages reached by a transaction, see `txn_latency`
+pub static TXN_STAGE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_txn_stage_latency_s",
+        "Time from the previous stage of a transaction to the given stage",
+        &["stage", "txn_type"],
+        TXN_LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+/// Time from when a transaction was first seen by this node until it was committed
+pub static TXN_E2E_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_txn_e2e_latency_s",
+        "Time from when a transaction was first seen until it was committed",
+        &["txn_type"],
+        TXN_LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
 pub static SHARED_MEMPOOL_TRANSACTION_BROADCAST: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "libra_shared_mempool_transaction_broadcast",
@@ -58,3 +88,44 @@ pub static SHARED_MEMPOOL_TRANSACTION_BROADCAST: Lazy<IntCounter> = Lazy::new(||
     )
     .unwrap()
 });
+
+/// Number of broadcast batches sent to a peer which it has not ACK'ed yet
+pub static SHARED_MEMPOOL_PENDING_BROADCASTS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "libra_shared_mempool_pending_broadcasts",
+        "Number of broadcast batches awaiting an ACK from a peer",
+        &["peer"]
+    )
+    .unwrap()
+});
+
+/// Age of the oldest broadcast batch a peer has not ACK'ed yet, i.e., how far behind it lags
+pub static SHARED_MEMPOOL_BROADCAST_LAG_MS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "libra_shared_mempool_broadcast_lag_ms",
+        "Time since the oldest broadcast batch awaiting an ACK from a peer was first sent",
+        &["peer"]
+    )
+    .unwrap()
+});
+
+/// Time from when a broadcast batch was first sent until the peer ACK'ed it
+pub static SHARED_MEMPOOL_BROADCAST_ACK_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_shared_mempool_broadcast_ack_latency_s",
+        "Time from when a broadcast batch was first sent until it was ACK'ed",
+        &["peer"],
+        TXN_LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+/// Number of broadcast batches resent to a peer because they were not ACK'ed in time
+pub static SHARED_MEMPOOL_BROADCAST_RETRIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_shared_mempool_broadcast_retries",
+        "Number of broadcast batches resent to a peer for lack of an ACK",
+        &["peer"]
+    )
+    .unwrap()
+});
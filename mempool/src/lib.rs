@@ -66,6 +66,7 @@ pub use shared_mempool::{
         CommittedTransaction, ConsensusRequest, ConsensusResponse, MempoolClientSender,
         SubmissionStatus, TransactionExclusion,
     },
+    MempoolHandle,
 };
 #[cfg(feature = "fuzzing")]
 pub use tests::mocks;
@@ -73,6 +74,8 @@ pub use tests::mocks;
 mod core_mempool;
 mod counters;
 mod shared_mempool;
+/// End-to-end latency of transactions, broken down by stage
+pub mod txn_latency;
 
 // module op counters
 use libra_metrics::OpMetrics;
@@ -4,9 +4,9 @@
 pub mod network;
 mod runtime;
 pub(crate) mod types;
-pub use runtime::bootstrap;
 #[cfg(feature = "fuzzing")]
 pub(crate) use runtime::start_shared_mempool;
+pub use runtime::{bootstrap, MempoolHandle};
 mod coordinator;
 mod peer_manager;
 mod tasks;
@@ -10,7 +10,10 @@ use libra_types::{transaction::SignedTransaction, PeerId};
 use network::{
     error::NetworkError,
     peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
-    protocols::network::{NetworkEvents, NetworkSender, NewNetworkSender},
+    protocols::{
+        network::{NetworkEvents, NetworkSender, NewNetworkSender},
+        wire::handshake::v1::ApplicationVersions,
+    },
     ProtocolId,
 };
 use serde::{Deserialize, Serialize};
@@ -80,6 +83,14 @@ pub fn network_endpoint_config(
     )
 }
 
+/// The versions of the mempool message formats this node supports, advertised to peers during
+/// the network handshake.
+pub fn supported_versions() -> ApplicationVersions {
+    let mut versions = ApplicationVersions::default();
+    versions.add(ProtocolId::MempoolDirectSend, vec![0]);
+    versions
+}
+
 impl NewNetworkSender for MempoolNetworkSender {
     /// Returns a Sender that only sends for the `MEMPOOL_DIRECT_SEND_PROTOCOL` ProtocolId.
     fn new(
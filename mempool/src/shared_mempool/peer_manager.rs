@@ -1,10 +1,12 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::counters;
 use libra_config::config::{PeerNetworkId, UpstreamConfig};
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     sync::Mutex,
+    time::{Duration, Instant},
 };
 
 /// stores only peers that receive txns from this node
@@ -28,8 +30,8 @@ pub(crate) struct PeerManager {
 
 #[derive(Clone)]
 pub struct BroadcastInfo {
-    // broadcasts that have not been ACK'ed for yet
-    pub sent_batches: HashMap<String, Vec<u64>>,
+    // broadcasts that have not been ACK'ed for yet, by request ID
+    pub sent_batches: BTreeMap<String, SentBatch>,
     // timeline IDs of all txns that need to be retried and ACKed for
     pub total_retry_txns: BTreeSet<u64>,
     // whether broadcasts are in backoff/backpressure mode, e.g. broadcasting at longer intervals
@@ -39,11 +41,34 @@ pub struct BroadcastInfo {
 impl BroadcastInfo {
     fn new() -> Self {
         Self {
-            sent_batches: HashMap::new(),
+            sent_batches: BTreeMap::new(),
             total_retry_txns: BTreeSet::new(),
             backoff_mode: false,
         }
     }
+
+    /// returns the request ID and the timeline IDs of the batch whose ACK is overdue for the
+    /// longest, if any
+    pub fn expired_batch(&self, now: Instant) -> Option<(String, Vec<u64>)> {
+        self.sent_batches
+            .iter()
+            .filter(|(_, batch)| batch.retry_at <= now)
+            .min_by_key(|(_, batch)| batch.retry_at)
+            .map(|(request_id, batch)| (request_id.clone(), batch.timeline_ids.clone()))
+    }
+}
+
+/// a broadcast batch awaiting an ACK
+#[derive(Clone)]
+pub struct SentBatch {
+    // timeline IDs of the txns in the batch
+    pub timeline_ids: Vec<u64>,
+    // when the batch was first sent
+    pub first_sent_at: Instant,
+    // when the batch is resent if it is still not ACK'ed by then
+    pub retry_at: Instant,
+    // number of times the batch was sent
+    pub attempts: u32,
 }
 
 impl PeerManager {
@@ -63,14 +88,20 @@ impl PeerManager {
             .expect("failed to acquire peer_info lock");
         let is_new_peer = !peer_info.contains_key(&peer);
         if self.is_upstream_peer(&peer) {
-            peer_info
-                .entry(peer)
-                .or_insert(PeerSyncState {
-                    timeline_id: 0,
-                    is_alive: true,
-                    broadcast_info: BroadcastInfo::new(),
-                })
-                .is_alive = true;
+            let state = peer_info.entry(peer).or_insert(PeerSyncState {
+                timeline_id: 0,
+                is_alive: true,
+                broadcast_info: BroadcastInfo::new(),
+            });
+            if !state.is_alive {
+                // batches sent before the connection was lost may never have been delivered:
+                // resend them right away instead of waiting for their ACK to time out
+                let now = Instant::now();
+                for batch in state.broadcast_info.sent_batches.values_mut() {
+                    batch.retry_at = now;
+                }
+            }
+            state.is_alive = true;
         }
         is_new_peer
     }
@@ -97,6 +128,8 @@ impl PeerManager {
         timeline_id: u64,
         // timeline ID of first txn in timeline, used to remove potentially expired retry_txns
         earliest_timeline_id: u64,
+        // how long to wait for the ACK of this broadcast before resending it
+        ack_timeout: Duration,
     ) {
         let mut peer_info = self
             .peer_info
@@ -104,10 +137,16 @@ impl PeerManager {
             .expect("failed to acquire peer_info lock");
 
         let sync_state = peer_info.get_mut(&peer).expect("missing peer sync state");
-        sync_state
-            .broadcast_info
-            .sent_batches
-            .insert(batch_id, batch);
+        let now = Instant::now();
+        sync_state.broadcast_info.sent_batches.insert(
+            batch_id,
+            SentBatch {
+                timeline_ids: batch,
+                first_sent_at: now,
+                retry_at: now + ack_timeout,
+                attempts: 1,
+            },
+        );
         sync_state.timeline_id = std::cmp::max(sync_state.timeline_id, timeline_id);
 
         // clean up expired retriable txns
@@ -120,6 +159,46 @@ impl PeerManager {
             .collect::<BTreeSet<_>>();
 
         sync_state.broadcast_info.total_retry_txns = gc_retry_txns;
+        update_broadcast_metrics(&peer, &sync_state.broadcast_info);
+    }
+
+    /// records that the batch `batch_id` was resent to `peer` with the txns of `batch`, the ones
+    /// of the original batch which are still in Mempool
+    pub fn update_batch_retry(
+        &self,
+        peer: PeerNetworkId,
+        batch_id: &str,
+        batch: Vec<u64>,
+        ack_timeout: impl Fn(u32) -> Duration,
+    ) {
+        let mut peer_info = self
+            .peer_info
+            .lock()
+            .expect("failed to acquire peer_info lock");
+
+        let sync_state = peer_info.get_mut(&peer).expect("missing peer sync state");
+        if let Some(sent_batch) = sync_state.broadcast_info.sent_batches.get_mut(batch_id) {
+            sent_batch.timeline_ids = batch;
+            sent_batch.attempts += 1;
+            sent_batch.retry_at = Instant::now() + ack_timeout(sent_batch.attempts);
+        }
+        counters::SHARED_MEMPOOL_BROADCAST_RETRIES
+            .with_label_values(&[&peer.peer_id().to_string()])
+            .inc();
+        update_broadcast_metrics(&peer, &sync_state.broadcast_info);
+    }
+
+    /// stops tracking the batch `batch_id` sent to `peer`, e.g. because none of its txns are
+    /// left in Mempool to resend
+    pub fn remove_batch(&self, peer: PeerNetworkId, batch_id: &str) {
+        let mut peer_info = self
+            .peer_info
+            .lock()
+            .expect("failed to acquire peer_info lock");
+
+        let sync_state = peer_info.get_mut(&peer).expect("missing peer sync state");
+        sync_state.broadcast_info.sent_batches.remove(batch_id);
+        update_broadcast_metrics(&peer, &sync_state.broadcast_info);
     }
 
     pub fn process_broadcast_ack(
@@ -137,7 +216,11 @@ impl PeerManager {
         let sync_state = peer_info.get_mut(&peer).expect("missing peer sync state");
 
         if let Some(batch) = sync_state.broadcast_info.sent_batches.remove(&batch_id) {
+            counters::SHARED_MEMPOOL_BROADCAST_ACK_LATENCY
+                .with_label_values(&[&peer.peer_id().to_string()])
+                .observe(batch.first_sent_at.elapsed().as_secs_f64());
             // convert retry_txns from index within a batch to actual timeline ID of txn
+            let batch = batch.timeline_ids;
             let retry_timeline_ids = retry_txns
                 .iter()
                 .filter_map(|batch_index| batch.get(*batch_index as usize).cloned())
@@ -159,6 +242,7 @@ impl PeerManager {
             }
         }
         sync_state.broadcast_info.backoff_mode = backoff;
+        update_broadcast_metrics(&peer, &sync_state.broadcast_info);
     }
 
     pub fn is_upstream_peer(&self, peer: &PeerNetworkId) -> bool {
@@ -204,3 +288,20 @@ impl PeerManager {
         no_live_primaries && self.min_broadcast_recipient_count > 0
     }
 }
+
+/// updates the metrics of how far behind `peer` lags in ACK'ing broadcasts
+pub(crate) fn update_broadcast_metrics(peer: &PeerNetworkId, broadcast_info: &BroadcastInfo) {
+    let peer_label = peer.peer_id().to_string();
+    counters::SHARED_MEMPOOL_PENDING_BROADCASTS
+        .with_label_values(&[&peer_label])
+        .set(broadcast_info.sent_batches.len() as i64);
+    let lag = broadcast_info
+        .sent_batches
+        .values()
+        .map(|batch| batch.first_sent_at.elapsed())
+        .max()
+        .unwrap_or_default();
+    counters::SHARED_MEMPOOL_BROADCAST_LAG_MS
+        .with_label_values(&[&peer_label])
+        .set(lag.as_millis() as i64);
+}
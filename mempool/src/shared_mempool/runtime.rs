@@ -17,7 +17,12 @@ use futures::channel::{
     mpsc::{self, Receiver, UnboundedSender},
     oneshot,
 };
-use libra_config::{config::NodeConfig, network_id::NetworkId};
+use libra_config::{
+    config::{MempoolConfig, NodeConfig},
+    network_id::NetworkId,
+};
+use libra_metrics::health::{register_health_reporter, ComponentHealth};
+use libra_time_service::TimeService;
 use libra_types::{on_chain_config::OnChainConfigPayload, transaction::SignedTransaction};
 use std::{
     collections::HashMap,
@@ -102,19 +107,23 @@ pub fn bootstrap(
     consensus_requests: Receiver<ConsensusRequest>,
     state_sync_requests: Receiver<CommitNotification>,
     mempool_reconfig_events: libra_channel::Receiver<(), OnChainConfigPayload>,
-) -> Runtime {
+    time_service: TimeService,
+) -> (Runtime, MempoolHandle) {
     let runtime = Builder::new()
         .thread_name("shared-mem-")
         .threaded_scheduler()
         .enable_all()
         .build()
         .expect("[shared mempool] failed to create runtime");
-    let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
+    let mempool = Arc::new(Mutex::new(CoreMempool::new_with_time_service(
+        &config,
+        time_service,
+    )));
     let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
     start_shared_mempool(
         runtime.handle(),
         config,
-        mempool,
+        mempool.clone(),
         mempool_network_handles,
         client_events,
         consensus_requests,
@@ -124,5 +133,49 @@ pub fn bootstrap(
         vm_validator,
         vec![],
     );
-    runtime
+
+    let reporter = mempool.clone();
+    register_health_reporter(
+        "mempool",
+        Box::new(move || match reporter.lock() {
+            Ok(mempool) => {
+                let (size, capacity) = mempool.size_and_capacity();
+                let message = format!("{} of {} transactions", size, capacity);
+                if size >= capacity {
+                    ComponentHealth::not_ready(format!("full, {}", message))
+                } else {
+                    ComponentHealth::healthy(message)
+                }
+            }
+            Err(_) => ComponentHealth::unhealthy("mempool lock is poisoned"),
+        }),
+    );
+
+    (runtime, MempoolHandle { mempool })
+}
+
+/// Handle to a running Mempool for updating its configuration without a restart
+#[derive(Clone)]
+pub struct MempoolHandle {
+    mempool: Arc<Mutex<CoreMempool>>,
+}
+
+impl MempoolHandle {
+    /// Applies the capacity limits in the config to subsequently submitted transactions.
+    /// Transactions already in Mempool are kept even if they exceed the new limits.
+    pub fn update_capacity(&self, config: &MempoolConfig) {
+        self.mempool
+            .lock()
+            .expect("[shared mempool] failed to acquire mempool lock")
+            .update_capacity(config);
+    }
+
+    /// Removes all transactions from Mempool, returning how many were removed. The transactions
+    /// are not rejected: their senders can submit them again.
+    pub fn flush(&self) -> usize {
+        self.mempool
+            .lock()
+            .expect("[shared mempool] failed to acquire mempool lock")
+            .clear()
+    }
 }
@@ -7,15 +7,16 @@ use crate::{
     core_mempool::{CoreMempool, TimelineState, TxnPointer},
     counters,
     network::{MempoolNetworkSender, MempoolSyncMsg},
-    shared_mempool::types::{
-        notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification,
+    shared_mempool::{
+        peer_manager::update_broadcast_metrics,
+        types::{notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification},
     },
     CommitNotification, CommitResponse, CommittedTransaction, ConsensusRequest, ConsensusResponse,
     SubmissionStatus,
 };
 use anyhow::{format_err, Result};
 use futures::{channel::oneshot, stream::FuturesUnordered};
-use libra_config::config::PeerNetworkId;
+use libra_config::config::{MempoolConfig, PeerNetworkId};
 use libra_logger::prelude::*;
 use libra_types::{
     mempool_status::{MempoolStatus, MempoolStatusCode},
@@ -32,7 +33,7 @@ use std::{
     collections::HashSet,
     ops::Deref,
     sync::{Arc, Mutex, RwLock},
-    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant},
 };
 use tokio::runtime::Handle;
 use vm_validator::vm_validator::{get_account_sequence_number, TransactionValidation};
@@ -75,25 +76,29 @@ where
 {
     let peer_manager = &smp.peer_manager;
 
-    let (timeline_id, retry_txns_id, next_backoff) = if peer_manager.is_picked_peer(&peer) {
-        let state = peer_manager.get_peer_state(&peer);
-        let next_backoff = state.broadcast_info.backoff_mode;
-        if state.is_alive {
-            (
-                state.timeline_id,
-                state
-                    .broadcast_info
-                    .total_retry_txns
-                    .into_iter()
-                    .collect::<Vec<_>>(),
-                next_backoff,
-            )
+    let (timeline_id, retry_txns_id, next_backoff, broadcast_info) =
+        if peer_manager.is_picked_peer(&peer) {
+            let state = peer_manager.get_peer_state(&peer);
+            let next_backoff = state.broadcast_info.backoff_mode;
+            if state.is_alive {
+                (
+                    state.timeline_id,
+                    state
+                        .broadcast_info
+                        .total_retry_txns
+                        .iter()
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                    next_backoff,
+                    state.broadcast_info,
+                )
+            } else {
+                return next_backoff;
+            }
         } else {
-            return next_backoff;
-        }
-    } else {
-        return false;
-    };
+            return false;
+        };
+    update_broadcast_metrics(&peer, &broadcast_info);
 
     // It is possible that a broadcast was scheduled as non-backoff before an ACK received after the
     // broadcast scheduling turns on backoff mode
@@ -102,6 +107,16 @@ where
         return next_backoff;
     }
 
+    // a batch whose ACK is overdue is resent before any new txns
+    if let Some((request_id, batch_timeline_ids)) = broadcast_info.expired_batch(Instant::now()) {
+        resend_batch(peer, request_id, batch_timeline_ids, smp);
+        return next_backoff;
+    }
+    // the peer only queues so many broadcasts from us: wait for ACKs before sending more
+    if broadcast_info.sent_batches.len() >= smp.config.max_broadcasts_per_peer {
+        return next_backoff;
+    }
+
     // craft batch of txns to broadcast
     let mut mempool = smp
         .mempool
@@ -152,6 +167,10 @@ where
         .expect("[shared mempool] missing network sender");
 
     let request_id = create_request_id(timeline_id, new_timeline_id);
+    if broadcast_info.sent_batches.contains_key(&request_id) {
+        // the same batch is already awaiting an ACK, and is resent if it doesn't get one
+        return next_backoff;
+    }
     let txns_ct = batch_txns.len();
     if let Err(e) = send_mempool_sync_msg(
         MempoolSyncMsg::BroadcastTransactionsRequest {
@@ -173,6 +192,7 @@ where
             batch_timeline_ids,
             new_timeline_id,
             earliest_timeline_id,
+            ack_timeout(&smp.config, 1),
         );
         notify_subscribers(SharedMempoolNotification::Broadcast, &smp.subscribers);
     }
@@ -180,6 +200,71 @@ where
     next_backoff
 }
 
+/// resends to `peer` the txns of an un-ACK'ed batch which are still in Mempool, under the same
+/// request ID, and stops tracking the batch if none are left
+fn resend_batch<V>(
+    peer: PeerNetworkId,
+    request_id: String,
+    timeline_ids: Vec<u64>,
+    smp: &mut SharedMempool<V>,
+) where
+    V: TransactionValidation,
+{
+    let txns = smp
+        .mempool
+        .lock()
+        .expect("[shared mempool] failed to acquire mempool lock")
+        .filter_read_timeline(timeline_ids);
+    if txns.is_empty() {
+        // the txns were all committed or expired since the batch was sent
+        smp.peer_manager.remove_batch(peer, &request_id);
+        return;
+    }
+    let (batch_timeline_ids, batch_txns): (Vec<_>, Vec<_>) = txns.into_iter().unzip();
+
+    let mut network_sender = smp
+        .network_senders
+        .get_mut(&peer.network_id())
+        .expect("[shared mempool] missing network sender");
+
+    let txns_ct = batch_txns.len();
+    if let Err(e) = send_mempool_sync_msg(
+        MempoolSyncMsg::BroadcastTransactionsRequest {
+            request_id: request_id.clone(),
+            transactions: batch_txns,
+        },
+        peer.peer_id(),
+        &mut network_sender,
+    ) {
+        error!(
+            "[shared mempool] error resending transactions to peer {:?}: {}",
+            peer, e
+        );
+    } else {
+        counters::SHARED_MEMPOOL_TRANSACTION_BROADCAST.inc_by(txns_ct as i64);
+        let config = &smp.config;
+        smp.peer_manager
+            .update_batch_retry(peer, &request_id, batch_timeline_ids, |attempts| {
+                ack_timeout(config, attempts)
+            });
+        notify_subscribers(SharedMempoolNotification::Broadcast, &smp.subscribers);
+    }
+}
+
+/// how long to wait for the ACK of a batch sent `attempts` times before resending it
+/// the timeout doubles with every attempt, up to `shared_mempool_max_ack_timeout_ms`
+fn ack_timeout(config: &MempoolConfig, attempts: u32) -> Duration {
+    let multiplier = 1u64
+        .checked_shl(attempts.saturating_sub(1))
+        .unwrap_or(u64::MAX);
+    Duration::from_millis(cmp::min(
+        config
+            .shared_mempool_ack_timeout_ms
+            .saturating_mul(multiplier),
+        config.shared_mempool_max_ack_timeout_ms,
+    ))
+}
+
 fn send_mempool_sync_msg(
     msg: MempoolSyncMsg,
     recipient: PeerId,
@@ -344,6 +429,13 @@ where
             if let Ok(validation_result) = &validation_results[idx] {
                 match validation_result.status() {
                     None => {
+                        if let Err(quota_status) = mempool.check_admission_quotas(&transaction) {
+                            statuses.push((
+                                MempoolStatus::new(MempoolStatusCode::VmError),
+                                Some(quota_status),
+                            ));
+                            continue;
+                        }
                         let gas_amount = transaction.max_gas_amount();
                         let rankin_score = validation_result.score();
                         let is_governance_txn = validation_result.is_governance_txn();
@@ -441,10 +533,9 @@ pub(crate) async fn process_consensus_request(mempool: &Mutex<CoreMempool>, req:
             {
                 let mut mempool = mempool.lock().expect("failed to acquire mempool lock");
                 // gc before pulling block as extra protection against txns that may expire in consensus
-                // Note: this gc operation relies on the fact that consensus uses the system time to determine block timestamp
-                let curr_time = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .expect("Timestamp generated is before UNIX_EPOCH");
+                // Note: this gc operation relies on the fact that consensus uses the same time
+                // service as mempool to determine block timestamp
+                let curr_time = mempool.time_service.now();
                 mempool.gc_by_expiration_time(curr_time);
                 txns = mempool.get_block(block_size, exclude_transactions);
             }
@@ -489,6 +580,7 @@ async fn commit_txns(
 
     if block_timestamp_usecs > 0 {
         pool.gc_by_expiration_time(Duration::from_micros(block_timestamp_usecs));
+        pool.update_block_timestamp(block_timestamp_usecs);
     }
 }
 
@@ -21,7 +21,7 @@ use libra_config::{
 use libra_types::{
     account_address::AccountAddress,
     mempool_status::MempoolStatus,
-    on_chain_config::{ConfigID, LibraVersion, OnChainConfig, OnChainConfigPayload, VMConfig},
+    on_chain_config::{LibraVersion, ModulePublishingPolicy, OnChainConfigPayload, VMConfig},
     transaction::SignedTransaction,
     vm_error::VMStatus,
 };
@@ -33,7 +33,7 @@ use std::{
     time::Instant,
 };
 use storage_interface::DbReader;
-use subscription_service::ReconfigSubscription;
+use subscription_service::{ReconfigSubscription, SubscriptionBundle};
 use tokio::runtime::Handle;
 use vm_validator::vm_validator::TransactionValidation;
 
@@ -196,11 +196,13 @@ pub type SubmissionStatus = (MempoolStatus, Option<VMStatus>);
 pub type MempoolClientSender =
     mpsc::Sender<(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>)>;
 
-/// On-chain configs that mempool subscribes to for reconfiguration
-const MEMPOOL_SUBSCRIBED_CONFIGS: &[ConfigID] = &[LibraVersion::CONFIG_ID, VMConfig::CONFIG_ID];
-
 /// Creates mempool's subscription bundle for on-chain reconfiguration
 pub fn gen_mempool_reconfig_subscription(
 ) -> (ReconfigSubscription, Receiver<(), OnChainConfigPayload>) {
-    ReconfigSubscription::subscribe_all(MEMPOOL_SUBSCRIBED_CONFIGS.to_vec(), vec![])
+    ReconfigSubscription::subscribe(
+        SubscriptionBundle::default()
+            .with_config::<LibraVersion>()
+            .with_config::<VMConfig>()
+            .with_config::<ModulePublishingPolicy>(),
+    )
 }
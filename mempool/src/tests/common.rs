@@ -70,12 +70,39 @@ impl TestTransaction {
         self.make_signed_transaction_impl(100, std::time::Duration::from_secs(u64::max_value()))
     }
 
+    /// Makes a transaction whose gas is paid by the account at index `fee_payer`
+    pub(crate) fn make_sponsored_transaction(&self, fee_payer: usize) -> SignedTransaction {
+        let privkey = TestTransaction::private_key();
+        self.make_raw_transaction(100, std::time::Duration::from_secs(u64::max_value()))
+            .sign_with_fee_payer(
+                &privkey,
+                privkey.public_key(),
+                TestTransaction::get_address(fee_payer),
+                &privkey,
+                privkey.public_key(),
+            )
+            .expect("Failed to sign raw transaction.")
+            .into_inner()
+    }
+
     fn make_signed_transaction_impl(
         &self,
         max_gas_amount: u64,
         exp_time: std::time::Duration,
     ) -> SignedTransaction {
-        let raw_txn = RawTransaction::new_script(
+        let privkey = TestTransaction::private_key();
+        self.make_raw_transaction(max_gas_amount, exp_time)
+            .sign(&privkey, privkey.public_key())
+            .expect("Failed to sign raw transaction.")
+            .into_inner()
+    }
+
+    fn make_raw_transaction(
+        &self,
+        max_gas_amount: u64,
+        exp_time: std::time::Duration,
+    ) -> RawTransaction {
+        RawTransaction::new_script(
             TestTransaction::get_address(self.address),
             self.sequence_number,
             Script::new(vec![], vec![], vec![]),
@@ -83,15 +110,14 @@ impl TestTransaction {
             self.gas_price,
             LBR_NAME.to_owned(),
             exp_time,
-        );
+        )
+    }
+
+    fn private_key() -> Ed25519PrivateKey {
         let mut seed: [u8; 32] = [0u8; 32];
         seed[..4].copy_from_slice(&[1, 2, 3, 4]);
         let mut rng: StdRng = StdRng::from_seed(seed);
-        let privkey = Ed25519PrivateKey::generate(&mut rng);
-        raw_txn
-            .sign(&privkey, privkey.public_key())
-            .expect("Failed to sign raw transaction.")
-            .into_inner()
+        Ed25519PrivateKey::generate(&mut rng)
     }
 
     pub(crate) fn get_address(address: usize) -> AccountAddress {
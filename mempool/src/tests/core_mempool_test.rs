@@ -9,6 +9,7 @@ use crate::{
     },
 };
 use libra_config::config::NodeConfig;
+use libra_time_service::TimeService;
 use libra_types::transaction::SignedTransaction;
 use std::{
     collections::HashSet,
@@ -197,6 +198,29 @@ fn test_system_ttl() {
     assert_eq!(vec![transaction.make_signed_transaction()], batch);
 }
 
+#[test]
+fn test_system_ttl_with_mock_time() {
+    let mut config = NodeConfig::random();
+    config.mempool.system_transaction_timeout_secs = 10;
+    let time_service = TimeService::mock(Duration::from_secs(1000));
+    let mut mempool = CoreMempool::new_with_time_service(&config, time_service.clone());
+
+    add_txn(&mut mempool, TestTransaction::new(0, 0, 10)).unwrap();
+    time_service.advance(Duration::from_secs(5));
+    let transaction = TestTransaction::new(1, 0, 1);
+    add_txn(&mut mempool, transaction.clone()).unwrap();
+
+    // the first transaction outlived its 10 seconds, the last one has 4 seconds left
+    time_service.advance(Duration::from_secs(6));
+    mempool.gc();
+    let batch = mempool.get_block(2, HashSet::new());
+    assert_eq!(vec![transaction.make_signed_transaction()], batch);
+
+    time_service.advance(Duration::from_secs(5));
+    mempool.gc();
+    assert!(mempool.get_block(2, HashSet::new()).is_empty());
+}
+
 #[test]
 fn test_commit_callback() {
     // consensus commit callback should unlock txns in parking lot
@@ -303,6 +327,83 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_update_capacity() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
+
+    // raising the capacity allows further transactions
+    config.mempool.capacity = 2;
+    pool.update_capacity(&config.mempool);
+    add_txn(&mut pool, TestTransaction::new(1, 1, 1)).unwrap();
+
+    // lowering the capacity per user keeps existing transactions but rejects new ones
+    config.mempool.capacity = 10;
+    config.mempool.capacity_per_user = 1;
+    pool.update_capacity(&config.mempool);
+    assert_eq!(pool.get_block(10, HashSet::new()).len(), 2);
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_err());
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+}
+
+#[test]
+fn test_clear() {
+    let mut pool = setup_mempool().0;
+    add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::new(0, 0, 1),
+            TestTransaction::new(1, 0, 1),
+            TestTransaction::new(1, 2, 1),
+        ],
+    );
+    let (_, last_timeline_id) = pool.read_timeline(0, 10);
+
+    assert_eq!(pool.clear(), 3);
+    assert_eq!(pool.size_and_capacity().0, 0);
+    assert!(pool.get_block(10, HashSet::new()).is_empty());
+
+    // transactions added afterwards follow the previous ones on the timeline
+    add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 0, 1)]);
+    let (timeline, _) = pool.read_timeline(last_timeline_id, 10);
+    assert_eq!(timeline.len(), 1);
+}
+
+#[test]
+fn test_capacity_per_fee_payer() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity_per_user = 2;
+    let mut pool = CoreMempool::new(&config);
+
+    // account 0 pays for one transaction of its own and one of account 1
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    add_signed_txn(
+        &mut pool,
+        TestTransaction::new(1, 0, 1).make_sponsored_transaction(0),
+    )
+    .unwrap();
+    // it can't pay for more transactions, even for senders below their own capacity
+    assert!(add_signed_txn(
+        &mut pool,
+        TestTransaction::new(2, 0, 1).make_sponsored_transaction(0),
+    )
+    .is_err());
+    assert!(add_txn(&mut pool, TestTransaction::new(0, 1, 1)).is_err());
+    // other fee payers are not affected
+    add_signed_txn(
+        &mut pool,
+        TestTransaction::new(2, 0, 1).make_sponsored_transaction(3),
+    )
+    .unwrap();
+
+    // committing a sponsored transaction frees the capacity of its fee payer
+    pool.remove_transaction(&TestTransaction::get_address(1), 0, false);
+    add_txn(&mut pool, TestTransaction::new(0, 1, 1)).unwrap();
+}
+
 #[test]
 fn test_parking_lot_eviction() {
     let mut config = NodeConfig::random();
@@ -349,6 +349,25 @@ impl SharedMempoolNetwork {
         }
     }
 
+    /// drops next broadcast message from `peer`, as if it was lost on the way
+    fn drop_message(&mut self, peer: &PeerId) -> Vec<SignedTransaction> {
+        self.wait_for_event(peer, SharedMempoolNotification::Broadcast);
+
+        let network_reqs_rx = self.network_reqs_rxs.get_mut(peer).unwrap();
+        let network_req = block_on(network_reqs_rx.next()).unwrap();
+        if let PeerManagerRequest::SendMessage(_peer_id, msg) = network_req {
+            if let MempoolSyncMsg::BroadcastTransactionsRequest { transactions, .. } =
+                lcs::from_bytes(&msg.mdata).unwrap()
+            {
+                transactions
+            } else {
+                panic!("did not receive expected BroadcastTransactionsRequest");
+            }
+        } else {
+            panic!("peer {:?} didn't broadcast transaction", peer)
+        }
+    }
+
     /// delivers broadcast ACK from `peer`
     fn deliver_response(&mut self, peer: &PeerId) {
         let network_reqs_rx = self.network_reqs_rxs.get_mut(peer).unwrap();
@@ -505,6 +524,41 @@ fn test_interruption_in_sync() {
     assert_eq!(txn.get(0).unwrap().sequence_number(), 1);
 }
 
+#[test]
+fn test_rebroadcast_unacked_batch() {
+    let mut smp = SharedMempoolNetwork::default();
+    let mut peers = vec![];
+    for _ in 0..2 {
+        let mut config = NodeConfig::random();
+        config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
+        config.mempool.shared_mempool_batch_size = 1;
+        config.mempool.shared_mempool_ack_timeout_ms = 100;
+        let peer_id = config.validator_network.as_ref().unwrap().peer_id();
+        init_single_shared_mempool(&mut smp, peer_id, NetworkId::Validator, config);
+        peers.push(peer_id);
+    }
+    let (peer_a, peer_b) = (peers.get(0).unwrap(), peers.get(1).unwrap());
+    smp.add_txns(&peer_a, vec![TestTransaction::new(1, 0, 1)]);
+    smp.send_connection_event(
+        &peer_a,
+        ConnectionNotification::NewPeer(*peer_b, NetworkAddress::mock(), NetworkContext::mock()),
+    );
+
+    // the first broadcast is lost, so it is never ACK'ed
+    let txns = smp.drop_message(&peer_a);
+    assert_eq!(txns.get(0).unwrap().sequence_number(), 0);
+
+    // A resends it once its ACK is overdue
+    let (txns, peer_id) = smp.deliver_message(&peer_a, 1, true);
+    assert_eq!(peer_id, *peer_b);
+    assert_eq!(txns.get(0).unwrap().sequence_number(), 0);
+
+    // once ACK'ed, the batch is done with and broadcasting moves on to new txns
+    smp.add_txns(&peer_a, vec![TestTransaction::new(1, 1, 1)]);
+    let (txns, _) = smp.deliver_message(&peer_a, 1, true);
+    assert_eq!(txns.get(0).unwrap().sequence_number(), 1);
+}
+
 #[test]
 fn test_ready_transactions() {
     let (mut smp, peers) = SharedMempoolNetwork::bootstrap_validator_network(2, 1, None);
@@ -0,0 +1,253 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! End-to-end latency of transactions on this node, from submission to commit.
+//!
+//! Each component records the stages a transaction reaches. The time since the previous stage
+//! recorded for the transaction is observed in `libra_txn_stage_latency_s`, and upon commit the
+//! time since the transaction was first seen is observed in `libra_txn_e2e_latency_s`. Both are
+//! labeled by the type of transaction. Stages a node does not go through, e.g., execution of
+//! blocks on a full node, are simply not recorded.
+//!
+//! Transactions that are rejected or exceed the latency SLO have their trace captured, with the
+//! latency of each stage, whether or not they were sampled by libra-trace.
+
+use crate::counters;
+use compiled_stdlib::transaction_scripts::StdlibScript;
+use debug_interface::trace_store;
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, TransactionPayload},
+};
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Transactions not committed within this time are no longer tracked
+const TRACKING_TTL: Duration = Duration::from_secs(600);
+/// Bound on the number of tracked transactions
+const MAX_TRACKED_TXNS: usize = 100_000;
+
+/// A stage in the life of a transaction on this node
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TxnStage {
+    /// Submitted to the JSON-RPC endpoint of this node
+    Submitted,
+    /// Admitted into Mempool, whether submitted to this node or broadcast by a peer
+    Admitted,
+    /// Included in a block proposal that is about to be executed
+    Included,
+    /// Executed as part of a block
+    Executed,
+    /// Committed to storage, as notified to Mempool
+    Committed,
+}
+
+impl TxnStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            TxnStage::Submitted => "submitted",
+            TxnStage::Admitted => "admitted",
+            TxnStage::Included => "included",
+            TxnStage::Executed => "executed",
+            TxnStage::Committed => "committed",
+        }
+    }
+}
+
+static P2P_SCRIPT: Lazy<Vec<u8>> = Lazy::new(|| {
+    StdlibScript::PeerToPeerWithMetadata
+        .compiled_bytes()
+        .into_vec()
+});
+
+/// The type of a transaction, as used to label latencies
+pub fn txn_type(txn: &SignedTransaction) -> &'static str {
+    match txn.payload() {
+        TransactionPayload::Script(script) if script.code() == P2P_SCRIPT.as_slice() => "p2p",
+        TransactionPayload::Script(_) => "script",
+        TransactionPayload::Module(_) => "module_publish",
+        TransactionPayload::Batch(_) => "batch",
+        TransactionPayload::WriteSet(_) => "writeset",
+    }
+}
+
+struct TrackedTxn {
+    txn_type: &'static str,
+    first_seen: Instant,
+    last_stage: Instant,
+    // Latency in seconds of each stage reached after the first one
+    stages: Vec<(&'static str, f64)>,
+}
+
+impl TrackedTxn {
+    fn details(&self) -> serde_json::Value {
+        serde_json::json!({
+            "txn_type": self.txn_type,
+            "stage_latencies_s": self.stages,
+        })
+    }
+}
+
+fn trace_node(sender: AccountAddress, sequence_number: u64) -> String {
+    format!("txn::{}::{}", sender, sequence_number)
+}
+
+struct LatencyTracker {
+    txns: HashMap<(AccountAddress, u64), TrackedTxn>,
+}
+
+impl LatencyTracker {
+    fn record(
+        &mut self,
+        sender: AccountAddress,
+        sequence_number: u64,
+        stage: TxnStage,
+        txn_type: Option<&'static str>,
+        now: Instant,
+    ) {
+        let key = (sender, sequence_number);
+        match self.txns.get_mut(&key) {
+            Some(txn) => {
+                let stage_latency = now.duration_since(txn.last_stage).as_secs_f64();
+                counters::TXN_STAGE_LATENCY
+                    .with_label_values(&[stage.as_str(), txn.txn_type])
+                    .observe(stage_latency);
+                txn.last_stage = now;
+                txn.stages.push((stage.as_str(), stage_latency));
+                if stage == TxnStage::Committed {
+                    let e2e_latency = now.duration_since(txn.first_seen);
+                    counters::TXN_E2E_LATENCY
+                        .with_label_values(&[txn.txn_type])
+                        .observe(e2e_latency.as_secs_f64());
+                    trace_store::capture_if_slow(
+                        &trace_node(sender, sequence_number),
+                        e2e_latency,
+                        || txn.details(),
+                    );
+                    self.txns.remove(&key);
+                }
+            }
+            // Only stages that see the whole transaction can start tracking it
+            None => {
+                if let Some(txn_type) = txn_type {
+                    if self.txns.len() >= MAX_TRACKED_TXNS {
+                        self.gc(now);
+                    }
+                    if self.txns.len() < MAX_TRACKED_TXNS {
+                        self.txns.insert(
+                            key,
+                            TrackedTxn {
+                                txn_type,
+                                first_seen: now,
+                                last_stage: now,
+                                stages: vec![],
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn gc(&mut self, now: Instant) {
+        self.txns
+            .retain(|_, txn| now.duration_since(txn.first_seen) < TRACKING_TTL);
+    }
+}
+
+static TRACKER: Lazy<Mutex<LatencyTracker>> = Lazy::new(|| {
+    Mutex::new(LatencyTracker {
+        txns: HashMap::new(),
+    })
+});
+
+fn tracker() -> std::sync::MutexGuard<'static, LatencyTracker> {
+    TRACKER
+        .lock()
+        .expect("[txn latency] failed to acquire lock")
+}
+
+/// Records that the transaction reached the stage, starting to track it if it is not yet tracked
+pub fn record_txn(txn: &SignedTransaction, stage: TxnStage) {
+    record_typed_txn(txn.sender(), txn.sequence_number(), txn_type(txn), stage);
+}
+
+pub(crate) fn record_typed_txn(
+    sender: AccountAddress,
+    sequence_number: u64,
+    txn_type: &'static str,
+    stage: TxnStage,
+) {
+    tracker().record(
+        sender,
+        sequence_number,
+        stage,
+        Some(txn_type),
+        Instant::now(),
+    );
+}
+
+/// Records that a tracked transaction reached the stage
+pub fn record_txn_stage(sender: AccountAddress, sequence_number: u64, stage: TxnStage) {
+    tracker().record(sender, sequence_number, stage, None, Instant::now());
+}
+
+/// Stops tracking a transaction that will never be committed, e.g., because it was rejected, and
+/// captures its trace
+pub fn discard_txn(sender: AccountAddress, sequence_number: u64) {
+    let txn = tracker().txns.remove(&(sender, sequence_number));
+    trace_store::capture(
+        &trace_node(sender, sequence_number),
+        "rejected",
+        txn.map_or(serde_json::Value::Null, |txn| txn.details()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observations(stage: TxnStage) -> u64 {
+        counters::TXN_STAGE_LATENCY
+            .with_label_values(&[stage.as_str(), "script"])
+            .get_sample_count()
+    }
+
+    #[test]
+    fn test_latency_tracking() {
+        let mut tracker = LatencyTracker {
+            txns: HashMap::new(),
+        };
+        let sender = AccountAddress::random();
+        let start = Instant::now();
+
+        // Untracked transactions are only tracked from stages that see the whole transaction
+        tracker.record(sender, 0, TxnStage::Included, None, start);
+        assert!(tracker.txns.is_empty());
+
+        let included = observations(TxnStage::Included);
+        tracker.record(sender, 0, TxnStage::Admitted, Some("script"), start);
+        tracker.record(sender, 0, TxnStage::Included, None, start);
+        assert_eq!(observations(TxnStage::Included), included + 1);
+
+        let e2e = counters::TXN_E2E_LATENCY
+            .with_label_values(&["script"])
+            .get_sample_count();
+        tracker.record(sender, 0, TxnStage::Committed, None, start);
+        assert!(tracker.txns.is_empty());
+        assert_eq!(
+            counters::TXN_E2E_LATENCY
+                .with_label_values(&["script"])
+                .get_sample_count(),
+            e2e + 1
+        );
+
+        tracker.record(sender, 1, TxnStage::Admitted, Some("script"), start);
+        tracker.gc(start + TRACKING_TTL);
+        assert!(tracker.txns.is_empty());
+    }
+}
@@ -26,14 +26,14 @@ use network::{
     connectivity_manager::{ConnectivityManager, ConnectivityRequest},
     constants, counters,
     peer_manager::{
-        conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
-        PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
+        conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerApplicationVersions,
+        PeerManager, PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
     },
     protocols::{
         discovery::{self, builder::DiscoveryBuilder},
-        health_checker::{self, HealthChecker},
+        health_checker::{self, HealthChecker, PeerLatencies},
         network::{NewNetworkEvents, NewNetworkSender},
-        wire::handshake::v1::SupportedProtocols,
+        wire::handshake::v1::{ApplicationVersions, SupportedProtocols},
     },
     transport::{self, Connection, LibraNetTransport, LIBRA_TCP_TRANSPORT},
     ProtocolId,
@@ -41,7 +41,7 @@ use network::{
 use onchain_discovery::builder::OnchainDiscoveryBuilder;
 use std::{
     clone::Clone,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     num::NonZeroUsize,
     sync::{Arc, RwLock},
     time::Duration,
@@ -95,15 +95,18 @@ pub struct NetworkBuilder {
     listen_address: NetworkAddress,
     advertised_address: Option<NetworkAddress>,
     seed_peers: HashMap<PeerId, Vec<NetworkAddress>>,
-    trusted_peers: Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>,
+    trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     authentication_mode: Option<AuthenticationMode>,
     channel_size: usize,
     direct_send_protocols: Vec<ProtocolId>,
     rpc_protocols: Vec<ProtocolId>,
+    application_versions: ApplicationVersions,
+    peer_application_versions: PeerApplicationVersions,
     discovery_interval_ms: u64,
     ping_interval_ms: u64,
     ping_timeout_ms: u64,
     ping_failures_tolerated: u64,
+    peer_latencies: PeerLatencies,
     upstream_handlers:
         HashMap<ProtocolId, libra_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>>,
     connection_event_handlers: Vec<conn_notifs_channel::Sender>,
@@ -117,7 +120,8 @@ pub struct NetworkBuilder {
     max_concurrent_network_notifs: usize,
     max_connection_delay_ms: u64,
     /// For now full node connections are limited by
-    max_fullnode_connections: usize,
+    max_inbound_connections: usize,
+    max_outbound_connections: usize,
 
     discovery_builder: Option<DiscoveryBuilder>,
 }
@@ -156,6 +160,8 @@ impl NetworkBuilder {
             channel_size: constants::NETWORK_CHANNEL_SIZE,
             direct_send_protocols: vec![],
             rpc_protocols: vec![],
+            application_versions: ApplicationVersions::default(),
+            peer_application_versions: PeerApplicationVersions::default(),
             upstream_handlers: HashMap::new(),
             connection_event_handlers: Vec::new(),
             pm_reqs_tx,
@@ -167,11 +173,13 @@ impl NetworkBuilder {
             ping_interval_ms: constants::PING_INTERVAL_MS,
             ping_timeout_ms: constants::PING_TIMEOUT_MS,
             ping_failures_tolerated: constants::PING_FAILURES_TOLERATED,
+            peer_latencies: Arc::new(RwLock::new(HashMap::new())),
             connectivity_check_interval_ms: constants::CONNECTIVITY_CHECK_INTERNAL_MS,
             max_concurrent_network_reqs: constants::MAX_CONCURRENT_NETWORK_REQS,
             max_concurrent_network_notifs: constants::MAX_CONCURRENT_NETWORK_NOTIFS,
             max_connection_delay_ms: constants::MAX_CONNECTION_DELAY_MS,
-            max_fullnode_connections: constants::MAX_FULLNODE_CONNECTIONS,
+            max_inbound_connections: constants::MAX_INBOUND_CONNECTIONS,
+            max_outbound_connections: constants::MAX_FULLNODE_CONNECTIONS,
             discovery_builder: None,
         }
     }
@@ -201,7 +209,10 @@ impl NetworkBuilder {
             peer_id,
             config.listen_address.clone(),
         );
-        network_builder.add_connection_monitoring();
+        network_builder
+            .max_inbound_connections(config.max_inbound_connections)
+            .max_outbound_connections(config.max_outbound_connections)
+            .add_connection_monitoring();
 
         // Sanity check seed peer addresses.
         config
@@ -210,12 +221,15 @@ impl NetworkBuilder {
         let seed_peers = config.seed_peers.clone();
 
         if config.mutual_authentication {
-            let network_peers = config.network_peers.clone();
             let trusted_peers = if role == RoleType::Validator {
                 // for validators, trusted_peers is empty will be populated from consensus
                 HashMap::new()
             } else {
-                network_peers
+                config
+                    .network_peers
+                    .iter()
+                    .map(|(peer_id, key)| (*peer_id, vec![*key].into_iter().collect()))
+                    .collect()
             };
 
             info!(
@@ -292,7 +306,7 @@ impl NetworkBuilder {
     /// Set trusted peers.
     pub fn trusted_peers(
         &mut self,
-        trusted_peers: HashMap<PeerId, x25519::PublicKey>,
+        trusted_peers: HashMap<PeerId, HashSet<x25519::PublicKey>>,
     ) -> &mut Self {
         *self.trusted_peers.write().unwrap() = trusted_peers;
         self
@@ -319,10 +333,37 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set the maximum number of inbound connections
+    pub fn max_inbound_connections(&mut self, max_inbound_connections: usize) -> &mut Self {
+        self.max_inbound_connections = max_inbound_connections;
+        self
+    }
+
+    /// Set the maximum number of outbound connections, only enforced on full node networks
+    pub fn max_outbound_connections(&mut self, max_outbound_connections: usize) -> &mut Self {
+        self.max_outbound_connections = max_outbound_connections;
+        self
+    }
+
     pub fn conn_mgr_reqs_tx(&self) -> Option<channel::Sender<ConnectivityRequest>> {
         self.conn_mgr_reqs_tx.clone()
     }
 
+    /// Advertise the message format versions this node supports for application protocols during
+    /// the handshake. Protocols whose versions are not advertised only support version 0.
+    pub fn advertise_application_versions(
+        &mut self,
+        application_versions: ApplicationVersions,
+    ) -> &mut Self {
+        self.application_versions.extend(application_versions);
+        self
+    }
+
+    /// Returns the application protocol versions negotiated with the connected peers.
+    pub fn peer_application_versions(&self) -> PeerApplicationVersions {
+        self.peer_application_versions.clone()
+    }
+
     fn supported_protocols(&self) -> SupportedProtocols {
         self.direct_send_protocols
             .iter()
@@ -371,6 +412,12 @@ impl NetworkBuilder {
         )
     }
 
+    /// Returns a sender for requests to dial or disconnect peers outside of the protocols, e.g.,
+    /// on behalf of an operator.
+    pub fn connection_request_sender(&self) -> ConnectionRequestSender {
+        ConnectionRequestSender::new(self.connection_reqs_tx.clone())
+    }
+
     pub fn add_connection_event_listener(&mut self) -> conn_notifs_channel::Receiver {
         let (tx, rx) = conn_notifs_channel::new();
         self.connection_event_handlers.push(tx);
@@ -394,12 +441,13 @@ impl NetworkBuilder {
         self.conn_mgr_reqs_tx = Some(conn_mgr_reqs_tx);
         let trusted_peers = self.trusted_peers.clone();
         let seed_peers = self.seed_peers.clone();
+        let peer_latencies = self.peer_latencies.clone();
         let max_connection_delay_ms = self.max_connection_delay_ms;
         let connectivity_check_interval_ms = self.connectivity_check_interval_ms;
         let pm_conn_mgr_notifs_rx = self.add_connection_event_listener();
         let conn_mgr = self.executor.enter(|| {
             let connection_limit = if let RoleType::FullNode = self.network_context.role() {
-                Some(self.max_fullnode_connections)
+                Some(self.max_outbound_connections)
             } else {
                 None
             };
@@ -414,6 +462,7 @@ impl NetworkBuilder {
                 ExponentialBackoff::from_millis(2).factor(1000),
                 max_connection_delay_ms,
                 connection_limit,
+                peer_latencies,
             )
         });
         self.executor.spawn(conn_mgr.start());
@@ -496,6 +545,7 @@ impl NetworkBuilder {
         let ping_interval_ms = self.ping_interval_ms;
         let ping_timeout_ms = self.ping_timeout_ms;
         let ping_failures_tolerated = self.ping_failures_tolerated;
+        let peer_latencies = self.peer_latencies.clone();
         let health_checker = self.executor.enter(|| {
             HealthChecker::new(
                 self.network_context.clone(),
@@ -504,6 +554,7 @@ impl NetworkBuilder {
                 hc_network_rx,
                 Duration::from_millis(ping_timeout_ms),
                 ping_failures_tolerated,
+                peer_latencies,
             )
         });
         self.executor.spawn(health_checker.start());
@@ -519,6 +570,7 @@ impl NetworkBuilder {
         let chain_id = self.chain_id.clone();
         let network_id = self.network_context.network_id().clone();
         let protos = self.supported_protocols();
+        let application_versions = self.application_versions.clone();
 
         let authentication_mode = self
             .authentication_mode
@@ -555,6 +607,7 @@ impl NetworkBuilder {
                     chain_id,
                     network_id,
                     protos,
+                    application_versions,
                 ))
             }
             [Memory(_)] => self.build_with_transport(LibraNetTransport::new(
@@ -566,6 +619,7 @@ impl NetworkBuilder {
                 chain_id,
                 network_id,
                 protos,
+                application_versions,
             )),
             _ => panic!(
                 "{} Unsupported listen_address: '{}', expected '/memory/<port>', \
@@ -593,9 +647,12 @@ impl NetworkBuilder {
             self.connection_reqs_rx,
             self.upstream_handlers,
             self.connection_event_handlers,
+            self.peer_application_versions,
             self.max_concurrent_network_reqs,
             self.max_concurrent_network_notifs,
             self.channel_size,
+            self.trusted_peers,
+            self.max_inbound_connections,
         );
         let listen_addr = peer_mgr.listen_addr().clone();
 
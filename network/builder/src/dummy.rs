@@ -120,8 +120,14 @@ pub fn setup_network() -> DummyNetwork {
 
     // Setup trusted peers.
     let trusted_peers: HashMap<_, _> = vec![
-        (dialer_peer_id, dialer_identity_public_key),
-        (listener_peer_id, listener_identity_public_key),
+        (
+            dialer_peer_id,
+            vec![dialer_identity_public_key].into_iter().collect(),
+        ),
+        (
+            listener_peer_id,
+            vec![listener_identity_public_key].into_iter().collect(),
+        ),
     ]
     .into_iter()
     .collect();
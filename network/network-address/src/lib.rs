@@ -335,6 +335,36 @@ impl NetworkAddress {
         })
     }
 
+    /// Replace the pubkey of every `/ln-noise-ik/<pubkey>` in a `NetworkAddress`
+    /// with `network_pubkey`, e.g., to advertise the address a node is reachable
+    /// at after rotating its network key.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// use libra_crypto::{traits::ValidCryptoMaterialStringExt, x25519};
+    /// use libra_network_address::NetworkAddress;
+    /// use std::str::FromStr;
+    ///
+    /// let pubkey_str = "080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120";
+    /// let pubkey = x25519::PublicKey::from_encoded_string(pubkey_str).unwrap();
+    /// let addr_str = "/dns/example.com/tcp/6180/ln-noise-ik/a5e05e5ba2d1cc3dd2a8e10a6b1a0f5bbd1ce8a1d70ec8f1c7fcf5ad7b8e2e3a/ln-handshake/0";
+    /// let addr = NetworkAddress::from_str(addr_str).unwrap();
+    /// let addr = addr.rotate_noise_public_key(&pubkey);
+    /// assert_eq!(addr.find_noise_proto(), Some(pubkey));
+    /// ```
+    pub fn rotate_noise_public_key(&self, network_pubkey: &x25519::PublicKey) -> Self {
+        Self::new(
+            self.0
+                .iter()
+                .map(|proto| match proto {
+                    Protocol::NoiseIK(_) => Protocol::NoiseIK(*network_pubkey),
+                    proto => proto.clone(),
+                })
+                .collect(),
+        )
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn mock() -> Self {
         NetworkAddress::new(vec![Protocol::Memory(1234)])
@@ -175,17 +175,23 @@ impl DiscoveryInfoInternal {
                 validator_info
                     .config()
                     .validator_network_identity_public_key,
-                vec![NetworkAddress::try_from(
-                    &validator_info.config().validator_network_address,
-                )?],
+                validator_info
+                    .config()
+                    .validator_network_addresses()
+                    .iter()
+                    .map(NetworkAddress::try_from)
+                    .collect::<Result<_, _>>()?,
             ),
             RoleType::FullNode => Self(
                 validator_info
                     .config()
                     .full_node_network_identity_public_key,
-                vec![NetworkAddress::try_from(
-                    &validator_info.config().full_node_network_address,
-                )?],
+                validator_info
+                    .config()
+                    .full_node_network_addresses()
+                    .iter()
+                    .map(NetworkAddress::try_from)
+                    .collect::<Result<_, _>>()?,
             ),
         };
         Ok(info)
@@ -10,13 +10,13 @@ use libra_logger::prelude::*;
 use libra_metrics::{register_histogram, DurationHistogram};
 use libra_network_address::NetworkAddress;
 use libra_types::{
-    on_chain_config::{OnChainConfigPayload, ValidatorSet, ON_CHAIN_CONFIG_REGISTRY},
+    on_chain_config::{OnChainConfigPayload, ValidatorSet},
     validator_config::ValidatorConfig,
 };
 use network::connectivity_manager::{ConnectivityRequest, DiscoverySource};
 use once_cell::sync::Lazy;
-use std::{convert::TryFrom, time::Instant};
-use subscription_service::ReconfigSubscription;
+use std::{collections::HashSet, convert::TryFrom, time::Instant};
+use subscription_service::{ReconfigSubscription, SubscriptionBundle};
 
 /// Histogram of idle time of spent in event processing loop
 pub static EVENT_PROCESSING_LOOP_IDLE_DURATION_S: Lazy<DurationHistogram> = Lazy::new(|| {
@@ -49,22 +49,28 @@ pub struct ConfigurationChangeListener {
 
 pub fn gen_simple_discovery_reconfig_subscription(
 ) -> (ReconfigSubscription, Receiver<(), OnChainConfigPayload>) {
-    ReconfigSubscription::subscribe_all(ON_CHAIN_CONFIG_REGISTRY.to_vec(), vec![])
+    ReconfigSubscription::subscribe(SubscriptionBundle::default().with_config::<ValidatorSet>())
 }
 
-/// Extract the network_address from the provided config, depending on role.
-fn network_address(role: RoleType, config: &ValidatorConfig) -> Result<NetworkAddress, lcs::Error> {
-    match role {
-        RoleType::Validator => NetworkAddress::try_from(&config.validator_network_address),
-        RoleType::FullNode => NetworkAddress::try_from(&config.full_node_network_address),
-    }
+/// Extract the network addresses from the provided config, depending on role. While a node is
+/// rotating its network key this includes both the current and the next address.
+fn network_addresses(
+    role: RoleType,
+    config: &ValidatorConfig,
+) -> Result<Vec<NetworkAddress>, lcs::Error> {
+    let addresses = match role {
+        RoleType::Validator => config.validator_network_addresses(),
+        RoleType::FullNode => config.full_node_network_addresses(),
+    };
+    addresses.iter().map(NetworkAddress::try_from).collect()
 }
 
-/// Extracts the public key from the provided config, depending on role.
-fn public_key(role: RoleType, config: &ValidatorConfig) -> x25519::PublicKey {
+/// Extracts the public keys from the provided config, depending on role. While a node is
+/// rotating its network key this includes both the current and the next key.
+fn public_keys(role: RoleType, config: &ValidatorConfig) -> HashSet<x25519::PublicKey> {
     match role {
-        RoleType::Validator => config.validator_network_identity_public_key,
-        RoleType::FullNode => config.full_node_network_identity_public_key,
+        RoleType::Validator => config.validator_network_identity_public_keys(),
+        RoleType::FullNode => config.full_node_network_identity_public_keys(),
     }
 }
 
@@ -77,8 +83,8 @@ fn extract_updates(role: RoleType, node_set: ValidatorSet) -> Vec<ConnectivityRe
     // Collect the set of address updates.
     let address_map = node_list
         .iter()
-        .flat_map(|node| match network_address(role, node.config()) {
-            Ok(addr) => Some((*node.account_address(), vec![addr])),
+        .flat_map(|node| match network_addresses(role, node.config()) {
+            Ok(addrs) => Some((*node.account_address(), addrs)),
             Err(e) => {
                 warn!("Cannot parse network address {}", e);
                 None
@@ -95,7 +101,7 @@ fn extract_updates(role: RoleType, node_set: ValidatorSet) -> Vec<ConnectivityRe
     updates.push(ConnectivityRequest::UpdateEligibleNodes(
         node_list
             .iter()
-            .map(|node| (*node.account_address(), public_key(role, node.config())))
+            .map(|node| (*node.account_address(), public_keys(role, node.config())))
             .collect(),
     ));
 
@@ -26,10 +26,16 @@
 //! absolutely important that we maintain connectivity with all peers and heal
 //! any partitions asap, as we aren't currently gossiping consensus messages or
 //! using a relay protocol.
+//!
+//! When the number of outgoing connections is limited, we prefer to dial the
+//! peers with the lowest round-trip time, as measured by the HealthChecker on
+//! previous connections. Peers without a measured round-trip time are dialed
+//! last, in random order.
 
 use crate::{
     logging,
     peer_manager::{self, conn_notifs_channel, ConnectionRequestSender, PeerManagerError},
+    protocols::health_checker::PeerLatencies,
 };
 use futures::{
     channel::oneshot,
@@ -49,7 +55,7 @@ use rand::{
 use serde::Serialize;
 use std::{
     cmp::min,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt, mem,
     sync::{Arc, RwLock},
     time::{Duration, Instant},
@@ -63,7 +69,7 @@ mod test;
 pub struct ConnectivityManager<TTicker, TBackoff> {
     network_context: Arc<NetworkContext>,
     /// Nodes which are eligible to join the network.
-    eligible: Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>,
+    eligible: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     /// PeerId and address of remote peers to which this peer is connected.
     connected: HashMap<PeerId, NetworkAddress>,
     /// Addresses of peers received from discovery sources.
@@ -91,6 +97,8 @@ pub struct ConnectivityManager<TTicker, TBackoff> {
     event_id: u32,
     /// A way to limit the number of connected peers by outgoing dials.
     connection_limit: Option<usize>,
+    /// Smoothed round-trip times measured by the HealthChecker, used to rank the peers to dial.
+    peer_latencies: PeerLatencies,
     /// Random for shuffling which peers will be dialed
     rng: SmallRng,
 }
@@ -111,7 +119,7 @@ pub enum ConnectivityRequest {
     /// Request to update known addresses of peer with id `PeerId` to given list.
     UpdateAddresses(DiscoverySource, HashMap<PeerId, Vec<NetworkAddress>>),
     /// Update set of nodes eligible to join the network.
-    UpdateEligibleNodes(HashMap<PeerId, x25519::PublicKey>),
+    UpdateEligibleNodes(HashMap<PeerId, HashSet<x25519::PublicKey>>),
     /// Gets current size of dial queue. This is useful in tests.
     #[serde(skip)]
     GetDialQueueSize(oneshot::Sender<usize>),
@@ -152,7 +160,7 @@ where
     /// Creates a new instance of the [`ConnectivityManager`] actor.
     pub fn new(
         network_context: Arc<NetworkContext>,
-        eligible: Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>,
+        eligible: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
         seed_peers: HashMap<PeerId, Vec<NetworkAddress>>,
         ticker: TTicker,
         connection_reqs_tx: ConnectionRequestSender,
@@ -161,6 +169,7 @@ where
         backoff_strategy: TBackoff,
         max_delay_ms: u64,
         connection_limit: Option<usize>,
+        peer_latencies: PeerLatencies,
     ) -> Self {
         {
             // Reconcile the keysets eligible is only used to allow us to dial the remote peer
@@ -212,6 +221,7 @@ where
             max_delay_ms,
             event_id: 0,
             connection_limit,
+            peer_latencies,
             rng: SmallRng::from_entropy(),
         }
     }
@@ -338,7 +348,7 @@ where
         pending_dials: &'a mut FuturesUnordered<BoxFuture<'static, PeerId>>,
     ) {
         let eligible = self.eligible.read().unwrap().clone();
-        let to_connect: Vec<_> = self
+        let mut to_connect: Vec<_> = self
             .peer_addresses
             .0
             .iter()
@@ -363,13 +373,24 @@ where
             to_connect.len()
         };
 
+        // Rank the peers by their round-trip time, lowest first. Peers we have no round-trip time
+        // for come last, shuffled so that we don't always dial the same ones.
+        to_connect.shuffle(&mut self.rng);
+        {
+            let peer_latencies = self.peer_latencies.read().unwrap();
+            to_connect.sort_by_key(|(peer_id, _)| {
+                let latency = peer_latencies.get(*peer_id);
+                (latency.is_none(), latency.cloned())
+            });
+        }
+
         // The initial dial state; it has zero dial delay and uses the first
         // address.
         let init_dial_state = DialState::new(self.backoff_strategy.clone());
 
-        for (p, addrs) in to_connect.choose_multiple(&mut self.rng, to_connect_size) {
+        for (p, addrs) in to_connect.into_iter().take(to_connect_size) {
             let mut connction_reqs_tx = self.connection_reqs_tx.clone();
-            let peer_id = **p;
+            let peer_id = *p;
             let dial_state = self
                 .dial_states
                 .entry(peer_id)
@@ -378,7 +399,7 @@ where
             // Choose the next addr to dial for this peer. Currently, we just
             // round-robin the selection, i.e., try the sequence:
             // addr[0], .., addr[len-1], addr[0], ..
-            let addr = dial_state.next_addr(&addrs).clone();
+            let addr = dial_state.next_addr(addrs).clone();
 
             // Using the DialState's backoff strategy, compute the delay until
             // the next dial attempt for this peer.
@@ -36,18 +36,25 @@ fn setup_conn_mgr(
         .into_iter()
         .map(|peer_id| {
             let identity_public_key = x25519::PrivateKey::generate_for_testing().public_key();
-            (peer_id, identity_public_key)
+            (peer_id, vec![identity_public_key].into_iter().collect())
         })
         .collect::<HashMap<_, _>>();
 
-    setup_conn_mgr_with_context(network_context, rt, eligible_peers, seed_peers)
+    setup_conn_mgr_with_context(
+        network_context,
+        rt,
+        eligible_peers,
+        seed_peers,
+        Arc::new(RwLock::new(HashMap::new())),
+    )
 }
 
 fn setup_conn_mgr_with_context(
     network_context: NetworkContext,
     rt: &mut Runtime,
-    eligible_peers: HashMap<PeerId, PublicKey>,
+    eligible_peers: HashMap<PeerId, HashSet<PublicKey>>,
     seed_peers: HashMap<PeerId, Vec<NetworkAddress>>,
+    peer_latencies: PeerLatencies,
 ) -> (
     libra_channel::Receiver<PeerId, ConnectionRequest>,
     conn_notifs_channel::Sender,
@@ -72,6 +79,7 @@ fn setup_conn_mgr_with_context(
             FixedInterval::from_millis(100),
             300, /* ms */
             Some(MAX_TEST_CONNECTIONS),
+            peer_latencies,
         )
     };
     rt.spawn(conn_mgr.start());
@@ -837,7 +845,7 @@ fn backoff_on_failure() {
             .send(ConnectivityRequest::UpdateEligibleNodes(
                 [(peer_a, peer_a_keys), (peer_b, peer_b_keys)]
                     .iter()
-                    .cloned()
+                    .map(|(peer_id, key)| (*peer_id, vec![*key].into_iter().collect()))
                     .collect(),
             ))
             .await
@@ -1175,10 +1183,10 @@ fn test_public_connection_limit() {
     ::libra_logger::Logger::new().environment_only(true).init();
     let mut rt = Runtime::new().unwrap();
     let mut seed_peers: HashMap<PeerId, Vec<NetworkAddress>> = HashMap::new();
-    let mut eligible_peers: HashMap<PeerId, PublicKey> = HashMap::new();
+    let mut eligible_peers: HashMap<PeerId, HashSet<PublicKey>> = HashMap::new();
     for _ in 0..MAX_TEST_CONNECTIONS + 1 {
         let (peer_id, addr, key) = generate_peer_with_addr();
-        eligible_peers.insert(peer_id, key);
+        eligible_peers.insert(peer_id, vec![key].into_iter().collect());
         seed_peers.insert(peer_id, vec![addr]);
     }
 
@@ -1189,7 +1197,13 @@ fn test_public_connection_limit() {
         PeerId::random(),
     );
     let (mut connection_reqs_rx, mut connection_notifs_tx, mut conn_mgr_reqs_tx, mut ticker_tx) =
-        setup_conn_mgr_with_context(network_context, &mut rt, eligible_peers, seed_peers);
+        setup_conn_mgr_with_context(
+            network_context,
+            &mut rt,
+            eligible_peers,
+            seed_peers,
+            Arc::new(RwLock::new(HashMap::new())),
+        );
 
     // Fake peer manager and discovery.
     let f_peer_mgr = async move {
@@ -1218,3 +1232,48 @@ fn test_public_connection_limit() {
     };
     rt.block_on(f_peer_mgr);
 }
+
+#[test]
+fn dial_lowest_latency_peers_first() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut rt = Runtime::new().unwrap();
+    let mut seed_peers: HashMap<PeerId, Vec<NetworkAddress>> = HashMap::new();
+    let mut eligible_peers: HashMap<PeerId, HashSet<PublicKey>> = HashMap::new();
+    let mut peer_latencies = HashMap::new();
+    // One more peer than we may dial, which has the highest latency.
+    for i in 0..=MAX_TEST_CONNECTIONS {
+        let (peer_id, addr, key) = generate_peer_with_addr();
+        eligible_peers.insert(peer_id, vec![key].into_iter().collect());
+        seed_peers.insert(peer_id, vec![addr]);
+        peer_latencies.insert(peer_id, Duration::from_millis(10 * (i as u64 + 1)));
+    }
+    let slowest_peer = *peer_latencies
+        .iter()
+        .max_by_key(|(_, latency)| **latency)
+        .unwrap()
+        .0;
+
+    let network_context =
+        NetworkContext::new(NetworkId::Validator, RoleType::Validator, PeerId::random());
+    let (mut connection_reqs_rx, _, _, _) = setup_conn_mgr_with_context(
+        network_context,
+        &mut rt,
+        eligible_peers,
+        seed_peers,
+        Arc::new(RwLock::new(peer_latencies)),
+    );
+
+    // Fake peer manager.
+    let f_peer_mgr = async move {
+        for _ in 0..MAX_TEST_CONNECTIONS {
+            match connection_reqs_rx.next().await.unwrap() {
+                ConnectionRequest::DialPeer(peer_id, _, error_tx) => {
+                    assert_ne!(peer_id, slowest_peer);
+                    error_tx.send(Ok(())).unwrap();
+                }
+                _ => panic!("unexpected request to peer manager"),
+            }
+        }
+    };
+    rt.block_on(f_peer_mgr);
+}
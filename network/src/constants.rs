@@ -23,3 +23,7 @@ pub const MAX_CONCURRENT_NETWORK_REQS: usize = 100;
 pub const MAX_CONCURRENT_NETWORK_NOTIFS: usize = 100;
 pub const MAX_CONNECTION_DELAY_MS: u64 = 60_000; /* 1 minute */
 pub const MAX_FULLNODE_CONNECTIONS: usize = 3;
+pub const MAX_INBOUND_CONNECTIONS: usize = 100;
+/// How long an inbound connection from an untrusted peer must have been idle before a new inbound
+/// connection can evict it, once `MAX_INBOUND_CONNECTIONS` is reached.
+pub const INBOUND_CONNECTION_IDLE_EVICTION_MS: u64 = 60_000; /* 1 minute */
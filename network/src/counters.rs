@@ -30,6 +30,17 @@ pub static LIBRA_NETWORK_PEERS: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Inbound connections dropped because of the inbound connection limit, either `evicted` to make
+/// room for a new connection or `rejected` for lack of an evictable connection.
+pub static LIBRA_NETWORK_INBOUND_EVICTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "libra_network_inbound_evictions",
+        "Libra network inbound connections dropped because of the inbound connection limit",
+        &["role_type", "outcome"]
+    )
+    .unwrap()
+});
+
 pub static LIBRA_NETWORK_DISCOVERY_NOTES: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
         // metric name
@@ -69,6 +80,16 @@ pub static LIBRA_NETWORK_RPC_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Round-trip times of the health checker pings, per peer.
+pub static LIBRA_NETWORK_PING_RTT: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "libra_network_ping_rtt_seconds",
+        "Libra network health checker ping round-trip time histogram",
+        &["role_type", "peer_id"]
+    )
+    .unwrap()
+});
+
 pub static LIBRA_NETWORK_DIRECT_SEND_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "libra_network_direct_send_messages",
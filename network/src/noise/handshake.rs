@@ -16,7 +16,7 @@ use libra_crypto::{noise, x25519};
 use libra_types::PeerId;
 use netcore::transport::ConnectionOrigin;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::TryFrom as _,
     io,
     sync::{Arc, RwLock},
@@ -89,7 +89,7 @@ pub enum HandshakeAuthMode {
         // mutual-auth scenarios because we have a bounded set of trusted peers
         // that rarely changes.
         anti_replay_timestamps: RwLock<AntiReplayTimestamps>,
-        trusted_peers: Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>,
+        trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
     },
     /// In `ServerOnly` mode, the dialer authenticates the server. However, the
     /// server does not care who connects to them and will allow inbound connections
@@ -98,7 +98,7 @@ pub enum HandshakeAuthMode {
 }
 
 impl HandshakeAuthMode {
-    pub fn mutual(trusted_peers: Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>) -> Self {
+    pub fn mutual(trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>) -> Self {
         HandshakeAuthMode::Mutual {
             anti_replay_timestamps: RwLock::new(AntiReplayTimestamps::default()),
             trusted_peers,
@@ -115,7 +115,7 @@ impl HandshakeAuthMode {
         }
     }
 
-    fn trusted_peers(&self) -> Option<&RwLock<HashMap<PeerId, x25519::PublicKey>>> {
+    fn trusted_peers(&self) -> Option<&RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>> {
         match &self {
             HandshakeAuthMode::Mutual { trusted_peers, .. } => Some(&trusted_peers),
             HandshakeAuthMode::ServerOnly => None,
@@ -348,13 +348,14 @@ impl NoiseUpgrader {
                 })?
                 .get(&remote_peer_id)
             {
-                Some(key) => {
-                    if key != &remote_public_key {
+                // a peer rotating its network key may authenticate with any of its advertised keys
+                Some(keys) => {
+                    if !keys.contains(&remote_public_key) {
                         return Err(io::Error::new(
                             io::ErrorKind::InvalidData,
                             format!(
-                                "noise: peer id {} connecting to us with an unknown public key: {} (expected: {})",
-                                remote_peer_id, remote_public_key, key,
+                                "noise: peer id {} connecting to us with an unknown public key: {} (expected one of: {:?})",
+                                remote_peer_id, remote_public_key, keys,
                             ),
                         ));
                     }
@@ -475,8 +476,14 @@ mod test {
             let server_peer_id = PeerId::random();
             let trusted_peers = Arc::new(RwLock::new(
                 vec![
-                    (client_peer_id, client_public_key),
-                    (server_peer_id, server_public_key),
+                    (
+                        client_peer_id,
+                        vec![client_public_key].into_iter().collect(),
+                    ),
+                    (
+                        server_peer_id,
+                        vec![server_public_key].into_iter().collect(),
+                    ),
                 ]
                 .into_iter()
                 .collect(),
@@ -613,4 +620,64 @@ mod test {
     fn test_handshake_self_fails_mutual_auth() {
         test_handshake_self_fails(true /* is_mutual_auth */);
     }
+
+    #[test]
+    fn test_handshake_rotated_key_mutual_auth() {
+        let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED);
+        let current_private_key = x25519::PrivateKey::generate(&mut rng);
+        let next_private_key = x25519::PrivateKey::generate(&mut rng);
+        let unknown_private_key = x25519::PrivateKey::generate(&mut rng);
+        let server_private_key = x25519::PrivateKey::generate(&mut rng);
+        let server_public_key = server_private_key.public_key();
+
+        // the client advertises both its current and its next key
+        let client_peer_id = PeerId::random();
+        let server_peer_id = PeerId::random();
+        let trusted_peers = Arc::new(RwLock::new(
+            vec![
+                (
+                    client_peer_id,
+                    vec![
+                        current_private_key.public_key(),
+                        next_private_key.public_key(),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                (
+                    server_peer_id,
+                    vec![server_public_key].into_iter().collect(),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        ));
+        let server = NoiseUpgrader::new(
+            server_peer_id,
+            server_private_key,
+            HandshakeAuthMode::mutual(trusted_peers.clone()),
+        );
+
+        // the server accepts the client authenticating with either advertised key
+        for private_key in vec![current_private_key, next_private_key] {
+            let client = NoiseUpgrader::new(
+                client_peer_id,
+                private_key,
+                HandshakeAuthMode::mutual(trusted_peers.clone()),
+            );
+            let (client_res, server_res) = perform_handshake(&client, &server, server_public_key);
+            client_res.unwrap();
+            server_res.unwrap();
+        }
+
+        // but not with a key it never advertised
+        let client = NoiseUpgrader::new(
+            client_peer_id,
+            unknown_private_key,
+            HandshakeAuthMode::mutual(trusted_peers),
+        );
+        let (client_res, server_res) = perform_handshake(&client, &server, server_public_key);
+        client_res.unwrap_err();
+        server_res.unwrap_err();
+    }
 }
@@ -19,7 +19,7 @@
 //! use libra_crypto::{x25519, ed25519, Uniform, PrivateKey, test_utils::TEST_SEED};
 //! use rand::{rngs::StdRng, SeedableRng};
 //! use libra_types::PeerId;
-//! use std::{collections::HashMap, sync::{Arc, RwLock}};
+//! use std::{collections::{HashMap, HashSet}, iter, sync::{Arc, RwLock}};
 //!
 //! fn example() -> std::io::Result<()> {
 //! // create client and server NoiseUpgrader
@@ -32,11 +32,13 @@
 //! let server_public = server_private.public_key();
 //! let server_peer_id = PeerId::random();
 //!
-//! // create list of trusted peers
+//! // create list of trusted peers, each may have several valid keys while rotating
 //! let mut trusted_peers = Arc::new(RwLock::new(HashMap::new()));
 //! {
-//!     trusted_peers.write().unwrap().insert(client_peer_id, client_public);
-//!     trusted_peers.write().unwrap().insert(server_peer_id, server_public);
+//!     let client_keys: HashSet<_> = iter::once(client_public).collect();
+//!     let server_keys: HashSet<_> = iter::once(server_public).collect();
+//!     trusted_peers.write().unwrap().insert(client_peer_id, client_keys);
+//!     trusted_peers.write().unwrap().insert(server_peer_id, server_keys);
 //! }
 //!
 //! let client_auth = HandshakeAuthMode::mutual(trusted_peers.clone());
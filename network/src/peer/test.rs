@@ -15,7 +15,7 @@ use libra_network_address::NetworkAddress;
 use libra_types::PeerId;
 use memsocket::MemorySocket;
 use netcore::{compat::IoCompat, transport::ConnectionOrigin};
-use std::{mem::ManuallyDrop, str::FromStr, time::Duration};
+use std::{collections::BTreeMap, mem::ManuallyDrop, str::FromStr, time::Duration};
 use tokio::{
     runtime::{Handle, Runtime},
     time::timeout,
@@ -49,6 +49,7 @@ fn build_test_peer(
             origin,
             MessagingProtocolVersion::V1,
             [].iter().into(),
+            BTreeMap::new(),
         ),
         socket: a,
     };
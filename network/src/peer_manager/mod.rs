@@ -10,14 +10,20 @@
 //!  * A main event loop actor which is responsible for handling requests and sending
 //!  notification about new/lost Peers to the rest of the network stack.
 //!  * An actor responsible for dialing and listening for new connections.
+//!
+//! The number of inbound connections is capped. Once the cap is reached, a new inbound connection
+//! evicts the inbound connection of an untrusted peer which has been idle the longest, or is
+//! refused if there is no such connection idle for long enough. Connections from trusted peers
+//! are never evicted, and are accepted even beyond the cap.
 use crate::{
-    counters,
+    constants, counters,
     interface::{NetworkNotification, NetworkProvider, NetworkRequest},
     logging,
     peer::DisconnectReason,
     protocols::{
         direct_send::Message,
         rpc::{error::RpcError, InboundRpcRequest, OutboundRpcRequest},
+        wire::handshake::v1::ApplicationVersion,
     },
     transport,
     transport::{Connection, ConnectionId, ConnectionMetadata},
@@ -33,17 +39,18 @@ use futures::{
     stream::{Fuse, FuturesUnordered, StreamExt},
 };
 use libra_config::network_id::NetworkContext;
+use libra_crypto::x25519;
 use libra_logger::{prelude::*, StructuredLogEntry};
 use libra_network_address::NetworkAddress;
 use libra_types::PeerId;
 use netcore::transport::{ConnectionOrigin, Transport};
 use serde::Serialize;
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     fmt::Debug,
     marker::PhantomData,
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
 };
 use tokio::runtime::Handle;
 
@@ -210,6 +217,35 @@ impl ConnectionRequestSender {
     }
 }
 
+/// The versions of the application protocols negotiated with each connected peer. The
+/// PeerManager updates them as peers connect and disconnect, applications look them up to pick
+/// the message format to use with a peer.
+#[derive(Clone, Debug, Default)]
+pub struct PeerApplicationVersions(
+    Arc<RwLock<HashMap<PeerId, BTreeMap<ProtocolId, ApplicationVersion>>>>,
+);
+
+impl PeerApplicationVersions {
+    /// Returns the version of `protocol` negotiated with `peer`, or None if the peer is not
+    /// connected or has no common version of the protocol.
+    pub fn get(&self, peer: &PeerId, protocol: ProtocolId) -> Option<ApplicationVersion> {
+        self.0
+            .read()
+            .unwrap()
+            .get(peer)
+            .and_then(|versions| versions.get(&protocol))
+            .copied()
+    }
+
+    fn insert(&self, peer: PeerId, versions: BTreeMap<ProtocolId, ApplicationVersion>) {
+        self.0.write().unwrap().insert(peer, versions);
+    }
+
+    fn remove(&self, peer: &PeerId) {
+        self.0.write().unwrap().remove(peer);
+    }
+}
+
 /// Responsible for handling and maintaining connections to other Peers
 pub struct PeerManager<TTransport, TSocket>
 where
@@ -239,6 +275,8 @@ where
         HashMap<ProtocolId, libra_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>>,
     /// Channels to send NewPeer/LostPeer notifications to.
     connection_event_handlers: Vec<conn_notifs_channel::Sender>,
+    /// The application protocol versions negotiated with the active peers.
+    peer_application_versions: PeerApplicationVersions,
     /// Channel used to send Dial requests to the ConnectionHandler actor
     transport_reqs_tx: channel::Sender<TransportRequest>,
     /// Sender for connection events.
@@ -258,6 +296,12 @@ where
     max_concurrent_network_notifs: usize,
     /// Size of channels between different actors.
     channel_size: usize,
+    /// Peers trusted to connect to us, whose inbound connections are never evicted.
+    trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
+    /// The time of the last message exchanged with each active peer.
+    last_activity: HashMap<PeerId, Arc<Mutex<Instant>>>,
+    /// Maximum number of inbound connections, see the module documentation.
+    max_inbound_connections: usize,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -279,9 +323,12 @@ where
             libra_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
         >,
         connection_event_handlers: Vec<conn_notifs_channel::Sender>,
+        peer_application_versions: PeerApplicationVersions,
         channel_size: usize,
         max_concurrent_network_reqs: usize,
         max_concurrent_network_notifs: usize,
+        trusted_peers: Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>,
+        max_inbound_connections: usize,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = channel::new(
             channel_size,
@@ -316,9 +363,13 @@ where
             phantom_transport: PhantomData,
             upstream_handlers,
             connection_event_handlers,
+            peer_application_versions,
             max_concurrent_network_reqs,
             max_concurrent_network_notifs,
             channel_size,
+            trusted_peers,
+            last_activity: HashMap::new(),
+            max_inbound_connections,
         }
     }
 
@@ -406,6 +457,8 @@ where
                     if conn_metadata.connection_id() == lost_conn_metadata.connection_id() {
                         // We lost an active connection.
                         entry.remove();
+                        self.peer_application_versions.remove(&peer_id);
+                        self.last_activity.remove(&peer_id);
                     }
                 }
                 counters::LIBRA_NETWORK_PEERS
@@ -468,6 +521,8 @@ where
                 // Send a CloseConnection request to NetworkProvider and drop the send end of the
                 // NetworkRequest channel.
                 if let Some((conn_metadata, sender)) = self.active_peers.remove(&peer_id) {
+                    self.peer_application_versions.remove(&peer_id);
+                    self.last_activity.remove(&peer_id);
                     // This should trigger a disconnect.
                     drop(sender);
                     // Add to outstanding disconnect requests.
@@ -494,6 +549,7 @@ where
         trace!("{} PeerManagerRequest::{:?}", self.network_context, request);
         match request {
             PeerManagerRequest::SendMessage(peer_id, msg) => {
+                self.record_activity(&peer_id);
                 if let Some((_, sender)) = self.active_peers.get_mut(&peer_id) {
                     if let Err(err) = sender.push(msg.protocol, NetworkRequest::SendMessage(msg)) {
                         info!(
@@ -511,6 +567,7 @@ where
                 }
             }
             PeerManagerRequest::SendRpc(peer_id, req) => {
+                self.record_activity(&peer_id);
                 if let Some((_, sender)) = self.active_peers.get_mut(&peer_id) {
                     if let Err(err) = sender.push(req.protocol, NetworkRequest::SendRpc(req)) {
                         info!(
@@ -593,29 +650,26 @@ where
                     self.network_context,
                     peer_id.short_str()
                 );
-                let network_context = self.network_context.clone();
                 // Drop the new connection and keep the one already stored in active_peers
-                let drop_fut = async move {
-                    let mut connection = connection;
-                    if let Err(e) = tokio::time::timeout(
-                        transport::TRANSPORT_TIMEOUT,
-                        connection.socket.close(),
-                    )
-                    .await
-                    {
-                        error!(
-                            "{} Closing connection with Peer {} failed with error: {}",
-                            network_context,
-                            peer_id.short_str(),
-                            e
-                        );
-                    };
-                };
-                self.executor.spawn(drop_fut);
+                self.close_connection(connection);
                 return;
             }
         }
 
+        // Enforce the inbound connection limit on connections from new peers
+        if send_new_peer_notification
+            && conn_meta.origin() == ConnectionOrigin::Inbound
+            && !self.make_room_for_inbound_connection(peer_id)
+        {
+            info!(
+                "{} Closing incoming connection with Peer {}: inbound connection limit reached",
+                self.network_context,
+                peer_id.short_str()
+            );
+            self.close_connection(connection);
+            return;
+        }
+
         // Initialize a new network stack for this connection.
         let (network_reqs_tx, network_notifs_rx) = NetworkProvider::start(
             self.executor.clone(),
@@ -627,7 +681,11 @@ where
         );
         // Start background task to handle events (RPCs and DirectSend messages) received from
         // peer.
-        self.spawn_peer_network_events_handler(peer_id, network_notifs_rx);
+        let last_activity = Arc::new(Mutex::new(Instant::now()));
+        self.last_activity.insert(peer_id, last_activity.clone());
+        self.spawn_peer_network_events_handler(peer_id, network_notifs_rx, last_activity);
+        self.peer_application_versions
+            .insert(peer_id, conn_meta.application_versions().clone());
         // Save NetworkRequest sender to `active_peers`.
         self.active_peers
             .insert(peer_id, (conn_meta.clone(), network_reqs_tx));
@@ -648,6 +706,99 @@ where
         }
     }
 
+    /// Closes a connection which is not kept in `active_peers`.
+    fn close_connection(&self, connection: Connection<TSocket>) {
+        let network_context = self.network_context.clone();
+        let peer_id = connection.metadata.peer_id();
+        let drop_fut = async move {
+            let mut connection = connection;
+            if let Err(e) =
+                tokio::time::timeout(transport::TRANSPORT_TIMEOUT, connection.socket.close()).await
+            {
+                error!(
+                    "{} Closing connection with Peer {} failed with error: {}",
+                    network_context,
+                    peer_id.short_str(),
+                    e
+                );
+            };
+        };
+        self.executor.spawn(drop_fut);
+    }
+
+    /// Makes room for a new inbound connection from `peer_id` once the inbound connection limit
+    /// is reached, by evicting the inbound connection of an untrusted peer which has been idle
+    /// the longest. A trusted peer may evict any such connection, an untrusted peer only one idle
+    /// for at least `INBOUND_CONNECTION_IDLE_EVICTION_MS`.
+    ///
+    /// Returns `false` if the new connection should be refused.
+    fn make_room_for_inbound_connection(&mut self, peer_id: PeerId) -> bool {
+        let num_inbound = self
+            .active_peers
+            .values()
+            .filter(|(conn_metadata, _)| conn_metadata.origin() == ConnectionOrigin::Inbound)
+            .count();
+        if num_inbound < self.max_inbound_connections {
+            return true;
+        }
+
+        let (is_trusted, idlest) = {
+            let trusted_peers = self.trusted_peers.read().unwrap();
+            let last_activity = &self.last_activity;
+            let idlest = self
+                .active_peers
+                .iter()
+                .filter(|(peer_id, (conn_metadata, _))| {
+                    conn_metadata.origin() == ConnectionOrigin::Inbound
+                        && !trusted_peers.contains_key(*peer_id)
+                })
+                .filter_map(|(peer_id, _)| {
+                    let idle = last_activity.get(peer_id)?.lock().unwrap().elapsed();
+                    Some((*peer_id, idle))
+                })
+                .max_by_key(|(_, idle)| *idle);
+            (trusted_peers.contains_key(&peer_id), idlest)
+        };
+        let role = self.network_context.role().as_str();
+        let idle_eviction = Duration::from_millis(constants::INBOUND_CONNECTION_IDLE_EVICTION_MS);
+        match idlest {
+            Some((evicted_peer_id, idle)) if is_trusted || idle >= idle_eviction => {
+                info!(
+                    "{} Evicting connection with Peer {}, idle for {:?}, to make room for Peer {}",
+                    self.network_context,
+                    evicted_peer_id.short_str(),
+                    idle,
+                    peer_id.short_str()
+                );
+                // Dropping the sender closes the connection, and the LostPeer notification is
+                // sent once it is closed.
+                if let Some((_, sender)) = self.active_peers.remove(&evicted_peer_id) {
+                    self.peer_application_versions.remove(&evicted_peer_id);
+                    self.last_activity.remove(&evicted_peer_id);
+                    drop(sender);
+                }
+                counters::LIBRA_NETWORK_INBOUND_EVICTIONS
+                    .with_label_values(&[role, "evicted"])
+                    .inc();
+                true
+            }
+            // Trusted peers are accepted beyond the limit rather than refused.
+            _ if is_trusted => true,
+            _ => {
+                counters::LIBRA_NETWORK_INBOUND_EVICTIONS
+                    .with_label_values(&[role, "rejected"])
+                    .inc();
+                false
+            }
+        }
+    }
+
+    fn record_activity(&self, peer_id: &PeerId) {
+        if let Some(last_activity) = self.last_activity.get(peer_id) {
+            *last_activity.lock().unwrap() = Instant::now();
+        }
+    }
+
     fn send_lostpeer_notification(
         &mut self,
         peer_id: PeerId,
@@ -684,12 +835,14 @@ where
         &self,
         peer_id: PeerId,
         network_events: libra_channel::Receiver<ProtocolId, NetworkNotification>,
+        last_activity: Arc<Mutex<Instant>>,
     ) {
         let mut upstream_handlers = self.upstream_handlers.clone();
         let network_context = self.network_context.clone();
         self.executor.spawn(network_events.for_each_concurrent(
             self.max_concurrent_network_reqs,
             move |inbound_event| {
+                *last_activity.lock().unwrap() = Instant::now();
                 Self::handle_inbound_event(
                     network_context.clone(),
                     inbound_event,
@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    constants,
     peer::DisconnectReason,
     peer_manager::{
         conn_notifs_channel, error::PeerManagerError, ConnectionNotification, ConnectionRequest,
-        PeerManager, PeerManagerNotification, PeerManagerRequest, TransportNotification,
+        PeerApplicationVersions, PeerManager, PeerManagerNotification, PeerManagerRequest,
+        TransportNotification,
     },
     protocols::wire::{
         handshake::v1::MessagingProtocolVersion,
@@ -21,6 +23,7 @@ use libra_config::{
     config::RoleType,
     network_id::{NetworkContext, NetworkId},
 };
+use libra_crypto::{x25519, Uniform};
 use libra_network_address::NetworkAddress;
 use libra_types::PeerId;
 use memsocket::MemorySocket;
@@ -28,7 +31,12 @@ use netcore::{
     compat::IoCompat,
     transport::{boxed::BoxedTransport, memory::MemoryTransport, ConnectionOrigin, TransportExt},
 };
-use std::{collections::HashMap, iter::FromIterator, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    iter::FromIterator,
+    num::NonZeroUsize,
+    sync::{Arc, RwLock},
+};
 use tokio::runtime::Handle;
 use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
@@ -52,6 +60,7 @@ pub fn build_test_transport(
                     origin,
                     MessagingProtocolVersion::V1,
                     [TEST_PROTOCOL].iter().into(),
+                    BTreeMap::new(),
                 ),
             })
         })
@@ -83,6 +92,29 @@ fn build_test_peer_manager(
     libra_channel::Sender<PeerId, ConnectionRequest>,
     libra_channel::Receiver<(PeerId, ProtocolId), PeerManagerNotification>,
     conn_notifs_channel::Receiver,
+) {
+    build_test_peer_manager_with_limits(
+        executor,
+        peer_id,
+        HashMap::new(),
+        constants::MAX_INBOUND_CONNECTIONS,
+    )
+}
+
+fn build_test_peer_manager_with_limits(
+    executor: Handle,
+    peer_id: PeerId,
+    trusted_peers: HashMap<PeerId, HashSet<x25519::PublicKey>>,
+    max_inbound_connections: usize,
+) -> (
+    PeerManager<
+        BoxedTransport<Connection<MemorySocket>, impl std::error::Error + Sync + Send + 'static>,
+        MemorySocket,
+    >,
+    libra_channel::Sender<(PeerId, ProtocolId), PeerManagerRequest>,
+    libra_channel::Sender<PeerId, ConnectionRequest>,
+    libra_channel::Receiver<(PeerId, ProtocolId), PeerManagerNotification>,
+    conn_notifs_channel::Receiver,
 ) {
     let (peer_manager_request_tx, peer_manager_request_rx) =
         libra_channel::new(QueueStyle::FIFO, NonZeroUsize::new(1).unwrap(), None);
@@ -105,9 +137,12 @@ fn build_test_peer_manager(
         connection_reqs_rx,
         HashMap::from_iter([(TEST_PROTOCOL, hello_tx)].iter().cloned()),
         vec![conn_status_tx],
+        PeerApplicationVersions::default(),
         1024, /* max concurrent network requests */
         1024, /* max concurrent network notifications */
         1024, /* channel size */
+        Arc::new(RwLock::new(trusted_peers)),
+        max_inbound_connections,
     );
 
     (
@@ -226,6 +261,7 @@ fn create_connection<TSocket: transport::TSocket>(
             origin,
             MessagingProtocolVersion::V1,
             [TEST_PROTOCOL].iter().into(),
+            BTreeMap::new(),
         ),
     }
 }
@@ -550,6 +586,7 @@ fn peer_manager_simultaneous_dial_disconnect_event() {
                 ConnectionOrigin::Inbound,
                 MessagingProtocolVersion::V1,
                 [TEST_PROTOCOL].iter().into(),
+                BTreeMap::new(),
             ),
             DisconnectReason::ConnectionLost,
         );
@@ -607,6 +644,7 @@ fn test_dial_disconnect() {
                 ConnectionOrigin::Outbound,
                 MessagingProtocolVersion::V1,
                 [TEST_PROTOCOL].iter().into(),
+                BTreeMap::new(),
             ),
             DisconnectReason::Requested,
         );
@@ -625,3 +663,87 @@ fn test_dial_disconnect() {
 
     runtime.block_on(test);
 }
+
+#[test]
+fn peer_manager_inbound_limit_refuses_untrusted_peer() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut runtime = ::tokio::runtime::Runtime::new().unwrap();
+
+    let ids = ordered_peer_ids(3);
+    let (mut peer_manager, _request_tx, _connection_reqs_tx, _hello_rx, _conn_status_rx) =
+        build_test_peer_manager_with_limits(runtime.handle().clone(), ids[0], HashMap::new(), 1);
+
+    let test = async move {
+        let (_outbound1, inbound1) = build_test_connection();
+        peer_manager.add_peer(create_connection(
+            inbound1,
+            ids[1],
+            NetworkAddress::mock(),
+            ConnectionOrigin::Inbound,
+            ConnectionId::from(0),
+        ));
+
+        // The limit is reached and the existing connection isn't idle for long enough to be
+        // evicted, so the new connection is refused.
+        let (_outbound2, inbound2) = build_test_connection();
+        peer_manager.add_peer(create_connection(
+            inbound2,
+            ids[2],
+            NetworkAddress::mock(),
+            ConnectionOrigin::Inbound,
+            ConnectionId::from(1),
+        ));
+
+        assert!(peer_manager.active_peers.contains_key(&ids[1]));
+        assert!(!peer_manager.active_peers.contains_key(&ids[2]));
+    };
+
+    runtime.block_on(test);
+}
+
+#[test]
+fn peer_manager_inbound_limit_trusted_peer_evicts_untrusted_peer() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut runtime = ::tokio::runtime::Runtime::new().unwrap();
+
+    let ids = ordered_peer_ids(3);
+    let trusted_key = x25519::PrivateKey::generate_for_testing().public_key();
+    let trusted_peers = vec![(ids[2], vec![trusted_key].into_iter().collect())]
+        .into_iter()
+        .collect();
+    let (mut peer_manager, _request_tx, _connection_reqs_tx, _hello_rx, _conn_status_rx) =
+        build_test_peer_manager_with_limits(runtime.handle().clone(), ids[0], trusted_peers, 1);
+
+    let test = async move {
+        let (_outbound1, inbound1) = build_test_connection();
+        peer_manager.add_peer(create_connection(
+            inbound1,
+            ids[1],
+            NetworkAddress::mock(),
+            ConnectionOrigin::Inbound,
+            ConnectionId::from(0),
+        ));
+
+        // The trusted peer evicts the untrusted one.
+        let (_outbound2, inbound2) = build_test_connection();
+        peer_manager.add_peer(create_connection(
+            inbound2,
+            ids[2],
+            NetworkAddress::mock(),
+            ConnectionOrigin::Inbound,
+            ConnectionId::from(1),
+        ));
+
+        assert!(!peer_manager.active_peers.contains_key(&ids[1]));
+        assert!(peer_manager.active_peers.contains_key(&ids[2]));
+        assert_peer_disconnected_event(
+            ids[1],
+            ConnectionOrigin::Inbound,
+            DisconnectReason::Requested,
+            &mut peer_manager,
+        )
+        .await;
+    };
+
+    runtime.block_on(test);
+}
@@ -11,6 +11,14 @@
 //! disconnect from the peer. It relies on ConnectivityManager or the remote peer to re-establish
 //! the connection.
 //!
+//! The HealthChecker adapts how often it pings a peer to the stability of the connection: a peer
+//! which keeps answering is pinged less and less often, down to once every
+//! `MAX_PING_INTERVAL_ROUNDS` rounds, while a single failure brings it back to every round.
+//!
+//! The round-trip time of each successful ping is exported as a metric, and folded into a smoothed
+//! RTT per peer which is shared with the ConnectivityManager through [`PeerLatencies`], so that it
+//! can prefer peers with lower latency when choosing which peers to dial.
+//!
 //! Future Work
 //! -----------
 //! We can make a few other improvements to the health checker. These are:
@@ -41,11 +49,26 @@ use libra_security_logger::{security_log, SecurityEvent};
 use libra_types::PeerId;
 use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    cmp::min,
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 #[cfg(test)]
 mod test;
 
+/// Number of successive successful pings after which the interval between the pings of a peer
+/// grows by one round.
+const STABLE_PINGS_PER_INTERVAL_STEP: u64 = 8;
+/// The maximum number of rounds between two pings of a stable peer.
+const MAX_PING_INTERVAL_ROUNDS: u64 = 4;
+
+/// The smoothed round-trip time of the pings of each peer, kept across disconnections. It is
+/// written by the HealthChecker and read by the ConnectivityManager to rank the peers to dial.
+pub type PeerLatencies = Arc<RwLock<HashMap<PeerId, Duration>>>;
+
 /// The interface from Network to HealthChecker layer.
 ///
 /// `HealthCheckerNetworkEvents` is a `Stream` of `PeerManagerNotification` where the
@@ -130,6 +153,43 @@ pub struct Ping(u32);
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Pong(u32);
 
+/// The liveness of a connected peer, as observed through its pings.
+#[derive(Debug)]
+struct PeerHealth {
+    /// Round of the last successful ping.
+    last_success_round: u64,
+    /// Number of ping failures since the last successful ping.
+    failures: u64,
+    /// Number of successive successful pings, used to space out the pings of stable peers.
+    successes: u64,
+    /// Round of the last ping sent to the peer.
+    last_ping_round: u64,
+}
+
+impl PeerHealth {
+    fn new(round: u64) -> Self {
+        Self {
+            last_success_round: round,
+            failures: 0,
+            successes: 0,
+            last_ping_round: 0,
+        }
+    }
+
+    /// The number of rounds to wait between two pings of the peer.
+    fn ping_interval(&self) -> u64 {
+        min(
+            1 + self.successes / STABLE_PINGS_PER_INTERVAL_STEP,
+            MAX_PING_INTERVAL_ROUNDS,
+        )
+    }
+
+    /// Whether the peer should be pinged in `round`.
+    fn is_due(&self, round: u64) -> bool {
+        round >= self.last_ping_round + self.ping_interval()
+    }
+}
+
 /// The actor performing health checks by running the Ping protocol
 pub struct HealthChecker<TTicker> {
     network_context: Arc<NetworkContext>,
@@ -140,9 +200,10 @@ pub struct HealthChecker<TTicker> {
     network_tx: HealthCheckerNetworkSender,
     /// Channel to receive notifications from Network layer about new/lost connections.
     network_rx: HealthCheckerNetworkEvents,
-    /// Map from connected peer to last round of successful ping, and number of failures since
-    /// then.
-    connected: HashMap<PeerId, (u64, u64)>,
+    /// Map from connected peer to the health of its connection.
+    connected: HashMap<PeerId, PeerHealth>,
+    /// Smoothed round-trip time of the pings of each peer, shared with the ConnectivityManager.
+    peer_latencies: PeerLatencies,
     /// Random-number generator.
     rng: SmallRng,
     /// Ping timmeout duration.
@@ -167,6 +228,7 @@ where
         network_rx: HealthCheckerNetworkEvents,
         ping_timeout: Duration,
         ping_failures_tolerated: u64,
+        peer_latencies: PeerLatencies,
     ) -> Self {
         HealthChecker {
             network_context,
@@ -174,6 +236,7 @@ where
             network_tx,
             network_rx,
             connected: HashMap::new(),
+            peer_latencies,
             rng: SmallRng::from_entropy(),
             ping_timeout,
             ping_failures_tolerated,
@@ -188,7 +251,7 @@ where
                 event = self.network_rx.select_next_some() => {
                     match event {
                         Ok(Event::NewPeer(peer_id)) => {
-                            self.connected.insert(peer_id, (self.round, 0));
+                            self.connected.insert(peer_id, PeerHealth::new(self.round));
                         },
                         Ok(Event::LostPeer(peer_id)) => {
                             self.connected.remove(&peer_id);
@@ -221,11 +284,14 @@ where
                 _ = self.ticker.select_next_some() => {
                     self.round += 1;
                     debug!("{} Tick: Round number: {}", self.network_context, self.round);
-                    match self.sample_random_peer() {
+                    match self.sample_due_peer() {
                         Some(peer_id) => {
                             debug!("{} Will ping: {}", self.network_context, peer_id.short_str());
 
                             let nonce = self.sample_nonce();
+                            if let Some(health) = self.connected.get_mut(&peer_id) {
+                                health.last_ping_round = self.round;
+                            }
 
                             tick_handlers.push(
                                 Self::ping_peer(
@@ -237,13 +303,13 @@ where
                                     self.ping_timeout.clone()));
                         }
                         None => {
-                            debug!("{} No connected peer to ping", self.network_context);
+                            debug!("{} No connected peer due for a ping", self.network_context);
                         }
                     }
                 }
                 res = tick_handlers.select_next_some() => {
-                    let (peer_id, round, nonce, ping_result) = res;
-                    self.handle_ping_response(peer_id, round, nonce, ping_result).await;
+                    let (peer_id, round, nonce, ping_result, rtt) = res;
+                    self.handle_ping_response(peer_id, round, nonce, ping_result, rtt).await;
                 }
                 complete => {
                     break;
@@ -284,6 +350,7 @@ where
         round: u64,
         req_nonce: u32,
         ping_result: Result<Pong, RpcError>,
+        rtt: Duration,
     ) {
         debug!(
             "{} Got result for ping round: {}",
@@ -293,19 +360,20 @@ where
             Ok(pong) => {
                 if pong.0 == req_nonce {
                     debug!(
-                        "{} Ping successful for peer: {}",
+                        "{} Ping successful for peer: {}, rtt: {:?}",
                         self.network_context,
-                        peer_id.short_str()
+                        peer_id.short_str(),
+                        rtt
                     );
+                    self.record_rtt(peer_id, rtt);
                     // Update last successful ping to current round.
-                    self.connected
-                        .entry(peer_id)
-                        .and_modify(|(ref mut r, ref mut count)| {
-                            if round > *r {
-                                *r = round;
-                                *count = 0;
-                            }
-                        });
+                    self.connected.entry(peer_id).and_modify(|health| {
+                        health.successes += 1;
+                        if round > health.last_success_round {
+                            health.last_success_round = round;
+                            health.failures = 0;
+                        }
+                    });
                 } else {
                     security_log(SecurityEvent::InvalidHealthCheckerMsg)
                         .error("Pong nonce doesn't match our challenge Ping nonce")
@@ -328,17 +396,19 @@ where
                         // If we are no longer connected to the peer, we ignore ping
                         // failure.
                     }
-                    Some((ref mut prev, ref mut failures)) => {
+                    Some(health) => {
                         // If this is the result of an older ping, we ignore it.
-                        if *prev > round {
+                        if health.last_success_round > round {
                             return;
                         }
+                        // The connection is unstable, so ping the peer every round again.
+                        health.successes = 0;
                         // Increment num of failures. If the ping failures are now more than
                         // `self.ping_failures_tolerated`, we disconnect from the node.
                         // The HealthChecker only performs the disconnect. It relies on
                         // ConnectivityManager or the remote peer to re-establish the connection.
-                        *failures += 1;
-                        if *failures > self.ping_failures_tolerated {
+                        health.failures += 1;
+                        if health.failures > self.ping_failures_tolerated {
                             info!(
                                 "{} Disconnecting from peer: {}",
                                 self.network_context,
@@ -366,13 +436,14 @@ where
         round: u64,
         nonce: u32,
         ping_timeout: Duration,
-    ) -> (PeerId, u64, u32, Result<Pong, RpcError>) {
+    ) -> (PeerId, u64, u32, Result<Pong, RpcError>, Duration) {
         debug!(
             "{} Sending Ping request to peer: {} with nonce: {}",
             network_context,
             peer_id.short_str(),
             nonce
         );
+        let start = Instant::now();
         let res_pong_msg = network_tx
             .send_rpc(peer_id, HealthCheckerMsg::Ping(Ping(nonce)), ping_timeout)
             .await
@@ -380,11 +451,29 @@ where
                 HealthCheckerMsg::Pong(res) => Ok(res),
                 _ => Err(RpcError::InvalidRpcResponse),
             });
-        (peer_id, round, nonce, res_pong_msg)
+        (peer_id, round, nonce, res_pong_msg, start.elapsed())
+    }
+
+    /// Exports the round-trip time of a successful ping of `peer_id`, and folds it into the
+    /// smoothed RTT of the peer, weighting the new sample by 1/8 as TCP does.
+    fn record_rtt(&mut self, peer_id: PeerId, rtt: Duration) {
+        counters::LIBRA_NETWORK_PING_RTT
+            .with_label_values(&[self.network_context.role().as_str(), &peer_id.short_str()])
+            .observe(rtt.as_secs_f64());
+        let mut peer_latencies = self.peer_latencies.write().unwrap();
+        let srtt = peer_latencies.entry(peer_id).or_insert(rtt);
+        *srtt = (*srtt * 7 + rtt) / 8;
     }
 
-    fn sample_random_peer(&mut self) -> Option<PeerId> {
-        let peers: Vec<_> = self.connected.keys().cloned().collect();
+    /// Samples a random peer among the connected peers due for a ping in this round.
+    fn sample_due_peer(&mut self) -> Option<PeerId> {
+        let round = self.round;
+        let peers: Vec<_> = self
+            .connected
+            .iter()
+            .filter(|(_, health)| health.is_due(round))
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
         peers.choose(&mut self.rng).cloned()
     }
 
@@ -30,6 +30,24 @@ fn setup_permissive_health_checker(
     libra_channel::Receiver<PeerId, ConnectionRequest>,
     conn_notifs_channel::Sender,
     channel::Sender<()>,
+) {
+    setup_health_checker(
+        rt,
+        ping_failures_tolerated,
+        Arc::new(RwLock::new(HashMap::new())),
+    )
+}
+
+fn setup_health_checker(
+    rt: &mut Runtime,
+    ping_failures_tolerated: u64,
+    peer_latencies: PeerLatencies,
+) -> (
+    libra_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+    libra_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
+    libra_channel::Receiver<PeerId, ConnectionRequest>,
+    conn_notifs_channel::Sender,
+    channel::Sender<()>,
 ) {
     let (ticker_tx, ticker_rx) = channel::new_test(0);
 
@@ -55,6 +73,7 @@ fn setup_permissive_health_checker(
         hc_network_rx,
         PING_TIMEOUT,
         ping_failures_tolerated,
+        peer_latencies,
     );
     rt.spawn(health_checker.start());
     (
@@ -365,3 +384,50 @@ fn ping_timeout() {
     };
     rt.block_on(events_f);
 }
+
+#[test]
+fn ping_success_records_rtt() {
+    ::libra_logger::Logger::new().environment_only(true).init();
+    let mut rt = Runtime::new().unwrap();
+    let peer_latencies: PeerLatencies = Arc::new(RwLock::new(HashMap::new()));
+    let (mut network_reqs_rx, _, _, mut connection_notifs_tx, mut ticker_tx) =
+        setup_health_checker(&mut rt, 0, peer_latencies.clone());
+
+    let peer_id = PeerId::random();
+    let events_f = async move {
+        // Notify HealthChecker of new connected node.
+        send_new_peer_notification(peer_id, &mut connection_notifs_tx).await;
+
+        // Trigger ping to the peer, which succeeds.
+        ticker_tx.send(()).await.unwrap();
+        expect_ping_send_ok(&mut network_reqs_rx).await;
+    };
+    rt.block_on(events_f);
+
+    // The pong is handled asynchronously, wait for its rtt to be recorded.
+    for _ in 0..50 {
+        if peer_latencies.read().unwrap().contains_key(&peer_id) {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    panic!("No rtt recorded for the peer");
+}
+
+#[test]
+fn stable_peers_are_pinged_less_often() {
+    let mut health = PeerHealth::new(0);
+    assert!(health.is_due(1));
+    assert_eq!(health.ping_interval(), 1);
+
+    // A peer is pinged less often as it keeps answering pings.
+    health.successes = STABLE_PINGS_PER_INTERVAL_STEP;
+    health.last_ping_round = 10;
+    assert_eq!(health.ping_interval(), 2);
+    assert!(!health.is_due(11));
+    assert!(health.is_due(12));
+
+    // The interval between pings is bounded.
+    health.successes = u64::MAX;
+    assert_eq!(health.ping_interval(), MAX_PING_INTERVAL_ROUNDS);
+}
@@ -9,10 +9,21 @@
 //! supported messaging protocol versions to a bit vector representing application protocols
 //! supported over that messaging protocol. On receipt, both ends will determine the highest
 //! intersecting messaging protocol version and use that for the remainder of the session.
+//!
+//! The handshake message also lists the versions of the message format of each application
+//! protocol the node supports. Both ends settle on the highest version they have in common, and
+//! drop the application protocols they have no common version of, so that the format of a
+//! protocol can be upgraded in stages across the network: nodes first learn the new version while
+//! still speaking the old one to peers which do not support it.
 
 use libra_config::{chain_id::ChainId, network_id::NetworkId};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, convert::TryInto, fmt, iter::Iterator};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    fmt,
+    iter::Iterator,
+};
 
 #[cfg(test)]
 mod test;
@@ -20,7 +31,7 @@ mod test;
 /// Unique identifier associated with each application protocol.
 /// New application protocols can be added without bumping up the MessagingProtocolVersion.
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
 pub enum ProtocolId {
     ConsensusRpc = 0,
     ConsensusDirectSend = 1,
@@ -57,6 +68,14 @@ impl fmt::Display for ProtocolId {
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct SupportedProtocols(bitvec::BitVec);
 
+/// The version of the message format of an application protocol.
+pub type ApplicationVersion = u8;
+
+/// The message format versions of each application protocol a node supports. A protocol without
+/// advertised versions only supports version 0, the format it had before versions were negotiated.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ApplicationVersions(BTreeMap<ProtocolId, BTreeSet<ApplicationVersion>>);
+
 /// The HandshakeMsg contains a mapping from MessagingProtocolVersion suppported by the node to a
 /// bit-vector specifying application-level protocols supported over that version.
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -64,6 +83,7 @@ pub struct HandshakeMsg {
     pub supported_protocols: BTreeMap<MessagingProtocolVersion, SupportedProtocols>,
     pub chain_id: ChainId,
     pub network_id: NetworkId,
+    pub application_versions: ApplicationVersions,
 }
 
 /// Enum representing different versions of the Libra network protocol. These should be listed from
@@ -106,12 +126,56 @@ impl SupportedProtocols {
     }
 }
 
+impl ApplicationVersions {
+    /// Adds `versions` to the supported versions of `protocol`
+    pub fn add<T: IntoIterator<Item = ApplicationVersion>>(
+        &mut self,
+        protocol: ProtocolId,
+        versions: T,
+    ) {
+        self.0.entry(protocol).or_default().extend(versions);
+    }
+
+    /// Adds the supported versions of `other`
+    pub fn extend(&mut self, other: ApplicationVersions) {
+        for (protocol, versions) in other.0 {
+            self.add(protocol, versions);
+        }
+    }
+
+    fn supported(&self, protocol: ProtocolId) -> BTreeSet<ApplicationVersion> {
+        match self.0.get(&protocol) {
+            Some(versions) => versions.clone(),
+            None => std::iter::once(0).collect(),
+        }
+    }
+
+    /// Returns the highest version of each of `protocols` supported by both nodes. Protocols
+    /// without a common version are left out.
+    pub fn negotiate(
+        &self,
+        other: &ApplicationVersions,
+        protocols: &[ProtocolId],
+    ) -> BTreeMap<ProtocolId, ApplicationVersion> {
+        protocols
+            .iter()
+            .filter_map(|protocol| {
+                self.supported(*protocol)
+                    .intersection(&other.supported(*protocol))
+                    .max()
+                    .map(|version| (*protocol, *version))
+            })
+            .collect()
+    }
+}
+
 impl HandshakeMsg {
     pub fn new(chain_id: ChainId, network_id: NetworkId) -> Self {
         Self {
             supported_protocols: Default::default(),
             network_id,
             chain_id,
+            application_versions: Default::default(),
         }
     }
 
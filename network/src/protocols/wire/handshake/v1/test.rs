@@ -80,6 +80,7 @@ fn common_protocols() {
         network_id: network_id.clone(),
         supported_protocols: BTreeMap::default(),
         chain_id: chain_id.clone(),
+        application_versions: ApplicationVersions::default(),
     };
     assert_eq!(None, h1.find_common_protocols(&h2));
 
@@ -91,3 +92,33 @@ fn common_protocols() {
         h1.find_common_protocols(&h2)
     );
 }
+
+#[test]
+fn negotiate_application_versions() {
+    let protocols = [
+        ProtocolId::ConsensusRpc,
+        ProtocolId::MempoolDirectSend,
+        ProtocolId::StateSynchronizerDirectSend,
+        ProtocolId::HealthCheckerRpc,
+    ];
+    let mut v1 = ApplicationVersions::default();
+    v1.add(ProtocolId::ConsensusRpc, vec![0, 1, 2]);
+    v1.add(ProtocolId::MempoolDirectSend, vec![1]);
+    v1.add(ProtocolId::StateSynchronizerDirectSend, vec![0, 1]);
+    let mut v2 = ApplicationVersions::default();
+    v2.add(ProtocolId::ConsensusRpc, vec![1, 2, 3]);
+    v2.add(ProtocolId::MempoolDirectSend, vec![0]);
+
+    let negotiated = v1.negotiate(&v2, &protocols);
+    // The highest common version is picked
+    assert_eq!(negotiated.get(&ProtocolId::ConsensusRpc), Some(&2));
+    // Protocols without a common version are dropped
+    assert_eq!(negotiated.get(&ProtocolId::MempoolDirectSend), None);
+    // Protocols without advertised versions only support version 0
+    assert_eq!(
+        negotiated.get(&ProtocolId::StateSynchronizerDirectSend),
+        Some(&0)
+    );
+    assert_eq!(negotiated.get(&ProtocolId::HealthCheckerRpc), Some(&0));
+    assert_eq!(negotiated, v2.negotiate(&v1, &protocols));
+}
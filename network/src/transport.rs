@@ -5,8 +5,12 @@ use crate::{
     noise::{stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader},
     protocols::{
         identity::exchange_handshake,
-        wire::handshake::v1::{HandshakeMsg, MessagingProtocolVersion, SupportedProtocols},
+        wire::handshake::v1::{
+            ApplicationVersion, ApplicationVersions, HandshakeMsg, MessagingProtocolVersion,
+            SupportedProtocols,
+        },
     },
+    ProtocolId,
 };
 use futures::{
     future::{Future, FutureExt},
@@ -21,8 +25,8 @@ use libra_types::PeerId;
 use netcore::transport::{tcp, ConnectionOrigin, Transport};
 use serde::Serialize;
 use std::{
-    collections::HashMap,
-    convert::TryFrom,
+    collections::{BTreeMap, HashMap, HashSet},
+    convert::{TryFrom, TryInto},
     fmt::Debug,
     io,
     pin::Pin,
@@ -97,6 +101,7 @@ pub struct ConnectionMetadata {
     origin: ConnectionOrigin,
     messaging_protocol: MessagingProtocolVersion,
     application_protocols: SupportedProtocols,
+    application_versions: BTreeMap<ProtocolId, ApplicationVersion>,
 }
 
 impl ConnectionMetadata {
@@ -107,6 +112,7 @@ impl ConnectionMetadata {
         origin: ConnectionOrigin,
         messaging_protocol: MessagingProtocolVersion,
         application_protocols: SupportedProtocols,
+        application_versions: BTreeMap<ProtocolId, ApplicationVersion>,
     ) -> ConnectionMetadata {
         ConnectionMetadata {
             peer_id,
@@ -115,6 +121,7 @@ impl ConnectionMetadata {
             origin,
             messaging_protocol,
             application_protocols,
+            application_versions,
         }
     }
 
@@ -133,6 +140,11 @@ impl ConnectionMetadata {
     pub fn origin(&self) -> ConnectionOrigin {
         self.origin
     }
+
+    /// The versions of the application protocols negotiated with the peer
+    pub fn application_versions(&self) -> &BTreeMap<ProtocolId, ApplicationVersion> {
+        &self.application_versions
+    }
 }
 
 /// The `Connection` struct consists of connection metadata and the actual socket for
@@ -172,17 +184,32 @@ pub async fn perform_handshake<T: TSocket>(
                 "no matching messaging protocol",
             ))
         }
-        Some((messaging_protocol, application_protocols)) => Ok(Connection {
-            socket,
-            metadata: ConnectionMetadata::new(
-                peer_id,
-                CONNECTION_ID_GENERATOR.next(),
-                addr,
-                origin,
-                messaging_protocol,
-                application_protocols,
-            ),
-        }),
+        Some((messaging_protocol, application_protocols)) => {
+            // Only keep the application protocols both ends have a common version of.
+            let application_protocols: Vec<ProtocolId> =
+                application_protocols.try_into().map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Unknown application protocol: {}", e),
+                    )
+                })?;
+            let application_versions = own_handshake.application_versions.negotiate(
+                &remote_handshake.application_versions,
+                &application_protocols,
+            );
+            Ok(Connection {
+                socket,
+                metadata: ConnectionMetadata::new(
+                    peer_id,
+                    CONNECTION_ID_GENERATOR.next(),
+                    addr,
+                    origin,
+                    messaging_protocol,
+                    application_versions.keys().into(),
+                    application_versions,
+                ),
+            })
+        }
     }
 }
 
@@ -282,14 +309,16 @@ where
         base_transport: TTransport,
         self_peer_id: PeerId,
         identity_key: x25519::PrivateKey,
-        trusted_peers: Option<Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>>,
+        trusted_peers: Option<Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>>,
         handshake_version: u8,
         chain_id: ChainId,
         network_id: NetworkId,
         application_protocols: SupportedProtocols,
+        application_versions: ApplicationVersions,
     ) -> Self {
         let mut own_handshake = HandshakeMsg::new(chain_id, network_id);
         own_handshake.add(SUPPORTED_MESSAGING_PROTOCOL, application_protocols);
+        own_handshake.application_versions = application_versions;
         let identity_pubkey = identity_key.public_key();
 
         let auth_mode = match trusted_peers.as_ref() {
@@ -519,11 +548,14 @@ mod test {
         key1: &x25519::PrivateKey,
         id2: PeerId,
         key2: &x25519::PrivateKey,
-    ) -> Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>> {
+    ) -> Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>> {
         Arc::new(RwLock::new(
-            vec![(id1, key1.public_key()), (id2, key2.public_key())]
-                .into_iter()
-                .collect(),
+            vec![
+                (id1, vec![key1.public_key()].into_iter().collect()),
+                (id2, vec![key2.public_key()].into_iter().collect()),
+            ]
+            .into_iter()
+            .collect(),
         ))
     }
 
@@ -539,7 +571,7 @@ mod test {
         Runtime,
         (PeerId, LibraNetTransport<TTransport>),
         (PeerId, LibraNetTransport<TTransport>),
-        Option<Arc<RwLock<HashMap<PeerId, x25519::PublicKey>>>>,
+        Option<Arc<RwLock<HashMap<PeerId, HashSet<x25519::PublicKey>>>>>,
         SupportedProtocols,
     )
     where
@@ -590,6 +622,7 @@ mod test {
             chain_id.clone(),
             NetworkId::Validator,
             supported_protocols.clone(),
+            ApplicationVersions::default(),
         );
 
         let dialer_transport = LibraNetTransport::new(
@@ -601,6 +634,7 @@ mod test {
             chain_id,
             NetworkId::Validator,
             supported_protocols.clone(),
+            ApplicationVersions::default(),
         );
 
         (
@@ -12,12 +12,18 @@
 
 use hex::FromHexError;
 use libra_types::{
-    account_address::AccountAddress, account_state::AccountState,
-    account_state_blob::AccountStateBlob, transaction::SignedTransaction,
+    account_address::AccountAddress,
+    account_state::AccountState,
+    account_state_blob::{AccountStateBlob, AccountStateWithProof},
+    epoch_change::EpochChangeProof,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{AccountStateProof, TransactionInfoWithProof},
+    transaction::{SignedTransaction, TransactionListWithProof, Version},
+    waypoint::{SignedWaypoint, Waypoint},
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::{convert::TryFrom, io};
+use std::{convert::TryFrom, io, str::FromStr};
 use thiserror::Error;
 use ureq::Response;
 
@@ -148,6 +154,119 @@ impl JsonRpcClient {
         }
     }
 
+    /// Returns the latest ledger info along with the proof of the epoch changes since the known
+    /// version. This is done by sending a get_state_proof() request to the JSON RPC server. The
+    /// caller is responsible for verifying both against its trusted state.
+    pub fn get_state_proof(
+        &self,
+        known_version: Version,
+    ) -> Result<(LedgerInfoWithSignatures, EpochChangeProof), Error> {
+        let method = "get_state_proof".into();
+        let params = vec![json!(known_version)];
+        let response = self.execute_request(method, params);
+        let state_proof: StateProofResponse = process_response(response)?;
+
+        Ok((
+            lcs::from_bytes(&state_proof.ledger_info_with_signatures.into_bytes()?)?,
+            lcs::from_bytes(&state_proof.epoch_change_proof.into_bytes()?)?,
+        ))
+    }
+
+    /// Returns the AccountStateWithProof for a specific account at a given version height, with
+    /// the proof relative to the given ledger_version height. This is done by sending a
+    /// get_account_state_with_proof() request to the JSON RPC server. The caller is responsible
+    /// for verifying the proof against a trusted ledger info.
+    pub fn get_account_state_with_proof(
+        &self,
+        account: AccountAddress,
+        version: Option<Version>,
+        ledger_version: Option<Version>,
+    ) -> Result<AccountStateWithProof, Error> {
+        let method = "get_account_state_with_proof".into();
+        let params = vec![
+            Value::String(account.to_string()),
+            json!(version),
+            json!(ledger_version),
+        ];
+        let response = self.execute_request(method, params);
+        let account_state: AccountStateResponse = process_response(response)?;
+
+        let blob = match account_state.blob {
+            Some(blob_bytes) => Some(AccountStateBlob::from(lcs::from_bytes::<Vec<u8>>(
+                &*blob_bytes.into_bytes()?,
+            )?)),
+            None => None,
+        };
+        let proof = AccountStateProof::new(
+            TransactionInfoWithProof::new(
+                lcs::from_bytes(
+                    &account_state
+                        .proof
+                        .ledger_info_to_transaction_info_proof
+                        .into_bytes()?,
+                )?,
+                lcs::from_bytes(&account_state.proof.transaction_info.into_bytes()?)?,
+            ),
+            lcs::from_bytes(
+                &account_state
+                    .proof
+                    .transaction_info_to_account_proof
+                    .into_bytes()?,
+            )?,
+        );
+        Ok(AccountStateWithProof::new(
+            account_state.version,
+            blob,
+            proof,
+        ))
+    }
+
+    /// Returns the transactions starting at start_version, along with their events and the proof
+    /// of their transaction infos relative to the given ledger_version height. This is done by
+    /// sending a get_transactions_with_proofs() request to the JSON RPC server. The caller is
+    /// responsible for verifying the proof against a trusted ledger info.
+    pub fn get_transactions_with_proofs(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Option<Version>,
+    ) -> Result<TransactionListWithProof, Error> {
+        let method = "get_transactions_with_proofs".into();
+        let params = vec![json!(start_version), json!(limit), json!(ledger_version)];
+        let response = self.execute_request(method, params);
+        let transactions: TransactionListWithProofResponse = process_response(response)?;
+
+        Ok(lcs::from_bytes(
+            &transactions.transaction_list_with_proof.into_bytes()?,
+        )?)
+    }
+
+    /// Returns the waypoint emitted last by the server, along with the epoch-ending ledger info
+    /// it was generated from. This is done by sending a get_latest_waypoint() request to the JSON
+    /// RPC server. The caller is responsible for verifying it against a trusted waypoint.
+    pub fn get_latest_waypoint(&self) -> Result<Option<SignedWaypoint>, Error> {
+        let method = "get_latest_waypoint".into();
+        let response = self.execute_request(method, vec![]);
+        let signed_waypoint: SignedWaypointResponse = match process_response(response)? {
+            Some(signed_waypoint) => signed_waypoint,
+            None => return Ok(None),
+        };
+
+        let ledger_info_with_sigs =
+            lcs::from_bytes(&signed_waypoint.ledger_info_with_signatures.into_bytes()?)?;
+        let waypoint = Waypoint::from_str(&signed_waypoint.waypoint)
+            .map_err(|e| Error::SerializationError(e.to_string()))?;
+        let verified = SignedWaypoint::new(ledger_info_with_sigs)
+            .map_err(|e| Error::SerializationError(e.to_string()))?;
+        if verified.waypoint() != waypoint {
+            return Err(Error::SerializationError(format!(
+                "The waypoint {} doesn't match its ledger info",
+                waypoint
+            )));
+        }
+        Ok(Some(verified))
+    }
+
     // Executes the specified request method using the given parameters by contacting the JSON RPC
     // server.
     fn execute_request(&self, method: String, params: Vec<Value>) -> Response {
@@ -159,6 +278,29 @@ impl JsonRpcClient {
     }
 }
 
+// Returns the result of a successful JSON RPC call, or the error returned by the server.
+fn process_response<T: DeserializeOwned>(response: Response) -> Result<T, Error> {
+    match response.status() {
+        200 => {
+            let response = &response.into_string()?;
+            if let Ok(failure_response) = serde_json::from_str::<JSONRpcFailureResponse>(response) {
+                Err(Error::InternalRPCError(format!("{:?}", failure_response)))
+            } else {
+                Ok(serde_json::from_str::<JsonRpcResponse<T>>(response)?.result)
+            }
+        }
+        _ => Err(Error::RPCFailure(response.into_string()?)),
+    }
+}
+
+/// The response of a successful JSON RPC call, of which only the result is relevant.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct JsonRpcResponse<T> {
+    id: u64,
+    jsonrpc: String,
+    result: T,
+}
+
 /// Below is a sample response from a successful submit() JSON RPC call:
 /// "{
 ///   "id": 0,
@@ -201,6 +343,38 @@ struct AccountStateWithProofResponse {
 struct AccountStateResponse {
     version: u64,
     blob: Option<Bytes>,
+    proof: AccountStateProofResponse,
+}
+
+/// The proof of an AccountStateWithProof, where each field is an LCS encoded proof component.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct AccountStateProofResponse {
+    ledger_info_to_transaction_info_proof: Bytes,
+    transaction_info: Bytes,
+    transaction_info_to_account_proof: Bytes,
+}
+
+/// The result of a successful get_state_proof() JSON RPC call, where each field is LCS encoded.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct StateProofResponse {
+    ledger_info_with_signatures: Bytes,
+    epoch_change_proof: Bytes,
+    ledger_consistency_proof: Bytes,
+}
+
+/// The result of a successful get_latest_waypoint() JSON RPC call, where the ledger info is LCS
+/// encoded.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct SignedWaypointResponse {
+    epoch: u64,
+    waypoint: String,
+    ledger_info_with_signatures: Bytes,
+}
+
+/// The result of a successful get_transactions_with_proofs() JSON RPC call, LCS encoded.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct TransactionListWithProofResponse {
+    transaction_list_with_proof: Bytes,
 }
 
 /// Below is a sample response from a failed JSON RPC call:
@@ -265,7 +439,7 @@ mod test {
         account_state::AccountState,
         account_state_blob::{AccountStateBlob, AccountStateWithProof},
         block_info::BlockInfo,
-        contract_event::ContractEvent,
+        contract_event::{ContractEvent, EventWithProof},
         epoch_change::EpochChangeProof,
         event::{EventHandle, EventKey},
         ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
@@ -283,7 +457,7 @@ mod test {
     };
     use libradb::errors::LibraDbError::NotFound;
     use std::{collections::BTreeMap, convert::TryFrom, sync::Arc};
-    use storage_interface::{DbReader, StartupInfo, TreeState};
+    use storage_interface::{DbReader, Order, StartupInfo, TreeState};
     use tokio::runtime::Runtime;
     use vm_validator::{
         mocks::mock_vm_validator::MockVMValidator, vm_validator::TransactionValidation,
@@ -357,6 +531,31 @@ mod test {
         assert_eq!(result.unwrap(), account_state);
     }
 
+    #[test]
+    fn test_get_account_state_with_proof() {
+        // Create test account state data
+        let account = AccountAddress::random();
+        let account_state = create_test_account_state();
+        let version_height = 0;
+        let account_state_with_proof = create_test_state_with_proof(&account_state, version_height);
+
+        // Create an account to account_state_with_proof mapping
+        let mut map = BTreeMap::new();
+        map.insert(account, account_state_with_proof.clone());
+
+        // Populate the test database with the test data and create the client/server
+        let mock_db = MockLibraDB::new(map);
+        let (client, _server) = create_client_and_server(mock_db, true);
+
+        // Ensure the client returns the AccountStateWithProof, including the proof
+        let result = client.get_account_state_with_proof(
+            account,
+            Some(version_height),
+            Some(version_height),
+        );
+        assert_eq!(result.unwrap(), account_state_with_proof);
+    }
+
     #[test]
     fn test_get_account_state_missing() {
         let mock_db = create_empty_mock_db();
@@ -528,6 +727,17 @@ mod test {
             unimplemented!()
         }
 
+        fn get_events_with_proof_by_event_key(
+            &self,
+            _event_key: &EventKey,
+            _start_seq_num: u64,
+            _order: Order,
+            _limit: u64,
+            _known_version: Option<Version>,
+        ) -> Result<Vec<EventWithProof>> {
+            unimplemented!()
+        }
+
         fn get_latest_account_state(
             &self,
             _address: AccountAddress,
@@ -14,6 +14,10 @@ define_counters![
         completed_consensus_key_rotations: Counter,
         "counts the number of completed consensus key rotations performed by the key manager"
     ),
+    (
+        consensus_key_rollbacks: Counter,
+        "counts the number of consensus key rotations rolled back after failing to take effect on-chain"
+    ),
     (
         consensus_rotation_tx_resubmissions: Counter,
         "counts the number of times the key manager had to resubmit a consensus rotation transaction to the blockchain"
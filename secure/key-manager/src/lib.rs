@@ -13,6 +13,24 @@
 //! * if the current key in the ValidatorConfig matches the ValidatorSet, if it does not it
 //! evaluates the current time from the last reconfiguration and logs that delta with greater
 //! levels of severity depending on the delta.
+//! * if the rotation transaction has been resubmitted too many times without taking effect, it
+//! rolls back the rotation in the local store, so that the store once again matches the key
+//! registered on-chain. A new rotation is then attempted after the rotation period. The
+//! resubmissions are counted in the local store, so that restarts do not reset them.
+//!
+//! By default the store generates each new consensus key. If KeyManager is given a wallet, it
+//! instead derives the n-th new consensus key with SLIP-0010 at the Libra path
+//! `m/44'/637'/0'/0'/{n}'`, so that every consensus key can be restored from the wallet mnemonic.
+//! This requires a store that accepts given keys, i.e., not Vault.
+//!
+//! Network identity keys are rotated without downtime in two steps:
+//! * `announce_network_key` publishes the next key in the ValidatorConfig, together with the
+//! validator network address rewritten to authenticate with it, alongside the current key and
+//! address. Once a reconfiguration has made the announcement visible, peers dial either address
+//! and accept Noise handshakes authenticated by either key, and only then may the node switch to
+//! the next key.
+//! * `complete_network_key_rotation` makes the next key and address the only ones. It must not be
+//! called before the node runs with the next key.
 //!
 //! KeyManager talks to Libra via the LibraInterface that may either be a direct link into
 //! `LibraDB`/`Executor`, JSON-RPC, or some other concoction.
@@ -23,15 +41,17 @@ use crate::{counters::COUNTERS, libra_interface::LibraInterface};
 use libra_crypto::{ed25519::Ed25519PublicKey, hash::CryptoHash, x25519};
 use libra_global_constants::{CONSENSUS_KEY, OPERATOR_ACCOUNT, OPERATOR_KEY};
 use libra_logger::{error, info};
-use libra_network_address::RawNetworkAddress;
-use libra_secure_storage::{CryptoStorage, KVStorage};
+use libra_network_address::{NetworkAddress, RawNetworkAddress};
+use libra_secure_storage::{CryptoStorage, KVStorage, Value};
 use libra_secure_time::TimeService;
 use libra_types::{
     account_address::AccountAddress,
     account_config::LBR_NAME,
     transaction::{RawTransaction, Script, SignedTransaction, Transaction, TransactionArgument},
+    validator_config::{NextNetworkConfig, ValidatorConfig},
 };
-use std::{str::FromStr, time::Duration};
+use libra_wallet::WalletLibrary;
+use std::{convert::TryFrom, str::FromStr, time::Duration};
 use thiserror::Error;
 
 pub mod counters;
@@ -43,6 +63,15 @@ mod tests;
 const GAS_UNIT_PRICE: u64 = 0;
 const MAX_GAS_AMOUNT: u64 = 400_000;
 
+/// The wallet account under which consensus keys are derived
+const CONSENSUS_KEY_WALLET_ACCOUNT: u32 = 0;
+/// The storage entry holding the index of the next consensus key to derive
+const CONSENSUS_KEY_DERIVATION_INDEX: &str = "consensus_key_derivation_index";
+/// The storage entry holding the time the last rotation transaction was submitted
+const CONSENSUS_KEY_LAST_SUBMISSION: &str = "consensus_key_last_submission";
+/// The storage entry holding the resubmissions of the current rotation transaction
+const CONSENSUS_KEY_RESUBMISSIONS: &str = "consensus_key_resubmissions";
+
 /// Defines actions that KeyManager should perform after a check of all associated state.
 #[derive(Debug, PartialEq)]
 pub enum Action {
@@ -52,6 +81,9 @@ pub enum Action {
     FullKeyRotation,
     /// Storage and the blockchain are inconsistent, submit a new rotation
     SubmitKeyRotationTransaction,
+    /// Storage and the blockchain remain inconsistent despite resubmissions, restore the key
+    /// registered on-chain in storage
+    RollbackKeyRotation,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -87,9 +119,11 @@ pub struct KeyManager<LI, S, T> {
     storage: S,
     time_service: T,
     last_checked_libra_timestamp: u64,
+    max_resubmissions: u64, // The resubmissions after which a rotation is rolled back
     rotation_period_secs: u64, // The frequency by which to rotate all keys
-    sleep_period_secs: u64,    // The amount of time to sleep between key management checks
-    txn_expiration_secs: u64,  // The time after which a rotation transaction expires
+    sleep_period_secs: u64, // The amount of time to sleep between key management checks
+    txn_expiration_secs: u64, // The time after which a rotation transaction expires
+    consensus_key_wallet: Option<WalletLibrary>, // The wallet to derive consensus keys from
 }
 
 impl<LI, S, T> KeyManager<LI, S, T>
@@ -105,18 +139,27 @@ where
         rotation_period_secs: u64,
         sleep_period_secs: u64,
         txn_expiration_secs: u64,
+        max_resubmissions: u64,
     ) -> Self {
         Self {
             libra,
             storage,
             time_service,
             last_checked_libra_timestamp: 0,
+            max_resubmissions,
             rotation_period_secs,
             sleep_period_secs,
             txn_expiration_secs,
+            consensus_key_wallet: None,
         }
     }
 
+    /// Derives new consensus keys from the given wallet instead of generating them in storage.
+    pub fn with_consensus_key_wallet(mut self, wallet: WalletLibrary) -> Self {
+        self.consensus_key_wallet = Some(wallet);
+        self
+    }
+
     /// Begins execution of the key manager by running an infinite loop where the key manager will
     /// periodically wake up, verify the state of the validator keys (e.g., the consensus key), and
     /// initiate a key rotation when required. If something goes wrong that we can't handle, an
@@ -200,52 +243,183 @@ where
     pub fn resubmit_consensus_key_transaction(&mut self) -> Result<(), Error> {
         let consensus_key = self.storage.get_public_key(CONSENSUS_KEY)?.public_key;
         COUNTERS.consensus_rotation_tx_resubmissions.inc();
+        let resubmissions = self.resubmissions()?;
+        self.storage
+            .set(CONSENSUS_KEY_RESUBMISSIONS, Value::U64(resubmissions + 1))?;
         self.submit_key_rotation_transaction(consensus_key)
             .map(|_| ())
     }
 
     pub fn rotate_consensus_key(&mut self) -> Result<Ed25519PublicKey, Error> {
-        let consensus_key = self.storage.rotate_key(CONSENSUS_KEY)?;
+        let consensus_key = match &self.consensus_key_wallet {
+            Some(wallet) => {
+                let index = self.consensus_key_derivation_index()?;
+                let derived_key = wallet
+                    .slip10_key("", CONSENSUS_KEY_WALLET_ACCOUNT, index)
+                    .map_err(|e| Error::UnknownError(e.to_string()))?;
+                let consensus_key = self
+                    .storage
+                    .rotate_key_to(CONSENSUS_KEY, derived_key.private_key())?;
+                self.storage.set(
+                    CONSENSUS_KEY_DERIVATION_INDEX,
+                    Value::U64(u64::from(index) + 1),
+                )?;
+                consensus_key
+            }
+            None => self.storage.rotate_key(CONSENSUS_KEY)?,
+        };
         info!("Successfully rotated the consensus key in secure storage.");
         COUNTERS.completed_consensus_key_rotations.inc();
+        self.reset_resubmissions()?;
         self.submit_key_rotation_transaction(consensus_key)
     }
 
+    /// Restores the consensus key registered on-chain as the current key in storage, discarding
+    /// the key of a rotation that failed to take effect on-chain.
+    pub fn rollback_consensus_key(&mut self) -> Result<Ed25519PublicKey, Error> {
+        let operator_account = self.get_operator_account()?;
+        let config_key = self
+            .libra
+            .retrieve_validator_config(operator_account)?
+            .consensus_public_key;
+
+        // Only roll back if that restores the key registered on-chain
+        let previous_key = self.storage.get_previous_public_key(CONSENSUS_KEY)?;
+        if previous_key != config_key {
+            return Err(Error::ConfigStorageKeyMismatch(config_key, previous_key));
+        }
+        let storage_key = self.storage.rollback_key(CONSENSUS_KEY)?;
+
+        info!("Rolled back the consensus key in secure storage to the key registered on-chain.");
+        COUNTERS.consensus_key_rollbacks.inc();
+        self.reset_resubmissions()?;
+        Ok(storage_key)
+    }
+
+    /// The index of the next consensus key to derive, which starts at 0 and is never reused.
+    fn consensus_key_derivation_index(&self) -> Result<u32, Error> {
+        let index = self.get_u64_or_zero(CONSENSUS_KEY_DERIVATION_INDEX)?;
+        u32::try_from(index).map_err(|e| Error::UnknownError(e.to_string()))
+    }
+
+    /// The resubmissions of the current rotation transaction. These are kept in storage, so that
+    /// a failing rotation is still rolled back if the process restarts in between.
+    fn resubmissions(&self) -> Result<u64, Error> {
+        self.get_u64_or_zero(CONSENSUS_KEY_RESUBMISSIONS)
+    }
+
+    fn reset_resubmissions(&mut self) -> Result<(), Error> {
+        if self.resubmissions()? != 0 {
+            self.storage
+                .set(CONSENSUS_KEY_RESUBMISSIONS, Value::U64(0))?;
+        }
+        Ok(())
+    }
+
+    /// The time the last rotation transaction was submitted, or 0 if none ever was.
+    fn last_submission(&self) -> Result<u64, Error> {
+        self.get_u64_or_zero(CONSENSUS_KEY_LAST_SUBMISSION)
+    }
+
+    fn get_u64_or_zero(&self, key: &str) -> Result<u64, Error> {
+        match self.storage.get(key) {
+            Ok(response) => Ok(response.value.u64()?),
+            Err(libra_secure_storage::Error::KeyNotSet(_)) => Ok(0),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     pub fn submit_key_rotation_transaction(
         &mut self,
         consensus_key: Ed25519PublicKey,
     ) -> Result<Ed25519PublicKey, Error> {
+        // Retrieve existing network information as registered on-chain
+        let operator_account = self.get_operator_account()?;
+        let mut validator_config = self.libra.retrieve_validator_config(operator_account)?;
+        validator_config.consensus_public_key = consensus_key.clone();
+
+        self.submit_validator_config_transaction(&validator_config)?;
+        info!("Submitted the rotation transaction to the blockchain.");
+
+        Ok(consensus_key)
+    }
+
+    /// Publishes `next_network_key` as the next validator network identity key, along with the
+    /// validator network address rewritten to authenticate with it. Until the rotation completes,
+    /// peers dial either address and accept connections authenticated by either key.
+    pub fn announce_network_key(
+        &mut self,
+        next_network_key: x25519::PublicKey,
+    ) -> Result<(), Error> {
+        let operator_account = self.get_operator_account()?;
+        let validator_config = self.libra.retrieve_validator_config(operator_account)?;
+        let next_address = NetworkAddress::try_from(&validator_config.validator_network_address)
+            .map(|address| address.rotate_noise_public_key(&next_network_key))
+            .and_then(|address| RawNetworkAddress::try_from(&address))
+            .map_err(|e| Error::UnknownError(e.to_string()))?;
+        let validator_next_network_config = NextNetworkConfig {
+            identity_public_key: next_network_key,
+            address: next_address,
+        };
+
+        self.submit_operator_transaction(|sender, seq_id, expiration| {
+            build_next_network_config_transaction(
+                sender,
+                seq_id,
+                Some(&validator_next_network_config),
+                validator_config.full_node_next_network_config.as_ref(),
+                expiration,
+            )
+        })?;
+        info!("Submitted the next validator network key and address to the blockchain.");
+        Ok(())
+    }
+
+    /// Replaces the current validator network identity key and address with the previously
+    /// announced next ones, after which peers no longer accept the old key.
+    pub fn complete_network_key_rotation(&mut self) -> Result<x25519::PublicKey, Error> {
+        let operator_account = self.get_operator_account()?;
+        let validator_config = self.libra.retrieve_validator_config(operator_account)?;
+        let next_network_key = validator_config
+            .validator_next_network_config
+            .map(|next| next.identity_public_key)
+            .ok_or_else(|| Error::DataDoesNotExist("next validator network key".into()))?;
+
+        self.submit_operator_transaction(build_network_rotation_transaction)?;
+        info!("Submitted the validator network key rotation to the blockchain.");
+        Ok(next_network_key)
+    }
+
+    fn submit_validator_config_transaction(
+        &mut self,
+        validator_config: &ValidatorConfig,
+    ) -> Result<(), Error> {
+        self.storage.set(
+            CONSENSUS_KEY_LAST_SUBMISSION,
+            Value::U64(self.time_service.now()),
+        )?;
+        self.submit_operator_transaction(|sender, seq_id, expiration| {
+            build_rotation_transaction(sender, seq_id, validator_config, expiration)
+        })
+    }
+
+    /// Signs the transaction built by `build_transaction` for the operator account's next
+    /// sequence number with the operator key and submits it.
+    fn submit_operator_transaction<F>(&mut self, build_transaction: F) -> Result<(), Error>
+    where
+        F: FnOnce(AccountAddress, u64, Duration) -> RawTransaction,
+    {
         let operator_account = self.get_operator_account()?;
         let seq_id = self.libra.retrieve_sequence_number(operator_account)?;
         let expiration = Duration::from_secs(self.time_service.now() + self.txn_expiration_secs);
 
-        // Retrieve existing network information as registered on-chain
-        let validator_config = self.libra.retrieve_validator_config(operator_account)?;
-        let network_key = validator_config.validator_network_identity_public_key;
-        let network_address = validator_config.validator_network_address;
-        let fullnode_network_key = validator_config.full_node_network_identity_public_key;
-        let fullnode_network_address = validator_config.full_node_network_address;
-
-        let txn = build_rotation_transaction(
-            operator_account,
-            seq_id,
-            &consensus_key,
-            &network_key,
-            &network_address,
-            &fullnode_network_key,
-            &fullnode_network_address,
-            expiration,
-        );
+        let txn = build_transaction(operator_account, seq_id, expiration);
 
         let operator_pubkey = self.storage.get_public_key(OPERATOR_KEY)?.public_key;
         let txn_signature = self.storage.sign_message(OPERATOR_KEY, &txn.hash())?;
         let signed_txn = SignedTransaction::new(txn, operator_pubkey, txn_signature);
-
         self.libra
-            .submit_transaction(Transaction::UserTransaction(signed_txn))?;
-        info!("Submitted the rotation transaction to the blockchain.");
-
-        Ok(consensus_key)
+            .submit_transaction(Transaction::UserTransaction(signed_txn))
     }
 
     /// Ensures that the libra_timestamp() value registered on-chain is strictly monotonically
@@ -282,13 +456,18 @@ where
         let last_rotation = self.last_rotation()?;
 
         // If this is inconsistent, then the transaction either failed or was never submitted.
+        // Only once the last submitted transaction has expired can it be known to have failed.
         if let Err(Error::ConfigStorageKeyMismatch(..)) = self.compare_storage_to_config() {
-            return if last_rotation + self.txn_expiration_secs <= self.time_service.now() {
-                Ok(Action::SubmitKeyRotationTransaction)
-            } else {
+            let last_submission = std::cmp::max(last_rotation, self.last_submission()?);
+            return if last_submission + self.txn_expiration_secs > self.time_service.now() {
                 Ok(Action::NoAction)
+            } else if self.resubmissions()? >= self.max_resubmissions {
+                Ok(Action::RollbackKeyRotation)
+            } else {
+                Ok(Action::SubmitKeyRotationTransaction)
             };
         }
+        self.reset_resubmissions()?;
 
         if last_rotation + self.rotation_period_secs <= self.time_service.now() {
             Ok(Action::FullKeyRotation)
@@ -307,6 +486,14 @@ where
                 info!("The consensus key rotation transaction needs to be resubmitted");
                 self.resubmit_consensus_key_transaction()
             }
+            Action::RollbackKeyRotation => {
+                error!(
+                    "The consensus key rotation failed to take effect after {} resubmissions, \
+                     rolling back.",
+                    self.resubmissions()?
+                );
+                self.rollback_consensus_key().map(|_| ())
+            }
             Action::NoAction => {
                 info!("No actions need to be performed.");
                 COUNTERS.no_actions_required.inc();
@@ -331,11 +518,7 @@ where
 pub fn build_rotation_transaction(
     sender: AccountAddress,
     seq_id: u64,
-    consensus_key: &Ed25519PublicKey,
-    network_key: &x25519::PublicKey,
-    network_address: &RawNetworkAddress,
-    fullnode_network_key: &x25519::PublicKey,
-    fullnode_network_address: &RawNetworkAddress,
+    validator_config: &ValidatorConfig,
     expiration: Duration,
 ) -> RawTransaction {
     let script = Script::new(
@@ -343,13 +526,82 @@ pub fn build_rotation_transaction(
         vec![],
         vec![
             TransactionArgument::Address(sender),
-            TransactionArgument::U8Vector(consensus_key.to_bytes().to_vec()),
-            TransactionArgument::U8Vector(network_key.as_slice().to_vec()),
-            TransactionArgument::U8Vector(network_address.as_ref().to_vec()),
-            TransactionArgument::U8Vector(fullnode_network_key.as_slice().to_vec()),
-            TransactionArgument::U8Vector(fullnode_network_address.as_ref().to_vec()),
+            TransactionArgument::U8Vector(
+                validator_config.consensus_public_key.to_bytes().to_vec(),
+            ),
+            TransactionArgument::U8Vector(
+                validator_config
+                    .validator_network_identity_public_key
+                    .as_slice()
+                    .to_vec(),
+            ),
+            TransactionArgument::U8Vector(
+                validator_config.validator_network_address.as_ref().to_vec(),
+            ),
+            TransactionArgument::U8Vector(
+                validator_config
+                    .full_node_network_identity_public_key
+                    .as_slice()
+                    .to_vec(),
+            ),
+            TransactionArgument::U8Vector(
+                validator_config.full_node_network_address.as_ref().to_vec(),
+            ),
         ],
     );
+    build_operator_transaction(sender, seq_id, script, expiration)
+}
+
+/// Builds a transaction announcing the next network identity keys and addresses, where `None`
+/// leaves a network out of the rotation.
+pub fn build_next_network_config_transaction(
+    sender: AccountAddress,
+    seq_id: u64,
+    validator_next_network_config: Option<&NextNetworkConfig>,
+    full_node_next_network_config: Option<&NextNetworkConfig>,
+    expiration: Duration,
+) -> RawTransaction {
+    let mut args = vec![TransactionArgument::Address(sender)];
+    for next in &[validator_next_network_config, full_node_next_network_config] {
+        let (identity_public_key, address) = next
+            .map(|next| {
+                (
+                    next.identity_public_key.as_slice().to_vec(),
+                    next.address.as_ref().to_vec(),
+                )
+            })
+            .unwrap_or_default();
+        args.push(TransactionArgument::U8Vector(identity_public_key));
+        args.push(TransactionArgument::U8Vector(address));
+    }
+    let script = Script::new(
+        libra_transaction_scripts::SET_VALIDATOR_NEXT_NETWORK_CONFIG_TXN.clone(),
+        vec![],
+        args,
+    );
+    build_operator_transaction(sender, seq_id, script, expiration)
+}
+
+/// Builds a transaction switching each network to its announced next identity key and address.
+pub fn build_network_rotation_transaction(
+    sender: AccountAddress,
+    seq_id: u64,
+    expiration: Duration,
+) -> RawTransaction {
+    let script = Script::new(
+        libra_transaction_scripts::ROTATE_VALIDATOR_NETWORK_CONFIG_TXN.clone(),
+        vec![],
+        vec![TransactionArgument::Address(sender)],
+    );
+    build_operator_transaction(sender, seq_id, script, expiration)
+}
+
+fn build_operator_transaction(
+    sender: AccountAddress,
+    seq_id: u64,
+    script: Script,
+    expiration: Duration,
+) -> RawTransaction {
     RawTransaction::new_script(
         sender,
         seq_id,
@@ -13,7 +13,8 @@ use libra_logger::info;
 use libra_secure_push_metrics::MetricsPusher;
 use libra_secure_storage::Storage;
 use libra_secure_time::RealTimeService;
-use std::{convert::TryInto, env, process};
+use libra_wallet::WalletLibrary;
+use std::{convert::TryFrom, env, process};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -48,20 +49,25 @@ fn main() {
 
 fn create_and_execute_key_manager(key_manager_config: KeyManagerConfig) -> Result<(), Error> {
     let libra_interface = create_libra_interface(key_manager_config.json_rpc_endpoint);
-    let storage: Storage = (&key_manager_config.secure_backend)
-        .try_into()
-        .expect("Unable to initialize storage");
+    let storage = Storage::try_from(&key_manager_config.secure_backend)
+        .map_err(|e| Error::UnknownError(format!("Unable to initialize storage: {}", e)))?;
     let time_service = RealTimeService::new();
 
-    KeyManager::new(
+    let mut key_manager = KeyManager::new(
         libra_interface,
         storage,
         time_service,
         key_manager_config.rotation_period_secs,
         key_manager_config.sleep_period_secs,
         key_manager_config.txn_expiration_secs,
-    )
-    .execute()
+        key_manager_config.max_resubmissions,
+    );
+    if let Some(wallet_file) = &key_manager_config.consensus_key_wallet_file {
+        let wallet = WalletLibrary::recover(wallet_file)
+            .map_err(|e| Error::UnknownError(format!("Unable to load wallet: {}", e)))?;
+        key_manager = key_manager.with_consensus_key_wallet(wallet);
+    }
+    key_manager.execute()
 }
 
 fn create_libra_interface(json_rpc_endpoint: String) -> JsonRpcLibraInterface {
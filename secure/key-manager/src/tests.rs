@@ -16,8 +16,8 @@ use libra_config::{
 };
 use libra_crypto::{ed25519::Ed25519PrivateKey, x25519, HashValue, PrivateKey, Uniform};
 use libra_global_constants::{OPERATOR_ACCOUNT, OPERATOR_KEY};
-use libra_network_address::RawNetworkAddress;
-use libra_secure_storage::{InMemoryStorageInternal, KVStorage, Value};
+use libra_network_address::{NetworkAddress, RawNetworkAddress};
+use libra_secure_storage::{CryptoStorage, InMemoryStorageInternal, KVStorage, Value};
 use libra_secure_time::{MockTimeService, TimeService};
 use libra_types::{
     account_address::AccountAddress,
@@ -34,6 +34,7 @@ use libra_types::{
     validator_info::ValidatorInfo,
 };
 use libra_vm::LibraVM;
+use libra_wallet::{Mnemonic, WalletLibrary};
 use libradb::LibraDB;
 use rand::{rngs::StdRng, SeedableRng};
 use std::{cell::RefCell, collections::BTreeMap, convert::TryFrom, sync::Arc, time::Duration};
@@ -78,6 +79,28 @@ impl<T: LibraInterface> Node<T> {
         }
     }
 
+    // Replaces the key manager with a new one on the same secure storage, as a process restart
+    // would.
+    fn restart_key_manager(&mut self)
+    where
+        T: Clone,
+    {
+        let (_, key_manager_config) = get_test_configs();
+        let storage = std::mem::replace(
+            &mut self.key_manager.storage,
+            InMemoryStorageInternal::new_with_time_service(self.time.clone()),
+        );
+        self.key_manager = KeyManager::new(
+            self.libra.clone(),
+            storage,
+            self.time.clone(),
+            key_manager_config.rotation_period_secs,
+            key_manager_config.sleep_period_secs,
+            key_manager_config.txn_expiration_secs,
+            key_manager_config.max_resubmissions,
+        );
+    }
+
     // Increments the libra_timestamp on the blockchain by executing an empty block.
     fn update_libra_timestamp(&mut self) {
         self.execute_and_commit(vec![]);
@@ -90,7 +113,15 @@ impl<T: LibraInterface> Node<T> {
         let clock = self.time.now() * 1_000_000;
 
         let block_id = HashValue::zero();
-        let block_metadata = BlockMetadata::new(block_id, 0, clock, vec![], self.account);
+        let block_metadata = BlockMetadata::new(
+            block_id,
+            self.executor.committed_block_id(),
+            0,
+            clock,
+            vec![],
+            self.account,
+            vec![],
+        );
         let prologue = Transaction::BlockMetadata(block_metadata);
         block.insert(0, prologue);
 
@@ -371,6 +402,7 @@ fn setup_node<T: LibraInterface + Clone>(
         key_manager_config.rotation_period_secs,
         key_manager_config.sleep_period_secs,
         key_manager_config.txn_expiration_secs,
+        key_manager_config.max_resubmissions,
     );
 
     Node::new(account, executor, libra_test_harness, key_manager, time)
@@ -502,14 +534,17 @@ fn verify_manual_rotation_on_chain<T: LibraInterface>(mut node: Node<T>) {
     let new_privkey = Ed25519PrivateKey::generate(&mut rng);
     let new_pubkey = new_privkey.public_key();
     let new_network_pubkey = x25519::PrivateKey::generate(&mut rng).public_key();
+    let rotated_config = ValidatorConfig::new(
+        new_pubkey.clone(),
+        new_network_pubkey,
+        RawNetworkAddress::new(Vec::new()),
+        new_network_pubkey,
+        RawNetworkAddress::new(Vec::new()),
+    );
     let txn1 = crate::build_rotation_transaction(
         node.account,
         0,
-        &new_pubkey,
-        &new_network_pubkey,
-        &RawNetworkAddress::new(Vec::new()),
-        &new_network_pubkey,
-        &RawNetworkAddress::new(Vec::new()),
+        &rotated_config,
         Duration::from_secs(node.time.now() + TXN_EXPIRATION_SECS),
     );
     let txn1 = txn1
@@ -592,6 +627,113 @@ fn verify_init_and_basic_rotation<T: LibraInterface>(mut node: Node<T>) {
     );
 }
 
+#[test]
+// This verifies that consensus keys derived from a wallet can be restored from its mnemonic.
+fn test_derived_consensus_key_rotation() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_derived_consensus_key_rotation(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_derived_consensus_key_rotation(node);
+}
+
+fn verify_derived_consensus_key_rotation<T: LibraInterface>(mut node: Node<T>) {
+    let wallet = WalletLibrary::new();
+    let restored_wallet =
+        WalletLibrary::new_from_mnemonic(Mnemonic::from(&wallet.mnemonic()).unwrap());
+    node.key_manager = node.key_manager.with_consensus_key_wallet(wallet);
+
+    // Each rotation derives the key at the next index
+    for index in 0..3 {
+        let new_key = node.key_manager.rotate_consensus_key().unwrap();
+        let derived_key = restored_wallet.slip10_key("", 0, index).unwrap();
+        assert_eq!(derived_key.public_key(), new_key);
+    }
+
+    // A derived key takes effect on-chain like a generated one
+    node.libra.take_all_transactions();
+    let new_key = node.key_manager.rotate_consensus_key().unwrap();
+    submit_reconfiguration_transaction(&node);
+    node.execute_and_commit(node.libra.take_all_transactions());
+    let rotated_info = node.libra.retrieve_validator_info(node.account).unwrap();
+    assert_eq!(rotated_info.consensus_public_key(), &new_key);
+}
+
+#[test]
+// This verifies that a network key can be announced and then rotated to on-chain.
+fn test_network_key_rotation() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_network_key_rotation(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_network_key_rotation(node);
+}
+
+fn verify_network_key_rotation<T: LibraInterface>(mut node: Node<T>) {
+    let genesis_config = node.libra.retrieve_validator_config(node.account).unwrap();
+    assert_eq!(None, genesis_config.validator_next_network_config);
+    let current_key = genesis_config.validator_network_identity_public_key;
+    let current_address =
+        NetworkAddress::try_from(&genesis_config.validator_network_address).unwrap();
+
+    // Completing a rotation requires an announced key
+    assert!(node.key_manager.complete_network_key_rotation().is_err());
+
+    // Announce the next key, both keys and both addresses are now valid
+    let mut rng = StdRng::from_seed([45u8; 32]);
+    let next_key = x25519::PrivateKey::generate(&mut rng).public_key();
+    node.key_manager.announce_network_key(next_key).unwrap();
+    submit_reconfiguration_transaction(&node);
+    node.execute_and_commit(node.libra.take_all_transactions());
+
+    let next_address =
+        RawNetworkAddress::try_from(&current_address.rotate_noise_public_key(&next_key)).unwrap();
+    let announced_config = node.libra.retrieve_validator_config(node.account).unwrap();
+    assert_eq!(
+        vec![
+            genesis_config.validator_network_address.clone(),
+            next_address.clone()
+        ],
+        announced_config.validator_network_addresses()
+    );
+    let announced_info = node.libra.retrieve_validator_info(node.account).unwrap();
+    let keys = announced_info.network_identity_public_keys();
+    assert_eq!(2, keys.len());
+    assert!(keys.contains(&current_key));
+    assert!(keys.contains(&next_key));
+    assert_eq!(
+        genesis_config.consensus_public_key,
+        *announced_info.consensus_public_key()
+    );
+
+    // Complete the rotation, only the next key and address remain
+    assert_eq!(
+        next_key,
+        node.key_manager.complete_network_key_rotation().unwrap()
+    );
+    submit_reconfiguration_transaction(&node);
+    node.execute_and_commit(node.libra.take_all_transactions());
+
+    let rotated_config = node.libra.retrieve_validator_config(node.account).unwrap();
+    assert_eq!(None, rotated_config.validator_next_network_config);
+    assert_eq!(
+        next_key,
+        rotated_config.validator_network_identity_public_key
+    );
+    assert_eq!(next_address, rotated_config.validator_network_address);
+    let rotated_info = node.libra.retrieve_validator_info(node.account).unwrap();
+    assert_eq!(
+        vec![next_key]
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>(),
+        rotated_info.network_identity_public_keys()
+    );
+}
+
 #[test]
 // This tests the application's main loop to ensure it handles basic operations and reliabilities.
 // To do this, the test repeatedly calls "execute_once_and_sleep" -- identical to the main "execute"
@@ -662,6 +804,115 @@ fn verify_execute<T: LibraInterface>(mut node: Node<T>) {
     assert_ne!(0, node.libra.last_reconfiguration().unwrap());
 }
 
+#[test]
+// This tests that a consensus key rotation is rolled back in storage if the rotation transaction
+// repeatedly fails to take effect on-chain.
+fn test_execute_rollback() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_execute_rollback(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_execute_rollback(node);
+}
+
+fn verify_execute_rollback<T: LibraInterface + Clone>(mut node: Node<T>) {
+    let (_, key_manager_config) = get_test_configs();
+    let genesis_config = node.libra.retrieve_validator_config(node.account).unwrap();
+
+    // Perform a rotation but never execute the rotation transaction on-chain
+    node.time
+        .increment_by(key_manager_config.rotation_period_secs);
+    node.update_libra_timestamp();
+    node.key_manager.execute_once().unwrap();
+    node.libra.take_all_transactions();
+    node.key_manager.compare_storage_to_config().unwrap_err();
+
+    // Each resubmission must expire before the next one is made. A restart in between neither
+    // resets the resubmissions made so far nor the expiration of the last one.
+    for i in 0..key_manager_config.max_resubmissions {
+        if i == 1 {
+            node.restart_key_manager();
+        }
+        node.update_libra_timestamp();
+        assert_eq!(
+            Action::NoAction,
+            node.key_manager.evaluate_status().unwrap()
+        );
+
+        node.time
+            .increment_by(key_manager_config.txn_expiration_secs);
+        node.update_libra_timestamp();
+        assert_eq!(
+            Action::SubmitKeyRotationTransaction,
+            node.key_manager.evaluate_status().unwrap()
+        );
+        node.update_libra_timestamp();
+        node.key_manager.execute_once().unwrap();
+        assert_eq!(1, node.libra.take_all_transactions().len());
+    }
+
+    // Once the last resubmission expires, the rotation is rolled back
+    node.time
+        .increment_by(key_manager_config.txn_expiration_secs);
+    node.update_libra_timestamp();
+    assert_eq!(
+        Action::RollbackKeyRotation,
+        node.key_manager.evaluate_status().unwrap()
+    );
+    node.update_libra_timestamp();
+    node.key_manager.execute_once().unwrap();
+    assert!(node.libra.take_all_transactions().is_empty());
+
+    // Storage is consistent with the blockchain again and nothing remains to be done
+    node.key_manager.compare_storage_to_config().unwrap();
+    assert_eq!(
+        genesis_config.consensus_public_key,
+        node.key_manager
+            .storage
+            .get_public_key(crate::CONSENSUS_KEY)
+            .unwrap()
+            .public_key
+    );
+    node.update_libra_timestamp();
+    assert_eq!(
+        Action::NoAction,
+        node.key_manager.evaluate_status().unwrap()
+    );
+}
+
+#[test]
+// This tests that a rollback which would not restore the consensus key registered on-chain leaves
+// secure storage untouched.
+fn test_rollback_key_mismatch() {
+    // Test the mock libra interface implementation
+    let node = setup_node_using_test_mocks();
+    verify_rollback_key_mismatch(node);
+
+    // Test the json libra interface implementation
+    let (node, _runtime) = setup_node_using_json_rpc();
+    verify_rollback_key_mismatch(node);
+}
+
+fn verify_rollback_key_mismatch<T: LibraInterface>(mut node: Node<T>) {
+    // Rotate twice without executing either transaction, so the previous key in storage is not
+    // the key registered on-chain
+    node.key_manager.rotate_consensus_key().unwrap();
+    let storage_key = node.key_manager.rotate_consensus_key().unwrap();
+    node.libra.take_all_transactions();
+
+    node.key_manager.rollback_consensus_key().unwrap_err();
+    assert_eq!(
+        storage_key,
+        node.key_manager
+            .storage
+            .get_public_key(crate::CONSENSUS_KEY)
+            .unwrap()
+            .public_key
+    );
+}
+
 #[test]
 // This test ensures that execute() will return an error and halt the key manager if something goes
 // wrong.
@@ -14,15 +14,31 @@
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
 //! where a block is a length prefixed array of bytes.
-
+//!
+//! Optionally, the client and server encrypt and authenticate their communications with the Noise
+//! IK handshake: the client must know the public key of the server, and the server only accepts
+//! the clients whose public keys it trusts. Messages are then split into encrypted blocks that fit
+//! in Noise messages.
+
+use libra_crypto::{
+    noise::{self, NoiseConfig, NoiseError, NoiseSession},
+    x25519,
+};
 use libra_logger::{debug, trace};
+use rand::rngs::OsRng;
 use std::{
     io::{Read, Write},
     net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
     thread, time,
 };
 use thiserror::Error;
 
+/// Prologue of the Noise handshake, which both ends must agree on
+const NOISE_PROLOGUE: &[u8] = b"libra-secure-net";
+/// The largest plaintext that fits in a Noise message
+const NOISE_BLOCK_SIZE: usize = noise::MAX_SIZE_NOISE_MSG - noise::AES_GCM_TAGLEN;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Already called shutdown")]
@@ -33,13 +49,20 @@ pub enum Error {
     NetworkError(#[from] std::io::Error),
     #[error("No active stream")]
     NoActiveStream,
+    #[error("Noise error: {0}")]
+    NoiseError(#[from] NoiseError),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("Remote peer is not trusted: {0}")]
+    UntrustedPeer(x25519::PublicKey),
 }
 
 pub struct NetworkClient {
     server: SocketAddr,
     stream: Option<NetworkStream>,
+    /// The Noise configuration of the client and the public key of the server, for secure
+    /// connections
+    noise: Option<(Arc<NoiseConfig>, x25519::PublicKey)>,
 }
 
 impl NetworkClient {
@@ -47,6 +70,21 @@ impl NetworkClient {
         Self {
             server,
             stream: None,
+            noise: None,
+        }
+    }
+
+    /// A client whose connections are encrypted and authenticated with `noise_config`, to a
+    /// server whose public key is `server_public_key`.
+    pub fn new_secure(
+        server: SocketAddr,
+        noise_config: Arc<NoiseConfig>,
+        server_public_key: x25519::PublicKey,
+    ) -> Self {
+        Self {
+            server,
+            stream: None,
+            noise: Some((noise_config, server_public_key)),
         }
     }
 
@@ -94,7 +132,15 @@ impl NetworkClient {
 
             let stream = stream?;
             stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream));
+            let mut stream = NetworkStream::new(stream);
+            if let Some((noise_config, server_public_key)) = &self.noise {
+                stream.session = Some(initiate_handshake(
+                    &mut stream,
+                    noise_config,
+                    *server_public_key,
+                )?);
+            }
+            self.stream = Some(stream);
             debug!("Connection established to upstream {}", self.server);
         }
 
@@ -105,6 +151,9 @@ impl NetworkClient {
 pub struct NetworkServer {
     listener: Option<TcpListener>,
     stream: Option<NetworkStream>,
+    /// The Noise configuration of the server and the public keys of the clients it trusts, for
+    /// secure connections
+    noise: Option<(Arc<NoiseConfig>, Arc<Vec<x25519::PublicKey>>)>,
 }
 
 impl NetworkServer {
@@ -113,9 +162,44 @@ impl NetworkServer {
         Self {
             listener: Some(listener),
             stream: None,
+            noise: None,
+        }
+    }
+
+    /// A server whose connections are encrypted and authenticated with `noise_config`, accepting
+    /// only the clients whose public keys are in `trusted_clients`.
+    pub fn new_secure(
+        listen: SocketAddr,
+        noise_config: NoiseConfig,
+        trusted_clients: Vec<x25519::PublicKey>,
+    ) -> Self {
+        let listener = TcpListener::bind(listen).unwrap();
+        Self {
+            listener: Some(listener),
+            stream: None,
+            noise: Some((Arc::new(noise_config), Arc::new(trusted_clients))),
         }
     }
 
+    /// Blocks until a downstream client connects and returns a server for this client only, so
+    /// that several clients can be served concurrently from their own threads. The returned
+    /// server does not accept other clients: once its client disconnects, it fails with
+    /// `Error::AlreadyShutdown`. The secure handshake, if any, happens on its first read or write.
+    pub fn accept(&mut self) -> Result<NetworkServer, Error> {
+        let listener = self
+            .listener
+            .as_mut()
+            .ok_or_else(|| Error::AlreadyShutdown)?;
+        let (stream, stream_addr) = listener.accept()?;
+        debug!("Connection established with downstream {}", stream_addr);
+        stream.set_nodelay(true)?;
+        Ok(NetworkServer {
+            listener: None,
+            stream: Some(NetworkStream::new(stream)),
+            noise: self.noise.clone(),
+        })
+    }
+
     /// If there isn't already a downstream client, it accepts. Otherwise it
     /// blocks until able to successfully read an entire message
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
@@ -162,14 +246,70 @@ impl NetworkServer {
             self.stream = Some(NetworkStream::new(stream));
         }
 
+        if let (Some((noise_config, trusted_clients)), Some(stream)) =
+            (self.noise.as_ref(), self.stream.as_mut())
+        {
+            if stream.session.is_none() {
+                match respond_to_handshake(stream, noise_config, trusted_clients) {
+                    Ok(session) => stream.session = Some(session),
+                    Err(e) => {
+                        debug!("Secure handshake with downstream failed, setting stream to None");
+                        self.stream = None;
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
         self.stream.as_mut().ok_or_else(|| Error::NoActiveStream)
     }
 }
 
+/// Performs the client side of the Noise handshake on a new stream.
+fn initiate_handshake(
+    stream: &mut NetworkStream,
+    noise_config: &NoiseConfig,
+    server_public_key: x25519::PublicKey,
+) -> Result<NoiseSession, Error> {
+    let mut init_message = vec![0; noise::handshake_init_msg_len(0)];
+    let handshake_state = noise_config.initiate_connection(
+        &mut OsRng,
+        NOISE_PROLOGUE,
+        server_public_key,
+        None,
+        &mut init_message,
+    )?;
+    stream.write(&init_message)?;
+    let response = stream.read()?;
+    let (_, session) = noise_config.finalize_connection(handshake_state, &response)?;
+    Ok(session)
+}
+
+/// Performs the server side of the Noise handshake on a new stream, rejecting untrusted clients.
+fn respond_to_handshake(
+    stream: &mut NetworkStream,
+    noise_config: &NoiseConfig,
+    trusted_clients: &[x25519::PublicKey],
+) -> Result<NoiseSession, Error> {
+    let init_message = stream.read()?;
+    let (client_public_key, handshake_state, _) =
+        noise_config.parse_client_init_message(NOISE_PROLOGUE, &init_message)?;
+    if !trusted_clients.contains(&client_public_key) {
+        return Err(Error::UntrustedPeer(client_public_key));
+    }
+    let mut response = vec![0; noise::handshake_resp_msg_len(0)];
+    let session =
+        noise_config.respond_to_client(&mut OsRng, handshake_state, None, &mut response)?;
+    stream.write(&response)?;
+    Ok(session)
+}
+
 struct NetworkStream {
     stream: TcpStream,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
+    /// The Noise session once the secure handshake is done, if any
+    session: Option<NoiseSession>,
 }
 
 impl NetworkStream {
@@ -178,11 +318,69 @@ impl NetworkStream {
             stream,
             buffer: Vec::new(),
             temp_buffer: [0; 1024],
+            session: None,
         }
     }
 
-    /// Blocking read until able to successfully read an entire message
+    /// Blocking read until able to successfully read an entire message. On a secure stream, a
+    /// message is a block carrying its length followed by as many blocks as needed for its data.
     pub fn read(&mut self) -> Result<Vec<u8>, Error> {
+        if self.session.is_none() {
+            return self.read_block();
+        }
+
+        let header = self.read_secure_block()?;
+        if header.len() != 4 {
+            return Err(NoiseError::MsgTooShort.into());
+        }
+        let mut u32_bytes = [0; 4];
+        u32_bytes.copy_from_slice(&header);
+        let data_size = u32::from_le_bytes(u32_bytes) as usize;
+
+        let mut data = Vec::with_capacity(data_size);
+        while data.len() < data_size {
+            data.extend(self.read_secure_block()?);
+        }
+        Ok(data)
+    }
+
+    /// Blocking write until able to successfully send an entire message
+    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+        let session = match self.session.as_mut() {
+            Some(session) => session,
+            None => return self.write_block(data),
+        };
+
+        if u32::max_value() as usize <= data.len() {
+            return Err(Error::DataTooLarge(data.len()));
+        }
+        let header = (data.len() as u32).to_le_bytes();
+        let blocks = std::iter::once(&header[..])
+            .chain(data.chunks(NOISE_BLOCK_SIZE))
+            .map(|plaintext| -> Result<Vec<u8>, Error> {
+                let mut block = plaintext.to_vec();
+                let authentication_tag = session.write_message_in_place(&mut block)?;
+                block.extend(authentication_tag);
+                Ok(block)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for block in blocks {
+            self.write_block(&block)?;
+        }
+        Ok(())
+    }
+
+    /// Blocking read of a block of a secure stream, decrypted
+    fn read_secure_block(&mut self) -> Result<Vec<u8>, Error> {
+        let mut block = self.read_block()?;
+        let session = self.session.as_mut().ok_or_else(|| Error::NoActiveStream)?;
+        let plaintext_len = session.read_message_in_place(&mut block)?.len();
+        block.truncate(plaintext_len);
+        Ok(block)
+    }
+
+    /// Blocking read until able to successfully read an entire block
+    fn read_block(&mut self) -> Result<Vec<u8>, Error> {
         let result = self.read_buffer();
         if !result.is_empty() {
             return Ok(result);
@@ -210,8 +408,8 @@ impl NetworkStream {
         Ok(self.stream.shutdown(Shutdown::Both)?)
     }
 
-    /// Blocking write until able to successfully send an entire message
-    pub fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+    /// Blocking write until able to successfully send an entire block
+    fn write_block(&mut self, data: &[u8]) -> Result<(), Error> {
         let u32_max = u32::max_value() as usize;
         if u32_max <= data.len() {
             return Err(Error::DataTooLarge(data.len()));
@@ -269,6 +467,7 @@ impl NetworkStream {
 mod test {
     use super::*;
     use libra_config::utils;
+    use libra_crypto::Uniform;
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
     #[test]
@@ -351,4 +550,88 @@ mod test {
         assert_eq!(data1, result1);
         assert_eq!(data2, result2);
     }
+
+    #[test]
+    fn test_secure_ping() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let server_key = x25519::PrivateKey::generate(&mut OsRng);
+        let client_key = x25519::PrivateKey::generate(&mut OsRng);
+        let server_public_key = server_key.public_key();
+        let mut server = NetworkServer::new_secure(
+            server_addr,
+            NoiseConfig::new(server_key),
+            vec![client_key.public_key()],
+        );
+        let mut client = NetworkClient::new_secure(
+            server_addr,
+            Arc::new(NoiseConfig::new(client_key)),
+            server_public_key,
+        );
+
+        // Messages larger than a Noise message are split.
+        let data = (0..3 * noise::MAX_SIZE_NOISE_MSG)
+            .map(|i| i as u8)
+            .collect::<Vec<_>>();
+        let expected = data.clone();
+        let server_thread = thread::spawn(move || {
+            let result = server.read().unwrap();
+            assert_eq!(expected, result);
+            server.write(&[4, 5, 6, 7]).unwrap();
+        });
+        client.write(&data).unwrap();
+        assert_eq!(client.read().unwrap(), vec![4, 5, 6, 7]);
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_secure_untrusted_client() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let server_key = x25519::PrivateKey::generate(&mut OsRng);
+        let server_public_key = server_key.public_key();
+        let trusted_key = x25519::PrivateKey::generate(&mut OsRng);
+        let mut server = NetworkServer::new_secure(
+            server_addr,
+            NoiseConfig::new(server_key),
+            vec![trusted_key.public_key()],
+        );
+        let mut client = NetworkClient::new_secure(
+            server_addr,
+            Arc::new(NoiseConfig::new(x25519::PrivateKey::generate(&mut OsRng))),
+            server_public_key,
+        );
+
+        let server_thread = thread::spawn(move || match server.read() {
+            Err(Error::UntrustedPeer(_)) => (),
+            result => panic!("Unexpected result {:?}", result),
+        });
+        assert!(client.write(&[0, 1, 2, 3]).is_err());
+        server_thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_accept() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut server = NetworkServer::new(server_addr);
+        let mut client1 = NetworkClient::new(server_addr);
+        let mut client2 = NetworkClient::new(server_addr);
+
+        client1.write(&[0, 1, 2, 3]).unwrap();
+        let mut connection1 = server.accept().unwrap();
+        client2.write(&[4, 5, 6, 7]).unwrap();
+        let mut connection2 = server.accept().unwrap();
+
+        // Clients are served independently.
+        assert_eq!(connection2.read().unwrap(), vec![4, 5, 6, 7]);
+        assert_eq!(connection1.read().unwrap(), vec![0, 1, 2, 3]);
+        connection1.write(&[8]).unwrap();
+        assert_eq!(client1.read().unwrap(), vec![8]);
+
+        // A connection does not accept other clients.
+        client1.shutdown().unwrap();
+        assert!(connection1.read().is_err());
+        assert!(connection1.read().is_err());
+    }
 }
@@ -0,0 +1,201 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, Kms, EXPIRATION_MARGIN_SECS, TIMEOUT};
+use chrono::{DateTime, Duration, Utc};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use std::sync::Mutex;
+
+const KMS_HOST: &str = "https://cloudkms.googleapis.com/v1";
+const METADATA_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Client provides a client around the restful interface to GCP Cloud Key Management Service.
+/// Learn more here: https://cloud.google.com/kms/docs/reference/rest
+///
+/// Requests are authorized with an OAuth 2.0 access token, either one provided by the operator or
+/// that of the service account attached to the Compute Engine instance, which is fetched from the
+/// metadata server and refreshed before it expires. The service account is expected to hold the
+/// roles/cloudkms.cryptoKeyEncrypterDecrypter role on the key.
+pub struct Client {
+    agent: ureq::Agent,
+    key_name: String,
+    provider: TokenProvider,
+    cached_token: Mutex<Option<AccessToken>>,
+}
+
+/// The source of the access token used to authorize requests.
+pub enum TokenProvider {
+    /// An access token obtained out of band, e.g., via `gcloud auth print-access-token`.
+    Static(String),
+    /// The default service account of the instance, as exposed by the metadata server.
+    InstanceMetadata,
+}
+
+#[derive(Clone)]
+struct AccessToken {
+    token: String,
+    expiration: DateTime<Utc>,
+}
+
+impl Client {
+    /// Creates a client for the key with the resource name `key_name`, i.e.,
+    /// projects/PROJECT/locations/LOCATION/keyRings/KEY_RING/cryptoKeys/KEY.
+    pub fn new(key_name: String, provider: TokenProvider) -> Self {
+        Self {
+            agent: ureq::Agent::new().set("connection", "keep-alive").build(),
+            key_name,
+            provider,
+            cached_token: Mutex::new(None),
+        }
+    }
+
+    fn token(&self) -> Result<String, Error> {
+        if let TokenProvider::Static(token) = &self.provider {
+            return Ok(token.clone());
+        }
+
+        let mut cached_token = self.cached_token.lock().unwrap();
+        let now = Utc::now();
+        match &*cached_token {
+            Some(token) if token.expiration - Duration::seconds(EXPIRATION_MARGIN_SECS) > now => {
+                Ok(token.token.clone())
+            }
+            _ => {
+                let resp = self
+                    .agent
+                    .get(METADATA_TOKEN_URL)
+                    .set("Metadata-Flavor", "Google")
+                    .timeout_connect(TIMEOUT)
+                    .call();
+                if !resp.ok() {
+                    return Err(resp.into());
+                }
+                let resp: AccessTokenResponse = serde_json::from_str(&resp.into_string()?)?;
+                let token = AccessToken {
+                    token: resp.access_token,
+                    expiration: now + Duration::seconds(resp.expires_in),
+                };
+                *cached_token = Some(token.clone());
+                Ok(token.token)
+            }
+        }
+    }
+
+    fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, Error> {
+        let resp = self.upgrade_request(self.agent.get(url))?.call();
+        if resp.ok() {
+            Ok(serde_json::from_str(&resp.into_string()?)?)
+        } else {
+            Err(resp.into())
+        }
+    }
+
+    fn post<T: DeserializeOwned>(&self, url: &str, body: serde_json::Value) -> Result<T, Error> {
+        let resp = self.upgrade_request(self.agent.post(url))?.send_json(body);
+        if resp.ok() {
+            Ok(serde_json::from_str(&resp.into_string()?)?)
+        } else {
+            Err(resp.into())
+        }
+    }
+
+    fn upgrade_request(&self, mut request: ureq::Request) -> Result<ureq::Request, Error> {
+        request.set("Authorization", &format!("Bearer {}", self.token()?));
+        request.timeout_connect(TIMEOUT);
+        Ok(request)
+    }
+}
+
+impl Kms for Client {
+    fn available(&self) -> Result<(), Error> {
+        let resp: CryptoKeyResponse = self.get(&format!("{}/{}", KMS_HOST, self.key_name))?;
+        let state = resp
+            .primary
+            .ok_or_else(|| Error::MissingField("primary".into()))?
+            .state;
+        if state == "ENABLED" {
+            Ok(())
+        } else {
+            Err(Error::KeyNotEnabled(format!(
+                "{}: {}",
+                self.key_name, state
+            )))
+        }
+    }
+
+    fn encrypt(&self, plaintext: &[u8], context: &str) -> Result<Vec<u8>, Error> {
+        let resp: EncryptResponse = self.post(
+            &format!("{}/{}:encrypt", KMS_HOST, self.key_name),
+            json!({
+                "plaintext": base64::encode(plaintext),
+                "additionalAuthenticatedData": base64::encode(context),
+            }),
+        )?;
+        Ok(base64::decode(&resp.ciphertext)?)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8], context: &str) -> Result<Vec<u8>, Error> {
+        let resp: DecryptResponse = self.post(
+            &format!("{}/{}:decrypt", KMS_HOST, self.key_name),
+            json!({
+                "ciphertext": base64::encode(ciphertext),
+                "additionalAuthenticatedData": base64::encode(context),
+            }),
+        )?;
+        Ok(base64::decode(&resp.plaintext)?)
+    }
+}
+
+/// {
+///   "access_token": "ya29.c.Ko8B...",
+///   "expires_in": 3599,
+///   "token_type": "Bearer"
+/// }
+#[derive(Debug, Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+/// Below is a sample output of a CryptoKey. Only the fields leveraged by this framework are
+/// decoded.
+/// {
+///   "name": "projects/libra/locations/global/keyRings/validator/cryptoKeys/storage",
+///   "primary": {
+///     "name": ".../cryptoKeyVersions/1",
+///     "state": "ENABLED",
+///     ...
+///   },
+///   "purpose": "ENCRYPT_DECRYPT",
+///   ...
+/// }
+#[derive(Debug, Deserialize)]
+struct CryptoKeyResponse {
+    primary: Option<CryptoKeyVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CryptoKeyVersion {
+    state: String,
+}
+
+/// {
+///   "name": ".../cryptoKeyVersions/1",
+///   "ciphertext": "CiQAqD+xX...",
+///   ...
+/// }
+#[derive(Debug, Deserialize)]
+struct EncryptResponse {
+    ciphertext: String,
+}
+
+/// {
+///   "plaintext": "bGlicmE=",
+///   ...
+/// }
+#[derive(Debug, Deserialize)]
+struct DecryptResponse {
+    plaintext: String,
+}
@@ -0,0 +1,88 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Minimal restful clients for the key management services of AWS and GCP.
+//!
+//! Neither service supports ed25519, so keys are not generated within the KMS. Instead, a
+//! symmetric key held by the KMS encrypts and decrypts data on behalf of the caller, e.g., a
+//! secure storage engine persisting ed25519 private keys. Both services embed the version of the
+//! key within the ciphertext, so rotating the KMS key (automatically on AWS or by promoting a new
+//! primary version on GCP) requires no changes here: data is decrypted with the version that
+//! encrypted it, while new data is encrypted with the latest version.
+
+pub mod aws;
+pub mod gcp;
+
+use thiserror::Error;
+
+/// Request timeout for KMS operations
+const TIMEOUT: u64 = 10_000;
+/// Credentials are refreshed this many seconds before they expire
+const EXPIRATION_MARGIN_SECS: i64 = 300;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("Http error: {0}: {1}")]
+    HttpError(u16, String),
+    #[error("Internal error: {0}")]
+    InternalError(String),
+    #[error("Key is not enabled: {0}")]
+    KeyNotEnabled(String),
+    #[error("Missing field {0}")]
+    MissingField(String),
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+}
+
+impl From<base64::DecodeError> for Error {
+    fn from(error: base64::DecodeError) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<chrono::ParseError> for Error {
+    fn from(error: chrono::ParseError) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+impl From<ureq::Response> for Error {
+    fn from(resp: ureq::Response) -> Self {
+        let status = resp.status();
+        let status_line = resp.status_line().to_string();
+        // Both services describe failures within the body, which is far more useful than the
+        // status line.
+        match resp.into_string() {
+            Ok(body) if !body.is_empty() => Error::HttpError(status, body),
+            _ => Error::HttpError(status, status_line),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::SerializationError(format!("{}", error))
+    }
+}
+
+/// The operations offered by a key management service on a single symmetric key.
+pub trait Kms: Send + Sync {
+    /// Returns an error if the key cannot currently be used, e.g., it has been disabled or the
+    /// service is not reachable with the configured credentials.
+    fn available(&self) -> Result<(), Error>;
+
+    /// Encrypts the plaintext with the latest version of the key. The ciphertext is bound to the
+    /// context, which is authenticated but not encrypted, and must be provided again to decrypt.
+    fn encrypt(&self, plaintext: &[u8], context: &str) -> Result<Vec<u8>, Error>;
+
+    /// Decrypts a ciphertext produced by `encrypt` with any version of the key.
+    fn decrypt(&self, ciphertext: &[u8], context: &str) -> Result<Vec<u8>, Error>;
+}
@@ -0,0 +1,409 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    vault::VaultEngine, CryptoStorage, Error, GetResponse, KVStorage, Policy, PublicKeyResponse,
+    Storage, Value,
+};
+use fs2::FileExt;
+use libra_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    HashValue,
+};
+use libra_secure_time::{RealTimeService, TimeService};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// The operations on secure storage that are recorded in an audit log.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    Get,
+    Set,
+    CreateKey,
+    ExportPrivateKey,
+    ExportPrivateKeyForVersion,
+    ImportPrivateKey,
+    GetPublicKey,
+    GetPreviousPublicKey,
+    RotateKey,
+    RotateKeyTo,
+    RollbackKey,
+    SignMessage,
+    SignMessageUsingVersion,
+    SetPolicies,
+}
+
+/// A single record within an audit log. Each entry commits to its predecessor via
+/// `previous_hash`, so that removing, reordering or modifying entries breaks the chain from that
+/// point onward. The first entry in a log has a `previous_hash` of zero.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AuditEntry {
+    /// The position of this entry in the log, starting at 0
+    pub sequence_number: u64,
+    /// Time since Unix Epoch in seconds.
+    pub timestamp: u64,
+    /// The identity of the component that performed the operation, e.g., safety_rules
+    pub identity: String,
+    pub operation: AuditOperation,
+    /// The key or value name that the operation accessed
+    pub name: String,
+    /// Whether the underlying storage completed the operation successfully
+    pub succeeded: bool,
+    pub previous_hash: HashValue,
+    /// The SHA3-256 hash of the LCS encoding of all of the above fields
+    pub hash: HashValue,
+}
+
+/// The fields of an AuditEntry covered by its hash.
+#[derive(Serialize)]
+struct AuditEntryContents<'a> {
+    sequence_number: u64,
+    timestamp: u64,
+    identity: &'a str,
+    operation: AuditOperation,
+    name: &'a str,
+    succeeded: bool,
+    previous_hash: HashValue,
+}
+
+impl AuditEntry {
+    fn new(
+        sequence_number: u64,
+        timestamp: u64,
+        identity: String,
+        operation: AuditOperation,
+        name: String,
+        succeeded: bool,
+        previous_hash: HashValue,
+    ) -> Result<Self, Error> {
+        let mut entry = Self {
+            sequence_number,
+            timestamp,
+            identity,
+            operation,
+            name,
+            succeeded,
+            previous_hash,
+            hash: HashValue::zero(),
+        };
+        entry.hash = entry.compute_hash()?;
+        Ok(entry)
+    }
+
+    fn compute_hash(&self) -> Result<HashValue, Error> {
+        let contents = AuditEntryContents {
+            sequence_number: self.sequence_number,
+            timestamp: self.timestamp,
+            identity: &self.identity,
+            operation: self.operation,
+            name: &self.name,
+            succeeded: self.succeeded,
+            previous_hash: self.previous_hash,
+        };
+        Ok(HashValue::sha3_256_of(&lcs::to_bytes(&contents)?))
+    }
+}
+
+/// An append-only, hash-chained log persisted to the local filesystem with one JSON encoded
+/// AuditEntry per line. Every append is synced to disk before returning. Note, the chain detects
+/// tampering with or truncation of intermediate entries, but an attacker with write access to the
+/// file can still drop entries from the end of the log. Operators should therefore ship the log
+/// (or at least its latest hash) to a separate system.
+///
+/// Several AuditLogs, in the same or in different processes, may share a file: each append holds
+/// an exclusive lock on the file and extends whichever entry is last at that point.
+pub struct AuditLog {
+    file_path: PathBuf,
+    /// The end of the log as of the last append through this AuditLog
+    tail: Mutex<Tail>,
+    time_service: RealTimeService,
+}
+
+/// The end of a log, which the next entry extends.
+struct Tail {
+    /// The length of the file, so that entries appended by other writers are noticed
+    file_len: u64,
+    sequence_number: u64,
+    previous_hash: HashValue,
+}
+
+impl Tail {
+    /// Reads the end of the log from the given file.
+    fn read(file: &File) -> Result<Self, Error> {
+        let file_len = file.metadata()?.len();
+        let mut last_line = None;
+        for line in BufReader::new(file).lines() {
+            last_line = Some(line?);
+        }
+        let (sequence_number, previous_hash) = match last_line {
+            Some(line) => {
+                let entry: AuditEntry = serde_json::from_str(&line)?;
+                (entry.sequence_number + 1, entry.hash)
+            }
+            None => (0, HashValue::zero()),
+        };
+        Ok(Self {
+            file_len,
+            sequence_number,
+            previous_hash,
+        })
+    }
+}
+
+impl AuditLog {
+    /// Opens the log at the given path, creating it if it does not exist. New entries extend the
+    /// existing chain, which must verify.
+    pub fn open(file_path: PathBuf) -> Result<Self, Error> {
+        let file = Self::open_file(&file_path)?;
+        file.lock_exclusive()?;
+        Self::verify(&file_path)?;
+        let tail = Tail::read(&file)?;
+
+        Ok(Self {
+            file_path,
+            tail: Mutex::new(tail),
+            time_service: RealTimeService::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// Appends a new entry to the log and returns it.
+    pub fn append(
+        &self,
+        identity: &str,
+        operation: AuditOperation,
+        name: &str,
+        succeeded: bool,
+    ) -> Result<AuditEntry, Error> {
+        let mut tail = self.tail.lock().unwrap();
+        // The lock is released once the file is closed at the end of this scope
+        let mut file = Self::open_file(&self.file_path)?;
+        file.lock_exclusive()?;
+        if file.metadata()?.len() != tail.file_len {
+            *tail = Tail::read(&file)?;
+        }
+
+        let entry = AuditEntry::new(
+            tail.sequence_number,
+            self.time_service.now(),
+            identity.into(),
+            operation,
+            name.into(),
+            succeeded,
+            tail.previous_hash,
+        )?;
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        file.sync_data()?;
+
+        *tail = Tail {
+            file_len: tail.file_len + line.len() as u64,
+            sequence_number: entry.sequence_number + 1,
+            previous_hash: entry.hash,
+        };
+        Ok(entry)
+    }
+
+    fn open_file(file_path: &Path) -> Result<File, Error> {
+        Ok(OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(file_path)?)
+    }
+
+    /// Reads every entry in the log at the given path and verifies the hash chain, returning the
+    /// entries in order if and only if the entire log is intact.
+    pub fn verify(file_path: &Path) -> Result<Vec<AuditEntry>, Error> {
+        let file = File::open(file_path)?;
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        let mut previous_hash = HashValue::zero();
+
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let entry: AuditEntry = serde_json::from_str(&line).map_err(|e| {
+                Error::AuditLogInvalid(format!("Unable to parse entry {}: {}", index, e))
+            })?;
+
+            if entry.sequence_number != index as u64 {
+                return Err(Error::AuditLogInvalid(format!(
+                    "Entry {} has sequence number {}",
+                    index, entry.sequence_number
+                )));
+            }
+            if entry.previous_hash != previous_hash {
+                return Err(Error::AuditLogInvalid(format!(
+                    "Entry {} does not extend the chain",
+                    index
+                )));
+            }
+            if entry.hash != entry.compute_hash()? {
+                return Err(Error::AuditLogInvalid(format!(
+                    "Entry {} does not match its hash",
+                    index
+                )));
+            }
+
+            previous_hash = entry.hash;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+/// AuditedStorage records every operation performed through it, along with the identity of the
+/// caller, in an AuditLog before returning the result of the underlying storage. Failed operations
+/// are recorded as well. If an entry cannot be appended to the log, the operation fails, even if
+/// the underlying storage completed it, so that no access goes unrecorded.
+pub struct AuditedStorage {
+    identity: String,
+    log: AuditLog,
+    inner: Box<Storage>,
+}
+
+impl AuditedStorage {
+    pub fn new(storage: Storage, log: AuditLog, identity: String) -> Self {
+        Self {
+            identity,
+            log,
+            inner: Box::new(storage),
+        }
+    }
+
+    pub fn log(&self) -> &AuditLog {
+        &self.log
+    }
+
+    /// Applies the given policy to the name within Vault, the only storage engine that enforces
+    /// policies, and records the change.
+    pub fn set_policies(
+        &mut self,
+        name: &str,
+        engine: &VaultEngine,
+        policy: &Policy,
+    ) -> Result<(), Error> {
+        let result = match &*self.inner {
+            Storage::VaultStorage(storage) => storage.set_policies(name, engine, policy),
+            _ => Err(Error::InternalError(
+                "Policies are only supported by Vault".into(),
+            )),
+        };
+        self.record(AuditOperation::SetPolicies, name, result)
+    }
+
+    fn record<T>(
+        &self,
+        operation: AuditOperation,
+        name: &str,
+        result: Result<T, Error>,
+    ) -> Result<T, Error> {
+        self.log
+            .append(&self.identity, operation, name, result.is_ok())?;
+        result
+    }
+}
+
+impl KVStorage for AuditedStorage {
+    fn available(&self) -> Result<(), Error> {
+        self.inner.available()
+    }
+
+    fn get(&self, key: &str) -> Result<GetResponse, Error> {
+        self.record(AuditOperation::Get, key, self.inner.get(key))
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), Error> {
+        let result = self.inner.set(key, value);
+        self.record(AuditOperation::Set, key, result)
+    }
+
+    /// Note: this clears the underlying storage but not the audit log
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.inner.reset_and_clear()
+    }
+}
+
+impl CryptoStorage for AuditedStorage {
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let result = self.inner.create_key(name);
+        self.record(AuditOperation::CreateKey, name, result)
+    }
+
+    fn export_private_key(&self, name: &str) -> Result<Ed25519PrivateKey, Error> {
+        let result = self.inner.export_private_key(name);
+        self.record(AuditOperation::ExportPrivateKey, name, result)
+    }
+
+    fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
+        let result = self.inner.import_private_key(name, key);
+        self.record(AuditOperation::ImportPrivateKey, name, result)
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, Error> {
+        let result = self.inner.export_private_key_for_version(name, version);
+        self.record(AuditOperation::ExportPrivateKeyForVersion, name, result)
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
+        let result = self.inner.get_public_key(name);
+        self.record(AuditOperation::GetPublicKey, name, result)
+    }
+
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let result = self.inner.rotate_key(name);
+        self.record(AuditOperation::RotateKey, name, result)
+    }
+
+    fn rotate_key_to(
+        &mut self,
+        name: &str,
+        key: Ed25519PrivateKey,
+    ) -> Result<Ed25519PublicKey, Error> {
+        let result = self.inner.rotate_key_to(name, key);
+        self.record(AuditOperation::RotateKeyTo, name, result)
+    }
+
+    fn get_previous_public_key(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let result = self.inner.get_previous_public_key(name);
+        self.record(AuditOperation::GetPreviousPublicKey, name, result)
+    }
+
+    fn rollback_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let result = self.inner.rollback_key(name);
+        self.record(AuditOperation::RollbackKey, name, result)
+    }
+
+    fn sign_message(&mut self, name: &str, message: &HashValue) -> Result<Ed25519Signature, Error> {
+        let result = self.inner.sign_message(name, message);
+        self.record(AuditOperation::SignMessage, name, result)
+    }
+
+    fn sign_message_using_version(
+        &mut self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &HashValue,
+    ) -> Result<Ed25519Signature, Error> {
+        let result = self
+            .inner
+            .sign_message_using_version(name, version, message);
+        self.record(AuditOperation::SignMessageUsingVersion, name, result)
+    }
+}
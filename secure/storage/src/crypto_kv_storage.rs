@@ -70,20 +70,41 @@ impl<T: CryptoKVStorage> CryptoStorage for T {
     }
 
     fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let (new_private_key, _) = new_ed25519_key_pair()?;
+        self.rotate_key_to(name, new_private_key)
+    }
+
+    fn rotate_key_to(
+        &mut self,
+        name: &str,
+        key: Ed25519PrivateKey,
+    ) -> Result<Ed25519PublicKey, Error> {
         match self.get(name)?.value {
             Value::Ed25519PrivateKey(private_key) => {
-                let (new_private_key, new_public_key) = new_ed25519_key_pair()?;
+                let new_public_key = key.public_key();
                 self.set(
                     &get_previous_version_name(name),
                     Value::Ed25519PrivateKey(private_key),
                 )?;
-                self.set(name, Value::Ed25519PrivateKey(new_private_key))?;
+                self.set(name, Value::Ed25519PrivateKey(key))?;
                 Ok(new_public_key)
             }
             _ => Err(Error::UnexpectedValueType),
         }
     }
 
+    fn get_previous_public_key(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        self.export_private_key(&get_previous_version_name(name))
+            .map(|previous_private_key| previous_private_key.public_key())
+    }
+
+    fn rollback_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let previous_private_key = self.export_private_key(&get_previous_version_name(name))?;
+        let previous_public_key = previous_private_key.public_key();
+        self.set(name, Value::Ed25519PrivateKey(previous_private_key))?;
+        Ok(previous_public_key)
+    }
+
     fn sign_message(&mut self, name: &str, message: &HashValue) -> Result<Ed25519Signature, Error> {
         let private_key = self.export_private_key(name)?;
         Ok(private_key.sign_message(message))
@@ -63,6 +63,30 @@ pub trait CryptoStorage: Send + Sync {
     /// key for the rotated key pair is returned.
     fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error>;
 
+    /// Rotates an Ed25519 key pair like `rotate_key`, except that 'name' is updated to reference
+    /// the given private key instead of a freshly generated one (e.g., a key derived from a backed
+    /// up seed). Storage engines that only generate keys internally do not support this and fail
+    /// with an error. Otherwise, the public key of the given private key is returned.
+    fn rotate_key_to(
+        &mut self,
+        name: &str,
+        key: Ed25519PrivateKey,
+    ) -> Result<Ed25519PublicKey, Error>;
+
+    /// Returns the public key of the previous version of an Ed25519 key pair, i.e., the key pair
+    /// that `rollback_key` would restore, without changing which version 'name' references. If
+    /// the key pair has no previous version, or the caller doesn't have the appropriate
+    /// permissions, this call will fail with an error.
+    fn get_previous_public_key(&self, name: &str) -> Result<Ed25519PublicKey, Error>;
+
+    /// Reverts the most recent rotation of an Ed25519 key pair, so that 'name' references the
+    /// previous key pair again and the current key pair is no longer used. This allows recovering
+    /// from a rotation that could not be put into effect elsewhere (e.g., on-chain). If the key
+    /// pair has no previous version, or the caller doesn't have the appropriate permissions, this
+    /// call will fail with an error. Otherwise, the public key of the restored key pair is
+    /// returned.
+    fn rollback_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error>;
+
     /// Signs the given message using the private key associated with the given 'name'.
     /// If the key pair doesn't exist, or the caller doesn't have the appropriate
     /// permissions to retrieve and use the public key, this call will fail with an error.
@@ -7,6 +7,8 @@ use thiserror::Error;
 
 #[derive(Debug, Deserialize, Error, PartialEq, Serialize)]
 pub enum Error {
+    #[error("Audit log invalid: {0}")]
+    AuditLogInvalid(String),
     #[error("Entropy error: {0}")]
     EntropyError(String),
     #[error("Internal error: {0}")]
@@ -74,3 +76,12 @@ impl From<libra_github_client::Error> for Error {
         }
     }
 }
+
+impl From<libra_kms_client::Error> for Error {
+    fn from(error: libra_kms_client::Error) -> Self {
+        match error {
+            libra_kms_client::Error::HttpError(403, _) => Self::PermissionDenied,
+            _ => Self::InternalError(format!("{}", error)),
+        }
+    }
+}
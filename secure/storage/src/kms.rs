@@ -0,0 +1,59 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoKVStorage, Error, GetResponse, KVStorage, Value};
+use libra_kms_client::Kms;
+
+/// KmsStorage protects the values of an underlying KV storage with a key held by a cloud key
+/// management service, i.e., AWS KMS or GCP KMS. Neither service supports ed25519, so rather than
+/// signing within the service, every value is encrypted by the service before being handed to the
+/// underlying storage and decrypted on retrieval. Consequently, private keys are never persisted
+/// in plaintext and access to them is governed by the IAM policies of the service.
+///
+/// Each ciphertext is bound to the name under which it is stored, so values cannot be swapped
+/// between names by tampering with the underlying storage. Cryptographic operations, including key
+/// rotation, are offered via CryptoKVStorage.
+pub struct KmsStorage {
+    kms: Box<dyn Kms>,
+    inner: Box<dyn KVStorage>,
+}
+
+impl KmsStorage {
+    pub fn new(kms: Box<dyn Kms>, storage: Box<dyn KVStorage>) -> Self {
+        Self {
+            kms,
+            inner: storage,
+        }
+    }
+}
+
+impl KVStorage for KmsStorage {
+    fn available(&self) -> Result<(), Error> {
+        self.inner.available()?;
+        Ok(self.kms.available()?)
+    }
+
+    fn get(&self, key: &str) -> Result<GetResponse, Error> {
+        let response = self.inner.get(key)?;
+        let ciphertext = base64::decode(&response.value.string()?)?;
+        let plaintext = self.kms.decrypt(&ciphertext, key)?;
+        Ok(GetResponse {
+            last_update: response.last_update,
+            value: serde_json::from_slice(&plaintext)?,
+        })
+    }
+
+    fn set(&mut self, key: &str, value: Value) -> Result<(), Error> {
+        let plaintext = serde_json::to_vec(&value)?;
+        let ciphertext = self.kms.encrypt(&plaintext, key)?;
+        self.inner
+            .set(key, Value::String(base64::encode(&ciphertext)))
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.inner.reset_and_clear()
+    }
+}
+
+impl CryptoKVStorage for KmsStorage {}
@@ -3,11 +3,13 @@
 
 #![forbid(unsafe_code)]
 
+mod audit;
 mod crypto_kv_storage;
 mod crypto_storage;
 mod error;
 mod github;
 mod in_memory;
+mod kms;
 mod kv_storage;
 mod namespaced_storage;
 mod on_disk;
@@ -17,11 +19,13 @@ mod value;
 mod vault;
 
 pub use crate::{
+    audit::{AuditEntry, AuditLog, AuditOperation, AuditedStorage},
     crypto_kv_storage::CryptoKVStorage,
     crypto_storage::{CryptoStorage, PublicKeyResponse},
     error::Error,
     github::GitHubStorage,
     in_memory::{InMemoryStorage, InMemoryStorageInternal},
+    kms::KmsStorage,
     kv_storage::{GetResponse, KVStorage},
     namespaced_storage::NamespacedStorage,
     on_disk::{OnDiskStorage, OnDiskStorageInternal},
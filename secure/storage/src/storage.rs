@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    CryptoStorage, Error, GetResponse, GitHubStorage, InMemoryStorage, KVStorage,
-    NamespacedStorage, OnDiskStorage, PublicKeyResponse, Value, VaultStorage,
+    AuditedStorage, CryptoStorage, Error, GetResponse, GitHubStorage, InMemoryStorage, KVStorage,
+    KmsStorage, NamespacedStorage, OnDiskStorage, PublicKeyResponse, Value, VaultStorage,
 };
 use enum_dispatch::enum_dispatch;
 use libra_crypto::{
@@ -19,9 +19,11 @@ use libra_crypto::{
 /// T: Storage. This boilerplate can be 100% generated by a proc macro.
 #[enum_dispatch(KVStorage, CryptoStorage)]
 pub enum Storage {
+    AuditedStorage(AuditedStorage),
     GitHubStorage(GitHubStorage),
     VaultStorage(VaultStorage),
     InMemoryStorage(InMemoryStorage),
+    KmsStorage(KmsStorage),
     NamespacedStorage(NamespacedStorage),
     OnDiskStorage(OnDiskStorage),
 }
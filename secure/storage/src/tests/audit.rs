@@ -0,0 +1,149 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    tests::suite, vault::VaultEngine, AuditLog, AuditOperation, AuditedStorage, CryptoStorage,
+    Error, InMemoryStorage, KVStorage, Policy, Storage, Value,
+};
+use libra_crypto::HashValue;
+use libra_temppath::TempPath;
+use std::fs;
+
+fn audited_storage(log_path: &TempPath) -> AuditedStorage {
+    let log = AuditLog::open(log_path.path().to_path_buf()).unwrap();
+    AuditedStorage::new(
+        Storage::from(InMemoryStorage::new()),
+        log,
+        "safety_rules".into(),
+    )
+}
+
+#[test]
+fn audit() {
+    let log_path = TempPath::new();
+    let mut storage = Storage::from(audited_storage(&log_path));
+    suite::execute_all_storage_tests(&mut storage);
+    AuditLog::verify(log_path.path()).unwrap();
+}
+
+#[test]
+fn audit_records_operations() {
+    let log_path = TempPath::new();
+    let mut storage = audited_storage(&log_path);
+
+    storage.set("value", Value::U64(1)).unwrap();
+    storage.get("value").unwrap();
+    storage.get("missing").unwrap_err();
+    storage.create_key("key").unwrap();
+    storage.sign_message("key", &HashValue::zero()).unwrap();
+    storage.rotate_key("key").unwrap();
+
+    let entries = AuditLog::verify(log_path.path()).unwrap();
+    let operations: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.operation, entry.name.as_str(), entry.succeeded))
+        .collect();
+    assert_eq!(
+        operations,
+        vec![
+            (AuditOperation::Set, "value", true),
+            (AuditOperation::Get, "value", true),
+            (AuditOperation::Get, "missing", false),
+            (AuditOperation::CreateKey, "key", true),
+            (AuditOperation::SignMessage, "key", true),
+            (AuditOperation::RotateKey, "key", true),
+        ]
+    );
+    assert!(entries.iter().all(|entry| entry.identity == "safety_rules"));
+
+    // Policies can only be applied to Vault, but the attempt is still recorded.
+    storage
+        .set_policies("key", &VaultEngine::Transit, &Policy::public())
+        .unwrap_err();
+    let entries = AuditLog::verify(log_path.path()).unwrap();
+    assert_eq!(
+        entries.last().unwrap().operation,
+        AuditOperation::SetPolicies
+    );
+}
+
+#[test]
+fn audit_log_extends_existing_chain() {
+    let log_path = TempPath::new();
+    audited_storage(&log_path)
+        .set("value", Value::U64(1))
+        .unwrap();
+    audited_storage(&log_path).get("value").unwrap_err();
+
+    let entries = AuditLog::verify(log_path.path()).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].sequence_number, 1);
+    assert_eq!(entries[1].previous_hash, entries[0].hash);
+}
+
+#[test]
+fn audit_logs_share_a_file() {
+    let log_path = TempPath::new();
+    let log_1 = AuditLog::open(log_path.path().to_path_buf()).unwrap();
+    let log_2 = AuditLog::open(log_path.path().to_path_buf()).unwrap();
+
+    log_1
+        .append("safety_rules", AuditOperation::Get, "a", true)
+        .unwrap();
+    log_2
+        .append("key_manager", AuditOperation::Get, "b", true)
+        .unwrap();
+    let entry = log_1
+        .append("safety_rules", AuditOperation::Set, "c", true)
+        .unwrap();
+    assert_eq!(entry.sequence_number, 2);
+
+    let entries = AuditLog::verify(log_path.path()).unwrap();
+    let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn audit_log_detects_tampering() {
+    let log_path = TempPath::new();
+    let mut storage = audited_storage(&log_path);
+    storage.set("value", Value::U64(1)).unwrap();
+    storage.get("value").unwrap();
+    storage.create_key("key").unwrap();
+
+    let contents = fs::read_to_string(log_path.path()).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+
+    // Modifying an entry
+    fs::write(
+        log_path.path(),
+        contents.replace("\"safety_rules\"", "\"operator\""),
+    )
+    .unwrap();
+    assert!(matches!(
+        AuditLog::verify(log_path.path()).unwrap_err(),
+        Error::AuditLogInvalid(_)
+    ));
+    assert!(AuditLog::open(log_path.path().to_path_buf()).is_err());
+
+    // Removing an entry
+    fs::write(log_path.path(), format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+    assert!(matches!(
+        AuditLog::verify(log_path.path()).unwrap_err(),
+        Error::AuditLogInvalid(_)
+    ));
+
+    // Reordering entries
+    fs::write(
+        log_path.path(),
+        format!("{}\n{}\n{}\n", lines[1], lines[0], lines[2]),
+    )
+    .unwrap();
+    assert!(matches!(
+        AuditLog::verify(log_path.path()).unwrap_err(),
+        Error::AuditLogInvalid(_)
+    ));
+
+    fs::write(log_path.path(), contents).unwrap();
+    assert_eq!(AuditLog::verify(log_path.path()).unwrap().len(), 3);
+}
@@ -1,10 +1,31 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{tests::suite, InMemoryStorage, Storage};
+use crate::{tests::suite, CryptoStorage, InMemoryStorage, Storage};
+use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use rand::{rngs::StdRng, SeedableRng};
 
 #[test]
 fn in_memory() {
     let mut storage = Storage::from(InMemoryStorage::new());
     suite::execute_all_storage_tests(&mut storage);
 }
+
+#[test]
+fn rotate_key_to() {
+    let mut storage = Storage::from(InMemoryStorage::new());
+    let public_key = storage.create_key("key").unwrap();
+
+    let mut rng = StdRng::from_seed([7u8; 32]);
+    let private_key = Ed25519PrivateKey::generate(&mut rng);
+    let rotated_public_key = private_key.public_key();
+    assert_eq!(
+        storage.rotate_key_to("key", private_key).unwrap(),
+        rotated_public_key
+    );
+    assert_eq!(
+        storage.get_public_key("key").unwrap().public_key,
+        rotated_public_key
+    );
+    assert_eq!(storage.rollback_key("key").unwrap(), public_key);
+}
@@ -0,0 +1,81 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, Error, KVStorage, KmsStorage, OnDiskStorage, Storage, Value};
+use libra_kms_client::Kms;
+use libra_temppath::TempPath;
+
+/// A stand-in for a cloud KMS: the ciphertext is the context followed by the masked plaintext,
+/// which is sufficient to verify that values are not stored in the clear and are bound to their
+/// context.
+struct TestKms;
+
+const MASK: u8 = 0x5a;
+
+impl Kms for TestKms {
+    fn available(&self) -> Result<(), libra_kms_client::Error> {
+        Ok(())
+    }
+
+    fn encrypt(&self, plaintext: &[u8], context: &str) -> Result<Vec<u8>, libra_kms_client::Error> {
+        let mut ciphertext = context.as_bytes().to_vec();
+        ciphertext.extend(plaintext.iter().map(|byte| byte ^ MASK));
+        Ok(ciphertext)
+    }
+
+    fn decrypt(
+        &self,
+        ciphertext: &[u8],
+        context: &str,
+    ) -> Result<Vec<u8>, libra_kms_client::Error> {
+        if !ciphertext.starts_with(context.as_bytes()) {
+            return Err(libra_kms_client::Error::HttpError(
+                400,
+                "InvalidCiphertextException".into(),
+            ));
+        }
+        Ok(ciphertext[context.len()..]
+            .iter()
+            .map(|byte| byte ^ MASK)
+            .collect())
+    }
+}
+
+#[test]
+fn kms() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let storage = OnDiskStorage::new(path_buf);
+    let mut storage = Storage::from(KmsStorage::new(Box::new(TestKms), Box::new(storage)));
+    suite::execute_all_storage_tests(&mut storage);
+}
+
+#[test]
+fn kms_values_are_encrypted_and_bound_to_keys() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut kms = KmsStorage::new(
+        Box::new(TestKms),
+        Box::new(OnDiskStorage::new(path_buf.clone())),
+    );
+    let mut on_disk = OnDiskStorage::new(path_buf);
+
+    kms.set("key", Value::String("secret".into())).unwrap();
+    assert_eq!(
+        kms.get("key").unwrap().value,
+        Value::String("secret".into())
+    );
+
+    // Only the ciphertext reaches the underlying storage.
+    let ciphertext = on_disk.get("key").unwrap().value;
+    assert_ne!(ciphertext, Value::String("secret".into()));
+
+    // A ciphertext moved to another key can no longer be decrypted.
+    on_disk.set("other_key", ciphertext).unwrap();
+    assert!(kms.get("other_key").is_err());
+
+    // Values not written through KmsStorage are rejected.
+    on_disk.set("plain_key", Value::U64(1)).unwrap();
+    assert_eq!(
+        kms.get("plain_key").unwrap_err(),
+        Error::UnexpectedValueType
+    );
+}
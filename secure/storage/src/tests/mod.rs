@@ -1,8 +1,10 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod audit;
 mod github;
 mod in_memory;
+mod kms;
 mod on_disk;
 mod suite;
 mod vault;
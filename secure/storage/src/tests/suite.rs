@@ -18,6 +18,7 @@ const STORAGE_TESTS: &[fn(&mut Storage)] = &[
     test_create_and_get_non_existent_version,
     test_create_get_key_pair,
     test_create_key_pair_and_perform_rotations,
+    test_create_rotate_rollback,
     test_create_sign_rotate_sign,
     test_ensure_storage_is_available,
     test_get_non_existent,
@@ -269,6 +270,53 @@ fn test_create_key_pair_and_perform_rotations(storage: &mut Storage) {
     }
 }
 
+/// This test creates a new key pair, rotates the key pair, rolls back the rotation and asserts the
+/// original key pair is used again.
+fn test_create_rotate_rollback(storage: &mut Storage) {
+    let public_key = storage.create_key(CRYPTO_NAME).unwrap();
+    let private_key = storage.export_private_key(CRYPTO_NAME).unwrap();
+
+    // Without a rotation there is nothing to roll back to
+    storage.get_previous_public_key(CRYPTO_NAME).unwrap_err();
+    storage.rollback_key(CRYPTO_NAME).unwrap_err();
+
+    let rotated_public_key = storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert_ne!(public_key, rotated_public_key);
+    assert_eq!(
+        public_key,
+        storage.get_previous_public_key(CRYPTO_NAME).unwrap()
+    );
+    assert_eq!(
+        rotated_public_key,
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key
+    );
+
+    assert_eq!(public_key, storage.rollback_key(CRYPTO_NAME).unwrap());
+    assert_eq!(
+        public_key,
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key
+    );
+    assert_eq!(
+        private_key,
+        storage.export_private_key(CRYPTO_NAME).unwrap()
+    );
+
+    let message = HashValue::new([1; HashValue::LENGTH]);
+    let signature = storage.sign_message(CRYPTO_NAME, &message).unwrap();
+    assert!(signature.verify(&message, &public_key).is_ok());
+
+    // Rotating after a rollback yields a fresh key pair
+    let new_public_key = storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert_ne!(public_key, new_public_key);
+    assert_ne!(rotated_public_key, new_public_key);
+    assert_eq!(
+        new_public_key,
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key
+    );
+    let signature = storage.sign_message(CRYPTO_NAME, &message).unwrap();
+    assert!(signature.verify(&message, &new_public_key).is_ok());
+}
+
 /// This test creates a new key pair, signs a message using the key pair, rotates the key pair,
 /// re-signs the message using the previous key pair version, and asserts the same signature is
 /// produced.
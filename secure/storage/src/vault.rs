@@ -10,9 +10,12 @@ use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     HashValue,
 };
-use libra_vault_client::{self as vault, Client};
+use libra_vault_client::{self as vault, Client, ReadResponse};
+use std::{collections::HashMap, sync::Mutex};
 
 const LIBRA_DEFAULT: &str = "libra_default";
+/// Secrets under this path pin the version of a key that is in use after a rollback.
+const ACTIVE_KEY_VERSIONS: &str = "active_key_versions";
 
 /// VaultStorage utilizes Vault for maintaining encrypted, authenticated data for Libra. This
 /// version currently matches the behavior of OnDiskStorage and InMemoryStorage. In the future,
@@ -21,9 +24,22 @@ const LIBRA_DEFAULT: &str = "libra_default";
 /// Version 2 (https://www.vaultproject.io/api/secret/kv/kv-v2.html). So while Libra Secure Storage
 /// calls pointers to data keys, Vault has actually a secret that contains multiple key value
 /// pairs.
+///
+/// Keys are managed by the Transit Secrets Engine
+/// (https://www.vaultproject.io/api-docs/secret/transit), which signs messages within Vault, so
+/// private keys need never leave it. As public keys are immutable once created, the versions read
+/// from Vault are cached to avoid a round trip whenever signing with a specific version. Transit
+/// cannot remove the latest version of a key, so a rollback instead pins the version to use in a
+/// secret, which the next rotation clears. The pinned versions are cached too, so that signing does
+/// not read the pin first; they are only updated by the rotations and rollbacks of this client.
+///
+/// Two distinct notions of namespace are supported: `namespace` prefixes every secret and key
+/// name, while `enterprise_namespace` selects a Vault Enterprise namespace for all requests.
 pub struct VaultStorage {
     pub client: Client,
     namespace: Option<String>,
+    public_keys: Mutex<HashMap<String, Vec<ReadResponse<Ed25519PublicKey>>>>,
+    active_versions: Mutex<HashMap<String, Option<u32>>>,
 }
 
 impl VaultStorage {
@@ -31,11 +47,14 @@ impl VaultStorage {
         host: String,
         token: String,
         namespace: Option<String>,
+        enterprise_namespace: Option<String>,
         certificate: Option<String>,
     ) -> Self {
         Self {
-            client: Client::new(host, token, certificate),
+            client: Client::new(host, token, enterprise_namespace, certificate),
             namespace,
+            public_keys: Mutex::new(HashMap::new()),
+            active_versions: Mutex::new(HashMap::new()),
         }
     }
 
@@ -62,6 +81,7 @@ impl VaultStorage {
         };
         for key in keys {
             self.client.delete_key(&key)?;
+            self.invalidate_public_keys(&key);
         }
         Ok(())
     }
@@ -136,16 +156,110 @@ impl VaultStorage {
 
         let path = format!("{}/{}", engine.to_policy_path(), self.name(key, engine));
         vault_policy.add_policy(&path, core_capabilities);
+
+        // Every use of a key first looks up the version pinned by a rollback, and rotations
+        // (including rollbacks) update the pin.
+        if let VaultEngine::Transit = engine {
+            let pin = self.active_version_secret(key);
+            if !capabilities.is_empty() {
+                let pin_path = format!("{}/{}", VaultEngine::KVSecrets.to_policy_path(), pin);
+                let mut pin_capabilities = vec![vault::Capability::Read];
+                if capabilities.contains(&Capability::Rotate) {
+                    pin_capabilities.push(vault::Capability::Create);
+                    pin_capabilities.push(vault::Capability::Update);
+                    let pin_metadata = format!("secret/metadata/{}", pin);
+                    vault_policy.add_policy(&pin_metadata, vec![vault::Capability::Delete]);
+                }
+                vault_policy.add_policy(&pin_path, pin_capabilities);
+            }
+        }
+
         self.client.set_policy(&policy_name, &vault_policy)?;
         Ok(())
     }
 
+    /// Returns the version of the named key that corresponds to the given public key. The cached
+    /// versions are consulted first and only refreshed from Vault if the public key is not found,
+    /// e.g., because the key has since been rotated by another client.
     fn key_version(&self, name: &str, version: &Ed25519PublicKey) -> Result<u32, Error> {
+        let find_version = |pubkeys: &[ReadResponse<Ed25519PublicKey>]| {
+            pubkeys
+                .iter()
+                .find(|pubkey| version == &pubkey.value)
+                .map(|pubkey| pubkey.version)
+        };
+
+        if let Some(pubkeys) = self.public_keys.lock().unwrap().get(name) {
+            if let Some(version) = find_version(pubkeys) {
+                return Ok(version);
+            }
+        }
+
+        let pubkeys = self.read_public_keys(name)?;
+        find_version(&pubkeys).ok_or_else(|| Error::KeyVersionNotFound(name.into()))
+    }
+
+    /// Reads all versions of the named key from Vault and refreshes the cache with them.
+    fn read_public_keys(&self, name: &str) -> Result<Vec<ReadResponse<Ed25519PublicKey>>, Error> {
         let pubkeys = self.client.read_ed25519_key(name)?;
-        let pubkey = pubkeys.iter().find(|pubkey| version == &pubkey.value);
-        Ok(pubkey
-            .ok_or_else(|| Error::KeyVersionNotFound(name.into()))?
-            .version)
+        self.public_keys
+            .lock()
+            .unwrap()
+            .insert(name.into(), pubkeys.clone());
+        Ok(pubkeys)
+    }
+
+    fn invalidate_public_keys(&self, name: &str) {
+        self.public_keys.lock().unwrap().remove(name);
+    }
+
+    /// Returns the version of the named key pinned by a rollback, if any. Versions newer than the
+    /// pinned one belong to rotations that were rolled back and are never used.
+    fn active_version(&self, name: &str) -> Result<Option<u32>, Error> {
+        if let Some(version) = self.active_versions.lock().unwrap().get(name) {
+            return Ok(*version);
+        }
+
+        let secret = self.active_version_secret(name);
+        let version = match self.client.read_secret(&secret, name) {
+            Ok(resp) => resp.value.parse().map(Some).map_err(|_| {
+                Error::SerializationError(format!("Invalid key version {}", resp.value))
+            })?,
+            Err(vault::Error::NotFound(_, _)) => None,
+            Err(e) => return Err(e.into()),
+        };
+        self.set_active_version(name, version);
+        Ok(version)
+    }
+
+    fn set_active_version(&self, name: &str, version: Option<u32>) {
+        self.active_versions
+            .lock()
+            .unwrap()
+            .insert(name.into(), version);
+    }
+
+    /// Returns the latest version of the named key older than the one in use.
+    fn previous_key(&self, name: &str) -> Result<ReadResponse<Ed25519PublicKey>, Error> {
+        let ns_name = self.crypto_name(name);
+        let current = self.current_key(name)?.version;
+        self.read_public_keys(&ns_name)?
+            .into_iter()
+            .filter(|pubkey| pubkey.version < current)
+            .max_by_key(|pubkey| pubkey.version)
+            .ok_or_else(|| Error::KeyVersionNotFound(ns_name))
+    }
+
+    /// Returns the version of the named key that is in use: the version pinned by a rollback if
+    /// there is one, otherwise the latest version.
+    fn current_key(&self, name: &str) -> Result<ReadResponse<Ed25519PublicKey>, Error> {
+        let ns_name = self.crypto_name(name);
+        let pubkeys = self.read_public_keys(&ns_name)?;
+        let current = match self.active_version(name)? {
+            Some(version) => pubkeys.into_iter().find(|pubkey| pubkey.version == version),
+            None => pubkeys.into_iter().max_by_key(|pubkey| pubkey.version),
+        };
+        current.ok_or_else(|| Error::KeyNotSet(ns_name))
     }
 
     pub fn set_policies(
@@ -174,6 +288,10 @@ impl VaultStorage {
         self.name(name, &VaultEngine::KVSecrets)
     }
 
+    fn active_version_secret(&self, name: &str) -> String {
+        self.secret_name(&format!("{}/{}", ACTIVE_KEY_VERSIONS, name))
+    }
+
     fn name(&self, name: &str, engine: &VaultEngine) -> String {
         if let Some(namespace) = &self.namespace {
             format!("{}{}{}", namespace, engine.ns_seperator(), name)
@@ -210,6 +328,7 @@ impl KVStorage for VaultStorage {
     #[cfg(any(test, feature = "testing"))]
     fn reset_and_clear(&mut self) -> Result<(), Error> {
         self.reset_kv("")?;
+        self.active_versions.lock().unwrap().clear();
         self.reset_crypto()?;
         self.reset_policies()
     }
@@ -229,8 +348,9 @@ impl CryptoStorage for VaultStorage {
     }
 
     fn export_private_key(&self, name: &str) -> Result<Ed25519PrivateKey, Error> {
+        let version = self.active_version(name)?;
         let name = self.crypto_name(name);
-        Ok(self.client.export_ed25519_key(&name, None)?)
+        Ok(self.client.export_ed25519_key(&name, version)?)
     }
 
     fn export_private_key_for_version(
@@ -257,32 +377,59 @@ impl CryptoStorage for VaultStorage {
     }
 
     fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
-        let name = self.crypto_name(name);
-        let resp = self.client.read_ed25519_key(&name)?;
-        let mut last_key = resp.first().ok_or_else(|| Error::KeyNotSet(name))?;
-        for key in &resp {
-            last_key = if last_key.version > key.version {
-                last_key
-            } else {
-                key
-            }
-        }
-
+        let current_key = self.current_key(name)?;
         Ok(PublicKeyResponse {
-            last_update: DateTime::parse_from_rfc3339(&last_key.creation_time)?.timestamp() as u64,
-            public_key: last_key.value.clone(),
+            last_update: DateTime::parse_from_rfc3339(&current_key.creation_time)?.timestamp()
+                as u64,
+            public_key: current_key.value,
         })
     }
 
     fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
         let ns_name = self.crypto_name(name);
         self.client.rotate_key(&ns_name)?;
+        self.invalidate_public_keys(&ns_name);
+        if self.active_version(name)?.is_some() {
+            self.client
+                .delete_secret(&self.active_version_secret(name))?;
+            self.set_active_version(name, None);
+        }
         self.get_public_key(name).map(|v| v.public_key)
     }
 
+    fn rotate_key_to(
+        &mut self,
+        _name: &str,
+        _key: Ed25519PrivateKey,
+    ) -> Result<Ed25519PublicKey, Error> {
+        Err(Error::InternalError(
+            "Vault only rotates to keys generated by its transit engine".into(),
+        ))
+    }
+
+    fn get_previous_public_key(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        self.previous_key(name).map(|pubkey| pubkey.value)
+    }
+
+    fn rollback_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        let previous = self.previous_key(name)?;
+
+        // Pinning the previous version is a single write and the key material never leaves
+        // Vault. The minimum versions in the key's configuration cannot be used for this, as they
+        // only bound the versions in use from below.
+        self.client.write_secret(
+            &self.active_version_secret(name),
+            name,
+            &previous.version.to_string(),
+        )?;
+        self.set_active_version(name, Some(previous.version));
+        Ok(previous.value)
+    }
+
     fn sign_message(&mut self, name: &str, message: &HashValue) -> Result<Ed25519Signature, Error> {
+        let version = self.active_version(name)?;
         let name = self.crypto_name(name);
-        Ok(self.client.sign_ed25519(&name, message.as_ref(), None)?)
+        Ok(self.client.sign_ed25519(&name, message.as_ref(), version)?)
     }
 
     fn sign_message_using_version(
@@ -77,15 +77,24 @@ impl From<serde_json::Error> for Error {
 /// * Data is accessed in Vault via tokens. Policies can only be granted during creation of a
 /// token, but policies can be amended afterward. So you cannot add new policies to a token, but
 /// you can increase the tokens abilities by modifying the underlying policies.
+/// * Vault Enterprise supports namespaces, isolated environments each with their own secret
+/// engines, policies, and tokens. When a namespace is specified, every authenticated request is
+/// made within it via the X-Vault-Namespace header.
 pub struct Client {
     agent: ureq::Agent,
     host: String,
+    namespace: Option<String>,
     token: String,
     tls_config: Option<Arc<rustls::ClientConfig>>,
 }
 
 impl Client {
-    pub fn new(host: String, token: String, ca_certificate: Option<String>) -> Self {
+    pub fn new(
+        host: String,
+        token: String,
+        namespace: Option<String>,
+        ca_certificate: Option<String>,
+    ) -> Self {
         let tls_config = if let Some(certificate) = ca_certificate {
             let mut tls_config = rustls::ClientConfig::new();
             // First try the certificate as a DER encoded cert, then as a PEM, and then panic.
@@ -102,6 +111,7 @@ impl Client {
         Self {
             agent: ureq::Agent::new().set("connection", "keep-alive").build(),
             host,
+            namespace,
             token,
             tls_config,
         }
@@ -383,6 +393,8 @@ impl Client {
         }
     }
 
+    /// Signs the data within Vault using the transit engine, so the private key never leaves
+    /// Vault. If no version is specified, the latest version of the key is used.
     pub fn sign_ed25519(
         &self,
         name: &str,
@@ -449,6 +461,9 @@ impl Client {
     fn upgrade_request(&self, request: ureq::Request) -> ureq::Request {
         let mut request = self.upgrade_request_without_token(request);
         request.set("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request.set("X-Vault-Namespace", namespace);
+        }
         request
     }
 
@@ -589,7 +604,7 @@ pub struct BackupInfo {
 }
 
 /// Provides a simple wrapper for all read APIs.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ReadResponse<T> {
     pub creation_time: String,
     pub value: T,
@@ -33,6 +33,12 @@ pub static REMOVE_VALIDATOR_TXN: Lazy<Vec<u8>> = Lazy::new(|| script("remove_val
 
 pub static SET_VALIDATOR_CONFIG_TXN: Lazy<Vec<u8>> = Lazy::new(|| script("set_validator_config"));
 
+pub static SET_VALIDATOR_NEXT_NETWORK_CONFIG_TXN: Lazy<Vec<u8>> =
+    Lazy::new(|| script("set_validator_next_network_config"));
+
+pub static ROTATE_VALIDATOR_NETWORK_CONFIG_TXN: Lazy<Vec<u8>> =
+    Lazy::new(|| script("rotate_validator_network_config"));
+
 pub static ROTATE_AUTHENTICATION_KEY_TXN: Lazy<Vec<u8>> =
     Lazy::new(|| script("rotate_authentication_key"));
 
@@ -52,6 +58,8 @@ mod test {
             &RECONFIGURE_TXN,
             &REMOVE_VALIDATOR_TXN,
             &SET_VALIDATOR_CONFIG_TXN,
+            &SET_VALIDATOR_NEXT_NETWORK_CONFIG_TXN,
+            &ROTATE_VALIDATOR_NETWORK_CONFIG_TXN,
             &ROTATE_AUTHENTICATION_KEY_TXN,
             &MINT_TXN,
             &EMPTY_TXN,
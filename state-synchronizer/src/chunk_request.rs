@@ -78,3 +78,31 @@ impl fmt::Display for GetChunkRequest {
         )
     }
 }
+
+/// Subscribes to the chunks following `known_version` from the highest available LedgerInfo:
+/// rather than answering a single request, the upstream node pushes the next chunk every time it
+/// commits new transactions, until it has not pushed anything for `timeout_ms`.
+/// Pushed chunks are not requested one by one, so the subscriber only sends a new subscription
+/// request when it falls behind, e.g. if a chunk had to be limited, or when the subscription has
+/// timed out.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SubscriptionRequest {
+    /// The first pushed chunk should start with `known_version + 1`.
+    pub known_version: Version,
+    /// Epoch of known_version + 1.
+    pub current_epoch: u64,
+    /// Max size of a pushed chunk.
+    pub limit: u64,
+    /// The subscription is dropped when no chunk was pushed for this duration.
+    pub timeout_ms: u64,
+}
+
+impl fmt::Display for SubscriptionRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[SubscriptionRequest: known version: {}, epoch: {}, limit: {}, timeout: {}ms]",
+            self.known_version, self.current_epoch, self.limit, self.timeout_ms,
+        )
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streams the committed transactions with their events to local consumers, e.g. indexers, over a
+//! unix socket, so that they don't need to poll JSON-RPC.
+//!
+//! A consumer connects and sends the version to resume from, as a big-endian u64. The service
+//! then writes batches of consecutive committed transactions from that version on, each framed by
+//! its length as a big-endian u32 followed by the LCS encoded [`CommitBatch`], and waits for new
+//! commits once the consumer has caught up. After a disconnection, a consumer resumes from the
+//! version following the last batch it processed.
+//!
+//! The service reads a batch from storage only once the previous one is written to the socket, so
+//! a slow consumer only falls behind and never holds back the node. A consumer that doesn't read a
+//! batch within the write timeout is disconnected.
+
+use crate::counters;
+use anyhow::{ensure, format_err, Result};
+use libra_config::config::StateSyncConfig;
+use libra_logger::prelude::*;
+use libra_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{TransactionListWithProof, Version},
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, sync::Arc, time::Duration};
+use storage_interface::{DbReader, LedgerInfoSubscription};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{UnixListener, UnixStream},
+    runtime::Runtime,
+    time::{delay_for, timeout},
+};
+
+/// A batch of consecutive committed transactions sent to a commit stream consumer.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CommitBatch {
+    /// The ledger info the transactions are proven against.
+    pub ledger_info_with_sigs: LedgerInfoWithSignatures,
+    /// The transactions with their events, starting at the version the consumer resumed from or
+    /// following the previous batch.
+    pub transactions: TransactionListWithProof,
+}
+
+#[derive(Clone, Copy)]
+struct StreamLimits {
+    batch_size: u64,
+    write_timeout: Duration,
+    poll_interval: Duration,
+}
+
+/// Starts serving the commit stream on `runtime`, if a socket is configured.
+pub(crate) fn start(
+    runtime: &Runtime,
+    storage: Arc<dyn DbReader>,
+    config: &StateSyncConfig,
+) -> io::Result<()> {
+    let path = match &config.commit_stream_path {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+    // The socket of a previous run is left behind when the node stops.
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    // The listener can only be bound inside a tokio context.
+    let listener = runtime.enter(|| UnixListener::bind(path))?;
+    let limits = StreamLimits {
+        batch_size: config.commit_stream_batch_size,
+        write_timeout: Duration::from_millis(config.commit_stream_write_timeout_ms),
+        poll_interval: Duration::from_millis(config.tick_interval_ms),
+    };
+    runtime.spawn(accept_consumers(listener, storage, limits));
+    Ok(())
+}
+
+async fn accept_consumers(
+    mut listener: UnixListener,
+    storage: Arc<dyn DbReader>,
+    limits: StreamLimits,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(serve_consumer(stream, Arc::clone(&storage), limits));
+            }
+            Err(e) => warn!(
+                "[state sync] failed to accept a commit stream consumer: {}",
+                e
+            ),
+        }
+    }
+}
+
+async fn serve_consumer(mut stream: UnixStream, storage: Arc<dyn DbReader>, limits: StreamLimits) {
+    counters::COMMIT_STREAM_CONSUMERS.inc();
+    if let Err(e) = stream_commits(&mut stream, storage, limits).await {
+        debug!("[state sync] commit stream consumer disconnected: {}", e);
+    }
+    counters::COMMIT_STREAM_CONSUMERS.dec();
+}
+
+async fn stream_commits(
+    stream: &mut UnixStream,
+    storage: Arc<dyn DbReader>,
+    limits: StreamLimits,
+) -> Result<()> {
+    let mut cursor: Version = stream.read_u64().await?;
+    let mut ledger_infos = storage.subscribe_to_ledger_infos();
+    loop {
+        let ledger_info_with_sigs = storage.get_latest_ledger_info()?;
+        let ledger_version = ledger_info_with_sigs.ledger_info().version();
+        if cursor > ledger_version {
+            wait_for_commit(&mut ledger_infos, limits.poll_interval).await;
+            continue;
+        }
+
+        let transactions = storage.get_transactions(
+            cursor,
+            limits.batch_size,
+            ledger_version,
+            true, /* fetch_events */
+        )?;
+        let num_txns = transactions.transactions.len() as u64;
+        ensure!(
+            num_txns > 0,
+            "No transaction committed at version {} up to {}",
+            cursor,
+            ledger_version
+        );
+        let batch = lcs::to_bytes(&CommitBatch {
+            ledger_info_with_sigs,
+            transactions,
+        })?;
+        match timeout(limits.write_timeout, write_frame(stream, &batch)).await {
+            Ok(result) => result?,
+            Err(_) => {
+                counters::COMMIT_STREAM_SLOW_CONSUMERS.inc();
+                return Err(format_err!(
+                    "the consumer didn't read the batch at version {} in time",
+                    cursor
+                ));
+            }
+        }
+        cursor += num_txns;
+    }
+}
+
+async fn write_frame(stream: &mut UnixStream, frame: &[u8]) -> io::Result<()> {
+    stream.write_u32(frame.len() as u32).await?;
+    stream.write_all(frame).await?;
+    stream.flush().await
+}
+
+/// Waits until a new ledger info is committed. The storage only publishes its commits when it
+/// runs in the same process, otherwise it is polled.
+async fn wait_for_commit(
+    ledger_infos: &mut Option<LedgerInfoSubscription>,
+    poll_interval: Duration,
+) {
+    if let Some(subscription) = ledger_infos {
+        if subscription.recv().await.is_some() {
+            return;
+        }
+        // The storage is shutting down.
+        *ledger_infos = None;
+    }
+    delay_for(poll_interval).await
+}
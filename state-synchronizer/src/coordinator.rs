@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    chunk_request::{GetChunkRequest, TargetType},
+    chunk_request::{GetChunkRequest, SubscriptionRequest, TargetType},
     chunk_response::{GetChunkResponse, ResponseLedgerInfo},
     counters,
     executor_proxy::ExecutorProxyTrait,
+    health::SyncProgress,
     network::{StateSynchronizerEvents, StateSynchronizerMsg, StateSynchronizerSender},
     peer_manager::{PeerManager, PeerScoreUpdateType},
     SynchronizerState,
@@ -32,7 +33,9 @@ use libra_types::{
 use network::protocols::network::Event;
 use std::{
     collections::{BTreeMap, HashMap},
+    mem,
     ops::Bound::Included,
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::time::{interval, timeout};
@@ -61,6 +64,8 @@ pub(crate) enum CoordinatorMessage {
     GetState(oneshot::Sender<SynchronizerState>),
     // Receive a notification via a given channel when coordinator is initialized.
     WaitInitialize(oneshot::Sender<Result<()>>),
+    // used to start syncing over from the local storage
+    Rebootstrap(oneshot::Sender<Result<()>>),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -69,6 +74,9 @@ struct PendingRequestInfo {
     known_version: u64,
     request_epoch: u64,
     limit: u64,
+    // Set for subscriptions, which are kept after a chunk is pushed and expire after this
+    // duration without one. Long polling requests are answered by a single chunk.
+    subscription_timeout: Option<Duration>,
 }
 
 // DS to help sync requester to keep track of ledger infos in the future
@@ -128,6 +136,10 @@ impl PendingLedgerInfos {
     fn target_li(&self) -> Option<LedgerInfoWithSignatures> {
         self.target_li.clone()
     }
+
+    fn highest_version(&self) -> Option<Version> {
+        self.pending_li_queue.keys().next_back().cloned()
+    }
 }
 
 /// Coordination of synchronization process is driven by SyncCoordinator, which `start()` function
@@ -164,13 +176,24 @@ pub(crate) struct SyncCoordinator<T> {
     sync_request: Option<SyncRequest>,
     // Ledger infos in the future that have not been committed yet
     pending_ledger_infos: PendingLedgerInfos,
+    // Verified chunks received after the local storage version that are waiting to be executed
+    // and committed together, in order, with their target LI and optional end of epoch LI
+    pending_chunks: Vec<(
+        TransactionListWithProof,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    )>,
     // Option initialization listener to be called when the coordinator is caught up with
     // its waypoint.
     initialization_listener: Option<oneshot::Sender<Result<()>>>,
-    // queue of incoming long polling requests
+    // queue of incoming long polling requests and subscriptions
     // peer will be notified about new chunk of transactions if it's available before expiry time
     subscriptions: HashMap<PeerNetworkId, PendingRequestInfo>,
+    // the upstream peer this node subscribed to, which pushes the next chunks without requests
+    subscribed_to: Option<PeerNetworkId>,
     executor_proxy: T,
+    // progress reported to the health endpoint
+    progress: Arc<SyncProgress>,
 }
 
 impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
@@ -184,6 +207,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         upstream_config: UpstreamConfig,
         executor_proxy: T,
         initial_state: SynchronizerState,
+        progress: Arc<SyncProgress>,
     ) -> Self {
         let retry_timeout_val = match role {
             RoleType::FullNode => config.tick_interval_ms + config.long_poll_timeout_ms,
@@ -195,6 +219,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             state_sync_to_mempool_sender,
             local_state: initial_state,
             pending_ledger_infos: PendingLedgerInfos::new(),
+            pending_chunks: vec![],
             retry_timeout: Duration::from_millis(retry_timeout_val),
             config,
             role,
@@ -202,9 +227,11 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             network_senders,
             peer_manager: PeerManager::new(upstream_config),
             subscriptions: HashMap::new(),
+            subscribed_to: None,
             sync_request: None,
             initialization_listener: None,
             executor_proxy,
+            progress,
         }
     }
 
@@ -244,6 +271,11 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                         CoordinatorMessage::WaitInitialize(cb_sender) => {
                             self.set_initialization_listener(cb_sender);
                         }
+                        CoordinatorMessage::Rebootstrap(callback) => {
+                            if callback.send(self.rebootstrap()).is_err() {
+                                error!("[state sync] failed to send rebootstrap response");
+                            }
+                        }
                     };
                 },
                 (network_id, network_event) = network_events.select_next_some() => {
@@ -270,6 +302,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                 },
                 _ = interval.select_next_some() => {
                     self.check_progress();
+                    self.update_progress();
                 }
             }
         }
@@ -282,6 +315,14 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                     error!("[state sync] failed to serve chunk request from {:?}, local LI version {}: {}", peer, self.local_state.highest_local_li.ledger_info().version(), err);
                 }
             }
+            StateSynchronizerMsg::SubscriptionRequest(request) => {
+                if let Err(err) = self.process_subscription_request(peer.clone(), *request) {
+                    error!(
+                        "[state sync] failed to serve subscription request from {:?}: {}",
+                        peer, err
+                    );
+                }
+            }
             StateSynchronizerMsg::GetChunkResponse(response) => {
                 if let Err(err) = self.process_chunk_response(&peer.clone(), *response).await {
                     error!(
@@ -378,9 +419,19 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         );
 
         self.sync_request = Some(request);
-        self.send_chunk_request(
-            self.local_state.highest_version_in_local_storage(),
-            self.local_state.epoch(),
+        self.send_chunk_request(self.highest_received_version(), self.local_state.epoch())
+    }
+
+    /// Returns the version of the last transaction received so far: the end of the pending
+    /// chunks if there are any, the highest version in the local storage otherwise.
+    fn highest_received_version(&self) -> Version {
+        self.pending_chunks.last().map_or_else(
+            || self.local_state.highest_version_in_local_storage(),
+            |(txn_list_with_proof, _, _)| {
+                txn_list_with_proof.first_transaction_version.unwrap_or(0)
+                    + txn_list_with_proof.len() as u64
+                    - 1
+            },
         )
     }
 
@@ -497,6 +548,22 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         Ok(())
     }
 
+    /// Starts syncing over from the local storage: the ledger infos pending and the chunk requests
+    /// in flight are dropped, the scores of the peers are reset, and the next chunk is requested
+    /// right away.
+    fn rebootstrap(&mut self) -> Result<()> {
+        info!(
+            "[state sync] Rebootstrapping from version {}",
+            self.local_state.highest_version_in_local_storage()
+        );
+        self.pending_ledger_infos = PendingLedgerInfos::new();
+        self.pending_chunks.clear();
+        self.peer_manager.reset();
+        self.sync_state_with_local_storage()?;
+        self.check_progress();
+        Ok(())
+    }
+
     fn get_state(&self, callback: oneshot::Sender<SynchronizerState>) {
         if callback.send(self.local_state.clone()).is_err() {
             error!("[state sync] failed to send internal state");
@@ -518,6 +585,9 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             self.local_state.highest_local_li.ledger_info().version(),
             request,
         );
+        // A request replaces a subscription of the peer, whose pushed chunks would otherwise
+        // cross the response.
+        self.subscriptions.remove(&peer);
 
         match request.target().clone() {
             TargetType::TargetLedgerInfo(li) => self.process_request_target_li(peer, request, li),
@@ -581,6 +651,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                     known_version: request.known_version,
                     request_epoch: request.current_epoch,
                     limit,
+                    subscription_timeout: None,
                 };
                 self.subscriptions.insert(peer, request_info);
             }
@@ -598,6 +669,39 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         )
     }
 
+    /// Registers a subscription, replacing any previous one of the peer, and pushes the first
+    /// chunk right away if there is one.
+    fn process_subscription_request(
+        &mut self,
+        peer: PeerNetworkId,
+        request: SubscriptionRequest,
+    ) -> Result<()> {
+        self.sync_state_with_local_storage()?;
+        debug!(
+            "[state sync] subscription request: peer_id: {:?}, local li version: {}, req: {}",
+            peer,
+            self.local_state.highest_local_li.ledger_info().version(),
+            request,
+        );
+        let timeout = Duration::from_millis(std::cmp::min(
+            request.timeout_ms,
+            self.config.max_timeout_ms,
+        ));
+        let expiration_time = SystemTime::now()
+            .checked_add(timeout)
+            .ok_or_else(|| format_err!("Invalid subscription timeout {:?}", timeout))?;
+        let request_info = PendingRequestInfo {
+            expiration_time,
+            known_version: request.known_version,
+            request_epoch: request.current_epoch,
+            limit: std::cmp::min(request.limit, self.config.max_chunk_limit),
+            subscription_timeout: Some(timeout),
+        };
+        self.subscriptions.insert(peer, request_info);
+        self.check_subscriptions();
+        Ok(())
+    }
+
     fn process_request_waypoint(
         &mut self,
         peer: PeerNetworkId,
@@ -703,7 +807,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             .inc();
         debug!("[state sync] Processing chunk response {}", response);
         let txn_list_with_proof = response.txn_list_with_proof.clone();
-        let known_version = self.local_state.highest_version_in_local_storage();
+        let known_version = self.highest_received_version();
         let chunk_start_version =
             txn_list_with_proof
                 .first_transaction_version
@@ -727,9 +831,9 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
 
         let chunk_size = txn_list_with_proof.len() as u64;
         let new_version = known_version + chunk_size;
-        match response.response_li {
+        let committed_transactions = match response.response_li {
             ResponseLedgerInfo::VerifiableLedgerInfo(li) => {
-                self.process_response_with_verifiable_li(txn_list_with_proof, li)
+                self.process_response_with_verifiable_li(peer, txn_list_with_proof, li)
             }
             ResponseLedgerInfo::ProgressiveLedgerInfo {
                 target_li,
@@ -742,7 +846,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                     highest_li
                 );
                 self.pending_ledger_infos.add_li(highest_li);
-                self.process_response_with_verifiable_li(txn_list_with_proof, target_li)
+                self.process_response_with_verifiable_li(peer, txn_list_with_proof, target_li)
             }
             ResponseLedgerInfo::LedgerInfoForWaypoint {
                 waypoint_li,
@@ -774,17 +878,21 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             }
         }
 
-        self.process_commit(response.txn_list_with_proof.transactions, None)
-            .await
+        match committed_transactions {
+            Some(transactions) => self.process_commit(transactions, None).await,
+            // The chunk is waiting for the rest of its batch.
+            None => Ok(()),
+        }
     }
 
     /// Processing chunk responses that carry a LedgerInfo that should be verified using the
     /// current local trusted validator set.
     fn process_response_with_verifiable_li(
         &mut self,
+        peer: &PeerNetworkId,
         txn_list_with_proof: TransactionListWithProof,
         response_li: LedgerInfoWithSignatures,
-    ) -> Result<()> {
+    ) -> Result<Option<Vec<Transaction>>> {
         ensure!(
             self.is_initialized(),
             "Response with a non-waypoint LI while still not initialized"
@@ -801,8 +909,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         }
         // Optimistically fetch the next chunk assuming the current chunk is going to be applied
         // successfully.
-        let new_version =
-            self.local_state.highest_version_in_local_storage() + txn_list_with_proof.len() as u64;
+        let new_version = self.highest_received_version() + txn_list_with_proof.len() as u64;
         let new_epoch = if response_li.ledger_info().version() == new_version
             && response_li.ledger_info().next_epoch_state().is_some()
         {
@@ -814,13 +921,31 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             self.local_state.epoch()
         };
         self.local_state.trusted_epoch.verify(&response_li)?;
-        self.validate_and_store_chunk(txn_list_with_proof, response_li, None)?;
+        let committed_transactions =
+            self.validate_and_store_chunk(txn_list_with_proof, response_li, None)?;
 
         // need to sync with local storage to see whether response LI was actually committed
         // and update pending_ledger_infos accordingly
         self.sync_state_with_local_storage()?;
-        let new_version = self.local_state.highest_version_in_local_storage();
-        self.send_chunk_request(new_version, new_epoch)
+        let new_version = self.highest_received_version();
+        // The subscribed peer keeps pushing chunks to a caught up node, there is nothing to
+        // request but the progress is tracked as if the next chunk had been requested.
+        if self.subscribed_to.as_ref() == Some(peer) && self.is_caught_up() {
+            self.peer_manager
+                .process_request(new_version + 1, peer.clone());
+            return Ok(committed_transactions);
+        }
+        self.send_chunk_request(new_version, new_epoch)?;
+        Ok(committed_transactions)
+    }
+
+    /// Returns true if all the local transactions are committed and no ledger info or chunk
+    /// beyond them is pending, i.e., this node has synced everything its peers reported.
+    fn is_caught_up(&self) -> bool {
+        self.pending_ledger_infos.highest_version().is_none()
+            && self.pending_chunks.is_empty()
+            && self.local_state.highest_local_li.ledger_info().version()
+                == self.local_state.highest_version_in_local_storage()
     }
 
     /// Processing chunk responses that carry a LedgerInfo corresponding to the waypoint.
@@ -829,14 +954,13 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         txn_list_with_proof: TransactionListWithProof,
         waypoint_li: LedgerInfoWithSignatures,
         end_of_epoch_li: Option<LedgerInfoWithSignatures>,
-    ) -> Result<()> {
+    ) -> Result<Option<Vec<Transaction>>> {
         ensure!(
             !self.is_initialized(),
             "Response with a waypoint LI but we're already initialized"
         );
         // Optimistically fetch the next chunk.
-        let new_version =
-            self.local_state.highest_version_in_local_storage() + txn_list_with_proof.len() as u64;
+        let new_version = self.highest_received_version() + txn_list_with_proof.len() as u64;
         // The epoch in the optimistic request should be the next epoch if the current chunk
         // is the last one in its epoch.
         let new_epoch = end_of_epoch_li
@@ -862,12 +986,15 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
     }
 
     // Assumes that the target LI has been already verified by the caller.
+    // The chunk is appended to the pending ones, which are executed and committed together once
+    // the batch is full or the chunk carries a LI to commit. Returns the committed transactions
+    // if that happened.
     fn validate_and_store_chunk(
         &mut self,
         txn_list_with_proof: TransactionListWithProof,
         target: LedgerInfoWithSignatures,
         intermediate_end_of_epoch_li: Option<LedgerInfoWithSignatures>,
-    ) -> Result<()> {
+    ) -> Result<Option<Vec<Transaction>>> {
         let target_epoch_and_round = (target.ledger_info().epoch(), target.ledger_info().round());
         let local_epoch_and_round = (
             self.local_state.highest_local_li.ledger_info().epoch(),
@@ -878,16 +1005,28 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
                 "Ledger info is too old: local epoch/round: {:?}, epoch/round in request: {:?}.",
                 local_epoch_and_round, target_epoch_and_round,
             );
-            return Ok(());
+            return Ok(None);
         }
 
-        self.executor_proxy.execute_chunk(
-            txn_list_with_proof,
-            target,
-            intermediate_end_of_epoch_li,
-            &mut self.local_state.synced_trees,
-        )?;
-        Ok(())
+        // Only the last chunk of a batch may commit a LI: either the target LI or the end of
+        // epoch LI when the chunk ends at their version.
+        let chunk_end_version = self.highest_received_version() + txn_list_with_proof.len() as u64;
+        let commits_li = target.ledger_info().version() == chunk_end_version
+            || intermediate_end_of_epoch_li.is_some();
+        self.pending_chunks
+            .push((txn_list_with_proof, target, intermediate_end_of_epoch_li));
+        if !commits_li && (self.pending_chunks.len() as u64) < self.config.chunk_batch_size {
+            return Ok(None);
+        }
+
+        let chunks = mem::take(&mut self.pending_chunks);
+        let transactions = chunks
+            .iter()
+            .flat_map(|(txn_list_with_proof, _, _)| txn_list_with_proof.transactions.clone())
+            .collect();
+        self.executor_proxy
+            .execute_chunks(chunks, &mut self.local_state.synced_trees)?;
+        Ok(Some(transactions))
     }
 
     /// Ensures that StateSynchronizer is making progress:
@@ -922,7 +1061,7 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             }
         }
 
-        let known_version = self.local_state.highest_version_in_local_storage();
+        let known_version = self.highest_received_version();
         let last_request_tst = self
             .peer_manager
             .get_last_request_time(known_version + 1)
@@ -941,9 +1080,29 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         }
     }
 
+    /// Publishes the local version and the highest version known to exist, i.e., the target of
+    /// the current sync request, a pending LedgerInfo, or the waypoint.
+    fn update_progress(&self) {
+        let target_version = self
+            .sync_request
+            .as_ref()
+            .map(|req| req.target.ledger_info().version())
+            .into_iter()
+            .chain(self.pending_ledger_infos.highest_version())
+            .chain(self.waypoint.as_ref().map(|w| w.version()))
+            .max()
+            .unwrap_or(0);
+        self.progress.update(
+            self.is_initialized(),
+            self.local_state.highest_version_in_local_storage(),
+            target_version,
+        );
+    }
+
     /// Sends a chunk request with a given `known_version` and `known_epoch`
     /// (might be chosen optimistically).
-    /// The request includes a target for Validator and a non-zero timeout for a FullNode.
+    /// The request includes a target for Validator and a non-zero timeout for a FullNode, which
+    /// subscribes instead once it is caught up and the peer supports subscriptions.
     fn send_chunk_request(&mut self, known_version: u64, known_epoch: u64) -> Result<()> {
         let peer = self
             .peer_manager
@@ -959,6 +1118,19 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         } else {
             match self.sync_request.as_ref() {
                 None => {
+                    // Subscriptions push chunks relative to the highest ledger info of the peer,
+                    // so a node still syncing to a pending ledger info keeps requesting them.
+                    let supports_subscriptions = self
+                        .network_senders
+                        .get(&peer.network_id())
+                        .map_or(false, |sender| {
+                            sender.supports_subscriptions(&peer.peer_id())
+                        });
+                    if supports_subscriptions
+                        && self.pending_ledger_infos.highest_version().is_none()
+                    {
+                        return self.send_subscription_request(peer, known_version, known_epoch);
+                    }
                     TargetType::HighestAvailable {
                         // here, we need to ensure pending_ledger_infos is up-to-date with storage
                         // this is the responsibility of the caller of send_chunk_request
@@ -984,7 +1156,39 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             "[state sync] request next chunk. peer_id: {:?}, chunk req: {}",
             peer, req,
         );
+        self.subscribed_to = None;
         let msg = StateSynchronizerMsg::GetChunkRequest(Box::new(req));
+        self.send_request(peer, known_version, msg)
+    }
+
+    /// Subscribes to the chunks following `known_version` from the highest ledger info of `peer`.
+    fn send_subscription_request(
+        &mut self,
+        peer: PeerNetworkId,
+        known_version: u64,
+        known_epoch: u64,
+    ) -> Result<()> {
+        let req = SubscriptionRequest {
+            known_version,
+            current_epoch: known_epoch,
+            limit: self.config.chunk_limit,
+            timeout_ms: self.config.long_poll_timeout_ms,
+        };
+        debug!(
+            "[state sync] subscribe to next chunks. peer_id: {:?}, req: {}",
+            peer, req,
+        );
+        self.subscribed_to = Some(peer.clone());
+        let msg = StateSynchronizerMsg::SubscriptionRequest(Box::new(req));
+        self.send_request(peer, known_version, msg)
+    }
+
+    fn send_request(
+        &mut self,
+        peer: PeerNetworkId,
+        known_version: u64,
+        msg: StateSynchronizerMsg,
+    ) -> Result<()> {
         self.peer_manager
             .process_request(known_version + 1, peer.clone());
         let sender = self
@@ -999,19 +1203,54 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
         Ok(())
     }
 
+    /// Pushes the next chunk to a subscriber. Returns the subscription to keep for the chunks
+    /// following it, if any: only subscriptions, as opposed to long polling requests, are kept,
+    /// and only while the subscriber is caught up. A subscriber left behind by the chunk (e.g.,
+    /// because of the chunk limit) subscribes again, so that no chunk is pushed in the meantime.
     fn deliver_subscription(
         &mut self,
         peer: PeerNetworkId,
         request_info: PendingRequestInfo,
-    ) -> Result<()> {
-        let response_li =
+    ) -> Result<Option<PendingRequestInfo>> {
+        let target_li =
             self.choose_response_li(request_info.known_version, request_info.request_epoch, None)?;
+        let highest_li = self.local_state.highest_local_li.clone();
+        let target_version = target_li.ledger_info().version();
+        let ends_epoch = target_li.ledger_info().next_epoch_state().is_some();
         self.deliver_chunk(
             peer,
             request_info.known_version,
-            ResponseLedgerInfo::VerifiableLedgerInfo(response_li),
+            ResponseLedgerInfo::ProgressiveLedgerInfo {
+                target_li,
+                highest_li: highest_li.clone(),
+            },
             request_info.limit,
-        )
+        )?;
+
+        let timeout = match request_info.subscription_timeout {
+            Some(timeout) => timeout,
+            None => return Ok(None),
+        };
+        let known_version = std::cmp::min(
+            request_info.known_version + request_info.limit,
+            target_version,
+        );
+        if known_version < highest_li.ledger_info().version() {
+            return Ok(None);
+        }
+        let request_epoch = if known_version == target_version && ends_epoch {
+            request_info.request_epoch + 1
+        } else {
+            request_info.request_epoch
+        };
+        Ok(SystemTime::now()
+            .checked_add(timeout)
+            .map(|expiration_time| PendingRequestInfo {
+                expiration_time,
+                known_version,
+                request_epoch,
+                ..request_info
+            }))
     }
 
     /// The function is called after the local storage is updated with new transactions:
@@ -1041,10 +1280,14 @@ impl<T: ExecutorProxyTrait> SyncCoordinator<T> {
             }
         });
 
-        ready.into_iter().for_each(|(peer, request_info)| {
-            if let Err(err) = self.deliver_subscription(peer, request_info) {
-                error!("[state sync] failed to notify subscriber {}", err);
+        for (peer, request_info) in ready {
+            match self.deliver_subscription(peer.clone(), request_info) {
+                Ok(Some(subscription)) => {
+                    self.subscriptions.insert(peer, subscription);
+                }
+                Ok(None) => (),
+                Err(err) => error!("[state sync] failed to notify subscriber {}", err),
             }
-        });
+        }
     }
 }
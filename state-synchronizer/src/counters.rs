@@ -127,3 +127,21 @@ pub static COMMIT_TIMEOUT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
         .unwrap()
 });
+
+/// Number of consumers currently connected to the commit stream
+pub static COMMIT_STREAM_CONSUMERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "libra_state_sync_commit_stream_consumers",
+        "Number of consumers currently connected to the commit stream"
+    )
+    .unwrap()
+});
+
+/// Number of commit stream consumers disconnected for not reading a batch in time
+pub static COMMIT_STREAM_SLOW_CONSUMERS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "libra_state_sync_commit_stream_slow_consumers_total",
+        "Number of commit stream consumers disconnected for not reading a batch in time"
+    )
+    .unwrap()
+});
@@ -10,7 +10,7 @@ use libra_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
     move_resource::MoveStorage,
-    on_chain_config::{config_address, OnChainConfigPayload, ON_CHAIN_CONFIG_REGISTRY},
+    on_chain_config::{apply_pending_config, config_address, ConfigID, OnChainConfigPayload},
     transaction::TransactionListWithProof,
 };
 use std::{collections::HashSet, convert::TryFrom, sync::Arc};
@@ -22,12 +22,15 @@ pub trait ExecutorProxyTrait: Send {
     /// Sync the local state with the latest in storage.
     fn get_local_storage_state(&self) -> Result<SynchronizerState>;
 
-    /// Execute and commit a batch of transactions
-    fn execute_chunk(
+    /// Execute and commit several contiguous chunks of transactions in a single storage write.
+    /// Every chunk comes with its verified target LI and an optional end of epoch LI.
+    fn execute_chunks(
         &mut self,
-        txn_list_with_proof: TransactionListWithProof,
-        verified_target_li: LedgerInfoWithSignatures,
-        intermediate_end_of_epoch_li: Option<LedgerInfoWithSignatures>,
+        chunks: Vec<(
+            TransactionListWithProof,
+            LedgerInfoWithSignatures,
+            Option<LedgerInfoWithSignatures>,
+        )>,
         synced_trees: &mut ExecutedTrees,
     ) -> Result<()>;
 
@@ -60,6 +63,8 @@ pub(crate) struct ExecutorProxy {
     storage: Arc<dyn DbReader>,
     executor: Box<dyn ChunkExecutor>,
     reconfig_subscriptions: Vec<ReconfigSubscription>,
+    // The configs any of the subscriptions is interested in
+    subscribed_configs: Vec<ConfigID>,
     on_chain_configs: OnChainConfigPayload,
 }
 
@@ -69,7 +74,13 @@ impl ExecutorProxy {
         executor: Box<dyn ChunkExecutor>,
         mut reconfig_subscriptions: Vec<ReconfigSubscription>,
     ) -> Self {
-        let on_chain_configs = Self::fetch_all_configs(&*storage)
+        let subscribed_configs: Vec<_> = reconfig_subscriptions
+            .iter()
+            .flat_map(|subscription| subscription.subscribed_items().configs)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let on_chain_configs = Self::fetch_all_configs(&*storage, &subscribed_configs)
             .expect("[state sync] Failed initial read of on-chain configs");
         for subscription in reconfig_subscriptions.iter_mut() {
             subscription
@@ -80,40 +91,48 @@ impl ExecutorProxy {
             storage,
             executor,
             reconfig_subscriptions,
+            subscribed_configs,
             on_chain_configs,
         }
     }
 
     // TODO make this into more general trait method in `on_chain_config`
     // once `StorageRead` trait is replaced with `DbReader` and `batch_fetch_config` method is no longer async
-    fn fetch_all_configs(storage: &dyn DbReader) -> Result<OnChainConfigPayload> {
-        let access_paths = ON_CHAIN_CONFIG_REGISTRY
+    fn fetch_all_configs(
+        storage: &dyn DbReader,
+        config_ids: &[ConfigID],
+    ) -> Result<OnChainConfigPayload> {
+        let access_paths = config_ids
             .iter()
             .map(|config_id| config_id.access_path())
             .collect();
         let configs = storage.batch_fetch_resources(access_paths)?;
-        let epoch = storage
+        let config_account = storage
             .get_latest_account_state(config_address())?
-            .map(|blob| {
-                AccountState::try_from(&blob).and_then(|state| {
-                    Ok(state
-                        .get_configuration_resource()?
-                        .ok_or_else(|| format_err!("ConfigurationResource does not exist"))?
-                        .epoch())
-                })
+            .ok_or_else(|| format_err!("Failed to fetch the config account"))?;
+        let config_account = AccountState::try_from(&config_account)?;
+        let epoch = config_account
+            .get_configuration_resource()?
+            .ok_or_else(|| format_err!("ConfigurationResource does not exist"))?
+            .epoch();
+
+        // Publish the values in effect, with the scheduled updates whose epoch has started
+        let configs = config_ids
+            .iter()
+            .zip_eq(configs)
+            .map(|(config_id, config)| {
+                let pending_path = config_id.pending_access_path();
+                let config = match config_account.get(&pending_path.path) {
+                    Some(pending) if pending_path.address == config_address() => {
+                        apply_pending_config(config, pending, epoch)?
+                    }
+                    _ => config,
+                };
+                Ok((*config_id, config))
             })
-            .ok_or_else(|| format_err!("Failed to fetch ConfigurationResource"))??;
-
-        Ok(OnChainConfigPayload::new(
-            epoch,
-            Arc::new(
-                ON_CHAIN_CONFIG_REGISTRY
-                    .iter()
-                    .cloned()
-                    .zip_eq(configs)
-                    .collect(),
-            ),
-        ))
+            .collect::<Result<_>>()?;
+
+        Ok(OnChainConfigPayload::new(epoch, Arc::new(configs)))
     }
 }
 
@@ -139,18 +158,16 @@ impl ExecutorProxyTrait for ExecutorProxy {
         ))
     }
 
-    fn execute_chunk(
+    fn execute_chunks(
         &mut self,
-        txn_list_with_proof: TransactionListWithProof,
-        verified_target_li: LedgerInfoWithSignatures,
-        intermediate_end_of_epoch_li: Option<LedgerInfoWithSignatures>,
+        chunks: Vec<(
+            TransactionListWithProof,
+            LedgerInfoWithSignatures,
+            Option<LedgerInfoWithSignatures>,
+        )>,
         _synced_trees: &mut ExecutedTrees,
     ) -> Result<()> {
-        let reconfig_events = self.executor.execute_and_commit_chunk(
-            txn_list_with_proof,
-            verified_target_li,
-            intermediate_end_of_epoch_li,
-        )?;
+        let reconfig_events = self.executor.execute_and_commit_chunks(chunks)?;
         self.publish_on_chain_config_updates(reconfig_events)
     }
 
@@ -177,7 +194,7 @@ impl ExecutorProxyTrait for ExecutorProxy {
     }
 
     fn load_on_chain_configs(&mut self) -> Result<()> {
-        self.on_chain_configs = Self::fetch_all_configs(&*self.storage)?;
+        self.on_chain_configs = Self::fetch_all_configs(&*self.storage, &self.subscribed_configs)?;
         Ok(())
     }
 
@@ -191,7 +208,7 @@ impl ExecutorProxyTrait for ExecutorProxy {
             .collect::<HashSet<_>>();
 
         // calculate deltas
-        let new_configs = Self::fetch_all_configs(&*self.storage)?;
+        let new_configs = Self::fetch_all_configs(&*self.storage, &self.subscribed_configs)?;
         let changed_configs = new_configs
             .configs()
             .iter()
@@ -0,0 +1,102 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use libra_metrics::health::ComponentHealth;
+use libra_types::transaction::Version;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// The coordinator is considered stuck if its event loop has not ticked for this long
+const MAX_TICK_DELAY: Duration = Duration::from_secs(30);
+
+/// Progress of the coordinator, shared with the health reporter of state sync.
+pub(crate) struct SyncProgress {
+    initialized: AtomicBool,
+    synced_version: AtomicU64,
+    target_version: AtomicU64,
+    last_tick: Mutex<Instant>,
+    // A node lagging by no more than a chunk is considered synced
+    max_lag: u64,
+}
+
+impl SyncProgress {
+    pub fn new(max_lag: u64) -> Self {
+        Self {
+            initialized: AtomicBool::new(false),
+            synced_version: AtomicU64::new(0),
+            target_version: AtomicU64::new(0),
+            last_tick: Mutex::new(Instant::now()),
+            max_lag,
+        }
+    }
+
+    pub fn update(&self, initialized: bool, synced_version: Version, target_version: Version) {
+        self.initialized.store(initialized, Ordering::Relaxed);
+        self.synced_version.store(synced_version, Ordering::Relaxed);
+        self.target_version
+            .store(target_version.max(synced_version), Ordering::Relaxed);
+        *self.last_tick.lock().expect("Unable to lock sync progress") = Instant::now();
+    }
+
+    pub fn health(&self) -> ComponentHealth {
+        let since_tick = self
+            .last_tick
+            .lock()
+            .expect("Unable to lock sync progress")
+            .elapsed();
+        if since_tick > MAX_TICK_DELAY {
+            return ComponentHealth::unhealthy(format!(
+                "coordinator has not ticked for {}s",
+                since_tick.as_secs()
+            ));
+        }
+
+        let synced_version = self.synced_version.load(Ordering::Relaxed);
+        let target_version = self.target_version.load(Ordering::Relaxed);
+        let lag = target_version.saturating_sub(synced_version);
+        let message = format!(
+            "synced version {}, target version {}, lag {}",
+            synced_version, target_version, lag
+        );
+        if !self.initialized.load(Ordering::Relaxed) {
+            ComponentHealth::not_ready(format!("syncing to waypoint, {}", message))
+        } else if lag > self.max_lag {
+            ComponentHealth::not_ready(message)
+        } else {
+            ComponentHealth::healthy(message)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libra_metrics::health::HealthStatus;
+
+    #[test]
+    fn test_sync_progress_health() {
+        let progress = SyncProgress::new(10);
+        assert_eq!(progress.health().status, HealthStatus::NotReady);
+
+        progress.update(false, 100, 100);
+        assert_eq!(progress.health().status, HealthStatus::NotReady);
+
+        progress.update(true, 100, 111);
+        assert_eq!(progress.health().status, HealthStatus::NotReady);
+
+        progress.update(true, 101, 111);
+        assert_eq!(progress.health().status, HealthStatus::Healthy);
+
+        // A stale target never counts as lag
+        progress.update(true, 200, 111);
+        assert_eq!(
+            progress.health().message,
+            "synced version 200, target version 200, lag 0"
+        );
+    }
+}
@@ -13,9 +13,11 @@ pub use synchronizer::{StateSyncClient, StateSynchronizer};
 
 mod chunk_request;
 mod chunk_response;
+pub mod commit_stream;
 mod coordinator;
 mod counters;
 mod executor_proxy;
+mod health;
 pub mod network;
 mod peer_manager;
 mod synchronizer;
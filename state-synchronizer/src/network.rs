@@ -3,14 +3,21 @@
 
 //! Interface between StateSynchronizer and Network layers.
 
-use crate::{chunk_request::GetChunkRequest, chunk_response::GetChunkResponse, counters};
+use crate::{
+    chunk_request::{GetChunkRequest, SubscriptionRequest},
+    chunk_response::GetChunkResponse,
+    counters,
+};
 use channel::message_queues::QueueStyle;
 use libra_metrics::IntCounterVec;
 use libra_types::PeerId;
 use network::{
     error::NetworkError,
-    peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
-    protocols::network::{NetworkEvents, NetworkSender, NewNetworkSender},
+    peer_manager::{ConnectionRequestSender, PeerApplicationVersions, PeerManagerRequestSender},
+    protocols::{
+        network::{NetworkEvents, NetworkSender, NewNetworkSender},
+        wire::handshake::v1::{ApplicationVersion, ApplicationVersions},
+    },
     ProtocolId,
 };
 use serde::{Deserialize, Serialize};
@@ -20,8 +27,13 @@ use serde::{Deserialize, Serialize};
 pub enum StateSynchronizerMsg {
     GetChunkRequest(Box<GetChunkRequest>),
     GetChunkResponse(Box<GetChunkResponse>),
+    /// Only sent to peers which negotiated `SUBSCRIPTION_VERSION`.
+    SubscriptionRequest(Box<SubscriptionRequest>),
 }
 
+/// The first version of the message format with `StateSynchronizerMsg::SubscriptionRequest`.
+pub const SUBSCRIPTION_VERSION: ApplicationVersion = 1;
+
 /// The interface from Network to StateSynchronizer layer.
 ///
 /// `StateSynchronizerEvents` is a `Stream` of `PeerManagerNotification` where the
@@ -41,6 +53,7 @@ pub type StateSynchronizerEvents = NetworkEvents<StateSynchronizerMsg>;
 #[derive(Clone)]
 pub struct StateSynchronizerSender {
     inner: NetworkSender<StateSynchronizerMsg>,
+    peer_versions: PeerApplicationVersions,
 }
 
 /// Configuration for the network endpoints to support StateSynchronizer.
@@ -61,6 +74,17 @@ pub fn network_endpoint_config() -> (
     )
 }
 
+/// The versions of the state synchronizer message formats this node supports, advertised to
+/// peers during the network handshake.
+pub fn supported_versions() -> ApplicationVersions {
+    let mut versions = ApplicationVersions::default();
+    versions.add(
+        ProtocolId::StateSynchronizerDirectSend,
+        vec![0, SUBSCRIPTION_VERSION],
+    );
+    versions
+}
+
 impl NewNetworkSender for StateSynchronizerSender {
     fn new(
         peer_mgr_reqs_tx: PeerManagerRequestSender,
@@ -68,11 +92,26 @@ impl NewNetworkSender for StateSynchronizerSender {
     ) -> Self {
         Self {
             inner: NetworkSender::new(peer_mgr_reqs_tx, connection_reqs_tx),
+            peer_versions: PeerApplicationVersions::default(),
         }
     }
 }
 
 impl StateSynchronizerSender {
+    /// Looks up the message format versions negotiated with peers in `peer_versions`. Without
+    /// them, every peer is assumed to only support version 0.
+    pub fn with_peer_versions(mut self, peer_versions: PeerApplicationVersions) -> Self {
+        self.peer_versions = peer_versions;
+        self
+    }
+
+    /// Returns true if `peer` can be sent a `StateSynchronizerMsg::SubscriptionRequest`
+    pub fn supports_subscriptions(&self, peer: &PeerId) -> bool {
+        self.peer_versions
+            .get(peer, ProtocolId::StateSynchronizerDirectSend)
+            .map_or(false, |version| version >= SUBSCRIPTION_VERSION)
+    }
+
     pub fn send_to(
         &mut self,
         recipient: PeerId,
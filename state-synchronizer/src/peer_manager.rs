@@ -99,6 +99,15 @@ impl PeerManager {
         self.compute_weighted_index();
     }
 
+    /// Forgets the chunk requests in flight and restores the score of all peers
+    pub fn reset(&mut self) {
+        self.requests.clear();
+        for peer_info in self.peers.values_mut() {
+            peer_info.score = MAX_SCORE;
+        }
+        self.compute_weighted_index();
+    }
+
     pub fn is_empty(&self) -> bool {
         self.get_active_upstream_peers().is_empty()
     }
@@ -1,9 +1,11 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
+    commit_stream,
     coordinator::{CoordinatorMessage, SyncCoordinator, SyncRequest},
     counters,
     executor_proxy::{ExecutorProxy, ExecutorProxyTrait},
+    health::SyncProgress,
     network::{StateSynchronizerEvents, StateSynchronizerSender},
     SynchronizerState,
 };
@@ -19,6 +21,7 @@ use libra_config::{
     network_id::NetworkId,
 };
 use libra_mempool::{CommitNotification, CommitResponse};
+use libra_metrics::health::register_health_reporter;
 use libra_types::{
     contract_event::ContractEvent, ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
     waypoint::Waypoint,
@@ -37,7 +40,7 @@ use tokio::{
 };
 
 pub struct StateSynchronizer {
-    _runtime: Runtime,
+    runtime: Runtime,
     coordinator_sender: mpsc::UnboundedSender<CoordinatorMessage>,
 }
 
@@ -59,6 +62,8 @@ impl StateSynchronizer {
             .build()
             .expect("[state synchronizer] failed to create runtime");
 
+        commit_stream::start(&runtime, Arc::clone(&storage), &config.state_sync)
+            .expect("[state sync] failed to start the commit stream");
         let executor_proxy = ExecutorProxy::new(storage, executor, reconfig_event_subscriptions);
         Self::bootstrap_with_executor_proxy(
             runtime,
@@ -93,6 +98,10 @@ impl StateSynchronizer {
             .map(|(network_id, sender, _events)| (network_id.clone(), sender.clone()))
             .collect();
 
+        let progress = Arc::new(SyncProgress::new(state_sync_config.chunk_limit));
+        let reporter = progress.clone();
+        register_health_reporter("state_sync", Box::new(move || reporter.health()));
+
         let coordinator = SyncCoordinator::new(
             coordinator_receiver,
             state_sync_to_mempool_sender,
@@ -103,15 +112,21 @@ impl StateSynchronizer {
             upstream_config,
             executor_proxy,
             initial_state,
+            progress,
         );
         runtime.spawn(coordinator.start(network));
 
         Self {
-            _runtime: runtime,
+            runtime,
             coordinator_sender,
         }
     }
 
+    /// Stops state sync, waiting at most `timeout` for the commits in flight to complete.
+    pub fn shutdown(self, timeout: Duration) {
+        self.runtime.shutdown_timeout(timeout);
+    }
+
     pub fn create_client(&self) -> Arc<StateSyncClient> {
         Arc::new(StateSyncClient {
             coordinator_sender: self.coordinator_sender.clone(),
@@ -194,6 +209,19 @@ impl StateSyncClient {
         }
     }
 
+    /// Starts syncing over from the local storage, e.g., when state sync is stuck on peers that
+    /// serve invalid chunks.
+    pub fn rebootstrap(&self) -> impl Future<Output = Result<()>> {
+        let mut sender = self.coordinator_sender.clone();
+        let (callback, cb_receiver) = oneshot::channel();
+        async move {
+            sender
+                .send(CoordinatorMessage::Rebootstrap(callback))
+                .await?;
+            cb_receiver.await?
+        }
+    }
+
     /// Returns information about StateSynchronizer internal state
     pub fn get_state(&self) -> impl Future<Output = Result<SynchronizerState>> {
         let mut sender = self.coordinator_sender.clone();
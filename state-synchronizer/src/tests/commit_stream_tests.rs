@@ -0,0 +1,58 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commit_stream::{self, CommitBatch};
+use executor::db_bootstrapper::bootstrap_db_if_empty;
+use libra_config::utils::get_genesis_txn;
+use libra_vm::LibraVM;
+use libradb::LibraDB;
+use std::time::Duration;
+use storage_interface::DbReaderWriter;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::UnixStream,
+    runtime::Runtime,
+    time::timeout,
+};
+
+async fn read_batch(stream: &mut UnixStream) -> CommitBatch {
+    let len = stream.read_u32().await.unwrap();
+    let mut frame = vec![0; len as usize];
+    stream.read_exact(&mut frame).await.unwrap();
+    lcs::from_bytes(&frame).unwrap()
+}
+
+#[test]
+fn test_commit_stream() {
+    let mut rt = Runtime::new().unwrap();
+    let (mut config, _genesis_key) = config_builder::test_config();
+    let (db, db_rw) = DbReaderWriter::wrap(LibraDB::new_for_test(&config.storage.dir()));
+    bootstrap_db_if_empty::<LibraVM>(&db_rw, get_genesis_txn(&config).unwrap()).unwrap();
+
+    let path = config.storage.dir().join("commit_stream.sock");
+    config.state_sync.commit_stream_path = Some(path.clone());
+    commit_stream::start(&rt, db, &config.state_sync).unwrap();
+
+    rt.block_on(async {
+        // A consumer starting from scratch receives the genesis transaction.
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+        stream.write_u64(0).await.unwrap();
+        let batch = read_batch(&mut stream).await;
+        batch
+            .transactions
+            .verify(batch.ledger_info_with_sigs.ledger_info(), Some(0))
+            .unwrap();
+        assert_eq!(batch.transactions.transactions.len(), 1);
+        assert_eq!(
+            batch.transactions.events.map(|events| events.len()),
+            Some(1)
+        );
+
+        // A consumer resuming after the last commit waits for the next one.
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+        stream.write_u64(1).await.unwrap();
+        assert!(timeout(Duration::from_millis(200), stream.read_u32())
+            .await
+            .is_err());
+    });
+}
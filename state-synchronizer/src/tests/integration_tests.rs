@@ -67,18 +67,23 @@ impl ExecutorProxyTrait for MockExecutorProxy {
         Ok(self.storage.read().unwrap().get_local_storage_state())
     }
 
-    fn execute_chunk(
+    fn execute_chunks(
         &mut self,
-        txn_list_with_proof: TransactionListWithProof,
-        ledger_info_with_sigs: LedgerInfoWithSignatures,
-        intermediate_end_of_epoch_li: Option<LedgerInfoWithSignatures>,
+        chunks: Vec<(
+            TransactionListWithProof,
+            LedgerInfoWithSignatures,
+            Option<LedgerInfoWithSignatures>,
+        )>,
         _synced_trees: &mut ExecutedTrees,
     ) -> Result<()> {
-        self.storage.write().unwrap().add_txns_with_li(
-            txn_list_with_proof.transactions,
-            ledger_info_with_sigs,
-            intermediate_end_of_epoch_li,
-        );
+        let mut storage = self.storage.write().unwrap();
+        for (txn_list_with_proof, ledger_info_with_sigs, intermediate_end_of_epoch_li) in chunks {
+            storage.add_txns_with_li(
+                txn_list_with_proof.transactions,
+                ledger_info_with_sigs,
+                intermediate_end_of_epoch_li,
+            );
+        }
         Ok(())
     }
 
@@ -257,7 +262,7 @@ impl SynchronizerEnv {
             .map(|public_keys| {
                 (
                     *public_keys.account_address(),
-                    public_keys.network_identity_public_key(),
+                    public_keys.network_identity_public_keys(),
                 )
             })
             .collect();
@@ -310,6 +315,8 @@ impl SynchronizerEnv {
 
             let (sender, events) =
                 network_builder.add_protocol_handler(crate::network::network_endpoint_config());
+            network_builder.advertise_application_versions(crate::network::supported_versions());
+            let sender = sender.with_peer_versions(network_builder.peer_application_versions());
             let peer_addr = network_builder.build();
             self.peer_addresses.push(peer_addr);
             (sender, events)
@@ -562,9 +569,11 @@ fn test_full_node() {
     // first sync should be fulfilled immediately after peer discovery
     assert!(env.wait_for_version(1, 10, None));
     env.commit(0, 20);
-    // second sync will be done via long polling cause first node should send new request
-    // after receiving first chunk immediately
+    // second sync will be pushed by the subscription the full node has made once caught up
     assert!(env.wait_for_version(1, 20, None));
+    // and so will the following ones, without new requests
+    env.commit(0, 30);
+    assert!(env.wait_for_version(1, 30, None));
 }
 
 #[test]
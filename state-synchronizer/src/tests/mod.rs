@@ -1,6 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod commit_stream_tests;
 mod integration_tests;
 mod mock_storage;
 mod on_chain_config_tests;
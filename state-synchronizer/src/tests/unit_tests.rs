@@ -83,3 +83,24 @@ fn test_peer_manager_request_metadata() {
             <= peer_manager.get_last_request_time(1).unwrap()
     );
 }
+
+#[test]
+fn test_reset() {
+    let peers = vec![
+        PeerNetworkId::random_validator(),
+        PeerNetworkId::random_validator(),
+    ];
+    let mut peer_manager = PeerManager::new(UpstreamConfig::default());
+    for peer in peers.iter() {
+        peer_manager.enable_peer(peer.clone());
+    }
+    peer_manager.process_request(1, peers[0].clone());
+    peer_manager.update_score(&peers[0], PeerScoreUpdateType::InvalidChunk);
+    peer_manager.disable_peer(&peers[1]);
+
+    peer_manager.reset();
+    assert!(peer_manager.get_last_request_time(1).is_none());
+    assert!(peer_manager.peer_score(&peers[0]).unwrap() > 99.0);
+    // disabled peers stay disabled
+    assert_eq!(peer_manager.pick_peer(), Some(peers[0].clone()));
+}
@@ -111,7 +111,11 @@ use libra_types::proof::{
     AccumulatorConsistencyProof, AccumulatorProof, AccumulatorRangeProof, MerkleTreeInternalNode,
 };
 use mirai_annotations::*;
-use std::marker::PhantomData;
+use std::{
+    collections::{HashMap, VecDeque},
+    marker::PhantomData,
+    sync::{Mutex, MutexGuard},
+};
 
 /// Defines the interface between `MerkleAccumulator` and underlying storage.
 pub trait HashReader {
@@ -157,6 +161,21 @@ where
         MerkleAccumulatorView::<R, H>::new(reader, num_leaves).get_proof(leaf_index)
     }
 
+    /// Like `get_proof`, returning the proof from `cache` if it was produced recently.
+    pub fn get_proof_cached(
+        reader: &R,
+        num_leaves: LeafCount,
+        leaf_index: u64,
+        cache: &ProofCache<H>,
+    ) -> Result<AccumulatorProof<H>>
+    where
+        H: Clone,
+    {
+        cache.get_or_insert_with(leaf_index, num_leaves, || {
+            Self::get_proof(reader, num_leaves, leaf_index)
+        })
+    }
+
     /// Gets a proof that shows the full accumulator is consistent with a smaller accumulator.
     ///
     /// See [`libra_types::proof::AccumulatorConsistencyProof`] for proof format.
@@ -201,6 +220,152 @@ where
     }
 }
 
+/// The roots of the frozen subtrees of an accumulator, which are the only nodes that appending to
+/// it reads. Keeping them in memory allows appending batches of leaves without reading storage.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FrozenSubtrees {
+    num_leaves: LeafCount,
+    roots: HashMap<Position, HashValue>,
+}
+
+impl FrozenSubtrees {
+    /// Constructs the frozen subtrees of an accumulator of `num_leaves` leaves from their root
+    /// hashes, from left to right, as returned by `MerkleAccumulator::get_frozen_subtree_hashes`.
+    pub fn new(num_leaves: LeafCount, root_hashes: &[HashValue]) -> Result<Self> {
+        let positions = FrozenSubTreeIterator::new(num_leaves).collect::<Vec<_>>();
+        ensure!(
+            positions.len() == root_hashes.len(),
+            "An accumulator of {} leaves has {} frozen subtrees, got {} hashes.",
+            num_leaves,
+            positions.len(),
+            root_hashes.len(),
+        );
+        Ok(Self {
+            num_leaves,
+            roots: positions
+                .into_iter()
+                .zip(root_hashes.iter().cloned())
+                .collect(),
+        })
+    }
+
+    pub fn num_leaves(&self) -> LeafCount {
+        self.num_leaves
+    }
+
+    /// The root hashes of the frozen subtrees, from left to right.
+    pub fn root_hashes(&self) -> Vec<HashValue> {
+        FrozenSubTreeIterator::new(self.num_leaves)
+            .map(|position| self.roots[&position])
+            .collect()
+    }
+
+    /// Appends `new_leaves` in one pass, returning the new root hash, the new nodes to be frozen
+    /// like `MerkleAccumulator::append`, and the frozen subtrees of the resulting accumulator.
+    pub fn append<H: CryptoHasher>(
+        &self,
+        new_leaves: &[HashValue],
+    ) -> Result<(HashValue, Vec<Node>, Self)> {
+        let (root_hash, to_freeze) =
+            MerkleAccumulatorView::<Self, H>::new(self, self.num_leaves).append(new_leaves)?;
+
+        let num_leaves = self.num_leaves + new_leaves.len() as LeafCount;
+        let new_nodes = to_freeze.iter().cloned().collect::<HashMap<_, _>>();
+        let roots = FrozenSubTreeIterator::new(num_leaves)
+            .map(|position| {
+                let hash = new_nodes
+                    .get(&position)
+                    .or_else(|| self.roots.get(&position))
+                    .expect("Frozen subtree roots are either new or existing ones.");
+                (position, *hash)
+            })
+            .collect();
+
+        Ok((root_hash, to_freeze, Self { num_leaves, roots }))
+    }
+}
+
+impl HashReader for FrozenSubtrees {
+    fn get(&self, position: Position) -> Result<HashValue> {
+        self.roots
+            .get(&position)
+            .cloned()
+            .ok_or_else(|| format_err!("{} is not the root of a frozen subtree.", position))
+    }
+}
+
+/// A bounded cache of the proofs produced for an accumulator, keyed by leaf index and number of
+/// leaves in the accumulator. Proofs towards a given ledger version never change, so the ones
+/// recently served can be served again without walking the accumulator. The oldest proof is
+/// evicted when the cache is full.
+pub struct ProofCache<H> {
+    capacity: usize,
+    inner: Mutex<ProofCacheInner<H>>,
+}
+
+struct ProofCacheInner<H> {
+    proofs: HashMap<(u64, LeafCount), AccumulatorProof<H>>,
+    insertion_order: VecDeque<(u64, LeafCount)>,
+}
+
+impl<H: Clone> ProofCache<H> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(ProofCacheInner {
+                proofs: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the cached proof of the leaf at `leaf_index` in an accumulator of `num_leaves`
+    /// leaves, or produces it with `get_proof` and caches it.
+    pub fn get_or_insert_with<F>(
+        &self,
+        leaf_index: u64,
+        num_leaves: LeafCount,
+        get_proof: F,
+    ) -> Result<AccumulatorProof<H>>
+    where
+        F: FnOnce() -> Result<AccumulatorProof<H>>,
+    {
+        let key = (leaf_index, num_leaves);
+        if let Some(proof) = self.lock().proofs.get(&key) {
+            return Ok(proof.clone());
+        }
+
+        // Do not hold the lock while producing the proof, which reads storage.
+        let proof = get_proof()?;
+        if self.capacity > 0 {
+            let mut inner = self.lock();
+            if inner.proofs.insert(key, proof.clone()).is_none() {
+                inner.insertion_order.push_back(key);
+                if inner.insertion_order.len() > self.capacity {
+                    let oldest = inner
+                        .insertion_order
+                        .pop_front()
+                        .expect("The cache is not empty.");
+                    inner.proofs.remove(&oldest);
+                }
+            }
+        }
+        Ok(proof)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().proofs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<ProofCacheInner<H>> {
+        self.inner.lock().expect("Proof cache should lock.")
+    }
+}
+
 /// Actual implementation of Merkle Accumulator algorithms, which carries the `reader` and
 /// `num_leaves` on an instance for convenience
 struct MerkleAccumulatorView<'a, R, H> {
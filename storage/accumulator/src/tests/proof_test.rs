@@ -21,6 +21,29 @@ fn test_one_leaf() {
     verify(&store, 1, root_hash, &[hash], 0)
 }
 
+#[test]
+fn test_proof_cache() {
+    let leaves = (0..10).map(|_| HashValue::random()).collect::<Vec<_>>();
+    let mut store = MockHashStore::new();
+    let (root_hash, writes) = TestAccumulator::append(&store, 0, &leaves).unwrap();
+    store.put_many(&writes);
+
+    let cache = ProofCache::new(4);
+    for (i, hash) in leaves.iter().enumerate() {
+        let proof = TestAccumulator::get_proof_cached(&store, 10, i as u64, &cache).unwrap();
+        proof.verify(root_hash, *hash, i as u64).unwrap();
+    }
+    assert_eq!(cache.len(), 4);
+
+    // Cached proofs are served without reading the store.
+    let empty_store = MockHashStore::new();
+    let proof = TestAccumulator::get_proof_cached(&empty_store, 10, 9, &cache).unwrap();
+    proof.verify(root_hash, leaves[9], 9).unwrap();
+    // The oldest proofs were evicted, and proofs towards another root are different entries.
+    assert!(TestAccumulator::get_proof_cached(&empty_store, 10, 0, &cache).is_err());
+    assert!(TestAccumulator::get_proof_cached(&empty_store, 9, 8, &cache).is_err());
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -68,4 +68,32 @@ proptest! {
         assert_eq!(root_hash, root_hash2);
         assert!(writes2.is_empty());
     }
+
+    #[test]
+    fn test_append_from_frozen_subtrees(batches in vec(vec(any::<HashValue>(), 0..10), 10)) {
+        let mut store = MockHashStore::new();
+        let mut frozen_subtrees = FrozenSubtrees::new(0, &[]).unwrap();
+
+        let mut num_leaves = 0;
+        for hashes in batches.iter() {
+            let (root_hash, writes) =
+                TestAccumulator::append(&store, num_leaves, &hashes).unwrap();
+            store.put_many(&writes);
+            num_leaves += hashes.len() as LeafCount;
+
+            let (root_hash2, writes2, new_frozen_subtrees) =
+                frozen_subtrees.append::<TestOnlyHasher>(&hashes).unwrap();
+            prop_assert_eq!(root_hash, root_hash2);
+            prop_assert_eq!(writes, writes2);
+            prop_assert_eq!(
+                new_frozen_subtrees.clone(),
+                FrozenSubtrees::new(
+                    num_leaves,
+                    &TestAccumulator::get_frozen_subtree_hashes(&store, num_leaves).unwrap(),
+                )
+                .unwrap()
+            );
+            frozen_subtrees = new_frozen_subtrees;
+        }
+    }
 }
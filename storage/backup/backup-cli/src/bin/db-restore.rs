@@ -31,6 +31,8 @@ async fn main() -> Result<()> {
             opt.global.db_dir,
             false, /* read_only */
             None,  /* pruner */
+            None,  /* archiver */
+            None,  /* waypoint_interval */
         )
         .expect("Failed opening DB."),
     );
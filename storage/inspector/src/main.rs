@@ -164,8 +164,13 @@ fn main() {
     let log_dir = tempfile::tempdir().expect("Unable to get temp dir");
     info!("Opening DB at: {:?}, log at {:?}", p, log_dir.path());
 
-    let db =
-        LibraDB::open(p, true /* readonly */, None /* pruner */).expect("Unable to open LibraDB");
+    let db = LibraDB::open(
+        p, true, /* readonly */
+        None, /* pruner */
+        None, /* archiver */
+        None, /* waypoint_interval */
+    )
+    .expect("Unable to open LibraDB");
     info!("DB opened successfully.");
 
     if let Some(cmd) = opt.cmd {
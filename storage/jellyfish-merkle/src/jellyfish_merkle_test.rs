@@ -528,6 +528,52 @@ fn test_put_blob_sets() {
     }
 }
 
+fn put_blob_set_one_by_one(
+    db: &MockTreeStore,
+    blob_set: Vec<(HashValue, AccountStateBlob)>,
+    version: Version,
+) -> (HashValue, TreeUpdateBatch) {
+    let mut tree_cache = TreeCache::new(db, version).unwrap();
+    for (key, blob) in blob_set {
+        JellyfishMerkleTree::put(key, blob, version, &mut tree_cache).unwrap();
+    }
+    tree_cache.freeze();
+    let (root_hashes, batch) = tree_cache.into();
+    (root_hashes[0], batch)
+}
+
+#[test]
+fn test_batch_put_same_as_put_one_by_one() {
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+    let mut keys = vec![];
+
+    for version in 0..3 {
+        // Overwrite some existing keys, one of them twice, and insert new ones.
+        let mut blob_set: Vec<_> = keys
+            .iter()
+            .step_by(2)
+            .cloned()
+            .chain((0..MIN_KEYS_FOR_BATCH_PUT).map(|_| HashValue::random()))
+            .map(|key| (key, AccountStateBlob::from(HashValue::random().to_vec())))
+            .collect();
+        blob_set.push((
+            blob_set[0].0,
+            AccountStateBlob::from(HashValue::random().to_vec()),
+        ));
+        keys.extend(blob_set.iter().map(|(key, _)| *key));
+
+        let expected = put_blob_set_one_by_one(&db, blob_set.clone(), version);
+        let (root_hash, batch) = tree.put_blob_set(blob_set.clone(), version).unwrap();
+        assert_eq!((root_hash, batch.clone()), expected);
+
+        db.write_tree_update_batch(batch).unwrap();
+        for (key, blob) in blob_set.into_iter().collect::<BTreeMap<_, _>>() {
+            assert_eq!(tree.get(key, version).unwrap(), Some(blob));
+        }
+    }
+}
+
 fn many_keys_get_proof_and_verify_tree_root(seed: &[u8], num_keys: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -82,6 +82,7 @@ mod tree_cache;
 
 use anyhow::{bail, ensure, format_err, Result};
 use libra_crypto::HashValue;
+use libra_nibble::Nibble;
 use libra_types::{
     account_state_blob::AccountStateBlob,
     proof::{SparseMerkleProof, SparseMerkleRangeProof},
@@ -91,6 +92,7 @@ use nibble_path::{skip_common_prefix, NibbleIterator, NibblePath};
 use node_type::{Child, Children, InternalNode, LeafNode, Node, NodeKey};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
+use rayon::prelude::*;
 use std::collections::{BTreeMap, BTreeSet};
 use tree_cache::TreeCache;
 
@@ -149,6 +151,62 @@ pub struct TreeUpdateBatch {
     pub num_stale_leaves: usize,
 }
 
+/// The number of keys from which a blob set is applied with a single pass over the tree, which
+/// updates independent subtrees in parallel.
+const MIN_KEYS_FOR_BATCH_PUT: usize = 512;
+
+/// During a batch put, the number of keys below an internal node from which its subtrees are
+/// updated in parallel rather than on the current thread.
+const MIN_KEYS_FOR_PARALLEL_SUBTREES: usize = 64;
+
+/// The changes to make to the tree cache to apply the update of a subtree during a batch put.
+#[derive(Default)]
+struct SubtreeUpdate {
+    /// The replaced nodes, with whether they are leaves.
+    deleted_nodes: Vec<(NodeKey, bool)>,
+    new_nodes: Vec<(NodeKey, Node)>,
+}
+
+impl SubtreeUpdate {
+    fn merge(&mut self, other: SubtreeUpdate) {
+        self.deleted_nodes.extend(other.deleted_nodes);
+        self.new_nodes.extend(other.new_nodes);
+    }
+}
+
+/// Splits `leaves`, sorted by key, by their nibble at `depth`, the index of the child of the
+/// internal node at `depth` that they are below.
+fn group_by_nibble(leaves: &[LeafNode], depth: usize) -> Vec<(Nibble, &[LeafNode])> {
+    let mut groups = vec![];
+    let mut rest = leaves;
+    while let Some(first) = rest.first() {
+        let nibble = first.account_key().get_nibble(depth);
+        let len = rest
+            .iter()
+            .position(|leaf| leaf.account_key().get_nibble(depth) != nibble)
+            .unwrap_or_else(|| rest.len());
+        let (group, remaining) = rest.split_at(len);
+        groups.push((nibble, group));
+        rest = remaining;
+    }
+    groups
+}
+
+/// Applies `f` to each group of leaves, in parallel if there are enough leaves. The results are
+/// in the order of `groups` either way.
+fn map_subtrees<T, F>(groups: &[(Nibble, &[LeafNode])], f: F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&(Nibble, &[LeafNode])) -> Result<T> + Send + Sync,
+{
+    let num_leaves: usize = groups.iter().map(|(_, leaves)| leaves.len()).sum();
+    if num_leaves >= MIN_KEYS_FOR_PARALLEL_SUBTREES {
+        groups.par_iter().map(f).collect()
+    } else {
+        groups.iter().map(f).collect()
+    }
+}
+
 /// The Jellyfish Merkle tree data structure. See [`crate`] for description.
 pub struct JellyfishMerkleTree<'a, R: 'a + TreeReader> {
     reader: &'a R,
@@ -171,7 +229,10 @@ where
         &self,
         blob_set: Vec<(HashValue, AccountStateBlob)>,
         version: Version,
-    ) -> Result<(HashValue, TreeUpdateBatch)> {
+    ) -> Result<(HashValue, TreeUpdateBatch)>
+    where
+        R: Sync,
+    {
         let (root_hashes, tree_update_batch) = self.put_blob_sets(vec![blob_set], version)?;
         assert_eq!(
             root_hashes.len(),
@@ -222,11 +283,18 @@ where
     /// the returned batch, the state `S_{i+1}` is ready to be read from the tree by calling
     /// [`get_with_proof`](struct.JellyfishMerkleTree.html#method.get_with_proof). Anything inside
     /// the batch is not reachable from public interfaces before being committed.
+    ///
+    /// A `blob_set` of at least `MIN_KEYS_FOR_BATCH_PUT` keys is applied in one pass instead of
+    /// key by key, hashing and constructing the independent subtrees in parallel. The output is
+    /// the same either way.
     pub fn put_blob_sets(
         &self,
         blob_sets: Vec<Vec<(HashValue, AccountStateBlob)>>,
         first_version: Version,
-    ) -> Result<(Vec<HashValue>, TreeUpdateBatch)> {
+    ) -> Result<(Vec<HashValue>, TreeUpdateBatch)>
+    where
+        R: Sync,
+    {
         let mut tree_cache = TreeCache::new(self.reader, first_version)?;
         for (idx, blob_set) in blob_sets.into_iter().enumerate() {
             assert!(
@@ -234,10 +302,14 @@ where
                 "Transactions that output empty write set should not be included.",
             );
             let version = first_version + idx as u64;
-            blob_set
-                .into_iter()
-                .map(|(key, blob)| Self::put(key, blob, version, &mut tree_cache))
-                .collect::<Result<_>>()?;
+            if blob_set.len() >= MIN_KEYS_FOR_BATCH_PUT {
+                Self::batch_put(blob_set, version, &mut tree_cache)?;
+            } else {
+                blob_set
+                    .into_iter()
+                    .map(|(key, blob)| Self::put(key, blob, version, &mut tree_cache))
+                    .collect::<Result<_>>()?;
+            }
             // Freezes the current cache to make all contents in the current cache immutable.
             tree_cache.freeze();
         }
@@ -271,6 +343,167 @@ where
         Ok(())
     }
 
+    /// Applies all of `blob_set` at `version` in one pass: each node on the paths to the keys is
+    /// replaced once, and the subtrees below an internal node are updated in parallel.
+    fn batch_put(
+        blob_set: Vec<(HashValue, AccountStateBlob)>,
+        version: Version,
+        tree_cache: &mut TreeCache<R>,
+    ) -> Result<()>
+    where
+        R: Sync,
+    {
+        // Sorted by key, the leaves below each node are contiguous. The last blob of a key wins,
+        // like when putting the keys one by one.
+        let leaves = blob_set
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(key, blob)| LeafNode::new(key, blob))
+            .collect::<Vec<_>>();
+
+        let root_node_key = tree_cache.get_root_node_key().clone();
+        let (new_root_node_key, _, update) =
+            Self::batch_insert_at(tree_cache, root_node_key, version, &leaves)?;
+
+        // Deletions come first, since a node can be replaced by one with the same key.
+        for (node_key, is_leaf) in update.deleted_nodes {
+            tree_cache.delete_node(&node_key, is_leaf);
+        }
+        for (node_key, node) in update.new_nodes {
+            tree_cache.put_node(node_key, node)?;
+        }
+        tree_cache.set_root_node_key(new_root_node_key);
+        Ok(())
+    }
+
+    /// Inserts `leaves`, sorted by key, into the subtree that starts from `node_key`. Returns the
+    /// new root of the subtree with its [`NodeKey`](node_type/struct.NodeKey.html), and the
+    /// changes to make to the tree cache, which is only read here so that subtrees can be
+    /// updated in parallel.
+    fn batch_insert_at(
+        tree_cache: &TreeCache<R>,
+        node_key: NodeKey,
+        version: Version,
+        leaves: &[LeafNode],
+    ) -> Result<(NodeKey, Node, SubtreeUpdate)>
+    where
+        R: Sync,
+    {
+        let mut update = SubtreeUpdate::default();
+        let (new_node_key, new_node, subtree_update) = match tree_cache.get_node(&node_key)? {
+            Node::Internal(internal_node) => {
+                update
+                    .deleted_nodes
+                    .push((node_key.clone(), false /* is_leaf */));
+
+                let groups = group_by_nibble(leaves, node_key.nibble_path().num_nibbles());
+                let new_children =
+                    map_subtrees(&groups, |(child_index, leaves)| {
+                        match internal_node.child(*child_index) {
+                            Some(child) => Self::batch_insert_at(
+                                tree_cache,
+                                node_key.gen_child_node_key(child.version, *child_index),
+                                version,
+                                leaves,
+                            ),
+                            None => Ok(Self::create_subtree(
+                                node_key.gen_child_node_key(version, *child_index),
+                                leaves,
+                            )),
+                        }
+                    })?;
+
+                // Reuse the current `InternalNode` in memory to create a new internal node.
+                let mut children: Children = internal_node.into();
+                let mut subtree_update = SubtreeUpdate::default();
+                for ((child_index, _), (_, new_child_node, child_update)) in
+                    groups.iter().zip(new_children)
+                {
+                    children.insert(
+                        *child_index,
+                        Child::new(new_child_node.hash(), version, new_child_node.is_leaf()),
+                    );
+                    subtree_update.merge(child_update);
+                }
+                let mut new_node_key = node_key;
+                new_node_key.set_version(version);
+                let new_node: Node = InternalNode::new(children).into();
+                subtree_update
+                    .new_nodes
+                    .push((new_node_key.clone(), new_node.clone()));
+                (new_node_key, new_node, subtree_update)
+            }
+            Node::Leaf(existing_leaf_node) => {
+                update
+                    .deleted_nodes
+                    .push((node_key.clone(), true /* is_leaf */));
+
+                // The existing leaf moves down with the incoming keys, unless one of them
+                // overwrites it.
+                let mut all_leaves = leaves.to_vec();
+                if let Err(index) = leaves
+                    .binary_search_by_key(&existing_leaf_node.account_key(), LeafNode::account_key)
+                {
+                    all_leaves.insert(index, existing_leaf_node);
+                }
+                Self::create_subtree(
+                    NodeKey::new(version, node_key.nibble_path().clone()),
+                    &all_leaves,
+                )
+            }
+            Node::Null => {
+                if node_key.nibble_path().num_nibbles() != 0 {
+                    bail!(
+                        "Null node exists for non-root node with node_key {:?}",
+                        node_key
+                    );
+                }
+                // delete the old null node if the at the same version.
+                if node_key.version() == version {
+                    update.deleted_nodes.push((node_key, false /* is_leaf */));
+                }
+                Self::create_subtree(NodeKey::new_empty_path(version), leaves)
+            }
+        };
+        update.merge(subtree_update);
+        Ok((new_node_key, new_node, update))
+    }
+
+    /// Creates the subtree holding `leaves`, sorted by key, that starts from `node_key`. Like when
+    /// a key is inserted next to an existing leaf, internal nodes with a single child are created
+    /// down to where the keys fork.
+    fn create_subtree(node_key: NodeKey, leaves: &[LeafNode]) -> (NodeKey, Node, SubtreeUpdate) {
+        let mut update = SubtreeUpdate::default();
+        let new_node: Node = if let [leaf] = leaves {
+            leaf.clone().into()
+        } else {
+            let version = node_key.version();
+            let groups = group_by_nibble(leaves, node_key.nibble_path().num_nibbles());
+            let new_children = map_subtrees(&groups, |(child_index, leaves)| {
+                Ok(Self::create_subtree(
+                    node_key.gen_child_node_key(version, *child_index),
+                    leaves,
+                ))
+            })
+            .expect("Creating subtrees does not fail.");
+
+            let mut children = Children::new();
+            for ((child_index, _), (_, new_child_node, child_update)) in
+                groups.iter().zip(new_children)
+            {
+                children.insert(
+                    *child_index,
+                    Child::new(new_child_node.hash(), version, new_child_node.is_leaf()),
+                );
+                update.merge(child_update);
+            }
+            InternalNode::new(children).into()
+        };
+        update.new_nodes.push((node_key.clone(), new_node.clone()));
+        (node_key, new_node, update)
+    }
+
     /// Helper function for recursive insertion into the subtree that starts from the current
     /// [`NodeKey`](node_type/struct.NodeKey.html). Returns the newly inserted node.
     /// It is safe to use recursion here because the max depth is limited by the key length which
@@ -0,0 +1,394 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides the cold tier of the transaction and event data. `Archiver` manages a
+//! thread that moves the transactions older than a window of recent versions, along with their
+//! events, out of RocksDB into the chunk files of an `Archive`: compressed, append-only files that
+//! each hold a range of consecutive versions. Archived data stays readable through the transaction
+//! and event stores, at a higher latency; the indices, transaction infos and accumulators are kept
+//! in RocksDB.
+
+use crate::{
+    errors::LibraDbError,
+    schema::{event::EventSchema, transaction::TransactionSchema},
+    OP_COUNTER,
+};
+use anyhow::{ensure, format_err, Result};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use libra_logger::prelude::*;
+use libra_types::{
+    block_metadata::BlockMetadata,
+    contract_event::ContractEvent,
+    transaction::{Transaction, Version},
+};
+use schemadb::{ReadOptions, DB};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex, RwLock,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+#[cfg(test)]
+use std::{thread::sleep, time::Instant};
+
+const CHUNK_EXTENSION: &str = "chunk";
+
+/// The transactions of a range of consecutive versions, with their events, as stored in a chunk
+/// file.
+#[derive(Debug, Deserialize, Serialize)]
+struct ArchiveChunk {
+    first_version: Version,
+    transactions: Vec<Transaction>,
+    /// The events of each transaction, in the order they were emitted
+    events: Vec<Vec<ContractEvent>>,
+}
+
+impl ArchiveChunk {
+    fn end_version(&self) -> Version {
+        self.first_version + self.transactions.len() as Version
+    }
+}
+
+/// The chunk files of the archived versions, in a directory. The chunks cover all the versions
+/// from 0 up to `archived_up_to()`, excluded.
+pub(crate) struct Archive {
+    dir: PathBuf,
+    /// The path of each chunk file and its end version (excluded), by first version
+    chunks: RwLock<BTreeMap<Version, (Version, PathBuf)>>,
+    /// The last chunk read, decoded, as reads of archived data tend to be sequential
+    last_read: Mutex<Option<Arc<ArchiveChunk>>>,
+}
+
+impl Archive {
+    /// Opens the archive in `dir`, creating the directory if needed.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let mut chunks = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            // Chunk files being written have another extension until complete.
+            if path.extension().and_then(|ext| ext.to_str()) != Some(CHUNK_EXTENSION) {
+                continue;
+            }
+            let (first_version, end_version) = parse_chunk_name(&path)?;
+            chunks.insert(first_version, (end_version, path));
+        }
+
+        let mut expected_first_version = 0;
+        for (first_version, (end_version, path)) in &chunks {
+            ensure!(
+                *first_version == expected_first_version,
+                "Archive corrupt: chunk {:?} starts at version {}, expected {}.",
+                path,
+                first_version,
+                expected_first_version,
+            );
+            expected_first_version = *end_version;
+        }
+
+        Ok(Self {
+            dir,
+            chunks: RwLock::new(chunks),
+            last_read: Mutex::new(None),
+        })
+    }
+
+    /// The versions below this one are archived.
+    pub fn archived_up_to(&self) -> Version {
+        self.chunks
+            .read()
+            .expect("Archive chunks should lock.")
+            .values()
+            .next_back()
+            .map_or(0, |(end_version, _)| *end_version)
+    }
+
+    pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
+        let chunk = self.get_chunk(version)?;
+        Ok(chunk.transactions[(version - chunk.first_version) as usize].clone())
+    }
+
+    pub fn get_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        let chunk = self.get_chunk(version)?;
+        Ok(chunk.events[(version - chunk.first_version) as usize].clone())
+    }
+
+    /// Returns the block metadata carried on the archived block metadata transaction at or
+    /// preceding `version`, looking at `max_versions` versions at most.
+    pub fn get_block_metadata(
+        &self,
+        version: Version,
+        max_versions: usize,
+    ) -> Result<Option<(Version, BlockMetadata)>> {
+        let mut version = version;
+        for _ in 0..max_versions {
+            if let Transaction::BlockMetadata(block_meta) = self.get_transaction(version)? {
+                return Ok(Some((version, block_meta)));
+            } else if version == 0 {
+                return Ok(None);
+            }
+            version -= 1;
+        }
+
+        Err(LibraDbError::NotFound(format!("BlockMetadata preceding version {}", version)).into())
+    }
+
+    /// Writes the chunk following the archived versions. The file is written under a temporary
+    /// name and renamed once complete.
+    fn append(&self, chunk: ArchiveChunk) -> Result<()> {
+        ensure!(
+            chunk.first_version == self.archived_up_to(),
+            "Archive chunk starts at version {}, expected {}.",
+            chunk.first_version,
+            self.archived_up_to(),
+        );
+        let end_version = chunk.end_version();
+        let path = self.dir.join(format!(
+            "{:020}-{:020}.{}",
+            chunk.first_version, end_version, CHUNK_EXTENSION
+        ));
+        let tmp_path = path.with_extension("tmp");
+
+        let mut encoder = DeflateEncoder::new(File::create(&tmp_path)?, Compression::default());
+        encoder.write_all(&lcs::to_bytes(&chunk)?)?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+
+        self.chunks
+            .write()
+            .expect("Archive chunks should lock.")
+            .insert(chunk.first_version, (end_version, path));
+        Ok(())
+    }
+
+    fn get_chunk(&self, version: Version) -> Result<Arc<ArchiveChunk>> {
+        let mut last_read = self.last_read.lock().expect("Archive cache should lock.");
+        if let Some(chunk) = last_read.as_ref() {
+            if chunk.first_version <= version && version < chunk.end_version() {
+                return Ok(Arc::clone(chunk));
+            }
+        }
+
+        let path = self
+            .chunks
+            .read()
+            .expect("Archive chunks should lock.")
+            .range(..=version)
+            .next_back()
+            .filter(|(_, (end_version, _))| version < *end_version)
+            .map(|(_, (_, path))| path.clone())
+            .ok_or_else(|| LibraDbError::NotFound(format!("Archived txn {}", version)))?;
+        let mut bytes = vec![];
+        DeflateDecoder::new(File::open(&path)?).read_to_end(&mut bytes)?;
+        let chunk = Arc::new(lcs::from_bytes::<ArchiveChunk>(&bytes)?);
+        *last_read = Some(Arc::clone(&chunk));
+        Ok(chunk)
+    }
+}
+
+fn parse_chunk_name(path: &Path) -> Result<(Version, Version)> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| format_err!("Invalid archive file name {:?}.", path))?;
+    let mut versions = name
+        .splitn(2, '-')
+        .map(|version| version.parse::<Version>());
+    match (versions.next(), versions.next()) {
+        (Some(Ok(first_version)), Some(Ok(end_version))) if first_version < end_version => {
+            Ok((first_version, end_version))
+        }
+        _ => Err(format_err!("Invalid archive file name {:?}.", path)),
+    }
+}
+
+/// Moves the transactions in `[first_version, end_version)` and their events from the DB to the
+/// archive.
+fn archive_versions(
+    db: &DB,
+    archive: &Archive,
+    first_version: Version,
+    end_version: Version,
+) -> Result<()> {
+    let mut transactions = Vec::with_capacity((end_version - first_version) as usize);
+    let mut iter = db.iter::<TransactionSchema>(ReadOptions::default())?;
+    iter.seek(&first_version)?;
+    for res in iter.take((end_version - first_version) as usize) {
+        let (version, transaction) = res?;
+        ensure!(
+            version == first_version + transactions.len() as Version,
+            "Transaction versions are not consecutive.",
+        );
+        transactions.push(transaction);
+    }
+    ensure!(
+        transactions.len() as Version == end_version - first_version,
+        "Transactions to archive are missing from version {}.",
+        first_version + transactions.len() as Version,
+    );
+
+    let mut events = vec![vec![]; transactions.len()];
+    let mut iter = db.iter::<EventSchema>(ReadOptions::default())?;
+    iter.seek(&first_version)?;
+    for res in iter {
+        let ((version, _index), event) = res?;
+        if version >= end_version {
+            break;
+        }
+        events[(version - first_version) as usize].push(event);
+    }
+
+    archive.append(ArchiveChunk {
+        first_version,
+        transactions,
+        events,
+    })?;
+    delete_archived(db, first_version, end_version)
+}
+
+/// Deletes the archived versions in `[first_version, end_version)` from the DB.
+fn delete_archived(db: &DB, first_version: Version, end_version: Version) -> Result<()> {
+    db.range_delete::<TransactionSchema, Version>(&first_version, &end_version)?;
+    db.range_delete::<EventSchema, Version>(&first_version, &end_version)
+}
+
+/// The `Archiver` is meant to be part of a `LibraDB` instance and runs in the background to move
+/// old transactions and events to the archive.
+///
+/// Like the `Pruner`, it creates a worker thread on construction and joins it on destruction,
+/// without waiting for all pending work to be done.
+pub(crate) struct Archiver {
+    /// Other than the latest version, how many historical versions to keep in the DB.
+    versions_to_keep_in_db: u64,
+    worker_thread: Option<JoinHandle<()>>,
+    command_sender: Mutex<Sender<Command>>,
+    #[allow(dead_code)]
+    archive: Arc<Archive>,
+}
+
+impl Archiver {
+    /// The number of versions in each chunk file.
+    #[cfg(not(test))]
+    const VERSIONS_PER_CHUNK: u64 = 10_000;
+    #[cfg(test)]
+    const VERSIONS_PER_CHUNK: u64 = 4;
+
+    /// Creates a worker thread that waits on a channel for archiving commands. The versions
+    /// archived by a previous run that were not deleted from the DB yet are deleted first.
+    pub fn new(db: Arc<DB>, archive: Arc<Archive>, versions_to_keep_in_db: u64) -> Result<Self> {
+        delete_archived(&db, 0, archive.archived_up_to())?;
+
+        let (command_sender, command_receiver) = channel();
+        let worker_archive = Arc::clone(&archive);
+        let worker_thread = std::thread::Builder::new()
+            .name("libradb_archiver".into())
+            .spawn(move || work_loop(db, worker_archive, command_receiver))
+            .expect("Creating archiver thread should succeed.");
+
+        Ok(Self {
+            versions_to_keep_in_db,
+            worker_thread: Some(worker_thread),
+            command_sender: Mutex::new(command_sender),
+            archive,
+        })
+    }
+
+    /// Sends an archiving command to the worker thread when a whole chunk can be archived.
+    pub fn wake(&self, latest_version: Version) {
+        if latest_version > self.versions_to_keep_in_db + Self::VERSIONS_PER_CHUNK {
+            self.command_sender
+                .lock()
+                .expect("command_sender to archiver thread should lock.")
+                .send(Command::Archive {
+                    end_version: latest_version - self.versions_to_keep_in_db,
+                })
+                .expect("Receiver should not destruct prematurely.");
+        }
+    }
+
+    /// (For tests only.) Notifies the worker thread and waits for it to archive the versions
+    /// below `end_version`.
+    #[cfg(test)]
+    pub fn wake_and_wait(&self, latest_version: Version, end_version: Version) -> Result<()> {
+        self.wake(latest_version);
+
+        const TIMEOUT: Duration = Duration::from_secs(10);
+        let end = Instant::now() + TIMEOUT;
+        while Instant::now() < end {
+            if self.archive.archived_up_to() >= end_version {
+                return Ok(());
+            }
+            sleep(Duration::from_millis(1));
+        }
+        anyhow::bail!("Timeout waiting for archiver worker.");
+    }
+}
+
+impl Drop for Archiver {
+    fn drop(&mut self) {
+        self.command_sender
+            .lock()
+            .expect("Locking command_sender should not fail.")
+            .send(Command::Quit)
+            .expect("Receiver should not destruct.");
+        self.worker_thread
+            .take()
+            .expect("Worker thread must exist.")
+            .join()
+            .expect("Worker thread should join peacefully.");
+    }
+}
+
+enum Command {
+    Quit,
+    Archive { end_version: Version },
+}
+
+fn work_loop(db: Arc<DB>, archive: Arc<Archive>, command_receiver: Receiver<Command>) {
+    let mut target_end_version = 0;
+    loop {
+        // Archive one chunk at a time, receiving commands in between in case `Command::Quit` is
+        // received. Wait for the next command when there is nothing to archive.
+        let first_version = archive.archived_up_to();
+        let chunk_end_version = first_version + Archiver::VERSIONS_PER_CHUNK;
+        let command = if chunk_end_version <= target_end_version {
+            command_receiver.try_recv().ok()
+        } else {
+            Some(
+                command_receiver
+                    .recv()
+                    .expect("Sender should not destruct prematurely."),
+            )
+        };
+        match command {
+            Some(Command::Quit) => return,
+            Some(Command::Archive { end_version }) => {
+                target_end_version = target_end_version.max(end_version);
+                continue;
+            }
+            None => (),
+        }
+
+        match archive_versions(&db, &archive, first_version, chunk_end_version) {
+            Ok(()) => OP_COUNTER.set("archiver.archived_up_to", chunk_end_version as usize),
+            Err(e) => {
+                crit!("Error archiving versions from {}. {:?}", first_version, e);
+                // Stop retrying vigorously, until the next command.
+                target_end_version = first_version;
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,160 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    change_set::ChangeSet, event_store::EventStore, transaction_store::TransactionStore, LibraDB,
+};
+use libra_crypto::{hash::CryptoHash, HashValue};
+use libra_temppath::TempPath;
+use libra_types::{account_address::AccountAddress, event::EventKey, language_storage::TypeTag};
+
+fn transaction(version: Version) -> Transaction {
+    Transaction::BlockMetadata(BlockMetadata::new(
+        HashValue::zero(),
+        HashValue::zero(),
+        version, /* round */
+        0,       /* timestamp_usecs */
+        vec![],
+        AccountAddress::random(),
+        vec![],
+    ))
+}
+
+/// Transactions at even versions emit two events, the others none.
+fn events(version: Version) -> Vec<ContractEvent> {
+    if version % 2 == 0 {
+        (0..2)
+            .map(|i| ContractEvent::new(EventKey::random(), i, TypeTag::U64, vec![version as u8]))
+            .collect()
+    } else {
+        vec![]
+    }
+}
+
+/// Saves the transactions and events of versions `[0, num_versions)`, returning the event root
+/// hash of each version.
+fn save(db: &LibraDB, num_versions: Version) -> Vec<(Transaction, Vec<ContractEvent>, HashValue)> {
+    (0..num_versions)
+        .map(|version| {
+            let txn = transaction(version);
+            let events = events(version);
+            let mut cs = ChangeSet::new();
+            db.transaction_store
+                .put_transaction(version, &txn, &mut cs)
+                .unwrap();
+            let root_hash = db
+                .event_store
+                .put_events(version, &events, &mut cs)
+                .unwrap();
+            db.db.write_schemas(cs.batch).unwrap();
+            (txn, events, root_hash)
+        })
+        .collect()
+}
+
+fn verify(
+    transaction_store: &TransactionStore,
+    event_store: &EventStore,
+    expected: &[(Transaction, Vec<ContractEvent>, HashValue)],
+) {
+    for (version, (txn, events, root_hash)) in expected.iter().enumerate() {
+        let version = version as Version;
+        assert_eq!(&transaction_store.get_transaction(version).unwrap(), txn);
+        assert_eq!(&event_store.get_events_by_version(version).unwrap(), events);
+        for (index, event) in events.iter().enumerate() {
+            let (actual, proof) = event_store
+                .get_event_with_proof_by_version_and_index(version, index as u64)
+                .unwrap();
+            assert_eq!(&actual, event);
+            proof
+                .verify(*root_hash, event.hash(), index as u64)
+                .unwrap();
+        }
+        assert!(event_store
+            .get_event_with_proof_by_version_and_index(version, events.len() as u64)
+            .is_err());
+        assert_eq!(
+            transaction_store
+                .get_block_metadata(version)
+                .unwrap()
+                .unwrap()
+                .0,
+            version
+        );
+    }
+
+    let txns = transaction_store
+        .get_transaction_iter(1, expected.len() as u64 - 1)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        txns,
+        expected[1..]
+            .iter()
+            .map(|(txn, _, _)| txn.clone())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_archive_versions() {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+    let expected = save(&db, 7);
+
+    let archive = Arc::new(Archive::open(tmp_dir.path().join("archive")).unwrap());
+    assert_eq!(archive.archived_up_to(), 0);
+    archive_versions(&db.db, &archive, 0, 3).unwrap();
+    archive_versions(&db.db, &archive, 3, 5).unwrap();
+    assert_eq!(archive.archived_up_to(), 5);
+    // Chunks must follow each other.
+    assert!(archive_versions(&db.db, &archive, 6, 7).is_err());
+
+    // Archived versions are gone from the DB.
+    for version in 0..5 {
+        assert!(db.db.get::<TransactionSchema>(&version).unwrap().is_none());
+        assert!(db.db.get::<EventSchema>(&(version, 0)).unwrap().is_none());
+    }
+    assert!(db.db.get::<TransactionSchema>(&5).unwrap().is_some());
+
+    let transaction_store = TransactionStore::new(Arc::clone(&db.db), Some(Arc::clone(&archive)));
+    let event_store = EventStore::new(Arc::clone(&db.db), Some(archive));
+    verify(&transaction_store, &event_store, &expected);
+
+    // The chunks are found when reopening the archive.
+    let archive = Arc::new(Archive::open(tmp_dir.path().join("archive")).unwrap());
+    assert_eq!(archive.archived_up_to(), 5);
+    let transaction_store = TransactionStore::new(Arc::clone(&db.db), Some(Arc::clone(&archive)));
+    let event_store = EventStore::new(Arc::clone(&db.db), Some(archive));
+    verify(&transaction_store, &event_store, &expected);
+}
+
+#[test]
+fn test_archiver() {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+    let expected = save(&db, 11);
+
+    let archive = Arc::new(Archive::open(tmp_dir.path().join("archive")).unwrap());
+    let archiver = Archiver::new(
+        Arc::clone(&db.db),
+        Arc::clone(&archive),
+        2, /* versions_to_keep_in_db */
+    )
+    .unwrap();
+
+    // Not enough versions for a whole chunk outside of the window.
+    archiver.wake(5 /* latest_version */);
+    // Two chunks of 4 versions.
+    archiver
+        .wake_and_wait(10 /* latest_version */, 8 /* end_version */)
+        .unwrap();
+    drop(archiver);
+    assert_eq!(archive.archived_up_to(), 8);
+
+    let transaction_store = TransactionStore::new(Arc::clone(&db.db), Some(Arc::clone(&archive)));
+    let event_store = EventStore::new(Arc::clone(&db.db), Some(archive));
+    verify(&transaction_store, &event_store, &expected);
+}
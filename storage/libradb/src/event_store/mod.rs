@@ -7,6 +7,7 @@
 
 use super::LibraDB;
 use crate::{
+    archive::Archive,
     change_set::ChangeSet,
     errors::LibraDbError,
     ledger_counters::LedgerCounter,
@@ -33,17 +34,30 @@ use std::{convert::TryFrom, sync::Arc};
 
 pub(crate) struct EventStore {
     db: Arc<DB>,
+    /// Where the events of the transactions before `Archive::archived_up_to()` are, if archiving
+    /// is enabled.
+    archive: Option<Arc<Archive>>,
 }
 
 impl EventStore {
-    pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<DB>, archive: Option<Arc<Archive>>) -> Self {
+        Self { db, archive }
+    }
+
+    /// Returns the archive if the events of the transaction at `version` are in it.
+    fn archive_for(&self, version: Version) -> Option<&Archive> {
+        self.archive
+            .as_deref()
+            .filter(|archive| version < archive.archived_up_to())
     }
 
     /// Get all of the events given a transaction version.
     /// We don't need a proof for this because it's only used to get all events
     /// for a version which can be proved from the root hash of the event tree.
     pub fn get_events_by_version(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        if let Some(archive) = self.archive_for(version) {
+            return archive.get_events(version);
+        }
         let mut events = vec![];
 
         let mut iter = self.db.iter::<EventSchema>(ReadOptions::default())?;
@@ -65,6 +79,18 @@ impl EventStore {
         version: Version,
         index: u64,
     ) -> Result<(ContractEvent, EventAccumulatorProof)> {
+        if let Some(archive) = self.archive_for(version) {
+            let mut events = archive.get_events(version)?;
+            ensure!(
+                index < events.len() as u64,
+                LibraDbError::NotFound(format!("Event {} of Txn {}", index, version)),
+            );
+            let num_events = events.len() as u64;
+            let proof =
+                Accumulator::get_proof(&EventHashReader::new(self, version), num_events, index)?;
+            return Ok((events.swap_remove(index as usize), proof));
+        }
+
         // Get event content.
         let event = self
             .db
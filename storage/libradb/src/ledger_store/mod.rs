@@ -8,12 +8,15 @@ use crate::{
     change_set::ChangeSet,
     errors::LibraDbError,
     schema::{
-        epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema,
+        commit_record::{CommitRecord, CommitRecordSchema},
+        epoch_by_version::EpochByVersionSchema,
+        ledger_info::LedgerInfoSchema,
         transaction_accumulator::TransactionAccumulatorSchema,
         transaction_info::TransactionInfoSchema,
+        waypoint::WaypointSchema,
     },
 };
-use accumulator::{HashReader, MerkleAccumulator};
+use accumulator::{FrozenSubtrees, HashReader, MerkleAccumulator, ProofCache};
 use anyhow::{ensure, format_err, Result};
 use arc_swap::ArcSwap;
 use itertools::Itertools;
@@ -29,10 +32,15 @@ use libra_types::{
         TransactionAccumulatorProof, TransactionAccumulatorRangeProof, TransactionInfoWithProof,
     },
     transaction::{TransactionInfo, Version},
+    waypoint::{SignedWaypoint, Waypoint},
 };
 use schemadb::{ReadOptions, SchemaIterator, DB};
-use std::{ops::Deref, sync::Arc};
-use storage_interface::{StartupInfo, TreeState};
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+use storage_interface::{LedgerInfoSubscription, StartupInfo, TreeState};
+use tokio::sync::watch;
 
 pub(crate) struct LedgerStore {
     db: Arc<DB>,
@@ -41,8 +49,25 @@ pub(crate) struct LedgerStore {
     /// cache it in memory in order to avoid reading DB and deserializing the object frequently. It
     /// should be updated every time new ledger info and signatures are persisted.
     latest_ledger_info: ArcSwap<Option<LedgerInfoWithSignatures>>,
+
+    /// Publishes each ledger info persisted to the subscribers. The store keeps a receiver of its
+    /// own, which is cloned for each subscriber.
+    ledger_info_sender: watch::Sender<Option<LedgerInfoWithSignatures>>,
+    ledger_info_receiver: LedgerInfoSubscription,
+
+    /// The frozen subtrees of the transaction accumulator after the last transaction infos were
+    /// put, so that appending the next ones doesn't read the DB. If the last put was not
+    /// committed, they don't match the next put and are read from the DB again.
+    frozen_subtrees: Mutex<Option<FrozenSubtrees>>,
+
+    /// Proofs of transactions recently served, which are often requested repeatedly towards the
+    /// same ledger version.
+    proof_cache: ProofCache<TransactionAccumulatorHasher>,
 }
 
+/// The number of transaction accumulator proofs cached.
+const PROOF_CACHE_CAPACITY: usize = 10_000;
+
 impl LedgerStore {
     pub fn new(db: Arc<DB>) -> Self {
         // Upon restart, read the latest ledger info and signatures and cache them in memory.
@@ -57,9 +82,14 @@ impl LedgerStore {
                 .map(|kv| kv.1)
         };
 
+        let (ledger_info_sender, ledger_info_receiver) = watch::channel(ledger_info.clone());
         Self {
             db,
             latest_ledger_info: ArcSwap::from(Arc::new(ledger_info)),
+            ledger_info_sender,
+            ledger_info_receiver,
+            frozen_subtrees: Mutex::new(None),
+            proof_cache: ProofCache::new(PROOF_CACHE_CAPACITY),
         }
     }
 
@@ -180,7 +210,28 @@ impl LedgerStore {
 
     pub fn set_latest_ledger_info(&self, ledger_info_with_sigs: LedgerInfoWithSignatures) {
         self.latest_ledger_info
-            .store(Arc::new(Some(ledger_info_with_sigs)));
+            .store(Arc::new(Some(ledger_info_with_sigs.clone())));
+        // This never fails, as the store holds a receiver.
+        let _ = self
+            .ledger_info_sender
+            .broadcast(Some(ledger_info_with_sigs));
+    }
+
+    pub fn subscribe_to_ledger_infos(&self) -> LedgerInfoSubscription {
+        self.ledger_info_receiver.clone()
+    }
+
+    /// Returns the record of the last two-phase commit, if any.
+    pub fn get_commit_record(&self) -> Result<Option<CommitRecord>> {
+        self.db.get::<CommitRecordSchema>(&())
+    }
+
+    pub fn put_commit_record(
+        &self,
+        commit_record: &CommitRecord,
+        cs: &mut ChangeSet,
+    ) -> Result<()> {
+        cs.batch.put::<CommitRecordSchema>(&(), commit_record)
     }
 
     pub fn get_latest_ledger_info_in_epoch(&self, epoch: u64) -> Result<LedgerInfoWithSignatures> {
@@ -189,6 +240,23 @@ impl LedgerStore {
         })
     }
 
+    /// Returns the waypoint emitted last, with the ledger info it was generated from.
+    pub fn get_latest_signed_waypoint(&self) -> Result<Option<SignedWaypoint>> {
+        let mut iter = self.db.iter::<WaypointSchema>(ReadOptions::default())?;
+        iter.seek_to_last();
+        let (epoch, waypoint) = match iter.next().transpose()? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let signed_waypoint = SignedWaypoint::new(self.get_latest_ledger_info_in_epoch(epoch)?)?;
+        ensure!(
+            signed_waypoint.waypoint() == waypoint,
+            "DB corruption: the waypoint of epoch {} doesn't match its ledger info",
+            epoch,
+        );
+        Ok(Some(signed_waypoint))
+    }
+
     fn get_epoch_state(&self, epoch: u64) -> Result<EpochState> {
         ensure!(epoch > 0, "EpochState only queryable for epoch >= 1.",);
 
@@ -317,7 +385,12 @@ impl LedgerStore {
         version: Version,
         ledger_version: Version,
     ) -> Result<TransactionAccumulatorProof> {
-        Accumulator::get_proof(self, ledger_version + 1 /* num_leaves */, version)
+        Accumulator::get_proof_cached(
+            self,
+            ledger_version + 1, /* num_leaves */
+            version,
+            &self.proof_cache,
+        )
     }
 
     /// Get proof for `num_txns` consecutive transactions starting from `start_version` towards
@@ -362,11 +435,23 @@ impl LedgerStore {
 
         // write hash of txn_info into the accumulator
         let txn_hashes: Vec<HashValue> = txn_infos.iter().map(TransactionInfo::hash).collect();
-        let (root_hash, writes) = Accumulator::append(
-            self,
-            first_version, /* num_existing_leaves */
-            &txn_hashes,
-        )?;
+        let (root_hash, writes) = {
+            let mut frozen_subtrees = self
+                .frozen_subtrees
+                .lock()
+                .expect("Frozen subtrees should lock.");
+            let existing = match frozen_subtrees.take() {
+                Some(existing) if existing.num_leaves() == first_version => existing,
+                _ => FrozenSubtrees::new(
+                    first_version,
+                    &Accumulator::get_frozen_subtree_hashes(self, first_version)?,
+                )?,
+            };
+            let (root_hash, writes, new_frozen_subtrees) =
+                existing.append::<TransactionAccumulatorHasher>(&txn_hashes)?;
+            *frozen_subtrees = Some(new_frozen_subtrees);
+            (root_hash, writes)
+        };
         writes
             .iter()
             .map(|(pos, hash)| cs.batch.put::<TransactionAccumulatorSchema>(pos, hash))
@@ -392,6 +477,19 @@ impl LedgerStore {
             ledger_info_with_sigs,
         )
     }
+
+    /// Write the waypoint of the epoch-ending `ledger_info_with_sigs` to `batch`.
+    pub fn put_waypoint(
+        &self,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+        cs: &mut ChangeSet,
+    ) -> Result<()> {
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        cs.batch.put::<WaypointSchema>(
+            &ledger_info.epoch(),
+            &Waypoint::new_epoch_boundary(ledger_info)?,
+        )
+    }
 }
 
 type Accumulator = MerkleAccumulator<LedgerStore, TransactionAccumulatorHasher>;
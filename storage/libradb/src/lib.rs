@@ -17,6 +17,7 @@ pub mod backup;
 pub mod errors;
 pub mod schema;
 
+mod archive;
 mod change_set;
 mod event_store;
 mod ledger_counters;
@@ -34,6 +35,7 @@ mod libradb_test;
 pub use libradb_test::test_save_blocks_impl;
 
 use crate::{
+    archive::{Archive, Archiver},
     backup::BackupHandler,
     change_set::{ChangeSet, SealedChangeSet},
     errors::LibraDbError,
@@ -41,12 +43,12 @@ use crate::{
     ledger_counters::LedgerCounters,
     ledger_store::LedgerStore,
     pruner::Pruner,
-    schema::*,
+    schema::{commit_record::CommitRecord, *},
     state_store::StateStore,
     system_store::SystemStore,
     transaction_store::TransactionStore,
 };
-use anyhow::{ensure, Result};
+use anyhow::{bail, ensure, Result};
 use itertools::{izip, zip_eq};
 use jellyfish_merkle::{restore::JellyfishMerkleRestore, TreeReader, TreeWriter};
 use libra_crypto::hash::{CryptoHash, HashValue, SPARSE_MERKLE_PLACEHOLDER_HASH};
@@ -57,24 +59,27 @@ use libra_metrics::{
 };
 use libra_types::{
     account_address::AccountAddress,
-    account_state_blob::{AccountStateBlob, AccountStateWithProof},
+    account_state_blob::{AccountStateBlob, AccountStateWithProof, AccountStatesWithProof},
     contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{
-        AccountStateProof, AccumulatorConsistencyProof, EventProof, SparseMerkleProof,
-        SparseMerkleRangeProof, TransactionListProof,
+        AccountStateProof, AccountStatesProof, AccumulatorConsistencyProof, EventProof,
+        SparseMerkleProof, SparseMerkleRangeProof, TransactionListProof,
     },
     transaction::{
         TransactionInfo, TransactionListWithProof, TransactionToCommit, TransactionWithProof,
         Version, PRE_GENESIS_VERSION,
     },
+    waypoint::SignedWaypoint,
 };
 use once_cell::sync::Lazy;
 use schemadb::{DB, DEFAULT_CF_NAME};
 use std::{iter::Iterator, path::Path, sync::Arc, time::Instant};
-use storage_interface::{DbReader, DbWriter, StartupInfo, TreeState};
+use storage_interface::{
+    DbReader, DbWriter, LedgerInfoSubscription, Order, StartupInfo, TreeState,
+};
 
 static OP_COUNTER: Lazy<OpMetrics> = Lazy::new(|| OpMetrics::new_and_registered("storage"));
 
@@ -130,6 +135,9 @@ pub struct LibraDB {
     event_store: EventStore,
     system_store: SystemStore,
     pruner: Option<Pruner>,
+    archiver: Option<Archiver>,
+    /// A waypoint is emitted at the end of every `waypoint_interval` epochs, if set.
+    waypoint_interval: Option<u64>,
 }
 
 impl LibraDB {
@@ -137,9 +145,16 @@ impl LibraDB {
         db_root_path: P,
         readonly: bool,
         prune_window: Option<u64>,
+        archive_window: Option<u64>,
+        waypoint_interval: Option<u64>,
     ) -> Result<Self> {
+        ensure!(
+            waypoint_interval != Some(0),
+            "The waypoint interval must be positive."
+        );
         let column_families = vec![
             /* LedgerInfo CF = */ DEFAULT_CF_NAME,
+            COMMIT_RECORD_CF_NAME,
             EPOCH_BY_VERSION_CF_NAME,
             EVENT_ACCUMULATOR_CF_NAME,
             EVENT_BY_KEY_CF_NAME,
@@ -151,6 +166,7 @@ impl LibraDB {
             TRANSACTION_ACCUMULATOR_CF_NAME,
             TRANSACTION_BY_ACCOUNT_CF_NAME,
             TRANSACTION_INFO_CF_NAME,
+            WAYPOINT_CF_NAME,
         ];
 
         let path = db_root_path.as_ref().join("libradb");
@@ -168,24 +184,48 @@ impl LibraDB {
             instant.elapsed().as_millis()
         );
 
-        Ok(LibraDB {
+        // Archived data is read whenever the archive directory exists, so that a readonly
+        // instance sees the data archived by the node.
+        let archive_path = db_root_path.as_ref().join("archive");
+        let archive = if archive_window.is_some() || archive_path.exists() {
+            Some(Arc::new(Archive::open(archive_path)?))
+        } else {
+            None
+        };
+        let archiver = match (archive_window, &archive) {
+            (Some(n), Some(archive)) if !readonly => {
+                Some(Archiver::new(Arc::clone(&db), Arc::clone(archive), n)?)
+            }
+            _ => None,
+        };
+
+        let libra_db = LibraDB {
             db: Arc::clone(&db),
-            event_store: EventStore::new(Arc::clone(&db)),
+            event_store: EventStore::new(Arc::clone(&db), archive.clone()),
             ledger_store: Arc::new(LedgerStore::new(Arc::clone(&db))),
             state_store: Arc::new(StateStore::new(Arc::clone(&db))),
-            transaction_store: Arc::new(TransactionStore::new(Arc::clone(&db))),
+            transaction_store: Arc::new(TransactionStore::new(Arc::clone(&db), archive)),
             system_store: SystemStore::new(Arc::clone(&db)),
             pruner: prune_window.map(|n| Pruner::new(Arc::clone(&db), n)),
-        })
+            archiver,
+            waypoint_interval,
+        };
+        if !readonly {
+            libra_db.complete_pending_commit()?;
+        }
+        Ok(libra_db)
     }
 
-    /// This opens db in non-readonly mode, without the pruner.
+    /// This opens db in non-readonly mode, without the pruner and the archiver, emitting a
+    /// waypoint at the end of every epoch.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn new_for_test<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
         Self::open(
             db_root_path,
-            false, /* readonly */
-            None,  /* pruner */
+            false,   /* readonly */
+            None,    /* pruner */
+            None,    /* archiver */
+            Some(1), /* waypoint_interval */
         )
         .expect("Unable to open LibraDB")
     }
@@ -420,6 +460,88 @@ impl LibraDB {
         Ok(new_root_hash)
     }
 
+    /// Writes the ledger info, along with its waypoint if it ends an epoch that is a multiple of
+    /// the waypoint interval.
+    fn put_ledger_info(
+        &self,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+        cs: &mut ChangeSet,
+    ) -> Result<()> {
+        self.ledger_store
+            .put_ledger_info(ledger_info_with_sigs, cs)?;
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        if let Some(interval) = self.waypoint_interval {
+            if ledger_info.next_epoch_state().is_some() && ledger_info.epoch() % interval == 0 {
+                self.ledger_store.put_waypoint(ledger_info_with_sigs, cs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that no commit is left half done, as its ledger info must be written before any
+    /// later transactions.
+    fn ensure_no_pending_commit(&self) -> Result<()> {
+        if let Some(CommitRecord::Prepared(pending)) = self.ledger_store.get_commit_record()? {
+            bail!(
+                "The commit of version {} is prepared but not completed.",
+                pending.ledger_info().version()
+            );
+        }
+        Ok(())
+    }
+
+    /// Completes the commit left prepared by a crash, if any. Its ledger info is certified and its
+    /// transactions are persisted, so it is safe to roll it forward.
+    fn complete_pending_commit(&self) -> Result<()> {
+        if let Some(CommitRecord::Prepared(pending)) = self.ledger_store.get_commit_record()? {
+            info!(
+                "Completing the commit of version {} left prepared.",
+                pending.ledger_info().version()
+            );
+            self.complete_commit(&pending)?;
+        }
+        Ok(())
+    }
+
+    fn complete_commit(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<()> {
+        let mut cs = ChangeSet::new();
+        self.put_ledger_info(ledger_info_with_sigs, &mut cs)?;
+        self.ledger_store.put_commit_record(
+            &CommitRecord::Completed(ledger_info_with_sigs.ledger_info().version()),
+            &mut cs,
+        )?;
+        let (sealed_cs, _counters) =
+            self.seal_change_set(0 /* first_version */, 0 /* num_txns */, cs)?;
+        self.commit(sealed_cs)?;
+        self.ledger_store
+            .set_latest_ledger_info(ledger_info_with_sigs.clone());
+        Ok(())
+    }
+
+    /// Bumps the metrics and wakes up the pruner and the archiver once transactions are persisted.
+    fn on_transactions_committed(
+        &self,
+        first_version: Version,
+        num_txns: u64,
+        counters: Option<LedgerCounters>,
+    ) {
+        // Only increment counter if there are at least one transaction written to the storage.
+        // That's also when we'd inform the pruner and archiver threads to work.
+        if num_txns > 0 {
+            let last_version = first_version + num_txns - 1;
+            OP_COUNTER.inc_by("committed_txns", num_txns as usize);
+            LIBRA_STORAGE_COMMITTED_TXNS.inc_by(num_txns as i64);
+            OP_COUNTER.set("latest_transaction_version", last_version as usize);
+            LIBRA_STORAGE_LATEST_TXN_VERSION.set(last_version as i64);
+            counters
+                .expect("Counters should be bumped with transactions being saved.")
+                .bump_op_counters();
+
+            self.wake_pruner(last_version);
+            self.wake_archiver(last_version);
+        }
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support. Also committed are the
     /// LedgerCounters.
@@ -444,11 +566,35 @@ impl LibraDB {
         Ok(())
     }
 
+    /// Ensures the state at `version` can be proven by the ledger at `ledger_version`.
+    fn ensure_state_versions(&self, version: Version, ledger_version: Version) -> Result<()> {
+        ensure!(
+            version <= ledger_version,
+            "The queried version {} should be equal to or older than ledger version {}.",
+            version,
+            ledger_version
+        );
+        let latest_version = self.get_latest_version()?;
+        ensure!(
+            ledger_version <= latest_version,
+            "The ledger version {} is greater than the latest version currently in ledger: {}",
+            ledger_version,
+            latest_version
+        );
+        Ok(())
+    }
+
     fn wake_pruner(&self, latest_version: Version) {
         if let Some(pruner) = self.pruner.as_ref() {
             pruner.wake(latest_version)
         }
     }
+
+    fn wake_archiver(&self, latest_version: Version) {
+        if let Some(archiver) = self.archiver.as_ref() {
+            archiver.wake(latest_version)
+        }
+    }
 }
 
 impl DbReader for LibraDB {
@@ -478,6 +624,10 @@ impl DbReader for LibraDB {
         self.ledger_store.get_latest_ledger_info()
     }
 
+    fn get_latest_signed_waypoint(&self) -> Result<Option<SignedWaypoint>> {
+        self.ledger_store.get_latest_signed_waypoint()
+    }
+
     /// Returns a transaction that is the `seq_num`-th one associated with the given account. If
     /// the transaction with given `seq_num` doesn't exist, returns `None`.
     fn get_txn_by_account(
@@ -564,11 +714,48 @@ impl DbReader for LibraDB {
         Ok(events)
     }
 
+    /// Returns up to `limit` events of the stream `event_key` from `start_seq_num`, in `order`,
+    /// each with its proof towards the ledger at `known_version`, or at the latest version if
+    /// `None`. Descending from `u64::max_value()` starts from the latest event. Pages requested
+    /// with the same `known_version` are consistent with each other.
+    fn get_events_with_proof_by_event_key(
+        &self,
+        event_key: &EventKey,
+        start_seq_num: u64,
+        order: Order,
+        limit: u64,
+        known_version: Option<Version>,
+    ) -> Result<Vec<EventWithProof>> {
+        let latest_version = self
+            .ledger_store
+            .get_latest_ledger_info()?
+            .ledger_info()
+            .version();
+        let ledger_version = known_version.unwrap_or(latest_version);
+        ensure!(
+            ledger_version <= latest_version,
+            "Known version {} is newer than the latest version {}.",
+            ledger_version,
+            latest_version,
+        );
+        self.get_events_by_event_key(
+            event_key,
+            start_seq_num,
+            order == Order::Ascending,
+            limit,
+            ledger_version,
+        )
+    }
+
     /// Gets ledger info at specified version and ensures it's an epoch change.
     fn get_epoch_ending_ledger_info(&self, version: u64) -> Result<LedgerInfoWithSignatures> {
         self.ledger_store.get_epoch_ending_ledger_info(version)
     }
 
+    fn subscribe_to_ledger_infos(&self) -> Option<LedgerInfoSubscription> {
+        Some(self.ledger_store.subscribe_to_ledger_infos())
+    }
+
     fn get_state_proof_with_ledger_info(
         &self,
         known_version: u64,
@@ -614,19 +801,7 @@ impl DbReader for LibraDB {
         version: Version,
         ledger_version: Version,
     ) -> Result<AccountStateWithProof> {
-        ensure!(
-            version <= ledger_version,
-            "The queried version {} should be equal to or older than ledger version {}.",
-            version,
-            ledger_version
-        );
-        let latest_version = self.get_latest_version()?;
-        ensure!(
-            ledger_version <= latest_version,
-            "The ledger version {} is greater than the latest version currently in ledger: {}",
-            ledger_version,
-            latest_version
-        );
+        self.ensure_state_versions(version, ledger_version)?;
 
         let txn_info_with_proof = self
             .ledger_store
@@ -654,6 +829,40 @@ impl DbReader for LibraDB {
             .get_account_state_with_proof_by_version(address, version)
     }
 
+    /// Gets the states of several accounts at `version` with a single proof of the transaction
+    /// info at `version`, instead of reading and returning it again for each account.
+    fn get_account_states_with_proof(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+        ledger_version: Version,
+    ) -> Result<AccountStatesWithProof> {
+        self.ensure_state_versions(version, ledger_version)?;
+
+        let txn_info_with_proof = self
+            .ledger_store
+            .get_transaction_info_with_proof(version, ledger_version)?;
+        let (blobs, sparse_merkle_proofs) = self
+            .state_store
+            .get_account_states_with_proof_by_version(addresses, version)?
+            .into_iter()
+            .unzip();
+        Ok(AccountStatesWithProof::new(
+            version,
+            blobs,
+            AccountStatesProof::new(txn_info_with_proof, sparse_merkle_proofs),
+        ))
+    }
+
+    fn get_account_states_with_proof_by_version(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+    ) -> Result<Vec<(Option<AccountStateBlob>, SparseMerkleProof)>> {
+        self.state_store
+            .get_account_states_with_proof_by_version(addresses, version)
+    }
+
     fn get_latest_state_root(&self) -> Result<(Version, HashValue)> {
         let (version, txn_info) = self.ledger_store.get_latest_transaction_info()?;
         Ok((version, txn_info.state_root_hash()))
@@ -703,6 +912,7 @@ impl DbWriter for LibraDB {
             ledger_info_with_sigs.is_some() || num_txns > 0,
             "txns_to_commit is empty while ledger_info_with_sigs is None.",
         );
+        self.ensure_no_pending_commit()?;
 
         if let Some(x) = ledger_info_with_sigs {
             let claimed_last_version = x.ledger_info().version();
@@ -730,7 +940,7 @@ impl DbWriter for LibraDB {
                 expected_root_hash,
             );
 
-            self.ledger_store.put_ledger_info(x, &mut cs)?;
+            self.put_ledger_info(x, &mut cs)?;
         }
 
         // Persist.
@@ -740,24 +950,81 @@ impl DbWriter for LibraDB {
         if let Some(x) = ledger_info_with_sigs {
             self.ledger_store.set_latest_ledger_info(x.clone());
         }
+        self.on_transactions_committed(first_version, num_txns, counters);
 
-        // Only increment counter if commit succeeds and there are at least one transaction written
-        // to the storage. That's also when we'd inform the pruner thread to work.
-        if num_txns > 0 {
-            let last_version = first_version + num_txns - 1;
-            OP_COUNTER.inc_by("committed_txns", num_txns as usize);
-            LIBRA_STORAGE_COMMITTED_TXNS.inc_by(num_txns as i64);
-            OP_COUNTER.set("latest_transaction_version", last_version as usize);
-            LIBRA_STORAGE_LATEST_TXN_VERSION.set(last_version as i64);
-            counters
-                .expect("Counters should be bumped with transactions being saved.")
-                .bump_op_counters();
+        Ok(())
+    }
 
-            self.wake_pruner(last_version);
+    /// Prepares a two-phase commit: persists the transactions and the state they produce along
+    /// with a record of the commit, but not the ledger info, so readers keep seeing the previous
+    /// ledger info until [`commit_ledger_info`](#method.commit_ledger_info) completes it.
+    /// Preparing a commit already prepared with the same ledger info is a no-op.
+    fn pre_commit_transactions(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+    ) -> Result<()> {
+        if let Some(CommitRecord::Prepared(pending)) = self.ledger_store.get_commit_record()? {
+            ensure!(
+                &pending == ledger_info_with_sigs,
+                "The commit of version {} is prepared but not completed.",
+                pending.ledger_info().version()
+            );
+            return Ok(());
         }
 
+        let num_txns = txns_to_commit.len() as u64;
+        let claimed_last_version = ledger_info_with_sigs.ledger_info().version();
+        ensure!(
+            claimed_last_version + 1 == first_version + num_txns,
+            "Transaction batch not applicable: first_version {}, num_txns {}, last_version {}",
+            first_version,
+            num_txns,
+            claimed_last_version,
+        );
+
+        let mut cs = ChangeSet::new();
+        let new_root_hash = self.save_transactions_impl(txns_to_commit, first_version, &mut cs)?;
+        let expected_root_hash = ledger_info_with_sigs
+            .ledger_info()
+            .transaction_accumulator_hash();
+        ensure!(
+            new_root_hash == expected_root_hash,
+            "Root hash calculated doesn't match expected. {:?} vs {:?}",
+            new_root_hash,
+            expected_root_hash,
+        );
+        self.ledger_store.put_commit_record(
+            &CommitRecord::Prepared(ledger_info_with_sigs.clone()),
+            &mut cs,
+        )?;
+
+        let (sealed_cs, counters) = self.seal_change_set(first_version, num_txns, cs)?;
+        self.commit(sealed_cs)?;
+        self.on_transactions_committed(first_version, num_txns, counters);
+
         Ok(())
     }
+
+    /// Completes the prepared commit by persisting its ledger info. Completing a commit already
+    /// completed is a no-op, so that the caller can retry.
+    fn commit_ledger_info(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<()> {
+        let version = ledger_info_with_sigs.ledger_info().version();
+        match self.ledger_store.get_commit_record()? {
+            Some(CommitRecord::Prepared(pending)) => {
+                ensure!(
+                    &pending == ledger_info_with_sigs,
+                    "The commit prepared is of version {}, not {}.",
+                    pending.ledger_info().version(),
+                    version,
+                );
+                self.complete_commit(ledger_info_with_sigs)
+            }
+            Some(CommitRecord::Completed(completed)) if completed == version => Ok(()),
+            _ => bail!("No commit of version {} is prepared.", version),
+        }
+    }
 }
 
 // Convert requested range and order to a range in ascending order.
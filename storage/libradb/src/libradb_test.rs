@@ -36,6 +36,16 @@ fn verify_epochs(db: &LibraDB, ledger_infos_with_sigs: &[LedgerInfoWithSignature
         .cloned()
         .collect();
     assert_eq!(actual_epoch_change_lis, expected_epoch_change_lis,);
+
+    // The test DB emits a waypoint at the end of every epoch.
+    let expected_signed_waypoint = expected_epoch_change_lis
+        .last()
+        .cloned()
+        .map(|li| SignedWaypoint::new(li).unwrap());
+    assert_eq!(
+        db.get_latest_signed_waypoint().unwrap(),
+        expected_signed_waypoint
+    );
 }
 
 pub fn test_save_blocks_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
@@ -147,8 +157,18 @@ fn get_events_by_event_key(
 
     let mut ret = Vec::new();
     loop {
-        let events_with_proof =
-            db.get_events_by_event_key(event_key, cursor, ascending, LIMIT, ledger_info.version())?;
+        let order = if ascending {
+            Order::Ascending
+        } else {
+            Order::Descending
+        };
+        let events_with_proof = db.get_events_with_proof_by_event_key(
+            event_key,
+            cursor,
+            order,
+            LIMIT,
+            Some(ledger_info.version()),
+        )?;
 
         let num_events = events_with_proof.len() as u64;
         if cursor == u64::max_value() {
@@ -329,6 +349,20 @@ fn verify_committed_transactions(
                 .unwrap();
         }
 
+        // Fetch and verify the same account states in one batch.
+        let (addresses, expected_blobs): (Vec<_>, Vec<_>) = txn_to_commit
+            .account_states()
+            .iter()
+            .map(|(addr, blob)| (*addr, Some(blob.clone())))
+            .unzip();
+        let account_states_with_proof = db
+            .get_account_states_with_proof(&addresses, cur_ver, ledger_version)
+            .unwrap();
+        assert_eq!(account_states_with_proof.blobs, expected_blobs);
+        account_states_with_proof
+            .verify(ledger_info, cur_ver, &addresses)
+            .unwrap();
+
         cur_ver += 1;
     }
 
@@ -354,6 +388,76 @@ proptest! {
     fn test_sync_transactions(input in arb_blocks_to_commit()) {
         test_sync_transactions_impl(input);
     }
+
+    #[test]
+    fn test_two_phase_commit(input in arb_blocks_to_commit()) {
+        test_two_phase_commit_impl(input);
+    }
+
+    #[test]
+    fn test_ledger_info_subscription(input in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = LibraDB::new_for_test(&tmp_dir);
+        let ledger_infos = db.subscribe_to_ledger_infos().unwrap();
+        prop_assert!(ledger_infos.borrow().is_none());
+
+        let mut cur_ver = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &input {
+            db.save_transactions(txns_to_commit, cur_ver, Some(ledger_info_with_sigs))
+                .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+            prop_assert_eq!(ledger_infos.borrow().as_ref(), Some(ledger_info_with_sigs));
+        }
+
+        // A subscriber starts with the latest ledger info.
+        let ledger_infos = db.subscribe_to_ledger_infos().unwrap();
+        prop_assert_eq!(ledger_infos.borrow().as_ref(), Some(&input.last().unwrap().1));
+    }
+}
+
+fn test_two_phase_commit_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
+    let tmp_dir = TempPath::new();
+    let db = LibraDB::new_for_test(&tmp_dir);
+
+    let (last_txns, last_ledger_info_with_sigs) = input.last().unwrap();
+    let mut cur_ver = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in &input[..input.len() - 1] {
+        db.pre_commit_transactions(txns_to_commit, cur_ver, ledger_info_with_sigs)
+            .unwrap();
+        db.commit_ledger_info(ledger_info_with_sigs).unwrap();
+        // Completing a commit again is a no-op.
+        db.commit_ledger_info(ledger_info_with_sigs).unwrap();
+        assert_eq!(&db.get_latest_ledger_info().unwrap(), ledger_info_with_sigs);
+        cur_ver += txns_to_commit.len() as u64;
+    }
+
+    // Crash after the first phase of the last commit.
+    db.pre_commit_transactions(last_txns, cur_ver, last_ledger_info_with_sigs)
+        .unwrap();
+    assert_eq!(
+        db.get_latest_ledger_info().ok().as_ref(),
+        input.len().checked_sub(2).map(|i| &input[i].1)
+    );
+    // No other transactions can be saved until the commit is completed.
+    assert!(db
+        .save_transactions(last_txns, cur_ver, Some(last_ledger_info_with_sigs))
+        .is_err());
+    drop(db);
+
+    // The commit is completed when the DB is opened again.
+    let db = LibraDB::new_for_test(&tmp_dir);
+    assert_eq!(
+        &db.get_latest_ledger_info().unwrap(),
+        last_ledger_info_with_sigs
+    );
+    db.commit_ledger_info(last_ledger_info_with_sigs).unwrap();
+    verify_committed_transactions(
+        &db,
+        last_txns,
+        cur_ver,
+        last_ledger_info_with_sigs,
+        true, /* is_latest */
+    );
 }
 
 #[test]
@@ -0,0 +1,59 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the record of the last two-phase commit.
+//!
+//! There is a single record, under an empty key.
+//! ```text
+//! |<-key->|<----value---->|
+//! |       | commit record |
+//! ```
+//!
+//! A commit is first prepared: the transactions and the state they produce are written along
+//! with a `Prepared` record carrying the ledger info that certifies them. It is then completed:
+//! the ledger info is written along with a `Completed` record. Both phases are atomic, so after a
+//! crash in between the record tells exactly which ledger info is left to write.
+
+use super::COMMIT_RECORD_CF_NAME;
+use crate::schema::ensure_slice_len_eq;
+use anyhow::Result;
+use libra_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) enum CommitRecord {
+    /// The transactions up to the version of the ledger info are persisted, but not the ledger
+    /// info.
+    Prepared(LedgerInfoWithSignatures),
+    /// The ledger info at this version is persisted.
+    Completed(Version),
+}
+
+define_schema!(CommitRecordSchema, (), CommitRecord, COMMIT_RECORD_CF_NAME);
+
+impl KeyCodec<CommitRecordSchema> for () {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(vec![])
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)
+    }
+}
+
+impl ValueCodec<CommitRecordSchema> for CommitRecord {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        lcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        lcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
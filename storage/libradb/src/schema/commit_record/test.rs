@@ -0,0 +1,20 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::schema::assert_encode_decode;
+
+fn arb_commit_record() -> impl Strategy<Value = CommitRecord> {
+    prop_oneof![
+        any_with::<LedgerInfoWithSignatures>((1..10).into()).prop_map(CommitRecord::Prepared),
+        any::<Version>().prop_map(CommitRecord::Completed),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn test_encode_decode(commit_record in arb_commit_record()) {
+        assert_encode_decode::<CommitRecordSchema>(&(), &commit_record);
+    }
+}
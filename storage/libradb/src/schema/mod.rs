@@ -6,6 +6,7 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod commit_record;
 pub(crate) mod epoch_by_version;
 pub(crate) mod event;
 pub(crate) mod event_accumulator;
@@ -18,10 +19,12 @@ pub(crate) mod transaction;
 pub(crate) mod transaction_accumulator;
 pub(crate) mod transaction_by_account;
 pub(crate) mod transaction_info;
+pub(crate) mod waypoint;
 
 use anyhow::{ensure, Result};
 use schemadb::ColumnFamilyName;
 
+pub(super) const COMMIT_RECORD_CF_NAME: ColumnFamilyName = "commit_record";
 pub(super) const EPOCH_BY_VERSION_CF_NAME: ColumnFamilyName = "epoch_by_version";
 pub(super) const EVENT_ACCUMULATOR_CF_NAME: ColumnFamilyName = "event_accumulator";
 pub(super) const EVENT_BY_KEY_CF_NAME: ColumnFamilyName = "event_by_key";
@@ -33,6 +36,7 @@ pub(super) const TRANSACTION_CF_NAME: ColumnFamilyName = "transaction";
 pub(super) const TRANSACTION_ACCUMULATOR_CF_NAME: ColumnFamilyName = "transaction_accumulator";
 pub(super) const TRANSACTION_BY_ACCOUNT_CF_NAME: ColumnFamilyName = "transaction_by_account";
 pub(super) const TRANSACTION_INFO_CF_NAME: ColumnFamilyName = "transaction_info";
+pub(super) const WAYPOINT_CF_NAME: ColumnFamilyName = "waypoint";
 
 fn ensure_slice_len_eq(data: &[u8], len: usize) -> Result<()> {
     ensure!(
@@ -68,6 +72,7 @@ pub mod fuzzing {
     pub fn fuzz_decode(data: &[u8]) {
         #[allow(unused_must_use)]
         {
+            decode_key_value!(super::commit_record::CommitRecordSchema, data);
             decode_key_value!(super::epoch_by_version::EpochByVersionSchema, data);
             decode_key_value!(super::event::EventSchema, data);
             decode_key_value!(super::event_accumulator::EventAccumulatorSchema, data);
@@ -89,6 +94,7 @@ pub mod fuzzing {
                 data
             );
             decode_key_value!(super::transaction_info::TransactionInfoSchema, data);
+            decode_key_value!(super::waypoint::WaypointSchema, data);
         }
     }
 }
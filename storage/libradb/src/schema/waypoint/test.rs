@@ -0,0 +1,17 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use libra_types::ledger_info::LedgerInfo;
+use proptest::prelude::*;
+use schemadb::schema::assert_encode_decode;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        epoch in any::<u64>(),
+        ledger_info in any::<LedgerInfo>(),
+    ) {
+        assert_encode_decode::<WaypointSchema>(&epoch, &Waypoint::new_any(&ledger_info));
+    }
+}
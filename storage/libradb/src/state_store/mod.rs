@@ -46,6 +46,20 @@ impl StateStore {
         JellyfishMerkleTree::new(self).get_with_proof(address.hash(), version)
     }
 
+    /// Get the account state blobs of several accounts, in the order of `addresses`, out of the
+    /// state Merkle tree at `version`.
+    pub fn get_account_states_with_proof_by_version(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+    ) -> Result<Vec<(Option<AccountStateBlob>, SparseMerkleProof)>> {
+        let tree = JellyfishMerkleTree::new(self);
+        addresses
+            .iter()
+            .map(|address| tree.get_with_proof(address.hash(), version))
+            .collect()
+    }
+
     /// Gets the proof that proves a range of accounts.
     pub fn get_account_state_range_proof(
         &self,
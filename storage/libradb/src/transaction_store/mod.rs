@@ -4,6 +4,7 @@
 //! This file defines transaction store APIs that are related to committed signed transactions.
 
 use crate::{
+    archive::Archive,
     change_set::ChangeSet,
     errors::LibraDbError,
     schema::{transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema},
@@ -19,11 +20,19 @@ use std::sync::Arc;
 
 pub(crate) struct TransactionStore {
     db: Arc<DB>,
+    /// Where the transactions before `Archive::archived_up_to()` are, if archiving is enabled.
+    archive: Option<Arc<Archive>>,
 }
 
 impl TransactionStore {
-    pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+    pub fn new(db: Arc<DB>, archive: Option<Arc<Archive>>) -> Self {
+        Self { db, archive }
+    }
+
+    fn archived_up_to(&self) -> Version {
+        self.archive
+            .as_ref()
+            .map_or(0, |archive| archive.archived_up_to())
     }
 
     /// Gets the version of a transaction by the sender `address` and `sequence_number`.
@@ -47,9 +56,21 @@ impl TransactionStore {
 
     /// Get signed transaction given `version`
     pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
-        self.db
-            .get::<TransactionSchema>(&version)?
-            .ok_or_else(|| LibraDbError::NotFound(format!("Txn {}", version)).into())
+        if version < self.archived_up_to() {
+            if let Some(archive) = &self.archive {
+                return archive.get_transaction(version);
+            }
+        }
+        match self.db.get::<TransactionSchema>(&version)? {
+            Some(transaction) => Ok(transaction),
+            // It might have been archived in the meantime.
+            None if version < self.archived_up_to() => self
+                .archive
+                .as_ref()
+                .expect("Versions are only archived with an archive.")
+                .get_transaction(version),
+            None => Err(LibraDbError::NotFound(format!("Txn {}", version)).into()),
+        }
     }
 
     /// Gets an iterator that yields `num_transactions` transactions starting from `start_version`.
@@ -58,9 +79,14 @@ impl TransactionStore {
         start_version: Version,
         num_transactions: u64,
     ) -> Result<TransactionIter> {
+        // The archiver deletes versions from the DB after archiving them, so the iterator taken
+        // before reading the archive progress has all the versions the archive does not.
         let mut iter = self.db.iter::<TransactionSchema>(Default::default())?;
-        iter.seek(&start_version)?;
+        let archived_up_to = self.archived_up_to();
+        iter.seek(&start_version.max(archived_up_to))?;
         Ok(TransactionIter {
+            archive: self.archive.as_deref(),
+            archived_up_to,
             inner: iter,
             expected_next_version: start_version,
             end_version: start_version
@@ -80,14 +106,30 @@ impl TransactionStore {
         // Linear search via `DB::rev_iter()` here, NOT expecting performance hit, due to the fact
         // that the iterator caches data block and that there are limited number of transactions in
         // each block.
-        let mut iter = self.db.rev_iter::<TransactionSchema>(Default::default())?;
-        iter.seek(&version)?;
-        for res in iter.take(MAX_VERSIONS_TO_SEARCH) {
-            let (v, txn) = res?;
-            if let Transaction::BlockMetadata(block_meta) = txn {
-                return Ok(Some((v, block_meta)));
-            } else if v == 0 {
-                return Ok(None);
+        let archived_up_to = self.archived_up_to();
+        let mut num_searched = 0;
+        if version >= archived_up_to {
+            let mut iter = self.db.rev_iter::<TransactionSchema>(Default::default())?;
+            iter.seek(&version)?;
+            for res in iter.take(MAX_VERSIONS_TO_SEARCH) {
+                let (v, txn) = res?;
+                if let Transaction::BlockMetadata(block_meta) = txn {
+                    return Ok(Some((v, block_meta)));
+                } else if v == 0 {
+                    return Ok(None);
+                }
+                num_searched += 1;
+            }
+        }
+
+        // Continue the search in the archive, where the versions preceding the ones in the DB
+        // are.
+        if let Some(archive) = &self.archive {
+            if archived_up_to > 0 && num_searched < MAX_VERSIONS_TO_SEARCH {
+                return archive.get_block_metadata(
+                    version.min(archived_up_to - 1),
+                    MAX_VERSIONS_TO_SEARCH - num_searched,
+                );
             }
         }
 
@@ -114,6 +156,9 @@ impl TransactionStore {
 }
 
 pub struct TransactionIter<'a> {
+    archive: Option<&'a Archive>,
+    /// The transactions before this version are read from the archive, the others from the DB.
+    archived_up_to: Version,
     inner: SchemaIterator<'a, TransactionSchema>,
     expected_next_version: Version,
     end_version: Version,
@@ -125,6 +170,14 @@ impl<'a> TransactionIter<'a> {
             return Ok(None);
         }
 
+        if let Some(archive) = self.archive {
+            if self.expected_next_version < self.archived_up_to {
+                let transaction = archive.get_transaction(self.expected_next_version)?;
+                self.expected_next_version += 1;
+                return Ok(Some(transaction));
+            }
+        }
+
         let ret = match self.inner.next().transpose()? {
             Some((version, transaction)) => {
                 ensure!(
@@ -73,7 +73,7 @@ use libra_crypto::{
     HashValue,
 };
 use libra_types::{account_state_blob::AccountStateBlob, proof::SparseMerkleProof};
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 /// `AccountStatus` describes the result of querying an account from this SparseMerkleTree.
 #[derive(Debug, Eq, PartialEq)]
@@ -132,6 +132,142 @@ impl SparseMerkleTree {
         Ok(SparseMerkleTree { root })
     }
 
+    /// Constructs a new Sparse Merkle Tree like `update`, applying all the updates in a single
+    /// traversal of the tree. Keys sharing a prefix share the nodes on the path to it, so each
+    /// part of the tree is rebuilt once and each subtree node is expanded from a proof once,
+    /// however many keys of the batch are below it. When a key appears several times in
+    /// `updates`, the last value wins.
+    pub fn batch_update(
+        &self,
+        updates: &[(HashValue, AccountStateBlob)],
+        proof_reader: &impl ProofRead,
+    ) -> Result<Self, UpdateError> {
+        // Sorted by key, the updates below a node are contiguous and split in two at each level.
+        let updates: Vec<_> = updates
+            .iter()
+            .map(|(key, blob)| (*key, blob))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect();
+        let root = Self::batch_update_subtree(Arc::clone(&self.root), 0, &updates, proof_reader)?;
+        Ok(SparseMerkleTree { root })
+    }
+
+    /// Applies `updates`, sorted by key, to the subtree rooted at `node`, at `depth` in the tree.
+    fn batch_update_subtree(
+        node: Arc<SparseMerkleNode>,
+        depth: usize,
+        updates: &[(HashValue, &AccountStateBlob)],
+        proof_reader: &impl ProofRead,
+    ) -> Result<Arc<SparseMerkleNode>, UpdateError> {
+        if updates.is_empty() {
+            return Ok(node);
+        }
+
+        let children = match &*node.read_lock() {
+            Node::Internal(node) => Some((node.clone_left_child(), node.clone_right_child())),
+            Node::Leaf(leaf) => {
+                if updates.len() == 1 && updates[0].0 == leaf.key() {
+                    return Ok(Self::new_blob_leaf(updates[0].0, updates[0].1));
+                }
+                // The existing leaf goes down with the new keys, until they are apart.
+                let empty = Arc::new(SparseMerkleNode::new_empty());
+                if bit_at(leaf.key(), depth) {
+                    Some((empty, Arc::clone(&node)))
+                } else {
+                    Some((Arc::clone(&node), empty))
+                }
+            }
+            Node::Subtree(_) => None,
+            Node::Empty => {
+                if updates.len() == 1 {
+                    return Ok(Self::new_blob_leaf(updates[0].0, updates[0].1));
+                }
+                Some((
+                    Arc::new(SparseMerkleNode::new_empty()),
+                    Arc::new(SparseMerkleNode::new_empty()),
+                ))
+            }
+        };
+
+        match children {
+            Some((left_child, right_child)) => {
+                let num_left = updates
+                    .iter()
+                    .position(|(key, _)| bit_at(*key, depth))
+                    .unwrap_or_else(|| updates.len());
+                let (left_updates, right_updates) = updates.split_at(num_left);
+                Ok(Arc::new(SparseMerkleNode::new_internal(
+                    Self::batch_update_subtree(left_child, depth + 1, left_updates, proof_reader)?,
+                    Self::batch_update_subtree(
+                        right_child,
+                        depth + 1,
+                        right_updates,
+                        proof_reader,
+                    )?,
+                )))
+            }
+            None => {
+                // Replace the subtree node by the nodes on the path to the first key, as given by
+                // its proof. The other keys reuse them, and only need a proof of their own when
+                // they reach one of the siblings.
+                let expanded = Self::expand_subtree(updates[0].0, depth, proof_reader)?;
+                Self::batch_update_subtree(expanded, depth, updates, proof_reader)
+            }
+        }
+    }
+
+    /// Constructs, from the proof of `key`, the nodes of the subtree at `depth` on the path to
+    /// `key`. The siblings on the path are subtree or empty nodes.
+    fn expand_subtree(
+        key: HashValue,
+        depth: usize,
+        proof_reader: &impl ProofRead,
+    ) -> Result<Arc<SparseMerkleNode>, UpdateError> {
+        let proof = proof_reader
+            .get_proof(key)
+            .ok_or(UpdateError::MissingProof)?;
+        let proof_length = proof.siblings().len();
+        assert!(
+            proof_length >= depth,
+            "Proof of length {} for a subtree at depth {}.",
+            proof_length,
+            depth,
+        );
+
+        let bottom = Arc::new(match proof.leaf() {
+            Some(leaf) => {
+                SparseMerkleNode::new_leaf(leaf.key(), LeafValue::BlobHash(leaf.value_hash()))
+            }
+            None => SparseMerkleNode::new_empty(),
+        });
+        Ok(Self::construct_subtree(
+            key.iter_bits()
+                .rev()
+                .skip(HashValue::LENGTH_IN_BITS - proof_length)
+                .take(proof_length - depth),
+            proof
+                .siblings()
+                .iter()
+                .take(proof_length - depth)
+                .map(|sibling_hash| {
+                    Arc::new(if *sibling_hash != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+                        SparseMerkleNode::new_subtree(*sibling_hash)
+                    } else {
+                        SparseMerkleNode::new_empty()
+                    })
+                }),
+            bottom,
+        ))
+    }
+
+    fn new_blob_leaf(key: HashValue, blob: &AccountStateBlob) -> Arc<SparseMerkleNode> {
+        Arc::new(SparseMerkleNode::new_leaf(
+            key,
+            LeafValue::Blob(blob.clone()),
+        ))
+    }
+
     fn update_one(
         root: Arc<SparseMerkleNode>,
         key: HashValue,
@@ -441,6 +577,12 @@ impl Default for SparseMerkleTree {
     }
 }
 
+/// Returns the bit of `key` at `depth` in the tree, which says whether `key` is in the right
+/// subtree at that depth.
+fn bit_at(key: HashValue, depth: usize) -> bool {
+    (key[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
 /// A type that implements `ProofRead` can provide proof for keys in persistent storage.
 pub trait ProofRead {
     /// Gets verified proof for this key in persistent storage.
@@ -3,7 +3,7 @@
 
 use super::{
     node::{LeafNode, LeafValue, SparseMerkleNode},
-    AccountStatus, ProofRead, SparseMerkleTree,
+    AccountStatus, ProofRead, SparseMerkleTree, UpdateError,
 };
 use libra_crypto::{
     hash::{CryptoHash, TestOnlyHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
@@ -536,3 +536,126 @@ fn test_update() {
     assert_eq!(smt22.get(key3), AccountStatus::Unknown);
     assert_eq!(smt22.get(key4), AccountStatus::ExistsInScratchPad(value4));
 }
+
+#[test]
+fn test_batch_update() {
+    // Same tree as in `test_update`:
+    //             root
+    //            /    \
+    //           y      key3
+    //          / \
+    //         x   placeholder
+    //        / \
+    //    key1   key2
+    let key1 = b"aaaaa".test_only_hash();
+    let key2 = b"bb".test_only_hash();
+    let key3 = b"cccc".test_only_hash();
+    let key4 = b"d".test_only_hash();
+    let value1_hash = AccountStateBlob::from(b"value1".to_vec()).hash();
+    let value2_hash = AccountStateBlob::from(b"value2".to_vec()).hash();
+    let value3_hash = AccountStateBlob::from(b"value3".to_vec()).hash();
+    let leaf1 = SparseMerkleLeafNode::new(key1, value1_hash);
+    let leaf2_hash = hash_leaf(key2, value2_hash);
+    let leaf3 = SparseMerkleLeafNode::new(key3, value3_hash);
+    let x_hash = hash_internal(leaf1.hash(), leaf2_hash);
+    let y_hash = hash_internal(x_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH);
+    let old_root_hash = hash_internal(y_hash, leaf3.hash());
+
+    // Key4 sits at the placeholder, on the path of key1: the proof of key1 is enough for both.
+    let proof1 = SparseMerkleProof::new(
+        Some(leaf1),
+        vec![leaf2_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH, leaf3.hash()],
+    );
+    let proof3 = SparseMerkleProof::new(Some(leaf3), vec![y_hash]);
+    let proof_reader = ProofReader::new(vec![(key1, proof1), (key3, proof3)]);
+
+    let value1 = AccountStateBlob::from(b"value11111".to_vec());
+    let value3 = AccountStateBlob::from(b"value33333".to_vec());
+    let value4 = AccountStateBlob::from(b"value4".to_vec());
+    let old_smt = SparseMerkleTree::new(old_root_hash);
+    let smt = old_smt
+        .batch_update(
+            &[
+                (key4, AccountStateBlob::from(b"overwritten".to_vec())),
+                (key3, value3.clone()),
+                (key1, value1.clone()),
+                (key4, value4.clone()),
+            ],
+            &proof_reader,
+        )
+        .unwrap();
+
+    // Now the tree looks like:
+    //              root
+    //             /    \
+    //            y      key3
+    //           / \
+    //          x   key4
+    //         / \
+    //     key1   key2 (subtree)
+    assert_eq!(
+        smt.get(key1),
+        AccountStatus::ExistsInScratchPad(value1.clone())
+    );
+    assert_eq!(smt.get(key2), AccountStatus::Unknown);
+    assert_eq!(
+        smt.get(key3),
+        AccountStatus::ExistsInScratchPad(value3.clone())
+    );
+    assert_eq!(
+        smt.get(key4),
+        AccountStatus::ExistsInScratchPad(value4.clone())
+    );
+
+    let x_hash = hash_internal(hash_leaf(key1, value1.hash()), leaf2_hash);
+    let y_hash = hash_internal(x_hash, hash_leaf(key4, value4.hash()));
+    let root_hash = hash_internal(y_hash, hash_leaf(key3, value3.hash()));
+    assert_eq!(smt.root_hash(), root_hash);
+
+    // Updating the keys one by one gives the same tree.
+    let expected = old_smt
+        .update(
+            vec![(key1, value1), (key3, value3), (key4, value4)],
+            &proof_reader,
+        )
+        .unwrap();
+    assert_eq!(smt.root_hash(), expected.root_hash());
+
+    // Without the proof of key1, the subtree at the root cannot be expanded.
+    let proof_reader = ProofReader::default();
+    assert_eq!(
+        old_smt
+            .batch_update(&[(key4, AccountStateBlob::from(vec![]))], &proof_reader)
+            .unwrap_err(),
+        UpdateError::MissingProof
+    );
+}
+
+#[test]
+fn test_batch_update_same_as_update() {
+    let proof_reader = ProofReader::default();
+    let mut smt = SparseMerkleTree::default();
+    let mut keys = vec![];
+
+    for _ in 0..10 {
+        // Overwrite some existing keys and insert new ones.
+        let updates: Vec<_> = keys
+            .iter()
+            .step_by(3)
+            .cloned()
+            .chain((0..20).map(|_| HashValue::random()))
+            .map(|key| (key, AccountStateBlob::from(HashValue::random().to_vec())))
+            .collect();
+        keys.extend(updates.iter().map(|(key, _)| *key));
+
+        let expected = smt.update(updates.clone(), &proof_reader).unwrap();
+        smt = smt.batch_update(&updates, &proof_reader).unwrap();
+        assert_eq!(smt.root_hash(), expected.root_hash());
+        for (key, blob) in &updates {
+            assert_eq!(
+                smt.get(*key),
+                AccountStatus::ExistsInScratchPad(blob.clone())
+            );
+        }
+    }
+}
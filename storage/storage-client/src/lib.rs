@@ -3,43 +3,141 @@
 
 #![forbid(unsafe_code)]
 
+//! A client of the storage service, which can run on another host. Connections are pooled and
+//! reads are retried, while the latest version the service reported committed is sent along with
+//! every request, so that a client never reads a state older than one it has already seen.
+
 use anyhow::Result;
-use libra_crypto::HashValue;
+use libra_crypto::{noise::NoiseConfig, x25519, HashValue};
 use libra_secure_net::NetworkClient;
 use libra_types::{
     account_address::AccountAddress,
-    account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    account_state_blob::{AccountStateBlob, AccountStateWithProof, AccountStatesWithProof},
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{AccumulatorConsistencyProof, SparseMerkleProof},
     transaction::{TransactionListWithProof, TransactionToCommit, TransactionWithProof, Version},
+    waypoint::SignedWaypoint,
 };
 use serde::de::DeserializeOwned;
-use std::{net::SocketAddr, sync::Mutex};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
 use storage_interface::{
-    DbReader, DbWriter, Error, GetAccountStateWithProofByVersionRequest, SaveTransactionsRequest,
-    StartupInfo, StorageRequest, TreeState,
+    DbReader, DbWriter, Error, GetAccountStateWithProofByVersionRequest,
+    GetAccountStateWithProofRequest, GetAccountStatesWithProofByVersionRequest,
+    GetAccountStatesWithProofRequest, GetEpochEndingLedgerInfosRequest, GetEventsRequest,
+    GetEventsWithProofByEventKeyRequest, GetStateProofWithLedgerInfoRequest,
+    GetTransactionsRequest, GetTxnByAccountRequest, Order, PreCommitTransactionsRequest,
+    SaveTransactionsRequest, StartupInfo, StorageRequest, StorageRequestEnvelope, StorageResponse,
+    TreeState,
 };
 
+/// The number of idle connections kept open for the next requests.
+const MAX_IDLE_CONNECTIONS: usize = 8;
+/// How many times a read is retried after a network error or a stale read.
+const MAX_RETRIES: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
 pub struct StorageClient {
-    network_client: Mutex<NetworkClient>,
+    server_address: SocketAddr,
+    /// The Noise configuration of the client and the public key of the service, for a secure
+    /// channel.
+    noise: Option<(Arc<NoiseConfig>, x25519::PublicKey)>,
+    idle_connections: Mutex<Vec<NetworkClient>>,
+    /// The consistency token: the latest version the service reported committed.
+    latest_version: Mutex<Option<Version>>,
 }
 
 impl StorageClient {
     pub fn new(server_address: &SocketAddr) -> Self {
+        Self::new_with_noise(server_address, None)
+    }
+
+    pub fn new_secure(
+        server_address: &SocketAddr,
+        identity_key: x25519::PrivateKey,
+        server_public_key: x25519::PublicKey,
+    ) -> Self {
+        let noise_config = Arc::new(NoiseConfig::new(identity_key));
+        Self::new_with_noise(server_address, Some((noise_config, server_public_key)))
+    }
+
+    fn new_with_noise(
+        server_address: &SocketAddr,
+        noise: Option<(Arc<NoiseConfig>, x25519::PublicKey)>,
+    ) -> Self {
         Self {
-            network_client: Mutex::new(NetworkClient::new(*server_address)),
+            server_address: *server_address,
+            noise,
+            idle_connections: Mutex::new(vec![]),
+            latest_version: Mutex::new(None),
+        }
+    }
+
+    fn connection(&self) -> NetworkClient {
+        if let Some(connection) = self.idle_connections.lock().unwrap().pop() {
+            return connection;
+        }
+        match &self.noise {
+            Some((noise_config, server_public_key)) => NetworkClient::new_secure(
+                self.server_address,
+                noise_config.clone(),
+                *server_public_key,
+            ),
+            None => NetworkClient::new(self.server_address),
+        }
+    }
+
+    fn release(&self, connection: NetworkClient) {
+        let mut idle_connections = self.idle_connections.lock().unwrap();
+        if idle_connections.len() < MAX_IDLE_CONNECTIONS {
+            idle_connections.push(connection);
         }
     }
 
     fn request<T: DeserializeOwned>(&self, input: StorageRequest) -> std::result::Result<T, Error> {
-        let input_message = lcs::to_bytes(&input)?;
-        let mut client = self.network_client.lock().unwrap();
-        client.write(&input_message)?;
-        let result = client.read()?;
-        lcs::from_bytes(&result)?
+        let mut retries = 0;
+        loop {
+            let result = self.try_request(&input);
+            let retriable = matches!(
+                result,
+                Err(Error::NetworkError(_)) | Err(Error::StaleRead { .. })
+            );
+            // A write that failed on the network may still have been applied, so it is never
+            // retried
+            if !retriable || !input.is_read() || retries == MAX_RETRIES {
+                return result;
+            }
+            retries += 1;
+            thread::sleep(RETRY_DELAY * retries);
+        }
+    }
+
+    fn try_request<T: DeserializeOwned>(
+        &self,
+        input: &StorageRequest,
+    ) -> std::result::Result<T, Error> {
+        let min_version = *self.latest_version.lock().unwrap();
+        let input_message =
+            lcs::to_bytes(&StorageRequestEnvelope::new(min_version, input.clone()))?;
+        // A connection that failed is dropped rather than reused, as a response may still be
+        // pending on it
+        let mut connection = self.connection();
+        connection.write(&input_message)?;
+        let response: StorageResponse = lcs::from_bytes(&connection.read()?)?;
+        self.release(connection);
+
+        let mut latest_version = self.latest_version.lock().unwrap();
+        if response.latest_version > *latest_version {
+            *latest_version = response.latest_version;
+        }
+        lcs::from_bytes(&response.result)?
     }
 
     pub fn get_account_state_with_proof_by_version(
@@ -87,95 +185,177 @@ impl DbReader for StorageClient {
 
     fn get_latest_account_state(
         &self,
-        _address: AccountAddress,
+        address: AccountAddress,
     ) -> Result<Option<AccountStateBlob>> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetLatestAccountStateRequest(address))?)
     }
 
     fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetLatestLedgerInfoRequest)?)
     }
 
     fn get_txn_by_account(
         &self,
-        _address: AccountAddress,
-        _seq_num: u64,
-        _ledger_version: u64,
-        _fetch_events: bool,
+        address: AccountAddress,
+        seq_num: u64,
+        ledger_version: u64,
+        fetch_events: bool,
     ) -> Result<Option<TransactionWithProof>> {
-        unimplemented!()
+        Ok(
+            self.request(StorageRequest::GetTxnByAccountRequest(Box::new(
+                GetTxnByAccountRequest::new(address, seq_num, ledger_version, fetch_events),
+            )))?,
+        )
     }
 
     fn get_transactions(
         &self,
-        _start_version: u64,
-        _limit: u64,
-        _ledger_version: u64,
-        _fetch_events: bool,
+        start_version: u64,
+        limit: u64,
+        ledger_version: u64,
+        fetch_events: bool,
     ) -> Result<TransactionListWithProof> {
-        unimplemented!()
+        Ok(
+            self.request(StorageRequest::GetTransactionsRequest(Box::new(
+                GetTransactionsRequest::new(start_version, limit, ledger_version, fetch_events),
+            )))?,
+        )
     }
 
     fn get_events(
         &self,
-        _key: &EventKey,
-        _start: u64,
-        _ascending: bool,
-        _limit: u64,
+        key: &EventKey,
+        start: u64,
+        ascending: bool,
+        limit: u64,
     ) -> Result<Vec<(u64, ContractEvent)>> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetEventsRequest(Box::new(
+            GetEventsRequest::new(*key, start, ascending, limit),
+        )))?)
+    }
+
+    fn get_events_with_proof_by_event_key(
+        &self,
+        event_key: &EventKey,
+        start_seq_num: u64,
+        order: Order,
+        limit: u64,
+        known_version: Option<Version>,
+    ) -> Result<Vec<EventWithProof>> {
+        Ok(
+            self.request(StorageRequest::GetEventsWithProofByEventKeyRequest(
+                Box::new(GetEventsWithProofByEventKeyRequest::new(
+                    *event_key,
+                    start_seq_num,
+                    order,
+                    limit,
+                    known_version,
+                )),
+            ))?,
+        )
     }
 
     fn get_state_proof(
         &self,
-        _known_version: u64,
+        known_version: u64,
     ) -> Result<(
         LedgerInfoWithSignatures,
         EpochChangeProof,
         AccumulatorConsistencyProof,
     )> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetStateProofRequest(known_version))?)
     }
 
     fn get_state_proof_with_ledger_info(
         &self,
-        _known_version: u64,
-        _ledger_info: LedgerInfoWithSignatures,
+        known_version: u64,
+        ledger_info: LedgerInfoWithSignatures,
     ) -> Result<(EpochChangeProof, AccumulatorConsistencyProof)> {
-        unimplemented!()
+        Ok(
+            self.request(StorageRequest::GetStateProofWithLedgerInfoRequest(
+                Box::new(GetStateProofWithLedgerInfoRequest::new(
+                    known_version,
+                    ledger_info,
+                )),
+            ))?,
+        )
     }
 
     fn get_account_state_with_proof(
         &self,
-        _address: AccountAddress,
-        _version: Version,
-        _ledger_version: Version,
+        address: AccountAddress,
+        version: Version,
+        ledger_version: Version,
     ) -> Result<AccountStateWithProof> {
-        unimplemented!()
+        Ok(
+            self.request(StorageRequest::GetAccountStateWithProofRequest(Box::new(
+                GetAccountStateWithProofRequest::new(address, version, ledger_version),
+            )))?,
+        )
+    }
+
+    fn get_account_states_with_proof(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+        ledger_version: Version,
+    ) -> Result<AccountStatesWithProof> {
+        Ok(
+            self.request(StorageRequest::GetAccountStatesWithProofRequest(Box::new(
+                GetAccountStatesWithProofRequest::new(addresses.to_vec(), version, ledger_version),
+            )))?,
+        )
+    }
+
+    fn get_account_states_with_proof_by_version(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+    ) -> Result<Vec<(Option<AccountStateBlob>, SparseMerkleProof)>> {
+        Ok(
+            self.request(StorageRequest::GetAccountStatesWithProofByVersionRequest(
+                Box::new(GetAccountStatesWithProofByVersionRequest::new(
+                    addresses.to_vec(),
+                    version,
+                )),
+            ))?,
+        )
     }
 
     fn get_latest_state_root(&self) -> Result<(u64, HashValue)> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetLatestStateRootRequest)?)
     }
 
     fn get_latest_tree_state(&self) -> Result<TreeState> {
-        unimplemented!()
+        Ok(self.request(StorageRequest::GetLatestTreeStateRequest)?)
     }
 
     fn get_epoch_ending_ledger_infos(
         &self,
-        _start_epoch: u64,
-        _end_epoch: u64,
+        start_epoch: u64,
+        end_epoch: u64,
     ) -> Result<EpochChangeProof> {
-        unimplemented!()
+        Ok(
+            self.request(StorageRequest::GetEpochEndingLedgerInfosRequest(Box::new(
+                GetEpochEndingLedgerInfosRequest::new(start_epoch, end_epoch),
+            )))?,
+        )
+    }
+
+    fn get_epoch_ending_ledger_info(&self, known_version: u64) -> Result<LedgerInfoWithSignatures> {
+        Ok(
+            self.request(StorageRequest::GetEpochEndingLedgerInfoRequest(
+                known_version,
+            ))?,
+        )
     }
 
-    fn get_epoch_ending_ledger_info(&self, _: u64) -> Result<LedgerInfoWithSignatures> {
-        unimplemented!()
+    fn get_block_timestamp(&self, version: u64) -> Result<u64> {
+        Ok(self.request(StorageRequest::GetBlockTimestampRequest(version))?)
     }
 
-    fn get_block_timestamp(&self, _version: u64) -> Result<u64> {
-        unimplemented!()
+    fn get_latest_signed_waypoint(&self) -> Result<Option<SignedWaypoint>> {
+        Ok(self.request(StorageRequest::GetLatestSignedWaypointRequest)?)
     }
 }
 
@@ -193,4 +373,29 @@ impl DbWriter for StorageClient {
             ledger_info_with_sigs.cloned(),
         )?)
     }
+
+    fn pre_commit_transactions(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+    ) -> Result<()> {
+        Ok(
+            self.request::<()>(StorageRequest::PreCommitTransactionsRequest(Box::new(
+                PreCommitTransactionsRequest::new(
+                    txns_to_commit.to_vec(),
+                    first_version,
+                    ledger_info_with_sigs.clone(),
+                ),
+            )))?,
+        )
+    }
+
+    fn commit_ledger_info(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<()> {
+        Ok(
+            self.request::<()>(StorageRequest::CommitLedgerInfoRequest(Box::new(
+                ledger_info_with_sigs.clone(),
+            )))?,
+        )
+    }
 }
@@ -8,15 +8,18 @@ use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
     account_state::AccountState,
-    account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    account_state_blob::{AccountStateBlob, AccountStateWithProof, AccountStatesWithProof},
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     move_resource::MoveStorage,
-    proof::{definition::LeafCount, AccumulatorConsistencyProof, SparseMerkleProof},
+    proof::{
+        definition::LeafCount, AccountStatesProof, AccumulatorConsistencyProof, SparseMerkleProof,
+    },
     transaction::{TransactionListWithProof, TransactionToCommit, TransactionWithProof, Version},
+    waypoint::SignedWaypoint,
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -25,6 +28,7 @@ use std::{
     sync::Arc,
 };
 use thiserror::Error;
+use tokio::sync::watch;
 
 #[cfg(any(feature = "testing", feature = "fuzzing"))]
 pub mod mock;
@@ -101,6 +105,15 @@ pub enum Error {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Network error: {0}")]
+    NetworkError(String),
+
+    #[error("Storage at version {latest_version:?} is behind the client at version {min_version}")]
+    StaleRead {
+        latest_version: Option<Version>,
+        min_version: Version,
+    },
 }
 
 impl From<anyhow::Error> for Error {
@@ -119,12 +132,21 @@ impl From<lcs::Error> for Error {
 
 impl From<libra_secure_net::Error> for Error {
     fn from(error: libra_secure_net::Error) -> Self {
-        Self::ServiceError {
-            error: format!("{}", error),
-        }
+        Self::NetworkError(format!("{}", error))
     }
 }
 
+/// The receiving end of a subscription to the ledger infos committed to the DB. It holds the
+/// latest ledger info, if any, and is notified of each new one.
+pub type LedgerInfoSubscription = watch::Receiver<Option<LedgerInfoWithSignatures>>;
+
+/// The order in which the items of a stream are returned.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Order {
+    Ascending,
+    Descending,
+}
+
 /// Trait that is implemented by a DB that supports certain public (to client) read APIs
 /// expected of a Libra DB
 pub trait DbReader: Send + Sync {
@@ -158,6 +180,19 @@ pub trait DbReader: Send + Sync {
         limit: u64,
     ) -> Result<Vec<(u64, ContractEvent)>>;
 
+    /// See [`LibraDB::get_events_with_proof_by_event_key`].
+    ///
+    /// [`LibraDB::get_events_with_proof_by_event_key`]:
+    /// ../libradb/struct.LibraDB.html#method.get_events_with_proof_by_event_key
+    fn get_events_with_proof_by_event_key(
+        &self,
+        event_key: &EventKey,
+        start_seq_num: u64,
+        order: Order,
+        limit: u64,
+        known_version: Option<Version>,
+    ) -> Result<Vec<EventWithProof>>;
+
     /// See [`LibraDB::get_block_timestamp`].
     ///
     /// [`LibraDB::get_block_timestamp`]:
@@ -242,6 +277,60 @@ pub trait DbReader: Send + Sync {
         version: Version,
     ) -> Result<(Option<AccountStateBlob>, SparseMerkleProof)>;
 
+    /// Returns the states of the accounts of `addresses`, in the same order, at the given version
+    /// with a proof based on `ledger_version`. The proof of the transaction info at `version` is
+    /// shared by all the accounts instead of being repeated for each of them.
+    /// See [`LibraDB::get_account_states_with_proof`].
+    ///
+    /// [`LibraDB::get_account_states_with_proof`]:
+    /// ../libradb/struct.LibraDB.html#method.get_account_states_with_proof
+    fn get_account_states_with_proof(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+        ledger_version: Version,
+    ) -> Result<AccountStatesWithProof> {
+        let account_states = addresses
+            .iter()
+            .map(|address| self.get_account_state_with_proof(*address, version, ledger_version))
+            .collect::<Result<Vec<_>>>()?;
+        let transaction_info_with_proof = account_states
+            .first()
+            .ok_or_else(|| format_err!("No account to get the state of."))?
+            .proof
+            .transaction_info_with_proof()
+            .clone();
+        let (blobs, proofs) = account_states
+            .into_iter()
+            .map(|account_state| {
+                let proof = account_state.proof.transaction_info_to_account_proof();
+                (account_state.blob, proof.clone())
+            })
+            .unzip();
+        Ok(AccountStatesWithProof::new(
+            version,
+            blobs,
+            AccountStatesProof::new(transaction_info_with_proof, proofs),
+        ))
+    }
+
+    /// Batched variant of `get_account_state_with_proof_by_version`, returning the states of the
+    /// accounts of `addresses`, in the same order, out of the same state Merkle tree.
+    /// See [`LibraDB::get_account_states_with_proof_by_version`].
+    ///
+    /// [`LibraDB::get_account_states_with_proof_by_version`]:
+    /// ../libradb/struct.LibraDB.html#method.get_account_states_with_proof_by_version
+    fn get_account_states_with_proof_by_version(
+        &self,
+        addresses: &[AccountAddress],
+        version: Version,
+    ) -> Result<Vec<(Option<AccountStateBlob>, SparseMerkleProof)>> {
+        addresses
+            .iter()
+            .map(|address| self.get_account_state_with_proof_by_version(*address, version))
+            .collect()
+    }
+
     /// See [`LibraDB::get_latest_state_root`].
     ///
     /// [`LibraDB::get_latest_state_root`]:
@@ -254,6 +343,19 @@ pub trait DbReader: Send + Sync {
 
     /// Get the ledger info of the epoch that `known_version` belongs to.
     fn get_epoch_ending_ledger_info(&self, known_version: u64) -> Result<LedgerInfoWithSignatures>;
+
+    /// Subscribes to the ledger infos committed to the DB, so that components in the same process
+    /// are notified of commits instead of polling the DB. Returns None if the DB doesn't publish
+    /// them, e.g. when it is accessed remotely.
+    fn subscribe_to_ledger_infos(&self) -> Option<LedgerInfoSubscription> {
+        None
+    }
+
+    /// Returns the waypoint emitted last, along with the ledger info it was generated from, or
+    /// None if no waypoint has been emitted.
+    fn get_latest_signed_waypoint(&self) -> Result<Option<SignedWaypoint>> {
+        Ok(None)
+    }
 }
 
 impl MoveStorage for &dyn DbReader {
@@ -267,23 +369,20 @@ impl MoveStorage for &dyn DbReader {
         version: Version,
     ) -> Result<Vec<Vec<u8>>> {
         let addresses: Vec<AccountAddress> = access_paths
-            .iter()
-            .collect::<HashSet<_>>()
             .iter()
             .map(|path| path.address)
+            .collect::<HashSet<_>>()
+            .into_iter()
             .collect();
 
-        let results = addresses
-            .iter()
-            .map(|addr| self.get_account_state_with_proof(*addr, version, version))
-            .collect::<Result<Vec<_>>>()?;
+        let results = self.get_account_states_with_proof_by_version(&addresses, version)?;
 
         // Account address --> AccountState
         let account_states = addresses
             .iter()
             .zip_eq(results)
-            .map(|(addr, result)| {
-                let account_state = AccountState::try_from(&result.blob.ok_or_else(|| {
+            .map(|(addr, (blob, _proof))| {
+                let account_state = AccountState::try_from(&blob.ok_or_else(|| {
                     format_err!("missing blob in account state/account does not exist")
                 })?)?;
                 Ok((addr, account_state))
@@ -318,6 +417,26 @@ pub trait DbWriter: Send + Sync {
         first_version: Version,
         ledger_info_with_sigs: Option<&LedgerInfoWithSignatures>,
     ) -> Result<()>;
+
+    /// First phase of a two-phase commit: persists the transactions certified by
+    /// `ledger_info_with_sigs`, but not the ledger info itself. If the process crashes before the
+    /// second phase, the commit is completed when the DB is opened again.
+    /// See [`LibraDB::pre_commit_transactions`].
+    ///
+    /// [`LibraDB::pre_commit_transactions`]:
+    /// ../libradb/struct.LibraDB.html#method.pre_commit_transactions
+    fn pre_commit_transactions(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+    ) -> Result<()>;
+
+    /// Second phase of a two-phase commit: persists the ledger info of the prepared commit.
+    /// See [`LibraDB::commit_ledger_info`].
+    ///
+    /// [`LibraDB::commit_ledger_info`]: ../libradb/struct.LibraDB.html#method.commit_ledger_info
+    fn commit_ledger_info(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -358,6 +477,76 @@ pub enum StorageRequest {
     GetAccountStateWithProofByVersionRequest(Box<GetAccountStateWithProofByVersionRequest>),
     GetStartupInfoRequest,
     SaveTransactionsRequest(Box<SaveTransactionsRequest>),
+    GetEpochEndingLedgerInfosRequest(Box<GetEpochEndingLedgerInfosRequest>),
+    GetTransactionsRequest(Box<GetTransactionsRequest>),
+    GetEventsRequest(Box<GetEventsRequest>),
+    GetEventsWithProofByEventKeyRequest(Box<GetEventsWithProofByEventKeyRequest>),
+    GetBlockTimestampRequest(Version),
+    GetLatestAccountStateRequest(AccountAddress),
+    GetLatestLedgerInfoRequest,
+    GetTxnByAccountRequest(Box<GetTxnByAccountRequest>),
+    GetStateProofWithLedgerInfoRequest(Box<GetStateProofWithLedgerInfoRequest>),
+    GetStateProofRequest(Version),
+    GetAccountStateWithProofRequest(Box<GetAccountStateWithProofRequest>),
+    GetLatestStateRootRequest,
+    GetLatestTreeStateRequest,
+    GetEpochEndingLedgerInfoRequest(Version),
+    PreCommitTransactionsRequest(Box<PreCommitTransactionsRequest>),
+    CommitLedgerInfoRequest(Box<LedgerInfoWithSignatures>),
+    GetLatestSignedWaypointRequest,
+    GetAccountStatesWithProofRequest(Box<GetAccountStatesWithProofRequest>),
+    GetAccountStatesWithProofByVersionRequest(Box<GetAccountStatesWithProofByVersionRequest>),
+}
+
+impl StorageRequest {
+    /// Whether the request only reads the storage, and can safely be retried.
+    pub fn is_read(&self) -> bool {
+        !matches!(
+            self,
+            StorageRequest::SaveTransactionsRequest(_)
+                | StorageRequest::PreCommitTransactionsRequest(_)
+                | StorageRequest::CommitLedgerInfoRequest(_)
+        )
+    }
+}
+
+/// A request to the storage service, carrying the consistency token of the client: the latest
+/// version the client has seen committed. The service doesn't serve a request before it has
+/// caught up with this version, so that a client never reads a state older than one it has seen.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StorageRequestEnvelope {
+    pub min_version: Option<Version>,
+    pub request: StorageRequest,
+}
+
+impl StorageRequestEnvelope {
+    /// Constructor.
+    pub fn new(min_version: Option<Version>, request: StorageRequest) -> Self {
+        Self {
+            min_version,
+            request,
+        }
+    }
+}
+
+/// A response of the storage service.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StorageResponse {
+    /// The latest version committed when the request was served, which becomes the consistency
+    /// token of the client.
+    pub latest_version: Option<Version>,
+    /// The LCS serialized `Result` of the request.
+    pub result: Vec<u8>,
+}
+
+impl StorageResponse {
+    /// Constructor.
+    pub fn new(latest_version: Option<Version>, result: Vec<u8>) -> Self {
+        Self {
+            latest_version,
+            result,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -397,3 +586,207 @@ impl SaveTransactionsRequest {
         }
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetEpochEndingLedgerInfosRequest {
+    pub start_epoch: u64,
+    pub end_epoch: u64,
+}
+
+impl GetEpochEndingLedgerInfosRequest {
+    /// Constructor.
+    pub fn new(start_epoch: u64, end_epoch: u64) -> Self {
+        Self {
+            start_epoch,
+            end_epoch,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetTransactionsRequest {
+    pub start_version: Version,
+    pub batch_size: u64,
+    pub ledger_version: Version,
+    pub fetch_events: bool,
+}
+
+impl GetTransactionsRequest {
+    /// Constructor.
+    pub fn new(
+        start_version: Version,
+        batch_size: u64,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Self {
+        Self {
+            start_version,
+            batch_size,
+            ledger_version,
+            fetch_events,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetEventsRequest {
+    pub event_key: EventKey,
+    pub start: u64,
+    pub ascending: bool,
+    pub limit: u64,
+}
+
+impl GetEventsRequest {
+    /// Constructor.
+    pub fn new(event_key: EventKey, start: u64, ascending: bool, limit: u64) -> Self {
+        Self {
+            event_key,
+            start,
+            ascending,
+            limit,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetEventsWithProofByEventKeyRequest {
+    pub event_key: EventKey,
+    pub start_seq_num: u64,
+    pub order: Order,
+    pub limit: u64,
+    pub known_version: Option<Version>,
+}
+
+impl GetEventsWithProofByEventKeyRequest {
+    /// Constructor.
+    pub fn new(
+        event_key: EventKey,
+        start_seq_num: u64,
+        order: Order,
+        limit: u64,
+        known_version: Option<Version>,
+    ) -> Self {
+        Self {
+            event_key,
+            start_seq_num,
+            order,
+            limit,
+            known_version,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetTxnByAccountRequest {
+    pub address: AccountAddress,
+    pub seq_num: u64,
+    pub ledger_version: Version,
+    pub fetch_events: bool,
+}
+
+impl GetTxnByAccountRequest {
+    /// Constructor.
+    pub fn new(
+        address: AccountAddress,
+        seq_num: u64,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Self {
+        Self {
+            address,
+            seq_num,
+            ledger_version,
+            fetch_events,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetStateProofWithLedgerInfoRequest {
+    pub known_version: Version,
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+}
+
+impl GetStateProofWithLedgerInfoRequest {
+    /// Constructor.
+    pub fn new(
+        known_version: Version,
+        ledger_info_with_signatures: LedgerInfoWithSignatures,
+    ) -> Self {
+        Self {
+            known_version,
+            ledger_info_with_signatures,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetAccountStateWithProofRequest {
+    pub address: AccountAddress,
+    pub version: Version,
+    pub ledger_version: Version,
+}
+
+impl GetAccountStateWithProofRequest {
+    /// Constructor.
+    pub fn new(address: AccountAddress, version: Version, ledger_version: Version) -> Self {
+        Self {
+            address,
+            version,
+            ledger_version,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetAccountStatesWithProofRequest {
+    pub addresses: Vec<AccountAddress>,
+    pub version: Version,
+    pub ledger_version: Version,
+}
+
+impl GetAccountStatesWithProofRequest {
+    /// Constructor.
+    pub fn new(addresses: Vec<AccountAddress>, version: Version, ledger_version: Version) -> Self {
+        Self {
+            addresses,
+            version,
+            ledger_version,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct GetAccountStatesWithProofByVersionRequest {
+    pub addresses: Vec<AccountAddress>,
+    pub version: Version,
+}
+
+impl GetAccountStatesWithProofByVersionRequest {
+    /// Constructor.
+    pub fn new(addresses: Vec<AccountAddress>, version: Version) -> Self {
+        Self { addresses, version }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+pub struct PreCommitTransactionsRequest {
+    pub txns_to_commit: Vec<TransactionToCommit>,
+    pub first_version: Version,
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+}
+
+impl PreCommitTransactionsRequest {
+    /// Constructor.
+    pub fn new(
+        txns_to_commit: Vec<TransactionToCommit>,
+        first_version: Version,
+        ledger_info_with_signatures: LedgerInfoWithSignatures,
+    ) -> Self {
+        Self {
+            txns_to_commit,
+            first_version,
+            ledger_info_with_signatures,
+        }
+    }
+}
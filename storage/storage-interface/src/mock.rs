@@ -3,7 +3,7 @@
 
 //! This module provides mock dbreader for tests.
 
-use crate::{DbReader, StartupInfo, TreeState};
+use crate::{DbReader, Order, StartupInfo, TreeState};
 use anyhow::Result;
 use libra_crypto::HashValue;
 use libra_types::{
@@ -11,7 +11,7 @@ use libra_types::{
     account_config::AccountResource,
     account_state::AccountState,
     account_state_blob::{AccountStateBlob, AccountStateWithProof},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithProof},
     epoch_change::EpochChangeProof,
     event::{EventHandle, EventKey},
     ledger_info::LedgerInfoWithSignatures,
@@ -54,6 +54,17 @@ impl DbReader for MockDbReader {
         unimplemented!()
     }
 
+    fn get_events_with_proof_by_event_key(
+        &self,
+        _event_key: &EventKey,
+        _start_seq_num: u64,
+        _order: Order,
+        _limit: u64,
+        _known_version: Option<Version>,
+    ) -> Result<Vec<EventWithProof>> {
+        unimplemented!()
+    }
+
     fn get_block_timestamp(&self, _version: u64) -> Result<u64> {
         unimplemented!()
     }
@@ -3,6 +3,7 @@
 
 use crate::DbReader;
 use anyhow::{format_err, Result};
+use itertools::Itertools;
 use libra_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -12,13 +13,14 @@ use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
     account_state::AccountState,
+    account_state_blob::AccountStateBlob,
     proof::SparseMerkleProof,
     transaction::{Version, PRE_GENESIS_VERSION},
 };
 use scratchpad::{AccountStatus, SparseMerkleTree};
 use std::{
     cell::RefCell,
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     convert::TryInto,
     sync::Arc,
 };
@@ -112,6 +114,76 @@ impl<'a> VerifiedStateView<'a> {
             account_to_proof_cache: RefCell::new(HashMap::new()),
         }
     }
+
+    /// Loads the states of the accounts of `addresses` that have to be read from persistent
+    /// storage with a single batched read, e.g., those of the senders of a block, instead of
+    /// reading them one at a time as the VM queries them.
+    pub fn prefetch_accounts(&self, addresses: &[AccountAddress]) -> Result<()> {
+        let version = match self.latest_persistent_version {
+            Some(version) => version,
+            None => return Ok(()),
+        };
+        let addresses: Vec<_> = {
+            let account_to_state_cache = self.account_to_state_cache.borrow();
+            addresses
+                .iter()
+                .filter(|address| !account_to_state_cache.contains_key(*address))
+                .filter(|address| {
+                    matches!(
+                        self.speculative_state.get(address.hash()),
+                        AccountStatus::ExistsInDB | AccountStatus::Unknown
+                    )
+                })
+                .cloned()
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect()
+        };
+        if addresses.is_empty() {
+            return Ok(());
+        }
+
+        let account_states = self
+            .reader
+            .get_account_states_with_proof_by_version(&addresses, version)?;
+        for (address, (blob, proof)) in addresses.into_iter().zip_eq(account_states) {
+            self.verify_and_cache_proof(address, blob.as_ref(), proof)?;
+            let account_state = blob
+                .as_ref()
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default();
+            self.account_to_state_cache
+                .borrow_mut()
+                .insert(address, account_state);
+        }
+        Ok(())
+    }
+
+    fn verify_and_cache_proof(
+        &self,
+        address: AccountAddress,
+        blob: Option<&AccountStateBlob>,
+        proof: SparseMerkleProof,
+    ) -> Result<()> {
+        let address_hash = address.hash();
+        proof
+            .verify(self.latest_persistent_state_root, address_hash, blob)
+            .map_err(|err| {
+                format_err!(
+                    "Proof is invalid for address {:?} with state root hash {:?}: {}",
+                    address,
+                    self.latest_persistent_state_root,
+                    err
+                )
+            })?;
+        assert!(self
+            .account_to_proof_cache
+            .borrow_mut()
+            .insert(address_hash, proof)
+            .is_none());
+        Ok(())
+    }
 }
 
 impl<'a>
@@ -157,25 +229,7 @@ impl<'a> StateView for VerifiedStateView<'a> {
                                 .get_account_state_with_proof_by_version(address, version)?,
                             None => (None, SparseMerkleProof::new(None, vec![])),
                         };
-                        proof
-                            .verify(
-                                self.latest_persistent_state_root,
-                                address.hash(),
-                                blob.as_ref(),
-                            )
-                            .map_err(|err| {
-                                format_err!(
-                                "Proof is invalid for address {:?} with state root hash {:?}: {}",
-                                address,
-                                self.latest_persistent_state_root,
-                                err
-                            )
-                            })?;
-                        assert!(self
-                            .account_to_proof_cache
-                            .borrow_mut()
-                            .insert(address_hash, proof)
-                            .is_none());
+                        self.verify_and_cache_proof(address, blob.as_ref(), proof)?;
                         blob
                     }
                 };
@@ -8,23 +8,45 @@
 //! The user of storage service is supposed to use it via client lib provided in
 //! [`storage-client`](../storage-client/index.html) instead of via
 
-use anyhow::Result;
 use libra_config::config::NodeConfig;
+use libra_crypto::noise::NoiseConfig;
 use libra_logger::prelude::*;
+use libra_metrics::health::{register_health_reporter, ComponentHealth};
 use libra_secure_net::NetworkServer;
-use libra_types::{account_state_blob::AccountStateBlob, proof::SparseMerkleProof};
+use libra_types::transaction::Version;
 use libradb::LibraDB;
+use serde::Serialize;
 use std::{
     sync::Arc,
     thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
-use storage_interface::{DbReader, DbWriter, Error, StartupInfo};
+use storage_interface::{
+    DbReader, DbWriter, Error, StorageRequest, StorageRequestEnvelope, StorageResponse,
+};
+
+/// How long a request waits for the DB to catch up with the consistency token of the client
+/// before it fails with a stale read.
+const CATCH_UP_TIMEOUT: Duration = Duration::from_secs(1);
+const CATCH_UP_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Starts storage service with a given LibraDB
 pub fn start_storage_service_with_db(
     config: &NodeConfig,
     libra_db: Arc<LibraDB>,
 ) -> JoinHandle<()> {
+    let db = libra_db.clone();
+    register_health_reporter(
+        "storage",
+        Box::new(move || match db.get_latest_ledger_info() {
+            Ok(ledger_info) => ComponentHealth::healthy(format!(
+                "latest version {}",
+                ledger_info.ledger_info().version()
+            )),
+            Err(e) => ComponentHealth::unhealthy(format!("unable to read ledger info: {}", e)),
+        }),
+    );
+
     let storage_service = StorageService { db: libra_db };
     storage_service.run(config)
 }
@@ -36,54 +58,168 @@ pub struct StorageService {
 
 impl StorageService {
     fn handle_message(&self, input_message: Vec<u8>) -> Result<Vec<u8>, Error> {
-        let input = lcs::from_bytes(&input_message)?;
-        let output = match input {
-            storage_interface::StorageRequest::GetAccountStateWithProofByVersionRequest(req) => {
-                lcs::to_bytes(&self.get_account_state_with_proof_by_version(&req))
-            }
-            storage_interface::StorageRequest::GetStartupInfoRequest => {
-                lcs::to_bytes(&self.get_startup_info())
-            }
-            storage_interface::StorageRequest::SaveTransactionsRequest(req) => {
-                lcs::to_bytes(&self.save_transactions(&req))
-            }
+        let result = match lcs::from_bytes::<StorageRequestEnvelope>(&input_message) {
+            Ok(envelope) => match self.wait_for_version(envelope.min_version) {
+                Ok(()) => self.handle_request(envelope.request)?,
+                Err(e) => serialize_error(e)?,
+            },
+            Err(e) => serialize_error(e.into())?,
         };
-        Ok(output?)
+        let response = StorageResponse::new(self.latest_version(), result);
+        Ok(lcs::to_bytes(&response)?)
     }
 
-    fn get_account_state_with_proof_by_version(
-        &self,
-        req: &storage_interface::GetAccountStateWithProofByVersionRequest,
-    ) -> Result<(Option<AccountStateBlob>, SparseMerkleProof), Error> {
-        Ok(self
-            .db
-            .get_account_state_with_proof_by_version(req.address, req.version)?)
+    fn handle_request(&self, request: StorageRequest) -> Result<Vec<u8>, Error> {
+        // Calls through the trait, as some `LibraDB` methods share a name with it
+        let db: &dyn DbReader = &*self.db;
+        match request {
+            StorageRequest::GetAccountStateWithProofByVersionRequest(req) => serialize_result(
+                db.get_account_state_with_proof_by_version(req.address, req.version),
+            ),
+            StorageRequest::GetStartupInfoRequest => serialize_result(db.get_startup_info()),
+            StorageRequest::SaveTransactionsRequest(req) => {
+                serialize_result(self.db.save_transactions(
+                    &req.txns_to_commit,
+                    req.first_version,
+                    req.ledger_info_with_signatures.as_ref(),
+                ))
+            }
+            StorageRequest::GetEpochEndingLedgerInfosRequest(req) => {
+                serialize_result(db.get_epoch_ending_ledger_infos(req.start_epoch, req.end_epoch))
+            }
+            StorageRequest::GetTransactionsRequest(req) => serialize_result(db.get_transactions(
+                req.start_version,
+                req.batch_size,
+                req.ledger_version,
+                req.fetch_events,
+            )),
+            StorageRequest::GetEventsRequest(req) => {
+                serialize_result(db.get_events(&req.event_key, req.start, req.ascending, req.limit))
+            }
+            StorageRequest::GetEventsWithProofByEventKeyRequest(req) => {
+                serialize_result(db.get_events_with_proof_by_event_key(
+                    &req.event_key,
+                    req.start_seq_num,
+                    req.order,
+                    req.limit,
+                    req.known_version,
+                ))
+            }
+            StorageRequest::GetBlockTimestampRequest(version) => {
+                serialize_result(db.get_block_timestamp(version))
+            }
+            StorageRequest::GetLatestAccountStateRequest(address) => {
+                serialize_result(db.get_latest_account_state(address))
+            }
+            StorageRequest::GetLatestLedgerInfoRequest => {
+                serialize_result(db.get_latest_ledger_info())
+            }
+            StorageRequest::GetTxnByAccountRequest(req) => serialize_result(db.get_txn_by_account(
+                req.address,
+                req.seq_num,
+                req.ledger_version,
+                req.fetch_events,
+            )),
+            StorageRequest::GetStateProofWithLedgerInfoRequest(req) => {
+                serialize_result(db.get_state_proof_with_ledger_info(
+                    req.known_version,
+                    req.ledger_info_with_signatures,
+                ))
+            }
+            StorageRequest::GetStateProofRequest(known_version) => {
+                serialize_result(db.get_state_proof(known_version))
+            }
+            StorageRequest::GetAccountStateWithProofRequest(req) => serialize_result(
+                db.get_account_state_with_proof(req.address, req.version, req.ledger_version),
+            ),
+            StorageRequest::GetLatestStateRootRequest => {
+                serialize_result(db.get_latest_state_root())
+            }
+            StorageRequest::GetLatestTreeStateRequest => {
+                serialize_result(db.get_latest_tree_state())
+            }
+            StorageRequest::GetEpochEndingLedgerInfoRequest(known_version) => {
+                serialize_result(db.get_epoch_ending_ledger_info(known_version))
+            }
+            StorageRequest::PreCommitTransactionsRequest(req) => {
+                serialize_result(self.db.pre_commit_transactions(
+                    &req.txns_to_commit,
+                    req.first_version,
+                    &req.ledger_info_with_signatures,
+                ))
+            }
+            StorageRequest::CommitLedgerInfoRequest(ledger_info_with_sigs) => {
+                serialize_result(self.db.commit_ledger_info(&ledger_info_with_sigs))
+            }
+            StorageRequest::GetLatestSignedWaypointRequest => {
+                serialize_result(db.get_latest_signed_waypoint())
+            }
+            StorageRequest::GetAccountStatesWithProofRequest(req) => serialize_result(
+                db.get_account_states_with_proof(&req.addresses, req.version, req.ledger_version),
+            ),
+            StorageRequest::GetAccountStatesWithProofByVersionRequest(req) => serialize_result(
+                db.get_account_states_with_proof_by_version(&req.addresses, req.version),
+            ),
+        }
     }
 
-    fn get_startup_info(&self) -> Result<Option<StartupInfo>, Error> {
-        Ok(self.db.get_startup_info()?)
+    /// Waits until the DB has committed `min_version`, so that the client never reads a state
+    /// older than one it has already seen.
+    fn wait_for_version(&self, min_version: Option<Version>) -> Result<(), Error> {
+        let min_version = match min_version {
+            Some(min_version) => min_version,
+            None => return Ok(()),
+        };
+        let deadline = Instant::now() + CATCH_UP_TIMEOUT;
+        loop {
+            let latest_version = self.latest_version();
+            if latest_version.map_or(false, |version| version >= min_version) {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::StaleRead {
+                    latest_version,
+                    min_version,
+                });
+            }
+            thread::sleep(CATCH_UP_POLL_INTERVAL);
+        }
     }
 
-    fn save_transactions(
-        &self,
-        req: &storage_interface::SaveTransactionsRequest,
-    ) -> Result<(), Error> {
-        Ok(self.db.save_transactions(
-            &req.txns_to_commit,
-            req.first_version,
-            req.ledger_info_with_signatures.as_ref(),
-        )?)
+    fn latest_version(&self) -> Option<Version> {
+        self.db.get_latest_version().ok()
     }
 
     fn run(self, config: &NodeConfig) -> JoinHandle<()> {
-        let mut network_server = NetworkServer::new(config.storage.address);
+        let mut network_server = match &config.storage.secure_channel {
+            Some(secure_channel) => NetworkServer::new_secure(
+                config.storage.address,
+                NoiseConfig::new(secure_channel.identity_key()),
+                secure_channel.trusted_clients.clone(),
+            ),
+            None => NetworkServer::new(config.storage.address),
+        };
         thread::spawn(move || loop {
-            if let Err(e) = self.process_one_message(&mut network_server) {
-                warn!("Failed to process message: {}", e);
+            match network_server.accept() {
+                Ok(connection) => {
+                    let service = self.clone();
+                    thread::spawn(move || service.serve(connection));
+                }
+                Err(e) => warn!("Failed to accept connection: {}", e),
             }
         })
     }
 
+    /// Serves the requests of one client until it disconnects.
+    fn serve(&self, mut connection: NetworkServer) {
+        loop {
+            if let Err(e) = self.process_one_message(&mut connection) {
+                debug!("Closing storage client connection: {}", e);
+                return;
+            }
+        }
+    }
+
     fn process_one_message(&self, network_server: &mut NetworkServer) -> Result<(), Error> {
         let request = network_server.read()?;
         let response = self.handle_message(request)?;
@@ -92,5 +228,15 @@ impl StorageService {
     }
 }
 
+/// Serializes the result of a request, which the client deserializes as a `Result<T, Error>`.
+fn serialize_result<T: Serialize>(result: anyhow::Result<T>) -> Result<Vec<u8>, Error> {
+    Ok(lcs::to_bytes(&result.map_err(Error::from))?)
+}
+
+/// An error serializes the same whatever the type of the result the client expects.
+fn serialize_error(error: Error) -> Result<Vec<u8>, Error> {
+    Ok(lcs::to_bytes(&Result::<(), Error>::Err(error))?)
+}
+
 #[cfg(test)]
 mod storage_service_test;
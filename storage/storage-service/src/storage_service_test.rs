@@ -13,6 +13,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
 };
 use storage_client::StorageClient;
+use storage_interface::StartupInfo;
 
 fn start_test_storage_with_client() -> (JoinHandle<()>, libra_temppath::TempPath, StorageClient) {
     let mut config = NodeConfig::random();
@@ -69,6 +70,24 @@ proptest! {
                 .map(|address| client.get_account_state_with_proof_by_version(*address, version - 1).unwrap())
                 .collect::<Vec<_>>();
             let startup_info = client.get_startup_info().unwrap().unwrap();
+
+            let addresses = account_states.keys().cloned().collect::<Vec<_>>();
+            let account_states_with_proof = client
+                .get_account_states_with_proof(&addresses, version - 1, version - 1)
+                .unwrap();
+            prop_assert!(account_states_with_proof
+                .verify(ledger_info_with_sigs.ledger_info(), version - 1, &addresses)
+                .is_ok());
+            prop_assert_eq!(
+                account_states_with_proof.blobs,
+                client
+                    .get_account_states_with_proof_by_version(&addresses, version - 1)
+                    .unwrap()
+                    .into_iter()
+                    .map(|(blob, _proof)| blob)
+                    .collect::<Vec<_>>()
+            );
+
             for ((address, blob), state_with_proof) in zip_eq(account_states, account_states_returned) {
                  prop_assert_eq!(&Some(blob), &state_with_proof.0);
                  prop_assert!(state_with_proof.1
@@ -82,3 +101,66 @@ proptest! {
         }
     }
 }
+
+#[test]
+fn test_stale_read() {
+    let tmp_dir = libra_temppath::TempPath::new();
+    let service = StorageService {
+        db: Arc::new(LibraDB::new_for_test(&tmp_dir)),
+    };
+
+    let request = StorageRequestEnvelope::new(Some(10), StorageRequest::GetStartupInfoRequest);
+    let response: StorageResponse = lcs::from_bytes(
+        &service
+            .handle_message(lcs::to_bytes(&request).unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response.latest_version, None);
+    let result: Result<Option<StartupInfo>, Error> = lcs::from_bytes(&response.result).unwrap();
+    assert_eq!(
+        result.unwrap_err(),
+        Error::StaleRead {
+            latest_version: None,
+            min_version: 10,
+        }
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(5))]
+    #[test]
+    fn test_concurrent_clients(blocks in arb_blocks_to_commit().no_shrink()) {
+        let (_handle, _tmp_dir, client) = start_test_storage_with_client();
+        let client = Arc::new(client);
+
+        let mut version = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in &blocks {
+            client.save_transactions(
+                txns_to_commit.clone(),
+                version, /* first_version */
+                Some(ledger_info_with_sigs.clone()),
+            ).unwrap();
+            version += txns_to_commit.len() as u64;
+        }
+
+        let expected_version = blocks.last().unwrap().1.ledger_info().version();
+        let readers = (0..4)
+            .map(|_| {
+                let client = Arc::clone(&client);
+                thread::spawn(move || {
+                    (0..10)
+                        .map(|_| DbReader::get_latest_version(&*client).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        for reader in readers {
+            for latest_version in reader.join().unwrap() {
+                prop_assert_eq!(latest_version, expected_version);
+            }
+        }
+        let ledger_info = DbReader::get_latest_ledger_info(&*client).unwrap();
+        prop_assert_eq!(&ledger_info, &blocks.last().unwrap().1);
+    }
+}
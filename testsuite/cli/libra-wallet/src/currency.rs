@@ -0,0 +1,225 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for accounts holding multiple currencies. An account can only hold, send, or receive a
+//! currency after registering it via an add-currency-to-account transaction. The only on-chain
+//! exchange is between LBR and its reserve currencies, Coin1 and Coin2: minting LBR withdraws
+//! both reserve currencies from the account in proportion to the LBR reserve, while unminting LBR
+//! deposits both.
+
+use crate::error::WalletError;
+use anyhow::Result;
+use libra_types::{
+    account_address::AccountAddress,
+    account_config::{
+        from_currency_code_string, type_tag_for_currency_code, COIN1_NAME, COIN2_NAME, LBR_NAME,
+    },
+    transaction::Script,
+};
+use move_core_types::identifier::Identifier;
+use std::collections::BTreeSet;
+
+/// CurrencyFlow builds, in order, the scripts required to exchange and transfer currencies on
+/// behalf of a single account. Currencies needed along the way, e.g., LBR before minting it, are
+/// registered on demand. Each script must be submitted as a separate transaction, with
+/// consecutive sequence numbers, and each depends upon the success of those before it.
+#[derive(Debug)]
+pub struct CurrencyFlow {
+    registered: BTreeSet<Identifier>,
+    scripts: Vec<Script>,
+}
+
+impl CurrencyFlow {
+    /// Starts a flow for an account that has already registered the given currencies.
+    pub fn new<I: IntoIterator<Item = Identifier>>(registered: I) -> Self {
+        Self {
+            registered: registered.into_iter().collect(),
+            scripts: Vec::new(),
+        }
+    }
+
+    /// Registers the currency with the account, unless it already is.
+    pub fn add_currency(mut self, currency: Identifier) -> Self {
+        if !self.registered.contains(&currency) {
+            self.scripts
+                .push(transaction_builder::encode_add_currency_to_account_script(
+                    type_tag_for_currency_code(currency.clone()),
+                ));
+            self.registered.insert(currency);
+        }
+        self
+    }
+
+    /// Exchanges Coin1 and Coin2 for `amount_lbr` LBR.
+    pub fn mint_lbr(mut self, amount_lbr: u64) -> Result<Self> {
+        self.ensure_registered(COIN1_NAME)?;
+        self.ensure_registered(COIN2_NAME)?;
+        self = self.add_currency(from_currency_code_string(LBR_NAME)?);
+        self.scripts
+            .push(transaction_builder::encode_mint_lbr_script(amount_lbr));
+        Ok(self)
+    }
+
+    /// Exchanges `amount_lbr` LBR for Coin1 and Coin2.
+    pub fn unmint_lbr(mut self, amount_lbr: u64) -> Result<Self> {
+        self.ensure_registered(LBR_NAME)?;
+        self = self
+            .add_currency(from_currency_code_string(COIN1_NAME)?)
+            .add_currency(from_currency_code_string(COIN2_NAME)?);
+        self.scripts
+            .push(transaction_builder::encode_unmint_lbr_script(amount_lbr));
+        Ok(self)
+    }
+
+    /// Transfers `amount` of `currency` to `payee`, which must have registered the currency.
+    pub fn transfer(mut self, currency: &str, payee: AccountAddress, amount: u64) -> Result<Self> {
+        self.ensure_registered(currency)?;
+        self.scripts.push(
+            transaction_builder::encode_peer_to_peer_with_metadata_script(
+                type_tag_for_currency_code(from_currency_code_string(currency)?),
+                payee,
+                amount,
+                vec![],
+                vec![],
+            ),
+        );
+        Ok(self)
+    }
+
+    /// Exchanges `from_currency` for `to_currency` and transfers `amount` of the latter to
+    /// `payee`. Exchanging into LBR mints `amount_lbr`, which must be no less than `amount`.
+    /// Exchanging out of LBR unmints `amount_lbr`, the value of which in `to_currency` depends on
+    /// the LBR reserve and must be no less than `amount` for the transfer to succeed.
+    pub fn exchange_and_transfer(
+        self,
+        from_currency: &str,
+        to_currency: &str,
+        amount_lbr: u64,
+        payee: AccountAddress,
+        amount: u64,
+    ) -> Result<Self> {
+        let flow = match (from_currency, to_currency) {
+            (from, to) if from == to => self,
+            (COIN1_NAME, LBR_NAME) | (COIN2_NAME, LBR_NAME) => {
+                if amount_lbr < amount {
+                    return Err(WalletError::LibraWalletGeneric(format!(
+                        "Unable to transfer {} LBR after minting {} LBR",
+                        amount, amount_lbr
+                    ))
+                    .into());
+                }
+                self.mint_lbr(amount_lbr)?
+            }
+            (LBR_NAME, COIN1_NAME) | (LBR_NAME, COIN2_NAME) => self.unmint_lbr(amount_lbr)?,
+            (from, to) => {
+                return Err(WalletError::LibraWalletGeneric(format!(
+                    "Unable to exchange {} for {}",
+                    from, to
+                ))
+                .into())
+            }
+        };
+        flow.transfer(to_currency, payee, amount)
+    }
+
+    /// Returns the scripts in the order they must be executed.
+    pub fn into_scripts(self) -> Vec<Script> {
+        self.scripts
+    }
+
+    fn ensure_registered(&self, currency: &str) -> Result<()> {
+        if self
+            .registered
+            .contains(&from_currency_code_string(currency)?)
+        {
+            Ok(())
+        } else {
+            Err(WalletError::LibraWalletGeneric(format!(
+                "Account does not hold currency {}",
+                currency
+            ))
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currencies(codes: &[&str]) -> Vec<Identifier> {
+        codes
+            .iter()
+            .map(|code| from_currency_code_string(code).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_add_currency_on_demand() {
+        let scripts = CurrencyFlow::new(currencies(&[LBR_NAME]))
+            .add_currency(from_currency_code_string(LBR_NAME).unwrap())
+            .add_currency(from_currency_code_string(COIN1_NAME).unwrap())
+            .add_currency(from_currency_code_string(COIN1_NAME).unwrap())
+            .into_scripts();
+        assert_eq!(
+            scripts,
+            vec![transaction_builder::encode_add_currency_to_account_script(
+                type_tag_for_currency_code(from_currency_code_string(COIN1_NAME).unwrap())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_exchange_and_transfer() {
+        let payee = AccountAddress::random();
+
+        // Minting LBR registers it first
+        let scripts = CurrencyFlow::new(currencies(&[COIN1_NAME, COIN2_NAME]))
+            .exchange_and_transfer(COIN1_NAME, LBR_NAME, 20, payee, 10)
+            .unwrap()
+            .into_scripts();
+        assert_eq!(scripts.len(), 3);
+        assert_eq!(scripts[1], transaction_builder::encode_mint_lbr_script(20));
+        assert_eq!(
+            scripts[2],
+            transaction_builder::encode_peer_to_peer_with_metadata_script(
+                type_tag_for_currency_code(from_currency_code_string(LBR_NAME).unwrap()),
+                payee,
+                10,
+                vec![],
+                vec![],
+            )
+        );
+
+        // Minting less LBR than is transferred is rejected
+        CurrencyFlow::new(currencies(&[COIN1_NAME, COIN2_NAME]))
+            .exchange_and_transfer(COIN2_NAME, LBR_NAME, 5, payee, 10)
+            .unwrap_err();
+
+        // Unminting LBR deposits both reserve currencies
+        let scripts = CurrencyFlow::new(currencies(&[LBR_NAME, COIN1_NAME]))
+            .exchange_and_transfer(LBR_NAME, COIN2_NAME, 20, payee, 10)
+            .unwrap()
+            .into_scripts();
+        assert_eq!(scripts.len(), 3);
+        assert_eq!(
+            scripts[1],
+            transaction_builder::encode_unmint_lbr_script(20)
+        );
+
+        // Transfers within a currency need no exchange
+        let scripts = CurrencyFlow::new(currencies(&[COIN1_NAME]))
+            .exchange_and_transfer(COIN1_NAME, COIN1_NAME, 0, payee, 10)
+            .unwrap()
+            .into_scripts();
+        assert_eq!(scripts.len(), 1);
+
+        // Minting requires both reserve currencies and reserve currencies cannot be exchanged
+        CurrencyFlow::new(currencies(&[COIN1_NAME]))
+            .exchange_and_transfer(COIN1_NAME, LBR_NAME, 10, payee, 10)
+            .unwrap_err();
+        CurrencyFlow::new(currencies(&[COIN1_NAME, COIN2_NAME]))
+            .exchange_and_transfer(COIN1_NAME, COIN2_NAME, 0, payee, 10)
+            .unwrap_err();
+    }
+}
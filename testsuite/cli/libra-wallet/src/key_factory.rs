@@ -25,7 +25,10 @@ use libra_crypto::{
     hkdf::Hkdf,
     traits::SigningKey,
 };
-use libra_types::{account_address::AccountAddress, transaction::authenticator::AuthenticationKey};
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::authenticator::{AccountPublicKey, AuthenticationKey},
+};
 use mirai_annotations::*;
 use pbkdf2::pbkdf2;
 use serde::{Deserialize, Serialize};
@@ -101,12 +104,16 @@ impl ExtendedPrivKey {
 
     /// Compute the account address for this account's public key
     pub fn get_address(&self) -> AccountAddress {
-        libra_types::account_address::from_public_key(&self.get_public())
+        self.get_account_public_key().derived_address()
     }
 
     /// Compute the authentication key for this account's public key
     pub fn get_authentication_key(&self) -> AuthenticationKey {
-        AuthenticationKey::ed25519(&self.get_public())
+        self.get_account_public_key().authentication_key()
+    }
+
+    fn get_account_public_key(&self) -> AccountPublicKey {
+        AccountPublicKey::Ed25519(self.get_public())
     }
 
     /// Libra specific sign function that is capable of signing an arbitrary HashValue
@@ -3,6 +3,9 @@
 
 #![forbid(unsafe_code)]
 
+/// Multi-currency transaction flows
+pub mod currency;
+
 /// Error crate
 mod error;
 
@@ -19,6 +22,9 @@ mod key_factory;
 /// Utils for mnemonic seed
 mod mnemonic;
 
+/// SLIP-0010 hierarchical key derivation
+pub mod slip10;
+
 /// Utils for wallet library
 mod wallet_library;
 
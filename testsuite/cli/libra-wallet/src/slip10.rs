@@ -0,0 +1,399 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hierarchical deterministic key derivation for ed25519 following
+//! [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md).
+//!
+//! SLIP-0010 adapts [BIP32](https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki) to
+//! curves other than secp256k1. For ed25519 only hardened derivation is defined, hence every
+//! index in a [`DerivationPath`] is hardened and public parent to public child derivation is not
+//! available.
+//!
+//! Libra accounts are derived along the [BIP44](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki)
+//! path `m/44'/637'/{account}'/0'/{index}'`, where 637 is the coin type registered for Libra in
+//! [SLIP-0044](https://github.com/satoshilabs/slips/blob/master/slip-0044.md). The seed is the
+//! standard BIP39 seed of a [`Mnemonic`], which makes keys portable across wallets implementing
+//! the same scheme.
+
+use crate::{error::WalletError, mnemonic::Mnemonic};
+use anyhow::Result;
+use hmac::{Hmac, Mac, NewMac};
+use libra_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use libra_types::{
+    account_address::AccountAddress,
+    transaction::authenticator::{AccountPublicKey, AuthenticationKey},
+};
+use pbkdf2::pbkdf2;
+use sha2::Sha512;
+use std::{convert::TryFrom, fmt, str::FromStr};
+use zeroize::Zeroize;
+
+/// BIP44 purpose field.
+pub const BIP44_PURPOSE: u32 = 44;
+/// SLIP-0044 coin type registered for Libra.
+pub const LIBRA_COIN_TYPE: u32 = 637;
+/// Bit set on hardened child indices.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+const ED25519_CURVE_SEED: &[u8] = b"ed25519 seed";
+const BIP39_SALT_PREFIX: &str = "mnemonic";
+const BIP39_PBKDF2_ROUNDS: usize = 2048;
+
+/// Length in bytes of a BIP39 seed.
+pub const BIP39_SEED_LENGTH: usize = 64;
+
+/// Computes the BIP39 seed of a mnemonic, protected by an optional passphrase (empty string for
+/// none). This is PBKDF2-HMAC-SHA512 over the mnemonic sentence with the salt
+/// `"mnemonic" || passphrase`.
+pub fn bip39_seed(mnemonic: &Mnemonic, passphrase: &str) -> [u8; BIP39_SEED_LENGTH] {
+    let mut seed = [0u8; BIP39_SEED_LENGTH];
+    let salt = format!("{}{}", BIP39_SALT_PREFIX, passphrase);
+    pbkdf2::<Hmac<Sha512>>(
+        mnemonic.to_string().as_bytes(),
+        salt.as_bytes(),
+        BIP39_PBKDF2_ROUNDS,
+        &mut seed,
+    );
+    seed
+}
+
+/// A derivation path, i.e., the list of hardened child indices leading from the master key to a
+/// derived key. Indices are stored without the hardened bit.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Constructs a path from unhardened indices; each must be below `HARDENED_OFFSET`.
+    pub fn new(indices: Vec<u32>) -> Result<Self> {
+        if let Some(index) = indices.iter().find(|index| **index >= HARDENED_OFFSET) {
+            return Err(WalletError::LibraWalletGeneric(format!(
+                "Child index {} is out of range",
+                index
+            ))
+            .into());
+        }
+        Ok(Self(indices))
+    }
+
+    /// The Libra derivation path `m/44'/637'/{account}'/0'/{index}'`.
+    pub fn libra(account: u32, index: u32) -> Result<Self> {
+        Self::new(vec![BIP44_PURPOSE, LIBRA_COIN_TYPE, account, 0, index])
+    }
+
+    /// Unhardened indices of this path.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = anyhow::Error;
+
+    /// Parses paths of the form `m/44'/637'/0'`. Both `'` and `H` mark hardened indices; since
+    /// SLIP-0010 only defines hardened derivation for ed25519 every index must carry one.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(
+                WalletError::LibraWalletGeneric(format!("Path {} must start with m", s)).into(),
+            );
+        }
+        let indices = components
+            .map(|component| -> Result<u32> {
+                if !component.ends_with('\'') && !component.ends_with('H') {
+                    return Err(WalletError::LibraWalletGeneric(format!(
+                        "Index {} is not hardened",
+                        component
+                    ))
+                    .into());
+                }
+                component[..component.len() - 1]
+                    .parse::<u32>()
+                    .map_err(|_| {
+                        WalletError::LibraWalletGeneric(format!("Invalid index {}", component))
+                            .into()
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(indices)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            write!(f, "/{}'", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// An extended ed25519 private key: the 32 bytes of key material together with the chain code
+/// needed to derive its children. Both are zeroed when the key is dropped.
+pub struct Slip10Key {
+    private_key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+impl Slip10Key {
+    /// Derives the master key from a seed (usually [`bip39_seed`]).
+    pub fn master(seed: &[u8]) -> Result<Self> {
+        Self::from_hmac(ED25519_CURVE_SEED, &[seed])
+    }
+
+    /// Derives the key at `path` from a seed.
+    pub fn from_seed(seed: &[u8], path: &DerivationPath) -> Result<Self> {
+        path.indices()
+            .iter()
+            .try_fold(Self::master(seed)?, |key, index| key.child(*index))
+    }
+
+    /// Derives the key at `path` from a mnemonic and passphrase.
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Self> {
+        let mut seed = bip39_seed(mnemonic, passphrase);
+        let key = Self::from_seed(&seed, path);
+        seed.zeroize();
+        key
+    }
+
+    /// Derives the hardened child at `index`, which must not carry the hardened bit.
+    pub fn child(&self, index: u32) -> Result<Self> {
+        if index >= HARDENED_OFFSET {
+            return Err(WalletError::LibraWalletGeneric(format!(
+                "Child index {} is out of range",
+                index
+            ))
+            .into());
+        }
+        let index = (index | HARDENED_OFFSET).to_be_bytes();
+        Self::from_hmac(
+            &self.chain_code,
+            &[&[0u8][..], &self.private_key[..], &index[..]],
+        )
+    }
+
+    fn from_hmac(key: &[u8], data: &[&[u8]]) -> Result<Self> {
+        let mut mac = Hmac::<Sha512>::new_varkey(key)
+            .map_err(|_| WalletError::LibraWalletGeneric("Invalid HMAC key".to_string()))?;
+        for chunk in data {
+            mac.update(chunk);
+        }
+        let mut output = mac.finalize().into_bytes();
+
+        let mut key = Self {
+            private_key: [0u8; 32],
+            chain_code: [0u8; 32],
+        };
+        key.private_key.copy_from_slice(&output[..32]);
+        key.chain_code.copy_from_slice(&output[32..]);
+        output.as_mut_slice().zeroize();
+        Ok(key)
+    }
+
+    /// Chain code of this key.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// The ed25519 private key.
+    pub fn private_key(&self) -> Ed25519PrivateKey {
+        Ed25519PrivateKey::try_from(&self.private_key[..])
+            .expect("Any 32 bytes are a valid ed25519 private key")
+    }
+
+    /// The ed25519 public key.
+    pub fn public_key(&self) -> Ed25519PublicKey {
+        (&self.private_key()).into()
+    }
+
+    /// The authentication key for this key's public key.
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AccountPublicKey::Ed25519(self.public_key()).authentication_key()
+    }
+
+    /// The account address for this key's public key.
+    pub fn address(&self) -> AccountAddress {
+        AccountPublicKey::Ed25519(self.public_key()).derived_address()
+    }
+}
+
+impl Drop for Slip10Key {
+    fn drop(&mut self) {
+        self.private_key.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_derivation_path() {
+    let path = DerivationPath::libra(1, 2).unwrap();
+    assert_eq!(path.to_string(), "m/44'/637'/1'/0'/2'");
+    assert_eq!(path, "m/44'/637'/1'/0'/2'".parse().unwrap());
+    assert_eq!(path, "m/44H/637H/1H/0H/2H".parse().unwrap());
+    assert_eq!(DerivationPath::default(), "m".parse().unwrap());
+
+    assert!("44'/637'".parse::<DerivationPath>().is_err());
+    assert!("m/44'/637".parse::<DerivationPath>().is_err());
+    assert!("m/44'/x'".parse::<DerivationPath>().is_err());
+    assert!("m/2147483648'".parse::<DerivationPath>().is_err());
+}
+
+#[cfg(test)]
+#[test]
+fn test_bip39_seed() {
+    // Test vector from the reference BIP39 implementation.
+    let mnemonic = Mnemonic::from("legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will").unwrap();
+    assert_eq!(
+        hex::encode(&bip39_seed(&mnemonic, "TREZOR")[..]),
+        "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607"
+    );
+}
+
+#[cfg(test)]
+fn check_slip10_vector(seed: &str, expected: &[(&str, &str, &str, &str)]) {
+    let seed = hex::decode(seed).unwrap();
+    for (path, chain_code, private_key, public_key) in expected {
+        let key = Slip10Key::from_seed(&seed, &path.parse().unwrap()).unwrap();
+        assert_eq!(hex::encode(key.chain_code()), *chain_code, "{}", path);
+        assert_eq!(
+            hex::encode(&key.private_key().to_bytes()[..]),
+            *private_key,
+            "{}",
+            path
+        );
+        assert_eq!(
+            hex::encode(&key.public_key().to_bytes()[..]),
+            *public_key,
+            "{}",
+            path
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_slip10_vector_1() {
+    // Test vector 1 for ed25519 from SLIP-0010. Public keys are listed without the 0x00 prefix
+    // SLIP-0010 puts in front of them.
+    check_slip10_vector(
+        "000102030405060708090a0b0c0d0e0f",
+        &[
+            (
+                "m",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            ),
+            (
+                "m/0H",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            ),
+            (
+                "m/0H/1H",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            ),
+            (
+                "m/0H/1H/2H",
+                "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+            ),
+            (
+                "m/0H/1H/2H/2H",
+                "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+                "8abae2d66361c879b900d204ad2cc4984fa2aa344dd7ddc46007329ac76c429c",
+            ),
+            (
+                "m/0H/1H/2H/2H/1000000000H",
+                "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+                "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a",
+            ),
+        ],
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_slip10_vector_2() {
+    // Test vector 2 for ed25519 from SLIP-0010.
+    check_slip10_vector(
+        "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542",
+        &[
+        (
+            "m",
+            "ef70a74db9c3a5af931b5fe73ed8e1a53464133654fd55e7a66f8570b8e33c3b",
+            "171cb88b1b3c1db25add599712e36245d75bc65a1a5c9e18d76f9f2b1eab4012",
+            "8fe9693f8fa62a4305a140b9764c5ee01e455963744fe18204b4fb948249308a",
+        ),
+        (
+            "m/0H",
+            "0b78a3226f915c082bf118f83618a618ab6dec793752624cbeb622acb562862d",
+            "1559eb2bbec5790b0c65d8693e4d0875b1747f4970ae8b650486ed7470845635",
+            "86fab68dcb57aa196c77c5f264f215a112c22a912c10d123b0d03c3c28ef1037",
+        ),
+        (
+            "m/0H/2147483647H",
+            "138f0b2551bcafeca6ff2aa88ba8ed0ed8de070841f0c4ef0165df8181eaad7f",
+            "ea4f5bfe8694d8bb74b7b59404632fd5968b774ed545e810de9c32a4fb4192f4",
+            "5ba3b9ac6e90e83effcd25ac4e58a1365a9e35a3d3ae5eb07b9e4d90bcf7506d",
+        ),
+        (
+            "m/0H/2147483647H/1H",
+            "73bd9fff1cfbde33a1b846c27085f711c0fe2d66fd32e139d3ebc28e5a4a6b90",
+            "3757c7577170179c7868353ada796c839135b3d30554bbb74a4b1e4a5a58505c",
+            "2e66aa57069c86cc18249aecf5cb5a9cebbfd6fadeab056254763874a9352b45",
+        ),
+        (
+            "m/0H/2147483647H/1H/2147483646H",
+            "0902fe8a29f9140480a00ef244bd183e8a13288e4412d8389d140aac1794825a",
+            "5837736c89570de861ebc173b1086da4f505d4adb387c6a1b1342d5e4ac9ec72",
+            "e33c0f7d81d843c572275f287498e8d408654fdf0d1e065b84e2e6f157aab09b",
+        ),
+        (
+            "m/0H/2147483647H/1H/2147483646H/2H",
+            "5d70af781f3a37b829f0d060924d5e960bdc02e85423494afc0b1a41bbe196d4",
+            "551d333177df541ad876a60ea71f00447931c0a9da16f227c11ea080d7391b8d",
+            "47150c75db263559a70d5778bf36abbab30fb061ad69f69ece61a72b0cfa4fc0",
+        ),
+    ],
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_libra_path_derivation() {
+    let mnemonic = Mnemonic::from("legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will").unwrap();
+    let key =
+        Slip10Key::from_mnemonic(&mnemonic, "", &DerivationPath::libra(0, 0).unwrap()).unwrap();
+
+    // Deriving step by step yields the same key.
+    let seed = bip39_seed(&mnemonic, "");
+    let mut stepwise = Slip10Key::master(&seed).unwrap();
+    for index in &[BIP44_PURPOSE, LIBRA_COIN_TYPE, 0, 0, 0] {
+        stepwise = stepwise.child(*index).unwrap();
+    }
+    assert_eq!(key.public_key(), stepwise.public_key());
+    assert_eq!(key.address(), stepwise.address());
+
+    // Different indices and passphrases yield different keys.
+    let other =
+        Slip10Key::from_mnemonic(&mnemonic, "", &DerivationPath::libra(0, 1).unwrap()).unwrap();
+    assert_ne!(key.public_key(), other.public_key());
+    let other = Slip10Key::from_mnemonic(&mnemonic, "LIBRA", &DerivationPath::libra(0, 0).unwrap())
+        .unwrap();
+    assert_ne!(key.public_key(), other.public_key());
+
+    assert!(stepwise.child(HARDENED_OFFSET).is_err());
+}
@@ -12,22 +12,30 @@
 //! https://github.com/rust-bitcoin/rust-wallet/blob/master/wallet/src/walletlibrary.rs
 
 use crate::{
+    currency::CurrencyFlow,
     error::WalletError,
     io_utils,
     key_factory::{ChildNumber, KeyFactory, Seed},
     mnemonic::Mnemonic,
+    slip10::{DerivationPath, Slip10Key},
 };
 use anyhow::Result;
 use libra_crypto::hash::CryptoHash;
 use libra_types::{
     account_address::AccountAddress,
+    account_state::AccountState,
     transaction::{
-        authenticator::AuthenticationKey, helpers::TransactionSigner, RawTransaction,
-        SignedTransaction,
+        authenticator::AuthenticationKey,
+        helpers::{create_user_txn, TransactionSigner},
+        RawTransaction, Script, SignedTransaction, TransactionPayload,
     },
 };
+use move_core_types::identifier::{IdentStr, Identifier};
 use rand::{rngs::OsRng, Rng};
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
 /// WalletLibrary contains all the information needed to recreate a particular wallet
 pub struct WalletLibrary {
@@ -35,6 +43,7 @@ pub struct WalletLibrary {
     key_factory: KeyFactory,
     addr_map: HashMap<AccountAddress, ChildNumber>,
     key_leaf: ChildNumber,
+    balances: HashMap<AccountAddress, BTreeMap<Identifier, u64>>,
 }
 
 impl WalletLibrary {
@@ -56,6 +65,7 @@ impl WalletLibrary {
             key_factory: KeyFactory::new(&seed).unwrap(),
             addr_map: HashMap::new(),
             key_leaf: ChildNumber(0),
+            balances: HashMap::new(),
         }
     }
 
@@ -65,6 +75,16 @@ impl WalletLibrary {
         self.mnemonic.to_string()
     }
 
+    /// Derives the SLIP-0010 key at the Libra path `m/44'/637'/{account}'/0'/{index}'` from the
+    /// wallet Mnemonic. These keys are independent of the ones handed out by `new_address`.
+    pub fn slip10_key(&self, passphrase: &str, account: u32, index: u32) -> Result<Slip10Key> {
+        Slip10Key::from_mnemonic(
+            &self.mnemonic,
+            passphrase,
+            &DerivationPath::libra(account, index)?,
+        )
+    }
+
     /// Function that writes the wallet Mnemonic to file
     /// NOTE: This is not secure, and in general the Mnemonic would need to be decrypted before it
     /// can be written to file; otherwise the encrypted Mnemonic should be written to file
@@ -155,6 +175,83 @@ impl WalletLibrary {
         Ok(ret)
     }
 
+    /// Records the balances held by an account of this wallet, as found within its AccountState,
+    /// for each of the given currencies. Currencies not registered by the account are omitted and
+    /// previously recorded balances for the account are replaced.
+    pub fn update_balances(
+        &mut self,
+        address: AccountAddress,
+        account_state: &AccountState,
+        currency_codes: &[Identifier],
+    ) -> Result<()> {
+        if !self.addr_map.contains_key(&address) {
+            return Err(WalletError::LibraWalletGeneric(format!(
+                "Address {} is not in this wallet",
+                address
+            ))
+            .into());
+        }
+        let balances = account_state
+            .get_balance_resources(currency_codes)?
+            .into_iter()
+            .map(|(currency_code, balance)| (currency_code, balance.coin()))
+            .collect();
+        self.balances.insert(address, balances);
+        Ok(())
+    }
+
+    /// Returns the most recently recorded balances of an account, keyed by currency code
+    pub fn balances(&self, address: &AccountAddress) -> Option<&BTreeMap<Identifier, u64>> {
+        self.balances.get(address)
+    }
+
+    /// Returns the most recently recorded balance of a single currency held by an account
+    pub fn balance(&self, address: &AccountAddress, currency_code: &IdentStr) -> Option<u64> {
+        self.balances
+            .get(address)
+            .and_then(|balances| balances.get(currency_code))
+            .copied()
+    }
+
+    /// Starts a CurrencyFlow for an account based upon the currencies it was last known to hold.
+    /// Balances must have been recorded via `update_balances` beforehand.
+    pub fn currency_flow(&self, address: &AccountAddress) -> Result<CurrencyFlow> {
+        let balances = self.balances.get(address).ok_or_else(|| {
+            WalletError::LibraWalletGeneric(format!("No balances recorded for {}", address))
+        })?;
+        Ok(CurrencyFlow::new(balances.keys().cloned()))
+    }
+
+    /// Signs each script as a separate transaction sent by `sender`, starting at
+    /// `sequence_number`, so that they execute in order, e.g., the scripts of a CurrencyFlow.
+    pub fn sign_scripts(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+        scripts: Vec<Script>,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        gas_currency_code: &str,
+        txn_expiration: i64,
+    ) -> Result<Vec<SignedTransaction>> {
+        scripts
+            .into_iter()
+            .zip(sequence_number..)
+            .map(|(script, sequence_number)| {
+                create_user_txn(
+                    self,
+                    TransactionPayload::Script(script),
+                    sender,
+                    sequence_number,
+                    max_gas_amount,
+                    gas_unit_price,
+                    gas_currency_code.to_string(),
+                    txn_expiration,
+                )
+            })
+            .collect()
+    }
+
     /// Simple public function that allows to sign a Libra RawTransaction with the PrivateKey
     /// associated to a particular AccountAddress. If the PrivateKey associated to an
     /// AccountAddress is not contained in the addr_map, then this function will return an Error
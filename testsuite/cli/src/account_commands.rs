@@ -19,6 +19,7 @@ impl Command for AccountCommand {
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
         let commands: Vec<Box<dyn Command>> = vec![
             Box::new(AccountCommandCreate {}),
+            Box::new(AccountCommandDerive {}),
             Box::new(AccountCommandListAccounts {}),
             Box::new(AccountCommandRecoverWallet {}),
             Box::new(AccountCommandWriteRecovery {}),
@@ -44,15 +45,44 @@ impl Command for AccountCommandCreate {
         println!(">> Creating/retrieving next account from wallet");
         match client.create_next_account(true) {
             Ok(account_data) => println!(
-                "Created/retrieved account #{} address {}",
+                "Created/retrieved account #{} address {} ({})",
                 account_data.index,
-                hex::encode(account_data.address)
+                hex::encode(account_data.address),
+                account_data.address.to_bech32()
             ),
             Err(e) => report_error("Error creating account", e),
         }
     }
 }
 
+/// Sub command to add the account of a SLIP-0010 key derived from the wallet mnemonic.
+pub struct AccountCommandDerive {}
+
+impl Command for AccountCommandDerive {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["derive", "d"]
+    }
+    fn get_params_help(&self) -> &'static str {
+        "<account> <index> [passphrase]"
+    }
+    fn get_description(&self) -> &'static str {
+        "Add the account of the key at m/44'/637'/<account>'/0'/<index>' derived from the wallet \
+         mnemonic with SLIP-0010. Returns reference ID to use in other operations"
+    }
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        println!(">> Deriving account from wallet");
+        match client.derive_slip10_account(&params) {
+            Ok(account_data) => println!(
+                "Derived account #{} address {} ({})",
+                account_data.index,
+                hex::encode(account_data.address),
+                account_data.address.to_bech32()
+            ),
+            Err(e) => report_error("Error deriving account", e),
+        }
+    }
+}
+
 /// Sub command to recover wallet from the file specified.
 pub struct AccountCommandRecoverWallet {}
 
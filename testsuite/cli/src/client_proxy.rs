@@ -7,6 +7,7 @@ use crate::{
     AccountData, AccountStatus,
 };
 use anyhow::{bail, ensure, format_err, Error, Result};
+use bytecode_source_map::utils::compress_source_map;
 use compiled_stdlib::{transaction_scripts::StdlibScript, StdLibOptions};
 use libra_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
@@ -17,6 +18,7 @@ use libra_crypto::{
 use libra_json_rpc_client::views::{AccountView, BlockMetadata, EventView, TransactionView};
 use libra_logger::prelude::*;
 use libra_network_address::{NetworkAddress, RawNetworkAddress};
+use libra_state_view::StateView;
 use libra_temppath::TempPath;
 use libra_types::{
     access_path::AccessPath,
@@ -43,9 +45,10 @@ use num_traits::{
     identities::Zero,
 };
 use reqwest::Url;
-use resource_viewer::{AnnotatedAccountStateBlob, MoveValueAnnotator, NullStateView};
+use resource_viewer::{AnnotatedAccountStateBlob, MoveValueAnnotator};
 use rust_decimal::Decimal;
 use std::{
+    cell::RefCell,
     collections::HashMap,
     convert::TryFrom,
     fmt, fs,
@@ -56,6 +59,10 @@ use std::{
     thread, time,
 };
 use transaction_builder::encode_set_validator_config_script;
+use vm::{
+    access::ScriptAccess,
+    file_format::{CompiledScript, SignatureToken},
+};
 
 const CLIENT_WALLET_MNEMONIC_FILE: &str = "client.mnemonic";
 const GAS_UNIT_PRICE: u64 = 0;
@@ -115,6 +122,8 @@ pub struct ClientProxy {
     sync_on_wallet_recovery: bool,
     /// temp files (alive for duration of program)
     temp_files: Vec<PathBuf>,
+    /// Directory of the Move stdlib sources that scripts are compiled against by `dev run`.
+    stdlib_dir: Option<PathBuf>,
     // invariant self.address_to_ref_id.values().iter().all(|i| i < self.accounts.len())
 }
 
@@ -128,10 +137,11 @@ impl ClientProxy {
         faucet_server: Option<String>,
         mnemonic_file: Option<String>,
         waypoint: Waypoint,
+        trusted_state_file: Option<PathBuf>,
     ) -> Result<Self> {
         // fail fast if url is not valid
         let url = Url::parse(url)?;
-        let mut client = LibraClient::new(url.clone(), waypoint)?;
+        let mut client = LibraClient::new(url.clone(), waypoint, trusted_state_file)?;
 
         let accounts = vec![];
 
@@ -187,9 +197,15 @@ impl ClientProxy {
             wallet: Self::get_libra_wallet(mnemonic_file)?,
             sync_on_wallet_recovery,
             temp_files: vec![],
+            stdlib_dir: None,
         })
     }
 
+    /// Set the directory of the Move stdlib sources that `dev run` compiles scripts against.
+    pub fn set_stdlib_dir(&mut self, stdlib_dir: PathBuf) {
+        self.stdlib_dir = Some(stdlib_dir);
+    }
+
     fn get_account_ref_id(&self, sender_account_address: &AccountAddress) -> Result<usize> {
         Ok(*self
             .address_to_ref_id
@@ -217,6 +233,48 @@ impl ClientProxy {
         Ok(self.insert_account_data(account_data))
     }
 
+    /// Adds the account of the SLIP-0010 key at the Libra path
+    /// `m/44'/637'/{account}'/0'/{index}'`, derived from the wallet mnemonic and an optional BIP39
+    /// passphrase. The account signs with the derived key, so it can be restored by any wallet
+    /// implementing the same derivation.
+    pub fn derive_slip10_account(
+        &mut self,
+        space_delim_strings: &[&str],
+    ) -> Result<AddressAndIndex> {
+        ensure!(
+            space_delim_strings.len() == 3 || space_delim_strings.len() == 4,
+            "Invalid number of arguments for deriving an account"
+        );
+        let account = space_delim_strings[1].parse::<u32>().map_err(|error| {
+            format_parse_data_error(
+                "account",
+                InputType::UnsignedInt,
+                space_delim_strings[1],
+                error,
+            )
+        })?;
+        let index = space_delim_strings[2].parse::<u32>().map_err(|error| {
+            format_parse_data_error(
+                "index",
+                InputType::UnsignedInt,
+                space_delim_strings[2],
+                error,
+            )
+        })?;
+        let passphrase = space_delim_strings.get(3).copied().unwrap_or("");
+
+        let key = self.wallet.slip10_key(passphrase, account, index)?;
+        let account_data = Self::get_account_data_from_address(
+            &mut self.client,
+            key.address(),
+            true,
+            Some(KeyPair::from(key.private_key())),
+            Some(key.authentication_key().to_vec()),
+        )?;
+
+        Ok(self.insert_account_data(account_data))
+    }
+
     /// Returns the ledger info corresonding to the latest epoch change
     /// (could further be used for e.g., generating a waypoint)
     pub fn latest_epoch_change_li(&self) -> Option<&LedgerInfoWithSignatures> {
@@ -870,7 +928,17 @@ impl ClientProxy {
             space_delim_strings[0]
         );
         let (address, _) = self.get_account_address_from_parameter(space_delim_strings[1])?;
-        let file_path = space_delim_strings[2];
+        self.compile_move_sources(address, space_delim_strings[2], &space_delim_strings[3..])
+    }
+
+    /// Compile the Move source at `file_path` with `address` as the sender, returning the paths
+    /// of the generated bytecode files.
+    fn compile_move_sources(
+        &mut self,
+        address: AccountAddress,
+        file_path: &str,
+        dependencies: &[&str],
+    ) -> Result<Vec<String>> {
         let mut tmp_output_dir = TempPath::new();
         tmp_output_dir.persist();
         tmp_output_dir
@@ -885,7 +953,7 @@ impl ClientProxy {
             address,
             tmp_output_path.display(),
         );
-        for dep in &space_delim_strings[3..] {
+        for dep in dependencies {
             args.push_str(&format!(" -d {}", dep));
         }
 
@@ -953,7 +1021,7 @@ impl ClientProxy {
         self.wait_for_transaction(sender_address, sequence_number + 1)
     }
 
-    /// Publish Move module
+    /// Publish Move module, along with its source map if a source map file is given
     pub fn publish_module(&mut self, space_delim_strings: &[&str]) -> Result<()> {
         ensure!(
             space_delim_strings[0] == "publish",
@@ -961,10 +1029,14 @@ impl ClientProxy {
             space_delim_strings[0]
         );
         let module_bytes = fs::read(space_delim_strings[2])?;
-        self.submit_program(
-            space_delim_strings,
-            TransactionPayload::Module(Module::new(module_bytes)),
-        )
+        let module = match space_delim_strings.get(3) {
+            Some(source_map_path) => Module::with_source_map(
+                module_bytes,
+                compress_source_map(&fs::read(source_map_path)?)?,
+            ),
+            None => Module::new(module_bytes),
+        };
+        self.submit_program(space_delim_strings, TransactionPayload::Module(module))
     }
 
     /// Execute custom script
@@ -986,6 +1058,58 @@ impl ClientProxy {
         )
     }
 
+    /// Compile a Move script against the stdlib, check the supplied arguments against the
+    /// signature of its `main` function and submit it.
+    pub fn compile_and_execute_script(&mut self, space_delim_strings: &[&str]) -> Result<()> {
+        ensure!(
+            space_delim_strings[0] == "run",
+            "inconsistent command '{}' for compile_and_execute_script",
+            space_delim_strings[0]
+        );
+        ensure!(
+            space_delim_strings.len() >= 3,
+            "Invalid number of arguments to run script"
+        );
+        let (address, _) = self.get_account_address_from_parameter(space_delim_strings[1])?;
+
+        // Everything before an optional `--` separator is a dependency, everything after it a
+        // script argument. Without a separator all trailing parameters are script arguments.
+        let rest = &space_delim_strings[3..];
+        let (extra_deps, raw_args) = match rest.iter().position(|s| *s == "--") {
+            Some(idx) => (&rest[..idx], &rest[idx + 1..]),
+            None => (&rest[..0], rest),
+        };
+        let stdlib_dir = self.stdlib_dir.as_ref().ok_or_else(|| {
+            format_err!("No stdlib source directory configured, start the client with --stdlib-dir")
+        })?;
+        let mut dependencies = vec![stdlib_dir
+            .to_str()
+            .ok_or_else(|| format_err!("invalid stdlib path {:?}", stdlib_dir))?];
+        dependencies.extend_from_slice(extra_deps);
+
+        let mut compiled_paths =
+            self.compile_move_sources(address, space_delim_strings[2], &dependencies)?;
+        ensure!(
+            compiled_paths.len() == 1,
+            "expected a single compiled script, found {}",
+            compiled_paths.len()
+        );
+        let script_bytes = fs::read(compiled_paths.pop().unwrap())?;
+        let script = CompiledScript::deserialize(&script_bytes)
+            .map_err(|e| format_err!("compiled output is not a script: {:?}", e))?;
+        ensure!(
+            script.as_inner().type_parameters.is_empty(),
+            "generic scripts are not supported by run, compile and execute it instead"
+        );
+        let parameters = &script.signature_at(script.as_inner().parameters).0;
+        let arguments = parse_script_arguments(parameters, raw_args)?;
+
+        self.submit_program(
+            space_delim_strings,
+            TransactionPayload::Script(Script::new(script_bytes, vec![], arguments)),
+        )
+    }
+
     /// Get the latest account state from validator.
     pub fn get_latest_account_state(
         &mut self,
@@ -1232,10 +1356,11 @@ impl ClientProxy {
     ) -> Result<(Option<AnnotatedAccountStateBlob>, Version)> {
         let (blob, ver) = self.client.get_account_state_blob(address)?;
         if let Some(account_blob) = blob {
-            let state_view = NullStateView::default();
+            let account_state = AccountState::try_from(&account_blob)?;
+            // The modules declaring the types of the resources are fetched as they are needed
+            let state_view = ClientStateView::new(&mut self.client);
             let annotator = MoveValueAnnotator::new(&state_view);
-            let annotate_blob =
-                annotator.view_account_state(&AccountState::try_from(&account_blob)?)?;
+            let annotate_blob = annotator.view_account_state(&account_state)?;
             Ok((Some(annotate_blob), ver))
         } else {
             Ok((None, ver))
@@ -1247,7 +1372,7 @@ impl ClientProxy {
         &mut self,
         address: AccountAddress,
     ) -> Result<(Option<AccountView>, Version)> {
-        let account_state = self.client.get_account_state(address, true)?;
+        let account_state = self.client.get_account_state(address)?;
         if self.address_to_ref_id.contains_key(&address) {
             let account_ref_id = self
                 .address_to_ref_id
@@ -1284,7 +1409,7 @@ impl ClientProxy {
         authentication_key_opt: Option<Vec<u8>>,
     ) -> Result<AccountData> {
         let (sequence_number, authentication_key, status) = if sync_with_validator {
-            match client.get_account_state(address, true) {
+            match client.get_account_state(address) {
                 Ok(resp) => match resp.0 {
                     Some(account_view) => (
                         account_view.sequence_number,
@@ -1335,6 +1460,10 @@ impl ClientProxy {
     }
 
     fn address_from_strings(data: &str) -> Result<AccountAddress> {
+        if AccountAddress::is_bech32(data) {
+            return AccountAddress::from_bech32(data)
+                .map_err(|error| format_err!("The address {} is invalid, error: {}", data, error));
+        }
         let account_vec: Vec<u8> = hex::decode(data.parse::<String>()?)?;
         ensure!(
             account_vec.len() == AccountAddress::LENGTH,
@@ -1569,6 +1698,43 @@ impl ClientProxy {
     }
 }
 
+/// The latest state of the accounts, fetched from the validator when they are first read
+struct ClientStateView<'a> {
+    client: RefCell<&'a mut LibraClient>,
+    accounts: RefCell<HashMap<AccountAddress, Option<AccountState>>>,
+}
+
+impl<'a> ClientStateView<'a> {
+    fn new(client: &'a mut LibraClient) -> Self {
+        Self {
+            client: RefCell::new(client),
+            accounts: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<'a> StateView for ClientStateView<'a> {
+    fn get(&self, access_path: &AccessPath) -> Result<Option<Vec<u8>>> {
+        let address = access_path.address;
+        if !self.accounts.borrow().contains_key(&address) {
+            let (blob, _) = self.client.borrow_mut().get_account_state_blob(address)?;
+            let state = blob.as_ref().map(AccountState::try_from).transpose()?;
+            self.accounts.borrow_mut().insert(address, state);
+        }
+        Ok(self.accounts.borrow()[&address]
+            .as_ref()
+            .and_then(|state| state.get(&access_path.path).cloned()))
+    }
+
+    fn multi_get(&self, access_paths: &[AccessPath]) -> Result<Vec<Option<Vec<u8>>>> {
+        access_paths.iter().map(|path| self.get(path)).collect()
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+}
+
 fn parse_transaction_argument_for_client(s: &str) -> Result<TransactionArgument> {
     if is_address(s) {
         let account_address = ClientProxy::address_from_strings(s)?;
@@ -1577,6 +1743,54 @@ fn parse_transaction_argument_for_client(s: &str) -> Result<TransactionArgument>
     parse_transaction_argument(s)
 }
 
+/// Parse script arguments according to the parameter types of the script's `main` function.
+///
+/// A leading `&signer` parameter is filled in by the VM and does not take an argument.
+fn parse_script_arguments(
+    parameters: &[SignatureToken],
+    args: &[&str],
+) -> Result<Vec<TransactionArgument>> {
+    let parameters = match parameters.first() {
+        Some(SignatureToken::Reference(inner)) if **inner == SignatureToken::Signer => {
+            &parameters[1..]
+        }
+        _ => parameters,
+    };
+    ensure!(
+        parameters.len() == args.len(),
+        "script expects {} argument(s) but {} were given",
+        parameters.len(),
+        args.len()
+    );
+    parameters
+        .iter()
+        .zip(args)
+        .enumerate()
+        .map(|(idx, (ty, arg))| {
+            parse_script_argument(ty, arg)
+                .map_err(|e| format_err!("invalid argument #{} '{}': {}", idx, arg, e))
+        })
+        .collect()
+}
+
+fn parse_script_argument(ty: &SignatureToken, arg: &str) -> Result<TransactionArgument> {
+    use SignatureToken as S;
+    Ok(match ty {
+        S::Bool => TransactionArgument::Bool(parse_bool(arg)?),
+        S::U8 => TransactionArgument::U8(arg.trim_end_matches("u8").parse()?),
+        S::U64 => TransactionArgument::U64(arg.trim_end_matches("u64").parse()?),
+        S::U128 => TransactionArgument::U128(arg.trim_end_matches("u128").parse()?),
+        S::Address => TransactionArgument::Address(ClientProxy::address_from_strings(
+            arg.trim_start_matches("0x"),
+        )?),
+        S::Vector(inner) if **inner == S::U8 => match parse_transaction_argument(arg) {
+            Ok(arg @ TransactionArgument::U8Vector(_)) => arg,
+            _ => TransactionArgument::U8Vector(hex::decode(arg.trim_start_matches("0x"))?),
+        },
+        _ => bail!("parameter type {:?} cannot be passed to a script", ty),
+    })
+}
+
 fn format_parse_data_error<T: std::fmt::Debug>(
     field: &str,
     input_type: InputType,
@@ -1608,11 +1822,15 @@ impl fmt::Display for AccountEntry {
 
 #[cfg(test)]
 mod tests {
-    use crate::client_proxy::{parse_bool, AddressAndIndex, ClientProxy};
+    use crate::client_proxy::{parse_bool, parse_script_arguments, AddressAndIndex, ClientProxy};
     use libra_temppath::TempPath;
-    use libra_types::{ledger_info::LedgerInfo, on_chain_config::ValidatorSet, waypoint::Waypoint};
+    use libra_types::{
+        account_address::AccountAddress, ledger_info::LedgerInfo, on_chain_config::ValidatorSet,
+        transaction::TransactionArgument, waypoint::Waypoint,
+    };
     use libra_wallet::io_utils;
     use proptest::prelude::*;
+    use vm::file_format::SignatureToken;
 
     fn generate_accounts_from_wallet(count: usize) -> (ClientProxy, Vec<AddressAndIndex>) {
         let mut accounts = Vec::new();
@@ -1633,6 +1851,7 @@ mod tests {
             None,
             Some(mnemonic_path),
             waypoint,
+            None,
         )
         .unwrap();
         for _ in 0..count {
@@ -1660,6 +1879,43 @@ mod tests {
         assert!(parse_bool("ad1f").is_err());
     }
 
+    #[test]
+    fn test_parse_script_arguments() {
+        use SignatureToken as S;
+        let params = vec![
+            S::Reference(Box::new(S::Signer)),
+            S::Address,
+            S::U8,
+            S::U64,
+            S::Vector(Box::new(S::U8)),
+            S::Bool,
+        ];
+        let address = AccountAddress::random();
+        let address_str = address.to_string();
+        let args =
+            parse_script_arguments(&params, &[&address_str, "10", "10u64", "b\"0102\"", "true"])
+                .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                TransactionArgument::Address(address),
+                TransactionArgument::U8(10),
+                TransactionArgument::U64(10),
+                TransactionArgument::U8Vector(vec![1, 2]),
+                TransactionArgument::Bool(true),
+            ]
+        );
+
+        // Wrong arity.
+        assert!(parse_script_arguments(&params, &[&address_str]).is_err());
+        // Out of range for the declared type.
+        assert!(parse_script_arguments(&params[2..3], &["256"]).is_err());
+        // Not an address.
+        assert!(parse_script_arguments(&params[1..2], &["10"]).is_err());
+        // Structs cannot be passed in.
+        assert!(parse_script_arguments(&[S::Signer], &["0"]).is_err());
+    }
+
     #[test]
     fn test_micro_libra_conversion() {
         assert!(ClientProxy::convert_to_scaled_representation("", 1_000_000, 1_000_000).is_err());
@@ -26,9 +26,11 @@ pub fn debug_format_cmd(cmd: &str) -> bool {
     cmd.ends_with('?')
 }
 
-/// Check whether the input string is a valid libra address.
+/// Check whether the input string is a libra address, in hex or bech32. The checksum of a bech32
+/// address is only verified when it is parsed, to report mistyped addresses.
 pub fn is_address(data: &str) -> bool {
-    hex::decode(data).map_or(false, |vec| vec.len() == AccountAddress::LENGTH)
+    AccountAddress::is_bech32(data)
+        || hex::decode(data).map_or(false, |vec| vec.len() == AccountAddress::LENGTH)
 }
 
 /// Check whether the input string is a valid libra authentication key.
@@ -24,6 +24,7 @@ impl Command for DevCommand {
             Box::new(DevCommandCompile {}),
             Box::new(DevCommandPublish {}),
             Box::new(DevCommandExecute {}),
+            Box::new(DevCommandRun {}),
             Box::new(DevCommandUpgradeStdlib {}),
             Box::new(DevCommandAddValidator {}),
             Box::new(DevCommandRemoveValidator {}),
@@ -74,15 +75,15 @@ impl Command for DevCommandPublish {
     }
 
     fn get_params_help(&self) -> &'static str {
-        "<sender_account_address>|<sender_account_ref_id> <compiled_module_path>"
+        "<sender_account_address>|<sender_account_ref_id> <compiled_module_path> [source_map_path]"
     }
 
     fn get_description(&self) -> &'static str {
-        "Publish Move module on-chain"
+        "Publish Move module on-chain, optionally along with its compressed source map"
     }
 
     fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
-        if params.len() != 3 {
+        if params.len() != 3 && params.len() != 4 {
             println!("Invalid number of arguments to publish module");
             return;
         }
@@ -121,6 +122,35 @@ impl Command for DevCommandExecute {
     }
 }
 
+/// Sub command to compile, type-check and execute a Move script in one step
+pub struct DevCommandRun {}
+
+impl Command for DevCommandRun {
+    fn get_aliases(&self) -> Vec<&'static str> {
+        vec!["run", "r"]
+    }
+
+    fn get_params_help(&self) -> &'static str {
+        "<sender_account_address>|<sender_account_ref_id> <script_file_path> [dependency_source_files... --] [parameters]"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "Compile a Move script against the stdlib, check its arguments and execute it"
+    }
+
+    fn execute(&self, client: &mut ClientProxy, params: &[&str]) {
+        if params.len() < 3 {
+            println!("Invalid number of arguments to run script");
+            return;
+        }
+        println!(">> Compiling and executing script");
+        match client.compile_and_execute_script(params) {
+            Ok(_) => println!("Successfully finished execution"),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
 pub struct DevCommandEnableCustomScript {}
 
 impl Command for DevCommandEnableCustomScript {
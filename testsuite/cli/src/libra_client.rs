@@ -2,13 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::AccountData;
-use anyhow::{bail, ensure, Result};
+use anyhow::{bail, ensure, format_err, Context, Result};
+use libra_crypto::hash::CryptoHash;
 use libra_json_rpc_client::{
     errors::JsonRpcError,
     get_response_from_batch,
     views::{
         AccountStateWithProofView, AccountView, BlockMetadata, BytesView, CurrencyInfoView,
-        EventView, StateProofView, TransactionView,
+        EventView, EventWithProofView, StateProofView, TransactionListWithProofView,
+        TransactionView,
     },
     JsonRpcBatch, JsonRpcClient, JsonRpcResponse, ResponseAsView,
 };
@@ -16,22 +18,38 @@ use libra_logger::prelude::*;
 use libra_types::{
     access_path::AccessPath,
     account_address::AccountAddress,
-    account_config::{ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH},
+    account_config::{
+        association_address, CurrencyInfoResource, ACCOUNT_RECEIVED_EVENT_PATH,
+        ACCOUNT_SENT_EVENT_PATH,
+    },
+    account_state::AccountState,
     account_state_blob::AccountStateBlob,
     epoch_change::EpochChangeProof,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
-    transaction::{SignedTransaction, Version},
+    on_chain_config::{config_address, RegisteredCurrencies},
+    transaction::{SignedTransaction, TransactionListWithProof, Version},
     trusted_state::{TrustedState, TrustedStateChange},
     vm_error::StatusCode,
     waypoint::Waypoint,
 };
 use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// A client connection to an AdmissionControl (AC) service. `LibraClient` also
 /// handles verifying the server's responses, retrying on non-fatal failures, and
 /// ratcheting our latest verified state, which includes the latest verified
 /// version and latest verified epoch change ledger info.
 ///
+/// Queries are answered from data verified against the latest verified state, e.g., account
+/// views are built from the proven account state blob rather than taken from the server, so
+/// that the client never displays data it could not verify.
+///
 /// ### Note
 ///
 /// `LibraClient` will reject out-of-date responses. For example, this can happen if
@@ -43,24 +61,110 @@ use reqwest::Url;
 ///    AC will be behind us and we will reject their response as stale.
 pub struct LibraClient {
     client: JsonRpcClient,
+    /// The waypoint the trusted state was verified from.
+    waypoint: Waypoint,
     /// The latest verified chain state.
     trusted_state: TrustedState,
     /// The most recent epoch change ledger info. This is `None` if we only know
     /// about our local [`Waypoint`] and have not yet ratcheted to the remote's
     /// latest state.
     latest_epoch_change_li: Option<LedgerInfoWithSignatures>,
+    /// The ledger info of the latest verified state, `None` as long as
+    /// `latest_epoch_change_li` is.
+    latest_li: Option<LedgerInfoWithSignatures>,
+    /// If set, the verified state is restored from this file, and saved to it whenever it
+    /// changes.
+    trusted_state_file: Option<PathBuf>,
+}
+
+/// The verified state saved between runs of the client, so that it neither has to verify the
+/// chain from its waypoint again, nor accepts a response older than one it has already seen.
+#[derive(Deserialize, Serialize)]
+struct PersistedTrustedState {
+    waypoint: Waypoint,
+    latest_epoch_change_li: LedgerInfoWithSignatures,
+    latest_li: LedgerInfoWithSignatures,
 }
 
 impl LibraClient {
-    /// Construct a new Client instance.
-    pub fn new(url: Url, waypoint: Waypoint) -> Result<Self> {
+    /// Construct a new Client instance, trusting the `waypoint`, or the state saved in
+    /// `trusted_state_file` if it was verified from the same waypoint.
+    pub fn new(url: Url, waypoint: Waypoint, trusted_state_file: Option<PathBuf>) -> Result<Self> {
         let initial_trusted_state = TrustedState::from(waypoint);
         let client = JsonRpcClient::new(url)?;
-        Ok(LibraClient {
+        let mut libra_client = LibraClient {
             client,
+            waypoint,
             trusted_state: initial_trusted_state,
             latest_epoch_change_li: None,
-        })
+            latest_li: None,
+            trusted_state_file,
+        };
+        if let Some(path) = libra_client.trusted_state_file.clone() {
+            if path.exists() {
+                libra_client.restore_trusted_state(&path).with_context(|| {
+                    format!(
+                        "Invalid trusted state in {:?}, remove it to start from the waypoint",
+                        path
+                    )
+                })?;
+            }
+        }
+        Ok(libra_client)
+    }
+
+    fn restore_trusted_state(&mut self, path: &Path) -> Result<()> {
+        let persisted: PersistedTrustedState = lcs::from_bytes(&fs::read(path)?)?;
+        if persisted.waypoint != self.waypoint {
+            warn!(
+                "Ignoring the trusted state in {:?}, which was verified from waypoint {}",
+                path, persisted.waypoint
+            );
+            return Ok(());
+        }
+        let epoch_state = TrustedState::try_from(persisted.latest_epoch_change_li.ledger_info())?;
+        let trusted_state = match epoch_state
+            .verify_and_ratchet(&persisted.latest_li, &EpochChangeProof::new(vec![], false))?
+        {
+            TrustedStateChange::Version { new_state } => new_state,
+            TrustedStateChange::NoChange => epoch_state,
+            TrustedStateChange::Epoch { .. } => {
+                bail!("The latest ledger info is not in the epoch of the latest epoch change")
+            }
+        };
+        info!(
+            "Restored the trusted state at version {} from {:?}",
+            trusted_state.latest_version(),
+            path
+        );
+        self.trusted_state = trusted_state;
+        self.latest_epoch_change_li = Some(persisted.latest_epoch_change_li);
+        self.latest_li = Some(persisted.latest_li);
+        Ok(())
+    }
+
+    fn persist_trusted_state(&self) -> Result<()> {
+        let path = match &self.trusted_state_file {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let (latest_epoch_change_li, latest_li) =
+            match (&self.latest_epoch_change_li, &self.latest_li) {
+                (Some(latest_epoch_change_li), Some(latest_li)) => {
+                    (latest_epoch_change_li.clone(), latest_li.clone())
+                }
+                _ => return Ok(()),
+            };
+        let bytes = lcs::to_bytes(&PersistedTrustedState {
+            waypoint: self.waypoint,
+            latest_epoch_change_li,
+            latest_li,
+        })?;
+        // Write to a temporary file first, so that the state is never left half written
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, bytes)?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to save the trusted state to {:?}", path))
     }
 
     /// Submits a transaction and bumps the sequence number for the sender, pass in `None` for
@@ -105,120 +209,184 @@ impl LibraClient {
         }
     }
 
-    /// Retrieves account state
-    /// - If `with_state_proof`, will also retrieve state proof from node and update trusted_state accordingly
+    /// Retrieves the account state, verified against the latest ledger info, which the trusted
+    /// state is ratcheted to
     pub fn get_account_state(
         &mut self,
         account: AccountAddress,
-        with_state_proof: bool,
     ) -> Result<(Option<AccountView>, Version)> {
-        let client_version = self.trusted_state.latest_version();
-        let mut batch = JsonRpcBatch::new();
-        batch.add_get_account_state_request(account);
-        if with_state_proof {
-            batch.add_get_state_proof_request(client_version);
-        }
-        let responses = self.client.execute(batch)?;
-
-        if with_state_proof {
-            let state_proof = get_response_from_batch(1, &responses)?.as_ref();
-            self.process_state_proof_response(state_proof)?;
-        }
-
-        match get_response_from_batch(0, &responses)? {
-            Ok(result) => {
-                let account_view = AccountView::optional_from_response(result.clone())?;
-                Ok((account_view, self.trusted_state.latest_version()))
+        let (blobs, li) = self.get_verified_account_state_blobs(&[account, config_address()])?;
+        let currencies = Self::registered_currencies(&blobs[1])?;
+        let account_view = match &blobs[0] {
+            Some(blob) => {
+                let account_state = AccountState::try_from(blob)?;
+                match (
+                    account_state.get_account_resource()?,
+                    account_state.get_account_role()?,
+                ) {
+                    (Some(account), Some(account_role)) => Some(AccountView::new(
+                        &account,
+                        account_state.get_balance_resources(currencies.currency_codes())?,
+                        account_role,
+                    )),
+                    _ => None,
+                }
             }
-            Err(e) => bail!(
-                "Failed to get account state for account address {} with error: {:?}",
-                account,
-                e
-            ),
-        }
+            None => None,
+        };
+        Ok((account_view, li.ledger_info().version()))
     }
 
     pub fn get_account_state_blob(
         &mut self,
         account: AccountAddress,
     ) -> Result<(Option<AccountStateBlob>, Version)> {
+        let (mut blobs, li) = self.get_verified_account_state_blobs(&[account])?;
+        Ok((blobs.remove(0), li.ledger_info().version()))
+    }
+
+    /// Retrieves the state blobs of `accounts` at the latest version, and verifies them
+    fn get_verified_account_state_blobs(
+        &mut self,
+        accounts: &[AccountAddress],
+    ) -> Result<(Vec<Option<AccountStateBlob>>, LedgerInfoWithSignatures)> {
         let mut batch = JsonRpcBatch::new();
-        batch.add_get_account_state_with_proof_request(account, None, None);
-        let responses = self.client.execute(batch)?;
-        match get_response_from_batch(0, &responses)? {
-            Ok(result) => {
-                let account_state_with_proof =
-                    AccountStateWithProofView::from_response(result.clone())?;
-                if let Some(bytes) = account_state_with_proof.blob {
-                    Ok((
-                        Some(lcs::from_bytes(&bytes.into_bytes()?)?),
-                        account_state_with_proof.version,
-                    ))
-                } else {
-                    Ok((None, account_state_with_proof.version))
-                }
-            }
-            Err(e) => bail!(
-                "Failed to get account state blob for account address {} with error: {:?}",
-                account,
-                e
-            ),
+        for account in accounts {
+            batch.add_get_account_state_with_proof_request(*account, None, None);
+        }
+        let (responses, li) = self.execute_verified(batch)?;
+
+        let mut blobs = vec![];
+        for (index, account) in accounts.iter().enumerate() {
+            let view = match get_response_from_batch(index, &responses)? {
+                Ok(result) => AccountStateWithProofView::from_response(result.clone())?,
+                Err(e) => bail!(
+                    "Failed to get account state for account address {} with error: {:?}",
+                    account,
+                    e
+                ),
+            };
+            let account_state_with_proof = view.decode()?;
+            account_state_with_proof.verify(
+                li.ledger_info(),
+                li.ledger_info().version(),
+                *account,
+            )?;
+            blobs.push(account_state_with_proof.blob);
         }
+        Ok((blobs, li))
+    }
+
+    fn registered_currencies(
+        config_blob: &Option<AccountStateBlob>,
+    ) -> Result<RegisteredCurrencies> {
+        let config_blob = config_blob
+            .as_ref()
+            .ok_or_else(|| format_err!("Missing config account"))?;
+        AccountState::try_from(config_blob)?
+            .get_config::<RegisteredCurrencies>()?
+            .ok_or_else(|| format_err!("Missing registered currencies"))
     }
 
+    /// Retrieves `limit` events of the stream of `event_key` from `start`, verified against the
+    /// latest ledger info
     pub fn get_events(
         &mut self,
         event_key: String,
         start: u64,
         limit: u64,
     ) -> Result<Vec<EventView>> {
+        let key = EventKey::try_from(&hex::decode(&event_key)?[..])?;
         let mut batch = JsonRpcBatch::new();
-        batch.add_get_events_request(event_key, start, limit);
-        let responses = self.client.execute(batch)?;
+        batch.add_get_events_with_proofs_request(event_key, start, true, limit, None);
+        let (responses, li) = self.execute_verified(batch)?;
 
-        match get_response_from_batch(0, &responses)? {
-            Ok(resp) => Ok(EventView::vec_from_response(resp.clone())?),
+        let views = match get_response_from_batch(0, &responses)? {
+            Ok(resp) => EventWithProofView::vec_from_response(resp.clone())?,
             Err(e) => bail!("Failed to get events with error: {:?}", e),
+        };
+        ensure!(
+            views.len() as u64 <= limit,
+            "Got {} events, more than the limit of {}",
+            views.len(),
+            limit
+        );
+        let mut events = vec![];
+        for (sequence_number, view) in (start..).zip(views) {
+            let event_with_proof = view.decode()?;
+            event_with_proof.verify(
+                li.ledger_info(),
+                &key,
+                sequence_number,
+                event_with_proof.transaction_version,
+                event_with_proof.event_index,
+            )?;
+            events.push(EventView::from((
+                event_with_proof.transaction_version,
+                event_with_proof.event,
+            )));
         }
+        Ok(events)
     }
 
-    /// Gets the block metadata
+    /// Gets the block metadata of the latest verified ledger info
     pub fn get_metadata(&mut self) -> Result<BlockMetadata> {
-        let mut batch = JsonRpcBatch::new();
-        batch.add_get_metadata_request(None);
-        let responses = self.client.execute(batch)?;
-
-        match get_response_from_batch(0, &responses)? {
-            Ok(resp) => Ok(BlockMetadata::from_response(resp.clone())?),
-            Err(e) => bail!("Failed to get block metadata with error: {:?}", e),
-        }
+        let (_, li) = self.execute_verified(JsonRpcBatch::new())?;
+        Ok(BlockMetadata {
+            version: li.ledger_info().version(),
+            timestamp: li.ledger_info().timestamp_usecs(),
+        })
     }
 
-    /// Gets the currency info stored on-chain
+    /// Gets the currency info stored on-chain, verified against the latest ledger info
     pub fn get_currency_info(&mut self) -> Result<Vec<CurrencyInfoView>> {
-        let mut batch = JsonRpcBatch::new();
-        batch.add_get_currencies_info();
-        let responses = self.client.execute(batch)?;
-        match get_response_from_batch(0, &responses)? {
-            Ok(resp) => Ok(CurrencyInfoView::vec_from_response(resp.clone())?),
-            Err(e) => bail!("Failed to get currencies info with error: {:?}", e),
-        }
+        let (blobs, _) =
+            self.get_verified_account_state_blobs(&[config_address(), association_address()])?;
+        let currencies = Self::registered_currencies(&blobs[0])?;
+        let association_state = match &blobs[1] {
+            Some(blob) => AccountState::try_from(blob)?,
+            None => bail!("Missing association account"),
+        };
+        currencies
+            .currency_codes()
+            .iter()
+            .map(|code| {
+                let path = CurrencyInfoResource::resource_path_for(code.clone()).path;
+                let currency_info = association_state
+                    .get_resource::<CurrencyInfoResource>(&path)?
+                    .ok_or_else(|| format_err!("Missing currency info for {}", code))?;
+                Ok(CurrencyInfoView::from(currency_info))
+            })
+            .collect()
     }
 
     /// Retrieves and checks the state proof
     pub fn get_state_proof(&mut self) -> Result<()> {
-        let mut batch = JsonRpcBatch::new();
+        self.execute_verified(JsonRpcBatch::new()).map(|_| ())
+    }
+
+    /// Executes `batch` along with a request for a state proof, which is verified to ratchet the
+    /// trusted state. The server answers all the requests of a batch from the same snapshot of
+    /// the ledger, so the proofs in the other responses are relative to the returned ledger
+    /// info.
+    fn execute_verified(
+        &mut self,
+        mut batch: JsonRpcBatch,
+    ) -> Result<(Vec<Result<JsonRpcResponse>>, LedgerInfoWithSignatures)> {
+        let state_proof_index = batch.requests.len();
         batch.add_get_state_proof_request(self.trusted_state.latest_version());
         let responses = self.client.execute(batch)?;
 
-        let state_proof = get_response_from_batch(0, &responses)?.as_ref();
-        self.process_state_proof_response(state_proof)
+        let li = self.process_state_proof_response(
+            get_response_from_batch(state_proof_index, &responses)?.as_ref(),
+        )?;
+        Ok((responses, li))
     }
 
     fn process_state_proof_response(
         &mut self,
         response: Result<&JsonRpcResponse, &anyhow::Error>,
-    ) -> Result<()> {
+    ) -> Result<LedgerInfoWithSignatures> {
         match response {
             Ok(resp) => {
                 let state_proof = StateProofView::from_response(resp.clone())?;
@@ -228,12 +396,12 @@ impl LibraClient {
         }
     }
 
-    fn verify_state_proof(&mut self, state_proof: StateProofView) -> Result<()> {
+    fn verify_state_proof(
+        &mut self,
+        state_proof: StateProofView,
+    ) -> Result<LedgerInfoWithSignatures> {
         let client_version = self.trusted_state.latest_version();
-        let li: LedgerInfoWithSignatures =
-            lcs::from_bytes(&state_proof.ledger_info_with_signatures.into_bytes()?)?;
-        let epoch_change_proof: EpochChangeProof =
-            lcs::from_bytes(&state_proof.epoch_change_proof.into_bytes()?)?;
+        let (li, epoch_change_proof, _) = state_proof.decode()?;
 
         // check ledger info version
         ensure!(
@@ -262,16 +430,20 @@ impl LibraClient {
                 // Update client state
                 self.trusted_state = new_state;
                 self.latest_epoch_change_li = Some(latest_epoch_change_li.clone());
+                self.latest_li = Some(li.clone());
+                self.persist_trusted_state()?;
             }
             TrustedStateChange::Version { new_state } => {
                 if self.trusted_state.latest_version() < new_state.latest_version() {
                     info!("Verified version change to: {}", new_state.latest_version());
                 }
                 self.trusted_state = new_state;
+                self.latest_li = Some(li.clone());
+                self.persist_trusted_state()?;
             }
             TrustedStateChange::NoChange => (),
         }
-        Ok(())
+        Ok(li)
     }
 
     /// LedgerInfo corresponding to the latest epoch change.
@@ -279,7 +451,10 @@ impl LibraClient {
         self.latest_epoch_change_li.as_ref()
     }
 
-    /// Get transaction from validator by account and sequence number.
+    /// Get transaction from validator by account and sequence number. The server only tells at
+    /// which version the transaction is, the transaction itself is then verified. If the server
+    /// claims there is no such transaction, this is checked against the sequence number of the
+    /// account.
     pub fn get_txn_by_acc_seq(
         &mut self,
         account: AccountAddress,
@@ -287,42 +462,137 @@ impl LibraClient {
         fetch_events: bool,
     ) -> Result<Option<TransactionView>> {
         let mut batch = JsonRpcBatch::new();
-        batch.add_get_account_transaction_request(account, sequence_number, fetch_events);
-        batch.add_get_state_proof_request(self.trusted_state.latest_version());
+        batch.add_get_account_transaction_request(account, sequence_number, false);
+        let (responses, _) = self.execute_verified(batch)?;
 
-        let responses = self.client.execute(batch)?;
-        let state_proof_view = get_response_from_batch(1, &responses)?.as_ref();
-        self.process_state_proof_response(state_proof_view)?;
-
-        match get_response_from_batch(0, &responses)? {
-            Ok(response) => Ok(TransactionView::optional_from_response(response.clone())?),
+        let version = match get_response_from_batch(0, &responses)? {
+            Ok(response) => {
+                TransactionView::optional_from_response(response.clone())?.map(|view| view.version)
+            }
             Err(e) => bail!("Failed to get account txn with error: {:?}", e),
-        }
+        };
+        let version = match version {
+            Some(version) => version,
+            None => {
+                let account_sequence_number = match self.get_account_state(account)?.0 {
+                    Some(account_view) => account_view.sequence_number,
+                    None => 0,
+                };
+                ensure!(
+                    sequence_number >= account_sequence_number,
+                    "The server did not return committed transaction {} of account {}",
+                    sequence_number,
+                    account
+                );
+                return Ok(None);
+            }
+        };
+
+        let (txn_list, _) = self.get_verified_txns(version, 1)?;
+        let txn = txn_list
+            .transactions
+            .get(0)
+            .ok_or_else(|| format_err!("Missing transaction at version {}", version))?;
+        let signed_txn = txn.as_signed_user_txn()?;
+        ensure!(
+            signed_txn.sender() == account && signed_txn.sequence_number() == sequence_number,
+            "Transaction at version {} is not transaction {} of account {}",
+            version,
+            sequence_number,
+            account
+        );
+        Ok(Self::transaction_views(txn_list, fetch_events)?.pop())
     }
 
-    /// Get transactions in range (start_version..start_version + limit - 1) from validator.
+    /// Get transactions in range (start_version..start_version + limit - 1) from validator,
+    /// verified against the latest ledger info.
     pub fn get_txn_by_range(
         &mut self,
         start_version: u64,
         limit: u64,
         fetch_events: bool,
     ) -> Result<Vec<TransactionView>> {
-        let mut batch = JsonRpcBatch::new();
-        batch.add_get_transactions_request(start_version, limit, fetch_events);
-        batch.add_get_state_proof_request(self.trusted_state.latest_version());
+        let (txn_list, _) = self.get_verified_txns(start_version, limit)?;
+        Self::transaction_views(txn_list, fetch_events)
+    }
 
-        let responses = self.client.execute(batch)?;
-        let state_proof = get_response_from_batch(1, &responses)?.as_ref();
-        self.process_state_proof_response(state_proof)?;
+    fn get_verified_txns(
+        &mut self,
+        start_version: u64,
+        limit: u64,
+    ) -> Result<(TransactionListWithProof, LedgerInfoWithSignatures)> {
+        let mut batch = JsonRpcBatch::new();
+        batch.add_get_transactions_with_proofs_request(start_version, limit, None);
+        let (responses, li) = self.execute_verified(batch)?;
 
-        match get_response_from_batch(0, &responses)? {
-            Ok(result) => Ok(TransactionView::vec_from_response(result.clone())?),
+        let view = match get_response_from_batch(0, &responses)? {
+            Ok(result) => TransactionListWithProofView::from_response(result.clone())?,
             Err(e) => bail!("Failed to get transactions with error: {:?}", e),
+        };
+        let txn_list = view.decode()?;
+        // The server returns all transactions from start_version up to the ledger version, if
+        // there are fewer than the limit
+        let ledger_version = li.ledger_info().version();
+        let (expected_first_version, expected_len) = if start_version <= ledger_version {
+            (
+                Some(start_version),
+                std::cmp::min(limit, ledger_version - start_version + 1),
+            )
+        } else {
+            (None, 0)
+        };
+        ensure!(
+            txn_list.transactions.len() as u64 == expected_len,
+            "Got {} transactions, expected {}",
+            txn_list.transactions.len(),
+            expected_len
+        );
+        txn_list.verify(li.ledger_info(), expected_first_version)?;
+        Ok((txn_list, li))
+    }
+
+    fn transaction_views(
+        txn_list: TransactionListWithProof,
+        fetch_events: bool,
+    ) -> Result<Vec<TransactionView>> {
+        let first_version = match txn_list.first_transaction_version {
+            Some(version) => version,
+            None => return Ok(vec![]),
+        };
+        let mut all_events = txn_list
+            .events
+            .ok_or_else(|| format_err!("Missing events"))?
+            .into_iter();
+        let mut views = vec![];
+        for ((version, txn), info) in (first_version..)
+            .zip(txn_list.transactions)
+            .zip(txn_list.proof.transaction_infos())
+        {
+            let events = all_events
+                .next()
+                .ok_or_else(|| format_err!("Missing events for version {}", version))?;
+            let events = if fetch_events {
+                events
+                    .into_iter()
+                    .map(|event| EventView::from((version, event)))
+                    .collect()
+            } else {
+                vec![]
+            };
+            views.push(TransactionView {
+                version,
+                hash: txn.hash().to_string(),
+                transaction: txn.into(),
+                events,
+                vm_status: info.major_status(),
+                gas_used: info.gas_used(),
+            });
         }
+        Ok(views)
     }
 
     fn get_sequence_number(&mut self, account: AccountAddress) -> Result<u64> {
-        match self.get_account_state(account, true)?.0 {
+        match self.get_account_state(account)?.0 {
             None => bail!("No account found for address {:?}", account),
             Some(account_view) => Ok(account_view.sequence_number),
         }
@@ -335,7 +605,7 @@ impl LibraClient {
         limit: u64,
     ) -> Result<(Vec<EventView>, AccountView)> {
         // get event key from access_path
-        match self.get_account_state(access_path.address, false)?.0 {
+        match self.get_account_state(access_path.address)?.0 {
             None => bail!("No account found for address {:?}", access_path.address),
             Some(account_view) => {
                 let path = access_path.path;
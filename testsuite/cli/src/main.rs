@@ -14,6 +14,7 @@ use cli::{
 use libra_types::waypoint::Waypoint;
 use rustyline::{config::CompletionType, error::ReadlineError, Config, Editor};
 use std::{
+    path::PathBuf,
     str::FromStr,
     time::{Duration, UNIX_EPOCH},
 };
@@ -52,7 +53,8 @@ struct Args {
     #[structopt(
         name = "waypoint",
         long,
-        help = "Explicitly specify the waypoint to use"
+        visible_alias = "trust-anchor",
+        help = "Explicitly specify the waypoint to use as the trust anchor"
     )]
     pub waypoint: Option<Waypoint>,
     #[structopt(
@@ -61,6 +63,19 @@ struct Args {
         help = "URL for a file with the waypoint to use"
     )]
     pub waypoint_url: Option<String>,
+    /// File in which the client keeps the latest state it verified from the waypoint, so that it
+    /// does not start from the waypoint again on the next run. The state is discarded if it was
+    /// verified from another waypoint.
+    #[structopt(
+        long = "trusted-state-file",
+        default_value = "client.trusted_state",
+        parse(from_os_str)
+    )]
+    pub trusted_state_file: PathBuf,
+    /// Directory of the Move stdlib sources, e.g. `language/stdlib/modules` in a Libra checkout.
+    /// Required by `dev run` to compile scripts against the stdlib.
+    #[structopt(long = "stdlib-dir", parse(from_os_str))]
+    pub stdlib_dir: Option<PathBuf>,
     /// Verbose output.
     #[structopt(short = "v", long = "verbose")]
     pub verbose: bool,
@@ -101,8 +116,12 @@ fn main() {
         args.faucet_server.clone(),
         mnemonic_file,
         waypoint,
+        Some(args.trusted_state_file.clone()),
     )
     .expect("Failed to construct client.");
+    if let Some(stdlib_dir) = args.stdlib_dir.clone() {
+        client_proxy.set_stdlib_dir(stdlib_dir);
+    }
 
     // Test connection to validator
     let block_metadata = client_proxy
@@ -0,0 +1,49 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+/// ClockSkew moves the clock of the host of a given instance forward, and back once deactivated
+use crate::{effects::Effect, instance::Instance};
+use anyhow::Result;
+
+use async_trait::async_trait;
+use libra_logger::info;
+use std::{fmt, time::Duration};
+
+pub struct ClockSkew {
+    instance: Instance,
+    skew: Duration,
+}
+
+impl ClockSkew {
+    pub fn new(instance: Instance, skew: Duration) -> Self {
+        Self { instance, skew }
+    }
+}
+
+#[async_trait]
+impl Effect for ClockSkew {
+    async fn activate(&mut self) -> Result<()> {
+        info!("{}", self);
+        let cmd = format!("date -s @$(($(date +%s) + {}))", self.skew.as_secs());
+        self.instance.util_cmd(cmd, "ac-clock-skew").await
+    }
+
+    async fn deactivate(&mut self) -> Result<()> {
+        info!("Reverting {}", self);
+        let cmd = format!("date -s @$(($(date +%s) - {}))", self.skew.as_secs());
+        self.instance.util_cmd(cmd, "de-clock-skew").await
+    }
+}
+
+impl fmt::Display for ClockSkew {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ClockSkew {}s for {}",
+            self.skew.as_secs(),
+            self.instance
+        )
+    }
+}
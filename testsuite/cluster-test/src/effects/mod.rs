@@ -8,8 +8,11 @@ use async_trait::async_trait;
 use futures::future::try_join_all;
 use std::fmt::Display;
 
+pub mod clock_skew;
 pub mod network_delay;
+pub mod network_partition;
 pub mod packet_loss;
+pub mod stop_validator;
 
 #[async_trait]
 pub trait Effect: Display {
@@ -17,6 +20,17 @@ pub trait Effect: Display {
     async fn deactivate(&mut self) -> Result<()>;
 }
 
+#[async_trait]
+impl Effect for Box<dyn Effect + Send> {
+    async fn activate(&mut self) -> Result<()> {
+        self.as_mut().activate().await
+    }
+
+    async fn deactivate(&mut self) -> Result<()> {
+        self.as_mut().deactivate().await
+    }
+}
+
 pub async fn activate_all<T: Effect>(effects: &mut Vec<T>) -> Result<()> {
     try_join_all(effects.iter_mut().map(Effect::activate)).await?;
     Ok(())
@@ -0,0 +1,63 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+/// NetworkPartition drops all the traffic between a given instance and a list of instances
+use crate::{effects::Effect, instance::Instance};
+use anyhow::Result;
+
+use async_trait::async_trait;
+use libra_logger::info;
+use std::fmt;
+
+pub struct NetworkPartition {
+    instance: Instance,
+    peers: Vec<Instance>,
+}
+
+impl NetworkPartition {
+    pub fn new(instance: Instance, peers: Vec<Instance>) -> Self {
+        Self { instance, peers }
+    }
+
+    fn iptables_cmd(&self, action: &str) -> String {
+        let mut command = "".to_string();
+        for peer in &self.peers {
+            command += format!(
+                "iptables -{action} INPUT -s {ip} -j DROP; iptables -{action} OUTPUT -d {ip} -j DROP; ",
+                action = action,
+                ip = peer.ip()
+            )
+            .as_str();
+        }
+        command
+    }
+}
+
+#[async_trait]
+impl Effect for NetworkPartition {
+    async fn activate(&mut self) -> Result<()> {
+        info!("{}", self);
+        let command = self.iptables_cmd("A");
+        self.instance.util_cmd(command, "ac-partition").await
+    }
+
+    async fn deactivate(&mut self) -> Result<()> {
+        info!("Healing {}", self);
+        // Deleting a rule that was not added fails, which must not prevent deleting the others
+        let command = self.iptables_cmd("D") + "true";
+        self.instance.util_cmd(command, "de-partition").await
+    }
+}
+
+impl fmt::Display for NetworkPartition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "NetworkPartition between {} and {} instances",
+            self.instance,
+            self.peers.len()
+        )
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+/// StopValidator stops a given instance, and starts it again with its data once deactivated
+use crate::{effects::Effect, instance::Instance};
+use anyhow::Result;
+
+use async_trait::async_trait;
+use libra_logger::info;
+use std::fmt;
+
+pub struct StopValidator {
+    instance: Instance,
+}
+
+impl StopValidator {
+    pub fn new(instance: Instance) -> Self {
+        Self { instance }
+    }
+}
+
+#[async_trait]
+impl Effect for StopValidator {
+    async fn activate(&mut self) -> Result<()> {
+        info!("Stopping {}", self.instance);
+        self.instance.stop().await
+    }
+
+    async fn deactivate(&mut self) -> Result<()> {
+        info!("Starting {}", self.instance);
+        self.instance.start(false).await
+    }
+}
+
+impl fmt::Display for StopValidator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "StopValidator {}", self.instance)
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+/// This module provides an experiment composed of faults injected in random validators at the
+/// same time: network partitions, reboots, packet loss and clock skew. Transactions are emitted
+/// to the validators that are not affected, and health checks assert on the progress of the
+/// cluster while the faults are active and once they are reverted.
+use crate::{
+    cluster::Cluster,
+    effects::{
+        self, clock_skew::ClockSkew, network_partition::NetworkPartition, packet_loss::PacketLoss,
+        stop_validator::StopValidator, Effect,
+    },
+    experiments::{Context, Experiment, ExperimentParam},
+    health::{default_experiment_checks, CheckResult, ExperimentHealthCheck, ExperimentPhase},
+    instance,
+    instance::Instance,
+    tx_emitter::EmitJobRequest,
+    util::unix_timestamp_now,
+};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use libra_logger::info;
+use serde::Serialize;
+use std::{collections::HashSet, fmt, fs, path::PathBuf, time::Duration};
+use structopt::StructOpt;
+use tokio::time;
+
+#[derive(StructOpt, Debug)]
+pub struct ChaosExperimentParams {
+    #[structopt(
+        long,
+        default_value = "0",
+        help = "Number of validators partitioned from the others"
+    )]
+    partition: usize,
+    #[structopt(long, default_value = "0", help = "Number of validators to reboot")]
+    reboot: usize,
+    #[structopt(
+        long,
+        default_value = "0",
+        help = "Number of validators in which packet loss is introduced"
+    )]
+    packet_loss: usize,
+    #[structopt(
+        long,
+        default_value = "10",
+        help = "Percent of packet loss for each instance"
+    )]
+    packet_loss_percent: f32,
+    #[structopt(
+        long,
+        default_value = "0",
+        help = "Number of validators of which the clock is skewed"
+    )]
+    clock_skew: usize,
+    #[structopt(
+        long,
+        default_value = "30",
+        help = "Skew in secs of the clocks moved forward"
+    )]
+    clock_skew_secs: u64,
+    #[structopt(
+        long,
+        default_value = "120",
+        help = "Duration in secs for which the faults are active"
+    )]
+    duration_secs: u64,
+    #[structopt(
+        long,
+        default_value = "120",
+        help = "Duration in secs of the recovery checked once the faults are reverted"
+    )]
+    recovery_secs: u64,
+    #[structopt(
+        long,
+        help = "Path to which the JSON report of the experiment is written"
+    )]
+    report_path: Option<PathBuf>,
+}
+
+impl ExperimentParam for ChaosExperimentParams {
+    type E = ChaosExperiment;
+    fn build(self, cluster: &Cluster) -> Self::E {
+        let mut builder = ChaosExperimentBuilder::new(cluster)
+            .partition(self.partition)
+            .reboot(self.reboot)
+            .packet_loss(self.packet_loss, self.packet_loss_percent)
+            .clock_skew(self.clock_skew, Duration::from_secs(self.clock_skew_secs))
+            .duration(Duration::from_secs(self.duration_secs))
+            .recovery(Duration::from_secs(self.recovery_secs));
+        if let Some(report_path) = self.report_path {
+            builder = builder.report_path(report_path);
+        }
+        builder.build()
+    }
+}
+
+/// Composes the faults of a `ChaosExperiment`. Each fault picks its validators at random, among
+/// all validators of the cluster, so faults may overlap on a validator.
+pub struct ChaosExperimentBuilder {
+    cluster: Cluster,
+    faults: Vec<Box<dyn Effect + Send>>,
+    affected: Vec<Instance>,
+    checks: Option<Vec<Box<dyn ExperimentHealthCheck>>>,
+    duration: Duration,
+    recovery: Duration,
+    report_path: Option<PathBuf>,
+}
+
+impl ChaosExperimentBuilder {
+    pub fn new(cluster: &Cluster) -> Self {
+        Self {
+            cluster: cluster.clone(),
+            faults: vec![],
+            affected: vec![],
+            checks: None,
+            duration: Duration::from_secs(120),
+            recovery: Duration::from_secs(120),
+            report_path: None,
+        }
+    }
+
+    fn random_validators(&self, count: usize) -> Vec<Instance> {
+        let total = self.cluster.validator_instances().len();
+        if count > total {
+            panic!(
+                "Can not pick {} validators in cluster with {} instances",
+                count, total
+            );
+        }
+        let (picked, _) = self.cluster.split_n_validators_random(count);
+        picked.into_validator_instances()
+    }
+
+    fn add_faults<F>(self, count: usize, fault: F) -> Self
+    where
+        F: Fn(Instance) -> Box<dyn Effect + Send>,
+    {
+        let instances = self.random_validators(count);
+        self.add_faults_to(instances, fault)
+    }
+
+    fn add_faults_to<F>(mut self, instances: Vec<Instance>, fault: F) -> Self
+    where
+        F: Fn(Instance) -> Box<dyn Effect + Send>,
+    {
+        for instance in instances {
+            self.faults.push(fault(instance.clone()));
+            self.affected.push(instance);
+        }
+        self
+    }
+
+    /// Drops all traffic between `count` random validators and the other validators
+    pub fn partition(self, count: usize) -> Self {
+        if count == 0 {
+            return self;
+        }
+        let (minority, majority) = self.cluster.split_n_validators_random(count);
+        let majority = majority.into_validator_instances();
+        self.add_faults_to(minority.into_validator_instances(), |instance| {
+            Box::new(NetworkPartition::new(instance, majority.clone()))
+        })
+    }
+
+    /// Stops `count` random validators while the faults are active
+    pub fn reboot(self, count: usize) -> Self {
+        self.add_faults(count, |instance| Box::new(StopValidator::new(instance)))
+    }
+
+    pub fn packet_loss(self, count: usize, percent: f32) -> Self {
+        self.add_faults(count, |instance| {
+            Box::new(PacketLoss::new(instance, percent))
+        })
+    }
+
+    /// Moves the clocks of `count` random validators forward by `skew`
+    pub fn clock_skew(self, count: usize, skew: Duration) -> Self {
+        self.add_faults(count, |instance| Box::new(ClockSkew::new(instance, skew)))
+    }
+
+    /// Adds a health check, replacing the default ones
+    pub fn check(mut self, check: Box<dyn ExperimentHealthCheck>) -> Self {
+        self.checks.get_or_insert_with(Vec::new).push(check);
+        self
+    }
+
+    /// Sets for how long the faults are active
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets for how long the recovery is checked once the faults are reverted
+    pub fn recovery(mut self, recovery: Duration) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Writes the report of the experiment as JSON to `path`
+    pub fn report_path(mut self, path: PathBuf) -> Self {
+        self.report_path = Some(path);
+        self
+    }
+
+    pub fn build(self) -> ChaosExperiment {
+        let affected = instance::instancelist_to_set(&self.affected);
+        let unaffected = self
+            .cluster
+            .validator_instances()
+            .iter()
+            .filter(|instance| !affected.contains(instance.peer_name()))
+            .cloned()
+            .collect();
+        ChaosExperiment {
+            faults: self.faults,
+            affected,
+            unaffected,
+            checks: self.checks.unwrap_or_else(default_experiment_checks),
+            duration: self.duration,
+            recovery: self.recovery,
+            report_path: self.report_path,
+        }
+    }
+}
+
+pub struct ChaosExperiment {
+    faults: Vec<Box<dyn Effect + Send>>,
+    affected: HashSet<String>,
+    unaffected: Vec<Instance>,
+    checks: Vec<Box<dyn ExperimentHealthCheck>>,
+    duration: Duration,
+    recovery: Duration,
+    report_path: Option<PathBuf>,
+}
+
+/// The structured report of a `ChaosExperiment`, written as JSON
+#[derive(Serialize)]
+pub struct ChaosReport {
+    pub experiment: String,
+    pub faults: Vec<String>,
+    /// Unix timestamps, in seconds, of the start and end of each phase
+    pub phases: Vec<(ExperimentPhase, u64, u64)>,
+    pub submitted_txn: u64,
+    pub committed_txn: u64,
+    pub expired_txn: u64,
+    pub checks: Vec<CheckResult>,
+    pub passed: bool,
+}
+
+impl ChaosExperiment {
+    fn check_phase(
+        &self,
+        context: &Context<'_>,
+        phase: ExperimentPhase,
+        start: Duration,
+        end: Duration,
+    ) -> Vec<CheckResult> {
+        let results: Vec<_> = self
+            .checks
+            .iter()
+            .filter_map(|check| check.check(context.prometheus, phase, start, end))
+            .collect();
+        for result in &results {
+            info!("{}", result);
+        }
+        results
+    }
+}
+
+#[async_trait]
+impl Experiment for ChaosExperiment {
+    fn affected_validators(&self) -> HashSet<String> {
+        self.affected.clone()
+    }
+
+    async fn run(&mut self, context: &mut Context<'_>) -> Result<()> {
+        let emit_job_request =
+            EmitJobRequest::for_instances(self.unaffected.clone(), context.global_emit_job_request);
+        let fault_start = unix_timestamp_now();
+        effects::activate_all(&mut self.faults).await?;
+        let stats = context
+            .tx_emitter
+            .emit_txn_for(self.duration, emit_job_request)
+            .await;
+        // Faults are reverted even if emitting transactions failed
+        effects::deactivate_all(&mut self.faults).await?;
+        let stats = stats?;
+        let fault_end = unix_timestamp_now();
+        time::delay_for(self.recovery).await;
+        let recovery_end = unix_timestamp_now();
+
+        let mut checks = self.check_phase(context, ExperimentPhase::During, fault_start, fault_end);
+        checks.extend(self.check_phase(context, ExperimentPhase::After, fault_end, recovery_end));
+        let report = ChaosReport {
+            experiment: self.to_string(),
+            faults: self.faults.iter().map(ToString::to_string).collect(),
+            phases: vec![
+                (
+                    ExperimentPhase::During,
+                    fault_start.as_secs(),
+                    fault_end.as_secs(),
+                ),
+                (
+                    ExperimentPhase::After,
+                    fault_end.as_secs(),
+                    recovery_end.as_secs(),
+                ),
+            ],
+            submitted_txn: stats.submitted,
+            committed_txn: stats.committed,
+            expired_txn: stats.expired,
+            passed: checks.iter().all(|check| check.passed),
+            checks,
+        };
+
+        for check in &report.checks {
+            if let Some(value) = check.value {
+                context.report.report_metric(
+                    &self,
+                    format!("{}_{}", check.check, check.phase),
+                    value,
+                );
+            }
+        }
+        context.report.report_text(format!(
+            "{} : {} of {} checks passed, {} of {} txns committed",
+            self,
+            report.checks.iter().filter(|check| check.passed).count(),
+            report.checks.len(),
+            report.committed_txn,
+            report.submitted_txn
+        ));
+        if let Some(path) = &self.report_path {
+            fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            info!("Chaos report written to {:?}", path);
+        }
+
+        if !report.passed {
+            let failures: Vec<_> = report
+                .checks
+                .iter()
+                .filter(|check| !check.passed)
+                .map(ToString::to_string)
+                .collect();
+            bail!("Health checks failed:\n{}", failures.join("\n"));
+        }
+        Ok(())
+    }
+
+    fn deadline(&self) -> Duration {
+        Duration::from_secs(20 * 60) + self.duration + self.recovery
+    }
+}
+
+impl fmt::Display for ChaosExperiment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Chaos [")?;
+        for fault in self.faults.iter() {
+            write!(f, "{}, ", fault)?;
+        }
+        write!(f, "]")
+    }
+}
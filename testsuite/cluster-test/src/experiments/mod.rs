@@ -3,6 +3,7 @@
 
 #![forbid(unsafe_code)]
 
+mod chaos;
 mod cpu_flamegraph;
 mod packet_loss_random_validators;
 mod performance_benchmark;
@@ -12,6 +13,7 @@ mod recovery_time;
 
 use std::{collections::HashSet, fmt::Display, time::Duration};
 
+pub use chaos::{ChaosExperiment, ChaosExperimentBuilder, ChaosExperimentParams, ChaosReport};
 pub use packet_loss_random_validators::{
     PacketLossRandomValidators, PacketLossRandomValidatorsParams,
 };
@@ -128,6 +130,7 @@ pub fn get_experiment(name: &str, args: &[String], cluster: &Cluster) -> Box<dyn
         f::<RebootRandomValidatorsParams>(),
     );
     known_experiments.insert("generate_cpu_flamegraph", f::<CpuFlamegraphParams>());
+    known_experiments.insert("chaos", f::<ChaosExperimentParams>());
 
     let builder = known_experiments.get(name).expect("Experiment not found");
     builder(args, cluster)
@@ -0,0 +1,258 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Health checks asserting on the metrics of the validators, as scraped by Prometheus, in a
+//! window of time during or after an experiment. Unlike the checks driven by the events of the
+//! validators, these measure how well the cluster as a whole keeps making progress.
+
+use crate::prometheus::Prometheus;
+use serde::Serialize;
+use std::{fmt, time::Duration};
+
+/// Step of the Prometheus range queries, in seconds
+const QUERY_STEP: u64 = 10;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentPhase {
+    /// While the faults of the experiment are active
+    During,
+    /// Once the faults are reverted, while the cluster recovers
+    After,
+}
+
+impl fmt::Display for ExperimentPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExperimentPhase::During => write!(f, "during"),
+            ExperimentPhase::After => write!(f, "after"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckResult {
+    pub check: String,
+    pub phase: ExperimentPhase,
+    /// The measured value, if the metric could be queried
+    pub value: Option<f64>,
+    pub expectation: String,
+    pub passed: bool,
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match self.value {
+            Some(value) => format!("{:.2}", value),
+            None => "no data".to_string(),
+        };
+        write!(
+            f,
+            "{} {} {}: {} (expected {})",
+            if self.passed { "PASS" } else { "FAIL" },
+            self.check,
+            self.phase,
+            value,
+            self.expectation
+        )
+    }
+}
+
+pub trait ExperimentHealthCheck: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Checks the metrics between `start` and `end`, unix timestamps, in the given phase. Returns
+    /// None if the check does not apply to that phase.
+    fn check(
+        &self,
+        prometheus: &Prometheus,
+        phase: ExperimentPhase,
+        start: Duration,
+        end: Duration,
+    ) -> Option<CheckResult>;
+}
+
+/// A threshold on a metric averaged over all validators, which may differ in each phase
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PhaseThresholds {
+    pub during: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl PhaseThresholds {
+    pub fn new(during: Option<f64>, after: Option<f64>) -> Self {
+        Self { during, after }
+    }
+
+    fn get(&self, phase: ExperimentPhase) -> Option<f64> {
+        match phase {
+            ExperimentPhase::During => self.during,
+            ExperimentPhase::After => self.after,
+        }
+    }
+}
+
+fn check_avg(
+    name: &str,
+    prometheus: &Prometheus,
+    query: &str,
+    phase: ExperimentPhase,
+    (start, end): (Duration, Duration),
+    threshold: f64,
+    at_least: bool,
+) -> CheckResult {
+    let value = prometheus
+        .query_range_avg(query.to_string(), &start, &end, QUERY_STEP)
+        .ok();
+    let passed = match value {
+        Some(value) if at_least => value >= threshold,
+        Some(value) => value <= threshold,
+        None => false,
+    };
+    CheckResult {
+        check: name.to_string(),
+        phase,
+        value,
+        expectation: format!("{} {}", if at_least { ">=" } else { "<=" }, threshold),
+        passed,
+    }
+}
+
+/// Asserts that validators commit at least a number of blocks per second
+pub struct CommitRateCheck {
+    min_blocks_per_sec: PhaseThresholds,
+}
+
+impl CommitRateCheck {
+    pub fn new(min_blocks_per_sec: PhaseThresholds) -> Self {
+        Self { min_blocks_per_sec }
+    }
+}
+
+impl ExperimentHealthCheck for CommitRateCheck {
+    fn name(&self) -> &'static str {
+        "commit_rate"
+    }
+
+    fn check(
+        &self,
+        prometheus: &Prometheus,
+        phase: ExperimentPhase,
+        start: Duration,
+        end: Duration,
+    ) -> Option<CheckResult> {
+        let threshold = self.min_blocks_per_sec.get(phase)?;
+        Some(check_avg(
+            self.name(),
+            prometheus,
+            "rate(libra_consensus_committed_blocks_count[1m])",
+            phase,
+            (start, end),
+            threshold,
+            true,
+        ))
+    }
+}
+
+/// Asserts that the rounds of validators advance at least at a number of rounds per second
+pub struct RoundProgressionCheck {
+    min_rounds_per_sec: PhaseThresholds,
+}
+
+impl RoundProgressionCheck {
+    pub fn new(min_rounds_per_sec: PhaseThresholds) -> Self {
+        Self { min_rounds_per_sec }
+    }
+}
+
+impl ExperimentHealthCheck for RoundProgressionCheck {
+    fn name(&self) -> &'static str {
+        "round_progression"
+    }
+
+    fn check(
+        &self,
+        prometheus: &Prometheus,
+        phase: ExperimentPhase,
+        start: Duration,
+        end: Duration,
+    ) -> Option<CheckResult> {
+        let threshold = self.min_rounds_per_sec.get(phase)?;
+        Some(check_avg(
+            self.name(),
+            prometheus,
+            "deriv(libra_consensus_current_round[1m])",
+            phase,
+            (start, end),
+            threshold,
+            true,
+        ))
+    }
+}
+
+/// Asserts that the transactions accumulated in mempools during the faults get committed: the
+/// number of transactions in the mempools at the end of the recovery must not exceed a maximum.
+pub struct MempoolDrainCheck {
+    max_pending_txns: f64,
+    /// Length of the window, at the end of the recovery, in which the mempools are measured
+    window: Duration,
+}
+
+impl MempoolDrainCheck {
+    pub fn new(max_pending_txns: f64, window: Duration) -> Self {
+        Self {
+            max_pending_txns,
+            window,
+        }
+    }
+}
+
+impl ExperimentHealthCheck for MempoolDrainCheck {
+    fn name(&self) -> &'static str {
+        "mempool_drain"
+    }
+
+    fn check(
+        &self,
+        prometheus: &Prometheus,
+        phase: ExperimentPhase,
+        start: Duration,
+        end: Duration,
+    ) -> Option<CheckResult> {
+        if phase != ExperimentPhase::After {
+            return None;
+        }
+        let start = if end > start + self.window {
+            end - self.window
+        } else {
+            start
+        };
+        Some(check_avg(
+            self.name(),
+            prometheus,
+            "mempool_gauge{op=\"txn.system_ttl_index\"}",
+            phase,
+            (start, end),
+            self.max_pending_txns,
+            false,
+        ))
+    }
+}
+
+/// The checks of experiments that do not set their own: the cluster keeps committing while the
+/// faults are active, and catches up once they are reverted
+pub fn default_experiment_checks() -> Vec<Box<dyn ExperimentHealthCheck>> {
+    vec![
+        Box::new(CommitRateCheck::new(PhaseThresholds::new(
+            Some(0.1),
+            Some(0.5),
+        ))),
+        Box::new(RoundProgressionCheck::new(PhaseThresholds::new(
+            Some(0.1),
+            Some(0.5),
+        ))),
+        Box::new(MempoolDrainCheck::new(100.0, Duration::from_secs(30))),
+    ]
+}
@@ -5,6 +5,7 @@
 
 mod commit_check;
 mod debug_interface_log_tail;
+mod experiment_check;
 mod fullnode_check;
 mod liveness_check;
 mod log_tail;
@@ -14,6 +15,10 @@ use anyhow::{bail, Result};
 use async_trait::async_trait;
 pub use commit_check::CommitHistoryHealthCheck;
 pub use debug_interface_log_tail::DebugPortLogWorker;
+pub use experiment_check::{
+    default_experiment_checks, CheckResult, CommitRateCheck, ExperimentHealthCheck,
+    ExperimentPhase, MempoolDrainCheck, PhaseThresholds, RoundProgressionCheck,
+};
 pub use fullnode_check::FullNodeHealthCheck;
 use itertools::Itertools;
 pub use liveness_check::LivenessHealthCheck;
@@ -7,7 +7,7 @@ use crate::cluster_swarm::cluster_swarm_kube::ClusterSwarmKube;
 use anyhow::{format_err, Result};
 use debug_interface::AsyncNodeDebugClient;
 use libra_config::config::NodeConfig;
-use libra_json_rpc_client::{JsonRpcAsyncClient, JsonRpcBatch};
+use libra_json_rpc_client::{JsonRpcAsyncClient, JsonRpcBatch, RetryPolicy};
 use reqwest::{Client, Url};
 use serde_json::Value;
 use std::{
@@ -187,7 +187,11 @@ impl Instance {
     }
 
     pub async fn try_json_rpc(&self) -> Result<()> {
-        self.json_rpc_client().execute(JsonRpcBatch::new()).await?;
+        // Callers poll this until the node is up, so a failed probe should not be retried
+        self.json_rpc_client()
+            .with_retry_policy(RetryPolicy::no_retry())
+            .execute(JsonRpcBatch::new())
+            .await?;
         Ok(())
     }
 
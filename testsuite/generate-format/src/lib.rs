@@ -16,6 +16,8 @@ mod libra;
 mod move_abi;
 /// Network messages.
 mod network;
+/// Storage service messages.
+mod storage;
 
 arg_enum! {
 #[derive(Debug, StructOpt, Clone, Copy)]
@@ -25,6 +27,7 @@ pub enum Corpus {
     Consensus,
     Network,
     MoveABI,
+    Storage,
 }
 }
 
@@ -44,6 +47,7 @@ impl Corpus {
             Corpus::Consensus => consensus::get_registry().unwrap(),
             Corpus::Network => network::get_registry().unwrap(),
             Corpus::MoveABI => move_abi::get_registry().unwrap(),
+            Corpus::Storage => storage::get_registry().unwrap(),
         }
     }
 
@@ -54,6 +58,7 @@ impl Corpus {
             Corpus::Consensus => consensus::output_file(),
             Corpus::Network => network::output_file(),
             Corpus::MoveABI => move_abi::output_file(),
+            Corpus::Storage => storage::output_file(),
         }
     }
 }
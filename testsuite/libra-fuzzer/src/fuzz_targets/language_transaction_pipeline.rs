@@ -0,0 +1,201 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::FuzzTargetImpl;
+use compiled_stdlib::transaction_scripts::StdlibScript;
+use language_e2e_tests::{
+    account::{lbr_currency_code, AccountData, AccountRoleSpecifier},
+    executor::FakeExecutor,
+    keygen::KeyGen,
+};
+use libra_crypto::{traits::SigningKey, HashValue};
+use libra_proptest_helpers::ValueGenerator;
+use libra_types::{
+    account_config::{lbr_type_tag, LBR_NAME},
+    transaction::{
+        RawTransaction, Script, SignedTransaction, TransactionArgument, TransactionPayload,
+        TransactionStatus,
+    },
+    vm_error::StatusCode,
+};
+use once_cell::sync::Lazy;
+use proptest::{collection::vec, prelude::*, sample::select};
+use std::time::Duration;
+
+const NUM_ACCOUNTS: usize = 4;
+
+/// Accounts created on top of genesis. Their keys are derived from a fixed seed, so that the
+/// transactions in the corpus are still signed by existing accounts in later runs.
+static ACCOUNTS: Lazy<Vec<AccountData>> = Lazy::new(|| {
+    let mut keygen = KeyGen::from_seed([7; 32]);
+    (0..NUM_ACCOUNTS)
+        .map(|_| {
+            let (privkey, pubkey) = keygen.generate_keypair();
+            AccountData::with_keypair(
+                privkey,
+                pubkey,
+                1_000_000_000,
+                lbr_currency_code(),
+                0,
+                AccountRoleSpecifier::default(),
+            )
+        })
+        .collect()
+});
+
+static EXECUTOR: Lazy<FakeExecutor> = Lazy::new(|| {
+    let mut executor = FakeExecutor::from_genesis_file();
+    for account in ACCOUNTS.iter() {
+        executor.add_account_data(account);
+    }
+    executor
+});
+
+#[derive(Clone, Debug, Default)]
+pub struct LanguageTransactionPipeline;
+
+impl FuzzTargetImpl for LanguageTransactionPipeline {
+    fn name(&self) -> &'static str {
+        module_name!()
+    }
+
+    fn description(&self) -> &'static str {
+        "Language signature check, validate and execute structured transactions"
+    }
+
+    fn generate(&self, _idx: usize, gen: &mut ValueGenerator) -> Option<Vec<u8>> {
+        let txn = gen.generate(transaction_strategy());
+        Some(lcs::to_bytes(&txn).expect("serialization should work"))
+    }
+
+    fn fuzz(&self, data: &[u8]) {
+        let txn: SignedTransaction = match lcs::from_bytes(data) {
+            Ok(txn) => txn,
+            Err(_) => return,
+        };
+        let signature_valid = txn.clone().check_signature().is_ok();
+        let validation_status = EXECUTOR.verify_transaction(txn.clone()).status();
+        let output = EXECUTOR
+            .execute_block(vec![txn])
+            .expect("the VM should not fail on a single transaction")
+            .pop()
+            .expect("a block with one transaction should have one output");
+
+        if !signature_valid {
+            assert!(
+                validation_status.is_some(),
+                "transaction with an invalid signature was accepted by the validator"
+            );
+            match output.status() {
+                TransactionStatus::Discard(status) => {
+                    assert_eq!(status.major_status, StatusCode::INVALID_SIGNATURE)
+                }
+                status => panic!(
+                    "transaction with an invalid signature was not discarded: {:?}",
+                    status
+                ),
+            }
+        }
+        // Transactions rejected by the validator must not be committed
+        if let Some(validation_status) = validation_status {
+            assert!(
+                !matches!(output.status(), TransactionStatus::Keep(_)),
+                "transaction rejected by the validator with {:?} was kept: {:?}",
+                validation_status,
+                output.status()
+            );
+        }
+    }
+}
+
+/// How a transaction of one of the accounts is signed
+#[derive(Clone, Copy, Debug)]
+enum Signer {
+    Sender,
+    /// With the key of another account, so that the signature is valid but does not match the
+    /// authentication key of the sender
+    Account(usize),
+    /// With the key of the sender, but over another message
+    WrongMessage,
+}
+
+fn transaction_strategy() -> impl Strategy<Value = SignedTransaction> {
+    let signer = prop_oneof![
+        4 => Just(Signer::Sender),
+        1 => (0..NUM_ACCOUNTS).prop_map(Signer::Account),
+        1 => Just(Signer::WrongMessage),
+    ];
+    prop_oneof![
+        // Mostly rejected early, as the sender does not exist
+        1 => any::<SignedTransaction>(),
+        4 => (
+            0..NUM_ACCOUNTS,
+            0u64..3,
+            payload_strategy(),
+            0u64..2_000_000,
+            0u64..3,
+            select(vec![LBR_NAME.to_owned(), "Coin1".to_owned(), "XDX".to_owned()]),
+            0u64..100_000,
+            signer,
+        )
+            .prop_map(
+                |(
+                    sender,
+                    sequence_number,
+                    payload,
+                    max_gas_amount,
+                    gas_unit_price,
+                    gas_currency_code,
+                    expiration_time_secs,
+                    signer,
+                )| {
+                    let raw_txn = RawTransaction::new(
+                        *ACCOUNTS[sender].address(),
+                        sequence_number,
+                        payload,
+                        max_gas_amount,
+                        gas_unit_price,
+                        gas_currency_code,
+                        Duration::from_secs(expiration_time_secs),
+                    );
+                    sign(raw_txn, sender, signer)
+                },
+            ),
+    ]
+}
+
+fn payload_strategy() -> impl Strategy<Value = TransactionPayload> {
+    let stdlib_scripts: Vec<_> = StdlibScript::all()
+        .into_iter()
+        .map(|script| script.compiled_bytes().into_vec())
+        .collect();
+    let stdlib_script = (
+        select(stdlib_scripts),
+        select(vec![vec![], vec![lbr_type_tag()]]),
+        vec(any::<TransactionArgument>(), 0..6),
+    )
+        .prop_map(|(code, ty_args, args)| {
+            TransactionPayload::Script(Script::new(code, ty_args, args))
+        });
+    prop_oneof![
+        // Scripts of the stdlib get past the bytecode verifier, unlike arbitrary bytes
+        3 => stdlib_script,
+        1 => any::<TransactionPayload>(),
+    ]
+}
+
+fn sign(raw_txn: RawTransaction, sender: usize, signer: Signer) -> SignedTransaction {
+    let sender = ACCOUNTS[sender].account();
+    let account = match signer {
+        Signer::Sender => sender,
+        Signer::Account(idx) => ACCOUNTS[idx].account(),
+        Signer::WrongMessage => {
+            let signature = sender.privkey.sign_message(&HashValue::zero());
+            return SignedTransaction::new(raw_txn, sender.pubkey.clone(), signature);
+        }
+    };
+    raw_txn
+        .sign(&account.privkey, account.pubkey.clone())
+        .expect("signing should work")
+        .into_inner()
+}
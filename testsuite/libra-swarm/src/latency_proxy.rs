@@ -0,0 +1,274 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! TCP proxies that degrade the links between the nodes of a local swarm, so that consensus and
+//! state sync can be exercised under WAN-like conditions.
+//!
+//! Every node dials every other node through its own proxy, so the conditions are set per pair
+//! of nodes and per direction. As the proxies forward a TCP stream, bytes are never dropped:
+//! a lost segment is instead delayed by a retransmission timeout, which is how loss shows to the
+//! applications on top of TCP.
+
+use libra_logger::prelude::*;
+use rand::Rng;
+use std::{
+    cmp::max,
+    collections::HashMap,
+    io::{self, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const BUFFER_SIZE: usize = 64 * 1024;
+/// The minimum retransmission timeout of Linux
+const MIN_RETRANSMISSION_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// The conditions of one direction of a link
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LinkConditions {
+    /// Delay added to every chunk of data
+    pub latency: Duration,
+    /// Upper bound of the random delay added on top of the latency
+    pub jitter: Duration,
+    /// Percentage of the chunks of data that are delayed by a retransmission timeout
+    pub loss_percent: u8,
+}
+
+impl LinkConditions {
+    pub fn new(latency: Duration, jitter: Duration, loss_percent: u8) -> Self {
+        assert!(loss_percent <= 100, "Loss must be a percentage");
+        Self {
+            latency,
+            jitter,
+            loss_percent,
+        }
+    }
+
+    fn sample_delay<R: Rng>(&self, rng: &mut R) -> Duration {
+        let mut delay = self.latency;
+        if self.jitter > Duration::from_millis(0) {
+            delay += rng.gen_range(Duration::from_millis(0), self.jitter);
+        }
+        if rng.gen_range(0, 100) < self.loss_percent {
+            // The segment is retransmitted after a timeout of at least two round trips
+            delay += max(MIN_RETRANSMISSION_TIMEOUT, self.latency * 4);
+        }
+        delay
+    }
+}
+
+/// The conditions of the links between all the nodes of a swarm, by node index
+#[derive(Clone, Debug, Default)]
+pub struct LinkMatrix {
+    default: LinkConditions,
+    links: HashMap<(usize, usize), LinkConditions>,
+}
+
+impl LinkMatrix {
+    /// Links that all have the same conditions
+    pub fn new(default: LinkConditions) -> Self {
+        Self {
+            default,
+            links: HashMap::new(),
+        }
+    }
+
+    /// Links between nodes placed in regions: `regions[i]` is the region of node `i` and
+    /// `conditions(a, b)` gives the conditions of the links from region `a` to region `b`.
+    pub fn from_regions<F>(regions: &[usize], conditions: F) -> Self
+    where
+        F: Fn(usize, usize) -> LinkConditions,
+    {
+        let mut matrix = Self::default();
+        for (from, from_region) in regions.iter().enumerate() {
+            for (to, to_region) in regions.iter().enumerate() {
+                if from != to {
+                    matrix.set(from, to, conditions(*from_region, *to_region));
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Sets the conditions of the data sent by node `from` to node `to`
+    pub fn set(&mut self, from: usize, to: usize, conditions: LinkConditions) -> &mut Self {
+        self.links.insert((from, to), conditions);
+        self
+    }
+
+    /// Sets the conditions of the data sent in both directions between nodes `a` and `b`
+    pub fn set_symmetric(&mut self, a: usize, b: usize, conditions: LinkConditions) -> &mut Self {
+        self.set(a, b, conditions).set(b, a, conditions)
+    }
+
+    pub fn get(&self, from: usize, to: usize) -> LinkConditions {
+        self.links.get(&(from, to)).copied().unwrap_or(self.default)
+    }
+}
+
+/// A proxy listening on an ephemeral local port and forwarding all connections to a target
+/// address, with the conditions of the link in each direction. It stops accepting connections
+/// when dropped, while the connections already forwarded last until either end closes them.
+pub struct LatencyProxy {
+    address: SocketAddr,
+    target: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl LatencyProxy {
+    /// `outbound` applies to the data sent to the target, `inbound` to the data it sends back
+    pub fn start(
+        target: SocketAddr,
+        outbound: LinkConditions,
+        inbound: LinkConditions,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let accept_shutdown = Arc::clone(&shutdown);
+        thread::Builder::new()
+            .name(format!("latency-proxy-{}", address.port()))
+            .spawn(move || {
+                for client in listener.incoming() {
+                    if accept_shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let result = client.and_then(|client| {
+                        let server = TcpStream::connect(target)?;
+                        forward(client, server, outbound, inbound)
+                    });
+                    if let Err(e) = result {
+                        warn!("Proxy {} failed to forward to {}: {}", address, target, e);
+                    }
+                }
+            })?;
+        Ok(Self {
+            address,
+            target,
+            shutdown,
+        })
+    }
+
+    /// The address to dial instead of the target
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+}
+
+impl Drop for LatencyProxy {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake up the accepting thread so that it sees the shutdown
+        let _ = TcpStream::connect(self.address);
+    }
+}
+
+fn forward(
+    client: TcpStream,
+    server: TcpStream,
+    outbound: LinkConditions,
+    inbound: LinkConditions,
+) -> io::Result<()> {
+    client.set_nodelay(true)?;
+    server.set_nodelay(true)?;
+    let (client_reader, server_reader) = (client.try_clone()?, server.try_clone()?);
+    thread::spawn(move || pipe(client_reader, server, outbound));
+    thread::spawn(move || pipe(server_reader, client, inbound));
+    Ok(())
+}
+
+/// Copies the data read from `from` to `to`, each chunk once its delay elapsed. Chunks are
+/// written in the order they were read, as TCP delivers them, so a delayed chunk also holds back
+/// the chunks behind it.
+fn pipe(mut from: TcpStream, mut to: TcpStream, conditions: LinkConditions) {
+    let (sender, receiver) = mpsc::channel::<(Instant, Vec<u8>)>();
+    let writer = thread::spawn(move || {
+        for (due, data) in receiver {
+            let now = Instant::now();
+            if due > now {
+                thread::sleep(due - now);
+            }
+            if to.write_all(&data).is_err() {
+                break;
+            }
+        }
+        let _ = to.shutdown(Shutdown::Write);
+    });
+
+    let mut rng = rand::thread_rng();
+    let mut last_due = Instant::now();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    loop {
+        let len = match from.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(len) => len,
+        };
+        let due = max(Instant::now() + conditions.sample_delay(&mut rng), last_due);
+        last_due = due;
+        if sender.send((due, buffer[..len].to_vec())).is_err() {
+            break;
+        }
+    }
+    drop(sender);
+    let _ = writer.join();
+    let _ = from.shutdown(Shutdown::Read);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_delays_both_directions() {
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_address = server.local_addr().unwrap();
+        let echo = thread::spawn(move || {
+            let (mut stream, _) = server.accept().unwrap();
+            let mut buffer = [0; 4];
+            stream.read_exact(&mut buffer).unwrap();
+            stream.write_all(&buffer).unwrap();
+        });
+
+        let latency = Duration::from_millis(100);
+        let proxy = LatencyProxy::start(
+            server_address,
+            LinkConditions::new(latency, Duration::from_millis(0), 0),
+            LinkConditions::new(latency, Duration::from_millis(0), 0),
+        )
+        .unwrap();
+        let start = Instant::now();
+        let mut client = TcpStream::connect(proxy.address()).unwrap();
+        client.write_all(b"ping").unwrap();
+        let mut buffer = [0; 4];
+        client.read_exact(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"ping");
+        assert!(start.elapsed() >= latency * 2);
+        echo.join().unwrap();
+    }
+
+    #[test]
+    fn matrix_from_regions() {
+        let near = LinkConditions::new(Duration::from_millis(5), Duration::from_millis(1), 0);
+        let far = LinkConditions::new(Duration::from_millis(80), Duration::from_millis(10), 1);
+        let mut matrix =
+            LinkMatrix::from_regions(&[0, 0, 1], |a, b| if a == b { near } else { far });
+        assert_eq!(matrix.get(0, 1), near);
+        assert_eq!(matrix.get(1, 2), far);
+        assert_eq!(matrix.get(2, 0), far);
+
+        matrix.set(2, 0, near);
+        assert_eq!(matrix.get(2, 0), near);
+        assert_eq!(matrix.get(0, 2), far);
+        // Pairs outside of the regions get the default conditions
+        assert_eq!(matrix.get(0, 3), LinkConditions::default());
+    }
+}
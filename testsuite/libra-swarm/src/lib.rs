@@ -4,4 +4,6 @@
 #![forbid(unsafe_code)]
 
 pub mod client;
+pub mod latency_proxy;
 pub mod swarm;
+pub mod topology;
@@ -3,8 +3,11 @@
 
 #![forbid(unsafe_code)]
 
-use libra_config::config::{NodeConfig, RoleType, TestConfig};
-use libra_swarm::{client, swarm::LibraSwarm};
+use libra_config::config::{NodeConfig, TestConfig};
+use libra_swarm::{
+    client,
+    topology::{LibraSwarmTopology, SwarmTopology},
+};
 use libra_temppath::TempPath;
 use libra_types::on_chain_config::VMPublishingOption;
 use std::path::Path;
@@ -27,8 +30,11 @@ struct Args {
     /// If unspecified, a temporary dir will be used and auto deleted.
     #[structopt(short = "c", long)]
     pub config_dir: Option<String>,
-    /// If greater than 0, starts a full node swarm connected to the first node in the validator
-    /// swarm.
+    /// Number of validator full nodes, spread over the validators
+    #[structopt(short = "v", long, default_value = "0")]
+    pub num_validator_full_nodes: usize,
+    /// Number of public full nodes, spread over the validator full nodes if any, or else over the
+    /// validators
     #[structopt(short = "f", long, default_value = "0")]
     pub num_full_nodes: usize,
 }
@@ -46,44 +52,16 @@ fn main() {
 
     libra_logger::Logger::new().init();
 
-    let mut validator_swarm = LibraSwarm::configure_swarm(
-        num_nodes,
-        RoleType::Validator,
+    let mut topology = LibraSwarmTopology::configure(
+        SwarmTopology::new(num_nodes, args.num_validator_full_nodes, num_full_nodes),
         args.config_dir.clone(),
-        Some(dev_config.clone()), /* template config */
-        None,                     /* upstream_config_dir */
+        Some(dev_config), /* template config */
     )
-    .expect("Failed to configure validator swarm");
-
-    let mut full_node_swarm = if num_full_nodes > 0 {
-        Some(
-            LibraSwarm::configure_swarm(
-                num_full_nodes,
-                RoleType::FullNode,
-                None,             /* config dir */
-                Some(dev_config), /* template config */
-                Some(String::from(
-                    validator_swarm
-                        .dir
-                        .as_ref()
-                        .join("0")
-                        .to_str()
-                        .expect("Failed to convert std::fs::Path to String"),
-                )),
-            )
-            .expect("Failed to configure full node swarm"),
-        )
-    } else {
-        None
-    };
-    validator_swarm
-        .launch_attempt(RoleType::Validator, !args.enable_logging)
-        .expect("Failed to launch validator swarm");
-    if let Some(ref mut swarm) = full_node_swarm {
-        swarm
-            .launch_attempt(RoleType::FullNode, !args.enable_logging)
-            .expect("Failed to launch full node swarm");
-    }
+    .expect("Failed to configure swarm");
+    topology
+        .launch(!args.enable_logging)
+        .expect("Failed to launch swarm");
+    let validator_swarm = &topology.validators;
 
     let faucet_key_file_path = &validator_swarm.config.faucet_key_path;
     let validator_config = NodeConfig::load(&validator_swarm.config.config_files[0]).unwrap();
@@ -134,15 +112,22 @@ fn main() {
         faucet_key_file_path, node_address_list,
     );
 
-    if let Some(ref swarm) = full_node_swarm {
-        let full_node_config = NodeConfig::load(&swarm.config.config_files[0]).unwrap();
-        println!("To connect to the full nodes you just spawned, use this command:");
-        println!(
-            "\tcargo run --bin cli -- -u {} -m {:?} --waypoint {}",
-            format!("http://localhost:{}", full_node_config.rpc.address.port()),
-            faucet_key_file_path,
-            waypoint,
-        );
+    for (kind, ports) in &[
+        ("validator full nodes", topology.validator_full_node_ports()),
+        ("public full nodes", topology.public_full_node_ports()),
+    ] {
+        if let Some(port) = ports.first() {
+            println!(
+                "To connect to the {} you just spawned, use this command:",
+                kind
+            );
+            println!(
+                "\tcargo run --bin cli -- -u {} -m {:?} --waypoint {}",
+                format!("http://localhost:{}", port),
+                faucet_key_file_path,
+                waypoint,
+            );
+        }
     }
 
     if args.start_client {
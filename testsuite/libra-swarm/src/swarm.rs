@@ -1,11 +1,18 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{Context, Result};
+use crate::latency_proxy::{LatencyProxy, LinkMatrix};
+use anyhow::{ensure, format_err, Context, Result};
 use config_builder::{FullNodeConfig, SwarmConfig, ValidatorConfig};
 use debug_interface::NodeDebugClient;
-use libra_config::config::{NodeConfig, RoleType};
+use libra_config::{
+    config::{DiscoveryMethod, NodeConfig, RoleType, SeedPeersConfig},
+    generator,
+    network_id::NetworkId,
+    utils,
+};
 use libra_logger::prelude::*;
+use libra_network_address::{parse_ip_tcp, NetworkAddress};
 use libra_temppath::TempPath;
 use libra_types::account_address::AccountAddress;
 use std::{
@@ -13,9 +20,11 @@ use std::{
     env,
     fs::File,
     io::{self, Read},
+    net::{Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
     process::{Child, Command},
     str::FromStr,
+    time::Duration,
 };
 use thiserror::Error;
 
@@ -142,6 +151,14 @@ impl LibraNode {
         }
     }
 
+    /// Moves the clock of the node forward by `duration`, returning its new time. The node must
+    /// have been configured with an adjustable clock.
+    pub fn advance_time(&mut self, duration: Duration) -> Result<Duration> {
+        self.debug_client
+            .advance_time(duration)
+            .with_context(|| format!("Failed to advance the time of node {}", self.node_id))
+    }
+
     pub fn check_connectivity(&mut self, expected_peers: i64) -> bool {
         let connected_peers = format!(
             "libra_network_peers{{role_type={},state=connected}}",
@@ -223,6 +240,8 @@ pub struct LibraSwarm {
     // Maps the node id of a node to the LibraNode struct
     pub nodes: HashMap<String, LibraNode>,
     pub config: SwarmConfig,
+    // Proxies between the validators, when latency is injected
+    pub proxies: Vec<LatencyProxy>,
 }
 
 #[derive(Debug, Error)]
@@ -297,6 +316,41 @@ impl LibraSwarm {
         config_dir: Option<String>,
         template: Option<NodeConfig>,
         upstream_config_dir: Option<String>,
+    ) -> Result<LibraSwarm> {
+        let upstream = if role.is_validator() {
+            None
+        } else {
+            let upstream_config_dir = upstream_config_dir.expect("No upstream node for full nodes");
+            Some((PathBuf::from(upstream_config_dir), NetworkId::Public))
+        };
+        Self::configure(num_nodes, role, config_dir, template, upstream)
+    }
+
+    /// Configures a swarm of full nodes connected to the node of which the config is in
+    /// `upstream_config_dir`, over a new network with `network_id` added to the upstream node.
+    /// The upstream node, be it a validator or a full node, must not be launched yet.
+    pub fn configure_full_node_swarm(
+        num_nodes: usize,
+        config_dir: Option<String>,
+        template: Option<NodeConfig>,
+        upstream_config_dir: &Path,
+        network_id: NetworkId,
+    ) -> Result<LibraSwarm> {
+        Self::configure(
+            num_nodes,
+            RoleType::FullNode,
+            config_dir,
+            template,
+            Some((upstream_config_dir.to_path_buf(), network_id)),
+        )
+    }
+
+    fn configure(
+        num_nodes: usize,
+        role: RoleType,
+        config_dir: Option<String>,
+        template: Option<NodeConfig>,
+        upstream: Option<(PathBuf, NetworkId)>,
     ) -> Result<LibraSwarm> {
         LibraNode::prepare();
 
@@ -317,18 +371,29 @@ impl LibraSwarm {
             validator_builder.num_nodes = num_nodes;
             SwarmConfig::build(&validator_builder, config_path)?
         } else {
-            let upstream_config_dir = upstream_config_dir.expect("No upstream node for full nodes");
-            let upstream_config_file = PathBuf::from(upstream_config_dir).join("node.config.toml");
-            let mut validator_config = NodeConfig::load(&upstream_config_file)?;
-            let genesis = validator_config.execution.genesis.as_ref();
+            let (upstream_config_dir, network_id) =
+                upstream.expect("No upstream node for full nodes");
+            let upstream_config_file = upstream_config_dir.join("node.config.toml");
+            let mut upstream_config = NodeConfig::load(&upstream_config_file)?;
+            let genesis = upstream_config.execution.genesis.as_ref();
             let mut full_node_builder = FullNodeConfig::new();
             full_node_builder.num_full_nodes = num_nodes;
+            full_node_builder.network_id = network_id;
             full_node_builder.genesis =
-                Some(genesis.expect("Missing genesis from validator").clone());
+                Some(genesis.expect("Missing genesis from upstream node").clone());
+            // Upstream nodes may serve several networks, each on its own port
+            let port = utils::get_available_port();
+            full_node_builder.listen_address =
+                NetworkAddress::from_str(&format!("/ip4/0.0.0.0/tcp/{}", port))?;
+            full_node_builder.advertised_address =
+                NetworkAddress::from_str(&format!("/ip4/127.0.0.1/tcp/{}", port))?;
             full_node_builder.template(node_config);
-            full_node_builder.extend_validator(&mut validator_config)?;
-            validator_config.save(&upstream_config_file)?;
-            full_node_builder.bootstrap = validator_config.full_node_networks[0]
+            full_node_builder.extend_validator(&mut upstream_config)?;
+            upstream_config.save(&upstream_config_file)?;
+            full_node_builder.bootstrap = upstream_config
+                .full_node_networks
+                .last()
+                .expect("Missing the network added to the upstream node")
                 .discovery_method
                 .advertised_address();
             SwarmConfig::build(&full_node_builder, config_path)?
@@ -338,9 +403,72 @@ impl LibraSwarm {
             dir: swarm_config_dir,
             nodes: HashMap::new(),
             config,
+            proxies: vec![],
         })
     }
 
+    /// Makes every validator dial every other one through a proxy with the conditions of their
+    /// link. Gossip discovery is disabled, so that the validators only know the addresses of the
+    /// proxies. Must be called before the swarm is launched.
+    pub fn inject_latency(&mut self, links: &LinkMatrix) -> Result<()> {
+        ensure!(
+            self.nodes.is_empty(),
+            "Latency must be injected before the swarm is launched"
+        );
+        let mut configs = self
+            .config
+            .config_files
+            .iter()
+            .map(NodeConfig::load)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut listen_ports = vec![];
+        for config in &configs {
+            let network = config
+                .validator_network
+                .as_ref()
+                .ok_or_else(|| format_err!("Latency can only be injected between validators"))?;
+            let ((_, port), _) = parse_ip_tcp(network.listen_address.as_slice())
+                .ok_or_else(|| format_err!("Invalid listen address {}", network.listen_address))?;
+            listen_ports.push(port);
+        }
+
+        let mut proxies = vec![];
+        let mut seed_peers = vec![];
+        for from in 0..configs.len() {
+            let mut peers = SeedPeersConfig::default();
+            for (to, port) in listen_ports.iter().enumerate() {
+                if from == to {
+                    continue;
+                }
+                let proxy = LatencyProxy::start(
+                    SocketAddr::from((Ipv4Addr::LOCALHOST, *port)),
+                    links.get(from, to),
+                    links.get(to, from),
+                )?;
+                let network = configs[to].validator_network.as_ref().unwrap();
+                peers.extend(generator::build_seed_peers(
+                    network,
+                    NetworkAddress::from(proxy.address()),
+                ));
+                proxies.push(proxy);
+            }
+            seed_peers.push(peers);
+        }
+
+        for ((config, path), peers) in configs
+            .iter_mut()
+            .zip(self.config.config_files.iter())
+            .zip(seed_peers)
+        {
+            let network = config.validator_network.as_mut().unwrap();
+            network.discovery_method = DiscoveryMethod::None;
+            network.seed_peers = peers;
+            config.save(path)?;
+        }
+        self.proxies = proxies;
+        Ok(())
+    }
+
     pub fn launch_attempt(
         &mut self,
         role: RoleType,
@@ -527,6 +655,15 @@ impl LibraSwarm {
             .collect()
     }
 
+    /// Moves the clocks of all the nodes forward by `duration`, one after the other. The nodes
+    /// must have been configured with an adjustable clock, see `TestConfig::adjustable_time`.
+    pub fn advance_time(&mut self, duration: Duration) -> Result<()> {
+        for node in self.nodes.values_mut() {
+            node.advance_time(duration)?;
+        }
+        Ok(())
+    }
+
     pub fn get_validator(&self, idx: usize) -> Option<&LibraNode> {
         let node_id = format!("{}", idx);
         self.nodes.get(&node_id)
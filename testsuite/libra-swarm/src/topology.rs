@@ -0,0 +1,214 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Swarms of validators with full nodes chained behind them: validator full nodes (VFNs) connect
+//! to the validators over a private network, and public full nodes (PFNs) connect to the VFNs
+//! over the public network. Transactions submitted to a PFN are forwarded by the mempools up the
+//! chain, and state sync brings the committed transactions back down.
+
+use crate::swarm::{LibraSwarm, SwarmLaunchFailure};
+use anyhow::Result;
+use libra_config::{
+    config::{NodeConfig, RoleType},
+    network_id::NetworkId,
+};
+use std::path::{Path, PathBuf};
+
+/// The number of nodes of each kind in a topology
+#[derive(Clone, Copy, Debug)]
+pub struct SwarmTopology {
+    pub num_validators: usize,
+    /// Spread over the validators, round robin
+    pub num_validator_full_nodes: usize,
+    /// Spread over the validator full nodes, round robin, or over the validators if there are no
+    /// validator full nodes
+    pub num_public_full_nodes: usize,
+}
+
+impl SwarmTopology {
+    pub fn new(
+        num_validators: usize,
+        num_validator_full_nodes: usize,
+        num_public_full_nodes: usize,
+    ) -> Self {
+        Self {
+            num_validators,
+            num_validator_full_nodes,
+            num_public_full_nodes,
+        }
+    }
+}
+
+/// A full node swarm, along with the index of its upstream node among the validators, or among all
+/// the validator full nodes for public full nodes behind them
+pub struct FullNodeSwarm {
+    pub upstream: usize,
+    pub swarm: LibraSwarm,
+}
+
+pub struct LibraSwarmTopology {
+    pub validators: LibraSwarm,
+    pub validator_full_nodes: Vec<FullNodeSwarm>,
+    /// Behind the validator full nodes, or behind the validators if there are none
+    pub public_full_nodes: Vec<FullNodeSwarm>,
+}
+
+/// Splits `count` nodes over `num_upstreams` upstream nodes, round robin, and returns the number of
+/// nodes behind each upstream node that has any.
+fn spread(count: usize, num_upstreams: usize) -> Vec<(usize, usize)> {
+    (0..num_upstreams.min(count))
+        .map(|upstream| {
+            let extra = if upstream < count % num_upstreams {
+                1
+            } else {
+                0
+            };
+            (upstream, count / num_upstreams + extra)
+        })
+        .collect()
+}
+
+fn sub_dir(config_dir: &Option<String>, name: String) -> Option<String> {
+    config_dir.as_ref().map(|dir| {
+        Path::new(dir)
+            .join(name)
+            .to_str()
+            .expect("Failed to convert std::fs::Path to String")
+            .to_string()
+    })
+}
+
+/// Configures a swarm of full nodes behind each upstream node that has any
+fn configure_full_nodes(
+    upstreams: &[PathBuf],
+    count: usize,
+    network_id: NetworkId,
+    config_dir: &Option<String>,
+    name: &str,
+    template: &NodeConfig,
+) -> Result<Vec<FullNodeSwarm>> {
+    spread(count, upstreams.len())
+        .into_iter()
+        .map(|(upstream, num_nodes)| {
+            let swarm = LibraSwarm::configure_full_node_swarm(
+                num_nodes,
+                sub_dir(config_dir, format!("{}-{}", name, upstream)),
+                Some(template.clone()),
+                &upstreams[upstream],
+                network_id.clone(),
+            )?;
+            Ok(FullNodeSwarm { upstream, swarm })
+        })
+        .collect()
+}
+
+fn config_dirs(swarm: &LibraSwarm) -> Vec<PathBuf> {
+    swarm
+        .config
+        .config_files
+        .iter()
+        .map(|path| {
+            path.parent()
+                .expect("Config files are in the directory of their node")
+                .to_path_buf()
+        })
+        .collect()
+}
+
+impl LibraSwarmTopology {
+    /// Generates the configs of all the nodes. With `config_dir`, the configs of the validators
+    /// are in its `validators` directory, those of the full nodes behind the upstream node `i` in
+    /// its `vfn-i` and `pfn-i` directories.
+    pub fn configure(
+        topology: SwarmTopology,
+        config_dir: Option<String>,
+        template: Option<NodeConfig>,
+    ) -> Result<Self> {
+        let template = template.unwrap_or_default();
+        let validators = LibraSwarm::configure_swarm(
+            topology.num_validators,
+            RoleType::Validator,
+            sub_dir(&config_dir, "validators".to_string()),
+            Some(template.clone()),
+            None,
+        )?;
+        let validator_full_nodes = configure_full_nodes(
+            &config_dirs(&validators),
+            topology.num_validator_full_nodes,
+            NetworkId::vfn_network(),
+            &config_dir,
+            "vfn",
+            &template,
+        )?;
+        let public_upstreams = if validator_full_nodes.is_empty() {
+            config_dirs(&validators)
+        } else {
+            validator_full_nodes
+                .iter()
+                .flat_map(|vfns| config_dirs(&vfns.swarm))
+                .collect()
+        };
+        let public_full_nodes = configure_full_nodes(
+            &public_upstreams,
+            topology.num_public_full_nodes,
+            NetworkId::Public,
+            &config_dir,
+            "pfn",
+            &template,
+        )?;
+        Ok(Self {
+            validators,
+            validator_full_nodes,
+            public_full_nodes,
+        })
+    }
+
+    /// Launches the validators, then the validator full nodes and the public full nodes
+    pub fn launch(&mut self, disable_logging: bool) -> Result<(), SwarmLaunchFailure> {
+        self.validators
+            .launch_attempt(RoleType::Validator, disable_logging)?;
+        for full_nodes in self
+            .validator_full_nodes
+            .iter_mut()
+            .chain(self.public_full_nodes.iter_mut())
+        {
+            full_nodes
+                .swarm
+                .launch_attempt(RoleType::FullNode, disable_logging)?;
+        }
+        Ok(())
+    }
+
+    /// The JSON RPC ports of the validator full nodes, in the order of their upstream validators
+    pub fn validator_full_node_ports(&self) -> Vec<u16> {
+        Self::client_ports(&self.validator_full_nodes)
+    }
+
+    /// The JSON RPC ports of the public full nodes, in the order of their upstream nodes
+    pub fn public_full_node_ports(&self) -> Vec<u16> {
+        Self::client_ports(&self.public_full_nodes)
+    }
+
+    fn client_ports(full_nodes: &[FullNodeSwarm]) -> Vec<u16> {
+        full_nodes
+            .iter()
+            .flat_map(|full_nodes| {
+                (0..full_nodes.swarm.nodes.len())
+                    .map(move |index| full_nodes.swarm.get_client_port(index))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spread;
+
+    #[test]
+    fn spread_round_robin() {
+        assert_eq!(spread(5, 2), vec![(0, 3), (1, 2)]);
+        assert_eq!(spread(2, 4), vec![(0, 1), (1, 1)]);
+        assert_eq!(spread(4, 4), vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+        assert!(spread(0, 4).is_empty());
+    }
+}
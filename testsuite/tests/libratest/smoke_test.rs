@@ -12,7 +12,11 @@ use libra_json_rpc::views::{ScriptView, TransactionDataView};
 use libra_key_manager::libra_interface::{JsonRpcLibraInterface, LibraInterface};
 use libra_logger::prelude::*;
 use libra_secure_storage::{CryptoStorage, KVStorage, Storage, Value};
-use libra_swarm::swarm::{LibraNode, LibraSwarm};
+use libra_swarm::{
+    latency_proxy::{LinkConditions, LinkMatrix},
+    swarm::{LibraNode, LibraSwarm},
+    topology::{LibraSwarmTopology, SwarmTopology},
+};
 use libra_temppath::TempPath;
 use libra_types::{
     account_address::AccountAddress,
@@ -46,23 +50,29 @@ struct TestEnvironment {
 
 impl TestEnvironment {
     fn new(num_validators: usize) -> Self {
-        ::libra_logger::Logger::new().init();
-        let mut template = NodeConfig::default();
-        template.test = Some(TestConfig::open_module());
-        template.state_sync.chunk_limit = 5;
-        template.consensus.safety_rules.backend =
-            SecureBackend::OnDiskStorage(OnDiskStorageConfig::default());
-        template.execution.backend = SecureBackend::OnDiskStorage(OnDiskStorageConfig::default());
-
         let validator_swarm = LibraSwarm::configure_swarm(
             num_validators,
             RoleType::Validator,
             None,
-            Some(template),
+            Some(Self::template()),
             None,
         )
         .unwrap();
+        Self::with_validator_swarm(validator_swarm)
+    }
 
+    fn template() -> NodeConfig {
+        let mut template = NodeConfig::default();
+        template.test = Some(TestConfig::open_module());
+        template.state_sync.chunk_limit = 5;
+        template.consensus.safety_rules.backend =
+            SecureBackend::OnDiskStorage(OnDiskStorageConfig::default());
+        template.execution.backend = SecureBackend::OnDiskStorage(OnDiskStorageConfig::default());
+        template
+    }
+
+    fn with_validator_swarm(validator_swarm: LibraSwarm) -> Self {
+        ::libra_logger::Logger::new().init();
         let mnemonic_file = libra_temppath::TempPath::new();
         mnemonic_file
             .create_as_file()
@@ -142,6 +152,7 @@ impl TestEnvironment {
             /* faucet server */ None,
             Some(mnemonic_file_path),
             waypoint.unwrap_or_else(|| self.validator_swarm.config.waypoint),
+            None,
         )
         .unwrap()
     }
@@ -453,6 +464,47 @@ fn test_basic_fault_tolerance() {
     test_smoke_script(client_proxy);
 }
 
+#[test]
+fn smoke_test_multi_region() {
+    // Two validators in each of two regions, with a WAN link between the regions
+    let local = LinkConditions::new(Duration::from_millis(2), Duration::from_millis(1), 0);
+    let wan = LinkConditions::new(Duration::from_millis(75), Duration::from_millis(25), 2);
+    let links = LinkMatrix::from_regions(&[0, 0, 1, 1], |a, b| if a == b { local } else { wan });
+
+    let mut env = TestEnvironment::new(4);
+    env.validator_swarm.inject_latency(&links).unwrap();
+    env.launch_swarm(RoleType::Validator);
+    // Submit to a validator of the other region than the first proposers
+    let client_proxy = env.get_validator_ac_client(3, None);
+    test_smoke_script(client_proxy);
+
+    // Consensus keeps committing and every validator keeps up, despite the WAN link
+    assert!(env.validator_swarm.wait_for_all_nodes_to_catchup());
+    let last_committed_round = "libra_consensus_last_committed_round{}";
+    let rounds: Vec<_> = (0..4)
+        .map(|index| {
+            env.get_validator_debug_interface_client(index)
+                .get_node_metric(last_committed_round)
+                .unwrap()
+                .unwrap_or(0)
+        })
+        .collect();
+    sleep(Duration::from_secs(5));
+    for (index, round) in rounds.into_iter().enumerate() {
+        let new_round = env
+            .get_validator_debug_interface_client(index)
+            .get_node_metric(last_committed_round)
+            .unwrap()
+            .unwrap_or(0);
+        assert!(
+            new_round > round,
+            "Validator {} did not commit since round {}",
+            index,
+            round
+        );
+    }
+}
+
 #[test]
 fn test_basic_restartability() {
     let (mut env, mut client_proxy) = setup_swarm_and_client_proxy(4, 0);
@@ -1020,6 +1072,63 @@ fn test_full_node_basic_flow() {
     ));
 }
 
+#[test]
+fn test_full_node_topology() {
+    // 4 validators, a validator full node behind 2 of them, and a public full node behind each of
+    // the validator full nodes
+    let mut topology = LibraSwarmTopology::configure(
+        SwarmTopology::new(4, 2, 2),
+        None,
+        Some(TestEnvironment::template()),
+    )
+    .unwrap();
+    topology.launch(false).unwrap();
+    let vfn_ports = topology.validator_full_node_ports();
+    let pfn_ports = topology.public_full_node_ports();
+    let LibraSwarmTopology {
+        validators,
+        validator_full_nodes: _validator_full_nodes,
+        public_full_nodes: _public_full_nodes,
+    } = topology;
+    let env = TestEnvironment::with_validator_swarm(validators);
+
+    let mut validator_client = env.get_validator_ac_client(0, None);
+    let mut vfn_client = env.get_json_rpc_client(vfn_ports[1], None);
+    let mut pfn_client = env.get_json_rpc_client(pfn_ports[0], None);
+    let mut other_pfn_client = env.get_json_rpc_client(pfn_ports[1], None);
+    for client in &mut [
+        &mut validator_client,
+        &mut vfn_client,
+        &mut pfn_client,
+        &mut other_pfn_client,
+    ] {
+        client.create_next_account(false).unwrap();
+    }
+
+    // Minting through a public full node is forwarded up to the validators by the mempools of the
+    // public and validator full nodes, and the committed transaction is synced back down
+    pfn_client
+        .mint_coins(&["mintb", "0", "10", "LBR"], true)
+        .unwrap();
+    let sender_account = treasury_compliance_account_address();
+    let sequence = pfn_client
+        .get_sequence_number(&["sequence", &sender_account.to_string(), "true"])
+        .unwrap();
+    for client in &mut [
+        &mut validator_client,
+        &mut vfn_client,
+        &mut other_pfn_client,
+    ] {
+        client
+            .wait_for_transaction(sender_account, sequence)
+            .unwrap();
+        assert!(compare_balances(
+            vec![(10.0, "LBR".to_string())],
+            client.get_balances(&["b", "0"]).unwrap()
+        ));
+    }
+}
+
 #[test]
 fn test_e2e_reconfiguration() {
     let (env, mut client_proxy_1) = setup_swarm_and_client_proxy(3, 1);
@@ -1328,3 +1437,46 @@ fn test_key_manager_consensus_rotation() {
         );
     }
 }
+
+#[test]
+fn test_time_travel() {
+    let mut template = TestEnvironment::template();
+    if let Some(test) = template.test.as_mut() {
+        test.adjustable_time = true;
+    }
+    let validator_swarm =
+        LibraSwarm::configure_swarm(4, RoleType::Validator, None, Some(template), None).unwrap();
+    let mut env = TestEnvironment::with_validator_swarm(validator_swarm);
+    env.launch_swarm(RoleType::Validator);
+    let mut client_proxy = env.get_validator_ac_client(0, None);
+    client_proxy.create_next_account(false).unwrap();
+    client_proxy.create_next_account(false).unwrap();
+    client_proxy
+        .mint_coins(&["mintb", "0", "10", "LBR"], true)
+        .unwrap();
+    let before = Duration::from_micros(client_proxy.client.get_metadata().unwrap().timestamp);
+
+    let one_day = Duration::from_secs(24 * 3600);
+    env.validator_swarm.advance_time(one_day).unwrap();
+
+    // The next blocks are timestamped by the advanced clocks, without waiting for a day
+    let mut after = before;
+    for _ in 0..30 {
+        after = Duration::from_micros(client_proxy.client.get_metadata().unwrap().timestamp);
+        if after >= before + one_day {
+            break;
+        }
+        sleep(Duration::from_secs(1));
+    }
+    assert!(after >= before + one_day);
+
+    // The client signs transactions expiring a few minutes after its own clock, which the ledger
+    // is now a day ahead of
+    assert!(client_proxy
+        .transfer_coins(&["tb", "0", "1", "1", "LBR"], true)
+        .is_err());
+    assert!(compare_balances(
+        vec![(10.0, "LBR".to_string())],
+        client_proxy.get_balances(&["b", "0"]).unwrap()
+    ));
+}
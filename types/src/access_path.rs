@@ -41,7 +41,7 @@ use move_core_types::language_storage::{ModuleId, ResourceKey, StructTag, CODE_T
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{convert::TryFrom, fmt};
 
 #[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Ord, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
@@ -53,6 +53,14 @@ pub struct AccessPath {
 impl AccessPath {
     pub const CODE_TAG: u8 = 0;
     pub const RESOURCE_TAG: u8 = 1;
+    pub const SOURCE_MAP_TAG: u8 = 2;
+    /// The tag of the header and chunks of a resource too large to be stored inline, see
+    /// `chunked_resource`.
+    pub const CHUNKED_RESOURCE_TAG: u8 = 3;
+    /// The tag of the entries of a `Table`.
+    pub const TABLE_ENTRY_TAG: u8 = 4;
+    /// The tag of the error map published alongside a module, see `errmap`.
+    pub const ERROR_MAP_TAG: u8 = 5;
 
     pub fn new(address: AccountAddress, path: Vec<u8>) -> Self {
         AccessPath { address, path }
@@ -83,6 +91,40 @@ impl AccessPath {
             path,
         }
     }
+
+    /// The path of the compressed source map published alongside a module. It mirrors the code
+    /// path of the module, under its own tag.
+    pub fn source_map_access_path(key: &ModuleId) -> AccessPath {
+        let mut path = AccessPath::code_access_path_vec(key);
+        path[0] = AccessPath::SOURCE_MAP_TAG;
+        AccessPath {
+            address: *key.address(),
+            path,
+        }
+    }
+
+    /// The path of the LCS-serialized `ModuleErrorMap` published alongside a module. It mirrors
+    /// the code path of the module, under its own tag.
+    pub fn error_map_access_path(key: &ModuleId) -> AccessPath {
+        let mut path = AccessPath::code_access_path_vec(key);
+        path[0] = AccessPath::ERROR_MAP_TAG;
+        AccessPath {
+            address: *key.address(),
+            path,
+        }
+    }
+
+    /// The path of the entry for the LCS-serialized `key` in the table with the given `handle`.
+    /// The entries of a table are stored under an address derived from its handle rather than in
+    /// the account of its owner, each under its own path.
+    pub fn table_entry_access_path(handle: &[u8], key: &[u8]) -> AccessPath {
+        let handle_hash = HashValue::sha3_256_of(handle);
+        let address = AccountAddress::try_from(&handle_hash.as_ref()[..AccountAddress::LENGTH])
+            .expect("a hash is longer than an address");
+        let mut path = vec![AccessPath::TABLE_ENTRY_TAG];
+        path.extend_from_slice(HashValue::sha3_256_of(key).as_ref());
+        AccessPath { address, path }
+    }
 }
 
 impl fmt::Debug for AccessPath {
@@ -105,6 +147,10 @@ impl fmt::Display for AccessPath {
             match self.path[0] {
                 RESOURCE_TAG => write!(f, "type: Resource, ")?,
                 CODE_TAG => write!(f, "type: Module, ")?,
+                AccessPath::SOURCE_MAP_TAG => write!(f, "type: SourceMap, ")?,
+                AccessPath::CHUNKED_RESOURCE_TAG => write!(f, "type: ChunkedResource, ")?,
+                AccessPath::TABLE_ENTRY_TAG => write!(f, "type: TableEntry, ")?,
+                AccessPath::ERROR_MAP_TAG => write!(f, "type: ErrorMap, ")?,
                 tag => write!(f, "type: {:?}, ", tag)?,
             };
             write!(
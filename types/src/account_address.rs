@@ -1,10 +1,12 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
-use crate::transaction::authenticator::AuthenticationKey;
+use crate::transaction::authenticator::AccountPublicKey;
 use libra_crypto::ed25519::Ed25519PublicKey;
 
 pub use move_core_types::account_address::AccountAddress;
 
+/// The address of the account of a single Ed25519 key, see `AccountPublicKey::derived_address`
+/// for the other schemes
 pub fn from_public_key(public_key: &Ed25519PublicKey) -> AccountAddress {
-    AuthenticationKey::ed25519(public_key).derived_address()
+    AccountPublicKey::Ed25519(public_key.clone()).derived_address()
 }
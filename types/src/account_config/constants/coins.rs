@@ -7,6 +7,15 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 pub const COIN1_NAME: &str = "Coin1";
 pub const COIN2_NAME: &str = "Coin2";
 
+/// The `Coin1::Coin1` currency, as the type argument of generic resources, e.g.,
+/// `BalanceResource<Coin1>`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Coin1;
+
+/// The `Coin2::Coin2` currency, as the type argument of generic resources.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Coin2;
+
 pub fn coin1_tag() -> TypeTag {
     TypeTag::Struct(StructTag {
         address: CORE_CODE_ADDRESS,
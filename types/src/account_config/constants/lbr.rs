@@ -10,6 +10,10 @@ use once_cell::sync::Lazy;
 
 pub const LBR_NAME: &str = "LBR";
 
+/// The `LBR::LBR` currency, as the type argument of generic resources, e.g., `BalanceResource<LBR>`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LBR;
+
 pub static LBR_MODULE: Lazy<ModuleId> =
     Lazy::new(|| ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(LBR_NAME).unwrap()));
 pub static LBR_STRUCT_NAME: Lazy<Identifier> = Lazy::new(|| Identifier::new(LBR_NAME).unwrap());
@@ -1,12 +1,9 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, account_config::LIBRA_MODULE_NAME};
+use crate::account_address::AccountAddress;
 use anyhow::Result;
-use move_core_types::{
-    identifier::{IdentStr, Identifier},
-    move_resource::MoveResource,
-};
+use move_core_types::identifier::{IdentStr, Identifier};
 use serde::{Deserialize, Serialize};
 
 /// Struct that represents a BurnEvent.
@@ -37,8 +34,3 @@ impl BurnEvent {
         lcs::from_bytes(bytes).map_err(Into::into)
     }
 }
-
-impl MoveResource for BurnEvent {
-    const MODULE_NAME: &'static str = LIBRA_MODULE_NAME;
-    const STRUCT_NAME: &'static str = "BurnEvent";
-}
@@ -0,0 +1,27 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Struct that represents a MigrationEvent, emitted when the resources of an account are migrated
+/// to the next version of their layout.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrationEvent {
+    from_version: u64,
+    to_version: u64,
+}
+
+impl MigrationEvent {
+    pub fn from_version(&self) -> u64 {
+        self.from_version
+    }
+
+    pub fn to_version(&self) -> u64 {
+        self.to_version
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        lcs::from_bytes(bytes).map_err(Into::into)
+    }
+}
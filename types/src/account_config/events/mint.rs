@@ -1,12 +1,8 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::account_config::LIBRA_MODULE_NAME;
 use anyhow::Result;
-use move_core_types::{
-    identifier::{IdentStr, Identifier},
-    move_resource::MoveResource,
-};
+use move_core_types::identifier::{IdentStr, Identifier};
 use serde::{Deserialize, Serialize};
 
 /// Struct that represents a MintEvent.
@@ -31,8 +27,3 @@ impl MintEvent {
         lcs::from_bytes(bytes).map_err(Into::into)
     }
 }
-
-impl MoveResource for MintEvent {
-    const MODULE_NAME: &'static str = LIBRA_MODULE_NAME;
-    const STRUCT_NAME: &'static str = "MintEvent";
-}
@@ -3,7 +3,6 @@
 
 use crate::account_address::AccountAddress;
 use anyhow::Result;
-use move_core_types::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
 /// Struct that represents a NewBlockEvent.
@@ -32,8 +31,3 @@ impl NewBlockEvent {
         lcs::from_bytes(bytes).map_err(Into::into)
     }
 }
-
-impl MoveResource for NewBlockEvent {
-    const MODULE_NAME: &'static str = "LibraBlock";
-    const STRUCT_NAME: &'static str = "NewBlockEvent";
-}
@@ -3,7 +3,6 @@
 
 use crate::event::EventKey;
 use anyhow::Result;
-use move_core_types::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
 /// Struct that represents a NewEpochEvent.
@@ -25,8 +24,3 @@ impl NewEpochEvent {
         crate::on_chain_config::new_epoch_event_key()
     }
 }
-
-impl MoveResource for NewEpochEvent {
-    const MODULE_NAME: &'static str = "LibraConfig";
-    const STRUCT_NAME: &'static str = "NewBlockEvent";
-}
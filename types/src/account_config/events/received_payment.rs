@@ -1,10 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{
-    account_address::AccountAddress,
-    account_config::{constants::ACCOUNT_MODULE_NAME, resources::AccountResource},
-};
+use crate::{account_address::AccountAddress, account_config::resources::AccountResource};
 use anyhow::Result;
 use move_core_types::{
     identifier::{IdentStr, Identifier},
@@ -55,8 +52,3 @@ impl ReceivedPaymentEvent {
         &self.currency_code
     }
 }
-
-impl MoveResource for ReceivedPaymentEvent {
-    const MODULE_NAME: &'static str = ACCOUNT_MODULE_NAME;
-    const STRUCT_NAME: &'static str = "ReceivedPaymentEvent";
-}
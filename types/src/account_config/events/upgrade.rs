@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use move_core_types::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
 /// Struct that represents a UpgradeEvent.
@@ -21,8 +20,3 @@ impl UpgradeEvent {
         lcs::from_bytes(bytes).map_err(Into::into)
     }
 }
-
-impl MoveResource for UpgradeEvent {
-    const MODULE_NAME: &'static str = "LibraWriteSetManager";
-    const STRUCT_NAME: &'static str = "UpgradeEvent";
-}
@@ -0,0 +1,23 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::event::EventHandle;
+use serde::{Deserialize, Serialize};
+
+/// The Rust representation of `AccountMigration::AccountVersion`: the version of the layout of the
+/// resources of an account. Accounts without it are at version 0.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountVersionResource {
+    version: u64,
+    migration_events: EventHandle,
+}
+
+impl AccountVersionResource {
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn migration_events(&self) -> &EventHandle {
+        &self.migration_events
+    }
+}
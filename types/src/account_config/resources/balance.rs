@@ -3,53 +3,62 @@
 
 use crate::{
     access_path::AccessPath,
-    account_config::constants::{lbr_type_tag, ACCOUNT_MODULE_NAME, CORE_CODE_ADDRESS},
+    account_config::constants::{CORE_CODE_ADDRESS, LBR},
 };
 use move_core_types::{
     language_storage::{StructTag, TypeTag},
     move_resource::MoveResource,
 };
 #[cfg(any(test, feature = "fuzzing"))]
-use proptest_derive::Arbitrary;
+use proptest::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 
-/// The balance resource held under an account.
+/// The balance resource held under an account, in the currency `C`, e.g., `BalanceResource<Coin1>`
+/// for `LibraAccount::Balance<Coin1::Coin1>`.
 #[derive(Debug, Serialize, Deserialize)]
-#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
-pub struct BalanceResource {
+pub struct BalanceResource<C = LBR> {
     coin: u64,
+    #[serde(skip)]
+    currency: PhantomData<C>,
 }
 
-impl BalanceResource {
+impl<C> BalanceResource<C> {
     pub fn new(coin: u64) -> Self {
-        Self { coin }
+        Self {
+            coin,
+            currency: PhantomData,
+        }
     }
 
     pub fn coin(&self) -> u64 {
         self.coin
     }
+}
 
-    // TODO/XXX: remove this once the MoveResource trait allows type arguments to `struct_tag`.
+/// The balances of currencies only known at runtime, e.g., from their currency code, are accessed
+/// through the struct tag of their `LibraAccount::Balance`.
+impl BalanceResource {
     pub fn struct_tag_for_currency(currency_typetag: TypeTag) -> StructTag {
         StructTag {
             address: CORE_CODE_ADDRESS,
-            name: BalanceResource::struct_identifier(),
-            module: BalanceResource::module_identifier(),
+            name: Self::struct_identifier(),
+            module: Self::module_identifier(),
             type_params: vec![currency_typetag],
         }
     }
 
-    // TODO: remove this once the MoveResource trait allows type arguments to `resource_path`.
     pub fn access_path_for(currency_typetag: TypeTag) -> Vec<u8> {
-        AccessPath::resource_access_vec(&BalanceResource::struct_tag_for_currency(currency_typetag))
+        AccessPath::resource_access_vec(&Self::struct_tag_for_currency(currency_typetag))
     }
 }
 
-impl MoveResource for BalanceResource {
-    const MODULE_NAME: &'static str = ACCOUNT_MODULE_NAME;
-    const STRUCT_NAME: &'static str = "Balance";
+#[cfg(any(test, feature = "fuzzing"))]
+impl<C: std::fmt::Debug + 'static> Arbitrary for BalanceResource<C> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
 
-    fn type_params() -> Vec<TypeTag> {
-        vec![lbr_type_tag()]
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<u64>().prop_map(Self::new).boxed()
     }
 }
@@ -15,6 +15,7 @@ use move_core_types::{
     move_resource::MoveResource,
 };
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// Struct that represents a CurrencyInfo resource
 #[derive(Debug, Serialize, Deserialize)]
@@ -34,11 +35,6 @@ pub struct CurrencyInfoResource {
     exchange_rate_update_events: EventHandle,
 }
 
-impl MoveResource for CurrencyInfoResource {
-    const MODULE_NAME: &'static str = "Libra";
-    const STRUCT_NAME: &'static str = "CurrencyInfo";
-}
-
 impl CurrencyInfoResource {
     pub fn currency_code(&self) -> &IdentStr {
         &self.currency_code
@@ -61,8 +57,12 @@ impl CurrencyInfoResource {
         (unrounded * 100.0).round() / 100.0
     }
 
+    /// Converts `amount` to LBR at the on-chain exchange rate, truncating the result like
+    /// `Libra::approx_lbr_for_value` does. Saturates at `u64::max_value()`, where the Move
+    /// conversion aborts.
     pub fn convert_to_lbr(&self, amount: u64) -> u64 {
-        (self.exchange_rate() * (amount as f32)) as u64
+        let lbr = (u128::from(amount) * u128::from(self.to_lbr_exchange_rate)) >> 32;
+        u64::try_from(lbr).unwrap_or_else(|_| u64::max_value())
     }
 
     pub fn struct_tag_for(currency_code: Identifier) -> StructTag {
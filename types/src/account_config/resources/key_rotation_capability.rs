@@ -1,8 +1,7 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, account_config::constants::ACCOUNT_MODULE_NAME};
-use move_core_types::move_resource::MoveResource;
+use crate::account_address::AccountAddress;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -12,8 +11,3 @@ use serde::{Deserialize, Serialize};
 pub struct KeyRotationCapabilityResource {
     account_address: AccountAddress,
 }
-
-impl MoveResource for KeyRotationCapabilityResource {
-    const MODULE_NAME: &'static str = ACCOUNT_MODULE_NAME;
-    const STRUCT_NAME: &'static str = "KeyRotationCapability";
-}
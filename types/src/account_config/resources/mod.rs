@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod account;
+pub mod account_version;
 pub mod balance;
 pub mod currency_info;
 pub mod key_rotation_capability;
@@ -11,6 +12,7 @@ pub mod vasp;
 pub mod withdraw_capability;
 
 pub use account::*;
+pub use account_version::*;
 pub use balance::*;
 pub use currency_info::*;
 pub use key_rotation_capability::*;
@@ -1,7 +1,6 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_core_types::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,8 +13,3 @@ impl RoleId {
         self.role_id
     }
 }
-
-impl MoveResource for RoleId {
-    const MODULE_NAME: &'static str = "Roles";
-    const STRUCT_NAME: &'static str = "RoleId";
-}
@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_address::AccountAddress;
-use move_core_types::move_resource::MoveResource;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -49,13 +48,3 @@ impl ChildVASP {
         self.parent_vasp_addr
     }
 }
-
-impl MoveResource for ParentVASP {
-    const MODULE_NAME: &'static str = "VASP";
-    const STRUCT_NAME: &'static str = "ParentVASP";
-}
-
-impl MoveResource for ChildVASP {
-    const MODULE_NAME: &'static str = "VASP";
-    const STRUCT_NAME: &'static str = "ChildVASP";
-}
@@ -4,13 +4,14 @@
 use crate::{
     account_address::AccountAddress,
     account_config::{
-        type_tag_for_currency_code, AccountResource, AccountRole, BalanceResource, ChildVASP,
-        ParentVASP, ACCOUNT_RECEIVED_EVENT_PATH, ACCOUNT_SENT_EVENT_PATH,
+        type_tag_for_currency_code, AccountResource, AccountRole, AccountVersionResource,
+        BalanceResource, ChildVASP, ParentVASP, ACCOUNT_RECEIVED_EVENT_PATH,
+        ACCOUNT_SENT_EVENT_PATH, LBR,
     },
     block_metadata::{LibraBlockResource, NEW_BLOCK_EVENT_PATH},
     event::EventHandle,
     libra_timestamp::LibraTimestampResource,
-    on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
+    on_chain_config::{apply_pending_config, ConfigurationResource, OnChainConfig, ValidatorSet},
     validator_config::ValidatorConfigResource,
 };
 use anyhow::{bail, Error, Result};
@@ -28,8 +29,15 @@ impl AccountState {
             .map(|opt_ar| opt_ar.map(|ar| ar.sent_events().key().get_creator_address()))
     }
 
+    /// Returns the Move resource `T` held under the account, e.g.,
+    /// `get_move_resource::<BalanceResource<Coin1>>()` for its
+    /// `LibraAccount::Balance<Coin1::Coin1>`.
+    pub fn get_move_resource<T: MoveResource + DeserializeOwned>(&self) -> Result<Option<T>> {
+        self.get_resource(&T::resource_path())
+    }
+
     pub fn get_account_resource(&self) -> Result<Option<AccountResource>> {
-        self.get_resource(&AccountResource::resource_path())
+        self.get_move_resource()
     }
 
     pub fn get_balance_resources(
@@ -39,9 +47,8 @@ impl AccountState {
         currency_codes
             .iter()
             .filter_map(|currency_code| {
+                // The currencies are only known at runtime, so they cannot be type arguments
                 let currency_type_tag = type_tag_for_currency_code(currency_code.to_owned());
-                // TODO: update this to use BalanceResource::resource_path once that takes type
-                // parameters
                 self.get_resource(&BalanceResource::access_path_for(currency_type_tag))
                     .transpose()
                     .map(|balance| balance.map(|b| (currency_code.to_owned(), b)))
@@ -49,24 +56,28 @@ impl AccountState {
             .collect()
     }
 
+    pub fn get_account_version_resource(&self) -> Result<Option<AccountVersionResource>> {
+        self.get_move_resource()
+    }
+
     pub fn get_configuration_resource(&self) -> Result<Option<ConfigurationResource>> {
-        self.get_resource(&ConfigurationResource::resource_path())
+        self.get_move_resource()
     }
 
     pub fn get_libra_timestamp_resource(&self) -> Result<Option<LibraTimestampResource>> {
-        self.get_resource(&LibraTimestampResource::resource_path())
+        self.get_move_resource()
     }
 
     pub fn get_validator_config_resource(&self) -> Result<Option<ValidatorConfigResource>> {
-        self.get_resource(&ValidatorConfigResource::resource_path())
+        self.get_move_resource()
     }
 
     pub fn get_account_role(&self) -> Result<Option<AccountRole>> {
         if self.0.contains_key(&ParentVASP::resource_path()) {
-            self.get_resource(&ParentVASP::resource_path())
+            self.get_move_resource()
                 .map(|r_opt| r_opt.map(AccountRole::ParentVASP))
         } else if self.0.contains_key(&ChildVASP::resource_path()) {
-            self.get_resource(&ChildVASP::resource_path())
+            self.get_move_resource()
                 .map(|r_opt| r_opt.map(AccountRole::ChildVASP))
         } else {
             // TODO: add role_id to Unknown
@@ -75,11 +86,30 @@ impl AccountState {
     }
 
     pub fn get_validator_set(&self) -> Result<Option<ValidatorSet>> {
-        self.get_resource(&ValidatorSet::CONFIG_ID.access_path().path)
+        self.get_config::<ValidatorSet>()
+    }
+
+    /// Returns the value of an on-chain config in effect in the current epoch, for the config
+    /// account: the stored value, or the update scheduled for an epoch that has started.
+    pub fn get_config<T: OnChainConfig>(&self) -> Result<Option<T>> {
+        let bytes = match self.get(&T::CONFIG_ID.access_path().path) {
+            Some(bytes) => bytes.clone(),
+            None => return Ok(None),
+        };
+        let bytes = match (
+            self.get(&T::CONFIG_ID.pending_access_path().path),
+            self.get_configuration_resource()?,
+        ) {
+            (Some(pending), Some(configuration)) => {
+                apply_pending_config(bytes, pending, configuration.epoch())?
+            }
+            _ => bytes,
+        };
+        T::deserialize_into_config(&bytes).map(Some)
     }
 
     pub fn get_libra_block_resource(&self) -> Result<Option<LibraBlockResource>> {
-        self.get_resource(&LibraBlockResource::resource_path())
+        self.get_move_resource()
     }
 
     pub fn get_event_handle_by_query_path(&self, query_path: &[u8]) -> Result<Option<EventHandle>> {
@@ -172,7 +202,7 @@ impl TryFrom<(&AccountResource, &BalanceResource)> for AccountState {
             lcs::to_bytes(account_resource)?,
         );
         btree_map.insert(
-            BalanceResource::resource_path(),
+            BalanceResource::<LBR>::resource_path(),
             lcs::to_bytes(balance_resource)?,
         );
 
@@ -7,7 +7,7 @@ use crate::{
     account_state::AccountState,
     event::EventKey,
     ledger_info::LedgerInfo,
-    proof::AccountStateProof,
+    proof::{AccountStateProof, AccountStatesProof},
     transaction::Version,
 };
 use anyhow::{anyhow, ensure, Error, Result};
@@ -25,6 +25,7 @@ use std::{convert::TryFrom, fmt};
 
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, CryptoHasher)]
 pub struct AccountStateBlob {
+    #[serde(with = "crate::serde_helpers::hex_bytes")]
     blob: Vec<u8>,
 }
 
@@ -212,6 +213,65 @@ impl AccountStateWithProof {
     }
 }
 
+/// The states of several accounts seen at the same version, with a proof sharing the
+/// authentication of the transaction at that version.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct AccountStatesWithProof {
+    /// The transaction version at which these account states are seen.
+    pub version: Version,
+    /// Blob values representing the account states, in the order of the queried accounts. A blob
+    /// that is not set means the account does not exist.
+    pub blobs: Vec<Option<AccountStateBlob>>,
+    /// The proof the client can use to authenticate the values.
+    pub proof: AccountStatesProof,
+}
+
+impl AccountStatesWithProof {
+    /// Constructor.
+    pub fn new(
+        version: Version,
+        blobs: Vec<Option<AccountStateBlob>>,
+        proof: AccountStatesProof,
+    ) -> Self {
+        Self {
+            version,
+            blobs,
+            proof,
+        }
+    }
+
+    /// Verifies the account state blobs with the proof, both carried by `self`, for the accounts
+    /// of `addresses` in the order they were queried. See `AccountStateWithProof::verify`.
+    pub fn verify(
+        &self,
+        ledger_info: &LedgerInfo,
+        version: Version,
+        addresses: &[AccountAddress],
+    ) -> Result<()> {
+        ensure!(
+            self.version == version,
+            "State version ({}) is not expected ({}).",
+            self.version,
+            version,
+        );
+
+        let address_hashes: Vec<_> = addresses.iter().map(CryptoHash::hash).collect();
+        self.proof
+            .verify(ledger_info, version, &address_hashes, &self.blobs)
+    }
+
+    /// Splits the states into one `AccountStateWithProof` per account.
+    pub fn into_account_states_with_proof(self) -> Vec<AccountStateWithProof> {
+        let version = self.version;
+        self.blobs
+            .into_iter()
+            .zip(self.proof.into_account_state_proofs())
+            .map(|(blob, proof)| AccountStateWithProof::new(version, blob, proof))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -239,6 +299,11 @@ mod tests {
         fn account_state_with_proof_lcs_roundtrip(account_state_with_proof in any::<AccountStateWithProof>()) {
             assert_canonical_encode_decode(account_state_with_proof);
         }
+
+        #[test]
+        fn account_states_with_proof_lcs_roundtrip(account_states_with_proof in any::<AccountStatesWithProof>()) {
+            assert_canonical_encode_decode(account_states_with_proof);
+        }
     }
 
     #[test]
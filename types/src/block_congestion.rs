@@ -0,0 +1,59 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{access_path::AccessPath, account_config::association_address};
+use move_core_types::{
+    gas_schedule::{GasAlgebra, GasConstants},
+    move_resource::MoveResource,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+
+/// Surcharges added to the minimum gas unit price of the gas schedule, each applying from a
+/// recent block fullness, in percent.
+pub const GAS_PRICE_TIERS: [(u64, u64); 3] = [(50, 1), (75, 5), (90, 20)];
+
+/// Tracks how full the recent blocks were. Published under the Libra root account, it is updated
+/// by the epilogue of every user transaction and by the block prologue.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockCongestionResource {
+    block_capacity: u64,
+    current_block_transactions: u64,
+    recent_fullness: u64,
+}
+
+impl BlockCongestionResource {
+    pub fn new(block_capacity: u64, current_block_transactions: u64, recent_fullness: u64) -> Self {
+        Self {
+            block_capacity,
+            current_block_transactions,
+            recent_fullness,
+        }
+    }
+
+    pub fn access_path() -> AccessPath {
+        AccessPath::new(association_address(), Self::resource_path())
+    }
+
+    /// Moving average of the fullness of the recent blocks, in percent of their capacity.
+    pub fn recent_fullness(&self) -> u64 {
+        self.recent_fullness
+    }
+
+    /// The minimum gas unit price a transaction must offer given the recent congestion, which
+    /// never exceeds the maximum price of the gas schedule.
+    pub fn min_gas_unit_price(&self, gas_constants: &GasConstants) -> u64 {
+        let surcharge = GAS_PRICE_TIERS
+            .iter()
+            .rev()
+            .find(|(fullness, _)| self.recent_fullness >= *fullness)
+            .map_or(0, |(_, surcharge)| *surcharge);
+        min(
+            gas_constants
+                .min_price_per_gas_unit
+                .get()
+                .saturating_add(surcharge),
+            gas_constants.max_price_per_gas_unit.get(),
+        )
+    }
+}
@@ -26,27 +26,35 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockMetadata {
     id: HashValue,
+    parent_id: HashValue,
     round: u64,
     timestamp_usecs: u64,
     // The vector has to be sorted to ensure consistent result among all nodes
     previous_block_votes: Vec<AccountAddress>,
     proposer: AccountAddress,
+    // The proposers of the rounds since the parent block which did not produce a certified block,
+    // in round order
+    failed_proposers: Vec<AccountAddress>,
 }
 
 impl BlockMetadata {
     pub fn new(
         id: HashValue,
+        parent_id: HashValue,
         round: u64,
         timestamp_usecs: u64,
         previous_block_votes: Vec<AccountAddress>,
         proposer: AccountAddress,
+        failed_proposers: Vec<AccountAddress>,
     ) -> Self {
         Self {
             id,
+            parent_id,
             round,
             timestamp_usecs,
             previous_block_votes,
             proposer,
+            failed_proposers,
         }
     }
 
@@ -54,18 +62,37 @@ impl BlockMetadata {
         self.id
     }
 
-    pub fn into_inner(self) -> Result<(u64, u64, Vec<AccountAddress>, AccountAddress)> {
+    pub fn parent_id(&self) -> HashValue {
+        self.parent_id
+    }
+
+    pub fn into_inner(
+        self,
+    ) -> Result<(
+        u64,
+        u64,
+        Vec<AccountAddress>,
+        AccountAddress,
+        HashValue,
+        Vec<AccountAddress>,
+    )> {
         Ok((
             self.round,
             self.timestamp_usecs,
-            self.previous_block_votes.clone(),
+            self.previous_block_votes,
             self.proposer,
+            self.parent_id,
+            self.failed_proposers,
         ))
     }
 
     pub fn proposer(&self) -> AccountAddress {
         self.proposer
     }
+
+    pub fn failed_proposers(&self) -> &[AccountAddress] {
+        &self.failed_proposers
+    }
 }
 
 pub fn new_block_event_key() -> EventKey {
@@ -84,17 +111,22 @@ pub static NEW_BLOCK_EVENT_PATH: Lazy<Vec<u8>> = Lazy::new(|| {
 pub struct LibraBlockResource {
     height: u64,
     new_block_events: EventHandle,
+    parent_block_hash: Vec<u8>,
+    failed_proposers: Vec<AccountAddress>,
 }
 
 impl LibraBlockResource {
     pub fn new_block_events(&self) -> &EventHandle {
         &self.new_block_events
     }
-}
 
-impl MoveResource for LibraBlockResource {
-    const MODULE_NAME: &'static str = "LibraBlock";
-    const STRUCT_NAME: &'static str = "BlockMetadata";
+    pub fn parent_block_hash(&self) -> &[u8] {
+        &self.parent_block_hash
+    }
+
+    pub fn failed_proposers(&self) -> &[AccountAddress] {
+        &self.failed_proposers
+    }
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -103,6 +135,8 @@ pub struct NewBlockEvent {
     proposer: AccountAddress,
     votes: Vec<AccountAddress>,
     timestamp: u64,
+    parent_block_hash: Vec<u8>,
+    failed_proposers: Vec<AccountAddress>,
 }
 
 impl NewBlockEvent {
@@ -111,12 +145,16 @@ impl NewBlockEvent {
         proposer: AccountAddress,
         votes: Vec<AccountAddress>,
         timestamp: u64,
+        parent_block_hash: Vec<u8>,
+        failed_proposers: Vec<AccountAddress>,
     ) -> Self {
         Self {
             round,
             proposer,
             votes,
             timestamp,
+            parent_block_hash,
+            failed_proposers,
         }
     }
     pub fn round(&self) -> u64 {
@@ -130,4 +168,8 @@ impl NewBlockEvent {
     pub fn votes(&self) -> Vec<AccountAddress> {
         self.votes.clone()
     }
+
+    pub fn failed_proposers(&self) -> &[AccountAddress] {
+        &self.failed_proposers
+    }
 }
@@ -0,0 +1,214 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resources whose serialized size exceeds the chunk size of the gas schedule are not stored at
+//! their resource path. They are split into chunks of at most that size instead, stored under
+//! keys derived from the resource path:
+//!
+//!   <address>/<CHUNKED_RESOURCE_TAG><hash of the struct tag>:          header
+//!   <address>/<CHUNKED_RESOURCE_TAG><hash of the struct tag><index>:   chunk `index`
+//!
+//! where `index` is a big-endian u32. The header records the length and the hash of the whole
+//! value, and the number of chunks. A resource is stored in exactly one of the two layouts, so a
+//! reader looks the resource path up first and falls back to the header.
+//!
+//! The layout is transparent to Move: the VM splits the resources of a write set when the
+//! transaction output is produced, and reassembles them when reading from storage. The write sets
+//! of write set transactions are stored as given, which is always readable since a resource path
+//! takes precedence over a header.
+//!
+//! The header and the chunks are ordinary entries of the `AccountStateBlob`, so the proof of an
+//! account state covers all of them. Reassembling a value checks it against the length and hash
+//! in the header, so a chunked value read from a proven account state is as authenticated as an
+//! inline one: a missing, extra, reordered or modified chunk is rejected.
+
+use crate::{
+    access_path::AccessPath,
+    account_state::AccountState,
+    write_set::{WriteOp, WriteSet, WriteSetMut},
+};
+use anyhow::{ensure, format_err, Result};
+use libra_crypto::HashValue;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+/// The header of a chunked value, stored at its header path.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ChunkedValueHeader {
+    /// The length of the whole value.
+    pub length: u64,
+    /// The number of chunks the value is split into.
+    pub num_chunks: u32,
+    /// The SHA3-256 hash of the whole value.
+    pub hash: HashValue,
+}
+
+/// Returns true if `access_path` is the path of a resource, i.e., a path that may be chunked.
+pub fn is_resource_path(access_path: &AccessPath) -> bool {
+    access_path.path.len() == 1 + HashValue::LENGTH
+        && access_path.path[0] == AccessPath::RESOURCE_TAG
+}
+
+/// Returns the path of the header of the chunked resource stored for `resource_path`.
+pub fn header_path(resource_path: &AccessPath) -> AccessPath {
+    let mut path = resource_path.path.clone();
+    path[0] = AccessPath::CHUNKED_RESOURCE_TAG;
+    AccessPath::new(resource_path.address, path)
+}
+
+/// Returns the path of chunk `index` of the chunked resource stored for `resource_path`.
+pub fn chunk_path(resource_path: &AccessPath, index: u32) -> AccessPath {
+    let mut access_path = header_path(resource_path);
+    access_path.path.extend_from_slice(&index.to_be_bytes());
+    access_path
+}
+
+/// Splits `value` into chunks of at most `chunk_size` bytes, and returns them with their header.
+pub fn chunk(value: &[u8], chunk_size: usize) -> Result<(ChunkedValueHeader, Vec<Vec<u8>>)> {
+    ensure!(chunk_size > 0, "The chunk size must be positive");
+    let chunks: Vec<Vec<u8>> = value.chunks(chunk_size).map(<[u8]>::to_vec).collect();
+    let header = ChunkedValueHeader {
+        length: value.len() as u64,
+        num_chunks: u32::try_from(chunks.len())
+            .map_err(|_| format_err!("Value of {} bytes has too many chunks", value.len()))?,
+        hash: HashValue::sha3_256_of(value),
+    };
+    Ok((header, chunks))
+}
+
+/// Concatenates `chunks` back into the value described by `header`. Fails if they don't match
+/// the number of chunks, length and hash recorded in the header.
+pub fn reassemble(header: &ChunkedValueHeader, chunks: Vec<Vec<u8>>) -> Result<Vec<u8>> {
+    ensure!(
+        chunks.len() as u64 == u64::from(header.num_chunks),
+        "Expected {} chunks, got {}",
+        header.num_chunks,
+        chunks.len()
+    );
+    let value = chunks.concat();
+    ensure!(
+        value.len() as u64 == header.length,
+        "Expected a value of {} bytes, got {}",
+        header.length,
+        value.len()
+    );
+    ensure!(
+        HashValue::sha3_256_of(&value) == header.hash,
+        "Chunked value doesn't match the hash of its header"
+    );
+    Ok(value)
+}
+
+/// Reads the value of `access_path` through `get`, which returns the entries as they are stored.
+/// Resources stored as chunks are reassembled, any other path is read as is.
+pub fn read<F, E>(access_path: &AccessPath, get: F) -> Result<Option<Vec<u8>>>
+where
+    F: Fn(&AccessPath) -> std::result::Result<Option<Vec<u8>>, E>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if let Some(value) = get(access_path)? {
+        return Ok(Some(value));
+    }
+    if !is_resource_path(access_path) {
+        return Ok(None);
+    }
+    let header: ChunkedValueHeader = match get(&header_path(access_path))? {
+        Some(bytes) => lcs::from_bytes(&bytes)?,
+        None => return Ok(None),
+    };
+    let chunks = (0..header.num_chunks)
+        .map(|index| {
+            get(&chunk_path(access_path, index))?
+                .ok_or_else(|| format_err!("Missing chunk {} of {}", index, access_path))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    reassemble(&header, chunks).map(Some)
+}
+
+/// Rewrites the resource writes of `write_set` into the storage layout: values larger than
+/// `chunk_size` are split into chunks, the others are written inline. `get` returns the entries
+/// as they are stored before the write set is applied, and is used to delete the entries of the
+/// previous layout of each resource, e.g., the chunks left over when a resource shrinks.
+pub fn chunk_write_set<F, E>(write_set: WriteSet, chunk_size: usize, get: F) -> Result<WriteSet>
+where
+    F: Fn(&AccessPath) -> std::result::Result<Option<Vec<u8>>, E>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    // Keyed by access path, so that the deletion of a stale entry never shadows a write
+    let mut writes = BTreeMap::new();
+    for (access_path, write_op) in write_set {
+        if !is_resource_path(&access_path) {
+            writes.insert(access_path, write_op);
+            continue;
+        }
+
+        let old_num_chunks = match get(&header_path(&access_path))? {
+            Some(bytes) => Some(lcs::from_bytes::<ChunkedValueHeader>(&bytes)?.num_chunks),
+            None => None,
+        };
+        let mut new_num_chunks = None;
+        match write_op {
+            WriteOp::Value(value) if value.len() > chunk_size => {
+                let (header, chunks) = chunk(&value, chunk_size)?;
+                writes.insert(
+                    header_path(&access_path),
+                    WriteOp::Value(lcs::to_bytes(&header)?),
+                );
+                for (index, chunk) in (0..).zip(chunks) {
+                    writes.insert(chunk_path(&access_path, index), WriteOp::Value(chunk));
+                }
+                new_num_chunks = Some(header.num_chunks);
+                if get(&access_path)?.is_some() {
+                    writes.insert(access_path.clone(), WriteOp::Deletion);
+                }
+            }
+            write_op => {
+                // Deleting a resource stored as chunks only deletes its chunks
+                if old_num_chunks.is_none() || !write_op.is_deletion() {
+                    writes.insert(access_path.clone(), write_op);
+                }
+            }
+        }
+
+        if let Some(old_num_chunks) = old_num_chunks {
+            if new_num_chunks.is_none() {
+                writes.insert(header_path(&access_path), WriteOp::Deletion);
+            }
+            for index in new_num_chunks.unwrap_or(0)..old_num_chunks {
+                writes.insert(chunk_path(&access_path, index), WriteOp::Deletion);
+            }
+        }
+    }
+    writes.into_iter().collect::<WriteSetMut>().freeze()
+}
+
+/// Returns `account_state` with its chunked resources reassembled at their resource paths, as
+/// the VM sees them.
+pub fn reassemble_account_state(account_state: &AccountState) -> Result<AccountState> {
+    let mut reassembled = AccountState::default();
+    for (path, value) in account_state.iter() {
+        if path.is_empty() || path[0] != AccessPath::CHUNKED_RESOURCE_TAG {
+            reassembled.insert(path.clone(), value.clone());
+            continue;
+        }
+        // Chunks are read along with their header
+        if path.len() != 1 + HashValue::LENGTH {
+            continue;
+        }
+        let header: ChunkedValueHeader = lcs::from_bytes(value)?;
+        let chunks = (0..header.num_chunks)
+            .map(|index| {
+                let mut chunk_path = path.clone();
+                chunk_path.extend_from_slice(&index.to_be_bytes());
+                account_state
+                    .get(&chunk_path)
+                    .cloned()
+                    .ok_or_else(|| format_err!("Missing chunk {} of a resource", index))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut resource_path = path.clone();
+        resource_path[0] = AccessPath::RESOURCE_TAG;
+        reassembled.insert(resource_path, reassemble(&header, chunks)?);
+    }
+    Ok(reassembled)
+}
@@ -3,6 +3,8 @@
 
 use crate::account_address::AccountAddress;
 use anyhow::{ensure, Error, Result};
+use libra_crypto::HashValue;
+use move_core_types::language_storage::TypeTag;
 #[cfg(feature = "fuzzing")]
 use proptest_derive::Arbitrary;
 #[cfg(feature = "fuzzing")]
@@ -10,8 +12,16 @@ use rand::{rngs::OsRng, RngCore};
 use serde::{de, ser, Deserialize, Serialize};
 use std::{convert::TryFrom, fmt};
 
+/// Domain separator of the hash from which the keys of typed event streams are derived
+const TYPED_EVENT_KEY_DOMAIN: &[u8] = b"LIBRA::EventKey::typed";
+/// Set in the salt of the keys of typed event streams. The salt of the key of an event handle is
+/// the counter of the handles created by its account, which never gets that large.
+const TYPED_EVENT_KEY_SALT_BIT: u64 = 1 << 63;
+
 /// A struct that represents a globally unique id for an Event stream that a user can listen to.
 /// By design, the lower part of EventKey is the same as account address.
+/// The upper part is a salt: the counter of an `EventHandleGenerator` for the streams of event
+/// handles, or a hash of the type of the events for the typed streams emitted with `Event::emit`.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "fuzzing", derive(Arbitrary))]
 pub struct EventKey([u8; EventKey::LENGTH]);
@@ -59,6 +69,23 @@ impl EventKey {
         rhs.copy_from_slice(addr.as_ref());
         EventKey(output_bytes)
     }
+
+    /// Create the key of the stream of the events of type `type_tag` of an account, emitted
+    /// without an event handle.
+    pub fn new_for_type(addr: &AccountAddress, type_tag: &TypeTag) -> Self {
+        let mut bytes = TYPED_EVENT_KEY_DOMAIN.to_vec();
+        bytes.extend(lcs::to_bytes(type_tag).expect("type tags can be serialized"));
+        let mut salt = [0u8; 8];
+        salt.copy_from_slice(&HashValue::sha3_256_of(&bytes).to_vec()[..8]);
+        EventKey::new_from_address(addr, u64::from_le_bytes(salt) | TYPED_EVENT_KEY_SALT_BIT)
+    }
+
+    /// Whether this is the key of a typed event stream rather than of an event handle
+    pub fn is_typed(&self) -> bool {
+        let mut salt = [0u8; 8];
+        salt.copy_from_slice(&self.0[..8]);
+        u64::from_le_bytes(salt) & TYPED_EVENT_KEY_SALT_BIT != 0
+    }
 }
 
 impl From<EventKey> for [u8; EventKey::LENGTH] {
@@ -79,10 +106,14 @@ impl ser::Serialize for EventKey {
     where
         S: ser::Serializer,
     {
-        // In order to preserve the Serde data model and help analysis tools,
-        // make sure to wrap our value in a container with the same name
-        // as the original type.
-        serializer.serialize_newtype_struct("EventKey", serde_bytes::Bytes::new(&self.0))
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(&self.0))
+        } else {
+            // In order to preserve the Serde data model and help analysis tools,
+            // make sure to wrap our value in a container with the same name
+            // as the original type.
+            serializer.serialize_newtype_struct("EventKey", serde_bytes::Bytes::new(&self.0))
+        }
     }
 }
 
@@ -91,13 +122,20 @@ impl<'de> de::Deserialize<'de> for EventKey {
     where
         D: de::Deserializer<'de>,
     {
-        // See comment in serialize.
-        #[derive(::serde::Deserialize)]
-        #[serde(rename = "EventKey")]
-        struct Value<'a>(&'a [u8]);
-
-        let value = Value::deserialize(deserializer)?;
-        Self::try_from(value.0).map_err(<D::Error as ::serde::de::Error>::custom)
+        if deserializer.is_human_readable() {
+            let encoded_key = <String>::deserialize(deserializer)?;
+            let bytes =
+                hex::decode(encoded_key).map_err(<D::Error as ::serde::de::Error>::custom)?;
+            Self::try_from(bytes.as_slice()).map_err(<D::Error as ::serde::de::Error>::custom)
+        } else {
+            // See comment in serialize.
+            #[derive(::serde::Deserialize)]
+            #[serde(rename = "EventKey")]
+            struct Value<'a>(&'a [u8]);
+
+            let value = Value::deserialize(deserializer)?;
+            Self::try_from(value.0).map_err(<D::Error as ::serde::de::Error>::custom)
+        }
     }
 }
 
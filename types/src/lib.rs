@@ -8,8 +8,10 @@ pub mod account_address;
 pub mod account_config;
 pub mod account_state;
 pub mod account_state_blob;
+pub mod block_congestion;
 pub mod block_info;
 pub mod block_metadata;
+pub mod chunked_resource;
 pub mod contract_event;
 pub mod epoch_change;
 pub mod epoch_state;
@@ -22,6 +24,7 @@ pub mod on_chain_config;
 pub mod proof;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
+pub mod serde_helpers;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod test_helpers;
 pub mod transaction;
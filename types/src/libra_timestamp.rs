@@ -1,7 +1,6 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_core_types::move_resource::MoveResource;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -9,11 +8,6 @@ pub struct LibraTimestampResource {
     pub libra_timestamp: LibraTimestamp,
 }
 
-impl MoveResource for LibraTimestampResource {
-    const MODULE_NAME: &'static str = "LibraTimestamp";
-    const STRUCT_NAME: &'static str = "CurrentTimeMicroseconds";
-}
-
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LibraTimestamp {
     pub microseconds: u64,
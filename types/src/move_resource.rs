@@ -1,8 +1,24 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{access_path::AccessPath, transaction::Version};
+use crate::{
+    access_path::AccessPath,
+    account_config::{
+        AccountResource, AccountVersionResource, BalanceResource, BurnEvent, CancelBurnEvent,
+        ChildVASP, Coin1, Coin2, CurrencyInfoResource, KeyRotationCapabilityResource,
+        MigrationEvent, MintEvent, NewBlockEvent, NewEpochEvent, ParentVASP, PreburnEvent,
+        ReceivedPaymentEvent, RoleId, SentPaymentEvent, ToLBRExchangeRateUpdateEvent, UpgradeEvent,
+        WithdrawCapabilityResource, LBR,
+    },
+    block_congestion::BlockCongestionResource,
+    block_metadata::LibraBlockResource,
+    libra_timestamp::LibraTimestampResource,
+    on_chain_config::ConfigurationResource,
+    transaction::Version,
+    validator_config::ValidatorConfigResource,
+};
 use anyhow::Result;
+use move_core_types::{language_storage::TypeTag, move_resource::MoveResource};
 
 // TODO combine with ConfigStorage
 pub trait MoveStorage {
@@ -19,3 +35,79 @@ pub trait MoveStorage {
         version: Version,
     ) -> Result<Vec<Vec<u8>>>;
 }
+
+/// A Move struct of the stdlib that a Rust type stands for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StdlibStruct {
+    pub module: &'static str,
+    pub name: &'static str,
+    pub type_params: usize,
+}
+
+/// Implements `MoveResource` for the Rust types standing for Move structs of the stdlib, from the
+/// path of the struct in Move, and lists these structs in `stdlib_structs`, against which the
+/// stdlib is checked. A type parameter of the Rust type stands for the type argument of the Move
+/// struct, e.g., `BalanceResource<C>` with `C = Coin1` stands for `LibraAccount::Balance<Coin1>`.
+/// A type argument `_` is only known at runtime, e.g., from a currency code.
+macro_rules! stdlib_structs {
+    ($(
+        $rust:ident $(<$param:ident>)? => $module:ident :: $name:ident $(<$arg:tt>)?,
+    )*) => {
+        $(
+            impl$(<$param: MoveResource>)? MoveResource for $rust$(<$param>)? {
+                const MODULE_NAME: &'static str = stringify!($module);
+                const STRUCT_NAME: &'static str = stringify!($name);
+                $(
+                    fn type_params() -> Vec<TypeTag> {
+                        vec![TypeTag::Struct($param::struct_tag())]
+                    }
+                )?
+            }
+        )*
+
+        /// The Move structs of the stdlib that Rust types stand for.
+        pub fn stdlib_structs() -> Vec<StdlibStruct> {
+            vec![$(
+                StdlibStruct {
+                    module: stringify!($module),
+                    name: stringify!($name),
+                    type_params: {
+                        let args: &[&str] = &[$(stringify!($arg))?];
+                        args.len()
+                    },
+                },
+            )*]
+        }
+    };
+}
+
+stdlib_structs! {
+    AccountResource => LibraAccount::LibraAccount,
+    AccountVersionResource => AccountMigration::AccountVersion,
+    BalanceResource<C> => LibraAccount::Balance<C>,
+    BlockCongestionResource => BlockCongestion::BlockCongestion,
+    BurnEvent => Libra::BurnEvent,
+    CancelBurnEvent => Libra::CancelBurnEvent,
+    ChildVASP => VASP::ChildVASP,
+    Coin1 => Coin1::Coin1,
+    Coin2 => Coin2::Coin2,
+    ConfigurationResource => LibraConfig::Configuration,
+    CurrencyInfoResource => Libra::CurrencyInfo<_>,
+    KeyRotationCapabilityResource => LibraAccount::KeyRotationCapability,
+    LBR => LBR::LBR,
+    LibraBlockResource => LibraBlock::BlockMetadata,
+    LibraTimestampResource => LibraTimestamp::CurrentTimeMicroseconds,
+    MigrationEvent => AccountMigration::MigrationEvent,
+    MintEvent => Libra::MintEvent,
+    NewBlockEvent => LibraBlock::NewBlockEvent,
+    NewEpochEvent => LibraConfig::NewEpochEvent,
+    ParentVASP => VASP::ParentVASP,
+    PreburnEvent => Libra::PreburnEvent,
+    ReceivedPaymentEvent => LibraAccount::ReceivedPaymentEvent,
+    RoleId => Roles::RoleId,
+    SentPaymentEvent => LibraAccount::SentPaymentEvent,
+    ToLBRExchangeRateUpdateEvent => Libra::ToLBRExchangeRateUpdateEvent,
+    UpgradeEvent => LibraWriteSetManager::UpgradeEvent,
+    ValidatorConfigResource => ValidatorConfig::ValidatorConfig,
+    WithdrawCapabilityResource => LibraAccount::WithdrawCapability,
+}
@@ -14,25 +14,41 @@ use move_core_types::{
     move_resource::MoveResource,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 mod dual_attestation_limit;
 mod libra_version;
+mod module_publishing_policy;
 mod registered_currencies;
+mod transaction_timeout;
 mod validator_set;
 mod vm_config;
 
 pub use self::{
     dual_attestation_limit::DualAttestationLimit,
     libra_version::LibraVersion,
+    module_publishing_policy::ModulePublishingPolicy,
     registered_currencies::RegisteredCurrencies,
-    validator_set::ValidatorSet,
+    transaction_timeout::LibraTransactionTimeout,
+    validator_set::{ValidatorSet, ValidatorSetError},
     vm_config::{VMConfig, VMPublishingOption},
 };
 
-/// To register an on-chain config in Rust:
+/// To use an on-chain config in Rust:
 /// 1. Implement the `OnChainConfig` trait for the Rust representation of the config
-/// 2. Add the config's `ConfigID` to `ON_CHAIN_CONFIG_REGISTRY`
+/// 2. Subscribe to it from the component that needs it, with `SubscriptionBundle::with_config`
+///    in the subscription service. State sync reads the configs that are subscribed to, and
+///    publishes them to the subscribers on reconfiguration.
+///
+/// An update of a config can be scheduled on-chain for a future epoch, in a `PendingConfig`
+/// resource next to the config. The readers of configs apply it once its epoch has started, with
+/// `apply_pending_config`, so that the VM and the validators keep using the current value until
+/// then.
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ConfigID(&'static str, &'static str);
@@ -50,26 +66,38 @@ impl ConfigID {
             Identifier::new(self.1).expect("failed to get Identifier"),
         )
     }
+
+    pub fn pending_access_path(self) -> AccessPath {
+        pending_access_path_for_config(
+            AccountAddress::from_hex_literal(self.0).expect("failed to get address"),
+            Identifier::new(self.1).expect("failed to get Identifier"),
+        )
+    }
 }
 
-/// State sync will panic if the value of any config in this registry is uninitialized
-pub const ON_CHAIN_CONFIG_REGISTRY: &[ConfigID] = &[
-    VMConfig::CONFIG_ID,
-    LibraVersion::CONFIG_ID,
-    ValidatorSet::CONFIG_ID,
-    RegisteredCurrencies::CONFIG_ID,
-    DualAttestationLimit::CONFIG_ID,
-];
+impl fmt::Display for ConfigID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}::{}", self.0, self.1)
+    }
+}
 
-#[derive(Clone, Debug, PartialEq)]
+/// The values of the subscribed on-chain configs at an epoch.
+/// Configs are deserialized the first time they are read, and cached for all the clones of the
+/// payload.
+#[derive(Clone, Debug)]
 pub struct OnChainConfigPayload {
     epoch: u64,
     configs: Arc<HashMap<ConfigID, Vec<u8>>>,
+    cache: Arc<Mutex<HashMap<ConfigID, Box<dyn Any + Send + Sync>>>>,
 }
 
 impl OnChainConfigPayload {
     pub fn new(epoch: u64, configs: Arc<HashMap<ConfigID, Vec<u8>>>) -> Self {
-        Self { epoch, configs }
+        Self {
+            epoch,
+            configs,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
     }
 
     pub fn epoch(&self) -> u64 {
@@ -77,11 +105,20 @@ impl OnChainConfigPayload {
     }
 
     pub fn get<T: OnChainConfig>(&self) -> Result<T> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(config) = cache
+            .get(&T::CONFIG_ID)
+            .and_then(|config| config.downcast_ref::<T>())
+        {
+            return Ok(config.clone());
+        }
         let bytes = self
             .configs
             .get(&T::CONFIG_ID)
-            .ok_or_else(|| format_err!("[on-chain cfg] config not in payload"))?;
-        T::deserialize_into_config(bytes)
+            .ok_or_else(|| format_err!("[on-chain cfg] config {} not in payload", T::CONFIG_ID))?;
+        let config = T::deserialize_into_config(bytes)?;
+        cache.insert(T::CONFIG_ID, Box::new(config.clone()));
+        Ok(config)
     }
 
     pub fn configs(&self) -> &HashMap<ConfigID, Vec<u8>> {
@@ -89,6 +126,12 @@ impl OnChainConfigPayload {
     }
 }
 
+impl PartialEq for OnChainConfigPayload {
+    fn eq(&self, other: &Self) -> bool {
+        self.epoch == other.epoch && self.configs == other.configs
+    }
+}
+
 /// Trait to be implemented by a storage type from which to read on-chain configs
 pub trait ConfigStorage {
     fn fetch_config(&self, access_path: AccessPath) -> Option<Vec<u8>>;
@@ -96,7 +139,7 @@ pub trait ConfigStorage {
 
 /// Trait to be implemented by a Rust struct representation of an on-chain config
 /// that is stored in storage as a serialized byte array
-pub trait OnChainConfig: Send + Sync + DeserializeOwned {
+pub trait OnChainConfig: Clone + Send + Sync + DeserializeOwned + 'static {
     // association_address
     const ADDRESS: &'static str = CONFIG_ADDRESS_STR;
     const IDENTIFIER: &'static str;
@@ -122,13 +165,48 @@ pub trait OnChainConfig: Send + Sync + DeserializeOwned {
         Self::deserialize_default_impl(bytes)
     }
 
+    // Fetch the value of the config in effect in the current epoch
     fn fetch_config<T>(storage: T) -> Option<Self>
     where
         T: ConfigStorage,
     {
-        storage
-            .fetch_config(Self::CONFIG_ID.access_path())
-            .and_then(|bytes| Self::deserialize_into_config(&bytes).ok())
+        let bytes = storage.fetch_config(Self::CONFIG_ID.access_path())?;
+        let bytes = match storage.fetch_config(Self::CONFIG_ID.pending_access_path()) {
+            Some(pending) => {
+                let epoch = storage
+                    .fetch_config(ConfigurationResource::access_path())
+                    .and_then(|bytes| lcs::from_bytes::<ConfigurationResource>(&bytes).ok())?
+                    .epoch();
+                apply_pending_config(bytes, &pending, epoch).ok()?
+            }
+            None => bytes,
+        };
+        Self::deserialize_into_config(&bytes).ok()
+    }
+}
+
+/// Returns the serialized value of a config in effect at `epoch`, given its stored value and its
+/// serialized `LibraConfig::PendingConfig`.
+/// The pending config is made of the activation epoch followed by an optional value, which is a
+/// vector of at most one element in Move; this is parsed without knowing the type of the config.
+pub fn apply_pending_config(config: Vec<u8>, pending: &[u8], epoch: u64) -> Result<Vec<u8>> {
+    if pending.len() < 9 {
+        return Err(format_err!(
+            "[on-chain cfg] pending config too short: {} bytes",
+            pending.len()
+        ));
+    }
+    let mut activation_epoch = [0; 8];
+    activation_epoch.copy_from_slice(&pending[..8]);
+    let activation_epoch = u64::from_le_bytes(activation_epoch);
+    match pending[8] {
+        0 => Ok(config),
+        1 if activation_epoch <= epoch => Ok(pending[9..].to_vec()),
+        1 => Ok(config),
+        len => Err(format_err!(
+            "[on-chain cfg] pending config holds {} values instead of at most one",
+            len
+        )),
     }
 }
 
@@ -137,12 +215,27 @@ pub fn new_epoch_event_key() -> EventKey {
 }
 
 pub fn access_path_for_config(address: AccountAddress, config_name: Identifier) -> AccessPath {
+    config_resource_access_path(address, "LibraConfig", config_name)
+}
+
+pub fn pending_access_path_for_config(
+    address: AccountAddress,
+    config_name: Identifier,
+) -> AccessPath {
+    config_resource_access_path(address, "PendingConfig", config_name)
+}
+
+fn config_resource_access_path(
+    address: AccountAddress,
+    struct_name: &str,
+    config_name: Identifier,
+) -> AccessPath {
     AccessPath::new(
         address,
         AccessPath::resource_access_vec(&StructTag {
             address: CORE_CODE_ADDRESS,
             module: Identifier::new("LibraConfig").unwrap(),
-            name: Identifier::new("LibraConfig").unwrap(),
+            name: Identifier::new(struct_name).unwrap(),
             type_params: vec![TypeTag::Struct(StructTag {
                 address: CORE_CODE_ADDRESS,
                 module: config_name.clone(),
@@ -161,6 +254,10 @@ pub struct ConfigurationResource {
 }
 
 impl ConfigurationResource {
+    pub fn access_path() -> AccessPath {
+        AccessPath::new(config_address(), Self::resource_path())
+    }
+
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
@@ -201,8 +298,3 @@ impl Default for ConfigurationResource {
         }
     }
 }
-
-impl MoveResource for ConfigurationResource {
-    const MODULE_NAME: &'static str = "LibraConfig";
-    const STRUCT_NAME: &'static str = "Configuration";
-}
@@ -0,0 +1,73 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, on_chain_config::OnChainConfig};
+use anyhow::{format_err, Result};
+use serde::{Deserialize, Serialize};
+
+const OPEN: u8 = 0;
+const ALLOW_LIST: u8 = 1;
+const CLOSED: u8 = 2;
+
+/// Defines which accounts can publish modules under their own address. The accounts holding the
+/// module publishing privilege can always publish modules under the core code address.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum ModulePublishingPolicy {
+    /// Any account can publish modules
+    Open,
+    /// Only the accounts on the allow-list can publish modules
+    AllowList(Vec<AccountAddress>),
+    /// No account can publish modules
+    Closed,
+}
+
+impl ModulePublishingPolicy {
+    pub fn is_allowed_publisher(&self, address: &AccountAddress) -> bool {
+        match self {
+            ModulePublishingPolicy::Open => true,
+            ModulePublishingPolicy::AllowList(allowed_publishers) => {
+                allowed_publishers.contains(address)
+            }
+            ModulePublishingPolicy::Closed => false,
+        }
+    }
+
+    /// The code of the policy in the `LibraModulePublishingPolicy` Move module
+    pub fn code(&self) -> u8 {
+        match self {
+            ModulePublishingPolicy::Open => OPEN,
+            ModulePublishingPolicy::AllowList(_) => ALLOW_LIST,
+            ModulePublishingPolicy::Closed => CLOSED,
+        }
+    }
+
+    pub fn allowed_publishers(&self) -> &[AccountAddress] {
+        match self {
+            ModulePublishingPolicy::AllowList(allowed_publishers) => allowed_publishers,
+            ModulePublishingPolicy::Open | ModulePublishingPolicy::Closed => &[],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct ModulePublishingPolicyInner {
+    pub policy: u8,
+    pub allowed_publishers: Vec<AccountAddress>,
+}
+
+impl OnChainConfig for ModulePublishingPolicy {
+    const IDENTIFIER: &'static str = "LibraModulePublishingPolicy";
+
+    fn deserialize_into_config(bytes: &[u8]) -> Result<Self> {
+        let raw_policy = lcs::from_bytes::<ModulePublishingPolicyInner>(&bytes)
+            .map_err(|e| format_err!("Failed to deserialize ModulePublishingPolicyInner: {}", e))?;
+        match raw_policy.policy {
+            OPEN => Ok(ModulePublishingPolicy::Open),
+            ALLOW_LIST => Ok(ModulePublishingPolicy::AllowList(
+                raw_policy.allowed_publishers,
+            )),
+            CLOSED => Ok(ModulePublishingPolicy::Closed),
+            policy => Err(format_err!("Unknown module publishing policy {}", policy)),
+        }
+    }
+}
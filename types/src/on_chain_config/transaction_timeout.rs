@@ -0,0 +1,16 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// Defines the expiration window: transactions must expire after the current block time, and
+/// at most this duration after it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LibraTransactionTimeout {
+    pub duration_microseconds: u64,
+}
+
+impl OnChainConfig for LibraTransactionTimeout {
+    const IDENTIFIER: &'static str = "LibraTransactionTimeout";
+}
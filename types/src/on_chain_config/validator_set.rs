@@ -1,12 +1,37 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{on_chain_config::OnChainConfig, validator_info::ValidatorInfo};
-
+use crate::{
+    account_address::AccountAddress, on_chain_config::OnChainConfig, validator_info::ValidatorInfo,
+};
+use libra_crypto::ed25519::Ed25519PublicKey;
+use libra_network_address::{NetworkAddress, RawNetworkAddress};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::{fmt, iter::IntoIterator, vec};
+use std::{collections::HashSet, convert::TryFrom, fmt, iter::IntoIterator, vec};
+use thiserror::Error;
+
+/// The reasons a validator set cannot be used for an epoch.
+#[derive(Debug, Error, PartialEq)]
+pub enum ValidatorSetError {
+    #[error("The validator set is empty")]
+    Empty,
+    #[error("Validator {0} appears more than once")]
+    DuplicateValidator(AccountAddress),
+    #[error("Validator {0} has an invalid consensus public key: {1}")]
+    InvalidConsensusKey(AccountAddress, String),
+    #[error("Validator {0} has an unparseable {1} network address: {2}")]
+    InvalidNetworkAddress(AccountAddress, &'static str, String),
+    #[error("The total voting power is 0, no quorum can be formed")]
+    NoVotingPower,
+    #[error("The voting power overflows when adding validator {0}")]
+    VotingPowerOverflow(AccountAddress),
+    #[error("The total voting power ({0}) is too large to compute the quorum voting power")]
+    QuorumOverflow(u64),
+    #[error("Validator {0} is not in the validator set")]
+    NotInValidatorSet(AccountAddress),
+}
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
@@ -48,6 +73,67 @@ impl ValidatorSet {
     pub fn empty() -> Self {
         ValidatorSet::new(Vec::new())
     }
+
+    /// Checks that consensus can run an epoch with this validator set: each validator appears
+    /// once with a valid consensus key and parseable network addresses, and the quorum voting
+    /// power can be computed from the voting power of the validators.
+    pub fn verify(&self) -> Result<(), ValidatorSetError> {
+        if self.payload.is_empty() {
+            return Err(ValidatorSetError::Empty);
+        }
+        let mut accounts = HashSet::new();
+        let mut total_voting_power: u64 = 0;
+        for info in &self.payload {
+            let account = *info.account_address();
+            if !accounts.insert(account) {
+                return Err(ValidatorSetError::DuplicateValidator(account));
+            }
+            // The key may have been constructed without the checks done on deserialization
+            Ed25519PublicKey::try_from(&info.consensus_public_key().to_bytes()[..])
+                .map_err(|e| ValidatorSetError::InvalidConsensusKey(account, e.to_string()))?;
+            let config = info.config();
+            for address in config.validator_network_addresses() {
+                verify_network_address(account, "validator", &address)?;
+            }
+            for address in config.full_node_network_addresses() {
+                verify_network_address(account, "full node", &address)?;
+            }
+            total_voting_power = total_voting_power
+                .checked_add(info.consensus_voting_power())
+                .ok_or(ValidatorSetError::VotingPowerOverflow(account))?;
+        }
+        if total_voting_power == 0 {
+            return Err(ValidatorSetError::NoVotingPower);
+        }
+        // The quorum voting power is 2f + 1, i.e., total_voting_power * 2 / 3 + 1
+        if total_voting_power.checked_mul(2).is_none() {
+            return Err(ValidatorSetError::QuorumOverflow(total_voting_power));
+        }
+        Ok(())
+    }
+
+    /// Checks that `account` is one of the validators of this set.
+    pub fn verify_includes(&self, account: AccountAddress) -> Result<(), ValidatorSetError> {
+        if self
+            .payload
+            .iter()
+            .any(|info| info.account_address() == &account)
+        {
+            Ok(())
+        } else {
+            Err(ValidatorSetError::NotInValidatorSet(account))
+        }
+    }
+}
+
+fn verify_network_address(
+    account: AccountAddress,
+    network: &'static str,
+    address: &RawNetworkAddress,
+) -> Result<(), ValidatorSetError> {
+    NetworkAddress::try_from(address)
+        .map(|_| ())
+        .map_err(|e| ValidatorSetError::InvalidNetworkAddress(account, network, e.to_string()))
 }
 
 impl OnChainConfig for ValidatorSet {
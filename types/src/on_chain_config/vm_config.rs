@@ -7,19 +7,21 @@ use libra_crypto::HashValue;
 use move_core_types::gas_schedule::{CostTable, GasConstants};
 use serde::{Deserialize, Serialize};
 
-/// Defines and holds the publishing policies for the VM. There are three possible configurations:
-/// 1. No module publishing, only whitelisted scripts are allowed.
-/// 2. No module publishing, custom scripts are allowed.
-/// 3. Both module publishing and custom scripts are allowed.
-/// We represent these as an enum instead of a struct since whitelisting and module/script
-/// publishing are mutually exclusive options.
+/// Defines and holds the script publishing policies for the VM. There are three possible
+/// configurations:
+/// 1. Only whitelisted scripts are allowed.
+/// 2. Custom scripts are allowed.
+/// 3. Custom scripts are allowed, and genesis opens module publishing to all accounts.
+/// Module publishing is then governed by the on-chain `ModulePublishingPolicy`.
+/// We represent these as an enum instead of a struct since whitelisting and custom scripts are
+/// mutually exclusive options.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum VMPublishingOption {
     /// Only allow scripts on a whitelist to be run
     Locked(Vec<[u8; SCRIPT_HASH_LENGTH]>),
-    /// Allow custom scripts, but _not_ custom module publishing
+    /// Allow custom scripts
     CustomScripts,
-    /// Allow both custom scripts and custom module publishing
+    /// Allow custom scripts, with an open `ModulePublishingPolicy` at genesis
     Open,
 }
 
@@ -610,6 +610,92 @@ impl AccountStateProof {
     }
 }
 
+/// The proof used to authenticate the states of several accounts at the same version. Unlike a
+/// list of `AccountStateProof`s, the `AccumulatorProof` from `LedgerInfo` to `TransactionInfo` and
+/// the `TransactionInfo` object are carried once and shared by the `SparseMerkleProof`s from state
+/// root to each account.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct AccountStatesProof {
+    transaction_info_with_proof: TransactionInfoWithProof,
+
+    /// The sparse merkle proofs from state root to each account state.
+    transaction_info_to_account_proofs: Vec<SparseMerkleProof>,
+}
+
+impl AccountStatesProof {
+    /// Constructs a new `AccountStatesProof` using given `transaction_info_with_proof` and
+    /// `transaction_info_to_account_proofs`.
+    pub fn new(
+        transaction_info_with_proof: TransactionInfoWithProof,
+        transaction_info_to_account_proofs: Vec<SparseMerkleProof>,
+    ) -> Self {
+        AccountStatesProof {
+            transaction_info_with_proof,
+            transaction_info_to_account_proofs,
+        }
+    }
+
+    /// Returns the `transaction_info_with_proof` object in this proof.
+    pub fn transaction_info_with_proof(&self) -> &TransactionInfoWithProof {
+        &self.transaction_info_with_proof
+    }
+
+    /// Returns the `transaction_info_to_account_proofs` in this proof.
+    pub fn transaction_info_to_account_proofs(&self) -> &[SparseMerkleProof] {
+        &self.transaction_info_to_account_proofs
+    }
+
+    /// Splits this proof into one `AccountStateProof` per account, each carrying a copy of the
+    /// shared `TransactionInfoWithProof`.
+    pub fn into_account_state_proofs(self) -> Vec<AccountStateProof> {
+        let transaction_info_with_proof = self.transaction_info_with_proof;
+        self.transaction_info_to_account_proofs
+            .into_iter()
+            .map(|proof| AccountStateProof::new(transaction_info_with_proof.clone(), proof))
+            .collect()
+    }
+
+    /// Verifies that the states of the accounts at version `state_version` are correct using the
+    /// provided proof. The `account_state_blobs` are given in the order of the
+    /// `account_address_hashes` and of the sparse merkle proofs. As in `AccountStateProof`, a
+    /// missing blob means the account is expected to not exist.
+    pub fn verify(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_version: Version,
+        account_address_hashes: &[HashValue],
+        account_state_blobs: &[Option<AccountStateBlob>],
+    ) -> Result<()> {
+        ensure!(
+            account_address_hashes.len() == self.transaction_info_to_account_proofs.len()
+                && account_state_blobs.len() == self.transaction_info_to_account_proofs.len(),
+            "The number of accounts ({}) and of blobs ({}) should match the number of proofs ({}).",
+            account_address_hashes.len(),
+            account_state_blobs.len(),
+            self.transaction_info_to_account_proofs.len(),
+        );
+
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info
+            .state_root_hash();
+        for ((proof, address_hash), blob) in self
+            .transaction_info_to_account_proofs
+            .iter()
+            .zip(account_address_hashes)
+            .zip(account_state_blobs)
+        {
+            proof.verify(state_root_hash, *address_hash, blob.as_ref())?;
+        }
+
+        self.transaction_info_with_proof
+            .verify(ledger_info, state_version)?;
+
+        Ok(())
+    }
+}
+
 /// The complete proof used to authenticate a contract event. This structure consists of the
 /// `AccumulatorProof` from `LedgerInfo` to `TransactionInfo`, the `TransactionInfo` object and the
 /// `AccumulatorProof` from event accumulator root to the event.
@@ -29,8 +29,8 @@ use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
 pub use self::definition::{
-    AccountStateProof, AccumulatorConsistencyProof, AccumulatorExtensionProof, AccumulatorProof,
-    AccumulatorRangeProof, EventAccumulatorProof, EventProof, SparseMerkleProof,
+    AccountStateProof, AccountStatesProof, AccumulatorConsistencyProof, AccumulatorExtensionProof,
+    AccumulatorProof, AccumulatorRangeProof, EventAccumulatorProof, EventProof, SparseMerkleProof,
     SparseMerkleRangeProof, TransactionAccumulatorProof, TransactionAccumulatorRangeProof,
     TransactionInfoWithProof, TransactionListProof,
 };
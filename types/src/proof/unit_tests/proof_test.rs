@@ -7,10 +7,11 @@ use crate::{
     block_info::BlockInfo,
     ledger_info::LedgerInfo,
     proof::{
-        definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccountStateProof, AccumulatorExtensionProof,
-        EventAccumulatorInternalNode, EventAccumulatorProof, EventProof, SparseMerkleInternalNode,
-        SparseMerkleLeafNode, SparseMerkleProof, TestAccumulatorInternalNode, TestAccumulatorProof,
-        TransactionAccumulatorInternalNode, TransactionAccumulatorProof, TransactionInfoWithProof,
+        definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccountStateProof, AccountStatesProof,
+        AccumulatorExtensionProof, EventAccumulatorInternalNode, EventAccumulatorProof, EventProof,
+        SparseMerkleInternalNode, SparseMerkleLeafNode, SparseMerkleProof,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoWithProof,
     },
     transaction::{RawTransaction, Script, Transaction, TransactionInfo},
     vm_error::StatusCode,
@@ -434,6 +435,45 @@ fn test_verify_account_state_and_event() {
         )
         .is_err());
 
+    // Prove both `key1` and `key2`, sharing the proof of the transaction info.
+    let account_states_proof = AccountStatesProof::new(
+        account_state_proof.transaction_info_with_proof().clone(),
+        vec![
+            SparseMerkleProof::new(
+                Some(SparseMerkleLeafNode::new(key1, blob1.hash())),
+                vec![internal_d_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH],
+            ),
+            account_state_proof
+                .transaction_info_to_account_proof()
+                .clone(),
+        ],
+    );
+    assert!(account_states_proof
+        .verify(
+            &ledger_info,
+            /* state_version = */ 2,
+            &[key1, key2],
+            &[Some(blob1.clone()), Some(blob2.clone())],
+        )
+        .is_ok());
+    assert!(account_states_proof
+        .verify(
+            &ledger_info,
+            /* state_version = */ 2,
+            &[key1, key2],
+            &[Some(blob1), Some(bad_blob2)],
+        )
+        .is_err());
+    // The number of blobs has to match the number of proofs.
+    assert!(account_states_proof
+        .verify(
+            &ledger_info,
+            /* state_version = */ 2,
+            &[key2],
+            &[Some(blob2)],
+        )
+        .is_err());
+
     let transaction_info_to_event_proof = EventAccumulatorProof::new(vec![event1_hash]);
     let event_proof = EventProof::new(
         TransactionInfoWithProof::new(ledger_info_to_transaction_info_proof, txn_info2),
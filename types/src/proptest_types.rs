@@ -305,6 +305,15 @@ fn new_raw_transaction(
             gas_currency_code,
             Duration::from_secs(expiration_time_secs),
         ),
+        payload @ TransactionPayload::Batch(_) => RawTransaction::new(
+            sender,
+            sequence_number,
+            payload,
+            max_gas_amount,
+            gas_unit_price,
+            gas_currency_code,
+            Duration::from_secs(expiration_time_secs),
+        ),
         TransactionPayload::WriteSet(write_set) => {
             // It's a bit unfortunate that max_gas_amount etc is generated but
             // not used, but it isn't a huge deal.
@@ -467,6 +476,10 @@ impl TransactionPayload {
         any::<Module>().prop_map(TransactionPayload::Module)
     }
 
+    pub fn batch_strategy() -> impl Strategy<Value = Self> {
+        vec(any::<Script>(), 1..4).prop_map(TransactionPayload::Batch)
+    }
+
     pub fn write_set_strategy() -> impl Strategy<Value = Self> {
         any::<WriteSet>().prop_map(|ws| TransactionPayload::WriteSet(ChangeSet::new(ws, vec![])))
     }
@@ -502,6 +515,7 @@ impl Arbitrary for TransactionPayload {
         prop_oneof![
             4 => Self::script_strategy(),
             1 => Self::module_strategy(),
+            1 => Self::batch_strategy(),
             1 => Self::write_set_strategy(),
         ]
         .boxed()
@@ -534,7 +548,15 @@ impl Arbitrary for Module {
     fn arbitrary_with(_args: ()) -> Self::Strategy {
         // XXX How should we generate random modules?
         // The vector sizes are picked out of thin air.
-        vec(any::<u8>(), 0..100).prop_map(Module::new).boxed()
+        (
+            vec(any::<u8>(), 0..100),
+            option::of(vec(any::<u8>(), 0..100)),
+        )
+            .prop_map(|(code, source_map)| match source_map {
+                Some(source_map) => Module::with_source_map(code, source_map),
+                None => Module::new(code),
+            })
+            .boxed()
     }
 }
 
@@ -875,21 +897,27 @@ impl Arbitrary for BlockMetadata {
             },
         );
         (
+            any::<HashValue>(),
             any::<HashValue>(),
             any::<u64>(),
             any::<u64>(),
             signature_strategy,
             any::<AccountAddress>(),
+            prop::collection::vec(any::<AccountAddress>(), 0..3),
         )
-            .prop_map(|(id, round, timestamp, signatures, proposer)| {
-                BlockMetadata::new(
-                    id,
-                    round,
-                    timestamp,
-                    signatures.into_iter().map(|(addr, _)| addr).collect(),
-                    proposer,
-                )
-            })
+            .prop_map(
+                |(id, parent_id, round, timestamp, signatures, proposer, failed_proposers)| {
+                    BlockMetadata::new(
+                        id,
+                        parent_id,
+                        round,
+                        timestamp,
+                        signatures.into_iter().map(|(addr, _)| addr).collect(),
+                        proposer,
+                        failed_proposers,
+                    )
+                },
+            )
             .boxed()
     }
 
@@ -0,0 +1,35 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serde helpers for the human readable encoding of Libra types, i.e. JSON, which services not
+//! written in Rust rely on. They leave binary encodings such as LCS unchanged.
+
+/// Encodes bytes as a lowercase hex string in human readable formats, and as a plain `Vec<u8>`
+/// otherwise. Use it with `#[serde(with = "crate::serde_helpers::hex_bytes")]`.
+pub mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S, T>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]>,
+    {
+        if serializer.is_human_readable() {
+            hex::encode(bytes).serialize(serializer)
+        } else {
+            bytes.as_ref().serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let encoded = <String>::deserialize(deserializer)?;
+            hex::decode(encoded).map_err(D::Error::custom)
+        } else {
+            <Vec<u8>>::deserialize(deserializer)
+        }
+    }
+}
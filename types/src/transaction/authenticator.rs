@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_address::AccountAddress;
-use anyhow::{Error, Result};
+use anyhow::{bail, ensure, Error, Result};
 use libra_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
@@ -29,7 +29,11 @@ use std::{convert::TryFrom, fmt, str::FromStr};
 /// under the transaction's sender account address (2).
 
 // TODO: in the future, can tie these to the TransactionAuthenticator enum directly with https://github.com/rust-lang/rust/issues/60553
-#[derive(Debug)]
+/// The identifier of a signature scheme, i.e., the last byte of an `AuthenticationKeyPreimage`.
+/// Adding a scheme doesn't require any account to migrate: the on-chain `Authenticator` module
+/// keeps the set of schemes the prologue accepts, and accounts opt into a new scheme by rotating
+/// to an authentication key of that scheme, which keeps their address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[repr(u8)]
 pub enum Scheme {
     Ed25519 = 0,
@@ -47,6 +51,90 @@ impl fmt::Display for Scheme {
     }
 }
 
+impl TryFrom<u8> for Scheme {
+    type Error = Error;
+
+    fn try_from(scheme_id: u8) -> Result<Self> {
+        match scheme_id {
+            0 => Ok(Scheme::Ed25519),
+            1 => Ok(Scheme::MultiEd25519),
+            _ => bail!("Unknown signature scheme id {}", scheme_id),
+        }
+    }
+}
+
+/// The public key of an account in one of the supported signature schemes. This is the single
+/// place authentication keys and addresses are derived from: wallets, clients and transaction
+/// builders should go through it rather than hashing public key bytes themselves.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccountPublicKey {
+    Ed25519(Ed25519PublicKey),
+    MultiEd25519(MultiEd25519PublicKey),
+}
+
+impl AccountPublicKey {
+    /// Parse the public key of `scheme` from its raw bytes, checking they are a valid key of the
+    /// scheme
+    pub fn from_bytes(scheme: Scheme, bytes: &[u8]) -> Result<Self> {
+        Ok(match scheme {
+            Scheme::Ed25519 => Self::Ed25519(Ed25519PublicKey::try_from(bytes)?),
+            Scheme::MultiEd25519 => Self::MultiEd25519(MultiEd25519PublicKey::try_from(bytes)?),
+        })
+    }
+
+    /// Create the public key of a K-of-N multisignature account, signed by `threshold` of
+    /// `public_keys`
+    pub fn multi_ed25519(public_keys: Vec<Ed25519PublicKey>, threshold: u8) -> Result<Self> {
+        Ok(Self::MultiEd25519(MultiEd25519PublicKey::new(
+            public_keys,
+            threshold,
+        )?))
+    }
+
+    /// The signature scheme of this public key
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            Self::Ed25519(_) => Scheme::Ed25519,
+            Self::MultiEd25519(_) => Scheme::MultiEd25519,
+        }
+    }
+
+    /// The raw bytes of this public key, without the scheme id
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519(public_key) => public_key.to_bytes().to_vec(),
+            Self::MultiEd25519(public_key) => public_key.to_bytes(),
+        }
+    }
+
+    /// The preimage of the authentication key of this public key: (public_key | scheme_id)
+    pub fn authentication_key_preimage(&self) -> AuthenticationKeyPreimage {
+        AuthenticationKeyPreimage::new(self.to_bytes(), self.scheme())
+    }
+
+    /// The authentication key of this public key
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::from_preimage(&self.authentication_key_preimage())
+    }
+
+    /// The address of the account created with the authentication key of this public key
+    pub fn derived_address(&self) -> AccountAddress {
+        self.authentication_key().derived_address()
+    }
+}
+
+impl From<Ed25519PublicKey> for AccountPublicKey {
+    fn from(public_key: Ed25519PublicKey) -> Self {
+        Self::Ed25519(public_key)
+    }
+}
+
+impl From<MultiEd25519PublicKey> for AccountPublicKey {
+    fn from(public_key: MultiEd25519PublicKey) -> Self {
+        Self::MultiEd25519(public_key)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum TransactionAuthenticator {
     /// Single signature
@@ -59,6 +147,14 @@ pub enum TransactionAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// Sponsored transaction: the sender and the account paying for the gas, the fee payer, both
+    /// sign the transaction with their own authenticator. The inner authenticators cannot be
+    /// sponsored themselves.
+    FeePayer {
+        sender: Box<TransactionAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer: Box<TransactionAuthenticator>,
+    },
     // ... add more schemes here
 }
 
@@ -68,6 +164,7 @@ impl TransactionAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::FeePayer { sender, .. } => sender.scheme(),
         }
     }
 
@@ -90,6 +187,37 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create an authenticator for a transaction sponsored by `fee_payer_address`
+    pub fn fee_payer(
+        sender: TransactionAuthenticator,
+        fee_payer_address: AccountAddress,
+        fee_payer: TransactionAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender: Box::new(sender),
+            fee_payer_address,
+            fee_payer: Box::new(fee_payer),
+        }
+    }
+
+    /// Return the address of the account paying for the gas if the transaction is sponsored
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    /// Return the authenticator of the fee payer if the transaction is sponsored
+    pub fn fee_payer_authenticator(&self) -> Option<&TransactionAuthenticator> {
+        match self {
+            Self::FeePayer { fee_payer, .. } => Some(fee_payer),
+            _ => None,
+        }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify_signature(&self, message: &HashValue) -> Result<()> {
         match self {
@@ -101,33 +229,52 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::FeePayer {
+                sender, fee_payer, ..
+            } => {
+                ensure!(
+                    sender.fee_payer_address().is_none() && fee_payer.fee_payer_address().is_none(),
+                    "The authenticators of a sponsored transaction cannot be sponsored"
+                );
+                sender.verify_signature(message)?;
+                fee_payer.verify_signature(message)
+            }
         }
     }
 
-    /// Return the raw bytes of `self.public_key`
-    pub fn public_key_bytes(&self) -> Vec<u8> {
+    /// Return `self.public_key`, the one of the sender for sponsored transactions
+    pub fn public_key(&self) -> AccountPublicKey {
         match self {
-            Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
-            Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Ed25519 { public_key, .. } => AccountPublicKey::Ed25519(public_key.clone()),
+            Self::MultiEd25519 { public_key, .. } => {
+                AccountPublicKey::MultiEd25519(public_key.clone())
+            }
+            Self::FeePayer { sender, .. } => sender.public_key(),
         }
     }
 
-    /// Return the raw bytes of `self.signature`
+    /// Return the raw bytes of `self.public_key`, the one of the sender for sponsored transactions
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key().to_bytes()
+    }
+
+    /// Return the raw bytes of `self.signature`, the one of the sender for sponsored transactions
     pub fn signature_bytes(&self) -> Vec<u8> {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::FeePayer { sender, .. } => sender.signature_bytes(),
         }
     }
 
     /// Return an authentication key preimage derived from `self`'s public key and scheme id
     pub fn authentication_key_preimage(&self) -> AuthenticationKeyPreimage {
-        AuthenticationKeyPreimage::new(self.public_key_bytes(), self.scheme())
+        self.public_key().authentication_key_preimage()
     }
 
     /// Return an authentication key derived from `self`'s public key and scheme id
     pub fn authentication_key(&self) -> AuthenticationKey {
-        AuthenticationKey::from_preimage(&self.authentication_key_preimage())
+        self.public_key().authentication_key()
     }
 }
 
@@ -165,12 +312,12 @@ impl AuthenticationKey {
 
     /// Create an authentication key from an Ed25519 public key
     pub fn ed25519(public_key: &Ed25519PublicKey) -> AuthenticationKey {
-        Self::from_preimage(&AuthenticationKeyPreimage::ed25519(public_key))
+        AccountPublicKey::Ed25519(public_key.clone()).authentication_key()
     }
 
     /// Create an authentication key from a MultiEd25519 public key
     pub fn multi_ed25519(public_key: &MultiEd25519PublicKey) -> Self {
-        Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
+        AccountPublicKey::MultiEd25519(public_key.clone()).authentication_key()
     }
 
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
@@ -213,19 +360,27 @@ pub struct AuthenticationKeyPreimage(Vec<u8>);
 
 impl AuthenticationKeyPreimage {
     /// Return bytes for (public_key | scheme_id)
-    fn new(mut public_key_bytes: Vec<u8>, scheme: Scheme) -> Self {
+    pub fn new(mut public_key_bytes: Vec<u8>, scheme: Scheme) -> Self {
         public_key_bytes.push(scheme as u8);
         Self(public_key_bytes)
     }
 
     /// Construct a preimage from an Ed25519 public key
     pub fn ed25519(public_key: &Ed25519PublicKey) -> AuthenticationKeyPreimage {
-        Self::new(public_key.to_bytes().to_vec(), Scheme::Ed25519)
+        AccountPublicKey::Ed25519(public_key.clone()).authentication_key_preimage()
     }
 
     /// Construct a preimage from a MultiEd25519 public key
     pub fn multi_ed25519(public_key: &MultiEd25519PublicKey) -> AuthenticationKeyPreimage {
-        Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
+        AccountPublicKey::MultiEd25519(public_key.clone()).authentication_key_preimage()
+    }
+
+    /// Return the signature scheme of this preimage
+    pub fn scheme(&self) -> Result<Scheme> {
+        match self.0.last() {
+            Some(scheme_id) => Scheme::try_from(*scheme_id),
+            None => bail!("Empty authentication key preimage"),
+        }
     }
 
     /// Construct a vector from this authentication key
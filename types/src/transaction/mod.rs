@@ -41,7 +41,7 @@ mod script;
 mod transaction_argument;
 
 pub use change_set::ChangeSet;
-pub use module::Module;
+pub use module::{FunctionABI, Module, ModuleABI, ParameterABI, TypeABI};
 pub use script::{ArgumentABI, Script, ScriptABI, TypeArgumentABI, SCRIPT_HASH_LENGTH};
 
 use std::ops::Deref;
@@ -236,6 +236,31 @@ impl RawTransaction {
         )))
     }
 
+    /// Signs the transaction as both its sender and `fee_payer`, the account paying for its gas.
+    /// The sender and the fee payer usually sign on their own, by signing the hash of
+    /// [`RawTransactionWithFeePayer`].
+    pub fn sign_with_fee_payer(
+        self,
+        private_key: &Ed25519PrivateKey,
+        public_key: Ed25519PublicKey,
+        fee_payer: AccountAddress,
+        fee_payer_private_key: &Ed25519PrivateKey,
+        fee_payer_public_key: Ed25519PublicKey,
+    ) -> Result<SignatureCheckedTransaction> {
+        let message = RawTransactionWithFeePayer::new(self.clone(), fee_payer).hash();
+        let authenticator = TransactionAuthenticator::fee_payer(
+            TransactionAuthenticator::ed25519(public_key, private_key.sign_message(&message)),
+            fee_payer,
+            TransactionAuthenticator::ed25519(
+                fee_payer_public_key,
+                fee_payer_private_key.sign_message(&message),
+            ),
+        );
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_sponsored(self, authenticator)?,
+        ))
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn multi_sign_for_testing(
         self,
@@ -260,6 +285,10 @@ impl RawTransaction {
                 (get_transaction_name(script.code()), script.args())
             }
             TransactionPayload::Module(_) => ("module publishing".to_string(), &empty_vec[..]),
+            TransactionPayload::Batch(scripts) => (
+                format!("batch of {} scripts", scripts.len()),
+                &empty_vec[..],
+            ),
         };
         let mut f_args: String = "".to_string();
         for arg in args {
@@ -302,6 +331,24 @@ pub enum TransactionPayload {
     Script(Script),
     /// A transaction that publishes code.
     Module(Module),
+    /// A transaction that executes scripts one after the other, atomically: either all of them
+    /// succeed, or none of their effects are kept. The gas used by the scripts is charged as a
+    /// whole against the `max_gas_amount` of the transaction.
+    Batch(Vec<Script>),
+}
+
+/// The portion of a sponsored transaction that both its sender and its fee payer sign: binding the
+/// fee payer to the transaction prevents relays from changing or removing it.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, LCSCryptoHash)]
+pub struct RawTransactionWithFeePayer {
+    raw_txn: RawTransaction,
+    fee_payer: AccountAddress,
+}
+
+impl RawTransactionWithFeePayer {
+    pub fn new(raw_txn: RawTransaction, fee_payer: AccountAddress) -> Self {
+        Self { raw_txn, fee_payer }
+    }
 }
 
 /// A transaction that has been signed.
@@ -385,6 +432,22 @@ impl SignedTransaction {
         }
     }
 
+    /// Create a transaction sponsored by the fee payer of `authenticator`, which must be a
+    /// [`TransactionAuthenticator::FeePayer`].
+    pub fn new_sponsored(
+        raw_txn: RawTransaction,
+        authenticator: TransactionAuthenticator,
+    ) -> Result<SignedTransaction> {
+        ensure!(
+            authenticator.fee_payer_address().is_some(),
+            "A sponsored transaction needs a fee payer authenticator"
+        );
+        Ok(SignedTransaction {
+            raw_txn,
+            authenticator,
+        })
+    }
+
     pub fn authenticator(&self) -> TransactionAuthenticator {
         self.authenticator.clone()
     }
@@ -393,6 +456,14 @@ impl SignedTransaction {
         self.raw_txn.sender
     }
 
+    /// Returns the account paying for the gas: the fee payer for sponsored transactions, the
+    /// sender otherwise.
+    pub fn fee_payer(&self) -> AccountAddress {
+        self.authenticator
+            .fee_payer_address()
+            .unwrap_or(self.raw_txn.sender)
+    }
+
     pub fn into_raw_transaction(self) -> RawTransaction {
         self.raw_txn
     }
@@ -430,10 +501,63 @@ impl SignedTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.authenticator.verify_signature(&self.raw_txn.hash())?;
+        self.authenticator
+            .verify_signature(&self.signing_message())?;
         Ok(SignatureCheckedTransaction(self))
     }
 
+    /// The hash signed by the authenticator: the one of the raw transaction, or the one of the raw
+    /// transaction and the fee payer for sponsored transactions.
+    fn signing_message(&self) -> HashValue {
+        match self.authenticator.fee_payer_address() {
+            Some(fee_payer) => {
+                RawTransactionWithFeePayer::new(self.raw_txn.clone(), fee_payer).hash()
+            }
+            None => self.raw_txn.hash(),
+        }
+    }
+
+    /// Checks the signatures of a batch of transactions, returning the result of
+    /// [`check_signature`](SignedTransaction::check_signature) for each of them in order.
+    ///
+    /// Single-signature transactions are verified together using Ed25519 batch verification.
+    /// If the batch does not verify, every transaction is checked individually to find out
+    /// which ones are invalid.
+    pub fn check_signatures(txns: Vec<Self>) -> Vec<Result<SignatureCheckedTransaction>> {
+        let hashes: Vec<_> = txns
+            .iter()
+            .map(SignedTransaction::signing_message)
+            .collect();
+        let mut messages = vec![];
+        let mut keys = vec![];
+        let mut signatures = vec![];
+        for (txn, hash) in txns.iter().zip(hashes.iter()) {
+            if let TransactionAuthenticator::Ed25519 {
+                public_key,
+                signature,
+            } = &txn.authenticator
+            {
+                messages.push(&hash.as_ref()[..]);
+                keys.push(public_key);
+                signatures.push(signature);
+            }
+        }
+        let batch_verified = !keys.is_empty()
+            && Ed25519Signature::batch_verify(&messages, &keys, &signatures).is_ok();
+
+        txns.into_iter()
+            .zip(hashes.iter())
+            .map(|(txn, hash)| {
+                let batch_checked = batch_verified
+                    && matches!(txn.authenticator, TransactionAuthenticator::Ed25519 { .. });
+                if !batch_checked {
+                    txn.authenticator.verify_signature(hash)?;
+                }
+                Ok(SignatureCheckedTransaction(txn))
+            })
+            .collect()
+    }
+
     pub fn format_for_client(&self, get_transaction_name: impl Fn(&[u8]) -> String) -> String {
         format!(
             "SignedTransaction {{ \n \
@@ -1,28 +1,183 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::transaction::TypeArgumentABI;
+use move_core_types::language_storage::ModuleId;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Module {
+    #[serde(with = "crate::serde_helpers::hex_bytes")]
     code: Vec<u8>,
+    /// The compressed source map of the module, published alongside its code so that aborts can
+    /// be mapped back to source lines
+    source_map: Option<Vec<u8>>,
 }
 
 impl Module {
     pub fn new(code: Vec<u8>) -> Module {
-        Module { code }
+        Module {
+            code,
+            source_map: None,
+        }
+    }
+
+    pub fn with_source_map(code: Vec<u8>, source_map: Vec<u8>) -> Module {
+        Module {
+            code,
+            source_map: Some(source_map),
+        }
     }
 
     pub fn code(&self) -> &[u8] {
         &self.code
     }
+
+    pub fn source_map(&self) -> Option<&[u8]> {
+        self.source_map.as_deref()
+    }
 }
 
 impl fmt::Debug for Module {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Module")
             .field("code", &hex::encode(&self.code))
+            .field("source_map", &self.source_map.as_ref().map(hex::encode))
             .finish()
     }
 }
+
+/// How to call the public functions of a particular Move module (aka. a "module ABI").
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ModuleABI {
+    /// The address and name of the module.
+    module_id: ModuleId,
+    /// Some text comment.
+    doc: String,
+    /// The public functions of the module.
+    functions: Vec<FunctionABI>,
+}
+
+/// The description of a public function in a module.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FunctionABI {
+    /// The name of the function.
+    name: String,
+    /// Some text comment.
+    doc: String,
+    /// The names of the type arguments.
+    ty_args: Vec<TypeArgumentABI>,
+    /// The description of regular arguments.
+    args: Vec<ParameterABI>,
+    /// The types of the returned values.
+    returns: Vec<TypeABI>,
+}
+
+/// The description of a (regular) argument of a function.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ParameterABI {
+    /// The name of the argument.
+    name: String,
+    /// The expected type.
+    type_abi: TypeABI,
+}
+
+/// The type of an argument or of a returned value of a function.
+/// Unlike a `TypeTag`, it may refer to the type arguments of the function and be a reference.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TypeABI {
+    Bool,
+    U8,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<TypeABI>),
+    Struct {
+        module_id: ModuleId,
+        name: String,
+        ty_args: Vec<TypeABI>,
+    },
+    /// The type argument of the function at the given index.
+    TypeParameter(u16),
+    Reference {
+        mutable: bool,
+        to: Box<TypeABI>,
+    },
+}
+
+impl ModuleABI {
+    pub fn new(module_id: ModuleId, doc: String, functions: Vec<FunctionABI>) -> Self {
+        Self {
+            module_id,
+            doc,
+            functions,
+        }
+    }
+
+    pub fn module_id(&self) -> &ModuleId {
+        &self.module_id
+    }
+
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    pub fn functions(&self) -> &[FunctionABI] {
+        &self.functions
+    }
+}
+
+impl FunctionABI {
+    pub fn new(
+        name: String,
+        doc: String,
+        ty_args: Vec<TypeArgumentABI>,
+        args: Vec<ParameterABI>,
+        returns: Vec<TypeABI>,
+    ) -> Self {
+        Self {
+            name,
+            doc,
+            ty_args,
+            args,
+            returns,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn doc(&self) -> &str {
+        &self.doc
+    }
+
+    pub fn ty_args(&self) -> &[TypeArgumentABI] {
+        &self.ty_args
+    }
+
+    pub fn args(&self) -> &[ParameterABI] {
+        &self.args
+    }
+
+    pub fn returns(&self) -> &[TypeABI] {
+        &self.returns
+    }
+}
+
+impl ParameterABI {
+    pub fn new(name: String, type_abi: TypeABI) -> Self {
+        Self { name, type_abi }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_abi(&self) -> &TypeABI {
+        &self.type_abi
+    }
+}
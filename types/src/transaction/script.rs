@@ -12,6 +12,7 @@ pub const SCRIPT_HASH_LENGTH: usize = 32;
 /// Call a Move script.
 #[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Script {
+    #[serde(with = "crate::serde_helpers::hex_bytes")]
     code: Vec<u8>,
     ty_args: Vec<TypeTag>,
     args: Vec<TransactionArgument>,
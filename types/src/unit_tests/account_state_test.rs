@@ -0,0 +1,37 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    account_config::{coin1_tag, BalanceResource, Coin1, COIN1_NAME, LBR},
+    account_state::AccountState,
+};
+use move_core_types::{identifier::Identifier, move_resource::MoveResource};
+
+#[test]
+fn test_get_move_resource() {
+    let mut account_state = AccountState::default();
+    account_state.insert(
+        BalanceResource::<Coin1>::resource_path(),
+        lcs::to_bytes(&BalanceResource::<Coin1>::new(10)).unwrap(),
+    );
+
+    let balance = account_state
+        .get_move_resource::<BalanceResource<Coin1>>()
+        .unwrap()
+        .unwrap();
+    assert_eq!(balance.coin(), 10);
+    assert!(account_state
+        .get_move_resource::<BalanceResource<LBR>>()
+        .unwrap()
+        .is_none());
+
+    // The balances of currencies known at runtime are at the same path
+    assert_eq!(
+        BalanceResource::<Coin1>::resource_path(),
+        BalanceResource::access_path_for(coin1_tag())
+    );
+    let balances = account_state
+        .get_balance_resources(&[Identifier::new(COIN1_NAME).unwrap()])
+        .unwrap();
+    assert_eq!(balances.len(), 1);
+}
@@ -0,0 +1,34 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_congestion::BlockCongestionResource;
+use move_core_types::gas_schedule::{GasAlgebra, GasConstants, GasPrice};
+
+#[test]
+fn test_min_gas_unit_price_tiers() {
+    let gas_constants = GasConstants::default();
+    let min_gas_unit_price = |fullness| {
+        BlockCongestionResource::new(1000, 0, fullness).min_gas_unit_price(&gas_constants)
+    };
+
+    assert_eq!(min_gas_unit_price(0), 0);
+    assert_eq!(min_gas_unit_price(49), 0);
+    assert_eq!(min_gas_unit_price(50), 1);
+    assert_eq!(min_gas_unit_price(74), 1);
+    assert_eq!(min_gas_unit_price(75), 5);
+    assert_eq!(min_gas_unit_price(90), 20);
+    assert_eq!(min_gas_unit_price(100), 20);
+}
+
+#[test]
+fn test_min_gas_unit_price_bounds() {
+    let mut gas_constants = GasConstants::default();
+    gas_constants.min_price_per_gas_unit = GasPrice::new(10);
+    gas_constants.max_price_per_gas_unit = GasPrice::new(25);
+
+    // The surcharge is added to the minimum of the gas schedule, up to its maximum
+    let congestion = BlockCongestionResource::new(1000, 0, 80);
+    assert_eq!(congestion.min_gas_unit_price(&gas_constants), 15);
+    let congestion = BlockCongestionResource::new(1000, 0, 95);
+    assert_eq!(congestion.min_gas_unit_price(&gas_constants), 25);
+}
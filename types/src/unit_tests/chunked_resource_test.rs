@@ -0,0 +1,140 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_state::AccountState,
+    chunked_resource::{
+        chunk, chunk_path, chunk_write_set, header_path, read, reassemble, reassemble_account_state,
+    },
+    write_set::{WriteOp, WriteSetMut},
+};
+use libra_crypto::HashValue;
+use std::collections::BTreeMap;
+
+const CHUNK_SIZE: usize = 4;
+
+type Storage = BTreeMap<AccessPath, Vec<u8>>;
+
+fn resource_path() -> AccessPath {
+    let mut path = vec![AccessPath::RESOURCE_TAG];
+    path.extend_from_slice(&HashValue::random().to_vec());
+    AccessPath::new(AccountAddress::random(), path)
+}
+
+fn get(storage: &Storage, access_path: &AccessPath) -> Result<Option<Vec<u8>>, std::io::Error> {
+    Ok(storage.get(access_path).cloned())
+}
+
+// Writes `write_op` to `access_path` the way the VM does, and returns the resulting storage
+fn write(storage: &Storage, access_path: &AccessPath, write_op: WriteOp) -> Storage {
+    let write_set = WriteSetMut::new(vec![(access_path.clone(), write_op)])
+        .freeze()
+        .unwrap();
+    let write_set = chunk_write_set(write_set, CHUNK_SIZE, |ap| get(storage, ap)).unwrap();
+    let mut storage = storage.clone();
+    for (ap, write_op) in write_set {
+        match write_op {
+            WriteOp::Value(value) => storage.insert(ap, value),
+            WriteOp::Deletion => storage.remove(&ap),
+        };
+    }
+    storage
+}
+
+#[test]
+fn test_chunk_and_reassemble() {
+    let value: Vec<u8> = (0..10).collect();
+    let (header, chunks) = chunk(&value, CHUNK_SIZE).unwrap();
+    assert_eq!(header.length, 10);
+    assert_eq!(header.num_chunks, 3);
+    assert_eq!(chunks.last().unwrap(), &vec![8, 9]);
+    assert_eq!(reassemble(&header, chunks.clone()).unwrap(), value);
+
+    // A missing, reordered or modified chunk is rejected
+    assert!(reassemble(&header, chunks[..2].to_vec()).is_err());
+    let mut reordered = chunks.clone();
+    reordered.swap(0, 1);
+    assert!(reassemble(&header, reordered).is_err());
+    let mut modified = chunks;
+    modified[2][0] = 0;
+    assert!(reassemble(&header, modified).is_err());
+
+    assert!(chunk(&value, 0).is_err());
+}
+
+#[test]
+fn test_chunk_write_set() {
+    let access_path = resource_path();
+    let large_value: Vec<u8> = (0..10).collect();
+    let small_value = vec![1, 2];
+
+    // A large resource is only stored as chunks
+    let storage = write(
+        &Storage::new(),
+        &access_path,
+        WriteOp::Value(large_value.clone()),
+    );
+    assert_eq!(storage.len(), 4);
+    assert!(!storage.contains_key(&access_path));
+    assert!(storage.contains_key(&header_path(&access_path)));
+    assert_eq!(storage.get(&chunk_path(&access_path, 2)), Some(&vec![8, 9]));
+    assert_eq!(
+        read(&access_path, |ap| get(&storage, ap)).unwrap(),
+        Some(large_value.clone())
+    );
+
+    // The chunks left over when it shrinks are deleted
+    let storage = write(&storage, &access_path, WriteOp::Value(vec![0; 6]));
+    assert_eq!(storage.len(), 3);
+    assert_eq!(
+        read(&access_path, |ap| get(&storage, ap)).unwrap(),
+        Some(vec![0; 6])
+    );
+
+    // Down to an inline resource
+    let storage = write(&storage, &access_path, WriteOp::Value(small_value.clone()));
+    assert_eq!(storage.len(), 1);
+    assert_eq!(
+        read(&access_path, |ap| get(&storage, ap)).unwrap(),
+        Some(small_value)
+    );
+
+    // And back to chunks, which replace the inline value
+    let storage = write(&storage, &access_path, WriteOp::Value(large_value));
+    assert_eq!(storage.len(), 4);
+    assert!(!storage.contains_key(&access_path));
+
+    // Deleting the resource deletes its chunks
+    let chunked_storage = storage.clone();
+    let storage = write(&storage, &access_path, WriteOp::Deletion);
+    assert!(storage.is_empty());
+    assert_eq!(read(&access_path, |ap| get(&storage, ap)).unwrap(), None);
+
+    // A tampered chunk is detected by the reader
+    let mut storage = chunked_storage;
+    storage.insert(chunk_path(&access_path, 1), vec![0; 4]);
+    assert!(read(&access_path, |ap| get(&storage, ap)).is_err());
+    storage.remove(&chunk_path(&access_path, 1));
+    assert!(read(&access_path, |ap| get(&storage, ap)).is_err());
+}
+
+#[test]
+fn test_reassemble_account_state() {
+    let access_path = resource_path();
+    let large_value: Vec<u8> = (0..10).collect();
+    let storage = write(
+        &Storage::new(),
+        &access_path,
+        WriteOp::Value(large_value.clone()),
+    );
+
+    let mut account_state = AccountState::default();
+    for (ap, value) in storage {
+        account_state.insert(ap.path, value);
+    }
+    let reassembled = reassemble_account_state(&account_state).unwrap();
+    assert_eq!(reassembled.iter().count(), 1);
+    assert_eq!(reassembled.get(&access_path.path), Some(&large_value));
+}
@@ -1,8 +1,13 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::contract_event::{ContractEvent, EventWithProof};
+use crate::{
+    account_address::AccountAddress,
+    contract_event::{ContractEvent, EventWithProof},
+    event::EventKey,
+};
 use lcs::test_helpers::assert_canonical_encode_decode;
+use move_core_types::language_storage::TypeTag;
 use proptest::prelude::*;
 
 proptest! {
@@ -16,3 +21,14 @@ proptest! {
         assert_canonical_encode_decode(event_with_proof);
     }
 }
+
+#[test]
+fn typed_event_keys() {
+    let address = AccountAddress::random();
+    let key = EventKey::new_for_type(&address, &TypeTag::U64);
+    assert!(key.is_typed());
+    assert_eq!(key.get_creator_address(), address);
+    assert_eq!(key, EventKey::new_for_type(&address, &TypeTag::U64));
+    assert_ne!(key, EventKey::new_for_type(&address, &TypeTag::Bool));
+    assert!(!EventKey::new_from_address(&address, 2).is_typed());
+}
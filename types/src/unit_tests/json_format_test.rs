@@ -0,0 +1,151 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The JSON encoding of the core types is relied on by services not written in Rust, so it is
+//! recorded in the `json_format` directory. A failure here means that the encoding changed, e.g.
+//! because a field was renamed: if the change is intended, update the recorded file and consider
+//! tagging the pull-request as `breaking`.
+
+use crate::{
+    account_address::AccountAddress,
+    account_state_blob::AccountStateBlob,
+    block_metadata::BlockMetadata,
+    contract_event::ContractEvent,
+    event::EventKey,
+    transaction::{
+        RawTransaction, Script, SignedTransaction, Transaction, TransactionArgument,
+        TransactionInfo,
+    },
+    vm_error::StatusCode,
+};
+use libra_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    HashValue,
+};
+use move_core_types::language_storage::TypeTag;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{convert::TryFrom, fmt::Debug, time::Duration};
+
+fn assert_json_format<T>(value: &T, recorded: &str, file: &str)
+where
+    T: Debug + DeserializeOwned + PartialEq + Serialize,
+{
+    let expected: serde_json::Value = serde_json::from_str(recorded).unwrap();
+    assert_eq!(
+        serde_json::to_value(value).unwrap(),
+        expected,
+        "The JSON encoding does not match the one recorded in {}",
+        file,
+    );
+    assert_eq!(
+        &serde_json::from_value::<T>(expected).unwrap(),
+        value,
+        "The JSON recorded in {} does not decode to the same value",
+        file,
+    );
+}
+
+fn address() -> AccountAddress {
+    AccountAddress::new([0x11; AccountAddress::LENGTH])
+}
+
+#[test]
+fn test_contract_event_json_format() {
+    let event = ContractEvent::new(
+        EventKey::new([0x22; EventKey::LENGTH]),
+        7,
+        TypeTag::Vector(Box::new(TypeTag::U8)),
+        vec![1, 2, 3],
+    );
+    assert_json_format(
+        &event,
+        include_str!("json_format/contract_event.json"),
+        "contract_event.json",
+    );
+}
+
+#[test]
+fn test_account_state_blob_json_format() {
+    let blob = AccountStateBlob::from(vec![0xca, 0xfe]);
+    assert_json_format(
+        &blob,
+        include_str!("json_format/account_state_blob.json"),
+        "account_state_blob.json",
+    );
+}
+
+#[test]
+fn test_transaction_info_json_format() {
+    let txn_info = TransactionInfo::new(
+        HashValue::new([1; HashValue::LENGTH]),
+        HashValue::new([2; HashValue::LENGTH]),
+        HashValue::new([3; HashValue::LENGTH]),
+        100,
+        StatusCode::EXECUTED,
+    );
+    assert_json_format(
+        &txn_info,
+        include_str!("json_format/transaction_info.json"),
+        "transaction_info.json",
+    );
+}
+
+#[test]
+fn test_user_transaction_json_format() {
+    let raw_txn = RawTransaction::new_script(
+        address(),
+        3,
+        Script::new(
+            vec![0xde, 0xad],
+            vec![TypeTag::Bool],
+            vec![
+                TransactionArgument::U64(5),
+                TransactionArgument::Address(address()),
+                TransactionArgument::U8Vector(vec![0xab]),
+                TransactionArgument::Bool(true),
+            ],
+        ),
+        1_000_000,
+        0,
+        "LBR".to_string(),
+        Duration::from_secs(86400),
+    );
+    // The first test vector of RFC 8032
+    let public_key =
+        Ed25519PublicKey::try_from(
+            &hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap()[..],
+        )
+        .unwrap();
+    let signature = Ed25519Signature::try_from(
+        &hex::decode(
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        )
+        .unwrap()[..],
+    )
+    .unwrap();
+    let txn = Transaction::UserTransaction(SignedTransaction::new(raw_txn, public_key, signature));
+    assert_json_format(
+        &txn,
+        include_str!("json_format/user_transaction.json"),
+        "user_transaction.json",
+    );
+}
+
+#[test]
+fn test_block_metadata_json_format() {
+    let txn = Transaction::BlockMetadata(BlockMetadata::new(
+        HashValue::new([4; HashValue::LENGTH]),
+        HashValue::new([3; HashValue::LENGTH]),
+        5,
+        6,
+        vec![address()],
+        address(),
+        vec![address()],
+    ));
+    assert_json_format(
+        &txn,
+        include_str!("json_format/block_metadata.json"),
+        "block_metadata.json",
+    );
+}
@@ -2,11 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod access_path_test;
+mod account_state_test;
+mod block_congestion_test;
 mod block_metadata_test;
 mod canonical_serialization_examples;
+mod chunked_resource_test;
 mod code_debug_fmt_test;
 mod contract_event_test;
+mod json_format_test;
+mod on_chain_config_test;
 mod transaction_test;
 mod trusted_state_test;
+mod validator_config_test;
 mod validator_set_test;
 mod write_set_test;
@@ -0,0 +1,71 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::{
+    apply_pending_config, LibraVersion, OnChainConfig, OnChainConfigPayload, ValidatorSet,
+};
+use std::{collections::HashMap, sync::Arc};
+
+fn payload_with_version(major: u64) -> OnChainConfigPayload {
+    let mut configs = HashMap::new();
+    configs.insert(
+        LibraVersion::CONFIG_ID,
+        lcs::to_bytes(&LibraVersion { major }).unwrap(),
+    );
+    OnChainConfigPayload::new(1, Arc::new(configs))
+}
+
+#[test]
+fn test_payload_get_config() {
+    let payload = payload_with_version(7);
+    assert_eq!(payload.get::<LibraVersion>().unwrap().major, 7);
+    // Served from the cache, shared with the clones of the payload
+    assert_eq!(payload.clone().get::<LibraVersion>().unwrap().major, 7);
+    assert!(payload.get::<ValidatorSet>().is_err());
+}
+
+#[test]
+fn test_payload_equality_ignores_cache() {
+    let payload = payload_with_version(7);
+    let other = payload_with_version(7);
+    payload.get::<LibraVersion>().unwrap();
+    assert_eq!(payload, other);
+    assert_ne!(payload, payload_with_version(8));
+}
+
+fn pending_version(activation_epoch: u64, major: Option<u64>) -> Vec<u8> {
+    // The Move `Option` is a vector of at most one element
+    let payload: Vec<LibraVersion> = major
+        .into_iter()
+        .map(|major| LibraVersion { major })
+        .collect();
+    lcs::to_bytes(&(activation_epoch, payload)).unwrap()
+}
+
+#[test]
+fn test_apply_pending_config() {
+    let current = lcs::to_bytes(&LibraVersion { major: 1 }).unwrap();
+    let scheduled = lcs::to_bytes(&LibraVersion { major: 2 }).unwrap();
+
+    let pending = pending_version(5, Some(2));
+    assert_eq!(
+        apply_pending_config(current.clone(), &pending, 4).unwrap(),
+        current
+    );
+    assert_eq!(
+        apply_pending_config(current.clone(), &pending, 5).unwrap(),
+        scheduled
+    );
+    assert_eq!(
+        apply_pending_config(current.clone(), &pending, 6).unwrap(),
+        scheduled
+    );
+
+    let nothing_pending = pending_version(0, None);
+    assert_eq!(
+        apply_pending_config(current.clone(), &nothing_pending, 6).unwrap(),
+        current
+    );
+
+    assert!(apply_pending_config(current, &pending[..8], 6).is_err());
+}
@@ -5,6 +5,10 @@ use crate::{
     account_address::AccountAddress,
     account_config::LBR_NAME,
     transaction::{
+        authenticator::{
+            AccountPublicKey, AuthenticationKey, AuthenticationKeyPreimage, Scheme,
+            TransactionAuthenticator,
+        },
         RawTransaction, Script, SignedTransaction, Transaction, TransactionInfo,
         TransactionListWithProof, TransactionPayload, TransactionWithProof,
     },
@@ -36,7 +40,96 @@ fn test_invalid_signature() {
         .expect_err("signature checking should fail");
 }
 
+#[test]
+fn test_authentication_key_scheme() {
+    let public_key = Ed25519PrivateKey::generate_for_testing().public_key();
+    let preimage = AuthenticationKeyPreimage::ed25519(&public_key);
+    assert_eq!(preimage.scheme().unwrap(), Scheme::Ed25519);
+    assert_eq!(
+        AuthenticationKey::from_preimage(&AuthenticationKeyPreimage::new(
+            public_key.to_bytes().to_vec(),
+            Scheme::Ed25519
+        )),
+        AuthenticationKey::ed25519(&public_key)
+    );
+
+    assert_eq!(Scheme::try_from(1).unwrap(), Scheme::MultiEd25519);
+    assert!(Scheme::try_from(2).is_err());
+}
+
+#[test]
+fn test_account_public_key_derivation() {
+    let public_key = Ed25519PrivateKey::generate_for_testing().public_key();
+    let account_public_key = AccountPublicKey::from(public_key.clone());
+    assert_eq!(
+        account_public_key.authentication_key(),
+        AuthenticationKey::ed25519(&public_key)
+    );
+    assert_eq!(
+        account_public_key.derived_address(),
+        crate::account_address::from_public_key(&public_key)
+    );
+    assert_eq!(
+        AccountPublicKey::from_bytes(Scheme::Ed25519, &public_key.to_bytes()).unwrap(),
+        account_public_key
+    );
+
+    let other_public_key = Ed25519PrivateKey::generate_for_testing().public_key();
+    let multi_public_key =
+        AccountPublicKey::multi_ed25519(vec![public_key.clone(), other_public_key], 1).unwrap();
+    assert_eq!(multi_public_key.scheme(), Scheme::MultiEd25519);
+    // the same key in another scheme has another authentication key and address
+    assert_ne!(
+        multi_public_key.derived_address(),
+        account_public_key.derived_address()
+    );
+    assert_eq!(
+        AccountPublicKey::from_bytes(Scheme::MultiEd25519, &multi_public_key.to_bytes()).unwrap(),
+        multi_public_key
+    );
+    match &multi_public_key {
+        AccountPublicKey::MultiEd25519(key) => assert_eq!(
+            multi_public_key.authentication_key(),
+            AuthenticationKey::multi_ed25519(key)
+        ),
+        AccountPublicKey::Ed25519(_) => unreachable!(),
+    }
+
+    assert!(AccountPublicKey::from_bytes(Scheme::MultiEd25519, &public_key.to_bytes()).is_err());
+    assert!(AccountPublicKey::multi_ed25519(vec![public_key], 2).is_err());
+}
+
 proptest! {
+    #[test]
+    fn test_check_signatures(
+        raw_txns in prop::collection::vec(any::<RawTransaction>(), 1..10),
+        keypair in ed25519::keypair_strategy(),
+        invalid_idx in any::<prop::sample::Index>(),
+    ) {
+        let mut txns: Vec<_> = raw_txns
+            .into_iter()
+            .map(|raw_txn| {
+                raw_txn
+                    .sign(&keypair.private_key, keypair.public_key.clone())
+                    .unwrap()
+                    .into_inner()
+            })
+            .collect();
+        prop_assert!(SignedTransaction::check_signatures(txns.clone()).iter().all(|r| r.is_ok()));
+
+        // Replace one transaction with one whose signature doesn't match.
+        let idx = invalid_idx.index(txns.len());
+        txns[idx] = SignedTransaction::new(
+            txns[idx].clone().into_raw_transaction(),
+            keypair.public_key.clone(),
+            Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+        );
+        let results = SignedTransaction::check_signatures(txns);
+        for (i, result) in results.iter().enumerate() {
+            prop_assert_eq!(result.is_ok(), i != idx);
+        }
+    }
+
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
         let txn = raw_txn.sign(&keypair.private_key, keypair.public_key).unwrap();
@@ -44,6 +137,52 @@ proptest! {
         assert!(signed_txn.check_signature().is_ok());
     }
 
+    #[test]
+    fn test_sign_sponsored_transaction(
+        raw_txn in any::<RawTransaction>(),
+        fee_payer in any::<AccountAddress>(),
+        keypair in ed25519::keypair_strategy(),
+        fee_payer_keypair in ed25519::keypair_strategy(),
+    ) {
+        let signed_txn = raw_txn
+            .clone()
+            .sign_with_fee_payer(
+                &keypair.private_key,
+                keypair.public_key.clone(),
+                fee_payer,
+                &fee_payer_keypair.private_key,
+                fee_payer_keypair.public_key.clone(),
+            )
+            .unwrap()
+            .into_inner();
+        prop_assert_eq!(signed_txn.fee_payer(), fee_payer);
+        prop_assert!(signed_txn.clone().check_signature().is_ok());
+        prop_assert!(SignedTransaction::check_signatures(vec![signed_txn.clone()])[0].is_ok());
+
+        // The signatures are bound to the fee payer: relays can neither change nor remove it.
+        let authenticator = signed_txn.authenticator();
+        let (sender, fee_payer_authenticator) = match authenticator {
+            TransactionAuthenticator::FeePayer { sender, fee_payer, .. } => (sender, fee_payer),
+            _ => unreachable!(),
+        };
+        let other_fee_payer = SignedTransaction::new_sponsored(
+            raw_txn.clone(),
+            TransactionAuthenticator::fee_payer(
+                (*sender).clone(),
+                AccountAddress::random(),
+                *fee_payer_authenticator,
+            ),
+        )
+        .unwrap();
+        prop_assert!(other_fee_payer.check_signature().is_err());
+        let (public_key, signature) = match *sender {
+            TransactionAuthenticator::Ed25519 { public_key, signature } => (public_key, signature),
+            _ => unreachable!(),
+        };
+        let unsponsored = SignedTransaction::new(raw_txn, public_key, signature);
+        prop_assert!(unsponsored.check_signature().is_err());
+    }
+
     #[test]
     fn transaction_payload_lcs_roundtrip(txn_payload in any::<TransactionPayload>()) {
         assert_canonical_encode_decode(txn_payload);
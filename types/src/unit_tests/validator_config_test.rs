@@ -0,0 +1,92 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator_config::{NextNetworkConfig, ValidatorConfig};
+use lcs::test_helpers::assert_canonical_encode_decode;
+use libra_crypto::{ed25519::Ed25519PublicKey, x25519};
+use libra_network_address::RawNetworkAddress;
+use proptest::prelude::*;
+use serde::Serialize;
+
+/// The layout of `ValidatorConfig::Config` in the Move stdlib.
+#[derive(Serialize)]
+struct MoveValidatorConfig {
+    consensus_pubkey: Ed25519PublicKey,
+    validator_network_identity_pubkey: x25519::PublicKey,
+    validator_network_address: RawNetworkAddress,
+    full_node_network_identity_pubkey: x25519::PublicKey,
+    full_node_network_address: RawNetworkAddress,
+    #[serde(with = "serde_bytes")]
+    validator_network_next_identity_pubkey: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    validator_network_next_address: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    full_node_network_next_identity_pubkey: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    full_node_network_next_address: Vec<u8>,
+}
+
+impl MoveValidatorConfig {
+    fn without_next(config: &ValidatorConfig) -> Self {
+        MoveValidatorConfig {
+            consensus_pubkey: config.consensus_public_key.clone(),
+            validator_network_identity_pubkey: config.validator_network_identity_public_key,
+            validator_network_address: config.validator_network_address.clone(),
+            full_node_network_identity_pubkey: config.full_node_network_identity_public_key,
+            full_node_network_address: config.full_node_network_address.clone(),
+            validator_network_next_identity_pubkey: vec![],
+            validator_network_next_address: vec![],
+            full_node_network_next_identity_pubkey: vec![],
+            full_node_network_next_address: vec![],
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(20))]
+
+    #[test]
+    fn test_validator_config_canonical_serialization(config in any::<ValidatorConfig>()) {
+        assert_canonical_encode_decode(config);
+    }
+
+    #[test]
+    fn test_move_layout(mut config in any::<ValidatorConfig>(), next in any::<NextNetworkConfig>()) {
+        config.validator_next_network_config = None;
+        config.full_node_next_network_config = None;
+        let mut move_config = MoveValidatorConfig::without_next(&config);
+        prop_assert_eq!(lcs::to_bytes(&config).unwrap(), lcs::to_bytes(&move_config).unwrap());
+
+        config.validator_next_network_config = Some(next.clone());
+        move_config.validator_network_next_identity_pubkey =
+            next.identity_public_key.as_slice().to_vec();
+        move_config.validator_network_next_address = next.address.clone().into();
+        prop_assert_eq!(lcs::to_bytes(&config).unwrap(), lcs::to_bytes(&move_config).unwrap());
+    }
+
+    #[test]
+    fn test_next_network_config_accepted(
+        mut config in any::<ValidatorConfig>(),
+        next in any::<NextNetworkConfig>(),
+    ) {
+        config.validator_next_network_config = Some(next.clone());
+        let keys = config.validator_network_identity_public_keys();
+        prop_assert!(keys.contains(&config.validator_network_identity_public_key));
+        prop_assert!(keys.contains(&next.identity_public_key));
+        prop_assert_eq!(
+            config.validator_network_addresses(),
+            vec![config.validator_network_address.clone(), next.address]
+        );
+    }
+
+    #[test]
+    fn test_next_key_without_address_rejected(
+        config in any::<ValidatorConfig>(),
+        next_key in any::<x25519::PublicKey>(),
+    ) {
+        let mut move_config = MoveValidatorConfig::without_next(&config);
+        move_config.full_node_network_next_identity_pubkey = next_key.as_slice().to_vec();
+        let bytes = lcs::to_bytes(&move_config).unwrap();
+        prop_assert!(lcs::from_bytes::<ValidatorConfig>(&bytes).is_err());
+    }
+}
@@ -1,8 +1,14 @@
 // Copyright (c) The Libra Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::on_chain_config::ValidatorSet;
+use crate::{
+    account_address::AccountAddress,
+    on_chain_config::{ValidatorSet, ValidatorSetError},
+    validator_info::ValidatorInfo,
+    validator_signer::ValidatorSigner,
+};
 use lcs::test_helpers::assert_canonical_encode_decode;
+use libra_network_address::RawNetworkAddress;
 use proptest::prelude::*;
 
 proptest! {
@@ -13,3 +19,68 @@ proptest! {
         assert_canonical_encode_decode(set);
     }
 }
+
+fn validator(num: u8, voting_power: u64) -> ValidatorInfo {
+    let signer = ValidatorSigner::from_int(num);
+    ValidatorInfo::new_with_test_network_keys(signer.author(), signer.public_key(), voting_power)
+}
+
+#[test]
+fn test_verify_validator_set() {
+    let set = ValidatorSet::new(vec![validator(0, 1), validator(1, 2)]);
+    assert_eq!(set.verify(), Ok(()));
+    let author = ValidatorSigner::from_int(1).author();
+    assert_eq!(set.verify_includes(author), Ok(()));
+    let other = ValidatorSigner::from_int(2).author();
+    assert_eq!(
+        set.verify_includes(other),
+        Err(ValidatorSetError::NotInValidatorSet(other))
+    );
+
+    assert_eq!(
+        ValidatorSet::empty().verify(),
+        Err(ValidatorSetError::Empty)
+    );
+    assert_eq!(
+        ValidatorSet::new(vec![validator(0, 1), validator(0, 1)]).verify(),
+        Err(ValidatorSetError::DuplicateValidator(
+            ValidatorSigner::from_int(0).author()
+        ))
+    );
+    assert_eq!(
+        ValidatorSet::new(vec![validator(0, 0)]).verify(),
+        Err(ValidatorSetError::NoVotingPower)
+    );
+}
+
+#[test]
+fn test_verify_voting_power_arithmetic() {
+    let author = ValidatorSigner::from_int(1).author();
+    assert_eq!(
+        ValidatorSet::new(vec![validator(0, u64::max_value()), validator(1, 1)]).verify(),
+        Err(ValidatorSetError::VotingPowerOverflow(author))
+    );
+    assert_eq!(
+        ValidatorSet::new(vec![validator(0, u64::max_value())]).verify(),
+        Err(ValidatorSetError::QuorumOverflow(u64::max_value()))
+    );
+    assert_eq!(
+        ValidatorSet::new(vec![validator(0, u64::max_value() / 2)]).verify(),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_network_address() {
+    let info = validator(0, 1);
+    let mut config = info.config().clone();
+    config.full_node_network_address = RawNetworkAddress::new(vec![0xff]);
+    let account: AccountAddress = *info.account_address();
+    let set = ValidatorSet::new(vec![ValidatorInfo::new(account, 1, config)]);
+    match set.verify() {
+        Err(ValidatorSetError::InvalidNetworkAddress(invalid, "full node", _)) => {
+            assert_eq!(invalid, account)
+        }
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
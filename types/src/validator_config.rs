@@ -2,12 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::account_address::AccountAddress;
+use anyhow::{bail, Error, Result};
 use libra_crypto::{ed25519::Ed25519PublicKey, x25519};
 use libra_network_address::RawNetworkAddress;
-use move_core_types::move_resource::MoveResource;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, convert::TryFrom};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ValidatorConfigResource {
@@ -15,12 +16,13 @@ pub struct ValidatorConfigResource {
     pub delegated_account: Option<AccountAddress>,
 }
 
-impl MoveResource for ValidatorConfigResource {
-    const MODULE_NAME: &'static str = "ValidatorConfig";
-    const STRUCT_NAME: &'static str = "ValidatorConfig";
-}
-
+/// A validator's on-chain configuration.
+///
+/// During a network key rotation each network may announce the identity key and address it
+/// switches to next, alongside its current ones. Peers accept Noise handshakes authenticated by
+/// either identity key and dial either address, so the node stays reachable while it moves over.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "RawValidatorConfig", into = "RawValidatorConfig")]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct ValidatorConfig {
     pub consensus_public_key: Ed25519PublicKey,
@@ -28,6 +30,16 @@ pub struct ValidatorConfig {
     pub validator_network_address: RawNetworkAddress,
     pub full_node_network_identity_public_key: x25519::PublicKey,
     pub full_node_network_address: RawNetworkAddress,
+    pub validator_next_network_config: Option<NextNetworkConfig>,
+    pub full_node_next_network_config: Option<NextNetworkConfig>,
+}
+
+/// The identity key and address a network switches to once its network key rotation completes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct NextNetworkConfig {
+    pub identity_public_key: x25519::PublicKey,
+    pub address: RawNetworkAddress,
 }
 
 impl ValidatorConfig {
@@ -44,6 +56,132 @@ impl ValidatorConfig {
             validator_network_address,
             full_node_network_identity_public_key,
             full_node_network_address,
+            validator_next_network_config: None,
+            full_node_next_network_config: None,
+        }
+    }
+
+    /// All validator network identity keys peers should currently accept.
+    pub fn validator_network_identity_public_keys(&self) -> HashSet<x25519::PublicKey> {
+        std::iter::once(self.validator_network_identity_public_key)
+            .chain(
+                self.validator_next_network_config
+                    .iter()
+                    .map(|next| next.identity_public_key),
+            )
+            .collect()
+    }
+
+    /// All full node network identity keys peers should currently accept.
+    pub fn full_node_network_identity_public_keys(&self) -> HashSet<x25519::PublicKey> {
+        std::iter::once(self.full_node_network_identity_public_key)
+            .chain(
+                self.full_node_next_network_config
+                    .iter()
+                    .map(|next| next.identity_public_key),
+            )
+            .collect()
+    }
+
+    /// All validator network addresses peers may currently dial, the current one first.
+    pub fn validator_network_addresses(&self) -> Vec<RawNetworkAddress> {
+        std::iter::once(self.validator_network_address.clone())
+            .chain(
+                self.validator_next_network_config
+                    .iter()
+                    .map(|next| next.address.clone()),
+            )
+            .collect()
+    }
+
+    /// All full node network addresses peers may currently dial, the current one first.
+    pub fn full_node_network_addresses(&self) -> Vec<RawNetworkAddress> {
+        std::iter::once(self.full_node_network_address.clone())
+            .chain(
+                self.full_node_next_network_config
+                    .iter()
+                    .map(|next| next.address.clone()),
+            )
+            .collect()
+    }
+}
+
+impl NextNetworkConfig {
+    /// Decodes the on-chain next identity key and address, where both empty means none.
+    fn from_raw(identity_public_key: &[u8], address: RawNetworkAddress) -> Result<Option<Self>> {
+        match (identity_public_key.is_empty(), address.as_ref().is_empty()) {
+            (true, true) => Ok(None),
+            (false, false) => Ok(Some(NextNetworkConfig {
+                identity_public_key: x25519::PublicKey::try_from(identity_public_key)?,
+                address,
+            })),
+            _ => bail!("Next network identity key and address must be set together"),
+        }
+    }
+
+    fn into_raw(next: Option<Self>) -> (Vec<u8>, RawNetworkAddress) {
+        match next {
+            Some(next) => (next.identity_public_key.as_slice().to_vec(), next.address),
+            None => (Vec::new(), RawNetworkAddress::new(Vec::new())),
+        }
+    }
+}
+
+/// The layout of `ValidatorConfig` as stored on-chain, where empty next fields mean no network
+/// key rotation is in progress.
+#[derive(Clone, Deserialize, Serialize)]
+struct RawValidatorConfig {
+    consensus_public_key: Ed25519PublicKey,
+    validator_network_identity_public_key: x25519::PublicKey,
+    validator_network_address: RawNetworkAddress,
+    full_node_network_identity_public_key: x25519::PublicKey,
+    full_node_network_address: RawNetworkAddress,
+    #[serde(with = "serde_bytes")]
+    validator_network_next_identity_public_key: Vec<u8>,
+    validator_network_next_address: RawNetworkAddress,
+    #[serde(with = "serde_bytes")]
+    full_node_network_next_identity_public_key: Vec<u8>,
+    full_node_network_next_address: RawNetworkAddress,
+}
+
+impl TryFrom<RawValidatorConfig> for ValidatorConfig {
+    type Error = Error;
+
+    fn try_from(raw: RawValidatorConfig) -> Result<Self> {
+        Ok(ValidatorConfig {
+            consensus_public_key: raw.consensus_public_key,
+            validator_network_identity_public_key: raw.validator_network_identity_public_key,
+            validator_network_address: raw.validator_network_address,
+            full_node_network_identity_public_key: raw.full_node_network_identity_public_key,
+            full_node_network_address: raw.full_node_network_address,
+            validator_next_network_config: NextNetworkConfig::from_raw(
+                &raw.validator_network_next_identity_public_key,
+                raw.validator_network_next_address,
+            )?,
+            full_node_next_network_config: NextNetworkConfig::from_raw(
+                &raw.full_node_network_next_identity_public_key,
+                raw.full_node_network_next_address,
+            )?,
+        })
+    }
+}
+
+impl From<ValidatorConfig> for RawValidatorConfig {
+    fn from(config: ValidatorConfig) -> Self {
+        let (validator_network_next_identity_public_key, validator_network_next_address) =
+            NextNetworkConfig::into_raw(config.validator_next_network_config);
+        let (full_node_network_next_identity_public_key, full_node_network_next_address) =
+            NextNetworkConfig::into_raw(config.full_node_next_network_config);
+        RawValidatorConfig {
+            consensus_public_key: config.consensus_public_key,
+            validator_network_identity_public_key: config.validator_network_identity_public_key,
+            validator_network_address: config.validator_network_address,
+            full_node_network_identity_public_key: config.full_node_network_identity_public_key,
+            full_node_network_address: config.full_node_network_address,
+            validator_network_next_identity_public_key,
+            validator_network_next_address,
+            full_node_network_next_identity_public_key,
+            full_node_network_next_address,
         }
     }
 }
@@ -12,7 +12,7 @@ use libra_network_address::RawNetworkAddress;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{collections::HashSet, fmt};
 #[cfg(any(test, feature = "fuzzing"))]
 use std::{convert::TryFrom, str::FromStr};
 
@@ -103,6 +103,12 @@ impl ValidatorInfo {
         self.config.validator_network_identity_public_key
     }
 
+    /// Returns all keys, current and next, that establish a validator's identity in the p2p
+    /// network
+    pub fn network_identity_public_keys(&self) -> HashSet<x25519::PublicKey> {
+        self.config.validator_network_identity_public_keys()
+    }
+
     /// Returns the validator's config
     pub fn config(&self) -> &ValidatorConfig {
         &self.config
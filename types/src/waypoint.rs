@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    epoch_change::Verifier,
+    epoch_change::{EpochChangeProof, Verifier},
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::Version,
@@ -120,6 +120,74 @@ impl FromStr for Waypoint {
     }
 }
 
+/// A waypoint emitted by storage at an epoch boundary together with the epoch-ending ledger
+/// info it was generated from. The ledger info carries the signatures of the validators of its
+/// epoch, so a node can check a recent waypoint from an untrusted source against a waypoint it
+/// already trusts, and bootstrap from it instead of genesis.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignedWaypoint {
+    waypoint: Waypoint,
+    ledger_info_with_sigs: LedgerInfoWithSignatures,
+}
+
+impl SignedWaypoint {
+    /// Generates a new signed waypoint given the epoch change LedgerInfo.
+    pub fn new(ledger_info_with_sigs: LedgerInfoWithSignatures) -> Result<Self> {
+        Ok(Self {
+            waypoint: Waypoint::new_epoch_boundary(ledger_info_with_sigs.ledger_info())?,
+            ledger_info_with_sigs,
+        })
+    }
+
+    pub fn waypoint(&self) -> Waypoint {
+        self.waypoint
+    }
+
+    pub fn ledger_info_with_sigs(&self) -> &LedgerInfoWithSignatures {
+        &self.ledger_info_with_sigs
+    }
+
+    /// The epoch ended by the ledger info of the waypoint.
+    pub fn epoch(&self) -> u64 {
+        self.ledger_info_with_sigs.ledger_info().epoch()
+    }
+
+    /// Errors in case the waypoint can't be proven from the `trusted` waypoint: `proof` must
+    /// chain the epoch changes from the ledger info of `trusted` up to the ledger info of this
+    /// waypoint, any later epoch change being ignored.
+    pub fn verify(&self, trusted: &Waypoint, proof: &EpochChangeProof) -> Result<()> {
+        self.waypoint
+            .verify(self.ledger_info_with_sigs.ledger_info())?;
+        if &self.waypoint == trusted {
+            return Ok(());
+        }
+        let epoch = self.epoch();
+        let prefix = EpochChangeProof::new(
+            proof
+                .ledger_info_with_sigs
+                .iter()
+                .take_while(|li| li.ledger_info().epoch() <= epoch)
+                .cloned()
+                .collect(),
+            false, /* more */
+        );
+        let proven = prefix.verify(trusted)?;
+        ensure!(
+            proven == &self.ledger_info_with_sigs,
+            "The EpochChangeProof ends at epoch {}, not at the epoch {} of the waypoint",
+            proven.ledger_info().epoch(),
+            epoch,
+        );
+        Ok(())
+    }
+}
+
+impl Display for SignedWaypoint {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} (epoch {})", self.waypoint, self.epoch())
+    }
+}
+
 /// Keeps the fields of LedgerInfo that are hashed for generating a waypoint.
 /// Note that not all the fields of LedgerInfo are included: some consensus-related fields
 /// might not be the same for all the participants.
@@ -186,7 +254,7 @@ impl Serialize for Waypoint {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::block_info::BlockInfo;
+    use crate::{block_info::BlockInfo, validator_verifier::random_validator_verifier};
     use std::str::FromStr;
 
     #[test]
@@ -219,4 +287,71 @@ mod test {
         let waypoint = Waypoint::new_epoch_boundary(&li).unwrap();
         assert!(waypoint.verify(&li).is_ok());
     }
+
+    #[test]
+    fn test_signed_waypoint_verification() {
+        // Epoch-ending ledger infos for epochs 1 to 4, each signed by the validators of its epoch.
+        let (mut signers, _) = random_validator_verifier(1, None, true);
+        let mut ledger_infos = vec![];
+        for epoch in 1..=4 {
+            let (next_signers, next_verifier) = random_validator_verifier(2, None, true);
+            let li = LedgerInfo::new(
+                BlockInfo::new(
+                    epoch,
+                    0,
+                    HashValue::zero(),
+                    HashValue::random(),
+                    epoch * 10,
+                    0,
+                    Some(EpochState {
+                        epoch: epoch + 1,
+                        verifier: next_verifier,
+                    }),
+                ),
+                HashValue::zero(),
+            );
+            let signatures = signers
+                .iter()
+                .map(|s| (s.author(), s.sign_message(li.hash())))
+                .collect();
+            ledger_infos.push(LedgerInfoWithSignatures::new(li, signatures));
+            signers = next_signers;
+        }
+        let trusted = Waypoint::new_epoch_boundary(ledger_infos[0].ledger_info()).unwrap();
+        let proof = EpochChangeProof::new(ledger_infos.clone(), false /* more */);
+
+        let signed = SignedWaypoint::new(ledger_infos[2].clone()).unwrap();
+        assert_eq!(signed.epoch(), 3);
+        assert!(signed.verify(&trusted, &proof).is_ok());
+        // The trusted waypoint needs no proof.
+        let first = SignedWaypoint::new(ledger_infos[0].clone()).unwrap();
+        assert!(first
+            .verify(&trusted, &EpochChangeProof::new(vec![], false))
+            .is_ok());
+        // The proof must reach the epoch of the waypoint.
+        let short_proof = EpochChangeProof::new(ledger_infos[..2].to_vec(), false);
+        assert!(signed.verify(&trusted, &short_proof).is_err());
+        // The proof must start from the trusted waypoint.
+        let gapped_proof = EpochChangeProof::new(ledger_infos[1..].to_vec(), false);
+        assert!(signed.verify(&trusted, &gapped_proof).is_err());
+        // A signed waypoint whose ledger info isn't the proven one is rejected.
+        let original = &ledger_infos[2];
+        let forged = LedgerInfoWithSignatures::new(
+            LedgerInfo::new(
+                BlockInfo::new(
+                    3,
+                    0,
+                    HashValue::zero(),
+                    HashValue::random(),
+                    30,
+                    0,
+                    original.ledger_info().next_epoch_state().cloned(),
+                ),
+                HashValue::zero(),
+            ),
+            original.signatures().clone(),
+        );
+        let forged = SignedWaypoint::new(forged).unwrap();
+        assert!(forged.verify(&trusted, &proof).is_err());
+    }
 }
@@ -0,0 +1,111 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Quotas on the transactions a node admits, on top of the checks of the VM. They are local
+//! policy rather than consensus rules: a transaction refused by one node may still be admitted by
+//! another, and is executed normally once it is in a block.
+
+use libra_config::config::AdmissionQuotaConfig;
+use libra_types::{
+    transaction::{SignedTransaction, TransactionPayload},
+    vm_error::{StatusCode, VMStatus},
+};
+use std::{collections::VecDeque, time::Duration};
+
+#[cfg(test)]
+#[path = "unit_tests/admission_quotas_test.rs"]
+mod admission_quotas_test;
+
+pub struct AdmissionQuotas {
+    config: AdmissionQuotaConfig,
+    // The block timestamps at which the module publishing transactions of the window were
+    // admitted, oldest first
+    module_publishes: VecDeque<u64>,
+    // The timestamp of the latest committed block, in microseconds
+    block_timestamp_usecs: u64,
+}
+
+impl AdmissionQuotas {
+    pub fn new(config: &AdmissionQuotaConfig) -> Self {
+        Self {
+            config: config.clone(),
+            module_publishes: VecDeque::new(),
+            block_timestamp_usecs: 0,
+        }
+    }
+
+    /// Applies the quotas in `config` to subsequently admitted transactions
+    pub fn update_config(&mut self, config: &AdmissionQuotaConfig) {
+        self.config = config.clone();
+        self.expire_module_publishes();
+    }
+
+    /// Checks `txn` against the quotas, given the number of transactions of its sender already
+    /// in mempool
+    pub fn check(&self, txn: &SignedTransaction, in_flight: usize) -> Result<(), VMStatus> {
+        if let Some(max_in_flight) = self.config.max_in_flight_per_sender {
+            if in_flight >= max_in_flight {
+                return Err(
+                    VMStatus::new(StatusCode::SENDER_QUOTA_EXCEEDED).with_message(format!(
+                        "sender {} has {} transactions in mempool, the quota is {}",
+                        txn.sender(),
+                        in_flight,
+                        max_in_flight
+                    )),
+                );
+            }
+        }
+        match txn.payload() {
+            TransactionPayload::Module(_) => {
+                if let Some(max_publishes) = self.config.max_module_publishes_per_window {
+                    if self.module_publishes.len() >= max_publishes {
+                        return Err(VMStatus::new(StatusCode::MODULE_PUBLISH_QUOTA_EXCEEDED)
+                            .with_message(format!(
+                                "{} module publishing transactions admitted in the last {}s",
+                                self.module_publishes.len(),
+                                self.config.module_publish_window_secs
+                            )));
+                    }
+                }
+            }
+            TransactionPayload::WriteSet(_) => {
+                if let Some(senders) = &self.config.writeset_senders {
+                    if !senders.contains(&txn.sender()) {
+                        return Err(VMStatus::new(StatusCode::WRITESET_SENDER_NOT_ALLOWED)
+                            .with_message(format!(
+                                "writeset transactions are not admitted from {}",
+                                txn.sender()
+                            )));
+                    }
+                }
+            }
+            TransactionPayload::Script(_) | TransactionPayload::Batch(_) => (),
+        }
+        Ok(())
+    }
+
+    /// Records that a module publishing transaction was admitted into mempool, to count it
+    /// against the quota of the current window
+    pub fn record_module_publish(&mut self) {
+        self.module_publishes.push_back(self.block_timestamp_usecs);
+    }
+
+    /// Moves the window of the module publishing quota to the timestamp of a committed block
+    pub fn update_block_timestamp(&mut self, block_timestamp_usecs: u64) {
+        if block_timestamp_usecs > self.block_timestamp_usecs {
+            self.block_timestamp_usecs = block_timestamp_usecs;
+            self.expire_module_publishes();
+        }
+    }
+
+    fn expire_module_publishes(&mut self) {
+        let window_usecs =
+            Duration::from_secs(self.config.module_publish_window_secs).as_micros() as u64;
+        while let Some(&admitted_at) = self.module_publishes.front() {
+            if admitted_at.saturating_add(window_usecs) > self.block_timestamp_usecs {
+                break;
+            }
+            self.module_publishes.pop_front();
+        }
+    }
+}
@@ -3,5 +3,6 @@
 
 #![forbid(unsafe_code)]
 
+pub mod admission_quotas;
 pub mod mocks;
 pub mod vm_validator;
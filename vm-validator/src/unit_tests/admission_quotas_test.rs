@@ -0,0 +1,109 @@
+// Copyright (c) The Libra Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::admission_quotas::AdmissionQuotas;
+use libra_config::config::AdmissionQuotaConfig;
+use libra_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use libra_types::{
+    account_address::AccountAddress,
+    test_helpers::transaction_test_helpers,
+    transaction::{Module, SignedTransaction},
+    vm_error::StatusCode,
+};
+use rand::SeedableRng;
+
+fn private_key() -> Ed25519PrivateKey {
+    let mut rng = ::rand::rngs::StdRng::from_seed([1u8; 32]);
+    Ed25519PrivateKey::generate(&mut rng)
+}
+
+fn script_txn(sender: AccountAddress) -> SignedTransaction {
+    let key = private_key();
+    transaction_test_helpers::get_test_signed_txn(sender, 0, &key, key.public_key(), None)
+}
+
+fn module_txn(sender: AccountAddress) -> SignedTransaction {
+    let key = private_key();
+    transaction_test_helpers::get_test_signed_module_publishing_transaction(
+        sender,
+        0,
+        &key,
+        key.public_key(),
+        Module::new(vec![]),
+    )
+}
+
+fn write_set_txn(sender: AccountAddress) -> SignedTransaction {
+    let key = private_key();
+    transaction_test_helpers::get_write_set_txn(sender, 0, &key, key.public_key(), None)
+        .into_inner()
+}
+
+fn status_of(result: Result<(), libra_types::vm_error::VMStatus>) -> Option<StatusCode> {
+    result.err().map(|status| status.major_status)
+}
+
+#[test]
+fn test_no_quotas() {
+    let quotas = AdmissionQuotas::new(&AdmissionQuotaConfig::default());
+    let sender = AccountAddress::random();
+    assert!(quotas.check(&script_txn(sender), 1000).is_ok());
+    assert!(quotas.check(&module_txn(sender), 1000).is_ok());
+    assert!(quotas.check(&write_set_txn(sender), 1000).is_ok());
+}
+
+#[test]
+fn test_in_flight_per_sender() {
+    let quotas = AdmissionQuotas::new(&AdmissionQuotaConfig {
+        max_in_flight_per_sender: Some(2),
+        ..AdmissionQuotaConfig::default()
+    });
+    let txn = script_txn(AccountAddress::random());
+    assert!(quotas.check(&txn, 1).is_ok());
+    assert_eq!(
+        status_of(quotas.check(&txn, 2)),
+        Some(StatusCode::SENDER_QUOTA_EXCEEDED)
+    );
+}
+
+#[test]
+fn test_module_publishes_per_window() {
+    let mut quotas = AdmissionQuotas::new(&AdmissionQuotaConfig {
+        max_module_publishes_per_window: Some(2),
+        module_publish_window_secs: 10,
+        ..AdmissionQuotaConfig::default()
+    });
+    let txn = module_txn(AccountAddress::random());
+    quotas.update_block_timestamp(1_000_000);
+    quotas.record_module_publish();
+    quotas.update_block_timestamp(5_000_000);
+    quotas.record_module_publish();
+    assert_eq!(
+        status_of(quotas.check(&txn, 0)),
+        Some(StatusCode::MODULE_PUBLISH_QUOTA_EXCEEDED)
+    );
+    // Other transactions are not affected
+    assert!(quotas.check(&script_txn(txn.sender()), 0).is_ok());
+
+    // The first publish leaves the window 10s after it was admitted
+    quotas.update_block_timestamp(10_999_999);
+    assert!(quotas.check(&txn, 0).is_err());
+    quotas.update_block_timestamp(11_000_000);
+    assert!(quotas.check(&txn, 0).is_ok());
+}
+
+#[test]
+fn test_writeset_senders() {
+    let allowed = AccountAddress::random();
+    let quotas = AdmissionQuotas::new(&AdmissionQuotaConfig {
+        writeset_senders: Some(vec![allowed]),
+        ..AdmissionQuotaConfig::default()
+    });
+    assert!(quotas.check(&write_set_txn(allowed), 0).is_ok());
+    let other = AccountAddress::random();
+    assert_eq!(
+        status_of(quotas.check(&write_set_txn(other), 0)),
+        Some(StatusCode::WRITESET_SENDER_NOT_ALLOWED)
+    );
+    assert!(quotas.check(&script_txn(other), 0).is_ok());
+}
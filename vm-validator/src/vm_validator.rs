@@ -6,7 +6,7 @@ use libra_state_view::StateViewId;
 use libra_types::{
     account_address::AccountAddress,
     account_config::AccountResource,
-    on_chain_config::{LibraVersion, OnChainConfigPayload, VMConfig},
+    on_chain_config::{LibraVersion, ModulePublishingPolicy, OnChainConfigPayload, VMConfig},
     transaction::{SignedTransaction, VMValidatorResult},
 };
 use libra_vm::LibraVM;
@@ -79,8 +79,9 @@ impl TransactionValidation for VMValidator {
     fn restart(&mut self, config: OnChainConfigPayload) -> Result<()> {
         let vm_config = config.get::<VMConfig>()?;
         let version = config.get::<LibraVersion>()?;
+        let module_publishing_policy = config.get::<ModulePublishingPolicy>().ok();
 
-        self.vm = LibraVM::init_with_config(version, vm_config);
+        self.vm = LibraVM::init_with_config(version, vm_config, module_publishing_policy);
         Ok(())
     }
 }